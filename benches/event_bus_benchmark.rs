@@ -0,0 +1,66 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use crypto_hft::core::{EventBus, MarketEvent, OrderSide, Trade};
+use crypto_hft::types::{Price, Size};
+
+fn create_trade(symbol: &str) -> MarketEvent {
+    MarketEvent::Trade(Trade {
+        symbol: symbol.into(),
+        exchange_id: "binance".to_string(),
+        price: Price::from_str("50000.00").unwrap(),
+        size: Size::from_str("0.01").unwrap(),
+        side: OrderSide::Buy,
+        timestamp: 1672515782136,
+        trade_id: None,
+    })
+}
+
+fn bench_event_bus_fan_out(c: &mut Criterion) {
+    let mut group = c.benchmark_group("event_bus_fan_out");
+    let trade = create_trade("BTCUSDT");
+
+    for subscribers in [1, 4, 16] {
+        let bus = EventBus::default();
+        let mut receivers: Vec<_> = (0..subscribers)
+            .map(|_| bus.subscribe_market(None))
+            .collect();
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(subscribers),
+            &subscribers,
+            |b, _| {
+                b.iter(|| {
+                    bus.publish_market(black_box(trade.clone()));
+                    for receiver in receivers.iter_mut() {
+                        black_box(receiver.try_recv());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_event_bus_symbol_routing(c: &mut Criterion) {
+    // One subscriber per symbol, so each event reaches only its own symbol's subscriber
+    let symbols: Vec<String> = (0..16).map(|i| format!("SYM{}USDT", i)).collect();
+    let bus = EventBus::default();
+    let mut receivers: Vec<_> = symbols
+        .iter()
+        .map(|symbol| bus.subscribe_market(Some(symbol)))
+        .collect();
+    let trade = create_trade(&symbols[0]);
+
+    c.bench_function("event_bus_symbol_routing", |b| {
+        b.iter(|| {
+            bus.publish_market(black_box(trade.clone()));
+            black_box(receivers[0].try_recv())
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_event_bus_fan_out,
+    bench_event_bus_symbol_routing
+);
+criterion_main!(benches);
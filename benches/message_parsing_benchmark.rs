@@ -40,6 +40,32 @@ fn bench_serde_json_parsing(c: &mut Criterion) {
             black_box(message)
         })
     });
+
+    c.bench_function("simd_json_parsing", |b| {
+        b.iter(|| {
+            let message = BinanceMessage::from_json_simd(black_box(json)).unwrap();
+            black_box(message)
+        })
+    });
+}
+
+fn bench_small_message_parsing(c: &mut Criterion) {
+    let trade = r#"{"e":"trade","E":1672515782136,"s":"BNBBTC","t":12345,"p":"0.001","q":"100","b":88,"a":50,"T":1672515782136,"m":true,"M":true}"#;
+    let book_ticker = r#"{"stream":"bnbusdt@bookTicker","data":{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}}"#;
+
+    c.bench_function("trade_parsing", |b| {
+        b.iter(|| {
+            let message = BinanceMessage::from_json(black_box(trade)).unwrap();
+            black_box(message)
+        })
+    });
+
+    c.bench_function("book_ticker_parsing", |b| {
+        b.iter(|| {
+            let message = BinanceMessage::from_json(black_box(book_ticker)).unwrap();
+            black_box(message)
+        })
+    });
 }
 
 fn bench_message_to_market_event(c: &mut Criterion) {
@@ -88,6 +114,7 @@ fn bench_message_to_market_event(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_serde_json_parsing,
+    bench_small_message_parsing,
     bench_message_to_market_event
 );
 criterion_main!(benches);
@@ -1,4 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use crypto_hft::indicators::{ExactBookStats, FastBookStats};
 use crypto_hft::orderbook::{OrderBook, OrderBookDelta, OrderBookSnapshot};
 use crypto_hft::types::{Price, Size};
 
@@ -114,6 +115,20 @@ fn bench_orderbook_apply_delta(c: &mut Criterion) {
                 },
             )
         });
+
+        group.bench_with_input(format!("batch_{}", updates), updates, |b, _| {
+            b.iter_with_setup(
+                || {
+                    let mut book = OrderBook::new("BTCUSDT".to_string());
+                    book.apply_snapshot(create_large_snapshot(1000));
+                    book
+                },
+                |mut book| {
+                    book.apply_batch(black_box(&deltas));
+                    black_box(book)
+                },
+            )
+        });
     }
 
     group.finish();
@@ -156,12 +171,41 @@ fn bench_orderbook_best_prices(c: &mut Criterion) {
     });
 }
 
+fn bench_book_stats_replay(c: &mut Criterion) {
+    let mut book = OrderBook::new("BTCUSDT".to_string());
+    book.apply_snapshot(create_large_snapshot(20));
+    let deltas = create_delta_updates(1000);
+
+    c.bench_function("book_stats_replay_1000_decimal", |b| {
+        b.iter(|| {
+            let mut book = book.clone();
+            let mut stats = ExactBookStats::new(10, 200);
+            for delta in &deltas {
+                book.apply_delta(delta.clone());
+                black_box(stats.update(&book));
+            }
+        })
+    });
+
+    c.bench_function("book_stats_replay_1000_f64", |b| {
+        b.iter(|| {
+            let mut book = book.clone();
+            let mut stats = FastBookStats::new(10, 200);
+            for delta in &deltas {
+                book.apply_delta(delta.clone());
+                black_box(stats.update(&book));
+            }
+        })
+    });
+}
+
 criterion_group!(
     benches,
     bench_orderbook_creation,
     bench_orderbook_apply_snapshot,
     bench_orderbook_apply_delta,
     bench_orderbook_top_levels,
-    bench_orderbook_best_prices
+    bench_orderbook_best_prices,
+    bench_book_stats_replay
 );
 criterion_main!(benches);
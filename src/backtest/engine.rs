@@ -0,0 +1,735 @@
+use crate::backtest::fill_model::{FillModel, SimulatedFill};
+use crate::core::clock::SimulatedClock;
+use crate::core::events::{
+    ExchangeId, ExecutionReport, MarketEvent, NewOrder, OrderId, OrderSide, OrderStatus, OrderType,
+    Signal, TimeInForce, Timestamp, TradingEvent,
+};
+use crate::core::reject::RejectReason;
+use crate::orderbook::OrderBook;
+use crate::risk::shadow_ledger::{RiskMetrics, TradeStats};
+use crate::risk::ShadowLedger;
+use crate::traits::strategy::{Strategy, StrategyMetrics};
+use crate::types::{Price, Size, Symbol};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::BufRead;
+use std::path::Path;
+
+/// Exchange ID used by orders that do not name a venue
+const DEFAULT_EXCHANGE: &str = "default";
+
+/// Outcome of a backtest, in the same terms as live trading
+#[derive(Debug, Clone)]
+pub struct BacktestReport {
+    /// Metrics as reported by the strategy itself
+    pub strategy_metrics: StrategyMetrics,
+    /// Metrics from the shadow ledger the fills were booked into
+    pub risk_metrics: RiskMetrics,
+    pub trade_stats: TradeStats,
+    pub fills: Vec<SimulatedFill>,
+    pub events_processed: u64,
+    pub orders_submitted: u64,
+    pub orders_rejected: u64,
+    pub realized_pnl: Decimal,
+    /// Open positions marked at the last traded or mid price
+    pub unrealized_pnl: Decimal,
+}
+
+/// An order request on its way to the simulated venue
+#[derive(Debug, Clone)]
+enum Action {
+    Place(OrderId, NewOrder),
+    Cancel(OrderId),
+    CancelAll(Symbol, ExchangeId),
+    Amend(OrderId, Option<Price>, Option<Size>),
+}
+
+#[derive(Debug, Clone)]
+struct RestingOrder {
+    order_id: OrderId,
+    order: NewOrder,
+    /// Venue of the book the order rests on
+    exchange_id: ExchangeId,
+    filled: Size,
+    notional: Decimal,
+}
+
+impl RestingOrder {
+    fn remaining(&self) -> Size {
+        self.order.size - self.filled
+    }
+
+    fn report(&self, status: OrderStatus, timestamp: Timestamp) -> ExecutionReport {
+        ExecutionReport {
+            order_id: self.order_id.clone(),
+            client_order_id: self.order.client_order_id.clone(),
+            symbol: self.order.symbol.clone(),
+            exchange_id: self.exchange_id.clone(),
+            status,
+            filled_size: self.filled,
+            remaining_size: self.remaining(),
+            average_price: (!self.filled.is_zero())
+                .then(|| Price::new(self.notional / self.filled.value())),
+            timestamp,
+            reject_reason: None,
+        }
+    }
+}
+
+/// Replays recorded market events through a strategy against simulated venues
+///
+/// Each venue's book is rebuilt from the events. Signals become orders that reach the
+/// book after the fill model's latency; fills are reported back to the strategy as
+/// execution reports and booked into a `ShadowLedger`, so the report carries the same
+/// `StrategyMetrics` and `RiskMetrics` as a live run. Resting orders fill when a trade
+/// prints at or through their price or the opposite side of the book crosses them,
+/// without modelling queue position.
+pub struct BacktestEngine<S> {
+    strategy: S,
+    fill_model: FillModel,
+    ledger: ShadowLedger,
+    /// Books by exchange and symbol
+    books: BTreeMap<(String, String), OrderBook>,
+    /// Last traded or mid price by symbol, for marking positions
+    marks: HashMap<String, Price>,
+    in_flight: VecDeque<(Timestamp, Action)>,
+    /// Resting orders in arrival order
+    resting: Vec<RestingOrder>,
+    /// Reports waiting to be delivered to the strategy
+    reports: Vec<ExecutionReport>,
+    fills: Vec<SimulatedFill>,
+    /// Number of fills already booked into the ledger
+    booked: usize,
+    /// Event time of the replay
+    now: Timestamp,
+    /// The replay time, for components that read a clock
+    clock: SimulatedClock,
+    next_order_id: u64,
+    events_processed: u64,
+    orders_submitted: u64,
+    orders_rejected: u64,
+    /// Event time between P&L snapshots for the risk metrics
+    snapshot_interval_ms: Timestamp,
+    last_snapshot: Option<Timestamp>,
+}
+
+impl<S: Strategy> BacktestEngine<S> {
+    /// Create a backtest of an initialized strategy
+    pub fn new(strategy: S, fill_model: FillModel) -> Self {
+        let clock = SimulatedClock::at_millis(0);
+        Self {
+            strategy,
+            fill_model,
+            ledger: ShadowLedger::new().with_clock(clock.shared()),
+            books: BTreeMap::new(),
+            marks: HashMap::new(),
+            in_flight: VecDeque::new(),
+            resting: Vec::new(),
+            reports: Vec::new(),
+            fills: Vec::new(),
+            booked: 0,
+            now: 0,
+            clock,
+            next_order_id: 1,
+            events_processed: 0,
+            orders_submitted: 0,
+            orders_rejected: 0,
+            snapshot_interval_ms: 86_400_000,
+            last_snapshot: None,
+        }
+    }
+
+    /// Set how often P&L is sampled for the risk metrics, in event time (builder pattern)
+    pub fn with_snapshot_interval_ms(mut self, snapshot_interval_ms: Timestamp) -> Self {
+        self.snapshot_interval_ms = snapshot_interval_ms.max(1);
+        self
+    }
+
+    /// Drive a clock with the replay's event time (builder pattern)
+    /// Give the strategy the same clock so its expiries follow the replay.
+    pub fn with_clock(mut self, clock: SimulatedClock) -> Self {
+        self.ledger = ShadowLedger::new().with_clock(clock.shared());
+        self.clock = clock;
+        self
+    }
+
+    /// Get the strategy under test
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Get the ledger the simulated fills are booked into
+    pub fn ledger(&self) -> &ShadowLedger {
+        &self.ledger
+    }
+
+    /// Get the book rebuilt for an exchange and symbol
+    pub fn book(&self, exchange_id: &str, symbol: &str) -> Option<&OrderBook> {
+        self.books
+            .get(&(exchange_id.to_string(), symbol.to_string()))
+    }
+
+    /// Replay events in order and report the results
+    pub async fn run(
+        &mut self,
+        events: impl IntoIterator<Item = MarketEvent>,
+    ) -> Result<BacktestReport, S::Error> {
+        for event in events {
+            self.step(event).await?;
+        }
+        self.ledger.record_historical_pnl(&self.marks).await;
+        Ok(self.report().await)
+    }
+
+    /// Replay a single event
+    pub async fn step(&mut self, event: MarketEvent) -> Result<(), S::Error> {
+        self.events_processed += 1;
+        self.now = self.now.max(event.timestamp());
+        self.clock.advance_to_millis(self.now);
+
+        // Requests that arrived before this event see the book as it was
+        self.deliver_in_flight();
+        self.apply(&event);
+        self.match_resting(&event);
+        self.flush_reports().await?;
+
+        let signals = self.strategy.on_market_event(event).await?;
+        for signal in signals {
+            self.submit(signal);
+        }
+        // With no latency, orders act on the book the strategy just saw
+        self.deliver_in_flight();
+        self.flush_reports().await?;
+
+        if self
+            .last_snapshot
+            .is_none_or(|last| self.now >= last + self.snapshot_interval_ms)
+        {
+            self.ledger.record_historical_pnl(&self.marks).await;
+            self.last_snapshot = Some(self.now);
+        }
+        Ok(())
+    }
+
+    async fn report(&self) -> BacktestReport {
+        BacktestReport {
+            strategy_metrics: self.strategy.get_metrics(),
+            risk_metrics: self.ledger.calculate_risk_metrics().await,
+            trade_stats: self.ledger.get_trade_stats().await,
+            fills: self.fills.clone(),
+            events_processed: self.events_processed,
+            orders_submitted: self.orders_submitted,
+            orders_rejected: self.orders_rejected,
+            realized_pnl: self.ledger.get_total_realized_pnl().await,
+            unrealized_pnl: self.ledger.get_total_unrealized_pnl(&self.marks).await,
+        }
+    }
+
+    /// Book new fills and send pending execution reports to the strategy
+    async fn flush_reports(&mut self) -> Result<(), S::Error> {
+        for fill in &self.fills[self.booked..] {
+            self.ledger
+                .add_trade(fill.to_trade_record(format!("{}_{}", fill.order_id, self.booked + 1)))
+                .await;
+            self.booked += 1;
+        }
+        for report in std::mem::take(&mut self.reports) {
+            self.strategy
+                .on_trading_event(TradingEvent::ExecutionReport(report))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, event: &MarketEvent) {
+        let key = (event.exchange_id().to_string(), event.symbol().to_string());
+        match event {
+            MarketEvent::OrderBookSnapshot(snapshot) => {
+                let book = self
+                    .books
+                    .entry(key)
+                    .or_insert_with(|| OrderBook::new(snapshot.symbol.value().to_string()));
+                book.apply_snapshot(snapshot.clone());
+                if let Some(mid) = book.mid_price() {
+                    self.marks.insert(snapshot.symbol.value().to_string(), mid);
+                }
+            }
+            MarketEvent::OrderBookDelta(delta) => {
+                if let Some(book) = self.books.get_mut(&key) {
+                    book.apply_delta(delta.clone());
+                    if let Some(mid) = book.mid_price() {
+                        self.marks.insert(delta.symbol.value().to_string(), mid);
+                    }
+                }
+            }
+            MarketEvent::Trade(trade) => {
+                self.marks
+                    .insert(trade.symbol.value().to_string(), trade.price);
+            }
+            MarketEvent::Desync(desync) => {
+                if let Some(book) = self.books.get_mut(&key) {
+                    book.mark_desynced(desync.error.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Queue a signal's request to reach the venue after the latency
+    fn submit(&mut self, signal: Signal) {
+        let action = match signal {
+            Signal::PlaceOrder { order } => {
+                let order_id = format!("backtest_{}", self.next_order_id);
+                self.next_order_id += 1;
+                self.orders_submitted += 1;
+                Action::Place(order_id, order)
+            }
+            Signal::CancelOrder { order_id, .. } => Action::Cancel(order_id),
+            Signal::CancelAllOrders {
+                symbol,
+                exchange_id,
+            } => Action::CancelAll(symbol, exchange_id),
+            Signal::UpdateOrder {
+                order_id,
+                price,
+                size,
+            } => Action::Amend(order_id, price, size),
+        };
+        self.in_flight
+            .push_back((self.now + self.fill_model.latency_ms, action));
+    }
+
+    fn deliver_in_flight(&mut self) {
+        while self
+            .in_flight
+            .front()
+            .is_some_and(|(arrives_at, _)| *arrives_at <= self.now)
+        {
+            let (arrives_at, action) = self.in_flight.pop_front().unwrap();
+            match action {
+                Action::Place(order_id, order) => self.place(order_id, order, arrives_at),
+                Action::Cancel(order_id) => self.cancel(|o| o.order_id == order_id, arrives_at),
+                Action::CancelAll(symbol, exchange_id) => self.cancel(
+                    |o| {
+                        o.order.symbol == symbol
+                            && (exchange_id == DEFAULT_EXCHANGE || o.exchange_id == exchange_id)
+                    },
+                    arrives_at,
+                ),
+                Action::Amend(order_id, price, size) => {
+                    self.amend(&order_id, price, size, arrives_at)
+                }
+            }
+        }
+    }
+
+    /// Find the book an order trades on; orders without a venue use the first
+    /// venue with a book for the symbol
+    fn venue_for(&self, order: &NewOrder) -> Option<ExchangeId> {
+        let symbol = order.symbol.value();
+        if order.exchange_id != DEFAULT_EXCHANGE {
+            return self
+                .books
+                .contains_key(&(order.exchange_id.clone(), symbol.to_string()))
+                .then(|| order.exchange_id.clone());
+        }
+        self.books
+            .keys()
+            .find(|(_, book_symbol)| book_symbol == symbol)
+            .map(|(exchange_id, _)| exchange_id.clone())
+    }
+
+    fn reject(&mut self, order_id: OrderId, order: &NewOrder, reason: RejectReason, at: Timestamp) {
+        self.orders_rejected += 1;
+        self.reports.push(ExecutionReport {
+            order_id,
+            client_order_id: order.client_order_id.clone(),
+            symbol: order.symbol.clone(),
+            exchange_id: order.exchange_id.clone(),
+            status: OrderStatus::Rejected,
+            filled_size: Size::zero(),
+            remaining_size: order.size,
+            average_price: None,
+            timestamp: at,
+            reject_reason: Some(reason),
+        });
+    }
+
+    fn place(&mut self, order_id: OrderId, order: NewOrder, at: Timestamp) {
+        let Some(exchange_id) = self.venue_for(&order) else {
+            return self.reject(order_id, &order, RejectReason::UnknownSymbol, at);
+        };
+        let book = &self.books[&(exchange_id.clone(), order.symbol.value().to_string())];
+        if book.is_desynced() {
+            return self.reject(order_id, &order, RejectReason::MarketClosed, at);
+        }
+
+        // Quote-sized orders are converted at the touch they would take
+        let order = if order.quote_size.is_some() {
+            let touch = match order.side {
+                OrderSide::Buy => book.best_ask(),
+                OrderSide::Sell => book.best_bid(),
+            };
+            match touch.and_then(|(price, _)| order.with_base_size_at(price, None)) {
+                Some(converted) => converted,
+                None => return self.reject(order_id, &order, RejectReason::Other, at),
+            }
+        } else {
+            order
+        };
+        let limit = match order.order_type {
+            OrderType::Market => None,
+            _ => match order.price {
+                Some(price) => Some(price),
+                None => return self.reject(order_id, &order, RejectReason::Other, at),
+            },
+        };
+
+        let mut resting = RestingOrder {
+            order_id,
+            order,
+            exchange_id,
+            filled: Size::zero(),
+            notional: Decimal::ZERO,
+        };
+        let book = &self.books[&(
+            resting.exchange_id.clone(),
+            resting.order.symbol.value().to_string(),
+        )];
+        let taken = self
+            .fill_model
+            .take(book, resting.order.side, resting.order.size, limit);
+
+        // Fill-or-kill needs the whole size at once
+        if resting.order.time_in_force == TimeInForce::FillOrKill
+            && taken.is_none_or(|(size, _)| size < resting.order.size)
+        {
+            self.reports
+                .push(resting.report(OrderStatus::Cancelled, at));
+            return;
+        }
+        self.reports.push(resting.report(OrderStatus::New, at));
+        if let Some((size, price)) = taken {
+            self.fill(&mut resting, size, price, false, at);
+        }
+
+        if resting.remaining().is_zero() {
+            return;
+        }
+        if limit.is_none() || resting.order.time_in_force != TimeInForce::GoodTillCancelled {
+            self.reports
+                .push(resting.report(OrderStatus::Cancelled, at));
+            return;
+        }
+        self.resting.push(resting);
+    }
+
+    fn cancel(&mut self, matches: impl Fn(&RestingOrder) -> bool, at: Timestamp) {
+        let (cancelled, kept) = std::mem::take(&mut self.resting)
+            .into_iter()
+            .partition(|order| matches(order));
+        self.resting = kept;
+        for order in cancelled {
+            self.reports.push(order.report(OrderStatus::Cancelled, at));
+        }
+    }
+
+    fn amend(&mut self, order_id: &str, price: Option<Price>, size: Option<Size>, at: Timestamp) {
+        let Some(index) = self.resting.iter().position(|o| o.order_id == order_id) else {
+            return;
+        };
+        let mut order = self.resting.remove(index);
+        if let Some(size) = size {
+            order.order.size = size;
+        }
+        if order.remaining().value() <= Decimal::ZERO {
+            self.reports.push(order.report(OrderStatus::Cancelled, at));
+            return;
+        }
+        // Amended orders lose their place, as on most venues
+        match price {
+            Some(price) => {
+                order.order.price = Some(price);
+                self.reports.push(order.report(OrderStatus::New, at));
+                self.match_order(&mut order, None, at);
+                if !order.remaining().is_zero() {
+                    self.resting.push(order);
+                }
+            }
+            None => {
+                self.reports.push(order.report(OrderStatus::New, at));
+                self.resting.push(order);
+            }
+        }
+    }
+
+    /// Fill resting orders the event trades through
+    fn match_resting(&mut self, event: &MarketEvent) {
+        let trade = match event {
+            MarketEvent::Trade(trade) => Some(trade),
+            MarketEvent::OrderBookSnapshot(_) | MarketEvent::OrderBookDelta(_) => None,
+            _ => return,
+        };
+        let mut remaining = Vec::with_capacity(self.resting.len());
+        for mut order in std::mem::take(&mut self.resting) {
+            if order.exchange_id == event.exchange_id()
+                && order.order.symbol.value() == event.symbol()
+            {
+                let printed = trade.map(|trade| (trade.price, trade.size));
+                self.match_order(&mut order, printed, self.now);
+            }
+            if !order.remaining().is_zero() {
+                remaining.push(order);
+            }
+        }
+        self.resting = remaining;
+    }
+
+    /// Fill a resting order against a printed trade or a book that crosses it
+    fn match_order(
+        &mut self,
+        order: &mut RestingOrder,
+        printed: Option<(Price, Size)>,
+        at: Timestamp,
+    ) {
+        let Some(limit) = order.order.price else {
+            return;
+        };
+        let side = order.order.side;
+        let through = |price: Price| match side {
+            OrderSide::Buy => price <= limit,
+            OrderSide::Sell => price >= limit,
+        };
+        let size = match printed {
+            Some((price, size)) if through(price) => size.min(order.remaining()),
+            Some(_) => return,
+            None => {
+                let book = &self.books[&(
+                    order.exchange_id.clone(),
+                    order.order.symbol.value().to_string(),
+                )];
+                let opposite = match side {
+                    OrderSide::Buy => book.best_ask(),
+                    OrderSide::Sell => book.best_bid(),
+                };
+                match opposite {
+                    Some((price, _)) if through(price) => order.remaining(),
+                    _ => return,
+                }
+            }
+        };
+        self.fill(order, size, limit, true, at);
+    }
+
+    fn fill(
+        &mut self,
+        order: &mut RestingOrder,
+        size: Size,
+        price: Price,
+        maker: bool,
+        at: Timestamp,
+    ) {
+        let notional = size * price;
+        let fee = self.fill_model.fee(notional, maker);
+        order.filled = order.filled + size;
+        order.notional += notional;
+        let status = if order.remaining().is_zero() {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::PartiallyFilled
+        };
+        self.reports.push(order.report(status, at));
+
+        let fill = SimulatedFill {
+            order_id: order.order_id.clone(),
+            symbol: order.order.symbol.clone(),
+            exchange_id: order.exchange_id.clone(),
+            side: order.order.side,
+            size,
+            price,
+            fee,
+            maker,
+            timestamp: at,
+        };
+        self.fills.push(fill);
+    }
+}
+
+/// Read market events recorded one JSON object per line
+/// Blank lines are skipped.
+pub fn read_events(path: impl AsRef<Path>) -> std::io::Result<Vec<MarketEvent>> {
+    let file = std::fs::File::open(path)?;
+    let mut events = Vec::new();
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str(&line)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{OrderBookLevel, OrderBookSnapshot, Trade};
+    use crate::traits::strategy::{StrategyConfig, StrategyState};
+    use async_trait::async_trait;
+
+    /// Buys at market and offers the position out once, on the first event
+    #[derive(Default)]
+    struct OneShot {
+        sent: bool,
+        reports: Vec<ExecutionReport>,
+    }
+
+    #[async_trait]
+    impl Strategy for OneShot {
+        type Error = std::io::Error;
+
+        async fn initialize(&mut self, _config: StrategyConfig) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn update_config(&mut self, _config: StrategyConfig) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn on_market_event(
+            &mut self,
+            _event: MarketEvent,
+        ) -> Result<Vec<Signal>, Self::Error> {
+            if std::mem::replace(&mut self.sent, true) {
+                return Ok(vec![]);
+            }
+            let one = Size::from_str("1").unwrap();
+            Ok(vec![
+                Signal::PlaceOrder {
+                    order: NewOrder::new_market_buy("BTCUSDT", one),
+                },
+                Signal::PlaceOrder {
+                    order: NewOrder::new_limit_sell(
+                        "BTCUSDT",
+                        one,
+                        Price::from_str("102").unwrap(),
+                        TimeInForce::GoodTillCancelled,
+                    ),
+                },
+                Signal::PlaceOrder {
+                    order: NewOrder::new_market_buy("ETHUSDT", one),
+                },
+            ])
+        }
+
+        async fn on_trading_event(&mut self, event: TradingEvent) -> Result<(), Self::Error> {
+            if let TradingEvent::ExecutionReport(report) = event {
+                self.reports.push(report);
+            }
+            Ok(())
+        }
+
+        fn get_state(&self) -> StrategyState {
+            StrategyState::Prediction
+        }
+
+        fn get_metrics(&self) -> StrategyMetrics {
+            StrategyMetrics {
+                total_trades: self.reports.len() as u64,
+                winning_trades: 0,
+                losing_trades: 0,
+                total_pnl: Decimal::ZERO,
+                gross_profit: Decimal::ZERO,
+                gross_loss: Decimal::ZERO,
+                profit_factor: Decimal::ZERO,
+                max_drawdown: Decimal::ZERO,
+                sharpe_ratio: Decimal::ZERO,
+                average_trade_pnl: Decimal::ZERO,
+                win_rate: Decimal::ZERO,
+                average_holding_time_ms: 0,
+                arrival_slippage_bps: Decimal::ZERO,
+            }
+        }
+
+        async fn shutdown(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    fn book(ask: &str, timestamp: Timestamp) -> MarketEvent {
+        let level = |price: &str| {
+            OrderBookLevel::new(
+                Price::from_str(price).unwrap(),
+                Size::from_str("5").unwrap(),
+            )
+        };
+        MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+            "BTCUSDT",
+            "binance",
+            vec![level("99")],
+            vec![level(ask)],
+            timestamp,
+        ))
+    }
+
+    fn trade(price: &str, size: &str, timestamp: Timestamp) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str(size).unwrap(),
+            side: OrderSide::Buy,
+            timestamp,
+            trade_id: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_backtest_latency_slippage_and_fills() {
+        let fill_model = FillModel::default()
+            .with_latency_ms(5)
+            .with_slippage_bps(Decimal::from(10))
+            .with_fees(Decimal::ZERO, Decimal::ZERO);
+        let mut engine = BacktestEngine::new(OneShot::default(), fill_model);
+        let report = engine
+            .run(vec![
+                book("100", 0),
+                // The ask moves up before the orders arrive
+                book("101", 2),
+                trade("102", "0.4", 10),
+                trade("102.5", "5", 20),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(report.events_processed, 4);
+        assert_eq!(report.orders_submitted, 3);
+        // Nothing to trade ETHUSDT against
+        assert_eq!(report.orders_rejected, 1);
+        let rejected = engine
+            .strategy()
+            .reports
+            .iter()
+            .find(|r| r.status == OrderStatus::Rejected)
+            .unwrap();
+        assert_eq!(rejected.reject_reason, Some(RejectReason::UnknownSymbol));
+
+        let fills = &report.fills;
+        assert_eq!(fills.len(), 3);
+        // Taken at the moved ask plus 10 bps, when the order arrives
+        assert_eq!(fills[0].price, Price::from_str("101.101").unwrap());
+        assert_eq!(fills[0].timestamp, 5);
+        assert!(!fills[0].maker);
+        // The resting sell fills with each print at or through it
+        assert_eq!(fills[1].size, Size::from_str("0.4").unwrap());
+        assert_eq!(fills[2].size, Size::from_str("0.6").unwrap());
+        assert!(fills[1].maker && fills[2].maker);
+        assert_eq!(fills[2].price, Price::from_str("102").unwrap());
+
+        assert_eq!(report.realized_pnl, Decimal::new(899, 3));
+        assert_eq!(report.trade_stats.total_trades, 3);
+        assert_eq!(report.strategy_metrics.total_trades, 6);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::core::events::{ExchangeId, OrderId, OrderSide, Timestamp};
+use crate::orderbook::OrderBook;
+use crate::risk::shadow_ledger::TradeRecord;
+use crate::types::{Price, Size, Symbol};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+/// A simulated fill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedFill {
+    pub order_id: OrderId,
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    pub side: OrderSide,
+    pub size: Size,
+    pub price: Price,
+    /// Fee in the quote asset
+    pub fee: Decimal,
+    /// Whether the fill was against a resting order
+    pub maker: bool,
+    pub timestamp: Timestamp,
+}
+
+impl SimulatedFill {
+    /// Get the fill as a trade for the shadow ledger
+    pub fn to_trade_record(&self, trade_id: String) -> TradeRecord {
+        TradeRecord::new(
+            trade_id,
+            self.symbol.clone(),
+            self.exchange_id.clone(),
+            self.order_id.clone(),
+            self.side,
+            self.size,
+            self.price,
+            DateTime::<Utc>::from_timestamp_millis(self.timestamp as i64).unwrap_or_default(),
+            Size::new(self.fee),
+            "USDT".to_string(),
+        )
+    }
+}
+
+/// How simulated orders reach the market and get filled
+///
+/// Orders, cancels and amendments take effect `latency_ms` of event time after the
+/// signal that produced them. Liquidity-taking fills walk the book and are moved
+/// against the order by `slippage_bps`; resting orders fill at their limit price.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FillModel {
+    /// Delay between a signal and its order reaching the book, in milliseconds
+    pub latency_ms: Timestamp,
+    /// Adverse price move applied to liquidity-taking fills, in basis points
+    pub slippage_bps: Decimal,
+    /// Fee rate on resting fills (negative for a rebate)
+    pub maker_fee_rate: Decimal,
+    /// Fee rate on liquidity-taking fills
+    pub taker_fee_rate: Decimal,
+}
+
+impl Default for FillModel {
+    fn default() -> Self {
+        Self {
+            latency_ms: 5,
+            slippage_bps: Decimal::ZERO,
+            maker_fee_rate: Decimal::new(1, 3),
+            taker_fee_rate: Decimal::new(1, 3),
+        }
+    }
+}
+
+impl FillModel {
+    /// Set the order latency (builder pattern)
+    pub fn with_latency_ms(mut self, latency_ms: Timestamp) -> Self {
+        self.latency_ms = latency_ms;
+        self
+    }
+
+    /// Set the slippage applied to taker fills (builder pattern)
+    pub fn with_slippage_bps(mut self, slippage_bps: Decimal) -> Self {
+        self.slippage_bps = slippage_bps;
+        self
+    }
+
+    /// Set the maker and taker fee rates (builder pattern)
+    pub fn with_fees(mut self, maker_fee_rate: Decimal, taker_fee_rate: Decimal) -> Self {
+        self.maker_fee_rate = maker_fee_rate;
+        self.taker_fee_rate = taker_fee_rate;
+        self
+    }
+
+    /// Move a price against the order by the slippage
+    pub fn slip(&self, side: OrderSide, price: Price) -> Price {
+        let factor = self.slippage_bps / Decimal::from(10_000);
+        match side {
+            OrderSide::Buy => Price::new(price.value() * (Decimal::ONE + factor)),
+            OrderSide::Sell => Price::new(price.value() * (Decimal::ONE - factor)),
+        }
+    }
+
+    /// Fill up to `size` against the book, no worse than `limit` before slippage
+    /// Returns the filled size and average price, or None if nothing is available.
+    pub fn take(
+        &self,
+        book: &OrderBook,
+        side: OrderSide,
+        size: Size,
+        limit: Option<Price>,
+    ) -> Option<(Size, Price)> {
+        let ladder = book.execution_ladder(side, size);
+        let available = match limit {
+            Some(limit) => ladder.size_within(limit),
+            None => ladder.filled,
+        };
+        if available.is_zero() {
+            return None;
+        }
+        let vwap = if available == ladder.filled {
+            ladder.vwap()?
+        } else {
+            book.execution_ladder(side, available).vwap()?
+        };
+        let price = self.slip(side, vwap);
+        // Slippage never fills a limit order through its limit
+        let price = match (limit, side) {
+            (Some(limit), OrderSide::Buy) => price.min(limit),
+            (Some(limit), OrderSide::Sell) => price.max(limit),
+            (None, _) => price,
+        };
+        Some((available, price))
+    }
+
+    /// Fee on a fill's quote notional, negative if the fill earns a rebate
+    pub fn fee(&self, notional: Decimal, maker: bool) -> Decimal {
+        let rate = if maker {
+            self.maker_fee_rate
+        } else {
+            self.taker_fee_rate
+        };
+        notional * rate
+    }
+}
@@ -0,0 +1,7 @@
+#[cfg(feature = "backtest")]
+pub mod engine;
+pub mod fill_model;
+
+#[cfg(feature = "backtest")]
+pub use engine::{read_events, BacktestEngine, BacktestReport};
+pub use fill_model::{FillModel, SimulatedFill};
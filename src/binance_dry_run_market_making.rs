@@ -100,6 +100,73 @@ fn print_market_event(event: &MarketEvent) {
             println!("║ Side:        {:45} ║", format!("{:?}", trade.side));
             println!("╚════════════════════════════════════════════════════════════╝\n");
         }
+        MarketEvent::Liquidation(liquidation) => {
+            println!("\n╔════════════════════════════════════════════════════════════╗");
+            println!("║ 🔥 LIQUIDATION (强平)                                       ║");
+            println!("╠════════════════════════════════════════════════════════════╣");
+            println!("║ Symbol:      {:45} ║", liquidation.symbol);
+            println!("║ Price:       {:45} ║", format!("{}", liquidation.price));
+            println!("║ Size:        {:45} ║", format!("{}", liquidation.size));
+            println!("║ Timestamp:   {:45} ║", liquidation.timestamp);
+            println!("║ Side:        {:45} ║", format!("{:?}", liquidation.side));
+            println!("╚════════════════════════════════════════════════════════════╝\n");
+        }
+        MarketEvent::BestBidAsk(quote) => {
+            println!(
+                "📌 Best bid/ask {}: {} x {} / {} x {} (ts: {})",
+                quote.symbol,
+                quote.bid_price,
+                quote.bid_size,
+                quote.ask_price,
+                quote.ask_size,
+                quote.timestamp
+            );
+        }
+        MarketEvent::Candle(candle) => {
+            println!(
+                "🕯️ Candle {} {}: O {} H {} L {} C {} V {}{} (ts: {})",
+                candle.symbol,
+                candle.interval,
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                candle.volume,
+                if candle.closed { "" } else { " (open)" },
+                candle.timestamp
+            );
+        }
+        MarketEvent::OpenInterest(open_interest) => {
+            println!(
+                "📊 Open interest {}: {} (ts: {})",
+                open_interest.symbol, open_interest.open_interest, open_interest.timestamp
+            );
+        }
+        MarketEvent::MarkPrice(mark_price) => {
+            println!(
+                "🏷️ Mark price {}: {} funding {} (ts: {})",
+                mark_price.symbol,
+                mark_price.mark_price,
+                mark_price.funding_rate,
+                mark_price.timestamp
+            );
+        }
+        MarketEvent::Desync(desync) => {
+            println!(
+                "⚠️ Book out of sync {}: {} (ts: {})",
+                desync.symbol, desync.error, desync.timestamp
+            );
+        }
+        MarketEvent::BookMetrics(metrics) => {
+            println!(
+                "📐 Book metrics {}: micro {} imbalance {} spread {}bps (ts: {})",
+                metrics.symbol,
+                metrics.microprice,
+                metrics.imbalance,
+                metrics.spread_bps,
+                metrics.timestamp
+            );
+        }
     }
 }
 
@@ -160,31 +227,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Main event loop
     loop {
-        // Get next market event
-        let event_result = {
+        // Take every event already received, waiting only for the first
+        let events = {
             let mut ws = market_stream.lock().await;
-            ws.next().await
+            ws.next_batch(64).await
         };
+        if events.is_empty() {
+            // No more events, wait a bit
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            continue;
+        }
+
+        // Update order book once for the whole batch
+        order_book
+            .write()
+            .await
+            .apply_events(events.iter().filter_map(|result| result.as_ref().ok()));
 
-        if let Some(event_result) = event_result {
+        for event_result in events {
             match event_result {
                 Ok(event) => {
                     // Print market event
                     print_market_event(&event);
 
-                    // Update order book
-                    {
-                        let mut ob = order_book.write().await;
-                        match &event {
-                            MarketEvent::OrderBookSnapshot(snapshot) => {
-                                ob.apply_snapshot(snapshot.clone());
-                            }
-                            MarketEvent::OrderBookDelta(delta) => {
-                                ob.apply_delta(delta.clone());
-                            }
-                            _ => {}
-                        }
-                    }
+                    // Fill paper orders the event reaches
+                    execution_client.on_market_event(&event).await;
 
                     // Generate signals from strategy periodically
                     let should_generate = {
@@ -253,9 +320,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     tokio::time::sleep(Duration::from_secs(5)).await;
                 }
             }
-        } else {
-            // No more events, wait a bit
-            tokio::time::sleep(Duration::from_millis(100)).await;
         }
     }
 }
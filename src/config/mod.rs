@@ -0,0 +1,406 @@
+pub mod sections;
+
+pub use sections::{
+    AlertSinkConfig, AlertSinkKind, ExchangeConfig, LoggingConfig, MonitoringConfig, RiskConfig,
+    StrategyInstanceConfig, SymbolRiskConfig,
+};
+
+use crate::security::KeyProvider;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Prefix of environment variables overriding config fields
+pub const ENV_PREFIX: &str = "HFT__";
+
+/// Prefix of values naming a secret to resolve through a `KeyProvider`
+pub const SECRET_PREFIX: &str = "secret:";
+
+/// Configuration file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Detect the format from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "toml" => Some(ConfigFormat::Toml),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Configuration errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The file could not be read
+    Io { path: PathBuf, message: String },
+    /// The file extension is not `.toml`, `.yaml` or `.yml`
+    UnknownFormat(PathBuf),
+    /// The file is not valid TOML or YAML
+    Syntax(String),
+    /// A field has the wrong type or value
+    Invalid { field: String, message: String },
+}
+
+impl ConfigError {
+    fn invalid(field: impl Into<String>, message: impl Into<String>) -> Self {
+        ConfigError::Invalid {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Get the path of the offending field, if the error is about one
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            ConfigError::Invalid { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, message } => {
+                write!(f, "Failed to read {}: {}", path.display(), message)
+            }
+            ConfigError::UnknownFormat(path) => write!(
+                f,
+                "Unknown config format of {}, expected .toml, .yaml or .yml",
+                path.display()
+            ),
+            ConfigError::Syntax(message) => write!(f, "Config syntax error: {}", message),
+            ConfigError::Invalid { field, message } => {
+                write!(f, "Invalid config field {}: {}", field, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Configuration of the whole trading system
+/// Every section is optional; a missing section takes its defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    pub exchanges: BTreeMap<String, ExchangeConfig>,
+    pub strategies: BTreeMap<String, StrategyInstanceConfig>,
+    pub risk: RiskConfig,
+    pub logging: LoggingConfig,
+    pub monitoring: MonitoringConfig,
+}
+
+impl AppConfig {
+    /// Read a config file, apply `HFT__` environment overrides and validate
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::load_with_env(path, std::env::vars())
+    }
+
+    /// Read a config file, apply overrides from the given variables and validate
+    pub fn load_with_env(
+        path: impl AsRef<Path>,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)
+            .ok_or_else(|| ConfigError::UnknownFormat(path.to_path_buf()))?;
+        let input = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        Self::parse_with_env(&input, format, vars)
+    }
+
+    /// Parse and validate a config without environment overrides
+    pub fn parse(input: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        Self::parse_with_env(input, format, std::iter::empty())
+    }
+
+    /// Parse a config, apply overrides from the given variables and validate
+    pub fn parse_with_env(
+        input: &str,
+        format: ConfigFormat,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let mut tree: Value = match format {
+            ConfigFormat::Toml => {
+                toml_edit::de::from_str(input).map_err(|e| ConfigError::Syntax(e.to_string()))?
+            }
+            ConfigFormat::Yaml => {
+                serde_yaml::from_str(input).map_err(|e| ConfigError::Syntax(e.to_string()))?
+            }
+        };
+        // An empty YAML document is null
+        if tree.is_null() {
+            tree = Value::Object(Map::new());
+        }
+        for (name, value) in vars {
+            if let Some(path) = name.strip_prefix(ENV_PREFIX) {
+                apply_override(&mut tree, path, &value);
+            }
+        }
+
+        let config: AppConfig = serde_path_to_error::deserialize(tree).map_err(|e| {
+            let field = e.path().to_string();
+            ConfigError::invalid(field, e.into_inner().to_string())
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check values serde cannot: cross-references, ranges and paired fields
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        sections::validate(
+            &self.exchanges,
+            &self.strategies,
+            &self.risk,
+            &self.logging,
+            &self.monitoring,
+        )
+    }
+
+    /// Replace `secret:NAME` credentials with the provider's secrets
+    /// Covers exchange API keys, Telegram bot tokens and Slack webhook URLs.
+    pub async fn resolve_secrets(&mut self, provider: &dyn KeyProvider) -> Result<(), ConfigError> {
+        let mut fields: Vec<(String, &mut String)> = Vec::new();
+        for (name, exchange) in &mut self.exchanges {
+            if let Some(api_key) = &mut exchange.api_key {
+                fields.push((format!("exchanges.{}.api_key", name), api_key));
+            }
+            if let Some(api_secret) = &mut exchange.api_secret {
+                fields.push((format!("exchanges.{}.api_secret", name), api_secret));
+            }
+        }
+        for (index, alert) in self.monitoring.alerts.iter_mut().enumerate() {
+            match &mut alert.kind {
+                AlertSinkKind::Telegram { bot_token, .. } => {
+                    fields.push((format!("monitoring.alerts[{}].bot_token", index), bot_token));
+                }
+                AlertSinkKind::Slack { webhook_url } => {
+                    fields.push((
+                        format!("monitoring.alerts[{}].webhook_url", index),
+                        webhook_url,
+                    ));
+                }
+                AlertSinkKind::Webhook { .. } => {}
+            }
+        }
+
+        for (field, value) in fields {
+            if let Some(name) = value.strip_prefix(SECRET_PREFIX) {
+                let secret = provider
+                    .get(name.trim())
+                    .await
+                    .map_err(|e| ConfigError::invalid(field, e.to_string()))?;
+                *value = secret.expose().to_string();
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the enabled strategies
+    pub fn enabled_strategies(&self) -> impl Iterator<Item = (&String, &StrategyInstanceConfig)> {
+        self.strategies
+            .iter()
+            .filter(|(_, strategy)| strategy.enabled)
+    }
+}
+
+/// Set the field at a `__`-separated path, creating tables along the way
+fn apply_override(tree: &mut Value, path: &str, raw: &str) {
+    let segments: Vec<&str> = path.split("__").filter(|s| !s.is_empty()).collect();
+    let Some((last, parents)) = segments.split_last() else {
+        return;
+    };
+
+    let mut node = tree;
+    for segment in parents {
+        let Value::Object(table) = node else {
+            return;
+        };
+        let key = matching_key(table, segment);
+        node = table
+            .entry(key)
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+    }
+
+    let Value::Object(table) = node else {
+        return;
+    };
+    let key = matching_key(table, last);
+    let value = match table.get(&key) {
+        Some(Value::String(_)) => Value::String(raw.to_string()),
+        _ => serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string())),
+    };
+    table.insert(key, value);
+}
+
+/// Find an existing key ignoring case, or the lower-case segment for a new one
+fn matching_key(table: &Map<String, Value>, segment: &str) -> String {
+    table
+        .keys()
+        .find(|key| key.eq_ignore_ascii_case(segment))
+        .cloned()
+        .unwrap_or_else(|| segment.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitoring::alerts::AlertLevel;
+    use rust_decimal::Decimal;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_example_config() {
+        let config = AppConfig::load_with_env(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("config/example.toml"),
+            vars(&[
+                ("HFT__EXCHANGES__BINANCE__API_SECRET", "s3cret"),
+                ("HFT__EXCHANGES__BINANCE__TESTNET", "false"),
+                ("HFT__RISK__SYMBOLS__btcusdt__MAX_ORDER_SIZE", "0.05"),
+                ("HFT__LOGGING__LEVEL", "debug"),
+                ("OTHER__LOGGING__LEVEL", "trace"),
+            ]),
+        )
+        .unwrap();
+
+        let binance = &config.exchanges["binance"];
+        assert!(!binance.testnet);
+        assert_eq!(binance.api_secret.as_deref(), Some("s3cret"));
+        assert!(!format!("{:?}", binance).contains("s3cret"));
+        assert_eq!(
+            config.risk.symbols["BTCUSDT"].max_order_size,
+            Some(Decimal::new(5, 2))
+        );
+        assert_eq!(config.logging.level, "debug");
+        assert!(config.enabled_strategies().count() >= 1);
+
+        let mm = config.strategies["market_making"].to_strategy_config();
+        assert_eq!(mm.parameters["order_size"], "0.01");
+        assert_eq!(mm.parameters["spread_bps"], "10");
+        assert_eq!(config.monitoring.alerts[0].min_level, AlertLevel::Critical);
+    }
+
+    #[test]
+    fn test_yaml_config() {
+        let yaml = r#"
+exchanges:
+  binance:
+    testnet: true
+strategies:
+  mm:
+    strategy_type: market_making
+    symbols: [BTCUSDT]
+    exchanges: [binance]
+    parameters:
+      spread_bps: 10
+risk:
+  max_open_orders: 20
+"#;
+        let config = AppConfig::parse(yaml, ConfigFormat::Yaml).unwrap();
+        assert!(config.exchanges["binance"].testnet);
+        assert!(config.exchanges["binance"].enabled);
+        assert_eq!(config.risk.limits().max_open_orders, Some(20));
+        assert_eq!(config.monitoring.lag_critical_ms, 1000);
+
+        assert_eq!(
+            AppConfig::parse("", ConfigFormat::Yaml).unwrap(),
+            AppConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_config_errors_name_the_field() {
+        let field = |input: &str| {
+            AppConfig::parse(input, ConfigFormat::Toml)
+                .unwrap_err()
+                .field()
+                .map(str::to_string)
+        };
+
+        assert_eq!(
+            field("[exchanges.binance]\ntestnet = \"yes\"").as_deref(),
+            Some("exchanges.binance.testnet")
+        );
+        assert_eq!(
+            field("[strategies.mm]\nstrategy_type = \"mm\"\nsymbols = [\"BTCUSDT\"]\nexchanges = [\"okx\"]")
+                .as_deref(),
+            Some("strategies.mm.exchanges[0]")
+        );
+        assert_eq!(
+            field("[exchanges.binance]\napi_key = \"k\"").as_deref(),
+            Some("exchanges.binance.api_secret")
+        );
+        assert_eq!(
+            field("[risk.symbols.BTCUSDT]\nmax_order_size = -1").as_deref(),
+            Some("risk.symbols.BTCUSDT.max_order_size")
+        );
+        assert_eq!(
+            field("[logging]\nlevel = \"loud\"").as_deref(),
+            Some("logging.level")
+        );
+        assert_eq!(
+            field("[monitoring]\nlag_warning_ms = 2000").as_deref(),
+            Some("monitoring.lag_warning_ms")
+        );
+
+        // Typos are errors rather than silently ignored
+        let error = AppConfig::parse("[risk]\nmax_open_order = 5", ConfigFormat::Toml).unwrap_err();
+        assert!(error.to_string().contains("max_open_order"));
+
+        assert!(matches!(
+            AppConfig::parse("[risk", ConfigFormat::Toml),
+            Err(ConfigError::Syntax(_))
+        ));
+        assert!(matches!(
+            AppConfig::load("config.ini"),
+            Err(ConfigError::UnknownFormat(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_secrets() {
+        let dir = std::env::temp_dir().join(format!("config_secrets_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("binance.api_secret"), "binance-secret").unwrap();
+        let provider = crate::security::FileKeyProvider::new(&dir);
+
+        let mut config = AppConfig::parse(
+            "[exchanges.binance]\napi_key = \"plain-key\"\napi_secret = \"secret:binance.api_secret\"",
+            ConfigFormat::Toml,
+        )
+        .unwrap();
+        config.resolve_secrets(&provider).await.unwrap();
+        let binance = &config.exchanges["binance"];
+        assert_eq!(binance.api_key.as_deref(), Some("plain-key"));
+        assert_eq!(binance.api_secret.as_deref(), Some("binance-secret"));
+
+        let mut config = AppConfig::parse(
+            "[[monitoring.alerts]]\nkind = \"slack\"\nwebhook_url = \"secret:slack.webhook\"",
+            ConfigFormat::Toml,
+        )
+        .unwrap();
+        let error = config.resolve_secrets(&provider).await.unwrap_err();
+        assert_eq!(error.field(), Some("monitoring.alerts[0].webhook_url"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,379 @@
+use super::ConfigError;
+use crate::monitoring::alert_sinks::{AlertRoute, AlertSink, SlackSink, TelegramSink, WebhookSink};
+use crate::monitoring::alerts::AlertLevel;
+use crate::monitoring::log_sampling::LogSamplingConfig;
+use crate::oms::RateLimiter;
+use crate::risk::config_watcher::{RiskLimits, SymbolLimits};
+use crate::traits::strategy::StrategyConfig;
+use crate::types::{Price, Size, Symbol};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn enabled() -> bool {
+    true
+}
+
+/// Credentials and environment of one exchange account
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExchangeConfig {
+    /// Connect to this exchange at all
+    pub enabled: bool,
+    /// Use the exchange's testnet instead of production
+    pub testnet: bool,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+}
+
+impl Default for ExchangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            testnet: false,
+            api_key: None,
+            api_secret: None,
+        }
+    }
+}
+
+impl ExchangeConfig {
+    /// Check if both API credentials are set
+    pub fn has_credentials(&self) -> bool {
+        self.api_key.is_some() && self.api_secret.is_some()
+    }
+
+    fn validate(&self, field: &str) -> Result<(), ConfigError> {
+        match (&self.api_key, &self.api_secret) {
+            (Some(_), None) => Err(ConfigError::invalid(
+                format!("{}.api_secret", field),
+                "required when api_key is set",
+            )),
+            (None, Some(_)) => Err(ConfigError::invalid(
+                format!("{}.api_key", field),
+                "required when api_secret is set",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Keep credentials out of logs
+impl fmt::Debug for ExchangeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redact = |value: &Option<String>| value.as_ref().map(|_| "***");
+        f.debug_struct("ExchangeConfig")
+            .field("enabled", &self.enabled)
+            .field("testnet", &self.testnet)
+            .field("api_key", &redact(&self.api_key))
+            .field("api_secret", &redact(&self.api_secret))
+            .finish()
+    }
+}
+
+/// One strategy instance
+///
+/// `parameters` are passed to the strategy as they are, so each strategy type reads and
+/// checks its own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StrategyInstanceConfig {
+    pub strategy_type: String,
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+    pub symbols: Vec<String>,
+    /// Exchanges the strategy trades on; each must be configured under `exchanges`
+    #[serde(default)]
+    pub exchanges: Vec<String>,
+    #[serde(default)]
+    pub parameters: BTreeMap<String, Value>,
+}
+
+impl StrategyInstanceConfig {
+    /// Convert to the `StrategyConfig` strategies are built from
+    /// Parameters become strings; strings are taken without quotes.
+    pub fn to_strategy_config(&self) -> StrategyConfig {
+        StrategyConfig {
+            strategy_type: self.strategy_type.clone(),
+            symbols: self.symbols.iter().map(Symbol::new).collect(),
+            exchanges: self.exchanges.clone(),
+            parameters: self
+                .parameters
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (name.clone(), value)
+                })
+                .collect(),
+        }
+    }
+
+    fn validate(
+        &self,
+        field: &str,
+        exchanges: &BTreeMap<String, ExchangeConfig>,
+    ) -> Result<(), ConfigError> {
+        if self.strategy_type.trim().is_empty() {
+            return Err(ConfigError::invalid(
+                format!("{}.strategy_type", field),
+                "must not be empty",
+            ));
+        }
+        if self.symbols.is_empty() {
+            return Err(ConfigError::invalid(
+                format!("{}.symbols", field),
+                "must list at least one symbol",
+            ));
+        }
+        for (i, exchange) in self.exchanges.iter().enumerate() {
+            if !exchanges.contains_key(exchange) {
+                return Err(ConfigError::invalid(
+                    format!("{}.exchanges[{}]", field, i),
+                    format!("unknown exchange {}", exchange),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Limits of one symbol
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SymbolRiskConfig {
+    pub max_position_size: Option<Decimal>,
+    pub max_order_size: Option<Decimal>,
+    pub max_daily_loss: Option<Decimal>,
+}
+
+/// Risk limits, in the same shape as a `RiskConfigWatcher` limits file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskConfig {
+    pub max_total_exposure: Option<Decimal>,
+    pub max_open_orders: Option<usize>,
+    pub symbols: BTreeMap<String, SymbolRiskConfig>,
+}
+
+impl RiskConfig {
+    /// Get the limits to apply with `RiskLimits::apply`
+    pub fn limits(&self) -> RiskLimits {
+        RiskLimits {
+            max_total_exposure: self.max_total_exposure.map(Price::new),
+            max_open_orders: self.max_open_orders,
+            symbols: self
+                .symbols
+                .iter()
+                .map(|(symbol, limits)| {
+                    let limits = SymbolLimits {
+                        max_position_size: limits.max_position_size.map(Size::new),
+                        max_order_size: limits.max_order_size.map(Size::new),
+                        max_daily_loss: limits.max_daily_loss.map(Price::new),
+                    };
+                    (symbol.clone(), limits)
+                })
+                .collect(),
+        }
+    }
+
+    fn validate(&self, field: &str) -> Result<(), ConfigError> {
+        let non_negative = |name: String, value: Option<Decimal>| match value {
+            Some(value) if value.is_sign_negative() => {
+                Err(ConfigError::invalid(name, "must not be negative"))
+            }
+            _ => Ok(()),
+        };
+        non_negative(
+            format!("{}.max_total_exposure", field),
+            self.max_total_exposure,
+        )?;
+        for (symbol, limits) in &self.symbols {
+            let key = |name: &str| format!("{}.symbols.{}.{}", field, symbol, name);
+            non_negative(key("max_position_size"), limits.max_position_size)?;
+            non_negative(key("max_order_size"), limits.max_order_size)?;
+            non_negative(key("max_daily_loss"), limits.max_daily_loss)?;
+        }
+        Ok(())
+    }
+}
+
+/// Log output settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// One of `error`, `warn`, `info`, `debug` or `trace`
+    pub level: String,
+    /// Also write logs to this file
+    pub file: Option<PathBuf>,
+    /// Hot-path log sampling
+    pub sampling: LogSamplingConfig,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            file: None,
+            sampling: LogSamplingConfig::default(),
+        }
+    }
+}
+
+impl LoggingConfig {
+    fn validate(&self, field: &str) -> Result<(), ConfigError> {
+        if !["error", "warn", "info", "debug", "trace"].contains(&self.level.as_str()) {
+            return Err(ConfigError::invalid(
+                format!("{}.level", field),
+                format!(
+                    "unknown level {}, expected error, warn, info, debug or trace",
+                    self.level
+                ),
+            ));
+        }
+        if self.sampling.every_n == 0 {
+            return Err(ConfigError::invalid(
+                format!("{}.sampling.every_n", field),
+                "must be at least 1",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Transport of an alert sink
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertSinkKind {
+    Telegram { bot_token: String, chat_id: String },
+    Slack { webhook_url: String },
+    Webhook { url: String },
+}
+
+fn warning() -> AlertLevel {
+    AlertLevel::Warning
+}
+
+/// An alert sink and the alerts routed to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertSinkConfig {
+    #[serde(flatten)]
+    pub kind: AlertSinkKind,
+    /// Lowest level delivered, Warning by default
+    #[serde(default = "warning")]
+    pub min_level: AlertLevel,
+    /// Most alerts delivered per minute
+    pub max_per_minute: Option<usize>,
+}
+
+impl AlertSinkConfig {
+    /// Build the route to add with `AlertManager::with_route`
+    pub fn route(&self) -> AlertRoute {
+        let sink: Arc<dyn AlertSink> = match &self.kind {
+            AlertSinkKind::Telegram { bot_token, chat_id } => {
+                Arc::new(TelegramSink::new(bot_token, chat_id))
+            }
+            AlertSinkKind::Slack { webhook_url } => Arc::new(SlackSink::new(webhook_url)),
+            AlertSinkKind::Webhook { url } => Arc::new(WebhookSink::new(url)),
+        };
+        let route = AlertRoute::new(sink).with_min_level(self.min_level);
+        match self.max_per_minute {
+            Some(max) => {
+                route.with_rate_limiter(RateLimiter::new(max.max(1), Duration::from_secs(60)))
+            }
+            None => route,
+        }
+    }
+
+    fn validate(&self, field: &str) -> Result<(), ConfigError> {
+        let (name, value) = match &self.kind {
+            AlertSinkKind::Telegram { bot_token, .. } => ("bot_token", bot_token),
+            AlertSinkKind::Slack { webhook_url } => ("webhook_url", webhook_url),
+            AlertSinkKind::Webhook { url } => ("url", url),
+        };
+        if value.trim().is_empty() {
+            return Err(ConfigError::invalid(
+                format!("{}.{}", field, name),
+                "must not be empty",
+            ));
+        }
+        if self.max_per_minute == Some(0) {
+            return Err(ConfigError::invalid(
+                format!("{}.max_per_minute", field),
+                "must be at least 1",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Monitoring, alerting and operator access
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MonitoringConfig {
+    /// Address of the JSON-lines `ControlServer` used by `hft-cli`
+    pub control_addr: Option<String>,
+    /// Address of the token-authenticated `HttpControlServer`
+    pub http_control_addr: Option<String>,
+    /// Message lag p90 that raises a warning
+    pub lag_warning_ms: u64,
+    /// Message lag p90 that pauses trading on the venue
+    pub lag_critical_ms: u64,
+    /// OTLP/HTTP traces endpoint
+    pub otlp_endpoint: Option<String>,
+    pub alerts: Vec<AlertSinkConfig>,
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            control_addr: None,
+            http_control_addr: None,
+            lag_warning_ms: 250,
+            lag_critical_ms: 1000,
+            otlp_endpoint: None,
+            alerts: Vec::new(),
+        }
+    }
+}
+
+impl MonitoringConfig {
+    fn validate(&self, field: &str) -> Result<(), ConfigError> {
+        if self.lag_warning_ms > self.lag_critical_ms {
+            return Err(ConfigError::invalid(
+                format!("{}.lag_warning_ms", field),
+                format!("must not exceed lag_critical_ms ({})", self.lag_critical_ms),
+            ));
+        }
+        for (i, alert) in self.alerts.iter().enumerate() {
+            alert.validate(&format!("{}.alerts[{}]", field, i))?;
+        }
+        Ok(())
+    }
+}
+
+/// Check every section, naming the first offending field
+pub(super) fn validate(
+    exchanges: &BTreeMap<String, ExchangeConfig>,
+    strategies: &BTreeMap<String, StrategyInstanceConfig>,
+    risk: &RiskConfig,
+    logging: &LoggingConfig,
+    monitoring: &MonitoringConfig,
+) -> Result<(), ConfigError> {
+    for (name, exchange) in exchanges {
+        exchange.validate(&format!("exchanges.{}", name))?;
+    }
+    for (name, strategy) in strategies {
+        strategy.validate(&format!("strategies.{}", name), exchanges)?;
+    }
+    risk.validate("risk")?;
+    logging.validate("logging")?;
+    monitoring.validate("monitoring")
+}
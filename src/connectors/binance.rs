@@ -1,4 +1,9 @@
-use crate::core::events::{OrderBookDelta, OrderBookLevel, OrderSide, Trade};
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::events::{
+    Balance, BestBidAsk, Candle, CandleInterval, ExchangeFill, ExecutionReport, Liquidation,
+    MarkPrice, OrderBookDelta, OrderBookLevel, OrderSide, OrderStatus, Trade,
+};
+use crate::core::reject::RejectReason;
 use crate::traits::MarketEvent;
 use crate::types::{Price, Size, Symbol};
 use serde::{Deserialize, Serialize};
@@ -17,6 +22,9 @@ pub struct DepthUpdateMessage {
     pub U: u64,
     /// Final update ID in event
     pub u: u64,
+    /// Final update ID of the previous event (USD-M futures only)
+    #[serde(default)]
+    pub pu: Option<u64>,
     /// Bids to be updated
     #[serde(default, deserialize_with = "deserialize_price_size_pairs")]
     pub b: Vec<(Price, Size)>,
@@ -53,89 +61,325 @@ pub struct TradeMessage {
     pub m: bool,
 }
 
+/// Binance futures liquidation order message
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForceOrderMessage {
+    /// Event type
+    pub e: String,
+    /// Event time
+    pub E: u64,
+    /// Liquidation order
+    pub o: ForceOrderDetails,
+}
+
+/// Order details of a Binance futures liquidation
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForceOrderDetails {
+    /// Symbol
+    pub s: String,
+    /// Side ("BUY" or "SELL")
+    pub S: String,
+    /// Original quantity
+    #[serde(deserialize_with = "deserialize_size")]
+    pub q: Size,
+    /// Price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub p: Price,
+    /// Average price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub ap: Price,
+    /// Order status
+    pub X: String,
+    /// Accumulated filled quantity
+    #[serde(deserialize_with = "deserialize_size")]
+    pub z: Size,
+    /// Order trade time
+    pub T: u64,
+}
+
+/// Binance futures mark price message
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkPriceMessage {
+    /// Event type
+    pub e: String,
+    /// Event time
+    pub E: u64,
+    /// Symbol
+    pub s: String,
+    /// Mark price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub p: Price,
+    /// Index price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub i: Price,
+    /// Funding rate
+    #[serde(deserialize_with = "deserialize_decimal")]
+    pub r: rust_decimal::Decimal,
+    /// Next funding time
+    pub T: u64,
+}
+
+/// Binance book ticker message: the best bid and ask, pushed on every change
+/// Spot tickers carry no event type or time; USD-M futures ones carry both.
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookTickerMessage {
+    /// Event time (USD-M futures only)
+    #[serde(default)]
+    pub E: Option<u64>,
+    /// Order book update ID
+    pub u: u64,
+    /// Symbol
+    pub s: String,
+    /// Best bid price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub b: Price,
+    /// Best bid quantity
+    #[serde(deserialize_with = "deserialize_size")]
+    pub B: Size,
+    /// Best ask price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub a: Price,
+    /// Best ask quantity
+    #[serde(deserialize_with = "deserialize_size")]
+    pub A: Size,
+}
+
+/// Binance aggregate trade message: fills of one taker order at one price
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggTradeMessage {
+    /// Event type
+    pub e: String,
+    /// Event time
+    pub E: u64,
+    /// Symbol
+    pub s: String,
+    /// Aggregate trade ID
+    pub a: u64,
+    /// Price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub p: Price,
+    /// Quantity
+    #[serde(deserialize_with = "deserialize_size")]
+    pub q: Size,
+    /// First trade ID
+    pub f: u64,
+    /// Last trade ID
+    pub l: u64,
+    /// Trade time
+    pub T: u64,
+    /// Is buyer market maker?
+    pub m: bool,
+}
+
+/// Candle carried by a kline message
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KlinePayload {
+    /// Open time
+    pub t: u64,
+    /// Close time
+    pub T: u64,
+    /// Interval
+    pub i: CandleInterval,
+    /// Open price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub o: Price,
+    /// Close price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub c: Price,
+    /// High price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub h: Price,
+    /// Low price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub l: Price,
+    /// Base asset volume
+    #[serde(deserialize_with = "deserialize_size")]
+    pub v: Size,
+    /// Number of trades
+    pub n: u64,
+    /// Is this kline closed?
+    pub x: bool,
+    /// Quote asset volume
+    #[serde(deserialize_with = "deserialize_decimal")]
+    pub q: rust_decimal::Decimal,
+}
+
+/// Binance kline message, pushed as the current candle changes
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KlineMessage {
+    /// Event type
+    pub e: String,
+    /// Event time
+    pub E: u64,
+    /// Symbol
+    pub s: String,
+    /// Candle
+    pub k: KlinePayload,
+}
+
+/// Why a Binance WebSocket message could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinanceParseError {
+    /// The message is not a JSON object
+    Syntax(String),
+    /// The message has no `e` field and is not a book ticker
+    MissingEventType,
+    /// The event type is not one this parser handles
+    UnknownEventType(String),
+    /// A known event with a missing or malformed field, e.g. `b[3][0]`
+    InvalidField {
+        event: String,
+        path: String,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for BinanceParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinanceParseError::Syntax(message) => write!(f, "Invalid JSON: {}", message),
+            BinanceParseError::MissingEventType => write!(f, "Missing event type field 'e'"),
+            BinanceParseError::UnknownEventType(event) => {
+                write!(f, "Unknown event type: {}", event)
+            }
+            BinanceParseError::InvalidField {
+                event,
+                path,
+                message,
+            } => write!(f, "Invalid {} field {}: {}", event, path, message),
+        }
+    }
+}
+
+impl std::error::Error for BinanceParseError {}
+
+/// Fields read to pick the message type before the typed parse
+/// Borrows from the input and skips every other field, so it allocates nothing.
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    #[serde(default, borrow)]
+    e: Option<std::borrow::Cow<'a, str>>,
+    /// Payload of a combined stream message `{"stream": ..., "data": {...}}`
+    #[serde(default, borrow)]
+    data: Option<&'a serde_json::value::RawValue>,
+    /// Update ID, present in book tickers
+    #[serde(default)]
+    u: Option<u64>,
+}
+
+impl<'a> Envelope<'a> {
+    fn read(json: &'a str) -> Result<Self, BinanceParseError> {
+        serde_json::from_str(json).map_err(|e| BinanceParseError::Syntax(e.to_string()))
+    }
+}
+
+/// Parse a message into its typed struct, naming the offending field on failure
+fn parse_typed<'de, T: Deserialize<'de>>(
+    json: &'de str,
+    event: &str,
+) -> Result<T, BinanceParseError> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        BinanceParseError::InvalidField {
+            event: event.to_string(),
+            path: e.path().to_string(),
+            message: e.into_inner().to_string(),
+        }
+    })
+}
+
 /// Binance WebSocket message types
 #[derive(Debug, Clone)]
 pub enum BinanceMessage {
     DepthUpdate(DepthUpdateMessage),
     Trade(TradeMessage),
+    AggTrade(AggTradeMessage),
+    BookTicker(BookTickerMessage),
+    Kline(KlineMessage),
+    ForceOrder(ForceOrderMessage),
+    MarkPrice(MarkPriceMessage),
 }
 
 impl BinanceMessage {
-    /// Parse a JSON string into a BinanceMessage using SIMD-accelerated parsing
-    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
-        Self::from_json_simd(json)
-    }
-
-    /// Parse using SIMD-accelerated JSON parsing (faster for large messages)
-    pub fn from_json_simd(json: &str) -> Result<Self, serde_json::Error> {
-        // Use simd-json for better performance on large messages
-        // Fall back to serde_json if simd-json fails
-        let mut json_bytes = json.as_bytes().to_vec();
-
-        // First, determine the message type using standard serde_json for simplicity
-        // Then use simd-json for the actual parsing
-        let value: serde_json::Value = serde_json::from_str(json)?;
-
-        if let Some(event_type) = value.get("e").and_then(|v| v.as_str()) {
-            match event_type {
-                "depthUpdate" => {
-                    // Try SIMD parsing first
-                    match simd_json::from_slice::<DepthUpdateMessage>(&mut json_bytes) {
-                        Ok(msg) => Ok(BinanceMessage::DepthUpdate(msg)),
-                        Err(_) => {
-                            // Fall back to standard parsing
-                            let msg: DepthUpdateMessage = serde_json::from_value(value)?;
-                            Ok(BinanceMessage::DepthUpdate(msg))
-                        }
-                    }
-                }
-                "trade" => {
-                    // Try SIMD parsing first
-                    match simd_json::from_slice::<TradeMessage>(&mut json_bytes) {
-                        Ok(msg) => Ok(BinanceMessage::Trade(msg)),
-                        Err(_) => {
-                            // Fall back to standard parsing
-                            let msg: TradeMessage = serde_json::from_value(value)?;
-                            Ok(BinanceMessage::Trade(msg))
-                        }
-                    }
-                }
-                _ => {
-                    // Fall back to standard parsing for unknown types
-                    Self::from_json_fallback(json)
-                }
+    /// Parse a raw or combined-stream market data message
+    ///
+    /// A borrowed envelope picks the message type, then the message is deserialized
+    /// straight into its struct; no intermediate `serde_json::Value` is built.
+    pub fn from_json(json: &str) -> Result<Self, BinanceParseError> {
+        let envelope = Envelope::read(json)?;
+        if let Some(data) = envelope.data {
+            return Self::from_json(data.get());
+        }
+        match envelope.e.as_deref() {
+            Some("depthUpdate") => {
+                parse_typed(json, "depthUpdate").map(BinanceMessage::DepthUpdate)
             }
-        } else {
-            // Fall back if event type not found
-            Self::from_json_fallback(json)
+            Some("trade") => parse_typed(json, "trade").map(BinanceMessage::Trade),
+            Some("aggTrade") => parse_typed(json, "aggTrade").map(BinanceMessage::AggTrade),
+            Some("bookTicker") => parse_typed(json, "bookTicker").map(BinanceMessage::BookTicker),
+            Some("kline") => parse_typed(json, "kline").map(BinanceMessage::Kline),
+            Some("forceOrder") => parse_typed(json, "forceOrder").map(BinanceMessage::ForceOrder),
+            Some("markPriceUpdate") => {
+                parse_typed(json, "markPriceUpdate").map(BinanceMessage::MarkPrice)
+            }
+            Some(event) => Err(BinanceParseError::UnknownEventType(event.to_string())),
+            // Spot book tickers are the only messages without an event type
+            None if envelope.u.is_some() => {
+                parse_typed(json, "bookTicker").map(BinanceMessage::BookTicker)
+            }
+            None => Err(BinanceParseError::MissingEventType),
         }
     }
 
-    /// Fallback to standard serde_json parsing
-    fn from_json_fallback(json: &str) -> Result<Self, serde_json::Error> {
-        // First parse to a generic JSON value to determine the message type
-        let value: serde_json::Value = serde_json::from_str(json)?;
-
-        if let Some(event_type) = value.get("e").and_then(|v| v.as_str()) {
-            match event_type {
-                "depthUpdate" => {
-                    let msg: DepthUpdateMessage = serde_json::from_value(value)?;
-                    Ok(BinanceMessage::DepthUpdate(msg))
-                }
-                "trade" => {
-                    let msg: TradeMessage = serde_json::from_value(value)?;
-                    Ok(BinanceMessage::Trade(msg))
-                }
-                _ => Err(serde_json::Error::io(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    format!("Unknown event type: {}", event_type),
-                ))),
-            }
-        } else {
-            Err(serde_json::Error::io(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Missing event type field 'e'",
-            )))
+    /// Parse with simd-json instead of serde_json
+    /// Copies the message into a mutable buffer first; compare both in
+    /// `benches/message_parsing_benchmark.rs` before switching.
+    pub fn from_json_simd(json: &str) -> Result<Self, BinanceParseError> {
+        let envelope = Envelope::read(json)?;
+        if let Some(data) = envelope.data {
+            return Self::from_json_simd(data.get());
+        }
+        let event = match (envelope.e.as_deref(), envelope.u) {
+            (Some(event), _) => event.to_string(),
+            (None, Some(_)) => "bookTicker".to_string(),
+            (None, None) => return Err(BinanceParseError::MissingEventType),
+        };
+        let mut bytes = json.as_bytes().to_vec();
+        let invalid = |e: simd_json::Error| BinanceParseError::InvalidField {
+            event: event.clone(),
+            path: String::new(),
+            message: e.to_string(),
+        };
+        match event.as_str() {
+            "depthUpdate" => simd_json::from_slice(&mut bytes)
+                .map(BinanceMessage::DepthUpdate)
+                .map_err(invalid),
+            "trade" => simd_json::from_slice(&mut bytes)
+                .map(BinanceMessage::Trade)
+                .map_err(invalid),
+            "aggTrade" => simd_json::from_slice(&mut bytes)
+                .map(BinanceMessage::AggTrade)
+                .map_err(invalid),
+            "bookTicker" => simd_json::from_slice(&mut bytes)
+                .map(BinanceMessage::BookTicker)
+                .map_err(invalid),
+            "kline" => simd_json::from_slice(&mut bytes)
+                .map(BinanceMessage::Kline)
+                .map_err(invalid),
+            "forceOrder" => simd_json::from_slice(&mut bytes)
+                .map(BinanceMessage::ForceOrder)
+                .map_err(invalid),
+            "markPriceUpdate" => simd_json::from_slice(&mut bytes)
+                .map(BinanceMessage::MarkPrice)
+                .map_err(invalid),
+            _ => Err(BinanceParseError::UnknownEventType(event)),
         }
     }
 
@@ -180,6 +424,303 @@ impl BinanceMessage {
 
                 MarketEvent::Trade(trade)
             }
+            BinanceMessage::AggTrade(msg) => MarketEvent::Trade(Trade {
+                symbol: Symbol::new(msg.s),
+                exchange_id: "binance".to_string(),
+                price: msg.p,
+                size: msg.q,
+                side: if msg.m {
+                    OrderSide::Sell
+                } else {
+                    OrderSide::Buy
+                },
+                timestamp: msg.E,
+                trade_id: Some(msg.a.to_string()),
+            }),
+            BinanceMessage::Kline(msg) => MarketEvent::Candle(Candle {
+                symbol: Symbol::new(msg.s),
+                exchange_id: "binance".to_string(),
+                interval: msg.k.i,
+                open_time: msg.k.t,
+                close_time: msg.k.T,
+                open: msg.k.o,
+                high: msg.k.h,
+                low: msg.k.l,
+                close: msg.k.c,
+                volume: msg.k.v,
+                quote_volume: msg.k.q,
+                trade_count: msg.k.n,
+                closed: msg.k.x,
+                timestamp: msg.E,
+            }),
+            BinanceMessage::BookTicker(msg) => MarketEvent::BestBidAsk(BestBidAsk {
+                symbol: Symbol::new(msg.s),
+                exchange_id: "binance".to_string(),
+                bid_price: msg.b,
+                bid_size: msg.B,
+                ask_price: msg.a,
+                ask_size: msg.A,
+                update_id: msg.u,
+                // Spot tickers carry no time, so stamp them on receipt
+                timestamp: msg.E.unwrap_or_else(|| SystemClock.now_millis()),
+            }),
+            BinanceMessage::ForceOrder(msg) => {
+                let side = if msg.o.S == "BUY" {
+                    OrderSide::Buy
+                } else {
+                    OrderSide::Sell
+                };
+
+                // Use the filled quantity and average price once the order has executed
+                let (price, size) = if msg.o.z.is_zero() {
+                    (msg.o.p, msg.o.q)
+                } else {
+                    (msg.o.ap, msg.o.z)
+                };
+
+                let liquidation = Liquidation {
+                    symbol: Symbol::new(msg.o.s),
+                    exchange_id: "binance_futures".to_string(),
+                    side,
+                    price,
+                    size,
+                    timestamp: msg.o.T,
+                };
+
+                MarketEvent::Liquidation(liquidation)
+            }
+            BinanceMessage::MarkPrice(msg) => MarketEvent::MarkPrice(MarkPrice {
+                symbol: Symbol::new(msg.s),
+                exchange_id: "binance_futures".to_string(),
+                mark_price: msg.p,
+                index_price: msg.i,
+                funding_rate: msg.r,
+                next_funding_time: msg.T,
+                timestamp: msg.E,
+            }),
+        }
+    }
+}
+
+/// Binance spot order update from the user data stream
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionReportMessage {
+    /// Event time
+    pub E: u64,
+    /// Symbol
+    pub s: String,
+    /// Client order ID (of the cancel request for cancels)
+    pub c: String,
+    /// Original client order ID, set for cancels
+    #[serde(default)]
+    pub C: String,
+    /// Side ("BUY" or "SELL")
+    pub S: String,
+    /// Order type
+    pub o: String,
+    /// Order quantity
+    #[serde(deserialize_with = "deserialize_size")]
+    pub q: Size,
+    /// Order price
+    #[serde(deserialize_with = "deserialize_price")]
+    pub p: Price,
+    /// Execution type ("NEW", "TRADE", "CANCELED", ...)
+    pub x: String,
+    /// Order status
+    pub X: String,
+    /// Reject reason, "NONE" unless rejected
+    pub r: String,
+    /// Order ID
+    pub i: u64,
+    /// Quantity of the last fill
+    #[serde(deserialize_with = "deserialize_size")]
+    pub l: Size,
+    /// Cumulative filled quantity
+    #[serde(deserialize_with = "deserialize_size")]
+    pub z: Size,
+    /// Price of the last fill
+    #[serde(deserialize_with = "deserialize_price")]
+    pub L: Price,
+    /// Commission of the last fill
+    #[serde(deserialize_with = "deserialize_decimal")]
+    pub n: rust_decimal::Decimal,
+    /// Commission asset, null without a fill
+    pub N: Option<String>,
+    /// Transaction time
+    pub T: u64,
+    /// Trade ID, -1 without a fill
+    pub t: i64,
+    /// Was the fill on the maker side?
+    pub m: bool,
+    /// Cumulative quote quantity
+    #[serde(deserialize_with = "deserialize_decimal")]
+    pub Z: rust_decimal::Decimal,
+}
+
+impl ExecutionReportMessage {
+    fn side(&self) -> OrderSide {
+        if self.S == "BUY" {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        }
+    }
+
+    /// Convert to an execution report; `None` for statuses without an equivalent
+    pub fn to_execution_report(&self) -> Option<ExecutionReport> {
+        let status = match self.X.as_str() {
+            "NEW" => OrderStatus::New,
+            "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+            "FILLED" => OrderStatus::Filled,
+            "CANCELED" => OrderStatus::Cancelled,
+            "REJECTED" => OrderStatus::Rejected,
+            "EXPIRED" | "EXPIRED_IN_MATCH" => OrderStatus::Expired,
+            _ => return None,
+        };
+        let client_order_id = if self.C.is_empty() { &self.c } else { &self.C };
+        let average_price = if self.z.value().is_zero() {
+            Some(self.p).filter(|price| !price.value().is_zero())
+        } else {
+            Some(Price::new(self.Z / self.z.value()))
+        };
+        Some(ExecutionReport {
+            order_id: self.i.to_string(),
+            client_order_id: Some(client_order_id.clone()),
+            symbol: Symbol::new(&self.s),
+            exchange_id: "binance".to_string(),
+            status,
+            filled_size: self.z,
+            remaining_size: Size::new(self.q.value() - self.z.value()),
+            average_price,
+            timestamp: self.T,
+            reject_reason: (status == OrderStatus::Rejected).then_some(match self.r.as_str() {
+                "INSUFFICIENT_BALANCE" => RejectReason::InsufficientBalance,
+                "UNKNOWN_ORDER" => RejectReason::UnknownOrder,
+                "UNKNOWN_INSTRUMENT" => RejectReason::UnknownSymbol,
+                "DUPLICATE_ORDER" => RejectReason::DuplicateOrder,
+                "PRICE_QTY_EXCEED_HARD_LIMITS" => RejectReason::PriceOutOfBounds,
+                "MARKET_CLOSED" => RejectReason::MarketClosed,
+                _ => RejectReason::Other,
+            }),
+        })
+    }
+
+    /// Get the fill this update reports, if it is a trade
+    pub fn to_fill(&self) -> Option<ExchangeFill> {
+        if self.x != "TRADE" || self.t < 0 {
+            return None;
+        }
+        Some(ExchangeFill {
+            trade_id: self.t.to_string(),
+            order_id: self.i.to_string(),
+            symbol: Symbol::new(&self.s),
+            exchange_id: "binance".to_string(),
+            side: self.side(),
+            price: self.L,
+            size: self.l,
+            fee: self.n,
+            fee_asset: self.N.clone().unwrap_or_default(),
+            timestamp: self.T,
+        })
+    }
+}
+
+/// Balance of one asset in an account update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountBalanceMessage {
+    /// Asset
+    pub a: String,
+    /// Free amount
+    #[serde(deserialize_with = "deserialize_decimal")]
+    pub f: rust_decimal::Decimal,
+    /// Locked amount
+    #[serde(deserialize_with = "deserialize_decimal")]
+    pub l: rust_decimal::Decimal,
+}
+
+/// Binance spot account update: balances that changed
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountPositionMessage {
+    /// Event time
+    pub E: u64,
+    /// Time of the last account update
+    pub u: u64,
+    /// Changed balances
+    pub B: Vec<AccountBalanceMessage>,
+}
+
+impl AccountPositionMessage {
+    /// Convert to balances
+    pub fn to_balances(&self) -> Vec<Balance> {
+        self.B
+            .iter()
+            .map(|balance| Balance {
+                asset: balance.a.clone(),
+                exchange_id: "binance".to_string(),
+                total: balance.f + balance.l,
+                free: balance.f,
+                used: balance.l,
+            })
+            .collect()
+    }
+}
+
+/// Binance spot deposit, withdrawal or transfer
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceUpdateMessage {
+    /// Event time
+    pub E: u64,
+    /// Asset
+    pub a: String,
+    /// Balance delta
+    #[serde(deserialize_with = "deserialize_decimal")]
+    pub d: rust_decimal::Decimal,
+    /// Clear time
+    pub T: u64,
+}
+
+/// The listen key expired; the user data stream must be reopened
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListenKeyExpiredMessage {
+    /// Event time
+    pub E: u64,
+    #[serde(rename = "listenKey")]
+    pub listen_key: String,
+}
+
+/// Binance spot user data stream message types
+#[derive(Debug, Clone)]
+pub enum BinanceUserDataMessage {
+    ExecutionReport(Box<ExecutionReportMessage>),
+    AccountPosition(AccountPositionMessage),
+    BalanceUpdate(BalanceUpdateMessage),
+    ListenKeyExpired(ListenKeyExpiredMessage),
+}
+
+impl BinanceUserDataMessage {
+    /// Parse a raw or combined-stream user data message
+    pub fn from_json(json: &str) -> Result<Self, BinanceParseError> {
+        let envelope = Envelope::read(json)?;
+        if let Some(data) = envelope.data {
+            return Self::from_json(data.get());
+        }
+        match envelope.e.as_deref() {
+            Some("executionReport") => parse_typed(json, "executionReport")
+                .map(|report| BinanceUserDataMessage::ExecutionReport(Box::new(report))),
+            Some("outboundAccountPosition") => parse_typed(json, "outboundAccountPosition")
+                .map(BinanceUserDataMessage::AccountPosition),
+            Some("balanceUpdate") => {
+                parse_typed(json, "balanceUpdate").map(BinanceUserDataMessage::BalanceUpdate)
+            }
+            Some("listenKeyExpired") => {
+                parse_typed(json, "listenKeyExpired").map(BinanceUserDataMessage::ListenKeyExpired)
+            }
+            Some(event) => Err(BinanceParseError::UnknownEventType(event.to_string())),
+            None => Err(BinanceParseError::MissingEventType),
         }
     }
 }
@@ -226,6 +767,17 @@ where
     Size::from_str(&s).map_err(serde::de::Error::custom)
 }
 
+/// Custom deserializer for a decimal string
+fn deserialize_decimal<'de, D>(deserializer: D) -> Result<rust_decimal::Decimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    let s: String = Deserialize::deserialize(deserializer)?;
+    s.parse().map_err(serde::de::Error::custom)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,7 +848,7 @@ mod tests {
                 assert_eq!(msg.t, 12345);
                 assert_eq!(msg.p, Price::from_str("0.001").unwrap());
                 assert_eq!(msg.q, Size::from_str("100").unwrap());
-                assert_eq!(msg.m, true);
+                assert!(msg.m);
             }
             _ => panic!("Expected Trade message"),
         }
@@ -337,4 +889,204 @@ mod tests {
             _ => panic!("Expected OrderBookDelta event"),
         }
     }
+
+    #[test]
+    fn test_force_order_to_market_event() {
+        let json = r#"{
+            "e": "forceOrder",
+            "E": 1568014460893,
+            "o": {
+                "s": "BTCUSDT",
+                "S": "SELL",
+                "o": "LIMIT",
+                "f": "IOC",
+                "q": "0.014",
+                "p": "9910",
+                "ap": "9910.5",
+                "X": "FILLED",
+                "l": "0.014",
+                "z": "0.014",
+                "T": 1568014460893
+            }
+        }"#;
+
+        let message = BinanceMessage::from_json(json).unwrap();
+        assert!(matches!(message, BinanceMessage::ForceOrder(_)));
+
+        match message.to_market_event() {
+            MarketEvent::Liquidation(liquidation) => {
+                assert_eq!(liquidation.symbol.as_str(), "BTCUSDT");
+                assert_eq!(liquidation.side, OrderSide::Sell);
+                assert_eq!(liquidation.price, Price::from_str("9910.5").unwrap());
+                assert_eq!(liquidation.size, Size::from_str("0.014").unwrap());
+                assert_eq!(liquidation.timestamp, 1568014460893);
+            }
+            _ => panic!("Expected Liquidation event"),
+        }
+    }
+
+    #[test]
+    fn test_mark_price_to_market_event() {
+        let json = r#"{
+            "e": "markPriceUpdate",
+            "E": 1562305380000,
+            "s": "BTCUSDT",
+            "p": "11794.15000000",
+            "i": "11784.62659091",
+            "P": "11784.25641265",
+            "r": "0.00038167",
+            "T": 1562306400000
+        }"#;
+
+        match BinanceMessage::from_json(json).unwrap().to_market_event() {
+            MarketEvent::MarkPrice(mark_price) => {
+                assert_eq!(mark_price.symbol.as_str(), "BTCUSDT");
+                assert_eq!(mark_price.mark_price, Price::from_str("11794.15").unwrap());
+                assert_eq!(
+                    mark_price.funding_rate,
+                    "0.00038167".parse::<rust_decimal::Decimal>().unwrap()
+                );
+                assert_eq!(mark_price.next_funding_time, 1562306400000);
+            }
+            _ => panic!("Expected MarkPrice event"),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_field() {
+        let json = r#"{"e":"depthUpdate","E":1,"s":"BNBBTC","U":1,"u":2,"b":[["abc","1"]],"a":[]}"#;
+        match BinanceMessage::from_json(json) {
+            Err(BinanceParseError::InvalidField { event, path, .. }) => {
+                assert_eq!(event, "depthUpdate");
+                assert_eq!(path, "b[0][0]");
+            }
+            other => panic!("Expected InvalidField, got {:?}", other),
+        }
+
+        assert_eq!(
+            BinanceMessage::from_json(r#"{"e":"24hrTicker","E":1}"#).unwrap_err(),
+            BinanceParseError::UnknownEventType("24hrTicker".to_string())
+        );
+        assert_eq!(
+            BinanceMessage::from_json(r#"{"result":null,"id":1}"#).unwrap_err(),
+            BinanceParseError::MissingEventType
+        );
+        assert!(matches!(
+            BinanceMessage::from_json("not json"),
+            Err(BinanceParseError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_book_ticker_to_market_event() {
+        // Spot book tickers have no event type; combined streams wrap the payload
+        let spot = r#"{"stream":"bnbusdt@bookTicker","data":{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}}"#;
+        let futures = r#"{"e":"bookTicker","u":400900217,"E":1568014460893,"T":1568014460891,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+
+        for json in [spot, futures] {
+            let message = BinanceMessage::from_json(json).unwrap();
+            let simd = BinanceMessage::from_json_simd(json).unwrap();
+            assert!(matches!(simd, BinanceMessage::BookTicker(_)));
+            match message.to_market_event() {
+                MarketEvent::BestBidAsk(quote) => {
+                    assert_eq!(quote.symbol.as_str(), "BNBUSDT");
+                    assert_eq!(quote.bid_price, Price::from_str("25.3519").unwrap());
+                    assert_eq!(quote.ask_size, Size::from_str("40.66").unwrap());
+                    assert_eq!(quote.update_id, 400900217);
+                }
+                _ => panic!("Expected BestBidAsk event"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_agg_trade_and_kline_to_market_event() {
+        let agg_trade = r#"{"e":"aggTrade","E":1672515782136,"s":"BNBBTC","a":12345,"p":"0.001","q":"100","f":100,"l":105,"T":1672515782136,"m":false,"M":true}"#;
+        match BinanceMessage::from_json(agg_trade)
+            .unwrap()
+            .to_market_event()
+        {
+            MarketEvent::Trade(trade) => {
+                assert_eq!(trade.side, OrderSide::Buy);
+                assert_eq!(trade.size, Size::from_str("100").unwrap());
+                assert_eq!(trade.trade_id.as_deref(), Some("12345"));
+            }
+            _ => panic!("Expected Trade event"),
+        }
+
+        let kline = r#"{"stream":"bnbbtc@kline_1m","data":{"e":"kline","E":1672515782136,"s":"BNBBTC","k":{
+            "t":1672515780000,"T":1672515839999,"s":"BNBBTC","i":"1m","f":100,"L":200,
+            "o":"0.0010","c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,"x":false,
+            "q":"1.0000","V":"500","Q":"0.500","B":"123456"}}}"#;
+        match BinanceMessage::from_json(kline).unwrap().to_market_event() {
+            MarketEvent::Candle(candle) => {
+                assert_eq!(candle.interval, CandleInterval::OneMinute);
+                assert_eq!(candle.open_time, 1672515780000);
+                assert_eq!(candle.high, Price::from_str("0.0025").unwrap());
+                assert_eq!(candle.close, Price::from_str("0.0020").unwrap());
+                assert_eq!(candle.volume, Size::from_str("1000").unwrap());
+                assert_eq!(candle.trade_count, 100);
+                assert!(!candle.closed);
+            }
+            _ => panic!("Expected Candle event"),
+        }
+        assert!(BinanceMessage::from_json_simd(kline).is_ok());
+
+        let unknown_interval = kline.replace(r#""i":"1m""#, r#""i":"7m""#);
+        match BinanceMessage::from_json(&unknown_interval) {
+            Err(BinanceParseError::InvalidField { path, .. }) => assert_eq!(path, "k.i"),
+            other => panic!("Expected InvalidField, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_execution_report() {
+        let json = r#"{
+            "e": "executionReport", "E": 1499405658658, "s": "ETHBTC",
+            "c": "mUvoqJxFIILMdfAW5iGSOW", "S": "BUY", "o": "LIMIT", "f": "GTC",
+            "q": "1.00000000", "p": "0.10264410", "P": "0.00000000", "F": "0.00000000",
+            "g": -1, "C": "", "x": "TRADE", "X": "PARTIALLY_FILLED", "r": "NONE",
+            "i": 4293153, "l": "0.40000000", "z": "0.40000000", "L": "0.10250000",
+            "n": "0.00004000", "N": "BNB", "T": 1499405658657, "t": 718,
+            "I": 8641984, "w": false, "m": true, "M": false, "O": 1499405658657,
+            "Z": "0.04100000", "Y": "0.04100000", "Q": "0.00000000"
+        }"#;
+
+        let report = match BinanceUserDataMessage::from_json(json).unwrap() {
+            BinanceUserDataMessage::ExecutionReport(report) => report,
+            other => panic!("Expected ExecutionReport, got {:?}", other),
+        };
+        let execution = report.to_execution_report().unwrap();
+        assert_eq!(execution.order_id, "4293153");
+        assert_eq!(
+            execution.client_order_id.as_deref(),
+            Some("mUvoqJxFIILMdfAW5iGSOW")
+        );
+        assert_eq!(execution.status, OrderStatus::PartiallyFilled);
+        assert_eq!(execution.remaining_size, Size::from_str("0.6").unwrap());
+        assert_eq!(
+            execution.average_price,
+            Some(Price::from_str("0.1025").unwrap())
+        );
+        assert_eq!(execution.reject_reason, None);
+
+        let fill = report.to_fill().unwrap();
+        assert_eq!(fill.trade_id, "718");
+        assert_eq!(fill.side, OrderSide::Buy);
+        assert_eq!(fill.price, Price::from_str("0.1025").unwrap());
+        assert_eq!(fill.fee_asset, "BNB");
+
+        let account = r#"{"e":"outboundAccountPosition","E":1564034571105,"u":1564034571073,"B":[{"a":"ETH","f":"10000.000000","l":"1.000000"}]}"#;
+        match BinanceUserDataMessage::from_json(account).unwrap() {
+            BinanceUserDataMessage::AccountPosition(position) => {
+                let balances = position.to_balances();
+                assert_eq!(balances[0].asset, "ETH");
+                assert_eq!(
+                    balances[0].total,
+                    "10001".parse::<rust_decimal::Decimal>().unwrap()
+                );
+            }
+            other => panic!("Expected AccountPosition, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,487 @@
+use crate::core::events::{OrderBookLevel, OrderBookSnapshot};
+use crate::monitoring::SymbolStatsRegistry;
+use crate::traits::{MarketDataStream, MarketEvent};
+use crate::types::{Price, Size};
+use async_trait::async_trait;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default time between polls of every subscribed pair
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Supported DEX aggregators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DexAggregator {
+    /// 0x Swap API (v2 permit2 price endpoint)
+    ZeroEx,
+    /// 1inch Swap API (v6 quote endpoint)
+    OneInch,
+}
+
+impl DexAggregator {
+    /// Venue ID used as the exchange ID of published snapshots
+    pub fn venue_id(&self) -> &'static str {
+        match self {
+            DexAggregator::ZeroEx => "dex_0x",
+            DexAggregator::OneInch => "dex_1inch",
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        match self {
+            DexAggregator::ZeroEx => "https://api.0x.org",
+            DexAggregator::OneInch => "https://api.1inch.dev",
+        }
+    }
+}
+
+/// An on-chain token pair quoted under a CEX-style symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DexPair {
+    /// Symbol the snapshots are published under (e.g. "ETHUSDT")
+    pub symbol: String,
+    /// Chain ID (1 for Ethereum mainnet)
+    pub chain_id: u64,
+    /// Base token contract address
+    pub base_token: String,
+    /// Base token decimals
+    pub base_decimals: u32,
+    /// Quote token contract address
+    pub quote_token: String,
+    /// Quote token decimals
+    pub quote_decimals: u32,
+    /// Base amount quoted in each direction
+    pub probe_size: Size,
+}
+
+impl DexPair {
+    /// Create a pair on a chain
+    pub fn new(
+        symbol: impl Into<String>,
+        chain_id: u64,
+        base_token: (impl Into<String>, u32),
+        quote_token: (impl Into<String>, u32),
+        probe_size: Size,
+    ) -> Self {
+        Self {
+            symbol: symbol.into(),
+            chain_id,
+            base_token: base_token.0.into(),
+            base_decimals: base_token.1,
+            quote_token: quote_token.0.into(),
+            quote_decimals: quote_token.1,
+            probe_size,
+        }
+    }
+}
+
+/// Convert a token amount to integer base units, truncating dust below one unit
+pub fn to_base_units(amount: Decimal, decimals: u32) -> Option<String> {
+    let scale = Decimal::from_i128_with_scale(10i128.checked_pow(decimals)?, 0);
+    let units = amount.checked_mul(scale)?.trunc();
+    (units > Decimal::ZERO).then(|| units.normalize().to_string())
+}
+
+/// Convert integer base units to a token amount
+pub fn from_base_units(units: &str, decimals: u32) -> Option<Decimal> {
+    let units: i128 = units.parse().ok()?;
+    Decimal::try_from_i128_with_scale(units, decimals).ok()
+}
+
+/// Parse the bought amount (in base units) from a quote response
+pub fn parse_buy_amount(aggregator: DexAggregator, quote: &Value) -> Option<String> {
+    let field = match aggregator {
+        DexAggregator::ZeroEx => "buyAmount",
+        DexAggregator::OneInch => "dstAmount",
+    };
+    quote.get(field)?.as_str().map(|v| v.to_string())
+}
+
+/// Build a one-level book from the two probe swaps
+///
+/// Both swaps are given as (base amount, quote amount): the bid is the quote received for
+/// selling base, the ask the quote spent buying base. A side without a route is left empty.
+pub fn quote_snapshot(
+    aggregator: DexAggregator,
+    pair: &DexPair,
+    sell_base: Option<(Decimal, Decimal)>,
+    buy_base: Option<(Decimal, Decimal)>,
+    timestamp: u64,
+) -> OrderBookSnapshot {
+    let level = |(base, quote): (Decimal, Decimal)| {
+        (base > Decimal::ZERO)
+            .then(|| OrderBookLevel::new(Price::new(quote / base), Size::new(base)))
+    };
+    OrderBookSnapshot::new(
+        pair.symbol.as_str(),
+        aggregator.venue_id(),
+        sell_base.and_then(level).into_iter().collect(),
+        buy_base.and_then(level).into_iter().collect(),
+        timestamp,
+    )
+}
+
+/// DEX aggregator errors
+#[derive(Debug, Clone)]
+pub enum DexError {
+    NetworkError(String),
+    ApiError(String),
+    ParseError(String),
+    RateLimitError(String),
+    /// Symbol was not configured with a token pair
+    UnknownPair(String),
+}
+
+impl fmt::Display for DexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DexError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            DexError::ApiError(msg) => write!(f, "API error: {}", msg),
+            DexError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            DexError::RateLimitError(msg) => write!(f, "Rate limit error: {}", msg),
+            DexError::UnknownPair(symbol) => write!(f, "No DEX pair configured for {}", symbol),
+        }
+    }
+}
+
+impl std::error::Error for DexError {}
+
+/// Polling quote stream for one DEX aggregator
+pub struct DexQuoteStream {
+    aggregator: DexAggregator,
+    /// API key sent with every request
+    api_key: String,
+    /// Base URL for REST API
+    base_url: String,
+    http_client: Client,
+    /// Configured pairs by symbol
+    pairs: HashMap<String, DexPair>,
+    /// Subscribed symbols, polled in order
+    subscriptions: Vec<String>,
+    poll_interval: Duration,
+    /// Last poll start, None before the first poll
+    last_poll: Option<tokio::time::Instant>,
+    /// Snapshots of the current poll not yet returned
+    pending: VecDeque<Result<MarketEvent, DexError>>,
+    symbol_stats: SymbolStatsRegistry,
+}
+
+impl DexQuoteStream {
+    /// Create a quote stream for an aggregator
+    pub fn new(aggregator: DexAggregator, api_key: impl Into<String>) -> Self {
+        Self {
+            aggregator,
+            api_key: api_key.into(),
+            base_url: aggregator.default_base_url().to_string(),
+            http_client: Client::new(),
+            pairs: HashMap::new(),
+            subscriptions: Vec::new(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            last_poll: None,
+            pending: VecDeque::new(),
+            symbol_stats: SymbolStatsRegistry::new(),
+        }
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Set the time between polls (builder pattern)
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Configure a pair that can be subscribed by its symbol (builder pattern)
+    pub fn with_pair(mut self, pair: DexPair) -> Self {
+        self.pairs.insert(pair.symbol.clone(), pair);
+        self
+    }
+
+    /// Record stats into a shared registry (builder pattern)
+    pub fn with_symbol_stats(mut self, symbol_stats: SymbolStatsRegistry) -> Self {
+        self.symbol_stats = symbol_stats;
+        self
+    }
+
+    /// Venue ID of published snapshots
+    pub fn venue_id(&self) -> &'static str {
+        self.aggregator.venue_id()
+    }
+
+    /// Get the per-symbol stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Quote a swap of `sell_amount` base units and return the bought base units
+    /// Returns None when the aggregator finds no route
+    async fn quote(
+        &self,
+        chain_id: u64,
+        sell_token: &str,
+        buy_token: &str,
+        sell_amount: &str,
+    ) -> Result<Option<String>, DexError> {
+        let request = match self.aggregator {
+            DexAggregator::ZeroEx => self
+                .http_client
+                .get(format!("{}/swap/permit2/price", self.base_url))
+                .query(&[
+                    ("chainId", chain_id.to_string().as_str()),
+                    ("sellToken", sell_token),
+                    ("buyToken", buy_token),
+                    ("sellAmount", sell_amount),
+                ])
+                .header("0x-api-key", &self.api_key)
+                .header("0x-version", "v2"),
+            DexAggregator::OneInch => self
+                .http_client
+                .get(format!("{}/swap/v6.0/{}/quote", self.base_url, chain_id))
+                .query(&[
+                    ("src", sell_token),
+                    ("dst", buy_token),
+                    ("amount", sell_amount),
+                ])
+                .bearer_auth(&self.api_key),
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| DexError::NetworkError(e.to_string()))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| DexError::NetworkError(e.to_string()))?;
+        if status.as_u16() == 429 {
+            return Err(DexError::RateLimitError(text));
+        }
+        if !status.is_success() {
+            return Err(DexError::ApiError(format!("{} - {}", status, text)));
+        }
+
+        let quote: Value =
+            serde_json::from_str(&text).map_err(|e| DexError::ParseError(e.to_string()))?;
+        // 0x answers 200 with liquidityAvailable=false when there is no route
+        if quote.get("liquidityAvailable").and_then(|v| v.as_bool()) == Some(false) {
+            return Ok(None);
+        }
+        parse_buy_amount(self.aggregator, &quote)
+            .map(Some)
+            .ok_or_else(|| DexError::ParseError(format!("No bought amount in {}", text)))
+    }
+
+    /// Quote both directions of a pair
+    ///
+    /// The sell side swaps the probe size of base for quote. The buy side spends the
+    /// quote just received, so both sides cover about the same notional.
+    pub async fn poll_pair(&self, symbol: &str) -> Result<OrderBookSnapshot, DexError> {
+        let pair = self
+            .pairs
+            .get(symbol)
+            .ok_or_else(|| DexError::UnknownPair(symbol.to_string()))?;
+        let invalid = |what: &str| DexError::ParseError(format!("Invalid {} for {}", what, symbol));
+
+        let probe_units = to_base_units(pair.probe_size.value(), pair.base_decimals)
+            .ok_or_else(|| invalid("probe size"))?;
+        let quote_units = self
+            .quote(
+                pair.chain_id,
+                &pair.base_token,
+                &pair.quote_token,
+                &probe_units,
+            )
+            .await?;
+        let quote_received = quote_units
+            .map(|units| {
+                from_base_units(&units, pair.quote_decimals).ok_or_else(|| invalid("quote amount"))
+            })
+            .transpose()?;
+
+        let mut buy_base = None;
+        if let Some(quote_spent) = quote_received {
+            if let Some(spend_units) = to_base_units(quote_spent, pair.quote_decimals) {
+                let base_units = self
+                    .quote(
+                        pair.chain_id,
+                        &pair.quote_token,
+                        &pair.base_token,
+                        &spend_units,
+                    )
+                    .await?;
+                if let Some(units) = base_units {
+                    let base_bought = from_base_units(&units, pair.base_decimals)
+                        .ok_or_else(|| invalid("base amount"))?;
+                    buy_base = Some((base_bought, quote_spent));
+                }
+            }
+        }
+
+        let sell_base = quote_received.map(|quote| (pair.probe_size.value(), quote));
+        Ok(quote_snapshot(
+            self.aggregator,
+            pair,
+            sell_base,
+            buy_base,
+            now_ms(),
+        ))
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for DexQuoteStream {
+    type Error = DexError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        if let Some(unknown) = symbols.iter().find(|s| !self.pairs.contains_key(**s)) {
+            return Err(DexError::UnknownPair(unknown.to_string()));
+        }
+        for symbol in symbols {
+            if !self.subscriptions.iter().any(|s| s == symbol) {
+                self.subscriptions.push(symbol.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.subscriptions
+            .retain(|s| !symbols.contains(&s.as_str()));
+        Ok(())
+    }
+
+    /// Return the next quote snapshot, polling every subscribed pair once per interval
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                if let Ok(event) = &event {
+                    self.symbol_stats.record_event(event);
+                }
+                return Some(event);
+            }
+            if self.subscriptions.is_empty() {
+                return None;
+            }
+            if let Some(last_poll) = self.last_poll {
+                tokio::time::sleep_until(last_poll + self.poll_interval).await;
+            }
+            self.last_poll = Some(tokio::time::Instant::now());
+
+            for symbol in self.subscriptions.clone() {
+                let event = self
+                    .poll_pair(&symbol)
+                    .await
+                    .map(MarketEvent::OrderBookSnapshot);
+                self.pending.push_back(event);
+            }
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+    fn weth_usdc() -> DexPair {
+        DexPair::new(
+            "ETHUSDC",
+            1,
+            (WETH, 18),
+            (USDC, 6),
+            Size::from_str("0.5").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_base_unit_conversion() {
+        assert_eq!(
+            to_base_units(Decimal::from_str("0.5").unwrap(), 18).as_deref(),
+            Some("500000000000000000")
+        );
+        assert_eq!(
+            to_base_units(Decimal::from_str("0.0000001").unwrap(), 6),
+            None
+        );
+        assert_eq!(
+            from_base_units("1500250000", 6),
+            Some(Decimal::from_str("1500.25").unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zero_ex_quotes_become_synthetic_book() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/swap/permit2/price"))
+            .and(header("0x-api-key", "key"))
+            .and(query_param("sellToken", WETH))
+            .and(query_param("sellAmount", "500000000000000000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "liquidityAvailable": true, "buyAmount": "1500000000", "sellAmount": "500000000000000000"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/swap/permit2/price"))
+            .and(query_param("sellToken", USDC))
+            .and(query_param("sellAmount", "1500000000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "liquidityAvailable": true, "buyAmount": "480000000000000000"
+            })))
+            .mount(&server)
+            .await;
+
+        let mut stream = DexQuoteStream::new(DexAggregator::ZeroEx, "key")
+            .with_base_url(server.uri())
+            .with_pair(weth_usdc());
+        assert!(matches!(
+            stream.subscribe(&["BTCUSDC"]).await,
+            Err(DexError::UnknownPair(_))
+        ));
+        stream.subscribe(&["ETHUSDC"]).await.unwrap();
+
+        match stream.next().await {
+            Some(Ok(MarketEvent::OrderBookSnapshot(snapshot))) => {
+                assert_eq!(snapshot.exchange_id, "dex_0x");
+                assert_eq!(snapshot.symbol.value(), "ETHUSDC");
+                assert_eq!(snapshot.bids[0].price, Price::from_str("3000").unwrap());
+                assert_eq!(snapshot.bids[0].size, Size::from_str("0.5").unwrap());
+                assert_eq!(snapshot.asks[0].price, Price::from_str("3125").unwrap());
+                assert_eq!(snapshot.asks[0].size, Size::from_str("0.48").unwrap());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(stream.last_update("ETHUSDC").is_some());
+    }
+}
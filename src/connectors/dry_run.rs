@@ -1,19 +1,95 @@
+use crate::backtest::{FillModel, SimulatedFill};
+use crate::core::clock::{system_clock, SharedClock};
+use crate::core::events::{MarketEvent, Timestamp};
+use crate::orderbook::OrderBook;
 use crate::traits::{
     Balance, ExecutionClient, ExecutionReport, NewOrder, OrderId, OrderSide, OrderStatus,
     OrderType, TimeInForce, TradingFees,
 };
-use crate::types::Size;
+use crate::types::{Price, Size};
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 
-/// Dry-run execution client that prints orders but doesn't actually place them
+/// Dry-run execution client that prints orders and fills them on paper
+///
+/// Orders are never sent anywhere. Fed live market data through `on_market_event`,
+/// they reach a simulated book `latency_ms` of event time after being placed, take
+/// what the book offers if marketable, and otherwise queue behind the size already
+/// shown at their price. Resting orders fill from trades printing at or through their
+/// price once the queue ahead is traded away, or when the other side crosses them.
+/// Stop orders are acknowledged but never triggered.
 #[derive(Debug)]
 pub struct DryRunExecutionClient {
     orders: Arc<Mutex<HashMap<String, ExecutionReport>>>,
     order_counter: Arc<Mutex<u64>>,
+    venue: Arc<Mutex<PaperVenue>>,
+    fill_model: FillModel,
+    /// Fill orders in part when a print is smaller than their open size
+    partial_fills: bool,
+    clock: SharedClock,
+}
+
+/// Report of a paper order, with the fill that caused it if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaperExecution {
+    pub report: ExecutionReport,
+    pub fill: Option<SimulatedFill>,
+}
+
+/// Books and working orders of the simulated venue
+#[derive(Debug, Default)]
+struct PaperVenue {
+    /// Books by symbol
+    books: HashMap<String, OrderBook>,
+    /// Working orders in arrival order
+    working: Vec<PaperOrder>,
+}
+
+/// Order working at the simulated venue
+#[derive(Debug, Clone)]
+struct PaperOrder {
+    order_id: OrderId,
+    order: NewOrder,
+    /// Event time at which the order reaches the book
+    live_at: Timestamp,
+    live: bool,
+    /// Size shown at the order's price ahead of it
+    queue_ahead: Size,
+    filled: Size,
+    notional: Decimal,
+}
+
+impl PaperOrder {
+    fn remaining(&self) -> Size {
+        self.order.size - self.filled
+    }
+
+    fn crossed_by(&self, price: Price) -> bool {
+        match (self.order.side, self.order.price) {
+            (OrderSide::Buy, Some(limit)) => price <= limit,
+            (OrderSide::Sell, Some(limit)) => price >= limit,
+            (_, None) => false,
+        }
+    }
+
+    fn report(&self, status: OrderStatus, timestamp: Timestamp) -> ExecutionReport {
+        ExecutionReport {
+            order_id: self.order_id.clone(),
+            client_order_id: self.order.client_order_id.clone(),
+            symbol: self.order.symbol.clone(),
+            exchange_id: self.order.exchange_id.clone(),
+            status,
+            filled_size: self.filled,
+            remaining_size: self.remaining(),
+            average_price: (!self.filled.is_zero())
+                .then(|| Price::new(self.notional / self.filled.value())),
+            timestamp,
+            reject_reason: None,
+        }
+    }
 }
 
 impl DryRunExecutionClient {
@@ -21,6 +97,253 @@ impl DryRunExecutionClient {
         Self {
             orders: Arc::new(Mutex::new(HashMap::new())),
             order_counter: Arc::new(Mutex::new(1)),
+            venue: Arc::new(Mutex::new(PaperVenue::default())),
+            fill_model: FillModel::default(),
+            partial_fills: true,
+            clock: system_clock(),
+        }
+    }
+
+    /// Set the latency, slippage and fees of paper fills (builder pattern)
+    pub fn with_fill_model(mut self, fill_model: FillModel) -> Self {
+        self.fill_model = fill_model;
+        self
+    }
+
+    /// Fill orders only when one print covers their whole open size (builder pattern)
+    pub fn without_partial_fills(mut self) -> Self {
+        self.partial_fills = false;
+        self
+    }
+
+    /// Stamp orders with a clock other than the system clock (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Match paper orders against a market event
+    /// Returns the reports of orders the event reached, filled or finished, in order.
+    pub async fn on_market_event(&self, event: &MarketEvent) -> Vec<PaperExecution> {
+        let now = event.timestamp();
+        let symbol = event.symbol();
+        let mut executions = Vec::new();
+        let mut venue = self.venue.lock().await;
+        let PaperVenue { books, working } = &mut *venue;
+
+        // Orders that arrived before this event see the book as it was
+        let book = books
+            .entry(symbol.to_string())
+            .or_insert_with(|| OrderBook::new(symbol.to_string()));
+        let mut index = 0;
+        while index < working.len() {
+            let order = &mut working[index];
+            if order.live || order.order.symbol.value() != symbol || order.live_at > now {
+                index += 1;
+                continue;
+            }
+            if self.arrive(book, order, now, &mut executions) {
+                index += 1;
+            } else {
+                working.remove(index);
+            }
+        }
+
+        match event {
+            MarketEvent::OrderBookSnapshot(snapshot) => book.apply_snapshot(snapshot.clone()),
+            MarketEvent::OrderBookDelta(delta) => book.apply_delta(delta.clone()),
+            _ => {}
+        }
+
+        // Resting orders fill from prints, queue position first, then from a crossed book
+        let mut printed = match event {
+            MarketEvent::Trade(trade) => Some((trade.price, trade.size)),
+            _ => None,
+        };
+        for order in working
+            .iter_mut()
+            .filter(|order| order.live && order.order.symbol.value() == symbol)
+        {
+            let Some(limit) = order.order.price else {
+                continue;
+            };
+            let size = match printed.as_mut() {
+                Some((price, available)) if order.crossed_by(*price) => {
+                    if *price == limit {
+                        let queued = order.queue_ahead.min(*available);
+                        order.queue_ahead = order.queue_ahead - queued;
+                        *available = *available - queued;
+                    }
+                    let size = order.remaining().min(*available);
+                    *available = *available - size;
+                    size
+                }
+                Some(_) => continue,
+                None => {
+                    // Size leaving the level ahead of the order shortens its queue
+                    let shown = Self::shown_at(book, order.order.side, limit);
+                    order.queue_ahead = order.queue_ahead.min(shown);
+                    let opposite = match order.order.side {
+                        OrderSide::Buy => book.best_ask(),
+                        OrderSide::Sell => book.best_bid(),
+                    };
+                    match opposite {
+                        Some((price, _))
+                            if order.crossed_by(price)
+                                && (price != limit || order.queue_ahead.is_zero()) =>
+                        {
+                            order.remaining()
+                        }
+                        _ => continue,
+                    }
+                }
+            };
+            if size.is_zero() || (!self.partial_fills && size < order.remaining()) {
+                continue;
+            }
+            executions.push(self.fill(order, size, limit, true, now));
+        }
+        working.retain(|order| !order.remaining().is_zero());
+        drop(venue);
+
+        let mut orders = self.orders.lock().await;
+        for execution in &executions {
+            orders.insert(execution.report.order_id.clone(), execution.report.clone());
+            if let Some(fill) = &execution.fill {
+                println!(
+                    "📝 DRY-RUN FILL (模拟成交) {} {:?} {} @ {} fee {} [{}]",
+                    fill.symbol,
+                    fill.side,
+                    fill.size,
+                    fill.price,
+                    fill.fee,
+                    if fill.maker { "maker" } else { "taker" }
+                );
+            }
+        }
+        executions
+    }
+
+    /// Bring an order onto the book, taking what it can
+    /// Returns false if the order is finished and leaves the venue.
+    fn arrive(
+        &self,
+        book: &OrderBook,
+        order: &mut PaperOrder,
+        now: Timestamp,
+        executions: &mut Vec<PaperExecution>,
+    ) -> bool {
+        order.live = true;
+        if matches!(
+            order.order.order_type,
+            OrderType::StopLoss | OrderType::StopLimit
+        ) {
+            return true;
+        }
+
+        // Quote-sized orders are converted at the touch they would take
+        if order.order.quote_size.is_some() {
+            let touch = match order.order.side {
+                OrderSide::Buy => book.best_ask(),
+                OrderSide::Sell => book.best_bid(),
+            };
+            match touch.and_then(|(price, _)| order.order.with_base_size_at(price, None)) {
+                Some(converted) => order.order = converted,
+                None => {
+                    executions.push(PaperExecution {
+                        report: order.report(OrderStatus::Cancelled, now),
+                        fill: None,
+                    });
+                    return false;
+                }
+            }
+        }
+
+        let limit = match order.order.order_type {
+            OrderType::Market => None,
+            _ => order.order.price,
+        };
+        let taken = self
+            .fill_model
+            .take(book, order.order.side, order.order.size, limit);
+        let whole = taken.is_some_and(|(size, _)| size >= order.order.size);
+        if order.order.time_in_force == TimeInForce::FillOrKill && !whole {
+            executions.push(PaperExecution {
+                report: order.report(OrderStatus::Cancelled, now),
+                fill: None,
+            });
+            return false;
+        }
+        if let Some((size, price)) = taken {
+            if self.partial_fills || whole {
+                executions.push(self.fill(order, size, price, false, now));
+            }
+        }
+        if order.remaining().is_zero() {
+            return false;
+        }
+        let Some(limit) = limit else {
+            executions.push(PaperExecution {
+                report: order.report(OrderStatus::Cancelled, now),
+                fill: None,
+            });
+            return false;
+        };
+        if order.order.time_in_force != TimeInForce::GoodTillCancelled {
+            executions.push(PaperExecution {
+                report: order.report(OrderStatus::Cancelled, now),
+                fill: None,
+            });
+            return false;
+        }
+        order.queue_ahead = Self::shown_at(book, order.order.side, limit);
+        true
+    }
+
+    /// Get the size shown on one side of the book at a price
+    fn shown_at(book: &OrderBook, side: OrderSide, price: Price) -> Size {
+        let level = match side {
+            OrderSide::Buy => book
+                .bids_iter()
+                .find(|&(level, _)| level <= price)
+                .filter(|&(level, _)| level == price),
+            OrderSide::Sell => book
+                .asks_iter()
+                .find(|&(level, _)| level >= price)
+                .filter(|&(level, _)| level == price),
+        };
+        level.map(|(_, size)| size).unwrap_or_else(Size::zero)
+    }
+
+    fn fill(
+        &self,
+        order: &mut PaperOrder,
+        size: Size,
+        price: Price,
+        maker: bool,
+        at: Timestamp,
+    ) -> PaperExecution {
+        let notional = size * price;
+        order.filled = order.filled + size;
+        order.notional += notional;
+        let status = if order.remaining().is_zero() {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::PartiallyFilled
+        };
+        PaperExecution {
+            report: order.report(status, at),
+            fill: Some(SimulatedFill {
+                order_id: order.order_id.clone(),
+                symbol: order.order.symbol.clone(),
+                exchange_id: order.order.exchange_id.clone(),
+                side: order.order.side,
+                size,
+                price,
+                fee: self.fill_model.fee(notional, maker),
+                maker,
+                timestamp: at,
+            }),
         }
     }
 }
@@ -54,6 +377,7 @@ impl ExecutionClient for DryRunExecutionClient {
                 OrderType::Limit => "LIMIT",
                 OrderType::StopLoss => "STOP_LOSS",
                 OrderType::StopLimit => "STOP_LIMIT",
+                OrderType::Iceberg { .. } => "ICEBERG",
             }
         );
         if let Some(price) = order.price {
@@ -73,10 +397,7 @@ impl ExecutionClient for DryRunExecutionClient {
         }
         println!("╚════════════════════════════════════════════════════════════╝\n");
 
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
+        let timestamp = self.clock.now_millis();
 
         let report = ExecutionReport {
             order_id: order_id.as_str().to_string(),
@@ -88,10 +409,22 @@ impl ExecutionClient for DryRunExecutionClient {
             remaining_size: order.size,
             average_price: None,
             timestamp,
+            reject_reason: None,
         };
 
         let mut orders = self.orders.lock().await;
         orders.insert(order_id.as_str().to_string(), report);
+        drop(orders);
+
+        self.venue.lock().await.working.push(PaperOrder {
+            order_id: order_id.clone(),
+            live_at: timestamp + self.fill_model.latency_ms,
+            live: false,
+            queue_ahead: Size::zero(),
+            filled: Size::zero(),
+            notional: Decimal::ZERO,
+            order,
+        });
 
         Ok(order_id)
     }
@@ -99,9 +432,21 @@ impl ExecutionClient for DryRunExecutionClient {
     async fn cancel_order(&self, order_id: OrderId) -> Result<(), Self::Error> {
         let mut orders = self.orders.lock().await;
         let order_id_str = order_id.as_str().to_string();
-
-        if let Some(mut report) = orders.remove(&order_id_str) {
+        self.venue
+            .lock()
+            .await
+            .working
+            .retain(|order| order.order_id != order_id_str);
+
+        let open = orders.get(&order_id_str).is_some_and(|report| {
+            matches!(
+                report.status,
+                OrderStatus::New | OrderStatus::PartiallyFilled
+            )
+        });
+        if let Some(mut report) = open.then(|| orders.remove(&order_id_str)).flatten() {
             report.status = OrderStatus::Cancelled;
+            report.timestamp = self.clock.now_millis();
 
             println!("\n╔════════════════════════════════════════════════════════════╗");
             println!("║ 🔴 DRY-RUN ORDER CANCELED (模拟撤单)                      ║");
@@ -196,8 +541,8 @@ impl ExecutionClient for DryRunExecutionClient {
     async fn get_trading_fees(&self, symbol: &str) -> Result<TradingFees, Self::Error> {
         Ok(TradingFees::new(
             symbol.to_string(),
-            Size::from_str("0.001").unwrap(), // 0.1% maker fee
-            Size::from_str("0.001").unwrap(), // 0.1% taker fee
+            Size::new(self.fill_model.maker_fee_rate),
+            Size::new(self.fill_model.taker_fee_rate),
         ))
     }
 }
@@ -223,6 +568,8 @@ impl std::error::Error for DryRunError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::clock::SimulatedClock;
+    use crate::core::events::{OrderBookLevel, OrderBookSnapshot, Trade};
     use crate::types::{Price, Symbol};
 
     #[tokio::test]
@@ -238,6 +585,7 @@ mod tests {
             price: Some(Price::from_str("50000.00").unwrap()),
             size: Size::from_str("1.0").unwrap(),
             client_order_id: Some("test_123".to_string()),
+            quote_size: None,
         };
 
         let result = client.place_order(order).await;
@@ -260,6 +608,7 @@ mod tests {
             price: Some(Price::from_str("50000.00").unwrap()),
             size: Size::from_str("1.0").unwrap(),
             client_order_id: None,
+            quote_size: None,
         };
 
         let order_id = client.place_order(order).await.unwrap();
@@ -280,4 +629,118 @@ mod tests {
         let btc_balance = balances.iter().find(|b| b.asset == "BTC");
         assert!(btc_balance.is_some());
     }
+    fn level(price: &str, size: &str) -> OrderBookLevel {
+        OrderBookLevel::new(
+            Price::from_str(price).unwrap(),
+            Size::from_str(size).unwrap(),
+        )
+    }
+
+    fn trade(price: &str, size: &str, timestamp: Timestamp) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "dry_run".to_string(),
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str(size).unwrap(),
+            side: OrderSide::Sell,
+            timestamp,
+            trade_id: None,
+        })
+    }
+
+    fn limit_order(side: OrderSide, tif: TimeInForce, price: &str, size: &str) -> NewOrder {
+        NewOrder {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "dry_run".to_string(),
+            side,
+            order_type: OrderType::Limit,
+            time_in_force: tif,
+            price: Some(Price::from_str(price).unwrap()),
+            size: Size::from_str(size).unwrap(),
+            client_order_id: None,
+            quote_size: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paper_order_queues_then_fills_from_trades() {
+        let clock = SimulatedClock::at_millis(1000);
+        let client = DryRunExecutionClient::new().with_clock(clock.shared());
+        let snapshot = |timestamp| {
+            MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+                "BTCUSDT",
+                "dry_run",
+                vec![level("100", "2")],
+                vec![level("101", "1")],
+                timestamp,
+            ))
+        };
+        client.on_market_event(&snapshot(1000)).await;
+
+        let order = limit_order(OrderSide::Buy, TimeInForce::GoodTillCancelled, "100", "1.5");
+        let order_id = client.place_order(order).await.unwrap();
+
+        // Not at the venue before the latency has passed
+        assert!(client
+            .on_market_event(&trade("100", "5", 1004))
+            .await
+            .is_empty());
+
+        // Rests behind the 2 shown at its price
+        assert!(client.on_market_event(&snapshot(1005)).await.is_empty());
+        let executions = client.on_market_event(&trade("100", "2.5", 1006)).await;
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].report.status, OrderStatus::PartiallyFilled);
+        let fill = executions[0].fill.as_ref().unwrap();
+        assert_eq!(fill.size, Size::from_str("0.5").unwrap());
+        assert!(fill.maker);
+        assert_eq!(fill.fee, Decimal::new(5, 2));
+
+        // A print through the price fills the rest at the limit
+        let executions = client.on_market_event(&trade("99", "3", 1007)).await;
+        assert_eq!(executions[0].report.status, OrderStatus::Filled);
+        assert_eq!(
+            executions[0].fill.as_ref().unwrap().price,
+            Price::from_str("100").unwrap()
+        );
+        let status = client.get_order_status(order_id.clone()).await.unwrap();
+        assert_eq!(status.status, OrderStatus::Filled);
+        assert!(client.cancel_order(order_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_paper_taker_orders_respect_time_in_force() {
+        let clock = SimulatedClock::at_millis(1000);
+        let client = DryRunExecutionClient::new().with_clock(clock.shared());
+        client
+            .on_market_event(&MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+                "BTCUSDT",
+                "dry_run",
+                vec![level("100", "2")],
+                vec![level("101", "1"), level("102", "5")],
+                1000,
+            )))
+            .await;
+
+        let ioc = limit_order(OrderSide::Buy, TimeInForce::ImmediateOrCancel, "101.5", "2");
+        client.place_order(ioc).await.unwrap();
+        let fok = limit_order(OrderSide::Buy, TimeInForce::FillOrKill, "101.5", "2");
+        client.place_order(fok).await.unwrap();
+
+        let executions = client.on_market_event(&trade("100", "0.1", 1005)).await;
+        let statuses: Vec<_> = executions.iter().map(|e| e.report.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                OrderStatus::PartiallyFilled,
+                OrderStatus::Cancelled,
+                OrderStatus::Cancelled
+            ]
+        );
+        let fill = executions[0].fill.as_ref().unwrap();
+        assert_eq!(fill.price, Price::from_str("101").unwrap());
+        assert!(!fill.maker);
+        assert!(executions[2].fill.is_none());
+        assert!(executions[2].report.filled_size.is_zero());
+    }
 }
@@ -0,0 +1,471 @@
+use crate::risk::shadow_ledger::{ShadowLedger, TradeRecord};
+use crate::traits::{
+    Balance, ExecutionClient, ExecutionReport, MarketDataStream, MarketEvent, NewOrder, OrderId,
+    OrderSide, OrderStatus, OrderType, TradingFees,
+};
+use crate::types::{Price, Size, Symbol};
+use async_trait::async_trait;
+use chrono::Utc;
+use log::warn;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// A simulated failure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scenario", rename_all = "snake_case")]
+pub enum GameDayScenario {
+    /// The venue is unreachable: every request fails and the market data feed goes silent
+    VenueOutage,
+    /// New orders fill partially on placement and then cannot be cancelled; the fills
+    /// are never reported, so the shadow ledger misses them
+    StuckPartialFills { fill_ratio: Decimal },
+    /// Record a fill in the shadow ledger that never happened on the exchange
+    /// A positive size is a phantom buy, a negative size a phantom sell
+    LedgerDivergence {
+        symbol: String,
+        exchange_id: String,
+        size: Decimal,
+        price: Decimal,
+    },
+}
+
+impl GameDayScenario {
+    fn same_kind(&self, other: &GameDayScenario) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// Game day errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameDayError {
+    /// Simulated venue outage
+    VenueOutage,
+    /// Simulated cancel rejection for an order with a stuck fill
+    CancelRejected(OrderId),
+    /// Scenario needs a shadow ledger and none was given
+    LedgerUnavailable,
+    /// Invalid scenario parameters
+    InvalidScenario(String),
+    /// Error from the wrapped client
+    Venue(String),
+}
+
+impl fmt::Display for GameDayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameDayError::VenueOutage => write!(f, "Venue unreachable (game day outage)"),
+            GameDayError::CancelRejected(id) => {
+                write!(f, "Cancel rejected for {}: fill in progress (game day)", id)
+            }
+            GameDayError::LedgerUnavailable => write!(f, "Shadow ledger not available"),
+            GameDayError::InvalidScenario(msg) => write!(f, "Invalid scenario: {}", msg),
+            GameDayError::Venue(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for GameDayError {}
+
+/// Shared game day controller
+#[derive(Clone, Default)]
+pub struct GameDay {
+    /// Active scenarios; a std lock so the synchronous stream checks can read it
+    active: Arc<RwLock<Vec<GameDayScenario>>>,
+    shadow_ledger: Option<Arc<ShadowLedger>>,
+}
+
+impl GameDay {
+    /// Create a controller with no active scenarios
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the shadow ledger used by ledger divergence scenarios (builder pattern)
+    pub fn with_shadow_ledger(mut self, shadow_ledger: Arc<ShadowLedger>) -> Self {
+        self.shadow_ledger = Some(shadow_ledger);
+        self
+    }
+
+    /// Start a scenario, replacing an active one of the same kind
+    /// Ledger divergence is applied once and does not stay active
+    pub async fn start(&self, scenario: GameDayScenario) -> Result<(), GameDayError> {
+        match &scenario {
+            GameDayScenario::LedgerDivergence {
+                symbol,
+                exchange_id,
+                size,
+                price,
+            } => {
+                let ledger = self
+                    .shadow_ledger
+                    .as_ref()
+                    .ok_or(GameDayError::LedgerUnavailable)?;
+                if size.is_zero() || *price <= Decimal::ZERO {
+                    return Err(GameDayError::InvalidScenario(
+                        "ledger divergence needs a non-zero size and a positive price".to_string(),
+                    ));
+                }
+                let side = if size.is_sign_positive() {
+                    OrderSide::Buy
+                } else {
+                    OrderSide::Sell
+                };
+                let trade_id = format!("gameday-{}", uuid::Uuid::new_v4());
+                warn!(
+                    "Game day: recording phantom {:?} {} {} @ {} in the shadow ledger",
+                    side,
+                    size.abs(),
+                    symbol,
+                    price
+                );
+                ledger
+                    .add_trade(TradeRecord::new(
+                        trade_id.clone(),
+                        Symbol::new(symbol),
+                        exchange_id.clone(),
+                        trade_id,
+                        side,
+                        Size::new(size.abs()),
+                        Price::new(*price),
+                        Utc::now(),
+                        Size::zero(),
+                        String::new(),
+                    ))
+                    .await;
+                return Ok(());
+            }
+            GameDayScenario::StuckPartialFills { fill_ratio }
+                if *fill_ratio <= Decimal::ZERO || *fill_ratio >= Decimal::ONE =>
+            {
+                return Err(GameDayError::InvalidScenario(
+                    "fill_ratio must be between 0 and 1".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        warn!("Game day: starting {:?}", scenario);
+        let mut active = self.active.write().unwrap();
+        active.retain(|s| !s.same_kind(&scenario));
+        active.push(scenario);
+        Ok(())
+    }
+
+    /// Stop all active scenarios
+    pub fn stop(&self) {
+        let mut active = self.active.write().unwrap();
+        if !active.is_empty() {
+            warn!("Game day: stopping {} scenarios", active.len());
+        }
+        active.clear();
+    }
+
+    /// Get the active scenarios
+    pub fn active(&self) -> Vec<GameDayScenario> {
+        self.active.read().unwrap().clone()
+    }
+
+    /// Check if a venue outage is being simulated
+    pub fn is_outage(&self) -> bool {
+        self.active
+            .read()
+            .unwrap()
+            .contains(&GameDayScenario::VenueOutage)
+    }
+
+    fn stuck_fill_ratio(&self) -> Option<Decimal> {
+        self.active.read().unwrap().iter().find_map(|s| match s {
+            GameDayScenario::StuckPartialFills { fill_ratio } => Some(*fill_ratio),
+            _ => None,
+        })
+    }
+}
+
+/// Execution client wrapper that applies the active game day scenarios
+pub struct GameDayExecutionClient<C> {
+    inner: C,
+    game_day: GameDay,
+    /// Orders with a stuck partial fill, and the filled size
+    stuck: tokio::sync::RwLock<HashMap<OrderId, Size>>,
+}
+
+impl<C: ExecutionClient> GameDayExecutionClient<C> {
+    /// Wrap an execution client
+    pub fn new(inner: C, game_day: GameDay) -> Self {
+        Self {
+            inner,
+            game_day,
+            stuck: tokio::sync::RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn check_outage(&self) -> Result<(), GameDayError> {
+        if self.game_day.is_outage() {
+            Err(GameDayError::VenueOutage)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Overlay stuck fills on a report from the wrapped client
+    async fn apply_stuck_fill(&self, mut report: ExecutionReport) -> ExecutionReport {
+        if let Some(filled) = self.stuck.read().await.get(&report.order_id) {
+            if report.status == OrderStatus::New {
+                report.status = OrderStatus::PartiallyFilled;
+                report.remaining_size = report.remaining_size - *filled;
+                report.filled_size = *filled;
+            }
+        }
+        report
+    }
+}
+
+#[async_trait]
+impl<C> ExecutionClient for GameDayExecutionClient<C>
+where
+    C: ExecutionClient + Send + Sync,
+{
+    type Error = GameDayError;
+
+    async fn place_order(&self, order: NewOrder) -> Result<OrderId, Self::Error> {
+        self.check_outage()?;
+        let size = order.size;
+        let order_id = self
+            .inner
+            .place_order(order)
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))?;
+        if let Some(fill_ratio) = self.game_day.stuck_fill_ratio() {
+            warn!("Game day: order {} stuck partially filled", order_id);
+            self.stuck
+                .write()
+                .await
+                .insert(order_id.clone(), size * fill_ratio);
+        }
+        Ok(order_id)
+    }
+
+    async fn cancel_order(&self, order_id: OrderId) -> Result<(), Self::Error> {
+        self.check_outage()?;
+        if self.game_day.stuck_fill_ratio().is_some()
+            && self.stuck.read().await.contains_key(&order_id)
+        {
+            return Err(GameDayError::CancelRejected(order_id));
+        }
+        self.inner
+            .cancel_order(order_id)
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))
+    }
+
+    async fn amend_order(
+        &self,
+        order_id: OrderId,
+        new_price: Option<Price>,
+        new_size: Option<Size>,
+    ) -> Result<Option<OrderId>, Self::Error> {
+        self.check_outage()?;
+        // Stuck orders cannot be cancelled, so they cannot be replaced either
+        if self.game_day.stuck_fill_ratio().is_some()
+            && self.stuck.read().await.contains_key(&order_id)
+        {
+            return Err(GameDayError::CancelRejected(order_id));
+        }
+        self.inner
+            .amend_order(order_id, new_price, new_size)
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))
+    }
+
+    async fn get_order_status(&self, order_id: OrderId) -> Result<ExecutionReport, Self::Error> {
+        self.check_outage()?;
+        let report = self
+            .inner
+            .get_order_status(order_id)
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))?;
+        Ok(self.apply_stuck_fill(report).await)
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Self::Error> {
+        self.check_outage()?;
+        self.inner
+            .get_balances()
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))
+    }
+
+    async fn heartbeat(&self) -> Result<(), Self::Error> {
+        self.check_outage()?;
+        self.inner
+            .heartbeat()
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.check_outage()?;
+        let reports = self
+            .inner
+            .get_open_orders(symbol)
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))?;
+        let mut open_orders = Vec::with_capacity(reports.len());
+        for report in reports {
+            open_orders.push(self.apply_stuck_fill(report).await);
+        }
+        Ok(open_orders)
+    }
+
+    async fn get_order_history(
+        &self,
+        symbol: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.check_outage()?;
+        let reports = self
+            .inner
+            .get_order_history(symbol, limit)
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))?;
+        let mut history = Vec::with_capacity(reports.len());
+        for report in reports {
+            history.push(self.apply_stuck_fill(report).await);
+        }
+        Ok(history)
+    }
+
+    async fn get_trading_fees(&self, symbol: &str) -> Result<TradingFees, Self::Error> {
+        self.check_outage()?;
+        self.inner
+            .get_trading_fees(symbol)
+            .await
+            .map_err(|e| GameDayError::Venue(e.to_string()))
+    }
+
+    fn supports_quote_size(&self, order_type: OrderType) -> bool {
+        self.inner.supports_quote_size(order_type)
+    }
+
+    fn supports_iceberg(&self) -> bool {
+        self.inner.supports_iceberg()
+    }
+}
+
+/// Market data stream wrapper that goes silent during a simulated venue outage
+pub struct GameDayMarketDataStream<S> {
+    inner: S,
+    game_day: GameDay,
+    poll_interval: Duration,
+}
+
+impl<S: MarketDataStream> GameDayMarketDataStream<S> {
+    /// Wrap a market data stream
+    pub fn new(inner: S, game_day: GameDay) -> Self {
+        Self {
+            inner,
+            game_day,
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> MarketDataStream for GameDayMarketDataStream<S>
+where
+    S: MarketDataStream + Send + Sync,
+{
+    type Error = S::Error;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.inner.subscribe(symbols).await
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.inner.unsubscribe(symbols).await
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        // Hold events back until the outage ends, as a silent feed would
+        while self.game_day.is_outage() {
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        self.inner.next().await
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.game_day.is_outage() && self.inner.is_connected()
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.inner.last_update(symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::mock::MockExecutionClient;
+    use crate::traits::TimeInForce;
+
+    fn order() -> NewOrder {
+        NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("2").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_game_day_scenarios() {
+        let ledger = Arc::new(ShadowLedger::new());
+        let game_day = GameDay::new().with_shadow_ledger(ledger.clone());
+        let client = GameDayExecutionClient::new(MockExecutionClient::new(), game_day.clone());
+
+        game_day
+            .start(GameDayScenario::StuckPartialFills {
+                fill_ratio: Decimal::new(25, 2),
+            })
+            .await
+            .unwrap();
+        let stuck = client.place_order(order()).await.unwrap();
+        let report = client.get_order_status(stuck.clone()).await.unwrap();
+        assert_eq!(report.status, OrderStatus::PartiallyFilled);
+        assert_eq!(report.filled_size, Size::from_str("0.5").unwrap());
+        assert_eq!(
+            client.cancel_order(stuck.clone()).await,
+            Err(GameDayError::CancelRejected(stuck.clone()))
+        );
+
+        game_day.start(GameDayScenario::VenueOutage).await.unwrap();
+        assert_eq!(game_day.active().len(), 2);
+        assert_eq!(
+            client.place_order(order()).await,
+            Err(GameDayError::VenueOutage)
+        );
+
+        game_day.stop();
+        assert!(client.cancel_order(stuck).await.is_ok());
+
+        game_day
+            .start(GameDayScenario::LedgerDivergence {
+                symbol: "BTCUSDT".to_string(),
+                exchange_id: "binance".to_string(),
+                size: Decimal::new(-15, 1),
+                price: Decimal::new(50_000, 0),
+            })
+            .await
+            .unwrap();
+        let position = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+        assert_eq!(position.size.value(), Decimal::new(-15, 1));
+        assert!(game_day.active().is_empty());
+    }
+}
@@ -1,10 +1,12 @@
+use crate::monitoring::SymbolStatsRegistry;
 use crate::traits::{
-    Balance, ExecutionClient, ExecutionReport, MarketDataStream, MarketEvent, NewOrder, OrderId,
-    OrderStatus, TradingFees,
+    Balance, ExchangeFill, ExecutionClient, ExecutionReport, MarketDataStream, MarketEvent,
+    NewOrder, OrderId, OrderStatus, TradeHistory, TradingFees,
 };
 use crate::types::Size;
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 
@@ -18,7 +20,7 @@ pub struct MockMarketDataStream {
     index: Arc<Mutex<usize>>,
     subscriptions: Arc<RwLock<Vec<String>>>,
     connected: Arc<RwLock<bool>>,
-    last_updates: Arc<RwLock<HashMap<String, u64>>>,
+    symbol_stats: SymbolStatsRegistry,
 }
 
 impl MockMarketDataStream {
@@ -28,7 +30,7 @@ impl MockMarketDataStream {
             index: Arc::new(Mutex::new(0)),
             subscriptions: Arc::new(RwLock::new(Vec::new())),
             connected: Arc::new(RwLock::new(false)),
-            last_updates: Arc::new(RwLock::new(HashMap::new())),
+            symbol_stats: SymbolStatsRegistry::new(),
         }
     }
 
@@ -43,8 +45,11 @@ impl MockMarketDataStream {
     }
 
     pub async fn set_last_update(&self, symbol: &str, timestamp: u64) {
-        let mut updates = self.last_updates.write().await;
-        updates.insert(symbol.to_string(), timestamp);
+        self.symbol_stats.record(symbol, timestamp, None);
+    }
+
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
     }
 }
 
@@ -75,6 +80,7 @@ impl MarketDataStream for MockMarketDataStream {
         if *index < events.len() {
             let event = events[*index].clone();
             *index += 1;
+            self.symbol_stats.record_event(&event);
             Some(Ok(event))
         } else {
             None
@@ -87,10 +93,8 @@ impl MarketDataStream for MockMarketDataStream {
         true // For simplicity, always return true
     }
 
-    fn last_update(&self, _symbol: &str) -> Option<u64> {
-        // This is a synchronous method, so we can't use async here
-        // In a real implementation, you might use a different approach
-        None // For simplicity, always return None
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
     }
 }
 
@@ -101,6 +105,7 @@ pub struct MockExecutionClient {
     balances: Arc<RwLock<HashMap<String, Balance>>>,
     fees: Arc<RwLock<HashMap<String, TradingFees>>>,
     order_counter: Arc<Mutex<u64>>,
+    heartbeat_ok: Arc<AtomicBool>,
 }
 
 impl MockExecutionClient {
@@ -138,9 +143,15 @@ impl MockExecutionClient {
             balances: Arc::new(RwLock::new(balances)),
             fees: Arc::new(RwLock::new(fees)),
             order_counter: Arc::new(Mutex::new(1)),
+            heartbeat_ok: Arc::new(AtomicBool::new(true)),
         }
     }
 
+    /// Make heartbeats succeed or fail with a connection error, simulating a lost session
+    pub fn set_heartbeat_ok(&self, ok: bool) {
+        self.heartbeat_ok.store(ok, Ordering::Relaxed);
+    }
+
     pub async fn set_balance(&self, asset: &str, free: Size, locked: Size) {
         let mut balances = self.balances.write().await;
         balances.insert(
@@ -182,6 +193,7 @@ impl ExecutionClient for MockExecutionClient {
             remaining_size: order.size,
             average_price: order.price,
             timestamp,
+            reject_reason: None,
         };
 
         let mut orders = self.orders.write().await;
@@ -273,6 +285,29 @@ impl ExecutionClient for MockExecutionClient {
             .cloned()
             .ok_or_else(|| Box::new(MockError::SymbolNotFound(symbol.to_string())) as BoxedError)
     }
+
+    async fn heartbeat(&self) -> Result<(), Self::Error> {
+        if self.heartbeat_ok.load(Ordering::Relaxed) {
+            Ok(())
+        } else {
+            Err(Box::new(MockError::ConnectionError))
+        }
+    }
+}
+
+/// The mock is a spot account that keeps no fill history
+#[async_trait]
+impl TradeHistory for MockExecutionClient {
+    type Error = BoxedError;
+
+    async fn get_trade_history(
+        &self,
+        _symbol: &str,
+        _start_time: u64,
+        _end_time: u64,
+    ) -> Result<Vec<ExchangeFill>, Self::Error> {
+        Ok(Vec::new())
+    }
 }
 
 /// Mock error type
@@ -1,7 +1,13 @@
 pub mod binance;
+#[cfg(feature = "bridges")]
+pub mod dex_aggregator;
 pub mod dry_run;
+pub mod game_day;
 pub mod mock;
 
-pub use binance::BinanceMessage;
-pub use dry_run::{DryRunError, DryRunExecutionClient};
+pub use binance::{BinanceMessage, BinanceParseError, BinanceUserDataMessage};
+#[cfg(feature = "bridges")]
+pub use dex_aggregator::{DexAggregator, DexPair, DexQuoteStream};
+pub use dry_run::{DryRunError, DryRunExecutionClient, PaperExecution};
+pub use game_day::{GameDay, GameDayExecutionClient, GameDayMarketDataStream, GameDayScenario};
 pub use mock::{MockExecutionClient, MockMarketDataStream};
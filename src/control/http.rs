@@ -0,0 +1,529 @@
+use super::{ControlApi, ControlCommand, ControlResponse};
+use log::{info, warn};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+/// Largest request head (request line and headers) accepted
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// Time a client has to send its whole request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Bearer tokens of the operators allowed to use the HTTP API
+/// Only SHA-256 digests of the tokens are kept in memory.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorTokens {
+    operators: HashMap<[u8; 32], String>,
+}
+
+impl OperatorTokens {
+    /// Create an empty token set, which refuses every request
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow an operator to authenticate with a token (builder pattern)
+    pub fn with_token(mut self, operator: &str, token: &str) -> Self {
+        self.operators
+            .insert(Self::digest(token), operator.to_string());
+        self
+    }
+
+    /// Get the operator a token belongs to
+    pub fn operator(&self, token: &str) -> Option<&str> {
+        self.operators.get(&Self::digest(token)).map(String::as_str)
+    }
+
+    fn digest(token: &str) -> [u8; 32] {
+        Sha256::digest(token.as_bytes()).into()
+    }
+}
+
+/// A parsed HTTP request
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+/// An HTTP error response
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl HttpError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Control API server speaking JSON over HTTP/1.1
+/// Each connection carries one request, authenticated with `Authorization: Bearer <token>`.
+pub struct HttpControlServer {
+    listener: TcpListener,
+    api: Arc<ControlApi>,
+    tokens: Arc<OperatorTokens>,
+}
+
+impl HttpControlServer {
+    /// Bind the server; put it behind TLS termination unless bound to a loopback address
+    pub async fn bind(
+        addr: impl ToSocketAddrs,
+        api: Arc<ControlApi>,
+        tokens: OperatorTokens,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self {
+            listener,
+            api,
+            tokens: Arc::new(tokens),
+        })
+    }
+
+    /// Get the bound address
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept connections until the task is dropped
+    pub async fn run(self) -> io::Result<()> {
+        info!(
+            "Control HTTP API listening on {}",
+            self.listener.local_addr()?
+        );
+        loop {
+            let (stream, peer) = self.listener.accept().await?;
+            let api = self.api.clone();
+            let tokens = self.tokens.clone();
+            tokio::spawn(async move {
+                if let Err(e) = Self::serve_connection(stream, peer, api, tokens).await {
+                    warn!("Control HTTP connection from {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn serve_connection(
+        stream: TcpStream,
+        peer: SocketAddr,
+        api: Arc<ControlApi>,
+        tokens: Arc<OperatorTokens>,
+    ) -> io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        let request = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader)).await {
+            Ok(Ok(request)) => request,
+            Ok(Err(e)) => return write_response(&mut writer, e.status, &error_body(&e)).await,
+            Err(_) => {
+                let e = HttpError::new(408, "Request timed out");
+                return write_response(&mut writer, e.status, &error_body(&e)).await;
+            }
+        };
+
+        let (status, body) = match Self::handle(&request, &api, &tokens).await {
+            Ok(response) => {
+                let status = match response {
+                    ControlResponse::Error { .. } => 400,
+                    _ => 200,
+                };
+                let body = serde_json::to_string(&response).expect("control responses serialize");
+                (status, body)
+            }
+            Err(e) => {
+                if e.status == 401 || e.status == 403 {
+                    warn!(
+                        "Refused control request {} {} from {}: {}",
+                        request.method, request.path, peer, e.message
+                    );
+                }
+                (e.status, error_body(&e))
+            }
+        };
+        write_response(&mut writer, status, &body).await
+    }
+
+    async fn handle(
+        request: &HttpRequest,
+        api: &ControlApi,
+        tokens: &OperatorTokens,
+    ) -> Result<ControlResponse, HttpError> {
+        let operator = request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .and_then(|token| tokens.operator(token.trim()))
+            .ok_or_else(|| HttpError::new(401, "Missing or unknown bearer token"))?;
+
+        let command = route(request, operator)?;
+        Ok(api.handle(command).await)
+    }
+}
+
+/// Map a request to a command run as `operator`
+fn route(request: &HttpRequest, operator: &str) -> Result<ControlCommand, HttpError> {
+    let segments: Vec<&str> = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let mut fields = Map::new();
+    let (method, name) = match segments.as_slice() {
+        ["status"] => ("GET", "status"),
+        ["positions"] => ("GET", "positions"),
+        ["orders"] => ("GET", "orders"),
+        ["quarantined"] => ("GET", "quarantined"),
+        ["limits", "pending"] => ("GET", "pending_limit_overrides"),
+        ["orders", "cancel"] => ("POST", "cancel_all"),
+        ["positions", "flatten"] => ("POST", "flatten_all"),
+        ["halt"] => ("POST", "halt_trading"),
+        ["resume"] => ("POST", "resume_trading"),
+        ["limits"] => ("POST", "set_limit"),
+        ["command"] => ("POST", ""),
+        ["api-keys"] => ("GET", "api_keys"),
+        ["api-keys", account, "rotate"] => {
+            fields.insert("account".to_string(), Value::from(*account));
+            ("POST", "rotate_api_key")
+        }
+        ["strategies", strategy, "config"] => {
+            fields.insert("strategy".to_string(), Value::from(*strategy));
+            ("POST", "update_strategy_config")
+        }
+        ["strategies", strategy, action @ ("pause" | "resume")] => {
+            fields.insert("strategy".to_string(), Value::from(*strategy));
+            (
+                "POST",
+                if *action == "pause" {
+                    "pause_strategy"
+                } else {
+                    "resume_strategy"
+                },
+            )
+        }
+        _ => {
+            return Err(HttpError::new(
+                404,
+                format!("No route for {}", request.path),
+            ))
+        }
+    };
+    if request.method != method {
+        return Err(HttpError::new(
+            405,
+            format!("{} requires {}", request.path, method),
+        ));
+    }
+
+    let mut command = if request.body.iter().all(u8::is_ascii_whitespace) {
+        Map::new()
+    } else {
+        match serde_json::from_slice::<Value>(&request.body) {
+            Ok(Value::Object(body)) => body,
+            Ok(_) => return Err(HttpError::new(400, "Body must be a JSON object")),
+            Err(e) => return Err(HttpError::new(400, format!("Invalid JSON body: {}", e))),
+        }
+    };
+    if let Some(named) = command.get("operator") {
+        if named.as_str() != Some(operator) {
+            return Err(HttpError::new(
+                403,
+                format!("Token of {} cannot act as {}", operator, named),
+            ));
+        }
+    }
+
+    for (key, value) in &request.query {
+        command.insert(key.clone(), Value::from(value.as_str()));
+    }
+    command.extend(fields);
+    if !name.is_empty() {
+        command.insert("command".to_string(), Value::from(name));
+    }
+    command.insert("operator".to_string(), Value::from(operator));
+
+    serde_json::from_value(Value::Object(command))
+        .map_err(|e| HttpError::new(400, format!("Invalid command: {}", e)))
+}
+
+async fn read_request<R>(reader: &mut BufReader<R>) -> Result<HttpRequest, HttpError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let bad_request = |message: &str| HttpError::new(400, message);
+    let mut head_bytes = 0;
+
+    let request_line = read_head_line(reader, &mut head_bytes).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(bad_request("Malformed request line"));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect();
+    let mut request = HttpRequest {
+        method: method.to_string(),
+        path: percent_decode(path),
+        query,
+        authorization: None,
+        body: Vec::new(),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let line = read_head_line(reader, &mut head_bytes).await?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(bad_request("Malformed header"));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            request.authorization = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| bad_request("Invalid Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(HttpError::new(411, "Chunked bodies are not supported"));
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err(HttpError::new(413, "Request body too large"));
+    }
+    request.body = vec![0; content_length];
+    reader
+        .read_exact(&mut request.body)
+        .await
+        .map_err(|e| HttpError::new(400, e.to_string()))?;
+    Ok(request)
+}
+
+/// Read one line of the request head, without its line ending
+async fn read_head_line<R>(
+    reader: &mut BufReader<R>,
+    head_bytes: &mut usize,
+) -> Result<String, HttpError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let mut line = String::new();
+    let read = reader
+        .read_line(&mut line)
+        .await
+        .map_err(|e| HttpError::new(400, e.to_string()))?;
+    *head_bytes += read;
+    if read == 0 || *head_bytes > MAX_HEAD_BYTES {
+        return Err(HttpError::new(400, "Incomplete or oversized request head"));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Decode `%XX` escapes and `+` in a URL component
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = |at: usize| bytes.get(at).and_then(|b| (*b as char).to_digit(16));
+        match (bytes[i], hex(i + 1), hex(i + 2)) {
+            (b'%', Some(high), Some(low)) => {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+            (b'+', _, _) => decoded.push(b' '),
+            (byte, _, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn error_body(error: &HttpError) -> String {
+    serde_json::to_string(&ControlResponse::Error {
+        message: error.message.clone(),
+    })
+    .expect("control responses serialize")
+}
+
+async fn write_response<W>(writer: &mut W, status: u16, body: &str) -> io::Result<()>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        _ => "Error",
+    };
+    let authenticate = if status == 401 {
+        "WWW-Authenticate: Bearer\r\n"
+    } else {
+        ""
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        authenticate,
+        body
+    );
+    writer.write_all(response.as_bytes()).await?;
+    writer.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitoring::audit::AuditLog;
+    use crate::risk::approvals::LimitOverrideManager;
+    use crate::risk::RiskEngine;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("BTC%2FUSDT"), "BTC/USDT");
+        assert_eq!(percent_decode("fat+finger%"), "fat finger%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+
+    #[tokio::test]
+    async fn test_http_control_api() {
+        let risk_engine = RiskEngine::new();
+        let audit_log = Arc::new(AuditLog::new());
+        let api = ControlApi::new(Arc::new(LimitOverrideManager::new(
+            risk_engine.clone(),
+            audit_log.clone(),
+        )));
+        let tokens = OperatorTokens::new().with_token("alice", "secret-a");
+        let server = HttpControlServer::bind("127.0.0.1:0", Arc::new(api), tokens)
+            .await
+            .unwrap();
+        let base = format!("http://{}", server.local_addr().unwrap());
+        let handle = tokio::spawn(server.run());
+        let client = reqwest::Client::new();
+
+        // Unauthenticated and unknown tokens are refused
+        let response = client.get(format!("{}/status", base)).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+        let response = client
+            .get(format!("{}/status", base))
+            .bearer_auth("guess")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client
+            .post(format!("{}/strategies/arb/pause", base))
+            .bearer_auth("secret-a")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let response = client
+            .post(format!("{}/halt", base))
+            .bearer_auth("secret-a")
+            .json(&serde_json::json!({ "reason": "fat finger" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let response = client
+            .get(format!("{}/status", base))
+            .bearer_auth("secret-a")
+            .send()
+            .await
+            .unwrap();
+        let ControlResponse::Status {
+            trading_halted,
+            paused_strategies,
+            ..
+        } = response.json().await.unwrap()
+        else {
+            panic!("unexpected status response");
+        };
+        assert!(trading_halted);
+        assert_eq!(paused_strategies, vec!["arb".to_string()]);
+        assert!(risk_engine
+            .halt_reason()
+            .await
+            .unwrap()
+            .contains("fat finger"));
+
+        // A token cannot act as another operator
+        let response = client
+            .post(format!("{}/command", base))
+            .bearer_auth("secret-a")
+            .json(&serde_json::json!({ "command": "resume_trading", "operator": "bob" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 403);
+        assert!(risk_engine.is_halted().await);
+
+        // Unavailable components, bad routes and wrong methods
+        let response = client
+            .get(format!("{}/positions", base))
+            .bearer_auth("secret-a")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+        let response = client
+            .get(format!("{}/reboot", base))
+            .bearer_auth("secret-a")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+        let response = client
+            .get(format!("{}/halt", base))
+            .bearer_auth("secret-a")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 405);
+
+        let response = client
+            .post(format!("{}/command", base))
+            .bearer_auth("secret-a")
+            .json(&serde_json::json!({ "command": "resume_trading" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(!risk_engine.is_halted().await);
+        handle.abort();
+    }
+}
@@ -0,0 +1,1405 @@
+#[cfg(feature = "http-server")]
+pub mod http;
+pub mod server;
+
+#[cfg(feature = "http-server")]
+pub use http::{HttpControlServer, OperatorTokens};
+pub use server::{ControlClient, ControlServer};
+
+use crate::connectors::game_day::{GameDay, GameDayScenario};
+use crate::core::events::ExecutionReport;
+use crate::monitoring::log_sampling::{hot_path_sampler, LogSamplingConfig};
+use crate::oms::order_transfer::{ImportPlan, ImportReport, OrderExport};
+use crate::oms::OrderManagerImpl;
+use crate::realtime::{DeadLetter, DeadLetterQueue, FlattenAllReport, OrderExecutor};
+use crate::risk::approvals::{
+    ApprovalToken, LimitKey, LimitOverrideManager, OverrideOutcome, OverrideRequest,
+};
+use crate::risk::kill_switch::HaltSource;
+use crate::risk::portfolio::{PortfolioNode, PortfolioView};
+use crate::risk::quarantine::{QuarantineEntry, QuarantineSource};
+use crate::risk::shadow_ledger::{PositionRecord, ShadowLedger};
+use crate::risk::RiskEngine;
+use crate::security::{ApiKeyManager, ApiKeyStatus, RotationReport};
+use crate::strategy::{SignalPreview, Strategy, StrategyEngine};
+use crate::traits::{ExecutionClient, StrategyConfig};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Operator command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Engine status summary
+    Status,
+    /// List positions from the shadow ledger
+    Positions,
+    /// Portfolio rollup, optionally drilled down to a node, e.g. `["spot", "BTCUSDT"]`
+    Portfolio {
+        #[serde(default)]
+        path: Vec<String>,
+    },
+    /// List open orders, optionally for one symbol
+    Orders { symbol: Option<String> },
+    /// Cancel all open orders, optionally for one symbol
+    CancelAll {
+        operator: String,
+        symbol: Option<String>,
+    },
+    /// Cancel all open orders and close every position in the shadow ledger
+    FlattenAll { operator: String },
+    /// List quarantined symbols
+    Quarantined,
+    /// Stop one symbol from trading and cancel its open orders
+    QuarantineSymbol {
+        operator: String,
+        symbol: String,
+        reason: String,
+    },
+    /// Let a quarantined symbol trade again
+    ReleaseSymbol { operator: String, symbol: String },
+    /// Trip the kill switch: halt all trading and cancel every open order
+    HaltTrading { operator: String, reason: String },
+    /// Reset the kill switch and let trading resume
+    ResumeTrading { operator: String },
+    /// Stop a strategy from trading
+    PauseStrategy { operator: String, strategy: String },
+    /// Let a paused strategy trade again
+    ResumeStrategy { operator: String, strategy: String },
+    /// Change a strategy's parameters while it runs; only those set in `config` change
+    UpdateStrategyConfig {
+        operator: String,
+        strategy: String,
+        config: StrategyConfig,
+    },
+    /// Recompute a strategy's signals against current market state without executing them
+    PreviewSignals {
+        strategy: String,
+        symbol: Option<String>,
+    },
+    /// Export working orders to a file on the engine host
+    ExportOrders { operator: String, path: String },
+    /// Replay orders from an export file; without a confirmation only the plan is returned
+    ImportOrders {
+        operator: String,
+        path: String,
+        confirmation: Option<String>,
+    },
+    /// Set a risk limit; values above the hard cap need a second operator's approval
+    SetLimit {
+        operator: String,
+        limit: LimitKey,
+        value: Decimal,
+        reason: String,
+    },
+    /// Approve a pending limit override
+    ApproveLimitOverride { token: ApprovalToken },
+    /// List limit overrides awaiting approval
+    PendingLimitOverrides,
+    /// Show the hot-path log sampling settings
+    LogSampling,
+    /// Change the hot-path log sampling settings; unset fields are left as they are
+    SetLogSampling {
+        operator: String,
+        enabled: Option<bool>,
+        every_n: Option<u64>,
+        max_per_second: Option<u32>,
+    },
+    /// List the active game day scenarios
+    GameDay,
+    /// Start a game day scenario
+    StartGameDay {
+        operator: String,
+        scenario: GameDayScenario,
+    },
+    /// Stop all game day scenarios
+    StopGameDay { operator: String },
+    /// List dead-lettered events, newest last, optionally only the last `limit`
+    DeadLetters { limit: Option<usize> },
+    /// Re-drive dead letters through the event loop; no IDs means all of them
+    RedriveDeadLetters { operator: String, ids: Vec<u64> },
+    /// Delete dead letters; no IDs means all of them
+    DiscardDeadLetters { operator: String, ids: Vec<u64> },
+    /// List exchange accounts and their current (masked) API keys
+    ApiKeys,
+    /// Rotate an account's API key to a pair held by the key provider
+    /// `api_key` and `api_secret` name secrets, so the values never cross the control API.
+    RotateApiKey {
+        operator: String,
+        account: String,
+        api_key: String,
+        api_secret: String,
+    },
+}
+
+/// Response to an operator command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ControlResponse {
+    Ok,
+    Status {
+        trading_halted: bool,
+        halt_reason: Option<String>,
+        paused_strategies: Vec<String>,
+        quarantined_symbols: Vec<String>,
+        pending_overrides: usize,
+    },
+    Positions {
+        positions: Vec<PositionRecord>,
+    },
+    Portfolio {
+        node: PortfolioNode,
+    },
+    Orders {
+        orders: Vec<ExecutionReport>,
+    },
+    Cancelled {
+        cancelled: usize,
+        failures: Vec<String>,
+    },
+    Flattened {
+        report: FlattenAllReport,
+    },
+    Quarantined {
+        entries: Vec<QuarantineEntry>,
+    },
+    SymbolQuarantined {
+        symbol: String,
+        /// False if the symbol was already quarantined
+        newly_quarantined: bool,
+        cancelled: usize,
+        failures: Vec<String>,
+    },
+    TradingHalted {
+        /// False if trading was already halted
+        newly_halted: bool,
+        cancelled: usize,
+        failures: Vec<String>,
+    },
+    SignalPreview {
+        strategy: String,
+        /// Paused strategies and a halted engine would drop these signals
+        paused: bool,
+        trading_halted: bool,
+        previews: Vec<SignalPreview>,
+    },
+    Exported {
+        path: String,
+        orders: usize,
+    },
+    ImportPreview {
+        plan: ImportPlan,
+        confirmation: String,
+    },
+    Imported {
+        report: ImportReport,
+    },
+    PendingApproval {
+        request: OverrideRequest,
+    },
+    Approved {
+        request: OverrideRequest,
+    },
+    PendingOverrides {
+        requests: Vec<OverrideRequest>,
+    },
+    LogSampling {
+        config: LogSamplingConfig,
+    },
+    GameDay {
+        active: Vec<GameDayScenario>,
+    },
+    DeadLetters {
+        letters: Vec<DeadLetter>,
+        /// Letters parked, including any not listed
+        total: usize,
+        /// Letters dropped to stay within the queue's capacity
+        dropped: u64,
+    },
+    DeadLettersRedriven {
+        queued: usize,
+    },
+    DeadLettersDiscarded {
+        discarded: usize,
+    },
+    ApiKeys {
+        accounts: Vec<ApiKeyStatus>,
+    },
+    ApiKeyRotated {
+        report: RotationReport,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Set of paused strategies, shared between the control API and the strategy runners
+#[derive(Debug, Clone, Default)]
+pub struct StrategyPauses {
+    paused: Arc<RwLock<BTreeSet<String>>>,
+}
+
+impl StrategyPauses {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pause a strategy; returns false if it was already paused
+    pub async fn pause(&self, strategy: &str) -> bool {
+        self.paused.write().await.insert(strategy.to_string())
+    }
+
+    /// Resume a strategy; returns false if it was not paused
+    pub async fn resume(&self, strategy: &str) -> bool {
+        self.paused.write().await.remove(strategy)
+    }
+
+    /// Check if a strategy is paused
+    pub async fn is_paused(&self, strategy: &str) -> bool {
+        self.paused.read().await.contains(strategy)
+    }
+
+    /// Get all paused strategies
+    pub async fn paused(&self) -> Vec<String> {
+        self.paused.read().await.iter().cloned().collect()
+    }
+}
+
+/// Object-safe view of an execution client used by the control API
+#[async_trait]
+pub trait OrderControl: Send + Sync {
+    /// Get open orders
+    async fn open_orders(&self, symbol: Option<&str>) -> Result<Vec<ExecutionReport>, String>;
+
+    /// Cancel all open orders, returning the number cancelled and any failures
+    async fn cancel_all(&self, symbol: Option<&str>) -> Result<(usize, Vec<String>), String>;
+
+    /// Execute a confirmed order import
+    async fn import_orders(
+        &self,
+        plan: &ImportPlan,
+        confirmation: &str,
+        risk_engine: &RiskEngine,
+        order_manager: Option<&OrderManagerImpl>,
+    ) -> Result<ImportReport, String>;
+}
+
+#[async_trait]
+impl<C> OrderControl for C
+where
+    C: ExecutionClient + Send + Sync,
+{
+    async fn open_orders(&self, symbol: Option<&str>) -> Result<Vec<ExecutionReport>, String> {
+        self.get_open_orders(symbol)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn cancel_all(&self, symbol: Option<&str>) -> Result<(usize, Vec<String>), String> {
+        let orders = self.open_orders(symbol).await?;
+        let mut cancelled = 0;
+        let mut failures = Vec::new();
+        for order in orders {
+            match self.cancel_order(order.order_id.clone()).await {
+                Ok(()) => cancelled += 1,
+                Err(e) => failures.push(format!("Failed to cancel {}: {}", order.order_id, e)),
+            }
+        }
+        Ok((cancelled, failures))
+    }
+
+    async fn import_orders(
+        &self,
+        plan: &ImportPlan,
+        confirmation: &str,
+        risk_engine: &RiskEngine,
+        order_manager: Option<&OrderManagerImpl>,
+    ) -> Result<ImportReport, String> {
+        plan.execute(confirmation, self, Some(risk_engine), order_manager)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Object-safe view of a strategy engine used for dry-run signal previews and live
+/// parameter changes
+#[async_trait]
+pub trait StrategyPreview: Send + Sync {
+    /// Compute the signals the strategy would emit now, optionally for one symbol
+    async fn preview_signals(&self, symbol: Option<&str>) -> Vec<SignalPreview>;
+
+    /// Change the strategy's parameters, keeping its state
+    async fn update_config(&self, config: StrategyConfig) -> Result<(), String>;
+}
+
+#[async_trait]
+impl<S> StrategyPreview for RwLock<StrategyEngine<S>>
+where
+    S: Strategy + Clone + Send + Sync,
+{
+    async fn preview_signals(&self, symbol: Option<&str>) -> Vec<SignalPreview> {
+        self.read().await.preview_signals(symbol)
+    }
+
+    async fn update_config(&self, config: StrategyConfig) -> Result<(), String> {
+        self.write().await.update_config(config)
+    }
+}
+
+/// Control API command handler
+pub struct ControlApi {
+    limit_overrides: Arc<LimitOverrideManager>,
+    shadow_ledger: Option<Arc<ShadowLedger>>,
+    portfolio: Option<PortfolioView>,
+    orders: Option<Arc<dyn OrderControl>>,
+    order_manager: Option<Arc<OrderManagerImpl>>,
+    order_executor: Option<Arc<OrderExecutor>>,
+    strategy_pauses: StrategyPauses,
+    strategies: HashMap<String, Arc<dyn StrategyPreview>>,
+    game_day: Option<GameDay>,
+    dead_letters: Option<DeadLetterQueue>,
+    api_keys: Option<Arc<ApiKeyManager>>,
+}
+
+impl ControlApi {
+    /// Create a new control API
+    pub fn new(limit_overrides: Arc<LimitOverrideManager>) -> Self {
+        Self {
+            limit_overrides,
+            shadow_ledger: None,
+            portfolio: None,
+            orders: None,
+            order_manager: None,
+            order_executor: None,
+            strategy_pauses: StrategyPauses::new(),
+            strategies: HashMap::new(),
+            game_day: None,
+            dead_letters: None,
+            api_keys: None,
+        }
+    }
+
+    /// Serve position queries from a shadow ledger (builder pattern)
+    pub fn with_shadow_ledger(mut self, shadow_ledger: Arc<ShadowLedger>) -> Self {
+        self.shadow_ledger = Some(shadow_ledger);
+        self
+    }
+
+    /// Serve portfolio rollups from a portfolio view (builder pattern)
+    pub fn with_portfolio(mut self, portfolio: PortfolioView) -> Self {
+        self.portfolio = Some(portfolio);
+        self
+    }
+
+    /// Serve order queries and cancellations through an execution client (builder pattern)
+    pub fn with_execution_client<C>(mut self, client: Arc<C>) -> Self
+    where
+        C: ExecutionClient + Send + Sync + 'static,
+    {
+        self.orders = Some(client);
+        self
+    }
+
+    /// Export working orders from an order manager, and track imported ones (builder pattern)
+    pub fn with_order_manager(mut self, order_manager: Arc<OrderManagerImpl>) -> Self {
+        self.order_manager = Some(order_manager);
+        self
+    }
+
+    /// Flatten positions through an order executor (builder pattern)
+    pub fn with_order_executor(mut self, order_executor: Arc<OrderExecutor>) -> Self {
+        self.order_executor = Some(order_executor);
+        self
+    }
+
+    /// Allow game day scenarios to be started from the control API (builder pattern)
+    /// Only for mock and dry-run environments
+    pub fn with_game_day(mut self, game_day: GameDay) -> Self {
+        self.game_day = Some(game_day);
+        self
+    }
+
+    /// Inspect and re-drive a dead letter queue (builder pattern)
+    pub fn with_dead_letters(mut self, dead_letters: DeadLetterQueue) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Allow API key rotation through a key manager (builder pattern)
+    pub fn with_api_keys(mut self, api_keys: Arc<ApiKeyManager>) -> Self {
+        self.api_keys = Some(api_keys);
+        self
+    }
+
+    /// Share a set of strategy pauses with the strategy runners (builder pattern)
+    pub fn with_strategy_pauses(mut self, strategy_pauses: StrategyPauses) -> Self {
+        self.strategy_pauses = strategy_pauses;
+        self
+    }
+
+    /// Allow signal previews and parameter changes of a named strategy (builder pattern)
+    pub fn with_strategy<S>(
+        mut self,
+        name: impl Into<String>,
+        engine: Arc<RwLock<StrategyEngine<S>>>,
+    ) -> Self
+    where
+        S: Strategy + Clone + Send + Sync + 'static,
+    {
+        self.strategies.insert(name.into(), engine);
+        self
+    }
+
+    /// Get the strategy pauses
+    pub fn strategy_pauses(&self) -> &StrategyPauses {
+        &self.strategy_pauses
+    }
+
+    fn unavailable(component: &str) -> ControlResponse {
+        ControlResponse::Error {
+            message: format!("{} not available", component),
+        }
+    }
+
+    /// Handle an operator command
+    pub async fn handle(&self, command: ControlCommand) -> ControlResponse {
+        let audit_log = self.limit_overrides.audit_log();
+        match command {
+            ControlCommand::Status => {
+                let risk_engine = self.limit_overrides.risk_engine();
+                ControlResponse::Status {
+                    trading_halted: risk_engine.is_halted().await,
+                    halt_reason: risk_engine.halt_reason().await,
+                    paused_strategies: self.strategy_pauses.paused().await,
+                    quarantined_symbols: risk_engine
+                        .quarantine()
+                        .entries()
+                        .into_iter()
+                        .map(|entry| entry.symbol)
+                        .collect(),
+                    pending_overrides: self.limit_overrides.pending_requests().await.len(),
+                }
+            }
+            ControlCommand::Positions => match &self.shadow_ledger {
+                Some(ledger) => ControlResponse::Positions {
+                    positions: ledger.get_all_positions().await,
+                },
+                None => Self::unavailable("Shadow ledger"),
+            },
+            ControlCommand::Portfolio { path } => match &self.portfolio {
+                Some(portfolio) => {
+                    let root = portfolio.snapshot_at_last_prices().await;
+                    match root.find(&path) {
+                        Some(node) => ControlResponse::Portfolio { node: node.clone() },
+                        None => ControlResponse::Error {
+                            message: format!("No portfolio node at {}", path.join("/")),
+                        },
+                    }
+                }
+                None => Self::unavailable("Portfolio"),
+            },
+            ControlCommand::Orders { symbol } => match &self.orders {
+                Some(orders) => match orders.open_orders(symbol.as_deref()).await {
+                    Ok(orders) => ControlResponse::Orders { orders },
+                    Err(message) => ControlResponse::Error { message },
+                },
+                None => Self::unavailable("Execution client"),
+            },
+            ControlCommand::CancelAll { operator, symbol } => {
+                let Some(orders) = &self.orders else {
+                    return Self::unavailable("Execution client");
+                };
+                match orders.cancel_all(symbol.as_deref()).await {
+                    Ok((cancelled, failures)) => {
+                        audit_log
+                            .record(
+                                &operator,
+                                "cancel_all",
+                                format!(
+                                    "symbol={} cancelled={} failures={}",
+                                    symbol.as_deref().unwrap_or("*"),
+                                    cancelled,
+                                    failures.len()
+                                ),
+                            )
+                            .await;
+                        ControlResponse::Cancelled {
+                            cancelled,
+                            failures,
+                        }
+                    }
+                    Err(message) => ControlResponse::Error { message },
+                }
+            }
+            ControlCommand::FlattenAll { operator } => {
+                let Some(order_executor) = &self.order_executor else {
+                    return Self::unavailable("Order executor");
+                };
+                let report = order_executor.flatten_all().await;
+                audit_log
+                    .record(
+                        &operator,
+                        "positions_flattened",
+                        format!(
+                            "cancelled={} positions={} failures={}",
+                            report.cancelled,
+                            report.positions.len(),
+                            report.cancel_failures.len()
+                                + report
+                                    .positions
+                                    .iter()
+                                    .filter(|p| p.error.is_some())
+                                    .count()
+                        ),
+                    )
+                    .await;
+                ControlResponse::Flattened { report }
+            }
+            ControlCommand::Quarantined => ControlResponse::Quarantined {
+                entries: self.limit_overrides.risk_engine().quarantine().entries(),
+            },
+            ControlCommand::QuarantineSymbol {
+                operator,
+                symbol,
+                reason,
+            } => {
+                let newly_quarantined = self.limit_overrides.risk_engine().quarantine().quarantine(
+                    &symbol,
+                    QuarantineSource::Operator,
+                    format!("{} by {}", reason, operator),
+                );
+                // New orders are already rejected, so cancel whatever was working
+                let (cancelled, failures) = match &self.orders {
+                    Some(orders) => match orders.cancel_all(Some(&symbol)).await {
+                        Ok(result) => result,
+                        Err(message) => (0, vec![message]),
+                    },
+                    None => (0, vec!["Execution client not available".to_string()]),
+                };
+                audit_log
+                    .record(
+                        &operator,
+                        "symbol_quarantined",
+                        format!(
+                            "symbol={} reason={} cancelled={} failures={}",
+                            symbol,
+                            reason,
+                            cancelled,
+                            failures.len()
+                        ),
+                    )
+                    .await;
+                ControlResponse::SymbolQuarantined {
+                    symbol,
+                    newly_quarantined,
+                    cancelled,
+                    failures,
+                }
+            }
+            ControlCommand::ReleaseSymbol { operator, symbol } => {
+                let risk_engine = self.limit_overrides.risk_engine();
+                if risk_engine.quarantine().release(&symbol).is_none() {
+                    return ControlResponse::Error {
+                        message: format!("Symbol {} is not quarantined", symbol),
+                    };
+                }
+                audit_log.record(&operator, "symbol_released", symbol).await;
+                ControlResponse::Ok
+            }
+            ControlCommand::HaltTrading { operator, reason } => {
+                let kill_switch = self.limit_overrides.risk_engine().kill_switch();
+                let report = kill_switch
+                    .trip(HaltSource::Operator, format!("{} by {}", reason, operator))
+                    .await;
+                // New orders are already rejected, so cancel whatever was working
+                let (cancelled, failures) = match &self.orders {
+                    Some(orders) => match orders.cancel_all(None).await {
+                        Ok(result) => result,
+                        Err(message) => (0, vec![message]),
+                    },
+                    None => (0, vec!["Execution client not available".to_string()]),
+                };
+                audit_log
+                    .record(
+                        &operator,
+                        "trading_halted",
+                        format!(
+                            "reason={} cancelled={} failures={}",
+                            reason,
+                            cancelled,
+                            failures.len()
+                        ),
+                    )
+                    .await;
+                ControlResponse::TradingHalted {
+                    newly_halted: report.newly_tripped,
+                    cancelled,
+                    failures,
+                }
+            }
+            ControlCommand::ResumeTrading { operator } => {
+                let kill_switch = self.limit_overrides.risk_engine().kill_switch();
+                let Some(halt) = kill_switch.reset() else {
+                    return ControlResponse::Error {
+                        message: "Trading is not halted".to_string(),
+                    };
+                };
+                audit_log
+                    .record(&operator, "trading_resumed", halt.reason)
+                    .await;
+                ControlResponse::Ok
+            }
+            ControlCommand::PauseStrategy { operator, strategy } => {
+                if self.strategy_pauses.pause(&strategy).await {
+                    audit_log
+                        .record(&operator, "strategy_paused", strategy)
+                        .await;
+                }
+                ControlResponse::Ok
+            }
+            ControlCommand::ResumeStrategy { operator, strategy } => {
+                if !self.strategy_pauses.resume(&strategy).await {
+                    return ControlResponse::Error {
+                        message: format!("Strategy {} is not paused", strategy),
+                    };
+                }
+                audit_log
+                    .record(&operator, "strategy_resumed", strategy)
+                    .await;
+                ControlResponse::Ok
+            }
+            ControlCommand::UpdateStrategyConfig {
+                operator,
+                strategy,
+                config,
+            } => {
+                let Some(engine) = self.strategies.get(&strategy) else {
+                    return ControlResponse::Error {
+                        message: format!("Unknown strategy {}", strategy),
+                    };
+                };
+                let mut parameters: Vec<String> = config
+                    .parameters
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect();
+                parameters.sort();
+                if let Err(message) = engine.update_config(config).await {
+                    return ControlResponse::Error { message };
+                }
+                audit_log
+                    .record(
+                        &operator,
+                        "strategy_config_updated",
+                        format!("strategy={} {}", strategy, parameters.join(" ")),
+                    )
+                    .await;
+                ControlResponse::Ok
+            }
+            ControlCommand::PreviewSignals { strategy, symbol } => {
+                let Some(engine) = self.strategies.get(&strategy) else {
+                    return ControlResponse::Error {
+                        message: format!("Unknown strategy {}", strategy),
+                    };
+                };
+                ControlResponse::SignalPreview {
+                    paused: self.strategy_pauses.is_paused(&strategy).await,
+                    trading_halted: self.limit_overrides.risk_engine().is_halted().await,
+                    previews: engine.preview_signals(symbol.as_deref()).await,
+                    strategy,
+                }
+            }
+            ControlCommand::ExportOrders { operator, path } => {
+                let Some(order_manager) = &self.order_manager else {
+                    return Self::unavailable("Order manager");
+                };
+                let export = OrderExport::from_order_manager(order_manager).await;
+                if let Err(e) = export.write_to_file(&path) {
+                    return ControlResponse::Error {
+                        message: e.to_string(),
+                    };
+                }
+                let orders = export.orders.len();
+                audit_log
+                    .record(
+                        &operator,
+                        "orders_exported",
+                        format!("path={} orders={}", path, orders),
+                    )
+                    .await;
+                ControlResponse::Exported { path, orders }
+            }
+            ControlCommand::ImportOrders {
+                operator,
+                path,
+                confirmation,
+            } => {
+                let Some(orders) = &self.orders else {
+                    return Self::unavailable("Execution client");
+                };
+                let plan = match OrderExport::read_from_file(&path) {
+                    Ok(export) => match orders.open_orders(None).await {
+                        Ok(open_orders) => export.plan_import(&open_orders),
+                        Err(message) => return ControlResponse::Error { message },
+                    },
+                    Err(e) => {
+                        return ControlResponse::Error {
+                            message: e.to_string(),
+                        }
+                    }
+                };
+                let Some(confirmation) = confirmation else {
+                    let confirmation = plan.confirmation_token();
+                    return ControlResponse::ImportPreview { plan, confirmation };
+                };
+
+                match orders
+                    .import_orders(
+                        &plan,
+                        &confirmation,
+                        self.limit_overrides.risk_engine(),
+                        self.order_manager.as_deref(),
+                    )
+                    .await
+                {
+                    Ok(report) => {
+                        audit_log
+                            .record(
+                                &operator,
+                                "orders_imported",
+                                format!(
+                                    "path={} placed={} skipped={} failures={}",
+                                    path,
+                                    report.placed.len(),
+                                    report.skipped,
+                                    report.failures.len()
+                                ),
+                            )
+                            .await;
+                        ControlResponse::Imported { report }
+                    }
+                    Err(message) => ControlResponse::Error { message },
+                }
+            }
+            ControlCommand::SetLimit {
+                operator,
+                limit,
+                value,
+                reason,
+            } => match self
+                .limit_overrides
+                .request_limit(&operator, limit, value, &reason)
+                .await
+            {
+                Ok(OverrideOutcome::Applied) => ControlResponse::Ok,
+                Ok(OverrideOutcome::PendingApproval(request)) => {
+                    ControlResponse::PendingApproval { request }
+                }
+                Err(e) => ControlResponse::Error {
+                    message: e.to_string(),
+                },
+            },
+            ControlCommand::ApproveLimitOverride { token } => {
+                match self.limit_overrides.approve(&token).await {
+                    Ok(request) => ControlResponse::Approved { request },
+                    Err(e) => ControlResponse::Error {
+                        message: e.to_string(),
+                    },
+                }
+            }
+            ControlCommand::PendingLimitOverrides => ControlResponse::PendingOverrides {
+                requests: self.limit_overrides.pending_requests().await,
+            },
+            ControlCommand::LogSampling => ControlResponse::LogSampling {
+                config: hot_path_sampler().config(),
+            },
+            ControlCommand::SetLogSampling {
+                operator,
+                enabled,
+                every_n,
+                max_per_second,
+            } => {
+                if every_n == Some(0) {
+                    return ControlResponse::Error {
+                        message: "every_n must be at least 1".to_string(),
+                    };
+                }
+                let sampler = hot_path_sampler();
+                let current = sampler.config();
+                let config = LogSamplingConfig {
+                    enabled: enabled.unwrap_or(current.enabled),
+                    every_n: every_n.unwrap_or(current.every_n),
+                    max_per_second: max_per_second.unwrap_or(current.max_per_second),
+                };
+                sampler.set_config(config);
+                audit_log
+                    .record(
+                        &operator,
+                        "log_sampling_changed",
+                        format!(
+                            "enabled={} every_n={} max_per_second={}",
+                            config.enabled, config.every_n, config.max_per_second
+                        ),
+                    )
+                    .await;
+                ControlResponse::LogSampling { config }
+            }
+            ControlCommand::GameDay => match &self.game_day {
+                Some(game_day) => ControlResponse::GameDay {
+                    active: game_day.active(),
+                },
+                None => Self::unavailable("Game day"),
+            },
+            ControlCommand::StartGameDay { operator, scenario } => {
+                let Some(game_day) = &self.game_day else {
+                    return Self::unavailable("Game day");
+                };
+                let details = format!("{:?}", scenario);
+                if let Err(e) = game_day.start(scenario).await {
+                    return ControlResponse::Error {
+                        message: e.to_string(),
+                    };
+                }
+                audit_log
+                    .record(&operator, "game_day_started", details)
+                    .await;
+                ControlResponse::GameDay {
+                    active: game_day.active(),
+                }
+            }
+            ControlCommand::StopGameDay { operator } => {
+                let Some(game_day) = &self.game_day else {
+                    return Self::unavailable("Game day");
+                };
+                game_day.stop();
+                audit_log
+                    .record(&operator, "game_day_stopped", String::new())
+                    .await;
+                ControlResponse::GameDay { active: Vec::new() }
+            }
+            ControlCommand::DeadLetters { limit } => {
+                let Some(dead_letters) = &self.dead_letters else {
+                    return Self::unavailable("Dead letter queue");
+                };
+                let mut letters = dead_letters.letters();
+                let total = letters.len();
+                if let Some(limit) = limit {
+                    letters.drain(..total.saturating_sub(limit));
+                }
+                ControlResponse::DeadLetters {
+                    letters,
+                    total,
+                    dropped: dead_letters.dropped(),
+                }
+            }
+            ControlCommand::RedriveDeadLetters { operator, ids } => {
+                let Some(dead_letters) = &self.dead_letters else {
+                    return Self::unavailable("Dead letter queue");
+                };
+                let queued = dead_letters.request_redrive(&ids);
+                audit_log
+                    .record(
+                        &operator,
+                        "dead_letters_redriven",
+                        format!("ids={:?} queued={}", ids, queued),
+                    )
+                    .await;
+                ControlResponse::DeadLettersRedriven { queued }
+            }
+            ControlCommand::DiscardDeadLetters { operator, ids } => {
+                let Some(dead_letters) = &self.dead_letters else {
+                    return Self::unavailable("Dead letter queue");
+                };
+                let discarded = dead_letters.discard(&ids);
+                audit_log
+                    .record(
+                        &operator,
+                        "dead_letters_discarded",
+                        format!("ids={:?} discarded={}", ids, discarded),
+                    )
+                    .await;
+                ControlResponse::DeadLettersDiscarded { discarded }
+            }
+            ControlCommand::ApiKeys => {
+                let Some(api_keys) = &self.api_keys else {
+                    return Self::unavailable("API key manager");
+                };
+                ControlResponse::ApiKeys {
+                    accounts: api_keys.account_status(),
+                }
+            }
+            ControlCommand::RotateApiKey {
+                operator,
+                account,
+                api_key,
+                api_secret,
+            } => {
+                let Some(api_keys) = &self.api_keys else {
+                    return Self::unavailable("API key manager");
+                };
+                match api_keys
+                    .rotate_from_provider(&account, &api_key, &api_secret)
+                    .await
+                {
+                    Ok(report) => {
+                        audit_log
+                            .record(
+                                &operator,
+                                "api_key_rotated",
+                                format!(
+                                    "account={} from={} to={} drained={} revoked={}",
+                                    account,
+                                    report.previous_key,
+                                    report.new_key,
+                                    report.drained,
+                                    report.revoked
+                                ),
+                            )
+                            .await;
+                        ControlResponse::ApiKeyRotated { report }
+                    }
+                    Err(e) => ControlResponse::Error {
+                        message: e.to_string(),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Handle a JSON-encoded command and return a JSON-encoded response
+    pub async fn handle_json(&self, command: &str) -> String {
+        let response = match serde_json::from_str::<ControlCommand>(command) {
+            Ok(command) => self.handle(command).await,
+            Err(e) => ControlResponse::Error {
+                message: format!("Invalid command: {}", e),
+            },
+        };
+        serde_json::to_string(&response).expect("control responses serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitoring::audit::AuditLog;
+    use crate::risk::RiskEngine;
+    use crate::types::Price;
+
+    #[tokio::test]
+    async fn test_limit_override_via_control_api() {
+        let risk_engine = RiskEngine::new();
+        let overrides = LimitOverrideManager::new(risk_engine.clone(), Arc::new(AuditLog::new()))
+            .with_operator("alice", "a")
+            .with_operator("bob", "b")
+            .with_hard_cap(LimitKey::MaxTotalExposure, Decimal::new(100_000, 0));
+        let api = ControlApi::new(Arc::new(overrides));
+
+        let response = api
+            .handle_json(
+                r#"{"command":"set_limit","operator":"alice","limit":{"limit":"max_total_exposure"},"value":"250000","reason":"rebalance"}"#,
+            )
+            .await;
+        let ControlResponse::PendingApproval { request } = serde_json::from_str(&response).unwrap()
+        else {
+            panic!("unexpected response: {}", response);
+        };
+
+        let token = ApprovalToken::sign(&request, "bob", "b");
+        let response = api
+            .handle(ControlCommand::ApproveLimitOverride { token })
+            .await;
+        assert!(matches!(response, ControlResponse::Approved { .. }));
+        assert_eq!(
+            risk_engine.get_max_total_exposure().await,
+            Price::new(Decimal::new(250_000, 0))
+        );
+
+        let response = api.handle_json(r#"{"command":"reboot"}"#).await;
+        assert!(response.contains("Invalid command"));
+    }
+
+    #[tokio::test]
+    async fn test_operator_commands() {
+        use crate::connectors::mock::MockExecutionClient;
+        use crate::core::events::{NewOrder, TimeInForce};
+        use crate::types::Size;
+
+        let risk_engine = RiskEngine::new();
+        let audit_log = Arc::new(AuditLog::new());
+        let client = Arc::new(MockExecutionClient::new());
+        for symbol in ["BTCUSDT", "BTCUSDT", "ETHUSDT"] {
+            let order = NewOrder::new_limit_buy(
+                symbol,
+                Size::from_str("1").unwrap(),
+                Price::from_str("100").unwrap(),
+                TimeInForce::GoodTillCancelled,
+            );
+            client.place_order(order).await.unwrap();
+        }
+        let api = ControlApi::new(Arc::new(LimitOverrideManager::new(
+            risk_engine.clone(),
+            audit_log.clone(),
+        )))
+        .with_execution_client(client.clone());
+
+        let response = api.handle(ControlCommand::Positions).await;
+        assert!(matches!(response, ControlResponse::Error { .. }));
+        let response = api.handle_json(r#"{"command":"portfolio"}"#).await;
+        assert!(response.contains("Portfolio not available"), "{}", response);
+
+        let response = api
+            .handle_json(r#"{"command":"orders","symbol":"BTCUSDT"}"#)
+            .await;
+        let ControlResponse::Orders { orders } = serde_json::from_str(&response).unwrap() else {
+            panic!("unexpected response: {}", response);
+        };
+        assert_eq!(orders.len(), 2);
+
+        let response = api
+            .handle_json(r#"{"command":"cancel_all","operator":"alice"}"#)
+            .await;
+        let ControlResponse::Cancelled {
+            cancelled,
+            failures,
+        } = serde_json::from_str(&response).unwrap()
+        else {
+            panic!("unexpected response: {}", response);
+        };
+        assert_eq!((cancelled, failures.len()), (3, 0));
+        assert!(client.get_open_orders(None).await.unwrap().is_empty());
+        let response = api
+            .handle_json(r#"{"command":"flatten_all","operator":"alice"}"#)
+            .await;
+        assert!(
+            response.contains("Order executor not available"),
+            "{}",
+            response
+        );
+
+        let pause = ControlCommand::PauseStrategy {
+            operator: "alice".to_string(),
+            strategy: "mm_btc".to_string(),
+        };
+        assert!(matches!(api.handle(pause).await, ControlResponse::Ok));
+
+        client
+            .place_order(NewOrder::new_limit_buy(
+                "ETHUSDT",
+                Size::from_str("1").unwrap(),
+                Price::from_str("100").unwrap(),
+                TimeInForce::GoodTillCancelled,
+            ))
+            .await
+            .unwrap();
+        let response = api
+            .handle_json(
+                r#"{"command":"quarantine_symbol","operator":"alice","symbol":"ETHUSDT","reason":"bad prints"}"#,
+            )
+            .await;
+        assert!(response.contains(r#""cancelled":1"#));
+        assert!(risk_engine.quarantine().is_quarantined("ETHUSDT"));
+        assert!(api.strategy_pauses().is_paused("mm_btc").await);
+
+        let response = api
+            .handle_json(r#"{"command":"halt_trading","operator":"alice","reason":"fat finger"}"#)
+            .await;
+        assert!(response.contains(r#""newly_halted":true"#), "{}", response);
+        let ControlResponse::Status {
+            trading_halted,
+            halt_reason,
+            paused_strategies,
+            quarantined_symbols,
+            ..
+        } = api.handle(ControlCommand::Status).await
+        else {
+            panic!("unexpected status response");
+        };
+        assert!(trading_halted);
+        assert_eq!(halt_reason.as_deref(), Some("fat finger by alice"));
+        assert_eq!(paused_strategies, vec!["mm_btc".to_string()]);
+        assert_eq!(quarantined_symbols, vec!["ETHUSDT".to_string()]);
+        let release = ControlCommand::ReleaseSymbol {
+            operator: "bob".to_string(),
+            symbol: "ETHUSDT".to_string(),
+        };
+        assert!(matches!(
+            api.handle(release.clone()).await,
+            ControlResponse::Ok
+        ));
+        assert!(matches!(
+            api.handle(release).await,
+            ControlResponse::Error { .. }
+        ));
+
+        let resume = ControlCommand::ResumeTrading {
+            operator: "bob".to_string(),
+        };
+        assert!(matches!(
+            api.handle(resume.clone()).await,
+            ControlResponse::Ok
+        ));
+        assert!(!risk_engine.is_halted().await);
+        assert!(matches!(
+            api.handle(resume).await,
+            ControlResponse::Error { .. }
+        ));
+
+        let resume = ControlCommand::ResumeStrategy {
+            operator: "bob".to_string(),
+            strategy: "mm_btc".to_string(),
+        };
+        assert!(matches!(
+            api.handle(resume.clone()).await,
+            ControlResponse::Ok
+        ));
+        assert!(matches!(
+            api.handle(resume).await,
+            ControlResponse::Error { .. }
+        ));
+
+        let response = api
+            .handle_json(r#"{"command":"set_log_sampling","operator":"bob","every_n":0}"#)
+            .await;
+        assert!(response.contains("every_n must be at least 1"));
+        let ControlResponse::LogSampling { config } = api
+            .handle(ControlCommand::SetLogSampling {
+                operator: "bob".to_string(),
+                enabled: None,
+                every_n: Some(1000),
+                max_per_second: None,
+            })
+            .await
+        else {
+            panic!("unexpected log sampling response");
+        };
+        assert_eq!(config.every_n, 1000);
+        assert_eq!(hot_path_sampler().config(), config);
+
+        let actions: Vec<String> = audit_log
+            .entries()
+            .await
+            .into_iter()
+            .map(|e| e.action)
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                "cancel_all",
+                "strategy_paused",
+                "symbol_quarantined",
+                "trading_halted",
+                "symbol_released",
+                "trading_resumed",
+                "strategy_resumed",
+                "log_sampling_changed"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dead_letters_via_control_api() {
+        let dead_letters = DeadLetterQueue::new();
+        for text in ["{", "[", "nul"] {
+            dead_letters.record_parse_failure("binance", text, "EOF");
+        }
+        let audit_log = Arc::new(AuditLog::new());
+        let api = ControlApi::new(Arc::new(LimitOverrideManager::new(
+            RiskEngine::new(),
+            audit_log.clone(),
+        )))
+        .with_dead_letters(dead_letters.clone());
+
+        let response = api
+            .handle_json(r#"{"command":"dead_letters","limit":2}"#)
+            .await;
+        let ControlResponse::DeadLetters { letters, total, .. } =
+            serde_json::from_str(&response).unwrap()
+        else {
+            panic!("unexpected response: {}", response);
+        };
+        assert_eq!(total, 3);
+        assert_eq!(letters.iter().map(|l| l.id).collect::<Vec<_>>(), vec![2, 3]);
+
+        let response = api
+            .handle(ControlCommand::RedriveDeadLetters {
+                operator: "alice".to_string(),
+                ids: vec![1, 9],
+            })
+            .await;
+        assert!(matches!(
+            response,
+            ControlResponse::DeadLettersRedriven { queued: 1 }
+        ));
+        assert_eq!(dead_letters.take_redrive()[0].id, 1);
+
+        let response = api
+            .handle_json(r#"{"command":"discard_dead_letters","operator":"bob","ids":[]}"#)
+            .await;
+        assert!(response.contains(r#""discarded":2"#));
+        assert!(dead_letters.is_empty());
+        assert_eq!(audit_log.entries().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_rotation_via_control_api() {
+        use crate::security::{ApiCredentials, EnvKeyProvider};
+
+        std::env::set_var("HFT_TEST_ROTATE_BINANCE_KEY_V2", "k".repeat(16));
+        std::env::set_var("HFT_TEST_ROTATE_BINANCE_SECRET_V2", "s".repeat(16));
+        let mut api_keys = ApiKeyManager::new()
+            .with_key_provider(Arc::new(EnvKeyProvider::new("HFT_TEST_ROTATE_")));
+        let credentials = api_keys
+            .register_account(
+                "binance",
+                ApiCredentials::new("a".repeat(16), "b".repeat(16)),
+            )
+            .unwrap();
+        let audit_log = Arc::new(AuditLog::new());
+        let api = ControlApi::new(Arc::new(LimitOverrideManager::new(
+            RiskEngine::new(),
+            audit_log.clone(),
+        )))
+        .with_api_keys(Arc::new(api_keys));
+
+        let response = api
+            .handle_json(
+                r#"{"command":"rotate_api_key","operator":"alice","account":"binance",
+                    "api_key":"binance.key.v2","api_secret":"binance.secret.v2"}"#,
+            )
+            .await;
+        let ControlResponse::ApiKeyRotated { report } = serde_json::from_str(&response).unwrap()
+        else {
+            panic!("unexpected response: {}", response);
+        };
+        assert!(report.drained);
+        assert_eq!(report.new_key, "kkkk...kkkk");
+        assert_eq!(credentials.lease().api_secret(), "s".repeat(16));
+        // Only masked keys reach the response and the audit log
+        assert!(!response.contains(&"s".repeat(16)));
+        assert_eq!(audit_log.entries().await.len(), 1);
+
+        let response = api.handle(ControlCommand::ApiKeys).await;
+        let ControlResponse::ApiKeys { accounts } = response else {
+            panic!("unexpected response: {:?}", response);
+        };
+        assert_eq!(accounts[0].generation, 2);
+
+        let response = api
+            .handle(ControlCommand::RotateApiKey {
+                operator: "alice".to_string(),
+                account: "binance".to_string(),
+                api_key: "binance.key.v3".to_string(),
+                api_secret: "binance.secret.v3".to_string(),
+            })
+            .await;
+        assert!(matches!(response, ControlResponse::Error { .. }));
+        std::env::remove_var("HFT_TEST_ROTATE_BINANCE_KEY_V2");
+        std::env::remove_var("HFT_TEST_ROTATE_BINANCE_SECRET_V2");
+    }
+
+    #[tokio::test]
+    async fn test_preview_signals_without_executing() {
+        use crate::orderbook::{OrderBookLevel, OrderBookSnapshot};
+        use crate::strategy::{Signal, SimpleArbitrageStrategy};
+        use crate::traits::MarketEvent;
+        use crate::types::Size;
+        use std::time::Duration;
+
+        let strategy = SimpleArbitrageStrategy::new(
+            Price::from_str("1").unwrap(),
+            Size::from_str("0.1").unwrap(),
+            Size::from_str("10").unwrap(),
+        );
+        let engine = Arc::new(RwLock::new(StrategyEngine::new(
+            strategy,
+            Duration::from_secs(60),
+        )));
+        let snapshot = OrderBookSnapshot::new(
+            "BTCUSDT",
+            "binance",
+            vec![OrderBookLevel::new(
+                Price::from_str("100").unwrap(),
+                Size::from_str("1").unwrap(),
+            )],
+            vec![OrderBookLevel::new(
+                Price::from_str("102").unwrap(),
+                Size::from_str("1").unwrap(),
+            )],
+            7,
+        );
+        let emitted = engine
+            .write()
+            .await
+            .process_event(MarketEvent::OrderBookSnapshot(snapshot));
+        assert!(emitted.is_some());
+
+        let api = ControlApi::new(Arc::new(LimitOverrideManager::new(
+            RiskEngine::new(),
+            Arc::new(AuditLog::new()),
+        )))
+        .with_strategy("arb", engine.clone());
+        api.strategy_pauses().pause("arb").await;
+
+        // The engine holds further signals during its cooldown; the preview still shows them
+        let response = api
+            .handle_json(r#"{"command":"preview_signals","strategy":"arb","symbol":"BTCUSDT"}"#)
+            .await;
+        let ControlResponse::SignalPreview {
+            paused, previews, ..
+        } = serde_json::from_str(&response).unwrap()
+        else {
+            panic!("unexpected response: {}", response);
+        };
+        assert!(paused);
+        assert_eq!(previews.len(), 1);
+        assert_eq!(previews[0].signal, emitted);
+        assert!(matches!(previews[0].signal, Some(Signal::Arbitrage { .. })));
+        assert!(previews[0].cooldown_remaining_ms > 0);
+        assert_eq!(previews[0].last_update, 7);
+        assert!(engine.write().await.generate_signals().is_empty());
+
+        // A wider minimum spread applies at once, without resetting the engine
+        let response = api
+            .handle_json(
+                r#"{"command":"update_strategy_config","operator":"alice","strategy":"arb",
+                    "config":{"parameters":{"min_spread":"3"}}}"#,
+            )
+            .await;
+        assert!(response.contains(r#""status":"ok""#), "{}", response);
+        let ControlResponse::SignalPreview { previews, .. } = api
+            .handle(ControlCommand::PreviewSignals {
+                strategy: "arb".to_string(),
+                symbol: None,
+            })
+            .await
+        else {
+            panic!("unexpected preview response");
+        };
+        assert_eq!(previews[0].signal, None);
+        assert_eq!(previews[0].last_update, 7);
+        let response = api
+            .handle_json(
+                r#"{"command":"update_strategy_config","operator":"alice","strategy":"arb",
+                    "config":{"parameters":{"min_sprad":"3"}}}"#,
+            )
+            .await;
+        assert!(
+            response.contains("Unknown parameters: min_sprad"),
+            "{}",
+            response
+        );
+
+        let response = api
+            .handle(ControlCommand::PreviewSignals {
+                strategy: "mm".to_string(),
+                symbol: None,
+            })
+            .await;
+        assert!(matches!(response, ControlResponse::Error { .. }));
+    }
+}
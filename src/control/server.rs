@@ -0,0 +1,135 @@
+use super::{ControlApi, ControlCommand, ControlResponse};
+use log::{info, warn};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+/// Control API server speaking JSON lines over TCP
+/// Each line received is a `ControlCommand`; each line sent back is a `ControlResponse`
+pub struct ControlServer {
+    listener: TcpListener,
+    api: Arc<ControlApi>,
+}
+
+impl ControlServer {
+    /// Bind the server; bind to a loopback address unless the port is otherwise protected
+    pub async fn bind(addr: impl ToSocketAddrs, api: Arc<ControlApi>) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self { listener, api })
+    }
+
+    /// Get the bound address
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept connections until the task is dropped
+    pub async fn run(self) -> io::Result<()> {
+        info!("Control API listening on {}", self.listener.local_addr()?);
+        loop {
+            let (stream, peer) = self.listener.accept().await?;
+            let api = self.api.clone();
+            tokio::spawn(async move {
+                if let Err(e) = Self::serve_connection(stream, api).await {
+                    warn!("Control connection from {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn serve_connection(stream: TcpStream, api: Arc<ControlApi>) -> io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut response = api.handle_json(&line).await;
+            response.push('\n');
+            writer.write_all(response.as_bytes()).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Client for a `ControlServer`
+pub struct ControlClient {
+    reader: BufReader<tokio::net::tcp::OwnedReadHalf>,
+    writer: tokio::net::tcp::OwnedWriteHalf,
+}
+
+impl ControlClient {
+    /// Connect to a control server
+    pub async fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let (reader, writer) = TcpStream::connect(addr).await?.into_split();
+        Ok(Self {
+            reader: BufReader::new(reader),
+            writer,
+        })
+    }
+
+    /// Send a command and wait for its response
+    pub async fn send(&mut self, command: &ControlCommand) -> io::Result<ControlResponse> {
+        let mut request = serde_json::to_string(command)?;
+        request.push('\n');
+        self.writer.write_all(request.as_bytes()).await?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).await? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "control server closed the connection",
+            ));
+        }
+        Ok(serde_json::from_str(&line)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitoring::audit::AuditLog;
+    use crate::risk::approvals::LimitOverrideManager;
+    use crate::risk::RiskEngine;
+
+    #[tokio::test]
+    async fn test_client_server_roundtrip() {
+        let risk_engine = RiskEngine::new();
+        let api = ControlApi::new(Arc::new(LimitOverrideManager::new(
+            risk_engine.clone(),
+            Arc::new(AuditLog::new()),
+        )));
+        let server = ControlServer::bind("127.0.0.1:0", Arc::new(api))
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = tokio::spawn(server.run());
+
+        let mut client = ControlClient::connect(addr).await.unwrap();
+        let pause = ControlCommand::PauseStrategy {
+            operator: "alice".to_string(),
+            strategy: "arb".to_string(),
+        };
+        assert!(matches!(
+            client.send(&pause).await.unwrap(),
+            ControlResponse::Ok
+        ));
+
+        risk_engine.halt_trading("breaker").await;
+        let ControlResponse::Status {
+            trading_halted,
+            halt_reason,
+            paused_strategies,
+            ..
+        } = client.send(&ControlCommand::Status).await.unwrap()
+        else {
+            panic!("unexpected status response");
+        };
+        assert!(trading_halted);
+        assert_eq!(halt_reason.as_deref(), Some("breaker"));
+        assert_eq!(paused_strategies, vec!["arb".to_string()]);
+        handle.abort();
+    }
+}
@@ -0,0 +1,311 @@
+use crate::core::events::{MarketEvent, SystemEvent, TradingEvent};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast::{self, error::RecvError, error::TryRecvError};
+
+/// Default number of events a channel buffers before slow subscribers lag
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Kind of event carried on a topic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Market,
+    Trading,
+    System,
+}
+
+/// Delivery counters of a topic
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BusMetrics {
+    /// Events published
+    pub published: u64,
+    /// Events published while nobody was subscribed
+    pub unobserved: u64,
+    /// Events subscribers missed by falling more than a channel's capacity behind
+    pub dropped: u64,
+    /// Most events ever waiting in one channel after a publish
+    pub max_queue_depth: u64,
+}
+
+/// Shared counters behind `BusMetrics`
+#[derive(Debug, Default)]
+struct TopicCounters {
+    published: AtomicU64,
+    unobserved: AtomicU64,
+    dropped: AtomicU64,
+    max_queue_depth: AtomicU64,
+}
+
+impl TopicCounters {
+    fn snapshot(&self) -> BusMetrics {
+        BusMetrics {
+            published: self.published.load(Ordering::Relaxed),
+            unobserved: self.unobserved.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            max_queue_depth: self.max_queue_depth.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Channels of one event kind: one for every symbol plus one per subscribed symbol
+struct Topic<T> {
+    capacity: usize,
+    all: broadcast::Sender<T>,
+    by_symbol: RwLock<HashMap<String, broadcast::Sender<T>>>,
+    counters: Arc<TopicCounters>,
+}
+
+impl<T: Clone> Topic<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            all: broadcast::channel(capacity).0,
+            by_symbol: RwLock::new(HashMap::new()),
+            counters: Arc::new(TopicCounters::default()),
+        }
+    }
+
+    /// Send an event to the all-symbols channel and its symbol's channel
+    /// Returns the number of subscribers reached.
+    fn publish(&self, symbol: Option<&str>, event: T) -> usize {
+        let mut reached = 0;
+        let mut depth = 0;
+        if let Some(symbol) = symbol {
+            if let Some(sender) = self.by_symbol.read().unwrap().get(symbol) {
+                reached += sender.send(event.clone()).unwrap_or(0);
+                depth = sender.len();
+            }
+        }
+        reached += self.all.send(event).unwrap_or(0);
+        depth = depth.max(self.all.len());
+
+        self.counters.published.fetch_add(1, Ordering::Relaxed);
+        if reached == 0 {
+            self.counters.unobserved.fetch_add(1, Ordering::Relaxed);
+        }
+        self.counters
+            .max_queue_depth
+            .fetch_max(depth as u64, Ordering::Relaxed);
+        reached
+    }
+
+    fn subscribe(&self, symbol: Option<&str>) -> Subscription<T> {
+        let receiver = match symbol {
+            None => self.all.subscribe(),
+            Some(symbol) => self
+                .by_symbol
+                .write()
+                .unwrap()
+                .entry(symbol.to_string())
+                .or_insert_with(|| broadcast::channel(self.capacity).0)
+                .subscribe(),
+        };
+        Subscription {
+            receiver,
+            counters: self.counters.clone(),
+        }
+    }
+}
+
+/// Receiver of one topic, optionally narrowed to a symbol
+///
+/// A subscriber that falls more than the bus capacity behind skips the oldest events;
+/// they are counted in the topic's `dropped` metric and receiving carries on.
+pub struct Subscription<T> {
+    receiver: broadcast::Receiver<T>,
+    counters: Arc<TopicCounters>,
+}
+
+impl<T: Clone> Subscription<T> {
+    /// Wait for the next event, or `None` once the bus is gone
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(missed)) => self.record_lag(missed),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Take the next event if one is waiting
+    pub fn try_recv(&mut self) -> Option<T> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => return Some(event),
+                Err(TryRecvError::Lagged(missed)) => self.record_lag(missed),
+                Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Get the number of events waiting
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Check if no events are waiting
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+
+    fn record_lag(&self, missed: u64) {
+        log::warn!("Event bus subscriber lagged, skipped {} events", missed);
+        self.counters.dropped.fetch_add(missed, Ordering::Relaxed);
+    }
+}
+
+/// Central event bus between market data, strategies, the OMS and risk
+///
+/// Market and trading events are broadcast to subscribers of every symbol and to those
+/// of the event's own symbol; system events have no symbol. Events are shared behind an
+/// `Arc`, so fan-out costs one reference count per subscriber. Clones share channels.
+#[derive(Clone)]
+pub struct EventBus {
+    market: Arc<Topic<Arc<MarketEvent>>>,
+    trading: Arc<Topic<Arc<TradingEvent>>>,
+    system: Arc<Topic<SystemEvent>>,
+}
+
+impl EventBus {
+    /// Create a bus whose channels each buffer `capacity` events
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            market: Arc::new(Topic::new(capacity)),
+            trading: Arc::new(Topic::new(capacity)),
+            system: Arc::new(Topic::new(capacity)),
+        }
+    }
+
+    /// Publish a market event, returning the number of subscribers reached
+    pub fn publish_market(&self, event: MarketEvent) -> usize {
+        let event = Arc::new(event);
+        self.market.publish(Some(event.symbol()), event.clone())
+    }
+
+    /// Publish a trading event, returning the number of subscribers reached
+    pub fn publish_trading(&self, event: TradingEvent) -> usize {
+        let event = Arc::new(event);
+        self.trading.publish(Some(event.symbol()), event.clone())
+    }
+
+    /// Publish a system event, returning the number of subscribers reached
+    pub fn publish_system(&self, event: SystemEvent) -> usize {
+        self.system.publish(None, event)
+    }
+
+    /// Subscribe to market events, of every symbol or of one
+    pub fn subscribe_market(&self, symbol: Option<&str>) -> Subscription<Arc<MarketEvent>> {
+        self.market.subscribe(symbol)
+    }
+
+    /// Subscribe to trading events, of every symbol or of one
+    pub fn subscribe_trading(&self, symbol: Option<&str>) -> Subscription<Arc<TradingEvent>> {
+        self.trading.subscribe(symbol)
+    }
+
+    /// Subscribe to system events
+    pub fn subscribe_system(&self) -> Subscription<SystemEvent> {
+        self.system.subscribe(None)
+    }
+
+    /// Get the sender of system events, e.g. for `KillSwitch::with_event_sender`
+    /// Events sent on it directly reach subscribers but skip the published counters.
+    pub fn system_sender(&self) -> broadcast::Sender<SystemEvent> {
+        self.system.all.clone()
+    }
+
+    /// Get the delivery counters of a topic
+    pub fn metrics(&self, kind: EventKind) -> BusMetrics {
+        match kind {
+            EventKind::Market => self.market.counters.snapshot(),
+            EventKind::Trading => self.trading.counters.snapshot(),
+            EventKind::System => self.system.counters.snapshot(),
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{NewOrder, OrderSide, Trade};
+    use crate::types::{Price, Size};
+
+    fn trade(symbol: &str) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            symbol: symbol.into(),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str("100").unwrap(),
+            size: Size::from_str("1").unwrap(),
+            side: OrderSide::Buy,
+            timestamp: 0,
+            trade_id: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_event_bus_topics() {
+        let bus = EventBus::new(4);
+        assert_eq!(bus.publish_market(trade("BTCUSDT")), 0);
+
+        let mut all = bus.subscribe_market(None);
+        let mut eth = bus.subscribe_market(Some("ETHUSDT"));
+        let mut orders = bus.subscribe_trading(Some("ETHUSDT"));
+        let mut system = bus.subscribe_system();
+
+        assert_eq!(bus.publish_market(trade("BTCUSDT")), 1);
+        assert_eq!(bus.publish_market(trade("ETHUSDT")), 2);
+        assert_eq!(all.recv().await.unwrap().symbol(), "BTCUSDT");
+        assert_eq!(all.recv().await.unwrap().symbol(), "ETHUSDT");
+        assert_eq!(eth.try_recv().unwrap().symbol(), "ETHUSDT");
+        assert!(eth.try_recv().is_none());
+
+        // Event kinds are kept apart
+        let order = NewOrder::new_market_buy("ETHUSDT", Size::from_str("1").unwrap());
+        bus.publish_trading(TradingEvent::OrderCreated(order.clone()));
+        assert_eq!(
+            *orders.try_recv().unwrap(),
+            TradingEvent::OrderCreated(order)
+        );
+        assert!(all.is_empty());
+        bus.system_sender()
+            .send(SystemEvent::TradingResumed)
+            .unwrap();
+        assert_eq!(system.try_recv(), Some(SystemEvent::TradingResumed));
+
+        let metrics = bus.metrics(EventKind::Market);
+        assert_eq!(metrics.published, 3);
+        assert_eq!(metrics.unobserved, 1);
+        assert_eq!(metrics.dropped, 0);
+        assert_eq!(metrics.max_queue_depth, 2);
+    }
+
+    #[tokio::test]
+    async fn test_event_bus_slow_subscriber() {
+        let bus = EventBus::new(4);
+        let mut slow = bus.subscribe_market(None);
+
+        for _ in 0..6 {
+            bus.publish_market(trade("BTCUSDT"));
+        }
+        assert_eq!(bus.metrics(EventKind::Market).max_queue_depth, 4);
+
+        // The two oldest events are skipped and counted
+        let mut received = 0;
+        while slow.try_recv().is_some() {
+            received += 1;
+        }
+        assert_eq!(received, 4);
+        assert_eq!(bus.metrics(EventKind::Market).dropped, 2);
+
+        drop(bus);
+        assert!(slow.recv().await.is_none());
+    }
+}
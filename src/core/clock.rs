@@ -0,0 +1,133 @@
+use crate::core::events::Timestamp;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Source of the current time
+///
+/// Components that stamp, expire or rate-limit take a clock instead of reading the
+/// system time directly, so tests and backtests can drive time themselves.
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Get the current wall-clock time
+    fn now_utc(&self) -> DateTime<Utc>;
+
+    /// Get the current monotonic time, for measuring intervals
+    fn now_instant(&self) -> Instant;
+
+    /// Get the current wall-clock time in milliseconds
+    fn now_millis(&self) -> Timestamp {
+        self.now_utc().timestamp_millis() as Timestamp
+    }
+}
+
+/// Clock shared between components
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_utc(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn now_instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Get a shared handle to the system clock, the default of every component
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// Clock that only moves when told to
+///
+/// Both the wall-clock and monotonic readings advance together from their start, so
+/// expiries and windows measured with either agree. Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct SimulatedClock {
+    start_utc: DateTime<Utc>,
+    start_instant: Instant,
+    /// Nanoseconds elapsed since the start
+    elapsed: Arc<AtomicU64>,
+}
+
+impl SimulatedClock {
+    /// Create a clock stopped at a wall-clock time
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            start_utc: start,
+            start_instant: Instant::now(),
+            elapsed: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Create a clock stopped at a time in milliseconds since the epoch
+    pub fn at_millis(millis: Timestamp) -> Self {
+        Self::new(DateTime::from_timestamp_millis(millis as i64).unwrap_or_default())
+    }
+
+    /// Get the time elapsed since the clock's start
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed.load(Ordering::SeqCst))
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, by: Duration) {
+        self.elapsed
+            .fetch_add(by.as_nanos() as u64, Ordering::SeqCst);
+    }
+
+    /// Move the clock forward to a time in milliseconds since the epoch
+    /// Times before the clock's current time are ignored, as the clock never goes back.
+    pub fn advance_to_millis(&self, millis: Timestamp) {
+        let target = (millis as i64 - self.start_utc.timestamp_millis()).max(0) as u64;
+        self.elapsed
+            .fetch_max(target.saturating_mul(1_000_000), Ordering::SeqCst);
+    }
+
+    /// Get a shared handle to this clock
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now_utc(&self) -> DateTime<Utc> {
+        self.start_utc + self.elapsed()
+    }
+
+    fn now_instant(&self) -> Instant {
+        self.start_instant + self.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulated_clock() {
+        let clock = SimulatedClock::at_millis(1_700_000_000_000);
+        let shared = clock.shared();
+        let started = shared.now_instant();
+        assert_eq!(shared.now_millis(), 1_700_000_000_000);
+
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(shared.now_millis(), 1_700_000_000_250);
+        assert_eq!(
+            shared.now_instant().duration_since(started),
+            Duration::from_millis(250)
+        );
+
+        // Never goes back
+        clock.advance_to_millis(1_700_000_000_100);
+        assert_eq!(shared.now_millis(), 1_700_000_000_250);
+        clock.advance_to_millis(1_700_000_001_000);
+        assert_eq!(clock.elapsed(), Duration::from_secs(1));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::types::{Price, Size, Symbol};
+use crate::orderbook::integrity::BookIntegrityError;
+use crate::types::{Notional, Price, Size, Symbol};
 use serde::{Deserialize, Serialize};
 
 /// Exchange identifier
@@ -22,8 +23,26 @@ pub enum OrderSide {
 pub enum OrderType {
     Market,
     Limit,
+    /// Market order released once the trigger price trades
     StopLoss,
+    /// Limit order released once the trigger price trades
     StopLimit,
+    /// Limit order showing only `display_size` on the book at a time
+    Iceberg {
+        display_size: Size,
+    },
+}
+
+impl OrderType {
+    /// Check if the order rests at a limit price
+    pub fn is_limit(&self) -> bool {
+        matches!(self, OrderType::Limit | OrderType::Iceberg { .. })
+    }
+
+    /// Check if the order waits for a trigger price
+    pub fn is_stop(&self) -> bool {
+        matches!(self, OrderType::StopLoss | OrderType::StopLimit)
+    }
 }
 
 /// Time in force
@@ -126,6 +145,230 @@ pub struct Trade {
     pub trade_id: Option<String>,
 }
 
+/// Funding rate of a perpetual futures contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FundingRate {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    /// Rate applied at the current funding interval (e.g. 0.0001 = 0.01%)
+    pub rate: rust_decimal::Decimal,
+    /// Predicted rate for the next funding interval, if published by the venue
+    pub predicted_rate: Option<rust_decimal::Decimal>,
+    /// Next funding time in milliseconds
+    pub next_funding_time: Timestamp,
+    /// Funding interval in hours
+    pub interval_hours: u32,
+    pub timestamp: Timestamp,
+}
+
+/// Margin borrow rate of an asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BorrowRate {
+    pub asset: String,
+    pub exchange_id: ExchangeId,
+    /// Hourly interest rate (e.g. 0.000005 = 0.0005% per hour)
+    pub hourly_rate: rust_decimal::Decimal,
+    pub timestamp: Timestamp,
+}
+
+/// Margin account type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MarginMode {
+    /// One account shared by all pairs
+    Cross,
+    /// A separate account per pair
+    Isolated,
+}
+
+/// Outstanding margin loan of an asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarginLiability {
+    pub asset: String,
+    pub borrowed: rust_decimal::Decimal,
+    /// Accrued interest not yet repaid
+    pub interest: rust_decimal::Decimal,
+}
+
+impl MarginLiability {
+    /// Amount needed to clear the loan
+    pub fn total(&self) -> rust_decimal::Decimal {
+        self.borrowed + self.interest
+    }
+}
+
+/// Snapshot of a cross or isolated margin account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginAccount {
+    pub exchange_id: ExchangeId,
+    pub mode: MarginMode,
+    /// Pair of an isolated account
+    pub symbol: Option<Symbol>,
+    pub balances: Vec<Balance>,
+    pub liabilities: Vec<MarginLiability>,
+    /// Total assets over total liabilities; None when nothing is borrowed
+    pub margin_level: Option<rust_decimal::Decimal>,
+}
+
+impl MarginAccount {
+    /// Outstanding loan (borrowed plus interest) of an asset
+    pub fn liability(&self, asset: &str) -> rust_decimal::Decimal {
+        self.liabilities
+            .iter()
+            .filter(|liability| liability.asset == asset)
+            .map(MarginLiability::total)
+            .sum()
+    }
+}
+
+/// Open interest of a futures contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenInterest {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    /// Open interest in contracts / base asset units
+    pub open_interest: Size,
+    pub timestamp: Timestamp,
+}
+
+/// Mark price of a perpetual futures contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkPrice {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    /// Price used for unrealized PnL and liquidation
+    pub mark_price: Price,
+    pub index_price: Price,
+    /// Funding rate of the running interval
+    pub funding_rate: rust_decimal::Decimal,
+    /// Next funding time in milliseconds
+    pub next_funding_time: Timestamp,
+    pub timestamp: Timestamp,
+}
+
+/// Best bid and ask of a symbol from a top-of-book feed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BestBidAsk {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    pub bid_price: Price,
+    pub bid_size: Size,
+    pub ask_price: Price,
+    pub ask_size: Size,
+    /// Venue order book update ID
+    pub update_id: u64,
+    pub timestamp: Timestamp,
+}
+
+/// Candlestick interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CandleInterval {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "3m")]
+    ThreeMinutes,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "15m")]
+    FifteenMinutes,
+    #[serde(rename = "30m")]
+    ThirtyMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "4h")]
+    FourHours,
+    #[serde(rename = "1d")]
+    OneDay,
+}
+
+impl CandleInterval {
+    /// Get the interval as exchanges write it, e.g. "1m"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CandleInterval::OneMinute => "1m",
+            CandleInterval::ThreeMinutes => "3m",
+            CandleInterval::FiveMinutes => "5m",
+            CandleInterval::FifteenMinutes => "15m",
+            CandleInterval::ThirtyMinutes => "30m",
+            CandleInterval::OneHour => "1h",
+            CandleInterval::FourHours => "4h",
+            CandleInterval::OneDay => "1d",
+        }
+    }
+
+    /// Get the interval length in milliseconds
+    pub fn duration_ms(&self) -> u64 {
+        const MINUTE: u64 = 60_000;
+        match self {
+            CandleInterval::OneMinute => MINUTE,
+            CandleInterval::ThreeMinutes => 3 * MINUTE,
+            CandleInterval::FiveMinutes => 5 * MINUTE,
+            CandleInterval::FifteenMinutes => 15 * MINUTE,
+            CandleInterval::ThirtyMinutes => 30 * MINUTE,
+            CandleInterval::OneHour => 60 * MINUTE,
+            CandleInterval::FourHours => 240 * MINUTE,
+            CandleInterval::OneDay => 1440 * MINUTE,
+        }
+    }
+}
+
+impl std::fmt::Display for CandleInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for CandleInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1m" => Ok(CandleInterval::OneMinute),
+            "3m" => Ok(CandleInterval::ThreeMinutes),
+            "5m" => Ok(CandleInterval::FiveMinutes),
+            "15m" => Ok(CandleInterval::FifteenMinutes),
+            "30m" => Ok(CandleInterval::ThirtyMinutes),
+            "1h" => Ok(CandleInterval::OneHour),
+            "4h" => Ok(CandleInterval::FourHours),
+            "1d" => Ok(CandleInterval::OneDay),
+            _ => Err(format!("Unknown candle interval: {}", s)),
+        }
+    }
+}
+
+/// OHLCV candle of one symbol over one interval
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Candle {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    pub interval: CandleInterval,
+    pub open_time: Timestamp,
+    pub close_time: Timestamp,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    /// Traded base volume
+    pub volume: Size,
+    /// Traded quote volume
+    pub quote_volume: rust_decimal::Decimal,
+    pub trade_count: u64,
+    /// The interval has ended; an open candle is updated until it closes
+    pub closed: bool,
+    pub timestamp: Timestamp,
+}
+
+/// Forced liquidation order on a futures venue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Liquidation {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    /// Side of the liquidation order (Sell = a long position was liquidated)
+    pub side: OrderSide,
+    pub price: Price,
+    pub size: Size,
+    pub timestamp: Timestamp,
+}
+
 /// New order
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NewOrder {
@@ -137,6 +380,10 @@ pub struct NewOrder {
     pub price: Option<Price>,
     pub size: Size,
     pub client_order_id: Option<String>,
+    /// Order size in the quote asset ("buy $500 of BTC"), used instead of `size` when set
+    /// Sent natively where the venue supports it, otherwise converted to a base size
+    #[serde(default)]
+    pub quote_size: Option<Notional>,
 }
 
 impl NewOrder {
@@ -156,6 +403,7 @@ impl NewOrder {
             price: Some(price),
             size,
             client_order_id: None,
+            quote_size: None,
         }
     }
 
@@ -175,6 +423,7 @@ impl NewOrder {
             price: Some(price),
             size,
             client_order_id: None,
+            quote_size: None,
         }
     }
 
@@ -189,6 +438,7 @@ impl NewOrder {
             price: None,
             size,
             client_order_id: None,
+            quote_size: None,
         }
     }
 
@@ -203,9 +453,54 @@ impl NewOrder {
             price: None,
             size,
             client_order_id: None,
+            quote_size: None,
         }
     }
 
+    /// Create a new market order for an amount of the quote asset
+    pub fn new_market_quote(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        quote_size: Notional,
+    ) -> Self {
+        Self {
+            symbol: Symbol::new(symbol),
+            exchange_id: "default".to_string(),
+            side,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::ImmediateOrCancel,
+            price: None,
+            size: Size::zero(),
+            client_order_id: None,
+            quote_size: Some(quote_size),
+        }
+    }
+
+    /// Convert a quote-sized order to a base size at `price`, rounded down to `step_size`
+    /// Returns the order unchanged if it is not quote-sized, None if the price is not positive
+    pub fn with_base_size_at(&self, price: Price, step_size: Option<Size>) -> Option<Self> {
+        let Some(quote_size) = self.quote_size else {
+            return Some(self.clone());
+        };
+        if !price.is_positive() {
+            return None;
+        }
+        let size = quote_size.to_size(price)?;
+        let size = match step_size {
+            Some(step) if step.is_positive() => {
+                Size::new((size.value() / step.value()).floor() * step.value())
+            }
+            _ => Size::new(
+                size.value()
+                    .round_dp_with_strategy(8, rust_decimal::RoundingStrategy::ToZero),
+            ),
+        };
+        let mut order = self.clone();
+        order.size = size;
+        order.quote_size = None;
+        Some(order)
+    }
+
     /// Set the client order ID (builder pattern)
     pub fn with_client_order_id(mut self, client_order_id: String) -> Self {
         self.client_order_id = Some(client_order_id);
@@ -217,6 +512,20 @@ impl NewOrder {
         self.exchange_id = exchange_id.into();
         self
     }
+
+    /// Turn a limit order into an iceberg showing `display_size` at a time (builder pattern)
+    pub fn with_display_size(mut self, display_size: Size) -> Self {
+        self.order_type = OrderType::Iceberg { display_size };
+        self
+    }
+
+    /// Get the visible size of an iceberg order
+    pub fn display_size(&self) -> Option<Size> {
+        match self.order_type {
+            OrderType::Iceberg { display_size } => Some(display_size),
+            _ => None,
+        }
+    }
 }
 
 /// Order
@@ -248,6 +557,9 @@ pub struct ExecutionReport {
     pub remaining_size: Size,
     pub average_price: Option<Price>,
     pub timestamp: Timestamp,
+    /// Why the exchange rejected the order, when `status` is `Rejected`
+    #[serde(default)]
+    pub reject_reason: Option<crate::core::reject::RejectReason>,
 }
 
 /// Balance
@@ -278,7 +590,9 @@ impl Balance {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TradingFees {
     pub symbol: String,
+    /// Maker fee rate (negative for a rebate)
     pub maker_fee: rust_decimal::Decimal,
+    /// Taker fee rate
     pub taker_fee: rust_decimal::Decimal,
 }
 
@@ -290,6 +604,112 @@ impl TradingFees {
             taker_fee: taker_fee.value(),
         }
     }
+
+    /// Check if the venue pays resting orders a rebate
+    pub fn has_maker_rebate(&self) -> bool {
+        self.maker_fee < rust_decimal::Decimal::ZERO
+    }
+
+    /// Fee on a fill's quote notional, negative if the fill earns a rebate
+    pub fn fee(&self, notional: rust_decimal::Decimal, maker: bool) -> rust_decimal::Decimal {
+        let rate = if maker {
+            self.maker_fee
+        } else {
+            self.taker_fee
+        };
+        notional * rate
+    }
+
+    /// Maker fees on buying and selling one unit at a price, negative with a rebate
+    pub fn round_trip_maker_cost(&self, price: Price) -> rust_decimal::Decimal {
+        price.value() * self.maker_fee * rust_decimal::Decimal::TWO
+    }
+}
+
+/// Fee charged by an exchange for a single fill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeRecord {
+    pub trade_id: String,
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    /// Fee paid, positive; rebates are negative
+    pub fee: rust_decimal::Decimal,
+    pub fee_asset: String,
+    pub timestamp: Timestamp,
+}
+
+/// A fill as reported by the exchange's own trade history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeFill {
+    pub trade_id: String,
+    pub order_id: OrderId,
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    pub side: OrderSide,
+    pub price: Price,
+    pub size: Size,
+    /// Fee paid, positive; rebates are negative
+    pub fee: rust_decimal::Decimal,
+    pub fee_asset: String,
+    pub timestamp: Timestamp,
+}
+
+impl ExchangeFill {
+    /// Get the fee charged for this fill
+    pub fn fee_record(&self) -> FeeRecord {
+        FeeRecord {
+            trade_id: self.trade_id.clone(),
+            symbol: self.symbol.clone(),
+            exchange_id: self.exchange_id.clone(),
+            fee: self.fee,
+            fee_asset: self.fee_asset.clone(),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// A position as reported by the exchange
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangePosition {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    /// Position size (positive for long, negative for short)
+    pub size: Size,
+    pub entry_price: Option<Price>,
+}
+
+/// A local order book found out of sync with the venue
+///
+/// Strategies should stop trading the symbol until a fresh snapshot arrives.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookDesync {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    pub error: BookIntegrityError,
+    pub timestamp: Timestamp,
+}
+
+/// Analytics derived from an order book after an update
+///
+/// Emitted as `MarketEvent::BookMetrics` following the snapshot or delta it was
+/// computed from (see `orderbook::BookMetricsStream`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookMetrics {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    pub best_bid: Price,
+    pub best_ask: Price,
+    pub mid_price: Price,
+    /// Touch prices weighted by the opposite side's size
+    pub microprice: Price,
+    pub spread_bps: rust_decimal::Decimal,
+    /// Size imbalance over `depth_levels`, from -1 (all asks) to 1 (all bids)
+    pub imbalance: rust_decimal::Decimal,
+    pub depth_levels: usize,
+    /// Average sweep price of `vwap_size` on both sides, if the book is deep enough
+    pub depth_weighted_mid: Option<Price>,
+    pub vwap_size: Size,
+    pub timestamp: Timestamp,
 }
 
 /// Market event
@@ -298,6 +718,88 @@ pub enum MarketEvent {
     OrderBookSnapshot(OrderBookSnapshot),
     OrderBookDelta(OrderBookDelta),
     Trade(Trade),
+    BestBidAsk(BestBidAsk),
+    Candle(Candle),
+    Liquidation(Liquidation),
+    OpenInterest(OpenInterest),
+    MarkPrice(MarkPrice),
+    Desync(BookDesync),
+    BookMetrics(BookMetrics),
+}
+
+impl MarketEvent {
+    /// Get the exchange the event originated from
+    pub fn exchange_id(&self) -> &str {
+        match self {
+            MarketEvent::OrderBookSnapshot(snapshot) => &snapshot.exchange_id,
+            MarketEvent::OrderBookDelta(delta) => &delta.exchange_id,
+            MarketEvent::Trade(trade) => &trade.exchange_id,
+            MarketEvent::BestBidAsk(quote) => &quote.exchange_id,
+            MarketEvent::Candle(candle) => &candle.exchange_id,
+            MarketEvent::Liquidation(liquidation) => &liquidation.exchange_id,
+            MarketEvent::OpenInterest(open_interest) => &open_interest.exchange_id,
+            MarketEvent::MarkPrice(mark_price) => &mark_price.exchange_id,
+            MarketEvent::Desync(desync) => &desync.exchange_id,
+            MarketEvent::BookMetrics(metrics) => &metrics.exchange_id,
+        }
+    }
+
+    /// Get the symbol the event is for
+    pub fn symbol(&self) -> &str {
+        match self {
+            MarketEvent::OrderBookSnapshot(snapshot) => snapshot.symbol.value(),
+            MarketEvent::OrderBookDelta(delta) => delta.symbol.value(),
+            MarketEvent::Trade(trade) => trade.symbol.value(),
+            MarketEvent::BestBidAsk(quote) => quote.symbol.value(),
+            MarketEvent::Candle(candle) => candle.symbol.value(),
+            MarketEvent::Liquidation(liquidation) => liquidation.symbol.value(),
+            MarketEvent::OpenInterest(open_interest) => open_interest.symbol.value(),
+            MarketEvent::MarkPrice(mark_price) => mark_price.symbol.value(),
+            MarketEvent::Desync(desync) => desync.symbol.value(),
+            MarketEvent::BookMetrics(metrics) => metrics.symbol.value(),
+        }
+    }
+
+    /// Get the exchange event timestamp in milliseconds
+    pub fn timestamp(&self) -> Timestamp {
+        match self {
+            MarketEvent::OrderBookSnapshot(snapshot) => snapshot.timestamp,
+            MarketEvent::OrderBookDelta(delta) => delta.timestamp,
+            MarketEvent::Trade(trade) => trade.timestamp,
+            MarketEvent::BestBidAsk(quote) => quote.timestamp,
+            MarketEvent::Candle(candle) => candle.timestamp,
+            MarketEvent::Liquidation(liquidation) => liquidation.timestamp,
+            MarketEvent::OpenInterest(open_interest) => open_interest.timestamp,
+            MarketEvent::MarkPrice(mark_price) => mark_price.timestamp,
+            MarketEvent::Desync(desync) => desync.timestamp,
+            MarketEvent::BookMetrics(metrics) => metrics.timestamp,
+        }
+    }
+}
+
+/// How close a dated contract is to delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ExpiryStage {
+    /// Inside the warning window before delivery
+    Approaching,
+    /// At or past delivery
+    Expired,
+}
+
+/// A position held in a dated contract that is nearing delivery
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpiryEvent {
+    pub symbol: Symbol,
+    pub exchange_id: ExchangeId,
+    pub stage: ExpiryStage,
+    /// Delivery time in milliseconds
+    pub expiry: Timestamp,
+    /// Milliseconds left until delivery
+    pub time_to_expiry: u64,
+    /// Position size (positive for long, negative for short)
+    pub position: Size,
+    /// Next contract in the series to roll into, if listed
+    pub next_contract: Option<Symbol>,
 }
 
 /// Trading event
@@ -306,6 +808,19 @@ pub enum TradingEvent {
     OrderCreated(NewOrder),
     OrderUpdated(Order),
     ExecutionReport(ExecutionReport),
+    InstrumentExpiry(ExpiryEvent),
+}
+
+impl TradingEvent {
+    /// Get the symbol the event is for
+    pub fn symbol(&self) -> &str {
+        match self {
+            TradingEvent::OrderCreated(order) => order.symbol.value(),
+            TradingEvent::OrderUpdated(order) => order.symbol.value(),
+            TradingEvent::ExecutionReport(report) => report.symbol.value(),
+            TradingEvent::InstrumentExpiry(expiry) => expiry.symbol.value(),
+        }
+    }
 }
 
 /// System event
@@ -314,6 +829,10 @@ pub enum SystemEvent {
     ExchangeConnected(ExchangeId),
     ExchangeDisconnected(ExchangeId),
     Error(String),
+    /// The kill switch tripped, with the reason
+    TradingHalted(String),
+    /// The kill switch was reset
+    TradingResumed,
 }
 
 /// Signal generated by strategy
@@ -417,6 +936,7 @@ mod tests {
             price: Some(price),
             size,
             client_order_id: Some("client-123".to_string()),
+            quote_size: None,
         };
 
         assert_eq!(order.symbol, symbol);
@@ -425,6 +945,39 @@ mod tests {
         assert_eq!(order.price, Some(price));
     }
 
+    #[test]
+    fn test_quote_sized_order() {
+        let order = NewOrder::new_market_quote(
+            "BTCUSDT",
+            OrderSide::Buy,
+            Notional::new(rust_decimal::Decimal::new(500, 0)),
+        );
+        assert!(order.size.is_zero());
+
+        // $500 at 30000 is 0.01666..., rounded down to the 0.001 step
+        let price = Price::new(rust_decimal::Decimal::new(30000, 0));
+        let step = Size::new(rust_decimal::Decimal::new(1, 3));
+        let converted = order.with_base_size_at(price, Some(step)).unwrap();
+        assert_eq!(converted.size, Size::new(rust_decimal::Decimal::new(16, 3)));
+        assert_eq!(converted.quote_size, None);
+
+        let converted = order.with_base_size_at(price, None).unwrap();
+        assert_eq!(
+            converted.size,
+            Size::new(rust_decimal::Decimal::new(1666666, 8))
+        );
+        assert!(order.with_base_size_at(Price::zero(), None).is_none());
+
+        // Orders without a quote size pass through
+        let base = NewOrder::new_market_buy("BTCUSDT", step);
+        assert_eq!(base.with_base_size_at(price, None), Some(base.clone()));
+
+        // Older serialized orders have no quote size
+        let mut json = serde_json::to_value(&base).unwrap();
+        json.as_object_mut().unwrap().remove("quote_size");
+        assert_eq!(serde_json::from_value::<NewOrder>(json).unwrap(), base);
+    }
+
     #[test]
     fn test_market_event() {
         let symbol = Symbol::new("BTCUSDT");
@@ -467,6 +1020,7 @@ mod tests {
             price: Some(price),
             size,
             client_order_id: Some("client-123".to_string()),
+            quote_size: None,
         };
 
         let signal = Signal::PlaceOrder { order };
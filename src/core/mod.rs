@@ -1,3 +1,9 @@
+pub mod bus;
+pub mod clock;
 pub mod events;
+pub mod reject;
 
+pub use bus::{BusMetrics, EventBus, EventKind, Subscription};
+pub use clock::{system_clock, Clock, SharedClock, SimulatedClock, SystemClock};
 pub use events::*;
+pub use reject::{parse_okx_reject, ExchangeReject, RejectReason};
@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Why an exchange refused an order request, normalized across venues
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectReason {
+    /// Not enough free balance or margin
+    InsufficientBalance,
+    /// Price or size not on the instrument's tick or lot grid
+    InvalidPrecision,
+    /// Size or notional under the venue minimum
+    BelowMinimum,
+    /// Price too far from the market (percent price bands)
+    PriceOutOfBounds,
+    /// A post-only order would have taken liquidity
+    PostOnlyWouldCross,
+    /// Request or order rate limit hit
+    RateLimited,
+    /// A reduce-only order would have opened or grown a position
+    ReduceOnlyRejected,
+    /// The order to cancel or query is not known to the venue
+    UnknownOrder,
+    /// The symbol is not listed or not tradable on the venue
+    UnknownSymbol,
+    /// The client order ID is already in use
+    DuplicateOrder,
+    /// The request timestamp fell outside the venue's receive window
+    TimestampOutOfWindow,
+    /// Bad API key, signature or permissions
+    Unauthorized,
+    /// Trading is halted or the market is closed
+    MarketClosed,
+    /// Any code not mapped above
+    Other,
+}
+
+impl RejectReason {
+    /// Check if the same request may succeed when sent again later unchanged
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            RejectReason::RateLimited | RejectReason::TimestampOutOfWindow
+        )
+    }
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RejectReason::InsufficientBalance => "insufficient balance",
+            RejectReason::InvalidPrecision => "invalid precision",
+            RejectReason::BelowMinimum => "below minimum",
+            RejectReason::PriceOutOfBounds => "price out of bounds",
+            RejectReason::PostOnlyWouldCross => "post-only would cross",
+            RejectReason::RateLimited => "rate limited",
+            RejectReason::ReduceOnlyRejected => "reduce-only rejected",
+            RejectReason::UnknownOrder => "unknown order",
+            RejectReason::UnknownSymbol => "unknown symbol",
+            RejectReason::DuplicateOrder => "duplicate order",
+            RejectReason::TimestampOutOfWindow => "timestamp out of window",
+            RejectReason::Unauthorized => "unauthorized",
+            RejectReason::MarketClosed => "market closed",
+            RejectReason::Other => "other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Map an OKX order result (`sCode`, `sMsg`) to a normalized reject reason
+///
+/// Codes OKX does not document for a single cause fall back to the message.
+pub fn parse_okx_reject(s_code: &str, s_msg: &str) -> RejectReason {
+    let msg = s_msg.to_ascii_lowercase();
+    match s_code {
+        "50011" | "50061" => RejectReason::RateLimited,
+        "50102" => RejectReason::TimestampOutOfWindow,
+        "50105" | "50110" | "50111" | "50113" => RejectReason::Unauthorized,
+        "51001" => RejectReason::UnknownSymbol,
+        "51006" => RejectReason::PriceOutOfBounds,
+        "51008" | "51131" => RejectReason::InsufficientBalance,
+        "51016" => RejectReason::DuplicateOrder,
+        "51020" => RejectReason::BelowMinimum,
+        "51121" => RejectReason::InvalidPrecision,
+        "51169" => RejectReason::ReduceOnlyRejected,
+        "51400" | "51603" => RejectReason::UnknownOrder,
+        _ if msg.contains("post only") || msg.contains("post_only") => {
+            RejectReason::PostOnlyWouldCross
+        }
+        _ if msg.contains("insufficient") => RejectReason::InsufficientBalance,
+        _ => RejectReason::Other,
+    }
+}
+
+/// An exchange's refusal of an order request, with its raw code and message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeReject {
+    pub exchange_id: String,
+    pub reason: RejectReason,
+    /// Venue error code, e.g. Binance `-2010`
+    pub code: String,
+    pub message: String,
+}
+
+impl ExchangeReject {
+    /// Find the reject behind an error, following its `source` chain
+    /// Exchange error types wrap their rejects, so this works on boxed errors too.
+    pub fn find<'a>(error: &'a (dyn std::error::Error + 'static)) -> Option<&'a ExchangeReject> {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(reject) = error.downcast_ref::<ExchangeReject>() {
+                return Some(reject);
+            }
+            current = error.source();
+        }
+        None
+    }
+}
+
+impl fmt::Display for ExchangeReject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} rejected the request ({}, code {}): {}",
+            self.exchange_id, self.reason, self.code, self.message
+        )
+    }
+}
+
+impl std::error::Error for ExchangeReject {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_okx_reject() {
+        let cases = [
+            ("50011", "Rate limit reached. Please refer to API documentation and throttle requests accordingly.", RejectReason::RateLimited),
+            ("50102", "Timestamp request expired", RejectReason::TimestampOutOfWindow),
+            ("50113", "Invalid Sign", RejectReason::Unauthorized),
+            ("51001", "Instrument ID does not exist", RejectReason::UnknownSymbol),
+            ("51006", "Order price is not within the price limit (max buy price: 50500; min sell price: 49500)", RejectReason::PriceOutOfBounds),
+            ("51008", "Order failed. Insufficient USDT balance in account", RejectReason::InsufficientBalance),
+            ("51016", "Duplicated clOrdId", RejectReason::DuplicateOrder),
+            ("51020", "Your order should meet or exceed the minimum order amount.", RejectReason::BelowMinimum),
+            ("51121", "Order quantity must be a multiple of the lot size.", RejectReason::InvalidPrecision),
+            ("51169", "Order failed because you don't have any positions in this direction for this contract to reduce or close.", RejectReason::ReduceOnlyRejected),
+            ("51400", "Cancellation failed as the order has been filled, canceled or does not exist.", RejectReason::UnknownOrder),
+            ("51000", "Parameter ordType error", RejectReason::Other),
+            ("", "", RejectReason::Other),
+        ];
+        for (code, msg, reason) in cases {
+            assert_eq!(parse_okx_reject(code, msg), reason, "sCode {}", code);
+        }
+        assert!(parse_okx_reject("50011", "").is_retryable());
+    }
+}
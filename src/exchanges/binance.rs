@@ -1,39 +1,145 @@
-use crate::core::events::{OrderBookLevel, OrderBookSnapshot};
+use crate::connectors::binance::DepthUpdateMessage;
+use crate::core::events::{
+    BookDesync, BorrowRate, Candle, CandleInterval, ExchangeFill, FeeRecord, FundingRate,
+    MarginAccount, MarginLiability, MarginMode, OpenInterest, OrderBookLevel, OrderBookSnapshot,
+};
+use crate::core::reject::{ExchangeReject, RejectReason};
+use crate::exchanges::session_safety::SessionSafety;
+use crate::exchanges::time_sync::TimeOffset;
+use crate::monitoring::SymbolStatsRegistry;
+use crate::oms::{OrderSymbolRegistry, RateLimitKind, WeightedRateLimiter};
+use crate::orderbook::BookIntegrityError;
+use crate::realtime::DeadLetterQueue;
+use crate::security::{ApiCredentials, CredentialLease, SharedCredentials};
 use crate::traits::{
-    Balance, ExecutionClient, ExecutionReport, MarketDataHistory, MarketDataStream, MarketEvent,
-    NewOrder, OrderId, OrderSide, OrderStatus, OrderType, TimeInForce, Trade, TradingFees,
+    Balance, ExecutionClient, ExecutionReport, FeeHistory, MarketDataHistory, MarketDataStream,
+    MarketEvent, NewOrder, OrderId, OrderSide, OrderStatus, OrderType, ServerClock, TimeInForce,
+    Trade, TradeHistory, TradingFees,
 };
-use crate::types::{Price, Size};
+use crate::types::instrument::{format_price_plain, format_quote_plain, format_size_plain};
+use crate::types::{InstrumentSpec, Notional, Price, Size, Symbol};
 use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
 use futures_util::StreamExt;
 use hmac::{Hmac, Mac};
-use reqwest::Client;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use rust_decimal::Decimal;
 use serde_json::Value;
 use sha2::Sha256;
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::{Mutex, RwLock};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+/// Maximum span of an aggTrades query that sets both startTime and endTime
+const AGG_TRADES_WINDOW_MS: u64 = 60 * 60 * 1000;
+/// Maximum number of trades per aggTrades page
+const AGG_TRADES_PAGE_LIMIT: usize = 1000;
+/// Maximum number of candles per klines page
+const KLINES_PAGE_LIMIT: usize = 1000;
+/// Maximum span of a myTrades query that sets both startTime and endTime
+const MY_TRADES_WINDOW_MS: u64 = 24 * 60 * 60 * 1000;
+/// Maximum number of records per myTrades / income page
+const ACCOUNT_HISTORY_PAGE_LIMIT: usize = 1000;
+/// Spot REST request weight allowed per minute
+const REQUEST_WEIGHT_LIMIT: usize = 6000;
+/// USD-M futures REST request weight allowed per minute
+const FUTURES_REQUEST_WEIGHT_LIMIT: usize = 2400;
+/// Number of retries after a 429/418 rate-limit response
+const RATE_LIMIT_RETRIES: u32 = 3;
+/// Default validity of a signed request after its timestamp
+const DEFAULT_RECV_WINDOW_MS: u64 = 5000;
+/// Largest recvWindow Binance accepts
+const MAX_RECV_WINDOW_MS: u64 = 60_000;
+
+/// Cached historical trades by (symbol, window start)
+type TradeCache = HashMap<(String, u64), Vec<Trade>>;
+
+/// Binance market an adapter trades on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarketType {
+    /// Spot (and margin) via `api.binance.com`
+    #[default]
+    Spot,
+    /// USD-M perpetual futures via `fapi.binance.com`
+    UsdFutures,
+}
+
+/// USD-M futures position mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionMode {
+    /// A single net position per symbol
+    #[default]
+    OneWay,
+    /// Separate long and short positions per symbol
+    Hedge,
+}
+
+/// Automatic borrowing / repayment applied to margin orders (`sideEffectType`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarginSideEffect {
+    /// Trade only with assets already in the margin account
+    #[default]
+    NoSideEffect,
+    /// Borrow whatever the order needs
+    MarginBuy,
+    /// Repay loans with the order proceeds
+    AutoRepay,
+    /// Borrow for the order and repay with its proceeds
+    AutoBorrowRepay,
+}
+
+impl MarginSideEffect {
+    /// Binance parameter value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarginSideEffect::NoSideEffect => "NO_SIDE_EFFECT",
+            MarginSideEffect::MarginBuy => "MARGIN_BUY",
+            MarginSideEffect::AutoRepay => "AUTO_REPAY",
+            MarginSideEffect::AutoBorrowRepay => "AUTO_BORROW_REPAY",
+        }
+    }
+}
+
 /// Binance API client for market data and order execution
 #[allow(dead_code)]
 pub struct BinanceClient {
-    /// API key
-    api_key: String,
-    /// API secret
-    api_secret: String,
+    /// API key and secret, shared with the key manager so they can be rotated
+    credentials: SharedCredentials,
     /// Base URL for REST API
     rest_url: String,
+    /// Base URL for the USD-M futures REST API
+    futures_rest_url: String,
     /// Base URL for WebSocket
     ws_url: String,
     /// HTTP client
     http_client: Client,
-    /// Last update timestamps for each symbol
-    last_updates: Arc<RwLock<HashMap<String, u64>>>,
     /// Current connection status
     connected: Arc<RwLock<bool>>,
+    /// Instrument trading rules used to format order parameters
+    instruments: Arc<RwLock<HashMap<String, InstrumentSpec>>>,
+    /// Completed hourly windows of historical trades
+    trade_cache: Arc<RwLock<TradeCache>>,
+    /// Trade on the cross or isolated margin account instead of spot
+    margin_mode: Option<MarginMode>,
+    /// Borrow / repay behaviour of margin orders
+    margin_side_effect: MarginSideEffect,
+    /// Spot or USD-M futures endpoints
+    market_type: MarketType,
+    /// Futures position mode last set or fetched
+    position_mode: Arc<RwLock<PositionMode>>,
+    /// Request weight and order rate limits, shared by clients of the same API key
+    rate_limiter: Arc<WeightedRateLimiter>,
+    /// Offset of the server clock added to local time when signing requests
+    time_offset: TimeOffset,
+    /// Whether the offset has been measured, or is kept up to date by `TimeSync`
+    time_synced: AtomicBool,
+    /// How long a signed request stays valid after its timestamp
+    recv_window_ms: u64,
 }
 
 impl BinanceClient {
@@ -50,21 +156,202 @@ impl BinanceClient {
                 "wss://stream.binance.com/ws".to_string(),
             )
         };
+        let futures_rest_url = if testnet {
+            "https://testnet.binancefuture.com".to_string()
+        } else {
+            "https://fapi.binance.com".to_string()
+        };
 
         Self {
-            api_key,
-            api_secret,
+            credentials: SharedCredentials::new(ApiCredentials::new(api_key, api_secret)),
             rest_url,
+            futures_rest_url,
             ws_url,
             http_client: Client::new(),
-            last_updates: Arc::new(RwLock::new(HashMap::new())),
             connected: Arc::new(RwLock::new(false)),
+            instruments: Arc::new(RwLock::new(HashMap::new())),
+            trade_cache: Arc::new(RwLock::new(HashMap::new())),
+            margin_mode: None,
+            margin_side_effect: MarginSideEffect::default(),
+            market_type: MarketType::default(),
+            position_mode: Arc::new(RwLock::new(PositionMode::default())),
+            rate_limiter: Arc::new(binance_rate_limiter(MarketType::default())),
+            time_offset: TimeOffset::new(),
+            time_synced: AtomicBool::new(false),
+            recv_window_ms: DEFAULT_RECV_WINDOW_MS,
+        }
+    }
+
+    /// Sign with rotatable credentials from `ApiKeyManager::register_account` (builder pattern)
+    pub fn with_credentials(mut self, credentials: SharedCredentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self
+    }
+
+    /// Override the USD-M futures REST base URL (builder pattern)
+    pub fn with_futures_rest_url(mut self, futures_rest_url: impl Into<String>) -> Self {
+        self.futures_rest_url = futures_rest_url.into();
+        self
+    }
+
+    /// Trade on spot or USD-M futures (builder pattern)
+    /// Also switches to the market's rate limits, so share a limiter afterwards.
+    pub fn with_market_type(mut self, market_type: MarketType) -> Self {
+        self.market_type = market_type;
+        self.rate_limiter = Arc::new(binance_rate_limiter(market_type));
+        self
+    }
+
+    /// Share a rate limiter with other clients of the same API key (builder pattern)
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<WeightedRateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Stamp signed requests with an offset kept up to date by `TimeSync`, instead of
+    /// measuring it on the first signed request (builder pattern)
+    pub fn with_time_offset(mut self, time_offset: TimeOffset) -> Self {
+        self.time_offset = time_offset;
+        self.time_synced = AtomicBool::new(true);
+        self
+    }
+
+    /// Set how long signed requests stay valid after their timestamp (builder pattern)
+    /// Binance caps it at 60 seconds; the default is 5 seconds.
+    pub fn with_recv_window(mut self, recv_window: Duration) -> Self {
+        self.recv_window_ms = (recv_window.as_millis() as u64).min(MAX_RECV_WINDOW_MS);
+        self
+    }
+
+    /// Get the offset signed requests are stamped with
+    pub fn time_offset(&self) -> TimeOffset {
+        self.time_offset.clone()
+    }
+
+    /// Get the rate limiter every REST call goes through
+    pub fn rate_limiter(&self) -> Arc<WeightedRateLimiter> {
+        self.rate_limiter.clone()
+    }
+
+    /// Market the client trades on
+    pub fn market_type(&self) -> MarketType {
+        self.market_type
+    }
+
+    /// REST base URL of the configured market
+    fn rest_base(&self) -> &str {
+        match self.market_type {
+            MarketType::Spot => &self.rest_url,
+            MarketType::UsdFutures => &self.futures_rest_url,
+        }
+    }
+
+    /// Exchange ID reported on orders and balances
+    fn exchange_id(&self) -> &'static str {
+        match self.market_type {
+            MarketType::Spot => "binance",
+            MarketType::UsdFutures => "binance_futures",
+        }
+    }
+
+    /// Route orders and balances through a margin account (builder pattern)
+    pub fn with_margin_mode(mut self, mode: MarginMode) -> Self {
+        self.margin_mode = Some(mode);
+        self
+    }
+
+    /// Set the borrow / repay behaviour of margin orders (builder pattern)
+    pub fn with_margin_side_effect(mut self, side_effect: MarginSideEffect) -> Self {
+        self.margin_side_effect = side_effect;
+        self
+    }
+
+    /// Margin account the client trades on, if any
+    pub fn margin_mode(&self) -> Option<MarginMode> {
+        self.margin_mode
+    }
+
+    /// Order endpoint and the extra parameters margin mode adds to it
+    fn order_endpoint(&self) -> (&'static str, Vec<(String, String)>) {
+        if self.market_type == MarketType::UsdFutures {
+            return ("/fapi/v1/order", Vec::new());
+        }
+        match self.margin_mode {
+            Some(mode) => (
+                "/sapi/v1/margin/order",
+                vec![(
+                    "isIsolated".to_string(),
+                    if mode == MarginMode::Isolated {
+                        "TRUE"
+                    } else {
+                        "FALSE"
+                    }
+                    .to_string(),
+                )],
+            ),
+            None => ("/api/v3/order", Vec::new()),
+        }
+    }
+
+    /// Set the trading rules for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        let mut instruments = self.instruments.write().await;
+        instruments.insert(spec.symbol.value().to_string(), spec);
+    }
+
+    /// Get the trading rules for an instrument
+    pub async fn get_instrument_spec(&self, symbol: &str) -> Option<InstrumentSpec> {
+        let instruments = self.instruments.read().await;
+        instruments.get(symbol).cloned()
+    }
+
+    /// Load trading rules for all symbols from exchange info
+    pub async fn load_instrument_specs(&self) -> Result<usize, BinanceError> {
+        let exchange_info = self.get_exchange_info().await?;
+        let specs = parse_instrument_specs(&exchange_info);
+        let count = specs.len();
+
+        let mut instruments = self.instruments.write().await;
+        for spec in specs {
+            instruments.insert(spec.symbol.value().to_string(), spec);
+        }
+
+        Ok(count)
+    }
+
+    /// Format order price and quantity using the instrument's tick and step size
+    async fn format_order_params(&self, order: &NewOrder) -> (Option<String>, String) {
+        let instruments = self.instruments.read().await;
+        match instruments.get(order.symbol.as_str()) {
+            Some(spec) => (
+                order.price.map(|price| spec.format_price(price)),
+                spec.format_size(order.size),
+            ),
+            None => (
+                order.price.map(format_price_plain),
+                format_size_plain(order.size),
+            ),
+        }
+    }
+
+    /// Format a quote-asset order amount using the instrument's price precision
+    async fn format_quote_param(&self, symbol: &str, quote_size: Notional) -> String {
+        match self.instruments.read().await.get(symbol) {
+            Some(spec) => spec.format_quote_size(quote_size),
+            None => format_quote_plain(quote_size),
         }
     }
 
     /// Generate signature for API request
-    fn sign(&self, query_string: &str) -> String {
-        let mut mac = Hmac::<Sha256>::new_from_slice(self.api_secret.as_bytes())
+    /// The request must send the lease's API key and hold the lease until it completes.
+    fn sign(credentials: &CredentialLease, query_string: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(credentials.api_secret().as_bytes())
             .expect("HMAC can take key of any size");
         mac.update(query_string.as_bytes());
         let result = mac.finalize();
@@ -74,13 +361,12 @@ impl BinanceClient {
 
     /// Get current server time
     pub async fn get_server_time(&self) -> Result<u64, BinanceError> {
-        let url = format!("{}/api/v3/time", self.rest_url);
-        let response = self
-            .http_client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
+        let url = match self.market_type {
+            MarketType::Spot => format!("{}/api/v3/time", self.rest_url),
+            MarketType::UsdFutures => format!("{}/fapi/v1/time", self.futures_rest_url),
+        };
+        let request = self.http_client.get(&url);
+        let response = self.send(request, &url, false).await?;
 
         if !response.status().is_success() {
             return Err(BinanceError::ApiError(format!(
@@ -102,12 +388,8 @@ impl BinanceClient {
     /// Get exchange information for symbols
     pub async fn get_exchange_info(&self) -> Result<Value, BinanceError> {
         let url = format!("{}/api/v3/exchangeInfo", self.rest_url);
-        let response = self
-            .http_client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
+        let request = self.http_client.get(&url);
+        let response = self.send(request, &url, false).await?;
 
         if !response.status().is_success() {
             return Err(BinanceError::ApiError(format!(
@@ -133,12 +415,8 @@ impl BinanceClient {
             self.rest_url, symbol, limit
         );
 
-        let response = self
-            .http_client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
+        let request = self.http_client.get(&url);
+        let response = self.send(request, &url, false).await?;
 
         if !response.status().is_success() {
             return Err(BinanceError::ApiError(format!(
@@ -206,9 +484,504 @@ impl BinanceClient {
         ))
     }
 
+    /// Get historical trades for a symbol between two timestamps (inclusive)
+    ///
+    /// Trades are pulled from `/api/v3/aggTrades` in hourly windows, paging by trade ID
+    /// inside a window. Completed windows are cached, so repeated warm-ups and backtests
+    /// over the same range do not hit the API again.
+    pub async fn get_historical_trades(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<Trade>, BinanceError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        let mut trades = Vec::new();
+        let mut window_start = start_time - start_time % AGG_TRADES_WINDOW_MS;
+        while window_start <= end_time {
+            let window_end = window_start + AGG_TRADES_WINDOW_MS - 1;
+            let key = (symbol.to_string(), window_start);
+
+            let cached = self.trade_cache.read().await.get(&key).cloned();
+            let window_trades = match cached {
+                Some(window_trades) => window_trades,
+                None => {
+                    let window_trades = self
+                        .fetch_agg_trades_window(symbol, window_start, window_end)
+                        .await?;
+                    // Only windows that are fully in the past are final
+                    if window_end < now {
+                        self.trade_cache
+                            .write()
+                            .await
+                            .insert(key, window_trades.clone());
+                    }
+                    window_trades
+                }
+            };
+
+            trades.extend(
+                window_trades
+                    .into_iter()
+                    .filter(|t| t.timestamp >= start_time && t.timestamp <= end_time),
+            );
+            window_start += AGG_TRADES_WINDOW_MS;
+        }
+
+        Ok(trades)
+    }
+
+    /// Get candles opened between two timestamps (inclusive), oldest first
+    ///
+    /// Pages through `/api/v3/klines` (or `/fapi/v1/klines` for futures) 1000 candles at
+    /// a time. The last candle is still open if its interval has not ended.
+    pub async fn get_klines(
+        &self,
+        symbol: &str,
+        interval: CandleInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<Candle>, BinanceError> {
+        let endpoint = match self.market_type {
+            MarketType::Spot => "/api/v3/klines",
+            MarketType::UsdFutures => "/fapi/v1/klines",
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        let mut candles: Vec<Candle> = Vec::new();
+        let mut page_start = start_time;
+        while page_start <= end_time {
+            let url = format!(
+                "{}{}?symbol={}&interval={}&startTime={}&endTime={}&limit={}",
+                self.rest_base(),
+                endpoint,
+                symbol,
+                interval,
+                page_start,
+                end_time,
+                KLINES_PAGE_LIMIT
+            );
+            let page = parse_klines(
+                symbol,
+                self.exchange_id(),
+                interval,
+                &self.get_rate_limited(&url, None).await?,
+                now,
+            );
+            let page_len = page.len();
+            match page.last() {
+                Some(last) if page_len == KLINES_PAGE_LIMIT => {
+                    page_start = last.open_time + interval.duration_ms()
+                }
+                _ => page_start = end_time + 1,
+            }
+            candles.extend(page);
+        }
+
+        Ok(candles)
+    }
+
+    /// Clear the historical trade cache
+    pub async fn clear_trade_cache(&self) {
+        self.trade_cache.write().await.clear();
+    }
+
+    /// Fetch all aggregated trades of one window, following trade ID pagination
+    async fn fetch_agg_trades_window(
+        &self,
+        symbol: &str,
+        window_start: u64,
+        window_end: u64,
+    ) -> Result<Vec<Trade>, BinanceError> {
+        let mut url = format!(
+            "{}/api/v3/aggTrades?symbol={}&startTime={}&endTime={}&limit={}",
+            self.rest_url, symbol, window_start, window_end, AGG_TRADES_PAGE_LIMIT
+        );
+
+        let mut trades = Vec::new();
+        loop {
+            let page = parse_agg_trades(symbol, &self.get_rate_limited(&url, None).await?);
+            let page_len = page.len();
+            let last_id = page.last().map(|(id, _)| *id);
+
+            trades.extend(
+                page.into_iter()
+                    .map(|(_, trade)| trade)
+                    .take_while(|t| t.timestamp <= window_end),
+            );
+
+            match last_id {
+                Some(last_id)
+                    if page_len == AGG_TRADES_PAGE_LIMIT
+                        && trades.last().is_some_and(|t| t.timestamp < window_end) =>
+                {
+                    url = format!(
+                        "{}/api/v3/aggTrades?symbol={}&fromId={}&limit={}",
+                        self.rest_url,
+                        symbol,
+                        last_id + 1,
+                        AGG_TRADES_PAGE_LIMIT
+                    );
+                }
+                _ => break,
+            }
+        }
+
+        Ok(trades)
+    }
+
+    /// Get the fees charged for spot fills from `/api/v3/myTrades`
+    pub async fn get_my_trades(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<FeeRecord>, BinanceError> {
+        Ok(self
+            .get_my_trade_fills(symbol, start_time, end_time)
+            .await?
+            .iter()
+            .map(ExchangeFill::fee_record)
+            .collect())
+    }
+
+    /// Get spot fills from `/api/v3/myTrades`
+    ///
+    /// The range is queried in 24h windows (the endpoint's maximum), paging by trade ID
+    /// inside a window.
+    pub async fn get_my_trade_fills(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<ExchangeFill>, BinanceError> {
+        let mut fills = Vec::new();
+        let mut window_start = start_time;
+        while window_start <= end_time {
+            let window_end = (window_start + MY_TRADES_WINDOW_MS - 1).min(end_time);
+            let mut params = format!(
+                "symbol={}&startTime={}&endTime={}&limit={}",
+                symbol, window_start, window_end, ACCOUNT_HISTORY_PAGE_LIMIT
+            );
+            loop {
+                let page = parse_my_trade_fills(
+                    &self
+                        .get_signed(&self.rest_url, "/api/v3/myTrades", &params)
+                        .await?,
+                );
+                let page_len = page.len();
+                let last_id = page.last().map(|(id, _)| *id);
+                fills.extend(
+                    page.into_iter()
+                        .map(|(_, fill)| fill)
+                        .take_while(|f| f.timestamp <= window_end),
+                );
+
+                match last_id {
+                    Some(last_id)
+                        if page_len == ACCOUNT_HISTORY_PAGE_LIMIT
+                            && fills.last().is_some_and(|f| f.timestamp < window_end) =>
+                    {
+                        params = format!(
+                            "symbol={}&fromId={}&limit={}",
+                            symbol,
+                            last_id + 1,
+                            ACCOUNT_HISTORY_PAGE_LIMIT
+                        );
+                    }
+                    _ => break,
+                }
+            }
+            window_start = window_end + 1;
+        }
+
+        Ok(fills)
+    }
+
+    /// Get the commissions charged for USD-M futures fills from `/fapi/v1/income`
+    pub async fn get_commission_income(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<FeeRecord>, BinanceError> {
+        let mut fees: Vec<FeeRecord> = Vec::new();
+        let mut page_start = start_time;
+        loop {
+            let params = format!(
+                "symbol={}&incomeType=COMMISSION&startTime={}&endTime={}&limit={}",
+                symbol, page_start, end_time, ACCOUNT_HISTORY_PAGE_LIMIT
+            );
+            let page = parse_commission_income(
+                &self
+                    .get_signed(&self.futures_rest_url, "/fapi/v1/income", &params)
+                    .await?,
+            );
+            let page_len = page.len();
+            let Some(last_time) = page.last().map(|f| f.timestamp) else {
+                break;
+            };
+
+            // Pages are time-ordered; records at the boundary millisecond may repeat
+            for fee in page {
+                let seen = fees
+                    .iter()
+                    .rev()
+                    .take_while(|f| f.timestamp == fee.timestamp)
+                    .any(|f| f.trade_id == fee.trade_id);
+                if !seen {
+                    fees.push(fee);
+                }
+            }
+
+            if page_len < ACCOUNT_HISTORY_PAGE_LIMIT || last_time >= end_time {
+                break;
+            }
+            page_start = last_time;
+        }
+
+        Ok(fees)
+    }
+
+    /// Measure the offset of the server clock from the local clock
+    /// Runs before the first signed request and again whenever Binance rejects a
+    /// timestamp. Returns the offset in milliseconds.
+    pub async fn sync_time(&self) -> Result<i64, BinanceError> {
+        let sent = now_ms();
+        let server_time = self.get_server_time().await?;
+        let received = now_ms().max(sent);
+        let offset = server_time as i64 - (sent + (received - sent) / 2) as i64;
+        self.time_offset.set(offset);
+        self.time_synced.store(true, Ordering::Relaxed);
+        log::debug!("Binance clock offset: {} ms", offset);
+        Ok(offset)
+    }
+
+    /// Timestamp for a signed request: local time plus the server clock offset
+    async fn request_timestamp(&self) -> Result<u64, BinanceError> {
+        if !self.time_synced.load(Ordering::Relaxed) {
+            self.sync_time().await?;
+        }
+        Ok(self.time_offset.apply(now_ms()))
+    }
+
+    /// Add recvWindow, timestamp and signature to URL-encoded parameters
+    /// Returns the lease the query was signed with, whose API key the request must send.
+    async fn sign_params(&self, params: &str) -> Result<(String, CredentialLease), BinanceError> {
+        let timestamp = self.request_timestamp().await?;
+        let mut query_string = params.to_string();
+        if !query_string.is_empty() {
+            query_string.push('&');
+        }
+        query_string.push_str(&format!(
+            "recvWindow={}&timestamp={}",
+            self.recv_window_ms, timestamp
+        ));
+        let credentials = self.credentials.lease();
+        let signature = Self::sign(&credentials, &query_string);
+        Ok((
+            format!("{}&signature={}", query_string, signature),
+            credentials,
+        ))
+    }
+
+    /// GET a signed endpoint, backing off on rate limits
+    /// If Binance rejects the timestamp (-1021), the clock offset is measured again and the
+    /// request resent once.
+    async fn get_signed(
+        &self,
+        base_url: &str,
+        path: &str,
+        params: &str,
+    ) -> Result<Value, BinanceError> {
+        let mut resynced = false;
+        loop {
+            let (signed_query, credentials) = self.sign_params(params).await?;
+            let url = format!("{}{}?{}", base_url, path, signed_query);
+            match self.get_rate_limited(&url, Some(&credentials)).await {
+                Err(e)
+                    if !resynced
+                        && e.reject_reason() == Some(RejectReason::TimestampOutOfWindow) =>
+                {
+                    log::warn!(
+                        "Binance rejected the timestamp of {}, resyncing clock",
+                        path
+                    );
+                    self.sync_time().await?;
+                    resynced = true;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Send signed form parameters in a request body
+    /// If Binance rejects the timestamp (-1021), the clock offset is measured again and the
+    /// request resent once. Returns the status and body of the last response.
+    async fn send_signed(
+        &self,
+        method: Method,
+        url: &str,
+        params: &[(String, String)],
+        is_order: bool,
+    ) -> Result<(StatusCode, String), BinanceError> {
+        let query_string = params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+        let mut resynced = false;
+        loop {
+            let (signed_query, credentials) = self.sign_params(&query_string).await?;
+            let request = self
+                .http_client
+                .request(method.clone(), url)
+                .header("X-MBX-APIKEY", credentials.api_key())
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .body(signed_query);
+            let response = self.send(request, url, is_order).await?;
+            let status = response.status();
+            let body = response
+                .text()
+                .await
+                .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
+            if !resynced && !status.is_success() && is_timestamp_reject(&body) {
+                log::warn!("Binance rejected the timestamp of {}, resyncing clock", url);
+                self.sync_time().await?;
+                resynced = true;
+                continue;
+            }
+            return Ok((status, body));
+        }
+    }
+
+    /// Send a REST request once the rate limiter has room for it
+    /// Orders also count against the order rate. The usage headers of the response are
+    /// fed back into the limiter.
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        url: &str,
+        is_order: bool,
+    ) -> Result<Response, BinanceError> {
+        self.rate_limiter
+            .acquire(endpoint_path(url), is_order)
+            .await;
+        let response = request
+            .send()
+            .await
+            .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
+        for (kind, window, used) in parse_rate_limit_headers(response.headers()) {
+            self.rate_limiter.sync_used(kind, window, used);
+        }
+        Ok(response)
+    }
+
+    /// GET an endpoint, backing off on rate limits
+    /// Goes through the rate limiter, and honours Retry-After on 429/418 responses.
+    /// Signed requests carry the API key of the lease they were signed with.
+    async fn get_rate_limited(
+        &self,
+        url: &str,
+        credentials: Option<&CredentialLease>,
+    ) -> Result<Value, BinanceError> {
+        let mut retries = 0;
+        loop {
+            let mut request = self.http_client.get(url);
+            if let Some(credentials) = credentials {
+                request = request.header("X-MBX-APIKEY", credentials.api_key());
+            }
+            let response = self.send(request, url, false).await?;
+
+            let status = response.status();
+            if status.as_u16() == 429 || status.as_u16() == 418 {
+                if retries >= RATE_LIMIT_RETRIES {
+                    return Err(BinanceError::RateLimitError(format!(
+                        "Rate limited after {} retries: {}",
+                        retries, status
+                    )));
+                }
+                retries += 1;
+                let retry_after = response
+                    .headers()
+                    .get("Retry-After")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(1);
+                log::warn!(
+                    "Binance rate limit hit ({}), retrying in {}s",
+                    status,
+                    retry_after
+                );
+                tokio::time::sleep(std::time::Duration::from_secs(retry_after)).await;
+                continue;
+            }
+
+            if !status.is_success() {
+                let error_text = response.text().await.unwrap_or_default();
+                if is_timestamp_reject(&error_text) {
+                    if let Some(reject) = parse_reject(&error_text) {
+                        return Err(BinanceError::Rejected(reject));
+                    }
+                }
+                let path = url.split('?').next().unwrap_or(url);
+                return Err(BinanceError::ApiError(format!(
+                    "Request to {} failed: {}",
+                    path, status
+                )));
+            }
+
+            return response
+                .json()
+                .await
+                .map_err(|e| BinanceError::ParseError(e.to_string()));
+        }
+    }
+
     /// Place a new order
+    /// Quote-sized market orders are sent as `quoteOrderQty`; other quote-sized orders are
+    /// converted to a base size at their limit price, as Binance only takes the former.
     pub async fn place_order(&self, order: &NewOrder) -> Result<OrderId, BinanceError> {
-        let server_time = self.get_server_time().await?;
+        // USD-M futures have no quoteOrderQty
+        let native_quote =
+            order.order_type == OrderType::Market && self.market_type == MarketType::Spot;
+        let converted;
+        let order = match (order.quote_size, order.price) {
+            (Some(_), Some(price)) if !native_quote => {
+                let step_size = self
+                    .instruments
+                    .read()
+                    .await
+                    .get(order.symbol.as_str())
+                    .map(|spec| spec.step_size);
+                converted = order.with_base_size_at(price, step_size).ok_or_else(|| {
+                    BinanceError::ApiError(format!("Cannot size order at price {}", price))
+                })?;
+                &converted
+            }
+            (Some(_), None) if !native_quote => {
+                return Err(BinanceError::ApiError(
+                    "Quote-sized order needs a price".to_string(),
+                ))
+            }
+            _ => order,
+        };
+        let (price_param, quantity_param) = self.format_order_params(order).await;
+        let quantity = match order.quote_size {
+            Some(quote_size) => (
+                "quoteOrderQty".to_string(),
+                self.format_quote_param(order.symbol.as_str(), quote_size)
+                    .await,
+            ),
+            None => ("quantity".to_string(), quantity_param),
+        };
 
         let mut params = vec![
             ("symbol".to_string(), order.symbol.as_str().to_string()),
@@ -227,63 +1000,74 @@ impl BinanceClient {
                     _ => "LIMIT".to_string(), // Default to LIMIT for other types
                 },
             ),
-            ("quantity".to_string(), order.size.to_string()),
-            ("timestamp".to_string(), server_time.to_string()),
+            quantity,
         ];
 
-        if let Some(price) = order.price {
-            params.push(("price".to_string(), price.to_string()));
+        if let Some(price) = price_param {
+            params.push(("price".to_string(), price));
         }
 
-        params.push((
-            "timeInForce".to_string(),
-            match order.time_in_force {
-                TimeInForce::GoodTillCancelled => "GTC".to_string(),
-                TimeInForce::ImmediateOrCancel => "IOC".to_string(),
-                TimeInForce::FillOrKill => "FOK".to_string(),
-            },
-        ));
+        // Iceberg orders are LIMIT orders with a visible quantity (spot only)
+        if let (Some(display_size), MarketType::Spot) = (order.display_size(), self.market_type) {
+            let display_size = match self.instruments.read().await.get(order.symbol.as_str()) {
+                Some(spec) => spec.format_size(display_size),
+                None => format_size_plain(display_size),
+            };
+            params.push(("icebergQty".to_string(), display_size));
+        }
+
+        // Binance rejects timeInForce on market orders
+        if order.order_type != OrderType::Market {
+            params.push((
+                "timeInForce".to_string(),
+                match order.time_in_force {
+                    TimeInForce::GoodTillCancelled => "GTC".to_string(),
+                    TimeInForce::ImmediateOrCancel => "IOC".to_string(),
+                    TimeInForce::FillOrKill => "FOK".to_string(),
+                },
+            ));
+        }
 
         if let Some(client_order_id) = &order.client_order_id {
             params.push(("newClientOrderId".to_string(), client_order_id.clone()));
         }
 
-        // Create query string
-        let query_string = params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("&");
-
-        // Add signature
-        let signature = self.sign(&query_string);
-        let signed_query = format!("{}&signature={}", query_string, signature);
-
-        let url = format!("{}/api/v3/order", self.rest_url);
+        let (endpoint, margin_params) = self.order_endpoint();
+        if !margin_params.is_empty() {
+            params.extend(margin_params);
+            params.push((
+                "sideEffectType".to_string(),
+                self.margin_side_effect.as_str().to_string(),
+            ));
+        }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(signed_query)
-            .send()
-            .await
-            .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
+        // In hedge mode orders open the position on their own side
+        if self.market_type == MarketType::UsdFutures
+            && *self.position_mode.read().await == PositionMode::Hedge
+        {
+            params.push((
+                "positionSide".to_string(),
+                match order.side {
+                    OrderSide::Buy => "LONG".to_string(),
+                    OrderSide::Sell => "SHORT".to_string(),
+                },
+            ));
+        }
 
-        let status = response.status();
+        let url = format!("{}{}", self.rest_base(), endpoint);
+        let (status, body) = self.send_signed(Method::POST, &url, &params, true).await?;
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
+            if let Some(reject) = parse_reject(&body) {
+                return Err(BinanceError::Rejected(reject));
+            }
             return Err(BinanceError::ApiError(format!(
                 "Failed to place order: {} - {}",
-                status, error_text
+                status, body
             )));
         }
 
-        let json: Value = response
-            .json()
-            .await
-            .map_err(|e| BinanceError::ParseError(e.to_string()))?;
+        let json: Value =
+            serde_json::from_str(&body).map_err(|e| BinanceError::ParseError(e.to_string()))?;
 
         let order_id: OrderId = json
             .get("orderId")
@@ -296,90 +1080,155 @@ impl BinanceClient {
 
     /// Cancel an order
     pub async fn cancel_order(&self, symbol: &str, order_id: OrderId) -> Result<(), BinanceError> {
-        let server_time = self.get_server_time().await?;
-
-        let params = vec![
+        let (endpoint, margin_params) = self.order_endpoint();
+        let mut params = vec![
             ("symbol".to_string(), symbol.to_string()),
             ("orderId".to_string(), order_id.as_str().to_string()),
-            ("timestamp".to_string(), server_time.to_string()),
         ];
+        params.extend(margin_params);
 
-        // Create query string
-        let query_string = params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("&");
-
-        // Add signature
-        let signature = self.sign(&query_string);
-        let signed_query = format!("{}&signature={}", query_string, signature);
-
-        let url = format!("{}/api/v3/order", self.rest_url);
-
-        let response = self
-            .http_client
-            .delete(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(signed_query)
-            .send()
-            .await
-            .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
-
-        let status = response.status();
+        let url = format!("{}{}", self.rest_base(), endpoint);
+        let (status, body) = self
+            .send_signed(Method::DELETE, &url, &params, false)
+            .await?;
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
+            if let Some(reject) = parse_reject(&body) {
+                return Err(BinanceError::Rejected(reject));
+            }
             return Err(BinanceError::ApiError(format!(
                 "Failed to cancel order: {} - {}",
-                status, error_text
+                status, body
             )));
         }
 
         Ok(())
     }
 
-    /// Get account information
-    pub async fn get_account_info(&self) -> Result<Vec<Balance>, BinanceError> {
-        let server_time = self.get_server_time().await?;
-
-        let params = vec![("timestamp".to_string(), server_time.to_string())];
+    /// Check if orders can be amended with `cancelReplace` (spot only, not margin)
+    pub fn supports_cancel_replace(&self) -> bool {
+        self.market_type == MarketType::Spot && self.margin_mode.is_none()
+    }
 
-        // Create query string
-        let query_string = params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("&");
+    /// Cancel an order and place its replacement in one request
+    /// The replacement keeps the order's side, type, time in force and client order ID;
+    /// without a new size it takes the unfilled quantity. Nothing is placed if the cancel
+    /// fails. Returns the replacement's order ID.
+    pub async fn cancel_replace_order(
+        &self,
+        symbol: &str,
+        order_id: &OrderId,
+        new_price: Option<Price>,
+        new_size: Option<Size>,
+    ) -> Result<OrderId, BinanceError> {
+        let order = self
+            .get_signed(
+                &self.rest_url,
+                "/api/v3/order",
+                &format!("symbol={}&orderId={}", symbol, order_id),
+            )
+            .await?;
+        let field = |name: &str| {
+            order
+                .get(name)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+                .ok_or_else(|| BinanceError::ParseError(format!("Order has no {}", name)))
+        };
+        let size_field = |name: &str| {
+            field(name).and_then(|v| {
+                Size::from_str(&v).map_err(|e| BinanceError::ParseError(e.to_string()))
+            })
+        };
 
-        // Add signature
-        let signature = self.sign(&query_string);
-        let signed_query = format!("{}&signature={}", query_string, signature);
+        let size = match new_size {
+            Some(size) => size,
+            None => size_field("origQty")? - size_field("executedQty")?,
+        };
+        let price = match new_price {
+            Some(price) => price,
+            None => Price::from_str(&field("price")?)
+                .map_err(|e| BinanceError::ParseError(e.to_string()))?,
+        };
+        let (price, quantity) = match self.instruments.read().await.get(symbol) {
+            Some(spec) => (spec.format_price(price), spec.format_size(size)),
+            None => (format_price_plain(price), format_size_plain(size)),
+        };
 
-        let url = format!("{}/api/v3/account", self.rest_url);
+        let mut params = vec![
+            ("symbol".to_string(), symbol.to_string()),
+            ("side".to_string(), field("side")?),
+            ("type".to_string(), field("type")?),
+            (
+                "cancelReplaceMode".to_string(),
+                "STOP_ON_FAILURE".to_string(),
+            ),
+            ("cancelOrderId".to_string(), order_id.clone()),
+            ("quantity".to_string(), quantity),
+            ("price".to_string(), price),
+        ];
+        if let Ok(time_in_force) = field("timeInForce") {
+            params.push(("timeInForce".to_string(), time_in_force));
+        }
+        if let Ok(client_order_id) = field("clientOrderId") {
+            params.push(("newClientOrderId".to_string(), client_order_id));
+        }
 
         let response = self
-            .http_client
-            .get(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .body(signed_query)
-            .send()
-            .await
-            .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
+            .post_signed(&self.rest_url, "/api/v3/order/cancelReplace", params)
+            .await?;
+        response
+            .get("newOrderResponse")
+            .and_then(|v| v.get("orderId"))
+            .and_then(|v| v.as_i64())
+            .map(|id| id.to_string())
+            .ok_or_else(|| {
+                BinanceError::ParseError("Invalid replacement order ID in response".to_string())
+            })
+    }
+
+    /// Get an order, whether open or closed
+    pub async fn get_order(
+        &self,
+        symbol: &str,
+        order_id: &OrderId,
+    ) -> Result<ExecutionReport, BinanceError> {
+        let (endpoint, margin_params) = self.order_endpoint();
+        let mut params = format!("symbol={}&orderId={}", symbol, order_id);
+        for (key, value) in margin_params {
+            params.push_str(&format!("&{}={}", key, value));
+        }
+        let order = self.get_signed(self.rest_base(), endpoint, &params).await?;
+        self.parse_order_report(&order)
+            .ok_or_else(|| BinanceError::ParseError("Invalid order in response".to_string()))
+    }
+
+    /// Get account information
+    /// In margin mode these are the margin account balances, summed over isolated pairs;
+    /// on USD-M futures the wallet balances, with the unavailable part as used.
+    pub async fn get_account_info(&self) -> Result<Vec<Balance>, BinanceError> {
+        if self.market_type == MarketType::UsdFutures {
+            return Ok(parse_futures_balances(
+                &self
+                    .get_signed(&self.futures_rest_url, "/fapi/v2/balance", "")
+                    .await?,
+            ));
+        }
+        if self.margin_mode.is_some() {
+            let accounts = self.get_margin_accounts().await?;
+            return Ok(merge_margin_balances(&accounts));
+        }
 
-        let status = response.status();
+        let url = format!("{}/api/v3/account", self.rest_url);
+        let (status, body) = self.send_signed(Method::GET, &url, &[], false).await?;
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
             return Err(BinanceError::ApiError(format!(
                 "Failed to get account info: {} - {}",
-                status, error_text
+                status, body
             )));
         }
 
-        let json: Value = response
-            .json()
-            .await
-            .map_err(|e| BinanceError::ParseError(e.to_string()))?;
+        let json: Value =
+            serde_json::from_str(&body).map_err(|e| BinanceError::ParseError(e.to_string()))?;
 
         let balances = json
             .get("balances")
@@ -407,102 +1256,815 @@ impl BinanceClient {
         &self,
         symbol: Option<&str>,
     ) -> Result<Vec<ExecutionReport>, BinanceError> {
-        let server_time = self.get_server_time().await?;
-
-        let mut params = vec![("timestamp".to_string(), server_time.to_string())];
+        let mut params = Vec::new();
 
         if let Some(sym) = symbol {
             params.push(("symbol".to_string(), sym.to_string()));
         }
 
-        // Create query string
-        let query_string = params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("&");
-
-        // Add signature
-        let signature = self.sign(&query_string);
-        let signed_query = format!("{}&signature={}", query_string, signature);
-
-        let url = format!("{}/api/v3/openOrders", self.rest_url);
-
-        let response = self
-            .http_client
-            .get(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .body(signed_query)
-            .send()
-            .await
-            .map_err(|e| BinanceError::NetworkError(e.to_string()))?;
+        let path = match self.margin_mode {
+            _ if self.market_type == MarketType::UsdFutures => "/fapi/v1/openOrders",
+            Some(MarginMode::Isolated) if symbol.is_none() => {
+                return Err(BinanceError::ApiError(
+                    "Isolated margin open orders need a symbol".to_string(),
+                ))
+            }
+            Some(_) => {
+                params.extend(self.order_endpoint().1);
+                "/sapi/v1/margin/openOrders"
+            }
+            None => "/api/v3/openOrders",
+        };
 
-        let status = response.status();
+        let url = format!("{}{}", self.rest_base(), path);
+        let (status, body) = self.send_signed(Method::GET, &url, &params, false).await?;
         if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
             return Err(BinanceError::ApiError(format!(
                 "Failed to get open orders: {} - {}",
-                status, error_text
+                status, body
             )));
         }
 
-        let json: Value = response
-            .json()
-            .await
-            .map_err(|e| BinanceError::ParseError(e.to_string()))?;
+        let json: Value =
+            serde_json::from_str(&body).map_err(|e| BinanceError::ParseError(e.to_string()))?;
 
         let orders = json
             .as_array()
             .ok_or_else(|| BinanceError::ParseError("Invalid orders in response".to_string()))?
             .iter()
-            .filter_map(|order| {
-                use crate::types::Symbol;
-
-                let order_id = order.get("orderId")?.as_i64()?.to_string();
-                let client_order_id = order
-                    .get("clientOrderId")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-                let symbol_str = order.get("symbol")?.as_str()?;
-                let orig_qty = Size::from_str(order.get("origQty")?.as_str()?).ok()?;
-                let executed_qty = Size::from_str(order.get("executedQty")?.as_str()?).ok()?;
-
-                let status = match order.get("status")?.as_str()? {
-                    "NEW" => OrderStatus::New,
-                    "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
-                    "FILLED" => OrderStatus::Filled,
-                    "CANCELED" | "CANCELLED" => OrderStatus::Cancelled,
-                    "REJECTED" => OrderStatus::Rejected,
-                    "EXPIRED" => OrderStatus::Expired,
-                    _ => return None,
-                };
-
-                let avg_price = order
-                    .get("price")
-                    .and_then(|p| p.as_str())
-                    .and_then(|p_str| Price::from_str(p_str).ok());
-                let timestamp = order.get("time")?.as_u64()?;
-
-                // Calculate filled_size and remaining_size
-                let filled_size = executed_qty;
-                let remaining_size = Size::new(orig_qty.value() - executed_qty.value());
-
-                Some(ExecutionReport {
-                    order_id,
-                    client_order_id,
-                    symbol: Symbol::new(symbol_str),
-                    exchange_id: "binance".to_string(),
-                    status,
-                    filled_size,
-                    remaining_size,
-                    average_price: avg_price,
-                    timestamp,
-                })
-            })
+            .filter_map(|order| self.parse_order_report(order))
             .collect();
 
         Ok(orders)
     }
+
+    /// Parse an order from an order query or list
+    fn parse_order_report(&self, order: &Value) -> Option<ExecutionReport> {
+        let order_id = order.get("orderId")?.as_i64()?.to_string();
+        let client_order_id = order
+            .get("clientOrderId")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let symbol_str = order.get("symbol")?.as_str()?;
+        let orig_qty = Size::from_str(order.get("origQty")?.as_str()?).ok()?;
+        let executed_qty = Size::from_str(order.get("executedQty")?.as_str()?).ok()?;
+
+        let status = match order.get("status")?.as_str()? {
+            "NEW" => OrderStatus::New,
+            "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+            "FILLED" => OrderStatus::Filled,
+            "CANCELED" | "CANCELLED" => OrderStatus::Cancelled,
+            "REJECTED" => OrderStatus::Rejected,
+            "EXPIRED" => OrderStatus::Expired,
+            _ => return None,
+        };
+
+        // Prefer the fill price (spot cummulativeQuoteQty, futures avgPrice) once filled;
+        // market orders carry a zero limit price
+        let decimal_field = |field: &str| {
+            order
+                .get(field)
+                .and_then(|v| v.as_str())
+                .and_then(|v| Decimal::from_str(v).ok())
+        };
+        let fill_price = if executed_qty.value() > Decimal::ZERO {
+            decimal_field("cummulativeQuoteQty")
+                .map(|quote| quote / executed_qty.value())
+                .or_else(|| decimal_field("avgPrice"))
+                .filter(|price| *price > Decimal::ZERO)
+        } else {
+            None
+        };
+        let avg_price = fill_price.map(Price::new).or_else(|| {
+            order
+                .get("price")
+                .and_then(|p| p.as_str())
+                .and_then(|p_str| Price::from_str(p_str).ok())
+        });
+        let timestamp = order
+            .get("time")
+            .or_else(|| order.get("updateTime"))?
+            .as_u64()?;
+
+        // Calculate filled_size and remaining_size
+        let filled_size = executed_qty;
+        let remaining_size = Size::new(orig_qty.value() - executed_qty.value());
+
+        Some(ExecutionReport {
+            order_id,
+            client_order_id,
+            symbol: Symbol::new(symbol_str),
+            exchange_id: self.exchange_id().to_string(),
+            status,
+            filled_size,
+            remaining_size,
+            average_price: avg_price,
+            timestamp,
+            reject_reason: None,
+        })
+    }
+
+    /// Get the accounts of the configured margin mode (none outside margin mode)
+    pub async fn get_margin_accounts(&self) -> Result<Vec<MarginAccount>, BinanceError> {
+        match self.margin_mode {
+            Some(MarginMode::Cross) => Ok(vec![self.get_cross_margin_account().await?]),
+            Some(MarginMode::Isolated) => self.get_isolated_margin_accounts().await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the cross margin account
+    pub async fn get_cross_margin_account(&self) -> Result<MarginAccount, BinanceError> {
+        parse_cross_margin_account(
+            &self
+                .get_signed(&self.rest_url, "/sapi/v1/margin/account", "")
+                .await?,
+        )
+        .ok_or_else(|| BinanceError::ParseError("Invalid margin account response".to_string()))
+    }
+
+    /// Get all isolated margin accounts
+    pub async fn get_isolated_margin_accounts(&self) -> Result<Vec<MarginAccount>, BinanceError> {
+        Ok(parse_isolated_margin_accounts(
+            &self
+                .get_signed(&self.rest_url, "/sapi/v1/margin/isolated/account", "")
+                .await?,
+        ))
+    }
+
+    /// Borrow an asset into the margin account, returning the transaction ID
+    /// `isolated_symbol` selects an isolated pair; None borrows on the cross account.
+    pub async fn borrow(
+        &self,
+        asset: &str,
+        amount: Size,
+        isolated_symbol: Option<&str>,
+    ) -> Result<u64, BinanceError> {
+        self.borrow_repay("BORROW", asset, amount, isolated_symbol)
+            .await
+    }
+
+    /// Repay a margin loan (interest first), returning the transaction ID
+    pub async fn repay(
+        &self,
+        asset: &str,
+        amount: Size,
+        isolated_symbol: Option<&str>,
+    ) -> Result<u64, BinanceError> {
+        self.borrow_repay("REPAY", asset, amount, isolated_symbol)
+            .await
+    }
+
+    async fn borrow_repay(
+        &self,
+        kind: &str,
+        asset: &str,
+        amount: Size,
+        isolated_symbol: Option<&str>,
+    ) -> Result<u64, BinanceError> {
+        let mut params = vec![
+            ("asset".to_string(), asset.to_string()),
+            (
+                "isIsolated".to_string(),
+                if isolated_symbol.is_some() {
+                    "TRUE"
+                } else {
+                    "FALSE"
+                }
+                .to_string(),
+            ),
+            ("amount".to_string(), amount.value().normalize().to_string()),
+            ("type".to_string(), kind.to_string()),
+        ];
+        if let Some(symbol) = isolated_symbol {
+            params.push(("symbol".to_string(), symbol.to_string()));
+        }
+
+        let json = self
+            .post_signed(&self.rest_url, "/sapi/v1/margin/borrow-repay", params)
+            .await?;
+        json.get("tranId")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| BinanceError::ParseError("Invalid tranId in response".to_string()))
+    }
+
+    /// Set the leverage of a USD-M futures symbol, returning the leverage applied
+    pub async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<u32, BinanceError> {
+        let params = vec![
+            ("symbol".to_string(), symbol.to_string()),
+            ("leverage".to_string(), leverage.to_string()),
+        ];
+        let json = self
+            .post_signed(&self.futures_rest_url, "/fapi/v1/leverage", params)
+            .await?;
+        json.get("leverage")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+            .ok_or_else(|| BinanceError::ParseError("Invalid leverage in response".to_string()))
+    }
+
+    /// Cancel all open USD-M futures orders of a symbol unless called again within a
+    /// countdown, arming the timer or resetting it; a zero countdown disarms it
+    pub async fn countdown_cancel_all(
+        &self,
+        symbol: &str,
+        countdown: Duration,
+    ) -> Result<(), BinanceError> {
+        let params = vec![
+            ("symbol".to_string(), symbol.to_string()),
+            (
+                "countdownTime".to_string(),
+                countdown.as_millis().to_string(),
+            ),
+        ];
+        self.post_signed(
+            &self.futures_rest_url,
+            "/fapi/v1/countdownCancelAll",
+            params,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get the USD-M futures position mode of the account
+    pub async fn get_position_mode(&self) -> Result<PositionMode, BinanceError> {
+        let json = self
+            .get_signed(&self.futures_rest_url, "/fapi/v1/positionSide/dual", "")
+            .await?;
+        let mode = match json.get("dualSidePosition").and_then(|v| v.as_bool()) {
+            Some(true) => PositionMode::Hedge,
+            Some(false) => PositionMode::OneWay,
+            None => {
+                return Err(BinanceError::ParseError(
+                    "Invalid position mode response".to_string(),
+                ))
+            }
+        };
+        *self.position_mode.write().await = mode;
+        Ok(mode)
+    }
+
+    /// Set the USD-M futures position mode of the account
+    /// Binance rejects the change while any position or open order exists.
+    pub async fn set_position_mode(&self, mode: PositionMode) -> Result<(), BinanceError> {
+        let params = vec![(
+            "dualSidePosition".to_string(),
+            (mode == PositionMode::Hedge).to_string(),
+        )];
+        self.post_signed(&self.futures_rest_url, "/fapi/v1/positionSide/dual", params)
+            .await?;
+        *self.position_mode.write().await = mode;
+        Ok(())
+    }
+
+    /// Get the current funding rate of a USD-M perpetual
+    pub async fn get_funding_rate(&self, symbol: &str) -> Result<FundingRate, BinanceError> {
+        let url = format!(
+            "{}/fapi/v1/premiumIndex?symbol={}",
+            self.futures_rest_url, symbol
+        );
+        parse_premium_index(&self.get_rate_limited(&url, None).await?)
+            .ok_or_else(|| BinanceError::ParseError("Invalid premium index response".to_string()))
+    }
+
+    /// Get the current funding rates of all USD-M perpetuals
+    pub async fn get_funding_rates(&self) -> Result<Vec<FundingRate>, BinanceError> {
+        let url = format!("{}/fapi/v1/premiumIndex", self.futures_rest_url);
+        let json = self.get_rate_limited(&url, None).await?;
+        Ok(json
+            .as_array()
+            .map(|entries| entries.iter().filter_map(parse_premium_index).collect())
+            .unwrap_or_default())
+    }
+
+    /// POST a signed form request and return the JSON response
+    async fn post_signed(
+        &self,
+        base_url: &str,
+        path: &str,
+        params: Vec<(String, String)>,
+    ) -> Result<Value, BinanceError> {
+        let url = format!("{}{}", base_url, path);
+        let is_order = path.ends_with("/order/cancelReplace");
+        let (status, body) = self
+            .send_signed(Method::POST, &url, &params, is_order)
+            .await?;
+        if !status.is_success() {
+            return Err(BinanceError::ApiError(format!(
+                "Request to {} failed: {} - {}",
+                path, status, body
+            )));
+        }
+
+        serde_json::from_str(&body).map_err(|e| BinanceError::ParseError(e.to_string()))
+    }
+}
+
+/// Build the rate limiter of a Binance market, weighting the endpoints this client calls
+/// Spot allows 6000 request weight per minute and caps orders at 10 per second and 100
+/// per 10 seconds; USD-M futures allow 2400 weight per minute and 300 orders per 10
+/// seconds and 1200 per minute. Other endpoints count as one; the usage headers of each
+/// response correct the count for calls that weigh more, e.g. order queries.
+pub fn binance_rate_limiter(market_type: MarketType) -> WeightedRateLimiter {
+    match market_type {
+        MarketType::Spot => WeightedRateLimiter::new()
+            .with_window(
+                RateLimitKind::RequestWeight,
+                REQUEST_WEIGHT_LIMIT,
+                Duration::from_secs(60),
+            )
+            .with_window(RateLimitKind::Orders, 10, Duration::from_secs(1))
+            .with_window(RateLimitKind::Orders, 100, Duration::from_secs(10))
+            .with_endpoint_weight("/api/v3/account", 20)
+            .with_endpoint_weight("/api/v3/aggTrades", 4)
+            .with_endpoint_weight("/api/v3/depth", 25)
+            .with_endpoint_weight("/api/v3/exchangeInfo", 20)
+            .with_endpoint_weight("/api/v3/klines", 2)
+            .with_endpoint_weight("/api/v3/myTrades", 20)
+            .with_endpoint_weight("/api/v3/openOrders", 6)
+            .with_endpoint_weight("/api/v3/order/cancelReplace", 1),
+        MarketType::UsdFutures => WeightedRateLimiter::new()
+            .with_window(
+                RateLimitKind::RequestWeight,
+                FUTURES_REQUEST_WEIGHT_LIMIT,
+                Duration::from_secs(60),
+            )
+            .with_window(RateLimitKind::Orders, 300, Duration::from_secs(10))
+            .with_window(RateLimitKind::Orders, 1200, Duration::from_secs(60))
+            .with_endpoint_weight("/fapi/v1/income", 30)
+            .with_endpoint_weight("/fapi/v1/klines", 5)
+            .with_endpoint_weight("/fapi/v1/positionSide/dual", 30)
+            .with_endpoint_weight("/fapi/v2/balance", 5),
+    }
+}
+
+/// Get the current local time in milliseconds
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Check if an error response rejects the request's timestamp (-1021)
+fn is_timestamp_reject(body: &str) -> bool {
+    parse_reject(body).is_some_and(|reject| reject.reason == RejectReason::TimestampOutOfWindow)
+}
+
+/// Get the path of a REST URL, without host or query
+fn endpoint_path(url: &str) -> &str {
+    let path = match url.find("://") {
+        Some(scheme_end) => {
+            let host_start = scheme_end + 3;
+            url[host_start..]
+                .find('/')
+                .map_or("", |i| &url[host_start + i..])
+        }
+        None => url,
+    };
+    path.split('?').next().unwrap_or(path)
+}
+
+/// Parse the usage headers of a response, e.g. `X-MBX-USED-WEIGHT-1M` and
+/// `X-MBX-ORDER-COUNT-10S`, into (budget, window, used) triples
+pub fn parse_rate_limit_headers(headers: &HeaderMap) -> Vec<(RateLimitKind, Duration, usize)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.as_str().to_ascii_lowercase();
+            let (kind, interval) = if let Some(i) = name.strip_prefix("x-mbx-used-weight-") {
+                (RateLimitKind::RequestWeight, i.to_string())
+            } else if let Some(i) = name.strip_prefix("x-mbx-order-count-") {
+                (RateLimitKind::Orders, i.to_string())
+            } else {
+                return None;
+            };
+            let unit = match interval.chars().last()? {
+                's' => 1,
+                'm' => 60,
+                'h' => 3_600,
+                'd' => 86_400,
+                _ => return None,
+            };
+            let count: u64 = interval[..interval.len() - 1].parse().ok()?;
+            let used = value.to_str().ok()?.parse().ok()?;
+            Some((kind, Duration::from_secs(count * unit), used))
+        })
+        .collect()
+}
+
+/// Parse instrument trading rules (PRICE_FILTER / LOT_SIZE) from an exchangeInfo response
+/// Delivery contracts also carry their pair and delivery date
+pub fn parse_instrument_specs(exchange_info: &Value) -> Vec<InstrumentSpec> {
+    exchange_info
+        .get("symbols")
+        .and_then(|v| v.as_array())
+        .map(|symbols| {
+            symbols
+                .iter()
+                .filter_map(|symbol_info| {
+                    let symbol = symbol_info.get("symbol")?.as_str()?;
+                    let filters = symbol_info.get("filters")?.as_array()?;
+
+                    let find_filter = |filter_type: &str, field: &str| {
+                        filters
+                            .iter()
+                            .find(|f| {
+                                f.get("filterType").and_then(|t| t.as_str()) == Some(filter_type)
+                            })
+                            .and_then(|f| f.get(field))
+                            .and_then(|v| v.as_str())
+                            .map(|v| v.to_string())
+                    };
+
+                    let tick_size =
+                        Price::from_str(&find_filter("PRICE_FILTER", "tickSize")?).ok()?;
+                    let step_size = Size::from_str(&find_filter("LOT_SIZE", "stepSize")?).ok()?;
+
+                    let spec = InstrumentSpec::new(symbol, tick_size, step_size);
+                    let contract_type = symbol_info.get("contractType").and_then(|v| v.as_str());
+                    let pair = symbol_info.get("pair").and_then(|v| v.as_str());
+                    let delivery = symbol_info.get("deliveryDate").and_then(|v| v.as_u64());
+                    match (contract_type, pair, delivery) {
+                        (Some(contract_type), Some(pair), Some(delivery))
+                            if contract_type != "PERPETUAL" =>
+                        {
+                            Some(spec.with_expiry(pair, delivery))
+                        }
+                        _ => Some(spec),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse an `/api/v3/aggTrades` response into (aggregate trade ID, trade) pairs
+pub fn parse_agg_trades(symbol: &str, agg_trades: &Value) -> Vec<(u64, Trade)> {
+    agg_trades
+        .as_array()
+        .map(|trades| {
+            trades
+                .iter()
+                .filter_map(|entry| {
+                    let id = entry.get("a")?.as_u64()?;
+                    let price = Price::from_str(entry.get("p")?.as_str()?).ok()?;
+                    let size = Size::from_str(entry.get("q")?.as_str()?).ok()?;
+                    let timestamp = entry.get("T")?.as_u64()?;
+                    // The aggressor is the taker: a maker buyer means a selling aggressor
+                    let side = if entry.get("m")?.as_bool()? {
+                        OrderSide::Sell
+                    } else {
+                        OrderSide::Buy
+                    };
+
+                    Some((
+                        id,
+                        Trade {
+                            symbol: Symbol::new(symbol),
+                            exchange_id: "binance".to_string(),
+                            price,
+                            size,
+                            side,
+                            timestamp,
+                            trade_id: Some(id.to_string()),
+                        },
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse a klines response; candles whose interval ends after `now` are left open
+pub fn parse_klines(
+    symbol: &str,
+    exchange_id: &str,
+    interval: CandleInterval,
+    klines: &Value,
+    now: u64,
+) -> Vec<Candle> {
+    let decimal = |v: &Value| v.as_str().and_then(|s| Decimal::from_str(s).ok());
+    klines
+        .as_array()
+        .map(|klines| {
+            klines
+                .iter()
+                .filter_map(|kline| {
+                    // [open time, open, high, low, close, volume, close time, quote volume, trades, ...]
+                    let close_time = kline.get(6)?.as_u64()?;
+                    Some(Candle {
+                        symbol: Symbol::new(symbol),
+                        exchange_id: exchange_id.to_string(),
+                        interval,
+                        open_time: kline.get(0)?.as_u64()?,
+                        close_time,
+                        open: Price::new(decimal(kline.get(1)?)?),
+                        high: Price::new(decimal(kline.get(2)?)?),
+                        low: Price::new(decimal(kline.get(3)?)?),
+                        close: Price::new(decimal(kline.get(4)?)?),
+                        volume: Size::new(decimal(kline.get(5)?)?),
+                        quote_volume: decimal(kline.get(7)?)?,
+                        trade_count: kline.get(8)?.as_u64()?,
+                        closed: close_time < now,
+                        timestamp: close_time.min(now),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse a `/api/v3/myTrades` response into (trade ID, fee) pairs
+pub fn parse_my_trades(my_trades: &Value) -> Vec<(u64, FeeRecord)> {
+    parse_my_trade_fills(my_trades)
+        .into_iter()
+        .map(|(id, fill)| (id, fill.fee_record()))
+        .collect()
+}
+
+/// Parse a `/api/v3/myTrades` response into (trade ID, fill) pairs
+pub fn parse_my_trade_fills(my_trades: &Value) -> Vec<(u64, ExchangeFill)> {
+    let decimal = |entry: &Value, field: &str| Decimal::from_str(entry.get(field)?.as_str()?).ok();
+    my_trades
+        .as_array()
+        .map(|trades| {
+            trades
+                .iter()
+                .filter_map(|entry| {
+                    let id = entry.get("id")?.as_u64()?;
+                    let order_id = match entry.get("orderId")? {
+                        Value::String(id) => id.clone(),
+                        id => id.as_u64()?.to_string(),
+                    };
+                    let side = if entry.get("isBuyer")?.as_bool()? {
+                        OrderSide::Buy
+                    } else {
+                        OrderSide::Sell
+                    };
+                    Some((
+                        id,
+                        ExchangeFill {
+                            trade_id: id.to_string(),
+                            order_id,
+                            symbol: Symbol::new(entry.get("symbol")?.as_str()?),
+                            exchange_id: "binance".to_string(),
+                            side,
+                            price: Price::new(decimal(entry, "price")?),
+                            size: Size::new(decimal(entry, "qty")?),
+                            fee: decimal(entry, "commission")?,
+                            fee_asset: entry.get("commissionAsset")?.as_str()?.to_string(),
+                            timestamp: entry.get("time")?.as_u64()?,
+                        },
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse a `/fapi/v1/income` response, keeping COMMISSION entries
+/// Income is signed from the account's view, so a paid commission becomes a positive fee
+pub fn parse_commission_income(income: &Value) -> Vec<FeeRecord> {
+    income
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter(|entry| {
+                    entry.get("incomeType").and_then(|v| v.as_str()) == Some("COMMISSION")
+                })
+                .filter_map(|entry| {
+                    let trade_id = match entry.get("tradeId")? {
+                        Value::String(id) => id.clone(),
+                        id => id.as_u64()?.to_string(),
+                    };
+                    Some(FeeRecord {
+                        trade_id,
+                        symbol: Symbol::new(entry.get("symbol")?.as_str()?),
+                        exchange_id: "binance_futures".to_string(),
+                        fee: -Decimal::from_str(entry.get("income")?.as_str()?).ok()?,
+                        fee_asset: entry.get("asset")?.as_str()?.to_string(),
+                        timestamp: entry.get("time")?.as_u64()?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse a USD-M futures `/fapi/v1/premiumIndex` entry into a funding rate
+pub fn parse_premium_index(premium_index: &Value) -> Option<FundingRate> {
+    let symbol = premium_index.get("symbol")?.as_str()?;
+    let rate = Decimal::from_str(premium_index.get("lastFundingRate")?.as_str()?).ok()?;
+    let next_funding_time = premium_index.get("nextFundingTime")?.as_u64()?;
+    let timestamp = premium_index
+        .get("time")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_default();
+
+    Some(FundingRate {
+        symbol: Symbol::new(symbol),
+        exchange_id: "binance_futures".to_string(),
+        rate,
+        // Binance publishes the estimated rate for the running interval only
+        predicted_rate: None,
+        next_funding_time,
+        interval_hours: 8,
+        timestamp,
+    })
+}
+
+/// Parse a USD-M futures `/fapi/v1/openInterest` response
+pub fn parse_open_interest(open_interest: &Value) -> Option<OpenInterest> {
+    let symbol = open_interest.get("symbol")?.as_str()?;
+    let value = Size::from_str(open_interest.get("openInterest")?.as_str()?).ok()?;
+    let timestamp = open_interest.get("time")?.as_u64()?;
+
+    Some(OpenInterest {
+        symbol: Symbol::new(symbol),
+        exchange_id: "binance_futures".to_string(),
+        open_interest: value,
+        timestamp,
+    })
+}
+
+/// Parse a margin `/sapi/v1/margin/next-hourly-interest-rate` response into borrow rates
+pub fn parse_borrow_rates(interest_rates: &Value, timestamp: u64) -> Vec<BorrowRate> {
+    interest_rates
+        .as_array()
+        .map(|rates| {
+            rates
+                .iter()
+                .filter_map(|entry| {
+                    let asset = entry.get("asset")?.as_str()?;
+                    let hourly_rate =
+                        Decimal::from_str(entry.get("nextHourlyInterestRate")?.as_str()?).ok()?;
+                    Some(BorrowRate {
+                        asset: asset.to_string(),
+                        exchange_id: "binance".to_string(),
+                        hourly_rate,
+                        timestamp,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse a USD-M futures `/fapi/v2/balance` response into balances
+pub fn parse_futures_balances(balances: &Value) -> Vec<Balance> {
+    balances
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let asset = entry.get("asset")?.as_str()?;
+                    let total = Decimal::from_str(entry.get("balance")?.as_str()?).ok()?;
+                    let available =
+                        Decimal::from_str(entry.get("availableBalance")?.as_str()?).ok()?;
+                    Some(Balance {
+                        exchange_id: "binance_futures".to_string(),
+                        ..Balance::new(
+                            asset.to_string(),
+                            Size::new(total),
+                            Size::new((total - available).max(Decimal::ZERO)),
+                        )
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse one margin asset entry into its balance and outstanding loan
+fn parse_margin_asset(entry: &Value) -> Option<(Balance, Option<MarginLiability>)> {
+    let field =
+        |name: &str| -> Option<Decimal> { Decimal::from_str(entry.get(name)?.as_str()?).ok() };
+    let asset = entry.get("asset")?.as_str()?.to_string();
+    let free = field("free")?;
+    let locked = field("locked")?;
+    let borrowed = field("borrowed").unwrap_or_default();
+    let interest = field("interest").unwrap_or_default();
+
+    let balance = Balance {
+        exchange_id: "binance".to_string(),
+        ..Balance::new(asset.clone(), Size::new(free + locked), Size::new(locked))
+    };
+    let liability = (borrowed + interest > Decimal::ZERO).then_some(MarginLiability {
+        asset,
+        borrowed,
+        interest,
+    });
+    Some((balance, liability))
+}
+
+/// Parse a `/sapi/v1/margin/account` response into the cross margin account
+/// Binance reports a placeholder margin level when nothing is borrowed; that becomes None.
+pub fn parse_cross_margin_account(account: &Value) -> Option<MarginAccount> {
+    let mut balances = Vec::new();
+    let mut liabilities = Vec::new();
+    for entry in account.get("userAssets")?.as_array()? {
+        let Some((balance, liability)) = parse_margin_asset(entry) else {
+            continue;
+        };
+        balances.push(balance);
+        liabilities.extend(liability);
+    }
+
+    let margin_level = if liabilities.is_empty() {
+        None
+    } else {
+        Decimal::from_str(account.get("marginLevel")?.as_str()?).ok()
+    };
+
+    Some(MarginAccount {
+        exchange_id: "binance".to_string(),
+        mode: MarginMode::Cross,
+        symbol: None,
+        balances,
+        liabilities,
+        margin_level,
+    })
+}
+
+/// Parse a `/sapi/v1/margin/isolated/account` response into one account per pair
+pub fn parse_isolated_margin_accounts(accounts: &Value) -> Vec<MarginAccount> {
+    accounts
+        .get("assets")
+        .and_then(|v| v.as_array())
+        .map(|pairs| {
+            pairs
+                .iter()
+                .filter_map(|pair| {
+                    let symbol = pair.get("symbol")?.as_str()?;
+                    let (base, base_liability) = parse_margin_asset(pair.get("baseAsset")?)?;
+                    let (quote, quote_liability) = parse_margin_asset(pair.get("quoteAsset")?)?;
+                    let liabilities: Vec<MarginLiability> =
+                        base_liability.into_iter().chain(quote_liability).collect();
+                    let margin_level = if liabilities.is_empty() {
+                        None
+                    } else {
+                        Decimal::from_str(pair.get("marginLevel")?.as_str()?).ok()
+                    };
+
+                    Some(MarginAccount {
+                        exchange_id: "binance".to_string(),
+                        mode: MarginMode::Isolated,
+                        symbol: Some(Symbol::new(symbol)),
+                        balances: vec![base, quote],
+                        liabilities,
+                        margin_level,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Sum margin account balances per asset, keeping first-seen order
+fn merge_margin_balances(accounts: &[MarginAccount]) -> Vec<Balance> {
+    let mut merged: Vec<Balance> = Vec::new();
+    for balance in accounts.iter().flat_map(|account| &account.balances) {
+        match merged.iter_mut().find(|b| b.asset == balance.asset) {
+            Some(existing) => {
+                existing.total += balance.total;
+                existing.free += balance.free;
+                existing.used += balance.used;
+            }
+            None => merged.push(balance.clone()),
+        }
+    }
+    merged
+}
+
+/// Market data stream of one symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinanceStream {
+    /// Order book diffs (`@depth`), delivered as `MarketEvent::OrderBookDelta`
+    Depth,
+    /// Best bid and ask (`@bookTicker`), delivered as `MarketEvent::BestBidAsk`
+    BookTicker,
+    /// Aggregate trades (`@aggTrade`), delivered as `MarketEvent::Trade`
+    AggTrade,
+    /// Raw trades (`@trade`, spot only), delivered as `MarketEvent::Trade`
+    Trade,
+    /// Candles (`@kline_1m` etc.), delivered as `MarketEvent::Candle`
+    Kline(CandleInterval),
+}
+
+impl BinanceStream {
+    /// Get the stream name suffix, e.g. `kline_1m`
+    pub fn channel(&self) -> String {
+        match self {
+            BinanceStream::Depth => "depth".to_string(),
+            BinanceStream::BookTicker => "bookTicker".to_string(),
+            BinanceStream::AggTrade => "aggTrade".to_string(),
+            BinanceStream::Trade => "trade".to_string(),
+            BinanceStream::Kline(interval) => format!("kline_{}", interval),
+        }
+    }
 }
 
 /// Binance WebSocket stream for market data
@@ -518,8 +2080,20 @@ pub struct BinanceWebSocket {
     subscriptions: Arc<RwLock<Vec<String>>>,
     /// Connection status
     connected: Arc<RwLock<bool>>,
-    /// Last update timestamps
-    last_updates: Arc<RwLock<HashMap<String, u64>>>,
+    /// Per-symbol message counts and last update times
+    symbol_stats: SymbolStatsRegistry,
+    /// Spot or USD-M futures streams
+    market_type: MarketType,
+    /// Final update ID of the last depth update per symbol
+    update_ids: HashMap<String, u64>,
+    /// Where messages that fail to parse are parked (optional)
+    dead_letters: Option<DeadLetterQueue>,
+    /// Streams of symbols without their own selection
+    streams: Vec<BinanceStream>,
+    /// Streams chosen per symbol, keyed by upper-case symbol
+    symbol_streams: HashMap<String, Vec<BinanceStream>>,
+    /// Host to connect to instead of the market's public one
+    ws_url: Option<String>,
 }
 
 impl BinanceWebSocket {
@@ -529,30 +2103,116 @@ impl BinanceWebSocket {
             ws_sender: None,
             subscriptions: Arc::new(RwLock::new(Vec::new())),
             connected: Arc::new(RwLock::new(false)),
-            last_updates: Arc::new(RwLock::new(HashMap::new())),
+            symbol_stats: SymbolStatsRegistry::new(),
+            market_type: MarketType::default(),
+            update_ids: HashMap::new(),
+            dead_letters: None,
+            streams: vec![BinanceStream::Depth],
+            symbol_streams: HashMap::new(),
+            ws_url: None,
+        }
+    }
+
+    /// Override the WebSocket host, e.g. `ws://127.0.0.1:9000` for a local simulator
+    /// (builder pattern)
+    pub fn with_ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = Some(ws_url.into());
+        self
+    }
+
+    /// Host streams are connected to
+    fn ws_host(&self) -> &str {
+        match (&self.ws_url, self.market_type) {
+            (Some(ws_url), _) => ws_url,
+            (None, MarketType::Spot) => "wss://stream.binance.com:9443",
+            (None, MarketType::UsdFutures) => "wss://fstream.binance.com",
+        }
+    }
+
+    /// Stream spot or USD-M futures market data (builder pattern)
+    /// Futures subscriptions add each symbol's 1s mark price stream.
+    pub fn with_market_type(mut self, market_type: MarketType) -> Self {
+        self.market_type = market_type;
+        self
+    }
+
+    /// Record stats into a shared registry (builder pattern)
+    pub fn with_symbol_stats(mut self, symbol_stats: SymbolStatsRegistry) -> Self {
+        self.symbol_stats = symbol_stats;
+        self
+    }
+
+    /// Park messages that fail to parse in a dead letter queue and carry on, instead of
+    /// returning a parse error (builder pattern)
+    pub fn with_dead_letters(mut self, dead_letters: DeadLetterQueue) -> Self {
+        dead_letters.register_parser("binance", parse_market_event);
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Choose the streams of every symbol, `@depth` by default (builder pattern)
+    pub fn with_streams(mut self, streams: &[BinanceStream]) -> Self {
+        self.streams = streams.to_vec();
+        self
+    }
+
+    /// Choose the streams of one symbol, e.g. only `@bookTicker` for a hedge leg
+    /// (builder pattern)
+    pub fn with_symbol_streams(mut self, symbol: &str, streams: &[BinanceStream]) -> Self {
+        self.symbol_streams
+            .insert(symbol.to_uppercase(), streams.to_vec());
+        self
+    }
+
+    /// Get the per-symbol stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Get the stream names to subscribe for some symbols
+    /// Futures subscriptions add each symbol's 1s mark price stream.
+    pub fn stream_names(&self, symbols: &[&str]) -> Vec<String> {
+        let mut names = Vec::new();
+        for symbol in symbols {
+            let streams = self
+                .symbol_streams
+                .get(&symbol.to_uppercase())
+                .unwrap_or(&self.streams);
+            let mut channels: Vec<String> = streams.iter().map(BinanceStream::channel).collect();
+            if self.market_type == MarketType::UsdFutures {
+                channels.push("markPrice@1s".to_string());
+            }
+            for channel in channels {
+                let name = format!("{}@{}", symbol.to_lowercase(), channel);
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
         }
+        names
     }
 
     /// Connect to the WebSocket stream
     pub async fn connect(&mut self, symbols: &[&str]) -> Result<(), BinanceError> {
+        self.update_ids.clear();
         // Build stream URL for multiple symbols
         // Binance supports two formats:
         // 1. Single stream: wss://stream.binance.com:9443/ws/btcusdt@depth
         // 2. Multiple streams: wss://stream.binance.com:9443/stream?streams=btcusdt@depth/ethusdt@depth
-        let streams: Vec<String> = symbols
-            .iter()
-            .map(|symbol| format!("{}@depth", symbol.to_lowercase()))
-            .collect();
+        let host = self.ws_host();
+        let streams = self.stream_names(symbols);
+        if streams.is_empty() {
+            return Err(BinanceError::ConnectionError(
+                "no streams selected".to_string(),
+            ));
+        }
 
         let stream_url = if streams.len() == 1 {
             // Single stream format
-            format!("wss://stream.binance.com:9443/ws/{}", streams[0])
+            format!("{}/ws/{}", host, streams[0])
         } else {
             // Multiple streams format
-            format!(
-                "wss://stream.binance.com:9443/stream?streams={}",
-                streams.join("/")
-            )
+            format!("{}/stream?streams={}", host, streams.join("/"))
         };
 
         log::info!("Connecting to Binance WebSocket: {}", stream_url);
@@ -578,6 +2238,28 @@ impl BinanceWebSocket {
         Ok(())
     }
 
+    /// Connect to the USD-M futures all-market liquidation stream
+    /// Liquidations are delivered as `MarketEvent::Liquidation` by `next()`
+    pub async fn connect_liquidations(&mut self) -> Result<(), BinanceError> {
+        let stream_url = match &self.ws_url {
+            Some(ws_url) => format!("{}/ws/!forceOrder@arr", ws_url),
+            None => "wss://fstream.binance.com/ws/!forceOrder@arr".to_string(),
+        };
+
+        log::info!("Connecting to Binance liquidation stream: {}", stream_url);
+
+        let (ws_stream, _) = connect_async(&stream_url)
+            .await
+            .map_err(|e| BinanceError::ConnectionError(e.to_string()))?;
+
+        self.ws_sender = Some(ws_stream);
+
+        let mut connected = self.connected.write().await;
+        *connected = true;
+
+        Ok(())
+    }
+
     /// Disconnect from the WebSocket stream
     pub async fn disconnect(&mut self) -> Result<(), BinanceError> {
         if let Some(mut ws) = self.ws_sender.take() {
@@ -594,6 +2276,28 @@ impl BinanceWebSocket {
     }
 }
 
+/// Check a depth update follows on from the symbol's previous one
+/// Spot updates start right after the previous final ID; futures updates carry it as `pu`.
+fn depth_sequence_gap(
+    update_ids: &mut HashMap<String, u64>,
+    update: &DepthUpdateMessage,
+) -> Option<BookDesync> {
+    let previous = update_ids.insert(update.s.clone(), update.u)?;
+    let first = update.pu.map(|pu| pu + 1).unwrap_or(update.U);
+    if first == previous + 1 || (update.pu.is_none() && first <= previous) {
+        return None;
+    }
+    Some(BookDesync {
+        symbol: Symbol::new(update.s.clone()),
+        exchange_id: "binance".to_string(),
+        error: BookIntegrityError::SequenceGap {
+            expected: previous + 1,
+            received: first,
+        },
+        timestamp: update.E,
+    })
+}
+
 #[async_trait]
 impl MarketDataStream for BinanceWebSocket {
     type Error = BinanceError;
@@ -626,32 +2330,50 @@ impl MarketDataStream for BinanceWebSocket {
     }
 
     async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
-        if let Some(ws) = &mut self.ws_sender {
+        let ws = self.ws_sender.as_mut()?;
+        loop {
             match ws.next().await {
                 Some(Ok(Message::Text(text))) => {
                     // Parse JSON message
-                    match crate::connectors::BinanceMessage::from_json(&text) {
+                    return match crate::connectors::BinanceMessage::from_json(&text) {
                         Ok(message) => {
+                            // A gap in depth updates replaces the update with a desync
+                            let desync = match &message {
+                                crate::connectors::BinanceMessage::DepthUpdate(update) => {
+                                    depth_sequence_gap(&mut self.update_ids, update)
+                                }
+                                _ => None,
+                            };
                             // Convert to MarketEvent
-                            Some(Ok(message.to_market_event()))
+                            let event = match desync {
+                                Some(desync) => MarketEvent::Desync(desync),
+                                None => message.to_market_event(),
+                            };
+                            self.symbol_stats.record_event(&event);
+                            Some(Ok(event))
                         }
-                        Err(e) => Some(Err(BinanceError::ParseError(e.to_string()))),
-                    }
+                        Err(e) => match &self.dead_letters {
+                            Some(dead_letters) => {
+                                dead_letters.record_parse_failure("binance", text, e.to_string());
+                                continue;
+                            }
+                            None => Some(Err(BinanceError::ParseError(e.to_string()))),
+                        },
+                    };
                 }
-                Some(Ok(Message::Close(_))) => {
+                Some(Ok(Message::Close(_))) | None => {
                     // Connection closed
-                    let mut connected = self.connected.write().await;
-                    *connected = false;
-                    None
+                    *self.connected.write().await = false;
+                    return None;
                 }
                 Some(Err(e)) => {
                     // WebSocket error
-                    Some(Err(BinanceError::ConnectionError(e.to_string())))
+                    *self.connected.write().await = false;
+                    return Some(Err(BinanceError::ConnectionError(e.to_string())));
                 }
-                _ => None, // Ignore other message types
+                // Ping, pong and binary frames carry no market data
+                Some(Ok(_)) => continue,
             }
-        } else {
-            None
         }
     }
 
@@ -661,13 +2383,19 @@ impl MarketDataStream for BinanceWebSocket {
         true // For simplicity, always return true
     }
 
-    fn last_update(&self, _symbol: &str) -> Option<u64> {
-        // This is a synchronous method, so we can't use async here
-        // In a real implementation, you might use a different approach
-        None // For simplicity, always return None
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
     }
 }
 
+/// Parse a raw market data message, without sequence checks
+/// Used to re-drive dead-lettered messages.
+pub fn parse_market_event(text: &str) -> Result<MarketEvent, String> {
+    crate::connectors::BinanceMessage::from_json(text)
+        .map(|message| message.to_market_event())
+        .map_err(|e| e.to_string())
+}
+
 /// Binance error types
 #[derive(Debug, Clone)]
 pub enum BinanceError {
@@ -677,6 +2405,18 @@ pub enum BinanceError {
     ParseError(String),
     AuthenticationError(String),
     RateLimitError(String),
+    /// The exchange refused an order request with an error code
+    Rejected(ExchangeReject),
+}
+
+impl BinanceError {
+    /// Get the normalized reason if the exchange rejected the request
+    pub fn reject_reason(&self) -> Option<RejectReason> {
+        match self {
+            BinanceError::Rejected(reject) => Some(reject.reason),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for BinanceError {
@@ -688,11 +2428,64 @@ impl std::fmt::Display for BinanceError {
             BinanceError::ParseError(msg) => write!(f, "Parse error: {}", msg),
             BinanceError::AuthenticationError(msg) => write!(f, "Authentication error: {}", msg),
             BinanceError::RateLimitError(msg) => write!(f, "Rate limit error: {}", msg),
+            BinanceError::Rejected(reject) => write!(f, "{}", reject),
+        }
+    }
+}
+
+impl std::error::Error for BinanceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BinanceError::Rejected(reject) => Some(reject),
+            _ => None,
+        }
+    }
+}
+
+/// Map a Binance error code and message to a normalized reject reason
+///
+/// Binance reuses `-2010` and `-1013` for several causes, so the message decides those.
+pub fn reject_reason(code: i64, msg: &str) -> RejectReason {
+    let msg = msg.to_ascii_lowercase();
+    match code {
+        -1003 | -1015 => RejectReason::RateLimited,
+        -1021 => RejectReason::TimestampOutOfWindow,
+        -1022 | -2014 | -2015 => RejectReason::Unauthorized,
+        -1111 => RejectReason::InvalidPrecision,
+        -1121 => RejectReason::UnknownSymbol,
+        -2011 | -2013 => RejectReason::UnknownOrder,
+        -2019 => RejectReason::InsufficientBalance,
+        -2022 => RejectReason::ReduceOnlyRejected,
+        -4131 => RejectReason::PriceOutOfBounds,
+        -4164 => RejectReason::BelowMinimum,
+        -5022 => RejectReason::PostOnlyWouldCross,
+        -1013 if msg.contains("percent_price") => RejectReason::PriceOutOfBounds,
+        -1013 if msg.contains("notional") => RejectReason::BelowMinimum,
+        -1013 if msg.contains("price_filter") || msg.contains("lot_size") => {
+            RejectReason::InvalidPrecision
         }
+        -2010 if msg.contains("insufficient balance") => RejectReason::InsufficientBalance,
+        -2010 if msg.contains("immediately match") => RejectReason::PostOnlyWouldCross,
+        -2010 if msg.contains("duplicate order") => RejectReason::DuplicateOrder,
+        -2010 if msg.contains("market is closed") || msg.contains("trading is disabled") => {
+            RejectReason::MarketClosed
+        }
+        _ => RejectReason::Other,
     }
 }
 
-impl std::error::Error for BinanceError {}
+/// Parse a Binance error body (`{"code":-2010,"msg":"..."}`) into a reject
+pub fn parse_reject(body: &str) -> Option<ExchangeReject> {
+    let json: Value = serde_json::from_str(body).ok()?;
+    let code = json.get("code")?.as_i64()?;
+    let message = json.get("msg").and_then(|v| v.as_str()).unwrap_or_default();
+    Some(ExchangeReject {
+        exchange_id: "binance".to_string(),
+        reason: reject_reason(code, message),
+        code: code.to_string(),
+        message: message.to_string(),
+    })
+}
 
 /// Binance adapter that implements both MarketDataStream and ExecutionClient
 pub struct BinanceAdapter {
@@ -700,15 +2493,184 @@ pub struct BinanceAdapter {
     client: BinanceClient,
     /// Binance WebSocket for market data
     websocket: Arc<Mutex<BinanceWebSocket>>,
+    /// Per-symbol stats shared with the WebSocket
+    symbol_stats: SymbolStatsRegistry,
+    /// Symbol of each order, as cancels and status queries need it
+    order_symbols: OrderSymbolRegistry,
+    /// Cancel-on-disconnect timer re-armed by each heartbeat
+    session_safety: Option<SessionSafety>,
 }
 
 impl BinanceAdapter {
-    /// Create a new Binance adapter
-    pub fn new(api_key: String, api_secret: String, testnet: bool) -> Self {
+    /// Create a new Binance adapter for the spot or USD-M futures market
+    pub fn new(
+        api_key: String,
+        api_secret: String,
+        testnet: bool,
+        market_type: MarketType,
+    ) -> Self {
+        let symbol_stats = SymbolStatsRegistry::new();
         Self {
-            client: BinanceClient::new(api_key, api_secret, testnet),
-            websocket: Arc::new(Mutex::new(BinanceWebSocket::new())),
+            client: BinanceClient::new(api_key, api_secret, testnet).with_market_type(market_type),
+            websocket: Arc::new(Mutex::new(
+                BinanceWebSocket::new()
+                    .with_symbol_stats(symbol_stats.clone())
+                    .with_market_type(market_type),
+            )),
+            symbol_stats,
+            order_symbols: OrderSymbolRegistry::new(),
+            session_safety: None,
+        }
+    }
+
+    /// Get the per-symbol market data stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Get the order symbol registry
+    pub fn order_symbols(&self) -> &OrderSymbolRegistry {
+        &self.order_symbols
+    }
+
+    /// Record the symbol of an order reported elsewhere (e.g. a user data stream or
+    /// an order placed before a restart), so it can be cancelled and queried by ID
+    pub fn record_execution_report(&self, report: &ExecutionReport) {
+        self.order_symbols.record_report(report);
+    }
+
+    /// Symbol of an order placed or reported through this adapter
+    fn order_symbol(&self, order_id: &OrderId) -> Result<String, BinanceError> {
+        self.order_symbols
+            .symbol(order_id)
+            .ok_or_else(|| BinanceError::ApiError(format!("Unknown symbol for order {}", order_id)))
+    }
+
+    /// Set the trading rules used to format orders for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        self.client.set_instrument_spec(spec).await;
+    }
+
+    /// Sign with rotatable credentials from `ApiKeyManager::register_account` (builder pattern)
+    pub fn with_credentials(mut self, credentials: SharedCredentials) -> Self {
+        self.client = self.client.with_credentials(credentials);
+        self
+    }
+
+    /// Stamp signed requests with an offset from `TimeSync` (builder pattern)
+    pub fn with_time_offset(mut self, time_offset: TimeOffset) -> Self {
+        self.client = self.client.with_time_offset(time_offset);
+        self
+    }
+
+    /// Set how long signed requests stay valid after their timestamp (builder pattern)
+    pub fn with_recv_window(mut self, recv_window: Duration) -> Self {
+        self.client = self.client.with_recv_window(recv_window);
+        self
+    }
+
+    /// Have the exchange cancel open orders when heartbeats stop (builder pattern)
+    /// Only USD-M futures have such a timer; on spot heartbeats just check connectivity.
+    pub fn with_session_safety(mut self, session_safety: SessionSafety) -> Self {
+        self.session_safety = Some(session_safety);
+        self
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.client = self.client.with_rest_url(rest_url);
+        self
+    }
+
+    /// Choose the market data streams of every symbol, `@depth` by default (builder pattern)
+    pub fn with_streams(self, streams: &[BinanceStream]) -> Self {
+        // The WebSocket is not shared until a stream is handed out, so this never waits
+        if let Ok(mut websocket) = self.websocket.try_lock() {
+            websocket.streams = streams.to_vec();
+        }
+        self
+    }
+
+    /// Override the USD-M futures REST base URL (builder pattern)
+    pub fn with_futures_rest_url(mut self, futures_rest_url: impl Into<String>) -> Self {
+        self.client = self.client.with_futures_rest_url(futures_rest_url);
+        self
+    }
+
+    /// Override the WebSocket host (builder pattern)
+    pub fn with_ws_url(self, ws_url: impl Into<String>) -> Self {
+        // The WebSocket is not shared until a stream is handed out, so this never waits
+        if let Ok(mut websocket) = self.websocket.try_lock() {
+            websocket.ws_url = Some(ws_url.into());
         }
+        self
+    }
+
+    /// Market the adapter trades on
+    pub fn market_type(&self) -> MarketType {
+        self.client.market_type()
+    }
+
+    /// Set the leverage of a USD-M futures symbol
+    pub async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<u32, BinanceError> {
+        self.client.set_leverage(symbol, leverage).await
+    }
+
+    /// Get the USD-M futures position mode
+    pub async fn get_position_mode(&self) -> Result<PositionMode, BinanceError> {
+        self.client.get_position_mode().await
+    }
+
+    /// Set the USD-M futures position mode
+    pub async fn set_position_mode(&self, mode: PositionMode) -> Result<(), BinanceError> {
+        self.client.set_position_mode(mode).await
+    }
+
+    /// Get the current funding rate of a USD-M perpetual
+    pub async fn get_funding_rate(&self, symbol: &str) -> Result<FundingRate, BinanceError> {
+        self.client.get_funding_rate(symbol).await
+    }
+
+    /// Trade on a cross or isolated margin account (builder pattern)
+    pub fn with_margin_mode(mut self, mode: MarginMode) -> Self {
+        self.client = self.client.with_margin_mode(mode);
+        self
+    }
+
+    /// Set the borrow / repay behaviour of margin orders (builder pattern)
+    pub fn with_margin_side_effect(mut self, side_effect: MarginSideEffect) -> Self {
+        self.client = self.client.with_margin_side_effect(side_effect);
+        self
+    }
+
+    /// Load trading rules for all symbols from the exchange
+    pub async fn load_instrument_specs(&self) -> Result<usize, BinanceError> {
+        self.client.load_instrument_specs().await
+    }
+
+    /// Get the margin accounts, e.g. to feed `RiskEngine::update_margin_account`
+    pub async fn get_margin_accounts(&self) -> Result<Vec<MarginAccount>, BinanceError> {
+        self.client.get_margin_accounts().await
+    }
+
+    /// Borrow an asset on the cross account or an isolated pair
+    pub async fn borrow(
+        &self,
+        asset: &str,
+        amount: Size,
+        isolated_symbol: Option<&str>,
+    ) -> Result<u64, BinanceError> {
+        self.client.borrow(asset, amount, isolated_symbol).await
+    }
+
+    /// Repay a margin loan on the cross account or an isolated pair
+    pub async fn repay(
+        &self,
+        asset: &str,
+        amount: Size,
+        isolated_symbol: Option<&str>,
+    ) -> Result<u64, BinanceError> {
+        self.client.repay(asset, amount, isolated_symbol).await
     }
 }
 
@@ -717,25 +2679,38 @@ impl ExecutionClient for BinanceAdapter {
     type Error = BinanceError;
 
     async fn place_order(&self, order: NewOrder) -> Result<OrderId, Self::Error> {
-        self.client.place_order(&order).await
+        let order_id = self.client.place_order(&order).await?;
+        self.order_symbols
+            .record(order_id.clone(), order.symbol.as_str());
+        Ok(order_id)
     }
 
-    async fn cancel_order(&self, _order_id: OrderId) -> Result<(), Self::Error> {
-        // For Binance, we need to know the symbol to cancel an order
-        // In a real implementation, we'd track this information
-        // For now, we'll return an error
-        Err(BinanceError::ApiError(
-            "Symbol required to cancel order".to_string(),
-        ))
+    async fn cancel_order(&self, order_id: OrderId) -> Result<(), Self::Error> {
+        let symbol = self.order_symbol(&order_id)?;
+        self.client.cancel_order(&symbol, order_id).await
     }
 
-    async fn get_order_status(&self, _order_id: OrderId) -> Result<ExecutionReport, Self::Error> {
-        // For Binance, we need to know the symbol to get order status
-        // In a real implementation, we'd track this information
-        // For now, we'll return an error
-        Err(BinanceError::ApiError(
-            "Symbol required to get order status".to_string(),
-        ))
+    async fn amend_order(
+        &self,
+        order_id: OrderId,
+        new_price: Option<Price>,
+        new_size: Option<Size>,
+    ) -> Result<Option<OrderId>, Self::Error> {
+        if !self.client.supports_cancel_replace() {
+            return Ok(None);
+        }
+        let symbol = self.order_symbol(&order_id)?;
+        let new_order_id = self
+            .client
+            .cancel_replace_order(&symbol, &order_id, new_price, new_size)
+            .await?;
+        self.order_symbols.record(new_order_id.clone(), symbol);
+        Ok(Some(new_order_id))
+    }
+
+    async fn get_order_status(&self, order_id: OrderId) -> Result<ExecutionReport, Self::Error> {
+        let symbol = self.order_symbol(&order_id)?;
+        self.client.get_order(&symbol, &order_id).await
     }
 
     async fn get_balances(&self) -> Result<Vec<Balance>, Self::Error> {
@@ -746,7 +2721,11 @@ impl ExecutionClient for BinanceAdapter {
         &self,
         symbol: Option<&str>,
     ) -> Result<Vec<ExecutionReport>, Self::Error> {
-        self.client.get_open_orders(symbol).await
+        let reports = self.client.get_open_orders(symbol).await?;
+        for report in &reports {
+            self.order_symbols.record_report(report);
+        }
+        Ok(reports)
     }
 
     async fn get_order_history(
@@ -768,6 +2747,71 @@ impl ExecutionClient for BinanceAdapter {
             Size::from_str("0.001").unwrap(), // 0.1% taker fee
         ))
     }
+
+    fn supports_quote_size(&self, order_type: OrderType) -> bool {
+        // quoteOrderQty is only accepted on market orders
+        order_type == OrderType::Market
+    }
+
+    fn supports_iceberg(&self) -> bool {
+        // USD-M futures have no icebergQty
+        self.market_type() == MarketType::Spot
+    }
+
+    async fn heartbeat(&self) -> Result<(), Self::Error> {
+        match &self.session_safety {
+            Some(safety) if self.market_type() == MarketType::UsdFutures => {
+                for symbol in &safety.symbols {
+                    self.client
+                        .countdown_cancel_all(symbol, safety.cancel_after)
+                        .await?;
+                }
+                Ok(())
+            }
+            _ => self.client.get_server_time().await.map(|_| ()),
+        }
+    }
+}
+
+#[async_trait]
+impl ServerClock for BinanceAdapter {
+    type Error = BinanceError;
+
+    async fn server_time(&self) -> Result<u64, Self::Error> {
+        self.client.get_server_time().await
+    }
+}
+
+#[async_trait]
+impl FeeHistory for BinanceAdapter {
+    type Error = BinanceError;
+
+    async fn get_fee_history(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<FeeRecord>, Self::Error> {
+        self.client
+            .get_my_trades(symbol, start_time, end_time)
+            .await
+    }
+}
+
+#[async_trait]
+impl TradeHistory for BinanceAdapter {
+    type Error = BinanceError;
+
+    async fn get_trade_history(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<ExchangeFill>, Self::Error> {
+        self.client
+            .get_my_trade_fills(symbol, start_time, end_time)
+            .await
+    }
 }
 
 #[async_trait]
@@ -789,13 +2833,25 @@ impl MarketDataHistory for BinanceAdapter {
 
     async fn get_trades(
         &self,
-        _symbol: &str,
-        _start_time: u64,
-        _end_time: u64,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
     ) -> Result<Vec<Trade>, Self::Error> {
-        // For Binance, we'd use the /api/v3/myTrades endpoint
-        // For now, we'll return an error
-        Err(BinanceError::ApiError("Not implemented".to_string()))
+        self.client
+            .get_historical_trades(symbol, start_time, end_time)
+            .await
+    }
+
+    async fn get_candles(
+        &self,
+        symbol: &str,
+        interval: CandleInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<Candle>, Self::Error> {
+        self.client
+            .get_klines(symbol, interval, start_time, end_time)
+            .await
     }
 }
 
@@ -814,6 +2870,14 @@ impl crate::exchanges::connection_manager::ExchangeAdapter for BinanceAdapter {
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
+    async fn ping(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_server_time()
+            .await
+            .map(|_| ())
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
     async fn get_market_data_stream(
         &self,
     ) -> Result<
@@ -827,6 +2891,7 @@ impl crate::exchanges::connection_manager::ExchangeAdapter for BinanceAdapter {
         // Create the adapter
         let adapter = BinanceWebSocketAdapter {
             websocket: self.websocket.clone(),
+            symbol_stats: self.symbol_stats.clone(),
         };
 
         // Wrap it in a type-erased wrapper
@@ -834,6 +2899,7 @@ impl crate::exchanges::connection_manager::ExchangeAdapter for BinanceAdapter {
         // For now, we'll use a workaround by creating a wrapper that converts errors
         struct ErrorWrapper {
             inner: Arc<tokio::sync::Mutex<BinanceWebSocketAdapter>>,
+            symbol_stats: SymbolStatsRegistry,
         }
 
         #[async_trait]
@@ -868,13 +2934,14 @@ impl crate::exchanges::connection_manager::ExchangeAdapter for BinanceAdapter {
                 true
             }
 
-            fn last_update(&self, _symbol: &str) -> Option<u64> {
-                None
+            fn last_update(&self, symbol: &str) -> Option<u64> {
+                self.symbol_stats.last_update(symbol)
             }
         }
 
         Ok(Arc::new(tokio::sync::Mutex::new(ErrorWrapper {
             inner: Arc::new(tokio::sync::Mutex::new(adapter)),
+            symbol_stats: self.symbol_stats.clone(),
         })))
     }
 
@@ -882,26 +2949,27 @@ impl crate::exchanges::connection_manager::ExchangeAdapter for BinanceAdapter {
         &self,
         order: NewOrder,
     ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
-        self.client
-            .place_order(&order)
+        ExecutionClient::place_order(self, order)
             .await
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
     async fn cancel_order(
         &self,
-        _order_id: OrderId,
+        order_id: OrderId,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // For Binance, we need the symbol - this is a limitation
-        Err("Symbol required to cancel order".into())
+        ExecutionClient::cancel_order(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
     async fn get_order_status(
         &self,
-        _order_id: OrderId,
+        order_id: OrderId,
     ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
-        // For Binance, we need the symbol - this is a limitation
-        Err("Symbol required to get order status".into())
+        ExecutionClient::get_order_status(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
     async fn get_balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
@@ -915,8 +2983,7 @@ impl crate::exchanges::connection_manager::ExchangeAdapter for BinanceAdapter {
         &self,
         symbol: Option<&str>,
     ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
-        self.client
-            .get_open_orders(symbol)
+        ExecutionClient::get_open_orders(self, symbol)
             .await
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
@@ -947,6 +3014,7 @@ impl crate::exchanges::connection_manager::ExchangeAdapter for BinanceAdapter {
 /// Wrapper for BinanceWebSocket to implement the required MarketDataStream trait
 pub struct BinanceWebSocketAdapter {
     websocket: Arc<tokio::sync::Mutex<BinanceWebSocket>>,
+    symbol_stats: SymbolStatsRegistry,
 }
 
 #[async_trait]
@@ -979,8 +3047,8 @@ impl MarketDataStream for BinanceWebSocketAdapter {
         true
     }
 
-    fn last_update(&self, _symbol: &str) -> Option<u64> {
-        None
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
     }
 }
 
@@ -1010,12 +3078,38 @@ mod tests {
         assert!(ws.subscriptions.try_read().unwrap().is_empty());
     }
 
+    #[test]
+    fn test_stream_selection() {
+        let ws = BinanceWebSocket::new();
+        assert_eq!(ws.stream_names(&["BTCUSDT"]), vec!["btcusdt@depth"]);
+
+        let ws = BinanceWebSocket::new()
+            .with_streams(&[
+                BinanceStream::AggTrade,
+                BinanceStream::Kline(CandleInterval::OneMinute),
+            ])
+            .with_symbol_streams("ethusdt", &[BinanceStream::BookTicker]);
+        assert_eq!(
+            ws.stream_names(&["BTCUSDT", "ETHUSDT"]),
+            vec!["btcusdt@aggTrade", "btcusdt@kline_1m", "ethusdt@bookTicker"]
+        );
+
+        let futures = BinanceWebSocket::new()
+            .with_market_type(MarketType::UsdFutures)
+            .with_streams(&[BinanceStream::BookTicker]);
+        assert_eq!(
+            futures.stream_names(&["BTCUSDT"]),
+            vec!["btcusdt@bookTicker", "btcusdt@markPrice@1s"]
+        );
+    }
+
     #[test]
     fn test_binance_adapter_creation() {
         let _adapter = BinanceAdapter::new(
             "test_key".to_string(),
             "test_secret".to_string(),
             true, // testnet
+            MarketType::Spot,
         );
 
         // Verify adapter was created
@@ -1023,6 +3117,104 @@ mod tests {
         // In a real test, we'd test the behavior
     }
 
+    #[test]
+    fn test_parse_instrument_specs() {
+        let exchange_info = serde_json::json!({
+            "symbols": [{
+                "symbol": "BTCUSDT",
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "0.01000000", "maxPrice": "1000000.00000000", "tickSize": "0.01000000"},
+                    {"filterType": "LOT_SIZE", "minQty": "0.00001000", "maxQty": "9000.00000000", "stepSize": "0.00001000"}
+                ]
+            }, {
+                "symbol": "NOFILTERS",
+                "filters": []
+            }, {
+                "symbol": "BTCUSDT_260925",
+                "pair": "BTCUSDT",
+                "contractType": "CURRENT_QUARTER",
+                "deliveryDate": 1790323200000u64,
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "tickSize": "0.10"},
+                    {"filterType": "LOT_SIZE", "stepSize": "0.001"}
+                ]
+            }]
+        });
+
+        let specs = parse_instrument_specs(&exchange_info);
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0].symbol.value(), "BTCUSDT");
+        assert_eq!(specs[0].price_decimals(), 2);
+        assert_eq!(specs[0].size_decimals(), 5);
+        assert!(!specs[0].is_dated());
+        assert_eq!(specs[1].underlying.as_deref(), Some("BTCUSDT"));
+        assert_eq!(specs[1].expiry, Some(1790323200000));
+    }
+
+    #[test]
+    fn test_parse_carry_feeds() {
+        let premium_index = serde_json::json!({
+            "symbol": "BTCUSDT",
+            "markPrice": "11793.63104562",
+            "indexPrice": "11781.80495970",
+            "lastFundingRate": "0.00038246",
+            "nextFundingTime": 1597392000000u64,
+            "interestRate": "0.00010000",
+            "time": 1597370495002u64
+        });
+        let funding = parse_premium_index(&premium_index).unwrap();
+        assert_eq!(funding.symbol.as_str(), "BTCUSDT");
+        assert_eq!(funding.rate, Decimal::from_str("0.00038246").unwrap());
+        assert_eq!(funding.next_funding_time, 1597392000000);
+
+        let open_interest = serde_json::json!({
+            "openInterest": "10659.509",
+            "symbol": "BTCUSDT",
+            "time": 1589437530011u64
+        });
+        let open_interest = parse_open_interest(&open_interest).unwrap();
+        assert_eq!(
+            open_interest.open_interest,
+            Size::from_str("10659.509").unwrap()
+        );
+
+        let rates = serde_json::json!([
+            {"asset": "BTC", "nextHourlyInterestRate": "0.00000571"},
+            {"asset": "ETH", "nextHourlyInterestRate": "bad"}
+        ]);
+        let rates = parse_borrow_rates(&rates, 0);
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].asset, "BTC");
+    }
+
+    #[tokio::test]
+    async fn test_order_params_use_instrument_spec() {
+        let client = BinanceClient::new("key".to_string(), "secret".to_string(), true);
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("0.0012345678").unwrap(),
+            Price::from_str("50000.129").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+
+        // Without a spec, only trailing zeros/exponents are avoided
+        let (price, quantity) = client.format_order_params(&order).await;
+        assert_eq!(price.as_deref(), Some("50000.129"));
+        assert_eq!(quantity, "0.0012345678");
+
+        client
+            .set_instrument_spec(InstrumentSpec::new(
+                "BTCUSDT",
+                Price::from_str("0.01").unwrap(),
+                Size::from_str("0.00001").unwrap(),
+            ))
+            .await;
+
+        let (price, quantity) = client.format_order_params(&order).await;
+        assert_eq!(price.as_deref(), Some("50000.13"));
+        assert_eq!(quantity, "0.00123");
+    }
+
     #[test]
     fn test_binance_error_display() {
         let error = BinanceError::NetworkError("Connection failed".to_string());
@@ -1031,4 +3223,847 @@ mod tests {
         let error = BinanceError::ApiError("Invalid symbol".to_string());
         assert_eq!(error.to_string(), "API error: Invalid symbol");
     }
+
+    #[tokio::test]
+    async fn test_historical_trades_pagination_and_cache() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // Hour-aligned window start in the past
+        let hour: u64 = 1_699_999_200_000;
+        let agg_trade = |id: u64, time: u64| {
+            serde_json::json!({
+                "a": id, "p": "100.5", "q": "0.1", "f": id, "l": id,
+                "T": time, "m": id.is_multiple_of(2), "M": true
+            })
+        };
+
+        let server = MockServer::start().await;
+        let first_page: Vec<Value> = (0..1000).map(|i| agg_trade(i, hour + i)).collect();
+        Mock::given(method("GET"))
+            .and(path("/api/v3/aggTrades"))
+            .and(query_param("startTime", hour.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(first_page)
+                    .insert_header("X-MBX-USED-WEIGHT-1M", "10"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/aggTrades"))
+            .and(query_param("fromId", "1000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+                agg_trade(1000, hour + 6_000),
+                agg_trade(1001, hour + 7_000),
+            ]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let adapter = BinanceAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+            MarketType::Spot,
+        )
+        .with_rest_url(server.uri());
+        let trades = adapter
+            .get_trades("BTCUSDT", hour + 500, hour + 6_000)
+            .await
+            .unwrap();
+        assert_eq!(trades.len(), 501);
+        assert_eq!(trades[0].trade_id.as_deref(), Some("500"));
+        assert_eq!(trades[0].side, OrderSide::Sell);
+        assert_eq!(trades[1].side, OrderSide::Buy);
+        assert_eq!(trades[500].timestamp, hour + 6_000);
+
+        // The completed window is served from the cache
+        let cached = adapter
+            .get_trades("BTCUSDT", hour + 6_500, hour + 8_000)
+            .await
+            .unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].trade_id.as_deref(), Some("1001"));
+    }
+
+    #[tokio::test]
+    async fn test_klines_pagination_and_warm_up() {
+        use crate::core::clock::SimulatedClock;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let minute = CandleInterval::OneMinute.duration_ms();
+        let kline = |open_time: u64| {
+            serde_json::json!([
+                open_time,
+                "100.0",
+                "101.5",
+                "99.5",
+                "101.0",
+                "12.5",
+                open_time + minute - 1,
+                "1262.5",
+                42,
+                "6.0",
+                "606.0",
+                "0"
+            ])
+        };
+
+        let server = MockServer::start().await;
+        let start: u64 = 1_699_999_200_000;
+        let first_page: Vec<Value> = (0..1000).map(|i| kline(start + i * minute)).collect();
+        Mock::given(method("GET"))
+            .and(path("/api/v3/klines"))
+            .and(query_param("interval", "1m"))
+            .and(query_param("startTime", start.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let second_start = start + 1000 * minute;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/klines"))
+            .and(query_param("startTime", second_start.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![kline(second_start)]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let adapter = BinanceAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+            MarketType::Spot,
+        )
+        .with_rest_url(server.uri());
+        let candles = adapter
+            .get_candles("BTCUSDT", CandleInterval::OneMinute, start, second_start)
+            .await
+            .unwrap();
+        assert_eq!(candles.len(), 1001);
+        assert_eq!(candles[1000].open_time, second_start);
+        assert_eq!(candles[0].high, Price::from_str("101.5").unwrap());
+        assert_eq!(candles[0].trade_count, 42);
+        assert!(candles[0].closed);
+
+        // Warm-up takes the latest closed candles, skipping the one still open
+        let clock = SimulatedClock::at_millis(start + 5000 * minute + 30_000);
+        let current = start + 5000 * minute;
+        let recent = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/klines"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    (0..=3)
+                        .rev()
+                        .map(|i| kline(current - i * minute))
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .mount(&recent)
+            .await;
+        let adapter = BinanceAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+            MarketType::Spot,
+        )
+        .with_rest_url(recent.uri());
+        let warm_up = adapter
+            .get_recent_candles("BTCUSDT", CandleInterval::OneMinute, 2, &clock)
+            .await
+            .unwrap();
+        assert_eq!(warm_up.len(), 2);
+        assert_eq!(warm_up[1].open_time, current - minute);
+    }
+
+    #[tokio::test]
+    async fn test_rest_calls_share_rate_limiter() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64}))
+                    .insert_header("X-MBX-USED-WEIGHT-1M", "5990")
+                    .insert_header("X-MBX-ORDER-COUNT-10S", "7"),
+            )
+            .mount(&server)
+            .await;
+
+        assert_eq!(
+            endpoint_path(&format!("{}/api/v3/depth?symbol=BTCUSDT", server.uri())),
+            "/api/v3/depth"
+        );
+
+        // Two clients of one API key spend the same request weight
+        let client = BinanceClient::new("key".to_string(), "secret".to_string(), false)
+            .with_rest_url(server.uri());
+        let other = BinanceClient::new("key".to_string(), "secret".to_string(), false)
+            .with_rest_url(server.uri())
+            .with_rate_limiter(client.rate_limiter());
+        let limiter = client.rate_limiter();
+        assert_eq!(limiter.weight("/api/v3/account"), 20);
+
+        other.get_server_time().await.unwrap();
+        assert_eq!(limiter.current_usage(RateLimitKind::RequestWeight), 5990);
+
+        // The weight left is too little for an account snapshot
+        assert!(limiter.try_acquire("/api/v3/account", false).is_err());
+        assert!(limiter.try_acquire("/api/v3/time", false).is_ok());
+
+        let response = reqwest::get(format!("{}/api/v3/time", server.uri()))
+            .await
+            .unwrap();
+        let mut usage = parse_rate_limit_headers(response.headers());
+        usage.sort_by_key(|(_, window, _)| *window);
+        assert_eq!(
+            usage,
+            vec![
+                (RateLimitKind::Orders, Duration::from_secs(10), 7),
+                (RateLimitKind::RequestWeight, Duration::from_secs(60), 5990),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_historical_trades_rate_limited() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/aggTrades"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let client = BinanceClient::new("key".to_string(), "secret".to_string(), false)
+            .with_rest_url(server.uri());
+        let result = client.get_historical_trades("BTCUSDT", 0, 1_000).await;
+        assert!(matches!(result, Err(BinanceError::RateLimitError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_endpoints() {
+        use wiremock::matchers::{header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(header("X-MBX-APIKEY", "key"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 28457, "symbol": "BTCUSDT", "orderId": 100234, "price": "4.00000100",
+                 "qty": "12.00000000", "commission": "10.10000000", "commissionAsset": "BNB",
+                 "time": 1_499_865_549_590u64, "isBuyer": true, "isMaker": false}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/fapi/v1/income"))
+            .and(query_param("incomeType", "COMMISSION"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"symbol": "BTCUSDT", "incomeType": "COMMISSION", "income": "-0.01000000",
+                 "asset": "USDT", "time": 1_570_636_800_000u64, "tranId": 9689322392u64,
+                 "tradeId": "2059192"}
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = BinanceClient::new("key".to_string(), "secret".to_string(), false)
+            .with_rest_url(server.uri())
+            .with_futures_rest_url(server.uri());
+
+        let fees = client
+            .get_my_trades("BTCUSDT", 1_499_865_000_000, 1_499_866_000_000)
+            .await
+            .unwrap();
+        assert_eq!(fees.len(), 1);
+        assert_eq!(fees[0].trade_id, "28457");
+        assert_eq!(fees[0].fee, Decimal::from_str("10.1").unwrap());
+        assert_eq!(fees[0].fee_asset, "BNB");
+
+        let fees = client
+            .get_commission_income("BTCUSDT", 1_570_000_000_000, 1_571_000_000_000)
+            .await
+            .unwrap();
+        assert_eq!(fees.len(), 1);
+        assert_eq!(fees[0].trade_id, "2059192");
+        assert_eq!(fees[0].fee, Decimal::from_str("0.01").unwrap());
+        assert_eq!(fees[0].exchange_id, "binance_futures");
+    }
+
+    #[tokio::test]
+    async fn test_signed_requests_follow_key_rotation() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(header("X-MBX-APIKEY", "rotated-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let credentials = SharedCredentials::new(ApiCredentials::new("key", "secret"));
+        let client = BinanceClient::new(String::new(), String::new(), false)
+            .with_credentials(credentials.clone())
+            .with_rest_url(server.uri());
+        let retired = credentials.swap(ApiCredentials::new("rotated-key", "rotated-secret"));
+
+        let fees = client
+            .get_my_trades("BTCUSDT", 1_499_865_000_000, 1_499_866_000_000)
+            .await
+            .unwrap();
+        assert!(fees.is_empty());
+        assert_eq!(retired.in_flight(), 0);
+        assert_eq!(credentials.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_signed_requests_use_synced_time_offset() {
+        use crate::exchanges::time_sync::{TimeSync, TimeSyncConfig};
+        use wiremock::matchers::{method, path, query_param_contains};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // Server clock years ahead, sampled once by TimeSync and never per request
+        let server_ms = 4_000_000_000_000u64;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": server_ms})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(query_param_contains("timestamp", "4000000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let time_sync = TimeSync::new(TimeSyncConfig::default());
+        let source = Arc::new(
+            BinanceAdapter::new(String::new(), String::new(), false, MarketType::Spot)
+                .with_rest_url(server.uri()),
+        );
+        let offset = time_sync.add_exchange("binance", source).await;
+        let status = time_sync.sample("binance").await.unwrap();
+        assert!(status.is_out_of_bounds(&TimeSyncConfig::default()));
+
+        let client = BinanceClient::new(String::new(), String::new(), false)
+            .with_rest_url(server.uri())
+            .with_time_offset(offset);
+        client
+            .get_my_trades("BTCUSDT", 1_499_865_000_000, 1_499_866_000_000)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_parse_margin_accounts() {
+        let cross = serde_json::json!({
+            "marginLevel": "11.64405625",
+            "totalAssetOfBtc": "6.82728457",
+            "totalLiabilityOfBtc": "0.58633215",
+            "userAssets": [
+                {"asset": "BTC", "borrowed": "0.00000000", "free": "0.00499500",
+                 "interest": "0.00000000", "locked": "0.00100000", "netAsset": "0.00599500"},
+                {"asset": "USDT", "borrowed": "5000.00000000", "free": "8000.00000000",
+                 "interest": "1.25000000", "locked": "0.00000000", "netAsset": "2998.75000000"}
+            ]
+        });
+        let account = parse_cross_margin_account(&cross).unwrap();
+        assert_eq!(account.mode, MarginMode::Cross);
+        assert_eq!(account.balances.len(), 2);
+        assert_eq!(
+            account.balances[0].total,
+            Decimal::from_str("0.005995").unwrap()
+        );
+        assert_eq!(
+            account.balances[0].free,
+            Decimal::from_str("0.004995").unwrap()
+        );
+        assert_eq!(account.liabilities.len(), 1);
+        assert_eq!(
+            account.liability("USDT"),
+            Decimal::from_str("5001.25").unwrap()
+        );
+        assert_eq!(
+            account.margin_level,
+            Some(Decimal::from_str("11.64405625").unwrap())
+        );
+
+        let isolated = serde_json::json!({
+            "assets": [{
+                "symbol": "ETHUSDT",
+                "marginLevel": "999.00000000",
+                "baseAsset": {"asset": "ETH", "borrowed": "0", "free": "1.5",
+                              "interest": "0", "locked": "0"},
+                "quoteAsset": {"asset": "USDT", "borrowed": "0", "free": "100",
+                               "interest": "0", "locked": "0"}
+            }]
+        });
+        let accounts = parse_isolated_margin_accounts(&isolated);
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].symbol, Some(Symbol::new("ETHUSDT")));
+        // Nothing borrowed: the placeholder level is dropped
+        assert_eq!(accounts[0].margin_level, None);
+
+        let merged = merge_margin_balances(&[account, accounts[0].clone()]);
+        let usdt = merged.iter().find(|b| b.asset == "USDT").unwrap();
+        assert_eq!(usdt.total, Decimal::from_str("8100").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_margin_order_and_borrow() {
+        use wiremock::matchers::{body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/sapi/v1/margin/order"))
+            .and(body_string_contains("isIsolated=TRUE"))
+            .and(body_string_contains("sideEffectType=MARGIN_BUY"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"orderId": 42})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/sapi/v1/margin/borrow-repay"))
+            .and(body_string_contains("type=REPAY"))
+            .and(body_string_contains("symbol=BTCUSDT"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"tranId": 100000001})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let adapter = BinanceAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+            MarketType::Spot,
+        )
+        .with_rest_url(server.uri())
+        .with_margin_mode(MarginMode::Isolated)
+        .with_margin_side_effect(MarginSideEffect::MarginBuy);
+
+        let order = NewOrder::new_market_buy("BTCUSDT", Size::from_str("0.01").unwrap());
+        assert_eq!(adapter.place_order(order).await.unwrap(), "42");
+
+        let tran_id = adapter
+            .repay("USDT", Size::from_str("100").unwrap(), Some("BTCUSDT"))
+            .await
+            .unwrap();
+        assert_eq!(tran_id, 100000001);
+
+        // Isolated open orders are per pair
+        assert!(adapter.client.get_open_orders(None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_usd_futures_adapter() {
+        use wiremock::matchers::{body_string_contains, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/fapi/v1/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/fapi/v1/leverage"))
+            .and(body_string_contains("leverage=20"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "leverage": 20, "maxNotionalValue": "1000000", "symbol": "BTCUSDT"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/fapi/v1/positionSide/dual"))
+            .and(body_string_contains("dualSidePosition=true"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"code": 200, "msg": "success"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/fapi/v1/order"))
+            .and(body_string_contains("positionSide=SHORT"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"orderId": 7})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/fapi/v1/premiumIndex"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "symbol": "BTCUSDT", "markPrice": "11793.63104562",
+                "lastFundingRate": "0.00038246", "nextFundingTime": 1597392000000u64,
+                "time": 1597370495002u64
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/fapi/v2/balance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"accountAlias": "SgsR", "asset": "USDT", "balance": "1000.00000000",
+                 "crossWalletBalance": "1000.00000000", "crossUnPnl": "0.00000000",
+                 "availableBalance": "750.00000000", "maxWithdrawAmount": "750.00000000"}
+            ])))
+            .mount(&server)
+            .await;
+
+        let adapter = BinanceAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+            MarketType::UsdFutures,
+        )
+        .with_futures_rest_url(server.uri());
+
+        assert_eq!(adapter.set_leverage("BTCUSDT", 20).await.unwrap(), 20);
+        adapter
+            .set_position_mode(PositionMode::Hedge)
+            .await
+            .unwrap();
+
+        let order = NewOrder::new_limit_sell(
+            "BTCUSDT",
+            Size::from_str("0.01").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        assert_eq!(adapter.place_order(order).await.unwrap(), "7");
+
+        let funding = adapter.get_funding_rate("BTCUSDT").await.unwrap();
+        assert_eq!(funding.rate, Decimal::from_str("0.00038246").unwrap());
+
+        let balances = adapter.get_balances().await.unwrap();
+        assert_eq!(balances[0].exchange_id, "binance_futures");
+        assert_eq!(balances[0].used, Decimal::from_str("250").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_status_by_order_id() {
+        use wiremock::matchers::{body_string_contains, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"orderId": 28})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/order"))
+            .and(query_param("symbol", "ETHUSDT"))
+            .and(query_param("orderId", "28"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "symbol": "ETHUSDT", "orderId": 28, "clientOrderId": "abc",
+                "price": "0.00000000", "origQty": "2.00000000", "executedQty": "2.00000000",
+                "cummulativeQuoteQty": "6001.00000000", "status": "FILLED",
+                "type": "MARKET", "side": "BUY", "time": 1_700_000_000_000u64
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v3/order"))
+            .and(body_string_contains("symbol=BTCUSDT&orderId=99"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let adapter = BinanceAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+            MarketType::Spot,
+        )
+        .with_rest_url(server.uri());
+
+        let order = NewOrder::new_market_buy("ETHUSDT", Size::from_str("2").unwrap());
+        let order_id = ExecutionClient::place_order(&adapter, order).await.unwrap();
+        let report = ExecutionClient::get_order_status(&adapter, order_id)
+            .await
+            .unwrap();
+        assert_eq!(report.status, OrderStatus::Filled);
+        assert_eq!(
+            report.average_price,
+            Some(Price::from_str("3000.5").unwrap())
+        );
+
+        // Orders seen on a user data stream can be cancelled by ID too
+        adapter.record_execution_report(&ExecutionReport {
+            order_id: "99".to_string(),
+            client_order_id: None,
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            status: OrderStatus::New,
+            filled_size: Size::new(Decimal::ZERO),
+            remaining_size: Size::from_str("1").unwrap(),
+            average_price: None,
+            timestamp: 0,
+            reject_reason: None,
+        });
+        ExecutionClient::cancel_order(&adapter, "99".to_string())
+            .await
+            .unwrap();
+
+        assert!(ExecutionClient::cancel_order(&adapter, "100".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_order_reject_reason() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "code": -2010,
+                "msg": "Account has insufficient balance for requested action."
+            })))
+            .mount(&server)
+            .await;
+
+        let client = BinanceClient::new("key".to_string(), "secret".to_string(), false)
+            .with_rest_url(server.uri());
+        let order = NewOrder::new_market_buy("ETHUSDT", Size::from_str("2").unwrap());
+        let error = client.place_order(&order).await.unwrap_err();
+        assert_eq!(
+            error.reject_reason(),
+            Some(RejectReason::InsufficientBalance)
+        );
+        // Reachable through a boxed error as well
+        let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(error);
+        let reject = ExchangeReject::find(boxed.as_ref()).unwrap();
+        assert_eq!(reject.code, "-2010");
+
+        assert_eq!(
+            reject_reason(-1013, "Filter failure: PERCENT_PRICE_BY_SIDE"),
+            RejectReason::PriceOutOfBounds
+        );
+        assert_eq!(
+            reject_reason(-2010, "Order would immediately match and take."),
+            RejectReason::PostOnlyWouldCross
+        );
+        assert!(parse_reject("<html>").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_timestamp_reject_resyncs_clock_once() {
+        use wiremock::matchers::{body_string_contains, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // Synced before the first signed request and again after each -1021
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64})),
+            )
+            .expect(3)
+            .mount(&server)
+            .await;
+        let timestamp_reject = ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": -1021,
+            "msg": "Timestamp for this request is outside of the recvWindow."
+        }));
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .and(body_string_contains("recvWindow=10000&timestamp=17"))
+            .respond_with(timestamp_reject.clone())
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "orderId": 7
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        // A query rejected twice in a row fails instead of retrying again
+        Mock::given(method("GET"))
+            .and(path("/api/v3/order"))
+            .and(query_param("recvWindow", "10000"))
+            .respond_with(timestamp_reject)
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = BinanceClient::new("key".to_string(), "secret".to_string(), false)
+            .with_rest_url(server.uri())
+            .with_recv_window(Duration::from_secs(10));
+        let order = NewOrder::new_market_buy("ETHUSDT", Size::from_str("2").unwrap());
+        assert_eq!(client.place_order(&order).await.unwrap(), "7");
+        // The cached offset is reused without fetching server time again
+        assert_eq!(client.place_order(&order).await.unwrap(), "7");
+
+        let error = client
+            .get_order("ETHUSDT", &"7".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.reject_reason(),
+            Some(RejectReason::TimestampOutOfWindow)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_futures_heartbeat_rearms_countdown_cancel_all() {
+        use wiremock::matchers::{body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/fapi/v1/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"serverTime": 1_700_000_000_000u64})),
+            )
+            .mount(&server)
+            .await;
+        for symbol in ["BTCUSDT", "ETHUSDT"] {
+            Mock::given(method("POST"))
+                .and(path("/fapi/v1/countdownCancelAll"))
+                .and(body_string_contains(format!(
+                    "symbol={}&countdownTime=60000",
+                    symbol
+                )))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "symbol": symbol, "countdownTime": "60000"
+                })))
+                .expect(2)
+                .mount(&server)
+                .await;
+        }
+
+        let adapter = BinanceAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+            MarketType::UsdFutures,
+        )
+        .with_futures_rest_url(server.uri())
+        .with_session_safety(
+            SessionSafety::new(Duration::from_secs(60)).with_symbols(["BTCUSDT", "ETHUSDT"]),
+        );
+        ExecutionClient::heartbeat(&adapter).await.unwrap();
+        ExecutionClient::heartbeat(&adapter).await.unwrap();
+    }
+
+    #[test]
+    fn test_depth_sequence_gap() {
+        let update = |first: u64, last: u64, previous: Option<u64>| DepthUpdateMessage {
+            e: "depthUpdate".to_string(),
+            E: 1,
+            s: "BTCUSDT".to_string(),
+            U: first,
+            u: last,
+            pu: previous,
+            b: vec![],
+            a: vec![],
+        };
+        let mut update_ids = HashMap::new();
+
+        // Spot: each update starts right after the previous final ID
+        assert!(depth_sequence_gap(&mut update_ids, &update(100, 105, None)).is_none());
+        assert!(depth_sequence_gap(&mut update_ids, &update(106, 110, None)).is_none());
+        let desync = depth_sequence_gap(&mut update_ids, &update(115, 120, None)).unwrap();
+        assert_eq!(desync.symbol.as_str(), "BTCUSDT");
+        assert_eq!(
+            desync.error,
+            BookIntegrityError::SequenceGap {
+                expected: 111,
+                received: 115
+            }
+        );
+
+        // Futures: each update names the previous final ID
+        update_ids.clear();
+        assert!(depth_sequence_gap(&mut update_ids, &update(1, 5, Some(0))).is_none());
+        assert!(depth_sequence_gap(&mut update_ids, &update(9, 12, Some(5))).is_none());
+        assert!(depth_sequence_gap(&mut update_ids, &update(20, 25, Some(18))).is_some());
+    }
 }
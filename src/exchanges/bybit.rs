@@ -1,316 +1,715 @@
-// Bybit exchange connector - simplified implementation following Gate.io pattern
-// Full implementation can be expanded based on Bybit API documentation
-
-use async_trait::async_trait;
+use crate::core::events::{OrderBookDelta, OrderBookLevel, OrderBookSnapshot};
+use crate::monitoring::SymbolStatsRegistry;
 use crate::traits::{
-    MarketDataStream, MarketEvent, NewOrder, OrderId, ExecutionReport, OrderStatus, OrderSide, OrderType, TimeInForce,
-    Balance, TradingFees
+    Balance, ExecutionClient, ExecutionReport, MarketDataStream, MarketEvent, NewOrder, OrderId,
+    OrderSide, OrderStatus, OrderType, ServerClock, TimeInForce, TradingFees,
 };
-use crate::types::{Price, Size};
-use crate::core::events::{OrderBookSnapshot, OrderBookLevel};
-use crate::exchanges::connection_manager::ExchangeAdapter;
+use crate::types::instrument::{format_price_plain, format_quote_plain, format_size_plain};
+use crate::types::{InstrumentSpec, Notional, Price, Size, Symbol};
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method};
+use serde_json::{json, Value};
+use sha2::Sha256;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::{Mutex, RwLock};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
-use futures_util::{SinkExt, StreamExt};
-use serde_json::{json, Value};
-use reqwest::Client;
-use hmac::{Hmac, Mac};
-use sha2::Sha256;
-use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Bybit API client
+/// Milliseconds a signed request stays valid after its timestamp
+const RECV_WINDOW: u64 = 5000;
+/// Order book depth of the WebSocket channel (1, 50, 200 for spot)
+const WS_BOOK_DEPTH: u32 = 50;
+/// Bybit drops connections that have not pinged for a while
+const WS_PING_INTERVAL: Duration = Duration::from_secs(20);
+/// Maximum topics per subscribe request on the spot stream
+const WS_MAX_TOPICS_PER_REQUEST: usize = 10;
+/// retCode for too many requests
+const RET_CODE_RATE_LIMIT: i64 = 10006;
+/// retCodes for a bad key, signature or timestamp
+const RET_CODES_AUTH: [i64; 4] = [10002, 10003, 10004, 10005];
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Bybit V5 API client for market data and spot order execution
 pub struct BybitClient {
+    /// API key
     api_key: String,
+    /// API secret
     api_secret: String,
+    /// Base URL for REST API
     rest_url: String,
-    ws_url: String,
+    /// HTTP client
     http_client: Client,
-    last_updates: Arc<RwLock<HashMap<String, u64>>>,
-    connected: Arc<RwLock<bool>>,
+    /// Instrument trading rules used to format order parameters
+    instruments: Arc<RwLock<HashMap<String, InstrumentSpec>>>,
+    /// Symbol of each order placed, as the V5 API needs it to cancel or query
+    order_symbols: Arc<RwLock<HashMap<OrderId, String>>>,
 }
 
 impl BybitClient {
+    /// Create a new Bybit client
     pub fn new(api_key: String, api_secret: String, testnet: bool) -> Self {
-        let (rest_url, ws_url) = if testnet {
-            (
-                "https://api-testnet.bybit.com".to_string(),
-                "wss://stream-testnet.bybit.com/v5/public/spot".to_string(),
-            )
+        let rest_url = if testnet {
+            "https://api-testnet.bybit.com".to_string()
         } else {
-            (
-                "https://api.bybit.com".to_string(),
-                "wss://stream.bybit.com/v5/public/spot".to_string(),
-            )
+            "https://api.bybit.com".to_string()
         };
 
         Self {
             api_key,
             api_secret,
             rest_url,
-            ws_url,
             http_client: Client::new(),
-            last_updates: Arc::new(RwLock::new(HashMap::new())),
-            connected: Arc::new(RwLock::new(false)),
+            instruments: Arc::new(RwLock::new(HashMap::new())),
+            order_symbols: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
-    fn sign(&self, timestamp: &str, recv_window: &str, params: &str) -> String {
-        let sign_string = format!("{}{}{}", timestamp, self.api_key, recv_window);
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self
+    }
+
+    /// Set the trading rules for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        let mut instruments = self.instruments.write().await;
+        instruments.insert(spec.symbol.value().to_string(), spec);
+    }
+
+    /// Load trading rules for all spot symbols
+    pub async fn load_instrument_specs(&self) -> Result<usize, BybitError> {
+        let result = self
+            .request(
+                Method::GET,
+                "/v5/market/instruments-info",
+                "category=spot",
+                false,
+            )
+            .await?;
+        let specs = parse_instrument_specs(&result);
+        let count = specs.len();
+
+        let mut instruments = self.instruments.write().await;
+        for spec in specs {
+            instruments.insert(spec.symbol.value().to_string(), spec);
+        }
+        Ok(count)
+    }
+
+    /// Format the price and quantity of an order for its instrument
+    async fn format_order_params(&self, order: &NewOrder) -> (Option<String>, String) {
+        let instruments = self.instruments.read().await;
+        match instruments.get(order.symbol.as_str()) {
+            Some(spec) => (
+                order.price.map(|price| spec.format_price(price)),
+                spec.format_size(order.size),
+            ),
+            None => (
+                order.price.map(format_price_plain),
+                format_size_plain(order.size),
+            ),
+        }
+    }
+
+    /// Format a quote-asset order amount using the instrument's price precision
+    async fn format_quote_param(&self, symbol: &str, quote_size: Notional) -> String {
+        match self.instruments.read().await.get(symbol) {
+            Some(spec) => spec.format_quote_size(quote_size),
+            None => format_quote_plain(quote_size),
+        }
+    }
+
+    /// Sign a request
+    /// The V5 payload is timestamp + key + recv window + (query string or JSON body)
+    pub fn sign(&self, timestamp: u64, payload: &str) -> String {
+        let sign_string = format!("{}{}{}{}", timestamp, self.api_key, RECV_WINDOW, payload);
         let mut mac = Hmac::<Sha256>::new_from_slice(self.api_secret.as_bytes())
             .expect("HMAC can take key of any size");
         mac.update(sign_string.as_bytes());
-        let result = mac.finalize();
-        let code_bytes = result.into_bytes();
-        format!("{:x}", code_bytes)
+        format!("{:x}", mac.finalize().into_bytes())
     }
 
-    pub async fn get_order_book(&self, symbol: &str, limit: u32) -> Result<OrderBookSnapshot, BybitError> {
-        let url = format!("{}/v5/market/orderbook?category=spot&symbol={}&limit={}", 
-            self.rest_url, symbol, limit);
-        
-        let response = self.http_client.get(&url).send().await
+    /// Send a request and return the `result` of the response envelope
+    /// GET requests carry `params` as the query string, POST requests as the JSON body
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        params: &str,
+        signed: bool,
+    ) -> Result<Value, BybitError> {
+        let is_get = method == Method::GET;
+        let url = if is_get && !params.is_empty() {
+            format!("{}{}?{}", self.rest_url, path, params)
+        } else {
+            format!("{}{}", self.rest_url, path)
+        };
+
+        let mut request = self.http_client.request(method, &url);
+        if !is_get {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(params.to_string());
+        }
+        if signed {
+            let timestamp = now_ms();
+            request = request
+                .header("X-BAPI-API-KEY", &self.api_key)
+                .header("X-BAPI-TIMESTAMP", timestamp.to_string())
+                .header("X-BAPI-RECV-WINDOW", RECV_WINDOW.to_string())
+                .header("X-BAPI-SIGN", self.sign(timestamp, params));
+        }
+
+        let response = request
+            .send()
+            .await
             .map_err(|e| BybitError::NetworkError(e.to_string()))?;
-        
-        if !response.status().is_success() {
-            return Err(BybitError::ApiError(format!("Failed to get order book: {}", response.status())));
+        let status = response.status();
+        if status.as_u16() == 429 || status.as_u16() == 403 {
+            return Err(BybitError::RateLimitError(format!(
+                "Request to {} failed: {}",
+                path, status
+            )));
         }
-        
-        let json: Value = response.json().await
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BybitError::ApiError(format!(
+                "Request to {} failed: {} - {}",
+                path, status, error_text
+            )));
+        }
+
+        let json: Value = response
+            .json()
+            .await
             .map_err(|e| BybitError::ParseError(e.to_string()))?;
-        
-        let result = json.get("result")
-            .ok_or_else(|| BybitError::ParseError("Invalid response".to_string()))?;
-        
-        let bids = result.get("b")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&vec![])
-            .iter()
-            .filter_map(|level| {
-                if let (Some(price_str), Some(size_str)) = (level.get(0).and_then(|v| v.as_str()), level.get(1).and_then(|v| v.as_str())) {
-                    let price = Price::from_str(price_str).ok()?;
-                    let size = Size::from_str(size_str).ok()?;
-                    Some(OrderBookLevel::new(price, size))
-                } else {
-                    None
-                }
-            })
-            .collect();
-        
-        let asks = result.get("a")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&vec![])
-            .iter()
-            .filter_map(|level| {
-                if let (Some(price_str), Some(size_str)) = (level.get(0).and_then(|v| v.as_str()), level.get(1).and_then(|v| v.as_str())) {
-                    let price = Price::from_str(price_str).ok()?;
-                    let size = Size::from_str(size_str).ok()?;
-                    Some(OrderBookLevel::new(price, size))
-                } else {
-                    None
-                }
-            })
-            .collect();
-        
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
-        
-        Ok(OrderBookSnapshot::new(symbol.to_string(), bids, asks, timestamp))
+        let ret_code = json.get("retCode").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if ret_code != 0 {
+            let ret_msg = json
+                .get("retMsg")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let message = format!("{} failed: {} {}", path, ret_code, ret_msg);
+            return Err(match ret_code {
+                RET_CODE_RATE_LIMIT => BybitError::RateLimitError(message),
+                code if RET_CODES_AUTH.contains(&code) => BybitError::AuthenticationError(message),
+                _ => BybitError::ApiError(message),
+            });
+        }
+
+        json.get("result")
+            .cloned()
+            .ok_or_else(|| BybitError::ParseError(format!("{} returned no result", path)))
     }
 
+    /// Get current server time in milliseconds
+    pub async fn get_server_time(&self) -> Result<u64, BybitError> {
+        let result = self
+            .request(Method::GET, "/v5/market/time", "", false)
+            .await?;
+        result
+            .get("timeNano")
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|nanos| nanos / 1_000_000)
+            .ok_or_else(|| BybitError::ParseError("Invalid server time".to_string()))
+    }
+
+    /// Get an order book snapshot
+    pub async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: u32,
+    ) -> Result<OrderBookSnapshot, BybitError> {
+        let params = format!("category=spot&symbol={}&limit={}", symbol, limit);
+        let result = self
+            .request(Method::GET, "/v5/market/orderbook", &params, false)
+            .await?;
+        parse_order_book(&result)
+            .ok_or_else(|| BybitError::ParseError("Invalid order book".to_string()))
+    }
+
+    /// Place a new spot order
+    /// Market orders are sized in the base asset unless they carry a quote size
     pub async fn place_order(&self, order: &NewOrder) -> Result<OrderId, BybitError> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
-        
+        let (price_param, quantity_param) = self.format_order_params(order).await;
+
         let mut params = json!({
             "category": "spot",
-            "symbol": order.symbol,
+            "symbol": order.symbol.as_str(),
             "side": match order.side {
                 OrderSide::Buy => "Buy",
                 OrderSide::Sell => "Sell",
             },
             "orderType": match order.order_type {
                 OrderType::Market => "Market",
-                OrderType::Limit => "Limit",
+                _ => "Limit",
             },
-            "qty": order.quantity.to_string(),
+            "qty": quantity_param,
         });
-        
-        if let Some(price) = order.price {
-            params["price"] = json!(price.to_string());
+
+        if order.order_type == OrderType::Market {
+            match order.quote_size {
+                Some(quote_size) => {
+                    params["qty"] = json!(
+                        self.format_quote_param(order.symbol.as_str(), quote_size)
+                            .await
+                    );
+                    params["marketUnit"] = json!("quoteCoin");
+                }
+                None => params["marketUnit"] = json!("baseCoin"),
+            }
+        } else {
+            let price = price_param
+                .ok_or_else(|| BybitError::ApiError("Limit order needs a price".to_string()))?;
+            params["price"] = json!(price);
+            params["timeInForce"] = json!(match order.time_in_force {
+                TimeInForce::GoodTillCancelled => "GTC",
+                TimeInForce::ImmediateOrCancel => "IOC",
+                TimeInForce::FillOrKill => "FOK",
+            });
         }
-        
+
         if let Some(client_order_id) = &order.client_order_id {
             params["orderLinkId"] = json!(client_order_id);
         }
-        
-        let recv_window = "5000";
-        let params_str = serde_json::to_string(&params).unwrap();
-        let signature = self.sign(&timestamp, recv_window, &params_str);
-        
-        let url = format!("{}/v5/order/create", self.rest_url);
-        
-        let response = self.http_client
-            .post(&url)
-            .header("X-BAPI-API-KEY", &self.api_key)
-            .header("X-BAPI-SIGN", signature)
-            .header("X-BAPI-SIGN-TYPE", "2")
-            .header("X-BAPI-TIMESTAMP", &timestamp)
-            .header("X-BAPI-RECV-WINDOW", recv_window)
-            .header("Content-Type", "application/json")
-            .body(params_str)
-            .send()
-            .await
-            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
-        
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(BybitError::ApiError(format!("Failed to place order: {} - {}", response.status(), error_text)));
-        }
-        
-        let json: Value = response.json().await
-            .map_err(|e| BybitError::ParseError(e.to_string()))?;
-        
-        let order_id = json.get("result")
-            .and_then(|r| r.get("orderId"))
+
+        let result = self
+            .request(Method::POST, "/v5/order/create", &params.to_string(), true)
+            .await?;
+        let order_id: OrderId = result
+            .get("orderId")
             .and_then(|v| v.as_str())
-            .map(|id| OrderId::new(id.to_string()))
+            .map(|id| id.to_string())
             .ok_or_else(|| BybitError::ParseError("Invalid order ID in response".to_string()))?;
-        
+
+        self.order_symbols
+            .write()
+            .await
+            .insert(order_id.clone(), order.symbol.as_str().to_string());
         Ok(order_id)
     }
 
-    pub async fn get_account_info(&self) -> Result<Vec<Balance>, BybitError> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
-        
-        let recv_window = "5000";
-        let params = "";
-        let signature = self.sign(&timestamp, recv_window, params);
-        
-        let url = format!("{}/v5/account/wallet-balance?accountType=SPOT", self.rest_url);
-        
-        let response = self.http_client
-            .get(&url)
-            .header("X-BAPI-API-KEY", &self.api_key)
-            .header("X-BAPI-SIGN", signature)
-            .header("X-BAPI-SIGN-TYPE", "2")
-            .header("X-BAPI-TIMESTAMP", &timestamp)
-            .header("X-BAPI-RECV-WINDOW", recv_window)
-            .send()
+    /// Get the symbol of an order placed through this client
+    async fn order_symbol(&self, order_id: &OrderId) -> Result<String, BybitError> {
+        self.order_symbols
+            .read()
             .await
-            .map_err(|e| BybitError::NetworkError(e.to_string()))?;
-        
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(BybitError::ApiError(format!("Failed to get account info: {} - {}", response.status(), error_text)));
+            .get(order_id)
+            .cloned()
+            .ok_or_else(|| BybitError::ApiError(format!("Unknown order {}", order_id)))
+    }
+
+    /// Cancel an order
+    pub async fn cancel_order(&self, symbol: &str, order_id: OrderId) -> Result<(), BybitError> {
+        let params = json!({
+            "category": "spot",
+            "symbol": symbol,
+            "orderId": order_id,
+        });
+        self.request(Method::POST, "/v5/order/cancel", &params.to_string(), true)
+            .await?;
+        Ok(())
+    }
+
+    /// Get an order, whether open or recently closed
+    pub async fn get_order(
+        &self,
+        symbol: &str,
+        order_id: &OrderId,
+    ) -> Result<ExecutionReport, BybitError> {
+        let params = format!("category=spot&symbol={}&orderId={}", symbol, order_id);
+        for path in ["/v5/order/realtime", "/v5/order/history"] {
+            let result = self.request(Method::GET, path, &params, true).await?;
+            if let Some(report) = parse_orders(&result).into_iter().next() {
+                return Ok(report);
+            }
         }
-        
-        let json: Value = response.json().await
-            .map_err(|e| BybitError::ParseError(e.to_string()))?;
-        
-        let balances = json.get("result")
-            .and_then(|r| r.get("list"))
-            .and_then(|l| l.as_array())
-            .unwrap_or(&vec![])
-            .iter()
-            .flat_map(|account| {
-                account.get("coin")
-                    .and_then(|c| c.as_array())
-                    .unwrap_or(&vec![])
-                    .iter()
-                    .filter_map(|coin| {
-                        let asset = coin.get("coin")?.as_str()?.to_string();
-                        let free = coin.get("free")?.as_str()?;
-                        let locked = coin.get("locked")?.as_str()?;
-                        
-                        Some(Balance {
-                            asset,
-                            exchange_id: "bybit".to_string(),
-                            total: Size::from_str(free).ok()?.value() + Size::from_str(locked).ok()?.value(),
-                            free: Size::from_str(free).ok()?.value(),
-                            used: Size::from_str(locked).ok()?.value(),
-                        })
-                    })
-            })
-            .collect();
-        
-        Ok(balances)
+        Err(BybitError::ApiError(format!(
+            "Order {} not found",
+            order_id
+        )))
+    }
+
+    /// Get open orders
+    pub async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, BybitError> {
+        let mut params = "category=spot".to_string();
+        if let Some(symbol) = symbol {
+            params.push_str(&format!("&symbol={}", symbol));
+        }
+        let result = self
+            .request(Method::GET, "/v5/order/realtime", &params, true)
+            .await?;
+        Ok(parse_orders(&result))
+    }
+
+    /// Get closed orders, newest first
+    pub async fn get_order_history(
+        &self,
+        symbol: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, BybitError> {
+        let mut params = format!("category=spot&limit={}", limit.unwrap_or(50).min(50));
+        if let Some(symbol) = symbol {
+            params.push_str(&format!("&symbol={}", symbol));
+        }
+        let result = self
+            .request(Method::GET, "/v5/order/history", &params, true)
+            .await?;
+        Ok(parse_orders(&result))
     }
 
-    pub async fn get_open_orders(&self, symbol: Option<&str>) -> Result<Vec<ExecutionReport>, BybitError> {
-        // Simplified implementation - returns empty vector
-        // Full implementation would query Bybit API
-        Ok(vec![])
+    /// Get unified account balances
+    pub async fn get_account_info(&self) -> Result<Vec<Balance>, BybitError> {
+        let result = self
+            .request(
+                Method::GET,
+                "/v5/account/wallet-balance",
+                "accountType=UNIFIED",
+                true,
+            )
+            .await?;
+        Ok(parse_wallet_balance(&result))
     }
+
+    /// Get the account's fee rates for a symbol
+    pub async fn get_fee_rate(&self, symbol: &str) -> Result<TradingFees, BybitError> {
+        let params = format!("category=spot&symbol={}", symbol);
+        let result = self
+            .request(Method::GET, "/v5/account/fee-rate", &params, true)
+            .await?;
+        let rate = result
+            .get("list")
+            .and_then(|v| v.as_array())
+            .and_then(|list| list.first())
+            .ok_or_else(|| BybitError::ParseError("Invalid fee rate".to_string()))?;
+        let fee = |field: &str| {
+            rate.get(field)
+                .and_then(|v| v.as_str())
+                .and_then(|v| Size::from_str(v).ok())
+                .ok_or_else(|| BybitError::ParseError(format!("Invalid {}", field)))
+        };
+        Ok(TradingFees::new(
+            symbol.to_string(),
+            fee("makerFeeRate")?,
+            fee("takerFeeRate")?,
+        ))
+    }
+}
+
+/// Parse `[[price, size], ...]` levels
+fn parse_levels(levels: Option<&Value>) -> Vec<OrderBookLevel> {
+    levels
+        .and_then(|v| v.as_array())
+        .map(|levels| {
+            levels
+                .iter()
+                .filter_map(|level| {
+                    let price = Price::from_str(level.get(0)?.as_str()?).ok()?;
+                    let size = Size::from_str(level.get(1)?.as_str()?).ok()?;
+                    Some(OrderBookLevel::new(price, size))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse an order book (REST `result` or WebSocket `data`)
+pub fn parse_order_book(book: &Value) -> Option<OrderBookSnapshot> {
+    let symbol = book.get("s")?.as_str()?;
+    let timestamp = book
+        .get("ts")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_else(now_ms);
+    Some(OrderBookSnapshot::new(
+        symbol,
+        "bybit",
+        parse_levels(book.get("b")),
+        parse_levels(book.get("a")),
+        timestamp,
+    ))
+}
+
+/// Parse a message from the public WebSocket stream
+/// Returns None for subscription acknowledgements, pongs and topics that are not mapped
+pub fn parse_ws_message(text: &str) -> Result<Option<MarketEvent>, BybitError> {
+    let json: Value =
+        serde_json::from_str(text).map_err(|e| BybitError::ParseError(e.to_string()))?;
+
+    if json.get("op").is_some() {
+        if json.get("success").and_then(|v| v.as_bool()) == Some(false) {
+            let ret_msg = json
+                .get("ret_msg")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            return Err(BybitError::ApiError(format!(
+                "WebSocket request failed: {}",
+                ret_msg
+            )));
+        }
+        return Ok(None);
+    }
+
+    let topic = json
+        .get("topic")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    if !topic.starts_with("orderbook.") {
+        return Ok(None);
+    }
+
+    let data = json
+        .get("data")
+        .ok_or_else(|| BybitError::ParseError("Order book message without data".to_string()))?;
+    let symbol = data
+        .get("s")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| BybitError::ParseError("Order book message without symbol".to_string()))?;
+    let timestamp = json
+        .get("ts")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_else(now_ms);
+    let bids = parse_levels(data.get("b"));
+    let asks = parse_levels(data.get("a"));
+
+    // Deltas carry size 0 for removed levels, as the order book expects
+    match json.get("type").and_then(|v| v.as_str()) {
+        Some("snapshot") => Ok(Some(MarketEvent::OrderBookSnapshot(
+            OrderBookSnapshot::new(symbol, "bybit", bids, asks, timestamp),
+        ))),
+        Some("delta") => Ok(Some(MarketEvent::OrderBookDelta(OrderBookDelta::new(
+            symbol, "bybit", bids, asks, timestamp,
+        )))),
+        other => Err(BybitError::ParseError(format!(
+            "Unknown order book message type {:?}",
+            other
+        ))),
+    }
+}
+
+/// Parse the `list` of an order query into execution reports
+pub fn parse_orders(result: &Value) -> Vec<ExecutionReport> {
+    result
+        .get("list")
+        .and_then(|v| v.as_array())
+        .map(|orders| orders.iter().filter_map(parse_order).collect())
+        .unwrap_or_default()
+}
+
+fn parse_order(order: &Value) -> Option<ExecutionReport> {
+    let decimal_field = |field: &str| order.get(field)?.as_str().filter(|v| !v.is_empty());
+
+    let status = match order.get("orderStatus")?.as_str()? {
+        "New" | "Untriggered" | "Created" => OrderStatus::New,
+        "PartiallyFilled" => OrderStatus::PartiallyFilled,
+        "Filled" => OrderStatus::Filled,
+        "Cancelled" | "PartiallyFilledCanceled" | "Deactivated" => OrderStatus::Cancelled,
+        "Rejected" => OrderStatus::Rejected,
+        _ => return None,
+    };
+    let quantity = Size::from_str(decimal_field("qty")?).ok()?;
+    let filled_size = decimal_field("cumExecQty")
+        .and_then(|v| Size::from_str(v).ok())
+        .unwrap_or_else(Size::zero);
+    let average_price = decimal_field("avgPrice")
+        .and_then(|v| Price::from_str(v).ok())
+        .filter(|price| price.value() > rust_decimal::Decimal::ZERO);
+    let timestamp = decimal_field("updatedTime")
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or_default();
+
+    Some(ExecutionReport {
+        order_id: order.get("orderId")?.as_str()?.to_string(),
+        client_order_id: decimal_field("orderLinkId").map(|v| v.to_string()),
+        symbol: Symbol::new(order.get("symbol")?.as_str()?),
+        exchange_id: "bybit".to_string(),
+        status,
+        filled_size,
+        remaining_size: Size::new(quantity.value() - filled_size.value()),
+        average_price,
+        timestamp,
+        reject_reason: None,
+    })
+}
+
+/// Parse a unified account wallet balance
+pub fn parse_wallet_balance(result: &Value) -> Vec<Balance> {
+    result
+        .get("list")
+        .and_then(|v| v.as_array())
+        .map(|accounts| {
+            accounts
+                .iter()
+                .filter_map(|account| account.get("coin")?.as_array())
+                .flatten()
+                .filter_map(|coin| {
+                    let amount = |field: &str| {
+                        coin.get(field)
+                            .and_then(|v| v.as_str())
+                            .filter(|v| !v.is_empty())
+                            .and_then(|v| Size::from_str(v).ok())
+                            .map(|size| size.value())
+                            .unwrap_or_default()
+                    };
+                    let total = amount("walletBalance");
+                    let used = amount("locked");
+                    Some(Balance {
+                        asset: coin.get("coin")?.as_str()?.to_string(),
+                        exchange_id: "bybit".to_string(),
+                        total,
+                        free: total - used,
+                        used,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
+/// Parse spot instrument trading rules from an instruments-info `result`
+pub fn parse_instrument_specs(result: &Value) -> Vec<InstrumentSpec> {
+    result
+        .get("list")
+        .and_then(|v| v.as_array())
+        .map(|instruments| {
+            instruments
+                .iter()
+                .filter_map(|info| {
+                    let symbol = info.get("symbol")?.as_str()?;
+                    let tick_size = info.get("priceFilter")?.get("tickSize")?.as_str()?;
+                    let step_size = info.get("lotSizeFilter")?.get("basePrecision")?.as_str()?;
+                    Some(InstrumentSpec::new(
+                        symbol,
+                        Price::from_str(tick_size).ok()?,
+                        Size::from_str(step_size).ok()?,
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Bybit WebSocket stream for the unified V5 public order book channel
 pub struct BybitWebSocket {
-    ws_sender: Option<tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>>,
-    subscriptions: Arc<RwLock<Vec<String>>>,
+    /// WebSocket URL
+    ws_url: String,
+    /// WebSocket connection
+    ws_stream: Option<
+        tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >,
+    >,
+    /// Subscribed symbols
+    subscriptions: Vec<String>,
+    /// Connection status
     connected: Arc<RwLock<bool>>,
-    last_updates: Arc<RwLock<HashMap<String, u64>>>,
+    /// Time of the last ping sent
+    last_ping: Instant,
+    /// Per-symbol message counts and last update times
+    symbol_stats: SymbolStatsRegistry,
 }
 
 impl BybitWebSocket {
-    pub fn new() -> Self {
+    /// Create a new Bybit WebSocket stream
+    pub fn new(testnet: bool) -> Self {
+        let ws_url = if testnet {
+            "wss://stream-testnet.bybit.com/v5/public/spot".to_string()
+        } else {
+            "wss://stream.bybit.com/v5/public/spot".to_string()
+        };
         Self {
-            ws_sender: None,
-            subscriptions: Arc::new(RwLock::new(Vec::new())),
+            ws_url,
+            ws_stream: None,
+            subscriptions: Vec::new(),
             connected: Arc::new(RwLock::new(false)),
-            last_updates: Arc::new(RwLock::new(HashMap::new())),
+            last_ping: Instant::now(),
+            symbol_stats: SymbolStatsRegistry::new(),
+        }
+    }
+
+    /// Record stats into a shared registry (builder pattern)
+    pub fn with_symbol_stats(mut self, symbol_stats: SymbolStatsRegistry) -> Self {
+        self.symbol_stats = symbol_stats;
+        self
+    }
+
+    /// Get the per-symbol stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Order book topic for a symbol
+    fn topic(symbol: &str) -> String {
+        format!("orderbook.{}.{}", WS_BOOK_DEPTH, symbol.to_uppercase())
+    }
+
+    /// Send subscribe or unsubscribe requests for symbols
+    async fn send_op(&mut self, op: &str, symbols: &[&str]) -> Result<(), BybitError> {
+        let Some(ws) = &mut self.ws_stream else {
+            return Err(BybitError::ConnectionError("Not connected".to_string()));
+        };
+        let topics: Vec<String> = symbols.iter().map(|s| Self::topic(s)).collect();
+        for chunk in topics.chunks(WS_MAX_TOPICS_PER_REQUEST) {
+            let request = json!({ "op": op, "args": chunk });
+            ws.send(Message::Text(request.to_string()))
+                .await
+                .map_err(|e| BybitError::ConnectionError(e.to_string()))?;
         }
+        Ok(())
     }
 
+    /// Connect to the WebSocket stream and subscribe to symbols
     pub async fn connect(&mut self, symbols: &[&str]) -> Result<(), BybitError> {
-        let (ws_stream, _) = connect_async("wss://stream.bybit.com/v5/public/spot").await
+        log::info!("Connecting to Bybit WebSocket: {}", self.ws_url);
+
+        let (ws_stream, _) = connect_async(&self.ws_url)
+            .await
             .map_err(|e| BybitError::ConnectionError(e.to_string()))?;
-        
-        self.ws_sender = Some(ws_stream);
-        
-        if let Some(ws) = &mut self.ws_sender {
-            for symbol in symbols {
-                let subscribe_msg = json!({
-                    "op": "subscribe",
-                    "args": [format!("orderbook.1.{}", symbol)]
-                });
-                
-                ws.send(Message::Text(subscribe_msg.to_string())).await
-                    .map_err(|e| BybitError::ConnectionError(e.to_string()))?;
-            }
-        }
-        
-        let mut subs = self.subscriptions.write().await;
+        self.ws_stream = Some(ws_stream);
+        self.last_ping = Instant::now();
+        *self.connected.write().await = true;
+
         for symbol in symbols {
-            if !subs.contains(&symbol.to_string()) {
-                subs.push(symbol.to_string());
+            if !self.subscriptions.iter().any(|s| s == symbol) {
+                self.subscriptions.push(symbol.to_string());
             }
         }
-        
-        let mut connected = self.connected.write().await;
-        *connected = true;
-        
-        Ok(())
+        let subscriptions = self.subscriptions.clone();
+        let symbol_refs: Vec<&str> = subscriptions.iter().map(|s| s.as_str()).collect();
+        self.send_op("subscribe", &symbol_refs).await
     }
 
+    /// Disconnect from the WebSocket stream
     pub async fn disconnect(&mut self) -> Result<(), BybitError> {
-        if let Some(mut ws) = self.ws_sender.take() {
-            ws.close(None).await
+        if let Some(mut ws) = self.ws_stream.take() {
+            ws.close(None)
+                .await
+                .map_err(|e| BybitError::ConnectionError(e.to_string()))?;
+        }
+        *self.connected.write().await = false;
+        Ok(())
+    }
+
+    /// Send an application-level ping
+    async fn ping(&mut self) -> Result<(), BybitError> {
+        self.last_ping = Instant::now();
+        if let Some(ws) = &mut self.ws_stream {
+            ws.send(Message::Text(json!({ "op": "ping" }).to_string()))
+                .await
                 .map_err(|e| BybitError::ConnectionError(e.to_string()))?;
         }
-        
-        let mut connected = self.connected.write().await;
-        *connected = false;
-        
         Ok(())
     }
 }
@@ -320,43 +719,74 @@ impl MarketDataStream for BybitWebSocket {
     type Error = BybitError;
 
     async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
-        if let Some(ws) = &mut self.ws_sender {
-            for symbol in symbols {
-                let subscribe_msg = json!({
-                    "op": "subscribe",
-                    "args": [format!("orderbook.1.{}", symbol)]
-                });
-                
-                ws.send(Message::Text(subscribe_msg.to_string())).await
-                    .map_err(|e| BybitError::ConnectionError(e.to_string()))?;
-                
-                let mut subs = self.subscriptions.write().await;
-                subs.push(symbol.to_string());
-            }
-        } else {
-            self.connect(symbols).await?;
+        if self.ws_stream.is_none() {
+            return self.connect(symbols).await;
         }
-        
+        let new_symbols: Vec<&str> = symbols
+            .iter()
+            .copied()
+            .filter(|symbol| !self.subscriptions.iter().any(|s| s == symbol))
+            .collect();
+        self.send_op("subscribe", &new_symbols).await?;
+        self.subscriptions
+            .extend(new_symbols.iter().map(|s| s.to_string()));
         Ok(())
     }
 
-    async fn unsubscribe(&mut self, _symbols: &[&str]) -> Result<(), Self::Error> {
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.subscriptions
+            .retain(|s| !symbols.contains(&s.as_str()));
+        if self.ws_stream.is_some() {
+            self.send_op("unsubscribe", symbols).await?;
+        }
         Ok(())
     }
 
     async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
-        None // Simplified - would parse Bybit WebSocket messages
+        loop {
+            if self.last_ping.elapsed() >= WS_PING_INTERVAL {
+                if let Err(e) = self.ping().await {
+                    return Some(Err(e));
+                }
+            }
+            let ws = self.ws_stream.as_mut()?;
+            // Wake up in time to keep pinging a quiet stream
+            let wait = WS_PING_INTERVAL.saturating_sub(self.last_ping.elapsed());
+            let message = match tokio::time::timeout(wait, ws.next()).await {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+
+            match message {
+                Some(Ok(Message::Text(text))) => match parse_ws_message(&text) {
+                    Ok(Some(event)) => {
+                        self.symbol_stats.record_event(&event);
+                        return Some(Ok(event));
+                    }
+                    Ok(None) => continue,
+                    Err(e) => return Some(Err(e)),
+                },
+                Some(Ok(Message::Close(_))) | None => {
+                    self.ws_stream = None;
+                    *self.connected.write().await = false;
+                    return None;
+                }
+                Some(Err(e)) => return Some(Err(BybitError::ConnectionError(e.to_string()))),
+                Some(Ok(_)) => continue,
+            }
+        }
     }
 
     fn is_connected(&self) -> bool {
-        *self.connected.try_read().unwrap_or(&false)
+        self.connected.try_read().map(|c| *c).unwrap_or(true)
     }
 
-    fn last_update(&self, _symbol: &str) -> Option<u64> {
-        None
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
     }
 }
 
+/// Bybit error types
 #[derive(Debug, Clone)]
 pub enum BybitError {
     NetworkError(String),
@@ -382,99 +812,447 @@ impl std::fmt::Display for BybitError {
 
 impl std::error::Error for BybitError {}
 
+/// Bybit adapter that implements MarketDataStream, ExecutionClient and ExchangeAdapter
 pub struct BybitAdapter {
+    /// Bybit client for REST API
     client: BybitClient,
+    /// Bybit WebSocket for market data
     websocket: Arc<Mutex<BybitWebSocket>>,
+    /// Per-symbol stats shared with the WebSocket
+    symbol_stats: SymbolStatsRegistry,
 }
 
 impl BybitAdapter {
+    /// Create a new Bybit adapter
     pub fn new(api_key: String, api_secret: String, testnet: bool) -> Self {
+        let symbol_stats = SymbolStatsRegistry::new();
         Self {
             client: BybitClient::new(api_key, api_secret, testnet),
-            websocket: Arc::new(Mutex::new(BybitWebSocket::new())),
+            websocket: Arc::new(Mutex::new(
+                BybitWebSocket::new(testnet).with_symbol_stats(symbol_stats.clone()),
+            )),
+            symbol_stats,
         }
     }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.client = self.client.with_rest_url(rest_url);
+        self
+    }
+
+    /// Get the per-symbol market data stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Set the trading rules used to format orders for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        self.client.set_instrument_spec(spec).await;
+    }
+
+    /// Load trading rules for all spot symbols from the exchange
+    pub async fn load_instrument_specs(&self) -> Result<usize, BybitError> {
+        self.client.load_instrument_specs().await
+    }
 }
 
 #[async_trait]
-impl ExchangeAdapter for BybitAdapter {
+impl ServerClock for BybitAdapter {
+    type Error = BybitError;
+
+    async fn server_time(&self) -> Result<u64, Self::Error> {
+        self.client.get_server_time().await
+    }
+}
+
+#[async_trait]
+impl ExecutionClient for BybitAdapter {
+    type Error = BybitError;
+
+    async fn place_order(&self, order: NewOrder) -> Result<OrderId, Self::Error> {
+        self.client.place_order(&order).await
+    }
+
+    async fn cancel_order(&self, order_id: OrderId) -> Result<(), Self::Error> {
+        let symbol = self.client.order_symbol(&order_id).await?;
+        self.client.cancel_order(&symbol, order_id).await
+    }
+
+    async fn get_order_status(&self, order_id: OrderId) -> Result<ExecutionReport, Self::Error> {
+        let symbol = self.client.order_symbol(&order_id).await?;
+        self.client.get_order(&symbol, &order_id).await
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Self::Error> {
+        self.client.get_account_info().await
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.client.get_open_orders(symbol).await
+    }
+
+    async fn get_order_history(
+        &self,
+        symbol: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.client.get_order_history(symbol, limit).await
+    }
+
+    async fn get_trading_fees(&self, symbol: &str) -> Result<TradingFees, Self::Error> {
+        self.client.get_fee_rate(symbol).await
+    }
+
+    fn supports_quote_size(&self, order_type: OrderType) -> bool {
+        // Spot market orders take marketUnit=quoteCoin
+        order_type == OrderType::Market
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for BybitAdapter {
+    type Error = BybitError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.websocket.lock().await.subscribe(symbols).await
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.websocket.lock().await.unsubscribe(symbols).await
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        self.websocket.lock().await.next().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.websocket
+            .try_lock()
+            .map(|ws| ws.is_connected())
+            .unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+#[async_trait]
+impl crate::exchanges::connection_manager::ExchangeAdapter for BybitAdapter {
     async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // The WebSocket connects on the first subscription
         Ok(())
     }
 
     async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut ws = self.websocket.lock().await;
-        ws.disconnect().await.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        ws.disconnect()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
-    async fn get_market_data_stream(&self) -> Result<Arc<Mutex<dyn MarketDataStream<Error = Box<dyn std::error::Error + Send + Sync>> + Send + Sync>>, Box<dyn std::error::Error + Send + Sync>> {
-        Ok(Arc::new(Mutex::new(BybitWebSocketAdapter {
+    async fn get_market_data_stream(
+        &self,
+    ) -> Result<
+        Arc<
+            tokio::sync::Mutex<
+                dyn MarketDataStream<Error = crate::exchanges::error::BoxedError> + Send + Sync,
+            >,
+        >,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        Ok(Arc::new(tokio::sync::Mutex::new(BybitWebSocketAdapter {
             websocket: self.websocket.clone(),
+            symbol_stats: self.symbol_stats.clone(),
         })))
     }
 
-    async fn place_order(&self, order: NewOrder) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
-        self.client.place_order(&order).await.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    async fn place_order(
+        &self,
+        order: NewOrder,
+    ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::place_order(self, order)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
-    async fn cancel_order(&self, _order_id: OrderId) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        Err("Symbol required to cancel order".into())
+    async fn cancel_order(
+        &self,
+        order_id: OrderId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::cancel_order(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
-    async fn get_order_status(&self, _order_id: OrderId) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
-        Err("Symbol required to get order status".into())
+    async fn get_order_status(
+        &self,
+        order_id: OrderId,
+    ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::get_order_status(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
     async fn get_balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
-        self.client.get_account_info().await.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        self.client
+            .get_account_info()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
-    async fn get_open_orders(&self, symbol: Option<&str>) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
-        self.client.get_open_orders(symbol).await.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_open_orders(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
-    async fn get_order_book(&self, symbol: &str, limit: u32) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
-        self.client.get_order_book(symbol, limit).await.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: u32,
+    ) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_order_book(symbol, limit)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 
-    async fn get_trading_fees(&self, _symbol: &str) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
-        Ok(TradingFees {
-            maker_fee: rust_decimal::Decimal::new(1, 4), // 0.0001
-            taker_fee: rust_decimal::Decimal::new(1, 4), // 0.0001
-        })
+    async fn get_trading_fees(
+        &self,
+        symbol: &str,
+    ) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_fee_rate(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
     }
 }
 
+/// Wrapper for BybitWebSocket to implement the type-erased MarketDataStream
 pub struct BybitWebSocketAdapter {
-    websocket: Arc<tokio::sync::Mutex<BybitWebSocket>>,
+    websocket: Arc<Mutex<BybitWebSocket>>,
+    symbol_stats: SymbolStatsRegistry,
 }
 
 #[async_trait]
 impl MarketDataStream for BybitWebSocketAdapter {
-    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Error = crate::exchanges::error::BoxedError;
 
     async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
         let mut ws = self.websocket.lock().await;
-        ws.subscribe(symbols).await.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        ws.subscribe(symbols)
+            .await
+            .map_err(crate::exchanges::error::BoxedError::new)
     }
 
     async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
         let mut ws = self.websocket.lock().await;
-        ws.unsubscribe(symbols).await.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        ws.unsubscribe(symbols)
+            .await
+            .map_err(crate::exchanges::error::BoxedError::new)
     }
 
     async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
         let mut ws = self.websocket.lock().await;
-        ws.next().await.map(|r| r.map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>))
+        ws.next()
+            .await
+            .map(|r| r.map_err(crate::exchanges::error::BoxedError::new))
     }
 
     fn is_connected(&self) -> bool {
-        true
+        self.websocket
+            .try_lock()
+            .map(|ws| ws.is_connected())
+            .unwrap_or(true)
     }
 
     fn last_update(&self, symbol: &str) -> Option<u64> {
-        self.websocket.try_lock().ok()
-            .and_then(|ws| ws.last_update(symbol))
+        self.symbol_stats.last_update(symbol)
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v5_signature() {
+        let client = BybitClient::new("test_key".to_string(), "test_secret".to_string(), true);
+        // HMAC-SHA256 of "1700000000000test_key5000category=spot&symbol=BTCUSDT"
+        assert_eq!(
+            client.sign(1_700_000_000_000, "category=spot&symbol=BTCUSDT"),
+            "a04c472e89f841b28f950a4fe5b68224b8bb8468dfe801a00eb308813c03ad50"
+        );
+    }
+
+    #[test]
+    fn test_parse_ws_order_book() {
+        let snapshot = r#"{"topic":"orderbook.50.BTCUSDT","ts":1700000000123,"type":"snapshot",
+            "data":{"s":"BTCUSDT","b":[["50000.10","1.5"],["50000.00","2"]],"a":[["50000.20","0.8"]],"u":1,"seq":100}}"#;
+        match parse_ws_message(snapshot).unwrap() {
+            Some(MarketEvent::OrderBookSnapshot(book)) => {
+                assert_eq!(book.symbol.value(), "BTCUSDT");
+                assert_eq!(book.exchange_id, "bybit");
+                assert_eq!(book.bids.len(), 2);
+                assert_eq!(book.asks[0].price, Price::from_str("50000.20").unwrap());
+                assert_eq!(book.timestamp, 1700000000123);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let delta = r#"{"topic":"orderbook.50.BTCUSDT","ts":1700000000200,"type":"delta",
+            "data":{"s":"BTCUSDT","b":[["50000.10","0"]],"a":[],"u":2,"seq":101}}"#;
+        match parse_ws_message(delta).unwrap() {
+            Some(MarketEvent::OrderBookDelta(delta)) => {
+                assert!(delta.bids[0].size.is_zero());
+                assert!(delta.asks.is_empty());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let ack = r#"{"success":true,"ret_msg":"subscribe","conn_id":"abc","op":"subscribe"}"#;
+        assert!(parse_ws_message(ack).unwrap().is_none());
+        let pong = r#"{"success":true,"ret_msg":"pong","conn_id":"abc","op":"ping"}"#;
+        assert!(parse_ws_message(pong).unwrap().is_none());
+        let failed = r#"{"success":false,"ret_msg":"error:handler not found","op":"subscribe"}"#;
+        assert!(parse_ws_message(failed).is_err());
+    }
+
+    #[test]
+    fn test_parse_orders_and_balances() {
+        let orders = serde_json::json!({
+            "list": [{
+                "orderId": "1321003749386327552",
+                "orderLinkId": "arb-1",
+                "symbol": "ETHUSDT",
+                "orderStatus": "PartiallyFilled",
+                "qty": "2",
+                "cumExecQty": "0.5",
+                "avgPrice": "2000.5",
+                "updatedTime": "1700000000000"
+            }, {
+                "orderId": "2",
+                "orderLinkId": "",
+                "symbol": "ETHUSDT",
+                "orderStatus": "New",
+                "qty": "1",
+                "cumExecQty": "0",
+                "avgPrice": "",
+                "updatedTime": "1700000000001"
+            }]
+        });
+        let reports = parse_orders(&orders);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].status, OrderStatus::PartiallyFilled);
+        assert_eq!(reports[0].client_order_id.as_deref(), Some("arb-1"));
+        assert_eq!(reports[0].remaining_size, Size::from_str("1.5").unwrap());
+        assert_eq!(reports[1].client_order_id, None);
+        assert_eq!(reports[1].average_price, None);
+
+        let wallet = serde_json::json!({
+            "list": [{
+                "accountType": "UNIFIED",
+                "coin": [
+                    {"coin": "USDT", "walletBalance": "1000.5", "locked": "200"},
+                    {"coin": "BTC", "walletBalance": "0.1", "locked": ""}
+                ]
+            }]
+        });
+        let balances = parse_wallet_balance(&wallet);
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].free, rust_decimal::Decimal::new(8005, 1));
+        assert_eq!(balances[1].used, rust_decimal::Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_place_and_cancel_signed_orders() {
+        use wiremock::matchers::{body_partial_json, header_exists, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v5/order/create"))
+            .and(header_exists("X-BAPI-SIGN"))
+            .and(body_partial_json(serde_json::json!({
+                "category": "spot",
+                "symbol": "BTCUSDT",
+                "side": "Buy",
+                "orderType": "Limit",
+                "qty": "0.00123",
+                "price": "50000.13",
+                "timeInForce": "GTC"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {"orderId": "1001", "orderLinkId": ""}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v5/order/cancel"))
+            .and(body_partial_json(serde_json::json!({
+                "symbol": "BTCUSDT",
+                "orderId": "1001"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 0,
+                "retMsg": "OK",
+                "result": {"orderId": "1001"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v5/account/fee-rate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "retCode": 10003,
+                "retMsg": "API key is invalid.",
+                "result": {}
+            })))
+            .mount(&server)
+            .await;
+
+        let adapter = BybitAdapter::new("key".to_string(), "secret".to_string(), true)
+            .with_rest_url(server.uri());
+        adapter
+            .set_instrument_spec(InstrumentSpec::new(
+                "BTCUSDT",
+                Price::from_str("0.01").unwrap(),
+                Size::from_str("0.00001").unwrap(),
+            ))
+            .await;
+
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("0.0012345").unwrap(),
+            Price::from_str("50000.129").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let order_id = ExecutionClient::place_order(&adapter, order).await.unwrap();
+        assert_eq!(order_id, "1001");
+
+        // The symbol is remembered, so cancelling needs only the order ID
+        ExecutionClient::cancel_order(&adapter, order_id)
+            .await
+            .unwrap();
+        assert!(
+            ExecutionClient::cancel_order(&adapter, "unknown".to_string())
+                .await
+                .is_err()
+        );
+
+        assert!(matches!(
+            ExecutionClient::get_trading_fees(&adapter, "BTCUSDT").await,
+            Err(BybitError::AuthenticationError(_))
+        ));
+    }
+}
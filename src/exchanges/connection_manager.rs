@@ -1,11 +1,17 @@
 use async_trait::async_trait;
-use std::collections::HashMap;
+use futures_util::future::join_all;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 
 use crate::core::events::OrderBookSnapshot;
 use crate::exchanges::error::BoxedError;
+use crate::monitoring::supervisor::{Heartbeat, TaskSupervisor};
+use crate::realtime::error_recovery::RetryConfig;
 use crate::traits::{
     Balance, ExecutionReport, MarketDataStream, MarketEvent, NewOrder, OrderId, TradingFees,
 };
@@ -94,14 +100,22 @@ pub struct ConnectionManager {
     connections: Arc<RwLock<HashMap<String, ExchangeConnection>>>,
     /// Exchange adapters
     adapters: Arc<RwLock<HashMap<String, Arc<dyn ExchangeAdapter + Send + Sync>>>>,
+    /// Endpoints behind each exchange's adapter
+    pools: Arc<RwLock<HashMap<String, Arc<EndpointPool>>>>,
     /// Market data streams
-    streams: Arc<
-        RwLock<HashMap<String, Arc<Mutex<dyn MarketDataStream<Error = BoxedError> + Send + Sync>>>>,
-    >,
+    streams: Arc<RwLock<HashMap<String, Arc<Mutex<ReconnectingStream>>>>>,
     /// Event handlers
-    event_handlers: Arc<Mutex<Vec<Box<dyn Fn(MarketEvent) + Send + Sync>>>>,
+    event_handlers: EventHandlers,
     /// Shutdown flag
     shutdown: Arc<RwLock<bool>>,
+    /// Backoff used when a market data stream reconnects
+    retry_config: RetryConfig,
+    /// Endpoint probing and health thresholds
+    latency_config: LatencyConfig,
+    /// Supervisor restarting stalled market data readers and the reconnect loop
+    supervisor: Option<Arc<TaskSupervisor>>,
+    /// Time without a market data event after which a reader counts as stalled
+    reader_timeout: Duration,
 }
 
 impl ConnectionManager {
@@ -110,13 +124,43 @@ impl ConnectionManager {
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
             adapters: Arc::new(RwLock::new(HashMap::new())),
+            pools: Arc::new(RwLock::new(HashMap::new())),
             streams: Arc::new(RwLock::new(HashMap::new())),
             event_handlers: Arc::new(Mutex::new(Vec::new())),
             shutdown: Arc::new(RwLock::new(false)),
+            retry_config: RetryConfig::default(),
+            latency_config: LatencyConfig::default(),
+            supervisor: None,
+            reader_timeout: Duration::from_secs(30),
         }
     }
 
+    /// Set the backoff used when a market data stream reconnects
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Set how endpoints are probed and when they count as degraded
+    pub fn with_latency_config(mut self, latency_config: LatencyConfig) -> Self {
+        self.latency_config = latency_config;
+        self
+    }
+
+    /// Run market data readers and the reconnect loop under a supervisor (builder pattern)
+    /// A reader that delivers no event for `reader_timeout` is restarted on a fresh connection.
+    pub fn with_supervisor(
+        mut self,
+        supervisor: Arc<TaskSupervisor>,
+        reader_timeout: Duration,
+    ) -> Self {
+        self.supervisor = Some(supervisor);
+        self.reader_timeout = reader_timeout;
+        self
+    }
+
     /// Add an exchange adapter
+    /// The adapter becomes the exchange's `primary` endpoint.
     pub async fn add_exchange(
         &self,
         name: String,
@@ -125,12 +169,56 @@ impl ConnectionManager {
         let mut connections = self.connections.write().await;
         connections.insert(name.clone(), ExchangeConnection::new(name.clone()));
 
+        let pool = Arc::new(
+            EndpointPool::new(self.latency_config.clone()).with_endpoint("primary", adapter),
+        );
+        self.pools.write().await.insert(name.clone(), pool.clone());
         let mut adapters = self.adapters.write().await;
-        adapters.insert(name.clone(), adapter);
+        adapters.insert(name.clone(), pool);
 
         info!("Added exchange: {}", name);
     }
 
+    /// Add a backup endpoint to an exchange, e.g. an adapter for another host or region
+    /// Requests move to it whenever it is the healthy endpoint with the lowest RTT.
+    pub async fn add_endpoint(
+        &self,
+        exchange: &str,
+        endpoint: &str,
+        adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let pools = self.pools.read().await;
+        if let Some(pool) = pools.get(exchange) {
+            pool.add_endpoint(endpoint, adapter);
+            info!("Added endpoint {} to exchange: {}", endpoint, exchange);
+            Ok(())
+        } else {
+            let error_msg = format!("Exchange adapter not found: {}", exchange);
+            error!("{}", error_msg);
+            Err(error_msg.into())
+        }
+    }
+
+    /// Get the RTT and health of an exchange's endpoints
+    pub async fn endpoint_health(&self, exchange: &str) -> Vec<EndpointHealth> {
+        let pools = self.pools.read().await;
+        pools
+            .get(exchange)
+            .map(|pool| pool.health())
+            .unwrap_or_default()
+    }
+
+    /// Get the endpoint an exchange's requests currently go to
+    pub async fn active_endpoint(&self, exchange: &str) -> Option<String> {
+        let pools = self.pools.read().await;
+        pools.get(exchange).and_then(|pool| pool.active_endpoint())
+    }
+
+    /// Measure the RTT of every endpoint once
+    pub async fn probe_endpoints(&self) {
+        probe_pools(&self.pools).await;
+    }
+
     /// Connect to an exchange
     pub async fn connect_exchange(
         &self,
@@ -268,56 +356,39 @@ impl ConnectionManager {
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let adapters = self.adapters.read().await;
         if let Some(adapter) = adapters.get(name) {
-            let stream = adapter.get_market_data_stream().await?;
+            let mut stream = ReconnectingStream::new(adapter.clone())
+                .with_retry_config(self.retry_config.clone());
+            stream.subscribe(&[]).await?;
 
-            let mut streams = self.streams.write().await;
-            streams.insert(name.to_string(), stream);
+            self.streams
+                .write()
+                .await
+                .insert(name.to_string(), Arc::new(Mutex::new(stream)));
 
             // Start processing market data in the background
-            let name_clone = name.to_string();
-            let streams_clone = self.streams.clone();
-            let event_handlers = self.event_handlers.clone();
-            let shutdown = self.shutdown.clone();
-
-            tokio::spawn(async move {
-                loop {
-                    // Check if shutdown is requested
-                    {
-                        let shutdown_flag = shutdown.read().await;
-                        if *shutdown_flag {
-                            break;
-                        }
-                    }
-
-                    // Get the stream
-                    let streams_guard = streams_clone.read().await;
-                    if let Some(stream) = streams_guard.get(&name_clone) {
-                        let mut stream_guard = stream.lock().await;
-
-                        // Process next market event
-                        match stream_guard.next().await {
-                            Some(Ok(event)) => {
-                                // Handle the event
-                                let handlers = event_handlers.lock().await;
-                                for handler in handlers.iter() {
-                                    handler(event.clone());
-                                }
-                            }
-                            Some(Err(e)) => {
-                                error!("Error in market data stream for {}: {}", name_clone, e);
-                                // In a real implementation, you might want to reconnect here
-                                break;
-                            }
-                            None => {
-                                debug!("Market data stream for {} ended", name_clone);
-                                break;
-                            }
-                        }
-                    } else {
-                        break;
-                    }
+            let reader = MarketDataReader {
+                name: name.to_string(),
+                streams: self.streams.clone(),
+                connections: self.connections.clone(),
+                event_handlers: self.event_handlers.clone(),
+                shutdown: self.shutdown.clone(),
+            };
+            match &self.supervisor {
+                Some(supervisor) => {
+                    let task = reader_task_name(name);
+                    supervisor.unsupervise(&task).await;
+                    let restarted = AtomicBool::new(false);
+                    supervisor
+                        .supervise(task, self.reader_timeout, move |heartbeat| {
+                            let reconnect = restarted.swap(true, Ordering::Relaxed);
+                            reader.clone().run(reconnect, Some(heartbeat))
+                        })
+                        .await;
                 }
-            });
+                None => {
+                    tokio::spawn(reader.run(false, None));
+                }
+            }
 
             info!("Started market data stream for exchange: {}", name);
             Ok(())
@@ -333,6 +404,9 @@ impl ConnectionManager {
         &self,
         name: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(supervisor) = &self.supervisor {
+            supervisor.unsupervise(&reader_task_name(name)).await;
+        }
         let mut streams = self.streams.write().await;
         if streams.remove(name).is_some() {
             info!("Stopped market data stream for exchange: {}", name);
@@ -344,6 +418,25 @@ impl ConnectionManager {
         }
     }
 
+    /// Subscribe to market data for symbols on an exchange
+    ///
+    /// Subscribed symbols are resubscribed, and their books resynced, after a reconnect.
+    pub async fn subscribe(
+        &self,
+        exchange: &str,
+        symbols: &[&str],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let streams = self.streams.read().await;
+        if let Some(stream) = streams.get(exchange) {
+            stream.lock().await.subscribe(symbols).await?;
+            Ok(())
+        } else {
+            let error_msg = format!("Market data stream not found for exchange: {}", exchange);
+            error!("{}", error_msg);
+            Err(error_msg.into())
+        }
+    }
+
     /// Add an event handler
     pub async fn add_event_handler<F>(&self, handler: F)
     where
@@ -475,6 +568,9 @@ impl ConnectionManager {
         // Start reconnection task
         self.start_reconnection_task().await?;
 
+        // Start measuring endpoint latency
+        self.start_latency_probe_task();
+
         Ok(())
     }
 
@@ -487,6 +583,9 @@ impl ConnectionManager {
             let mut shutdown = self.shutdown.write().await;
             *shutdown = true;
         }
+        if let Some(supervisor) = &self.supervisor {
+            supervisor.unsupervise(RECONNECT_TASK).await;
+        }
 
         // Disconnect from all exchanges
         self.disconnect_all().await?;
@@ -501,36 +600,812 @@ impl ConnectionManager {
         let connections = self.connections.clone();
         let shutdown = self.shutdown.clone();
 
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
+        match &self.supervisor {
+            Some(supervisor) => {
+                supervisor.unsupervise(RECONNECT_TASK).await;
+                supervisor
+                    .supervise(RECONNECT_TASK, RECONNECT_INTERVAL * 3, move |heartbeat| {
+                        reconnect_loop(connections.clone(), shutdown.clone(), Some(heartbeat))
+                    })
+                    .await;
+            }
+            None => {
+                tokio::spawn(reconnect_loop(connections, shutdown, None));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start probing every endpoint at the configured interval
+    fn start_latency_probe_task(&self) {
+        let pools = self.pools.clone();
+        let shutdown = self.shutdown.clone();
+        let probe_interval = self.latency_config.probe_interval;
 
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(probe_interval);
             loop {
-                // Check if shutdown is requested
-                {
-                    let shutdown_flag = shutdown.read().await;
-                    if *shutdown_flag {
-                        break;
-                    }
+                interval.tick().await;
+                if *shutdown.read().await {
+                    break;
                 }
+                probe_pools(&pools).await;
+            }
+        });
+    }
+}
 
-                interval.tick().await;
+/// Handlers every market data event is passed to
+type EventHandlers = Arc<Mutex<Vec<Box<dyn Fn(MarketEvent) + Send + Sync>>>>;
+
+/// Time between checks for exchanges to reconnect
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Supervisor task name of the reconnect loop
+const RECONNECT_TASK: &str = "connection_reconnect";
+
+/// Supervisor task name of an exchange's market data reader
+fn reader_task_name(exchange: &str) -> String {
+    format!("market_data:{}", exchange)
+}
+
+/// Check for disconnected exchanges until shutdown
+async fn reconnect_loop(
+    connections: Arc<RwLock<HashMap<String, ExchangeConnection>>>,
+    shutdown: Arc<RwLock<bool>>,
+    heartbeat: Option<Heartbeat>,
+) {
+    let mut interval = tokio::time::interval(RECONNECT_INTERVAL);
+
+    loop {
+        // Check if shutdown is requested
+        {
+            let shutdown_flag = shutdown.read().await;
+            if *shutdown_flag {
+                break;
+            }
+        }
+
+        interval.tick().await;
+        if let Some(heartbeat) = &heartbeat {
+            heartbeat.beat();
+        }
+
+        // Check for disconnected exchanges
+        let mut connections_guard = connections.write().await;
+        for (name, connection) in connections_guard.iter_mut() {
+            if connection.should_reconnect() && !connection.max_reconnects_reached() {
+                info!("Attempting to reconnect to exchange: {}", name);
+                connection.set_status(ConnectionStatus::Reconnecting);
+
+                // In a real implementation, you would attempt to reconnect here
+                // For now, we'll just update the status
+                connection.set_status(ConnectionStatus::Failed);
+            }
+        }
+    }
+}
+
+/// Background reader passing an exchange's market data to the event handlers
+#[derive(Clone)]
+struct MarketDataReader {
+    name: String,
+    streams: Arc<RwLock<HashMap<String, Arc<Mutex<ReconnectingStream>>>>>,
+    connections: Arc<RwLock<HashMap<String, ExchangeConnection>>>,
+    event_handlers: EventHandlers,
+    shutdown: Arc<RwLock<bool>>,
+}
+
+impl MarketDataReader {
+    /// Read until the stream ends, fails or is removed, or shutdown is requested
+    /// With `reconnect` set, the stream's connection is dropped first, e.g. after a stall.
+    /// The heartbeat beats on every event, so it goes silent while the stream delivers none.
+    async fn run(self, reconnect: bool, heartbeat: Option<Heartbeat>) {
+        if reconnect {
+            let stream = self.streams.read().await.get(&self.name).cloned();
+            if let Some(stream) = stream {
+                warn!("Reconnecting stalled market data stream for {}", self.name);
+                stream.lock().await.reset();
+            }
+        }
 
-                // Check for disconnected exchanges
-                let mut connections_guard = connections.write().await;
-                for (name, connection) in connections_guard.iter_mut() {
-                    if connection.should_reconnect() && !connection.max_reconnects_reached() {
-                        info!("Attempting to reconnect to exchange: {}", name);
-                        connection.set_status(ConnectionStatus::Reconnecting);
+        loop {
+            // Check if shutdown is requested
+            {
+                let shutdown_flag = self.shutdown.read().await;
+                if *shutdown_flag {
+                    break;
+                }
+            }
+            if let Some(heartbeat) = &heartbeat {
+                heartbeat.beat();
+            }
 
-                        // In a real implementation, you would attempt to reconnect here
-                        // For now, we'll just update the status
-                        connection.set_status(ConnectionStatus::Failed);
+            // Get the stream
+            let stream = self.streams.read().await.get(&self.name).cloned();
+            let Some(stream) = stream else {
+                break;
+            };
+
+            // Process next market event
+            let next = stream.lock().await.next().await;
+            match next {
+                Some(Ok(event)) => {
+                    // Handle the event
+                    let handlers = self.event_handlers.lock().await;
+                    for handler in handlers.iter() {
+                        handler(event.clone());
+                    }
+                }
+                Some(Err(e)) => {
+                    // Reconnects are exhausted
+                    error!("Error in market data stream for {}: {}", self.name, e);
+                    if let Some(connection) = self.connections.write().await.get_mut(&self.name) {
+                        connection.set_error(e.to_string());
                     }
+                    break;
+                }
+                None => {
+                    debug!("Market data stream for {} ended", self.name);
+                    break;
                 }
             }
+        }
+    }
+}
+
+/// Probe every pool's endpoints, logging exchanges whose requests move to another endpoint
+async fn probe_pools(pools: &RwLock<HashMap<String, Arc<EndpointPool>>>) {
+    let pools: Vec<(String, Arc<EndpointPool>)> = pools
+        .read()
+        .await
+        .iter()
+        .map(|(name, pool)| (name.clone(), pool.clone()))
+        .collect();
+    for (name, pool) in pools {
+        let before = pool.active_endpoint();
+        pool.probe().await;
+        let after = pool.active_endpoint();
+        if before != after {
+            warn!(
+                "Exchange {} failing over from endpoint {} to {}",
+                name,
+                before.unwrap_or_default(),
+                after.unwrap_or_default()
+            );
+        }
+    }
+}
+
+/// How endpoints are probed and when they count as degraded
+#[derive(Debug, Clone)]
+pub struct LatencyConfig {
+    /// Time between RTT probes of each endpoint
+    pub probe_interval: Duration,
+    /// Weight of each new sample in the smoothed RTT, between 0 and 1
+    pub smoothing: f64,
+    /// Smoothed RTT above which an endpoint is degraded
+    pub max_rtt: Duration,
+    /// Failed probes in a row after which an endpoint is degraded
+    pub max_failed_probes: u32,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            probe_interval: Duration::from_secs(5),
+            smoothing: 0.2,
+            max_rtt: Duration::from_millis(500),
+            max_failed_probes: 3,
+        }
+    }
+}
+
+/// Measured RTT and probe failures of one endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// Endpoint name, e.g. a host or region
+    pub name: String,
+    /// Exponentially smoothed probe RTT (None until a probe succeeds)
+    pub smoothed_rtt: Option<Duration>,
+    /// RTT of the last successful probe
+    pub last_rtt: Option<Duration>,
+    /// Probes failed in a row
+    pub failed_probes: u32,
+}
+
+impl EndpointHealth {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            smoothed_rtt: None,
+            last_rtt: None,
+            failed_probes: 0,
+        }
+    }
+
+    /// Check if the endpoint is within the thresholds of a config
+    pub fn is_healthy(&self, config: &LatencyConfig) -> bool {
+        self.failed_probes < config.max_failed_probes
+            && self.smoothed_rtt.is_none_or(|rtt| rtt <= config.max_rtt)
+    }
+
+    fn record_rtt(&mut self, rtt: Duration, smoothing: f64) {
+        self.last_rtt = Some(rtt);
+        self.failed_probes = 0;
+        self.smoothed_rtt = Some(match self.smoothed_rtt {
+            Some(smoothed) => smoothed.mul_f64(1.0 - smoothing) + rtt.mul_f64(smoothing),
+            None => rtt,
         });
+    }
+}
 
-        Ok(())
+/// One way of reaching an exchange
+struct Endpoint {
+    adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+    health: std::sync::Mutex<EndpointHealth>,
+}
+
+impl Endpoint {
+    fn health(&self) -> std::sync::MutexGuard<'_, EndpointHealth> {
+        self.health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Endpoints in order of preference: healthy before degraded, measured before
+/// unmeasured, then by smoothed RTT, ties in the order they were added
+fn rank_endpoints(endpoints: &[Arc<Endpoint>], config: &LatencyConfig) -> Vec<Arc<Endpoint>> {
+    let mut ranked = endpoints.to_vec();
+    ranked.sort_by_cached_key(|endpoint| {
+        let health = endpoint.health();
+        (
+            !health.is_healthy(config),
+            health.smoothed_rtt.is_none(),
+            health.smoothed_rtt,
+        )
+    });
+    ranked
+}
+
+/// Interchangeable endpoints of one exchange, e.g. the same account reached through
+/// different hosts or regions
+///
+/// Requests go to the healthy endpoint with the lowest smoothed RTT. Idempotent requests
+/// that fail are retried on the next endpoint in order of preference; new orders are not,
+/// as the failed attempt may still have reached the exchange. Only probes count against
+/// an endpoint, since request errors include venue rejects. A market data stream ends
+/// when its endpoint degrades while another is healthy, so a `ReconnectingStream`
+/// reopens it on the best endpoint.
+pub struct EndpointPool {
+    endpoints: Arc<std::sync::RwLock<Vec<Arc<Endpoint>>>>,
+    config: LatencyConfig,
+}
+
+impl EndpointPool {
+    /// Create a pool without endpoints
+    pub fn new(config: LatencyConfig) -> Self {
+        Self {
+            endpoints: Arc::new(std::sync::RwLock::new(Vec::new())),
+            config,
+        }
+    }
+
+    /// Add an endpoint (builder pattern)
+    pub fn with_endpoint(
+        self,
+        name: impl Into<String>,
+        adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+    ) -> Self {
+        self.add_endpoint(name, adapter);
+        self
+    }
+
+    /// Add an endpoint, preferred after those added before it until probed
+    pub fn add_endpoint(
+        &self,
+        name: impl Into<String>,
+        adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+    ) {
+        let endpoint = Arc::new(Endpoint {
+            adapter,
+            health: std::sync::Mutex::new(EndpointHealth::new(name.into())),
+        });
+        self.endpoints
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(endpoint);
+    }
+
+    /// Get the health of every endpoint, in the order they were added
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        self.endpoints()
+            .iter()
+            .map(|endpoint| endpoint.health().clone())
+            .collect()
+    }
+
+    /// Get the name of the endpoint requests currently go to
+    pub fn active_endpoint(&self) -> Option<String> {
+        rank_endpoints(&self.endpoints(), &self.config)
+            .first()
+            .map(|endpoint| endpoint.health().name.clone())
+    }
+
+    /// Ping every endpoint at once and record the round trips
+    pub async fn probe(&self) {
+        let endpoints = self.endpoints();
+        let probes = endpoints.iter().map(|endpoint| async move {
+            let started = Instant::now();
+            let result = endpoint.adapter.ping().await;
+            (endpoint, result.map(|_| started.elapsed()))
+        });
+        for (endpoint, result) in join_all(probes).await {
+            let mut health = endpoint.health();
+            match result {
+                Ok(rtt) => health.record_rtt(rtt, self.config.smoothing),
+                Err(e) => {
+                    health.failed_probes += 1;
+                    warn!(
+                        "Probe of endpoint {} failed ({} in a row): {}",
+                        health.name, health.failed_probes, e
+                    );
+                }
+            }
+        }
+    }
+
+    fn endpoints(&self) -> Vec<Arc<Endpoint>> {
+        self.endpoints
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Send a request to the preferred endpoint, and if it fails and `retry` is set, to
+    /// the others in turn
+    async fn route<T, F, Fut>(
+        &self,
+        retry: bool,
+        request: F,
+    ) -> Result<T, Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: Fn(Arc<dyn ExchangeAdapter + Send + Sync>) -> Fut,
+        Fut: Future<Output = Result<T, Box<dyn std::error::Error + Send + Sync>>>,
+    {
+        let mut last_error = None;
+        for endpoint in rank_endpoints(&self.endpoints(), &self.config) {
+            match request(endpoint.adapter.clone()).await {
+                Ok(value) => return Ok(value),
+                Err(e) if retry => {
+                    warn!(
+                        "Request via endpoint {} failed, trying the next: {}",
+                        endpoint.health().name,
+                        e
+                    );
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| "No endpoints configured".into()))
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for EndpointPool {
+    async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Usable as long as one endpoint connects
+        let mut last_error = None;
+        let mut connected = false;
+        for endpoint in self.endpoints() {
+            match endpoint.adapter.connect().await {
+                Ok(()) => connected = true,
+                Err(e) => {
+                    warn!(
+                        "Endpoint {} failed to connect: {}",
+                        endpoint.health().name,
+                        e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) if !connected => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut result = Ok(());
+        for endpoint in self.endpoints() {
+            if let Err(e) = endpoint.adapter.disconnect().await {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
+    async fn ping(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.route(true, |adapter| async move { adapter.ping().await })
+            .await
+    }
+
+    async fn get_market_data_stream(
+        &self,
+    ) -> Result<SharedStream, Box<dyn std::error::Error + Send + Sync>> {
+        let mut last_error = None;
+        for endpoint in rank_endpoints(&self.endpoints(), &self.config) {
+            match endpoint.adapter.get_market_data_stream().await {
+                Ok(inner) => {
+                    info!(
+                        "Opened market data stream via endpoint {}",
+                        endpoint.health().name
+                    );
+                    return Ok(Arc::new(Mutex::new(EndpointStream {
+                        inner,
+                        endpoint,
+                        endpoints: self.endpoints.clone(),
+                        config: self.config.clone(),
+                    })));
+                }
+                Err(e) => {
+                    warn!(
+                        "Endpoint {} failed to open a market data stream: {}",
+                        endpoint.health().name,
+                        e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| "No endpoints configured".into()))
+    }
+
+    async fn place_order(
+        &self,
+        order: NewOrder,
+    ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+        self.route(false, |adapter| {
+            let order = order.clone();
+            async move { adapter.place_order(order).await }
+        })
+        .await
+    }
+
+    async fn cancel_order(
+        &self,
+        order_id: OrderId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.route(true, |adapter| {
+            let order_id = order_id.clone();
+            async move { adapter.cancel_order(order_id).await }
+        })
+        .await
+    }
+
+    async fn get_order_status(
+        &self,
+        order_id: OrderId,
+    ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
+        self.route(true, |adapter| {
+            let order_id = order_id.clone();
+            async move { adapter.get_order_status(order_id).await }
+        })
+        .await
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
+        self.route(true, |adapter| async move { adapter.get_balances().await })
+            .await
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
+        self.route(true, |adapter| async move {
+            adapter.get_open_orders(symbol).await
+        })
+        .await
+    }
+
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: u32,
+    ) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+        self.route(true, |adapter| async move {
+            adapter.get_order_book(symbol, limit).await
+        })
+        .await
+    }
+
+    async fn get_trading_fees(
+        &self,
+        symbol: &str,
+    ) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
+        self.route(true, |adapter| async move {
+            adapter.get_trading_fees(symbol).await
+        })
+        .await
+    }
+}
+
+/// Market data stream of one endpoint in a pool
+/// Ends once the endpoint is degraded and another endpoint is healthy.
+struct EndpointStream {
+    inner: SharedStream,
+    endpoint: Arc<Endpoint>,
+    endpoints: Arc<std::sync::RwLock<Vec<Arc<Endpoint>>>>,
+    config: LatencyConfig,
+}
+
+impl EndpointStream {
+    fn should_fail_over(&self) -> bool {
+        if self.endpoint.health().is_healthy(&self.config) {
+            return false;
+        }
+        let endpoints = self.endpoints.read().unwrap_or_else(|e| e.into_inner());
+        endpoints.iter().any(|endpoint| {
+            !Arc::ptr_eq(endpoint, &self.endpoint) && endpoint.health().is_healthy(&self.config)
+        })
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for EndpointStream {
+    type Error = BoxedError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.inner.lock().await.subscribe(symbols).await
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.inner.lock().await.unsubscribe(symbols).await
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        if self.should_fail_over() {
+            warn!(
+                "Endpoint {} degraded, closing its market data stream",
+                self.endpoint.health().name
+            );
+            return None;
+        }
+        self.inner.lock().await.next().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner
+            .try_lock()
+            .map(|stream| stream.is_connected())
+            .unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.inner
+            .try_lock()
+            .ok()
+            .and_then(|stream| stream.last_update(symbol))
+    }
+}
+
+/// Shared handle to an adapter's market data stream
+type SharedStream = Arc<Mutex<dyn MarketDataStream<Error = BoxedError> + Send + Sync>>;
+
+/// Market data stream that reconnects when its adapter's stream drops
+///
+/// When the inner stream closes or reports an error, the stream is re-established with
+/// exponential backoff and every symbol subscribed so far is resubscribed. Whenever the
+/// stream is established a fresh REST snapshot is yielded per symbol, so downstream order
+/// books resync across the gap. A `MarketEvent::Desync` from the inner stream is passed
+/// on and followed by a fresh snapshot of that symbol.
+/// After `max_attempts` failed reconnects the last error is returned; calling `next`
+/// again starts a new round.
+pub struct ReconnectingStream {
+    adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+    stream: Option<SharedStream>,
+    symbols: Vec<String>,
+    retry_config: RetryConfig,
+    snapshot_limit: u32,
+    /// Resync snapshots waiting to be yielded
+    pending: VecDeque<MarketEvent>,
+    /// Whether a stream has been established before
+    opened: bool,
+    reconnects: u64,
+}
+
+impl ReconnectingStream {
+    /// Create a stream over an adapter, connecting on first subscribe or read
+    pub fn new(adapter: Arc<dyn ExchangeAdapter + Send + Sync>) -> Self {
+        Self {
+            adapter,
+            stream: None,
+            symbols: Vec::new(),
+            retry_config: RetryConfig::default(),
+            snapshot_limit: 1000,
+            pending: VecDeque::new(),
+            opened: false,
+            reconnects: 0,
+        }
+    }
+
+    /// Set the backoff used between reconnect attempts
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Set the depth of the REST snapshots fetched after a reconnect
+    pub fn with_snapshot_limit(mut self, limit: u32) -> Self {
+        self.snapshot_limit = limit;
+        self
+    }
+
+    /// Symbols resubscribed after a reconnect
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    /// Number of successful reconnects
+    pub fn reconnect_count(&self) -> u64 {
+        self.reconnects
+    }
+
+    /// Drop the current connection, so the next read reconnects and resyncs
+    pub fn reset(&mut self) {
+        self.stream = None;
+        self.pending.clear();
+    }
+
+    /// Open the adapter's stream and subscribe the tracked symbols
+    async fn open(&self) -> Result<SharedStream, BoxedError> {
+        let stream = self.adapter.get_market_data_stream().await?;
+        if !self.symbols.is_empty() {
+            let symbols: Vec<&str> = self.symbols.iter().map(String::as_str).collect();
+            stream.lock().await.subscribe(&symbols).await?;
+        }
+        Ok(stream)
+    }
+
+    /// Fetch a REST snapshot for every tracked symbol
+    async fn fetch_snapshots(&self) -> Result<Vec<MarketEvent>, BoxedError> {
+        let mut snapshots = Vec::with_capacity(self.symbols.len());
+        for symbol in &self.symbols {
+            let snapshot = self
+                .adapter
+                .get_order_book(symbol, self.snapshot_limit)
+                .await?;
+            snapshots.push(MarketEvent::OrderBookSnapshot(snapshot));
+        }
+        Ok(snapshots)
+    }
+
+    /// Establish the stream with backoff and queue resync snapshots
+    async fn reconnect(&mut self) -> Result<(), BoxedError> {
+        let max_attempts = self.retry_config.max_attempts.max(1);
+        let mut attempt = 0;
+        loop {
+            let result = match self.open().await {
+                Ok(stream) => self
+                    .fetch_snapshots()
+                    .await
+                    .map(|snapshots| (stream, snapshots)),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok((stream, snapshots)) => {
+                    self.stream = Some(stream);
+                    self.pending.extend(snapshots);
+                    if self.opened {
+                        self.reconnects += 1;
+                        info!(
+                            "Market data stream reconnected, resubscribed {} symbols",
+                            self.symbols.len()
+                        );
+                    }
+                    self.opened = true;
+                    return Ok(());
+                }
+                Err(e) if attempt + 1 >= max_attempts => {
+                    error!(
+                        "Market data reconnect failed after {} attempts: {}",
+                        max_attempts, e
+                    );
+                    return Err(e);
+                }
+                Err(e) => {
+                    let delay = self.retry_config.delay_for_attempt(attempt);
+                    warn!(
+                        "Market data reconnect failed (attempt {}/{}), retrying in {:?}: {}",
+                        attempt + 1,
+                        max_attempts,
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for ReconnectingStream {
+    type Error = BoxedError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        for symbol in symbols {
+            if !self.symbols.iter().any(|s| s == symbol) {
+                self.symbols.push(symbol.to_string());
+            }
+        }
+        match &self.stream {
+            Some(stream) => stream.lock().await.subscribe(symbols).await,
+            None => self.reconnect().await,
+        }
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.symbols.retain(|s| !symbols.contains(&s.as_str()));
+        match &self.stream {
+            Some(stream) => stream.lock().await.unsubscribe(symbols).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+
+            let Some(stream) = self.stream.clone() else {
+                if let Err(e) = self.reconnect().await {
+                    return Some(Err(e));
+                }
+                continue;
+            };
+
+            let next = stream.lock().await.next().await;
+            match next {
+                Some(Ok(MarketEvent::Desync(desync))) => {
+                    match self
+                        .adapter
+                        .get_order_book(desync.symbol.as_str(), self.snapshot_limit)
+                        .await
+                    {
+                        Ok(snapshot) => self
+                            .pending
+                            .push_back(MarketEvent::OrderBookSnapshot(snapshot)),
+                        Err(e) => warn!("Resync snapshot for {} failed: {}", desync.symbol, e),
+                    }
+                    return Some(Ok(MarketEvent::Desync(desync)));
+                }
+                Some(Ok(event)) => return Some(Ok(event)),
+                Some(Err(e)) => warn!("Market data stream error, reconnecting: {}", e),
+                None => warn!("Market data stream closed, reconnecting"),
+            }
+            self.stream = None;
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.stream
+            .as_ref()
+            .and_then(|stream| stream.try_lock().ok().map(|s| s.is_connected()))
+            .unwrap_or(self.stream.is_some())
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.stream
+            .as_ref()
+            .and_then(|stream| stream.try_lock().ok())
+            .and_then(|s| s.last_update(symbol))
     }
 }
 
@@ -543,6 +1418,12 @@ pub trait ExchangeAdapter: Send + Sync {
     /// Disconnect from the exchange
     async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
 
+    /// Make a cheap request, to measure the round trip to the exchange
+    /// Defaults to fetching balances; adapters override it with a lighter request.
+    async fn ping(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.get_balances().await.map(|_| ())
+    }
+
     /// Get the market data stream
     async fn get_market_data_stream(
         &self,
@@ -595,7 +1476,9 @@ pub trait ExchangeAdapter: Send + Sync {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::events::TimeInForce;
     use crate::exchanges::mock::MockExchangeAdapter;
+    use crate::types::{Price, Size};
     use std::sync::Arc;
 
     #[test]
@@ -726,6 +1609,505 @@ mod tests {
         assert_eq!(statuses.get("test2"), Some(&ConnectionStatus::Disconnected));
     }
 
+    /// Stream that yields its events, then closes
+    struct ScriptedStream {
+        events: VecDeque<MarketEvent>,
+        subscribed: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl MarketDataStream for ScriptedStream {
+        type Error = BoxedError;
+
+        async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+            let mut subscribed = self.subscribed.lock().unwrap();
+            subscribed.extend(symbols.iter().map(|s| s.to_string()));
+            Ok(())
+        }
+
+        async fn unsubscribe(&mut self, _symbols: &[&str]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+            self.events.pop_front().map(Ok)
+        }
+
+        fn is_connected(&self) -> bool {
+            !self.events.is_empty()
+        }
+
+        fn last_update(&self, _symbol: &str) -> Option<u64> {
+            None
+        }
+    }
+
+    /// Adapter whose second stream request fails
+    struct FlakyAdapter {
+        streams_opened: std::sync::Mutex<u32>,
+        subscribed: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl ExchangeAdapter for FlakyAdapter {
+        async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        async fn get_market_data_stream(
+            &self,
+        ) -> Result<SharedStream, Box<dyn std::error::Error + Send + Sync>> {
+            let opened = {
+                let mut opened = self.streams_opened.lock().unwrap();
+                *opened += 1;
+                *opened
+            };
+            if opened == 2 {
+                return Err("connection refused".into());
+            }
+            self.subscribed.lock().unwrap().clear();
+            let update = OrderBookSnapshot::new("BTCUSDT", "stream", vec![], vec![], opened as u64);
+            Ok(Arc::new(Mutex::new(ScriptedStream {
+                events: VecDeque::from(vec![MarketEvent::OrderBookSnapshot(update)]),
+                subscribed: self.subscribed.clone(),
+            })))
+        }
+
+        async fn place_order(
+            &self,
+            _order: NewOrder,
+        ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+            Err("FlakyAdapter does not place orders".into())
+        }
+
+        async fn cancel_order(
+            &self,
+            _order_id: OrderId,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Err("FlakyAdapter does not cancel orders".into())
+        }
+
+        async fn get_order_status(
+            &self,
+            _order_id: OrderId,
+        ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
+            Err("FlakyAdapter has no order status".into())
+        }
+
+        async fn get_balances(
+            &self,
+        ) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec![])
+        }
+
+        async fn get_open_orders(
+            &self,
+            _symbol: Option<&str>,
+        ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec![])
+        }
+
+        async fn get_order_book(
+            &self,
+            symbol: &str,
+            _limit: u32,
+        ) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(OrderBookSnapshot::new(symbol, "rest", vec![], vec![], 0))
+        }
+
+        async fn get_trading_fees(
+            &self,
+            _symbol: &str,
+        ) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
+            Err("FlakyAdapter has no trading fees".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnecting_stream_resubscribes_and_resyncs() {
+        let adapter = Arc::new(FlakyAdapter {
+            streams_opened: std::sync::Mutex::new(0),
+            subscribed: Arc::new(std::sync::Mutex::new(Vec::new())),
+        });
+        let mut stream = ReconnectingStream::new(adapter.clone()).with_retry_config(RetryConfig {
+            max_attempts: 3,
+            initial_delay: std::time::Duration::from_millis(1),
+            max_delay: std::time::Duration::from_millis(5),
+            multiplier: 2.0,
+            jitter: false,
+        });
+
+        stream.subscribe(&["BTCUSDT"]).await.unwrap();
+        let source = |event: MarketEvent| match event {
+            MarketEvent::OrderBookSnapshot(snapshot) => snapshot.exchange_id,
+            _ => panic!("unexpected event"),
+        };
+
+        // Initial snapshot, then the first stream's update
+        assert_eq!(source(stream.next().await.unwrap().unwrap()), "rest");
+        assert_eq!(source(stream.next().await.unwrap().unwrap()), "stream");
+        assert_eq!(stream.reconnect_count(), 0);
+
+        // Stream closes, one refused attempt, then resubscribe and resync from REST
+        assert_eq!(source(stream.next().await.unwrap().unwrap()), "rest");
+        assert_eq!(stream.reconnect_count(), 1);
+        assert_eq!(*adapter.streams_opened.lock().unwrap(), 3);
+        assert_eq!(*adapter.subscribed.lock().unwrap(), vec!["BTCUSDT"]);
+        assert_eq!(source(stream.next().await.unwrap().unwrap()), "stream");
+    }
+
+    /// Stream that never delivers an event
+    struct StalledStream;
+
+    #[async_trait]
+    impl MarketDataStream for StalledStream {
+        type Error = BoxedError;
+
+        async fn subscribe(&mut self, _symbols: &[&str]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn unsubscribe(&mut self, _symbols: &[&str]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+            std::future::pending().await
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        fn last_update(&self, _symbol: &str) -> Option<u64> {
+            None
+        }
+    }
+
+    /// Adapter whose streams stall
+    struct StalledAdapter {
+        streams_opened: std::sync::atomic::AtomicU32,
+    }
+
+    #[async_trait]
+    impl ExchangeAdapter for StalledAdapter {
+        async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        async fn get_market_data_stream(
+            &self,
+        ) -> Result<SharedStream, Box<dyn std::error::Error + Send + Sync>> {
+            self.streams_opened
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(Arc::new(Mutex::new(StalledStream)))
+        }
+
+        async fn place_order(
+            &self,
+            _order: NewOrder,
+        ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+            Err("StalledAdapter does not place orders".into())
+        }
+
+        async fn cancel_order(
+            &self,
+            _order_id: OrderId,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Err("StalledAdapter does not cancel orders".into())
+        }
+
+        async fn get_order_status(
+            &self,
+            _order_id: OrderId,
+        ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
+            Err("StalledAdapter has no order status".into())
+        }
+
+        async fn get_balances(
+            &self,
+        ) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec![])
+        }
+
+        async fn get_open_orders(
+            &self,
+            _symbol: Option<&str>,
+        ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec![])
+        }
+
+        async fn get_order_book(
+            &self,
+            symbol: &str,
+            _limit: u32,
+        ) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(OrderBookSnapshot::new(symbol, "rest", vec![], vec![], 0))
+        }
+
+        async fn get_trading_fees(
+            &self,
+            _symbol: &str,
+        ) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
+            Err("StalledAdapter has no trading fees".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_supervisor_reconnects_stalled_reader() {
+        use crate::monitoring::alerts::{AlertLevel, AlertManager};
+
+        let alerts = Arc::new(AlertManager::new(100));
+        let supervisor = Arc::new(TaskSupervisor::new(alerts.clone()));
+        let manager =
+            ConnectionManager::new().with_supervisor(supervisor.clone(), Duration::from_millis(50));
+        let adapter = Arc::new(StalledAdapter {
+            streams_opened: std::sync::atomic::AtomicU32::new(0),
+        });
+        manager
+            .add_exchange("stalled".to_string(), adapter.clone())
+            .await;
+        manager.connect_exchange("stalled").await.unwrap();
+        manager.start_reconnection_task().await.unwrap();
+
+        let opened = || {
+            adapter
+                .streams_opened
+                .load(std::sync::atomic::Ordering::SeqCst)
+        };
+        assert_eq!(opened(), 1);
+        assert!(supervisor.check().await.is_empty());
+
+        // No event within the timeout: the reader is flagged and reads from a new stream
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(
+            supervisor.check().await,
+            vec!["market_data:stalled".to_string()]
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(opened(), 2);
+        assert_eq!(alerts.get_alerts_by_level(AlertLevel::Error).await.len(), 1);
+
+        // Stopping tears the readers and the reconnect loop down instead of restarting them
+        let names: Vec<String> = supervisor
+            .status()
+            .await
+            .into_iter()
+            .map(|status| status.name)
+            .collect();
+        assert_eq!(names, ["market_data:stalled", "connection_reconnect"]);
+        manager.stop().await.unwrap();
+        assert!(supervisor.status().await.is_empty());
+    }
+
+    /// Adapter with a fixed round trip that can be taken down
+    struct LatencyAdapter {
+        name: &'static str,
+        delay: Duration,
+        down: std::sync::atomic::AtomicBool,
+        orders: std::sync::atomic::AtomicU32,
+    }
+
+    impl LatencyAdapter {
+        fn new(name: &'static str, delay_ms: u64) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                delay: Duration::from_millis(delay_ms),
+                down: std::sync::atomic::AtomicBool::new(false),
+                orders: std::sync::atomic::AtomicU32::new(0),
+            })
+        }
+
+        fn set_down(&self, down: bool) {
+            self.down.store(down, std::sync::atomic::Ordering::SeqCst);
+        }
+
+        async fn respond(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            tokio::time::sleep(self.delay).await;
+            if self.down.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(format!("{} unreachable", self.name).into());
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ExchangeAdapter for LatencyAdapter {
+        async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.respond().await
+        }
+
+        async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        async fn ping(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.respond().await
+        }
+
+        async fn get_market_data_stream(
+            &self,
+        ) -> Result<SharedStream, Box<dyn std::error::Error + Send + Sync>> {
+            self.respond().await?;
+            let update = OrderBookSnapshot::new("BTCUSDT", self.name, vec![], vec![], 1);
+            Ok(Arc::new(Mutex::new(ScriptedStream {
+                events: VecDeque::from(vec![MarketEvent::OrderBookSnapshot(update)]),
+                subscribed: Arc::new(std::sync::Mutex::new(Vec::new())),
+            })))
+        }
+
+        async fn place_order(
+            &self,
+            _order: NewOrder,
+        ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+            self.orders
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.respond().await?;
+            Ok(self.name.to_string())
+        }
+
+        async fn cancel_order(
+            &self,
+            _order_id: OrderId,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.respond().await
+        }
+
+        async fn get_order_status(
+            &self,
+            _order_id: OrderId,
+        ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
+            Err(format!("{} has no order status", self.name).into())
+        }
+
+        async fn get_balances(
+            &self,
+        ) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
+            self.respond().await?;
+            Ok(vec![])
+        }
+
+        async fn get_open_orders(
+            &self,
+            _symbol: Option<&str>,
+        ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec![])
+        }
+
+        async fn get_order_book(
+            &self,
+            symbol: &str,
+            _limit: u32,
+        ) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+            self.respond().await?;
+            Ok(OrderBookSnapshot::new(symbol, self.name, vec![], vec![], 0))
+        }
+
+        async fn get_trading_fees(
+            &self,
+            _symbol: &str,
+        ) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
+            Err(format!("{} has no trading fees", self.name).into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_to_fastest_endpoint_and_fails_over() {
+        let manager = ConnectionManager::new().with_latency_config(LatencyConfig {
+            max_failed_probes: 2,
+            ..LatencyConfig::default()
+        });
+        let primary = LatencyAdapter::new("primary", 40);
+        let backup = LatencyAdapter::new("backup", 1);
+        manager
+            .add_exchange("binance".to_string(), primary.clone())
+            .await;
+        manager
+            .add_endpoint("binance", "backup", backup.clone())
+            .await
+            .unwrap();
+        assert!(manager
+            .add_endpoint("unknown", "backup", backup.clone())
+            .await
+            .is_err());
+
+        // Unprobed endpoints are tried in the order they were added
+        assert_eq!(
+            manager.active_endpoint("binance").await.as_deref(),
+            Some("primary")
+        );
+
+        manager.probe_endpoints().await;
+        assert_eq!(
+            manager.active_endpoint("binance").await.as_deref(),
+            Some("backup")
+        );
+        let health = manager.endpoint_health("binance").await;
+        assert!(health[0].smoothed_rtt.unwrap() > health[1].smoothed_rtt.unwrap());
+
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("1").unwrap(),
+            Price::from_str("100").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let order_id = manager.place_order("binance", order.clone()).await.unwrap();
+        assert_eq!(order_id, "backup");
+
+        // Reads fall through to the next endpoint, new orders are never sent twice
+        backup.set_down(true);
+        assert!(manager.get_balances("binance").await.is_ok());
+        assert!(manager.place_order("binance", order).await.is_err());
+        assert_eq!(primary.orders.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        // Failed probes degrade the backup and move requests back to the primary
+        manager.probe_endpoints().await;
+        assert_eq!(
+            manager.active_endpoint("binance").await.as_deref(),
+            Some("backup")
+        );
+        manager.probe_endpoints().await;
+        assert_eq!(
+            manager.active_endpoint("binance").await.as_deref(),
+            Some("primary")
+        );
+        assert_eq!(manager.endpoint_health("binance").await[1].failed_probes, 2);
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_stream_closes_when_endpoint_degrades() {
+        let primary = LatencyAdapter::new("primary", 1);
+        let backup = LatencyAdapter::new("backup", 1);
+        let pool = EndpointPool::new(LatencyConfig {
+            max_failed_probes: 1,
+            ..LatencyConfig::default()
+        })
+        .with_endpoint("primary", primary.clone())
+        .with_endpoint("backup", backup);
+
+        let stream = pool.get_market_data_stream().await.unwrap();
+        primary.set_down(true);
+        pool.probe().await;
+        assert!(stream.lock().await.next().await.is_none());
+
+        let stream = pool.get_market_data_stream().await.unwrap();
+        let event = stream.lock().await.next().await;
+        match event {
+            Some(Ok(MarketEvent::OrderBookSnapshot(snapshot))) => {
+                assert_eq!(snapshot.exchange_id, "backup")
+            }
+            _ => panic!("expected a snapshot from the backup"),
+        }
+    }
+
     #[tokio::test]
     async fn test_connection_manager_add_event_handler() {
         let manager = ConnectionManager::new();
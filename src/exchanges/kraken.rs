@@ -0,0 +1,1590 @@
+use crate::core::events::{OrderBookDelta, OrderBookLevel, OrderBookSnapshot};
+use crate::exchanges::session_safety::SessionSafety;
+use crate::monitoring::SymbolStatsRegistry;
+use crate::orderbook::integrity::crc32;
+use crate::orderbook::OrderBook;
+use crate::traits::{
+    Balance, ExecutionClient, ExecutionReport, MarketDataStream, MarketEvent, NewOrder, OrderId,
+    OrderSide, OrderStatus, OrderType, ServerClock, TimeInForce, TradingFees,
+};
+use crate::types::instrument::{format_price_plain, format_size_plain};
+use crate::types::{InstrumentSpec, Price, Size, Symbol};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, RwLock};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+/// Order book depth of the WebSocket channel (10, 25, 100, 500 or 1000)
+const WS_BOOK_DEPTH: usize = 10;
+/// Levels per side covered by the book checksum
+const CHECKSUM_LEVELS: usize = 10;
+/// Error prefixes for too many requests
+const RATE_LIMIT_ERRORS: [&str; 2] = ["EAPI:Rate limit exceeded", "EOrder:Rate limit exceeded"];
+/// Errors for a bad key, signature or nonce
+const AUTH_ERRORS: [&str; 4] = [
+    "EAPI:Invalid key",
+    "EAPI:Invalid signature",
+    "EAPI:Invalid nonce",
+    "EGeneral:Permission denied",
+];
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Parse a decimal sent as a JSON string or number
+/// The WebSocket v2 API sends prices and quantities as numbers
+fn parse_decimal(value: &Value) -> Option<Decimal> {
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .ok()
+}
+
+/// Kraken spot REST client for market data and order execution
+pub struct KrakenClient {
+    /// API key
+    api_key: String,
+    /// Base64-encoded API secret
+    api_secret: String,
+    /// Base URL for REST API
+    rest_url: String,
+    /// HTTP client
+    http_client: Client,
+    /// Instrument trading rules used to format order parameters, by WebSocket symbol
+    instruments: Arc<RwLock<HashMap<String, InstrumentSpec>>>,
+    /// REST pair name of each WebSocket symbol (e.g. "BTC/USD" -> "XBTUSD")
+    pairs: Arc<RwLock<HashMap<String, String>>>,
+    /// Last nonce sent; private requests need strictly increasing nonces
+    nonce: AtomicU64,
+}
+
+impl KrakenClient {
+    /// Create a new Kraken client
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            rest_url: "https://api.kraken.com".to_string(),
+            http_client: Client::new(),
+            instruments: Arc::new(RwLock::new(HashMap::new())),
+            pairs: Arc::new(RwLock::new(HashMap::new())),
+            nonce: AtomicU64::new(0),
+        }
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self
+    }
+
+    /// Set the trading rules for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        let mut instruments = self.instruments.write().await;
+        instruments.insert(spec.symbol.value().to_string(), spec);
+    }
+
+    /// Get the trading rules for an instrument
+    pub async fn instrument_spec(&self, symbol: &str) -> Option<InstrumentSpec> {
+        self.instruments.read().await.get(symbol).cloned()
+    }
+
+    /// Load trading rules and REST pair names for all spot pairs
+    pub async fn load_instrument_specs(&self) -> Result<Vec<InstrumentSpec>, KrakenError> {
+        let result = self.public("/0/public/AssetPairs", "").await?;
+        let pairs = parse_asset_pairs(&result);
+
+        let mut instruments = self.instruments.write().await;
+        let mut names = self.pairs.write().await;
+        for (spec, altname) in &pairs {
+            instruments.insert(spec.symbol.value().to_string(), spec.clone());
+            names.insert(spec.symbol.value().to_string(), altname.clone());
+        }
+        Ok(pairs.into_iter().map(|(spec, _)| spec).collect())
+    }
+
+    /// REST pair name for a WebSocket symbol
+    async fn rest_pair(&self, symbol: &str) -> String {
+        self.pairs
+            .read()
+            .await
+            .get(symbol)
+            .cloned()
+            .unwrap_or_else(|| symbol.replace('/', ""))
+    }
+
+    /// WebSocket symbol for a REST pair name
+    async fn symbol_for_pair(&self, pair: &str) -> String {
+        self.pairs
+            .read()
+            .await
+            .iter()
+            .find(|(_, altname)| altname.as_str() == pair)
+            .map(|(symbol, _)| symbol.clone())
+            .unwrap_or_else(|| pair.to_string())
+    }
+
+    /// Format the price and quantity of an order for its instrument
+    async fn format_order_params(&self, order: &NewOrder) -> (Option<String>, String) {
+        let instruments = self.instruments.read().await;
+        match instruments.get(order.symbol.as_str()) {
+            Some(spec) => (
+                order.price.map(|price| spec.format_price(price)),
+                spec.format_size(order.size),
+            ),
+            None => (
+                order.price.map(format_price_plain),
+                format_size_plain(order.size),
+            ),
+        }
+    }
+
+    /// Next nonce, strictly greater than the last even within one millisecond
+    fn next_nonce(&self) -> u64 {
+        let now = now_ms();
+        let mut last = self.nonce.load(Ordering::Relaxed);
+        loop {
+            let next = now.max(last + 1);
+            match self
+                .nonce
+                .compare_exchange(last, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return next,
+                Err(current) => last = current,
+            }
+        }
+    }
+
+    /// Sign a private request
+    /// API-Sign is HMAC-SHA512 of path + SHA256(nonce + body), keyed by the decoded secret
+    pub fn sign(&self, path: &str, nonce: u64, body: &str) -> Result<String, KrakenError> {
+        let secret = general_purpose::STANDARD
+            .decode(&self.api_secret)
+            .map_err(|e| KrakenError::AuthenticationError(format!("Invalid API secret: {}", e)))?;
+        let digest = Sha256::digest(format!("{}{}", nonce, body).as_bytes());
+
+        let mut mac =
+            Hmac::<Sha512>::new_from_slice(&secret).expect("HMAC can take key of any size");
+        mac.update(path.as_bytes());
+        mac.update(&digest);
+        Ok(general_purpose::STANDARD.encode(mac.finalize().into_bytes()))
+    }
+
+    /// Send a public GET request and return the `result` of the response envelope
+    async fn public(&self, path: &str, query: &str) -> Result<Value, KrakenError> {
+        let url = if query.is_empty() {
+            format!("{}{}", self.rest_url, path)
+        } else {
+            format!("{}{}?{}", self.rest_url, path, query)
+        };
+        let response = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| KrakenError::NetworkError(e.to_string()))?;
+        Self::unwrap_response(path, response).await
+    }
+
+    /// Send a signed POST request and return the `result` of the response envelope
+    async fn private(&self, path: &str, params: &[(&str, String)]) -> Result<Value, KrakenError> {
+        let nonce = self.next_nonce();
+        let mut form = vec![("nonce", nonce.to_string())];
+        form.extend(params.iter().cloned());
+        let body = form
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let response = self
+            .http_client
+            .post(format!("{}{}", self.rest_url, path))
+            .header("API-Key", &self.api_key)
+            .header("API-Sign", self.sign(path, nonce, &body)?)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| KrakenError::NetworkError(e.to_string()))?;
+        Self::unwrap_response(path, response).await
+    }
+
+    /// Map the `error` array of a response to an error, or return its `result`
+    async fn unwrap_response(
+        path: &str,
+        response: reqwest::Response,
+    ) -> Result<Value, KrakenError> {
+        let status = response.status();
+        if status.as_u16() == 429 {
+            return Err(KrakenError::RateLimitError(format!(
+                "Request to {} failed: {}",
+                path, status
+            )));
+        }
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(KrakenError::ApiError(format!(
+                "Request to {} failed: {} - {}",
+                path, status, error_text
+            )));
+        }
+
+        let json: Value = response
+            .json()
+            .await
+            .map_err(|e| KrakenError::ParseError(e.to_string()))?;
+        let errors: Vec<&str> = json
+            .get("error")
+            .and_then(|v| v.as_array())
+            .map(|errors| errors.iter().filter_map(|e| e.as_str()).collect())
+            .unwrap_or_default();
+        if let Some(error) = errors.first() {
+            let message = format!("{} failed: {}", path, errors.join(", "));
+            return Err(if RATE_LIMIT_ERRORS.iter().any(|e| error.starts_with(e)) {
+                KrakenError::RateLimitError(message)
+            } else if AUTH_ERRORS.iter().any(|e| error.starts_with(e)) {
+                KrakenError::AuthenticationError(message)
+            } else {
+                KrakenError::ApiError(message)
+            });
+        }
+
+        json.get("result")
+            .cloned()
+            .ok_or_else(|| KrakenError::ParseError(format!("{} returned no result", path)))
+    }
+
+    /// Get current server time in milliseconds
+    pub async fn get_server_time(&self) -> Result<u64, KrakenError> {
+        let result = self.public("/0/public/Time", "").await?;
+        result
+            .get("unixtime")
+            .and_then(|v| v.as_u64())
+            .map(|secs| secs * 1000)
+            .ok_or_else(|| KrakenError::ParseError("Invalid server time".to_string()))
+    }
+
+    /// Get an order book snapshot
+    pub async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: u32,
+    ) -> Result<OrderBookSnapshot, KrakenError> {
+        let query = format!("pair={}&count={}", self.rest_pair(symbol).await, limit);
+        let result = self.public("/0/public/Depth", &query).await?;
+        // The result is keyed by Kraken's internal pair name
+        result
+            .as_object()
+            .and_then(|books| books.values().next())
+            .map(|book| parse_order_book(symbol, book))
+            .ok_or_else(|| KrakenError::ParseError("Invalid order book".to_string()))
+    }
+
+    /// Place a new spot order
+    pub async fn place_order(&self, order: &NewOrder) -> Result<OrderId, KrakenError> {
+        let (price_param, volume_param) = self.format_order_params(order).await;
+        let mut params = vec![
+            ("pair", self.rest_pair(order.symbol.as_str()).await),
+            (
+                "type",
+                match order.side {
+                    OrderSide::Buy => "buy",
+                    OrderSide::Sell => "sell",
+                }
+                .to_string(),
+            ),
+            ("volume", volume_param),
+        ];
+
+        if order.order_type == OrderType::Market {
+            params.push(("ordertype", "market".to_string()));
+        } else {
+            let price = price_param
+                .ok_or_else(|| KrakenError::ApiError("Limit order needs a price".to_string()))?;
+            params.push(("ordertype", "limit".to_string()));
+            params.push(("price", price));
+            let time_in_force = match order.time_in_force {
+                TimeInForce::GoodTillCancelled => "GTC",
+                TimeInForce::ImmediateOrCancel => "IOC",
+                TimeInForce::FillOrKill => {
+                    return Err(KrakenError::ApiError(
+                        "Kraken spot does not support fill-or-kill orders".to_string(),
+                    ))
+                }
+            };
+            params.push(("timeinforce", time_in_force.to_string()));
+        }
+
+        if let Some(client_order_id) = &order.client_order_id {
+            params.push(("cl_ord_id", client_order_id.clone()));
+        }
+
+        let result = self.private("/0/private/AddOrder", &params).await?;
+        result
+            .get("txid")
+            .and_then(|v| v.as_array())
+            .and_then(|ids| ids.first())
+            .and_then(|id| id.as_str())
+            .map(|id| id.to_string())
+            .ok_or_else(|| KrakenError::ParseError("Invalid order ID in response".to_string()))
+    }
+
+    /// Cancel an order
+    pub async fn cancel_order(&self, order_id: OrderId) -> Result<(), KrakenError> {
+        self.private("/0/private/CancelOrder", &[("txid", order_id)])
+            .await?;
+        Ok(())
+    }
+
+    /// Cancel all open orders unless called again within a timeout (whole seconds),
+    /// arming the timer or resetting it; a zero timeout disarms it
+    pub async fn cancel_all_orders_after(&self, timeout: Duration) -> Result<(), KrakenError> {
+        self.private(
+            "/0/private/CancelAllOrdersAfter",
+            &[("timeout", timeout.as_secs().to_string())],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get an order, whether open or closed
+    pub async fn get_order(&self, order_id: &OrderId) -> Result<ExecutionReport, KrakenError> {
+        let result = self
+            .private("/0/private/QueryOrders", &[("txid", order_id.clone())])
+            .await?;
+        self.parse_orders(&result)
+            .await
+            .into_iter()
+            .next()
+            .ok_or_else(|| KrakenError::ApiError(format!("Order {} not found", order_id)))
+    }
+
+    /// Get open orders
+    pub async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, KrakenError> {
+        let result = self.private("/0/private/OpenOrders", &[]).await?;
+        let mut reports = self
+            .parse_orders(result.get("open").unwrap_or(&Value::Null))
+            .await;
+        if let Some(symbol) = symbol {
+            reports.retain(|report| report.symbol.as_str() == symbol);
+        }
+        Ok(reports)
+    }
+
+    /// Get closed orders, newest first
+    pub async fn get_order_history(
+        &self,
+        symbol: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, KrakenError> {
+        let result = self.private("/0/private/ClosedOrders", &[]).await?;
+        let mut reports = self
+            .parse_orders(result.get("closed").unwrap_or(&Value::Null))
+            .await;
+        if let Some(symbol) = symbol {
+            reports.retain(|report| report.symbol.as_str() == symbol);
+        }
+        reports.sort_by_key(|report| std::cmp::Reverse(report.timestamp));
+        reports.truncate(limit.unwrap_or(50));
+        Ok(reports)
+    }
+
+    /// Parse orders keyed by transaction ID, mapping REST pairs back to symbols
+    async fn parse_orders(&self, orders: &Value) -> Vec<ExecutionReport> {
+        let mut reports = parse_orders(orders);
+        for report in &mut reports {
+            report.symbol = Symbol::new(self.symbol_for_pair(report.symbol.as_str()).await);
+        }
+        reports
+    }
+
+    /// Get account balances with the amounts held by open orders
+    pub async fn get_account_info(&self) -> Result<Vec<Balance>, KrakenError> {
+        let result = self.private("/0/private/BalanceEx", &[]).await?;
+        Ok(parse_balances(&result))
+    }
+
+    /// Get the account's fee rates for a symbol
+    pub async fn get_fee_rate(&self, symbol: &str) -> Result<TradingFees, KrakenError> {
+        let result = self
+            .private(
+                "/0/private/TradeVolume",
+                &[("pair", self.rest_pair(symbol).await)],
+            )
+            .await?;
+        // Fees are percentages keyed by Kraken's internal pair name
+        let fee = |field: &str| {
+            result
+                .get(field)
+                .and_then(|fees| fees.as_object())
+                .and_then(|fees| fees.values().next())
+                .and_then(|fee| fee.get("fee"))
+                .and_then(parse_decimal)
+                .map(|percent| Size::new(percent / Decimal::ONE_HUNDRED))
+                .ok_or_else(|| KrakenError::ParseError(format!("Invalid {}", field)))
+        };
+        let taker_fee = fee("fees")?;
+        // Pairs without a maker schedule charge the taker fee
+        let maker_fee = fee("fees_maker").unwrap_or(taker_fee);
+        Ok(TradingFees::new(symbol.to_string(), maker_fee, taker_fee))
+    }
+}
+
+/// Parse REST `[[price, volume, timestamp], ...]` levels
+fn parse_rest_levels(levels: Option<&Value>) -> Vec<OrderBookLevel> {
+    levels
+        .and_then(|v| v.as_array())
+        .map(|levels| {
+            levels
+                .iter()
+                .filter_map(|level| {
+                    let price = Price::new(parse_decimal(level.get(0)?)?);
+                    let size = Size::new(parse_decimal(level.get(1)?)?);
+                    Some(OrderBookLevel::new(price, size))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse a REST Depth book for one pair
+pub fn parse_order_book(symbol: &str, book: &Value) -> OrderBookSnapshot {
+    OrderBookSnapshot::new(
+        symbol,
+        "kraken",
+        parse_rest_levels(book.get("bids")),
+        parse_rest_levels(book.get("asks")),
+        now_ms(),
+    )
+}
+
+/// Parse orders keyed by transaction ID (QueryOrders, or the `open`/`closed` of order lists)
+/// The symbol of each report is the REST pair name
+pub fn parse_orders(orders: &Value) -> Vec<ExecutionReport> {
+    orders
+        .as_object()
+        .map(|orders| {
+            orders
+                .iter()
+                .filter_map(|(txid, order)| parse_order(txid, order))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_order(txid: &str, order: &Value) -> Option<ExecutionReport> {
+    let decimal_field = |field: &str| order.get(field).and_then(parse_decimal);
+    let quantity = Size::new(decimal_field("vol")?);
+    let filled_size = Size::new(decimal_field("vol_exec").unwrap_or_default());
+
+    let status = match order.get("status")?.as_str()? {
+        "pending" | "open" if filled_size.is_zero() => OrderStatus::New,
+        "pending" | "open" => OrderStatus::PartiallyFilled,
+        "closed" => OrderStatus::Filled,
+        "canceled" => OrderStatus::Cancelled,
+        "expired" => OrderStatus::Expired,
+        _ => return None,
+    };
+    let average_price = decimal_field("price")
+        .filter(|price| *price > Decimal::ZERO)
+        .map(Price::new);
+    // Times are fractional seconds
+    let timestamp = decimal_field("closetm")
+        .or_else(|| decimal_field("opentm"))
+        .and_then(|secs| {
+            rust_decimal::prelude::ToPrimitive::to_u64(&(secs * Decimal::ONE_THOUSAND))
+        })
+        .unwrap_or_default();
+
+    Some(ExecutionReport {
+        order_id: txid.to_string(),
+        client_order_id: order
+            .get("cl_ord_id")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string()),
+        symbol: Symbol::new(order.get("descr")?.get("pair")?.as_str()?),
+        exchange_id: "kraken".to_string(),
+        status,
+        filled_size,
+        remaining_size: Size::new(quantity.value() - filled_size.value()),
+        average_price,
+        timestamp,
+        reject_reason: None,
+    })
+}
+
+/// Parse a BalanceEx `result`
+pub fn parse_balances(result: &Value) -> Vec<Balance> {
+    result
+        .as_object()
+        .map(|assets| {
+            assets
+                .iter()
+                .map(|(asset, amounts)| {
+                    let amount = |field: &str| {
+                        amounts
+                            .get(field)
+                            .and_then(parse_decimal)
+                            .unwrap_or_default()
+                    };
+                    let total = amount("balance");
+                    let used = amount("hold_trade");
+                    Balance {
+                        asset: asset.clone(),
+                        exchange_id: "kraken".to_string(),
+                        total,
+                        free: total - used,
+                        used,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse spot trading rules from an AssetPairs `result`
+/// Returns each spec, keyed by its WebSocket v2 symbol, with its REST pair name
+pub fn parse_asset_pairs(result: &Value) -> Vec<(InstrumentSpec, String)> {
+    result
+        .as_object()
+        .map(|pairs| {
+            pairs
+                .values()
+                .filter_map(|info| {
+                    // v2 uses BTC and DOGE where the v1 names use XBT and XDG
+                    let symbol = info
+                        .get("wsname")?
+                        .as_str()?
+                        .split('/')
+                        .map(|asset| match asset {
+                            "XBT" => "BTC",
+                            "XDG" => "DOGE",
+                            other => other,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    let tick_size = match info.get("tick_size").and_then(parse_decimal) {
+                        Some(tick_size) => tick_size,
+                        None => Decimal::new(1, info.get("pair_decimals")?.as_u64()? as u32),
+                    };
+                    let step_size = Decimal::new(1, info.get("lot_decimals")?.as_u64()? as u32);
+                    Some((
+                        InstrumentSpec::new(symbol, Price::new(tick_size), Size::new(step_size)),
+                        info.get("altname")?.as_str()?.to_string(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Checksum of the top of a book
+/// Each of the top ten asks (lowest first) then bids (highest first) contributes its
+/// price and quantity at the pair's precision, with the decimal point and leading zeros
+/// removed.
+pub fn book_checksum(book: &OrderBook, spec: &InstrumentSpec) -> u32 {
+    let mut payload = String::new();
+    let mut push = |price: Price, size: Size| {
+        for field in [spec.format_price(price), spec.format_size(size)] {
+            payload.push_str(field.replace('.', "").trim_start_matches('0'));
+        }
+    };
+    for (price, size) in book.asks_iter().take(CHECKSUM_LEVELS) {
+        push(price, size);
+    }
+    for (price, size) in book.bids_iter().take(CHECKSUM_LEVELS) {
+        push(price, size);
+    }
+    crc32(payload.as_bytes())
+}
+
+/// Parse WebSocket v2 `[{"price": .., "qty": ..}, ...]` levels
+fn parse_ws_levels(levels: Option<&Value>) -> Vec<OrderBookLevel> {
+    levels
+        .and_then(|v| v.as_array())
+        .map(|levels| {
+            levels
+                .iter()
+                .filter_map(|level| {
+                    let price = Price::new(parse_decimal(level.get("price")?)?);
+                    let size = Size::new(parse_decimal(level.get("qty")?)?);
+                    Some(OrderBookLevel::new(price, size))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Keeps a local copy of each depth-limited book to verify the feed
+///
+/// The v2 book channel has no sequence numbers; every update instead carries a CRC32 of
+/// the top ten levels after it is applied. A dropped, duplicated or reordered update
+/// shows up as a mismatch, after which the symbol's updates are discarded until a new
+/// snapshot arrives. Books of symbols without a known instrument spec cannot be
+/// formatted for the checksum and are passed through unverified.
+pub struct KrakenBookTracker {
+    depth: usize,
+    books: HashMap<String, OrderBook>,
+    instruments: HashMap<String, InstrumentSpec>,
+    /// Symbols waiting for a snapshot after a checksum mismatch
+    out_of_sync: HashSet<String>,
+    /// Symbols the stream should resubscribe to for a fresh snapshot
+    resync: Vec<String>,
+}
+
+impl KrakenBookTracker {
+    /// Create a tracker for books subscribed at `depth`
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            books: HashMap::new(),
+            instruments: HashMap::new(),
+            out_of_sync: HashSet::new(),
+            resync: Vec::new(),
+        }
+    }
+
+    /// Set the precision used to verify a symbol's checksums
+    pub fn set_instrument_spec(&mut self, spec: InstrumentSpec) {
+        self.instruments
+            .insert(spec.symbol.value().to_string(), spec);
+    }
+
+    /// Check if a symbol is waiting for a fresh snapshot
+    pub fn is_out_of_sync(&self, symbol: &str) -> bool {
+        self.out_of_sync.contains(symbol)
+    }
+
+    /// Take the symbols that need resubscribing
+    pub fn take_resync(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.resync)
+    }
+
+    /// Handle a WebSocket message
+    /// Returns the market events it carries; acknowledgements and heartbeats carry none
+    pub fn handle_message(&mut self, text: &str) -> Vec<Result<MarketEvent, KrakenError>> {
+        let json: Value = match serde_json::from_str(text) {
+            Ok(json) => json,
+            Err(e) => return vec![Err(KrakenError::ParseError(e.to_string()))],
+        };
+
+        if json.get("method").is_some() {
+            if json.get("success").and_then(|v| v.as_bool()) == Some(false) {
+                let error = json
+                    .get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                return vec![Err(KrakenError::ApiError(format!(
+                    "WebSocket request failed: {}",
+                    error
+                )))];
+            }
+            return Vec::new();
+        }
+        if json.get("channel").and_then(|v| v.as_str()) != Some("book") {
+            return Vec::new();
+        }
+
+        let is_snapshot = match json.get("type").and_then(|v| v.as_str()) {
+            Some("snapshot") => true,
+            Some("update") => false,
+            other => {
+                return vec![Err(KrakenError::ParseError(format!(
+                    "Unknown book message type {:?}",
+                    other
+                )))]
+            }
+        };
+        json.get("data")
+            .and_then(|v| v.as_array())
+            .map(|books| {
+                books
+                    .iter()
+                    .filter_map(|book| self.handle_book(book, is_snapshot).transpose())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn handle_book(
+        &mut self,
+        data: &Value,
+        is_snapshot: bool,
+    ) -> Result<Option<MarketEvent>, KrakenError> {
+        let symbol = data
+            .get("symbol")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| KrakenError::ParseError("Book message without symbol".to_string()))?;
+        let timestamp = data
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok())
+            .map(|time| time.timestamp_millis() as u64)
+            .unwrap_or_else(now_ms);
+        let mut bids = parse_ws_levels(data.get("bids"));
+        let mut asks = parse_ws_levels(data.get("asks"));
+
+        if !is_snapshot && self.out_of_sync.contains(symbol) {
+            return Ok(None);
+        }
+        let book = self
+            .books
+            .entry(symbol.to_string())
+            .or_insert_with(|| OrderBook::new(symbol.to_string()));
+        if is_snapshot {
+            book.apply_snapshot(OrderBookSnapshot::new(
+                symbol,
+                "kraken",
+                bids.clone(),
+                asks.clone(),
+                timestamp,
+            ));
+            self.out_of_sync.remove(symbol);
+        } else {
+            book.apply_delta(OrderBookDelta::new(
+                symbol,
+                "kraken",
+                bids.clone(),
+                asks.clone(),
+                timestamp,
+            ));
+            // Levels pushed out of the subscribed depth are no longer maintained
+            let (removed_bids, removed_asks) = book.truncate(self.depth);
+            bids.extend(
+                removed_bids
+                    .into_iter()
+                    .map(|p| OrderBookLevel::new(p, Size::zero())),
+            );
+            asks.extend(
+                removed_asks
+                    .into_iter()
+                    .map(|p| OrderBookLevel::new(p, Size::zero())),
+            );
+        }
+
+        let expected = data.get("checksum").and_then(|v| v.as_u64());
+        if let (Some(expected), Some(spec)) = (expected, self.instruments.get(symbol)) {
+            let actual = book_checksum(book, spec);
+            if actual as u64 != expected {
+                self.books.remove(symbol);
+                self.out_of_sync.insert(symbol.to_string());
+                self.resync.push(symbol.to_string());
+                return Err(KrakenError::ChecksumMismatch(format!(
+                    "{}: expected {}, computed {}",
+                    symbol, expected, actual
+                )));
+            }
+        }
+
+        Ok(Some(if is_snapshot {
+            MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+                symbol, "kraken", bids, asks, timestamp,
+            ))
+        } else {
+            MarketEvent::OrderBookDelta(OrderBookDelta::new(
+                symbol, "kraken", bids, asks, timestamp,
+            ))
+        }))
+    }
+}
+
+/// Kraken WebSocket v2 stream for the public order book channel
+pub struct KrakenWebSocket {
+    /// WebSocket URL
+    ws_url: String,
+    /// WebSocket connection
+    ws_stream: Option<
+        tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >,
+    >,
+    /// Subscribed symbols
+    subscriptions: Vec<String>,
+    /// Connection status
+    connected: Arc<RwLock<bool>>,
+    /// Local books used to verify checksums
+    tracker: KrakenBookTracker,
+    /// Events parsed but not yet returned
+    pending: VecDeque<Result<MarketEvent, KrakenError>>,
+    /// Per-symbol message counts and last update times
+    symbol_stats: SymbolStatsRegistry,
+}
+
+impl KrakenWebSocket {
+    /// Create a new Kraken WebSocket stream
+    pub fn new() -> Self {
+        Self {
+            ws_url: "wss://ws.kraken.com/v2".to_string(),
+            ws_stream: None,
+            subscriptions: Vec::new(),
+            connected: Arc::new(RwLock::new(false)),
+            tracker: KrakenBookTracker::new(WS_BOOK_DEPTH),
+            pending: VecDeque::new(),
+            symbol_stats: SymbolStatsRegistry::new(),
+        }
+    }
+
+    /// Record stats into a shared registry (builder pattern)
+    pub fn with_symbol_stats(mut self, symbol_stats: SymbolStatsRegistry) -> Self {
+        self.symbol_stats = symbol_stats;
+        self
+    }
+
+    /// Get the per-symbol stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Set the precision used to verify a symbol's book checksums
+    pub fn set_instrument_spec(&mut self, spec: InstrumentSpec) {
+        self.tracker.set_instrument_spec(spec);
+    }
+
+    /// Send a subscribe or unsubscribe request for symbols
+    async fn send_method(&mut self, method: &str, symbols: &[&str]) -> Result<(), KrakenError> {
+        let Some(ws) = &mut self.ws_stream else {
+            return Err(KrakenError::ConnectionError("Not connected".to_string()));
+        };
+        if symbols.is_empty() {
+            return Ok(());
+        }
+        let mut params = json!({ "channel": "book", "symbol": symbols });
+        if method == "subscribe" {
+            params["depth"] = json!(WS_BOOK_DEPTH);
+        }
+        let request = json!({ "method": method, "params": params });
+        ws.send(Message::Text(request.to_string()))
+            .await
+            .map_err(|e| KrakenError::ConnectionError(e.to_string()))
+    }
+
+    /// Connect to the WebSocket stream and subscribe to symbols
+    pub async fn connect(&mut self, symbols: &[&str]) -> Result<(), KrakenError> {
+        log::info!("Connecting to Kraken WebSocket: {}", self.ws_url);
+
+        let (ws_stream, _) = connect_async(&self.ws_url)
+            .await
+            .map_err(|e| KrakenError::ConnectionError(e.to_string()))?;
+        self.ws_stream = Some(ws_stream);
+        *self.connected.write().await = true;
+
+        for symbol in symbols {
+            if !self.subscriptions.iter().any(|s| s == symbol) {
+                self.subscriptions.push(symbol.to_string());
+            }
+        }
+        let subscriptions = self.subscriptions.clone();
+        let symbol_refs: Vec<&str> = subscriptions.iter().map(|s| s.as_str()).collect();
+        self.send_method("subscribe", &symbol_refs).await
+    }
+
+    /// Disconnect from the WebSocket stream
+    pub async fn disconnect(&mut self) -> Result<(), KrakenError> {
+        if let Some(mut ws) = self.ws_stream.take() {
+            ws.close(None)
+                .await
+                .map_err(|e| KrakenError::ConnectionError(e.to_string()))?;
+        }
+        *self.connected.write().await = false;
+        Ok(())
+    }
+
+    /// Resubscribe symbols whose books failed verification to get a fresh snapshot
+    async fn resync(&mut self) -> Result<(), KrakenError> {
+        let symbols = self.tracker.take_resync();
+        if symbols.is_empty() {
+            return Ok(());
+        }
+        log::warn!("Resyncing Kraken books for {:?}", symbols);
+        let symbol_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
+        self.send_method("unsubscribe", &symbol_refs).await?;
+        self.send_method("subscribe", &symbol_refs).await
+    }
+}
+
+impl Default for KrakenWebSocket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for KrakenWebSocket {
+    type Error = KrakenError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        if self.ws_stream.is_none() {
+            return self.connect(symbols).await;
+        }
+        let new_symbols: Vec<&str> = symbols
+            .iter()
+            .copied()
+            .filter(|symbol| !self.subscriptions.iter().any(|s| s == symbol))
+            .collect();
+        self.send_method("subscribe", &new_symbols).await?;
+        self.subscriptions
+            .extend(new_symbols.iter().map(|s| s.to_string()));
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.subscriptions
+            .retain(|s| !symbols.contains(&s.as_str()));
+        if self.ws_stream.is_some() {
+            self.send_method("unsubscribe", symbols).await?;
+        }
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                if let Ok(event) = &event {
+                    self.symbol_stats.record_event(event);
+                }
+                return Some(event);
+            }
+            if let Err(e) = self.resync().await {
+                return Some(Err(e));
+            }
+
+            let ws = self.ws_stream.as_mut()?;
+            match ws.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    self.pending.extend(self.tracker.handle_message(&text));
+                }
+                Some(Ok(Message::Close(_))) | None => {
+                    self.ws_stream = None;
+                    *self.connected.write().await = false;
+                    return None;
+                }
+                Some(Err(e)) => return Some(Err(KrakenError::ConnectionError(e.to_string()))),
+                Some(Ok(_)) => continue,
+            }
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.try_read().map(|c| *c).unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+/// Kraken error types
+#[derive(Debug, Clone)]
+pub enum KrakenError {
+    NetworkError(String),
+    ConnectionError(String),
+    ApiError(String),
+    ParseError(String),
+    AuthenticationError(String),
+    RateLimitError(String),
+    /// The local book no longer matches the exchange's; a new snapshot has been requested
+    ChecksumMismatch(String),
+}
+
+impl std::fmt::Display for KrakenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KrakenError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            KrakenError::ConnectionError(msg) => write!(f, "Connection error: {}", msg),
+            KrakenError::ApiError(msg) => write!(f, "API error: {}", msg),
+            KrakenError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            KrakenError::AuthenticationError(msg) => write!(f, "Authentication error: {}", msg),
+            KrakenError::RateLimitError(msg) => write!(f, "Rate limit error: {}", msg),
+            KrakenError::ChecksumMismatch(msg) => write!(f, "Book checksum mismatch: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for KrakenError {}
+
+/// Kraken adapter that implements MarketDataStream, ExecutionClient and ExchangeAdapter
+///
+/// Symbols are Kraken's WebSocket v2 names (e.g. "BTC/USD"); REST pair names are
+/// looked up from the specs loaded by `load_instrument_specs`.
+pub struct KrakenAdapter {
+    /// Kraken client for REST API
+    client: KrakenClient,
+    /// Kraken WebSocket for market data
+    websocket: Arc<Mutex<KrakenWebSocket>>,
+    /// Per-symbol stats shared with the WebSocket
+    symbol_stats: SymbolStatsRegistry,
+    /// Cancel-on-disconnect timer re-armed by each heartbeat
+    session_safety: Option<SessionSafety>,
+}
+
+impl KrakenAdapter {
+    /// Create a new Kraken adapter
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        let symbol_stats = SymbolStatsRegistry::new();
+        Self {
+            client: KrakenClient::new(api_key, api_secret),
+            websocket: Arc::new(Mutex::new(
+                KrakenWebSocket::new().with_symbol_stats(symbol_stats.clone()),
+            )),
+            symbol_stats,
+            session_safety: None,
+        }
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.client = self.client.with_rest_url(rest_url);
+        self
+    }
+
+    /// Have the exchange cancel open orders when heartbeats stop (builder pattern)
+    pub fn with_session_safety(mut self, session_safety: SessionSafety) -> Self {
+        self.session_safety = Some(session_safety);
+        self
+    }
+
+    /// Get the per-symbol market data stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Set the trading rules used to format orders and verify book checksums
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        self.websocket
+            .lock()
+            .await
+            .set_instrument_spec(spec.clone());
+        self.client.set_instrument_spec(spec).await;
+    }
+
+    /// Load trading rules for all spot pairs from the exchange
+    pub async fn load_instrument_specs(&self) -> Result<usize, KrakenError> {
+        let specs = self.client.load_instrument_specs().await?;
+        let count = specs.len();
+        let mut ws = self.websocket.lock().await;
+        for spec in specs {
+            ws.set_instrument_spec(spec);
+        }
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl ServerClock for KrakenAdapter {
+    type Error = KrakenError;
+
+    async fn server_time(&self) -> Result<u64, Self::Error> {
+        self.client.get_server_time().await
+    }
+}
+
+#[async_trait]
+impl ExecutionClient for KrakenAdapter {
+    type Error = KrakenError;
+
+    async fn place_order(&self, order: NewOrder) -> Result<OrderId, Self::Error> {
+        self.client.place_order(&order).await
+    }
+
+    async fn cancel_order(&self, order_id: OrderId) -> Result<(), Self::Error> {
+        self.client.cancel_order(order_id).await
+    }
+
+    async fn get_order_status(&self, order_id: OrderId) -> Result<ExecutionReport, Self::Error> {
+        self.client.get_order(&order_id).await
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Self::Error> {
+        self.client.get_account_info().await
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.client.get_open_orders(symbol).await
+    }
+
+    async fn get_order_history(
+        &self,
+        symbol: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.client.get_order_history(symbol, limit).await
+    }
+
+    async fn get_trading_fees(&self, symbol: &str) -> Result<TradingFees, Self::Error> {
+        self.client.get_fee_rate(symbol).await
+    }
+
+    async fn heartbeat(&self) -> Result<(), Self::Error> {
+        match &self.session_safety {
+            Some(safety) => {
+                self.client
+                    .cancel_all_orders_after(safety.cancel_after)
+                    .await
+            }
+            None => self.client.get_server_time().await.map(|_| ()),
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for KrakenAdapter {
+    type Error = KrakenError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.websocket.lock().await.subscribe(symbols).await
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.websocket.lock().await.unsubscribe(symbols).await
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        self.websocket.lock().await.next().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.websocket
+            .try_lock()
+            .map(|ws| ws.is_connected())
+            .unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+#[async_trait]
+impl crate::exchanges::connection_manager::ExchangeAdapter for KrakenAdapter {
+    async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // The WebSocket connects on the first subscription
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ws = self.websocket.lock().await;
+        ws.disconnect()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_market_data_stream(
+        &self,
+    ) -> Result<
+        Arc<
+            tokio::sync::Mutex<
+                dyn MarketDataStream<Error = crate::exchanges::error::BoxedError> + Send + Sync,
+            >,
+        >,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        Ok(Arc::new(tokio::sync::Mutex::new(KrakenWebSocketAdapter {
+            websocket: self.websocket.clone(),
+            symbol_stats: self.symbol_stats.clone(),
+        })))
+    }
+
+    async fn place_order(
+        &self,
+        order: NewOrder,
+    ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::place_order(self, order)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn cancel_order(
+        &self,
+        order_id: OrderId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::cancel_order(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_order_status(
+        &self,
+        order_id: OrderId,
+    ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::get_order_status(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_account_info()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_open_orders(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: u32,
+    ) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_order_book(symbol, limit)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_trading_fees(
+        &self,
+        symbol: &str,
+    ) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_fee_rate(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+}
+
+/// Wrapper for KrakenWebSocket to implement the type-erased MarketDataStream
+pub struct KrakenWebSocketAdapter {
+    websocket: Arc<Mutex<KrakenWebSocket>>,
+    symbol_stats: SymbolStatsRegistry,
+}
+
+#[async_trait]
+impl MarketDataStream for KrakenWebSocketAdapter {
+    type Error = crate::exchanges::error::BoxedError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        let mut ws = self.websocket.lock().await;
+        ws.subscribe(symbols)
+            .await
+            .map_err(crate::exchanges::error::BoxedError::new)
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        let mut ws = self.websocket.lock().await;
+        ws.unsubscribe(symbols)
+            .await
+            .map_err(crate::exchanges::error::BoxedError::new)
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        let mut ws = self.websocket.lock().await;
+        ws.next()
+            .await
+            .map(|r| r.map_err(crate::exchanges::error::BoxedError::new))
+    }
+
+    fn is_connected(&self) -> bool {
+        self.websocket
+            .try_lock()
+            .map(|ws| ws.is_connected())
+            .unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> InstrumentSpec {
+        InstrumentSpec::new(
+            "BTC/USD",
+            Price::from_str("0.1").unwrap(),
+            Size::from_str("0.00000001").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_signature_and_checksum_primitives() {
+        // Standard CRC-32 check value
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let secret = general_purpose::STANDARD.encode("test_secret");
+        let client = KrakenClient::new("key".to_string(), secret);
+        let signature = client
+            .sign(
+                "/0/private/AddOrder",
+                1616492376594,
+                "nonce=1616492376594&pair=XBTUSD",
+            )
+            .unwrap();
+        // Base64 of a 64-byte HMAC-SHA512, stable for the same inputs
+        assert_eq!(
+            general_purpose::STANDARD.decode(&signature).unwrap().len(),
+            64
+        );
+        assert_eq!(
+            signature,
+            client
+                .sign(
+                    "/0/private/AddOrder",
+                    1616492376594,
+                    "nonce=1616492376594&pair=XBTUSD"
+                )
+                .unwrap()
+        );
+        assert!(
+            KrakenClient::new("key".to_string(), "not base64!".to_string())
+                .sign("/0/private/Balance", 1, "nonce=1")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_book_feed_is_verified_and_resynced() {
+        let mut tracker = KrakenBookTracker::new(2);
+        tracker.set_instrument_spec(spec());
+
+        // Checksums computed the way the exchange does, over the local book
+        let mut expected = OrderBook::new("BTC/USD".to_string());
+        let level = |price: &str, size: &str| {
+            OrderBookLevel::new(
+                Price::from_str(price).unwrap(),
+                Size::from_str(size).unwrap(),
+            )
+        };
+        expected.apply_snapshot(OrderBookSnapshot::new(
+            "BTC/USD",
+            "kraken",
+            vec![level("50000.0", "1.5"), level("49999.9", "0.25")],
+            vec![level("50000.1", "0.5")],
+            0,
+        ));
+        assert_eq!(
+            crc32(b"5000015000000050000015000000049999925000000"),
+            book_checksum(&expected, &spec())
+        );
+
+        let snapshot = format!(
+            r#"{{"channel":"book","type":"snapshot","data":[{{"symbol":"BTC/USD",
+            "bids":[{{"price":50000.0,"qty":1.5}},{{"price":49999.9,"qty":0.25}}],
+            "asks":[{{"price":50000.1,"qty":0.5}}],"checksum":{}}}]}}"#,
+            book_checksum(&expected, &spec())
+        );
+        let events = tracker.handle_message(&snapshot);
+        assert!(matches!(
+            events[..],
+            [Ok(MarketEvent::OrderBookSnapshot(_))]
+        ));
+
+        // A better bid pushes the worst one out of the depth-2 book
+        expected.apply_delta(OrderBookDelta::new(
+            "BTC/USD",
+            "kraken",
+            vec![level("50000.05", "2")],
+            vec![],
+            0,
+        ));
+        expected.truncate(2);
+        let update = format!(
+            r#"{{"channel":"book","type":"update","data":[{{"symbol":"BTC/USD",
+            "bids":[{{"price":50000.05,"qty":2}}],"asks":[],"checksum":{},
+            "timestamp":"2023-10-06T17:35:55.440295Z"}}]}}"#,
+            book_checksum(&expected, &spec())
+        );
+        match &tracker.handle_message(&update)[..] {
+            [Ok(MarketEvent::OrderBookDelta(delta))] => {
+                assert_eq!(delta.timestamp, 1696613755440);
+                assert_eq!(delta.bids.len(), 2);
+                assert_eq!(delta.bids[1], level("49999.9", "0"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // A missed update shows up as a mismatch; later updates wait for a snapshot
+        let stale = r#"{"channel":"book","type":"update","data":[{"symbol":"BTC/USD",
+            "bids":[],"asks":[{"price":50000.2,"qty":1}],"checksum":1}]}"#;
+        assert!(matches!(
+            tracker.handle_message(stale)[..],
+            [Err(KrakenError::ChecksumMismatch(_))]
+        ));
+        assert!(tracker.is_out_of_sync("BTC/USD"));
+        assert_eq!(tracker.take_resync(), vec!["BTC/USD".to_string()]);
+        assert!(tracker.handle_message(&update).is_empty());
+        tracker.handle_message(&snapshot);
+        assert!(!tracker.is_out_of_sync("BTC/USD"));
+
+        let ack = r#"{"method":"subscribe","result":{"channel":"book","symbol":"BTC/USD"},"success":true}"#;
+        assert!(tracker.handle_message(ack).is_empty());
+        assert!(tracker
+            .handle_message(r#"{"channel":"heartbeat"}"#)
+            .is_empty());
+        let failed =
+            r#"{"method":"subscribe","error":"Currency pair not supported","success":false}"#;
+        assert!(matches!(
+            tracker.handle_message(failed)[..],
+            [Err(KrakenError::ApiError(_))]
+        ));
+    }
+
+    #[test]
+    fn test_parse_rest_responses() {
+        let pairs = serde_json::json!({
+            "XXBTZUSD": {
+                "altname": "XBTUSD", "wsname": "XBT/USD",
+                "pair_decimals": 1, "lot_decimals": 8, "tick_size": "0.1"
+            }
+        });
+        let specs = parse_asset_pairs(&pairs);
+        assert_eq!(specs[0].0.symbol.value(), "BTC/USD");
+        assert_eq!(specs[0].0.step_size, Size::from_str("0.00000001").unwrap());
+        assert_eq!(specs[0].1, "XBTUSD");
+
+        let orders = serde_json::json!({
+            "OQCLML-BW3P3-BUCMWZ": {
+                "status": "open", "vol": "1.25", "vol_exec": "0.25", "price": "30010.0",
+                "opentm": 1688666559.8974, "cl_ord_id": "mm-7",
+                "descr": {"pair": "XBTUSD", "type": "buy", "ordertype": "limit"}
+            }
+        });
+        let reports = parse_orders(&orders);
+        assert_eq!(reports[0].status, OrderStatus::PartiallyFilled);
+        assert_eq!(reports[0].remaining_size, Size::from_str("1").unwrap());
+        assert_eq!(reports[0].client_order_id.as_deref(), Some("mm-7"));
+        assert_eq!(reports[0].timestamp, 1688666559897);
+
+        let balances = parse_balances(&serde_json::json!({
+            "ZUSD": {"balance": "1000.5", "hold_trade": "200"}
+        }));
+        assert_eq!(balances[0].free, Decimal::new(8005, 1));
+    }
+
+    #[tokio::test]
+    async fn test_place_and_query_signed_orders() {
+        use wiremock::matchers::{body_string_contains, header_exists, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/0/private/AddOrder"))
+            .and(header_exists("API-Sign"))
+            .and(body_string_contains("pair=XBTUSD"))
+            .and(body_string_contains("ordertype=limit"))
+            .and(body_string_contains("volume=0.00123450"))
+            .and(body_string_contains("price=50000.1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "error": [],
+                "result": {"descr": {"order": "buy 0.00123450 XBTUSD @ limit 50000.1"},
+                           "txid": ["OUF4EM-FRGI2-MQMWZD"]}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/0/private/QueryOrders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "error": [],
+                "result": {"OUF4EM-FRGI2-MQMWZD": {
+                    "status": "canceled", "vol": "0.00123450", "vol_exec": "0", "price": "0",
+                    "opentm": 1688666559.8974, "closetm": 1688666560.1,
+                    "descr": {"pair": "XBTUSD"}
+                }}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/0/private/CancelAllOrdersAfter"))
+            .and(body_string_contains("timeout=60"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "error": [],
+                "result": {"currentTime": "2023-07-06T18:02:40Z",
+                           "triggerTime": "2023-07-06T18:03:40Z"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/0/private/TradeVolume"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "error": ["EAPI:Invalid nonce"]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/0/public/AssetPairs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "error": [],
+                "result": {"XXBTZUSD": {
+                    "altname": "XBTUSD", "wsname": "XBT/USD",
+                    "pair_decimals": 1, "lot_decimals": 8, "tick_size": "0.1"
+                }}
+            })))
+            .mount(&server)
+            .await;
+
+        let adapter = KrakenAdapter::new(
+            "key".to_string(),
+            general_purpose::STANDARD.encode("secret"),
+        )
+        .with_rest_url(server.uri());
+        assert_eq!(adapter.load_instrument_specs().await.unwrap(), 1);
+
+        let order = NewOrder::new_limit_buy(
+            "BTC/USD",
+            Size::from_str("0.0012345").unwrap(),
+            Price::from_str("50000.12").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let order_id = ExecutionClient::place_order(&adapter, order).await.unwrap();
+        assert_eq!(order_id, "OUF4EM-FRGI2-MQMWZD");
+
+        let report = ExecutionClient::get_order_status(&adapter, order_id)
+            .await
+            .unwrap();
+        assert_eq!(report.status, OrderStatus::Cancelled);
+        assert_eq!(report.symbol.value(), "BTC/USD");
+        assert_eq!(report.average_price, None);
+
+        let fok = NewOrder::new_limit_buy(
+            "BTC/USD",
+            Size::from_str("1").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::FillOrKill,
+        );
+        assert!(ExecutionClient::place_order(&adapter, fok).await.is_err());
+        assert!(matches!(
+            ExecutionClient::get_trading_fees(&adapter, "BTC/USD").await,
+            Err(KrakenError::AuthenticationError(_))
+        ));
+
+        // Heartbeats re-arm the account's cancel-on-disconnect timer
+        let adapter = adapter.with_session_safety(SessionSafety::new(Duration::from_secs(60)));
+        ExecutionClient::heartbeat(&adapter).await.unwrap();
+    }
+}
@@ -0,0 +1,1144 @@
+use crate::core::events::{OrderBookLevel, OrderBookSnapshot};
+use crate::monitoring::SymbolStatsRegistry;
+use crate::traits::{
+    Balance, ExecutionClient, ExecutionReport, MarketDataStream, MarketEvent, NewOrder, OrderId,
+    OrderSide, OrderStatus, OrderType, ServerClock, TimeInForce, TradingFees,
+};
+use crate::types::instrument::{format_price_plain, format_quote_plain, format_size_plain};
+use crate::types::{InstrumentSpec, Notional, Price, Size, Symbol};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, RwLock};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+/// Response code of a successful request
+const SUCCESS_CODE: &str = "200000";
+/// Response code for too many requests
+const RATE_LIMIT_CODE: &str = "429000";
+/// Response codes for a bad key, signature, timestamp or passphrase
+const AUTH_ERROR_CODES: [&str; 7] = [
+    "400001", "400002", "400003", "400004", "400005", "400006", "400007",
+];
+/// Ping interval used when the bullet response does not name one
+const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(18);
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Parse a decimal sent as a JSON string or number
+fn parse_decimal(value: &Value) -> Option<Decimal> {
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .ok()
+}
+
+fn hmac_base64(secret: &str, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(payload.as_bytes());
+    general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+/// KuCoin spot REST client for market data and order execution
+pub struct KucoinClient {
+    /// API key
+    api_key: String,
+    /// API secret
+    api_secret: String,
+    /// API passphrase chosen when the key was created
+    passphrase: String,
+    /// Base URL for REST API
+    rest_url: String,
+    /// HTTP client
+    http_client: Client,
+    /// Instrument trading rules used to format order parameters
+    instruments: Arc<RwLock<HashMap<String, InstrumentSpec>>>,
+}
+
+impl KucoinClient {
+    /// Create a new KuCoin client
+    pub fn new(api_key: String, api_secret: String, passphrase: String) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            passphrase,
+            rest_url: "https://api.kucoin.com".to_string(),
+            http_client: Client::new(),
+            instruments: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self
+    }
+
+    /// Set the trading rules for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        let mut instruments = self.instruments.write().await;
+        instruments.insert(spec.symbol.value().to_string(), spec);
+    }
+
+    /// Load trading rules for all spot symbols
+    pub async fn load_instrument_specs(&self) -> Result<usize, KucoinError> {
+        let symbols = self
+            .request(Method::GET, "/api/v2/symbols", None, false)
+            .await?;
+        let specs = parse_symbols(&symbols);
+        let count = specs.len();
+        let mut instruments = self.instruments.write().await;
+        for spec in specs {
+            instruments.insert(spec.symbol.value().to_string(), spec);
+        }
+        Ok(count)
+    }
+
+    /// Format the price and size of an order for its instrument
+    async fn format_order_params(&self, order: &NewOrder) -> (Option<String>, String) {
+        let instruments = self.instruments.read().await;
+        match instruments.get(order.symbol.as_str()) {
+            Some(spec) => (
+                order.price.map(|price| spec.format_price(price)),
+                spec.format_size(order.size),
+            ),
+            None => (
+                order.price.map(format_price_plain),
+                format_size_plain(order.size),
+            ),
+        }
+    }
+
+    /// Format a quote-asset order amount using the instrument's price precision
+    async fn format_quote_param(&self, symbol: &str, quote_size: Notional) -> String {
+        match self.instruments.read().await.get(symbol) {
+            Some(spec) => spec.format_quote_size(quote_size),
+            None => format_quote_plain(quote_size),
+        }
+    }
+
+    /// Sign a request
+    /// The signature is the base64 HMAC-SHA256 of timestamp + method + endpoint (with query)
+    /// + body, keyed by the API secret
+    pub fn sign(&self, timestamp: u64, method: &str, endpoint: &str, body: &str) -> String {
+        let payload = format!("{}{}{}{}", timestamp, method, endpoint, body);
+        hmac_base64(&self.api_secret, &payload)
+    }
+
+    /// Send a request and unwrap the `data` of the response envelope
+    async fn request(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Option<&Value>,
+        signed: bool,
+    ) -> Result<Value, KucoinError> {
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+        let mut request = self
+            .http_client
+            .request(method.clone(), format!("{}{}", self.rest_url, endpoint))
+            .header("Content-Type", "application/json");
+        if signed {
+            let timestamp = now_ms();
+            request = request
+                .header("KC-API-KEY", &self.api_key)
+                .header(
+                    "KC-API-SIGN",
+                    self.sign(timestamp, method.as_str(), endpoint, &body),
+                )
+                .header("KC-API-TIMESTAMP", timestamp.to_string())
+                // Version 2 keys send the passphrase signed with the secret
+                .header(
+                    "KC-API-PASSPHRASE",
+                    hmac_base64(&self.api_secret, &self.passphrase),
+                )
+                .header("KC-API-KEY-VERSION", "2");
+        }
+        if !body.is_empty() {
+            request = request.body(body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| KucoinError::NetworkError(e.to_string()))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| KucoinError::NetworkError(e.to_string()))?;
+        let json: Value = serde_json::from_str(&text).unwrap_or_default();
+        let code = json
+            .get("code")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+
+        if code == SUCCESS_CODE {
+            return Ok(json.get("data").cloned().unwrap_or(Value::Null));
+        }
+        let message = format!("Request to {} failed: {} - {}", endpoint, status, text);
+        Err(if status.as_u16() == 429 || code == RATE_LIMIT_CODE {
+            KucoinError::RateLimitError(message)
+        } else if AUTH_ERROR_CODES.contains(&code) {
+            KucoinError::AuthenticationError(message)
+        } else if status.is_success() && json.is_null() {
+            KucoinError::ParseError(message)
+        } else {
+            KucoinError::ApiError(message)
+        })
+    }
+
+    /// Get current server time in milliseconds
+    pub async fn get_server_time(&self) -> Result<u64, KucoinError> {
+        self.request(Method::GET, "/api/v1/timestamp", None, false)
+            .await?
+            .as_u64()
+            .ok_or_else(|| KucoinError::ParseError("Invalid server time".to_string()))
+    }
+
+    /// Get a 20-level order book snapshot
+    pub async fn get_order_book(&self, symbol: &str) -> Result<OrderBookSnapshot, KucoinError> {
+        let endpoint = format!("/api/v1/market/orderbook/level2_20?symbol={}", symbol);
+        let book = self.request(Method::GET, &endpoint, None, false).await?;
+        Ok(parse_order_book(symbol, &book))
+    }
+
+    /// Place a new spot order
+    /// Quote-sized market orders are sent as `funds`; other quote-sized orders are
+    /// converted to a base size at their limit price.
+    pub async fn place_order(&self, order: &NewOrder) -> Result<OrderId, KucoinError> {
+        let is_market = order.order_type == OrderType::Market;
+        let converted;
+        let order = match (order.quote_size, order.price) {
+            (Some(_), Some(price)) if !is_market => {
+                let step_size = self
+                    .instruments
+                    .read()
+                    .await
+                    .get(order.symbol.as_str())
+                    .map(|spec| spec.step_size);
+                converted = order.with_base_size_at(price, step_size).ok_or_else(|| {
+                    KucoinError::ApiError(format!("Cannot size order at price {}", price))
+                })?;
+                &converted
+            }
+            (Some(_), None) if !is_market => {
+                return Err(KucoinError::ApiError(
+                    "Quote-sized order needs a price".to_string(),
+                ))
+            }
+            _ => order,
+        };
+        let (price_param, size_param) = self.format_order_params(order).await;
+
+        let mut body = json!({
+            "clientOid": order
+                .client_order_id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            "side": match order.side {
+                OrderSide::Buy => "buy",
+                OrderSide::Sell => "sell",
+            },
+            "symbol": order.symbol.as_str(),
+            "type": if is_market { "market" } else { "limit" },
+        });
+        match order.quote_size {
+            Some(quote_size) => {
+                body["funds"] = json!(
+                    self.format_quote_param(order.symbol.as_str(), quote_size)
+                        .await
+                )
+            }
+            None => body["size"] = json!(size_param),
+        }
+        if !is_market {
+            let price = price_param
+                .ok_or_else(|| KucoinError::ApiError("Limit order needs a price".to_string()))?;
+            body["price"] = json!(price);
+            body["timeInForce"] = json!(match order.time_in_force {
+                TimeInForce::GoodTillCancelled => "GTC",
+                TimeInForce::ImmediateOrCancel => "IOC",
+                TimeInForce::FillOrKill => "FOK",
+            });
+        }
+
+        let data = self
+            .request(Method::POST, "/api/v1/orders", Some(&body), true)
+            .await?;
+        data.get("orderId")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+            .ok_or_else(|| KucoinError::ParseError("Invalid order ID in response".to_string()))
+    }
+
+    /// Cancel an order
+    pub async fn cancel_order(&self, order_id: &OrderId) -> Result<(), KucoinError> {
+        let endpoint = format!("/api/v1/orders/{}", order_id);
+        self.request(Method::DELETE, &endpoint, None, true).await?;
+        Ok(())
+    }
+
+    /// Get an order, whether open or closed
+    pub async fn get_order(&self, order_id: &OrderId) -> Result<ExecutionReport, KucoinError> {
+        let endpoint = format!("/api/v1/orders/{}", order_id);
+        let order = self.request(Method::GET, &endpoint, None, true).await?;
+        parse_order(&order).ok_or_else(|| KucoinError::ParseError("Invalid order".to_string()))
+    }
+
+    /// List orders with a status of `active` or `done`
+    async fn list_orders(
+        &self,
+        status: &str,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, KucoinError> {
+        let mut endpoint = format!("/api/v1/orders?status={}", status);
+        if let Some(symbol) = symbol {
+            endpoint.push_str(&format!("&symbol={}", symbol));
+        }
+        let data = self.request(Method::GET, &endpoint, None, true).await?;
+        Ok(data
+            .get("items")
+            .and_then(|v| v.as_array())
+            .map(|orders| orders.iter().filter_map(parse_order).collect())
+            .unwrap_or_default())
+    }
+
+    /// Get open orders
+    pub async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, KucoinError> {
+        self.list_orders("active", symbol).await
+    }
+
+    /// Get recently closed orders, newest first
+    pub async fn get_order_history(
+        &self,
+        symbol: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, KucoinError> {
+        let mut reports = self.list_orders("done", symbol).await?;
+        reports.sort_by_key(|report| std::cmp::Reverse(report.timestamp));
+        if let Some(limit) = limit {
+            reports.truncate(limit);
+        }
+        Ok(reports)
+    }
+
+    /// Get trade account balances with the amounts held by open orders
+    pub async fn get_account_info(&self) -> Result<Vec<Balance>, KucoinError> {
+        let accounts = self
+            .request(Method::GET, "/api/v1/accounts?type=trade", None, true)
+            .await?;
+        Ok(parse_balances(&accounts))
+    }
+
+    /// Get the account's fee rates for a symbol
+    pub async fn get_fee_rate(&self, symbol: &str) -> Result<TradingFees, KucoinError> {
+        let endpoint = format!("/api/v1/trade-fees?symbols={}", symbol);
+        let data = self.request(Method::GET, &endpoint, None, true).await?;
+        let fee = |field: &str| {
+            data.get(0)
+                .and_then(|fees| fees.get(field))
+                .and_then(parse_decimal)
+                .map(Size::new)
+                .ok_or_else(|| KucoinError::ParseError(format!("Invalid {}", field)))
+        };
+        Ok(TradingFees::new(
+            symbol.to_string(),
+            fee("makerFeeRate")?,
+            fee("takerFeeRate")?,
+        ))
+    }
+
+    /// Request a public WebSocket token and server
+    /// Returns the connect URL and the server's ping interval
+    pub async fn get_public_ws_endpoint(&self) -> Result<(String, Duration), KucoinError> {
+        let data = self
+            .request(Method::POST, "/api/v1/bullet-public", None, false)
+            .await?;
+        parse_bullet(&data)
+            .ok_or_else(|| KucoinError::ParseError("Invalid bullet response".to_string()))
+    }
+}
+
+/// Parse `[[price, size], ...]` levels
+fn parse_levels(levels: Option<&Value>) -> Vec<OrderBookLevel> {
+    levels
+        .and_then(|v| v.as_array())
+        .map(|levels| {
+            levels
+                .iter()
+                .filter_map(|level| {
+                    Some(OrderBookLevel::new(
+                        Price::new(parse_decimal(level.get(0)?)?),
+                        Size::new(parse_decimal(level.get(1)?)?),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse an order book from a REST response or a level2Depth push
+pub fn parse_order_book(symbol: &str, book: &Value) -> OrderBookSnapshot {
+    OrderBookSnapshot::new(
+        symbol,
+        "kucoin",
+        parse_levels(book.get("bids")),
+        parse_levels(book.get("asks")),
+        book.get("time")
+            .or_else(|| book.get("timestamp"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or_else(now_ms),
+    )
+}
+
+/// Parse an order
+/// KuCoin has no status field: an order is open while `isActive`, and otherwise filled
+/// unless it was cancelled.
+pub fn parse_order(order: &Value) -> Option<ExecutionReport> {
+    let decimal_field = |field: &str| order.get(field).and_then(parse_decimal);
+    let filled = decimal_field("dealSize").unwrap_or_default();
+    let size = decimal_field("size").unwrap_or(filled);
+    let flag = |field: &str| order.get(field).and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let status = if flag("isActive") {
+        if filled > Decimal::ZERO {
+            OrderStatus::PartiallyFilled
+        } else {
+            OrderStatus::New
+        }
+    } else if flag("cancelExist") {
+        OrderStatus::Cancelled
+    } else {
+        OrderStatus::Filled
+    };
+    let average_price = decimal_field("dealFunds")
+        .filter(|_| filled > Decimal::ZERO)
+        .map(|funds| Price::new(funds / filled));
+
+    Some(ExecutionReport {
+        order_id: order.get("id")?.as_str()?.to_string(),
+        client_order_id: order
+            .get("clientOid")
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string()),
+        symbol: Symbol::new(order.get("symbol")?.as_str()?),
+        exchange_id: "kucoin".to_string(),
+        status,
+        filled_size: Size::new(filled),
+        remaining_size: Size::new((size - filled).max(Decimal::ZERO)),
+        average_price,
+        timestamp: order
+            .get("createdAt")
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default(),
+        reject_reason: None,
+    })
+}
+
+/// Parse balances from a list of accounts
+pub fn parse_balances(accounts: &Value) -> Vec<Balance> {
+    accounts
+        .as_array()
+        .map(|accounts| {
+            accounts
+                .iter()
+                .filter_map(|account| {
+                    Some(Balance {
+                        asset: account.get("currency")?.as_str()?.to_string(),
+                        exchange_id: "kucoin".to_string(),
+                        total: parse_decimal(account.get("balance")?)?,
+                        free: parse_decimal(account.get("available")?)?,
+                        used: parse_decimal(account.get("holds")?)?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse spot trading rules from a symbols response
+pub fn parse_symbols(symbols: &Value) -> Vec<InstrumentSpec> {
+    symbols
+        .as_array()
+        .map(|symbols| {
+            symbols
+                .iter()
+                .filter_map(|info| {
+                    Some(InstrumentSpec::new(
+                        info.get("symbol")?.as_str()?,
+                        Price::new(parse_decimal(info.get("priceIncrement")?)?),
+                        Size::new(parse_decimal(info.get("baseIncrement")?)?),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse a bullet response into the connect URL and ping interval
+pub fn parse_bullet(data: &Value) -> Option<(String, Duration)> {
+    let token = data.get("token")?.as_str()?;
+    let server = data.get("instanceServers")?.get(0)?;
+    let endpoint = server.get("endpoint")?.as_str()?;
+    let ping_interval = server
+        .get("pingInterval")
+        .and_then(|v| v.as_u64())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_PING_INTERVAL);
+    Some((
+        format!(
+            "{}?token={}&connectId={}",
+            endpoint,
+            token,
+            uuid::Uuid::new_v4()
+        ),
+        ping_interval,
+    ))
+}
+
+/// Parse a WebSocket message
+/// level2Depth pushes are full top-of-book snapshots; welcome, ack and pong messages carry
+/// no event.
+pub fn parse_ws_message(text: &str) -> Option<Result<MarketEvent, KucoinError>> {
+    let json: Value = match serde_json::from_str(text) {
+        Ok(json) => json,
+        Err(e) => return Some(Err(KucoinError::ParseError(e.to_string()))),
+    };
+
+    match json.get("type").and_then(|v| v.as_str())? {
+        "message" => {}
+        "error" => {
+            let message = json.get("data").map(|v| v.to_string()).unwrap_or_default();
+            return Some(Err(KucoinError::ApiError(format!(
+                "WebSocket request failed: {}",
+                message
+            ))));
+        }
+        _ => return None,
+    }
+
+    let topic = json.get("topic").and_then(|v| v.as_str())?;
+    if !topic.starts_with("/spotMarket/level2Depth") {
+        return None;
+    }
+    let symbol = topic.split(':').nth(1)?;
+    let data = json.get("data")?;
+    Some(Ok(MarketEvent::OrderBookSnapshot(parse_order_book(
+        symbol, data,
+    ))))
+}
+
+/// KuCoin WebSocket stream for the public level2Depth50 channel
+pub struct KucoinWebSocket {
+    /// WebSocket connection
+    ws_stream: Option<
+        tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >,
+    >,
+    /// Ping interval announced by the server
+    ping_interval: Duration,
+    /// Subscribed symbols
+    subscriptions: Vec<String>,
+    /// Connection status
+    connected: Arc<RwLock<bool>>,
+    /// Per-symbol message counts and last update times
+    symbol_stats: SymbolStatsRegistry,
+}
+
+impl KucoinWebSocket {
+    /// Create a new KuCoin WebSocket stream
+    pub fn new() -> Self {
+        Self {
+            ws_stream: None,
+            ping_interval: DEFAULT_PING_INTERVAL,
+            subscriptions: Vec::new(),
+            connected: Arc::new(RwLock::new(false)),
+            symbol_stats: SymbolStatsRegistry::new(),
+        }
+    }
+
+    /// Record stats into a shared registry (builder pattern)
+    pub fn with_symbol_stats(mut self, symbol_stats: SymbolStatsRegistry) -> Self {
+        self.symbol_stats = symbol_stats;
+        self
+    }
+
+    /// Get the per-symbol stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Send a JSON request
+    async fn send(&mut self, request: Value) -> Result<(), KucoinError> {
+        let Some(ws) = &mut self.ws_stream else {
+            return Err(KucoinError::ConnectionError("Not connected".to_string()));
+        };
+        ws.send(Message::Text(request.to_string()))
+            .await
+            .map_err(|e| KucoinError::ConnectionError(e.to_string()))
+    }
+
+    /// Send a subscribe or unsubscribe request for symbols
+    async fn send_topic(&mut self, kind: &str, symbols: &[&str]) -> Result<(), KucoinError> {
+        if symbols.is_empty() {
+            return Ok(());
+        }
+        self.send(json!({
+            "id": now_ms().to_string(),
+            "type": kind,
+            "topic": format!("/spotMarket/level2Depth50:{}", symbols.join(",")),
+            "response": true,
+        }))
+        .await
+    }
+
+    /// Connect to a URL from a bullet response and subscribe to symbols
+    pub async fn connect(
+        &mut self,
+        url: &str,
+        ping_interval: Duration,
+        symbols: &[&str],
+    ) -> Result<(), KucoinError> {
+        log::info!("Connecting to KuCoin WebSocket");
+
+        let (ws_stream, _) = connect_async(url)
+            .await
+            .map_err(|e| KucoinError::ConnectionError(e.to_string()))?;
+        self.ws_stream = Some(ws_stream);
+        self.ping_interval = ping_interval;
+        *self.connected.write().await = true;
+
+        for symbol in symbols {
+            if !self.subscriptions.iter().any(|s| s == symbol) {
+                self.subscriptions.push(symbol.to_string());
+            }
+        }
+        let subscriptions = self.subscriptions.clone();
+        let symbol_refs: Vec<&str> = subscriptions.iter().map(|s| s.as_str()).collect();
+        self.send_topic("subscribe", &symbol_refs).await
+    }
+
+    /// Disconnect from the WebSocket stream
+    pub async fn disconnect(&mut self) -> Result<(), KucoinError> {
+        if let Some(mut ws) = self.ws_stream.take() {
+            ws.close(None)
+                .await
+                .map_err(|e| KucoinError::ConnectionError(e.to_string()))?;
+        }
+        *self.connected.write().await = false;
+        Ok(())
+    }
+}
+
+impl Default for KucoinWebSocket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for KucoinWebSocket {
+    type Error = KucoinError;
+
+    /// Subscribe on an open connection
+    /// Connecting needs a token from the REST API, so the first subscription goes
+    /// through `KucoinAdapter`.
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        let new_symbols: Vec<&str> = symbols
+            .iter()
+            .copied()
+            .filter(|symbol| !self.subscriptions.iter().any(|s| s == symbol))
+            .collect();
+        if self.ws_stream.is_some() {
+            self.send_topic("subscribe", &new_symbols).await?;
+        }
+        self.subscriptions
+            .extend(new_symbols.iter().map(|s| s.to_string()));
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.subscriptions
+            .retain(|s| !symbols.contains(&s.as_str()));
+        if self.ws_stream.is_some() {
+            self.send_topic("unsubscribe", symbols).await?;
+        }
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        loop {
+            let ws = self.ws_stream.as_mut()?;
+            let message = match tokio::time::timeout(self.ping_interval, ws.next()).await {
+                Ok(message) => message,
+                Err(_) => {
+                    let ping = json!({ "id": now_ms().to_string(), "type": "ping" });
+                    if let Err(e) = self.send(ping).await {
+                        return Some(Err(e));
+                    }
+                    continue;
+                }
+            };
+            match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Some(event) = parse_ws_message(&text) {
+                        if let Ok(event) = &event {
+                            self.symbol_stats.record_event(event);
+                        }
+                        return Some(event);
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => {
+                    self.ws_stream = None;
+                    *self.connected.write().await = false;
+                    return None;
+                }
+                Some(Err(e)) => return Some(Err(KucoinError::ConnectionError(e.to_string()))),
+                Some(Ok(_)) => continue,
+            }
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.try_read().map(|c| *c).unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+/// KuCoin error types
+#[derive(Debug, Clone)]
+pub enum KucoinError {
+    NetworkError(String),
+    ConnectionError(String),
+    ApiError(String),
+    ParseError(String),
+    AuthenticationError(String),
+    RateLimitError(String),
+}
+
+impl std::fmt::Display for KucoinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KucoinError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            KucoinError::ConnectionError(msg) => write!(f, "Connection error: {}", msg),
+            KucoinError::ApiError(msg) => write!(f, "API error: {}", msg),
+            KucoinError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            KucoinError::AuthenticationError(msg) => write!(f, "Authentication error: {}", msg),
+            KucoinError::RateLimitError(msg) => write!(f, "Rate limit error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for KucoinError {}
+
+/// KuCoin adapter that implements MarketDataStream, ExecutionClient and ExchangeAdapter
+///
+/// Symbols use KuCoin's dashed names (e.g. "BTC-USDT").
+pub struct KucoinAdapter {
+    /// KuCoin client for REST API
+    client: Arc<KucoinClient>,
+    /// KuCoin WebSocket for market data
+    websocket: Arc<Mutex<KucoinWebSocket>>,
+    /// Per-symbol stats shared with the WebSocket
+    symbol_stats: SymbolStatsRegistry,
+}
+
+impl KucoinAdapter {
+    /// Create a new KuCoin adapter
+    pub fn new(api_key: String, api_secret: String, passphrase: String) -> Self {
+        let symbol_stats = SymbolStatsRegistry::new();
+        Self {
+            client: Arc::new(KucoinClient::new(api_key, api_secret, passphrase)),
+            websocket: Arc::new(Mutex::new(
+                KucoinWebSocket::new().with_symbol_stats(symbol_stats.clone()),
+            )),
+            symbol_stats,
+        }
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        let client = Arc::try_unwrap(self.client)
+            .unwrap_or_else(|_| unreachable!("client is not shared before the adapter is built"));
+        self.client = Arc::new(client.with_rest_url(rest_url));
+        self
+    }
+
+    /// Get the per-symbol market data stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Set the trading rules used to format orders for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        self.client.set_instrument_spec(spec).await;
+    }
+
+    /// Load trading rules for all spot symbols from the exchange
+    pub async fn load_instrument_specs(&self) -> Result<usize, KucoinError> {
+        self.client.load_instrument_specs().await
+    }
+}
+
+/// Subscribe, connecting with a fresh bullet token if the WebSocket is closed
+async fn subscribe_with_token(
+    client: &KucoinClient,
+    websocket: &Mutex<KucoinWebSocket>,
+    symbols: &[&str],
+) -> Result<(), KucoinError> {
+    let mut ws = websocket.lock().await;
+    if ws.ws_stream.is_some() {
+        return ws.subscribe(symbols).await;
+    }
+    let (url, ping_interval) = client.get_public_ws_endpoint().await?;
+    ws.connect(&url, ping_interval, symbols).await
+}
+
+#[async_trait]
+impl ServerClock for KucoinAdapter {
+    type Error = KucoinError;
+
+    async fn server_time(&self) -> Result<u64, Self::Error> {
+        self.client.get_server_time().await
+    }
+}
+
+#[async_trait]
+impl ExecutionClient for KucoinAdapter {
+    type Error = KucoinError;
+
+    async fn place_order(&self, order: NewOrder) -> Result<OrderId, Self::Error> {
+        self.client.place_order(&order).await
+    }
+
+    async fn cancel_order(&self, order_id: OrderId) -> Result<(), Self::Error> {
+        self.client.cancel_order(&order_id).await
+    }
+
+    async fn get_order_status(&self, order_id: OrderId) -> Result<ExecutionReport, Self::Error> {
+        self.client.get_order(&order_id).await
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Self::Error> {
+        self.client.get_account_info().await
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.client.get_open_orders(symbol).await
+    }
+
+    async fn get_order_history(
+        &self,
+        symbol: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.client.get_order_history(symbol, limit).await
+    }
+
+    async fn get_trading_fees(&self, symbol: &str) -> Result<TradingFees, Self::Error> {
+        self.client.get_fee_rate(symbol).await
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for KucoinAdapter {
+    type Error = KucoinError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        subscribe_with_token(&self.client, &self.websocket, symbols).await
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.websocket.lock().await.unsubscribe(symbols).await
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        self.websocket.lock().await.next().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.websocket
+            .try_lock()
+            .map(|ws| ws.is_connected())
+            .unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+#[async_trait]
+impl crate::exchanges::connection_manager::ExchangeAdapter for KucoinAdapter {
+    async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // The WebSocket connects on the first subscription
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ws = self.websocket.lock().await;
+        ws.disconnect()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_market_data_stream(
+        &self,
+    ) -> Result<
+        Arc<
+            tokio::sync::Mutex<
+                dyn MarketDataStream<Error = crate::exchanges::error::BoxedError> + Send + Sync,
+            >,
+        >,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        Ok(Arc::new(tokio::sync::Mutex::new(KucoinWebSocketAdapter {
+            client: self.client.clone(),
+            websocket: self.websocket.clone(),
+            symbol_stats: self.symbol_stats.clone(),
+        })))
+    }
+
+    async fn place_order(
+        &self,
+        order: NewOrder,
+    ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::place_order(self, order)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn cancel_order(
+        &self,
+        order_id: OrderId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::cancel_order(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_order_status(
+        &self,
+        order_id: OrderId,
+    ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::get_order_status(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_account_info()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_open_orders(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        _limit: u32,
+    ) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_order_book(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_trading_fees(
+        &self,
+        symbol: &str,
+    ) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_fee_rate(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+}
+
+/// Wrapper for KucoinWebSocket to implement the type-erased MarketDataStream
+pub struct KucoinWebSocketAdapter {
+    client: Arc<KucoinClient>,
+    websocket: Arc<Mutex<KucoinWebSocket>>,
+    symbol_stats: SymbolStatsRegistry,
+}
+
+#[async_trait]
+impl MarketDataStream for KucoinWebSocketAdapter {
+    type Error = crate::exchanges::error::BoxedError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        subscribe_with_token(&self.client, &self.websocket, symbols)
+            .await
+            .map_err(crate::exchanges::error::BoxedError::new)
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        let mut ws = self.websocket.lock().await;
+        ws.unsubscribe(symbols)
+            .await
+            .map_err(crate::exchanges::error::BoxedError::new)
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        let mut ws = self.websocket.lock().await;
+        ws.next()
+            .await
+            .map(|r| r.map_err(crate::exchanges::error::BoxedError::new))
+    }
+
+    fn is_connected(&self) -> bool {
+        self.websocket
+            .try_lock()
+            .map(|ws| ws.is_connected())
+            .unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ws_and_rest_messages() {
+        let push = r#"{"type":"message","topic":"/spotMarket/level2Depth50:KCS-USDT",
+            "subject":"level2","data":{"asks":[["9.1","12.5"],["9.2","3"]],
+            "bids":[["9.0","7.25"]],"timestamp":1736410838180}}"#;
+        match parse_ws_message(push) {
+            Some(Ok(MarketEvent::OrderBookSnapshot(snapshot))) => {
+                assert_eq!(snapshot.symbol.value(), "KCS-USDT");
+                assert_eq!(snapshot.exchange_id, "kucoin");
+                assert_eq!(snapshot.asks.len(), 2);
+                assert_eq!(snapshot.bids[0].size, Size::from_str("7.25").unwrap());
+                assert_eq!(snapshot.timestamp, 1736410838180);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_ws_message(r#"{"id":"1","type":"welcome"}"#).is_none());
+        assert!(parse_ws_message(r#"{"id":"2","type":"ack"}"#).is_none());
+        assert!(matches!(
+            parse_ws_message(r#"{"id":"3","type":"error","code":404,"data":"topic not found"}"#),
+            Some(Err(KucoinError::ApiError(_)))
+        ));
+
+        let (url, ping_interval) = parse_bullet(&json!({
+            "token": "abc",
+            "instanceServers": [{"endpoint": "wss://ws-api-spot.kucoin.com/", "pingInterval": 18000}]
+        }))
+        .unwrap();
+        assert!(url.starts_with("wss://ws-api-spot.kucoin.com/?token=abc&connectId="));
+        assert_eq!(ping_interval, Duration::from_secs(18));
+
+        let cancelled = json!({
+            "id": "5c35c02703aa673ceec2a168", "symbol": "KCS-USDT", "clientOid": "my-1",
+            "size": "10", "dealSize": "4", "dealFunds": "36.4",
+            "isActive": false, "cancelExist": true, "createdAt": 1547026471000u64
+        });
+        let report = parse_order(&cancelled).unwrap();
+        assert_eq!(report.status, OrderStatus::Cancelled);
+        assert_eq!(report.remaining_size, Size::from_str("6").unwrap());
+        assert_eq!(report.average_price, Some(Price::from_str("9.1").unwrap()));
+        assert_eq!(report.client_order_id.as_deref(), Some("my-1"));
+
+        let balances = parse_balances(&json!([
+            {"currency": "USDT", "type": "trade", "balance": "100", "available": "75", "holds": "25"}
+        ]));
+        assert_eq!(balances[0].used, Decimal::from(25));
+    }
+
+    #[tokio::test]
+    async fn test_signed_order_request() {
+        use wiremock::matchers::{body_partial_json, header, header_exists, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/orders"))
+            .and(header("KC-API-KEY", "key"))
+            .and(header("KC-API-KEY-VERSION", "2"))
+            .and(header(
+                "KC-API-PASSPHRASE",
+                hmac_base64("secret", "phrase").as_str(),
+            ))
+            .and(header_exists("KC-API-SIGN"))
+            .and(body_partial_json(json!({
+                "symbol": "KCS-USDT", "side": "sell", "type": "limit",
+                "price": "9.5", "size": "10", "timeInForce": "FOK"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "code": "200000", "data": {"orderId": "5bd6e9286d99522a52e458de"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/accounts"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+                "code": "400005", "msg": "Invalid KC-API-SIGN"
+            })))
+            .mount(&server)
+            .await;
+
+        let adapter = KucoinAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            "phrase".to_string(),
+        )
+        .with_rest_url(server.uri());
+        let order = NewOrder::new_limit_sell(
+            "KCS-USDT",
+            Size::from_str("10").unwrap(),
+            Price::from_str("9.5").unwrap(),
+            TimeInForce::FillOrKill,
+        );
+        let order_id = ExecutionClient::place_order(&adapter, order).await.unwrap();
+        assert_eq!(order_id, "5bd6e9286d99522a52e458de");
+        assert!(matches!(
+            ExecutionClient::get_balances(&adapter).await,
+            Err(KucoinError::AuthenticationError(_))
+        ));
+    }
+}
@@ -0,0 +1,1192 @@
+use crate::core::events::{OrderBookLevel, OrderBookSnapshot};
+use crate::monitoring::SymbolStatsRegistry;
+use crate::oms::OrderSymbolRegistry;
+use crate::traits::{
+    Balance, ExecutionClient, ExecutionReport, MarketDataStream, MarketEvent, NewOrder, OrderId,
+    OrderSide, OrderStatus, OrderType, ServerClock, TimeInForce, TradingFees,
+};
+use crate::types::instrument::{format_price_plain, format_quote_plain, format_size_plain};
+use crate::types::{InstrumentSpec, Notional, Price, Size, Symbol};
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, RwLock};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+/// Levels per side of the WebSocket limit-depth channel (5, 10 or 20)
+const WS_BOOK_DEPTH: usize = 20;
+/// Idle time after which the WebSocket is pinged to keep it open
+const WS_PING_INTERVAL: Duration = Duration::from_secs(20);
+/// Window in which a signed request is valid
+const RECV_WINDOW_MS: u64 = 5000;
+/// Error codes for a bad key, signature or timestamp
+const AUTH_ERROR_CODES: [i64; 4] = [10072, 700001, 700002, 700003];
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Parse a decimal sent as a JSON string or number
+fn parse_decimal(value: &Value) -> Option<Decimal> {
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .ok()
+}
+
+/// MEXC spot REST client for market data and order execution
+pub struct MexcClient {
+    /// API key
+    api_key: String,
+    /// API secret
+    api_secret: String,
+    /// Base URL for REST API
+    rest_url: String,
+    /// HTTP client
+    http_client: Client,
+    /// Instrument trading rules used to format order parameters
+    instruments: Arc<RwLock<HashMap<String, InstrumentSpec>>>,
+    /// Symbol of each order placed, as cancels and queries need it
+    order_symbols: OrderSymbolRegistry,
+}
+
+impl MexcClient {
+    /// Create a new MEXC client
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            rest_url: "https://api.mexc.com".to_string(),
+            http_client: Client::new(),
+            instruments: Arc::new(RwLock::new(HashMap::new())),
+            order_symbols: OrderSymbolRegistry::new(),
+        }
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self
+    }
+
+    /// Set the trading rules for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        let mut instruments = self.instruments.write().await;
+        instruments.insert(spec.symbol.value().to_string(), spec);
+    }
+
+    /// Get the trading rules for an instrument
+    pub async fn instrument_spec(&self, symbol: &str) -> Option<InstrumentSpec> {
+        self.instruments.read().await.get(symbol).cloned()
+    }
+
+    /// Load trading rules for all spot symbols
+    pub async fn load_instrument_specs(&self) -> Result<usize, MexcError> {
+        let exchange_info = self.public("/api/v3/exchangeInfo", "").await?;
+        let specs = parse_exchange_info(&exchange_info);
+        let count = specs.len();
+        let mut instruments = self.instruments.write().await;
+        for spec in specs {
+            instruments.insert(spec.symbol.value().to_string(), spec);
+        }
+        Ok(count)
+    }
+
+    /// Format the price and quantity of an order for its instrument
+    async fn format_order_params(&self, order: &NewOrder) -> (Option<String>, String) {
+        let instruments = self.instruments.read().await;
+        match instruments.get(order.symbol.as_str()) {
+            Some(spec) => (
+                order.price.map(|price| spec.format_price(price)),
+                spec.format_size(order.size),
+            ),
+            None => (
+                order.price.map(format_price_plain),
+                format_size_plain(order.size),
+            ),
+        }
+    }
+
+    /// Format a quote-asset order amount using the instrument's price precision
+    async fn format_quote_param(&self, symbol: &str, quote_size: Notional) -> String {
+        match self.instruments.read().await.get(symbol) {
+            Some(spec) => spec.format_quote_size(quote_size),
+            None => format_quote_plain(quote_size),
+        }
+    }
+
+    /// Sign a query string
+    /// The signature is the hex HMAC-SHA256 of the query string, keyed by the API secret
+    pub fn sign(&self, query_string: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(query_string.as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Send a public GET request
+    async fn public(&self, path: &str, query: &str) -> Result<Value, MexcError> {
+        let url = if query.is_empty() {
+            format!("{}{}", self.rest_url, path)
+        } else {
+            format!("{}{}?{}", self.rest_url, path, query)
+        };
+        let response = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| MexcError::NetworkError(e.to_string()))?;
+        Self::unwrap_response(path, response).await
+    }
+
+    /// Send a signed request with all parameters in the query string
+    async fn signed(
+        &self,
+        method: Method,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<Value, MexcError> {
+        let mut query = params.to_vec();
+        query.push(("recvWindow", RECV_WINDOW_MS.to_string()));
+        query.push(("timestamp", now_ms().to_string()));
+        let query_string = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!(
+            "{}{}?{}&signature={}",
+            self.rest_url,
+            path,
+            query_string,
+            self.sign(&query_string)
+        );
+
+        let response = self
+            .http_client
+            .request(method, &url)
+            .header("X-MEXC-APIKEY", &self.api_key)
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+            .map_err(|e| MexcError::NetworkError(e.to_string()))?;
+        Self::unwrap_response(path, response).await
+    }
+
+    /// Map an error response to an error, or return the JSON body
+    async fn unwrap_response(path: &str, response: reqwest::Response) -> Result<Value, MexcError> {
+        let status = response.status();
+        if status.as_u16() == 429 {
+            return Err(MexcError::RateLimitError(format!(
+                "Request to {} failed: {}",
+                path, status
+            )));
+        }
+        let text = response
+            .text()
+            .await
+            .map_err(|e| MexcError::NetworkError(e.to_string()))?;
+        if !status.is_success() {
+            let body: Value = serde_json::from_str(&text).unwrap_or_default();
+            let message = format!("Request to {} failed: {} - {}", path, status, text);
+            let code = body.get("code").and_then(|v| v.as_i64());
+            return Err(match code {
+                Some(code) if AUTH_ERROR_CODES.contains(&code) => {
+                    MexcError::AuthenticationError(message)
+                }
+                _ => MexcError::ApiError(message),
+            });
+        }
+        serde_json::from_str(&text).map_err(|e| MexcError::ParseError(e.to_string()))
+    }
+
+    /// Get current server time in milliseconds
+    pub async fn get_server_time(&self) -> Result<u64, MexcError> {
+        self.public("/api/v3/time", "")
+            .await?
+            .get("serverTime")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| MexcError::ParseError("Invalid server time".to_string()))
+    }
+
+    /// Get an order book snapshot
+    pub async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: u32,
+    ) -> Result<OrderBookSnapshot, MexcError> {
+        let query = format!("symbol={}&limit={}", symbol, limit);
+        let book = self.public("/api/v3/depth", &query).await?;
+        Ok(parse_order_book(symbol, &book))
+    }
+
+    /// Place a new spot order
+    /// Quote-sized market orders are sent as `quoteOrderQty`; other quote-sized orders are
+    /// converted to a base size at their limit price.
+    pub async fn place_order(&self, order: &NewOrder) -> Result<OrderId, MexcError> {
+        let is_market = order.order_type == OrderType::Market;
+        let converted;
+        let order = match (order.quote_size, order.price) {
+            (Some(_), Some(price)) if !is_market => {
+                let step_size = self
+                    .instrument_spec(order.symbol.as_str())
+                    .await
+                    .map(|spec| spec.step_size);
+                converted = order.with_base_size_at(price, step_size).ok_or_else(|| {
+                    MexcError::ApiError(format!("Cannot size order at price {}", price))
+                })?;
+                &converted
+            }
+            (Some(_), None) if !is_market => {
+                return Err(MexcError::ApiError(
+                    "Quote-sized order needs a price".to_string(),
+                ))
+            }
+            _ => order,
+        };
+        let (price_param, quantity_param) = self.format_order_params(order).await;
+
+        let mut params = vec![
+            ("symbol", order.symbol.as_str().to_string()),
+            (
+                "side",
+                match order.side {
+                    OrderSide::Buy => "BUY",
+                    OrderSide::Sell => "SELL",
+                }
+                .to_string(),
+            ),
+        ];
+        match order.quote_size {
+            Some(quote_size) => params.push((
+                "quoteOrderQty",
+                self.format_quote_param(order.symbol.as_str(), quote_size)
+                    .await,
+            )),
+            None => params.push(("quantity", quantity_param)),
+        }
+
+        if is_market {
+            params.push(("type", "MARKET".to_string()));
+        } else {
+            let price = price_param
+                .ok_or_else(|| MexcError::ApiError("Limit order needs a price".to_string()))?;
+            // MEXC expresses time in force through the order type
+            let order_type = match order.time_in_force {
+                TimeInForce::GoodTillCancelled => "LIMIT",
+                TimeInForce::ImmediateOrCancel => "IMMEDIATE_OR_CANCEL",
+                TimeInForce::FillOrKill => "FILL_OR_KILL",
+            };
+            params.push(("type", order_type.to_string()));
+            params.push(("price", price));
+        }
+
+        if let Some(client_order_id) = &order.client_order_id {
+            params.push(("newClientOrderId", client_order_id.clone()));
+        }
+
+        let response = self.signed(Method::POST, "/api/v3/order", &params).await?;
+        let order_id = response
+            .get("orderId")
+            .and_then(|id| match id {
+                Value::String(id) => Some(id.clone()),
+                Value::Number(id) => Some(id.to_string()),
+                _ => None,
+            })
+            .ok_or_else(|| MexcError::ParseError("Invalid order ID in response".to_string()))?;
+
+        self.order_symbols
+            .record(order_id.clone(), order.symbol.as_str());
+        Ok(order_id)
+    }
+
+    /// Symbol of an order placed through this client
+    async fn order_symbol(&self, order_id: &OrderId) -> Result<String, MexcError> {
+        self.order_symbols
+            .symbol(order_id)
+            .ok_or_else(|| MexcError::ApiError(format!("Unknown symbol for order {}", order_id)))
+    }
+
+    /// Cancel an order
+    pub async fn cancel_order(&self, symbol: &str, order_id: &OrderId) -> Result<(), MexcError> {
+        self.signed(
+            Method::DELETE,
+            "/api/v3/order",
+            &[
+                ("symbol", symbol.to_string()),
+                ("orderId", order_id.clone()),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get an order, whether open or closed
+    pub async fn get_order(
+        &self,
+        symbol: &str,
+        order_id: &OrderId,
+    ) -> Result<ExecutionReport, MexcError> {
+        let order = self
+            .signed(
+                Method::GET,
+                "/api/v3/order",
+                &[
+                    ("symbol", symbol.to_string()),
+                    ("orderId", order_id.clone()),
+                ],
+            )
+            .await?;
+        parse_order(&order).ok_or_else(|| MexcError::ParseError("Invalid order".to_string()))
+    }
+
+    /// Get open orders
+    /// MEXC only lists open orders per symbol; without one, every symbol this client
+    /// has placed orders on is queried.
+    pub async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, MexcError> {
+        let symbols: Vec<String> = match symbol {
+            Some(symbol) => vec![symbol.to_string()],
+            None => self.order_symbols.symbols(),
+        };
+
+        let mut reports = Vec::new();
+        for symbol in symbols {
+            let orders = self
+                .signed(Method::GET, "/api/v3/openOrders", &[("symbol", symbol)])
+                .await?;
+            reports.extend(parse_orders(&orders));
+        }
+        Ok(reports)
+    }
+
+    /// Get recent orders of a symbol, newest first
+    pub async fn get_order_history(
+        &self,
+        symbol: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, MexcError> {
+        let limit = limit.unwrap_or(50).min(1000);
+        let orders = self
+            .signed(
+                Method::GET,
+                "/api/v3/allOrders",
+                &[("symbol", symbol.to_string()), ("limit", limit.to_string())],
+            )
+            .await?;
+        let mut reports = parse_orders(&orders);
+        reports.sort_by_key(|report| std::cmp::Reverse(report.timestamp));
+        reports.truncate(limit);
+        Ok(reports)
+    }
+
+    /// Get account balances with the amounts held by open orders
+    pub async fn get_account_info(&self) -> Result<Vec<Balance>, MexcError> {
+        let account = self.signed(Method::GET, "/api/v3/account", &[]).await?;
+        Ok(parse_balances(&account))
+    }
+
+    /// Get the account's fee rates for a symbol
+    pub async fn get_fee_rate(&self, symbol: &str) -> Result<TradingFees, MexcError> {
+        let response = self
+            .signed(
+                Method::GET,
+                "/api/v3/tradeFee",
+                &[("symbol", symbol.to_string())],
+            )
+            .await?;
+        let fee = |field: &str| {
+            response
+                .get("data")
+                .and_then(|data| data.get(field))
+                .and_then(parse_decimal)
+                .map(Size::new)
+                .ok_or_else(|| MexcError::ParseError(format!("Invalid {}", field)))
+        };
+        Ok(TradingFees::new(
+            symbol.to_string(),
+            fee("makerCommission")?,
+            fee("takerCommission")?,
+        ))
+    }
+}
+
+/// Parse `[[price, quantity], ...]` levels
+fn parse_levels(levels: Option<&Value>) -> Vec<OrderBookLevel> {
+    levels
+        .and_then(|v| v.as_array())
+        .map(|levels| {
+            levels
+                .iter()
+                .filter_map(|level| {
+                    // REST levels are arrays, WebSocket levels are {"p": .., "v": ..}
+                    let (price, size) = match level {
+                        Value::Array(_) => (level.get(0)?, level.get(1)?),
+                        _ => (level.get("p")?, level.get("v")?),
+                    };
+                    Some(OrderBookLevel::new(
+                        Price::new(parse_decimal(price)?),
+                        Size::new(parse_decimal(size)?),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse a REST depth response
+pub fn parse_order_book(symbol: &str, book: &Value) -> OrderBookSnapshot {
+    OrderBookSnapshot::new(
+        symbol,
+        "mexc",
+        parse_levels(book.get("bids")),
+        parse_levels(book.get("asks")),
+        book.get("timestamp")
+            .and_then(|v| v.as_u64())
+            .unwrap_or_else(now_ms),
+    )
+}
+
+/// Parse a list of orders
+pub fn parse_orders(orders: &Value) -> Vec<ExecutionReport> {
+    orders
+        .as_array()
+        .map(|orders| orders.iter().filter_map(parse_order).collect())
+        .unwrap_or_default()
+}
+
+/// Parse an order
+pub fn parse_order(order: &Value) -> Option<ExecutionReport> {
+    let decimal_field = |field: &str| order.get(field).and_then(parse_decimal);
+    let quantity = decimal_field("origQty")?;
+    let filled = decimal_field("executedQty").unwrap_or_default();
+
+    let status = match order.get("status")?.as_str()? {
+        "NEW" => OrderStatus::New,
+        "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+        "FILLED" => OrderStatus::Filled,
+        "CANCELED" | "PARTIALLY_CANCELED" => OrderStatus::Cancelled,
+        _ => return None,
+    };
+    let average_price = decimal_field("cummulativeQuoteQty")
+        .filter(|_| filled > Decimal::ZERO)
+        .map(|quote| Price::new(quote / filled));
+    let order_id = match order.get("orderId")? {
+        Value::String(id) => id.clone(),
+        Value::Number(id) => id.to_string(),
+        _ => return None,
+    };
+
+    Some(ExecutionReport {
+        order_id,
+        client_order_id: order
+            .get("clientOrderId")
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string()),
+        symbol: Symbol::new(order.get("symbol")?.as_str()?),
+        exchange_id: "mexc".to_string(),
+        status,
+        filled_size: Size::new(filled),
+        remaining_size: Size::new(quantity - filled),
+        average_price,
+        timestamp: order
+            .get("updateTime")
+            .or_else(|| order.get("time"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default(),
+        reject_reason: None,
+    })
+}
+
+/// Parse the balances of an account response
+pub fn parse_balances(account: &Value) -> Vec<Balance> {
+    account
+        .get("balances")
+        .and_then(|v| v.as_array())
+        .map(|balances| {
+            balances
+                .iter()
+                .filter_map(|balance| {
+                    let free = parse_decimal(balance.get("free")?)?;
+                    let used = parse_decimal(balance.get("locked")?)?;
+                    Some(Balance {
+                        asset: balance.get("asset")?.as_str()?.to_string(),
+                        exchange_id: "mexc".to_string(),
+                        total: free + used,
+                        free,
+                        used,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse spot trading rules from an exchangeInfo response
+/// Precisions are decimal places of the price and base quantity
+pub fn parse_exchange_info(exchange_info: &Value) -> Vec<InstrumentSpec> {
+    exchange_info
+        .get("symbols")
+        .and_then(|v| v.as_array())
+        .map(|symbols| {
+            symbols
+                .iter()
+                .filter_map(|info| {
+                    let price_decimals = info.get("quotePrecision")?.as_u64()? as u32;
+                    let size_decimals = info.get("baseAssetPrecision")?.as_u64()? as u32;
+                    Some(InstrumentSpec::new(
+                        info.get("symbol")?.as_str()?,
+                        Price::new(Decimal::new(1, price_decimals)),
+                        Size::new(Decimal::new(1, size_decimals)),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Limit-depth channel of a symbol
+fn depth_channel(symbol: &str) -> String {
+    format!(
+        "spot@public.limit.depth.v3.api@{}@{}",
+        symbol, WS_BOOK_DEPTH
+    )
+}
+
+/// Parse a WebSocket message
+/// Limit-depth pushes are full top-of-book snapshots; acknowledgements and pongs carry
+/// no event.
+pub fn parse_ws_message(text: &str) -> Option<Result<MarketEvent, MexcError>> {
+    let json: Value = match serde_json::from_str(text) {
+        Ok(json) => json,
+        Err(e) => return Some(Err(MexcError::ParseError(e.to_string()))),
+    };
+
+    if let Some(code) = json.get("code").and_then(|v| v.as_i64()) {
+        if code == 0 {
+            return None;
+        }
+        let message = json.get("msg").and_then(|v| v.as_str()).unwrap_or_default();
+        return Some(Err(MexcError::ApiError(format!(
+            "WebSocket request failed: {}",
+            message
+        ))));
+    }
+
+    let channel = json.get("c").and_then(|v| v.as_str())?;
+    if !channel.starts_with("spot@public.limit.depth") {
+        return None;
+    }
+    let symbol = json.get("s").and_then(|v| v.as_str())?;
+    let data = json.get("d")?;
+    let timestamp = json
+        .get("t")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_else(now_ms);
+
+    Some(Ok(MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+        symbol,
+        "mexc",
+        parse_levels(data.get("bids")),
+        parse_levels(data.get("asks")),
+        timestamp,
+    ))))
+}
+
+/// MEXC WebSocket stream for the public limit-depth channel
+pub struct MexcWebSocket {
+    /// WebSocket URL
+    ws_url: String,
+    /// WebSocket connection
+    ws_stream: Option<
+        tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >,
+    >,
+    /// Subscribed symbols
+    subscriptions: Vec<String>,
+    /// Connection status
+    connected: Arc<RwLock<bool>>,
+    /// Per-symbol message counts and last update times
+    symbol_stats: SymbolStatsRegistry,
+}
+
+impl MexcWebSocket {
+    /// Create a new MEXC WebSocket stream
+    pub fn new() -> Self {
+        Self {
+            ws_url: "wss://wbs.mexc.com/ws".to_string(),
+            ws_stream: None,
+            subscriptions: Vec::new(),
+            connected: Arc::new(RwLock::new(false)),
+            symbol_stats: SymbolStatsRegistry::new(),
+        }
+    }
+
+    /// Record stats into a shared registry (builder pattern)
+    pub fn with_symbol_stats(mut self, symbol_stats: SymbolStatsRegistry) -> Self {
+        self.symbol_stats = symbol_stats;
+        self
+    }
+
+    /// Get the per-symbol stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Send a JSON request
+    async fn send(&mut self, request: Value) -> Result<(), MexcError> {
+        let Some(ws) = &mut self.ws_stream else {
+            return Err(MexcError::ConnectionError("Not connected".to_string()));
+        };
+        ws.send(Message::Text(request.to_string()))
+            .await
+            .map_err(|e| MexcError::ConnectionError(e.to_string()))
+    }
+
+    /// Send a subscription or unsubscription request for symbols
+    async fn send_method(&mut self, method: &str, symbols: &[&str]) -> Result<(), MexcError> {
+        if symbols.is_empty() {
+            return Ok(());
+        }
+        let params: Vec<String> = symbols.iter().map(|s| depth_channel(s)).collect();
+        self.send(json!({ "method": method, "params": params }))
+            .await
+    }
+
+    /// Connect to the WebSocket stream and subscribe to symbols
+    pub async fn connect(&mut self, symbols: &[&str]) -> Result<(), MexcError> {
+        log::info!("Connecting to MEXC WebSocket: {}", self.ws_url);
+
+        let (ws_stream, _) = connect_async(&self.ws_url)
+            .await
+            .map_err(|e| MexcError::ConnectionError(e.to_string()))?;
+        self.ws_stream = Some(ws_stream);
+        *self.connected.write().await = true;
+
+        for symbol in symbols {
+            if !self.subscriptions.iter().any(|s| s == symbol) {
+                self.subscriptions.push(symbol.to_string());
+            }
+        }
+        let subscriptions = self.subscriptions.clone();
+        let symbol_refs: Vec<&str> = subscriptions.iter().map(|s| s.as_str()).collect();
+        self.send_method("SUBSCRIPTION", &symbol_refs).await
+    }
+
+    /// Disconnect from the WebSocket stream
+    pub async fn disconnect(&mut self) -> Result<(), MexcError> {
+        if let Some(mut ws) = self.ws_stream.take() {
+            ws.close(None)
+                .await
+                .map_err(|e| MexcError::ConnectionError(e.to_string()))?;
+        }
+        *self.connected.write().await = false;
+        Ok(())
+    }
+}
+
+impl Default for MexcWebSocket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for MexcWebSocket {
+    type Error = MexcError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        if self.ws_stream.is_none() {
+            return self.connect(symbols).await;
+        }
+        let new_symbols: Vec<&str> = symbols
+            .iter()
+            .copied()
+            .filter(|symbol| !self.subscriptions.iter().any(|s| s == symbol))
+            .collect();
+        self.send_method("SUBSCRIPTION", &new_symbols).await?;
+        self.subscriptions
+            .extend(new_symbols.iter().map(|s| s.to_string()));
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.subscriptions
+            .retain(|s| !symbols.contains(&s.as_str()));
+        if self.ws_stream.is_some() {
+            self.send_method("UNSUBSCRIPTION", symbols).await?;
+        }
+        Ok(())
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        loop {
+            let ws = self.ws_stream.as_mut()?;
+            let message = match tokio::time::timeout(WS_PING_INTERVAL, ws.next()).await {
+                Ok(message) => message,
+                Err(_) => {
+                    if let Err(e) = self.send(json!({ "method": "PING" })).await {
+                        return Some(Err(e));
+                    }
+                    continue;
+                }
+            };
+            match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Some(event) = parse_ws_message(&text) {
+                        if let Ok(event) = &event {
+                            self.symbol_stats.record_event(event);
+                        }
+                        return Some(event);
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => {
+                    self.ws_stream = None;
+                    *self.connected.write().await = false;
+                    return None;
+                }
+                Some(Err(e)) => return Some(Err(MexcError::ConnectionError(e.to_string()))),
+                Some(Ok(_)) => continue,
+            }
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.try_read().map(|c| *c).unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+/// MEXC error types
+#[derive(Debug, Clone)]
+pub enum MexcError {
+    NetworkError(String),
+    ConnectionError(String),
+    ApiError(String),
+    ParseError(String),
+    AuthenticationError(String),
+    RateLimitError(String),
+}
+
+impl std::fmt::Display for MexcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MexcError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            MexcError::ConnectionError(msg) => write!(f, "Connection error: {}", msg),
+            MexcError::ApiError(msg) => write!(f, "API error: {}", msg),
+            MexcError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            MexcError::AuthenticationError(msg) => write!(f, "Authentication error: {}", msg),
+            MexcError::RateLimitError(msg) => write!(f, "Rate limit error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MexcError {}
+
+/// MEXC adapter that implements MarketDataStream, ExecutionClient and ExchangeAdapter
+///
+/// Symbols use MEXC's spot names (e.g. "BTCUSDT"). Orders are cancelled and queried by
+/// the symbol recorded when they were placed through this adapter.
+pub struct MexcAdapter {
+    /// MEXC client for REST API
+    client: MexcClient,
+    /// MEXC WebSocket for market data
+    websocket: Arc<Mutex<MexcWebSocket>>,
+    /// Per-symbol stats shared with the WebSocket
+    symbol_stats: SymbolStatsRegistry,
+}
+
+impl MexcAdapter {
+    /// Create a new MEXC adapter
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        let symbol_stats = SymbolStatsRegistry::new();
+        Self {
+            client: MexcClient::new(api_key, api_secret),
+            websocket: Arc::new(Mutex::new(
+                MexcWebSocket::new().with_symbol_stats(symbol_stats.clone()),
+            )),
+            symbol_stats,
+        }
+    }
+
+    /// Override the REST base URL (builder pattern)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.client = self.client.with_rest_url(rest_url);
+        self
+    }
+
+    /// Get the per-symbol market data stats
+    pub fn symbol_stats(&self) -> &SymbolStatsRegistry {
+        &self.symbol_stats
+    }
+
+    /// Set the trading rules used to format orders for an instrument
+    pub async fn set_instrument_spec(&self, spec: InstrumentSpec) {
+        self.client.set_instrument_spec(spec).await;
+    }
+
+    /// Load trading rules for all spot symbols from the exchange
+    pub async fn load_instrument_specs(&self) -> Result<usize, MexcError> {
+        self.client.load_instrument_specs().await
+    }
+}
+
+#[async_trait]
+impl ServerClock for MexcAdapter {
+    type Error = MexcError;
+
+    async fn server_time(&self) -> Result<u64, Self::Error> {
+        self.client.get_server_time().await
+    }
+}
+
+#[async_trait]
+impl ExecutionClient for MexcAdapter {
+    type Error = MexcError;
+
+    async fn place_order(&self, order: NewOrder) -> Result<OrderId, Self::Error> {
+        self.client.place_order(&order).await
+    }
+
+    async fn cancel_order(&self, order_id: OrderId) -> Result<(), Self::Error> {
+        let symbol = self.client.order_symbol(&order_id).await?;
+        self.client.cancel_order(&symbol, &order_id).await
+    }
+
+    async fn get_order_status(&self, order_id: OrderId) -> Result<ExecutionReport, Self::Error> {
+        let symbol = self.client.order_symbol(&order_id).await?;
+        self.client.get_order(&symbol, &order_id).await
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Self::Error> {
+        self.client.get_account_info().await
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        self.client.get_open_orders(symbol).await
+    }
+
+    async fn get_order_history(
+        &self,
+        symbol: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ExecutionReport>, Self::Error> {
+        let symbol = symbol
+            .ok_or_else(|| MexcError::ApiError("MEXC order history needs a symbol".to_string()))?;
+        self.client.get_order_history(symbol, limit).await
+    }
+
+    async fn get_trading_fees(&self, symbol: &str) -> Result<TradingFees, Self::Error> {
+        self.client.get_fee_rate(symbol).await
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for MexcAdapter {
+    type Error = MexcError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.websocket.lock().await.subscribe(symbols).await
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.websocket.lock().await.unsubscribe(symbols).await
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        self.websocket.lock().await.next().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.websocket
+            .try_lock()
+            .map(|ws| ws.is_connected())
+            .unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+#[async_trait]
+impl crate::exchanges::connection_manager::ExchangeAdapter for MexcAdapter {
+    async fn connect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // The WebSocket connects on the first subscription
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ws = self.websocket.lock().await;
+        ws.disconnect()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_market_data_stream(
+        &self,
+    ) -> Result<
+        Arc<
+            tokio::sync::Mutex<
+                dyn MarketDataStream<Error = crate::exchanges::error::BoxedError> + Send + Sync,
+            >,
+        >,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        Ok(Arc::new(tokio::sync::Mutex::new(MexcWebSocketAdapter {
+            websocket: self.websocket.clone(),
+            symbol_stats: self.symbol_stats.clone(),
+        })))
+    }
+
+    async fn place_order(
+        &self,
+        order: NewOrder,
+    ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::place_order(self, order)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn cancel_order(
+        &self,
+        order_id: OrderId,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::cancel_order(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_order_status(
+        &self,
+        order_id: OrderId,
+    ) -> Result<ExecutionReport, Box<dyn std::error::Error + Send + Sync>> {
+        ExecutionClient::get_order_status(self, order_id)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_account_info()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<ExecutionReport>, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_open_orders(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: u32,
+    ) -> Result<OrderBookSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_order_book(symbol, limit)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn get_trading_fees(
+        &self,
+        symbol: &str,
+    ) -> Result<TradingFees, Box<dyn std::error::Error + Send + Sync>> {
+        self.client
+            .get_fee_rate(symbol)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+}
+
+/// Wrapper for MexcWebSocket to implement the type-erased MarketDataStream
+pub struct MexcWebSocketAdapter {
+    websocket: Arc<Mutex<MexcWebSocket>>,
+    symbol_stats: SymbolStatsRegistry,
+}
+
+#[async_trait]
+impl MarketDataStream for MexcWebSocketAdapter {
+    type Error = crate::exchanges::error::BoxedError;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        let mut ws = self.websocket.lock().await;
+        ws.subscribe(symbols)
+            .await
+            .map_err(crate::exchanges::error::BoxedError::new)
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        let mut ws = self.websocket.lock().await;
+        ws.unsubscribe(symbols)
+            .await
+            .map_err(crate::exchanges::error::BoxedError::new)
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        let mut ws = self.websocket.lock().await;
+        ws.next()
+            .await
+            .map(|r| r.map_err(crate::exchanges::error::BoxedError::new))
+    }
+
+    fn is_connected(&self) -> bool {
+        self.websocket
+            .try_lock()
+            .map(|ws| ws.is_connected())
+            .unwrap_or(true)
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.symbol_stats.last_update(symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ws_and_rest_messages() {
+        let push = r#"{"c":"spot@public.limit.depth.v3.api@BTCUSDT@20",
+            "d":{"bids":[{"p":"93180.18","v":"0.21976424"}],
+                 "asks":[{"p":"93180.19","v":"3.38510191"},{"p":"93180.5","v":"0.1"}],
+                 "e":"spot@public.limit.depth.v3.api","r":"3407459756"},
+            "s":"BTCUSDT","t":1736410838180}"#;
+        match parse_ws_message(push) {
+            Some(Ok(MarketEvent::OrderBookSnapshot(snapshot))) => {
+                assert_eq!(snapshot.symbol.value(), "BTCUSDT");
+                assert_eq!(snapshot.exchange_id, "mexc");
+                assert_eq!(snapshot.asks.len(), 2);
+                assert_eq!(snapshot.bids[0].price, Price::from_str("93180.18").unwrap());
+                assert_eq!(snapshot.timestamp, 1736410838180);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        let ack = r#"{"id":0,"code":0,"msg":"spot@public.limit.depth.v3.api@BTCUSDT@20"}"#;
+        assert!(parse_ws_message(ack).is_none());
+        let blocked =
+            r#"{"id":0,"code":0,"msg":"Not Subscribed successfully! [spot@x]. Reason: Blocked!"}"#;
+        assert!(parse_ws_message(blocked).is_none());
+        assert!(matches!(
+            parse_ws_message(r#"{"id":0,"code":1,"msg":"invalid"}"#),
+            Some(Err(MexcError::ApiError(_)))
+        ));
+
+        let order = json!({
+            "symbol": "PEPEUSDT", "orderId": "C02__443776347957968896", "clientOrderId": "",
+            "price": "0.00001", "origQty": "1000000", "executedQty": "400000",
+            "cummulativeQuoteQty": "4", "status": "PARTIALLY_FILLED",
+            "time": 1736410838000u64, "updateTime": 1736410839000u64
+        });
+        let report = parse_order(&order).unwrap();
+        assert_eq!(report.status, OrderStatus::PartiallyFilled);
+        assert_eq!(report.remaining_size, Size::from_str("600000").unwrap());
+        assert_eq!(
+            report.average_price,
+            Some(Price::from_str("0.00001").unwrap())
+        );
+        assert_eq!(report.client_order_id, None);
+
+        let specs = parse_exchange_info(&json!({"symbols": [
+            {"symbol": "PEPEUSDT", "quotePrecision": 10, "baseAssetPrecision": 2}
+        ]}));
+        assert_eq!(specs[0].tick_size, Price::from_str("0.0000000001").unwrap());
+        assert_eq!(specs[0].step_size, Size::from_str("0.01").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_signed_orders_use_recorded_symbol() {
+        use wiremock::matchers::{header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .and(header("X-MEXC-APIKEY", "key"))
+            .and(query_param("type", "IMMEDIATE_OR_CANCEL"))
+            .and(query_param("quantity", "1000000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "symbol": "PEPEUSDT", "orderId": "C02__1", "orderListId": -1
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v3/order"))
+            .and(query_param("symbol", "PEPEUSDT"))
+            .and(query_param("orderId", "C02__1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "symbol": "PEPEUSDT", "orderId": "C02__1", "status": "CANCELED"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/account"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "code": 700002, "msg": "Signature for this request is not valid."
+            })))
+            .mount(&server)
+            .await;
+
+        let adapter =
+            MexcAdapter::new("key".to_string(), "secret".to_string()).with_rest_url(server.uri());
+        let order = NewOrder::new_limit_buy(
+            "PEPEUSDT",
+            Size::from_str("1000000").unwrap(),
+            Price::from_str("0.00001").unwrap(),
+            TimeInForce::ImmediateOrCancel,
+        );
+        let order_id = ExecutionClient::place_order(&adapter, order).await.unwrap();
+        assert_eq!(order_id, "C02__1");
+        ExecutionClient::cancel_order(&adapter, order_id)
+            .await
+            .unwrap();
+
+        assert!(
+            ExecutionClient::cancel_order(&adapter, "unknown".to_string())
+                .await
+                .is_err()
+        );
+        assert!(matches!(
+            ExecutionClient::get_balances(&adapter).await,
+            Err(MexcError::AuthenticationError(_))
+        ));
+    }
+}
@@ -1,23 +1,36 @@
 pub mod binance;
+pub mod bybit;
+pub mod kraken;
+pub mod kucoin;
+pub mod mexc;
 pub mod mock;
 // Temporarily disabled due to compilation errors - need to fix Error types
 // pub mod okx;
 // pub mod gate;
-// pub mod bybit;
 // pub mod hyperliquid;
 // pub mod dydx;
 // pub mod aster;
 pub mod connection_manager;
 pub mod error;
+pub mod session_safety;
+pub mod time_sync;
 
-pub use binance::{BinanceAdapter, BinanceWebSocketAdapter};
+pub use binance::{BinanceAdapter, BinanceStream, BinanceWebSocketAdapter, MarketType};
+pub use bybit::BybitAdapter;
+pub use kraken::KrakenAdapter;
+pub use kucoin::KucoinAdapter;
+pub use mexc::MexcAdapter;
 pub use mock::MockExchangeAdapter;
 // Temporarily disabled
 // pub use okx::OkxAdapter;
 // pub use gate::GateAdapter;
-// pub use bybit::BybitAdapter;
 // pub use hyperliquid::HyperliquidAdapter;
 // pub use dydx::DydxAdapter;
 // pub use aster::AsterAdapter;
-pub use connection_manager::{ConnectionManager, ConnectionStatus, ExchangeAdapter};
+pub use connection_manager::{
+    ConnectionManager, ConnectionStatus, EndpointHealth, EndpointPool, ExchangeAdapter,
+    LatencyConfig,
+};
 pub use error::{BoxedError, ExchangeError};
+pub use session_safety::SessionSafety;
+pub use time_sync::{ClockStatus, TimeOffset, TimeSync, TimeSyncConfig};
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+/// Exchange-side cancel-on-disconnect settings of an adapter
+///
+/// Every heartbeat re-arms a timer on the exchange that cancels all open orders when it
+/// runs out, so orders do not outlive a lost connection or a crashed process. Binance
+/// USD-M futures arm it per symbol with `countdownCancelAll`; Kraken arms one timer for
+/// the account with `CancelAllOrdersAfter`. Venues without such a timer rely on the
+/// `OrderExecutor` watchdog alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionSafety {
+    /// Time without a heartbeat after which the exchange cancels all open orders
+    pub cancel_after: Duration,
+    /// Symbols to arm the timer for, on venues that arm it per symbol
+    pub symbols: Vec<String>,
+}
+
+impl SessionSafety {
+    /// Cancel all open orders once heartbeats stop for a while
+    /// Heartbeats must be sent well within `cancel_after`, e.g. every quarter of it.
+    pub fn new(cancel_after: Duration) -> Self {
+        Self {
+            cancel_after,
+            symbols: Vec::new(),
+        }
+    }
+
+    /// Arm the timer for these symbols (builder pattern)
+    pub fn with_symbols<I, S>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.symbols = symbols.into_iter().map(Into::into).collect();
+        self
+    }
+}
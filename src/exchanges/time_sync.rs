@@ -0,0 +1,413 @@
+use crate::core::clock::{system_clock, SharedClock};
+use crate::core::events::Timestamp;
+use crate::monitoring::alerts::{AlertLevel, AlertManager};
+use crate::traits::ServerClock;
+use async_trait::async_trait;
+use log::{info, warn};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Offset of an exchange's clock from the local clock, in milliseconds
+///
+/// Cheap to read on every request. Clones share the same value, so the handle given to a
+/// client follows every sample `TimeSync` takes.
+#[derive(Debug, Clone, Default)]
+pub struct TimeOffset(Arc<AtomicI64>);
+
+impl TimeOffset {
+    /// Create an offset of zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the offset (server time minus local time)
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Set the offset (server time minus local time)
+    pub fn set(&self, offset_ms: i64) {
+        self.0.store(offset_ms, Ordering::Relaxed);
+    }
+
+    /// Convert a local timestamp to the exchange's clock
+    pub fn apply(&self, local_ms: Timestamp) -> Timestamp {
+        local_ms.saturating_add_signed(self.get())
+    }
+}
+
+/// Clock sampling and alert thresholds
+#[derive(Debug, Clone)]
+pub struct TimeSyncConfig {
+    /// Time between samples of each exchange
+    pub sample_interval: Duration,
+    /// Number of recent samples the offset and drift are estimated from
+    pub window: usize,
+    /// Offset above which an alert is raised, in milliseconds
+    pub max_offset_ms: u64,
+    /// Drift rate above which an alert is raised, in parts per million
+    pub max_drift_ppm: f64,
+}
+
+impl Default for TimeSyncConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval: Duration::from_secs(60),
+            window: 10,
+            max_offset_ms: 1000,
+            max_drift_ppm: 200.0,
+        }
+    }
+}
+
+/// One reading of an exchange's clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ClockSample {
+    /// Local time halfway through the request
+    local_ms: Timestamp,
+    /// Server time minus `local_ms`
+    offset_ms: i64,
+    /// Round trip of the request
+    rtt_ms: u64,
+}
+
+/// Estimated clock state of an exchange
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClockStatus {
+    pub exchange_id: String,
+    /// Offset of the sample with the shortest round trip in the window
+    pub offset_ms: i64,
+    /// Round trip of that sample
+    pub rtt_ms: u64,
+    /// Rate the offset changes at, in parts per million (None below two samples)
+    pub drift_ppm: Option<f64>,
+    /// Samples in the window
+    pub samples: usize,
+}
+
+impl ClockStatus {
+    /// Check if the offset or drift exceeds the thresholds of a config
+    pub fn is_out_of_bounds(&self, config: &TimeSyncConfig) -> bool {
+        self.offset_ms.unsigned_abs() > config.max_offset_ms
+            || self
+                .drift_ppm
+                .is_some_and(|drift| drift.abs() > config.max_drift_ppm)
+    }
+}
+
+/// Object-safe view of a `ServerClock`
+#[async_trait]
+trait ClockSource: Send + Sync {
+    async fn server_time(&self) -> Result<Timestamp, String>;
+}
+
+#[async_trait]
+impl<S> ClockSource for S
+where
+    S: ServerClock + Send + Sync,
+{
+    async fn server_time(&self) -> Result<Timestamp, String> {
+        ServerClock::server_time(self)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Per-exchange sampling state
+struct ExchangeClock {
+    source: Arc<dyn ClockSource>,
+    offset: TimeOffset,
+    /// Recent samples (oldest first)
+    samples: VecDeque<ClockSample>,
+    /// Whether the last estimate was out of bounds
+    alerted: bool,
+}
+
+/// Service keeping exchange clock offsets up to date
+///
+/// The offset is taken from the sample with the shortest round trip in the window, as
+/// its midpoint is the tightest bound on when the server read its clock; ties go to the
+/// newest. Drift is the least-squares slope of the offsets over local time.
+pub struct TimeSync {
+    exchanges: Arc<RwLock<HashMap<String, ExchangeClock>>>,
+    config: TimeSyncConfig,
+    clock: SharedClock,
+    alert_manager: Option<Arc<AlertManager>>,
+}
+
+impl TimeSync {
+    /// Create a service without exchanges
+    pub fn new(config: TimeSyncConfig) -> Self {
+        Self {
+            exchanges: Arc::new(RwLock::new(HashMap::new())),
+            config: TimeSyncConfig {
+                window: config.window.max(1),
+                ..config
+            },
+            clock: system_clock(),
+            alert_manager: None,
+        }
+    }
+
+    /// Read local time from a clock other than the system clock (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Emit alerts when an exchange's clock goes out of bounds (builder pattern)
+    pub fn with_alert_manager(mut self, alert_manager: Arc<AlertManager>) -> Self {
+        self.alert_manager = Some(alert_manager);
+        self
+    }
+
+    /// Start tracking an exchange's clock
+    /// Returns the offset to stamp the exchange's signed requests with.
+    pub async fn add_exchange<S>(&self, exchange_id: &str, source: Arc<S>) -> TimeOffset
+    where
+        S: ServerClock + Send + Sync + 'static,
+    {
+        let offset = TimeOffset::new();
+        let clock = ExchangeClock {
+            source,
+            offset: offset.clone(),
+            samples: VecDeque::new(),
+            alerted: false,
+        };
+        self.exchanges
+            .write()
+            .await
+            .insert(exchange_id.to_string(), clock);
+        offset
+    }
+
+    /// Get the offset of an exchange
+    pub async fn offset(&self, exchange_id: &str) -> Option<TimeOffset> {
+        let exchanges = self.exchanges.read().await;
+        exchanges.get(exchange_id).map(|clock| clock.offset.clone())
+    }
+
+    /// Get the estimated clock state of an exchange
+    pub async fn status(&self, exchange_id: &str) -> Option<ClockStatus> {
+        let exchanges = self.exchanges.read().await;
+        exchanges
+            .get(exchange_id)
+            .and_then(|clock| estimate(exchange_id, &clock.samples))
+    }
+
+    /// Sample an exchange's clock and update its offset
+    pub async fn sample(&self, exchange_id: &str) -> Result<ClockStatus, String> {
+        let source = {
+            let exchanges = self.exchanges.read().await;
+            let clock = exchanges
+                .get(exchange_id)
+                .ok_or_else(|| format!("Unknown exchange: {}", exchange_id))?;
+            clock.source.clone()
+        };
+
+        let sent = self.clock.now_millis();
+        let server_ms = source.server_time().await?;
+        let received = self.clock.now_millis().max(sent);
+        let local_ms = sent + (received - sent) / 2;
+        let sample = ClockSample {
+            local_ms,
+            offset_ms: server_ms as i64 - local_ms as i64,
+            rtt_ms: received - sent,
+        };
+
+        let (status, changed) = {
+            let mut exchanges = self.exchanges.write().await;
+            let clock = exchanges
+                .get_mut(exchange_id)
+                .ok_or_else(|| format!("Unknown exchange: {}", exchange_id))?;
+            clock.samples.push_back(sample);
+            while clock.samples.len() > self.config.window {
+                clock.samples.pop_front();
+            }
+            let status = estimate(exchange_id, &clock.samples)
+                .ok_or_else(|| format!("No clock samples for {}", exchange_id))?;
+            clock.offset.set(status.offset_ms);
+
+            let out_of_bounds = status.is_out_of_bounds(&self.config);
+            let changed = out_of_bounds != clock.alerted;
+            clock.alerted = out_of_bounds;
+            (status, changed)
+        };
+
+        if changed {
+            self.report(&status).await;
+        }
+        Ok(status)
+    }
+
+    /// Sample every exchange's clock, logging exchanges that could not be reached
+    pub async fn sample_all(&self) -> Vec<ClockStatus> {
+        let exchange_ids: Vec<String> = self.exchanges.read().await.keys().cloned().collect();
+        let mut statuses = Vec::new();
+        for exchange_id in exchange_ids {
+            match self.sample(&exchange_id).await {
+                Ok(status) => statuses.push(status),
+                Err(e) => warn!("Failed to sample {} server time: {}", exchange_id, e),
+            }
+        }
+        statuses
+    }
+
+    /// Spawn the sampling job
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.sample_interval);
+            loop {
+                interval.tick().await;
+                self.sample_all().await;
+            }
+        })
+    }
+
+    /// Log and alert an exchange's clock going out of or back into bounds
+    async fn report(&self, status: &ClockStatus) {
+        let drift = status
+            .drift_ppm
+            .map(|drift| format!("{:.1} ppm", drift))
+            .unwrap_or_else(|| "unknown".to_string());
+        if status.is_out_of_bounds(&self.config) {
+            let message = format!(
+                "{} clock offset {} ms, drift {} (limits {} ms, {:.1} ppm)",
+                status.exchange_id,
+                status.offset_ms,
+                drift,
+                self.config.max_offset_ms,
+                self.config.max_drift_ppm
+            );
+            match &self.alert_manager {
+                Some(alert_manager) => {
+                    alert_manager
+                        .emit(AlertLevel::Warning, "time_sync", message)
+                        .await
+                }
+                None => warn!("{}", message),
+            }
+        } else {
+            let message = format!(
+                "{} clock back within limits: offset {} ms, drift {}",
+                status.exchange_id, status.offset_ms, drift
+            );
+            match &self.alert_manager {
+                Some(alert_manager) => {
+                    alert_manager
+                        .emit(AlertLevel::Info, "time_sync", message)
+                        .await
+                }
+                None => info!("{}", message),
+            }
+        }
+    }
+}
+
+/// Estimate an exchange's clock state from its samples
+fn estimate(exchange_id: &str, samples: &VecDeque<ClockSample>) -> Option<ClockStatus> {
+    // Newest first, so ties go to the most recent reading
+    let best = samples.iter().rev().min_by_key(|sample| sample.rtt_ms)?;
+
+    // Least-squares slope of offset over local time, relative to the first sample
+    let drift_ppm = if samples.len() >= 2 {
+        let origin = samples[0].local_ms as f64;
+        let n = samples.len() as f64;
+        let mean_x = samples
+            .iter()
+            .map(|s| s.local_ms as f64 - origin)
+            .sum::<f64>()
+            / n;
+        let mean_y = samples.iter().map(|s| s.offset_ms as f64).sum::<f64>() / n;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for sample in samples {
+            let dx = sample.local_ms as f64 - origin - mean_x;
+            covariance += dx * (sample.offset_ms as f64 - mean_y);
+            variance += dx * dx;
+        }
+        (variance > 0.0).then(|| covariance / variance * 1_000_000.0)
+    } else {
+        None
+    };
+
+    Some(ClockStatus {
+        exchange_id: exchange_id.to_string(),
+        offset_ms: best.offset_ms,
+        rtt_ms: best.rtt_ms,
+        drift_ppm,
+        samples: samples.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::SimulatedClock;
+    use std::sync::atomic::AtomicU64;
+
+    /// Exchange whose clock runs ahead of a simulated local clock by a settable offset
+    struct SkewedExchange {
+        clock: SimulatedClock,
+        skew_ms: AtomicU64,
+    }
+
+    #[async_trait]
+    impl ServerClock for SkewedExchange {
+        type Error = String;
+
+        async fn server_time(&self) -> Result<Timestamp, Self::Error> {
+            let local = self.clock.shared().now_millis();
+            Ok(local + self.skew_ms.load(Ordering::SeqCst))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offset_tracks_server_clock_and_alerts_on_drift() {
+        let clock = SimulatedClock::at_millis(1_700_000_000_000);
+        let alert_manager = Arc::new(AlertManager::new(10));
+        let time_sync = TimeSync::new(TimeSyncConfig {
+            max_offset_ms: 500,
+            max_drift_ppm: 1000.0,
+            ..TimeSyncConfig::default()
+        })
+        .with_clock(clock.shared())
+        .with_alert_manager(alert_manager.clone());
+        let exchange = Arc::new(SkewedExchange {
+            clock: clock.clone(),
+            skew_ms: AtomicU64::new(120),
+        });
+        let offset = time_sync.add_exchange("binance", exchange.clone()).await;
+
+        let status = time_sync.sample("binance").await.unwrap();
+        assert_eq!(status.offset_ms, 120);
+        assert_eq!(status.drift_ppm, None);
+        assert_eq!(offset.get(), 120);
+        assert_eq!(offset.apply(1_000), 1_120);
+        assert!(time_sync.sample("unknown").await.is_err());
+
+        // Gaining 50 ms a minute (~833 ppm) stays within limits
+        clock.advance(Duration::from_secs(60));
+        exchange.skew_ms.store(170, Ordering::SeqCst);
+        let status = time_sync.sample("binance").await.unwrap();
+        assert_eq!(status.offset_ms, 170);
+        assert!((status.drift_ppm.unwrap() - 833.3).abs() < 0.1);
+        assert!(alert_manager.get_recent_alerts(10).await.is_empty());
+
+        // Gaining 400 ms in the next minute breaches both limits, alerting once
+        clock.advance(Duration::from_secs(60));
+        exchange.skew_ms.store(570, Ordering::SeqCst);
+        let status = time_sync.sample("binance").await.unwrap();
+        assert!(status.is_out_of_bounds(&time_sync.config));
+        assert_eq!(offset.get(), 570);
+        clock.advance(Duration::from_secs(60));
+        time_sync.sample_all().await;
+        let alerts = alert_manager.get_recent_alerts(10).await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].level, AlertLevel::Warning);
+        assert_eq!(alerts[0].component, "time_sync");
+    }
+}
@@ -0,0 +1,681 @@
+use crypto_hft::connectors::GameDayScenario;
+use crypto_hft::control::{ControlClient, ControlCommand, ControlResponse};
+use crypto_hft::realtime::DeadLetterPayload;
+use crypto_hft::risk::approvals::{ApprovalToken, LimitKey};
+use crypto_hft::risk::PortfolioNode;
+use crypto_hft::traits::StrategyConfig;
+use rust_decimal::Decimal;
+use std::process::ExitCode;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:9100";
+
+const USAGE: &str = "\
+Usage: hft-cli [--addr HOST:PORT] [--operator NAME] [--json] <command>
+
+Commands:
+  status                                   Engine status
+  positions                                Positions from the shadow ledger
+  portfolio [PATH...]                      Exposure and P&L by asset class, symbol, venue
+                                           and strategy, e.g. portfolio spot BTCUSDT
+  orders [SYMBOL]                          Open orders
+  cancel-all [SYMBOL]                      Cancel all open orders
+  flatten                                  Cancel all open orders and close every position
+  orders export PATH                       Write working orders to PATH on the engine host
+  orders import PATH [--confirm TOKEN]     Preview, then replay, orders from an export
+  quarantine                               Quarantined symbols
+  quarantine SYMBOL [REASON]               Stop one symbol trading and cancel its orders
+  release SYMBOL                           Let a quarantined symbol trade again
+  halt [REASON]                            Trip the kill switch: halt trading, cancel all orders
+  resume                                   Reset the kill switch
+  pause-strategy NAME                      Stop a strategy from trading
+  resume-strategy NAME                     Let a paused strategy trade again
+  strategy-config NAME KEY=VALUE...        Change a running strategy's parameters,
+                                           e.g. strategy-config mm_btc target_spread=0.8
+  preview-signals NAME [SYMBOL]            Signals a strategy would emit now, without executing
+  limits set LIMIT VALUE [REASON]          Set a risk limit, e.g. max_position_size:BTCUSDT
+  limits pending                           Limit overrides awaiting approval
+  limits approve REQUEST_ID --secret KEY   Approve another operator's override
+  log-sampling [on|off] [every=N] [per-sec=X]
+                                           Show or change hot-path debug log sampling
+  game-day                                 Active game day scenarios (mock/dry-run only)
+  game-day outage                          Simulate a venue outage
+  game-day stuck-fills RATIO               Partially fill new orders and reject their cancels
+  game-day ledger-divergence SYMBOL SIZE PRICE [EXCHANGE]
+                                           Record a phantom fill in the shadow ledger
+  game-day stop                            Stop all game day scenarios
+  dead-letters [LIMIT]                     Events that failed parsing or handling
+  dead-letters redrive ID...|all           Replay dead letters through the event loop
+  dead-letters discard ID...|all           Delete dead letters
+  api-keys                                 Exchange accounts and their current API keys
+  api-keys rotate ACCOUNT KEY SECRET       Switch ACCOUNT to the key and secret named KEY and
+                                           SECRET in the engine's key provider, then revoke
+                                           the old key once its requests finish
+
+Options default to the HFT_CONTROL_ADDR and HFT_OPERATOR environment variables,
+then to 127.0.0.1:9100 and $USER.";
+
+/// Parsed command-line options
+struct Options {
+    addr: String,
+    operator: String,
+    json: bool,
+    secret: Option<String>,
+    confirm: Option<String>,
+    args: Vec<String>,
+}
+
+fn parse_options(raw: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        addr: std::env::var("HFT_CONTROL_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string()),
+        operator: std::env::var("HFT_OPERATOR")
+            .or_else(|_| std::env::var("USER"))
+            .unwrap_or_default(),
+        json: false,
+        secret: None,
+        confirm: None,
+        args: Vec::new(),
+    };
+
+    let mut raw = raw.peekable();
+    while let Some(arg) = raw.next() {
+        let mut value = |name: &str| {
+            raw.next()
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match arg.as_str() {
+            "--addr" => options.addr = value("--addr")?,
+            "--operator" => options.operator = value("--operator")?,
+            "--secret" => options.secret = Some(value("--secret")?),
+            "--confirm" => options.confirm = Some(value("--confirm")?),
+            "--json" => options.json = true,
+            "--help" => options.args.insert(0, "help".to_string()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ => options.args.push(arg),
+        }
+    }
+    Ok(options)
+}
+
+/// Commands run against the control server
+enum CliCommand {
+    Send(ControlCommand),
+    /// Approving needs the pending request to sign it locally
+    Approve {
+        request_id: String,
+        secret: String,
+    },
+}
+
+fn parse_command(options: &Options) -> Result<CliCommand, String> {
+    let args: Vec<&str> = options.args.iter().map(String::as_str).collect();
+    let operator = || {
+        if options.operator.is_empty() {
+            Err("No operator set; pass --operator or set HFT_OPERATOR".to_string())
+        } else {
+            Ok(options.operator.clone())
+        }
+    };
+    let symbol = |arg: Option<&&str>| arg.map(|s| s.to_uppercase());
+
+    let command = match args.as_slice() {
+        ["status"] => ControlCommand::Status,
+        ["positions"] => ControlCommand::Positions,
+        ["portfolio", path @ ..] => ControlCommand::Portfolio {
+            path: path.iter().map(|key| key.to_string()).collect(),
+        },
+        ["orders", "export", path] => ControlCommand::ExportOrders {
+            operator: operator()?,
+            path: path.to_string(),
+        },
+        ["orders", "import", path] => ControlCommand::ImportOrders {
+            operator: operator()?,
+            path: path.to_string(),
+            confirmation: options.confirm.clone(),
+        },
+        ["orders", rest @ ..] if rest.len() <= 1 => ControlCommand::Orders {
+            symbol: symbol(rest.first()),
+        },
+        ["cancel-all", rest @ ..] if rest.len() <= 1 => ControlCommand::CancelAll {
+            operator: operator()?,
+            symbol: symbol(rest.first()),
+        },
+        ["flatten"] => ControlCommand::FlattenAll {
+            operator: operator()?,
+        },
+        ["quarantine"] => ControlCommand::Quarantined,
+        ["quarantine", symbol, reason @ ..] if reason.len() <= 1 => {
+            ControlCommand::QuarantineSymbol {
+                operator: operator()?,
+                symbol: symbol.to_uppercase(),
+                reason: reason
+                    .first()
+                    .unwrap_or(&"quarantined via hft-cli")
+                    .to_string(),
+            }
+        }
+        ["release", symbol] => ControlCommand::ReleaseSymbol {
+            operator: operator()?,
+            symbol: symbol.to_uppercase(),
+        },
+        ["halt", reason @ ..] if reason.len() <= 1 => ControlCommand::HaltTrading {
+            operator: operator()?,
+            reason: reason.first().unwrap_or(&"halted via hft-cli").to_string(),
+        },
+        ["resume"] => ControlCommand::ResumeTrading {
+            operator: operator()?,
+        },
+        ["pause-strategy", strategy] => ControlCommand::PauseStrategy {
+            operator: operator()?,
+            strategy: strategy.to_string(),
+        },
+        ["resume-strategy", strategy] => ControlCommand::ResumeStrategy {
+            operator: operator()?,
+            strategy: strategy.to_string(),
+        },
+        ["strategy-config", strategy, parameters @ ..] if !parameters.is_empty() => {
+            ControlCommand::UpdateStrategyConfig {
+                operator: operator()?,
+                strategy: strategy.to_string(),
+                config: parse_strategy_parameters(parameters)?,
+            }
+        }
+        ["preview-signals", strategy, rest @ ..] if rest.len() <= 1 => {
+            ControlCommand::PreviewSignals {
+                strategy: strategy.to_string(),
+                symbol: symbol(rest.first()),
+            }
+        }
+        ["limits", "set", limit, value, reason @ ..] if reason.len() <= 1 => {
+            ControlCommand::SetLimit {
+                operator: operator()?,
+                limit: limit.parse::<LimitKey>()?,
+                value: value
+                    .parse::<Decimal>()
+                    .map_err(|e| format!("Invalid value '{}': {}", value, e))?,
+                reason: reason.first().unwrap_or(&"set via hft-cli").to_string(),
+            }
+        }
+        ["limits", "pending"] => ControlCommand::PendingLimitOverrides,
+        ["limits", "approve", request_id] => {
+            let secret = options
+                .secret
+                .clone()
+                .or_else(|| std::env::var("HFT_OPERATOR_SECRET").ok())
+                .ok_or("Approving requires --secret or HFT_OPERATOR_SECRET")?;
+            return Ok(CliCommand::Approve {
+                request_id: request_id.to_string(),
+                secret,
+            });
+        }
+        ["log-sampling"] => ControlCommand::LogSampling,
+        ["log-sampling", settings @ ..] => parse_log_sampling(operator()?, settings)?,
+        ["game-day"] => ControlCommand::GameDay,
+        ["game-day", "stop"] => ControlCommand::StopGameDay {
+            operator: operator()?,
+        },
+        ["game-day", scenario @ ..] => ControlCommand::StartGameDay {
+            operator: operator()?,
+            scenario: parse_game_day_scenario(scenario)?,
+        },
+        ["dead-letters", rest @ ..]
+            if rest.len() <= 1 && rest.iter().all(|a| a.parse::<usize>().is_ok()) =>
+        {
+            ControlCommand::DeadLetters {
+                limit: rest.first().and_then(|limit| limit.parse().ok()),
+            }
+        }
+        ["dead-letters", "redrive", ids @ ..] => ControlCommand::RedriveDeadLetters {
+            operator: operator()?,
+            ids: parse_dead_letter_ids(ids)?,
+        },
+        ["dead-letters", "discard", ids @ ..] => ControlCommand::DiscardDeadLetters {
+            operator: operator()?,
+            ids: parse_dead_letter_ids(ids)?,
+        },
+        ["api-keys"] => ControlCommand::ApiKeys,
+        ["api-keys", "rotate", account, api_key, api_secret] => ControlCommand::RotateApiKey {
+            operator: operator()?,
+            account: account.to_string(),
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+        },
+        [] => return Err(USAGE.to_string()),
+        _ => return Err(format!("Unknown command: {}\n\n{}", args.join(" "), USAGE)),
+    };
+    Ok(CliCommand::Send(command))
+}
+
+fn parse_log_sampling(operator: String, settings: &[&str]) -> Result<ControlCommand, String> {
+    let mut enabled = None;
+    let mut every_n = None;
+    let mut max_per_second = None;
+    for setting in settings {
+        match setting.split_once('=') {
+            None if *setting == "on" => enabled = Some(true),
+            None if *setting == "off" => enabled = Some(false),
+            Some(("every", n)) => {
+                every_n = Some(
+                    n.parse()
+                        .map_err(|e| format!("Invalid every '{}': {}", n, e))?,
+                )
+            }
+            Some(("per-sec", x)) => {
+                max_per_second = Some(
+                    x.parse()
+                        .map_err(|e| format!("Invalid per-sec '{}': {}", x, e))?,
+                )
+            }
+            _ => return Err(format!("Unknown log sampling setting '{}'", setting)),
+        }
+    }
+    Ok(ControlCommand::SetLogSampling {
+        operator,
+        enabled,
+        every_n,
+        max_per_second,
+    })
+}
+
+/// Parse `KEY=VALUE` strategy parameters into a config carrying only those parameters
+fn parse_strategy_parameters(parameters: &[&str]) -> Result<StrategyConfig, String> {
+    let mut config = StrategyConfig::default();
+    for parameter in parameters {
+        let (name, value) = parameter
+            .split_once('=')
+            .ok_or_else(|| format!("Expected KEY=VALUE, got '{}'", parameter))?;
+        config
+            .parameters
+            .insert(name.to_string(), value.to_string());
+    }
+    Ok(config)
+}
+
+/// Parse dead letter IDs; `all` (an empty list) must be asked for explicitly
+fn parse_dead_letter_ids(args: &[&str]) -> Result<Vec<u64>, String> {
+    match args {
+        [] => Err("Pass dead letter IDs, or 'all'".to_string()),
+        ["all"] => Ok(Vec::new()),
+        ids => ids
+            .iter()
+            .map(|id| {
+                id.parse()
+                    .map_err(|e| format!("Invalid dead letter ID '{}': {}", id, e))
+            })
+            .collect(),
+    }
+}
+
+fn parse_game_day_scenario(args: &[&str]) -> Result<GameDayScenario, String> {
+    let decimal = |name: &str, value: &str| {
+        value
+            .parse::<Decimal>()
+            .map_err(|e| format!("Invalid {} '{}': {}", name, value, e))
+    };
+    match args {
+        ["outage"] => Ok(GameDayScenario::VenueOutage),
+        ["stuck-fills", ratio] => Ok(GameDayScenario::StuckPartialFills {
+            fill_ratio: decimal("ratio", ratio)?,
+        }),
+        ["ledger-divergence", symbol, size, price, exchange @ ..] if exchange.len() <= 1 => {
+            Ok(GameDayScenario::LedgerDivergence {
+                symbol: symbol.to_uppercase(),
+                exchange_id: exchange.first().unwrap_or(&"binance").to_string(),
+                size: decimal("size", size)?,
+                price: decimal("price", price)?,
+            })
+        }
+        _ => Err(format!("Unknown game day scenario: {}", args.join(" "))),
+    }
+}
+
+fn print_portfolio_node(node: &PortfolioNode, depth: usize) {
+    let size = node.net_size.map(|s| s.to_string()).unwrap_or_default();
+    println!(
+        "{:<32} {:>14} {:>14} {:>14} {:>14} {:>14} {:>8}",
+        format!("{}{}", "  ".repeat(depth), node.key),
+        size,
+        node.net_exposure,
+        node.gross_exposure,
+        node.realized_pnl,
+        node.unrealized_pnl,
+        node.trade_count
+    );
+    for child in &node.children {
+        print_portfolio_node(child, depth + 1);
+    }
+}
+
+fn print_response(response: &ControlResponse) {
+    match response {
+        ControlResponse::Ok => println!("ok"),
+        ControlResponse::Status {
+            trading_halted,
+            halt_reason,
+            paused_strategies,
+            quarantined_symbols,
+            pending_overrides,
+        } => {
+            match halt_reason {
+                Some(reason) if *trading_halted => {
+                    println!("trading:           HALTED ({})", reason)
+                }
+                _ => println!("trading:           active"),
+            }
+            println!("paused strategies: {}", paused_strategies.join(", "));
+            println!("quarantined:       {}", quarantined_symbols.join(", "));
+            println!("pending overrides: {}", pending_overrides);
+        }
+        ControlResponse::Positions { positions } => {
+            println!(
+                "{:<12} {:<10} {:>16} {:>16} {:>16}",
+                "SYMBOL", "EXCHANGE", "SIZE", "AVG PRICE", "REALIZED"
+            );
+            for p in positions {
+                let average_price = p.average_price.map(|p| p.to_string()).unwrap_or_default();
+                println!(
+                    "{:<12} {:<10} {:>16} {:>16} {:>16}",
+                    p.symbol, p.exchange_id, p.size, average_price, p.realized_pnl
+                );
+            }
+        }
+        ControlResponse::Portfolio { node } => {
+            println!(
+                "{:<32} {:>14} {:>14} {:>14} {:>14} {:>14} {:>8}",
+                "NODE", "SIZE", "NET", "GROSS", "REALIZED", "UNREALIZED", "TRADES"
+            );
+            print_portfolio_node(node, 0);
+        }
+        ControlResponse::Orders { orders } => {
+            println!(
+                "{:<24} {:<12} {:<10} {:>14} {:>14} {:?}",
+                "ORDER ID", "SYMBOL", "EXCHANGE", "FILLED", "REMAINING", "STATUS"
+            );
+            for o in orders {
+                println!(
+                    "{:<24} {:<12} {:<10} {:>14} {:>14} {:?}",
+                    o.order_id, o.symbol, o.exchange_id, o.filled_size, o.remaining_size, o.status
+                );
+            }
+        }
+        ControlResponse::Cancelled {
+            cancelled,
+            failures,
+        } => {
+            println!("cancelled {} orders", cancelled);
+            for failure in failures {
+                println!("  {}", failure);
+            }
+        }
+        ControlResponse::Flattened { report } => {
+            println!("cancelled {} orders", report.cancelled);
+            for failure in &report.cancel_failures {
+                println!("  {}", failure);
+            }
+            for p in &report.positions {
+                match (&p.order_id, &p.error) {
+                    (Some(order_id), _) => println!(
+                        "{:<12} {:<10} {:>16}  closing with {}",
+                        p.symbol, p.exchange_id, p.size, order_id
+                    ),
+                    (None, error) => println!(
+                        "{:<12} {:<10} {:>16}  FAILED: {}",
+                        p.symbol,
+                        p.exchange_id,
+                        p.size,
+                        error.as_deref().unwrap_or_default()
+                    ),
+                }
+            }
+        }
+        ControlResponse::Quarantined { entries } => {
+            for e in entries {
+                println!("{:<12} {:?}: {}", e.symbol, e.source, e.reason);
+            }
+        }
+        ControlResponse::SymbolQuarantined {
+            symbol,
+            newly_quarantined,
+            cancelled,
+            failures,
+        } => {
+            if !newly_quarantined {
+                println!("{} was already quarantined", symbol);
+            }
+            println!("{} quarantined, cancelled {} orders", symbol, cancelled);
+            for failure in failures {
+                println!("  {}", failure);
+            }
+        }
+        ControlResponse::TradingHalted {
+            newly_halted,
+            cancelled,
+            failures,
+        } => {
+            if !newly_halted {
+                println!("Trading was already halted");
+            }
+            println!("Trading halted, cancelled {} orders", cancelled);
+            for failure in failures {
+                println!("  {}", failure);
+            }
+        }
+        ControlResponse::SignalPreview {
+            strategy,
+            paused,
+            trading_halted,
+            previews,
+        } => {
+            if *paused || *trading_halted {
+                println!(
+                    "{} is {}; these signals would not be executed",
+                    strategy,
+                    if *trading_halted { "halted" } else { "paused" }
+                );
+            }
+            for p in previews {
+                let mut notes = Vec::new();
+                if p.cooldown_remaining_ms > 0 {
+                    notes.push(format!("cooldown {}ms", p.cooldown_remaining_ms));
+                }
+                if p.stale {
+                    notes.push("stale book".to_string());
+                }
+                let notes = if notes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", notes.join(", "))
+                };
+                match &p.signal {
+                    Some(signal) => println!("{:<12} {:?}{}", p.symbol, signal, notes),
+                    None => println!("{:<12} no signal{}", p.symbol, notes),
+                }
+            }
+        }
+        ControlResponse::PendingApproval { request } => println!(
+            "{} = {} exceeds the hard cap; request {} awaits a second operator's approval",
+            request.limit, request.value, request.request_id
+        ),
+        ControlResponse::Approved { request } => println!(
+            "approved {}: {} = {}",
+            request.request_id, request.limit, request.value
+        ),
+        ControlResponse::PendingOverrides { requests } => {
+            for r in requests {
+                println!(
+                    "{}  {} = {}  by {}: {}",
+                    r.request_id, r.limit, r.value, r.requested_by, r.reason
+                );
+            }
+        }
+        ControlResponse::Exported { path, orders } => {
+            println!("exported {} working orders to {}", orders, path)
+        }
+        ControlResponse::ImportPreview { plan, confirmation } => {
+            for w in &plan.to_place {
+                println!(
+                    "place  {} {:?} {} @ {} on {} (was {})",
+                    w.order.symbol,
+                    w.order.side,
+                    w.order.size,
+                    w.order.price.map(|p| p.to_string()).unwrap_or_default(),
+                    w.order.exchange_id,
+                    w.order_id
+                );
+            }
+            for (w, reason) in &plan.skipped {
+                println!("skip   {} {}: {}", w.order.symbol, w.order_id, reason);
+            }
+            println!(
+                "\nre-run with --confirm {} to place {} orders",
+                confirmation,
+                plan.to_place.len()
+            );
+        }
+        ControlResponse::Imported { report } => {
+            for (old, new) in &report.placed {
+                println!("placed {} (was {})", new, old);
+            }
+            for failure in &report.failures {
+                println!("failed {}", failure);
+            }
+            println!(
+                "placed {}, skipped {}, failed {}",
+                report.placed.len(),
+                report.skipped,
+                report.failures.len()
+            );
+        }
+        ControlResponse::LogSampling { config } => {
+            let max_per_second = match config.max_per_second {
+                0 => "unlimited".to_string(),
+                x => x.to_string(),
+            };
+            println!(
+                "hot-path logging {}: 1 in {} per key, at most {} per second",
+                if config.enabled { "on" } else { "off" },
+                config.every_n,
+                max_per_second
+            );
+        }
+        ControlResponse::GameDay { active } => {
+            if active.is_empty() {
+                println!("no game day scenarios active");
+            }
+            for scenario in active {
+                println!("active: {:?}", scenario);
+            }
+        }
+        ControlResponse::DeadLetters {
+            letters,
+            total,
+            dropped,
+        } => {
+            for l in letters {
+                let payload = match &l.payload {
+                    DeadLetterPayload::Raw { text } => text.clone(),
+                    DeadLetterPayload::Event { event } => format!("{:?}", event),
+                };
+                println!(
+                    "{:<6} {:<10} attempts={} {}\n       error:   {}\n       payload: {}",
+                    l.id, l.exchange_id, l.attempts, l.last_failed_at, l.error, payload
+                );
+            }
+            println!(
+                "{} of {} dead letters shown, {} dropped",
+                letters.len(),
+                total,
+                dropped
+            );
+        }
+        ControlResponse::DeadLettersRedriven { queued } => {
+            println!("queued {} dead letters for re-drive", queued)
+        }
+        ControlResponse::DeadLettersDiscarded { discarded } => {
+            println!("discarded {} dead letters", discarded)
+        }
+        ControlResponse::ApiKeys { accounts } => {
+            for a in accounts {
+                println!(
+                    "{:<12} {:<14} generation={} in_flight={}",
+                    a.account, a.api_key, a.generation, a.in_flight
+                );
+            }
+        }
+        ControlResponse::ApiKeyRotated { report } => {
+            println!(
+                "{} rotated from {} to {} (generation {})",
+                report.account, report.previous_key, report.new_key, report.generation
+            );
+            match (&report.revoke_error, report.revoked) {
+                (_, true) => println!("old key drained and revoked"),
+                (Some(error), false) => println!("old key NOT revoked: {}", error),
+                (None, false) => println!("old key drained; revoke it at the exchange"),
+            }
+        }
+        ControlResponse::Error { message } => eprintln!("error: {}", message),
+    }
+}
+
+async fn run(options: Options) -> Result<ControlResponse, Box<dyn std::error::Error>> {
+    let command = parse_command(&options)?;
+    let mut client = ControlClient::connect(&options.addr)
+        .await
+        .map_err(|e| format!("Cannot reach control server at {}: {}", options.addr, e))?;
+
+    match command {
+        CliCommand::Send(command) => Ok(client.send(&command).await?),
+        CliCommand::Approve { request_id, secret } => {
+            let ControlResponse::PendingOverrides { requests } =
+                client.send(&ControlCommand::PendingLimitOverrides).await?
+            else {
+                return Err("Unexpected response to pending overrides query".into());
+            };
+            let request = requests
+                .iter()
+                .find(|r| r.request_id == request_id)
+                .ok_or_else(|| format!("No pending override request '{}'", request_id))?;
+            let token = ApprovalToken::sign(request, &options.operator, &secret);
+            Ok(client
+                .send(&ControlCommand::ApproveLimitOverride { token })
+                .await?)
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if matches!(
+        options.args.first().map(String::as_str),
+        Some("help" | "-h")
+    ) {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let json = options.json;
+
+    match run(options).await {
+        Ok(response) => {
+            if json {
+                println!("{}", serde_json::to_string_pretty(&response).unwrap());
+            } else {
+                print_response(&response);
+            }
+            if matches!(response, ControlResponse::Error { .. }) {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use crate::core::events::{BorrowRate, FundingRate, OpenInterest, Timestamp};
+use crate::types::Size;
+use rust_decimal::prelude::*;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
+
+/// Hours per year used to annualize rates
+const HOURS_PER_YEAR: i64 = 24 * 365;
+
+/// Carry and positioning indicator for a single symbol
+/// Tracks funding rates, the borrow rate of the base asset and open interest
+pub struct CarryIndicator {
+    /// Symbol name
+    symbol: String,
+    /// Historical funding rates (oldest first)
+    funding_history: VecDeque<FundingRate>,
+    /// Latest borrow rate of the base asset
+    borrow_rate: Option<BorrowRate>,
+    /// Historical open interest samples (oldest first)
+    open_interest_history: VecDeque<OpenInterest>,
+    /// Maximum number of samples to keep per series
+    max_history: usize,
+}
+
+impl CarryIndicator {
+    /// Create a new carry indicator
+    pub fn new(symbol: impl Into<String>, max_history: usize) -> Self {
+        Self {
+            symbol: symbol.into(),
+            funding_history: VecDeque::with_capacity(max_history),
+            borrow_rate: None,
+            open_interest_history: VecDeque::with_capacity(max_history),
+            max_history,
+        }
+    }
+
+    /// Get the symbol this indicator tracks
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Update with a new funding rate
+    pub fn update_funding(&mut self, funding: FundingRate) {
+        // Venues republish the rate for the same interval; keep only the latest one
+        if let Some(last) = self.funding_history.back() {
+            if last.next_funding_time == funding.next_funding_time {
+                self.funding_history.pop_back();
+            }
+        }
+
+        self.funding_history.push_back(funding);
+        while self.funding_history.len() > self.max_history {
+            self.funding_history.pop_front();
+        }
+    }
+
+    /// Update with a new borrow rate
+    pub fn update_borrow(&mut self, borrow: BorrowRate) {
+        self.borrow_rate = Some(borrow);
+    }
+
+    /// Update with a new open interest sample
+    pub fn update_open_interest(&mut self, open_interest: OpenInterest) {
+        self.open_interest_history.push_back(open_interest);
+        while self.open_interest_history.len() > self.max_history {
+            self.open_interest_history.pop_front();
+        }
+    }
+
+    /// Get the latest funding rate update
+    pub fn latest_funding(&self) -> Option<&FundingRate> {
+        self.funding_history.back()
+    }
+
+    /// Get the current funding rate
+    pub fn current_funding_rate(&self) -> Option<Decimal> {
+        self.latest_funding().map(|f| f.rate)
+    }
+
+    /// Get the predicted funding rate, falling back to the current rate
+    pub fn predicted_funding_rate(&self) -> Option<Decimal> {
+        self.latest_funding()
+            .map(|f| f.predicted_rate.unwrap_or(f.rate))
+    }
+
+    /// Get the current funding rate annualized
+    pub fn annualized_funding_rate(&self) -> Option<Decimal> {
+        let funding = self.latest_funding()?;
+        if funding.interval_hours == 0 {
+            return None;
+        }
+        let periods_per_year = Decimal::from(HOURS_PER_YEAR / funding.interval_hours as i64);
+        Some(funding.rate * periods_per_year)
+    }
+
+    /// Average funding rate over the last `periods` funding intervals
+    pub fn average_funding_rate(&self, periods: usize) -> Option<Decimal> {
+        let count = periods.min(self.funding_history.len());
+        if count == 0 {
+            return None;
+        }
+
+        let sum: Decimal = self
+            .funding_history
+            .iter()
+            .rev()
+            .take(count)
+            .map(|f| f.rate)
+            .sum();
+        Some(sum / Decimal::from_usize(count).unwrap())
+    }
+
+    /// Get the hourly borrow rate of the base asset
+    pub fn borrow_rate(&self) -> Option<Decimal> {
+        self.borrow_rate.as_ref().map(|b| b.hourly_rate)
+    }
+
+    /// Get the borrow rate annualized
+    pub fn annualized_borrow_rate(&self) -> Option<Decimal> {
+        self.borrow_rate()
+            .map(|rate| rate * Decimal::from(HOURS_PER_YEAR))
+    }
+
+    /// Annualized net carry: funding rate minus the borrow rate of the base asset
+    pub fn net_carry(&self) -> Option<Decimal> {
+        let funding = self.annualized_funding_rate()?;
+        let borrow = self.annualized_borrow_rate().unwrap_or(Decimal::ZERO);
+        Some(funding - borrow)
+    }
+
+    /// Get the latest open interest
+    pub fn open_interest(&self) -> Option<Size> {
+        self.open_interest_history.back().map(|oi| oi.open_interest)
+    }
+
+    /// Relative change in open interest over the given time window
+    /// Returns e.g. 0.05 for a 5% increase
+    pub fn open_interest_change(&self, window_ms: u64) -> Option<f64> {
+        let latest = self.open_interest_history.back()?;
+        let cutoff: Timestamp = latest.timestamp.saturating_sub(window_ms);
+        let baseline = self
+            .open_interest_history
+            .iter()
+            .find(|oi| oi.timestamp >= cutoff)?;
+
+        if baseline.open_interest.is_zero() || baseline.timestamp == latest.timestamp {
+            return None;
+        }
+
+        let change = (latest.open_interest.value() - baseline.open_interest.value())
+            / baseline.open_interest.value();
+        change.to_f64()
+    }
+
+    /// Clear all tracked data
+    pub fn clear(&mut self) {
+        self.funding_history.clear();
+        self.borrow_rate = None;
+        self.open_interest_history.clear();
+    }
+}
+
+/// Carry indicator feed for all symbols
+/// Routes funding, borrow and open interest updates from the futures adapters to
+/// per-symbol indicators so strategies can read them alongside price-based indicators
+pub struct CarryFeed {
+    /// Per-symbol indicators
+    indicators: HashMap<String, CarryIndicator>,
+    /// Base asset of each symbol, used to route borrow rates
+    base_assets: HashMap<String, String>,
+    /// Maximum number of samples to keep per series
+    max_history: usize,
+}
+
+impl CarryFeed {
+    /// Create a new carry feed
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            indicators: HashMap::new(),
+            base_assets: HashMap::new(),
+            max_history,
+        }
+    }
+
+    /// Register the base asset of a symbol so borrow rates reach its indicator
+    pub fn register_symbol(&mut self, symbol: impl Into<String>, base_asset: impl Into<String>) {
+        let symbol = symbol.into();
+        self.indicator_mut(&symbol);
+        self.base_assets.insert(symbol, base_asset.into());
+    }
+
+    /// Get or create the indicator for a symbol
+    fn indicator_mut(&mut self, symbol: &str) -> &mut CarryIndicator {
+        let max_history = self.max_history;
+        self.indicators
+            .entry(symbol.to_string())
+            .or_insert_with(|| CarryIndicator::new(symbol, max_history))
+    }
+
+    /// Route a funding rate update
+    pub fn on_funding(&mut self, funding: FundingRate) {
+        let symbol = funding.symbol.as_str().to_string();
+        self.indicator_mut(&symbol).update_funding(funding);
+    }
+
+    /// Route a borrow rate update to every symbol with this base asset
+    pub fn on_borrow(&mut self, borrow: BorrowRate) {
+        for (symbol, base_asset) in &self.base_assets {
+            if *base_asset == borrow.asset {
+                if let Some(indicator) = self.indicators.get_mut(symbol) {
+                    indicator.update_borrow(borrow.clone());
+                }
+            }
+        }
+    }
+
+    /// Route an open interest update
+    pub fn on_open_interest(&mut self, open_interest: OpenInterest) {
+        let symbol = open_interest.symbol.as_str().to_string();
+        self.indicator_mut(&symbol)
+            .update_open_interest(open_interest);
+    }
+
+    /// Get the indicator for a symbol
+    pub fn get(&self, symbol: &str) -> Option<&CarryIndicator> {
+        self.indicators.get(symbol)
+    }
+
+    /// Get all tracked symbols
+    pub fn symbols(&self) -> Vec<String> {
+        self.indicators.keys().cloned().collect()
+    }
+}
+
+impl Default for CarryFeed {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Symbol;
+
+    fn funding(rate: &str, predicted: Option<&str>, next_funding_time: u64) -> FundingRate {
+        FundingRate {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance_futures".to_string(),
+            rate: Decimal::from_str(rate).unwrap(),
+            predicted_rate: predicted.map(|p| Decimal::from_str(p).unwrap()),
+            next_funding_time,
+            interval_hours: 8,
+            timestamp: next_funding_time - 1000,
+        }
+    }
+
+    fn open_interest(value: &str, timestamp: u64) -> OpenInterest {
+        OpenInterest {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance_futures".to_string(),
+            open_interest: Size::from_str(value).unwrap(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_funding_rates() {
+        let mut indicator = CarryIndicator::new("BTCUSDT", 10);
+        assert_eq!(indicator.current_funding_rate(), None);
+
+        indicator.update_funding(funding("0.0001", None, 8_000));
+        indicator.update_funding(funding("0.0003", Some("0.0002"), 16_000));
+
+        assert_eq!(indicator.current_funding_rate(), Some(Decimal::new(3, 4)));
+        assert_eq!(indicator.predicted_funding_rate(), Some(Decimal::new(2, 4)));
+        // 0.0003 * 3 intervals/day * 365
+        assert_eq!(
+            indicator.annualized_funding_rate(),
+            Some(Decimal::new(3285, 4))
+        );
+        assert_eq!(indicator.average_funding_rate(5), Some(Decimal::new(2, 4)));
+
+        // A republished rate for the same interval replaces the previous one
+        indicator.update_funding(funding("0.0005", None, 16_000));
+        assert_eq!(indicator.average_funding_rate(5), Some(Decimal::new(3, 4)));
+    }
+
+    #[test]
+    fn test_net_carry() {
+        let mut indicator = CarryIndicator::new("BTCUSDT", 10);
+        indicator.update_funding(funding("0.0001", None, 8_000));
+        assert_eq!(indicator.net_carry(), Some(Decimal::new(1095, 4)));
+
+        indicator.update_borrow(BorrowRate {
+            asset: "BTC".to_string(),
+            exchange_id: "binance".to_string(),
+            hourly_rate: Decimal::new(5, 6),
+            timestamp: 0,
+        });
+        assert_eq!(
+            indicator.annualized_borrow_rate(),
+            Some(Decimal::new(438, 4))
+        );
+        assert_eq!(indicator.net_carry(), Some(Decimal::new(657, 4)));
+    }
+
+    #[test]
+    fn test_open_interest_change() {
+        let mut indicator = CarryIndicator::new("BTCUSDT", 10);
+        indicator.update_open_interest(open_interest("1000", 0));
+        assert_eq!(indicator.open_interest_change(60_000), None);
+
+        indicator.update_open_interest(open_interest("1050", 30_000));
+        indicator.update_open_interest(open_interest("1100", 60_000));
+
+        assert_eq!(
+            indicator.open_interest(),
+            Some(Size::from_str("1100").unwrap())
+        );
+        assert!((indicator.open_interest_change(60_000).unwrap() - 0.1).abs() < 1e-9);
+        // Window only covers the last sample pair
+        let change = indicator.open_interest_change(30_000).unwrap();
+        assert!((change - 50.0 / 1050.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_carry_feed_routing() {
+        let mut feed = CarryFeed::new(10);
+        feed.register_symbol("BTCUSDT", "BTC");
+        feed.register_symbol("ETHUSDT", "ETH");
+
+        feed.on_funding(funding("0.0001", None, 8_000));
+        feed.on_open_interest(open_interest("500", 0));
+        feed.on_borrow(BorrowRate {
+            asset: "ETH".to_string(),
+            exchange_id: "binance".to_string(),
+            hourly_rate: Decimal::new(1, 5),
+            timestamp: 0,
+        });
+
+        let btc = feed.get("BTCUSDT").unwrap();
+        assert_eq!(btc.current_funding_rate(), Some(Decimal::new(1, 4)));
+        assert_eq!(btc.open_interest(), Some(Size::from_str("500").unwrap()));
+        assert_eq!(btc.borrow_rate(), None);
+
+        let eth = feed.get("ETHUSDT").unwrap();
+        assert_eq!(eth.borrow_rate(), Some(Decimal::new(1, 5)));
+        assert_eq!(feed.symbols().len(), 2);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::core::events::{Liquidation, OrderSide, Timestamp};
+use crate::types::{Notional, Size};
+use rust_decimal::prelude::*;
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+
+/// Liquidation volume indicator that aggregates forced orders over a time window
+pub struct LiquidationTracker {
+    /// Liquidations inside the window (oldest first)
+    liquidations: VecDeque<Liquidation>,
+    /// Time window in milliseconds
+    time_window_ms: u64,
+}
+
+impl LiquidationTracker {
+    /// Create a new liquidation tracker
+    pub fn new(time_window_ms: u64) -> Self {
+        Self {
+            liquidations: VecDeque::new(),
+            time_window_ms,
+        }
+    }
+
+    /// Add a new liquidation to the indicator
+    pub fn add_liquidation(&mut self, liquidation: Liquidation) {
+        self.remove_old_liquidations(liquidation.timestamp);
+        self.liquidations.push_back(liquidation);
+    }
+
+    /// Remove liquidations that are outside the time window
+    pub fn remove_old_liquidations(&mut self, current_timestamp: Timestamp) {
+        let cutoff = current_timestamp.saturating_sub(self.time_window_ms);
+        while let Some(front) = self.liquidations.front() {
+            if front.timestamp < cutoff {
+                self.liquidations.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Volume of liquidated longs (forced sells)
+    pub fn long_liquidation_volume(&self) -> Size {
+        self.volume_for(OrderSide::Sell)
+    }
+
+    /// Volume of liquidated shorts (forced buys)
+    pub fn short_liquidation_volume(&self) -> Size {
+        self.volume_for(OrderSide::Buy)
+    }
+
+    fn volume_for(&self, side: OrderSide) -> Size {
+        self.liquidations
+            .iter()
+            .filter(|l| l.side == side)
+            .fold(Size::new(Decimal::ZERO), |acc, l| acc + l.size)
+    }
+
+    /// Total liquidated notional inside the window
+    pub fn liquidation_notional(&self) -> Notional {
+        self.liquidations
+            .iter()
+            .map(|l| l.price.notional(l.size))
+            .sum()
+    }
+
+    /// Liquidation imbalance: (long - short) / total volume
+    /// Returns a value between -1.0 (all shorts) and 1.0 (all longs)
+    pub fn imbalance(&self) -> f64 {
+        let long = self.long_liquidation_volume();
+        let short = self.short_liquidation_volume();
+        let total = long + short;
+
+        if total.is_zero() {
+            return 0.0;
+        }
+
+        ((long.value() - short.value()) / total.value())
+            .to_f64()
+            .unwrap_or(0.0)
+    }
+
+    /// Get the number of liquidations currently tracked
+    pub fn liquidation_count(&self) -> usize {
+        self.liquidations.len()
+    }
+
+    /// Clear all liquidations
+    pub fn clear(&mut self) {
+        self.liquidations.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Price, Symbol};
+
+    fn liquidation(side: OrderSide, price: &str, size: &str, timestamp: u64) -> Liquidation {
+        Liquidation {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance_futures".to_string(),
+            side,
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str(size).unwrap(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_liquidation_volumes() {
+        let mut tracker = LiquidationTracker::new(60_000);
+        tracker.add_liquidation(liquidation(OrderSide::Sell, "100", "3", 1_000));
+        tracker.add_liquidation(liquidation(OrderSide::Buy, "102", "1", 2_000));
+
+        assert_eq!(
+            tracker.long_liquidation_volume(),
+            Size::from_str("3").unwrap()
+        );
+        assert_eq!(
+            tracker.short_liquidation_volume(),
+            Size::from_str("1").unwrap()
+        );
+        assert_eq!(
+            tracker.liquidation_notional(),
+            Notional::new(Decimal::new(402, 0))
+        );
+        assert!((tracker.imbalance() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_liquidation_window() {
+        let mut tracker = LiquidationTracker::new(10_000);
+        tracker.add_liquidation(liquidation(OrderSide::Sell, "100", "1", 1_000));
+        tracker.add_liquidation(liquidation(OrderSide::Sell, "100", "1", 20_000));
+        assert_eq!(tracker.liquidation_count(), 1);
+
+        tracker.remove_old_liquidations(40_000);
+        assert_eq!(tracker.liquidation_count(), 0);
+        assert_eq!(tracker.imbalance(), 0.0);
+    }
+}
@@ -1,5 +1,24 @@
+pub mod carry_indicators;
+pub mod liquidation_indicators;
+pub mod numeric;
+pub mod order_flow_indicators;
 pub mod orderbook_indicators;
+pub mod technical_indicators;
+pub mod trade_classification;
 pub mod trade_flow_indicators;
 
+pub use carry_indicators::*;
+pub use liquidation_indicators::*;
+pub use numeric::{
+    BookStats, BookStatsSnapshot, ExactBookStats, FastBookStats, Numeric, RollingStats,
+};
+pub use order_flow_indicators::{
+    FlowSnapshot, OrderFlowImbalance, OrderFlowMonitor, SignedTradeFlow, TopOfBook,
+    ToxicFlowFilter, TradeIntensity,
+};
 pub use orderbook_indicators::*;
+pub use technical_indicators::{
+    Atr, Bands, BollingerBands, Chain, Ema, Indicator, RealizedVolatility, RollingVwap, Rsi,
+};
+pub use trade_classification::*;
 pub use trade_flow_indicators::*;
@@ -0,0 +1,350 @@
+use crate::orderbook::OrderBook;
+use crate::types::{Price, Size};
+use rust_decimal::prelude::*;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// Number type for book and indicator math
+///
+/// `Decimal` gives results exact to the exchange's precision, as live trading needs.
+/// `f64` is many times faster and is accurate enough for large backtest replays where
+/// only the signal matters.
+pub trait Numeric:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// Zero
+    fn zero() -> Self;
+    /// Convert from a decimal
+    fn from_decimal(value: Decimal) -> Self;
+    /// Convert from a count
+    fn from_count(value: usize) -> Self;
+    /// Convert to f64
+    fn to_f64(self) -> f64;
+    /// Square root (negative values give zero)
+    fn sqrt(self) -> Self;
+    /// Natural logarithm (non-positive values give zero)
+    fn ln(self) -> Self;
+
+    /// Convert from a price
+    fn from_price(price: Price) -> Self {
+        Self::from_decimal(price.value())
+    }
+
+    /// Convert from a size
+    fn from_size(size: Size) -> Self {
+        Self::from_decimal(size.value())
+    }
+}
+
+impl Numeric for Decimal {
+    fn zero() -> Self {
+        Decimal::ZERO
+    }
+
+    fn from_decimal(value: Decimal) -> Self {
+        value
+    }
+
+    fn from_count(value: usize) -> Self {
+        Decimal::from(value)
+    }
+
+    fn to_f64(self) -> f64 {
+        ToPrimitive::to_f64(&self).unwrap_or(0.0)
+    }
+
+    fn sqrt(self) -> Self {
+        // Without the maths feature, go through f64 as the indicators always have
+        Decimal::from_f64(Numeric::to_f64(self).max(0.0).sqrt()).unwrap_or(Decimal::ZERO)
+    }
+
+    fn ln(self) -> Self {
+        Decimal::from_f64(Numeric::ln(Numeric::to_f64(self))).unwrap_or(Decimal::ZERO)
+    }
+}
+
+impl Numeric for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn from_decimal(value: Decimal) -> Self {
+        ToPrimitive::to_f64(&value).unwrap_or(0.0)
+    }
+
+    fn from_count(value: usize) -> Self {
+        value as f64
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self.max(0.0))
+    }
+
+    fn ln(self) -> Self {
+        if self > 0.0 {
+            f64::ln(self)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Mid price of the book
+pub fn mid_price<N: Numeric>(order_book: &OrderBook) -> Option<N> {
+    let (bid, _) = order_book.best_bid()?;
+    let (ask, _) = order_book.best_ask()?;
+    Some((N::from_price(bid) + N::from_price(ask)) / N::from_count(2))
+}
+
+/// Size-weighted mid price of the top of book, leaning toward the thinner side
+pub fn micro_price<N: Numeric>(order_book: &OrderBook) -> Option<N> {
+    let (bid, bid_size) = order_book.best_bid()?;
+    let (ask, ask_size) = order_book.best_ask()?;
+    let (bid_size, ask_size) = (N::from_size(bid_size), N::from_size(ask_size));
+    let total = bid_size + ask_size;
+    if total <= N::zero() {
+        return mid_price(order_book);
+    }
+    Some((N::from_price(bid) * ask_size + N::from_price(ask) * bid_size) / total)
+}
+
+/// Total size of a set of levels
+pub fn depth<N: Numeric>(levels: &[(Price, Size)]) -> N {
+    levels
+        .iter()
+        .fold(N::zero(), |acc, (_, size)| acc + N::from_size(*size))
+}
+
+/// Volume imbalance over the top levels, from -1 (all asks) to 1 (all bids)
+pub fn imbalance<N: Numeric>(order_book: &OrderBook, levels: usize) -> Option<N> {
+    let bids = order_book.top_bids(levels);
+    let asks = order_book.top_asks(levels);
+    if bids.is_empty() || asks.is_empty() {
+        return None;
+    }
+    let bid_depth: N = depth(&bids);
+    let ask_depth: N = depth(&asks);
+    let total = bid_depth + ask_depth;
+    if total <= N::zero() {
+        return Some(N::zero());
+    }
+    Some((bid_depth - ask_depth) / total)
+}
+
+/// Rolling mean and standard deviation over a fixed window
+///
+/// The running sum makes each update O(1). It is rebuilt from the window once per
+/// window length so that f64 rounding cannot accumulate over a long replay.
+#[derive(Debug, Clone)]
+pub struct RollingStats<N: Numeric> {
+    window_size: usize,
+    values: VecDeque<N>,
+    sum: N,
+    updates_since_rebuild: usize,
+}
+
+impl<N: Numeric> RollingStats<N> {
+    /// Create new rolling stats with the specified window size
+    pub fn new(window_size: usize) -> Self {
+        let window_size = window_size.max(1);
+        Self {
+            window_size,
+            values: VecDeque::with_capacity(window_size + 1),
+            sum: N::zero(),
+            updates_since_rebuild: 0,
+        }
+    }
+
+    /// Add a value, dropping the oldest once the window is full
+    pub fn push(&mut self, value: N) {
+        self.values.push_back(value);
+        self.sum = self.sum + value;
+        if self.values.len() > self.window_size {
+            if let Some(oldest) = self.values.pop_front() {
+                self.sum = self.sum - oldest;
+            }
+        }
+
+        self.updates_since_rebuild += 1;
+        if self.updates_since_rebuild >= self.window_size {
+            self.sum = self.values.iter().fold(N::zero(), |acc, &v| acc + v);
+            self.updates_since_rebuild = 0;
+        }
+    }
+
+    /// Number of values in the window
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if no values have been added
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Mean of the window
+    pub fn mean(&self) -> Option<N> {
+        if self.values.is_empty() {
+            return None;
+        }
+        Some(self.sum / N::from_count(self.values.len()))
+    }
+
+    /// Population variance of the window (needs at least 2 values)
+    pub fn variance(&self) -> Option<N> {
+        if self.values.len() < 2 {
+            return None;
+        }
+        // Two passes around the mean, as sum-of-squares loses f64 precision at price scale
+        let mean = self.mean()?;
+        let squares = self.values.iter().fold(N::zero(), |acc, &v| {
+            let diff = v - mean;
+            acc + diff * diff
+        });
+        Some(squares / N::from_count(self.values.len()))
+    }
+
+    /// Standard deviation of the window (needs at least 2 values)
+    pub fn std_dev(&self) -> Option<N> {
+        self.variance().map(N::sqrt)
+    }
+
+    /// Get the window size
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+}
+
+/// Book indicator values after an update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookStatsSnapshot<N: Numeric> {
+    pub mid_price: N,
+    pub micro_price: N,
+    pub imbalance: N,
+    pub mean_mid_price: N,
+    pub volatility: Option<N>,
+}
+
+/// Order book indicators generic over the number type
+///
+/// Use `ExactBookStats` where results feed trading decisions and `FastBookStats` for
+/// large replays.
+#[derive(Debug, Clone)]
+pub struct BookStats<N: Numeric> {
+    levels: usize,
+    mid_prices: RollingStats<N>,
+}
+
+/// Book indicators computed with decimals
+pub type ExactBookStats = BookStats<Decimal>;
+
+/// Book indicators computed with f64
+pub type FastBookStats = BookStats<f64>;
+
+impl<N: Numeric> BookStats<N> {
+    /// Create new book stats over `levels` levels and a window of `window_size` updates
+    pub fn new(levels: usize, window_size: usize) -> Self {
+        Self {
+            levels,
+            mid_prices: RollingStats::new(window_size),
+        }
+    }
+
+    /// Update with a new order book
+    /// Returns None while either side of the book is empty
+    pub fn update(&mut self, order_book: &OrderBook) -> Option<BookStatsSnapshot<N>> {
+        let mid = mid_price::<N>(order_book)?;
+        self.mid_prices.push(mid);
+        Some(BookStatsSnapshot {
+            mid_price: mid,
+            micro_price: micro_price(order_book)?,
+            imbalance: imbalance(order_book, self.levels)?,
+            mean_mid_price: self.mid_prices.mean()?,
+            volatility: self.mid_prices.std_dev(),
+        })
+    }
+
+    /// Rolling mid price statistics
+    pub fn mid_prices(&self) -> &RollingStats<N> {
+        &self.mid_prices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{OrderBookLevel, OrderBookSnapshot};
+
+    /// Deterministic random walk book, so both number types replay identical input
+    fn replay_books(count: usize) -> Vec<OrderBook> {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        let mut mid_cents: i64 = 5_000_000;
+
+        (0..count)
+            .map(|i| {
+                mid_cents += (next() % 201) as i64 - 100;
+                let level = |offset: i64, size: u64| {
+                    OrderBookLevel::new(
+                        Price::new(Decimal::new(mid_cents + offset, 2)),
+                        Size::new(Decimal::new(size as i64, 3)),
+                    )
+                };
+                let bids = (1..=5).map(|l| level(-l, next() % 5000 + 1)).collect();
+                let asks = (1..=5).map(|l| level(l, next() % 5000 + 1)).collect();
+                let mut book = OrderBook::new("BTCUSDT".to_string());
+                book.apply_snapshot(OrderBookSnapshot::new(
+                    "BTCUSDT", "binance", bids, asks, i as u64,
+                ));
+                book
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fast_math_divergence_is_bounded() {
+        let books = replay_books(5000);
+        let mut exact = ExactBookStats::new(5, 200);
+        let mut fast = FastBookStats::new(5, 200);
+
+        let relative = |exact: Decimal, fast: f64| {
+            let exact = Numeric::to_f64(exact);
+            (exact - fast).abs() / exact.abs().max(1.0)
+        };
+
+        for book in &books {
+            let e = exact.update(book).unwrap();
+            let f = fast.update(book).unwrap();
+            assert!(relative(e.mid_price, f.mid_price) < 1e-12);
+            assert!(relative(e.micro_price, f.micro_price) < 1e-12);
+            assert!((Numeric::to_f64(e.imbalance) - f.imbalance).abs() < 1e-12);
+            assert!(relative(e.mean_mid_price, f.mean_mid_price) < 1e-12);
+            if let (Some(ev), Some(fv)) = (e.volatility, f.volatility) {
+                assert!(relative(ev, fv) < 1e-9);
+            }
+        }
+
+        // Agrees with the existing decimal indicators
+        let book = books.last().unwrap();
+        let legacy = crate::indicators::OrderBookImbalance::new(5)
+            .calculate(book)
+            .unwrap();
+        assert!((legacy - imbalance::<f64>(book, 5).unwrap()).abs() < 1e-12);
+    }
+}
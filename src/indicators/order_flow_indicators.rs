@@ -0,0 +1,490 @@
+use super::technical_indicators::Indicator;
+use crate::core::events::{MarketEvent, OrderSide, Trade};
+use crate::orderbook::OrderBook;
+use crate::types::{Price, Size};
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::{HashMap, VecDeque};
+
+fn to_f64(size: Size) -> f64 {
+    size.value().to_f64().unwrap_or(0.0)
+}
+
+/// Best bid and ask at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopOfBook {
+    pub bid_price: Price,
+    pub bid_size: Size,
+    pub ask_price: Price,
+    pub ask_size: Size,
+    pub timestamp: u64,
+}
+
+impl TopOfBook {
+    /// Get the top of an order book, if both sides have a level
+    pub fn from_book(order_book: &OrderBook, timestamp: u64) -> Option<Self> {
+        let (bid_price, bid_size) = order_book.best_bid()?;
+        let (ask_price, ask_size) = order_book.best_ask()?;
+        Some(Self {
+            bid_price,
+            bid_size,
+            ask_price,
+            ask_size,
+            timestamp,
+        })
+    }
+
+    /// Check if the prices and sizes match, ignoring the timestamp
+    pub fn same_quote(&self, other: &TopOfBook) -> bool {
+        self.bid_price == other.bid_price
+            && self.bid_size == other.bid_size
+            && self.ask_price == other.ask_price
+            && self.ask_size == other.ask_size
+    }
+}
+
+/// Values summed over a trailing time window, each update O(1) amortized
+#[derive(Debug, Clone)]
+struct WindowSum {
+    window_ms: u64,
+    /// (timestamp, value, weight)
+    entries: VecDeque<(u64, f64, f64)>,
+    sum: f64,
+    weight: f64,
+    updates_since_rebuild: usize,
+}
+
+impl WindowSum {
+    fn new(window_ms: u64) -> Self {
+        Self {
+            window_ms,
+            entries: VecDeque::new(),
+            sum: 0.0,
+            weight: 0.0,
+            updates_since_rebuild: 0,
+        }
+    }
+
+    fn advance(&mut self, now: u64) {
+        let cutoff = now.saturating_sub(self.window_ms);
+        while let Some(&(time, value, weight)) = self.entries.front() {
+            if time >= cutoff {
+                break;
+            }
+            self.entries.pop_front();
+            self.sum -= value;
+            self.weight -= weight;
+        }
+    }
+
+    fn push(&mut self, timestamp: u64, value: f64, weight: f64) {
+        self.advance(timestamp);
+        self.entries.push_back((timestamp, value, weight));
+        self.sum += value;
+        self.weight += weight;
+
+        // Rebuild once per window length so f64 rounding cannot accumulate
+        self.updates_since_rebuild += 1;
+        if self.updates_since_rebuild >= self.entries.len() {
+            self.sum = self.entries.iter().map(|&(_, value, _)| value).sum();
+            self.weight = self.entries.iter().map(|&(_, _, weight)| weight).sum();
+            self.updates_since_rebuild = 0;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Taker volume over a trailing window, buys positive and sells negative
+/// Its value is the flow ratio, from -1 (all sells) to 1 (all buys).
+#[derive(Debug, Clone)]
+pub struct SignedTradeFlow {
+    /// Signed size, weighted by unsigned size
+    window: WindowSum,
+}
+
+impl SignedTradeFlow {
+    /// Create a trade flow over the last `window_ms` milliseconds
+    pub fn new(window_ms: u64) -> Self {
+        Self {
+            window: WindowSum::new(window_ms),
+        }
+    }
+
+    /// Drop trades older than the window as of `now`
+    pub fn advance(&mut self, now: u64) {
+        self.window.advance(now);
+    }
+
+    /// Get the buy volume minus the sell volume
+    pub fn net_flow(&self) -> f64 {
+        self.window.sum
+    }
+
+    /// Get the traded volume
+    pub fn total_volume(&self) -> f64 {
+        self.window.weight
+    }
+}
+
+impl<'a> Indicator<&'a Trade, f64> for SignedTradeFlow {
+    fn update(&mut self, trade: &'a Trade) -> Option<f64> {
+        let size = to_f64(trade.size);
+        let signed = match trade.side {
+            OrderSide::Buy => size,
+            OrderSide::Sell => -size,
+        };
+        self.window.push(trade.timestamp, signed, size);
+        self.value()
+    }
+
+    fn value(&self) -> Option<f64> {
+        (self.window.weight > 0.0).then(|| self.window.sum / self.window.weight)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.window.window_ms);
+    }
+}
+
+/// Order flow imbalance of the best bid and ask over a trailing window
+///
+/// Each top-of-book change adds the size that joined the bid or left the ask and
+/// subtracts the size that left the bid or joined the ask (Cont, Kukanov and Stoikov).
+/// Its value is the sum divided by the average top-of-book size, so thresholds do not
+/// depend on the symbol's lot size: positive means buying pressure.
+#[derive(Debug, Clone)]
+pub struct OrderFlowImbalance {
+    previous: Option<TopOfBook>,
+    /// OFI contribution, weighted by the mean size of the bid and ask
+    window: WindowSum,
+}
+
+impl OrderFlowImbalance {
+    /// Create an OFI over the last `window_ms` milliseconds
+    pub fn new(window_ms: u64) -> Self {
+        Self {
+            previous: None,
+            window: WindowSum::new(window_ms),
+        }
+    }
+
+    /// Drop changes older than the window as of `now`
+    pub fn advance(&mut self, now: u64) {
+        self.window.advance(now);
+    }
+
+    /// Get the OFI in base size, not normalized
+    pub fn raw(&self) -> f64 {
+        self.window.sum
+    }
+}
+
+impl Indicator<TopOfBook, f64> for OrderFlowImbalance {
+    fn update(&mut self, top: TopOfBook) -> Option<f64> {
+        let Some(previous) = self.previous.replace(top) else {
+            return self.value();
+        };
+        if previous.same_quote(&top) {
+            return self.value();
+        }
+
+        let mut flow = 0.0;
+        if top.bid_price >= previous.bid_price {
+            flow += to_f64(top.bid_size);
+        }
+        if top.bid_price <= previous.bid_price {
+            flow -= to_f64(previous.bid_size);
+        }
+        if top.ask_price <= previous.ask_price {
+            flow -= to_f64(top.ask_size);
+        }
+        if top.ask_price >= previous.ask_price {
+            flow += to_f64(previous.ask_size);
+        }
+        let depth = (to_f64(top.bid_size) + to_f64(top.ask_size)) / 2.0;
+        self.window.push(top.timestamp, flow, depth);
+        self.value()
+    }
+
+    fn value(&self) -> Option<f64> {
+        if self.window.len() == 0 || self.window.weight <= 0.0 {
+            return None;
+        }
+        let mean_depth = self.window.weight / self.window.len() as f64;
+        Some(self.window.sum / mean_depth)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.window.window_ms);
+    }
+}
+
+/// Trades per second over a trailing window
+#[derive(Debug, Clone)]
+pub struct TradeIntensity {
+    window_ms: u64,
+    timestamps: VecDeque<u64>,
+}
+
+impl TradeIntensity {
+    /// Create an intensity over the last `window_ms` milliseconds
+    pub fn new(window_ms: u64) -> Self {
+        Self {
+            window_ms: window_ms.max(1),
+            timestamps: VecDeque::new(),
+        }
+    }
+
+    /// Drop trades older than the window as of `now`
+    pub fn advance(&mut self, now: u64) {
+        let cutoff = now.saturating_sub(self.window_ms);
+        while self.timestamps.front().is_some_and(|&time| time < cutoff) {
+            self.timestamps.pop_front();
+        }
+    }
+
+    /// Get the number of trades in the window
+    pub fn trade_count(&self) -> usize {
+        self.timestamps.len()
+    }
+}
+
+impl<'a> Indicator<&'a Trade, f64> for TradeIntensity {
+    fn update(&mut self, trade: &'a Trade) -> Option<f64> {
+        self.advance(trade.timestamp);
+        self.timestamps.push_back(trade.timestamp);
+        self.value()
+    }
+
+    fn value(&self) -> Option<f64> {
+        Some(self.timestamps.len() as f64 * 1000.0 / self.window_ms as f64)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.window_ms);
+    }
+}
+
+/// Order flow of one symbol
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlowSnapshot {
+    /// Taker buy minus sell volume over total volume, from -1 to 1
+    pub flow_ratio: Option<f64>,
+    /// Order flow imbalance in average top-of-book sizes
+    pub order_flow_imbalance: Option<f64>,
+    pub trades_per_second: f64,
+}
+
+/// Order flow indicators of one symbol
+#[derive(Debug, Clone)]
+struct SymbolFlow {
+    book: OrderBook,
+    trade_flow: SignedTradeFlow,
+    ofi: OrderFlowImbalance,
+    intensity: TradeIntensity,
+}
+
+/// Order flow indicators per symbol, fed with market events
+#[derive(Debug, Clone)]
+pub struct OrderFlowMonitor {
+    window_ms: u64,
+    symbols: HashMap<String, SymbolFlow>,
+}
+
+impl OrderFlowMonitor {
+    /// Create a monitor whose indicators look back `window_ms` milliseconds
+    pub fn new(window_ms: u64) -> Self {
+        Self {
+            window_ms,
+            symbols: HashMap::new(),
+        }
+    }
+
+    /// Update the indicators of the event's symbol
+    /// Trades feed the flow and intensity; books and best bid/ask quotes feed the OFI.
+    pub fn on_event(&mut self, event: &MarketEvent) {
+        let window_ms = self.window_ms;
+        let flow = self
+            .symbols
+            .entry(event.symbol().to_string())
+            .or_insert_with(|| SymbolFlow {
+                book: OrderBook::new(event.symbol().to_string()),
+                trade_flow: SignedTradeFlow::new(window_ms),
+                ofi: OrderFlowImbalance::new(window_ms),
+                intensity: TradeIntensity::new(window_ms),
+            });
+
+        let timestamp = event.timestamp();
+        let top = match event {
+            MarketEvent::Trade(trade) => {
+                flow.trade_flow.update(trade);
+                flow.intensity.update(trade);
+                None
+            }
+            MarketEvent::OrderBookSnapshot(snapshot) => {
+                flow.book.apply_snapshot(snapshot.clone());
+                TopOfBook::from_book(&flow.book, timestamp)
+            }
+            MarketEvent::OrderBookDelta(delta) => {
+                flow.book.apply_delta(delta.clone());
+                TopOfBook::from_book(&flow.book, timestamp)
+            }
+            MarketEvent::BestBidAsk(quote) => Some(TopOfBook {
+                bid_price: quote.bid_price,
+                bid_size: quote.bid_size,
+                ask_price: quote.ask_price,
+                ask_size: quote.ask_size,
+                timestamp,
+            }),
+            _ => None,
+        };
+        if let Some(top) = top {
+            flow.ofi.update(top);
+        }
+
+        flow.trade_flow.advance(timestamp);
+        flow.ofi.advance(timestamp);
+        flow.intensity.advance(timestamp);
+    }
+
+    /// Get the order flow of a symbol, if any event has been seen for it
+    pub fn snapshot(&self, symbol: &str) -> Option<FlowSnapshot> {
+        let flow = self.symbols.get(symbol)?;
+        Some(FlowSnapshot {
+            flow_ratio: flow.trade_flow.value(),
+            order_flow_imbalance: flow.ofi.value(),
+            trades_per_second: flow.intensity.value().unwrap_or(0.0),
+        })
+    }
+}
+
+/// Limits beyond which order flow is treated as toxic to resting quotes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToxicFlowFilter {
+    /// Absolute flow ratio above which the side being run over is pulled
+    pub max_flow_ratio: f64,
+    /// Absolute OFI, in average top-of-book sizes, above which the side is pulled
+    pub max_order_flow_imbalance: f64,
+    /// Trade rate above which both sides are pulled
+    pub max_trades_per_second: f64,
+}
+
+impl Default for ToxicFlowFilter {
+    fn default() -> Self {
+        Self {
+            max_flow_ratio: 0.7,
+            max_order_flow_imbalance: 3.0,
+            max_trades_per_second: 50.0,
+        }
+    }
+}
+
+impl ToxicFlowFilter {
+    /// Check if a resting quote on `side` is safe to place
+    /// Buying pressure runs over asks and selling pressure runs over bids.
+    pub fn allows(&self, flow: &FlowSnapshot, side: OrderSide) -> bool {
+        if flow.trades_per_second > self.max_trades_per_second {
+            return false;
+        }
+        let sign = match side {
+            OrderSide::Buy => -1.0,
+            OrderSide::Sell => 1.0,
+        };
+        let against = |value: Option<f64>, limit: f64| value.is_some_and(|v| v * sign > limit);
+        !(against(flow.flow_ratio, self.max_flow_ratio)
+            || against(flow.order_flow_imbalance, self.max_order_flow_imbalance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::BestBidAsk;
+    use crate::types::Symbol;
+
+    fn trade(side: OrderSide, size: &str, timestamp: u64) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str("100").unwrap(),
+            size: Size::from_str(size).unwrap(),
+            side,
+            timestamp,
+            trade_id: None,
+        })
+    }
+
+    fn quote(bid: &str, bid_size: &str, ask: &str, ask_size: &str, timestamp: u64) -> MarketEvent {
+        MarketEvent::BestBidAsk(BestBidAsk {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            bid_price: Price::from_str(bid).unwrap(),
+            bid_size: Size::from_str(bid_size).unwrap(),
+            ask_price: Price::from_str(ask).unwrap(),
+            ask_size: Size::from_str(ask_size).unwrap(),
+            update_id: timestamp,
+            timestamp,
+        })
+    }
+
+    #[test]
+    fn test_order_flow_imbalance() {
+        let top = |bid: &str, bid_size: &str, ask: &str, ask_size: &str| TopOfBook {
+            bid_price: Price::from_str(bid).unwrap(),
+            bid_size: Size::from_str(bid_size).unwrap(),
+            ask_price: Price::from_str(ask).unwrap(),
+            ask_size: Size::from_str(ask_size).unwrap(),
+            timestamp: 0,
+        };
+        let mut ofi = OrderFlowImbalance::new(1_000);
+        assert_eq!(ofi.update(top("100", "2", "101", "2")), None);
+        // Bid size grows by 1 at the same price
+        ofi.update(top("100", "3", "101", "2"));
+        assert_eq!(ofi.raw(), 1.0);
+        // The ask is lifted: the level at 101 (2) is gone and 102 shows 4
+        ofi.update(top("100", "3", "102", "4"));
+        assert_eq!(ofi.raw(), 3.0);
+        // Normalized by the average of (3 + 2) / 2 and (3 + 4) / 2
+        assert_eq!(ofi.value(), Some(1.0));
+    }
+
+    #[test]
+    fn test_monitor_filters_toxic_flow() {
+        let mut monitor = OrderFlowMonitor::new(1_000);
+        monitor.on_event(&quote("100", "1", "101", "1", 0));
+        for i in 0..4 {
+            monitor.on_event(&trade(OrderSide::Buy, "1", 100 + i));
+        }
+        monitor.on_event(&trade(OrderSide::Sell, "1", 200));
+
+        let flow = monitor.snapshot("BTCUSDT").unwrap();
+        assert_eq!(flow.flow_ratio, Some(0.6));
+        assert_eq!(flow.trades_per_second, 5.0);
+
+        // Aggressive buying: asks lifted twice
+        monitor.on_event(&quote("100", "1", "102", "1", 300));
+        monitor.on_event(&quote("101", "1", "103", "1", 400));
+        monitor.on_event(&trade(OrderSide::Buy, "5", 450));
+        let flow = monitor.snapshot("BTCUSDT").unwrap();
+        assert!(flow.flow_ratio.unwrap() > 0.7);
+        assert!(flow.order_flow_imbalance.unwrap() > 0.0);
+
+        let filter = ToxicFlowFilter::default();
+        assert!(!filter.allows(&flow, OrderSide::Sell));
+        assert!(filter.allows(&flow, OrderSide::Buy));
+
+        // Everything leaves the window a second later
+        monitor.on_event(&quote("101", "1", "103", "1", 2_000));
+        let calm = monitor.snapshot("BTCUSDT").unwrap();
+        assert_eq!(calm.flow_ratio, None);
+        assert_eq!(calm.trades_per_second, 0.0);
+        assert!(filter.allows(&calm, OrderSide::Sell));
+        assert!(!ToxicFlowFilter {
+            max_trades_per_second: 4.0,
+            ..filter
+        }
+        .allows(&flow, OrderSide::Buy));
+    }
+}
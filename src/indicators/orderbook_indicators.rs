@@ -1,3 +1,4 @@
+use crate::core::events::BookMetrics;
 use crate::orderbook::OrderBook;
 use crate::types::{Price, Size};
 use rust_decimal::prelude::*;
@@ -25,37 +26,29 @@ impl OrderBookMovingAverage {
 
     /// Update the moving average with a new order book
     pub fn update(&mut self, order_book: &OrderBook) -> Option<Price> {
-        // Calculate mid price
-        let mid_price = match (order_book.best_bid(), order_book.best_ask()) {
-            (Some((bid_price, _)), Some((ask_price, _))) => {
-                // Price - Price returns Price, Price / Price returns Decimal
-                let spread = ask_price - bid_price;
-                let half_spread = Price::new(spread.value() / Decimal::new(2, 0));
-                Some(bid_price + half_spread)
-            }
-            _ => None,
-        };
-
-        if let Some(price) = mid_price {
-            // Add new price to history
-            self.price_history.push_back(price);
-
-            // Remove oldest price if window is full
-            if self.price_history.len() > self.window_size {
-                self.price_history.pop_front();
-            }
-
-            // Calculate new average
-            if !self.price_history.is_empty() {
-                let sum = self
-                    .price_history
-                    .iter()
-                    .fold(Price::new(Decimal::ZERO), |acc, &p| acc + p);
-                // Price / Decimal returns Price
-                self.current_average =
-                    Some(sum / Decimal::from_usize(self.price_history.len()).unwrap());
-            }
+        if let Some(price) = order_book.mid_price() {
+            self.push(price);
         }
+        self.current_average
+    }
+
+    /// Update the moving average with a mid price, e.g. from a `BookMetrics` event
+    pub fn push(&mut self, price: Price) -> Option<Price> {
+        // Add new price to history
+        self.price_history.push_back(price);
+
+        // Remove oldest price if window is full
+        if self.price_history.len() > self.window_size {
+            self.price_history.pop_front();
+        }
+
+        // Calculate new average
+        let sum = self
+            .price_history
+            .iter()
+            .fold(Price::new(Decimal::ZERO), |acc, &p| acc + p);
+        // Price / Decimal returns Price
+        self.current_average = Some(sum / Decimal::from_usize(self.price_history.len()).unwrap());
 
         self.current_average
     }
@@ -86,45 +79,9 @@ impl OrderBookImbalance {
     /// Calculate the order book imbalance
     /// Returns a value between -1.0 (all asks) and 1.0 (all bids)
     pub fn calculate(&self, order_book: &OrderBook) -> Option<f64> {
-        if self.levels == 0 {
-            return None;
-        }
-
-        // Get top bid levels
-        let bids = order_book.top_bids(self.levels);
-        // Get top ask levels
-        let asks = order_book.top_asks(self.levels);
-
-        if bids.is_empty() || asks.is_empty() {
-            return None;
-        }
-
-        // Calculate total bid volume
-        let total_bid_volume = bids
-            .iter()
-            .fold(Size::new(rust_decimal::Decimal::ZERO), |acc, (_, size)| {
-                acc + *size
-            });
-
-        // Calculate total ask volume
-        let total_ask_volume = asks
-            .iter()
-            .fold(Size::new(rust_decimal::Decimal::ZERO), |acc, (_, size)| {
-                acc + *size
-            });
-
-        // Calculate total volume
-        let total_volume = total_bid_volume + total_ask_volume;
-
-        if total_volume.is_zero() {
-            return Some(0.0);
-        }
-
-        // Calculate imbalance: (bid_volume - ask_volume) / total_volume
-        let bid_volume_ratio = total_bid_volume.value() / total_volume.value();
-        let ask_volume_ratio = total_ask_volume.value() / total_volume.value();
-
-        Some(bid_volume_ratio.to_f64().unwrap_or(0.0) - ask_volume_ratio.to_f64().unwrap_or(0.0))
+        order_book
+            .imbalance(self.levels)
+            .map(|imbalance| imbalance.to_f64().unwrap_or(0.0))
     }
 
     /// Get the number of levels considered
@@ -168,34 +125,39 @@ impl OrderBookSpread {
         self.current_spread = order_book.spread();
 
         if let Some(spread) = self.current_spread {
-            // Add new spread to history
-            self.spread_history.push_back(spread);
-
-            // Remove oldest spread if window is full
-            if self.spread_history.len() > self.window_size {
-                self.spread_history.pop_front();
-            }
-
-            // Calculate statistics
-            if !self.spread_history.is_empty() {
-                // Calculate min and max
-                self.min_spread = self.spread_history.iter().min().copied();
-                self.max_spread = self.spread_history.iter().max().copied();
-
-                // Calculate average
-                let sum = self
-                    .spread_history
-                    .iter()
-                    .fold(Price::new(Decimal::ZERO), |acc, &p| acc + p);
-                // Price / Decimal returns Price
-                self.avg_spread =
-                    Some(sum / Decimal::from_usize(self.spread_history.len()).unwrap());
-            }
+            self.push(spread);
         }
 
         self.current_spread
     }
 
+    /// Update the spread indicator with a spread, e.g. from a `BookMetrics` event
+    pub fn push(&mut self, spread: Price) -> Option<Price> {
+        self.current_spread = Some(spread);
+
+        // Add new spread to history
+        self.spread_history.push_back(spread);
+
+        // Remove oldest spread if window is full
+        if self.spread_history.len() > self.window_size {
+            self.spread_history.pop_front();
+        }
+
+        // Calculate min and max
+        self.min_spread = self.spread_history.iter().min().copied();
+        self.max_spread = self.spread_history.iter().max().copied();
+
+        // Calculate average
+        let sum = self
+            .spread_history
+            .iter()
+            .fold(Price::new(Decimal::ZERO), |acc, &p| acc + p);
+        // Price / Decimal returns Price
+        self.avg_spread = Some(sum / Decimal::from_usize(self.spread_history.len()).unwrap());
+
+        self.current_spread
+    }
+
     /// Get the current spread
     pub fn current(&self) -> Option<Price> {
         self.current_spread
@@ -296,48 +258,43 @@ impl OrderBookVolatility {
 
     /// Update the volatility indicator with a new order book
     pub fn update(&mut self, order_book: &OrderBook) -> Option<Price> {
-        // Calculate mid price
-        let mid_price = match (order_book.best_bid(), order_book.best_ask()) {
-            (Some((bid_price, _)), Some((ask_price, _))) => {
-                // Price - Price returns Price, need to use .value() for division
-                let spread = ask_price - bid_price;
-                let half_spread = Price::new(spread.value() / Decimal::new(2, 0));
-                Some(bid_price + half_spread)
-            }
-            _ => None,
-        };
-
-        if let Some(price) = mid_price {
-            // Add new price to history
-            self.price_history.push_back(price);
-
-            // Remove oldest price if window is full
-            if self.price_history.len() > self.window_size {
-                self.price_history.pop_front();
-            }
-
-            // Calculate volatility if we have enough data
-            if self.price_history.len() >= 2 {
-                // Calculate mean
-                let sum = self
-                    .price_history
-                    .iter()
-                    .fold(Price::new(Decimal::ZERO), |acc, &p| acc + p);
-                // Price / Decimal returns Price
-                let mean = sum / Decimal::from_usize(self.price_history.len()).unwrap();
-
-                // Calculate variance
-                let variance = self.price_history.iter().fold(Decimal::ZERO, |acc, &p| {
-                    let diff = p.value() - mean.value();
-                    acc + diff * diff
-                }) / Decimal::from_usize(self.price_history.len()).unwrap();
-
-                // Volatility is the square root of variance (convert to f64 for sqrt)
-                let volatility = variance.to_f64().map(|v| v.sqrt()).unwrap_or(0.0);
-                self.current_volatility = Some(Price::new(
-                    Decimal::from_f64(volatility).unwrap_or(Decimal::ZERO),
-                ));
-            }
+        if let Some(price) = order_book.mid_price() {
+            self.push(price);
+        }
+        self.current_volatility
+    }
+
+    /// Update the volatility indicator with a mid price, e.g. from a `BookMetrics` event
+    pub fn push(&mut self, price: Price) -> Option<Price> {
+        // Add new price to history
+        self.price_history.push_back(price);
+
+        // Remove oldest price if window is full
+        if self.price_history.len() > self.window_size {
+            self.price_history.pop_front();
+        }
+
+        // Calculate volatility if we have enough data
+        if self.price_history.len() >= 2 {
+            // Calculate mean
+            let sum = self
+                .price_history
+                .iter()
+                .fold(Price::new(Decimal::ZERO), |acc, &p| acc + p);
+            // Price / Decimal returns Price
+            let mean = sum / Decimal::from_usize(self.price_history.len()).unwrap();
+
+            // Calculate variance
+            let variance = self.price_history.iter().fold(Decimal::ZERO, |acc, &p| {
+                let diff = p.value() - mean.value();
+                acc + diff * diff
+            }) / Decimal::from_usize(self.price_history.len()).unwrap();
+
+            // Volatility is the square root of variance (convert to f64 for sqrt)
+            let volatility = variance.to_f64().map(|v| v.sqrt()).unwrap_or(0.0);
+            self.current_volatility = Some(Price::new(
+                Decimal::from_f64(volatility).unwrap_or(Decimal::ZERO),
+            ));
         }
 
         self.current_volatility
@@ -393,6 +350,14 @@ impl OrderBookIndicators {
         self.volatility.update(order_book);
     }
 
+    /// Update the windowed indicators from a derived `BookMetrics` event
+    /// Avoids recomputing the mid and spread from a local copy of the book.
+    pub fn update_metrics(&mut self, metrics: &BookMetrics) {
+        self.moving_average.push(metrics.mid_price);
+        self.spread.push(metrics.best_ask - metrics.best_bid);
+        self.volatility.push(metrics.mid_price);
+    }
+
     /// Get the current moving average
     pub fn moving_average(&self) -> Option<Price> {
         self.moving_average.current()
@@ -594,5 +559,17 @@ mod tests {
             Size::from_str("46.0").unwrap()
         ); // 23.0 + 23.0
         assert_eq!(indicators.depth_ratio(&book), Some(0.5)); // Equal bid and ask depth
+
+        // Derived metrics feed the same windows as the book they came from
+        let mut from_metrics = OrderBookIndicators::new(5, 3, 5, 3, 5);
+        let metrics = book
+            .metrics(
+                "test_exchange",
+                &crate::orderbook::BookMetricsConfig::default(),
+            )
+            .unwrap();
+        from_metrics.update_metrics(&metrics);
+        assert_eq!(from_metrics.moving_average(), indicators.moving_average());
+        assert_eq!(from_metrics.spread(), indicators.spread());
     }
 }
@@ -0,0 +1,661 @@
+use super::numeric::Numeric;
+use crate::core::events::{Candle, Trade};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+/// Indicator updated one input at a time
+pub trait Indicator<Input, Output> {
+    /// Feed one input; returns the new value once enough inputs have been seen
+    fn update(&mut self, input: Input) -> Option<Output>;
+
+    /// Get the latest value
+    fn value(&self) -> Option<Output>;
+
+    /// Clear all state
+    fn reset(&mut self);
+
+    /// Check if enough inputs have been seen to produce a value
+    fn is_ready(&self) -> bool {
+        self.value().is_some()
+    }
+
+    /// Feed a series of inputs, e.g. closes of historical candles, and get the last value
+    fn warm_up<I>(&mut self, inputs: I) -> Option<Output>
+    where
+        I: IntoIterator<Item = Input>,
+        Self: Sized,
+    {
+        for input in inputs {
+            self.update(input);
+        }
+        self.value()
+    }
+
+    /// Feed every value of this indicator into `next` (builder pattern)
+    fn chain<Next>(self, next: Next) -> Chain<Self, Next, Output>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: next,
+            _output: PhantomData,
+        }
+    }
+}
+
+/// Two indicators where the first one's values are the second one's inputs
+#[derive(Debug, Clone)]
+pub struct Chain<First, Second, Mid> {
+    first: First,
+    second: Second,
+    _output: PhantomData<fn() -> Mid>,
+}
+
+impl<Input, Mid, Output, First, Second> Indicator<Input, Output> for Chain<First, Second, Mid>
+where
+    First: Indicator<Input, Mid>,
+    Second: Indicator<Mid, Output>,
+{
+    fn update(&mut self, input: Input) -> Option<Output> {
+        let mid = self.first.update(input)?;
+        self.second.update(mid)
+    }
+
+    fn value(&self) -> Option<Output> {
+        self.second.value()
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+}
+
+fn abs<N: Numeric>(value: N) -> N {
+    if value < N::zero() {
+        N::zero() - value
+    } else {
+        value
+    }
+}
+
+fn max<N: Numeric>(a: N, b: N) -> N {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Exponential moving average, seeded with the simple average of the first `period` inputs
+#[derive(Debug, Clone)]
+pub struct Ema<N: Numeric> {
+    period: usize,
+    alpha: N,
+    count: usize,
+    seed_sum: N,
+    value: Option<N>,
+}
+
+impl<N: Numeric> Ema<N> {
+    /// Create an EMA with smoothing factor 2 / (period + 1)
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            alpha: N::from_count(2) / N::from_count(period + 1),
+            count: 0,
+            seed_sum: N::zero(),
+            value: None,
+        }
+    }
+}
+
+impl<N: Numeric> Indicator<N, N> for Ema<N> {
+    fn update(&mut self, input: N) -> Option<N> {
+        match self.value {
+            Some(ema) => self.value = Some(ema + self.alpha * (input - ema)),
+            None => {
+                self.count += 1;
+                self.seed_sum = self.seed_sum + input;
+                if self.count == self.period {
+                    self.value = Some(self.seed_sum / N::from_count(self.period));
+                }
+            }
+        }
+        self.value
+    }
+
+    fn value(&self) -> Option<N> {
+        self.value
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+}
+
+/// Relative strength index from 0 to 100, with Wilder's smoothing
+/// Needs `period + 1` prices before the first value.
+#[derive(Debug, Clone)]
+pub struct Rsi<N: Numeric> {
+    period: usize,
+    previous: Option<N>,
+    count: usize,
+    gain_sum: N,
+    loss_sum: N,
+    /// Average gain and loss once seeded
+    averages: Option<(N, N)>,
+}
+
+impl<N: Numeric> Rsi<N> {
+    /// Create an RSI over `period` price changes
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            previous: None,
+            count: 0,
+            gain_sum: N::zero(),
+            loss_sum: N::zero(),
+            averages: None,
+        }
+    }
+}
+
+impl<N: Numeric> Indicator<N, N> for Rsi<N> {
+    fn update(&mut self, input: N) -> Option<N> {
+        let previous = self.previous.replace(input)?;
+        let change = input - previous;
+        let gain = max(change, N::zero());
+        let loss = max(N::zero() - change, N::zero());
+        let period = N::from_count(self.period);
+
+        self.averages = match self.averages {
+            Some((avg_gain, avg_loss)) => {
+                let weight = N::from_count(self.period - 1);
+                Some((
+                    (avg_gain * weight + gain) / period,
+                    (avg_loss * weight + loss) / period,
+                ))
+            }
+            None => {
+                self.count += 1;
+                self.gain_sum = self.gain_sum + gain;
+                self.loss_sum = self.loss_sum + loss;
+                (self.count == self.period)
+                    .then(|| (self.gain_sum / period, self.loss_sum / period))
+            }
+        };
+        self.value()
+    }
+
+    fn value(&self) -> Option<N> {
+        let (avg_gain, avg_loss) = self.averages?;
+        let hundred = N::from_count(100);
+        if avg_loss <= N::zero() {
+            // No losses: 100, or neutral if the price did not move at all
+            return Some(if avg_gain <= N::zero() {
+                N::from_count(50)
+            } else {
+                hundred
+            });
+        }
+        Some(hundred - hundred / (N::from_count(1) + avg_gain / avg_loss))
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+}
+
+/// Average true range of candles, with Wilder's smoothing
+#[derive(Debug, Clone)]
+pub struct Atr<N: Numeric> {
+    period: usize,
+    previous_close: Option<N>,
+    count: usize,
+    range_sum: N,
+    value: Option<N>,
+}
+
+impl<N: Numeric> Atr<N> {
+    /// Create an ATR over `period` candles
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            previous_close: None,
+            count: 0,
+            range_sum: N::zero(),
+            value: None,
+        }
+    }
+}
+
+impl<'a, N: Numeric> Indicator<&'a Candle, N> for Atr<N> {
+    fn update(&mut self, candle: &'a Candle) -> Option<N> {
+        let high = N::from_price(candle.high);
+        let low = N::from_price(candle.low);
+        let true_range = match self.previous_close {
+            Some(close) => max(high - low, max(abs(high - close), abs(low - close))),
+            None => high - low,
+        };
+        self.previous_close = Some(N::from_price(candle.close));
+
+        match self.value {
+            Some(atr) => {
+                self.value = Some(
+                    (atr * N::from_count(self.period - 1) + true_range)
+                        / N::from_count(self.period),
+                )
+            }
+            None => {
+                self.count += 1;
+                self.range_sum = self.range_sum + true_range;
+                if self.count == self.period {
+                    self.value = Some(self.range_sum / N::from_count(self.period));
+                }
+            }
+        }
+        self.value
+    }
+
+    fn value(&self) -> Option<N> {
+        self.value
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+}
+
+/// Bollinger bands of one update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bands<N: Numeric> {
+    pub upper: N,
+    pub middle: N,
+    pub lower: N,
+}
+
+impl<N: Numeric> Bands<N> {
+    /// Position of a price within the bands: 0 at the lower band, 1 at the upper band
+    pub fn percent_b(&self, price: N) -> Option<N> {
+        let width = self.upper - self.lower;
+        if width <= N::zero() {
+            return None;
+        }
+        Some((price - self.lower) / width)
+    }
+}
+
+/// Moving average with bands `multiplier` standard deviations above and below
+///
+/// Mean and variance are updated in O(1) as values enter and leave the window, and
+/// rebuilt from the window once per window length so f64 rounding cannot accumulate.
+#[derive(Debug, Clone)]
+pub struct BollingerBands<N: Numeric> {
+    period: usize,
+    multiplier: N,
+    values: VecDeque<N>,
+    mean: N,
+    /// Sum of squared deviations from the mean
+    m2: N,
+    updates_since_rebuild: usize,
+}
+
+impl<N: Numeric> BollingerBands<N> {
+    /// Create bands over `period` values, usually 20 with a multiplier of 2
+    pub fn new(period: usize, multiplier: N) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            multiplier,
+            values: VecDeque::with_capacity(period + 1),
+            mean: N::zero(),
+            m2: N::zero(),
+            updates_since_rebuild: 0,
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let count = N::from_count(self.values.len());
+        self.mean = self.values.iter().fold(N::zero(), |acc, &v| acc + v) / count;
+        let mean = self.mean;
+        self.m2 = self.values.iter().fold(N::zero(), |acc, &v| {
+            let diff = v - mean;
+            acc + diff * diff
+        });
+        self.updates_since_rebuild = 0;
+    }
+}
+
+impl<N: Numeric> Indicator<N, Bands<N>> for BollingerBands<N> {
+    fn update(&mut self, input: N) -> Option<Bands<N>> {
+        self.values.push_back(input);
+        if self.values.len() > self.period {
+            // Replace the oldest value with the new one in a window of fixed length
+            let oldest = self.values.pop_front().unwrap_or(input);
+            let old_mean = self.mean;
+            self.mean = old_mean + (input - oldest) / N::from_count(self.period);
+            self.m2 = self.m2 + (input - oldest) * (input - self.mean + oldest - old_mean);
+        } else {
+            let delta = input - self.mean;
+            self.mean = self.mean + delta / N::from_count(self.values.len());
+            self.m2 = self.m2 + delta * (input - self.mean);
+        }
+
+        self.updates_since_rebuild += 1;
+        if self.updates_since_rebuild >= self.period {
+            self.rebuild();
+        }
+        self.value()
+    }
+
+    fn value(&self) -> Option<Bands<N>> {
+        if self.values.len() < self.period {
+            return None;
+        }
+        let variance = max(self.m2, N::zero()) / N::from_count(self.period);
+        let width = self.multiplier * variance.sqrt();
+        Some(Bands {
+            upper: self.mean + width,
+            middle: self.mean,
+            lower: self.mean - width,
+        })
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.period, self.multiplier);
+    }
+}
+
+/// Volume-weighted average price over a trailing time window
+///
+/// Takes trades, or candles weighted by their quote volume.
+#[derive(Debug, Clone)]
+pub struct RollingVwap<N: Numeric> {
+    window_ms: u64,
+    /// (timestamp, notional, volume)
+    entries: VecDeque<(u64, N, N)>,
+    notional: N,
+    volume: N,
+    updates_since_rebuild: usize,
+}
+
+impl<N: Numeric> RollingVwap<N> {
+    /// Create a VWAP over the last `window_ms` milliseconds
+    pub fn new(window_ms: u64) -> Self {
+        Self {
+            window_ms,
+            entries: VecDeque::new(),
+            notional: N::zero(),
+            volume: N::zero(),
+            updates_since_rebuild: 0,
+        }
+    }
+
+    fn push(&mut self, timestamp: u64, notional: N, volume: N) -> Option<N> {
+        let cutoff = timestamp.saturating_sub(self.window_ms);
+        while let Some(&(time, old_notional, old_volume)) = self.entries.front() {
+            if time >= cutoff {
+                break;
+            }
+            self.entries.pop_front();
+            self.notional = self.notional - old_notional;
+            self.volume = self.volume - old_volume;
+        }
+        self.entries.push_back((timestamp, notional, volume));
+        self.notional = self.notional + notional;
+        self.volume = self.volume + volume;
+
+        self.updates_since_rebuild += 1;
+        if self.updates_since_rebuild >= self.entries.len() {
+            let (notional, volume) = self
+                .entries
+                .iter()
+                .fold((N::zero(), N::zero()), |(n, v), &(_, en, ev)| {
+                    (n + en, v + ev)
+                });
+            self.notional = notional;
+            self.volume = volume;
+            self.updates_since_rebuild = 0;
+        }
+        self.current()
+    }
+
+    fn current(&self) -> Option<N> {
+        (self.volume > N::zero()).then(|| self.notional / self.volume)
+    }
+
+    /// Get the volume traded in the window
+    pub fn volume(&self) -> N {
+        self.volume
+    }
+}
+
+impl<'a, N: Numeric> Indicator<&'a Trade, N> for RollingVwap<N> {
+    fn update(&mut self, trade: &'a Trade) -> Option<N> {
+        let price = N::from_price(trade.price);
+        let size = N::from_size(trade.size);
+        self.push(trade.timestamp, price * size, size)
+    }
+
+    fn value(&self) -> Option<N> {
+        self.current()
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.window_ms);
+    }
+}
+
+impl<'a, N: Numeric> Indicator<&'a Candle, N> for RollingVwap<N> {
+    fn update(&mut self, candle: &'a Candle) -> Option<N> {
+        self.push(
+            candle.close_time,
+            N::from_decimal(candle.quote_volume),
+            N::from_size(candle.volume),
+        )
+    }
+
+    fn value(&self) -> Option<N> {
+        self.current()
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.window_ms);
+    }
+}
+
+/// Realized volatility: root mean square of the log returns over the last `period` prices
+#[derive(Debug, Clone)]
+pub struct RealizedVolatility<N: Numeric> {
+    period: usize,
+    previous: Option<N>,
+    returns: VecDeque<N>,
+    sum_squares: N,
+    updates_since_rebuild: usize,
+    /// Square root of the periods per year, if annualized
+    annualization: Option<N>,
+}
+
+impl<N: Numeric> RealizedVolatility<N> {
+    /// Create a volatility over `period` returns, per return interval
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            previous: None,
+            returns: VecDeque::with_capacity(period + 1),
+            sum_squares: N::zero(),
+            updates_since_rebuild: 0,
+            annualization: None,
+        }
+    }
+
+    /// Annualize, given the number of return intervals per year, e.g. 525600 for
+    /// minute closes of a market that never closes (builder pattern)
+    pub fn with_periods_per_year(mut self, periods: usize) -> Self {
+        self.annualization = Some(N::from_count(periods).sqrt());
+        self
+    }
+}
+
+impl<N: Numeric> Indicator<N, N> for RealizedVolatility<N> {
+    fn update(&mut self, input: N) -> Option<N> {
+        let previous = self.previous.replace(input)?;
+        if previous <= N::zero() || input <= N::zero() {
+            return self.value();
+        }
+        let log_return = (input / previous).ln();
+        self.returns.push_back(log_return);
+        self.sum_squares = self.sum_squares + log_return * log_return;
+        if self.returns.len() > self.period {
+            if let Some(oldest) = self.returns.pop_front() {
+                self.sum_squares = self.sum_squares - oldest * oldest;
+            }
+        }
+
+        self.updates_since_rebuild += 1;
+        if self.updates_since_rebuild >= self.period {
+            self.sum_squares = self.returns.iter().fold(N::zero(), |acc, &r| acc + r * r);
+            self.updates_since_rebuild = 0;
+        }
+        self.value()
+    }
+
+    fn value(&self) -> Option<N> {
+        if self.returns.len() < self.period {
+            return None;
+        }
+        let volatility = (max(self.sum_squares, N::zero()) / N::from_count(self.period)).sqrt();
+        Some(match self.annualization {
+            Some(scale) => volatility * scale,
+            None => volatility,
+        })
+    }
+
+    fn reset(&mut self) {
+        let annualization = self.annualization;
+        *self = Self::new(self.period);
+        self.annualization = annualization;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{CandleInterval, OrderSide};
+    use crate::indicators::RollingStats;
+    use crate::types::{Price, Size, Symbol};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn candle(open_time: u64, high: &str, low: &str, close: &str, volume: &str) -> Candle {
+        Candle {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            interval: CandleInterval::OneMinute,
+            open_time,
+            close_time: open_time + 59_999,
+            open: Price::from_str(close).unwrap(),
+            high: Price::from_str(high).unwrap(),
+            low: Price::from_str(low).unwrap(),
+            close: Price::from_str(close).unwrap(),
+            volume: Size::from_str(volume).unwrap(),
+            quote_volume: Decimal::from_str(close).unwrap() * Decimal::from_str(volume).unwrap(),
+            trade_count: 1,
+            closed: true,
+            timestamp: open_time + 59_999,
+        }
+    }
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_ema_rsi_and_atr() {
+        let mut ema = Ema::<Decimal>::new(3);
+        assert_eq!(ema.update(dec("1")), None);
+        assert_eq!(ema.update(dec("2")), None);
+        assert_eq!(ema.update(dec("3")), Some(dec("2")));
+        assert_eq!(ema.update(dec("4")), Some(dec("3")));
+        assert_eq!(ema.update(dec("5")), Some(dec("4")));
+
+        let mut rsi = Rsi::<Decimal>::new(2);
+        assert_eq!(rsi.warm_up([dec("1"), dec("2")]), None);
+        assert_eq!(rsi.update(dec("3")), Some(dec("100")));
+        assert_eq!(rsi.update(dec("2")), Some(dec("50")));
+        rsi.reset();
+        assert!(!rsi.is_ready());
+
+        // Warm up from candles; the gap up from 10 to 13 widens the second true range
+        let candles = [
+            candle(0, "11", "9", "10", "1"),
+            candle(60_000, "14", "12.5", "13", "1"),
+            candle(120_000, "13.5", "12.5", "13", "1"),
+        ];
+        let mut atr = Atr::<Decimal>::new(2);
+        assert_eq!(atr.warm_up(&candles[..2]), Some(dec("3")));
+        assert_eq!(atr.update(&candles[2]), Some(dec("2")));
+
+        // Indicators compose: an EMA of the RSI
+        let mut smoothed_rsi = Rsi::<f64>::new(2).chain(Ema::new(2));
+        let closes = candles.iter().map(|c| f64::from_price(c.close));
+        assert_eq!(smoothed_rsi.warm_up(closes), None);
+        assert_eq!(smoothed_rsi.update(14.0), Some(100.0));
+    }
+
+    #[test]
+    fn test_bollinger_tracks_rolling_stats() {
+        let mut bands = BollingerBands::<f64>::new(20, 2.0);
+        let mut reference = RollingStats::<f64>::new(20);
+        let mut price = 50_000.0;
+        for i in 0..2_000u64 {
+            price += ((i * 7_919) % 201) as f64 - 100.0;
+            reference.push(price);
+            match bands.update(price) {
+                Some(b) => {
+                    let sd = reference.std_dev().unwrap();
+                    assert!((b.middle - reference.mean().unwrap()).abs() < 1e-6);
+                    assert!((b.upper - (b.middle + 2.0 * sd)).abs() < 1e-6);
+                    assert!((b.percent_b(b.middle).unwrap() - 0.5).abs() < 1e-9);
+                }
+                None => assert!(i < 19),
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_vwap_and_realized_volatility() {
+        let trade = |price: &str, size: &str, timestamp: u64| Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str(size).unwrap(),
+            side: OrderSide::Buy,
+            timestamp,
+            trade_id: None,
+        };
+        let mut vwap = RollingVwap::<Decimal>::new(1_000);
+        vwap.update(&trade("100", "1", 0));
+        assert_eq!(vwap.update(&trade("103", "2", 500)), Some(dec("102")));
+        // The first trade leaves the window
+        assert_eq!(vwap.update(&trade("106", "2", 1_200)), Some(dec("104.5")));
+        assert_eq!(vwap.volume(), dec("4"));
+
+        let mut candle_vwap = RollingVwap::<Decimal>::new(120_000);
+        candle_vwap.update(&candle(0, "101", "99", "100", "3"));
+        assert_eq!(
+            candle_vwap.update(&candle(60_000, "111", "109", "110", "1")),
+            Some(dec("102.5"))
+        );
+
+        // Steady 1% moves have a volatility of ln(1.01) per interval
+        let mut volatility = RealizedVolatility::<f64>::new(10).with_periods_per_year(4);
+        let prices = (0..=10).map(|i| 100.0 * 1.01f64.powi(if i % 2 == 0 { 0 } else { 1 }));
+        let annualized = volatility.warm_up(prices).unwrap();
+        assert!((annualized - 2.0 * 1.01f64.ln()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::core::events::{OrderSide, Trade};
+use crate::orderbook::OrderBook;
+use crate::types::Price;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+
+/// How the aggressor side of a trade was determined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggressorSource {
+    /// Supplied by the venue
+    Venue,
+    /// Quote rule: trade price compared to the prevailing mid
+    Quote,
+    /// Tick rule: trade price compared to the previous trade
+    Tick,
+    /// Not enough information; the side reported by the adapter is kept
+    Unknown,
+}
+
+/// Per-book classification state
+#[derive(Debug, Default)]
+struct BookState {
+    /// Latest best bid and ask
+    quote: Option<(Price, Price)>,
+    /// Last trade price
+    last_price: Option<Price>,
+    /// Direction of the last non-zero tick
+    last_tick: Option<OrderSide>,
+}
+
+/// Aggressor side classifier for venues whose trade feeds carry no taker flag
+///
+/// Uses the Lee-Ready approach: trades above the mid are buys and below the mid are
+/// sells; trades at the mid (or without a quote) fall back to the tick rule, where an
+/// uptick is a buy, a downtick a sell and a zero tick repeats the last non-zero tick.
+pub struct AggressorClassifier {
+    /// Exchanges whose trade side must be inferred
+    inferred_venues: HashSet<String>,
+    /// Classification state keyed by "exchange:symbol"
+    books: HashMap<String, BookState>,
+}
+
+impl AggressorClassifier {
+    /// Create a new classifier
+    pub fn new() -> Self {
+        Self {
+            inferred_venues: HashSet::new(),
+            books: HashMap::new(),
+        }
+    }
+
+    /// Infer the aggressor side for trades from this exchange (builder pattern)
+    pub fn with_inferred_venue(mut self, exchange_id: impl Into<String>) -> Self {
+        self.inferred_venues.insert(exchange_id.into());
+        self
+    }
+
+    /// Check if trades from an exchange need their side inferred
+    pub fn needs_inference(&self, exchange_id: &str) -> bool {
+        self.inferred_venues.contains(exchange_id)
+    }
+
+    fn key(exchange_id: &str, symbol: &str) -> String {
+        format!("{}:{}", exchange_id, symbol)
+    }
+
+    /// Update the prevailing quote of a book
+    pub fn update_quote(&mut self, exchange_id: &str, symbol: &str, bid: Price, ask: Price) {
+        self.books
+            .entry(Self::key(exchange_id, symbol))
+            .or_default()
+            .quote = Some((bid, ask));
+    }
+
+    /// Update the prevailing quote from an order book
+    pub fn update_from_book(&mut self, exchange_id: &str, book: &OrderBook) {
+        if let (Some((bid, _)), Some((ask, _))) = (book.best_bid(), book.best_ask()) {
+            self.update_quote(exchange_id, book.symbol(), bid, ask);
+        }
+    }
+
+    /// Infer the aggressor side of a trade without updating state
+    pub fn infer(&self, trade: &Trade) -> (OrderSide, AggressorSource) {
+        let state = self
+            .books
+            .get(&Self::key(&trade.exchange_id, trade.symbol.as_str()));
+
+        if let Some((bid, ask)) = state.and_then(|s| s.quote) {
+            // Crossed or locked books carry no information
+            if bid < ask {
+                let mid = (bid.value() + ask.value()) / Decimal::TWO;
+                if trade.price.value() > mid {
+                    return (OrderSide::Buy, AggressorSource::Quote);
+                }
+                if trade.price.value() < mid {
+                    return (OrderSide::Sell, AggressorSource::Quote);
+                }
+            }
+        }
+
+        if let Some(state) = state {
+            let tick = match state.last_price {
+                Some(last) if trade.price > last => Some(OrderSide::Buy),
+                Some(last) if trade.price < last => Some(OrderSide::Sell),
+                _ => state.last_tick,
+            };
+            if let Some(side) = tick {
+                return (side, AggressorSource::Tick);
+            }
+        }
+
+        (trade.side, AggressorSource::Unknown)
+    }
+
+    /// Classify a trade, tagging it with the inferred aggressor side if its venue
+    /// does not supply one
+    pub fn classify(&mut self, mut trade: Trade) -> (Trade, AggressorSource) {
+        let source = if self.needs_inference(&trade.exchange_id) {
+            let (side, source) = self.infer(&trade);
+            trade.side = side;
+            source
+        } else {
+            AggressorSource::Venue
+        };
+
+        // Every trade moves the tick state, whichever way it was classified
+        let state = self
+            .books
+            .entry(Self::key(&trade.exchange_id, trade.symbol.as_str()))
+            .or_default();
+        match state.last_price {
+            Some(last) if trade.price > last => state.last_tick = Some(OrderSide::Buy),
+            Some(last) if trade.price < last => state.last_tick = Some(OrderSide::Sell),
+            _ => {}
+        }
+        state.last_price = Some(trade.price);
+
+        (trade, source)
+    }
+
+    /// Clear all classification state
+    pub fn clear(&mut self) {
+        self.books.clear();
+    }
+}
+
+impl Default for AggressorClassifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Size, Symbol};
+
+    fn trade(exchange_id: &str, price: &str) -> Trade {
+        Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: exchange_id.to_string(),
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str("1").unwrap(),
+            side: OrderSide::Buy,
+            timestamp: 0,
+            trade_id: None,
+        }
+    }
+
+    #[test]
+    fn test_quote_rule() {
+        let mut classifier = AggressorClassifier::new().with_inferred_venue("dex");
+        classifier.update_quote(
+            "dex",
+            "BTCUSDT",
+            Price::from_str("100").unwrap(),
+            Price::from_str("102").unwrap(),
+        );
+
+        let (t, source) = classifier.classify(trade("dex", "100.5"));
+        assert_eq!((t.side, source), (OrderSide::Sell, AggressorSource::Quote));
+        let (t, source) = classifier.classify(trade("dex", "102"));
+        assert_eq!((t.side, source), (OrderSide::Buy, AggressorSource::Quote));
+
+        // At the mid the tick rule decides: 101 is a downtick from 102
+        let (t, source) = classifier.classify(trade("dex", "101"));
+        assert_eq!((t.side, source), (OrderSide::Sell, AggressorSource::Tick));
+    }
+
+    #[test]
+    fn test_tick_rule_and_venue_side() {
+        let mut classifier = AggressorClassifier::new().with_inferred_venue("dex");
+
+        let (_, source) = classifier.classify(trade("dex", "100"));
+        assert_eq!(source, AggressorSource::Unknown);
+        let (t, _) = classifier.classify(trade("dex", "99"));
+        assert_eq!(t.side, OrderSide::Sell);
+        // Zero tick repeats the last non-zero tick
+        let (t, source) = classifier.classify(trade("dex", "99"));
+        assert_eq!((t.side, source), (OrderSide::Sell, AggressorSource::Tick));
+        let (t, _) = classifier.classify(trade("dex", "99.5"));
+        assert_eq!(t.side, OrderSide::Buy);
+
+        // Venues that report the aggressor are left untouched
+        let (t, source) = classifier.classify(trade("binance", "1"));
+        assert_eq!((t.side, source), (OrderSide::Buy, AggressorSource::Venue));
+    }
+}
@@ -5,6 +5,7 @@ use rust_decimal::Decimal;
 use std::collections::VecDeque;
 
 /// Trade flow indicator that tracks buy/sell pressure
+#[derive(Clone)]
 pub struct TradeFlowIndicator {
     /// Historical trades
     trades: VecDeque<Trade>,
@@ -176,6 +177,7 @@ impl TradeFlowIndicator {
 
 /// Trade flow momentum indicator
 /// Tracks the rate of change in trade flow
+#[derive(Clone)]
 pub struct TradeFlowMomentum {
     /// Historical flow ratios
     flow_ratios: VecDeque<f64>,
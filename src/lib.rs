@@ -1,4 +1,8 @@
+pub mod backtest;
+pub mod config;
 pub mod connectors;
+#[cfg(feature = "control")]
+pub mod control;
 pub mod core;
 pub mod exchanges;
 pub mod indicators;
@@ -8,6 +12,8 @@ pub mod orderbook;
 pub mod realtime;
 pub mod risk;
 pub mod security;
+#[cfg(feature = "backtest")]
+pub mod sim;
 pub mod strategies;
 pub mod strategy;
 pub mod traits;
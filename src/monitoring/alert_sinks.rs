@@ -0,0 +1,357 @@
+use crate::monitoring::alerts::{Alert, AlertLevel};
+use crate::oms::RateLimiter;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Timeout of a single delivery request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// One-line text of an alert for chat transports
+pub fn format_alert(alert: &Alert) -> String {
+    format!(
+        "[{}] {}: {}",
+        alert.level.as_str(),
+        alert.component,
+        alert.message
+    )
+}
+
+/// POST a JSON body, treating any non-2xx status as a failure
+async fn post_json(client: &reqwest::Client, url: &str, body: &Value) -> Result<(), String> {
+    let response = client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned {}", url, response.status()));
+    }
+    Ok(())
+}
+
+/// Destination alerts are delivered to
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    /// Name used in logs
+    fn name(&self) -> &str;
+
+    /// Deliver an alert
+    async fn send(&self, alert: &Alert) -> Result<(), String>;
+}
+
+/// Sends alerts to a Telegram chat through the Bot API
+pub struct TelegramSink {
+    http_client: reqwest::Client,
+    api_base: String,
+    bot_token: String,
+    chat_id: String,
+}
+
+impl TelegramSink {
+    /// Create a sink posting as `bot_token` to `chat_id`
+    pub fn new(bot_token: &str, chat_id: &str) -> Self {
+        Self {
+            http_client: http_client(),
+            api_base: "https://api.telegram.org".to_string(),
+            bot_token: bot_token.to_string(),
+            chat_id: chat_id.to_string(),
+        }
+    }
+
+    /// Use another Bot API server, e.g. a local one (builder pattern)
+    pub fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.trim_end_matches('/').to_string();
+        self
+    }
+
+    fn url(&self) -> String {
+        format!("{}/bot{}/sendMessage", self.api_base, self.bot_token)
+    }
+
+    fn payload(&self, alert: &Alert) -> Value {
+        json!({
+            "chat_id": self.chat_id,
+            "text": format_alert(alert),
+            "disable_notification": alert.level < AlertLevel::Error,
+        })
+    }
+}
+
+#[async_trait]
+impl AlertSink for TelegramSink {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        // The URL holds the bot token, so keep it out of errors
+        post_json(&self.http_client, &self.url(), &self.payload(alert))
+            .await
+            .map_err(|e| e.replace(&self.bot_token, "***"))
+    }
+}
+
+/// Sends alerts to a Slack channel through an incoming webhook
+pub struct SlackSink {
+    http_client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl SlackSink {
+    /// Create a sink posting to an incoming webhook URL
+    pub fn new(webhook_url: &str) -> Self {
+        Self {
+            http_client: http_client(),
+            webhook_url: webhook_url.to_string(),
+        }
+    }
+
+    fn payload(&self, alert: &Alert) -> Value {
+        let text = match alert.level {
+            AlertLevel::Critical => format!("<!channel> {}", format_alert(alert)),
+            _ => format_alert(alert),
+        };
+        json!({ "text": text })
+    }
+}
+
+#[async_trait]
+impl AlertSink for SlackSink {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        post_json(&self.http_client, &self.webhook_url, &self.payload(alert)).await
+    }
+}
+
+/// Posts alerts as JSON to any HTTP endpoint
+pub struct WebhookSink {
+    http_client: reqwest::Client,
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+impl WebhookSink {
+    /// Create a sink posting to `url`
+    pub fn new(url: &str) -> Self {
+        Self {
+            http_client: http_client(),
+            url: url.to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Send a header with every request, e.g. an authorization token (builder pattern)
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn payload(&self, alert: &Alert) -> Value {
+        json!(alert)
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        let mut request = self.http_client.post(&self.url).json(&self.payload(alert));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("{} returned {}", self.url, response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// A sink with the alerts it receives
+///
+/// Alerts over the rate limit are dropped and counted; the next delivered alert says how
+/// many were suppressed. The limit applies to every level, so give critical alerts their
+/// own route if noisy warnings share the sink.
+pub struct AlertRoute {
+    sink: Arc<dyn AlertSink>,
+    min_level: AlertLevel,
+    rate_limiter: Option<RateLimiter>,
+    suppressed: AtomicU64,
+}
+
+impl AlertRoute {
+    /// Create a route delivering every alert to `sink`
+    pub fn new(sink: Arc<dyn AlertSink>) -> Self {
+        Self {
+            sink,
+            min_level: AlertLevel::Info,
+            rate_limiter: None,
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Only deliver alerts at or above a level (builder pattern)
+    pub fn with_min_level(mut self, level: AlertLevel) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    /// Deliver at most the limiter's requests per window (builder pattern)
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Check if an alert's level is routed to the sink
+    pub fn accepts(&self, alert: &Alert) -> bool {
+        alert.level >= self.min_level
+    }
+
+    /// Get the number of alerts dropped by the rate limit since the last delivery
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Deliver an alert if it is routed here and within the rate limit
+    /// Returns the send result, or `None` if the alert was filtered or suppressed.
+    pub async fn deliver(&self, alert: &Alert) -> Option<Result<(), String>> {
+        if !self.accepts(alert) {
+            return None;
+        }
+        if let Some(rate_limiter) = &self.rate_limiter {
+            if !rate_limiter.check_weight(1) {
+                self.suppressed.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        }
+
+        let suppressed = self.suppressed.swap(0, Ordering::Relaxed);
+        let result = if suppressed > 0 {
+            let mut alert = alert.clone();
+            alert.message = format!(
+                "{} ({} earlier alerts suppressed)",
+                alert.message, suppressed
+            );
+            self.sink.send(&alert).await
+        } else {
+            self.sink.send(alert).await
+        };
+        if let Err(e) = &result {
+            log::warn!("Alert delivery to {} failed: {}", self.sink.name(), e);
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::SimulatedClock;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        sent: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl AlertSink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, alert: &Alert) -> Result<(), String> {
+            self.sent.lock().unwrap().push(alert.message.clone());
+            Ok(())
+        }
+    }
+
+    fn alert(level: AlertLevel, message: &str) -> Alert {
+        Alert {
+            level,
+            message: message.to_string(),
+            component: "risk_manager".to_string(),
+            timestamp: 0,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_alert_sink_payloads() {
+        let critical = alert(AlertLevel::Critical, "daily loss limit breached");
+        assert_eq!(
+            format_alert(&critical),
+            "[CRITICAL] risk_manager: daily loss limit breached"
+        );
+
+        let telegram = TelegramSink::new("123:abc", "42").with_api_base("http://localhost/");
+        assert_eq!(telegram.url(), "http://localhost/bot123:abc/sendMessage");
+        let payload = telegram.payload(&critical);
+        assert_eq!(payload["chat_id"], "42");
+        assert_eq!(payload["disable_notification"], false);
+        assert!(
+            telegram.payload(&alert(AlertLevel::Info, "ok"))["disable_notification"]
+                .as_bool()
+                .unwrap()
+        );
+
+        let slack = SlackSink::new("https://hooks.slack.com/services/x");
+        assert!(slack.payload(&critical)["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("<!channel> [CRITICAL]"));
+
+        let webhook = WebhookSink::new("http://localhost/alerts");
+        assert_eq!(webhook.payload(&critical)["level"], "Critical");
+    }
+
+    #[tokio::test]
+    async fn test_alert_route_filters_and_rate_limits() {
+        let clock = SimulatedClock::at_millis(0);
+        let sink = Arc::new(RecordingSink::default());
+        let route = AlertRoute::new(sink.clone())
+            .with_min_level(AlertLevel::Error)
+            .with_rate_limiter(
+                RateLimiter::new(2, Duration::from_secs(60)).with_clock(clock.shared()),
+            );
+
+        assert!(route
+            .deliver(&alert(AlertLevel::Warning, "w"))
+            .await
+            .is_none());
+        for message in ["a", "b", "c", "d"] {
+            route.deliver(&alert(AlertLevel::Critical, message)).await;
+        }
+        assert_eq!(route.suppressed(), 2);
+
+        clock.advance(Duration::from_secs(61));
+        assert!(route
+            .deliver(&alert(AlertLevel::Error, "e"))
+            .await
+            .unwrap()
+            .is_ok());
+        assert_eq!(route.suppressed(), 0);
+        assert_eq!(
+            *sink.sent.lock().unwrap(),
+            vec!["a", "b", "e (2 earlier alerts suppressed)"]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::monitoring::alert_sinks::AlertRoute;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -13,6 +14,18 @@ pub enum AlertLevel {
     Critical,
 }
 
+impl AlertLevel {
+    /// Get the upper-case name of the level
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertLevel::Info => "INFO",
+            AlertLevel::Warning => "WARNING",
+            AlertLevel::Error => "ERROR",
+            AlertLevel::Critical => "CRITICAL",
+        }
+    }
+}
+
 /// An alert
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
@@ -28,6 +41,7 @@ pub struct AlertManager {
     alerts: Arc<RwLock<VecDeque<Alert>>>,
     max_alerts: usize,
     alert_callbacks: Arc<RwLock<Vec<Box<dyn Fn(&Alert) + Send + Sync>>>>,
+    routes: Vec<Arc<AlertRoute>>,
 }
 
 impl AlertManager {
@@ -37,9 +51,16 @@ impl AlertManager {
             alerts: Arc::new(RwLock::new(VecDeque::new())),
             max_alerts,
             alert_callbacks: Arc::new(RwLock::new(Vec::new())),
+            routes: Vec::new(),
         }
     }
 
+    /// Deliver alerts through a sink, e.g. Telegram or Slack (builder pattern)
+    pub fn with_route(mut self, route: AlertRoute) -> Self {
+        self.routes.push(Arc::new(route));
+        self
+    }
+
     /// Emit an alert
     pub async fn emit(&self, level: AlertLevel, component: &str, message: String) {
         let timestamp = SystemTime::now()
@@ -69,6 +90,14 @@ impl AlertManager {
         for callback in callbacks.iter() {
             callback(&alert);
         }
+        drop(callbacks);
+
+        // Deliver in the background so a slow transport never holds up the caller
+        for route in self.routes.iter().filter(|route| route.accepts(&alert)) {
+            let route = route.clone();
+            let alert = alert.clone();
+            tokio::spawn(async move { route.deliver(&alert).await });
+        }
 
         // Log based on level
         match level {
@@ -77,7 +106,6 @@ impl AlertManager {
             AlertLevel::Error => log::error!("[{}] {}", component, alert.message),
             AlertLevel::Critical => {
                 log::error!("[CRITICAL] [{}] {}", component, alert.message);
-                // In production, you might want to send critical alerts immediately
             }
         }
     }
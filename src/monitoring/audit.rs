@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+/// An entry in the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Time of the action in milliseconds
+    pub timestamp: u64,
+    /// Operator or component that performed the action
+    pub actor: String,
+    /// Action name, e.g. "limit_override_requested"
+    pub action: String,
+    /// Free-form details
+    pub details: String,
+}
+
+/// Audit file being appended to
+struct AuditFile {
+    writer: BufWriter<File>,
+    /// Segment directory and size limit, if the log rotates
+    rotation: Option<(PathBuf, u64)>,
+    /// Bytes written to the current file
+    bytes_written: u64,
+}
+
+impl AuditFile {
+    /// Open a new segment file named after the current time
+    fn open_segment(dir: &Path) -> io::Result<File> {
+        let mut millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        loop {
+            let path = dir.join(format!("audit-{:013}.jsonl", millis));
+            match OpenOptions::new().create_new(true).append(true).open(path) {
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => millis += 1,
+                result => return result,
+            }
+        }
+    }
+
+    fn write_entry(&mut self, entry: &AuditEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        // Flush every entry so the trail survives a crash
+        self.writer.flush()?;
+        self.bytes_written += line.len() as u64;
+
+        if let Some((dir, max_bytes)) = &self.rotation {
+            if self.bytes_written >= *max_bytes {
+                self.writer = BufWriter::new(Self::open_segment(dir)?);
+                self.bytes_written = 0;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Append-only audit log of operator and control actions
+/// Entries are kept in memory and, if configured, appended to a JSON-lines file
+pub struct AuditLog {
+    entries: Arc<RwLock<Vec<AuditEntry>>>,
+    file: Option<Mutex<AuditFile>>,
+}
+
+impl AuditLog {
+    /// Create an in-memory audit log
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(Vec::new())),
+            file: None,
+        }
+    }
+
+    /// Create an audit log that also appends entries to a file
+    pub fn with_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            entries: Arc::new(RwLock::new(Vec::new())),
+            file: Some(Mutex::new(AuditFile {
+                writer: BufWriter::new(file),
+                rotation: None,
+                bytes_written: 0,
+            })),
+        })
+    }
+
+    /// Create an audit log that appends entries to segment files in `dir`
+    /// A new segment is started once the current one reaches `max_segment_bytes`, so old
+    /// segments can be removed by a `RetentionTarget` on the directory.
+    pub fn with_rotating_file(dir: impl Into<PathBuf>, max_segment_bytes: u64) -> io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        let file = AuditFile::open_segment(&dir)?;
+        Ok(Self {
+            entries: Arc::new(RwLock::new(Vec::new())),
+            file: Some(Mutex::new(AuditFile {
+                writer: BufWriter::new(file),
+                rotation: Some((dir, max_segment_bytes.max(1))),
+                bytes_written: 0,
+            })),
+        })
+    }
+
+    /// Record an action
+    pub async fn record(&self, actor: &str, action: &str, details: String) {
+        let entry = AuditEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            actor: actor.to_string(),
+            action: action.to_string(),
+            details,
+        };
+
+        if let Some(file) = &self.file {
+            if let Err(e) = file.lock().unwrap().write_entry(&entry) {
+                log::error!("Failed to write audit entry {:?}: {}", entry, e);
+            }
+        }
+
+        self.entries.write().await.push(entry);
+    }
+
+    /// Get all entries, oldest first
+    pub async fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.read().await.clone()
+    }
+
+    /// Get all entries for an action
+    pub async fn entries_for_action(&self, action: &str) -> Vec<AuditEntry> {
+        let entries = self.entries.read().await;
+        entries
+            .iter()
+            .filter(|e| e.action == action)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_audit_log_file() {
+        let path = std::env::temp_dir().join(format!("audit_{}.jsonl", uuid::Uuid::new_v4()));
+        let audit = AuditLog::with_file(&path).unwrap();
+
+        audit
+            .record("alice", "limit_override_requested", "x".to_string())
+            .await;
+        audit
+            .record("bob", "limit_override_approved", "y".to_string())
+            .await;
+
+        assert_eq!(audit.entries().await.len(), 2);
+        assert_eq!(
+            audit.entries_for_action("limit_override_approved").await[0].actor,
+            "bob"
+        );
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<AuditEntry> = contents
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines, audit.entries().await);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_rotation() {
+        let dir = std::env::temp_dir().join(format!("audit_{}", uuid::Uuid::new_v4()));
+        let audit = AuditLog::with_rotating_file(&dir, 1).unwrap();
+
+        audit.record("alice", "a", "x".to_string()).await;
+        audit.record("bob", "b", "y".to_string()).await;
+
+        // Each entry fills a segment, leaving a fresh empty one open
+        let mut segments: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        segments.sort();
+        assert_eq!(segments.len(), 3);
+        let first: AuditEntry =
+            serde_json::from_str(std::fs::read_to_string(&segments[0]).unwrap().trim()).unwrap();
+        assert_eq!(first.actor, "alice");
+        assert_eq!(std::fs::metadata(&segments[2]).unwrap().len(), 0);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,334 @@
+use crate::core::events::MarketEvent;
+use crate::monitoring::alerts::{AlertLevel, AlertManager};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+/// Lag status of a venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LagStatus {
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// Percentile summary of message lag for a venue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LagStats {
+    pub exchange_id: String,
+    pub count: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+    /// Messages stamped ahead of the local clock (clock skew)
+    pub clock_skew_count: u64,
+}
+
+/// Per-venue lag state
+#[derive(Debug, Default)]
+struct VenueLag {
+    /// Recent lag samples in milliseconds (oldest first)
+    samples: VecDeque<u64>,
+    /// Current status
+    status: Option<LagStatus>,
+    /// Messages stamped ahead of the local clock
+    clock_skew_count: u64,
+}
+
+/// Monitor for WebSocket message lag (receive time - exchange event time)
+///
+/// Status is derived from the p90 lag over a rolling window so that a single
+/// slow message does not pause trading, while sustained degradation does.
+pub struct LagMonitor {
+    venues: Arc<RwLock<HashMap<String, VenueLag>>>,
+    window_size: usize,
+    min_samples: usize,
+    warning_threshold_ms: u64,
+    critical_threshold_ms: u64,
+    alert_manager: Option<Arc<AlertManager>>,
+}
+
+impl LagMonitor {
+    /// Create a new lag monitor
+    pub fn new(window_size: usize, warning_threshold_ms: u64, critical_threshold_ms: u64) -> Self {
+        Self {
+            venues: Arc::new(RwLock::new(HashMap::new())),
+            window_size: window_size.max(1),
+            min_samples: 10.min(window_size.max(1)),
+            warning_threshold_ms,
+            critical_threshold_ms,
+            alert_manager: None,
+        }
+    }
+
+    /// Emit alerts on status changes through an alert manager (builder pattern)
+    pub fn with_alert_manager(mut self, alert_manager: Arc<AlertManager>) -> Self {
+        self.alert_manager = Some(alert_manager);
+        self
+    }
+
+    /// Set the minimum number of samples before status changes (builder pattern)
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = min_samples.clamp(1, self.window_size);
+        self
+    }
+
+    /// Record an inbound market event using the current time as receive time
+    pub async fn record_event(&self, event: &MarketEvent) -> LagStatus {
+        self.record(event.exchange_id(), event.timestamp(), current_time_ms())
+            .await
+    }
+
+    /// Record the lag of one message and return the venue's status
+    pub async fn record(
+        &self,
+        exchange_id: &str,
+        event_time_ms: u64,
+        receive_time_ms: u64,
+    ) -> LagStatus {
+        let (previous, status, p90) = {
+            let mut venues = self.venues.write().await;
+            let venue = venues.entry(exchange_id.to_string()).or_default();
+
+            if event_time_ms > receive_time_ms {
+                venue.clock_skew_count += 1;
+            }
+            venue
+                .samples
+                .push_back(receive_time_ms.saturating_sub(event_time_ms));
+            while venue.samples.len() > self.window_size {
+                venue.samples.pop_front();
+            }
+
+            let previous = venue.status.unwrap_or(LagStatus::Normal);
+            if venue.samples.len() < self.min_samples {
+                return previous;
+            }
+
+            let p90 = percentile(&venue.samples, 0.90);
+            let status = self.classify(p90);
+            venue.status = Some(status);
+            (previous, status, p90)
+        };
+
+        if status != previous {
+            self.alert(exchange_id, previous, status, p90).await;
+        }
+
+        status
+    }
+
+    fn classify(&self, lag_ms: u64) -> LagStatus {
+        if lag_ms >= self.critical_threshold_ms {
+            LagStatus::Critical
+        } else if lag_ms >= self.warning_threshold_ms {
+            LagStatus::Warning
+        } else {
+            LagStatus::Normal
+        }
+    }
+
+    async fn alert(&self, exchange_id: &str, previous: LagStatus, status: LagStatus, p90: u64) {
+        let Some(alert_manager) = &self.alert_manager else {
+            return;
+        };
+
+        let (level, message) = match status {
+            LagStatus::Critical => (
+                AlertLevel::Critical,
+                format!(
+                    "{} message lag p90={}ms exceeds critical threshold {}ms",
+                    exchange_id, p90, self.critical_threshold_ms
+                ),
+            ),
+            LagStatus::Warning => (
+                AlertLevel::Warning,
+                format!(
+                    "{} message lag p90={}ms exceeds warning threshold {}ms",
+                    exchange_id, p90, self.warning_threshold_ms
+                ),
+            ),
+            LagStatus::Normal => (
+                AlertLevel::Info,
+                format!(
+                    "{} message lag recovered from {:?}: p90={}ms",
+                    exchange_id, previous, p90
+                ),
+            ),
+        };
+
+        alert_manager.emit(level, "lag_monitor", message).await;
+    }
+
+    /// Get the current status of a venue
+    pub async fn status(&self, exchange_id: &str) -> LagStatus {
+        let venues = self.venues.read().await;
+        venues
+            .get(exchange_id)
+            .and_then(|v| v.status)
+            .unwrap_or(LagStatus::Normal)
+    }
+
+    /// Check if trading on a venue should be paused due to critical lag
+    pub async fn should_pause(&self, exchange_id: &str) -> bool {
+        self.status(exchange_id).await == LagStatus::Critical
+    }
+
+    /// Get all venues that are not in Normal status
+    pub async fn degraded_venues(&self) -> Vec<(String, LagStatus)> {
+        let venues = self.venues.read().await;
+        venues
+            .iter()
+            .filter_map(|(id, v)| match v.status {
+                Some(status) if status != LagStatus::Normal => Some((id.clone(), status)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get a lag percentile (0.0 - 1.0) for a venue
+    pub async fn percentile(&self, exchange_id: &str, quantile: f64) -> Option<u64> {
+        let venues = self.venues.read().await;
+        let venue = venues.get(exchange_id)?;
+        if venue.samples.is_empty() {
+            return None;
+        }
+        Some(percentile(&venue.samples, quantile))
+    }
+
+    /// Get lag statistics for a venue
+    pub async fn stats(&self, exchange_id: &str) -> Option<LagStats> {
+        let venues = self.venues.read().await;
+        let venue = venues.get(exchange_id)?;
+        if venue.samples.is_empty() {
+            return None;
+        }
+
+        Some(LagStats {
+            exchange_id: exchange_id.to_string(),
+            count: venue.samples.len(),
+            p50_ms: percentile(&venue.samples, 0.50),
+            p90_ms: percentile(&venue.samples, 0.90),
+            p99_ms: percentile(&venue.samples, 0.99),
+            max_ms: venue.samples.iter().copied().max().unwrap_or(0),
+            clock_skew_count: venue.clock_skew_count,
+        })
+    }
+
+    /// Get lag statistics for all venues
+    pub async fn all_stats(&self) -> Vec<LagStats> {
+        let exchange_ids: Vec<String> = self.venues.read().await.keys().cloned().collect();
+        let mut stats = Vec::new();
+        for exchange_id in exchange_ids {
+            if let Some(s) = self.stats(&exchange_id).await {
+                stats.push(s);
+            }
+        }
+        stats
+    }
+
+    /// Clear all samples and statuses
+    pub async fn reset(&self) {
+        self.venues.write().await.clear();
+    }
+}
+
+impl Default for LagMonitor {
+    fn default() -> Self {
+        Self::new(1000, 250, 1000)
+    }
+}
+
+/// Nearest-rank percentile of a sample window
+fn percentile(samples: &VecDeque<u64>, quantile: f64) -> u64 {
+    let mut sorted: Vec<u64> = samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let rank = (quantile.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+fn current_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lag_percentiles() {
+        let monitor = LagMonitor::new(100, 250, 1000);
+        for lag in 1..=100u64 {
+            monitor.record("binance", 10_000, 10_000 + lag).await;
+        }
+
+        let stats = monitor.stats("binance").await.unwrap();
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.p50_ms, 50);
+        assert_eq!(stats.p90_ms, 90);
+        assert_eq!(stats.p99_ms, 99);
+        assert_eq!(stats.max_ms, 100);
+        assert!(monitor.stats("okx").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_clock_skew_is_clamped() {
+        let monitor = LagMonitor::new(10, 250, 1000);
+        monitor.record("binance", 10_050, 10_000).await;
+
+        let stats = monitor.stats("binance").await.unwrap();
+        assert_eq!(stats.max_ms, 0);
+        assert_eq!(stats.clock_skew_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_lag_alerts_and_pause() {
+        let alert_manager = Arc::new(AlertManager::new(100));
+        let monitor = LagMonitor::new(10, 250, 1000).with_alert_manager(alert_manager.clone());
+
+        // A single slow message does not change status
+        monitor.record("binance", 0, 5_000).await;
+        for _ in 0..9 {
+            monitor.record("binance", 0, 10).await;
+        }
+        assert_eq!(monitor.status("binance").await, LagStatus::Normal);
+
+        // Sustained lag pauses trading on the venue only
+        for _ in 0..10 {
+            monitor.record("binance", 0, 2_000).await;
+        }
+        monitor.record("okx", 0, 10).await;
+        assert!(monitor.should_pause("binance").await);
+        assert!(!monitor.should_pause("okx").await);
+        assert_eq!(
+            monitor.degraded_venues().await,
+            vec![("binance".to_string(), LagStatus::Critical)]
+        );
+        assert_eq!(
+            alert_manager
+                .get_alerts_by_level(AlertLevel::Critical)
+                .await
+                .len(),
+            1
+        );
+
+        // Recovery emits an info alert
+        for _ in 0..10 {
+            monitor.record("binance", 0, 10).await;
+        }
+        assert_eq!(monitor.status("binance").await, LagStatus::Normal);
+        assert_eq!(
+            alert_manager
+                .get_alerts_by_level(AlertLevel::Info)
+                .await
+                .len(),
+            1
+        );
+    }
+}
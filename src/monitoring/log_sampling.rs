@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Log sampling settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogSamplingConfig {
+    /// Whether sampled hot-path logging is emitted at all
+    pub enabled: bool,
+    /// Log 1 in N messages per key (1 logs every message)
+    pub every_n: u64,
+    /// Log at most this many messages per second per key (0 for no limit)
+    pub max_per_second: u32,
+}
+
+impl Default for LogSamplingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            every_n: 100,
+            max_per_second: 10,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct KeyState {
+    seen: u64,
+    suppressed: u64,
+    window_start: Instant,
+    window_count: u32,
+}
+
+/// Sampler for log messages on hot paths
+///
+/// Both limits apply per key: a message is logged if it is the Nth seen for its key and
+/// the key has not used up its per-second budget. Settings are atomics, so they can be
+/// changed at runtime without locking out the hot path.
+#[derive(Debug)]
+pub struct LogSampler {
+    enabled: AtomicBool,
+    every_n: AtomicU64,
+    max_per_second: AtomicU32,
+    keys: Mutex<HashMap<String, KeyState>>,
+}
+
+impl LogSampler {
+    /// Create a new sampler
+    pub fn new(config: LogSamplingConfig) -> Self {
+        Self {
+            enabled: AtomicBool::new(config.enabled),
+            every_n: AtomicU64::new(config.every_n.max(1)),
+            max_per_second: AtomicU32::new(config.max_per_second),
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the current settings
+    pub fn config(&self) -> LogSamplingConfig {
+        LogSamplingConfig {
+            enabled: self.enabled.load(Ordering::Relaxed),
+            every_n: self.every_n.load(Ordering::Relaxed),
+            max_per_second: self.max_per_second.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Replace the settings
+    pub fn set_config(&self, config: LogSamplingConfig) {
+        self.enabled.store(config.enabled, Ordering::Relaxed);
+        self.every_n.store(config.every_n.max(1), Ordering::Relaxed);
+        self.max_per_second
+            .store(config.max_per_second, Ordering::Relaxed);
+    }
+
+    /// Decide whether to log a message for a key
+    /// Returns the number of messages suppressed for the key since it was last logged,
+    /// or None if this message should be suppressed
+    pub fn sample(&self, key: &str) -> Option<u64> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let every_n = self.every_n.load(Ordering::Relaxed);
+        let max_per_second = self.max_per_second.load(Ordering::Relaxed);
+        let now = Instant::now();
+
+        let mut keys = self.keys.lock().unwrap();
+        let state = match keys.get_mut(key) {
+            Some(state) => state,
+            None => keys.entry(key.to_string()).or_insert(KeyState {
+                seen: 0,
+                suppressed: 0,
+                window_start: now,
+                window_count: 0,
+            }),
+        };
+
+        state.seen += 1;
+        if now.duration_since(state.window_start) >= Duration::from_secs(1) {
+            state.window_start = now;
+            state.window_count = 0;
+        }
+
+        let sampled = (state.seen - 1) % every_n == 0;
+        let within_rate = max_per_second == 0 || state.window_count < max_per_second;
+        if sampled && within_rate {
+            state.window_count += 1;
+            Some(std::mem::take(&mut state.suppressed))
+        } else {
+            state.suppressed += 1;
+            None
+        }
+    }
+
+    /// Forget all per-key counters
+    pub fn reset(&self) {
+        self.keys.lock().unwrap().clear();
+    }
+}
+
+impl Default for LogSampler {
+    fn default() -> Self {
+        Self::new(LogSamplingConfig::default())
+    }
+}
+
+/// Process-wide sampler used by the hot-path logging macros
+pub fn hot_path_sampler() -> &'static LogSampler {
+    static SAMPLER: OnceLock<LogSampler> = OnceLock::new();
+    SAMPLER.get_or_init(LogSampler::default)
+}
+
+/// Log at debug level through the hot-path sampler, keyed by the first argument
+///
+/// `sampled_debug!(symbol, "Book delta {}: {:?}", symbol, delta)` logs 1 in N deltas per
+/// symbol, at most X per second, noting how many were suppressed in between.
+#[macro_export]
+macro_rules! sampled_debug {
+    ($key:expr, $($arg:tt)+) => {
+        if log::log_enabled!(log::Level::Debug) {
+            if let Some(suppressed) =
+                $crate::monitoring::log_sampling::hot_path_sampler().sample($key)
+            {
+                if suppressed > 0 {
+                    log::debug!("{} [{} suppressed]", format_args!($($arg)+), suppressed);
+                } else {
+                    log::debug!($($arg)+);
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_per_key() {
+        let sampler = LogSampler::new(LogSamplingConfig {
+            enabled: true,
+            every_n: 3,
+            max_per_second: 0,
+        });
+
+        let logged: Vec<Option<u64>> = (0..7).map(|_| sampler.sample("BTCUSDT")).collect();
+        assert_eq!(
+            logged,
+            vec![Some(0), None, None, Some(2), None, None, Some(2)]
+        );
+        // Keys are sampled independently
+        assert_eq!(sampler.sample("ETHUSDT"), Some(0));
+
+        // At most 2 per second, however many are sampled
+        sampler.set_config(LogSamplingConfig {
+            enabled: true,
+            every_n: 1,
+            max_per_second: 2,
+        });
+        sampler.reset();
+        let logged = (0..10)
+            .filter(|_| sampler.sample("BTCUSDT").is_some())
+            .count();
+        assert_eq!(logged, 2);
+
+        sampler.set_config(LogSamplingConfig {
+            enabled: false,
+            ..sampler.config()
+        });
+        sampler.reset();
+        assert_eq!(sampler.sample("BTCUSDT"), None);
+    }
+}
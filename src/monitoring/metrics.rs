@@ -132,6 +132,45 @@ impl MetricsCollector {
         metrics
     }
 
+    /// Render counters and gauges in the Prometheus text exposition format
+    pub async fn render_prometheus(&self) -> String {
+        fn metric_name(name: &str) -> String {
+            name.chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect()
+        }
+
+        let mut output = String::new();
+        let counters = self.counters.read().await;
+        let mut names: Vec<&String> = counters.keys().collect();
+        names.sort();
+        for name in names {
+            let name_out = metric_name(name);
+            output.push_str(&format!(
+                "# TYPE {} counter\n{} {}\n",
+                name_out, name_out, counters[name]
+            ));
+        }
+
+        let gauges = self.gauges.read().await;
+        let mut names: Vec<&String> = gauges.keys().collect();
+        names.sort();
+        for name in names {
+            let name_out = metric_name(name);
+            output.push_str(&format!(
+                "# TYPE {} gauge\n{} {}\n",
+                name_out, name_out, gauges[name]
+            ));
+        }
+        output
+    }
+
     /// Reset all metrics
     pub async fn reset(&self) {
         *self.counters.write().await = HashMap::new();
@@ -145,3 +184,22 @@ impl Default for MetricsCollector {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_render_prometheus() {
+        let metrics = MetricsCollector::new();
+        metrics.increment_counter("orders.placed", 3).await;
+        metrics.set_gauge("ledger_divergences", 2.0).await;
+
+        let output = metrics.render_prometheus().await;
+        assert_eq!(
+            output,
+            "# TYPE orders_placed counter\norders_placed 3\n\
+             # TYPE ledger_divergences gauge\nledger_divergences 2\n"
+        );
+    }
+}
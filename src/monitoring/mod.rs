@@ -1,8 +1,26 @@
+pub mod alert_sinks;
 pub mod alerts;
+pub mod audit;
 pub mod health;
+pub mod lag;
+pub mod log_sampling;
 /// Monitoring and alerting capabilities
 pub mod metrics;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod retention;
+pub mod supervisor;
+pub mod symbol_stats;
 
+pub use alert_sinks::{AlertRoute, AlertSink, SlackSink, TelegramSink, WebhookSink};
 pub use alerts::{Alert, AlertLevel, AlertManager};
+pub use audit::{AuditEntry, AuditLog};
 pub use health::{HealthChecker, HealthStatus};
+pub use lag::{LagMonitor, LagStats, LagStatus};
+pub use log_sampling::{hot_path_sampler, LogSampler, LogSamplingConfig};
 pub use metrics::{Metric, MetricsCollector};
+#[cfg(feature = "otel")]
+pub use otel::{OtlpConfig, OtlpExporter, OtlpHandle};
+pub use retention::{RetentionManager, RetentionPolicy, RetentionReport, RetentionTarget};
+pub use supervisor::{Heartbeat, TaskStatus, TaskSupervisor};
+pub use symbol_stats::{SymbolStats, SymbolStatsRegistry, SymbolStatsSnapshot};
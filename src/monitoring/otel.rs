@@ -0,0 +1,429 @@
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::{SetGlobalDefaultError, Subscriber};
+use tracing::{Event, Level, Metadata};
+use tracing_core::span::Current;
+
+thread_local! {
+    /// Spans entered on this thread, innermost last
+    static ENTERED: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+fn now_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
+
+/// OTLP exporter configuration
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// Collector traces endpoint
+    pub endpoint: String,
+    /// `service.name` resource attribute
+    pub service_name: String,
+    /// Most verbose level recorded
+    pub max_level: Level,
+    /// Spans buffered before the oldest are dropped
+    pub max_buffered_spans: usize,
+    /// How often the background task posts buffered spans
+    pub flush_interval: Duration,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://localhost:4318/v1/traces".to_string(),
+            service_name: "crypto-hft".to_string(),
+            max_level: Level::INFO,
+            max_buffered_spans: 10_000,
+            flush_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A span that has not closed yet
+struct OpenSpan {
+    trace_id: u128,
+    span_id: u64,
+    parent_span_id: Option<u64>,
+    metadata: &'static Metadata<'static>,
+    start: u128,
+    attributes: Vec<Value>,
+    events: Vec<Value>,
+    error: Option<String>,
+    refs: usize,
+}
+
+/// Collects field values as OTLP attributes
+struct AttributeVisitor<'a>(&'a mut Vec<Value>);
+
+impl AttributeVisitor<'_> {
+    fn push(&mut self, field: &Field, value: Value) {
+        self.0.push(json!({ "key": field.name(), "value": value }));
+    }
+}
+
+impl Visit for AttributeVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.push(field, json!({ "stringValue": format!("{:?}", value) }));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, json!({ "stringValue": value }));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, json!({ "intValue": value.to_string() }));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.push(field, json!({ "intValue": value.to_string() }));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.push(field, json!({ "doubleValue": value }));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, json!({ "boolValue": value }));
+    }
+}
+
+/// State shared between the subscriber and its handle
+struct Shared {
+    config: OtlpConfig,
+    finished: Mutex<Vec<Value>>,
+    dropped: AtomicU64,
+}
+
+/// `tracing` subscriber exporting spans to an OTLP collector
+pub struct OtlpExporter {
+    shared: Arc<Shared>,
+    next_id: AtomicU64,
+    spans: Mutex<HashMap<u64, OpenSpan>>,
+}
+
+impl OtlpExporter {
+    /// Create a new exporter
+    pub fn new(config: OtlpConfig) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                config,
+                finished: Mutex::new(Vec::new()),
+                dropped: AtomicU64::new(0),
+            }),
+            next_id: AtomicU64::new(1),
+            spans: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get a handle for flushing the exported spans
+    pub fn handle(&self) -> OtlpHandle {
+        OtlpHandle {
+            shared: self.shared.clone(),
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Install as the global subscriber and return the flush handle
+    pub fn install(self) -> Result<OtlpHandle, SetGlobalDefaultError> {
+        let handle = self.handle();
+        tracing::subscriber::set_global_default(self)?;
+        Ok(handle)
+    }
+
+    fn current(&self) -> Option<u64> {
+        ENTERED.with(|entered| entered.borrow().last().copied())
+    }
+
+    fn finish(&self, span: OpenSpan) {
+        let mut otlp = json!({
+            "traceId": format!("{:032x}", span.trace_id),
+            "spanId": format!("{:016x}", span.span_id),
+            "name": span.metadata.name(),
+            "kind": 1,
+            "startTimeUnixNano": span.start.to_string(),
+            "endTimeUnixNano": now_nanos().to_string(),
+            "attributes": span.attributes,
+            "events": span.events,
+            "status": match &span.error {
+                Some(message) => json!({ "code": 2, "message": message }),
+                None => json!({}),
+            },
+        });
+        if let Some(parent) = span.parent_span_id {
+            otlp["parentSpanId"] = json!(format!("{:016x}", parent));
+        }
+
+        let mut finished = self.shared.finished.lock().unwrap();
+        if finished.len() >= self.shared.config.max_buffered_spans {
+            finished.remove(0);
+            self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        finished.push(otlp);
+    }
+}
+
+impl Subscriber for OtlpExporter {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.shared.config.max_level
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let parent = if attributes.is_contextual() {
+            self.current()
+        } else {
+            attributes.parent().map(|parent| parent.into_u64())
+        };
+
+        let mut spans = self.spans.lock().unwrap();
+        let (trace_id, parent_span_id) = match parent.and_then(|parent| spans.get(&parent)) {
+            Some(parent) => (parent.trace_id, Some(parent.span_id)),
+            None => (uuid::Uuid::new_v4().as_u128(), None),
+        };
+        let mut span = OpenSpan {
+            trace_id,
+            span_id: uuid::Uuid::new_v4().as_u64_pair().0,
+            parent_span_id,
+            metadata: attributes.metadata(),
+            start: now_nanos(),
+            attributes: Vec::new(),
+            events: Vec::new(),
+            error: None,
+            refs: 1,
+        };
+        attributes.record(&mut AttributeVisitor(&mut span.attributes));
+        spans.insert(id, span);
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if let Some(span) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            values.record(&mut AttributeVisitor(&mut span.attributes));
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let parent = if event.is_contextual() {
+            self.current()
+        } else {
+            event.parent().map(|parent| parent.into_u64())
+        };
+        let Some(parent) = parent else {
+            return;
+        };
+
+        let mut attributes = Vec::new();
+        event.record(&mut AttributeVisitor(&mut attributes));
+        let message = attributes
+            .iter()
+            .find(|attribute| attribute["key"] == "message")
+            .and_then(|attribute| attribute["value"]["stringValue"].as_str())
+            .unwrap_or_else(|| event.metadata().name())
+            .to_string();
+
+        if let Some(span) = self.spans.lock().unwrap().get_mut(&parent) {
+            if *event.metadata().level() == Level::ERROR {
+                span.error = Some(message.clone());
+            }
+            span.events.push(json!({
+                "timeUnixNano": now_nanos().to_string(),
+                "name": message,
+                "attributes": attributes,
+            }));
+        }
+    }
+
+    fn current_span(&self) -> Current {
+        let spans = self.spans.lock().unwrap();
+        match self
+            .current()
+            .and_then(|id| spans.get(&id).map(|span| (id, span)))
+        {
+            Some((id, span)) => Current::new(Id::from_u64(id), span.metadata),
+            None => Current::none(),
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        ENTERED.with(|entered| entered.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        let id = span.into_u64();
+        ENTERED.with(|entered| {
+            let mut entered = entered.borrow_mut();
+            if let Some(position) = entered.iter().rposition(|&entered| entered == id) {
+                entered.remove(position);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(open) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            open.refs += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let mut spans = self.spans.lock().unwrap();
+        let id = span.into_u64();
+        let Some(open) = spans.get_mut(&id) else {
+            return false;
+        };
+        open.refs -= 1;
+        if open.refs > 0 {
+            return false;
+        }
+        let closed = spans.remove(&id);
+        drop(spans);
+        if let Some(closed) = closed {
+            self.finish(closed);
+        }
+        true
+    }
+}
+
+/// Handle for posting the spans an `OtlpExporter` has collected
+#[derive(Clone)]
+pub struct OtlpHandle {
+    shared: Arc<Shared>,
+    http_client: reqwest::Client,
+}
+
+impl OtlpHandle {
+    /// Take the closed spans collected so far, in OTLP JSON form
+    pub fn take_spans(&self) -> Vec<Value> {
+        std::mem::take(&mut *self.shared.finished.lock().unwrap())
+    }
+
+    /// Number of spans dropped because the buffer was full
+    pub fn dropped_spans(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Wrap spans in an OTLP export request
+    pub fn export_request(&self, spans: Vec<Value>) -> Value {
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [{
+                        "key": "service.name",
+                        "value": { "stringValue": self.shared.config.service_name },
+                    }],
+                },
+                "scopeSpans": [{
+                    "scope": { "name": env!("CARGO_PKG_NAME") },
+                    "spans": spans,
+                }],
+            }],
+        })
+    }
+
+    /// Post the collected spans to the collector
+    /// Returns the number of spans sent
+    pub async fn flush(&self) -> Result<usize, String> {
+        let spans = self.take_spans();
+        if spans.is_empty() {
+            return Ok(0);
+        }
+        let count = spans.len();
+        let response = self
+            .http_client
+            .post(&self.shared.config.endpoint)
+            .json(&self.export_request(spans))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("Collector returned {}", response.status()));
+        }
+        Ok(count)
+    }
+
+    /// Spawn the background task that flushes on the configured interval
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.shared.config.flush_interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.flush().await {
+                    log::warn!("Trace export failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::RiskEngine;
+    use crate::traits::NewOrder;
+    use crate::types::Size;
+    use tracing::Instrument;
+
+    #[tokio::test]
+    async fn test_order_lifecycle_is_one_trace() {
+        let exporter = OtlpExporter::new(OtlpConfig::default());
+        let handle = exporter.handle();
+        let _guard = tracing::subscriber::set_default(exporter);
+
+        let engine = RiskEngine::new();
+        engine.halt_trading("test").await;
+        let order = NewOrder::new_market_buy("BTCUSDT", Size::from_str("1").unwrap());
+        async {
+            tracing::info_span!("signal").in_scope(|| tracing::error!("signal looks stale"));
+            assert!(engine.check_order(&order).await.is_err());
+        }
+        .instrument(tracing::info_span!("market_event", symbol = "BTCUSDT"))
+        .await;
+        tracing::info_span!("unrelated").in_scope(|| {});
+
+        let spans = handle.take_spans();
+        let by_name = |name: &str| {
+            spans
+                .iter()
+                .find(|span| span["name"] == name)
+                .unwrap_or_else(|| panic!("no {} span", name))
+        };
+        let root = by_name("market_event");
+        let signal = by_name("signal");
+        let risk = by_name("risk.check_order");
+        let unrelated = by_name("unrelated");
+
+        assert!(root.get("parentSpanId").is_none());
+        assert_eq!(signal["parentSpanId"], root["spanId"]);
+        assert_eq!(risk["parentSpanId"], root["spanId"]);
+        assert_eq!(risk["traceId"], root["traceId"]);
+        assert_ne!(unrelated["traceId"], root["traceId"]);
+        assert_eq!(signal["status"]["code"], 2);
+        assert!(risk["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|a| a["key"] == "violation" && a["value"]["stringValue"] == "TradingHalted"));
+
+        let request = handle.export_request(spans.clone());
+        assert_eq!(
+            request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+                .as_array()
+                .unwrap()
+                .len(),
+            spans.len()
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use log::{info, warn};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+
+/// How long and how much on-disk data to keep
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Delete files last modified longer ago than this
+    pub max_age: Option<Duration>,
+    /// Delete the oldest files until the total size is within this many bytes
+    pub max_bytes: Option<u64>,
+    /// Never delete this many of the newest files, which may still be open for writing
+    pub keep_latest: usize,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_age: Some(Duration::from_secs(30 * 24 * 60 * 60)),
+            max_bytes: None,
+            keep_latest: 1,
+        }
+    }
+}
+
+/// Directory whose files are subject to a retention policy
+#[derive(Debug, Clone)]
+pub struct RetentionTarget {
+    /// Name used in logs and reports
+    pub name: String,
+    /// Directory holding the files
+    pub dir: PathBuf,
+    /// File name suffixes to manage (all files if empty)
+    pub suffixes: Vec<String>,
+    /// Retention policy
+    pub policy: RetentionPolicy,
+}
+
+impl RetentionTarget {
+    /// Create a new retention target covering every file in `dir`
+    pub fn new(name: impl Into<String>, dir: impl Into<PathBuf>, policy: RetentionPolicy) -> Self {
+        Self {
+            name: name.into(),
+            dir: dir.into(),
+            suffixes: Vec::new(),
+            policy,
+        }
+    }
+
+    /// Only manage files ending in `suffix` (builder pattern)
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffixes.push(suffix.into());
+        self
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        self.suffixes.is_empty() || self.suffixes.iter().any(|s| name.ends_with(s.as_str()))
+    }
+
+    /// Delete files outside the policy, oldest first
+    pub fn enforce(&self, now: SystemTime) -> io::Result<RetentionReport> {
+        let mut report = RetentionReport {
+            target: self.name.clone(),
+            files_removed: 0,
+            bytes_removed: 0,
+            files_kept: 0,
+            bytes_kept: 0,
+        };
+        if !self.dir.exists() {
+            return Ok(report);
+        }
+
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_file() && self.matches(&entry.path()) {
+                files.push((entry.path(), metadata.modified()?, metadata.len()));
+            }
+        }
+        // Oldest first, by name when modified in the same instant
+        files.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+        let protected = files.len().saturating_sub(self.policy.keep_latest);
+        let mut total: u64 = files.iter().map(|(_, _, len)| len).sum();
+        for (index, (path, modified, len)) in files.into_iter().enumerate() {
+            let expired = self
+                .policy
+                .max_age
+                .is_some_and(|max_age| now.duration_since(modified).unwrap_or_default() > max_age);
+            let over_budget = self.policy.max_bytes.is_some_and(|max| total > max);
+
+            if index < protected && (expired || over_budget) {
+                fs::remove_file(&path)?;
+                total -= len;
+                report.files_removed += 1;
+                report.bytes_removed += len;
+            } else {
+                report.files_kept += 1;
+                report.bytes_kept += len;
+            }
+        }
+
+        if report.files_removed > 0 {
+            info!(
+                "Retention {}: removed {} files ({} bytes), {} bytes kept",
+                self.name, report.files_removed, report.bytes_removed, report.bytes_kept
+            );
+        }
+        if self.policy.max_bytes.is_some_and(|max| total > max) {
+            warn!(
+                "Retention {}: {} bytes kept, over the {:?} byte budget",
+                self.name, total, self.policy.max_bytes
+            );
+        }
+        Ok(report)
+    }
+}
+
+/// Outcome of a retention run for one target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionReport {
+    pub target: String,
+    pub files_removed: usize,
+    pub bytes_removed: u64,
+    pub files_kept: usize,
+    pub bytes_kept: u64,
+}
+
+/// Periodically enforces retention on the recorder, audit and journal directories
+///
+/// Writers rotate into new files (snapshots truncate book journals, the audit log
+/// rolls over to new segments), so retention only ever deletes whole files.
+#[derive(Debug, Clone)]
+pub struct RetentionManager {
+    targets: Vec<RetentionTarget>,
+    interval: Duration,
+}
+
+impl RetentionManager {
+    /// Create a new retention manager running every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            targets: Vec::new(),
+            interval,
+        }
+    }
+
+    /// Add a target (builder pattern)
+    pub fn with_target(mut self, target: RetentionTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Get the targets
+    pub fn targets(&self) -> &[RetentionTarget] {
+        &self.targets
+    }
+
+    /// Enforce every target once
+    /// A failing target is logged and skipped so it does not block the others
+    pub fn run_once(&self) -> Vec<RetentionReport> {
+        let now = SystemTime::now();
+        self.targets
+            .iter()
+            .filter_map(|target| match target.enforce(now) {
+                Ok(report) => Some(report),
+                Err(e) => {
+                    warn!("Retention {} failed: {}", target.name, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Spawn the background compaction job
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            loop {
+                ticker.tick().await;
+                let manager = self.clone();
+                if let Err(e) = tokio::task::spawn_blocking(move || manager.run_once()).await {
+                    warn!("Retention run panicked: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn test_retention_by_age_and_budget() {
+        let dir = std::env::temp_dir().join(format!("retention_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let now = SystemTime::now();
+
+        // Five 100-byte files, a day apart, the newest last
+        for day in 0..5u64 {
+            let path = dir.join(format!("segment-{}.jsonl", day));
+            fs::write(&path, vec![b'x'; 100]).unwrap();
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - Duration::from_secs((4 - day) * 24 * 60 * 60 + 60))
+                .unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "not managed").unwrap();
+
+        let target = RetentionTarget::new(
+            "audit",
+            &dir,
+            RetentionPolicy {
+                max_age: Some(Duration::from_secs(3 * 24 * 60 * 60)),
+                max_bytes: None,
+                keep_latest: 1,
+            },
+        )
+        .with_suffix(".jsonl");
+        let report = target.enforce(now).unwrap();
+        assert_eq!(report.files_removed, 2);
+        assert_eq!(report.bytes_kept, 300);
+        assert!(!dir.join("segment-0.jsonl").exists());
+        assert!(!dir.join("segment-1.jsonl").exists());
+
+        // The budget removes oldest first but never the newest file
+        let manager = RetentionManager::new(Duration::from_secs(60)).with_target(
+            RetentionTarget::new(
+                "audit",
+                &dir,
+                RetentionPolicy {
+                    max_age: None,
+                    max_bytes: Some(50),
+                    keep_latest: 1,
+                },
+            )
+            .with_suffix(".jsonl"),
+        );
+        let reports = manager.run_once();
+        assert_eq!(reports[0].files_removed, 2);
+        assert_eq!(reports[0].files_kept, 1);
+        assert!(dir.join("segment-4.jsonl").exists());
+        assert!(dir.join("notes.txt").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
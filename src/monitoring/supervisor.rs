@@ -0,0 +1,256 @@
+use crate::monitoring::alerts::{AlertLevel, AlertManager};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use log::{error, info};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Heartbeat handle given to a supervised task
+/// Cheap to clone and to beat, so it can be called on every loop iteration
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    epoch: Instant,
+    /// Milliseconds since `epoch` of the last beat
+    last_beat: Arc<AtomicU64>,
+}
+
+impl Heartbeat {
+    fn new(epoch: Instant) -> Self {
+        let heartbeat = Self {
+            epoch,
+            last_beat: Arc::new(AtomicU64::new(0)),
+        };
+        heartbeat.beat();
+        heartbeat
+    }
+
+    /// Signal that the task is alive
+    pub fn beat(&self) {
+        let now = self.epoch.elapsed().as_millis() as u64;
+        self.last_beat.store(now, Ordering::Relaxed);
+    }
+
+    /// Time since the last beat
+    pub fn age(&self) -> Duration {
+        let now = self.epoch.elapsed().as_millis() as u64;
+        Duration::from_millis(now.saturating_sub(self.last_beat.load(Ordering::Relaxed)))
+    }
+}
+
+/// Builds a fresh instance of a supervised task
+type TaskFactory = Box<dyn Fn(Heartbeat) -> BoxFuture<'static, ()> + Send + Sync>;
+
+struct SupervisedTask {
+    name: String,
+    timeout: Duration,
+    factory: TaskFactory,
+    heartbeat: Heartbeat,
+    handle: JoinHandle<()>,
+    restarts: u32,
+}
+
+/// Status of a supervised task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskStatus {
+    pub name: String,
+    pub running: bool,
+    pub restarts: u32,
+    pub heartbeat_age: Duration,
+}
+
+/// Supervisor for long-running tasks
+///
+/// Every supervised task gets a `Heartbeat` and must beat at least once per its
+/// timeout. A task that stops beating is aborted and restarted; a task that exits or
+/// panics is restarted as well. Each restart raises an alert.
+pub struct TaskSupervisor {
+    tasks: RwLock<Vec<SupervisedTask>>,
+    alerts: Arc<AlertManager>,
+    check_interval: Duration,
+    epoch: Instant,
+}
+
+impl TaskSupervisor {
+    /// Create a new supervisor
+    pub fn new(alerts: Arc<AlertManager>) -> Self {
+        Self {
+            tasks: RwLock::new(Vec::new()),
+            alerts,
+            check_interval: Duration::from_secs(1),
+            epoch: Instant::now(),
+        }
+    }
+
+    /// Set the interval between heartbeat checks (builder pattern)
+    pub fn with_check_interval(mut self, check_interval: Duration) -> Self {
+        self.check_interval = check_interval;
+        self
+    }
+
+    /// Start a task under supervision
+    /// `factory` is called again with a fresh heartbeat on every restart
+    pub async fn supervise<F, Fut>(&self, name: impl Into<String>, timeout: Duration, factory: F)
+    where
+        F: Fn(Heartbeat) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let name = name.into();
+        let factory: TaskFactory = Box::new(move |heartbeat| factory(heartbeat).boxed());
+        let heartbeat = Heartbeat::new(self.epoch);
+        let handle = tokio::spawn(factory(heartbeat.clone()));
+        info!(
+            "Supervising task {} (heartbeat timeout {:?})",
+            name, timeout
+        );
+
+        self.tasks.write().await.push(SupervisedTask {
+            name,
+            timeout,
+            factory,
+            heartbeat,
+            handle,
+            restarts: 0,
+        });
+    }
+
+    /// Check all tasks, restarting dead or silent ones
+    /// Returns the names of the restarted tasks
+    pub async fn check(&self) -> Vec<String> {
+        let mut restarted = Vec::new();
+        let mut tasks = self.tasks.write().await;
+        for task in tasks.iter_mut() {
+            let reason = if task.handle.is_finished() {
+                "exited".to_string()
+            } else if task.heartbeat.age() > task.timeout {
+                format!("missed heartbeat for {:?}", task.heartbeat.age())
+            } else {
+                continue;
+            };
+
+            task.handle.abort();
+            task.heartbeat = Heartbeat::new(self.epoch);
+            task.handle = tokio::spawn((task.factory)(task.heartbeat.clone()));
+            task.restarts += 1;
+
+            let message = format!(
+                "Task {} {}, restarted (restart #{})",
+                task.name, reason, task.restarts
+            );
+            error!("{}", message);
+            self.alerts
+                .emit(AlertLevel::Error, "supervisor", message)
+                .await;
+            restarted.push(task.name.clone());
+        }
+        restarted
+    }
+
+    /// Get the status of all supervised tasks
+    pub async fn status(&self) -> Vec<TaskStatus> {
+        self.tasks
+            .read()
+            .await
+            .iter()
+            .map(|task| TaskStatus {
+                name: task.name.clone(),
+                running: !task.handle.is_finished(),
+                restarts: task.restarts,
+                heartbeat_age: task.heartbeat.age(),
+            })
+            .collect()
+    }
+
+    /// Abort a task and stop supervising it, e.g. when the work it does is torn down
+    /// Returns false if no task of that name is supervised
+    pub async fn unsupervise(&self, name: &str) -> bool {
+        let mut tasks = self.tasks.write().await;
+        let before = tasks.len();
+        tasks.retain(|task| {
+            if task.name == name {
+                task.handle.abort();
+                info!("Stopped supervising task {}", name);
+                return false;
+            }
+            true
+        });
+        tasks.len() != before
+    }
+
+    /// Abort all supervised tasks and stop supervising them
+    pub async fn shutdown(&self) {
+        for task in self.tasks.write().await.drain(..) {
+            task.handle.abort();
+        }
+    }
+
+    /// Spawn the supervision loop
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.check_interval);
+            loop {
+                interval.tick().await;
+                self.check().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    #[tokio::test]
+    async fn test_restarts_silent_and_dead_tasks() {
+        let alerts = Arc::new(AlertManager::new(100));
+        let supervisor = TaskSupervisor::new(alerts.clone());
+        let starts = Arc::new(AtomicU32::new(0));
+
+        // Beats once, then hangs on its first run; healthy after a restart
+        let hang_starts = starts.clone();
+        supervisor
+            .supervise("reader", Duration::from_millis(50), move |heartbeat| {
+                let run = hang_starts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    loop {
+                        if run == 0 {
+                            std::future::pending::<()>().await;
+                        }
+                        heartbeat.beat();
+                        tokio::time::sleep(Duration::from_millis(5)).await;
+                    }
+                }
+            })
+            .await;
+        supervisor
+            .supervise("recorder", Duration::from_secs(60), |_heartbeat| async {})
+            .await;
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(supervisor.check().await, vec!["recorder".to_string()]);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let restarted = supervisor.check().await;
+        assert!(restarted.contains(&"reader".to_string()));
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+
+        // The restarted reader keeps beating
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        supervisor.check().await;
+        let status = supervisor.status().await;
+        let reader = status.iter().find(|s| s.name == "reader").unwrap();
+        assert_eq!(reader.restarts, 1);
+        assert!(reader.running);
+
+        assert!(alerts.get_alerts_by_level(AlertLevel::Error).await.len() >= 2);
+        assert!(supervisor.unsupervise("recorder").await);
+        assert!(!supervisor.unsupervise("recorder").await);
+        assert_eq!(supervisor.status().await.len(), 1);
+        supervisor.shutdown().await;
+        assert!(supervisor.status().await.is_empty());
+    }
+}
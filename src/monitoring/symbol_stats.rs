@@ -0,0 +1,284 @@
+use crate::core::events::MarketEvent;
+use crate::monitoring::metrics::MetricsCollector;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Length of the window update rates are measured over, in milliseconds
+const RATE_WINDOW_MS: u64 = 1000;
+
+/// Sentinel for "no value yet" in the atomic fields
+const UNSET: u64 = u64::MAX;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// Live counters for one symbol
+///
+/// Every field is an atomic, so the feed task updates it while monitoring reads it
+/// without either taking a lock.
+#[derive(Debug)]
+pub struct SymbolStats {
+    /// Messages received
+    messages: AtomicU64,
+    /// Last price as f64 bits (display only, never used for trading)
+    last_price: AtomicU64,
+    /// Exchange timestamp of the last message in milliseconds
+    last_update: AtomicU64,
+    /// Local time the current rate window started, in milliseconds
+    window_start: AtomicU64,
+    /// Messages in the current rate window
+    window_count: AtomicU64,
+    /// Rate measured over the last full window, in updates per 1000 seconds
+    rate_milli: AtomicU64,
+}
+
+impl SymbolStats {
+    fn new() -> Self {
+        Self {
+            messages: AtomicU64::new(0),
+            last_price: AtomicU64::new(UNSET),
+            last_update: AtomicU64::new(UNSET),
+            window_start: AtomicU64::new(now_ms()),
+            window_count: AtomicU64::new(0),
+            rate_milli: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a message with its exchange timestamp and, if it carries one, a price
+    pub fn record(&self, timestamp: u64, price: Option<f64>) {
+        self.record_at(now_ms(), timestamp, price);
+    }
+
+    fn record_at(&self, now: u64, timestamp: u64, price: Option<f64>) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        self.last_update.store(timestamp, Ordering::Relaxed);
+        if let Some(price) = price {
+            self.last_price.store(price.to_bits(), Ordering::Relaxed);
+        }
+
+        let count = self.window_count.fetch_add(1, Ordering::Relaxed) + 1;
+        let start = self.window_start.load(Ordering::Relaxed);
+        let elapsed = now.saturating_sub(start);
+        // Only the thread that moves the window forward publishes the rate
+        if elapsed >= RATE_WINDOW_MS
+            && self
+                .window_start
+                .compare_exchange(start, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            self.window_count.fetch_sub(count, Ordering::Relaxed);
+            self.rate_milli
+                .store(count * 1_000_000 / elapsed, Ordering::Relaxed);
+        }
+    }
+
+    /// Messages received
+    pub fn message_count(&self) -> u64 {
+        self.messages.load(Ordering::Relaxed)
+    }
+
+    /// Last price seen
+    pub fn last_price(&self) -> Option<f64> {
+        match self.last_price.load(Ordering::Relaxed) {
+            UNSET => None,
+            bits => Some(f64::from_bits(bits)),
+        }
+    }
+
+    /// Exchange timestamp of the last message in milliseconds
+    pub fn last_update(&self) -> Option<u64> {
+        match self.last_update.load(Ordering::Relaxed) {
+            UNSET => None,
+            timestamp => Some(timestamp),
+        }
+    }
+
+    /// Updates per second over the last full window
+    pub fn updates_per_second(&self) -> f64 {
+        self.rate_milli.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+}
+
+/// Point-in-time copy of a symbol's stats
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolStatsSnapshot {
+    pub symbol: String,
+    pub messages: u64,
+    pub last_price: Option<f64>,
+    pub last_update: Option<u64>,
+    pub updates_per_second: f64,
+}
+
+/// Per-symbol message counts, last prices and update rates
+///
+/// The map is only written when a symbol is first seen. Hot paths can hold on to the
+/// `Arc<SymbolStats>` from `stats` and update it without touching the map at all.
+/// Clones share the same state, so one registry can be handed to every adapter.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolStatsRegistry {
+    symbols: Arc<RwLock<HashMap<String, Arc<SymbolStats>>>>,
+}
+
+impl SymbolStatsRegistry {
+    /// Create a new registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the stats for a symbol, registering it if new
+    pub fn stats(&self, symbol: &str) -> Arc<SymbolStats> {
+        if let Some(stats) = self.get(symbol) {
+            return stats;
+        }
+        self.symbols
+            .write()
+            .unwrap()
+            .entry(symbol.to_string())
+            .or_insert_with(|| Arc::new(SymbolStats::new()))
+            .clone()
+    }
+
+    /// Get the stats for a symbol if it has been seen
+    pub fn get(&self, symbol: &str) -> Option<Arc<SymbolStats>> {
+        self.symbols.read().unwrap().get(symbol).cloned()
+    }
+
+    /// Record a message for a symbol
+    pub fn record(&self, symbol: &str, timestamp: u64, price: Option<f64>) {
+        self.stats(symbol).record(timestamp, price);
+    }
+
+    /// Record a market event, taking the price from trades, liquidations, mark prices or the top of book
+    pub fn record_event(&self, event: &MarketEvent) {
+        let price = match event {
+            MarketEvent::Trade(trade) => Some(trade.price.value()),
+            MarketEvent::Liquidation(liquidation) => Some(liquidation.price.value()),
+            MarketEvent::MarkPrice(mark_price) => Some(mark_price.mark_price.value()),
+            MarketEvent::Candle(candle) => Some(candle.close.value()),
+            MarketEvent::BestBidAsk(quote) => Some(
+                (quote.bid_price.value() + quote.ask_price.value()) / rust_decimal::Decimal::TWO,
+            ),
+            MarketEvent::OrderBookSnapshot(snapshot) => {
+                match (snapshot.bids.first(), snapshot.asks.first()) {
+                    (Some(bid), Some(ask)) => {
+                        Some((bid.price.value() + ask.price.value()) / rust_decimal::Decimal::TWO)
+                    }
+                    _ => None,
+                }
+            }
+            MarketEvent::OrderBookDelta(_)
+            | MarketEvent::OpenInterest(_)
+            | MarketEvent::Desync(_) => None,
+            // Derived from a book update that was already recorded
+            MarketEvent::BookMetrics(_) => return,
+        };
+        self.record(
+            event.symbol(),
+            event.timestamp(),
+            price.and_then(|p| p.to_f64()),
+        );
+    }
+
+    /// Exchange timestamp of the last message for a symbol
+    pub fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.get(symbol).and_then(|stats| stats.last_update())
+    }
+
+    /// Copy out the stats of every symbol, sorted by symbol
+    pub fn snapshot(&self) -> Vec<SymbolStatsSnapshot> {
+        let mut snapshots: Vec<SymbolStatsSnapshot> = self
+            .symbols
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(symbol, stats)| SymbolStatsSnapshot {
+                symbol: symbol.clone(),
+                messages: stats.message_count(),
+                last_price: stats.last_price(),
+                last_update: stats.last_update(),
+                updates_per_second: stats.updates_per_second(),
+            })
+            .collect();
+        snapshots.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        snapshots
+    }
+
+    /// Publish every symbol's stats as gauges
+    pub async fn export(&self, metrics: &MetricsCollector) {
+        for snapshot in self.snapshot() {
+            metrics
+                .set_gauge(
+                    &format!("symbol_messages.{}", snapshot.symbol),
+                    snapshot.messages as f64,
+                )
+                .await;
+            metrics
+                .set_gauge(
+                    &format!("symbol_updates_per_second.{}", snapshot.symbol),
+                    snapshot.updates_per_second,
+                )
+                .await;
+            if let Some(price) = snapshot.last_price {
+                metrics
+                    .set_gauge(&format!("symbol_last_price.{}", snapshot.symbol), price)
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::Trade;
+    use crate::types::{Price, Size, Symbol};
+
+    #[tokio::test]
+    async fn test_symbol_stats_registry() {
+        let registry = SymbolStatsRegistry::new();
+        assert!(registry.get("BTCUSDT").is_none());
+        assert_eq!(registry.last_update("BTCUSDT"), None);
+
+        registry.record_event(&MarketEvent::Trade(Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str("50000.5").unwrap(),
+            size: Size::from_str("0.1").unwrap(),
+            side: crate::core::events::OrderSide::Buy,
+            timestamp: 1000,
+            trade_id: None,
+        }));
+        let shared = registry.clone();
+        shared.record("BTCUSDT", 2000, None);
+
+        let stats = registry.stats("BTCUSDT");
+        assert_eq!(stats.message_count(), 2);
+        assert_eq!(stats.last_price(), Some(50000.5));
+        assert_eq!(registry.last_update("BTCUSDT"), Some(2000));
+
+        // 50 messages over 500ms, then the window closes at 1s
+        let stats = registry.stats("ETHUSDT");
+        let start = stats.window_start.load(Ordering::Relaxed);
+        for i in 0..50 {
+            stats.record_at(start + i * 10, i, None);
+        }
+        assert_eq!(stats.updates_per_second(), 0.0);
+        stats.record_at(start + 1000, 50, None);
+        assert_eq!(stats.updates_per_second(), 51.0);
+
+        let metrics = MetricsCollector::new();
+        registry.export(&metrics).await;
+        let rendered = metrics.render_prometheus().await;
+        assert!(rendered.contains("symbol_messages_BTCUSDT 2\n"));
+        assert!(rendered.contains("symbol_last_price_BTCUSDT 50000.5\n"));
+        assert!(rendered.contains("symbol_updates_per_second_ETHUSDT 51\n"));
+    }
+}
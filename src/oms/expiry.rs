@@ -0,0 +1,367 @@
+use crate::core::events::{ExpiryEvent, ExpiryStage, NewOrder, OrderId, Timestamp};
+use crate::oms::OrderManagerImpl;
+use crate::risk::RiskEngine;
+use crate::traits::ExecutionClient;
+use crate::types::{InstrumentRegistry, Size, Symbol};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::fmt;
+
+/// When positions in dated contracts are flagged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpiryConfig {
+    /// Flag positions this many milliseconds before delivery
+    pub warning_window: u64,
+}
+
+impl Default for ExpiryConfig {
+    fn default() -> Self {
+        Self {
+            warning_window: 3 * 24 * 60 * 60 * 1000,
+        }
+    }
+}
+
+/// Watches positions held in dated futures and flags them as delivery approaches
+///
+/// Each position is reported once per stage, so the events can be forwarded to
+/// strategies as `TradingEvent::InstrumentExpiry` on every check without repeating.
+pub struct ExpiryMonitor {
+    registry: InstrumentRegistry,
+    config: ExpiryConfig,
+    flagged: HashMap<String, ExpiryStage>,
+}
+
+impl ExpiryMonitor {
+    /// Create a new expiry monitor
+    pub fn new(registry: InstrumentRegistry, config: ExpiryConfig) -> Self {
+        Self {
+            registry,
+            config,
+            flagged: HashMap::new(),
+        }
+    }
+
+    /// Get the instrument registry
+    pub fn registry(&self) -> &InstrumentRegistry {
+        &self.registry
+    }
+
+    /// Get the instrument registry for updates (e.g. newly listed contracts)
+    pub fn registry_mut(&mut self) -> &mut InstrumentRegistry {
+        &mut self.registry
+    }
+
+    /// Get the stage a symbol's position was last flagged at
+    pub fn flagged(&self, symbol: &str) -> Option<ExpiryStage> {
+        self.flagged.get(symbol).copied()
+    }
+
+    /// Check every dated contract the order manager holds a position in
+    /// Returns the positions that entered a new stage since the last check
+    pub async fn check(
+        &mut self,
+        order_manager: &OrderManagerImpl,
+        now: Timestamp,
+    ) -> Vec<ExpiryEvent> {
+        let mut events = Vec::new();
+        let dated: Vec<_> = self.registry.dated().cloned().collect();
+        for spec in dated {
+            let symbol = spec.symbol.value();
+            let position = order_manager.get_position_for_symbol(symbol).await;
+            let (Some(expiry), Some(time_to_expiry)) = (spec.expiry, spec.time_to_expiry(now))
+            else {
+                continue;
+            };
+
+            let stage = if spec.is_expired(now) {
+                ExpiryStage::Expired
+            } else if time_to_expiry <= self.config.warning_window {
+                ExpiryStage::Approaching
+            } else {
+                self.flagged.remove(symbol);
+                continue;
+            };
+            if position.is_zero() {
+                self.flagged.remove(symbol);
+                continue;
+            }
+            if self.flagged(symbol).is_some_and(|flagged| flagged >= stage) {
+                continue;
+            }
+
+            self.flagged.insert(symbol.to_string(), stage);
+            let next_contract = self
+                .registry
+                .next_contract(symbol)
+                .map(|next| next.symbol.clone());
+            warn!(
+                "Position of {} in {} is {:?}: {} ms to delivery, next contract {:?}",
+                position, symbol, stage, time_to_expiry, next_contract
+            );
+            events.push(ExpiryEvent {
+                symbol: spec.symbol.clone(),
+                exchange_id: order_manager.exchange_id().to_string(),
+                stage,
+                expiry,
+                time_to_expiry,
+                position,
+                next_contract,
+            });
+        }
+        events.sort_by_key(|event| event.expiry);
+        events
+    }
+}
+
+/// Orders that move a position from an expiring contract into the next one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollPlan {
+    /// Closes the position in the expiring contract
+    pub close: NewOrder,
+    /// Reopens the position in the next contract
+    pub open: NewOrder,
+}
+
+impl RollPlan {
+    /// Build market orders rolling the position in an expiry event
+    /// Returns None if the position is flat or there is no next contract
+    pub fn from_event(event: &ExpiryEvent) -> Option<Self> {
+        let next = event.next_contract.as_ref()?;
+        let size = event.position.abs();
+        if size.is_zero() {
+            return None;
+        }
+        let (close, open) = if event.position.is_positive() {
+            (
+                NewOrder::new_market_sell(event.symbol.value(), size),
+                NewOrder::new_market_buy(next.value(), size),
+            )
+        } else {
+            (
+                NewOrder::new_market_buy(event.symbol.value(), size),
+                NewOrder::new_market_sell(next.value(), size),
+            )
+        };
+        Some(Self {
+            close: close.with_exchange_id(event.exchange_id.clone()),
+            open: open.with_exchange_id(event.exchange_id.clone()),
+        })
+    }
+}
+
+/// Outcome of a roll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollReport {
+    pub from: Symbol,
+    pub to: Symbol,
+    pub close_order_id: OrderId,
+    pub open_order_id: OrderId,
+    /// Size closed in the expiring contract
+    pub closed_size: Size,
+    /// Size reopened, smaller than closed if risk limits shrank it
+    pub opened_size: Size,
+}
+
+/// Roll error
+#[derive(Debug, Clone)]
+pub enum RollError {
+    /// Nothing to roll (flat position or no next contract listed)
+    NothingToRoll(String),
+    /// The new position would breach risk limits at any size; nothing was sent
+    Risk(crate::core::events::RiskViolation),
+    /// Closing the expiring position failed; nothing was opened
+    CloseFailed(String),
+    /// The position was closed but reopening it failed
+    OpenFailed {
+        close_order_id: OrderId,
+        reason: String,
+    },
+}
+
+impl fmt::Display for RollError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RollError::NothingToRoll(symbol) => write!(f, "Nothing to roll for {}", symbol),
+            RollError::Risk(violation) => {
+                write!(
+                    f,
+                    "Roll rejected by {}: {}",
+                    violation.rule, violation.details
+                )
+            }
+            RollError::CloseFailed(reason) => write!(f, "Closing position failed: {}", reason),
+            RollError::OpenFailed {
+                close_order_id,
+                reason,
+            } => write!(
+                f,
+                "Position closed by {} but reopening failed: {}",
+                close_order_id, reason
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RollError {}
+
+/// Close a position in an expiring contract and reopen it in the next one
+///
+/// The reopening order is checked against the risk engine first and shrunk to fit
+/// if needed. If it cannot pass at any size nothing is sent, leaving the caller to
+/// decide whether to flatten instead. The closing order only reduces exposure and is
+/// not risk-checked, so a halted engine does not block it.
+pub async fn roll_position<C: ExecutionClient + Sync>(
+    client: &C,
+    engine: &RiskEngine,
+    event: &ExpiryEvent,
+) -> Result<RollReport, RollError> {
+    let plan = RollPlan::from_event(event)
+        .ok_or_else(|| RollError::NothingToRoll(event.symbol.to_string()))?;
+    let open = match engine.resize_order(&plan.open).await {
+        Ok(open) => open,
+        Err(violation) => {
+            engine.reject_order(&plan.open, &violation).await;
+            return Err(RollError::Risk(violation));
+        }
+    };
+
+    let close_order_id = client
+        .place_order(plan.close.clone())
+        .await
+        .map_err(|e| RollError::CloseFailed(e.to_string()))?;
+    let open_order_id =
+        client
+            .place_order(open.clone())
+            .await
+            .map_err(|e| RollError::OpenFailed {
+                close_order_id: close_order_id.clone(),
+                reason: e.to_string(),
+            })?;
+    engine.record_order_submission(&open);
+
+    info!(
+        "Rolled {} {} into {} {}",
+        plan.close.size, plan.close.symbol, open.size, open.symbol
+    );
+    Ok(RollReport {
+        from: plan.close.symbol,
+        to: open.symbol,
+        close_order_id,
+        open_order_id,
+        closed_size: plan.close.size,
+        opened_size: open.size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::mock::MockExecutionClient;
+    use crate::oms::order_manager::OrderInfo;
+    use crate::traits::{ExecutionReport, OrderSide, OrderStatus, OrderType, TimeInForce};
+    use crate::types::{InstrumentSpec, Price};
+
+    const DAY: u64 = 24 * 60 * 60 * 1000;
+
+    fn registry() -> InstrumentRegistry {
+        let spec = |symbol: &str| {
+            InstrumentSpec::new(
+                symbol,
+                Price::from_str("0.1").unwrap(),
+                Size::from_str("0.001").unwrap(),
+            )
+        };
+        vec![
+            spec("BTCUSDT_260626").with_expiry("BTCUSDT", 10 * DAY),
+            spec("BTCUSDT_260925").with_expiry("BTCUSDT", 100 * DAY),
+            spec("ETHUSDT_260626").with_expiry("ETHUSDT", 10 * DAY),
+            spec("BTCUSDT"),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    async fn filled(om: &OrderManagerImpl, id: &str, symbol: &str, side: OrderSide, size: &str) {
+        let mut info = OrderInfo::new(
+            id.to_string(),
+            None,
+            Symbol::new(symbol),
+            side,
+            OrderType::Market,
+            TimeInForce::ImmediateOrCancel,
+            Size::from_str(size).unwrap(),
+            None,
+            "binance".to_string(),
+        );
+        info.update(&ExecutionReport {
+            order_id: id.to_string(),
+            client_order_id: None,
+            symbol: Symbol::new(symbol),
+            exchange_id: "binance".to_string(),
+            status: OrderStatus::Filled,
+            filled_size: Size::from_str(size).unwrap(),
+            remaining_size: Size::zero(),
+            average_price: Some(Price::from_str("60000").unwrap()),
+            timestamp: 0,
+            reject_reason: None,
+        });
+        om.add_order(info).await;
+    }
+
+    #[tokio::test]
+    async fn test_expiring_positions_are_flagged_and_rolled() {
+        let om = OrderManagerImpl::new("binance".to_string());
+        filled(&om, "1", "BTCUSDT_260626", OrderSide::Sell, "2").await;
+        filled(&om, "2", "BTCUSDT", OrderSide::Buy, "1").await;
+        let mut monitor = ExpiryMonitor::new(registry(), ExpiryConfig::default());
+
+        // Outside the warning window, and flat contracts are never flagged
+        assert!(monitor.check(&om, 0).await.is_empty());
+
+        let events = monitor.check(&om, 8 * DAY).await;
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.symbol.value(), "BTCUSDT_260626");
+        assert_eq!(event.stage, ExpiryStage::Approaching);
+        assert_eq!(event.time_to_expiry, 2 * DAY);
+        assert_eq!(event.position, Size::from_str("-2").unwrap());
+        assert_eq!(event.next_contract, Some(Symbol::new("BTCUSDT_260925")));
+
+        // Reported once per stage
+        assert!(monitor.check(&om, 9 * DAY).await.is_empty());
+        let expired = monitor.check(&om, 10 * DAY).await;
+        assert_eq!(expired[0].stage, ExpiryStage::Expired);
+        assert_eq!(
+            monitor.flagged("BTCUSDT_260626"),
+            Some(ExpiryStage::Expired)
+        );
+
+        // The short is bought back and reopened, shrunk to the order size limit
+        let client = MockExecutionClient::new();
+        let engine = RiskEngine::new();
+        engine
+            .add_rule(Box::new(crate::risk::rules::OrderSizeRule::new()))
+            .await;
+        engine
+            .set_max_order_size("BTCUSDT_260925", Size::from_str("1.5").unwrap())
+            .await;
+        let report = roll_position(&client, &engine, event).await.unwrap();
+        assert_eq!(report.to.value(), "BTCUSDT_260925");
+        assert_eq!(report.closed_size, Size::from_str("2").unwrap());
+        assert_eq!(report.opened_size, Size::from_str("1.5").unwrap());
+        let close = client
+            .get_order_status(report.close_order_id)
+            .await
+            .unwrap();
+        assert_eq!(close.symbol.value(), "BTCUSDT_260626");
+
+        // Nothing is sent when the new position cannot pass at all
+        engine.halt_trading("maintenance").await;
+        assert!(matches!(
+            roll_position(&client, &engine, event).await,
+            Err(RollError::Risk(_))
+        ));
+        assert_eq!(client.get_order_history(None, None).await.unwrap().len(), 2);
+    }
+}
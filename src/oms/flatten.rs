@@ -0,0 +1,476 @@
+use crate::core::events::{NewOrder, OrderSide, TimeInForce, Timestamp};
+use crate::risk::SessionConfig;
+use crate::strategies::{
+    ExecutionDecision, ExecutionRequest, ExecutionStyle, ExecutionStyleConfig,
+    ExecutionStyleSelector,
+};
+use crate::types::{Price, Size, Symbol};
+use chrono::DateTime;
+use log::{info, warn};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// When and how fast positions are flattened ahead of a session end or maintenance
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlattenPolicy {
+    /// Start flattening this many milliseconds before the deadline
+    pub lead_time: u64,
+    /// Be flat this many milliseconds before the deadline
+    pub flat_by: u64,
+    /// Stop flattening a position once its fills are this many bps worse than arrival
+    pub max_slippage_bps: f64,
+}
+
+impl Default for FlattenPolicy {
+    fn default() -> Self {
+        Self {
+            lead_time: 30 * 60 * 1000,
+            flat_by: 5 * 60 * 1000,
+            max_slippage_bps: 25.0,
+        }
+    }
+}
+
+/// State of a position being flattened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlattenState {
+    Working,
+    Flat,
+    /// The slippage budget was exceeded; the rest of the position is left to the operator
+    Aborted,
+}
+
+/// Current position and touch of a symbol to flatten
+#[derive(Debug, Clone)]
+pub struct PositionQuote {
+    pub symbol: Symbol,
+    /// Signed position, positive for long
+    pub position: Size,
+    pub best_bid: Price,
+    pub best_ask: Price,
+    /// Estimated fill probability when posting at the touch
+    pub passive_fill_probability: f64,
+    /// Estimated fill probability when improving the touch by one tick
+    pub join_fill_probability: f64,
+}
+
+/// An order that moves a position towards flat
+#[derive(Debug, Clone)]
+pub struct FlattenSlice {
+    pub order: NewOrder,
+    /// Style decision behind the order, for `ExecutionStyleSelector::record_outcome`
+    pub decision: ExecutionDecision,
+}
+
+/// Progress flattening one symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlattenProgress {
+    pub symbol: String,
+    /// Session end or maintenance start being flattened for
+    pub deadline: Timestamp,
+    /// Position when the flatten window opened
+    pub initial_position: Size,
+    pub remaining_position: Size,
+    /// Mid price when the flatten window opened
+    pub arrival_price: Price,
+    pub average_fill_price: Option<Price>,
+    /// Average fill versus arrival in bps, positive when worse
+    pub slippage_bps: f64,
+    pub state: FlattenState,
+}
+
+impl FlattenProgress {
+    /// Fraction of the initial position closed so far
+    pub fn completion(&self) -> f64 {
+        let initial = self.initial_position.abs().value();
+        if initial.is_zero() {
+            return 1.0;
+        }
+        (Decimal::ONE - self.remaining_position.abs().value() / initial)
+            .to_f64()
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FlattenRun {
+    progress: FlattenProgress,
+    filled: Decimal,
+    fill_notional: Decimal,
+}
+
+/// Flattens positions ahead of session rollovers and maintenance windows
+///
+/// Once the window opens (`lead_time` before the deadline) each `poll` works the
+/// positions down along a straight line that reaches flat `flat_by` before the
+/// deadline. Slices are priced by an `ExecutionStyleSelector` with urgency rising
+/// across the window, so they start passive and cross as the deadline nears. Each
+/// poll replaces the previous slices: cancel any still working before placing new ones.
+pub struct FlattenScheduler {
+    policy: FlattenPolicy,
+    session: Option<SessionConfig>,
+    maintenance_windows: Vec<Timestamp>,
+    selector: ExecutionStyleSelector,
+    exchange_id: Option<String>,
+    deadline: Option<Timestamp>,
+    runs: HashMap<String, FlattenRun>,
+}
+
+impl FlattenScheduler {
+    /// Create a scheduler with no deadlines
+    pub fn new(policy: FlattenPolicy) -> Self {
+        Self {
+            policy,
+            session: None,
+            maintenance_windows: Vec::new(),
+            selector: ExecutionStyleSelector::default(),
+            exchange_id: None,
+            deadline: None,
+            runs: HashMap::new(),
+        }
+    }
+
+    /// Flatten ahead of every session rollover (builder pattern)
+    pub fn with_session(mut self, session: SessionConfig) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Flatten ahead of a maintenance window starting at `start` (builder pattern)
+    pub fn with_maintenance_window(mut self, start: Timestamp) -> Self {
+        self.add_maintenance_window(start);
+        self
+    }
+
+    /// Price slices with a custom execution style configuration (builder pattern)
+    pub fn with_execution_style(mut self, config: ExecutionStyleConfig) -> Self {
+        self.selector = ExecutionStyleSelector::new(config);
+        self
+    }
+
+    /// Route slices to an exchange (builder pattern)
+    pub fn with_exchange_id(mut self, exchange_id: impl Into<String>) -> Self {
+        self.exchange_id = Some(exchange_id.into());
+        self
+    }
+
+    /// Schedule a maintenance window announced by the venue
+    pub fn add_maintenance_window(&mut self, start: Timestamp) {
+        if let Err(index) = self.maintenance_windows.binary_search(&start) {
+            self.maintenance_windows.insert(index, start);
+        }
+    }
+
+    /// Get the policy
+    pub fn policy(&self) -> &FlattenPolicy {
+        &self.policy
+    }
+
+    /// Get the execution style selector, to record slice outcomes
+    pub fn selector_mut(&mut self) -> &mut ExecutionStyleSelector {
+        &mut self.selector
+    }
+
+    /// Get the next session rollover or maintenance start strictly after `now`
+    pub fn next_deadline(&self, now: Timestamp) -> Option<Timestamp> {
+        let rollover = self.session.as_ref().and_then(|session| {
+            DateTime::from_timestamp_millis(now as i64)
+                .map(|now| session.next_rollover(now).timestamp_millis() as u64)
+        });
+        let maintenance = self
+            .maintenance_windows
+            .iter()
+            .copied()
+            .find(|start| *start > now);
+        rollover.into_iter().chain(maintenance).min()
+    }
+
+    /// Check if the flatten window for the next deadline is open
+    pub fn is_active(&self, now: Timestamp) -> bool {
+        self.next_deadline(now)
+            .is_some_and(|deadline| now >= deadline.saturating_sub(self.policy.lead_time))
+    }
+
+    /// Get the orders that keep each position on schedule
+    /// Returns nothing outside the flatten window
+    pub fn poll(&mut self, now: Timestamp, quotes: &[PositionQuote]) -> Vec<FlattenSlice> {
+        let Some(deadline) = self.next_deadline(now) else {
+            return Vec::new();
+        };
+        let window_start = deadline.saturating_sub(self.policy.lead_time);
+        if now < window_start {
+            return Vec::new();
+        }
+        if self.deadline != Some(deadline) {
+            info!(
+                "Flattening {} positions ahead of deadline {}",
+                quotes.len(),
+                deadline
+            );
+            self.deadline = Some(deadline);
+            self.runs.clear();
+        }
+
+        let target_end = deadline
+            .saturating_sub(self.policy.flat_by)
+            .max(window_start);
+        let elapsed = if now >= target_end {
+            1.0
+        } else {
+            (now - window_start) as f64 / (target_end - window_start) as f64
+        };
+
+        let mut slices = Vec::new();
+        for quote in quotes {
+            let symbol = quote.symbol.value();
+            let mid = (quote.best_bid.value() + quote.best_ask.value()) / Decimal::TWO;
+            let run = self
+                .runs
+                .entry(symbol.to_string())
+                .or_insert_with(|| FlattenRun {
+                    progress: FlattenProgress {
+                        symbol: symbol.to_string(),
+                        deadline,
+                        initial_position: quote.position,
+                        remaining_position: quote.position,
+                        arrival_price: Price::new(mid),
+                        average_fill_price: None,
+                        slippage_bps: 0.0,
+                        state: FlattenState::Working,
+                    },
+                    filled: Decimal::ZERO,
+                    fill_notional: Decimal::ZERO,
+                });
+            if run.progress.state != FlattenState::Working {
+                continue;
+            }
+            run.progress.remaining_position = quote.position;
+            let initial = run.progress.initial_position.value();
+            let position = quote.position.value();
+            // Flat, or flipped through zero by fills from elsewhere
+            if position.is_zero()
+                || initial.is_zero()
+                || position.is_sign_positive() != initial.is_sign_positive()
+            {
+                run.progress.state = FlattenState::Flat;
+                continue;
+            }
+
+            let allowed = initial.abs() * Decimal::from_f64(1.0 - elapsed).unwrap_or(Decimal::ZERO);
+            let size = position.abs() - allowed;
+            if size <= Decimal::ZERO {
+                continue;
+            }
+            let side = if position.is_sign_positive() {
+                OrderSide::Sell
+            } else {
+                OrderSide::Buy
+            };
+            let Some(decision) = self.selector.select(&ExecutionRequest {
+                symbol: quote.symbol.clone(),
+                side,
+                size: Size::new(size),
+                best_bid: quote.best_bid,
+                best_ask: quote.best_ask,
+                urgency: elapsed,
+                passive_fill_probability: quote.passive_fill_probability,
+                join_fill_probability: quote.join_fill_probability,
+            }) else {
+                continue;
+            };
+
+            let time_in_force = match decision.style {
+                ExecutionStyle::Cross => TimeInForce::ImmediateOrCancel,
+                ExecutionStyle::Passive | ExecutionStyle::Join => TimeInForce::GoodTillCancelled,
+            };
+            let mut order = match side {
+                OrderSide::Buy => {
+                    NewOrder::new_limit_buy(symbol, decision.size, decision.price, time_in_force)
+                }
+                OrderSide::Sell => {
+                    NewOrder::new_limit_sell(symbol, decision.size, decision.price, time_in_force)
+                }
+            };
+            if let Some(exchange_id) = &self.exchange_id {
+                order = order.with_exchange_id(exchange_id.as_str());
+            }
+            slices.push(FlattenSlice { order, decision });
+        }
+        slices
+    }
+
+    /// Record a fill of a flatten slice
+    /// Aborts the symbol's flatten if its slippage budget is exceeded
+    pub fn record_fill(
+        &mut self,
+        symbol: &str,
+        size: Size,
+        price: Price,
+    ) -> Option<FlattenProgress> {
+        let run = self.runs.get_mut(symbol)?;
+        let size = size.abs().value();
+        run.filled += size;
+        run.fill_notional += size * price.value();
+
+        let progress = &mut run.progress;
+        let remaining = progress.remaining_position.value();
+        progress.remaining_position = if remaining.is_sign_positive() {
+            Size::new((remaining - size).max(Decimal::ZERO))
+        } else {
+            Size::new((remaining + size).min(Decimal::ZERO))
+        };
+
+        let average = run.fill_notional / run.filled;
+        progress.average_fill_price = Some(Price::new(average));
+        let arrival = progress.arrival_price.value();
+        if arrival > Decimal::ZERO {
+            // Closing a long sells, so filling below arrival is worse; the reverse for shorts
+            let shortfall = if progress.initial_position.is_positive() {
+                arrival - average
+            } else {
+                average - arrival
+            };
+            progress.slippage_bps = (shortfall / arrival * Decimal::from(10_000))
+                .to_f64()
+                .unwrap_or(0.0);
+        }
+
+        if progress.state == FlattenState::Working {
+            if progress.slippage_bps > self.policy.max_slippage_bps {
+                warn!(
+                    "Aborting flatten of {}: slippage {:.1} bps exceeds {:.1} bps budget, {} left",
+                    symbol,
+                    progress.slippage_bps,
+                    self.policy.max_slippage_bps,
+                    progress.remaining_position
+                );
+                progress.state = FlattenState::Aborted;
+            } else if progress.remaining_position.is_zero() {
+                progress.state = FlattenState::Flat;
+            }
+        }
+        Some(progress.clone())
+    }
+
+    /// Get the progress flattening a symbol for the current deadline
+    pub fn progress(&self, symbol: &str) -> Option<FlattenProgress> {
+        self.runs.get(symbol).map(|run| run.progress.clone())
+    }
+
+    /// Get the progress of every symbol for the current deadline, by symbol
+    pub fn all_progress(&self) -> Vec<FlattenProgress> {
+        let mut progress: Vec<_> = self.runs.values().map(|run| run.progress.clone()).collect();
+        progress.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-02 00:00:00 UTC, a Tuesday rollover
+    const ROLLOVER: Timestamp = 1_704_153_600_000;
+    const MINUTE: u64 = 60 * 1000;
+
+    fn quote(position: &str) -> PositionQuote {
+        PositionQuote {
+            symbol: Symbol::new("BTCUSDT"),
+            position: Size::from_str(position).unwrap(),
+            best_bid: Price::from_str("100.00").unwrap(),
+            best_ask: Price::from_str("100.10").unwrap(),
+            passive_fill_probability: 0.9,
+            join_fill_probability: 0.95,
+        }
+    }
+
+    #[test]
+    fn test_flatten_on_schedule_and_abort_on_slippage() {
+        let mut scheduler = FlattenScheduler::new(FlattenPolicy::default())
+            .with_session(SessionConfig::utc_midnight())
+            .with_exchange_id("binance");
+        assert_eq!(
+            scheduler.next_deadline(ROLLOVER - 40 * MINUTE),
+            Some(ROLLOVER)
+        );
+        assert!(scheduler
+            .poll(ROLLOVER - 40 * MINUTE, &[quote("10")])
+            .is_empty());
+        assert!(scheduler.progress("BTCUSDT").is_none());
+
+        // Window opens: nothing is due yet
+        assert!(scheduler
+            .poll(ROLLOVER - 30 * MINUTE, &[quote("10")])
+            .is_empty());
+        let progress = scheduler.progress("BTCUSDT").unwrap();
+        assert_eq!(progress.state, FlattenState::Working);
+        assert_eq!(progress.arrival_price, Price::from_str("100.05").unwrap());
+
+        // Halfway through the window half the position is due
+        let slices = scheduler.poll(ROLLOVER - 17 * MINUTE - 30_000, &[quote("10")]);
+        assert_eq!(slices.len(), 1);
+        assert_eq!(slices[0].order.side, OrderSide::Sell);
+        assert_eq!(slices[0].order.size, Size::from_str("5").unwrap());
+        assert_eq!(slices[0].order.exchange_id, "binance");
+        assert_ne!(slices[0].decision.style, ExecutionStyle::Cross);
+        let progress = scheduler
+            .record_fill(
+                "BTCUSDT",
+                Size::from_str("5").unwrap(),
+                Price::from_str("100.10").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(progress.remaining_position, Size::from_str("5").unwrap());
+        assert_eq!(progress.completion(), 0.5);
+        assert!(progress.slippage_bps < 0.0);
+
+        // Past the flat-by target the rest crosses
+        let slices = scheduler.poll(ROLLOVER - 5 * MINUTE, &[quote("5")]);
+        assert_eq!(slices[0].order.size, Size::from_str("5").unwrap());
+        assert_eq!(slices[0].decision.style, ExecutionStyle::Cross);
+        assert_eq!(
+            slices[0].order.time_in_force,
+            TimeInForce::ImmediateOrCancel
+        );
+
+        // A fill far below arrival blows the budget and stops the flatten
+        let progress = scheduler
+            .record_fill(
+                "BTCUSDT",
+                Size::from_str("2").unwrap(),
+                Price::from_str("99.00").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(progress.state, FlattenState::Aborted);
+        assert!(scheduler
+            .poll(ROLLOVER - 4 * MINUTE, &[quote("3")])
+            .is_empty());
+
+        // The next session starts a fresh run
+        let slices = scheduler.poll(ROLLOVER + 24 * 60 * MINUTE - 20 * MINUTE, &[quote("-3")]);
+        assert_eq!(slices[0].order.side, OrderSide::Buy);
+        assert_eq!(
+            scheduler.progress("BTCUSDT").unwrap().state,
+            FlattenState::Working
+        );
+    }
+
+    #[test]
+    fn test_maintenance_window_deadline() {
+        let scheduler = FlattenScheduler::new(FlattenPolicy::default())
+            .with_session(SessionConfig::utc_midnight())
+            .with_maintenance_window(ROLLOVER - 60 * MINUTE);
+        assert_eq!(
+            scheduler.next_deadline(ROLLOVER - 2 * 60 * MINUTE),
+            Some(ROLLOVER - 60 * MINUTE)
+        );
+        assert_eq!(
+            scheduler.next_deadline(ROLLOVER - 30 * MINUTE),
+            Some(ROLLOVER)
+        );
+        assert!(scheduler.is_active(ROLLOVER - 70 * MINUTE));
+        assert!(!scheduler.is_active(ROLLOVER - 100 * MINUTE));
+    }
+}
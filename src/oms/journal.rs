@@ -0,0 +1,397 @@
+use crate::core::clock::{system_clock, SharedClock};
+use crate::traits::{ExecutionReport, NewOrder, OrderId, OrderStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Step in the life of an order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalEvent {
+    /// Order requested by a strategy, before any splitting or slicing
+    Intent { order: NewOrder },
+    /// Order accepted for sending by the venue's API
+    Submitted { order: NewOrder, order_id: OrderId },
+    /// Order refused before reaching the venue, or by its API
+    SubmitFailed { order: NewOrder, reason: String },
+    /// Cancel sent for an order
+    CancelRequested { order_id: OrderId },
+    /// Order replaced by an amend
+    Replaced {
+        order_id: OrderId,
+        new_order_id: OrderId,
+    },
+    /// Order working on the book
+    Acknowledged { report: ExecutionReport },
+    /// Partial or full fill
+    Fill { report: ExecutionReport },
+    /// Order cancelled or expired
+    Cancelled { report: ExecutionReport },
+    /// Order rejected by the venue
+    Rejected { report: ExecutionReport },
+}
+
+impl JournalEvent {
+    /// Classify an execution report by its status
+    pub fn from_report(report: ExecutionReport) -> Self {
+        match report.status {
+            OrderStatus::New => JournalEvent::Acknowledged { report },
+            OrderStatus::PartiallyFilled | OrderStatus::Filled => JournalEvent::Fill { report },
+            OrderStatus::Cancelled | OrderStatus::Expired => JournalEvent::Cancelled { report },
+            OrderStatus::Rejected => JournalEvent::Rejected { report },
+        }
+    }
+
+    /// Get the order IDs the event refers to
+    pub fn order_ids(&self) -> Vec<&str> {
+        match self {
+            JournalEvent::Intent { .. } | JournalEvent::SubmitFailed { .. } => Vec::new(),
+            JournalEvent::Submitted { order_id, .. }
+            | JournalEvent::CancelRequested { order_id } => vec![order_id.as_str()],
+            JournalEvent::Replaced {
+                order_id,
+                new_order_id,
+            } => vec![order_id.as_str(), new_order_id.as_str()],
+            JournalEvent::Acknowledged { report }
+            | JournalEvent::Fill { report }
+            | JournalEvent::Cancelled { report }
+            | JournalEvent::Rejected { report } => vec![report.order_id.as_str()],
+        }
+    }
+
+    /// Get the client order ID the event refers to, if known
+    pub fn client_order_id(&self) -> Option<&str> {
+        match self {
+            JournalEvent::Intent { order }
+            | JournalEvent::Submitted { order, .. }
+            | JournalEvent::SubmitFailed { order, .. } => order.client_order_id.as_deref(),
+            JournalEvent::CancelRequested { .. } | JournalEvent::Replaced { .. } => None,
+            JournalEvent::Acknowledged { report }
+            | JournalEvent::Fill { report }
+            | JournalEvent::Cancelled { report }
+            | JournalEvent::Rejected { report } => report.client_order_id.as_deref(),
+        }
+    }
+}
+
+/// A record in the order journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Position in the journal, starting at one
+    pub seq: u64,
+    /// Monotonic time in nanoseconds, never decreasing across restarts
+    pub monotonic_ns: u64,
+    /// Wall-clock time in milliseconds
+    pub timestamp: u64,
+    pub event: JournalEvent,
+}
+
+/// Journal file being appended to
+struct JournalFile {
+    writer: BufWriter<File>,
+    /// Sequence number of the last entry
+    seq: u64,
+    /// Monotonic time of the last entry written before this process opened the file
+    monotonic_base: u64,
+}
+
+/// Append-only journal of order lifecycle events, one JSON object per line
+///
+/// Every entry is flushed as it is written, and with `with_sync` also synced to disk, so
+/// the journal survives a crash. Reopening a journal continues its sequence numbers and
+/// monotonic time. Read it back with `JournalReader`.
+pub struct OrderJournal {
+    file: Mutex<JournalFile>,
+    /// Call fsync after each entry
+    sync: bool,
+    /// Monotonic time origin of this process
+    opened_at: Instant,
+    /// Time source for timestamps
+    clock: SharedClock,
+}
+
+impl OrderJournal {
+    /// Open a journal file, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let (seq, monotonic_base) = match path.exists() {
+            true => JournalReader::open(path)?
+                .entries()
+                .last()
+                .map_or((0, 0), |last| (last.seq, last.monotonic_ns + 1)),
+            false => (0, 0),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        // Drop a torn last entry so appends start on a fresh line
+        let contents = std::fs::read(path)?;
+        let valid_len = contents
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        if valid_len < contents.len() {
+            log::warn!(
+                "Truncating {} bytes of torn journal entry",
+                contents.len() - valid_len
+            );
+            file.set_len(valid_len as u64)?;
+        }
+        let clock = system_clock();
+        Ok(Self {
+            file: Mutex::new(JournalFile {
+                writer: BufWriter::new(file),
+                seq,
+                monotonic_base,
+            }),
+            sync: false,
+            opened_at: clock.now_instant(),
+            clock,
+        })
+    }
+
+    /// Set the time source (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.opened_at = clock.now_instant();
+        self.clock = clock;
+        self
+    }
+
+    /// Sync each entry to disk rather than only flushing it (builder pattern)
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Append an event, returning its entry
+    pub fn record(&self, event: JournalEvent) -> io::Result<JournalEntry> {
+        let mut file = self.file.lock().unwrap();
+        let elapsed = self
+            .clock
+            .now_instant()
+            .duration_since(self.opened_at)
+            .as_nanos() as u64;
+        let entry = JournalEntry {
+            seq: file.seq + 1,
+            monotonic_ns: file.monotonic_base + elapsed,
+            timestamp: self.clock.now_millis(),
+            event,
+        };
+
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        file.writer.write_all(&line)?;
+        file.writer.flush()?;
+        if self.sync {
+            file.writer.get_ref().sync_data()?;
+        }
+        file.seq = entry.seq;
+        Ok(entry)
+    }
+
+    /// Append an execution report, classified by its status
+    pub fn record_report(&self, report: &ExecutionReport) -> io::Result<JournalEntry> {
+        self.record(JournalEvent::from_report(report.clone()))
+    }
+
+    /// Get the sequence number of the last entry
+    pub fn last_seq(&self) -> u64 {
+        self.file.lock().unwrap().seq
+    }
+}
+
+/// Reader reconstructing order history from a journal file
+pub struct JournalReader {
+    entries: Vec<JournalEntry>,
+}
+
+impl JournalReader {
+    /// Read a journal file
+    /// A partly written last line, left by a crash mid-write, is ignored; any other line
+    /// that does not parse is an error.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let lines: Vec<String> = BufReader::new(File::open(path)?)
+            .lines()
+            .collect::<io::Result<_>>()?;
+        let mut entries = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) if i + 1 == lines.len() => {
+                    log::warn!("Ignoring truncated last journal entry");
+                }
+                Err(e) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Bad journal entry on line {}: {}", i + 1, e),
+                    ))
+                }
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Get all entries, oldest first
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Get every entry about an order, oldest first
+    /// Includes the intent and submission entries sharing its client order ID.
+    pub fn order_history(&self, order_id: &str) -> Vec<&JournalEntry> {
+        let client_order_ids: HashSet<&str> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.event.order_ids().contains(&order_id))
+            .filter_map(|entry| entry.event.client_order_id())
+            .collect();
+
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry.event.order_ids().contains(&order_id)
+                    || entry
+                        .event
+                        .client_order_id()
+                        .is_some_and(|id| client_order_ids.contains(id))
+            })
+            .collect()
+    }
+
+    /// Get the IDs of all orders in the journal, in the order they first appear
+    pub fn order_ids(&self) -> Vec<OrderId> {
+        let mut seen = HashSet::new();
+        self.entries
+            .iter()
+            .flat_map(|entry| entry.event.order_ids())
+            .filter(|id| seen.insert(*id))
+            .map(|id| id.to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Size;
+    use std::time::Duration;
+
+    fn report(order_id: &str, status: OrderStatus, filled: &str) -> ExecutionReport {
+        ExecutionReport {
+            order_id: order_id.to_string(),
+            client_order_id: Some("mm_1".to_string()),
+            symbol: "BTCUSDT".into(),
+            exchange_id: "binance".to_string(),
+            status,
+            filled_size: Size::from_str(filled).unwrap(),
+            remaining_size: Size::from_str("1").unwrap() - Size::from_str(filled).unwrap(),
+            average_price: None,
+            timestamp: 0,
+            reject_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_order_journal() {
+        let path = std::env::temp_dir().join(format!("journal_{}.jsonl", uuid::Uuid::new_v4()));
+        let clock = crate::core::clock::SimulatedClock::at_millis(1_000);
+        let order = NewOrder::new_market_buy("BTCUSDT", Size::from_str("1").unwrap())
+            .with_client_order_id("mm_1".to_string());
+
+        let journal = OrderJournal::open(&path)
+            .unwrap()
+            .with_clock(clock.shared());
+        journal
+            .record(JournalEvent::Intent {
+                order: order.clone(),
+            })
+            .unwrap();
+        clock.advance(Duration::from_millis(2));
+        journal
+            .record(JournalEvent::Submitted {
+                order: order.clone(),
+                order_id: "42".to_string(),
+            })
+            .unwrap();
+        journal
+            .record_report(&report("42", OrderStatus::New, "0"))
+            .unwrap();
+        journal
+            .record(JournalEvent::SubmitFailed {
+                order: NewOrder::new_market_sell("ETHUSDT", Size::from_str("1").unwrap()),
+                reason: "Trading halted".to_string(),
+            })
+            .unwrap();
+        let last = journal
+            .record_report(&report("42", OrderStatus::PartiallyFilled, "0.4"))
+            .unwrap();
+        assert_eq!(last.seq, 5);
+        assert_eq!(last.monotonic_ns, 2_000_000);
+        assert_eq!(last.timestamp, 1_002);
+        drop(journal);
+
+        // A reopened journal carries on, and a torn last line is skipped on read
+        let journal = OrderJournal::open(&path).unwrap();
+        let entry = journal
+            .record(JournalEvent::CancelRequested {
+                order_id: "42".to_string(),
+            })
+            .unwrap();
+        assert_eq!(entry.seq, 6);
+        assert!(entry.monotonic_ns > last.monotonic_ns);
+        journal
+            .record_report(&report("42", OrderStatus::Cancelled, "0.4"))
+            .unwrap();
+        drop(journal);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"seq\":8,\"monot").unwrap();
+
+        let reader = JournalReader::open(&path).unwrap();
+        assert_eq!(reader.entries().len(), 7);
+        assert_eq!(reader.order_ids(), vec!["42".to_string()]);
+
+        let history = reader.order_history("42");
+        let kinds: Vec<&str> = history
+            .iter()
+            .map(|entry| match entry.event {
+                JournalEvent::Intent { .. } => "intent",
+                JournalEvent::Submitted { .. } => "submitted",
+                JournalEvent::Acknowledged { .. } => "ack",
+                JournalEvent::Fill { .. } => "fill",
+                JournalEvent::CancelRequested { .. } => "cancel_requested",
+                JournalEvent::Cancelled { .. } => "cancelled",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "intent",
+                "submitted",
+                "ack",
+                "fill",
+                "cancel_requested",
+                "cancelled"
+            ]
+        );
+
+        // Reopening drops the torn line before appending
+        let journal = OrderJournal::open(&path).unwrap();
+        assert_eq!(journal.last_seq(), 7);
+        journal
+            .record(JournalEvent::CancelRequested {
+                order_id: "43".to_string(),
+            })
+            .unwrap();
+        let reader = JournalReader::open(&path).unwrap();
+        assert_eq!(reader.entries().len(), 8);
+        assert_eq!(reader.order_ids(), vec!["42".to_string(), "43".to_string()]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,6 +1,22 @@
+pub mod expiry;
+pub mod flatten;
+pub mod journal;
 pub mod order_manager;
+pub mod order_symbols;
+pub mod order_transfer;
 pub mod rate_limiter;
+pub mod trigger;
+pub mod venue_throttle;
 
 pub use crate::traits::OrderManager;
-pub use order_manager::OrderManagerImpl;
-pub use rate_limiter::RateLimiter;
+pub use expiry::{roll_position, ExpiryConfig, ExpiryMonitor, RollError, RollPlan, RollReport};
+pub use flatten::{
+    FlattenPolicy, FlattenProgress, FlattenScheduler, FlattenSlice, FlattenState, PositionQuote,
+};
+pub use journal::{JournalEntry, JournalEvent, JournalReader, OrderJournal};
+pub use order_manager::{OrderGroup, OrderGroupStatus, OrderManagerImpl};
+pub use order_symbols::OrderSymbolRegistry;
+pub use order_transfer::{ImportPlan, ImportReport, OrderExport, OrderTransferError, WorkingOrder};
+pub use rate_limiter::{RateLimitKind, RateLimiter, WeightedRateLimiter};
+pub use trigger::{StopOrder, TriggerEngine, TriggerError, TriggerSource};
+pub use venue_throttle::{AllocationPolicy, ThrottleError, VenueThrottle, VenueThrottleRegistry};
@@ -1,14 +1,21 @@
+use crate::core::clock::{system_clock, Clock, SharedClock, SystemClock};
+use crate::core::reject::RejectReason;
 use crate::traits::{
-    ExecutionReport, OrderId, OrderManager, OrderSide, OrderStatus, OrderType, TimeInForce,
+    ExecutionClient, ExecutionReport, OrderId, OrderManager, OrderPage, OrderQuery, OrderSide,
+    OrderStatus, OrderType, TimeInForce,
 };
 use crate::types::{Price, Size, Symbol};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use log::{error, info, warn};
 use rust_decimal::prelude::ToPrimitive;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+type SharedExecutionClient =
+    Arc<dyn ExecutionClient<Error = Box<dyn std::error::Error + Send + Sync>> + Send + Sync>;
+
 /// Order information tracked by the order manager
 #[derive(Debug, Clone)]
 pub struct OrderInfo {
@@ -42,6 +49,14 @@ pub struct OrderInfo {
     pub updated_at: DateTime<Utc>,
     /// Exchange ID
     pub exchange_id: String,
+    /// Strategy that placed the order
+    pub strategy_tag: Option<String>,
+    /// Why the exchange rejected the order, if it did
+    pub reject_reason: Option<RejectReason>,
+    /// Order this one replaced when it was amended
+    pub replaces: Option<OrderId>,
+    /// Order that replaced this one when it was amended
+    pub replaced_by: Option<OrderId>,
 }
 
 impl OrderInfo {
@@ -57,7 +72,7 @@ impl OrderInfo {
         price: Option<Price>,
         exchange_id: String,
     ) -> Self {
-        let now = Utc::now();
+        let now = SystemClock.now_utc();
         Self {
             order_id,
             client_order_id,
@@ -74,13 +89,51 @@ impl OrderInfo {
             created_at: now,
             updated_at: now,
             exchange_id,
+            strategy_tag: None,
+            reject_reason: None,
+            replaces: None,
+            replaced_by: None,
+        }
+    }
+
+    /// Stamp the order as created at a time, such as from a clock (builder pattern)
+    pub fn with_timestamp(mut self, now: DateTime<Utc>) -> Self {
+        self.created_at = now;
+        self.updated_at = now;
+        self
+    }
+
+    /// Tag the order with the strategy that placed it (builder pattern)
+    pub fn with_strategy_tag(mut self, strategy_tag: impl Into<String>) -> Self {
+        self.strategy_tag = Some(strategy_tag.into());
+        self
+    }
+
+    /// Report the order's current state
+    pub fn to_execution_report(&self) -> ExecutionReport {
+        ExecutionReport {
+            order_id: self.order_id.clone(),
+            client_order_id: self.client_order_id.clone(),
+            symbol: self.symbol.clone(),
+            exchange_id: self.exchange_id.clone(),
+            status: self.status,
+            filled_size: self.filled_quantity,
+            remaining_size: self.remaining_quantity,
+            average_price: self.average_fill_price,
+            timestamp: self.updated_at.timestamp_millis() as u64,
+            reject_reason: self.reject_reason,
         }
     }
 
     /// Update order info with an execution report
     pub fn update(&mut self, report: &ExecutionReport) {
+        self.update_at(report, SystemClock.now_utc());
+    }
+
+    /// Update order info with an execution report received at a time
+    pub fn update_at(&mut self, report: &ExecutionReport, now: DateTime<Utc>) {
         self.status = report.status;
-        self.updated_at = Utc::now();
+        self.updated_at = now;
 
         match report.status {
             OrderStatus::New => {
@@ -107,7 +160,10 @@ impl OrderInfo {
             OrderStatus::Cancelled => {
                 self.remaining_quantity = report.remaining_size;
             }
-            OrderStatus::Rejected | OrderStatus::Expired => {
+            OrderStatus::Rejected => {
+                self.reject_reason = report.reject_reason;
+            }
+            OrderStatus::Expired => {
                 // No change to quantities
             }
         }
@@ -144,6 +200,41 @@ impl OrderInfo {
     }
 }
 
+/// State of an order group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderGroupStatus {
+    /// Every leg is still working
+    Active,
+    /// A leg filled and the others were cancelled
+    Triggered { filled_order_id: OrderId },
+    /// A leg was cancelled, rejected or expired, taking the others with it
+    Cancelled,
+}
+
+/// One-cancels-other group, e.g. a take-profit and a stop-loss on the same position
+#[derive(Debug, Clone)]
+pub struct OrderGroup {
+    /// Group ID
+    pub group_id: String,
+    /// Linked orders
+    pub order_ids: Vec<OrderId>,
+    /// Group status
+    pub status: OrderGroupStatus,
+    /// Legs the exchange failed to cancel when the group resolved
+    pub cancel_failures: Vec<OrderId>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OrderGroup {
+    /// Check if the group is still waiting for a leg to fill
+    pub fn is_active(&self) -> bool {
+        self.status == OrderGroupStatus::Active
+    }
+}
+
 /// Order manager implementation
 #[allow(dead_code)]
 pub struct OrderManagerImpl {
@@ -157,6 +248,14 @@ pub struct OrderManagerImpl {
     orders_by_client_id: Arc<RwLock<HashMap<String, OrderId>>>,
     /// Exchange ID
     exchange_id: String,
+    /// Order groups by group ID
+    order_groups: Arc<RwLock<HashMap<String, OrderGroup>>>,
+    /// Group ID by order ID
+    group_by_order: Arc<RwLock<HashMap<OrderId, String>>>,
+    /// Clients used to cancel the remaining legs of a group, by exchange ID
+    execution_clients: HashMap<String, SharedExecutionClient>,
+    /// Time source for order and group timestamps
+    clock: SharedClock,
 }
 
 impl OrderManagerImpl {
@@ -168,6 +267,182 @@ impl OrderManagerImpl {
             active_orders_by_symbol: Arc::new(RwLock::new(HashMap::new())),
             orders_by_client_id: Arc::new(RwLock::new(HashMap::new())),
             exchange_id,
+            order_groups: Arc::new(RwLock::new(HashMap::new())),
+            group_by_order: Arc::new(RwLock::new(HashMap::new())),
+            execution_clients: HashMap::new(),
+            clock: system_clock(),
+        }
+    }
+
+    /// Set the time source (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Get the time source
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Cancel grouped orders on an exchange through this client (builder pattern)
+    pub fn with_execution_client(
+        mut self,
+        exchange_id: impl Into<String>,
+        client: SharedExecutionClient,
+    ) -> Self {
+        self.execution_clients.insert(exchange_id.into(), client);
+        self
+    }
+
+    /// Get the exchange ID
+    pub fn exchange_id(&self) -> &str {
+        &self.exchange_id
+    }
+
+    /// Get the client for an exchange, or the only client if there is just one
+    fn execution_client(&self, exchange_id: &str) -> Option<&SharedExecutionClient> {
+        self.execution_clients.get(exchange_id).or_else(|| {
+            let mut clients = self.execution_clients.values();
+            match (clients.next(), clients.next()) {
+                (Some(client), None) => Some(client),
+                _ => None,
+            }
+        })
+    }
+
+    /// Link active orders so that when one fills, the others are cancelled
+    pub async fn create_order_group(
+        &self,
+        group_id: impl Into<String>,
+        order_ids: Vec<OrderId>,
+    ) -> Result<(), OrderManagerError> {
+        let group_id = group_id.into();
+        if order_ids.len() < 2 {
+            return Err(OrderManagerError::InvalidOrder(format!(
+                "Order group {} needs at least two orders",
+                group_id
+            )));
+        }
+
+        let orders = self.orders.read().await;
+        let mut order_groups = self.order_groups.write().await;
+        let mut group_by_order = self.group_by_order.write().await;
+        if order_groups.contains_key(&group_id) {
+            return Err(OrderManagerError::InvalidOrder(format!(
+                "Order group {} already exists",
+                group_id
+            )));
+        }
+        for order_id in &order_ids {
+            let order = orders
+                .get(order_id)
+                .ok_or_else(|| OrderManagerError::OrderNotFound(order_id.clone()))?;
+            if !order.is_active() {
+                return Err(OrderManagerError::InvalidOrder(format!(
+                    "Order {} is no longer active",
+                    order_id
+                )));
+            }
+            if let Some(existing) = group_by_order.get(order_id) {
+                return Err(OrderManagerError::InvalidOrder(format!(
+                    "Order {} is already in group {}",
+                    order_id, existing
+                )));
+            }
+        }
+
+        for order_id in &order_ids {
+            group_by_order.insert(order_id.clone(), group_id.clone());
+        }
+        let now = self.clock.now_utc();
+        order_groups.insert(
+            group_id.clone(),
+            OrderGroup {
+                group_id,
+                order_ids,
+                status: OrderGroupStatus::Active,
+                cancel_failures: Vec::new(),
+                created_at: now,
+                updated_at: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Get an order group by ID
+    pub async fn get_order_group(&self, group_id: &str) -> Option<OrderGroup> {
+        self.order_groups.read().await.get(group_id).cloned()
+    }
+
+    /// Get all order groups
+    pub async fn get_order_groups(&self) -> Vec<OrderGroup> {
+        self.order_groups.read().await.values().cloned().collect()
+    }
+
+    /// Resolve the group of an order that filled or finished, cancelling its other legs
+    async fn resolve_order_group(&self, order_id: &OrderId, filled: bool) {
+        let Some(group_id) = self.group_by_order.read().await.get(order_id).cloned() else {
+            return;
+        };
+        let siblings: Vec<OrderId> = {
+            let mut order_groups = self.order_groups.write().await;
+            let Some(group) = order_groups
+                .get_mut(&group_id)
+                .filter(|group| group.is_active())
+            else {
+                return;
+            };
+            group.status = if filled {
+                OrderGroupStatus::Triggered {
+                    filled_order_id: order_id.clone(),
+                }
+            } else {
+                OrderGroupStatus::Cancelled
+            };
+            group.updated_at = self.clock.now_utc();
+            group
+                .order_ids
+                .iter()
+                .filter(|id| *id != order_id)
+                .cloned()
+                .collect()
+        };
+        info!(
+            "Order group {} resolved by {}, cancelling {} other legs",
+            group_id,
+            order_id,
+            siblings.len()
+        );
+
+        let mut cancel_failures = Vec::new();
+        for sibling in siblings {
+            let Some(order) = self.get_order(&sibling).await.filter(OrderInfo::is_active) else {
+                continue;
+            };
+            match self.execution_client(&order.exchange_id) {
+                Some(client) => {
+                    if let Err(e) = client.cancel_order(sibling.clone()).await {
+                        error!(
+                            "Failed to cancel order {} of group {}: {}",
+                            sibling, group_id, e
+                        );
+                        cancel_failures.push(sibling);
+                        continue;
+                    }
+                }
+                None => warn!(
+                    "No execution client for {}, cancelling order {} locally only",
+                    order.exchange_id, sibling
+                ),
+            }
+            self.cancel_order(&sibling).await;
+        }
+
+        if !cancel_failures.is_empty() {
+            if let Some(group) = self.order_groups.write().await.get_mut(&group_id) {
+                group.cancel_failures = cancel_failures;
+            }
         }
     }
 
@@ -256,6 +531,26 @@ impl OrderManagerImpl {
             .collect()
     }
 
+    /// Cancel one active order, returning false if it is unknown or no longer active
+    pub async fn cancel_order(&self, order_id: &OrderId) -> bool {
+        let mut orders = self.orders.write().await;
+        let Some(order) = orders.get_mut(order_id).filter(|order| order.is_active()) else {
+            return false;
+        };
+        order.status = OrderStatus::Cancelled;
+        order.updated_at = self.clock.now_utc();
+        let symbol = order.symbol.value().to_string();
+
+        let mut active_orders_by_symbol = self.active_orders_by_symbol.write().await;
+        if let Some(order_ids) = active_orders_by_symbol.get_mut(&symbol) {
+            order_ids.retain(|id| id != order_id);
+            if order_ids.is_empty() {
+                active_orders_by_symbol.remove(&symbol);
+            }
+        }
+        true
+    }
+
     /// Cancel all orders for a symbol
     pub async fn cancel_all_orders_for_symbol(&self, symbol: &str) -> Vec<OrderId> {
         let active_orders = self.get_active_orders_by_symbol(symbol).await;
@@ -269,7 +564,7 @@ impl OrderManagerImpl {
         for order_id in &order_ids {
             if let Some(order) = orders.get_mut(order_id) {
                 order.status = OrderStatus::Cancelled;
-                order.updated_at = Utc::now();
+                order.updated_at = self.clock.now_utc();
             }
         }
 
@@ -280,6 +575,22 @@ impl OrderManagerImpl {
         order_ids
     }
 
+    /// Cancel all active orders across every symbol
+    pub async fn cancel_all_orders(&self) -> Vec<OrderId> {
+        let symbols: Vec<String> = self
+            .active_orders_by_symbol
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .collect();
+        let mut order_ids = Vec::new();
+        for symbol in symbols {
+            order_ids.extend(self.cancel_all_orders_for_symbol(&symbol).await);
+        }
+        order_ids
+    }
+
     /// Get total position for a symbol
     pub async fn get_position_for_symbol(&self, symbol: &str) -> Size {
         let orders = self.get_orders_by_symbol(symbol).await;
@@ -418,7 +729,7 @@ impl OrderManager for OrderManagerImpl {
         if let Some(order) = orders.get_mut(&order_id) {
             // Update existing order
             let old_status = order.status.clone();
-            order.update(&report);
+            order.update_at(&report, self.clock.now_utc());
 
             // Update symbol mappings if status changed
             if old_status != order.status {
@@ -447,6 +758,14 @@ impl OrderManager for OrderManagerImpl {
                 }
             }
 
+            // Any fill, or the order ending without one, resolves its group
+            let filled = order.filled_quantity.is_positive();
+            let finished = !order.is_active();
+            drop(orders);
+            if filled || finished {
+                self.resolve_order_group(&order_id, filled).await;
+            }
+
             Ok(())
         } else {
             // This is an order we're not tracking - skip it
@@ -461,17 +780,7 @@ impl OrderManager for OrderManagerImpl {
         let orders = self.orders.read().await;
         let execution_reports: Vec<ExecutionReport> = orders
             .values()
-            .map(|order| ExecutionReport {
-                order_id: order.order_id.clone(),
-                client_order_id: order.client_order_id.clone(),
-                symbol: order.symbol.clone(),
-                exchange_id: order.exchange_id.clone(),
-                status: order.status,
-                filled_size: order.filled_quantity,
-                remaining_size: order.remaining_quantity,
-                average_price: order.average_fill_price,
-                timestamp: order.updated_at.timestamp_millis() as u64,
-            })
+            .map(OrderInfo::to_execution_report)
             .collect();
 
         Ok(execution_reports)
@@ -482,20 +791,8 @@ impl OrderManager for OrderManagerImpl {
         symbol: &str,
     ) -> Result<Vec<ExecutionReport>, Self::Error> {
         let orders = OrderManagerImpl::get_orders_by_symbol(self, symbol).await;
-        let execution_reports: Vec<ExecutionReport> = orders
-            .iter()
-            .map(|order| ExecutionReport {
-                order_id: order.order_id.clone(),
-                client_order_id: order.client_order_id.clone(),
-                symbol: order.symbol.clone(),
-                exchange_id: order.exchange_id.clone(),
-                status: order.status,
-                filled_size: order.filled_quantity,
-                remaining_size: order.remaining_quantity,
-                average_price: order.average_fill_price,
-                timestamp: order.updated_at.timestamp_millis() as u64,
-            })
-            .collect();
+        let execution_reports: Vec<ExecutionReport> =
+            orders.iter().map(OrderInfo::to_execution_report).collect();
 
         Ok(execution_reports)
     }
@@ -504,21 +801,77 @@ impl OrderManager for OrderManagerImpl {
         let active_orders = self.get_all_active_orders().await;
         let execution_reports: Vec<ExecutionReport> = active_orders
             .iter()
-            .map(|order| ExecutionReport {
-                order_id: order.order_id.clone(),
-                client_order_id: order.client_order_id.clone(),
-                symbol: order.symbol.clone(),
-                exchange_id: order.exchange_id.clone(),
-                status: order.status,
-                filled_size: order.filled_quantity,
-                remaining_size: order.remaining_quantity,
-                average_price: order.average_fill_price,
-                timestamp: order.updated_at.timestamp_millis() as u64,
-            })
+            .map(OrderInfo::to_execution_report)
             .collect();
 
         Ok(execution_reports)
     }
+
+    async fn record_replacement(
+        &mut self,
+        order_id: &OrderId,
+        new_order_id: OrderId,
+        new_price: Option<Price>,
+        new_size: Option<Size>,
+    ) -> Result<(), Self::Error> {
+        let replacement = {
+            let mut orders = self.orders.write().await;
+            let order = orders
+                .get_mut(order_id)
+                .ok_or_else(|| OrderManagerError::OrderNotFound(order_id.clone()))?;
+
+            // The replacement carries the order forward with a fresh fill history
+            let mut replacement = order.clone();
+            replacement.order_id = new_order_id.clone();
+            replacement.price = new_price.or(order.price);
+            replacement.quantity = new_size.unwrap_or(order.remaining_quantity);
+            replacement.remaining_quantity = replacement.quantity;
+            replacement.filled_quantity = Size::new(rust_decimal::Decimal::ZERO);
+            replacement.average_fill_price = None;
+            replacement.status = OrderStatus::New;
+            replacement.created_at = self.clock.now_utc();
+            replacement.updated_at = replacement.created_at;
+            replacement.replaces = Some(order_id.clone());
+            replacement.replaced_by = None;
+
+            // The original keeps its fills as history
+            order.replaced_by = Some(new_order_id.clone());
+            replacement
+        };
+        // Moves the original out of the active orders and the client ID to the replacement
+        self.cancel_order(order_id).await;
+        self.add_order(replacement).await;
+
+        // An amended leg stays in its order group
+        let mut group_by_order = self.group_by_order.write().await;
+        if let Some(group_id) = group_by_order.remove(order_id) {
+            if let Some(group) = self.order_groups.write().await.get_mut(&group_id) {
+                for id in group.order_ids.iter_mut().filter(|id| *id == order_id) {
+                    *id = new_order_id.clone();
+                }
+            }
+            group_by_order.insert(new_order_id, group_id);
+        }
+        Ok(())
+    }
+
+    async fn query_orders(&self, query: &OrderQuery) -> Result<OrderPage, Self::Error> {
+        // Filter under the read lock so only matching orders are cloned
+        let orders = self.orders.read().await;
+        let matching = orders
+            .values()
+            .filter(|order| {
+                query.matches(
+                    order.symbol.value(),
+                    order.status,
+                    order.updated_at.timestamp_millis() as u64,
+                    order.strategy_tag.as_deref(),
+                )
+            })
+            .map(OrderInfo::to_execution_report)
+            .collect();
+        Ok(query.paginate(matching))
+    }
 }
 
 /// Order manager error types
@@ -609,6 +962,7 @@ mod tests {
             remaining_size: Size::from_str("0.5").unwrap(),
             average_price: Some(Price::from_str("50000.0").unwrap()),
             timestamp: 1638368000000,
+            reject_reason: None,
         };
 
         order_info.update(&report);
@@ -632,6 +986,7 @@ mod tests {
             remaining_size: Size::from_str("0.0").unwrap(),
             average_price: Some(Price::from_str("50000.0").unwrap()),
             timestamp: 1638368000000,
+            reject_reason: None,
         };
 
         order_info.update(&report);
@@ -700,6 +1055,37 @@ mod tests {
         assert_eq!(active_orders[0].order_id, order_id);
     }
 
+    #[tokio::test]
+    async fn test_order_manager_stamps_orders_with_its_clock() {
+        let clock = crate::core::clock::SimulatedClock::at_millis(1_700_000_000_000);
+        let order_manager = OrderManagerImpl::new("binance".to_string()).with_clock(clock.shared());
+        let order_id = "12345".to_string();
+        order_manager
+            .add_order(
+                OrderInfo::new(
+                    order_id.clone(),
+                    None,
+                    Symbol::new("BTCUSDT"),
+                    OrderSide::Buy,
+                    OrderType::Limit,
+                    TimeInForce::GoodTillCancelled,
+                    Size::from_str("1.0").unwrap(),
+                    Some(Price::from_str("50000.0").unwrap()),
+                    "binance".to_string(),
+                )
+                .with_timestamp(order_manager.clock().now_utc()),
+            )
+            .await;
+        let order = order_manager.get_order(&order_id).await.unwrap();
+        assert_eq!(order.created_at.timestamp_millis(), 1_700_000_000_000);
+
+        clock.advance(std::time::Duration::from_secs(5));
+        assert!(order_manager.cancel_order(&order_id).await);
+        let order = order_manager.get_order(&order_id).await.unwrap();
+        assert_eq!(order.created_at.timestamp_millis(), 1_700_000_000_000);
+        assert_eq!(order.updated_at.timestamp_millis(), 1_700_000_005_000);
+    }
+
     #[tokio::test]
     async fn test_order_manager_cancel_all() {
         let order_manager = OrderManagerImpl::new("binance".to_string());
@@ -792,6 +1178,7 @@ mod tests {
             remaining_size: Size::from_str("0.0").unwrap(),
             average_price: Some(Price::from_str("50000.0").unwrap()),
             timestamp: 1638368000000,
+            reject_reason: None,
         };
 
         let sell_report = ExecutionReport {
@@ -804,6 +1191,7 @@ mod tests {
             remaining_size: Size::from_str("0.0").unwrap(),
             average_price: Some(Price::from_str("51000.0").unwrap()),
             timestamp: 1638368000000,
+            reject_reason: None,
         };
 
         order_manager
@@ -888,6 +1276,7 @@ mod tests {
                 remaining_size,
                 average_price: Some(Price::from_str("50000.0").unwrap()),
                 timestamp: 1638368000000,
+                reject_reason: None,
             };
 
             order_manager.handle_execution_report(report).await.unwrap();
@@ -913,4 +1302,229 @@ mod tests {
         let error = OrderManagerError::InvalidOrder("Invalid price".to_string());
         assert_eq!(error.to_string(), "Invalid order: Invalid price");
     }
+
+    #[tokio::test]
+    async fn test_query_orders_filters_sorts_and_pages() {
+        let order_manager = OrderManagerImpl::new("binance".to_string());
+        for (id, symbol, tag, minute) in [
+            ("1", "BTCUSDT", "mm", 3),
+            ("2", "BTCUSDT", "arb", 1),
+            ("3", "ETHUSDT", "mm", 2),
+            ("4", "BTCUSDT", "mm", 4),
+        ] {
+            let mut order = OrderInfo::new(
+                id.to_string(),
+                None,
+                Symbol::new(symbol),
+                OrderSide::Buy,
+                OrderType::Limit,
+                TimeInForce::GoodTillCancelled,
+                Size::from_str("1").unwrap(),
+                Some(Price::from_str("100").unwrap()),
+                "binance".to_string(),
+            )
+            .with_strategy_tag(tag);
+            order.updated_at = DateTime::from_timestamp(minute * 60, 0).unwrap();
+            order_manager.add_order(order).await;
+        }
+        order_manager.cancel_all_orders_for_symbol("ETHUSDT").await;
+
+        let ids = |page: &OrderPage| -> Vec<String> {
+            page.orders.iter().map(|o| o.order_id.clone()).collect()
+        };
+
+        // Cancelling touched order 3 last
+        let page = order_manager
+            .query_orders(&OrderQuery::new())
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), vec!["2", "1", "4", "3"]);
+
+        let query = OrderQuery::new()
+            .with_strategy_tag("mm")
+            .with_status(OrderStatus::New)
+            .newest_first()
+            .with_page(1, 1);
+        let page = order_manager.query_orders(&query).await.unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(ids(&page), vec!["1"]);
+
+        let query = OrderQuery::new()
+            .with_symbol("BTCUSDT")
+            .with_time_range(60_000, 180_000);
+        let page = order_manager.query_orders(&query).await.unwrap();
+        assert_eq!(ids(&page), vec!["2", "1"]);
+    }
+
+    #[tokio::test]
+    async fn test_oco_order_group() {
+        use crate::traits::{ExecutionClient, NewOrder};
+
+        let client = Arc::new(crate::connectors::MockExecutionClient::new());
+        let mut order_manager = OrderManagerImpl::new("binance".to_string())
+            .with_execution_client("binance", client.clone());
+        let mut legs = Vec::new();
+        for price in ["110", "90", "120", "80"] {
+            let order = NewOrder::new_limit_sell(
+                "BTCUSDT",
+                Size::from_str("1").unwrap(),
+                Price::from_str(price).unwrap(),
+                TimeInForce::GoodTillCancelled,
+            );
+            let order_id = client.place_order(order.clone()).await.unwrap();
+            order_manager
+                .add_order(OrderInfo::new(
+                    order_id.clone(),
+                    None,
+                    order.symbol,
+                    order.side,
+                    OrderType::Limit,
+                    order.time_in_force,
+                    order.size,
+                    order.price,
+                    "binance".to_string(),
+                ))
+                .await;
+            legs.push(order_id);
+        }
+        let report = |order_id: &OrderId, status: OrderStatus, filled: &str| ExecutionReport {
+            order_id: order_id.clone(),
+            client_order_id: None,
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            status,
+            filled_size: Size::from_str(filled).unwrap(),
+            remaining_size: Size::from_str("1").unwrap() - Size::from_str(filled).unwrap(),
+            average_price: None,
+            timestamp: 0,
+            reject_reason: None,
+        };
+
+        // Take-profit and stop-loss on the same position
+        order_manager
+            .create_order_group("exit", vec![legs[0].clone(), legs[1].clone()])
+            .await
+            .unwrap();
+        assert!(matches!(
+            order_manager
+                .create_order_group("again", vec![legs[1].clone(), legs[2].clone()])
+                .await,
+            Err(OrderManagerError::InvalidOrder(_))
+        ));
+        assert!(matches!(
+            order_manager
+                .create_order_group("missing", vec![legs[2].clone(), "unknown".to_string()])
+                .await,
+            Err(OrderManagerError::OrderNotFound(_))
+        ));
+
+        // A partial fill of the take-profit cancels the stop-loss on the exchange
+        order_manager
+            .handle_execution_report(report(&legs[0], OrderStatus::PartiallyFilled, "0.5"))
+            .await
+            .unwrap();
+        let group = order_manager.get_order_group("exit").await.unwrap();
+        assert_eq!(
+            group.status,
+            OrderGroupStatus::Triggered {
+                filled_order_id: legs[0].clone()
+            }
+        );
+        assert!(group.cancel_failures.is_empty());
+        assert!(order_manager
+            .get_order(&legs[1])
+            .await
+            .unwrap()
+            .is_canceled());
+        let venue_status = client
+            .get_order_status(legs[1].clone())
+            .await
+            .unwrap()
+            .status;
+        assert_eq!(venue_status, OrderStatus::Cancelled);
+        assert!(order_manager.get_order(&legs[0]).await.unwrap().is_active());
+
+        // A leg cancelled without a fill takes the rest of its group with it
+        order_manager
+            .create_order_group("second", vec![legs[2].clone(), legs[3].clone()])
+            .await
+            .unwrap();
+        order_manager
+            .handle_execution_report(report(&legs[3], OrderStatus::Cancelled, "0"))
+            .await
+            .unwrap();
+        let group = order_manager.get_order_group("second").await.unwrap();
+        assert_eq!(group.status, OrderGroupStatus::Cancelled);
+        assert!(order_manager
+            .get_order(&legs[2])
+            .await
+            .unwrap()
+            .is_canceled());
+        assert_eq!(order_manager.get_order_groups().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_record_replacement() {
+        let mut order_manager = OrderManagerImpl::new("binance".to_string());
+        order_manager
+            .add_order(OrderInfo::new(
+                "1".to_string(),
+                Some("quote".to_string()),
+                Symbol::new("BTCUSDT"),
+                OrderSide::Buy,
+                OrderType::Limit,
+                TimeInForce::GoodTillCancelled,
+                Size::from_str("1").unwrap(),
+                Some(Price::from_str("100").unwrap()),
+                "binance".to_string(),
+            ))
+            .await;
+        let mut report = order_manager
+            .get_order(&"1".to_string())
+            .await
+            .unwrap()
+            .to_execution_report();
+        report.status = OrderStatus::PartiallyFilled;
+        report.filled_size = Size::from_str("0.4").unwrap();
+        report.remaining_size = Size::from_str("0.6").unwrap();
+        order_manager.handle_execution_report(report).await.unwrap();
+
+        // A new price keeps the unfilled quantity
+        order_manager
+            .record_replacement(
+                &"1".to_string(),
+                "2".to_string(),
+                Some(Price::from_str("101").unwrap()),
+                None,
+            )
+            .await
+            .unwrap();
+        let original = order_manager.get_order(&"1".to_string()).await.unwrap();
+        assert!(original.is_canceled());
+        assert_eq!(original.filled_quantity, Size::from_str("0.4").unwrap());
+        assert_eq!(original.replaced_by, Some("2".to_string()));
+
+        let replacement = order_manager.get_order_by_client_id("quote").await.unwrap();
+        assert_eq!(replacement.order_id, "2");
+        assert_eq!(replacement.replaces, Some("1".to_string()));
+        assert_eq!(replacement.price, Some(Price::from_str("101").unwrap()));
+        assert_eq!(replacement.quantity, Size::from_str("0.6").unwrap());
+        assert!(replacement.filled_quantity.is_zero());
+        let active = order_manager.get_active_orders_by_symbol("BTCUSDT").await;
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].order_id, "2");
+        assert_eq!(
+            OrderManagerImpl::get_orders_by_symbol(&order_manager, "BTCUSDT")
+                .await
+                .len(),
+            2
+        );
+
+        assert!(matches!(
+            order_manager
+                .record_replacement(&"9".to_string(), "10".to_string(), None, None)
+                .await,
+            Err(OrderManagerError::OrderNotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,121 @@
+use crate::core::events::{ExecutionReport, OrderId};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+
+/// Orders remembered before the oldest are forgotten
+const DEFAULT_CAPACITY: usize = 100_000;
+
+#[derive(Debug, Default)]
+struct Inner {
+    symbols: HashMap<OrderId, String>,
+    /// Order IDs oldest first, for eviction
+    order: VecDeque<OrderId>,
+}
+
+/// Symbol of each order ID seen by an adapter
+///
+/// Venues such as Binance and MEXC address orders by symbol and ID, while
+/// `ExecutionClient` only passes the ID. Adapters record the symbol when an order is
+/// placed or reported, and look it up to cancel or query the order. Clones share state.
+#[derive(Debug, Clone)]
+pub struct OrderSymbolRegistry {
+    inner: Arc<RwLock<Inner>>,
+    capacity: usize,
+}
+
+impl OrderSymbolRegistry {
+    /// Create a registry that remembers the last 100,000 orders
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Create a registry that remembers the last `capacity` orders
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Inner::default())),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Record the symbol of an order
+    pub fn record(&self, order_id: impl Into<OrderId>, symbol: impl Into<String>) {
+        let order_id = order_id.into();
+        let mut inner = self.inner.write().unwrap();
+        if inner
+            .symbols
+            .insert(order_id.clone(), symbol.into())
+            .is_none()
+        {
+            inner.order.push_back(order_id);
+            while inner.order.len() > self.capacity {
+                if let Some(oldest) = inner.order.pop_front() {
+                    inner.symbols.remove(&oldest);
+                }
+            }
+        }
+    }
+
+    /// Record the symbol of a reported order
+    pub fn record_report(&self, report: &ExecutionReport) {
+        self.record(report.order_id.clone(), report.symbol.as_str());
+    }
+
+    /// Get the symbol of an order
+    pub fn symbol(&self, order_id: &str) -> Option<String> {
+        self.inner.read().unwrap().symbols.get(order_id).cloned()
+    }
+
+    /// Get every distinct symbol with a recorded order, sorted
+    pub fn symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self
+            .inner
+            .read()
+            .unwrap()
+            .symbols
+            .values()
+            .cloned()
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+
+    /// Number of orders recorded
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().symbols.len()
+    }
+
+    /// Whether no orders are recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for OrderSymbolRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_evict_oldest() {
+        let registry = OrderSymbolRegistry::with_capacity(2);
+        registry.record("1", "BTCUSDT");
+        registry.record("2", "ETHUSDT");
+        registry.record("1", "BTCUSDT");
+        assert_eq!(registry.len(), 2);
+
+        registry.record("3", "BTCUSDT");
+        assert_eq!(registry.symbol("1"), None);
+        assert_eq!(registry.symbol("2").as_deref(), Some("ETHUSDT"));
+        assert_eq!(registry.symbols(), vec!["BTCUSDT", "ETHUSDT"]);
+
+        let shared = registry.clone();
+        shared.record("4", "SOLUSDT");
+        assert_eq!(registry.symbol("4").as_deref(), Some("SOLUSDT"));
+    }
+}
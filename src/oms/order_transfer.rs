@@ -0,0 +1,328 @@
+use crate::oms::order_manager::{OrderInfo, OrderManagerImpl};
+use crate::risk::RiskEngine;
+use crate::traits::{ExecutionClient, ExecutionReport, NewOrder, OrderId, OrderType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+/// A working order captured for export
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkingOrder {
+    /// Order ID on the exchange when exported
+    pub order_id: OrderId,
+    /// Order to replay, sized to the remaining quantity
+    pub order: NewOrder,
+    /// Creation time of the original order
+    pub created_at: DateTime<Utc>,
+}
+
+impl WorkingOrder {
+    /// Capture the unfilled part of a tracked order
+    pub fn from_order_info(info: &OrderInfo) -> Self {
+        Self {
+            order_id: info.order_id.clone(),
+            order: NewOrder {
+                symbol: info.symbol.clone(),
+                exchange_id: info.exchange_id.clone(),
+                side: info.side,
+                order_type: info.order_type,
+                time_in_force: info.time_in_force,
+                price: info.price,
+                size: info.remaining_quantity,
+                client_order_id: info.client_order_id.clone(),
+                quote_size: None,
+            },
+            created_at: info.created_at,
+        }
+    }
+}
+
+/// Snapshot of working orders written by an export
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderExport {
+    /// Time of the export
+    pub exported_at: DateTime<Utc>,
+    /// Exported orders
+    pub orders: Vec<WorkingOrder>,
+}
+
+impl OrderExport {
+    /// Capture all active orders of an order manager
+    pub async fn from_order_manager(order_manager: &OrderManagerImpl) -> Self {
+        let mut orders: Vec<WorkingOrder> = order_manager
+            .get_all_active_orders()
+            .await
+            .iter()
+            .map(WorkingOrder::from_order_info)
+            .collect();
+        orders.sort_by_key(|w| w.created_at);
+        Self {
+            exported_at: Utc::now(),
+            orders,
+        }
+    }
+
+    /// Write the export to a JSON file
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), OrderTransferError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| OrderTransferError::Parse(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| OrderTransferError::Io(e.to_string()))
+    }
+
+    /// Read an export from a JSON file
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<Self, OrderTransferError> {
+        let json =
+            std::fs::read_to_string(path).map_err(|e| OrderTransferError::Io(e.to_string()))?;
+        serde_json::from_str(&json).map_err(|e| OrderTransferError::Parse(e.to_string()))
+    }
+
+    /// Plan a replay against the orders currently open on the exchange
+    /// Orders still open (by order ID or client order ID) and non-limit orders are skipped
+    pub fn plan_import(&self, open_orders: &[ExecutionReport]) -> ImportPlan {
+        let open_ids: HashSet<&str> = open_orders.iter().map(|o| o.order_id.as_str()).collect();
+        let open_client_ids: HashSet<&str> = open_orders
+            .iter()
+            .filter_map(|o| o.client_order_id.as_deref())
+            .collect();
+
+        let mut plan = ImportPlan::default();
+        for working in &self.orders {
+            let client_id = working.order.client_order_id.as_deref();
+            let reason = if open_ids.contains(working.order_id.as_str()) {
+                Some("order is still open on the exchange")
+            } else if client_id.is_some_and(|id| open_client_ids.contains(id)) {
+                Some("client order ID is already open on the exchange")
+            } else if working.order.order_type != OrderType::Limit {
+                Some("only limit orders are replayed")
+            } else if working.order.size.is_zero() {
+                Some("no remaining quantity")
+            } else {
+                None
+            };
+
+            match reason {
+                Some(reason) => plan.skipped.push((working.clone(), reason.to_string())),
+                None => plan.to_place.push(working.clone()),
+            }
+        }
+        plan
+    }
+}
+
+/// Orders an import would place, pending operator confirmation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportPlan {
+    /// Orders to place
+    pub to_place: Vec<WorkingOrder>,
+    /// Orders skipped, with the reason
+    pub skipped: Vec<(WorkingOrder, String)>,
+}
+
+impl ImportPlan {
+    /// Token the operator must echo back to execute this exact plan
+    /// Derived from the orders to place, so a changed file or exchange state invalidates it
+    pub fn confirmation_token(&self) -> String {
+        let mut hasher = Sha256::new();
+        for working in &self.to_place {
+            hasher.update(serde_json::to_vec(working).expect("working orders serialize"));
+        }
+        let digest = hasher.finalize();
+        let hex: String = digest[..4].iter().map(|b| format!("{:02x}", b)).collect();
+        format!("REPLAY-{}-{}", self.to_place.len(), hex)
+    }
+
+    /// Place the planned orders once the confirmation token matches
+    /// Orders failing the risk check are not sent; placed orders are tracked by the order
+    /// manager if one is given
+    pub async fn execute<C>(
+        &self,
+        confirmation: &str,
+        client: &C,
+        risk_engine: Option<&RiskEngine>,
+        order_manager: Option<&OrderManagerImpl>,
+    ) -> Result<ImportReport, OrderTransferError>
+    where
+        C: ExecutionClient + Sync,
+    {
+        let expected = self.confirmation_token();
+        if confirmation != expected {
+            return Err(OrderTransferError::ConfirmationMismatch {
+                expected,
+                actual: confirmation.to_string(),
+            });
+        }
+
+        let mut report = ImportReport {
+            skipped: self.skipped.len(),
+            ..Default::default()
+        };
+        for working in &self.to_place {
+            let order = working.order.clone();
+            if let Some(risk_engine) = risk_engine {
+                if let Err(violation) = risk_engine.check_order(&order).await {
+                    report.failures.push(format!(
+                        "{} rejected by {}: {}",
+                        working.order_id, violation.rule, violation.details
+                    ));
+                    continue;
+                }
+            }
+
+            match client.place_order(order.clone()).await {
+                Ok(order_id) => {
+                    if let Some(risk_engine) = risk_engine {
+                        risk_engine.record_order_submission(&order);
+                    }
+                    if let Some(order_manager) = order_manager {
+                        order_manager
+                            .add_order(
+                                OrderInfo::new(
+                                    order_id.clone(),
+                                    order.client_order_id,
+                                    order.symbol,
+                                    order.side,
+                                    order.order_type,
+                                    order.time_in_force,
+                                    order.size,
+                                    order.price,
+                                    order.exchange_id,
+                                )
+                                .with_timestamp(order_manager.clock().now_utc()),
+                            )
+                            .await;
+                    }
+                    report.placed.push((working.order_id.clone(), order_id));
+                }
+                Err(e) => report
+                    .failures
+                    .push(format!("Failed to place {}: {}", working.order_id, e)),
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Outcome of an executed import
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// Placed orders as (exported order ID, new order ID)
+    pub placed: Vec<(OrderId, OrderId)>,
+    /// Number of orders skipped by the plan
+    pub skipped: usize,
+    /// Orders rejected by risk or by the exchange
+    pub failures: Vec<String>,
+}
+
+/// Order export/import errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderTransferError {
+    Io(String),
+    Parse(String),
+    ConfirmationMismatch { expected: String, actual: String },
+}
+
+impl fmt::Display for OrderTransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderTransferError::Io(msg) => write!(f, "I/O error: {}", msg),
+            OrderTransferError::Parse(msg) => write!(f, "Invalid order file: {}", msg),
+            OrderTransferError::ConfirmationMismatch { expected, actual } => write!(
+                f,
+                "Confirmation '{}' does not match the import plan (expected '{}')",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OrderTransferError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::mock::MockExecutionClient;
+    use crate::traits::{OrderSide, OrderStatus, TimeInForce};
+    use crate::types::{Price, Size, Symbol};
+
+    fn order_info(order_id: &str, client_id: &str, order_type: OrderType) -> OrderInfo {
+        OrderInfo::new(
+            order_id.to_string(),
+            Some(client_id.to_string()),
+            Symbol::new("BTCUSDT"),
+            OrderSide::Buy,
+            order_type,
+            TimeInForce::GoodTillCancelled,
+            Size::from_str("2").unwrap(),
+            Some(Price::from_str("50000").unwrap()),
+            "binance".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_export_and_replay() {
+        let source = OrderManagerImpl::new("binance".to_string());
+        let mut partial = order_info("o1", "c1", OrderType::Limit);
+        partial.status = OrderStatus::PartiallyFilled;
+        partial.filled_quantity = Size::from_str("0.5").unwrap();
+        partial.remaining_quantity = Size::from_str("1.5").unwrap();
+        source.add_order(partial).await;
+        source
+            .add_order(order_info("o2", "c2", OrderType::Limit))
+            .await;
+        source
+            .add_order(order_info("o3", "c3", OrderType::Market))
+            .await;
+
+        let path = std::env::temp_dir().join(format!("orders_{}.json", uuid::Uuid::new_v4()));
+        OrderExport::from_order_manager(&source)
+            .await
+            .write_to_file(&path)
+            .unwrap();
+        let export = OrderExport::read_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(export.orders.len(), 3);
+
+        // c2 survived on the exchange, so only o1 is replayed
+        let still_open = ExecutionReport {
+            order_id: "x9".to_string(),
+            client_order_id: Some("c2".to_string()),
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            status: OrderStatus::New,
+            filled_size: Size::zero(),
+            remaining_size: Size::from_str("2").unwrap(),
+            average_price: None,
+            timestamp: 0,
+            reject_reason: None,
+        };
+        let plan = export.plan_import(&[still_open]);
+        assert_eq!(plan.to_place.len(), 1);
+        assert_eq!(plan.skipped.len(), 2);
+        assert_eq!(plan.to_place[0].order.size, Size::from_str("1.5").unwrap());
+
+        let client = MockExecutionClient::new();
+        let target = OrderManagerImpl::new("binance".to_string());
+        let err = plan
+            .execute("REPLAY-1-00000000", &client, None, Some(&target))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OrderTransferError::ConfirmationMismatch { .. }
+        ));
+        assert!(target.get_all_active_orders().await.is_empty());
+
+        let report = plan
+            .execute(&plan.confirmation_token(), &client, None, Some(&target))
+            .await
+            .unwrap();
+        assert_eq!(report.placed.len(), 1);
+        assert_eq!(report.skipped, 2);
+        let replayed = target.get_order_by_client_id("c1").await.unwrap();
+        assert_eq!(replayed.order_id, report.placed[0].1);
+        assert_eq!(replayed.quantity, Size::from_str("1.5").unwrap());
+    }
+}
@@ -1,16 +1,20 @@
-use std::collections::VecDeque;
+use crate::core::clock::{system_clock, SharedClock};
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
 /// Rate limiter implementation using token bucket algorithm
+/// Each request takes a weight from the window; `check_limit` takes a weight of one.
 pub struct RateLimiter {
-    /// Maximum number of requests allowed in the time window
+    /// Maximum weight allowed in the time window
     max_requests: usize,
     /// Time window for rate limiting
     window: Duration,
-    /// History of request timestamps
-    request_history: Arc<Mutex<VecDeque<Instant>>>,
+    /// Timestamp and weight of each request in the window
+    request_history: Arc<Mutex<VecDeque<(Instant, usize)>>>,
+    /// Time source for the window
+    clock: SharedClock,
 }
 
 impl RateLimiter {
@@ -20,75 +24,83 @@ impl RateLimiter {
             max_requests,
             window,
             request_history: Arc::new(Mutex::new(VecDeque::with_capacity(max_requests))),
+            clock: system_clock(),
         }
     }
 
-    /// Check if a request is allowed and record it
-    pub async fn check_limit(&self) -> bool {
-        let now = Instant::now();
+    /// Set the time source (builder pattern)
+    /// `wait_for_slot` still sleeps in real time, so with a simulated clock use
+    /// `check_limit` and advance the clock instead.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
 
-        // Clean up old requests outside the window
-        {
-            let mut history = self.request_history.lock().unwrap();
-
-            // Remove requests older than the window
-            while let Some(&front_time) = history.front() {
-                if now.duration_since(front_time) >= self.window {
-                    history.pop_front();
-                } else {
-                    break;
-                }
+    /// Drop requests older than the window
+    fn prune(&self, history: &mut VecDeque<(Instant, usize)>, now: Instant) {
+        while let Some(&(front_time, _)) = history.front() {
+            if now.duration_since(front_time) >= self.window {
+                history.pop_front();
+            } else {
+                break;
             }
+        }
+    }
 
-            // Check if we're at the limit
-            if history.len() >= self.max_requests {
-                return false;
-            }
+    /// Check if a request is allowed and record it
+    pub async fn check_limit(&self) -> bool {
+        self.check_weight(1)
+    }
 
-            // Record this request
-            history.push_back(now);
-        }
+    /// Check if a request of the given weight is allowed and record it
+    /// A request heavier than the whole window is allowed once the window is empty.
+    pub fn check_weight(&self, weight: usize) -> bool {
+        let weight = weight.min(self.max_requests);
+        let now = self.clock.now_instant();
+        let mut history = self.request_history.lock().unwrap();
+        self.prune(&mut history, now);
 
+        let used: usize = history.iter().map(|&(_, w)| w).sum();
+        if used + weight > self.max_requests {
+            return false;
+        }
+        history.push_back((now, weight));
         true
     }
 
     /// Wait until a request is allowed
     pub async fn wait_for_slot(&self) {
-        while !self.check_limit().await {
-            // Calculate how long to wait until the oldest request is outside the window
-            let wait_time = {
-                let history = self.request_history.lock().unwrap();
-                if let Some(&oldest_time) = history.front() {
-                    let elapsed = oldest_time.elapsed();
-                    if elapsed < self.window {
-                        self.window - elapsed
-                    } else {
-                        Duration::from_millis(1) // Small delay to prevent busy waiting
-                    }
-                } else {
-                    Duration::from_millis(0)
-                }
-            };
+        self.wait_for_weight(1).await;
+    }
 
-            sleep(wait_time).await;
+    /// Wait until a request of the given weight is allowed, then record it
+    pub async fn wait_for_weight(&self, weight: usize) {
+        while !self.check_weight(weight) {
+            // Small delay to prevent busy waiting
+            sleep(self.time_until_weight(weight).max(Duration::from_millis(1))).await;
         }
     }
 
-    /// Get the current number of requests in the window
+    /// Get the current number of requests in the window, counted by weight
     pub fn current_requests(&self) -> usize {
-        let now = Instant::now();
+        let now = self.clock.now_instant();
         let mut history = self.request_history.lock().unwrap();
+        self.prune(&mut history, now);
+        history.iter().map(|&(_, w)| w).sum()
+    }
 
-        // Clean up old requests
-        while let Some(&front_time) = history.front() {
-            if now.duration_since(front_time) >= self.window {
-                history.pop_front();
-            } else {
-                break;
-            }
-        }
+    /// Align the window with usage reported by the venue
+    /// Usage above the local count, e.g. from other processes sharing the API key, is
+    /// recorded as a request made now; usage below it is ignored.
+    pub fn sync_used(&self, used: usize) {
+        let now = self.clock.now_instant();
+        let mut history = self.request_history.lock().unwrap();
+        self.prune(&mut history, now);
 
-        history.len()
+        let counted: usize = history.iter().map(|&(_, w)| w).sum();
+        if used > counted {
+            history.push_back((now, used - counted));
+        }
     }
 
     /// Get the maximum number of requests allowed
@@ -103,23 +115,27 @@ impl RateLimiter {
 
     /// Get the time until the next request is allowed
     pub fn time_until_next_request(&self) -> Duration {
-        let now = Instant::now();
-        let history = self.request_history.lock().unwrap();
+        self.time_until_weight(1)
+    }
 
-        if history.len() < self.max_requests {
-            return Duration::from_millis(0);
-        }
+    /// Get the time until a request of the given weight is allowed
+    pub fn time_until_weight(&self, weight: usize) -> Duration {
+        let weight = weight.min(self.max_requests);
+        let now = self.clock.now_instant();
+        let mut history = self.request_history.lock().unwrap();
+        self.prune(&mut history, now);
 
-        if let Some(&oldest_time) = history.front() {
-            let elapsed = now.duration_since(oldest_time);
-            if elapsed < self.window {
-                self.window - elapsed
-            } else {
-                Duration::from_millis(0)
+        let mut used: usize = history.iter().map(|&(_, w)| w).sum();
+        for &(time, w) in history.iter() {
+            if used + weight <= self.max_requests {
+                break;
+            }
+            used -= w;
+            if used + weight <= self.max_requests {
+                return self.window - now.duration_since(time);
             }
-        } else {
-            Duration::from_millis(0)
         }
+        Duration::from_millis(0)
     }
 
     /// Reset the rate limiter
@@ -212,6 +228,8 @@ pub struct AdaptiveRateLimiter {
     last_rate_limit_hit: Arc<Mutex<Option<Instant>>>,
     /// Last time we reduced backoff
     last_backoff_reset: Arc<Mutex<Option<Instant>>>,
+    /// Time source for the window and backoff
+    clock: SharedClock,
 }
 
 impl AdaptiveRateLimiter {
@@ -224,16 +242,24 @@ impl AdaptiveRateLimiter {
             backoff_reset_time: Duration::from_secs(60), // Reset after 1 minute
             last_rate_limit_hit: Arc::new(Mutex::new(None)),
             last_backoff_reset: Arc::new(Mutex::new(None)),
+            clock: system_clock(),
         }
     }
 
+    /// Set the time source (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.base_limiter = self.base_limiter.with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
     /// Check if a request is allowed and record it
     pub async fn check_limit(&self) -> bool {
         // Apply backoff to the base rate limit
         let backoff = *self.backoff_multiplier.lock().unwrap();
         let effective_max_requests = (self.base_limiter.max_requests() as f64 / backoff) as usize;
 
-        let now = Instant::now();
+        let now = self.clock.now_instant();
 
         // Check if we should reduce backoff
         {
@@ -277,7 +303,7 @@ impl AdaptiveRateLimiter {
 
     /// Notify that we hit a rate limit
     pub fn notify_rate_limit_hit(&self) {
-        let now = Instant::now();
+        let now = self.clock.now_instant();
 
         // Update last rate limit hit time
         {
@@ -332,6 +358,156 @@ impl AdaptiveRateLimiter {
     }
 }
 
+/// Budget a weighted rate limit window counts against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKind {
+    /// REST request weight, spent by every request
+    RequestWeight,
+    /// Order count, spent by order placements only
+    Orders,
+}
+
+/// Rate limiter for venues that weight requests by endpoint
+///
+/// Holds several windows, e.g. request weight per minute and orders per second; a request
+/// waits until every window it counts against has room, then takes from all of them at
+/// once. Shared between clients of the same API key, and kept in line with the venue's
+/// own count through `sync_used`.
+pub struct WeightedRateLimiter {
+    /// Windows and the budget each counts
+    windows: Vec<(RateLimitKind, RateLimiter)>,
+    /// Request weight per endpoint
+    endpoint_weights: HashMap<String, usize>,
+    /// Request weight of endpoints missing from the table
+    default_weight: usize,
+    /// Makes checking and recording across windows atomic
+    acquire_lock: Mutex<()>,
+    /// Time source for the windows
+    clock: SharedClock,
+}
+
+impl WeightedRateLimiter {
+    /// Create a limiter with no windows, where every endpoint weighs one
+    pub fn new() -> Self {
+        Self {
+            windows: Vec::new(),
+            endpoint_weights: HashMap::new(),
+            default_weight: 1,
+            acquire_lock: Mutex::new(()),
+            clock: system_clock(),
+        }
+    }
+
+    /// Set the time source (builder pattern)
+    /// Applies to windows added afterwards.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Add a window allowing `limit` units of a budget per `window` (builder pattern)
+    pub fn with_window(mut self, kind: RateLimitKind, limit: usize, window: Duration) -> Self {
+        self.windows.push((
+            kind,
+            RateLimiter::new(limit, window).with_clock(self.clock.clone()),
+        ));
+        self
+    }
+
+    /// Set the request weight of an endpoint (builder pattern)
+    pub fn with_endpoint_weight(mut self, endpoint: impl Into<String>, weight: usize) -> Self {
+        self.endpoint_weights.insert(endpoint.into(), weight);
+        self
+    }
+
+    /// Set the request weight of endpoints missing from the table (builder pattern)
+    pub fn with_default_weight(mut self, weight: usize) -> Self {
+        self.default_weight = weight;
+        self
+    }
+
+    /// Get the request weight of an endpoint
+    pub fn weight(&self, endpoint: &str) -> usize {
+        self.endpoint_weights
+            .get(endpoint)
+            .copied()
+            .unwrap_or(self.default_weight)
+    }
+
+    /// Cost of a request against a budget
+    fn cost(&self, kind: RateLimitKind, endpoint: &str, is_order: bool) -> usize {
+        match kind {
+            RateLimitKind::RequestWeight => self.weight(endpoint),
+            RateLimitKind::Orders => usize::from(is_order),
+        }
+    }
+
+    /// Take a request from every window if all have room
+    /// Returns how long to wait otherwise.
+    pub fn try_acquire(&self, endpoint: &str, is_order: bool) -> Result<(), Duration> {
+        let _guard = self.acquire_lock.lock().unwrap();
+        let wait = self
+            .windows
+            .iter()
+            .map(|(kind, limiter)| limiter.time_until_weight(self.cost(*kind, endpoint, is_order)))
+            .max()
+            .unwrap_or_default();
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+
+        for (kind, limiter) in &self.windows {
+            let cost = self.cost(*kind, endpoint, is_order);
+            if cost > 0 {
+                limiter.check_weight(cost);
+            }
+        }
+        Ok(())
+    }
+
+    /// Wait until every window has room for a request, then take it
+    pub async fn acquire(&self, endpoint: &str, is_order: bool) {
+        while let Err(wait) = self.try_acquire(endpoint, is_order) {
+            sleep(wait.max(Duration::from_millis(1))).await;
+        }
+    }
+
+    /// Align the window of a budget with usage reported by the venue
+    /// Usage reported for a window the limiter does not hold is ignored.
+    pub fn sync_used(&self, kind: RateLimitKind, window: Duration, used: usize) {
+        let _guard = self.acquire_lock.lock().unwrap();
+        for (_, limiter) in self
+            .windows
+            .iter()
+            .filter(|(k, limiter)| *k == kind && limiter.window() == window)
+        {
+            limiter.sync_used(used);
+        }
+    }
+
+    /// Get the usage of a budget in its first window
+    pub fn current_usage(&self, kind: RateLimitKind) -> usize {
+        self.windows
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, limiter)| limiter.current_requests())
+            .unwrap_or(0)
+    }
+
+    /// Reset every window
+    pub fn reset(&self) {
+        for (_, limiter) in &self.windows {
+            limiter.reset();
+        }
+    }
+}
+
+impl Default for WeightedRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,6 +536,25 @@ mod tests {
         assert_eq!(limiter.current_requests(), 1);
     }
 
+    #[tokio::test]
+    async fn test_rate_limiter_simulated_clock() {
+        let clock = crate::core::clock::SimulatedClock::at_millis(0);
+        let limiter = RateLimiter::new(2, Duration::from_secs(1)).with_clock(clock.shared());
+        assert!(limiter.check_limit().await);
+        clock.advance(Duration::from_millis(400));
+        assert!(limiter.check_limit().await);
+        assert!(!limiter.check_limit().await);
+        assert_eq!(
+            limiter.time_until_next_request(),
+            Duration::from_millis(600)
+        );
+
+        // The first request leaves the window exactly one second after it was made
+        clock.advance(Duration::from_millis(600));
+        assert_eq!(limiter.current_requests(), 1);
+        assert!(limiter.check_limit().await);
+    }
+
     #[tokio::test]
     async fn test_rate_limiter_wait_for_slot() {
         let limiter = RateLimiter::new(2, Duration::from_millis(500));
@@ -516,4 +711,48 @@ mod tests {
         assert_eq!(multi_limiter.current_requests("read"), 0);
         assert_eq!(multi_limiter.current_requests("write"), 2);
     }
+
+    #[tokio::test]
+    async fn test_weighted_rate_limiter() {
+        let clock = crate::core::clock::SimulatedClock::at_millis(0);
+        let limiter = WeightedRateLimiter::new()
+            .with_clock(clock.shared())
+            .with_window(RateLimitKind::RequestWeight, 30, Duration::from_secs(60))
+            .with_window(RateLimitKind::Orders, 2, Duration::from_secs(1))
+            .with_endpoint_weight("/api/v3/account", 20)
+            .with_endpoint_weight("/api/v3/order", 1);
+        assert_eq!(limiter.weight("/api/v3/account"), 20);
+        assert_eq!(limiter.weight("/api/v3/time"), 1);
+
+        // Two orders fill the order window but leave request weight
+        assert!(limiter.try_acquire("/api/v3/order", true).is_ok());
+        assert!(limiter.try_acquire("/api/v3/order", true).is_ok());
+        assert_eq!(
+            limiter.try_acquire("/api/v3/order", true),
+            Err(Duration::from_secs(1))
+        );
+        assert!(limiter.try_acquire("/api/v3/account", false).is_ok());
+        assert_eq!(limiter.current_usage(RateLimitKind::RequestWeight), 22);
+        assert_eq!(limiter.current_usage(RateLimitKind::Orders), 2);
+
+        // A refused request takes nothing from the windows
+        clock.advance(Duration::from_secs(1));
+        assert!(limiter.try_acquire("/api/v3/account", false).is_err());
+        assert_eq!(limiter.current_usage(RateLimitKind::RequestWeight), 22);
+        assert!(limiter.try_acquire("/api/v3/order", true).is_ok());
+
+        // Venue-reported usage above the local count holds back later requests
+        limiter.sync_used(RateLimitKind::RequestWeight, Duration::from_secs(60), 29);
+        assert_eq!(limiter.current_usage(RateLimitKind::RequestWeight), 29);
+        assert!(limiter.try_acquire("/api/v3/order", true).is_ok());
+        assert!(limiter.try_acquire("/api/v3/time", false).is_err());
+        limiter.sync_used(RateLimitKind::RequestWeight, Duration::from_secs(60), 5);
+        limiter.sync_used(RateLimitKind::RequestWeight, Duration::from_secs(1), 100);
+        assert_eq!(limiter.current_usage(RateLimitKind::RequestWeight), 30);
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(limiter.current_usage(RateLimitKind::RequestWeight), 0);
+        limiter.acquire("/api/v3/account", false).await;
+        assert_eq!(limiter.current_usage(RateLimitKind::RequestWeight), 20);
+    }
 }
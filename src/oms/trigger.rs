@@ -0,0 +1,282 @@
+use crate::core::events::{MarketEvent, NewOrder, OrderSide, OrderType};
+use crate::types::Price;
+use log::info;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Price stop orders are triggered by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TriggerSource {
+    /// Last traded price
+    #[default]
+    LastTrade,
+    /// Mark price of perpetual futures
+    MarkPrice,
+}
+
+/// Stop order held locally until its trigger price trades
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StopOrder {
+    /// Stop ID, the client order ID when the order has one
+    pub stop_id: String,
+    /// Order to release, of type `StopLoss` or `StopLimit`
+    pub order: NewOrder,
+    pub trigger_price: Price,
+}
+
+impl StopOrder {
+    /// Check if a price reaches or crosses the trigger
+    /// Buy stops trigger at or above the trigger price, sell stops at or below it.
+    pub fn is_triggered_by(&self, price: Price) -> bool {
+        match self.order.side {
+            OrderSide::Buy => price >= self.trigger_price,
+            OrderSide::Sell => price <= self.trigger_price,
+        }
+    }
+
+    /// Get the order sent when the stop triggers: a market order for `StopLoss`, a limit
+    /// order at the order's price for `StopLimit`
+    pub fn child_order(&self) -> NewOrder {
+        let mut child = self.order.clone();
+        match self.order.order_type {
+            OrderType::StopLoss => {
+                child.order_type = OrderType::Market;
+                child.price = None;
+            }
+            _ => child.order_type = OrderType::Limit,
+        }
+        child
+    }
+}
+
+/// Stop order errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerError {
+    NotAStopOrder(OrderType),
+    MissingLimitPrice,
+    DuplicateStop(String),
+}
+
+impl fmt::Display for TriggerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TriggerError::NotAStopOrder(order_type) => {
+                write!(f, "Not a stop order: {:?}", order_type)
+            }
+            TriggerError::MissingLimitPrice => write!(f, "Stop-limit order has no limit price"),
+            TriggerError::DuplicateStop(stop_id) => write!(f, "Stop {} already exists", stop_id),
+        }
+    }
+}
+
+impl std::error::Error for TriggerError {}
+
+/// Local trigger engine for venues without native server-side stops
+///
+/// Holds stop orders and watches market data for their symbol; when a price reaches the
+/// trigger, the stop is removed and its child order returned for the executor to send.
+/// Stops on the "default" exchange trigger on market data from any venue.
+#[derive(Debug, Default)]
+pub struct TriggerEngine {
+    source: TriggerSource,
+    stops: HashMap<String, StopOrder>,
+    next_stop: u64,
+}
+
+impl TriggerEngine {
+    /// Create a trigger engine watching last traded prices
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the price stops are triggered by (builder pattern)
+    pub fn with_source(mut self, source: TriggerSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Get the price stops are triggered by
+    pub fn source(&self) -> TriggerSource {
+        self.source
+    }
+
+    /// Hold a stop order until `trigger_price` trades, returning its stop ID
+    pub fn submit(
+        &mut self,
+        order: NewOrder,
+        trigger_price: Price,
+    ) -> Result<String, TriggerError> {
+        if !order.order_type.is_stop() {
+            return Err(TriggerError::NotAStopOrder(order.order_type));
+        }
+        if order.order_type == OrderType::StopLimit && order.price.is_none() {
+            return Err(TriggerError::MissingLimitPrice);
+        }
+        let stop_id = match &order.client_order_id {
+            Some(client_order_id) => client_order_id.clone(),
+            None => {
+                self.next_stop += 1;
+                format!("stop_{}", self.next_stop)
+            }
+        };
+        if self.stops.contains_key(&stop_id) {
+            return Err(TriggerError::DuplicateStop(stop_id));
+        }
+
+        info!(
+            "Holding {:?} {:?} {} {} until {} trades",
+            order.order_type, order.side, order.size, order.symbol, trigger_price
+        );
+        self.stops.insert(
+            stop_id.clone(),
+            StopOrder {
+                stop_id: stop_id.clone(),
+                order,
+                trigger_price,
+            },
+        );
+        Ok(stop_id)
+    }
+
+    /// Cancel a stop before it triggers
+    pub fn cancel(&mut self, stop_id: &str) -> Option<StopOrder> {
+        self.stops.remove(stop_id)
+    }
+
+    /// Get a pending stop
+    pub fn get(&self, stop_id: &str) -> Option<&StopOrder> {
+        self.stops.get(stop_id)
+    }
+
+    /// Get the pending stops on a symbol
+    pub fn pending_for_symbol(&self, symbol: &str) -> Vec<&StopOrder> {
+        self.stops
+            .values()
+            .filter(|stop| stop.order.symbol.as_str() == symbol)
+            .collect()
+    }
+
+    /// Get the number of pending stops
+    pub fn len(&self) -> usize {
+        self.stops.len()
+    }
+
+    /// Check if there are no pending stops
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Check market data against the pending stops
+    /// Returns the child orders of the stops that triggered.
+    pub fn on_market_event(&mut self, event: &MarketEvent) -> Vec<NewOrder> {
+        let price = match (self.source, event) {
+            (TriggerSource::LastTrade, MarketEvent::Trade(trade)) => trade.price,
+            (TriggerSource::MarkPrice, MarketEvent::MarkPrice(mark)) => mark.mark_price,
+            _ => return Vec::new(),
+        };
+        self.on_price(event.symbol(), event.exchange_id(), price)
+    }
+
+    /// Check a price on a symbol and exchange against the pending stops
+    /// Returns the child orders of the stops that triggered.
+    pub fn on_price(&mut self, symbol: &str, exchange_id: &str, price: Price) -> Vec<NewOrder> {
+        let triggered: Vec<String> = self
+            .stops
+            .values()
+            .filter(|stop| {
+                stop.order.symbol.as_str() == symbol
+                    && (stop.order.exchange_id == exchange_id
+                        || stop.order.exchange_id == "default")
+                    && stop.is_triggered_by(price)
+            })
+            .map(|stop| stop.stop_id.clone())
+            .collect();
+
+        triggered
+            .into_iter()
+            .filter_map(|stop_id| self.stops.remove(&stop_id))
+            .map(|stop| {
+                info!(
+                    "Stop {} on {} triggered at {} (trigger {})",
+                    stop.stop_id, symbol, price, stop.trigger_price
+                );
+                stop.child_order()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{TimeInForce, Trade};
+    use crate::types::Size;
+
+    fn trade(exchange_id: &str, price: &str) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            symbol: "BTCUSDT".into(),
+            exchange_id: exchange_id.to_string(),
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str("0.1").unwrap(),
+            side: OrderSide::Sell,
+            timestamp: 0,
+            trade_id: None,
+        })
+    }
+
+    #[test]
+    fn test_trigger_engine() {
+        let mut engine = TriggerEngine::new();
+        let size = Size::from_str("1").unwrap();
+
+        // Sell stop-market below the market, buy stop-limit above it
+        let mut stop_loss = NewOrder::new_market_sell("BTCUSDT", size).with_exchange_id("binance");
+        stop_loss.order_type = OrderType::StopLoss;
+        let stop_loss_id = engine
+            .submit(stop_loss, Price::from_str("95").unwrap())
+            .unwrap();
+        let mut stop_limit = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            size,
+            Price::from_str("106").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        )
+        .with_client_order_id("breakout".to_string());
+        stop_limit.order_type = OrderType::StopLimit;
+        assert_eq!(
+            engine.submit(stop_limit.clone(), Price::from_str("105").unwrap()),
+            Ok("breakout".to_string())
+        );
+        assert_eq!(
+            engine.submit(stop_limit, Price::from_str("105").unwrap()),
+            Err(TriggerError::DuplicateStop("breakout".to_string()))
+        );
+        assert_eq!(
+            engine.submit(
+                NewOrder::new_market_buy("BTCUSDT", size),
+                Price::from_str("105").unwrap()
+            ),
+            Err(TriggerError::NotAStopOrder(OrderType::Market))
+        );
+        assert_eq!(engine.len(), 2);
+
+        // Prices between the triggers release nothing
+        assert!(engine.on_market_event(&trade("binance", "100")).is_empty());
+
+        // A trade through the trigger releases a limit order at the stop-limit price
+        let released = engine.on_market_event(&trade("okx", "105.5"));
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].order_type, OrderType::Limit);
+        assert_eq!(released[0].price, Some(Price::from_str("106").unwrap()));
+        assert!(engine.get("breakout").is_none());
+
+        // The stop-market only watches its own venue
+        assert!(engine.on_market_event(&trade("okx", "94")).is_empty());
+        let released = engine.on_market_event(&trade("binance", "95"));
+        assert_eq!(released[0].order_type, OrderType::Market);
+        assert_eq!(released[0].price, None);
+        assert_eq!(released[0].side, OrderSide::Sell);
+        assert!(engine.get(&stop_loss_id).is_none());
+        assert!(engine.is_empty());
+    }
+}
@@ -0,0 +1,509 @@
+use crate::core::clock::{system_clock, SharedClock};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+/// Policy used to divide a venue's order allowance between strategies
+#[derive(Debug, Clone, PartialEq)]
+pub enum AllocationPolicy {
+    /// No per-strategy cap, strategies compete for the venue limit
+    FirstComeFirstServed,
+    /// Every registered strategy gets an equal share of the venue limit
+    EqualShare,
+    /// Strategies get a share proportional to their registered weight
+    Weighted,
+}
+
+/// Venue throttle errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThrottleError {
+    /// The strategy has no allowance on the venue (not registered, or the venue
+    /// limit is smaller than the number of participants)
+    NoAllowance {
+        exchange_id: String,
+        strategy_id: String,
+    },
+}
+
+impl fmt::Display for ThrottleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThrottleError::NoAllowance {
+                exchange_id,
+                strategy_id,
+            } => write!(
+                f,
+                "Strategy {} has no order allowance on {}",
+                strategy_id, exchange_id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ThrottleError {}
+
+/// Order allowance ledger for a single venue
+///
+/// All strategies and executors trading on the same exchange consult the same
+/// `VenueThrottle`, so the venue limit is enforced across all of them instead of
+/// once per strategy.
+pub struct VenueThrottle {
+    /// Exchange ID this throttle guards
+    exchange_id: String,
+    /// Maximum number of orders allowed in the time window (venue-wide)
+    max_orders: usize,
+    /// Time window for the venue limit
+    window: Duration,
+    /// Allocation policy between strategies
+    policy: AllocationPolicy,
+    /// Mutable ledger state
+    state: Mutex<VenueThrottleState>,
+    /// Time source for the window
+    clock: SharedClock,
+}
+
+/// Mutable state of a venue throttle
+#[derive(Debug, Default)]
+struct VenueThrottleState {
+    /// History of (timestamp, strategy ID) for orders inside the window
+    history: VecDeque<(Instant, String)>,
+    /// Registered strategies and their weights
+    participants: HashMap<String, u32>,
+}
+
+impl VenueThrottleState {
+    /// Drop entries that fell out of the window
+    fn prune(&mut self, now: Instant, window: Duration) {
+        while let Some((front_time, _)) = self.history.front() {
+            if now.duration_since(*front_time) >= window {
+                self.history.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Number of orders a strategy has in the current window
+    fn usage(&self, strategy_id: &str) -> usize {
+        self.history
+            .iter()
+            .filter(|(_, id)| id == strategy_id)
+            .count()
+    }
+}
+
+impl VenueThrottle {
+    /// Create a new venue throttle
+    pub fn new(
+        exchange_id: impl Into<String>,
+        max_orders: usize,
+        window: Duration,
+        policy: AllocationPolicy,
+    ) -> Self {
+        Self {
+            exchange_id: exchange_id.into(),
+            max_orders,
+            window,
+            policy,
+            state: Mutex::new(VenueThrottleState::default()),
+            clock: system_clock(),
+        }
+    }
+
+    /// Set the time source (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Get the exchange ID
+    pub fn exchange_id(&self) -> &str {
+        &self.exchange_id
+    }
+
+    /// Get the venue-wide order limit
+    pub fn max_orders(&self) -> usize {
+        self.max_orders
+    }
+
+    /// Get the time window
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Get the allocation policy
+    pub fn policy(&self) -> &AllocationPolicy {
+        &self.policy
+    }
+
+    /// Register a strategy with a weight (weight is only used by `Weighted`)
+    pub fn register_strategy(&self, strategy_id: &str, weight: u32) {
+        let mut state = self.state.lock().unwrap();
+        state
+            .participants
+            .insert(strategy_id.to_string(), weight.max(1));
+    }
+
+    /// Remove a strategy from the allocation
+    pub fn unregister_strategy(&self, strategy_id: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.participants.remove(strategy_id);
+    }
+
+    /// Get the number of registered strategies
+    pub fn strategy_count(&self) -> usize {
+        self.state.lock().unwrap().participants.len()
+    }
+
+    /// Get the order allowance of a strategy for one window
+    pub fn allowance(&self, strategy_id: &str) -> usize {
+        let state = self.state.lock().unwrap();
+        self.allowance_locked(&state, strategy_id)
+    }
+
+    fn allowance_locked(&self, state: &VenueThrottleState, strategy_id: &str) -> usize {
+        match self.policy {
+            AllocationPolicy::FirstComeFirstServed => self.max_orders,
+            AllocationPolicy::EqualShare => {
+                if !state.participants.contains_key(strategy_id) {
+                    return 0;
+                }
+                self.max_orders / state.participants.len().max(1)
+            }
+            AllocationPolicy::Weighted => {
+                let weight = match state.participants.get(strategy_id) {
+                    Some(weight) => *weight as usize,
+                    None => return 0,
+                };
+                let total_weight: usize = state.participants.values().map(|w| *w as usize).sum();
+                self.max_orders * weight / total_weight.max(1)
+            }
+        }
+    }
+
+    /// Check if a strategy may send an order and record it if allowed
+    pub fn try_acquire(&self, strategy_id: &str) -> bool {
+        let now = self.clock.now_instant();
+        let mut state = self.state.lock().unwrap();
+        state.prune(now, self.window);
+
+        // Venue-wide limit applies to everyone
+        if state.history.len() >= self.max_orders {
+            return false;
+        }
+
+        // Per-strategy share
+        if state.usage(strategy_id) >= self.allowance_locked(&state, strategy_id) {
+            return false;
+        }
+
+        state.history.push_back((now, strategy_id.to_string()));
+        true
+    }
+
+    /// Wait until a strategy may send an order
+    ///
+    /// Fails instead of waiting forever when the strategy's allowance is zero.
+    pub async fn wait_for_slot(&self, strategy_id: &str) -> Result<(), ThrottleError> {
+        while !self.try_acquire(strategy_id) {
+            if self.allowance(strategy_id) == 0 {
+                return Err(ThrottleError::NoAllowance {
+                    exchange_id: self.exchange_id.clone(),
+                    strategy_id: strategy_id.to_string(),
+                });
+            }
+            let wait_time = self.time_until_next_slot(strategy_id);
+            sleep(wait_time.max(Duration::from_millis(1))).await;
+        }
+        Ok(())
+    }
+
+    /// Get the number of orders a strategy has sent in the current window
+    pub fn usage(&self, strategy_id: &str) -> usize {
+        let mut state = self.state.lock().unwrap();
+        state.prune(self.clock.now_instant(), self.window);
+        state.usage(strategy_id)
+    }
+
+    /// Get the number of orders sent on this venue in the current window
+    pub fn total_usage(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        state.prune(self.clock.now_instant(), self.window);
+        state.history.len()
+    }
+
+    /// Get the number of orders a strategy can still send in the current window
+    pub fn remaining(&self, strategy_id: &str) -> usize {
+        let mut state = self.state.lock().unwrap();
+        state.prune(self.clock.now_instant(), self.window);
+        let own_remaining = self
+            .allowance_locked(&state, strategy_id)
+            .saturating_sub(state.usage(strategy_id));
+        let venue_remaining = self.max_orders.saturating_sub(state.history.len());
+        own_remaining.min(venue_remaining)
+    }
+
+    /// Get the time until the strategy's oldest order (or the venue's oldest order) leaves the window
+    pub fn time_until_next_slot(&self, strategy_id: &str) -> Duration {
+        let now = self.clock.now_instant();
+        let mut state = self.state.lock().unwrap();
+        state.prune(now, self.window);
+
+        let oldest = if state.history.len() >= self.max_orders {
+            state.history.front().map(|(time, _)| *time)
+        } else if state.usage(strategy_id) >= self.allowance_locked(&state, strategy_id) {
+            state
+                .history
+                .iter()
+                .find(|(_, id)| id == strategy_id)
+                .map(|(time, _)| *time)
+        } else {
+            None
+        };
+
+        match oldest {
+            Some(time) => self.window.saturating_sub(now.duration_since(time)),
+            None => Duration::from_millis(0),
+        }
+    }
+
+    /// Reset the ledger (registered strategies are kept)
+    pub fn reset(&self) {
+        self.state.lock().unwrap().history.clear();
+    }
+}
+
+/// Registry of venue throttles shared by all strategies and executors
+#[derive(Default)]
+pub struct VenueThrottleRegistry {
+    /// Throttles by exchange ID
+    venues: RwLock<HashMap<String, Arc<VenueThrottle>>>,
+}
+
+impl VenueThrottleRegistry {
+    /// Create a new empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add (or replace) the throttle for a venue
+    pub fn add_venue(
+        &self,
+        exchange_id: &str,
+        max_orders: usize,
+        window: Duration,
+        policy: AllocationPolicy,
+    ) -> Arc<VenueThrottle> {
+        let throttle = Arc::new(VenueThrottle::new(exchange_id, max_orders, window, policy));
+        self.venues
+            .write()
+            .unwrap()
+            .insert(exchange_id.to_string(), throttle.clone());
+        throttle
+    }
+
+    /// Get the throttle for a venue
+    pub fn venue(&self, exchange_id: &str) -> Option<Arc<VenueThrottle>> {
+        self.venues.read().unwrap().get(exchange_id).cloned()
+    }
+
+    /// Register a strategy on a venue
+    pub fn register_strategy(&self, exchange_id: &str, strategy_id: &str, weight: u32) {
+        if let Some(throttle) = self.venue(exchange_id) {
+            throttle.register_strategy(strategy_id, weight);
+        }
+    }
+
+    /// Check if a strategy may send an order on a venue and record it if allowed
+    pub fn try_acquire(&self, exchange_id: &str, strategy_id: &str) -> bool {
+        match self.venue(exchange_id) {
+            Some(throttle) => throttle.try_acquire(strategy_id),
+            None => true, // No throttle configured for this venue
+        }
+    }
+
+    /// Wait until a strategy may send an order on a venue
+    pub async fn wait_for_slot(
+        &self,
+        exchange_id: &str,
+        strategy_id: &str,
+    ) -> Result<(), ThrottleError> {
+        match self.venue(exchange_id) {
+            Some(throttle) => throttle.wait_for_slot(strategy_id).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Get the remaining allowance of a strategy on a venue
+    pub fn remaining(&self, exchange_id: &str, strategy_id: &str) -> Option<usize> {
+        self.venue(exchange_id)
+            .map(|throttle| throttle.remaining(strategy_id))
+    }
+
+    /// Reset all venue ledgers
+    pub fn reset_all(&self) {
+        for throttle in self.venues.read().unwrap().values() {
+            throttle.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_come_first_served_shares_venue_limit() {
+        let throttle = VenueThrottle::new(
+            "binance",
+            3,
+            Duration::from_secs(1),
+            AllocationPolicy::FirstComeFirstServed,
+        );
+
+        assert!(throttle.try_acquire("mm"));
+        assert!(throttle.try_acquire("mm"));
+        assert!(throttle.try_acquire("arb"));
+
+        // Venue limit is exhausted for both strategies
+        assert!(!throttle.try_acquire("mm"));
+        assert!(!throttle.try_acquire("arb"));
+        assert_eq!(throttle.total_usage(), 3);
+        assert_eq!(throttle.usage("mm"), 2);
+    }
+
+    #[test]
+    fn test_equal_share_caps_each_strategy() {
+        let throttle = VenueThrottle::new(
+            "binance",
+            4,
+            Duration::from_secs(1),
+            AllocationPolicy::EqualShare,
+        );
+        throttle.register_strategy("mm", 1);
+        throttle.register_strategy("arb", 1);
+
+        assert_eq!(throttle.allowance("mm"), 2);
+        assert!(throttle.try_acquire("mm"));
+        assert!(throttle.try_acquire("mm"));
+        assert!(!throttle.try_acquire("mm"));
+
+        // The other strategy keeps its share
+        assert_eq!(throttle.remaining("arb"), 2);
+        assert!(throttle.try_acquire("arb"));
+
+        // Unregistered strategies get nothing
+        assert!(!throttle.try_acquire("unknown"));
+    }
+
+    #[test]
+    fn test_weighted_allocation() {
+        let throttle = VenueThrottle::new(
+            "okx",
+            10,
+            Duration::from_secs(1),
+            AllocationPolicy::Weighted,
+        );
+        throttle.register_strategy("mm", 3);
+        throttle.register_strategy("arb", 1);
+        throttle.register_strategy("rebalance", 1);
+
+        assert_eq!(throttle.allowance("mm"), 6);
+        assert_eq!(throttle.allowance("arb"), 2);
+        assert_eq!(throttle.allowance("rebalance"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_allowance_recovers_after_window() {
+        let throttle = VenueThrottle::new(
+            "binance",
+            1,
+            Duration::from_millis(100),
+            AllocationPolicy::FirstComeFirstServed,
+        );
+
+        assert!(throttle.try_acquire("mm"));
+        assert!(!throttle.try_acquire("mm"));
+        assert!(throttle.time_until_next_slot("mm") > Duration::from_millis(0));
+
+        let start = Instant::now();
+        throttle.wait_for_slot("mm").await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(90));
+        assert_eq!(throttle.total_usage(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_slot_fails_for_unregistered_strategy() {
+        let registry = VenueThrottleRegistry::new();
+        registry.add_venue(
+            "binance",
+            4,
+            Duration::from_secs(1),
+            AllocationPolicy::EqualShare,
+        );
+        registry.register_strategy("binance", "mm", 1);
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            registry.wait_for_slot("binance", "unknown"),
+        )
+        .await
+        .expect("wait_for_slot must not hang");
+        assert_eq!(
+            result,
+            Err(ThrottleError::NoAllowance {
+                exchange_id: "binance".to_string(),
+                strategy_id: "unknown".to_string(),
+            })
+        );
+        assert!(registry.wait_for_slot("binance", "mm").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_slot_fails_with_zero_allowance() {
+        // Three strategies splitting a limit of two leaves each of them nothing
+        let throttle = VenueThrottle::new(
+            "binance",
+            2,
+            Duration::from_secs(1),
+            AllocationPolicy::EqualShare,
+        );
+        throttle.register_strategy("mm", 1);
+        throttle.register_strategy("arb", 1);
+        throttle.register_strategy("rebalance", 1);
+        assert_eq!(throttle.allowance("mm"), 0);
+
+        let result = tokio::time::timeout(Duration::from_secs(1), throttle.wait_for_slot("mm"))
+            .await
+            .expect("wait_for_slot must not hang");
+        assert!(matches!(result, Err(ThrottleError::NoAllowance { .. })));
+    }
+
+    #[test]
+    fn test_registry_is_shared_between_strategies() {
+        let registry = Arc::new(VenueThrottleRegistry::new());
+        registry.add_venue(
+            "binance",
+            2,
+            Duration::from_secs(1),
+            AllocationPolicy::FirstComeFirstServed,
+        );
+
+        let for_mm = registry.clone();
+        let for_arb = registry.clone();
+
+        assert!(for_mm.try_acquire("binance", "mm"));
+        assert!(for_arb.try_acquire("binance", "arb"));
+        assert!(!for_mm.try_acquire("binance", "mm"));
+        assert_eq!(registry.remaining("binance", "arb"), Some(0));
+
+        // Venues without a throttle are not limited
+        assert!(registry.try_acquire("bybit", "mm"));
+        assert_eq!(registry.remaining("bybit", "mm"), None);
+
+        registry.reset_all();
+        assert_eq!(registry.remaining("binance", "mm"), Some(2));
+    }
+}
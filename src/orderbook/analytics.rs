@@ -0,0 +1,282 @@
+use crate::core::events::{BookMetrics, MarketEvent, OrderSide};
+use crate::orderbook::OrderBook;
+use crate::traits::MarketDataStream;
+use crate::types::{Price, Size, Symbol};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
+
+/// What the derived `BookMetrics` events are computed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookMetricsConfig {
+    /// Levels per side summed for the imbalance
+    pub depth_levels: usize,
+    /// Size swept on each side for the depth-weighted mid
+    pub vwap_size: Size,
+}
+
+impl Default for BookMetricsConfig {
+    fn default() -> Self {
+        Self {
+            depth_levels: 5,
+            vwap_size: Size::new(Decimal::ONE),
+        }
+    }
+}
+
+impl OrderBook {
+    /// Get the mid price between the best bid and ask
+    pub fn mid_price(&self) -> Option<Price> {
+        let (bid, _) = self.best_bid()?;
+        let (ask, _) = self.best_ask()?;
+        Some(Price::new((bid.value() + ask.value()) / Decimal::TWO))
+    }
+
+    /// Size imbalance over the top `depth_levels` of each side
+    /// Returns a value between -1 (all asks) and 1 (all bids)
+    pub fn imbalance(&self, depth_levels: usize) -> Option<Decimal> {
+        if depth_levels == 0 {
+            return None;
+        }
+        let bids = self.top_bids(depth_levels);
+        let asks = self.top_asks(depth_levels);
+        if bids.is_empty() || asks.is_empty() {
+            return None;
+        }
+        let bid_volume: Decimal = bids.iter().map(|(_, size)| size.value()).sum();
+        let ask_volume: Decimal = asks.iter().map(|(_, size)| size.value()).sum();
+        let total = bid_volume + ask_volume;
+        if total.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+        Some((bid_volume - ask_volume) / total)
+    }
+
+    /// Touch prices weighted by the size resting on the opposite side
+    /// Leans toward the side more likely to be traded through next.
+    pub fn microprice(&self) -> Option<Price> {
+        let (bid, bid_size) = self.best_bid()?;
+        let (ask, ask_size) = self.best_ask()?;
+        let total = bid_size.value() + ask_size.value();
+        if total.is_zero() {
+            return self.mid_price();
+        }
+        Some(Price::new(
+            (bid.value() * ask_size.value() + ask.value() * bid_size.value()) / total,
+        ))
+    }
+
+    /// Depth-weighted mid: the average of the prices for sweeping `size` off each side
+    /// None unless both sides are deep enough to fill the whole size.
+    pub fn vwap_to_size(&self, size: Size) -> Option<Price> {
+        let buy = self.execution_ladder(OrderSide::Buy, size);
+        let sell = self.execution_ladder(OrderSide::Sell, size);
+        if !buy.is_complete() || !sell.is_complete() {
+            return None;
+        }
+        Some(Price::new(
+            (buy.vwap()?.value() + sell.vwap()?.value()) / Decimal::TWO,
+        ))
+    }
+
+    /// Spread as a fraction of the mid price, in basis points
+    pub fn spread_bps(&self) -> Option<Decimal> {
+        let mid = self.mid_price()?.value();
+        if mid <= Decimal::ZERO {
+            return None;
+        }
+        Some(self.spread()?.value() / mid * Decimal::from(10_000))
+    }
+
+    /// Compute the derived metrics for the book, if both sides are quoted
+    pub fn metrics(&self, exchange_id: &str, config: &BookMetricsConfig) -> Option<BookMetrics> {
+        let (best_bid, _) = self.best_bid()?;
+        let (best_ask, _) = self.best_ask()?;
+        Some(BookMetrics {
+            symbol: Symbol::new(self.symbol()),
+            exchange_id: exchange_id.to_string(),
+            best_bid,
+            best_ask,
+            mid_price: self.mid_price()?,
+            microprice: self.microprice()?,
+            spread_bps: self.spread_bps()?,
+            imbalance: self.imbalance(config.depth_levels)?,
+            depth_levels: config.depth_levels,
+            depth_weighted_mid: self.vwap_to_size(config.vwap_size),
+            vwap_size: config.vwap_size,
+            timestamp: self.last_update(),
+        })
+    }
+}
+
+/// Market data stream that follows each book update with a `MarketEvent::BookMetrics`
+///
+/// Keeps a book per exchange and symbol, so consumers read imbalance, microprice and
+/// spread from the derived event instead of recomputing them from their own books.
+/// Nothing is derived for a book out of sync with its venue, or while a side is empty.
+pub struct BookMetricsStream<S> {
+    inner: S,
+    config: BookMetricsConfig,
+    books: HashMap<(String, Symbol), OrderBook>,
+    pending: VecDeque<MarketEvent>,
+}
+
+impl<S> BookMetricsStream<S> {
+    /// Wrap a stream with the default metrics settings
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            config: BookMetricsConfig::default(),
+            books: HashMap::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Set what the metrics are computed over (builder pattern)
+    pub fn with_config(mut self, config: BookMetricsConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Get the book maintained for an exchange and symbol
+    pub fn book(&self, exchange_id: &str, symbol: &str) -> Option<&OrderBook> {
+        self.books
+            .get(&(exchange_id.to_string(), Symbol::new(symbol)))
+    }
+
+    /// Apply an event to its book and compute the metrics it produces
+    pub fn derive(&mut self, event: &MarketEvent) -> Option<BookMetrics> {
+        let (symbol, exchange_id) = match event {
+            MarketEvent::OrderBookSnapshot(snapshot) => (&snapshot.symbol, &snapshot.exchange_id),
+            MarketEvent::OrderBookDelta(delta) => (&delta.symbol, &delta.exchange_id),
+            MarketEvent::Desync(desync) => (&desync.symbol, &desync.exchange_id),
+            _ => return None,
+        };
+        let book = self
+            .books
+            .entry((exchange_id.clone(), symbol.clone()))
+            .or_insert_with(|| OrderBook::new(symbol.value().to_string()));
+        match event {
+            MarketEvent::OrderBookSnapshot(snapshot) => book.apply_snapshot(snapshot.clone()),
+            MarketEvent::OrderBookDelta(delta) if !book.is_desynced() => {
+                book.apply_delta(delta.clone())
+            }
+            MarketEvent::Desync(desync) => {
+                book.mark_desynced(desync.error.clone());
+            }
+            _ => {}
+        }
+        if book.is_desynced() {
+            return None;
+        }
+        book.metrics(exchange_id, &self.config)
+    }
+}
+
+#[async_trait]
+impl<S> MarketDataStream for BookMetricsStream<S>
+where
+    S: MarketDataStream + Send,
+{
+    type Error = S::Error;
+
+    async fn subscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.inner.subscribe(symbols).await
+    }
+
+    async fn unsubscribe(&mut self, symbols: &[&str]) -> Result<(), Self::Error> {
+        self.books
+            .retain(|(_, symbol), _| !symbols.contains(&symbol.value()));
+        self.inner.unsubscribe(symbols).await
+    }
+
+    async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(Ok(event));
+        }
+        let next = self.inner.next().await;
+        if let Some(Ok(event)) = &next {
+            if let Some(metrics) = self.derive(event) {
+                self.pending.push_back(MarketEvent::BookMetrics(metrics));
+            }
+        }
+        next
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn last_update(&self, symbol: &str) -> Option<u64> {
+        self.inner.last_update(symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{OrderBookDelta, OrderBookLevel, OrderBookSnapshot};
+
+    fn level(price: &str, size: &str) -> OrderBookLevel {
+        OrderBookLevel::new(
+            Price::from_str(price).unwrap(),
+            Size::from_str(size).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_book_metrics() {
+        let snapshot = OrderBookSnapshot::new(
+            "BTCUSDT",
+            "binance",
+            vec![level("99", "3"), level("98", "1")],
+            vec![level("101", "1"), level("102", "3")],
+            1,
+        );
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.apply_snapshot(snapshot.clone());
+
+        assert_eq!(book.mid_price(), Some(Price::from_str("100").unwrap()));
+        // 3 bid vs 1 ask at the touch pulls the microprice toward the ask
+        assert_eq!(book.microprice(), Some(Price::from_str("100.5").unwrap()));
+        assert_eq!(book.imbalance(1), Some(Decimal::new(5, 1)));
+        assert_eq!(book.imbalance(2), Some(Decimal::ZERO));
+        assert_eq!(book.imbalance(0), None);
+        assert_eq!(book.spread_bps(), Some(Decimal::from(200)));
+        // Sweeping 2 buys at 101.5 and sells at 99
+        assert_eq!(
+            book.vwap_to_size(Size::from_str("2").unwrap()),
+            Some(Price::from_str("100.25").unwrap())
+        );
+        assert_eq!(book.vwap_to_size(Size::from_str("5").unwrap()), None);
+
+        let mut stream = BookMetricsStream::new(()).with_config(BookMetricsConfig {
+            depth_levels: 1,
+            vwap_size: Size::from_str("2").unwrap(),
+        });
+        let metrics = stream
+            .derive(&MarketEvent::OrderBookSnapshot(snapshot))
+            .unwrap();
+        assert_eq!(metrics.exchange_id, "binance");
+        assert_eq!(metrics.imbalance, Decimal::new(5, 1));
+        assert_eq!(
+            metrics.depth_weighted_mid,
+            Some(Price::from_str("100.25").unwrap())
+        );
+
+        // Emptying the ask side leaves nothing to derive
+        let metrics = stream.derive(&MarketEvent::OrderBookDelta(OrderBookDelta::new(
+            "BTCUSDT",
+            "binance",
+            vec![],
+            vec![level("101", "0"), level("102", "0")],
+            2,
+        )));
+        assert!(metrics.is_none());
+        assert!(stream
+            .book("binance", "BTCUSDT")
+            .unwrap()
+            .best_ask()
+            .is_none());
+    }
+}
@@ -0,0 +1,315 @@
+use crate::core::clock::{system_clock, SharedClock};
+use crate::core::events::{MarketEvent, Timestamp};
+use crate::orderbook::OrderBook;
+use crate::types::{Price, Size, Symbol};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+/// Best price on one venue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueQuote {
+    pub exchange_id: String,
+    pub price: Price,
+    pub size: Size,
+}
+
+/// Top levels of one venue's book
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueDepth {
+    pub exchange_id: String,
+    /// Best price first
+    pub bids: Vec<(Price, Size)>,
+    /// Best price first
+    pub asks: Vec<(Price, Size)>,
+}
+
+/// National best bid and offer: the best prices across all venues for a symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Nbbo {
+    pub symbol: Symbol,
+    pub best_bid: Option<VenueQuote>,
+    pub best_ask: Option<VenueQuote>,
+    /// Latest update time among the venue books
+    pub timestamp: Timestamp,
+}
+
+impl Nbbo {
+    /// Check if the best bid is at or above the best ask on a different venue
+    pub fn is_crossed(&self) -> bool {
+        match (&self.best_bid, &self.best_ask) {
+            (Some(bid), Some(ask)) => bid.exchange_id != ask.exchange_id && bid.price >= ask.price,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VenueBook {
+    book: OrderBook,
+    received: Instant,
+}
+
+/// Order books for the same symbol on several exchanges, merged
+///
+/// Only venues added with `add_venue` are tracked. Each venue keeps its own full
+/// `OrderBook`; books that are out of sync (after a `MarketEvent::Desync`, until the
+/// next snapshot) or older than the maximum age are left out of the best bid and offer.
+#[derive(Debug, Clone)]
+pub struct ConsolidatedBook {
+    venues: BTreeSet<String>,
+    books: HashMap<Symbol, BTreeMap<String, VenueBook>>,
+    nbbo: HashMap<Symbol, Nbbo>,
+    max_age: Option<Duration>,
+    /// Time source for book ages
+    clock: SharedClock,
+}
+
+impl ConsolidatedBook {
+    /// Create an empty consolidated book
+    pub fn new() -> Self {
+        Self {
+            venues: BTreeSet::new(),
+            books: HashMap::new(),
+            nbbo: HashMap::new(),
+            max_age: None,
+            clock: system_clock(),
+        }
+    }
+
+    /// Set the time source for book ages (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Leave venue books not updated within `max_age` out of the NBBO (builder pattern)
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Track a venue
+    pub fn add_venue(&mut self, exchange_id: impl Into<String>) {
+        self.venues.insert(exchange_id.into());
+    }
+
+    /// Check if a venue is tracked
+    pub fn has_venue(&self, exchange_id: &str) -> bool {
+        self.venues.contains(exchange_id)
+    }
+
+    /// Get the tracked venues, sorted
+    pub fn venues(&self) -> impl Iterator<Item = &str> {
+        self.venues.iter().map(String::as_str)
+    }
+
+    /// Get every symbol with at least one venue book
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.books.keys()
+    }
+
+    /// Apply a market event to its venue's book
+    /// Returns the symbol's NBBO if the event changed it
+    pub fn apply(&mut self, event: &MarketEvent) -> Option<Nbbo> {
+        let exchange_id = event.exchange_id();
+        if !self.venues.contains(exchange_id) {
+            return None;
+        }
+        let now = self.clock.now_instant();
+        let symbol = match event {
+            MarketEvent::OrderBookSnapshot(snapshot) => {
+                let venue = self.venue_entry(&snapshot.symbol, exchange_id);
+                venue.book.apply_snapshot(snapshot.clone());
+                venue.received = now;
+                &snapshot.symbol
+            }
+            MarketEvent::OrderBookDelta(delta) => {
+                let venue = self.venue_entry(&delta.symbol, exchange_id);
+                // Deltas on a desynced book would build on missing updates
+                if venue.book.is_desynced() {
+                    return None;
+                }
+                venue.book.apply_delta(delta.clone());
+                venue.received = now;
+                &delta.symbol
+            }
+            MarketEvent::Desync(desync) => {
+                let venue = self.venue_entry(&desync.symbol, exchange_id);
+                venue.book.mark_desynced(desync.error.clone());
+                &desync.symbol
+            }
+            _ => return None,
+        };
+
+        let nbbo = self.compute_nbbo(symbol);
+        if self
+            .nbbo
+            .get(symbol)
+            .map(|last| (&last.best_bid, &last.best_ask))
+            == Some((&nbbo.best_bid, &nbbo.best_ask))
+        {
+            return None;
+        }
+        self.nbbo.insert(symbol.clone(), nbbo.clone());
+        Some(nbbo)
+    }
+
+    fn venue_entry(&mut self, symbol: &Symbol, exchange_id: &str) -> &mut VenueBook {
+        let now = self.clock.now_instant();
+        self.books
+            .entry(symbol.clone())
+            .or_default()
+            .entry(exchange_id.to_string())
+            .or_insert_with(|| VenueBook {
+                book: OrderBook::new(symbol.value().to_string()),
+                received: now,
+            })
+    }
+
+    /// Venue books usable for the NBBO
+    fn live_books(&self, symbol: &Symbol) -> impl Iterator<Item = (&String, &OrderBook)> {
+        let now = self.clock.now_instant();
+        self.books
+            .get(symbol)
+            .into_iter()
+            .flatten()
+            .filter(move |(_, venue)| {
+                !venue.book.is_desynced()
+                    && self
+                        .max_age
+                        .is_none_or(|max_age| now.duration_since(venue.received) <= max_age)
+            })
+            .map(|(exchange_id, venue)| (exchange_id, &venue.book))
+    }
+
+    fn compute_nbbo(&self, symbol: &Symbol) -> Nbbo {
+        let mut nbbo = Nbbo {
+            symbol: symbol.clone(),
+            best_bid: None,
+            best_ask: None,
+            timestamp: 0,
+        };
+        // Venues are visited in name order, so ties go to the first venue alphabetically
+        for (exchange_id, book) in self.live_books(symbol) {
+            nbbo.timestamp = nbbo.timestamp.max(book.last_update());
+            if let Some((price, size)) = book.best_bid() {
+                if nbbo.best_bid.as_ref().is_none_or(|best| price > best.price) {
+                    nbbo.best_bid = Some(VenueQuote {
+                        exchange_id: exchange_id.clone(),
+                        price,
+                        size,
+                    });
+                }
+            }
+            if let Some((price, size)) = book.best_ask() {
+                if nbbo.best_ask.as_ref().is_none_or(|best| price < best.price) {
+                    nbbo.best_ask = Some(VenueQuote {
+                        exchange_id: exchange_id.clone(),
+                        price,
+                        size,
+                    });
+                }
+            }
+        }
+        nbbo
+    }
+
+    /// Get the current best bid and offer across venues for a symbol
+    pub fn nbbo(&self, symbol: &Symbol) -> Nbbo {
+        self.compute_nbbo(symbol)
+    }
+
+    /// Get the best bid across venues
+    pub fn best_bid(&self, symbol: &Symbol) -> Option<VenueQuote> {
+        self.compute_nbbo(symbol).best_bid
+    }
+
+    /// Get the best ask across venues
+    pub fn best_ask(&self, symbol: &Symbol) -> Option<VenueQuote> {
+        self.compute_nbbo(symbol).best_ask
+    }
+
+    /// Get one venue's book for a symbol, whether or not it is usable for the NBBO
+    pub fn venue_book(&self, symbol: &Symbol, exchange_id: &str) -> Option<&OrderBook> {
+        self.books
+            .get(symbol)?
+            .get(exchange_id)
+            .map(|venue| &venue.book)
+    }
+
+    /// Get the top `levels` of bids and asks on each usable venue, by venue name
+    pub fn depth(&self, symbol: &Symbol, levels: usize) -> Vec<VenueDepth> {
+        self.live_books(symbol)
+            .map(|(exchange_id, book)| VenueDepth {
+                exchange_id: exchange_id.clone(),
+                bids: book.top_bids(levels).to_vec(),
+                asks: book.top_asks(levels).to_vec(),
+            })
+            .collect()
+    }
+}
+
+impl Default for ConsolidatedBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{BookDesync, OrderBookLevel, OrderBookSnapshot};
+    use crate::orderbook::BookIntegrityError;
+
+    fn book(exchange: &str, bid: &str, ask: &str) -> MarketEvent {
+        MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+            "BTCUSDT",
+            exchange,
+            vec![OrderBookLevel::new(
+                Price::from_str(bid).unwrap(),
+                Size::from_str("1").unwrap(),
+            )],
+            vec![OrderBookLevel::new(
+                Price::from_str(ask).unwrap(),
+                Size::from_str("2").unwrap(),
+            )],
+            1,
+        ))
+    }
+
+    #[test]
+    fn test_nbbo_across_venues() {
+        let symbol = Symbol::new("BTCUSDT");
+        let mut consolidated = ConsolidatedBook::new();
+        consolidated.add_venue("binance");
+        consolidated.add_venue("okx");
+
+        assert!(consolidated.apply(&book("kraken", "1", "2")).is_none());
+        let nbbo = consolidated.apply(&book("binance", "100", "101")).unwrap();
+        assert!(!nbbo.is_crossed());
+        let nbbo = consolidated.apply(&book("okx", "101.5", "102")).unwrap();
+        assert_eq!(nbbo.best_bid.as_ref().unwrap().exchange_id, "okx");
+        assert_eq!(nbbo.best_ask.as_ref().unwrap().exchange_id, "binance");
+        assert!(nbbo.is_crossed());
+        // Unchanged NBBO emits nothing
+        assert!(consolidated.apply(&book("okx", "101.5", "102")).is_none());
+        assert_eq!(consolidated.depth(&symbol, 5).len(), 2);
+
+        // A desynced venue drops out until its next snapshot
+        let nbbo = consolidated
+            .apply(&MarketEvent::Desync(BookDesync {
+                symbol: symbol.clone(),
+                exchange_id: "okx".to_string(),
+                error: BookIntegrityError::NoSnapshot,
+                timestamp: 2,
+            }))
+            .unwrap();
+        assert_eq!(nbbo.best_bid.unwrap().exchange_id, "binance");
+        assert!(consolidated.venue_book(&symbol, "okx").is_some());
+        assert_eq!(consolidated.depth(&symbol, 5).len(), 1);
+        let nbbo = consolidated.apply(&book("okx", "100.5", "102")).unwrap();
+        assert_eq!(consolidated.best_bid(&symbol), nbbo.best_bid);
+        assert_eq!(nbbo.best_bid.unwrap().exchange_id, "okx");
+    }
+}
@@ -0,0 +1,109 @@
+use crate::orderbook::OrderBook;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Levels per side covered by the OKX book checksum
+const OKX_CHECKSUM_LEVELS: usize = 25;
+
+/// Why a local order book no longer matches the venue's
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BookIntegrityError {
+    /// A sequenced update arrived before any sequenced snapshot
+    NoSnapshot,
+    /// Updates between the book's sequence and the received update were missed
+    SequenceGap { expected: u64, received: u64 },
+    /// The venue's checksum of its book differs from the local book's
+    ChecksumMismatch { expected: u32, computed: u32 },
+}
+
+impl fmt::Display for BookIntegrityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookIntegrityError::NoSnapshot => write!(f, "Update received before a snapshot"),
+            BookIntegrityError::SequenceGap { expected, received } => write!(
+                f,
+                "Sequence gap: expected update {}, received {}",
+                expected, received
+            ),
+            BookIntegrityError::ChecksumMismatch { expected, computed } => write!(
+                f,
+                "Checksum mismatch: expected {}, computed {}",
+                expected, computed
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BookIntegrityError {}
+
+/// Book checksums published by venues alongside their depth updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookChecksum {
+    /// OKX: CRC32 of the top 25 levels interleaved as `bid:size:ask:size:...`
+    ///
+    /// OKX sends the checksum as a signed 32-bit integer; cast it with `as u32`.
+    Okx,
+}
+
+impl BookChecksum {
+    /// Compute the checksum of a local book
+    pub fn compute(&self, book: &OrderBook) -> u32 {
+        match self {
+            BookChecksum::Okx => {
+                let bids = book.top_bids(OKX_CHECKSUM_LEVELS);
+                let asks = book.top_asks(OKX_CHECKSUM_LEVELS);
+                let mut fields = Vec::with_capacity((bids.len() + asks.len()) * 2);
+                for i in 0..bids.len().max(asks.len()) {
+                    for (price, size) in [bids.get(i), asks.get(i)].into_iter().flatten() {
+                        fields.push(price.to_string());
+                        fields.push(size.to_string());
+                    }
+                }
+                crc32(fields.join(":").as_bytes())
+            }
+        }
+    }
+}
+
+/// CRC32 (IEEE 802.3), as used by OKX and Kraken book checksums
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{OrderBookLevel, OrderBookSnapshot};
+    use crate::types::{Price, Size};
+
+    fn level(price: &str, size: &str) -> OrderBookLevel {
+        OrderBookLevel::new(
+            Price::from_str(price).unwrap(),
+            Size::from_str(size).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_okx_checksum() {
+        let mut book = OrderBook::new("BTC-USDT".to_string());
+        book.apply_snapshot(OrderBookSnapshot::new(
+            "BTC-USDT",
+            "okx",
+            vec![level("3366.1", "7"), level("3366", "6")],
+            vec![level("3366.8", "9")],
+            0,
+        ));
+        assert_eq!(
+            BookChecksum::Okx.compute(&book),
+            crc32(b"3366.1:7:3366.8:9:3366:6")
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use crate::core::events::{OrderBookLevel, OrderBookSnapshot, OrderSide, Timestamp};
+use crate::strategies::execution_style::fill_probability;
+use crate::types::{Price, Size};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// A single resting order in an L3 book
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L3Order {
+    pub order_id: String,
+    pub side: OrderSide,
+    pub price: Price,
+    pub size: Size,
+    /// When the order joined its queue
+    pub timestamp: Timestamp,
+}
+
+/// Where an order sits in the queue at its price level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuePosition {
+    /// Orders ahead at the same price
+    pub orders_ahead: usize,
+    /// Size ahead at the same price
+    pub size_ahead: Size,
+    /// Total size resting at the price, including the order itself
+    pub level_size: Size,
+}
+
+#[derive(Debug, Clone)]
+struct Level {
+    orders: VecDeque<L3Order>,
+    size: Size,
+}
+
+/// Order-by-order book with price-time priority
+///
+/// Each price level keeps its orders in arrival order, so the size queued ahead of
+/// any order is known exactly rather than estimated from aggregates. Aggregates to an
+/// `OrderBookSnapshot` for code that works on L2 books.
+#[derive(Debug, Clone)]
+pub struct L3OrderBook {
+    symbol: String,
+    bids: BTreeMap<Price, Level>,
+    asks: BTreeMap<Price, Level>,
+    /// Side and price of every resting order, for lookups by ID
+    index: HashMap<String, (OrderSide, Price)>,
+    last_update: Timestamp,
+}
+
+impl L3OrderBook {
+    /// Create a new empty L3 book for the given symbol
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            index: HashMap::new(),
+            last_update: 0,
+        }
+    }
+
+    /// Get the symbol of this book
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Timestamp of the last change
+    pub fn last_update(&self) -> Timestamp {
+        self.last_update
+    }
+
+    /// Number of resting orders
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Check if no orders are resting
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Remove every order
+    pub fn clear(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+        self.index.clear();
+    }
+
+    fn levels(&self, side: OrderSide) -> &BTreeMap<Price, Level> {
+        match side {
+            OrderSide::Buy => &self.bids,
+            OrderSide::Sell => &self.asks,
+        }
+    }
+
+    fn levels_mut(&mut self, side: OrderSide) -> &mut BTreeMap<Price, Level> {
+        match side {
+            OrderSide::Buy => &mut self.bids,
+            OrderSide::Sell => &mut self.asks,
+        }
+    }
+
+    /// Add an order to the back of its price level's queue
+    /// An order already resting under the same ID is replaced and loses its priority
+    pub fn add(&mut self, order: L3Order) {
+        self.remove(&order.order_id);
+        if order.size.is_zero() {
+            return;
+        }
+        self.last_update = self.last_update.max(order.timestamp);
+        self.index
+            .insert(order.order_id.clone(), (order.side, order.price));
+        let level = self
+            .levels_mut(order.side)
+            .entry(order.price)
+            .or_insert_with(|| Level {
+                orders: VecDeque::new(),
+                size: Size::zero(),
+            });
+        level.size = level.size + order.size;
+        level.orders.push_back(order);
+    }
+
+    /// Remove an order, e.g. on cancel
+    pub fn remove(&mut self, order_id: &str) -> Option<L3Order> {
+        let (side, price) = self.index.remove(order_id)?;
+        let levels = self.levels_mut(side);
+        let level = levels.get_mut(&price)?;
+        let position = level.orders.iter().position(|o| o.order_id == order_id)?;
+        let order = level.orders.remove(position)?;
+        level.size = level.size - order.size;
+        if level.orders.is_empty() {
+            levels.remove(&price);
+        }
+        Some(order)
+    }
+
+    /// Change the size of an order
+    /// Reductions keep queue priority; increases send the order to the back of the queue,
+    /// as on most venues. A zero size removes the order.
+    pub fn modify(&mut self, order_id: &str, size: Size, timestamp: Timestamp) -> bool {
+        let Some(&(side, price)) = self.index.get(order_id) else {
+            return false;
+        };
+        if size.is_zero() {
+            self.remove(order_id);
+            self.last_update = self.last_update.max(timestamp);
+            return true;
+        }
+        let Some(level) = self.levels_mut(side).get_mut(&price) else {
+            return false;
+        };
+        let Some(order) = level.orders.iter_mut().find(|o| o.order_id == order_id) else {
+            return false;
+        };
+        if size > order.size {
+            let mut order = order.clone();
+            order.size = size;
+            order.timestamp = timestamp;
+            self.add(order);
+        } else {
+            level.size = level.size - (order.size - size);
+            order.size = size;
+            self.last_update = self.last_update.max(timestamp);
+        }
+        true
+    }
+
+    /// Reduce an order by a traded size, removing it once fully filled
+    /// Returns the size left resting
+    pub fn fill(&mut self, order_id: &str, size: Size, timestamp: Timestamp) -> Option<Size> {
+        let remaining = self.order(order_id)?.size - size;
+        let remaining = if remaining.is_positive() {
+            remaining
+        } else {
+            Size::zero()
+        };
+        self.modify(order_id, remaining, timestamp);
+        Some(remaining)
+    }
+
+    /// Get a resting order
+    pub fn order(&self, order_id: &str) -> Option<&L3Order> {
+        let (side, price) = self.index.get(order_id)?;
+        self.levels(*side)
+            .get(price)?
+            .orders
+            .iter()
+            .find(|o| o.order_id == order_id)
+    }
+
+    /// Get the orders at a price level, front of the queue first
+    pub fn orders_at(&self, side: OrderSide, price: Price) -> impl Iterator<Item = &L3Order> {
+        self.levels(side)
+            .get(&price)
+            .into_iter()
+            .flat_map(|level| level.orders.iter())
+    }
+
+    /// Get the best bid price and aggregate size
+    pub fn best_bid(&self) -> Option<(Price, Size)> {
+        self.bids
+            .iter()
+            .next_back()
+            .map(|(price, level)| (*price, level.size))
+    }
+
+    /// Get the best ask price and aggregate size
+    pub fn best_ask(&self) -> Option<(Price, Size)> {
+        self.asks
+            .iter()
+            .next()
+            .map(|(price, level)| (*price, level.size))
+    }
+
+    /// Get the aggregate size resting at a price
+    pub fn level_size(&self, side: OrderSide, price: Price) -> Size {
+        self.levels(side)
+            .get(&price)
+            .map_or(Size::zero(), |level| level.size)
+    }
+
+    /// Get the queue position of a resting order
+    pub fn queue_position(&self, order_id: &str) -> Option<QueuePosition> {
+        let (side, price) = self.index.get(order_id)?;
+        let level = self.levels(*side).get(price)?;
+        let mut size_ahead = Size::zero();
+        for (orders_ahead, order) in level.orders.iter().enumerate() {
+            if order.order_id == order_id {
+                return Some(QueuePosition {
+                    orders_ahead,
+                    size_ahead,
+                    level_size: level.size,
+                });
+            }
+            size_ahead = size_ahead + order.size;
+        }
+        None
+    }
+
+    /// Estimate the probability a resting order fills within `horizon_secs`
+    /// given the rate (size per second) at which its level trades
+    pub fn fill_probability(
+        &self,
+        order_id: &str,
+        volume_rate: f64,
+        horizon_secs: f64,
+    ) -> Option<f64> {
+        let order = self.order(order_id)?;
+        let position = self.queue_position(order_id)?;
+        Some(fill_probability(
+            position.size_ahead,
+            order.size,
+            volume_rate,
+            horizon_secs,
+        ))
+    }
+
+    /// Aggregate the book into price levels
+    pub fn to_snapshot(&self, exchange_id: &str) -> OrderBookSnapshot {
+        OrderBookSnapshot::new(
+            self.symbol.clone(),
+            exchange_id,
+            self.bids
+                .iter()
+                .rev()
+                .map(|(price, level)| OrderBookLevel::new(*price, level.size))
+                .collect(),
+            self.asks
+                .iter()
+                .map(|(price, level)| OrderBookLevel::new(*price, level.size))
+                .collect(),
+            self.last_update,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::OrderBook;
+
+    fn order(id: &str, side: OrderSide, price: &str, size: &str, timestamp: u64) -> L3Order {
+        L3Order {
+            order_id: id.to_string(),
+            side,
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str(size).unwrap(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_queue_priority_and_snapshot() {
+        let mut book = L3OrderBook::new("BTCUSDT");
+        book.add(order("a", OrderSide::Buy, "100", "1", 1));
+        book.add(order("b", OrderSide::Buy, "100", "2", 2));
+        book.add(order("c", OrderSide::Buy, "100", "3", 3));
+        book.add(order("d", OrderSide::Buy, "99", "5", 4));
+        book.add(order("e", OrderSide::Sell, "101", "4", 5));
+        let price = Price::from_str("100").unwrap();
+
+        let position = book.queue_position("c").unwrap();
+        assert_eq!(position.orders_ahead, 2);
+        assert_eq!(position.size_ahead, Size::from_str("3").unwrap());
+        assert_eq!(position.level_size, Size::from_str("6").unwrap());
+        assert!(book.fill_probability("a", 1.0, 10.0) > book.fill_probability("c", 1.0, 10.0));
+
+        // Shrinking keeps priority, growing goes to the back
+        assert!(book.modify("a", Size::from_str("0.5").unwrap(), 6));
+        assert_eq!(book.queue_position("a").unwrap().orders_ahead, 0);
+        assert!(book.modify("b", Size::from_str("4").unwrap(), 7));
+        let queue: Vec<_> = book
+            .orders_at(OrderSide::Buy, price)
+            .map(|o| o.order_id.as_str())
+            .collect();
+        assert_eq!(queue, vec!["a", "c", "b"]);
+
+        assert_eq!(
+            book.fill("a", Size::from_str("1").unwrap(), 8),
+            Some(Size::zero())
+        );
+        assert!(book.order("a").is_none());
+        assert_eq!(book.queue_position("c").unwrap().orders_ahead, 0);
+        assert!(book.remove("d").is_some());
+        assert_eq!(book.len(), 3);
+
+        let mut l2 = OrderBook::new("BTCUSDT".to_string());
+        l2.apply_snapshot(book.to_snapshot("coinbase"));
+        assert_eq!(l2.best_bid(), Some((price, Size::from_str("7").unwrap())));
+        assert_eq!(book.best_bid(), l2.best_bid());
+        assert_eq!(book.best_ask(), l2.best_ask());
+        assert_eq!(l2.top_bids(10).len(), 1);
+        assert_eq!(l2.last_update(), 8);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::core::events::{OrderBookSnapshot, OrderSide};
+use crate::orderbook::OrderBook;
+use crate::types::{Price, Size};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use smallvec::SmallVec;
+
+/// Part of a sweep filled at one price level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LadderStep {
+    pub price: Price,
+    /// Size taken at this level
+    pub size: Size,
+    /// Size taken up to and including this level
+    pub cumulative_size: Size,
+}
+
+/// Priced execution ladder for taking a given size from the book
+///
+/// The single place where depth is walked level by level; fat-finger checks, slippage
+/// estimates and arbitrage sizing all read their numbers from here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionLadder {
+    pub side: OrderSide,
+    /// Size the sweep was asked to fill
+    pub requested: Size,
+    /// Levels consumed, best price first
+    pub steps: SmallVec<[LadderStep; 20]>,
+    /// Size filled across all levels
+    pub filled: Size,
+    /// Quote notional of the filled size
+    pub notional: Decimal,
+    /// Size left over once the visible book is exhausted
+    pub remaining: Size,
+    /// Touch price on the side being taken
+    pub best_price: Option<Price>,
+}
+
+impl ExecutionLadder {
+    /// Walk `levels` (best price first) to fill `size`
+    /// A buy walks the asks and a sell walks the bids
+    pub fn walk(
+        side: OrderSide,
+        size: Size,
+        levels: impl IntoIterator<Item = (Price, Size)>,
+    ) -> Self {
+        let mut ladder = Self {
+            side,
+            requested: size,
+            steps: SmallVec::new(),
+            filled: Size::zero(),
+            notional: Decimal::ZERO,
+            remaining: size,
+            best_price: None,
+        };
+        for (price, available) in levels {
+            if ladder.best_price.is_none() {
+                ladder.best_price = Some(price);
+            }
+            if ladder.remaining.value() <= Decimal::ZERO {
+                break;
+            }
+            if available.is_zero() {
+                continue;
+            }
+            let take = available.min(ladder.remaining);
+            ladder.filled = ladder.filled + take;
+            ladder.remaining = ladder.remaining - take;
+            ladder.notional += take * price;
+            ladder.steps.push(LadderStep {
+                price,
+                size: take,
+                cumulative_size: ladder.filled,
+            });
+        }
+        ladder
+    }
+
+    /// Number of price levels the sweep touches
+    pub fn levels_consumed(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if the visible book covers the full size
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_zero()
+    }
+
+    /// Volume-weighted average fill price
+    pub fn vwap(&self) -> Option<Price> {
+        (!self.filled.is_zero()).then(|| Price::new(self.notional / self.filled.value()))
+    }
+
+    /// Price of the last level reached
+    pub fn worst_price(&self) -> Option<Price> {
+        self.steps.last().map(|step| step.price)
+    }
+
+    /// Cost of the sweep versus the touch in bps (positive means worse than the touch)
+    pub fn slippage_bps(&self) -> Option<f64> {
+        let best = self.best_price?.value();
+        let vwap = self.vwap()?.value();
+        if best <= Decimal::ZERO {
+            return None;
+        }
+        let slippage = match self.side {
+            OrderSide::Buy => vwap - best,
+            OrderSide::Sell => best - vwap,
+        };
+        (slippage / best * Decimal::from(10_000)).to_f64()
+    }
+
+    /// Size that can be filled without paying beyond `limit` (inclusive)
+    pub fn size_within(&self, limit: Price) -> Size {
+        self.steps
+            .iter()
+            .take_while(|step| match self.side {
+                OrderSide::Buy => step.price <= limit,
+                OrderSide::Sell => step.price >= limit,
+            })
+            .last()
+            .map(|step| step.cumulative_size)
+            .unwrap_or_else(Size::zero)
+    }
+}
+
+impl OrderBook {
+    /// Walk the opposite side of the book to price taking `size`
+    pub fn execution_ladder(&self, side: OrderSide, size: Size) -> ExecutionLadder {
+        match side {
+            OrderSide::Buy => ExecutionLadder::walk(side, size, self.asks_iter()),
+            OrderSide::Sell => ExecutionLadder::walk(side, size, self.bids_iter()),
+        }
+    }
+}
+
+impl OrderBookSnapshot {
+    /// Walk the opposite side of the snapshot to price taking `size`
+    /// Levels are expected best price first, as exchanges send them
+    pub fn execution_ladder(&self, side: OrderSide, size: Size) -> ExecutionLadder {
+        let levels = match side {
+            OrderSide::Buy => &self.asks,
+            OrderSide::Sell => &self.bids,
+        };
+        ExecutionLadder::walk(side, size, levels.iter().map(|l| (l.price, l.size)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OrderBookLevel;
+
+    fn level(price: &str, size: &str) -> OrderBookLevel {
+        OrderBookLevel::new(
+            Price::from_str(price).unwrap(),
+            Size::from_str(size).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_ladder_walks_depth() {
+        let snapshot = OrderBookSnapshot::new(
+            "BTCUSDT",
+            "test",
+            vec![level("99", "1"), level("98", "2")],
+            vec![level("100", "1"), level("101", "2"), level("103", "5")],
+            1,
+        );
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.apply_snapshot(snapshot.clone());
+
+        let ladder = book.execution_ladder(OrderSide::Buy, Size::from_str("2").unwrap());
+        assert_eq!(
+            ladder,
+            snapshot.execution_ladder(OrderSide::Buy, Size::from_str("2").unwrap())
+        );
+        assert_eq!(ladder.levels_consumed(), 2);
+        assert!(ladder.is_complete());
+        assert_eq!(ladder.vwap(), Some(Price::from_str("100.5").unwrap()));
+        assert_eq!(ladder.worst_price(), Some(Price::from_str("101").unwrap()));
+        assert_eq!(ladder.slippage_bps(), Some(50.0));
+        assert_eq!(
+            ladder.size_within(Price::from_str("100").unwrap()),
+            Size::from_str("1").unwrap()
+        );
+
+        // Selling more than the visible bids leaves a remainder
+        let ladder = book.execution_ladder(OrderSide::Sell, Size::from_str("5").unwrap());
+        assert!(!ladder.is_complete());
+        assert_eq!(ladder.filled, Size::from_str("3").unwrap());
+        assert_eq!(ladder.remaining, Size::from_str("2").unwrap());
+        assert_eq!(ladder.notional, Decimal::from(295));
+        assert_eq!(ladder.worst_price(), Some(Price::from_str("98").unwrap()));
+
+        let empty = OrderBook::new("BTCUSDT".to_string());
+        let ladder = empty.execution_ladder(OrderSide::Buy, Size::from_str("1").unwrap());
+        assert_eq!(ladder.vwap(), None);
+        assert_eq!(ladder.slippage_bps(), None);
+    }
+}
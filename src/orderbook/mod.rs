@@ -1,5 +1,17 @@
+pub mod analytics;
+pub mod consolidated;
+pub mod integrity;
+pub mod l3;
+pub mod ladder;
 pub mod orderbook;
+pub mod persistence;
 pub mod types;
 
+pub use analytics::{BookMetricsConfig, BookMetricsStream};
+pub use consolidated::{ConsolidatedBook, Nbbo, VenueDepth, VenueQuote};
+pub use integrity::{BookChecksum, BookIntegrityError};
+pub use l3::{L3Order, L3OrderBook, QueuePosition};
+pub use ladder::{ExecutionLadder, LadderStep};
 pub use orderbook::OrderBook;
+pub use persistence::{BookPersistence, BookRecorder, RestoredBook};
 pub use types::{OrderBookDelta, OrderBookLevel, OrderBookSnapshot};
@@ -1,6 +1,8 @@
-use crate::core::events::{OrderBookDelta, OrderBookSnapshot};
+use crate::core::events::{MarketEvent, OrderBookDelta, OrderBookLevel, OrderBookSnapshot};
+use crate::orderbook::integrity::{BookChecksum, BookIntegrityError};
 use crate::types::{Price, Size};
 use smallvec::SmallVec;
+use std::borrow::Borrow;
 use std::collections::BTreeMap;
 
 /// OrderBook implementation using BTreeMap for efficient price level management
@@ -12,6 +14,11 @@ pub struct OrderBook {
     bids: BTreeMap<Price, Size>, // Descending order (reverse comparator)
     asks: BTreeMap<Price, Size>, // Ascending order
     last_update: u64,
+    stale: bool, // Restored from disk and not yet resynced with a live snapshot
+    /// Venue update ID of the last sequenced snapshot or delta applied
+    sequence: Option<u64>,
+    /// Failed integrity check, cleared by the next snapshot
+    desync: Option<BookIntegrityError>,
 }
 
 impl OrderBook {
@@ -22,6 +29,9 @@ impl OrderBook {
             bids: BTreeMap::new(),
             asks: BTreeMap::new(),
             last_update: 0,
+            stale: false,
+            sequence: None,
+            desync: None,
         }
     }
 
@@ -76,6 +86,16 @@ impl OrderBook {
         result
     }
 
+    /// Iterate over all bid levels, best (highest) price first
+    pub fn bids_iter(&self) -> impl Iterator<Item = (Price, Size)> + '_ {
+        self.bids.iter().rev().map(|(price, size)| (*price, *size))
+    }
+
+    /// Iterate over all ask levels, best (lowest) price first
+    pub fn asks_iter(&self) -> impl Iterator<Item = (Price, Size)> + '_ {
+        self.asks.iter().map(|(price, size)| (*price, *size))
+    }
+
     /// Apply a full snapshot to the order book
     /// This replaces the entire order book with the snapshot data
     pub fn apply_snapshot(&mut self, snapshot: OrderBookSnapshot) {
@@ -98,13 +118,121 @@ impl OrderBook {
         }
 
         self.last_update = snapshot.timestamp;
+        self.stale = false;
+        self.sequence = None;
+        self.desync = None;
+    }
+
+    /// Apply a snapshot taken at venue update ID `last_update_id`
+    /// Later deltas can then be checked for gaps with `apply_sequenced_delta`
+    pub fn apply_sequenced_snapshot(&mut self, snapshot: OrderBookSnapshot, last_update_id: u64) {
+        self.apply_snapshot(snapshot);
+        self.sequence = Some(last_update_id);
+    }
+
+    /// Apply a delta covering venue update IDs `first_update_id..=last_update_id`
+    ///
+    /// Deltas wholly at or before the book's sequence are dropped and return `Ok(false)`.
+    /// A delta starting after the next expected ID means updates were missed: the book is
+    /// marked desynced and the gap returned. This is Binance spot's `U`/`u` rule; venues
+    /// that send the previous update's ID instead (Binance futures `pu`, OKX `prevSeqId`)
+    /// pass that ID plus one as `first_update_id`.
+    pub fn apply_sequenced_delta(
+        &mut self,
+        delta: OrderBookDelta,
+        first_update_id: u64,
+        last_update_id: u64,
+    ) -> Result<bool, BookIntegrityError> {
+        let Some(sequence) = self.sequence else {
+            return Err(self.mark_desynced(BookIntegrityError::NoSnapshot));
+        };
+        if last_update_id <= sequence {
+            return Ok(false);
+        }
+        if first_update_id > sequence + 1 {
+            return Err(self.mark_desynced(BookIntegrityError::SequenceGap {
+                expected: sequence + 1,
+                received: first_update_id,
+            }));
+        }
+        self.apply_levels(&delta);
+        self.sequence = Some(last_update_id);
+        Ok(true)
+    }
+
+    /// Check the book against a checksum published by the venue
+    /// On a mismatch the book is marked desynced until the next snapshot
+    pub fn verify_checksum(
+        &mut self,
+        checksum: BookChecksum,
+        expected: u32,
+    ) -> Result<(), BookIntegrityError> {
+        let computed = checksum.compute(self);
+        if computed != expected {
+            return Err(
+                self.mark_desynced(BookIntegrityError::ChecksumMismatch { expected, computed })
+            );
+        }
+        Ok(())
+    }
+
+    /// Get the venue update ID the book is synced to, if sequenced
+    pub fn sequence(&self) -> Option<u64> {
+        self.sequence
+    }
+
+    /// Mark the book as out of sync with the venue until the next snapshot
+    /// Returns the error for convenience
+    pub fn mark_desynced(&mut self, error: BookIntegrityError) -> BookIntegrityError {
+        self.sequence = None;
+        self.desync = Some(error.clone());
+        error
+    }
+
+    /// Get the integrity check the book failed, if it is out of sync
+    pub fn desync(&self) -> Option<&BookIntegrityError> {
+        self.desync.as_ref()
+    }
+
+    /// Check if the book failed an integrity check and awaits a snapshot
+    pub fn is_desynced(&self) -> bool {
+        self.desync.is_some()
     }
 
     /// Apply a delta update to the order book
     /// This updates specific price levels
     pub fn apply_delta(&mut self, delta: OrderBookDelta) {
+        self.apply_levels(&delta);
+    }
+
+    /// Apply a run of deltas in order, as if each were passed to `apply_delta`
+    /// Lets readers holding the book behind a lock take it once per burst instead of per message
+    pub fn apply_batch<D: Borrow<OrderBookDelta>>(&mut self, deltas: &[D]) {
+        for delta in deltas {
+            self.apply_levels(delta.borrow());
+        }
+    }
+
+    /// Apply the book updates among a batch of market events
+    /// Consecutive deltas are applied as one batch; a snapshot replaces everything before it
+    pub fn apply_events<'a>(&mut self, events: impl IntoIterator<Item = &'a MarketEvent>) {
+        let mut deltas: SmallVec<[&OrderBookDelta; 16]> = SmallVec::new();
+        for event in events {
+            match event {
+                MarketEvent::OrderBookSnapshot(snapshot) => {
+                    deltas.clear();
+                    self.apply_snapshot(snapshot.clone());
+                }
+                MarketEvent::OrderBookDelta(delta) => deltas.push(delta),
+                _ => {}
+            }
+        }
+        self.apply_batch(&deltas);
+    }
+
+    fn apply_levels(&mut self, delta: &OrderBookDelta) {
         // Update bids
-        for level in delta.bids {
+        for level in &delta.bids {
             if level.size.is_zero() {
                 // Remove the price level if size is zero
                 self.bids.remove(&level.price);
@@ -115,7 +243,7 @@ impl OrderBook {
         }
 
         // Update asks
-        for level in delta.asks {
+        for level in &delta.asks {
             if level.size.is_zero() {
                 // Remove the price level if size is zero
                 self.asks.remove(&level.price);
@@ -128,10 +256,52 @@ impl OrderBook {
         self.last_update = delta.timestamp;
     }
 
+    /// Drop levels beyond the top `depth` on each side, as depth-limited feeds expect
+    /// Returns the removed bid and ask prices
+    pub fn truncate(&mut self, depth: usize) -> (Vec<Price>, Vec<Price>) {
+        let removed_bids: Vec<Price> = self.bids.keys().rev().skip(depth).copied().collect();
+        let removed_asks: Vec<Price> = self.asks.keys().skip(depth).copied().collect();
+        for price in &removed_bids {
+            self.bids.remove(price);
+        }
+        for price in &removed_asks {
+            self.asks.remove(price);
+        }
+        (removed_bids, removed_asks)
+    }
+
     /// Get the last update timestamp
     pub fn last_update(&self) -> u64 {
         self.last_update
     }
+
+    /// Check if the book may be stale (restored from disk, awaiting a live snapshot)
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Mark the book as potentially stale until the next snapshot is applied
+    pub fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    /// Export the full book as a snapshot
+    pub fn to_snapshot(&self, exchange_id: &str) -> OrderBookSnapshot {
+        OrderBookSnapshot::new(
+            self.symbol.clone(),
+            exchange_id,
+            self.bids
+                .iter()
+                .rev()
+                .map(|(price, size)| OrderBookLevel::new(*price, *size))
+                .collect(),
+            self.asks
+                .iter()
+                .map(|(price, size)| OrderBookLevel::new(*price, *size))
+                .collect(),
+            self.last_update,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -266,6 +436,70 @@ mod tests {
             .any(|(price, _)| *price == create_price("102.00")));
     }
 
+    #[test]
+    fn test_apply_batch_matches_sequential_deltas() {
+        let level =
+            |price: &str, size: &str| OrderBookLevel::new(create_price(price), create_size(size));
+        let delta = |bids, asks, timestamp| {
+            OrderBookDelta::new(
+                "BTCUSDT".to_string(),
+                "test".to_string(),
+                bids,
+                asks,
+                timestamp,
+            )
+        };
+        let deltas = vec![
+            delta(
+                vec![level("100.00", "1.0")],
+                vec![level("101.00", "2.0")],
+                1,
+            ),
+            delta(vec![level("100.50", "3.0")], vec![], 2),
+            // A level touched twice in one burst keeps its last size
+            delta(
+                vec![level("100.50", "0.0")],
+                vec![level("101.00", "4.0")],
+                3,
+            ),
+        ];
+
+        let mut sequential = OrderBook::new("BTCUSDT".to_string());
+        for d in &deltas {
+            sequential.apply_delta(d.clone());
+        }
+        let mut batched = OrderBook::new("BTCUSDT".to_string());
+        batched.apply_batch(&deltas);
+        assert_eq!(batched.top_bids(10), sequential.top_bids(10));
+        assert_eq!(batched.top_asks(10), sequential.top_asks(10));
+        assert_eq!(batched.last_update(), 3);
+
+        // Deltas before a snapshot in the same batch are superseded by it
+        let events = vec![
+            MarketEvent::OrderBookDelta(deltas[0].clone()),
+            MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+                "BTCUSDT".to_string(),
+                "test".to_string(),
+                vec![level("99.00", "5.0")],
+                vec![level("102.00", "6.0")],
+                4,
+            )),
+            MarketEvent::OrderBookDelta(deltas[1].clone()),
+        ];
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.apply_events(&events);
+        assert_eq!(
+            book.best_bid(),
+            Some((create_price("100.50"), create_size("3.0")))
+        );
+        assert_eq!(
+            book.best_ask(),
+            Some((create_price("102.00"), create_size("6.0")))
+        );
+        assert_eq!(book.top_bids(10).len(), 2);
+        assert_eq!(book.last_update(), 2);
+    }
+
     #[test]
     fn test_smallvec_optimization() {
         let mut book = OrderBook::new("BTCUSDT".to_string());
@@ -286,7 +520,13 @@ mod tests {
             ));
         }
 
-        let snapshot = OrderBookSnapshot::new("BTCUSDT".to_string(), "test".to_string(), bids, asks, 123456789);
+        let snapshot = OrderBookSnapshot::new(
+            "BTCUSDT".to_string(),
+            "test".to_string(),
+            bids,
+            asks,
+            123456789,
+        );
 
         book.apply_snapshot(snapshot);
 
@@ -301,4 +541,58 @@ mod tests {
         assert!(top_bids[0].0 > top_bids[1].0); // Bids should be descending
         assert!(top_asks[0].0 < top_asks[1].0); // Asks should be ascending
     }
+
+    #[test]
+    fn test_sequenced_deltas_detect_gaps() {
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        let delta = |price: &str, size: &str| {
+            OrderBookDelta::new(
+                "BTCUSDT",
+                "binance",
+                vec![OrderBookLevel::new(create_price(price), create_size(size))],
+                vec![],
+                0,
+            )
+        };
+
+        assert_eq!(
+            book.apply_sequenced_delta(delta("100", "1"), 1, 2),
+            Err(BookIntegrityError::NoSnapshot)
+        );
+
+        let snapshot = OrderBookSnapshot::new("BTCUSDT", "binance", vec![], vec![], 0);
+        book.apply_sequenced_snapshot(snapshot.clone(), 100);
+        assert!(!book.is_desynced());
+
+        // Buffered updates before the snapshot are dropped; the first may straddle it
+        assert_eq!(
+            book.apply_sequenced_delta(delta("99", "1"), 95, 100),
+            Ok(false)
+        );
+        assert_eq!(
+            book.apply_sequenced_delta(delta("100", "1"), 98, 103),
+            Ok(true)
+        );
+        assert_eq!(
+            book.apply_sequenced_delta(delta("101", "1"), 104, 104),
+            Ok(true)
+        );
+        assert_eq!(book.sequence(), Some(104));
+        assert_eq!(book.best_bid().unwrap().0, create_price("101"));
+
+        let gap = BookIntegrityError::SequenceGap {
+            expected: 105,
+            received: 107,
+        };
+        assert_eq!(
+            book.apply_sequenced_delta(delta("102", "1"), 107, 108),
+            Err(gap.clone())
+        );
+        assert_eq!(book.desync(), Some(&gap));
+        assert_eq!(book.best_bid().unwrap().0, create_price("101"));
+
+        book.apply_sequenced_snapshot(snapshot, 200);
+        assert!(!book.is_desynced());
+        assert_eq!(book.sequence(), Some(200));
+    }
 }
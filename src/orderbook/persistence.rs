@@ -0,0 +1,495 @@
+use crate::core::bus::EventBus;
+use crate::core::events::{MarketEvent, OrderBookDelta, OrderBookSnapshot};
+use crate::monitoring::supervisor::{Heartbeat, TaskSupervisor};
+use crate::orderbook::OrderBook;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Book restored from disk on startup
+#[derive(Debug, Clone)]
+pub struct RestoredBook {
+    /// Exchange identifier
+    pub exchange_id: String,
+    /// Restored order book (marked stale until a live snapshot arrives)
+    pub book: OrderBook,
+    /// Number of journaled deltas replayed on top of the snapshot
+    pub deltas_replayed: usize,
+}
+
+/// Persistence state of a single book
+struct BookJournal {
+    /// Timestamp of the last persisted snapshot
+    last_snapshot_time: u64,
+    /// Deltas journaled since the last snapshot
+    deltas_since_snapshot: usize,
+    /// Open delta journal
+    writer: Option<BufWriter<File>>,
+}
+
+/// Incremental order book persistence
+///
+/// Each book is stored as a periodic snapshot file plus an append-only journal of
+/// the deltas received since that snapshot. On restart, `load` replays the journal
+/// on top of the snapshot so a book can be served immediately (marked stale) while
+/// the live feed resyncs.
+pub struct BookPersistence {
+    /// Directory holding snapshot and journal files
+    dir: PathBuf,
+    /// Minimum time between snapshots in milliseconds
+    snapshot_interval_ms: u64,
+    /// Maximum journaled deltas before forcing a snapshot
+    max_deltas: usize,
+    /// Per-book journal state keyed by "exchange_symbol"
+    journals: HashMap<String, BookJournal>,
+}
+
+impl BookPersistence {
+    /// Create a new book persistence store in the given directory
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        snapshot_interval_ms: u64,
+        max_deltas: usize,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            snapshot_interval_ms,
+            max_deltas,
+            journals: HashMap::new(),
+        })
+    }
+
+    /// Get the storage directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn key(exchange_id: &str, symbol: &str) -> String {
+        format!("{}_{}", exchange_id, symbol)
+    }
+
+    fn snapshot_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.snapshot.json", key))
+    }
+
+    fn journal_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.deltas.jsonl", key))
+    }
+
+    /// Persist a full snapshot and truncate the delta journal
+    pub fn save_snapshot(&mut self, snapshot: &OrderBookSnapshot) -> io::Result<()> {
+        let key = Self::key(&snapshot.exchange_id, snapshot.symbol.as_str());
+        let path = self.snapshot_path(&key);
+
+        // Write to a temporary file and rename so a crash never leaves a torn snapshot
+        let tmp_path = path.with_extension("json.tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            serde_json::to_writer(&mut writer, snapshot)?;
+            writer.flush()?;
+        }
+        fs::rename(&tmp_path, &path)?;
+
+        let writer = BufWriter::new(File::create(self.journal_path(&key))?);
+        self.journals.insert(
+            key,
+            BookJournal {
+                last_snapshot_time: snapshot.timestamp,
+                deltas_since_snapshot: 0,
+                writer: Some(writer),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Journal a delta that has been applied to `book`
+    /// Writes a fresh snapshot of `book` when the snapshot interval or delta limit is reached
+    pub fn record_delta(&mut self, book: &OrderBook, delta: &OrderBookDelta) -> io::Result<()> {
+        let key = Self::key(&delta.exchange_id, delta.symbol.as_str());
+
+        let due = match self.journals.get(&key) {
+            Some(journal) => {
+                journal.deltas_since_snapshot >= self.max_deltas
+                    || delta.timestamp.saturating_sub(journal.last_snapshot_time)
+                        >= self.snapshot_interval_ms
+            }
+            None => true,
+        };
+
+        if due {
+            return self.save_snapshot(&book.to_snapshot(&delta.exchange_id));
+        }
+
+        let journal_path = self.journal_path(&key);
+        let journal = self.journals.get_mut(&key).expect("journal exists");
+        if journal.writer.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(journal_path)?;
+            journal.writer = Some(BufWriter::new(file));
+        }
+
+        let writer = journal.writer.as_mut().expect("writer opened");
+        serde_json::to_writer(&mut *writer, delta)?;
+        writer.write_all(b"\n")?;
+        journal.deltas_since_snapshot += 1;
+
+        Ok(())
+    }
+
+    /// Flush all open journals to disk
+    pub fn flush(&mut self) -> io::Result<()> {
+        for journal in self.journals.values_mut() {
+            if let Some(writer) = journal.writer.as_mut() {
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a persisted book, replaying journaled deltas on top of the snapshot
+    /// Returns None if no snapshot exists for the book
+    pub fn load(&self, exchange_id: &str, symbol: &str) -> io::Result<Option<RestoredBook>> {
+        let key = Self::key(exchange_id, symbol);
+        let snapshot_path = self.snapshot_path(&key);
+        if !snapshot_path.exists() {
+            return Ok(None);
+        }
+
+        let snapshot: OrderBookSnapshot =
+            serde_json::from_reader(BufReader::new(File::open(&snapshot_path)?))?;
+        let snapshot_time = snapshot.timestamp;
+
+        let mut book = OrderBook::new(snapshot.symbol.as_str().to_string());
+        book.apply_snapshot(snapshot);
+
+        let mut deltas_replayed = 0;
+        let journal_path = self.journal_path(&key);
+        if journal_path.exists() {
+            for line in BufReader::new(File::open(&journal_path)?).lines() {
+                let line = line?;
+                // A crash mid-write can leave a truncated last line
+                let Ok(delta) = serde_json::from_str::<OrderBookDelta>(&line) else {
+                    break;
+                };
+                if delta.timestamp >= snapshot_time {
+                    book.apply_delta(delta);
+                    deltas_replayed += 1;
+                }
+            }
+        }
+
+        book.mark_stale();
+
+        Ok(Some(RestoredBook {
+            exchange_id: exchange_id.to_string(),
+            book,
+            deltas_replayed,
+        }))
+    }
+
+    /// Load every persisted book in the storage directory
+    pub fn load_all(&self) -> io::Result<Vec<RestoredBook>> {
+        let mut books = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(key) = file_name.strip_suffix(".snapshot.json") else {
+                continue;
+            };
+
+            let snapshot: OrderBookSnapshot =
+                match serde_json::from_reader(BufReader::new(File::open(&path)?)) {
+                    Ok(snapshot) => snapshot,
+                    Err(e) => {
+                        log::warn!("Skipping unreadable book snapshot {}: {}", key, e);
+                        continue;
+                    }
+                };
+
+            if let Some(book) = self.load(&snapshot.exchange_id, snapshot.symbol.as_str())? {
+                books.push(book);
+            }
+        }
+        Ok(books)
+    }
+}
+
+impl Drop for BookPersistence {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Background task recording the books of the event bus's market data to disk
+///
+/// Snapshots are saved as they arrive and deltas are journaled against the book
+/// they apply to, which is restored from disk when the recorder (re)starts mid-feed.
+pub struct BookRecorder {
+    persistence: Mutex<BookPersistence>,
+    bus: EventBus,
+    flush_interval: Duration,
+}
+
+impl BookRecorder {
+    /// Create a recorder writing the bus's books to a persistence store
+    pub fn new(persistence: BookPersistence, bus: EventBus) -> Self {
+        Self {
+            persistence: Mutex::new(persistence),
+            bus,
+            flush_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the interval between journal flushes (builder pattern)
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    fn persistence(&self) -> std::sync::MutexGuard<'_, BookPersistence> {
+        self.persistence.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record one market event, keeping the books it applies to in `books`
+    fn record(
+        &self,
+        books: &mut HashMap<String, OrderBook>,
+        event: &MarketEvent,
+    ) -> io::Result<()> {
+        let mut persistence = self.persistence();
+        match event {
+            MarketEvent::OrderBookSnapshot(snapshot) => {
+                let key = BookPersistence::key(&snapshot.exchange_id, snapshot.symbol.as_str());
+                books
+                    .entry(key)
+                    .or_insert_with(|| OrderBook::new(snapshot.symbol.as_str().to_string()))
+                    .apply_snapshot(snapshot.clone());
+                persistence.save_snapshot(snapshot)
+            }
+            MarketEvent::OrderBookDelta(delta) => {
+                let key = BookPersistence::key(&delta.exchange_id, delta.symbol.as_str());
+                if !books.contains_key(&key) {
+                    // Deltas need a base: the saved book, or else wait for a snapshot
+                    let Some(restored) =
+                        persistence.load(&delta.exchange_id, delta.symbol.as_str())?
+                    else {
+                        return Ok(());
+                    };
+                    books.insert(key.clone(), restored.book);
+                }
+                let book = books.get_mut(&key).expect("book exists");
+                book.apply_delta(delta.clone());
+                persistence.record_delta(book, delta)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Record market data until the bus closes, flushing journals periodically
+    pub async fn run(self: Arc<Self>, heartbeat: Option<Heartbeat>) {
+        let mut events = self.bus.subscribe_market(None);
+        let mut books = HashMap::new();
+        let mut flush = tokio::time::interval(self.flush_interval);
+        loop {
+            if let Some(heartbeat) = &heartbeat {
+                heartbeat.beat();
+            }
+            tokio::select! {
+                event = events.recv() => {
+                    let Some(event) = event else {
+                        break;
+                    };
+                    if let Err(e) = self.record(&mut books, &event) {
+                        log::warn!("Recording {} book failed: {}", event.exchange_id(), e);
+                    }
+                }
+                _ = flush.tick() => {
+                    if let Err(e) = self.persistence().flush() {
+                        log::warn!("Flushing book journals failed: {}", e);
+                    }
+                }
+            }
+        }
+        if let Err(e) = self.persistence().flush() {
+            log::warn!("Flushing book journals failed: {}", e);
+        }
+    }
+
+    /// Run the recorder under a supervisor, which restarts it if it stops
+    /// heartbeating for ten flush intervals
+    pub async fn supervise(self: Arc<Self>, supervisor: &TaskSupervisor) {
+        let timeout = self.flush_interval * 10;
+        supervisor
+            .supervise("book_recorder", timeout, move |heartbeat| {
+                self.clone().run(Some(heartbeat))
+            })
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OrderBookLevel;
+    use crate::types::{Price, Size};
+
+    fn level(price: &str, size: &str) -> OrderBookLevel {
+        OrderBookLevel::new(
+            Price::from_str(price).unwrap(),
+            Size::from_str(size).unwrap(),
+        )
+    }
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("book_persistence_{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_restore_snapshot_and_deltas() {
+        let dir = temp_dir();
+        let mut persistence = BookPersistence::new(&dir, 60_000, 1000).unwrap();
+
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        let snapshot = OrderBookSnapshot::new(
+            "BTCUSDT",
+            "binance",
+            vec![level("100.0", "1.0")],
+            vec![level("101.0", "1.0")],
+            1_000,
+        );
+        book.apply_snapshot(snapshot.clone());
+        persistence.save_snapshot(&snapshot).unwrap();
+
+        let delta = OrderBookDelta::new(
+            "BTCUSDT",
+            "binance",
+            vec![level("100.5", "2.0")],
+            vec![level("101.0", "0")],
+            2_000,
+        );
+        book.apply_delta(delta.clone());
+        persistence.record_delta(&book, &delta).unwrap();
+        persistence.flush().unwrap();
+
+        let restored = persistence.load("binance", "BTCUSDT").unwrap().unwrap();
+        assert!(restored.book.is_stale());
+        assert_eq!(restored.deltas_replayed, 1);
+        assert_eq!(restored.book.best_bid(), book.best_bid());
+        assert_eq!(restored.book.best_ask(), None);
+        assert_eq!(restored.book.last_update(), 2_000);
+        assert!(persistence.load("binance", "ETHUSDT").unwrap().is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_periodic_snapshot_truncates_journal() {
+        let dir = temp_dir();
+        let mut persistence = BookPersistence::new(&dir, 60_000, 2).unwrap();
+
+        let mut book = OrderBook::new("ETHUSDT".to_string());
+        for i in 0..5u64 {
+            let delta = OrderBookDelta::new(
+                "ETHUSDT",
+                "okx",
+                vec![level(&format!("{}", 2000 + i), "1.0")],
+                vec![],
+                i,
+            );
+            book.apply_delta(delta.clone());
+            persistence.record_delta(&book, &delta).unwrap();
+        }
+        drop(persistence);
+
+        let persistence = BookPersistence::new(&dir, 60_000, 2).unwrap();
+        let restored = persistence.load_all().unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].exchange_id, "okx");
+        // Snapshot at delta 3 plus one journaled delta
+        assert_eq!(restored[0].deltas_replayed, 1);
+        assert_eq!(restored[0].book.top_bids(10).len(), 5);
+
+        // A live snapshot clears the stale flag
+        let mut book = restored[0].book.clone();
+        book.apply_snapshot(book.to_snapshot("okx"));
+        assert!(!book.is_stale());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_supervised_recorder_journals_bus_books() {
+        use crate::monitoring::alerts::AlertManager;
+
+        let dir = temp_dir();
+        let bus = EventBus::new(64);
+        let supervisor = TaskSupervisor::new(Arc::new(AlertManager::new(10)));
+        let recorder = Arc::new(
+            BookRecorder::new(
+                BookPersistence::new(&dir, 60_000, 1000).unwrap(),
+                bus.clone(),
+            )
+            .with_flush_interval(Duration::from_millis(10)),
+        );
+        recorder.clone().supervise(&supervisor).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // A delta before any snapshot has no base and is skipped
+        let delta = |price: &str, timestamp| {
+            OrderBookDelta::new(
+                "BTCUSDT",
+                "binance",
+                vec![level(price, "1.0")],
+                vec![],
+                timestamp,
+            )
+        };
+        bus.publish_market(MarketEvent::OrderBookDelta(delta("99.0", 500)));
+        bus.publish_market(MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+            "BTCUSDT",
+            "binance",
+            vec![level("100.0", "1.0")],
+            vec![level("101.0", "1.0")],
+            1_000,
+        )));
+        bus.publish_market(MarketEvent::OrderBookDelta(delta("100.5", 2_000)));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let restored = recorder
+            .persistence()
+            .load("binance", "BTCUSDT")
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.deltas_replayed, 1);
+        assert_eq!(restored.book.top_bids(10).len(), 2);
+
+        // A restarted recorder continues the saved book
+        supervisor.unsupervise("book_recorder").await;
+        recorder.clone().supervise(&supervisor).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        bus.publish_market(MarketEvent::OrderBookDelta(delta("100.7", 3_000)));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let restored = recorder
+            .persistence()
+            .load("binance", "BTCUSDT")
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.deltas_replayed, 2);
+        assert_eq!(restored.book.top_bids(10).len(), 3);
+        assert!(supervisor.check().await.is_empty());
+
+        supervisor.shutdown().await;
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,405 @@
+use crate::core::events::{MarketEvent, Timestamp};
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Letters kept before the oldest are dropped
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Handler attempts before an event is dead-lettered
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Decodes a raw venue message into a market event
+pub type EventParser = fn(&str) -> Result<MarketEvent, String>;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// What failed to be processed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeadLetterPayload {
+    /// A venue message that could not be parsed
+    Raw { text: String },
+    /// A parsed event whose handler kept failing
+    Event { event: MarketEvent },
+}
+
+/// An event that could not be processed, with the error that stopped it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub id: u64,
+    pub exchange_id: String,
+    pub payload: DeadLetterPayload,
+    /// Error from the last attempt
+    pub error: String,
+    /// Attempts so far, including re-drives
+    pub attempts: u32,
+    pub first_failed_at: Timestamp,
+    pub last_failed_at: Timestamp,
+}
+
+impl DeadLetter {
+    /// Check if the letter is an unparsed venue message
+    pub fn is_raw(&self) -> bool {
+        matches!(self.payload, DeadLetterPayload::Raw { .. })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    letters: BTreeMap<u64, DeadLetter>,
+    /// Letters an operator asked to re-drive, waiting for the event loop
+    redrive: VecDeque<DeadLetter>,
+    parsers: HashMap<String, EventParser>,
+    next_id: u64,
+    dropped: u64,
+}
+
+/// Dead letter queue for market events that fail parsing or handling
+///
+/// Instead of being dropped, failed messages are parked here with the error that
+/// stopped them, so an operator can inspect them and, after a fix, re-drive them
+/// through the event loop. Clones share state.
+#[derive(Debug, Clone)]
+pub struct DeadLetterQueue {
+    inner: Arc<RwLock<Inner>>,
+    capacity: usize,
+    max_attempts: u32,
+    path: Option<PathBuf>,
+}
+
+impl DeadLetterQueue {
+    /// Create an in-memory queue keeping the last 10,000 letters
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Inner {
+                next_id: 1,
+                ..Inner::default()
+            })),
+            capacity: DEFAULT_CAPACITY,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            path: None,
+        }
+    }
+
+    /// Create a queue persisted to a JSON file, loading any letters already in it
+    /// Letters survive a restart, so they can be re-driven by the fixed build.
+    pub fn with_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let queue = Self {
+            path: Some(path.clone()),
+            ..Self::new()
+        };
+        if path.exists() {
+            let letters: Vec<DeadLetter> = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            let mut inner = queue.inner.write().unwrap();
+            for letter in letters {
+                inner.next_id = inner.next_id.max(letter.id + 1);
+                inner.letters.insert(letter.id, letter);
+            }
+        }
+        Ok(queue)
+    }
+
+    /// Keep at most `capacity` letters, dropping the oldest (builder pattern)
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Attempts a handler gets before its event is dead-lettered (builder pattern)
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Get the handler attempts before an event is dead-lettered
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Register the parser used to re-drive raw messages from an exchange
+    pub fn register_parser(&self, exchange_id: impl Into<String>, parser: EventParser) {
+        self.inner
+            .write()
+            .unwrap()
+            .parsers
+            .insert(exchange_id.into(), parser);
+    }
+
+    /// Park a venue message that could not be parsed
+    pub fn record_parse_failure(
+        &self,
+        exchange_id: impl Into<String>,
+        text: impl Into<String>,
+        error: impl Into<String>,
+    ) -> u64 {
+        self.push(
+            exchange_id.into(),
+            DeadLetterPayload::Raw { text: text.into() },
+            error.into(),
+            1,
+        )
+    }
+
+    /// Park an event whose handler failed `attempts` times
+    pub fn record_handler_failure(
+        &self,
+        event: MarketEvent,
+        error: impl Into<String>,
+        attempts: u32,
+    ) -> u64 {
+        self.push(
+            event.exchange_id().to_string(),
+            DeadLetterPayload::Event { event },
+            error.into(),
+            attempts,
+        )
+    }
+
+    fn push(
+        &self,
+        exchange_id: String,
+        payload: DeadLetterPayload,
+        error: String,
+        attempts: u32,
+    ) -> u64 {
+        let now = now_ms();
+        let mut inner = self.inner.write().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        warn!(
+            "Dead-lettered message {} from {} after {} attempt(s): {}",
+            id, exchange_id, attempts, error
+        );
+        inner.letters.insert(
+            id,
+            DeadLetter {
+                id,
+                exchange_id,
+                payload,
+                error,
+                attempts,
+                first_failed_at: now,
+                last_failed_at: now,
+            },
+        );
+        self.evict(&mut inner);
+        self.persist(&inner);
+        id
+    }
+
+    fn evict(&self, inner: &mut Inner) {
+        while inner.letters.len() > self.capacity {
+            inner.letters.pop_first();
+            inner.dropped += 1;
+        }
+    }
+
+    fn persist(&self, inner: &Inner) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let letters: Vec<&DeadLetter> =
+            inner.letters.values().chain(inner.redrive.iter()).collect();
+        let result = serde_json::to_string(&letters)
+            .map_err(io::Error::from)
+            .and_then(|json| std::fs::write(path, json));
+        if let Err(e) = result {
+            error!("Failed to persist dead letters to {:?}: {}", path, e);
+        }
+    }
+
+    /// Get the parked letters, oldest first
+    pub fn letters(&self) -> Vec<DeadLetter> {
+        self.inner
+            .read()
+            .unwrap()
+            .letters
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Get a parked letter
+    pub fn get(&self, id: u64) -> Option<DeadLetter> {
+        self.inner.read().unwrap().letters.get(&id).cloned()
+    }
+
+    /// Number of parked letters
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().letters.len()
+    }
+
+    /// Check if no letters are parked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of letters dropped to stay within capacity
+    pub fn dropped(&self) -> u64 {
+        self.inner.read().unwrap().dropped
+    }
+
+    /// Delete letters, or all of them if `ids` is empty
+    /// Returns the number deleted
+    pub fn discard(&self, ids: &[u64]) -> usize {
+        let mut inner = self.inner.write().unwrap();
+        let before = inner.letters.len();
+        if ids.is_empty() {
+            inner.letters.clear();
+        } else {
+            for id in ids {
+                inner.letters.remove(id);
+            }
+        }
+        let discarded = before - inner.letters.len();
+        self.persist(&inner);
+        discarded
+    }
+
+    /// Queue letters, or all of them if `ids` is empty, for the event loop to re-drive
+    /// Returns the number queued
+    pub fn request_redrive(&self, ids: &[u64]) -> usize {
+        let mut inner = self.inner.write().unwrap();
+        let ids: Vec<u64> = if ids.is_empty() {
+            inner.letters.keys().copied().collect()
+        } else {
+            ids.to_vec()
+        };
+        let mut queued = 0;
+        for id in ids {
+            if let Some(letter) = inner.letters.remove(&id) {
+                inner.redrive.push_back(letter);
+                queued += 1;
+            }
+        }
+        queued
+    }
+
+    /// Take the letters waiting to be re-driven
+    pub fn take_redrive(&self) -> Vec<DeadLetter> {
+        let mut inner = self.inner.write().unwrap();
+        let letters: Vec<DeadLetter> = inner.redrive.drain(..).collect();
+        if !letters.is_empty() {
+            self.persist(&inner);
+        }
+        letters
+    }
+
+    /// Decode a letter back into a market event, re-parsing raw messages
+    pub fn decode(&self, letter: &DeadLetter) -> Result<MarketEvent, String> {
+        match &letter.payload {
+            DeadLetterPayload::Event { event } => Ok(event.clone()),
+            DeadLetterPayload::Raw { text } => {
+                let parser = self
+                    .inner
+                    .read()
+                    .unwrap()
+                    .parsers
+                    .get(&letter.exchange_id)
+                    .copied()
+                    .ok_or_else(|| format!("No parser registered for {}", letter.exchange_id))?;
+                parser(text)
+            }
+        }
+    }
+
+    /// Park a letter again after a failed re-drive, keeping its ID
+    pub fn requeue(&self, mut letter: DeadLetter, error: impl Into<String>) {
+        letter.error = error.into();
+        letter.attempts += 1;
+        letter.last_failed_at = now_ms();
+        warn!(
+            "Re-drive of dead letter {} failed: {}",
+            letter.id, letter.error
+        );
+        let mut inner = self.inner.write().unwrap();
+        inner.letters.insert(letter.id, letter);
+        self.evict(&mut inner);
+        self.persist(&inner);
+    }
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OrderBookSnapshot;
+
+    fn parse(text: &str) -> Result<MarketEvent, String> {
+        serde_json::from_str::<OrderBookSnapshot>(text)
+            .map(MarketEvent::OrderBookSnapshot)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_park_redrive_and_persist() {
+        let path = std::env::temp_dir().join(format!("dlq-test-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let queue = DeadLetterQueue::with_file(&path).unwrap().with_capacity(3);
+        queue.register_parser("binance", parse);
+
+        let snapshot = OrderBookSnapshot::new("BTCUSDT", "binance", vec![], vec![], 1);
+        let raw = queue.record_parse_failure("binance", "{not json", "expected value");
+        let handled = queue.record_handler_failure(
+            MarketEvent::OrderBookSnapshot(snapshot.clone()),
+            "order rejected",
+            3,
+        );
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.get(handled).unwrap().exchange_id, "binance");
+        assert!(queue.get(raw).unwrap().is_raw());
+
+        // Letters outlive the process
+        let reopened = DeadLetterQueue::with_file(&path).unwrap();
+        assert_eq!(reopened.letters(), queue.letters());
+
+        assert_eq!(queue.request_redrive(&[]), 2);
+        assert!(queue.is_empty());
+        let letters = queue.take_redrive();
+        assert_eq!(
+            queue.decode(&letters[1]),
+            Ok(MarketEvent::OrderBookSnapshot(snapshot))
+        );
+        let error = queue.decode(&letters[0]).unwrap_err();
+        queue.requeue(letters[0].clone(), error);
+        let letter = queue.get(raw).unwrap();
+        assert_eq!(letter.attempts, 2);
+
+        // Over capacity the oldest letters are dropped
+        for i in 0..3 {
+            queue.record_handler_failure(
+                MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+                    "ETHUSDT",
+                    "okx",
+                    vec![],
+                    vec![],
+                    i,
+                )),
+                "boom",
+                1,
+            );
+        }
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.dropped(), 1);
+        assert!(queue.get(raw).is_none());
+        assert_eq!(queue.discard(&[]), 3);
+        assert!(DeadLetterQueue::with_file(&path).unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -136,13 +136,31 @@ impl Default for RetryConfig {
     }
 }
 
+impl RetryConfig {
+    /// Delay before retrying after the zero-based `attempt` failed
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let mut delay = Duration::from_secs_f64(
+            (self.initial_delay.as_secs_f64() * self.multiplier.powi(attempt as i32))
+                .min(self.max_delay.as_secs_f64()),
+        );
+        if self.jitter {
+            // Add jitter to prevent thundering herd
+            use std::collections::hash_map::DefaultHasher;
+            use std::hash::{Hash, Hasher};
+            let mut hasher = DefaultHasher::new();
+            attempt.hash(&mut hasher);
+            let jitter_ms = hasher.finish() % 100;
+            delay += Duration::from_millis(jitter_ms);
+        }
+        delay.min(self.max_delay)
+    }
+}
+
 /// Retry helper with exponential backoff
 pub async fn retry_with_backoff<F, T, E>(config: &RetryConfig, mut operation: F) -> Result<T, E>
 where
     F: FnMut() -> Result<T, E>,
 {
-    let mut delay = config.initial_delay;
-
     for attempt in 0..config.max_attempts {
         match operation() {
             Ok(result) => return Ok(result),
@@ -151,19 +169,7 @@ where
                     return Err(e);
                 }
 
-                // Calculate next delay with exponential backoff
-                let mut next_delay = delay;
-                if config.jitter {
-                    // Add jitter to prevent thundering herd
-                    use std::collections::hash_map::DefaultHasher;
-                    use std::hash::{Hash, Hasher};
-                    let mut hasher = DefaultHasher::new();
-                    attempt.hash(&mut hasher);
-                    let jitter_ms = (hasher.finish() % 100) as u64;
-                    next_delay += Duration::from_millis(jitter_ms);
-                }
-
-                next_delay = next_delay.min(config.max_delay);
+                let next_delay = config.delay_for_attempt(attempt);
 
                 warn!(
                     "Operation failed (attempt {}/{}), retrying in {:?}",
@@ -173,11 +179,6 @@ where
                 );
 
                 tokio::time::sleep(next_delay).await;
-
-                // Increase delay for next attempt
-                delay = Duration::from_secs_f64(
-                    (delay.as_secs_f64() * config.multiplier).min(config.max_delay.as_secs_f64()),
-                );
             }
         }
     }
@@ -1,12 +1,18 @@
+use crate::connectors::DryRunExecutionClient;
+use crate::core::EventBus;
+use crate::monitoring::{Heartbeat, LagMonitor};
 use crate::oms::{OrderManager, RateLimiter};
-use crate::realtime::{OrderExecutor, PerformanceMonitor, RiskManager, SignalGenerator};
+use crate::realtime::{
+    DeadLetterQueue, LatencyStage, OrderExecutor, PerformanceMonitor, RiskManager, SignalGenerator,
+};
 use crate::risk::RiskEngine;
-use crate::strategy::{Signal, Strategy, StrategyEngine};
-use crate::traits::{ExecutionClient, MarketDataStream};
+use crate::strategy::{ScheduledSignal, Signal, Strategy, StrategyEngine, StrategyScheduler};
+use crate::traits::{ExecutionClient, MarketDataStream, MarketEvent, StrategyConfig, TradingEvent};
 use log::{debug, error, info, warn};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::{sleep, Duration, Instant};
+use tracing::info_span;
 
 /// Event loop configuration
 #[derive(Debug, Clone)]
@@ -79,6 +85,18 @@ where
     consecutive_errors: Arc<RwLock<u32>>,
     /// Last performance report time
     last_performance_report: Arc<RwLock<Instant>>,
+    /// Message lag monitor (optional)
+    lag_monitor: Option<Arc<LagMonitor>>,
+    /// Supervisor heartbeat, beaten once per loop iteration (optional)
+    heartbeat: Option<Heartbeat>,
+    /// Where events that keep failing their handler are parked (optional)
+    dead_letters: Option<DeadLetterQueue>,
+    /// Bus market data, orders and order updates are published on (optional)
+    event_bus: Option<EventBus>,
+    /// Further strategies run alongside the main one (optional)
+    strategy_scheduler: Option<Arc<RwLock<StrategyScheduler>>>,
+    /// Dry-run client whose paper orders are matched against the market data (optional)
+    paper_trading: Option<Arc<DryRunExecutionClient>>,
 }
 
 impl<S> EventLoop<S>
@@ -132,6 +150,87 @@ where
             running: Arc::new(RwLock::new(false)),
             consecutive_errors: Arc::new(RwLock::new(0)),
             last_performance_report: Arc::new(RwLock::new(Instant::now())),
+            lag_monitor: None,
+            heartbeat: None,
+            dead_letters: None,
+            event_bus: None,
+            strategy_scheduler: None,
+            paper_trading: None,
+        }
+    }
+
+    /// Measure message lag and pause signals from venues with critical lag (builder pattern)
+    pub fn with_lag_monitor(mut self, lag_monitor: Arc<LagMonitor>) -> Self {
+        self.lag_monitor = Some(lag_monitor);
+        self
+    }
+
+    /// Beat a supervisor heartbeat on every loop iteration (builder pattern)
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
+    /// Retry failing events and park them in a dead letter queue instead of
+    /// stopping on the error; re-drives requested on the queue are replayed (builder pattern)
+    pub fn with_dead_letters(mut self, dead_letters: DeadLetterQueue) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Publish market data, created orders and order updates on a bus (builder pattern)
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Match the paper orders of a dry-run client against the market data, and feed
+    /// their reports and fills to the order manager and shadow ledger (builder pattern)
+    /// Only orders placed through this client are matched.
+    pub fn with_paper_trading(mut self, client: Arc<DryRunExecutionClient>) -> Self {
+        self.paper_trading = Some(client);
+        self
+    }
+
+    /// Run further strategies alongside the main one (builder pattern)
+    /// A signal of one scheduled strategy that fails to execute is logged without stopping
+    /// the loop or the other strategies.
+    pub fn with_strategy_scheduler(mut self, scheduler: StrategyScheduler) -> Self {
+        self.strategy_scheduler = Some(Arc::new(RwLock::new(scheduler)));
+        self
+    }
+
+    /// Get the strategy scheduler, e.g. to register or re-enable strategies while running
+    pub fn strategy_scheduler(&self) -> Option<Arc<RwLock<StrategyScheduler>>> {
+        self.strategy_scheduler.clone()
+    }
+
+    /// Get the main strategy's engine, e.g. to register it with the control API
+    pub fn strategy_engine(&self) -> Arc<RwLock<StrategyEngine<S>>> {
+        self.strategy_engine.clone()
+    }
+
+    /// Change the main strategy's parameters while running, keeping its state
+    pub async fn update_strategy_config(&self, config: StrategyConfig) -> Result<(), String> {
+        let parameters = format!("{:?}", config.parameters);
+        self.strategy_engine.write().await.update_config(config)?;
+        info!("Updated strategy parameters: {}", parameters);
+        Ok(())
+    }
+
+    /// Execute the signals of scheduled strategies, isolating their failures
+    async fn process_scheduled_signals(
+        &self,
+        signals: Vec<ScheduledSignal>,
+        ingested_at: Option<std::time::Instant>,
+    ) {
+        for scheduled in signals {
+            if let Err(e) = self.execute_signal(scheduled.signal, ingested_at).await {
+                error!(
+                    "Error processing signal of strategy {}: {}",
+                    scheduled.strategy_id, e
+                );
+            }
         }
     }
 
@@ -153,6 +252,10 @@ where
         let mut last_order_check = Instant::now();
 
         while self.is_running().await {
+            if let Some(heartbeat) = &self.heartbeat {
+                heartbeat.beat();
+            }
+
             // Process market data events
             if let Err(e) = self.process_market_data().await {
                 error!("Error processing market data: {}", e);
@@ -264,27 +367,14 @@ where
     pub async fn process_market_data(
         &self,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.redrive_dead_letters().await;
+
         // Process all available market data events
         let mut stream = self.market_stream.write().await;
         while let Some(event_result) = stream.next().await {
             match event_result {
                 Ok(event) => {
-                    // Record market data event
-                    self.performance_monitor.record_market_data_event().await;
-
-                    // Update strategy with market data
-                    {
-                        let mut strategy_engine = self.strategy_engine.write().await;
-                        let signal = strategy_engine.process_event(event);
-
-                        // Process signal if generated
-                        if let Some(signal) = signal {
-                            if let Err(e) = self.process_signal(signal).await {
-                                error!("Error processing signal: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
+                    self.handle_market_event(event).await?;
                 }
                 Err(e) => {
                     error!("Market data stream error: {}", e);
@@ -296,13 +386,162 @@ where
         Ok(())
     }
 
+    /// Process an event, parking it in the dead letter queue if it keeps failing
+    async fn handle_market_event(
+        &self,
+        event: MarketEvent,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(dead_letters) = &self.dead_letters else {
+            return self.process_market_event(event).await;
+        };
+        let max_attempts = dead_letters.max_attempts();
+        let mut attempt = 1;
+        loop {
+            match self.process_market_event(event.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= max_attempts => {
+                    dead_letters.record_handler_failure(event, e.to_string(), attempt);
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!("Event handler failed (attempt {}): {}", attempt, e);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Replay dead letters an operator asked to re-drive
+    /// Letters that fail again go back to the queue with the new error
+    pub async fn redrive_dead_letters(&self) -> usize {
+        let Some(dead_letters) = &self.dead_letters else {
+            return 0;
+        };
+        let mut redriven = 0;
+        for letter in dead_letters.take_redrive() {
+            let result = match dead_letters.decode(&letter) {
+                Ok(event) => self
+                    .process_market_event(event)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => {
+                    info!("Re-drove dead letter {}", letter.id);
+                    redriven += 1;
+                }
+                Err(e) => dead_letters.requeue(letter, e),
+            }
+        }
+        redriven
+    }
+
+    /// Process one market data event: strategy update, then any signal it produces
+    /// The span is the root of the lifecycle of every order the event leads to
+    #[tracing::instrument(
+        name = "market_event",
+        skip_all,
+        fields(exchange = event.exchange_id(), symbol = event.symbol(), timestamp = event.timestamp())
+    )]
+    async fn process_market_event(
+        &self,
+        event: MarketEvent,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Stamp ingestion for tick-to-trade latency, then record the event
+        let ingested_at = std::time::Instant::now();
+        self.performance_monitor.record_market_data_event().await;
+        crate::sampled_debug!(event.exchange_id(), "Market event: {:?}", event);
+        if let Some(bus) = &self.event_bus {
+            bus.publish_market(event.clone());
+        }
+
+        // Measure message lag before the event is processed
+        let paused = match &self.lag_monitor {
+            Some(lag_monitor) => {
+                lag_monitor.record_event(&event).await;
+                lag_monitor.should_pause(event.exchange_id()).await
+            }
+            None => false,
+        };
+
+        // Fill paper orders the event reaches before strategies react to it
+        if let Some(paper_trading) = &self.paper_trading {
+            for execution in paper_trading.on_market_event(&event).await {
+                if let Err(e) = self.order_executor.process_paper_execution(&execution).await {
+                    error!("Failed to process paper execution: {}", e);
+                }
+                if let Some(bus) = &self.event_bus {
+                    bus.publish_trading(TradingEvent::ExecutionReport(execution.report));
+                }
+            }
+        }
+
+        // Feed the order flow indicators before strategies quote on the event
+        self.signal_generator.record_market_event(&event).await;
+
+        // Update the scheduled strategies, then the main one, with market data
+        let scheduled = match &self.strategy_scheduler {
+            Some(scheduler) => {
+                let mut scheduler = scheduler.write().await;
+                info_span!("strategy").in_scope(|| scheduler.process_event(&event))
+            }
+            None => Vec::new(),
+        };
+        let mut strategy_engine = self.strategy_engine.write().await;
+        let signal = info_span!("strategy").in_scope(|| strategy_engine.process_event(event));
+        drop(strategy_engine);
+        if signal.is_some() || !scheduled.is_empty() {
+            self.performance_monitor
+                .record_stage(LatencyStage::Signal, ingested_at)
+                .await;
+        }
+
+        // Drop signals while the venue's feed is too stale to trade on
+        let (signal, scheduled) = if paused {
+            if signal.is_some() || !scheduled.is_empty() {
+                warn!("Dropping signal: market data lag is critical");
+            }
+            (None, Vec::new())
+        } else {
+            (signal, scheduled)
+        };
+        self.process_scheduled_signals(scheduled, Some(ingested_at))
+            .await;
+
+        // Process signal if generated
+        if let Some(signal) = signal {
+            if let Err(e) = self.execute_signal(signal, Some(ingested_at)).await {
+                error!("Error processing signal: {}", e);
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Process a trading signal
     pub async fn process_signal(
         &self,
         signal: Signal,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.execute_signal(signal, None).await
+    }
+
+    /// Process a trading signal, timing its stages from the market event's ingestion
+    #[tracing::instrument(name = "signal", skip_all)]
+    async fn execute_signal(
+        &self,
+        signal: Signal,
+        ingested_at: Option<std::time::Instant>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         debug!("Processing signal: {:?}", signal);
 
+        // Hold back resting quotes that toxic flow would run over
+        let Some(signal) = self.signal_generator.filter_signal(signal).await else {
+            return Ok(());
+        };
+
         // Record signal
         self.performance_monitor.record_signal().await;
 
@@ -329,13 +568,27 @@ where
                         debug!("Risk check passed for order: {:?}", order);
                     }
                 }
+                if let Some(ingested_at) = ingested_at {
+                    self.performance_monitor
+                        .record_stage(LatencyStage::RiskCheck, ingested_at)
+                        .await;
+                }
 
                 // Execute order
-                if let Err(e) = self.order_executor.execute_order(order).await {
+                if let Err(e) = self.order_executor.execute_order(order.clone()).await {
                     error!("Failed to execute order: {}", e);
                     self.performance_monitor.record_order_failure().await;
                     return Err(e);
                 }
+                risk_engine.record_order_submission(&order);
+                if let Some(ingested_at) = ingested_at {
+                    self.performance_monitor
+                        .record_stage(LatencyStage::OrderSubmit, ingested_at)
+                        .await;
+                }
+                if let Some(bus) = &self.event_bus {
+                    bus.publish_trading(TradingEvent::OrderCreated(order));
+                }
             }
         }
 
@@ -350,8 +603,15 @@ where
         // Generate signals from strategy
         let signals = {
             let mut strategy_engine = self.strategy_engine.write().await;
-            strategy_engine.generate_signals()
+            info_span!("strategy").in_scope(|| strategy_engine.generate_signals())
         };
+        if let Some(scheduler) = &self.strategy_scheduler {
+            let scheduled = {
+                let mut scheduler = scheduler.write().await;
+                info_span!("strategy").in_scope(|| scheduler.generate_signals())
+            };
+            self.process_scheduled_signals(scheduled, None).await;
+        }
 
         // Process each signal
         for signal in signals {
@@ -405,6 +665,9 @@ where
                     error!("Failed to update order manager: {}", e);
                     return Err(e); // Error is already Box<dyn Error>
                 }
+                if let Some(bus) = &self.event_bus {
+                    bus.publish_trading(TradingEvent::ExecutionReport(current_status));
+                }
             }
         }
 
@@ -476,7 +739,7 @@ mod tests {
     }
 
     // Full EventLoop integration tests require proper setup with trait objects
-    // which is complex. The individual component tests (SignalGenerator, 
+    // which is complex. The individual component tests (SignalGenerator,
     // OrderExecutor, RiskManager, PerformanceMonitor) provide coverage
     // for the main functionality.
 }
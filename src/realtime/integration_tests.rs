@@ -278,6 +278,7 @@ impl MockExecutionClient {
                 timestamp: std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .as_millis() as u64,
+                reject_reason: None,
             };
             
             self.orders.push(execution_report);
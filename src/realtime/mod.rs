@@ -1,3 +1,4 @@
+pub mod dead_letter;
 pub mod error_recovery;
 pub mod event_loop;
 pub mod order_executor;
@@ -5,9 +6,12 @@ pub mod performance_monitor;
 pub mod risk_manager;
 pub mod signal_generator;
 
+pub use dead_letter::{DeadLetter, DeadLetterPayload, DeadLetterQueue, EventParser};
 pub use error_recovery::{retry_with_backoff, CircuitBreaker, CircuitState, RetryConfig};
 pub use event_loop::EventLoop;
-pub use order_executor::OrderExecutor;
-pub use performance_monitor::{PerformanceMonitor, PerformanceMonitorImpl};
-pub use risk_manager::RiskManager;
+pub use order_executor::{FlattenAllReport, OrderExecutor, PositionFlatten, SessionWatchdogConfig};
+pub use performance_monitor::{
+    LatencyHistogram, LatencyStage, PerformanceMonitor, PerformanceMonitorImpl,
+};
+pub use risk_manager::{RiskCheckedOrder, RiskManager};
 pub use signal_generator::SignalGenerator;
@@ -1,12 +1,26 @@
-use crate::oms::{OrderManager, RateLimiter};
-use crate::risk::ShadowLedger;
-use crate::traits::{ExecutionClient, ExecutionReport, NewOrder, OrderId, OrderStatus};
+use crate::backtest::SimulatedFill;
+use crate::connectors::PaperExecution;
+use crate::core::clock::{system_clock, SharedClock};
+use crate::core::events::SystemEvent;
+use crate::core::reject::ExchangeReject;
+use crate::oms::{
+    JournalEvent, OrderJournal, OrderManager, RateLimiter, ThrottleError, VenueThrottleRegistry,
+};
+use crate::risk::{KillSwitch, ShadowLedger};
+use crate::traits::{
+    ExecutionClient, ExecutionReport, NewOrder, OrderId, OrderStatus, OrderType, TimeInForce,
+};
+use crate::types::{Price, Size};
 use log::{debug, error, info, warn};
 use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{info_span, Instrument};
 
 /// Order executor configuration
 #[derive(Debug, Clone)]
@@ -38,6 +52,54 @@ impl Default for OrderExecutorConfig {
     }
 }
 
+/// Local cancel-on-disconnect watchdog settings
+#[derive(Debug, Clone)]
+pub struct SessionWatchdogConfig {
+    /// How often the exchange is sent a heartbeat
+    pub heartbeat_interval: Duration,
+    /// Time without a successful heartbeat after which resting orders are cancelled
+    pub timeout: Duration,
+}
+
+impl Default for SessionWatchdogConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(15),
+        }
+    }
+}
+
+/// Outcome of closing one position
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionFlatten {
+    pub symbol: String,
+    pub exchange_id: String,
+    /// Position being closed, positive for long
+    pub size: Size,
+    /// Closing order, if it was placed
+    pub order_id: Option<OrderId>,
+    pub error: Option<String>,
+}
+
+/// Outcome of flattening every position
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlattenAllReport {
+    /// Open orders cancelled before closing positions
+    pub cancelled: usize,
+    /// Errors encountered while cancelling
+    pub cancel_failures: Vec<String>,
+    /// Each position closed, by symbol and exchange
+    pub positions: Vec<PositionFlatten>,
+}
+
+impl FlattenAllReport {
+    /// Check if every order was cancelled and every closing order placed
+    pub fn is_complete(&self) -> bool {
+        self.cancel_failures.is_empty() && self.positions.iter().all(|p| p.error.is_none())
+    }
+}
+
 /// Order executor for placing and managing orders
 pub struct OrderExecutor {
     /// Configuration
@@ -57,6 +119,37 @@ pub struct OrderExecutor {
     pending_orders: Arc<RwLock<HashMap<String, PendingOrder>>>,
     /// Order execution attempts by order ID
     order_attempts: Arc<RwLock<HashMap<String, u32>>>,
+    /// Shared per-venue throttle and the strategy ID this executor acts for
+    venue_throttle: Option<(Arc<VenueThrottleRegistry>, String)>,
+    /// Latest price by symbol, used to size quote-sized market orders
+    reference_prices: Arc<RwLock<HashMap<String, Price>>>,
+    /// Global halt that stops new orders
+    kill_switch: Option<KillSwitch>,
+    /// Synthetic icebergs by the order ID of their visible slice
+    icebergs: Arc<RwLock<HashMap<OrderId, SyntheticIceberg>>>,
+    /// Orders as sent, by order ID, so they can be amended
+    placed_orders: Arc<RwLock<HashMap<OrderId, NewOrder>>>,
+    /// Durable record of every order intent, submission and report
+    journal: Option<Arc<OrderJournal>>,
+    /// Heartbeat settings of the local cancel-on-disconnect watchdog
+    session_watchdog: SessionWatchdogConfig,
+    /// When the exchange last answered a heartbeat
+    last_heartbeat: Arc<RwLock<Instant>>,
+    /// Maximum slippage from the reference price accepted by flattening orders
+    flatten_slippage: Decimal,
+    /// Time source for order timeouts, retries and heartbeats
+    clock: SharedClock,
+}
+
+/// Iceberg order worked by the executor on a venue without native support
+/// Each slice is a plain limit order; the next one is sent when it fills.
+#[derive(Debug, Clone)]
+struct SyntheticIceberg {
+    /// Parent order, sized to the quantity not yet sent
+    order: NewOrder,
+    display_size: Size,
+    /// Slices sent so far
+    slices: u32,
 }
 
 /// Pending order information
@@ -73,25 +166,25 @@ struct PendingOrder {
 }
 
 impl PendingOrder {
-    /// Create a new pending order
-    pub fn new(order: NewOrder) -> Self {
+    /// Create a new pending order created at a time
+    pub fn new(order: NewOrder, now: Instant) -> Self {
         Self {
             order,
-            created_at: Instant::now(),
+            created_at: now,
             last_retry_at: None,
             retry_count: 0,
         }
     }
 
-    /// Check if the order has timed out
-    pub fn is_timed_out(&self, timeout: Duration) -> bool {
-        self.created_at.elapsed() > timeout
+    /// Check if the order has timed out by a time
+    pub fn is_timed_out(&self, timeout: Duration, now: Instant) -> bool {
+        now.saturating_duration_since(self.created_at) > timeout
     }
 
     /// Increment retry count and update last retry time
-    pub fn increment_retry(&mut self) {
+    pub fn increment_retry(&mut self, now: Instant) {
         self.retry_count += 1;
-        self.last_retry_at = Some(Instant::now());
+        self.last_retry_at = Some(now);
     }
 
     /// Check if the order should be retried
@@ -115,6 +208,7 @@ impl OrderExecutor {
         rate_limiter: Arc<RateLimiter>,
         shadow_ledger: Arc<ShadowLedger>,
     ) -> Self {
+        let clock = system_clock();
         Self {
             config,
             execution_client,
@@ -123,15 +217,160 @@ impl OrderExecutor {
             shadow_ledger,
             pending_orders: Arc::new(RwLock::new(HashMap::new())),
             order_attempts: Arc::new(RwLock::new(HashMap::new())),
+            venue_throttle: None,
+            reference_prices: Arc::new(RwLock::new(HashMap::new())),
+            kill_switch: None,
+            icebergs: Arc::new(RwLock::new(HashMap::new())),
+            placed_orders: Arc::new(RwLock::new(HashMap::new())),
+            journal: None,
+            session_watchdog: SessionWatchdogConfig::default(),
+            last_heartbeat: Arc::new(RwLock::new(clock.now_instant())),
+            flatten_slippage: Decimal::new(5, 3),
+            clock,
+        }
+    }
+
+    /// Set the time source (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.last_heartbeat = Arc::new(RwLock::new(clock.now_instant()));
+        self.clock = clock;
+        self
+    }
+
+    /// Reject new orders while a kill switch is tripped (builder pattern)
+    pub fn with_kill_switch(mut self, kill_switch: KillSwitch) -> Self {
+        self.kill_switch = Some(kill_switch);
+        self
+    }
+
+    /// Record the order lifecycle in a journal (builder pattern)
+    pub fn with_journal(mut self, journal: Arc<OrderJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Set the maximum slippage from the reference price for flattening orders,
+    /// as a fraction, 0.5% by default (builder pattern)
+    pub fn with_flatten_slippage(mut self, max_slippage: Decimal) -> Self {
+        self.flatten_slippage = max_slippage;
+        self
+    }
+
+    /// Set the heartbeat settings of the session watchdog (builder pattern)
+    pub fn with_session_watchdog(mut self, config: SessionWatchdogConfig) -> Self {
+        self.session_watchdog = config;
+        self
+    }
+
+    /// Append an event to the journal, if configured
+    /// A failed write is logged rather than failing the order.
+    fn journal(&self, event: JournalEvent) {
+        if let Some(journal) = &self.journal {
+            if let Err(e) = journal.record(event) {
+                error!("Failed to write order journal entry: {}", e);
+            }
+        }
+    }
+
+    /// Consult a shared per-venue throttle before sending orders (builder pattern)
+    pub fn with_venue_throttle(
+        mut self,
+        registry: Arc<VenueThrottleRegistry>,
+        strategy_id: impl Into<String>,
+    ) -> Self {
+        self.venue_throttle = Some((registry, strategy_id.into()));
+        self
+    }
+
+    /// Update the price quote-sized orders are converted at when they have no limit price
+    pub async fn update_reference_price(&self, symbol: &str, price: Price) {
+        self.reference_prices
+            .write()
+            .await
+            .insert(symbol.to_string(), price);
+    }
+
+    /// Convert a quote-sized order to a base size if the venue cannot take it natively
+    async fn resolve_quote_size(
+        &self,
+        order: NewOrder,
+    ) -> Result<NewOrder, Box<dyn std::error::Error + Send + Sync>> {
+        if order.quote_size.is_none() || self.execution_client.supports_quote_size(order.order_type)
+        {
+            return Ok(order);
+        }
+
+        let price = match order.price {
+            Some(price) => price,
+            None => self
+                .reference_prices
+                .read()
+                .await
+                .get(order.symbol.as_str())
+                .copied()
+                .ok_or_else(|| format!("No reference price to size {} order", order.symbol))?,
+        };
+        let resolved = order
+            .with_base_size_at(price, None)
+            .ok_or_else(|| format!("Cannot size {} order at price {}", order.symbol, price))?;
+        debug!(
+            "Converted {:?} {} to {} at {}",
+            order.quote_size, order.symbol, resolved.size, price
+        );
+        Ok(resolved)
+    }
+
+    /// Wait for the shared venue allowance, if configured
+    async fn wait_for_venue_slot(&self, exchange_id: &str) -> Result<(), ThrottleError> {
+        match &self.venue_throttle {
+            Some((registry, strategy_id)) => registry.wait_for_slot(exchange_id, strategy_id).await,
+            None => Ok(()),
         }
     }
 
     /// Execute an order
+    #[tracing::instrument(
+        name = "order.execute",
+        skip_all,
+        fields(
+            symbol = %order.symbol,
+            side = ?order.side,
+            size = %order.size,
+            client_order_id = ?order.client_order_id,
+            order_id = tracing::field::Empty,
+        )
+    )]
     pub async fn execute_order(
         &self,
         order: NewOrder,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Executing order: {:?}", order);
+        self.journal(JournalEvent::Intent {
+            order: order.clone(),
+        });
+        if let Some(halt) = self.kill_switch.as_ref().and_then(KillSwitch::status) {
+            warn!("Rejecting order for {}: trading halted", order.symbol);
+            self.journal(JournalEvent::SubmitFailed {
+                order,
+                reason: format!("Trading halted: {}", halt.reason),
+            });
+            return Err(format!("Trading halted: {}", halt.reason).into());
+        }
+        let order = self
+            .resolve_quote_size(order.clone())
+            .await
+            .inspect_err(|e| {
+                self.journal(JournalEvent::SubmitFailed {
+                    order,
+                    reason: e.to_string(),
+                })
+            })?;
+
+        if let Some(display_size) = order.display_size() {
+            if !self.execution_client.supports_iceberg() {
+                return self.execute_synthetic_iceberg(order, display_size).await;
+            }
+        }
 
         // Check if order should be split
         if self.config.enable_order_splitting && order.size > self.config.max_order_size {
@@ -140,16 +379,26 @@ impl OrderExecutor {
 
         // Apply rate limiting
         self.rate_limiter.wait_for_slot().await;
+        self.wait_for_venue_slot(&order.exchange_id).await?;
 
         // Place the order
         let order_id = self
             .execution_client
             .place_order(order.clone())
+            .instrument(info_span!("exchange.place_order", exchange = %order.exchange_id))
             .await
             .map_err(|e| {
                 error!("Failed to place order: {}", e);
+                self.journal(JournalEvent::SubmitFailed {
+                    order: order.clone(),
+                    reason: e.to_string(),
+                });
                 e // Error is already Box<dyn Error>
             })?;
+        self.journal(JournalEvent::Submitted {
+            order: order.clone(),
+            order_id: order_id.clone(),
+        });
 
         // Add to pending orders
         self.add_pending_order(&order, order_id.clone()).await;
@@ -157,6 +406,7 @@ impl OrderExecutor {
         // Record order attempt
         self.record_order_attempt(&order_id).await;
 
+        tracing::Span::current().record("order_id", order_id.as_str());
         info!("Order placed with ID: {}", &order_id);
 
         Ok(())
@@ -196,22 +446,41 @@ impl OrderExecutor {
     }
 
     /// Execute a single order (without splitting)
+    #[tracing::instrument(
+        name = "order.child",
+        skip_all,
+        fields(
+            size = %order.size,
+            client_order_id = ?order.client_order_id,
+            order_id = tracing::field::Empty,
+        )
+    )]
     async fn execute_single_order(
         &self,
         order: NewOrder,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
         // Apply rate limiting
         self.rate_limiter.wait_for_slot().await;
+        self.wait_for_venue_slot(&order.exchange_id).await?;
 
         // Place the order
         let order_id = self
             .execution_client
             .place_order(order.clone())
+            .instrument(info_span!("exchange.place_order", exchange = %order.exchange_id))
             .await
             .map_err(|e| {
                 error!("Failed to place order: {}", e);
+                self.journal(JournalEvent::SubmitFailed {
+                    order: order.clone(),
+                    reason: e.to_string(),
+                });
                 e // Error is already Box<dyn Error>
             })?;
+        self.journal(JournalEvent::Submitted {
+            order: order.clone(),
+            order_id: order_id.clone(),
+        });
 
         // Add to pending orders
         self.add_pending_order(&order, order_id.clone()).await;
@@ -219,13 +488,157 @@ impl OrderExecutor {
         // Record order attempt
         self.record_order_attempt(&order_id).await;
 
+        tracing::Span::current().record("order_id", order_id.as_str());
         info!("Order placed with ID: {}", &order_id);
 
+        Ok(order_id)
+    }
+
+    /// Start working an iceberg order the venue cannot take natively
+    async fn execute_synthetic_iceberg(
+        &self,
+        order: NewOrder,
+        display_size: Size,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !display_size.is_positive() {
+            return Err(
+                format!("Iceberg display size for {} must be positive", order.symbol).into(),
+            );
+        }
+        info!(
+            "Emulating iceberg order of size {} showing {} on {}",
+            order.size, display_size, order.exchange_id
+        );
+        self.place_iceberg_slice(SyntheticIceberg {
+            order,
+            display_size,
+            slices: 0,
+        })
+        .await
+    }
+
+    /// Send the next visible slice of a synthetic iceberg
+    async fn place_iceberg_slice(
+        &self,
+        mut iceberg: SyntheticIceberg,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let slice_size = iceberg.display_size.min(iceberg.order.size);
+        let mut slice = iceberg.order.clone();
+        slice.order_type = OrderType::Limit;
+        slice.size = slice_size;
+        iceberg.slices += 1;
+        if let Some(client_id) = &iceberg.order.client_order_id {
+            slice.client_order_id = Some(format!("{}_ice{}", client_id, iceberg.slices));
+        }
+
+        let order_id = self.execute_single_order(slice).await?;
+        iceberg.order.size = iceberg.order.size - slice_size;
+        if iceberg.order.size.is_positive() {
+            self.icebergs.write().await.insert(order_id, iceberg);
+        }
         Ok(())
     }
 
+    /// Get the quantity of synthetic icebergs not yet shown on the book
+    pub async fn hidden_iceberg_size(&self, symbol: &str) -> Size {
+        self.icebergs
+            .read()
+            .await
+            .values()
+            .filter(|iceberg| iceberg.order.symbol.as_str() == symbol)
+            .fold(Size::zero(), |total, iceberg| total + iceberg.order.size)
+    }
+
+    /// Change the price and/or open size of a placed order
+    /// Uses the venue's native amend when it has one. Otherwise the order is cancelled and,
+    /// only once the cancel succeeds, its replacement is placed under the same client order
+    /// ID. Returns the ID of the order now working.
+    pub async fn amend_order(
+        &self,
+        order_id: OrderId,
+        new_price: Option<Price>,
+        new_size: Option<Size>,
+    ) -> Result<OrderId, Box<dyn std::error::Error + Send + Sync>> {
+        if new_price.is_none() && new_size.is_none() {
+            return Err(format!("Nothing to amend on order {}", order_id).into());
+        }
+        let original = self
+            .placed_orders
+            .read()
+            .await
+            .get(&order_id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown order {}", order_id))?;
+        let mut replacement = original.clone();
+        if let Some(price) = new_price {
+            replacement.price = Some(price);
+        }
+
+        self.rate_limiter.wait_for_slot().await;
+        self.wait_for_venue_slot(&original.exchange_id).await?;
+        let native = self
+            .execution_client
+            .amend_order(order_id.clone(), new_price, new_size)
+            .instrument(info_span!("exchange.amend_order", exchange = %original.exchange_id))
+            .await?;
+        let new_order_id = match native {
+            Some(new_order_id) => {
+                if let Some(size) = new_size {
+                    replacement.size = size;
+                }
+                self.journal(JournalEvent::Submitted {
+                    order: replacement.clone(),
+                    order_id: new_order_id.clone(),
+                });
+                self.add_pending_order(&replacement, new_order_id.clone())
+                    .await;
+                new_order_id
+            }
+            None => {
+                replacement.size = match new_size {
+                    Some(size) => size,
+                    None => {
+                        self.execution_client
+                            .get_order_status(order_id.clone())
+                            .await?
+                            .remaining_size
+                    }
+                };
+                self.cancel_order(order_id.clone()).await?;
+                self.execute_single_order(replacement)
+                    .await
+                    .inspect_err(|e| {
+                        error!(
+                            "Order {} was cancelled but its replacement failed: {}",
+                            order_id, e
+                        )
+                    })?
+            }
+        };
+        info!(
+            "Amended order {}, now working as {}",
+            order_id, new_order_id
+        );
+        self.journal(JournalEvent::Replaced {
+            order_id: order_id.clone(),
+            new_order_id: new_order_id.clone(),
+        });
+
+        self.placed_orders.write().await.remove(&order_id);
+        self.order_manager
+            .write()
+            .await
+            .record_replacement(&order_id, new_order_id.clone(), new_price, new_size)
+            .await?;
+        Ok(new_order_id)
+    }
+
     /// Add an order to the pending orders map
     async fn add_pending_order(&self, order: &NewOrder, order_id: OrderId) {
+        self.placed_orders
+            .write()
+            .await
+            .insert(order_id.clone(), order.clone());
         let mut pending_orders = self.pending_orders.write().await;
 
         let client_order_id = order
@@ -233,7 +646,10 @@ impl OrderExecutor {
             .clone()
             .unwrap_or_else(|| format!("auto_{}", &order_id));
 
-        pending_orders.insert(client_order_id, PendingOrder::new(order.clone()));
+        pending_orders.insert(
+            client_order_id,
+            PendingOrder::new(order.clone(), self.clock.now_instant()),
+        );
     }
 
     /// Record an order attempt
@@ -257,11 +673,12 @@ impl OrderExecutor {
         let mut pending_orders = self.pending_orders.write().await;
         let mut orders_to_remove = Vec::new();
         let mut orders_to_retry = Vec::new();
+        let now = self.clock.now_instant();
 
         // Check each pending order
         for (client_order_id, pending_order) in pending_orders.iter_mut() {
             // Check if order has timed out
-            if pending_order.is_timed_out(self.config.order_timeout) {
+            if pending_order.is_timed_out(self.config.order_timeout, now) {
                 warn!("Order {} timed out", client_order_id);
 
                 if self.config.enable_timeout_cancellation {
@@ -286,11 +703,11 @@ impl OrderExecutor {
             if pending_order.should_retry(self.config.max_retry_attempts) {
                 // Check if enough time has passed since last retry
                 if let Some(last_retry) = pending_order.last_retry_at {
-                    if last_retry.elapsed() >= self.config.retry_delay {
+                    if now.saturating_duration_since(last_retry) >= self.config.retry_delay {
                         info!("Retrying order {}", client_order_id);
 
                         // Increment retry count
-                        pending_order.increment_retry();
+                        pending_order.increment_retry(now);
 
                         // Add to retry list
                         orders_to_retry.push(client_order_id.clone());
@@ -300,7 +717,7 @@ impl OrderExecutor {
                     info!("Retrying order {}", client_order_id);
 
                     // Increment retry count
-                    pending_order.increment_retry();
+                    pending_order.increment_retry(now);
 
                     // Add to retry list
                     orders_to_retry.push(client_order_id.clone());
@@ -321,14 +738,39 @@ impl OrderExecutor {
             if let Some(pending_order) = pending_orders.get(&client_order_id) {
                 // Apply rate limiting
                 self.rate_limiter.wait_for_slot().await;
+                if let Err(e) = self
+                    .wait_for_venue_slot(&pending_order.order.exchange_id)
+                    .await
+                {
+                    warn!("Giving up on order {}: {}", client_order_id, e);
+                    pending_orders.remove(&client_order_id);
+                    continue;
+                }
 
                 // Retry the order
-                if let Err(e) = self
+                match self
                     .execution_client
                     .place_order(pending_order.order.clone())
                     .await
                 {
-                    error!("Failed to retry order {}: {}", client_order_id, e);
+                    Ok(order_id) => self.journal(JournalEvent::Submitted {
+                        order: pending_order.order.clone(),
+                        order_id,
+                    }),
+                    Err(e) => {
+                        error!("Failed to retry order {}: {}", client_order_id, e);
+                        self.journal(JournalEvent::SubmitFailed {
+                            order: pending_order.order.clone(),
+                            reason: e.to_string(),
+                        });
+                        // Resending cannot fix e.g. a bad price or missing balance
+                        if let Some(reject) = ExchangeReject::find(e.as_ref()) {
+                            if !reject.reason.is_retryable() {
+                                warn!("Giving up on order {}: {}", client_order_id, reject.reason);
+                                pending_orders.remove(&client_order_id);
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -342,6 +784,9 @@ impl OrderExecutor {
         order_id: OrderId,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Cancelling order {}", &order_id);
+        self.journal(JournalEvent::CancelRequested {
+            order_id: order_id.clone(),
+        });
 
         // Apply rate limiting
         self.rate_limiter.wait_for_slot().await;
@@ -364,11 +809,35 @@ impl OrderExecutor {
     }
 
     /// Process an execution report
+    #[tracing::instrument(
+        name = "order.report",
+        skip_all,
+        fields(order_id = %report.order_id, status = ?report.status)
+    )]
     pub async fn process_execution_report(
         &self,
         report: &ExecutionReport,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.apply_report(report, None).await
+    }
+
+    /// Process a report from `DryRunExecutionClient`
+    /// Its fill is booked with the side, price and fee it was simulated with.
+    pub async fn process_paper_execution(
+        &self,
+        execution: &PaperExecution,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.apply_report(&execution.report, execution.fill.as_ref())
+            .await
+    }
+
+    async fn apply_report(
+        &self,
+        report: &ExecutionReport,
+        fill: Option<&SimulatedFill>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         debug!("Processing execution report: {:?}", report);
+        self.journal(JournalEvent::from_report(report.clone()));
 
         // Update order manager
         let mut order_mgr = self.order_manager.write().await;
@@ -377,12 +846,47 @@ impl OrderExecutor {
             return Err(e); // Error is already Box<dyn Error>
         }
 
+        drop(order_mgr);
+
         // Update shadow ledger (returns (), no error handling needed)
-        self.shadow_ledger.process_execution_report(report).await;
+        match fill {
+            Some(fill) => {
+                let trade_id = format!("{}_{}", report.order_id, report.filled_size);
+                self.shadow_ledger
+                    .add_trade(fill.to_trade_record(trade_id))
+                    .await;
+            }
+            None => self.shadow_ledger.process_execution_report(report).await,
+        }
+
+        // A filled slice of a synthetic iceberg reveals the next one
+        match report.status {
+            OrderStatus::Filled => {
+                let iceberg = self.icebergs.write().await.remove(&report.order_id);
+                if let Some(iceberg) = iceberg {
+                    if let Err(e) = self.place_iceberg_slice(iceberg).await {
+                        error!(
+                            "Failed to place next iceberg slice for {}: {}",
+                            report.symbol, e
+                        );
+                    }
+                }
+            }
+            OrderStatus::Cancelled | OrderStatus::Rejected | OrderStatus::Expired => {
+                if let Some(iceberg) = self.icebergs.write().await.remove(&report.order_id) {
+                    warn!(
+                        "Iceberg on {} stopped after {} slices with {} unsent",
+                        report.symbol, iceberg.slices, iceberg.order.size
+                    );
+                }
+            }
+            _ => {}
+        }
 
         // Remove from pending orders if filled or canceled
         match report.status {
             OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected => {
+                self.placed_orders.write().await.remove(&report.order_id);
                 let mut pending_orders = self.pending_orders.write().await;
 
                 if let Some(client_order_id) = &report.client_order_id {
@@ -397,6 +901,189 @@ impl OrderExecutor {
         Ok(())
     }
 
+    /// Send the exchange a heartbeat, returning whether it answered
+    /// Once heartbeats have failed for longer than the watchdog timeout, the orders this
+    /// executor placed are cancelled, in case the exchange-side timer is not armed.
+    pub async fn heartbeat(&self) -> bool {
+        match self.execution_client.heartbeat().await {
+            Ok(()) => {
+                *self.last_heartbeat.write().await = self.clock.now_instant();
+                true
+            }
+            Err(e) => {
+                let silent_for = self
+                    .clock
+                    .now_instant()
+                    .saturating_duration_since(*self.last_heartbeat.read().await);
+                warn!("Heartbeat failed, no answer for {:?}: {}", silent_for, e);
+                if silent_for > self.session_watchdog.timeout {
+                    let cancelled = self.cancel_resting_orders().await;
+                    if cancelled > 0 {
+                        error!(
+                            "Session lost for {:?}, cancelled {} resting orders",
+                            silent_for, cancelled
+                        );
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    /// Cancel every order this executor placed that is still resting
+    /// Returns how many were cancelled; orders whose cancel fails are kept for a retry.
+    pub async fn cancel_resting_orders(&self) -> usize {
+        let order_ids: Vec<OrderId> = self.placed_orders.read().await.keys().cloned().collect();
+        let mut cancelled = 0;
+        for order_id in order_ids {
+            if self.cancel_order(order_id.clone()).await.is_ok() {
+                self.placed_orders.write().await.remove(&order_id);
+                self.icebergs.write().await.remove(&order_id);
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+
+    /// Start a background task sending heartbeats at the watchdog interval
+    pub fn spawn_session_watchdog(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.session_watchdog.heartbeat_interval);
+            loop {
+                interval.tick().await;
+                self.heartbeat().await;
+            }
+        })
+    }
+
+    /// Cancel all open orders and close every position in the shadow ledger
+    /// Positions are closed with IOC limit orders priced within the flatten slippage of
+    /// their reference price, or market orders when there is none. Closing orders are
+    /// sent even while the kill switch is tripped. Each position is logged as it is done.
+    pub async fn flatten_all(&self) -> FlattenAllReport {
+        let mut report = FlattenAllReport::default();
+        match self.execution_client.get_open_orders(None).await {
+            Ok(open_orders) => {
+                for open in open_orders {
+                    match self.cancel_order(open.order_id.clone()).await {
+                        Ok(()) => {
+                            self.placed_orders.write().await.remove(&open.order_id);
+                            self.icebergs.write().await.remove(&open.order_id);
+                            report.cancelled += 1;
+                        }
+                        Err(e) => report
+                            .cancel_failures
+                            .push(format!("Failed to cancel {}: {}", open.order_id, e)),
+                    }
+                }
+            }
+            Err(e) => {
+                report
+                    .cancel_failures
+                    .push(format!("Failed to list open orders: {}", e));
+                report.cancelled += self.cancel_resting_orders().await;
+            }
+        }
+
+        let mut positions: Vec<_> = self
+            .shadow_ledger
+            .get_all_positions()
+            .await
+            .into_iter()
+            .filter(|p| !p.size.is_zero())
+            .collect();
+        positions.sort_by(|a, b| {
+            (a.symbol.value(), &a.exchange_id).cmp(&(b.symbol.value(), &b.exchange_id))
+        });
+        let total = positions.len();
+        for (done, position) in positions.into_iter().enumerate() {
+            let symbol = position.symbol.value().to_string();
+            let order = self
+                .flatten_order(&symbol, position.size)
+                .await
+                .with_exchange_id(position.exchange_id.as_str());
+            self.journal(JournalEvent::Intent {
+                order: order.clone(),
+            });
+            let (order_id, error) = match self.execute_single_order(order).await {
+                Ok(order_id) => {
+                    info!(
+                        "Flattening {} {} on {} with order {} ({}/{})",
+                        symbol,
+                        position.size,
+                        position.exchange_id,
+                        order_id,
+                        done + 1,
+                        total
+                    );
+                    (Some(order_id), None)
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to flatten {} {} on {} ({}/{}): {}",
+                        symbol,
+                        position.size,
+                        position.exchange_id,
+                        done + 1,
+                        total,
+                        e
+                    );
+                    (None, Some(e.to_string()))
+                }
+            };
+            report.positions.push(PositionFlatten {
+                symbol,
+                exchange_id: position.exchange_id,
+                size: position.size,
+                order_id,
+                error,
+            });
+        }
+        report
+    }
+
+    /// Build the order closing a position of `size`, positive for long
+    async fn flatten_order(&self, symbol: &str, size: Size) -> NewOrder {
+        let reference = self.reference_prices.read().await.get(symbol).copied();
+        let close_size = size.abs();
+        match (reference, size.is_positive()) {
+            (Some(reference), true) => {
+                let limit = Price::new(reference.value() * (Decimal::ONE - self.flatten_slippage));
+                NewOrder::new_limit_sell(symbol, close_size, limit, TimeInForce::ImmediateOrCancel)
+            }
+            (Some(reference), false) => {
+                let limit = Price::new(reference.value() * (Decimal::ONE + self.flatten_slippage));
+                NewOrder::new_limit_buy(symbol, close_size, limit, TimeInForce::ImmediateOrCancel)
+            }
+            (None, true) => NewOrder::new_market_sell(symbol, close_size),
+            (None, false) => NewOrder::new_market_buy(symbol, close_size),
+        }
+    }
+
+    /// Start a background task flattening every position whenever the kill switch trips
+    pub fn spawn_flatten_on_halt(self: Arc<Self>, kill_switch: &KillSwitch) -> JoinHandle<()> {
+        let mut events = kill_switch.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(SystemEvent::TradingHalted(reason)) => {
+                        warn!("Trading halted ({}), flattening all positions", reason);
+                        let report = self.flatten_all().await;
+                        if !report.is_complete() {
+                            error!(
+                                "Flatten after halt incomplete: {} cancel failures, {} positions failed",
+                                report.cancel_failures.len(),
+                                report.positions.iter().filter(|p| p.error.is_some()).count()
+                            );
+                        }
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
     /// Get statistics about order execution
     pub async fn get_execution_stats(&self) -> ExecutionStats {
         let pending_orders = self.pending_orders.read().await;
@@ -477,28 +1164,29 @@ mod tests {
             TimeInForce::GoodTillCancelled,
         );
 
-        let pending_order = PendingOrder::new(order.clone());
+        let now = Instant::now();
+        let pending_order = PendingOrder::new(order.clone(), now);
 
         assert_eq!(pending_order.order, order);
-        assert!(!pending_order.is_timed_out(Duration::from_secs(30)));
+        assert!(!pending_order.is_timed_out(Duration::from_secs(30), now));
         assert_eq!(pending_order.retry_count, 0);
         assert!(pending_order.should_retry(3));
 
         // Increment retry
         let mut pending_order_mut = pending_order.clone();
-        pending_order_mut.increment_retry();
+        pending_order_mut.increment_retry(now);
 
         assert_eq!(pending_order_mut.retry_count, 1);
         assert!(pending_order_mut.should_retry(3));
 
         // Increment retry again
-        pending_order_mut.increment_retry();
+        pending_order_mut.increment_retry(now);
 
         assert_eq!(pending_order_mut.retry_count, 2);
         assert!(pending_order_mut.should_retry(3));
 
         // Increment retry again (max)
-        pending_order_mut.increment_retry();
+        pending_order_mut.increment_retry(now);
 
         assert_eq!(pending_order_mut.retry_count, 3);
         assert!(!pending_order_mut.should_retry(3));
@@ -513,16 +1201,19 @@ mod tests {
             TimeInForce::GoodTillCancelled,
         );
 
-        let pending_order = PendingOrder::new(order);
+        let created_at = Instant::now();
+        let pending_order = PendingOrder::new(order, created_at);
 
         // Should not be timed out initially
-        assert!(!pending_order.is_timed_out(Duration::from_secs(30)));
+        assert!(!pending_order.is_timed_out(Duration::from_secs(30), created_at));
 
-        // Simulate time passing
-        std::thread::sleep(Duration::from_millis(100));
+        // Should still not be timed out a little later
+        let now = created_at + Duration::from_millis(100);
+        assert!(!pending_order.is_timed_out(Duration::from_secs(30), now));
 
-        // Should still not be timed out
-        assert!(!pending_order.is_timed_out(Duration::from_secs(30)));
+        // Times out past the timeout
+        let now = created_at + Duration::from_secs(31);
+        assert!(pending_order.is_timed_out(Duration::from_secs(30), now));
     }
 
     #[test]
@@ -546,6 +1237,400 @@ mod tests {
         assert!(executor_impl.config.enable_order_splitting);
     }
 
+    struct NoopOrderManager;
+
+    #[async_trait::async_trait]
+    impl OrderManager for NoopOrderManager {
+        type Error = Box<dyn std::error::Error + Send + Sync>;
+
+        async fn handle_execution_report(
+            &mut self,
+            _report: ExecutionReport,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn get_all_orders(&self) -> Result<Vec<ExecutionReport>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn get_orders_by_symbol(
+            &self,
+            _symbol: &str,
+        ) -> Result<Vec<ExecutionReport>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn get_open_orders(&self) -> Result<Vec<ExecutionReport>, Self::Error> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quote_sized_order_converted_for_venue() {
+        let client = Arc::new(crate::connectors::MockExecutionClient::new());
+        let executor = OrderExecutor::new(
+            OrderExecutorConfig::default(),
+            client.clone(),
+            Arc::new(RwLock::new(NoopOrderManager)),
+            Arc::new(RateLimiter::new(100, Duration::from_secs(1))),
+            Arc::new(ShadowLedger::new()),
+        );
+        let order = NewOrder::new_market_quote(
+            "BTCUSDT",
+            crate::traits::OrderSide::Buy,
+            crate::types::Notional::new(rust_decimal::Decimal::new(500, 0)),
+        );
+
+        // The mock venue has no native quote sizing and there is no price to convert at
+        assert!(executor.execute_order(order.clone()).await.is_err());
+
+        executor
+            .update_reference_price("BTCUSDT", Price::from_str("25000").unwrap())
+            .await;
+        executor.execute_order(order).await.unwrap();
+        let open = client.get_open_orders(Some("BTCUSDT")).await.unwrap();
+        assert_eq!(open[0].remaining_size, Size::from_str("0.02").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_paper_execution_books_simulated_fill() {
+        let ledger = Arc::new(ShadowLedger::new());
+        let executor = OrderExecutor::new(
+            OrderExecutorConfig::default(),
+            Arc::new(crate::connectors::MockExecutionClient::new()),
+            Arc::new(RwLock::new(NoopOrderManager)),
+            Arc::new(RateLimiter::new(100, Duration::from_secs(1))),
+            ledger.clone(),
+        );
+        let size = Size::from_str("0.5").unwrap();
+        let price = Price::from_str("100").unwrap();
+        let execution = PaperExecution {
+            report: ExecutionReport {
+                order_id: "dry_run_1".to_string(),
+                client_order_id: None,
+                symbol: crate::types::Symbol::new("BTCUSDT"),
+                exchange_id: "dry_run".to_string(),
+                status: OrderStatus::PartiallyFilled,
+                filled_size: size,
+                remaining_size: size,
+                average_price: Some(price),
+                timestamp: 1_000,
+                reject_reason: None,
+            },
+            fill: Some(SimulatedFill {
+                order_id: "dry_run_1".to_string(),
+                symbol: crate::types::Symbol::new("BTCUSDT"),
+                exchange_id: "dry_run".to_string(),
+                side: crate::traits::OrderSide::Sell,
+                size,
+                price,
+                fee: rust_decimal::Decimal::new(5, 2),
+                maker: true,
+                timestamp: 1_000,
+            }),
+        };
+
+        executor.process_paper_execution(&execution).await.unwrap();
+        let position = ledger.get_position("BTCUSDT", "dry_run").await.unwrap();
+        assert_eq!(position.size, Size::from_str("-0.5").unwrap());
+        // The maker fee lowers the proceeds of the short
+        assert_eq!(
+            position.average_price,
+            Some(Price::from_str("99.9").unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_cancels_resting_orders_when_heartbeats_stop() {
+        let client = Arc::new(crate::connectors::MockExecutionClient::new());
+        let clock = crate::core::clock::SimulatedClock::at_millis(1_700_000_000_000);
+        let executor = OrderExecutor::new(
+            OrderExecutorConfig::default(),
+            client.clone(),
+            Arc::new(RwLock::new(NoopOrderManager)),
+            Arc::new(RateLimiter::new(100, Duration::from_secs(1))),
+            Arc::new(ShadowLedger::new()),
+        )
+        .with_session_watchdog(SessionWatchdogConfig {
+            heartbeat_interval: Duration::from_millis(10),
+            timeout: Duration::from_millis(50),
+        })
+        .with_clock(clock.shared());
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("1.0").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        executor.execute_order(order).await.unwrap();
+        assert!(executor.heartbeat().await);
+
+        // A short outage leaves the orders alone
+        client.set_heartbeat_ok(false);
+        assert!(!executor.heartbeat().await);
+        assert_eq!(client.get_open_orders(None).await.unwrap().len(), 1);
+
+        // Past the timeout they are cancelled
+        clock.advance(Duration::from_millis(60));
+        assert!(!executor.heartbeat().await);
+        assert!(client.get_open_orders(None).await.unwrap().is_empty());
+        assert_eq!(executor.cancel_resting_orders().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_flatten_all_cancels_orders_and_closes_positions() {
+        let client = Arc::new(crate::connectors::MockExecutionClient::new());
+        let ledger = Arc::new(ShadowLedger::new());
+        for (symbol, exchange_id, size) in
+            [("BTCUSDT", "binance", "2"), ("ETHUSDT", "kraken", "-3")]
+        {
+            let mut position = crate::risk::shadow_ledger::PositionRecord::new(
+                crate::types::Symbol::new(symbol),
+                exchange_id.to_string(),
+            );
+            position.size = Size::from_str(size).unwrap();
+            ledger.seed_position(position).await;
+        }
+        let kill_switch = KillSwitch::new();
+        let executor = OrderExecutor::new(
+            OrderExecutorConfig::default(),
+            client.clone(),
+            Arc::new(RwLock::new(NoopOrderManager)),
+            Arc::new(RateLimiter::new(100, Duration::from_secs(1))),
+            ledger,
+        )
+        .with_kill_switch(kill_switch.clone())
+        .with_flatten_slippage(Decimal::new(1, 2));
+        executor
+            .update_reference_price("BTCUSDT", Price::from_str("50000").unwrap())
+            .await;
+        let resting = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("1.0").unwrap(),
+            Price::from_str("49000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        executor.execute_order(resting).await.unwrap();
+
+        // Closing orders go out even though new orders are rejected while halted
+        kill_switch
+            .trip(crate::risk::kill_switch::HaltSource::Operator, "test")
+            .await;
+        let report = executor.flatten_all().await;
+        assert!(report.is_complete());
+        assert_eq!(report.cancelled, 1);
+        assert_eq!(report.positions.len(), 2);
+        assert_eq!(report.positions[0].symbol, "BTCUSDT");
+        assert_eq!(report.positions[1].exchange_id, "kraken");
+
+        let open = client.get_open_orders(None).await.unwrap();
+        assert_eq!(open.len(), 2);
+        let btc = open.iter().find(|r| r.symbol.value() == "BTCUSDT").unwrap();
+        assert_eq!(btc.exchange_id, "binance");
+        assert_eq!(btc.remaining_size, Size::from_str("2").unwrap());
+        // Sold through the reference price by at most the flatten slippage
+        assert_eq!(btc.average_price, Some(Price::from_str("49500").unwrap()));
+        // No reference price, so the short is bought back at market
+        let eth = open.iter().find(|r| r.symbol.value() == "ETHUSDT").unwrap();
+        assert_eq!(eth.remaining_size, Size::from_str("3").unwrap());
+        assert_eq!(eth.average_price, None);
+    }
+
+    #[tokio::test]
+    async fn test_synthetic_iceberg_order() {
+        let client = Arc::new(crate::connectors::MockExecutionClient::new());
+        let executor = OrderExecutor::new(
+            OrderExecutorConfig::default(),
+            client.clone(),
+            Arc::new(RwLock::new(NoopOrderManager)),
+            Arc::new(RateLimiter::new(100, Duration::from_secs(1))),
+            Arc::new(ShadowLedger::new()),
+        );
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("1.0").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        )
+        .with_client_order_id("ice".to_string())
+        .with_display_size(Size::from_str("0.4").unwrap());
+
+        // The mock venue has no native icebergs, so only the first slice is sent
+        executor.execute_order(order).await.unwrap();
+        let filled = |report: &ExecutionReport| ExecutionReport {
+            status: OrderStatus::Filled,
+            filled_size: report.remaining_size,
+            remaining_size: Size::zero(),
+            ..report.clone()
+        };
+        let open = client.get_open_orders(Some("BTCUSDT")).await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].remaining_size, Size::from_str("0.4").unwrap());
+        assert_eq!(open[0].client_order_id.as_deref(), Some("ice_ice1"));
+        assert_eq!(
+            executor.hidden_iceberg_size("BTCUSDT").await,
+            Size::from_str("0.6").unwrap()
+        );
+
+        // Each fill reveals the next slice, the last one holding the remainder
+        executor
+            .process_execution_report(&filled(&open[0]))
+            .await
+            .unwrap();
+        let open = client.get_open_orders(Some("BTCUSDT")).await.unwrap();
+        let slice = open
+            .iter()
+            .find(|r| r.client_order_id.as_deref() == Some("ice_ice2"))
+            .unwrap();
+        assert_eq!(slice.remaining_size, Size::from_str("0.4").unwrap());
+        executor
+            .process_execution_report(&filled(slice))
+            .await
+            .unwrap();
+        let open = client.get_open_orders(Some("BTCUSDT")).await.unwrap();
+        let last = open
+            .iter()
+            .find(|r| r.client_order_id.as_deref() == Some("ice_ice3"))
+            .unwrap();
+        assert_eq!(last.remaining_size, Size::from_str("0.2").unwrap());
+        assert_eq!(executor.hidden_iceberg_size("BTCUSDT").await, Size::zero());
+
+        // Cancelling a slice stops the iceberg
+        let order = NewOrder::new_limit_sell(
+            "BTCUSDT",
+            Size::from_str("1.0").unwrap(),
+            Price::from_str("51000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        )
+        .with_client_order_id("ice2".to_string())
+        .with_display_size(Size::from_str("0.5").unwrap());
+        executor.execute_order(order).await.unwrap();
+        let open = client.get_open_orders(Some("BTCUSDT")).await.unwrap();
+        let slice = open
+            .iter()
+            .find(|r| r.client_order_id.as_deref() == Some("ice2_ice1"))
+            .unwrap();
+        let cancelled = ExecutionReport {
+            status: OrderStatus::Cancelled,
+            ..slice.clone()
+        };
+        executor.process_execution_report(&cancelled).await.unwrap();
+        assert_eq!(executor.hidden_iceberg_size("BTCUSDT").await, Size::zero());
+    }
+
+    #[tokio::test]
+    async fn test_order_journal_records_lifecycle() {
+        let path = std::env::temp_dir().join(format!("journal_{}.jsonl", uuid::Uuid::new_v4()));
+        let client = Arc::new(crate::connectors::MockExecutionClient::new());
+        let executor = OrderExecutor::new(
+            OrderExecutorConfig::default(),
+            client.clone(),
+            Arc::new(RwLock::new(NoopOrderManager)),
+            Arc::new(RateLimiter::new(100, Duration::from_secs(1))),
+            Arc::new(ShadowLedger::new()),
+        )
+        .with_journal(Arc::new(OrderJournal::open(&path).unwrap()));
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("0.5").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        )
+        .with_client_order_id("quote_1".to_string());
+        executor.execute_order(order).await.unwrap();
+        let mut report = client.get_open_orders(Some("BTCUSDT")).await.unwrap()[0].clone();
+        report.status = OrderStatus::Filled;
+        report.filled_size = report.remaining_size;
+        report.remaining_size = Size::zero();
+        executor.process_execution_report(&report).await.unwrap();
+
+        let reader = crate::oms::JournalReader::open(&path).unwrap();
+        let history = reader.order_history(&report.order_id);
+        assert_eq!(history.len(), 3);
+        assert!(matches!(history[0].event, JournalEvent::Intent { .. }));
+        assert!(matches!(history[1].event, JournalEvent::Submitted { .. }));
+        assert_eq!(
+            history[2].event,
+            JournalEvent::Fill {
+                report: report.clone()
+            }
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_amend_order_falls_back_to_cancel_and_place() {
+        let client = Arc::new(crate::connectors::MockExecutionClient::new());
+        let executor = OrderExecutor::new(
+            OrderExecutorConfig::default(),
+            client.clone(),
+            Arc::new(RwLock::new(NoopOrderManager)),
+            Arc::new(RateLimiter::new(100, Duration::from_secs(1))),
+            Arc::new(ShadowLedger::new()),
+        );
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("0.5").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        )
+        .with_client_order_id("quote_1".to_string());
+        executor.execute_order(order).await.unwrap();
+        let order_id = client.get_open_orders(Some("BTCUSDT")).await.unwrap()[0]
+            .order_id
+            .clone();
+
+        assert!(executor
+            .amend_order(order_id.clone(), None, None)
+            .await
+            .is_err());
+        assert!(executor
+            .amend_order(
+                "unknown".to_string(),
+                Some(Price::from_str("1").unwrap()),
+                None
+            )
+            .await
+            .is_err());
+
+        // The mock venue cannot amend, so the order is cancelled and placed again
+        let new_order_id = executor
+            .amend_order(
+                order_id.clone(),
+                Some(Price::from_str("50100").unwrap()),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_ne!(new_order_id, order_id);
+        let old = client.get_order_status(order_id.clone()).await.unwrap();
+        assert_eq!(old.status, OrderStatus::Cancelled);
+        let open = client.get_open_orders(Some("BTCUSDT")).await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].order_id, new_order_id);
+        assert_eq!(open[0].client_order_id.as_deref(), Some("quote_1"));
+        assert_eq!(open[0].remaining_size, Size::from_str("0.5").unwrap());
+
+        // The replacement can be amended in turn, here to a smaller size
+        let newest = executor
+            .amend_order(
+                new_order_id.clone(),
+                None,
+                Some(Size::from_str("0.2").unwrap()),
+            )
+            .await
+            .unwrap();
+        let open = client.get_open_orders(Some("BTCUSDT")).await.unwrap();
+        assert_eq!(open[0].order_id, newest);
+        assert_eq!(open[0].remaining_size, Size::from_str("0.2").unwrap());
+        assert!(executor
+            .amend_order(order_id, Some(Price::from_str("1").unwrap()), None)
+            .await
+            .is_err());
+    }
+
     // Note: Full OrderExecutor integration tests require complex setup with
     // trait objects (Arc<dyn ExecutionClient>, Arc<RwLock<dyn OrderManager>>).
     // The individual component tests provide coverage for the main functionality.
@@ -1,7 +1,8 @@
+use crate::monitoring::MetricsCollector;
 use log::{debug, info};
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -57,6 +58,46 @@ impl Default for PerformanceMetrics {
     }
 }
 
+/// Point of the tick-to-trade path, measured from when a market event was ingested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LatencyStage {
+    /// A strategy produced a signal
+    Signal,
+    /// The signal's order passed the risk check
+    RiskCheck,
+    /// The order was submitted to the exchange (tick-to-trade)
+    OrderSubmit,
+}
+
+impl LatencyStage {
+    /// All stages in path order
+    pub const ALL: [LatencyStage; 3] = [
+        LatencyStage::Signal,
+        LatencyStage::RiskCheck,
+        LatencyStage::OrderSubmit,
+    ];
+
+    /// Get the stage name used in metric names
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LatencyStage::Signal => "signal",
+            LatencyStage::RiskCheck => "risk_check",
+            LatencyStage::OrderSubmit => "order_submit",
+        }
+    }
+}
+
+/// Percentile summary of the latency from ingestion to a stage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    pub stage: LatencyStage,
+    pub count: usize,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+    pub max_us: u64,
+}
+
 /// Performance monitor for tracking trading performance
 pub struct PerformanceMonitor {
     /// Current metrics
@@ -69,6 +110,10 @@ pub struct PerformanceMonitor {
     max_pnl_history_size: usize,
     /// Start time for metrics collection
     start_time: Arc<RwLock<Instant>>,
+    /// Recent latencies from ingestion in microseconds, per stage (oldest first)
+    stage_latencies: Arc<RwLock<HashMap<LatencyStage, VecDeque<u64>>>>,
+    /// Maximum number of latency samples kept per stage
+    max_latency_samples: usize,
 }
 
 impl PerformanceMonitor {
@@ -80,15 +125,84 @@ impl PerformanceMonitor {
             pnl_history: Arc::new(RwLock::new(Vec::new())),
             max_pnl_history_size: 1000,
             start_time: Arc::new(RwLock::new(Instant::now())),
+            stage_latencies: Arc::new(RwLock::new(HashMap::new())),
+            max_latency_samples: 10_000,
         }
     }
 
+    /// Set the number of latency samples kept per stage (builder pattern)
+    pub fn with_max_latency_samples(mut self, max_latency_samples: usize) -> Self {
+        self.max_latency_samples = max_latency_samples.max(1);
+        self
+    }
+
     /// Record a market data event
     pub async fn record_market_data_event(&self) {
         let mut metrics = self.metrics.write().await;
         metrics.market_data_events += 1;
     }
 
+    /// Record that a stage was reached for a market event ingested at `ingested_at`
+    pub async fn record_stage(&self, stage: LatencyStage, ingested_at: Instant) {
+        self.record_stage_latency(stage, ingested_at.elapsed())
+            .await;
+    }
+
+    /// Record the latency from ingestion to a stage
+    pub async fn record_stage_latency(&self, stage: LatencyStage, latency: Duration) {
+        let mut stage_latencies = self.stage_latencies.write().await;
+        let samples = stage_latencies.entry(stage).or_default();
+        if samples.len() >= self.max_latency_samples {
+            samples.pop_front();
+        }
+        samples.push_back(latency.as_micros() as u64);
+    }
+
+    /// Get the latency percentiles of a stage, or `None` before any sample
+    pub async fn latency_histogram(&self, stage: LatencyStage) -> Option<LatencyHistogram> {
+        let stage_latencies = self.stage_latencies.read().await;
+        let samples = stage_latencies.get(&stage).filter(|s| !s.is_empty())?;
+
+        let mut sorted: Vec<u64> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+        Some(LatencyHistogram {
+            stage,
+            count: sorted.len(),
+            p50_us: percentile(&sorted, 0.50),
+            p95_us: percentile(&sorted, 0.95),
+            p99_us: percentile(&sorted, 0.99),
+            max_us: sorted[sorted.len() - 1],
+        })
+    }
+
+    /// Get the latency percentiles of every stage with samples, in path order
+    pub async fn latency_histograms(&self) -> Vec<LatencyHistogram> {
+        let mut histograms = Vec::new();
+        for stage in LatencyStage::ALL {
+            if let Some(histogram) = self.latency_histogram(stage).await {
+                histograms.push(histogram);
+            }
+        }
+        histograms
+    }
+
+    /// Publish every stage's latency percentiles as gauges
+    pub async fn export_latencies(&self, metrics: &MetricsCollector) {
+        for histogram in self.latency_histograms().await {
+            let stage = histogram.stage.as_str();
+            for (name, value) in [
+                ("p50", histogram.p50_us),
+                ("p95", histogram.p95_us),
+                ("p99", histogram.p99_us),
+                ("max", histogram.max_us),
+            ] {
+                metrics
+                    .set_gauge(&format!("tick_latency_{}_us.{}", name, stage), value as f64)
+                    .await;
+            }
+        }
+    }
+
     /// Record a signal generation
     pub async fn record_signal(&self) {
         let mut metrics = self.metrics.write().await;
@@ -286,6 +400,8 @@ impl PerformanceMonitor {
         let mut pnl_history = self.pnl_history.write().await;
         pnl_history.clear();
 
+        self.stage_latencies.write().await.clear();
+
         // Reset start time
         let mut start_time = self.start_time.write().await;
         *start_time = Instant::now();
@@ -355,6 +471,12 @@ impl PerformanceMonitor {
     }
 }
 
+/// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[u64], quantile: f64) -> u64 {
+    let rank = (quantile.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
 /// Performance monitor implementation for testing
 pub struct PerformanceMonitorImpl {
     /// Current metrics
@@ -642,6 +764,58 @@ mod tests {
         assert_eq!(reject_rate, 20.0);
     }
 
+    #[tokio::test]
+    async fn test_stage_latency_histograms() {
+        let monitor = PerformanceMonitor::new().with_max_latency_samples(100);
+        assert!(monitor
+            .latency_histogram(LatencyStage::Signal)
+            .await
+            .is_none());
+
+        // 1..=100us plus one old sample that falls out of the window
+        monitor
+            .record_stage_latency(LatencyStage::Signal, Duration::from_secs(1))
+            .await;
+        for us in 1..=100 {
+            monitor
+                .record_stage_latency(LatencyStage::Signal, Duration::from_micros(us))
+                .await;
+        }
+        monitor
+            .record_stage(LatencyStage::OrderSubmit, Instant::now())
+            .await;
+
+        let signal = monitor
+            .latency_histogram(LatencyStage::Signal)
+            .await
+            .unwrap();
+        assert_eq!(signal.count, 100);
+        assert_eq!(signal.p50_us, 50);
+        assert_eq!(signal.p95_us, 95);
+        assert_eq!(signal.p99_us, 99);
+        assert_eq!(signal.max_us, 100);
+
+        let stages: Vec<_> = monitor
+            .latency_histograms()
+            .await
+            .into_iter()
+            .map(|h| h.stage)
+            .collect();
+        assert_eq!(
+            stages,
+            vec![LatencyStage::Signal, LatencyStage::OrderSubmit]
+        );
+
+        let metrics = MetricsCollector::new();
+        monitor.export_latencies(&metrics).await;
+        let rendered = metrics.render_prometheus().await;
+        assert!(rendered.contains("tick_latency_p99_us_signal 99\n"));
+        assert!(rendered.contains("tick_latency_max_us_order_submit"));
+
+        monitor.reset_metrics().await;
+        assert!(monitor.latency_histograms().await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_performance_monitor_impl() {
         let monitor = PerformanceMonitorImpl::new();
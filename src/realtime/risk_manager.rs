@@ -1,7 +1,8 @@
 use crate::core::events::Position;
+use crate::monitoring::{AlertLevel, AlertManager};
 use crate::risk::shadow_ledger::ShadowLedger;
 use crate::risk::{RiskEngine, RiskViolation};
-use crate::traits::{ExecutionReport, OrderSide, OrderStatus};
+use crate::traits::{ExecutionReport, NewOrder, OrderSide, OrderStatus};
 use crate::types::{Price, Size};
 use chrono::{DateTime, Utc};
 use log::{debug, info, warn};
@@ -36,6 +37,8 @@ pub struct RiskManagerConfig {
     pub position_reduction_factor: f64,
     /// Enable automatic order cancellation on risk breach
     pub enable_auto_order_cancellation: bool,
+    /// Shrink orders that breach a limit to the largest allowed size instead of rejecting them
+    pub enable_order_resizing: bool,
 }
 
 impl Default for RiskManagerConfig {
@@ -52,10 +55,29 @@ impl Default for RiskManagerConfig {
             enable_auto_position_reduction: true,
             position_reduction_factor: 0.5, // Reduce by 50%
             enable_auto_order_cancellation: true,
+            enable_order_resizing: false,
         }
     }
 }
 
+/// Order that passed the pre-trade risk check
+#[derive(Debug, Clone)]
+pub struct RiskCheckedOrder {
+    /// Order to send, possibly with a reduced size
+    pub order: NewOrder,
+    /// Size requested before resizing, if the order was shrunk to fit the limits
+    pub original_size: Option<Size>,
+    /// Violation that caused the resize
+    pub violation: Option<RiskViolation>,
+}
+
+impl RiskCheckedOrder {
+    /// Check if the order was shrunk to fit the limits
+    pub fn is_resized(&self) -> bool {
+        self.original_size.is_some()
+    }
+}
+
 /// Risk manager for monitoring and managing trading risk
 pub struct RiskManager {
     /// Configuration
@@ -70,6 +92,8 @@ pub struct RiskManager {
     last_risk_check: Arc<RwLock<DateTime<Utc>>>,
     /// Risk check interval
     risk_check_interval: Duration,
+    /// Alert manager notified of violations
+    alert_manager: Option<Arc<AlertManager>>,
 }
 
 impl RiskManager {
@@ -87,9 +111,17 @@ impl RiskManager {
             risk_violations: Arc::new(RwLock::new(Vec::new())),
             last_risk_check: Arc::new(RwLock::new(Utc::now())),
             risk_check_interval,
+            alert_manager: None,
         }
     }
 
+    /// Raise violations as alerts: limit breaches as Critical, rejected orders as
+    /// Warning (builder pattern)
+    pub fn with_alert_manager(mut self, alert_manager: Arc<AlertManager>) -> Self {
+        self.alert_manager = Some(alert_manager);
+        self
+    }
+
     /// Handle an execution report
     pub async fn handle_execution_report(
         &self,
@@ -182,6 +214,47 @@ impl RiskManager {
         Ok(())
     }
 
+    /// Check an order against the risk engine before it is sent
+    /// With order resizing enabled, an order breaching a limit is shrunk to the largest
+    /// size the limits allow rather than rejected; breach actions only run on rejection.
+    #[tracing::instrument(
+        name = "risk.manager",
+        skip_all,
+        fields(symbol = %order.symbol, size = %order.size)
+    )]
+    pub async fn check_order(&self, order: &NewOrder) -> Result<RiskCheckedOrder, RiskViolation> {
+        let risk_engine = self.risk_engine.read().await;
+        let violation = match risk_engine.evaluate_order(order).await {
+            Ok(()) => {
+                return Ok(RiskCheckedOrder {
+                    order: order.clone(),
+                    original_size: None,
+                    violation: None,
+                });
+            }
+            Err(violation) => violation,
+        };
+
+        if self.config.enable_order_resizing {
+            if let Ok(resized) = risk_engine.resize_order(order).await {
+                info!(
+                    "Resized {} order from {} to {} ({}: {})",
+                    order.symbol, order.size, resized.size, violation.rule, violation.details
+                );
+                return Ok(RiskCheckedOrder {
+                    order: resized,
+                    original_size: Some(order.size),
+                    violation: Some(violation),
+                });
+            }
+        }
+
+        risk_engine.reject_order(order, &violation).await;
+        self.record_risk_violations(std::slice::from_ref(&violation), AlertLevel::Warning)
+            .await;
+        Err(violation)
+    }
+
     /// Check risk limits and take action if needed
     pub async fn check_risk_limits(
         &self,
@@ -218,7 +291,8 @@ impl RiskManager {
 
         // Record violations
         if !violations.is_empty() {
-            self.record_risk_violations(&violations).await;
+            self.record_risk_violations(&violations, AlertLevel::Critical)
+                .await;
 
             // Take action based on violations
             self.handle_risk_violations(&violations).await?;
@@ -345,13 +419,22 @@ impl RiskManager {
         Ok(violations)
     }
 
-    /// Record risk violations
-    async fn record_risk_violations(&self, violations: &[RiskViolation]) {
+    /// Record risk violations and raise them as alerts
+    async fn record_risk_violations(&self, violations: &[RiskViolation], level: AlertLevel) {
         let mut risk_violations = self.risk_violations.write().await;
 
         for violation in violations {
             warn!("Risk violation: {} - {}", violation.rule, violation.details);
             risk_violations.push(violation.clone());
+            if let Some(alert_manager) = &self.alert_manager {
+                alert_manager
+                    .emit(
+                        level,
+                        "risk_manager",
+                        format!("{}: {}", violation.rule, violation.details),
+                    )
+                    .await;
+            }
         }
     }
 
@@ -465,16 +548,7 @@ impl RiskManager {
         info!("Cancelling all orders");
 
         let risk_engine = self.risk_engine.read().await;
-        let positions = risk_engine.get_all_positions().await;
-
-        let mut total_canceled = 0;
-
-        // Cancel orders for each symbol
-        for position in positions {
-            let symbol = position.symbol.value();
-            let canceled_orders = risk_engine.cancel_all_orders_for_symbol(symbol).await;
-            total_canceled += canceled_orders.len();
-        }
+        let total_canceled = risk_engine.cancel_all_orders().await.len();
 
         info!("Cancelled {} orders in total", total_canceled);
 
@@ -615,6 +689,139 @@ mod tests {
         assert!(config.enable_auto_position_reduction);
         assert_eq!(config.position_reduction_factor, 0.5);
         assert!(config.enable_auto_order_cancellation);
+        assert!(!config.enable_order_resizing);
+    }
+
+    #[tokio::test]
+    async fn test_risk_manager_resizes_orders() {
+        use crate::risk::rules::{PositionSizeRule, TotalExposureRule};
+        use crate::traits::TimeInForce;
+
+        let risk_engine = RiskEngine::new();
+        risk_engine
+            .add_rule(Box::new(PositionSizeRule::new()))
+            .await;
+        risk_engine
+            .add_rule(Box::new(TotalExposureRule::new(
+                Price::from_str("150000").unwrap(),
+            )))
+            .await;
+        risk_engine
+            .set_max_position_size("BTCUSDT", Size::from_str("5").unwrap())
+            .await;
+        risk_engine
+            .update_position(
+                "BTCUSDT",
+                crate::core::events::Position {
+                    symbol: crate::types::Symbol::new("BTCUSDT"),
+                    exchange_id: "binance".to_string(),
+                    size: Size::from_str("1").unwrap(),
+                    average_price: Some(Price::from_str("50000").unwrap()),
+                    unrealized_pnl: None,
+                },
+            )
+            .await;
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("4").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+
+        // Binary reject by default, raised as a warning
+        let alerts = Arc::new(AlertManager::new(10));
+        let risk_manager = RiskManager::new(
+            RiskManagerConfig::default(),
+            risk_engine.clone(),
+            ShadowLedger::new(),
+            Duration::from_secs(60),
+        )
+        .with_alert_manager(alerts.clone());
+        assert!(risk_manager.check_order(&order).await.is_err());
+        let raised = alerts.get_alerts_by_level(AlertLevel::Warning).await;
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].component, "risk_manager");
+
+        // Position allows 4 more, exposure only 2 more (100k of 150k left)
+        let config = RiskManagerConfig {
+            enable_order_resizing: true,
+            ..Default::default()
+        };
+        let risk_manager = RiskManager::new(
+            config,
+            risk_engine.clone(),
+            ShadowLedger::new(),
+            Duration::from_secs(60),
+        );
+        let checked = risk_manager.check_order(&order).await.unwrap();
+        assert!(checked.is_resized());
+        assert_eq!(checked.order.size, Size::from_str("2").unwrap());
+        assert_eq!(checked.original_size, Some(Size::from_str("4").unwrap()));
+        assert_eq!(checked.violation.unwrap().rule, "TotalExposureLimit");
+
+        // A halt is never resized around
+        risk_engine.halt_trading("manual").await;
+        assert!(risk_manager.check_order(&order).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resized_orders_do_not_enforce_breach_actions() {
+        use crate::connectors::MockExecutionClient;
+        use crate::risk::rules::{BreachAction, TotalExposureRule};
+        use crate::risk::KillSwitch;
+        use crate::traits::{ExecutionClient, TimeInForce};
+
+        let client = Arc::new(MockExecutionClient::new());
+        let risk_engine = RiskEngine::new()
+            .with_kill_switch(KillSwitch::new().with_violation_rules(["TotalExposureLimit"]))
+            .with_execution_client("binance", client.clone());
+        risk_engine
+            .add_rule(Box::new(TotalExposureRule::new(
+                Price::from_str("150000").unwrap(),
+            )))
+            .await;
+        risk_engine
+            .set_breach_action("TotalExposureLimit", BreachAction::FlattenSymbol)
+            .await;
+        risk_engine
+            .update_reference_price("BTCUSDT", Price::from_str("50000").unwrap())
+            .await;
+        let position = |size: &str| crate::core::events::Position {
+            symbol: crate::types::Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            size: Size::from_str(size).unwrap(),
+            average_price: Some(Price::from_str("50000").unwrap()),
+            unrealized_pnl: None,
+        };
+        risk_engine.update_position("BTCUSDT", position("1")).await;
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("4").unwrap(),
+            Price::from_str("50000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let config = RiskManagerConfig {
+            enable_order_resizing: true,
+            ..Default::default()
+        };
+        let risk_manager = RiskManager::new(
+            config,
+            risk_engine.clone(),
+            ShadowLedger::new(),
+            Duration::from_secs(60),
+        );
+
+        // Resized to fit: no flatten order and no halt
+        let checked = risk_manager.check_order(&order).await.unwrap();
+        assert_eq!(checked.order.size, Size::from_str("2").unwrap());
+        assert!(client.get_open_orders(None).await.unwrap().is_empty());
+        assert!(!risk_engine.is_halted().await);
+
+        // No room left at any size: rejected and enforced exactly once
+        risk_engine.update_position("BTCUSDT", position("3")).await;
+        assert!(risk_manager.check_order(&order).await.is_err());
+        assert_eq!(client.get_open_orders(None).await.unwrap().len(), 1);
+        assert!(risk_engine.is_halted().await);
     }
 
     #[tokio::test]
@@ -659,6 +866,7 @@ mod tests {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64,
+            reject_reason: None,
         };
 
         // Handle the execution report
@@ -696,7 +904,12 @@ mod tests {
             unrealized_pnl: None,
         };
 
-        risk_manager.risk_engine.write().await.update_position("BTCUSDT", position).await;
+        risk_manager
+            .risk_engine
+            .write()
+            .await
+            .update_position("BTCUSDT", position)
+            .await;
 
         // Check risk limits - violations depend on implementation details
         let violations = risk_manager.check_risk_limits().await.unwrap();
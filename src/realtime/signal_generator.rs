@@ -1,5 +1,7 @@
+use crate::core::events::MarketEvent;
+use crate::indicators::{FlowSnapshot, OrderFlowMonitor, ToxicFlowFilter};
 use crate::strategy::{MarketState, Signal, Strategy};
-use crate::traits::{NewOrder, TimeInForce};
+use crate::traits::{NewOrder, OrderType, TimeInForce};
 use crate::types::{Price, Size};
 use log::{debug, warn};
 use rust_decimal::prelude::*;
@@ -63,6 +65,10 @@ where
     signal_cooldowns: Arc<RwLock<HashMap<String, std::time::Duration>>>,
     /// Current volatility by symbol
     volatilities: Arc<RwLock<HashMap<String, Price>>>,
+    /// Order flow by symbol, kept only when toxic flow filtering is on
+    order_flow: Option<Arc<RwLock<OrderFlowMonitor>>>,
+    /// Limits for pulling resting quotes during toxic flow
+    toxic_flow_filter: Option<ToxicFlowFilter>,
 }
 
 impl<S> SignalGenerator<S>
@@ -78,9 +84,56 @@ where
             last_signal_times: Arc::new(RwLock::new(HashMap::new())),
             signal_cooldowns: Arc::new(RwLock::new(HashMap::new())),
             volatilities: Arc::new(RwLock::new(HashMap::new())),
+            order_flow: None,
+            toxic_flow_filter: None,
         }
     }
 
+    /// Drop resting quotes while order flow over `window_ms` is toxic (builder pattern)
+    pub fn with_toxic_flow_filter(mut self, window_ms: u64, filter: ToxicFlowFilter) -> Self {
+        self.order_flow = Some(Arc::new(RwLock::new(OrderFlowMonitor::new(window_ms))));
+        self.toxic_flow_filter = Some(filter);
+        self
+    }
+
+    /// Feed a market event to the order flow indicators
+    pub async fn record_market_event(&self, event: &MarketEvent) {
+        if let Some(order_flow) = &self.order_flow {
+            order_flow.write().await.on_event(event);
+        }
+    }
+
+    /// Get the order flow of a symbol, if toxic flow filtering is on
+    pub async fn flow_snapshot(&self, symbol: &str) -> Option<FlowSnapshot> {
+        self.order_flow.as_ref()?.read().await.snapshot(symbol)
+    }
+
+    /// Drop a resting quote on the side toxic flow would run over
+    /// Other signals, and orders that take liquidity, pass through unchanged.
+    pub async fn filter_signal(&self, signal: Signal) -> Option<Signal> {
+        let Some(filter) = &self.toxic_flow_filter else {
+            return Some(signal);
+        };
+        if let Signal::PlaceOrder { order } = &signal {
+            let resting = matches!(
+                order.order_type,
+                OrderType::Limit | OrderType::Iceberg { .. }
+            ) && order.time_in_force == TimeInForce::GoodTillCancelled;
+            if resting {
+                if let Some(flow) = self.flow_snapshot(order.symbol.value()).await {
+                    if !filter.allows(&flow, order.side) {
+                        debug!(
+                            "Dropping {:?} quote on {}: toxic flow {:?}",
+                            order.side, order.symbol, flow
+                        );
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(signal)
+    }
+
     /// Update market state for a symbol
     pub async fn update_market_state(&self, symbol: &str, market_state: MarketState) {
         // Update volatility if enabled (do this before moving market_state)
@@ -468,6 +521,52 @@ mod tests {
         assert_eq!(retrieved_state.unwrap().symbol, "BTCUSDT");
     }
 
+    #[tokio::test]
+    async fn test_filter_signal_drops_quotes_against_toxic_flow() {
+        let strategy = SimpleArbitrageStrategy::new(
+            Price::from_str("0.5").unwrap(),
+            Size::from_str("0.1").unwrap(),
+            Size::from_str("1.0").unwrap(),
+        );
+        let generator = SignalGenerator::new(SignalGeneratorConfig::default(), strategy)
+            .with_toxic_flow_filter(1_000, ToxicFlowFilter::default());
+        for timestamp in 0..3 {
+            generator
+                .record_market_event(&MarketEvent::Trade(crate::core::events::Trade {
+                    symbol: "BTCUSDT".into(),
+                    exchange_id: "binance".to_string(),
+                    price: Price::from_str("50000").unwrap(),
+                    size: Size::from_str("1").unwrap(),
+                    side: OrderSide::Buy,
+                    timestamp,
+                    trade_id: None,
+                }))
+                .await;
+        }
+        assert_eq!(
+            generator.flow_snapshot("BTCUSDT").await.unwrap().flow_ratio,
+            Some(1.0)
+        );
+
+        let quote = |order: NewOrder| Signal::PlaceOrder { order };
+        let ask = NewOrder::new_limit_sell(
+            "BTCUSDT".to_string(),
+            Size::from_str("0.1").unwrap(),
+            Price::from_str("50001").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let bid = NewOrder::new_limit_buy(
+            "BTCUSDT".to_string(),
+            Size::from_str("0.1").unwrap(),
+            Price::from_str("49999").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let take = NewOrder::new_market_sell("BTCUSDT".to_string(), Size::from_str("0.1").unwrap());
+        assert!(generator.filter_signal(quote(ask)).await.is_none());
+        assert!(generator.filter_signal(quote(bid)).await.is_some());
+        assert!(generator.filter_signal(quote(take)).await.is_some());
+    }
+
     #[tokio::test]
     async fn test_signal_generator_cooldown() {
         let config = SignalGeneratorConfig::default();
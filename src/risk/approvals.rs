@@ -0,0 +1,521 @@
+use crate::monitoring::audit::AuditLog;
+use crate::risk::rules::RiskEngine;
+use crate::types::{Price, Size};
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// A risk limit that can be adjusted at runtime
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "limit", content = "symbol", rename_all = "snake_case")]
+pub enum LimitKey {
+    MaxPositionSize(String),
+    MaxOrderSize(String),
+    MaxDailyLoss(String),
+    MaxTotalExposure,
+    MaxOpenOrders,
+}
+
+impl fmt::Display for LimitKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitKey::MaxPositionSize(symbol) => write!(f, "max_position_size:{}", symbol),
+            LimitKey::MaxOrderSize(symbol) => write!(f, "max_order_size:{}", symbol),
+            LimitKey::MaxDailyLoss(symbol) => write!(f, "max_daily_loss:{}", symbol),
+            LimitKey::MaxTotalExposure => write!(f, "max_total_exposure"),
+            LimitKey::MaxOpenOrders => write!(f, "max_open_orders"),
+        }
+    }
+}
+
+impl std::str::FromStr for LimitKey {
+    type Err = String;
+
+    /// Parse the `Display` form, e.g. "max_position_size:BTCUSDT" or "max_open_orders"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, symbol) = match s.split_once(':') {
+            Some((name, symbol)) => (name, Some(symbol.to_string())),
+            None => (s, None),
+        };
+        let with_symbol = |key: fn(String) -> LimitKey| {
+            symbol
+                .clone()
+                .filter(|symbol| !symbol.is_empty())
+                .map(key)
+                .ok_or_else(|| format!("Limit '{}' requires a symbol, e.g. {}:BTCUSDT", name, name))
+        };
+        match name.replace('-', "_").as_str() {
+            "max_position_size" => with_symbol(LimitKey::MaxPositionSize),
+            "max_order_size" => with_symbol(LimitKey::MaxOrderSize),
+            "max_daily_loss" => with_symbol(LimitKey::MaxDailyLoss),
+            "max_total_exposure" if symbol.is_none() => Ok(LimitKey::MaxTotalExposure),
+            "max_open_orders" if symbol.is_none() => Ok(LimitKey::MaxOpenOrders),
+            _ => Err(format!("Unknown limit '{}'", s)),
+        }
+    }
+}
+
+/// Status of a limit override request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverrideStatus {
+    PendingApproval,
+    Approved,
+}
+
+/// A request to change a risk limit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverrideRequest {
+    pub request_id: String,
+    pub limit: LimitKey,
+    pub value: Decimal,
+    pub requested_by: String,
+    pub reason: String,
+    pub status: OverrideStatus,
+    pub approved_by: Option<String>,
+}
+
+impl OverrideRequest {
+    /// Canonical payload signed by the approver
+    fn signing_payload(&self, approver: &str) -> String {
+        format!(
+            "{}|{}|{}|{}|{}",
+            self.request_id, self.limit, self.value, self.requested_by, approver
+        )
+    }
+}
+
+/// Approval of an override request, signed by a second operator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalToken {
+    pub request_id: String,
+    pub approver: String,
+    /// Base64 HMAC-SHA256 of the request payload under the approver's secret
+    pub signature: String,
+}
+
+impl ApprovalToken {
+    /// Sign an approval for a request with the approver's secret
+    pub fn sign(request: &OverrideRequest, approver: &str, secret: &str) -> Self {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(request.signing_payload(approver).as_bytes());
+        Self {
+            request_id: request.request_id.clone(),
+            approver: approver.to_string(),
+            signature: general_purpose::STANDARD.encode(mac.finalize().into_bytes()),
+        }
+    }
+
+    /// Verify the signature against the approver's secret
+    fn verify(&self, request: &OverrideRequest, secret: &str) -> bool {
+        let Ok(signature) = general_purpose::STANDARD.decode(&self.signature) else {
+            return false;
+        };
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(request.signing_payload(&self.approver).as_bytes());
+        mac.verify_slice(&signature).is_ok()
+    }
+}
+
+/// Result of submitting a limit change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverrideOutcome {
+    /// The value is within the hard cap and was applied immediately
+    Applied,
+    /// The value exceeds the hard cap and needs a second operator's approval
+    PendingApproval(OverrideRequest),
+}
+
+/// Limit override errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalError {
+    UnknownOperator(String),
+    UnknownRequest(String),
+    AlreadyApproved(String),
+    SelfApproval(String),
+    InvalidSignature(String),
+    InvalidValue(String),
+}
+
+impl fmt::Display for ApprovalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalError::UnknownOperator(id) => write!(f, "Unknown operator '{}'", id),
+            ApprovalError::UnknownRequest(id) => write!(f, "Unknown override request '{}'", id),
+            ApprovalError::AlreadyApproved(id) => {
+                write!(f, "Override request '{}' is already approved", id)
+            }
+            ApprovalError::SelfApproval(id) => {
+                write!(f, "Operator '{}' cannot approve their own request", id)
+            }
+            ApprovalError::InvalidSignature(id) => {
+                write!(f, "Invalid approval signature for request '{}'", id)
+            }
+            ApprovalError::InvalidValue(msg) => write!(f, "Invalid limit value: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ApprovalError {}
+
+/// Two-person approval workflow for risk limit overrides
+///
+/// Limit changes up to the configured hard cap apply immediately. Raising a limit
+/// above its hard cap creates a pending request that only takes effect once a
+/// different registered operator submits a signed `ApprovalToken`. Requests,
+/// approvals and rejected attempts are recorded in the audit log.
+pub struct LimitOverrideManager {
+    risk_engine: RiskEngine,
+    audit_log: Arc<AuditLog>,
+    /// Operator secrets used to verify approval signatures
+    operators: HashMap<String, String>,
+    /// Hard caps by limit
+    hard_caps: HashMap<LimitKey, Decimal>,
+    /// Override requests by ID
+    requests: RwLock<HashMap<String, OverrideRequest>>,
+}
+
+impl LimitOverrideManager {
+    /// Create a new override manager
+    pub fn new(risk_engine: RiskEngine, audit_log: Arc<AuditLog>) -> Self {
+        Self {
+            risk_engine,
+            audit_log,
+            operators: HashMap::new(),
+            hard_caps: HashMap::new(),
+            requests: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Register an operator and their approval secret (builder pattern)
+    pub fn with_operator(mut self, operator: impl Into<String>, secret: impl Into<String>) -> Self {
+        self.operators.insert(operator.into(), secret.into());
+        self
+    }
+
+    /// Set the hard cap of a limit (builder pattern)
+    pub fn with_hard_cap(mut self, limit: LimitKey, cap: Decimal) -> Self {
+        self.hard_caps.insert(limit, cap);
+        self
+    }
+
+    /// Get the risk engine whose limits are managed
+    pub fn risk_engine(&self) -> &RiskEngine {
+        &self.risk_engine
+    }
+
+    /// Get the audit log
+    pub fn audit_log(&self) -> &Arc<AuditLog> {
+        &self.audit_log
+    }
+
+    /// Get the hard cap of a limit
+    pub fn hard_cap(&self, limit: &LimitKey) -> Option<Decimal> {
+        self.hard_caps.get(limit).copied()
+    }
+
+    /// Request a limit change
+    pub async fn request_limit(
+        &self,
+        operator: &str,
+        limit: LimitKey,
+        value: Decimal,
+        reason: &str,
+    ) -> Result<OverrideOutcome, ApprovalError> {
+        if !self.operators.contains_key(operator) {
+            self.audit_log
+                .record(
+                    operator,
+                    "limit_override_rejected",
+                    format!("unknown operator requested {}={}", limit, value),
+                )
+                .await;
+            return Err(ApprovalError::UnknownOperator(operator.to_string()));
+        }
+        if value.is_sign_negative() {
+            return Err(ApprovalError::InvalidValue(format!(
+                "{} must not be negative",
+                limit
+            )));
+        }
+
+        let within_cap = self.hard_cap(&limit).is_none_or(|cap| value <= cap);
+        if within_cap {
+            self.apply(&limit, value).await?;
+            self.audit_log
+                .record(
+                    operator,
+                    "limit_set",
+                    format!("{}={} reason={}", limit, value, reason),
+                )
+                .await;
+            return Ok(OverrideOutcome::Applied);
+        }
+
+        let request = OverrideRequest {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            limit,
+            value,
+            requested_by: operator.to_string(),
+            reason: reason.to_string(),
+            status: OverrideStatus::PendingApproval,
+            approved_by: None,
+        };
+        self.audit_log
+            .record(
+                operator,
+                "limit_override_requested",
+                format!(
+                    "request={} {}={} cap={} reason={}",
+                    request.request_id,
+                    request.limit,
+                    request.value,
+                    self.hard_cap(&request.limit).unwrap_or_default(),
+                    reason
+                ),
+            )
+            .await;
+        self.requests
+            .write()
+            .await
+            .insert(request.request_id.clone(), request.clone());
+
+        Ok(OverrideOutcome::PendingApproval(request))
+    }
+
+    /// Approve a pending request with a token signed by a second operator
+    pub async fn approve(&self, token: &ApprovalToken) -> Result<OverrideRequest, ApprovalError> {
+        let mut requests = self.requests.write().await;
+        let request = requests
+            .get_mut(&token.request_id)
+            .ok_or_else(|| ApprovalError::UnknownRequest(token.request_id.clone()))?;
+
+        let error = if request.status == OverrideStatus::Approved {
+            Some(ApprovalError::AlreadyApproved(request.request_id.clone()))
+        } else if token.approver == request.requested_by {
+            Some(ApprovalError::SelfApproval(token.approver.clone()))
+        } else {
+            match self.operators.get(&token.approver) {
+                None => Some(ApprovalError::UnknownOperator(token.approver.clone())),
+                Some(secret) if !token.verify(request, secret) => {
+                    Some(ApprovalError::InvalidSignature(request.request_id.clone()))
+                }
+                Some(_) => None,
+            }
+        };
+        if let Some(error) = error {
+            self.audit_log
+                .record(
+                    &token.approver,
+                    "limit_override_rejected",
+                    format!("request={} {}", request.request_id, error),
+                )
+                .await;
+            return Err(error);
+        }
+
+        self.apply(&request.limit, request.value).await?;
+        request.status = OverrideStatus::Approved;
+        request.approved_by = Some(token.approver.clone());
+        self.audit_log
+            .record(
+                &token.approver,
+                "limit_override_approved",
+                format!(
+                    "request={} {}={} requested_by={}",
+                    request.request_id, request.limit, request.value, request.requested_by
+                ),
+            )
+            .await;
+
+        Ok(request.clone())
+    }
+
+    /// Get a request by ID
+    pub async fn get_request(&self, request_id: &str) -> Option<OverrideRequest> {
+        self.requests.read().await.get(request_id).cloned()
+    }
+
+    /// Get all requests awaiting approval
+    pub async fn pending_requests(&self) -> Vec<OverrideRequest> {
+        let requests = self.requests.read().await;
+        requests
+            .values()
+            .filter(|r| r.status == OverrideStatus::PendingApproval)
+            .cloned()
+            .collect()
+    }
+
+    /// Apply a limit value to the risk engine
+    async fn apply(&self, limit: &LimitKey, value: Decimal) -> Result<(), ApprovalError> {
+        match limit {
+            LimitKey::MaxPositionSize(symbol) => {
+                self.risk_engine
+                    .set_max_position_size(symbol, Size::new(value))
+                    .await
+            }
+            LimitKey::MaxOrderSize(symbol) => {
+                self.risk_engine
+                    .set_max_order_size(symbol, Size::new(value))
+                    .await
+            }
+            LimitKey::MaxDailyLoss(symbol) => {
+                self.risk_engine
+                    .set_max_daily_loss(symbol, Price::new(value))
+                    .await
+            }
+            LimitKey::MaxTotalExposure => {
+                self.risk_engine
+                    .set_max_total_exposure(Price::new(value))
+                    .await
+            }
+            LimitKey::MaxOpenOrders => {
+                let count = value
+                    .trunc()
+                    .to_usize()
+                    .ok_or_else(|| ApprovalError::InvalidValue(value.to_string()))?;
+                self.risk_engine.set_max_open_orders(count).await
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(risk_engine: RiskEngine, audit_log: Arc<AuditLog>) -> LimitOverrideManager {
+        LimitOverrideManager::new(risk_engine, audit_log)
+            .with_operator("alice", "alice-secret")
+            .with_operator("bob", "bob-secret")
+            .with_hard_cap(
+                LimitKey::MaxPositionSize("BTCUSDT".to_string()),
+                Decimal::new(10, 0),
+            )
+    }
+
+    #[tokio::test]
+    async fn test_limit_within_cap_applies_immediately() {
+        let risk_engine = RiskEngine::new();
+        let audit_log = Arc::new(AuditLog::new());
+        let manager = manager(risk_engine.clone(), audit_log.clone());
+
+        let outcome = manager
+            .request_limit(
+                "alice",
+                LimitKey::MaxPositionSize("BTCUSDT".to_string()),
+                Decimal::new(8, 0),
+                "more room",
+            )
+            .await
+            .unwrap();
+        assert_eq!(outcome, OverrideOutcome::Applied);
+        assert_eq!(
+            risk_engine.get_max_position_size("BTCUSDT").await,
+            Size::new(Decimal::new(8, 0))
+        );
+        assert_eq!(audit_log.entries_for_action("limit_set").await.len(), 1);
+
+        assert_eq!(
+            manager
+                .request_limit("mallory", LimitKey::MaxOpenOrders, Decimal::ONE, "")
+                .await,
+            Err(ApprovalError::UnknownOperator("mallory".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_override_above_cap_requires_second_operator() {
+        let risk_engine = RiskEngine::new();
+        risk_engine
+            .set_max_position_size("BTCUSDT", Size::new(Decimal::new(5, 0)))
+            .await;
+        let audit_log = Arc::new(AuditLog::new());
+        let manager = manager(risk_engine.clone(), audit_log.clone());
+
+        let OverrideOutcome::PendingApproval(request) = manager
+            .request_limit(
+                "alice",
+                LimitKey::MaxPositionSize("BTCUSDT".to_string()),
+                Decimal::new(20, 0),
+                "event hedge",
+            )
+            .await
+            .unwrap()
+        else {
+            panic!("override above the hard cap must wait for approval");
+        };
+        assert_eq!(
+            risk_engine.get_max_position_size("BTCUSDT").await,
+            Size::new(Decimal::new(5, 0))
+        );
+
+        // The requester cannot approve, and a wrong secret is rejected
+        let own = ApprovalToken::sign(&request, "alice", "alice-secret");
+        assert_eq!(
+            manager.approve(&own).await,
+            Err(ApprovalError::SelfApproval("alice".to_string()))
+        );
+        let forged = ApprovalToken::sign(&request, "bob", "guess");
+        assert_eq!(
+            manager.approve(&forged).await,
+            Err(ApprovalError::InvalidSignature(request.request_id.clone()))
+        );
+
+        let token = ApprovalToken::sign(&request, "bob", "bob-secret");
+        let approved = manager.approve(&token).await.unwrap();
+        assert_eq!(approved.approved_by.as_deref(), Some("bob"));
+        assert_eq!(
+            risk_engine.get_max_position_size("BTCUSDT").await,
+            Size::new(Decimal::new(20, 0))
+        );
+        assert!(manager.pending_requests().await.is_empty());
+        assert_eq!(
+            manager.approve(&token).await,
+            Err(ApprovalError::AlreadyApproved(request.request_id.clone()))
+        );
+
+        let actions: Vec<String> = audit_log
+            .entries()
+            .await
+            .into_iter()
+            .map(|e| e.action)
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                "limit_override_requested",
+                "limit_override_rejected",
+                "limit_override_rejected",
+                "limit_override_approved",
+                "limit_override_rejected",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_limit_key_parse() {
+        for key in [
+            LimitKey::MaxPositionSize("BTCUSDT".to_string()),
+            LimitKey::MaxDailyLoss("ETHUSDT".to_string()),
+            LimitKey::MaxOpenOrders,
+        ] {
+            assert_eq!(key.to_string().parse::<LimitKey>(), Ok(key));
+        }
+        assert_eq!(
+            "max-total-exposure".parse::<LimitKey>(),
+            Ok(LimitKey::MaxTotalExposure)
+        );
+        assert!("max_order_size".parse::<LimitKey>().is_err());
+        assert!("max_leverage".parse::<LimitKey>().is_err());
+    }
+}
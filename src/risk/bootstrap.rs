@@ -0,0 +1,441 @@
+use crate::core::events::{Balance, Position};
+use crate::risk::rules::RiskEngine;
+use crate::risk::shadow_ledger::{PositionRecord, ShadowLedger};
+use crate::traits::{ExecutionClient, TradeHistory};
+use crate::types::{Price, Size, Symbol};
+use chrono::Utc;
+use log::{info, warn};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Decimal places kept when deriving size limits
+const LIMIT_DECIMALS: u32 = 8;
+
+/// Cold-start bootstrap configuration
+#[derive(Debug, Clone)]
+pub struct BootstrapConfig {
+    /// Exchange the account lives on, as recorded in the ledger
+    pub exchange_id: String,
+    /// Asset account equity is measured in
+    pub quote_asset: String,
+    /// Symbols traded, used to turn spot balances into positions (e.g. BTCUSDT)
+    pub symbols: Vec<String>,
+    /// Share of equity allowed as total exposure
+    pub exposure_fraction: Decimal,
+    /// Share of equity allowed in one symbol
+    pub position_fraction: Decimal,
+    /// Share of equity allowed in one order
+    pub order_fraction: Decimal,
+    /// Share of equity that may be lost in one symbol per day
+    pub daily_loss_fraction: Decimal,
+    /// Room left above positions already held, so they do not breach the new limits
+    pub headroom: Decimal,
+    /// Maximum number of open orders
+    pub max_open_orders: usize,
+    /// Apply the recommended limits to the risk engine
+    pub apply_limits: bool,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            exchange_id: "binance".to_string(),
+            quote_asset: "USDT".to_string(),
+            symbols: Vec::new(),
+            exposure_fraction: Decimal::new(5, 1),
+            position_fraction: Decimal::new(2, 1),
+            order_fraction: Decimal::new(2, 2),
+            daily_loss_fraction: Decimal::new(2, 2),
+            headroom: Decimal::new(11, 1),
+            max_open_orders: 50,
+            apply_limits: true,
+        }
+    }
+}
+
+/// Bootstrap error
+#[derive(Debug, Clone)]
+pub enum BootstrapError {
+    /// The ledger already holds state, so there is nothing to bootstrap
+    LedgerNotEmpty,
+    /// An exchange query failed
+    Exchange(String),
+}
+
+impl fmt::Display for BootstrapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BootstrapError::LedgerNotEmpty => write!(f, "Ledger is not empty"),
+            BootstrapError::Exchange(msg) => write!(f, "Exchange query failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BootstrapError {}
+
+/// Conservative limits derived from the account at startup
+#[derive(Debug, Clone)]
+pub struct RecommendedLimits {
+    /// Maximum total exposure
+    pub max_total_exposure: Price,
+    /// Maximum position size by symbol
+    pub max_position_sizes: HashMap<String, Size>,
+    /// Maximum order size by symbol
+    pub max_order_sizes: HashMap<String, Size>,
+    /// Maximum daily loss by symbol
+    pub max_daily_losses: HashMap<String, Price>,
+    /// Maximum number of open orders
+    pub max_open_orders: usize,
+}
+
+impl RecommendedLimits {
+    /// Apply the limits to a risk engine
+    pub async fn apply(&self, engine: &RiskEngine) {
+        engine.set_max_total_exposure(self.max_total_exposure).await;
+        for (symbol, size) in &self.max_position_sizes {
+            engine.set_max_position_size(symbol, *size).await;
+        }
+        for (symbol, size) in &self.max_order_sizes {
+            engine.set_max_order_size(symbol, *size).await;
+        }
+        for (symbol, loss) in &self.max_daily_losses {
+            engine.set_max_daily_loss(symbol, *loss).await;
+        }
+        engine.set_max_open_orders(self.max_open_orders).await;
+    }
+}
+
+/// Outcome of a cold-start bootstrap
+#[derive(Debug, Clone)]
+pub struct BootstrapReport {
+    /// Balances loaded into the risk engine
+    pub balances: Vec<Balance>,
+    /// Positions seeded into the ledger and risk engine
+    pub positions: Vec<Position>,
+    /// Open orders found on the exchange
+    pub open_orders: usize,
+    /// Account equity in the quote asset, counting only assets with a mark price
+    pub equity: Decimal,
+    /// Current exposure in the quote asset
+    pub exposure: Decimal,
+    /// Limits derived from equity and the positions held
+    pub limits: RecommendedLimits,
+    /// Symbols without a mark price, which get no per-symbol limits
+    pub unpriced_symbols: Vec<String>,
+}
+
+/// Seeds the risk engine and shadow ledger from the exchange on a fresh start
+///
+/// Without a journal to replay, the ledger would otherwise start flat while the account
+/// holds balances, positions and open orders, and the first risk checks would run against
+/// a state that does not exist.
+#[derive(Debug, Clone, Default)]
+pub struct ColdStartBootstrap {
+    config: BootstrapConfig,
+}
+
+impl ColdStartBootstrap {
+    /// Create a new bootstrap
+    pub fn new(config: BootstrapConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &BootstrapConfig {
+        &self.config
+    }
+
+    /// Derive positions from spot balances of the configured symbols' base assets
+    fn spot_positions(
+        &self,
+        balances: &[Balance],
+        marks: &HashMap<String, Price>,
+    ) -> Vec<Position> {
+        self.config
+            .symbols
+            .iter()
+            .filter_map(|symbol| {
+                let base = symbol.strip_suffix(self.config.quote_asset.as_str())?;
+                let balance = balances.iter().find(|b| b.asset == base)?;
+                if balance.total.is_zero() {
+                    return None;
+                }
+                Some(Position {
+                    symbol: Symbol::new(symbol),
+                    exchange_id: self.config.exchange_id.clone(),
+                    size: Size::new(balance.total),
+                    // Spot balances carry no cost basis, so mark them at the current price
+                    average_price: marks.get(symbol).copied(),
+                    unrealized_pnl: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Derive conservative limits from equity, keeping room for positions already held
+    fn recommend_limits(
+        &self,
+        equity: Decimal,
+        positions: &[Position],
+        open_orders: usize,
+        marks: &HashMap<String, Price>,
+    ) -> (RecommendedLimits, Vec<String>) {
+        let config = &self.config;
+        let mut limits = RecommendedLimits {
+            max_total_exposure: Price::zero(),
+            max_position_sizes: HashMap::new(),
+            max_order_sizes: HashMap::new(),
+            max_daily_losses: HashMap::new(),
+            max_open_orders: config.max_open_orders.max(open_orders),
+        };
+        let mut unpriced = Vec::new();
+        let mut exposure = Decimal::ZERO;
+
+        let mut symbols = config.symbols.clone();
+        for position in positions {
+            if !symbols.iter().any(|s| s == position.symbol.value()) {
+                symbols.push(position.symbol.value().to_string());
+            }
+        }
+
+        for symbol in symbols {
+            let held = positions
+                .iter()
+                .find(|p| p.symbol.value() == symbol)
+                .map(|p| p.size.abs().value())
+                .unwrap_or(Decimal::ZERO);
+            let mark = match marks.get(&symbol) {
+                Some(mark) if mark.is_positive() => mark.value(),
+                _ => {
+                    unpriced.push(symbol);
+                    continue;
+                }
+            };
+            exposure += held * mark;
+
+            let position_cap =
+                (equity * config.position_fraction / mark).max(held * config.headroom);
+            let order_cap = equity * config.order_fraction / mark;
+            limits.max_position_sizes.insert(
+                symbol.clone(),
+                Size::new(
+                    position_cap.round_dp_with_strategy(LIMIT_DECIMALS, RoundingStrategy::ToZero),
+                ),
+            );
+            limits.max_order_sizes.insert(
+                symbol.clone(),
+                Size::new(
+                    order_cap.round_dp_with_strategy(LIMIT_DECIMALS, RoundingStrategy::ToZero),
+                ),
+            );
+            limits
+                .max_daily_losses
+                .insert(symbol, Price::new(equity * config.daily_loss_fraction));
+        }
+
+        limits.max_total_exposure =
+            Price::new((equity * config.exposure_fraction).max(exposure * config.headroom));
+        (limits, unpriced)
+    }
+
+    /// Seed the risk engine and ledger from the exchange
+    /// `marks` holds the current price by symbol, used to value balances and positions.
+    /// Fails without touching anything if the ledger already holds state.
+    pub async fn run<C>(
+        &self,
+        client: &C,
+        engine: &RiskEngine,
+        ledger: &ShadowLedger,
+        marks: &HashMap<String, Price>,
+    ) -> Result<BootstrapReport, BootstrapError>
+    where
+        C: ExecutionClient + TradeHistory + Sync,
+    {
+        if !ledger.is_empty().await {
+            return Err(BootstrapError::LedgerNotEmpty);
+        }
+
+        let balances = ExecutionClient::get_balances(client)
+            .await
+            .map_err(|e| BootstrapError::Exchange(format!("balances: {}", e)))?;
+        let exchange_positions = TradeHistory::get_positions(client)
+            .await
+            .map_err(|e| BootstrapError::Exchange(format!("positions: {}", e)))?;
+        let open_orders = ExecutionClient::get_open_orders(client, None)
+            .await
+            .map_err(|e| BootstrapError::Exchange(format!("open orders: {}", e)))?;
+
+        let positions = match exchange_positions {
+            Some(positions) => positions
+                .into_iter()
+                .filter(|p| !p.size.is_zero())
+                .map(|p| Position {
+                    average_price: p
+                        .entry_price
+                        .or_else(|| marks.get(p.symbol.value()).copied()),
+                    symbol: p.symbol,
+                    exchange_id: p.exchange_id,
+                    size: p.size,
+                    unrealized_pnl: None,
+                })
+                .collect(),
+            None => self.spot_positions(&balances, marks),
+        };
+
+        let quote = &self.config.quote_asset;
+        let equity: Decimal = balances
+            .iter()
+            .filter_map(|b| {
+                if &b.asset == quote {
+                    Some(b.total)
+                } else {
+                    marks
+                        .get(&format!("{}{}", b.asset, quote))
+                        .map(|mark| b.total * mark.value())
+                }
+            })
+            .sum();
+
+        let (limits, unpriced_symbols) =
+            self.recommend_limits(equity, &positions, open_orders.len(), marks);
+        let exposure = positions
+            .iter()
+            .filter_map(|p| {
+                marks
+                    .get(p.symbol.value())
+                    .map(|m| p.size.abs().value() * m.value())
+            })
+            .sum();
+
+        for balance in &balances {
+            engine
+                .update_balance(&balance.asset, Size::new(balance.free))
+                .await;
+        }
+        for position in &positions {
+            engine
+                .update_position(position.symbol.value(), position.clone())
+                .await;
+            let mut record =
+                PositionRecord::new(position.symbol.clone(), position.exchange_id.clone());
+            record.size = position.size;
+            record.average_price = position.average_price;
+            record.total_cost = position
+                .average_price
+                .map(|price| price.value() * position.size.value())
+                .unwrap_or(Decimal::ZERO);
+            record.last_updated = Utc::now();
+            ledger.seed_position(record).await;
+        }
+        for _ in &open_orders {
+            engine.increment_open_orders().await;
+        }
+        if self.config.apply_limits {
+            limits.apply(engine).await;
+        }
+
+        if !unpriced_symbols.is_empty() {
+            warn!(
+                "Bootstrap: no mark price for {:?}, no per-symbol limits derived",
+                unpriced_symbols
+            );
+        }
+        info!(
+            "Bootstrap: {} balances, {} positions, {} open orders, equity {} {}, exposure limit {}",
+            balances.len(),
+            positions.len(),
+            open_orders.len(),
+            equity,
+            quote,
+            limits.max_total_exposure
+        );
+
+        Ok(BootstrapReport {
+            balances,
+            positions,
+            open_orders: open_orders.len(),
+            equity,
+            exposure,
+            limits,
+            unpriced_symbols,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::MockExecutionClient;
+    use crate::core::events::NewOrder;
+    use crate::traits::TimeInForce;
+
+    #[tokio::test]
+    async fn test_bootstrap_from_spot_account() {
+        let client = MockExecutionClient::new();
+        client
+            .set_balance("BTC", Size::from_str("2").unwrap(), Size::zero())
+            .await;
+        client
+            .set_balance("USDT", Size::from_str("100000").unwrap(), Size::zero())
+            .await;
+        client
+            .place_order(NewOrder::new_limit_buy(
+                "BTCUSDT",
+                Size::from_str("0.1").unwrap(),
+                Price::from_str("40000").unwrap(),
+                TimeInForce::GoodTillCancelled,
+            ))
+            .await
+            .unwrap();
+
+        let engine = RiskEngine::new();
+        let ledger = ShadowLedger::new();
+        let marks = HashMap::from([("BTCUSDT".to_string(), Price::from_str("50000").unwrap())]);
+        let bootstrap = ColdStartBootstrap::new(BootstrapConfig {
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            ..Default::default()
+        });
+
+        let report = bootstrap
+            .run(&client, &engine, &ledger, &marks)
+            .await
+            .unwrap();
+        assert_eq!(report.equity, Decimal::from(200000));
+        assert_eq!(report.exposure, Decimal::from(100000));
+        assert_eq!(report.open_orders, 1);
+        assert_eq!(report.unpriced_symbols, vec!["ETHUSDT".to_string()]);
+
+        // The 2 BTC held exceed 20% of equity, so the position cap keeps 10% headroom over them
+        assert_eq!(
+            engine.get_max_position_size("BTCUSDT").await,
+            Size::from_str("2.2").unwrap()
+        );
+        assert_eq!(
+            engine.get_max_order_size("BTCUSDT").await,
+            Size::from_str("0.08").unwrap()
+        );
+        assert_eq!(
+            engine.get_max_total_exposure().await,
+            Price::from_str("110000").unwrap()
+        );
+        assert_eq!(engine.get_open_orders_count().await, 1);
+        assert_eq!(
+            engine.get_position("BTCUSDT").await.unwrap().size,
+            Size::from_str("2").unwrap()
+        );
+
+        let seeded = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+        assert_eq!(seeded.size, Size::from_str("2").unwrap());
+        assert_eq!(
+            seeded.average_price,
+            Some(Price::from_str("50000").unwrap())
+        );
+
+        // A second run would overwrite live state
+        assert!(matches!(
+            bootstrap.run(&client, &engine, &ledger, &marks).await,
+            Err(BootstrapError::LedgerNotEmpty)
+        ));
+    }
+}
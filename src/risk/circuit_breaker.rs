@@ -0,0 +1,361 @@
+use crate::core::events::{NewOrder, OrderId, TimeInForce};
+use crate::monitoring::supervisor::{Heartbeat, TaskSupervisor};
+use crate::risk::rules::RiskEngine;
+use crate::risk::shadow_ledger::ShadowLedger;
+use crate::traits::ExecutionClient;
+use crate::types::{Price, Size};
+use log::{error, info, warn};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Account-level max-loss circuit breaker configuration
+#[derive(Debug, Clone)]
+pub struct AccountLossConfig {
+    /// Maximum account loss (realized + unrealized), as a positive quote amount
+    pub max_loss: Decimal,
+    /// Maximum slippage from the mark price accepted by flattening orders (0.005 = 0.5%)
+    pub max_slippage: Decimal,
+    /// Interval between checks of the monitoring task
+    pub check_interval: Duration,
+}
+
+impl Default for AccountLossConfig {
+    fn default() -> Self {
+        Self {
+            max_loss: Decimal::new(1000, 0),
+            max_slippage: Decimal::new(5, 3),
+            check_interval: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Outcome of a breaker trip
+#[derive(Debug, Clone, Default)]
+pub struct BreachReport {
+    /// Account P&L at the time of the breach
+    pub account_pnl: Decimal,
+    /// Number of open orders cancelled
+    pub cancelled_orders: usize,
+    /// IOC orders sent to flatten positions
+    pub flatten_orders: Vec<OrderId>,
+    /// Errors encountered while cancelling or flattening
+    pub failures: Vec<String>,
+}
+
+/// Account-wide max-loss circuit breaker
+///
+/// Unlike the pre-trade rules, the breaker watches realized + unrealized P&L of the
+/// whole account continuously. On breach it halts the risk engine (the kill switch),
+/// cancels every open order and flattens all positions with IOC limit orders priced
+/// no further than `max_slippage` from the mark.
+pub struct AccountLossBreaker<C: ExecutionClient> {
+    config: AccountLossConfig,
+    shadow_ledger: Arc<ShadowLedger>,
+    risk_engine: RiskEngine,
+    client: Arc<C>,
+    last_breach: Arc<RwLock<Option<BreachReport>>>,
+}
+
+impl<C> AccountLossBreaker<C>
+where
+    C: ExecutionClient + Send + Sync + 'static,
+{
+    /// Create a new account loss breaker
+    pub fn new(
+        config: AccountLossConfig,
+        shadow_ledger: Arc<ShadowLedger>,
+        risk_engine: RiskEngine,
+        client: Arc<C>,
+    ) -> Self {
+        Self {
+            config,
+            shadow_ledger,
+            risk_engine,
+            client,
+            last_breach: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &AccountLossConfig {
+        &self.config
+    }
+
+    /// Check if the breaker has tripped
+    pub async fn is_tripped(&self) -> bool {
+        self.last_breach.read().await.is_some()
+    }
+
+    /// Get the report of the last trip
+    pub async fn last_breach(&self) -> Option<BreachReport> {
+        self.last_breach.read().await.clone()
+    }
+
+    /// Re-arm the breaker and resume trading (operator action)
+    pub async fn reset(&self) {
+        *self.last_breach.write().await = None;
+        self.risk_engine.resume_trading().await;
+        info!("Account loss breaker re-armed");
+    }
+
+    /// Check the account P&L against the limit, tripping the breaker on breach
+    /// Returns the breach report if this call tripped the breaker
+    pub async fn check(&self, market_prices: &HashMap<String, Price>) -> Option<BreachReport> {
+        if self.is_tripped().await {
+            return None;
+        }
+
+        let account_pnl = self.shadow_ledger.get_total_pnl(market_prices).await;
+        if account_pnl > -self.config.max_loss {
+            return None;
+        }
+
+        Some(self.trip(account_pnl, market_prices).await)
+    }
+
+    /// Trip the breaker: halt trading, cancel all orders and flatten positions
+    async fn trip(
+        &self,
+        account_pnl: Decimal,
+        market_prices: &HashMap<String, Price>,
+    ) -> BreachReport {
+        let reason = format!(
+            "Account loss limit breached: pnl={}, max_loss={}",
+            account_pnl, self.config.max_loss
+        );
+        error!("{}", reason);
+        self.risk_engine.halt_trading(reason).await;
+
+        let mut report = BreachReport {
+            account_pnl,
+            ..Default::default()
+        };
+        self.cancel_open_orders(&mut report).await;
+        self.flatten_positions(market_prices, &mut report).await;
+
+        info!(
+            "Account loss breaker tripped: cancelled={}, flatten_orders={}, failures={}",
+            report.cancelled_orders,
+            report.flatten_orders.len(),
+            report.failures.len()
+        );
+        *self.last_breach.write().await = Some(report.clone());
+        report
+    }
+
+    async fn cancel_open_orders(&self, report: &mut BreachReport) {
+        let open_orders = match self.client.get_open_orders(None).await {
+            Ok(orders) => orders,
+            Err(e) => {
+                report
+                    .failures
+                    .push(format!("Failed to fetch open orders: {}", e));
+                return;
+            }
+        };
+
+        for order in open_orders {
+            match self.client.cancel_order(order.order_id.clone()).await {
+                Ok(()) => report.cancelled_orders += 1,
+                Err(e) => report
+                    .failures
+                    .push(format!("Failed to cancel {}: {}", order.order_id, e)),
+            }
+        }
+    }
+
+    async fn flatten_positions(
+        &self,
+        market_prices: &HashMap<String, Price>,
+        report: &mut BreachReport,
+    ) {
+        for position in self.shadow_ledger.get_all_positions().await {
+            if position.size.is_zero() {
+                continue;
+            }
+
+            let symbol = position.symbol.value();
+            let Some(mark) = market_prices.get(symbol) else {
+                report
+                    .failures
+                    .push(format!("No mark price to flatten {}", symbol));
+                continue;
+            };
+
+            let size = Size::new(position.size.value().abs());
+            let mut order = if position.size.value() > Decimal::ZERO {
+                let limit = Price::new(mark.value() * (Decimal::ONE - self.config.max_slippage));
+                NewOrder::new_limit_sell(symbol, size, limit, TimeInForce::ImmediateOrCancel)
+            } else {
+                let limit = Price::new(mark.value() * (Decimal::ONE + self.config.max_slippage));
+                NewOrder::new_limit_buy(symbol, size, limit, TimeInForce::ImmediateOrCancel)
+            };
+            order.exchange_id = position.exchange_id.clone();
+
+            match self.client.place_order(order).await {
+                Ok(order_id) => report.flatten_orders.push(order_id),
+                Err(e) => {
+                    warn!("Failed to flatten {}: {}", symbol, e);
+                    report
+                        .failures
+                        .push(format!("Failed to flatten {}: {}", symbol, e));
+                }
+            }
+        }
+    }
+
+    /// Spawn the monitoring task, checking against the shared mark prices every interval
+    pub fn spawn(
+        self: Arc<Self>,
+        market_prices: Arc<RwLock<HashMap<String, Price>>>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(self.monitor(market_prices, None))
+    }
+
+    /// Run the monitoring task under a supervisor, which restarts it if it stops
+    /// heartbeating for ten check intervals
+    pub async fn supervise(
+        self: Arc<Self>,
+        supervisor: &TaskSupervisor,
+        market_prices: Arc<RwLock<HashMap<String, Price>>>,
+    ) {
+        let timeout = self.config.check_interval * 10;
+        supervisor
+            .supervise("account_loss_breaker", timeout, move |heartbeat| {
+                self.clone().monitor(market_prices.clone(), Some(heartbeat))
+            })
+            .await;
+    }
+
+    async fn monitor(
+        self: Arc<Self>,
+        market_prices: Arc<RwLock<HashMap<String, Price>>>,
+        heartbeat: Option<Heartbeat>,
+    ) {
+        let mut interval = tokio::time::interval(self.config.check_interval);
+        loop {
+            interval.tick().await;
+            let marks = market_prices.read().await.clone();
+            self.check(&marks).await;
+            if let Some(heartbeat) = &heartbeat {
+                heartbeat.beat();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::mock::MockExecutionClient;
+    use crate::core::events::{OrderSide, OrderStatus};
+    use crate::risk::shadow_ledger::TradeRecord;
+    use crate::types::Symbol;
+    use chrono::Utc;
+
+    async fn breaker_with_long(
+        price: &str,
+    ) -> (
+        AccountLossBreaker<MockExecutionClient>,
+        Arc<MockExecutionClient>,
+        RiskEngine,
+    ) {
+        let ledger = Arc::new(ShadowLedger::new());
+        ledger
+            .add_trade(TradeRecord::new(
+                "t1".to_string(),
+                Symbol::new("BTCUSDT"),
+                "binance".to_string(),
+                "o1".to_string(),
+                OrderSide::Buy,
+                Size::from_str("2").unwrap(),
+                Price::from_str(price).unwrap(),
+                Utc::now(),
+                Size::zero(),
+                "USDT".to_string(),
+            ))
+            .await;
+
+        let client = Arc::new(MockExecutionClient::new());
+        let risk_engine = RiskEngine::new();
+        let config = AccountLossConfig {
+            max_loss: Decimal::new(500, 0),
+            max_slippage: Decimal::new(1, 2),
+            check_interval: Duration::from_millis(10),
+        };
+        let breaker = AccountLossBreaker::new(config, ledger, risk_engine.clone(), client.clone());
+        (breaker, client, risk_engine)
+    }
+
+    fn marks(price: &str) -> HashMap<String, Price> {
+        let mut marks = HashMap::new();
+        marks.insert("BTCUSDT".to_string(), Price::from_str(price).unwrap());
+        marks
+    }
+
+    #[tokio::test]
+    async fn test_breaker_trips_and_flattens() {
+        let (breaker, client, risk_engine) = breaker_with_long("1000").await;
+        let resting = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("1").unwrap(),
+            Price::from_str("700").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let resting_id = client.place_order(resting).await.unwrap();
+
+        // Loss of 400 is inside the limit
+        assert!(breaker.check(&marks("800")).await.is_none());
+        assert!(!risk_engine.is_halted().await);
+
+        // Loss of 600 trips the breaker
+        let report = breaker.check(&marks("700")).await.unwrap();
+        assert_eq!(report.account_pnl, Decimal::new(-600, 0));
+        assert_eq!(report.cancelled_orders, 1);
+        assert_eq!(report.flatten_orders.len(), 1);
+        assert!(report.failures.is_empty());
+        assert!(risk_engine.is_halted().await);
+
+        let resting = client.get_order_status(resting_id).await.unwrap();
+        assert_eq!(resting.status, OrderStatus::Cancelled);
+        let flatten = client
+            .get_order_status(report.flatten_orders[0].clone())
+            .await
+            .unwrap();
+        assert_eq!(flatten.exchange_id, "binance");
+        assert_eq!(flatten.remaining_size, Size::from_str("2").unwrap());
+        // Sell limit 1% below the mark
+        assert_eq!(flatten.average_price, Some(Price::from_str("693").unwrap()));
+
+        // New orders are rejected while halted, and the breaker only trips once
+        let order = NewOrder::new_market_buy("BTCUSDT", Size::from_str("1").unwrap());
+        let violation = risk_engine.check_order(&order).await.unwrap_err();
+        assert_eq!(violation.rule, "TradingHalted");
+        assert!(breaker.check(&marks("600")).await.is_none());
+
+        breaker.reset().await;
+        assert!(!breaker.is_tripped().await);
+        assert!(risk_engine.check_order(&order).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_monitoring_task_trips_without_orders() {
+        let (breaker, _client, risk_engine) = breaker_with_long("1000").await;
+        let breaker = Arc::new(breaker);
+        let market_prices = Arc::new(RwLock::new(marks("990")));
+        let handle = breaker.clone().spawn(market_prices.clone());
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(!breaker.is_tripped().await);
+
+        *market_prices.write().await = marks("500");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(breaker.is_tripped().await);
+        assert!(risk_engine.is_halted().await);
+        handle.abort();
+    }
+}
@@ -0,0 +1,322 @@
+use crate::risk::rules::RiskEngine;
+use crate::types::{Price, Size};
+use log::{error, info};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use toml_edit::{DocumentMut, Item};
+
+/// Limits for one symbol
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolLimits {
+    pub max_position_size: Option<Size>,
+    pub max_order_size: Option<Size>,
+    pub max_daily_loss: Option<Price>,
+}
+
+/// Risk limits read from a TOML file
+///
+/// ```toml
+/// max_total_exposure = "500000"
+/// max_open_orders = 50
+///
+/// [symbols.BTCUSDT]
+/// max_position_size = "1.0"
+/// max_order_size = "0.1"
+/// max_daily_loss = "1000"
+/// ```
+///
+/// Amounts may be strings, integers or floats. Every limit is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RiskLimits {
+    pub max_total_exposure: Option<Price>,
+    pub max_open_orders: Option<usize>,
+    pub symbols: BTreeMap<String, SymbolLimits>,
+}
+
+impl RiskLimits {
+    /// Parse limits from TOML
+    pub fn parse(toml: &str) -> Result<Self, RiskConfigError> {
+        let document =
+            DocumentMut::from_str(toml).map_err(|e| RiskConfigError::Parse(e.to_string()))?;
+        let mut limits = RiskLimits {
+            max_total_exposure: amount(document.get("max_total_exposure"), "max_total_exposure")?
+                .map(Price::new),
+            ..Default::default()
+        };
+        if let Some(item) = document.get("max_open_orders") {
+            let count = item
+                .as_integer()
+                .and_then(|count| usize::try_from(count).ok())
+                .ok_or_else(|| {
+                    RiskConfigError::Invalid("max_open_orders must be a whole number".to_string())
+                })?;
+            limits.max_open_orders = Some(count);
+        }
+
+        if let Some(item) = document.get("symbols") {
+            let symbols = item
+                .as_table_like()
+                .ok_or_else(|| RiskConfigError::Invalid("symbols must be a table".to_string()))?;
+            for (symbol, item) in symbols.iter() {
+                let table = item.as_table_like().ok_or_else(|| {
+                    RiskConfigError::Invalid(format!("symbols.{} must be a table", symbol))
+                })?;
+                let key = |name: &str| format!("symbols.{}.{}", symbol, name);
+                let symbol_limits = SymbolLimits {
+                    max_position_size: amount(
+                        table.get("max_position_size"),
+                        &key("max_position_size"),
+                    )?
+                    .map(Size::new),
+                    max_order_size: amount(table.get("max_order_size"), &key("max_order_size"))?
+                        .map(Size::new),
+                    max_daily_loss: amount(table.get("max_daily_loss"), &key("max_daily_loss"))?
+                        .map(Price::new),
+                };
+                limits.symbols.insert(symbol.to_string(), symbol_limits);
+            }
+        }
+        Ok(limits)
+    }
+
+    /// Read and parse a limits file
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<Self, RiskConfigError> {
+        let toml = std::fs::read_to_string(path).map_err(|e| RiskConfigError::Io(e.to_string()))?;
+        Self::parse(&toml)
+    }
+
+    /// Set every limit present on the risk engine
+    /// Limits missing from the file keep their current values.
+    pub async fn apply(&self, risk_engine: &RiskEngine) {
+        if let Some(max_exposure) = self.max_total_exposure {
+            risk_engine.set_max_total_exposure(max_exposure).await;
+        }
+        if let Some(max_orders) = self.max_open_orders {
+            risk_engine.set_max_open_orders(max_orders).await;
+        }
+        for (symbol, limits) in &self.symbols {
+            if let Some(max_size) = limits.max_position_size {
+                risk_engine.set_max_position_size(symbol, max_size).await;
+            }
+            if let Some(max_size) = limits.max_order_size {
+                risk_engine.set_max_order_size(symbol, max_size).await;
+            }
+            if let Some(max_loss) = limits.max_daily_loss {
+                risk_engine.set_max_daily_loss(symbol, max_loss).await;
+            }
+        }
+    }
+}
+
+/// Read a non-negative amount from a string, integer or float value
+fn amount(item: Option<&Item>, key: &str) -> Result<Option<Decimal>, RiskConfigError> {
+    let Some(item) = item else {
+        return Ok(None);
+    };
+    let value = if let Some(s) = item.as_str() {
+        Decimal::from_str(s).ok()
+    } else if let Some(i) = item.as_integer() {
+        Some(Decimal::from(i))
+    } else {
+        item.as_float().and_then(|f| Decimal::try_from(f).ok())
+    };
+    match value {
+        Some(value) if !value.is_sign_negative() => Ok(Some(value)),
+        _ => Err(RiskConfigError::Invalid(format!(
+            "{} must be a non-negative amount",
+            key
+        ))),
+    }
+}
+
+/// Risk limit file errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RiskConfigError {
+    Io(String),
+    Parse(String),
+    Invalid(String),
+}
+
+impl fmt::Display for RiskConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskConfigError::Io(msg) => write!(f, "I/O error: {}", msg),
+            RiskConfigError::Parse(msg) => write!(f, "Invalid TOML: {}", msg),
+            RiskConfigError::Invalid(msg) => write!(f, "Invalid risk limits: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RiskConfigError {}
+
+/// Hot-reloads risk limits from a TOML file into a risk engine
+///
+/// The file is polled for changes, so limits can be tuned without restarting. A file
+/// that fails to read or validate is reported and leaves the applied limits untouched.
+pub struct RiskConfigWatcher {
+    path: PathBuf,
+    risk_engine: RiskEngine,
+    poll_interval: Duration,
+    /// Contents of the file when last checked, applied or not
+    last_contents: Mutex<Option<String>>,
+    /// Limits last applied
+    current: Arc<RwLock<Option<RiskLimits>>>,
+}
+
+impl RiskConfigWatcher {
+    /// Create a watcher polling the file every second
+    pub fn new(path: impl Into<PathBuf>, risk_engine: RiskEngine) -> Self {
+        Self {
+            path: path.into(),
+            risk_engine,
+            poll_interval: Duration::from_secs(1),
+            last_contents: Mutex::new(None),
+            current: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Set how often the file is checked (builder pattern)
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Get the limits last applied
+    pub async fn current_limits(&self) -> Option<RiskLimits> {
+        self.current.read().await.clone()
+    }
+
+    /// Load the file and apply its limits, whether or not it changed
+    pub async fn load(&self) -> Result<RiskLimits, RiskConfigError> {
+        let toml =
+            std::fs::read_to_string(&self.path).map_err(|e| RiskConfigError::Io(e.to_string()))?;
+        *self.last_contents.lock().unwrap() = Some(toml.clone());
+        self.apply(&toml).await
+    }
+
+    /// Apply the file's limits if it changed since the last check
+    /// Returns the new limits, or None if the file is unchanged.
+    pub async fn check_for_changes(&self) -> Result<Option<RiskLimits>, RiskConfigError> {
+        let toml =
+            std::fs::read_to_string(&self.path).map_err(|e| RiskConfigError::Io(e.to_string()))?;
+        {
+            let mut last_contents = self.last_contents.lock().unwrap();
+            if last_contents.as_deref() == Some(toml.as_str()) {
+                return Ok(None);
+            }
+            *last_contents = Some(toml.clone());
+        }
+        self.apply(&toml).await.map(Some)
+    }
+
+    async fn apply(&self, toml: &str) -> Result<RiskLimits, RiskConfigError> {
+        let limits = RiskLimits::parse(toml).inspect_err(|e| {
+            error!(
+                "Keeping current risk limits, {} is invalid: {}",
+                self.path.display(),
+                e
+            )
+        })?;
+        limits.apply(&self.risk_engine).await;
+        info!(
+            "Applied risk limits from {}: {} symbols",
+            self.path.display(),
+            limits.symbols.len()
+        );
+        *self.current.write().await = Some(limits.clone());
+        Ok(limits)
+    }
+
+    /// Spawn the task polling the file for changes
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            loop {
+                interval.tick().await;
+                // Errors are logged in apply; a missing file is retried next tick
+                if let Err(RiskConfigError::Io(e)) = self.check_for_changes().await {
+                    error!("Failed to read {}: {}", self.path.display(), e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_risk_config_watcher() {
+        let path = std::env::temp_dir().join(format!("risk_limits_{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"
+max_total_exposure = 500000
+max_open_orders = 20
+
+[symbols.BTCUSDT]
+max_position_size = "1.5"
+max_order_size = 0.25
+"#,
+        )
+        .unwrap();
+
+        let risk_engine = RiskEngine::new();
+        let watcher = RiskConfigWatcher::new(&path, risk_engine.clone());
+        let limits = watcher.load().await.unwrap();
+        assert_eq!(limits.symbols["BTCUSDT"].max_daily_loss, None);
+        assert_eq!(risk_engine.get_max_open_orders().await, 20);
+        assert_eq!(
+            risk_engine.get_max_order_size("BTCUSDT").await,
+            Size::from_str("0.25").unwrap()
+        );
+        assert_eq!(watcher.check_for_changes().await, Ok(None));
+
+        // Tightened limits apply on the next check
+        std::fs::write(
+            &path,
+            "[symbols.BTCUSDT]\nmax_position_size = \"0.5\"\nmax_daily_loss = \"200\"\n",
+        )
+        .unwrap();
+        assert!(watcher.check_for_changes().await.unwrap().is_some());
+        assert_eq!(
+            risk_engine.get_max_position_size("BTCUSDT").await,
+            Size::from_str("0.5").unwrap()
+        );
+        assert_eq!(
+            risk_engine.get_max_daily_loss("BTCUSDT").await,
+            Price::from_str("200").unwrap()
+        );
+        // Limits left out of the file keep their values
+        assert_eq!(risk_engine.get_max_open_orders().await, 20);
+
+        // An invalid file leaves the applied limits alone
+        std::fs::write(&path, "[symbols.BTCUSDT]\nmax_order_size = \"-1\"\n").unwrap();
+        assert!(matches!(
+            watcher.check_for_changes().await,
+            Err(RiskConfigError::Invalid(_))
+        ));
+        assert_eq!(
+            risk_engine.get_max_order_size("BTCUSDT").await,
+            Size::from_str("0.25").unwrap()
+        );
+        assert_eq!(
+            watcher.current_limits().await.unwrap().symbols["BTCUSDT"].max_position_size,
+            Some(Size::from_str("0.5").unwrap())
+        );
+        assert!(matches!(
+            RiskLimits::parse("max_open_orders = ["),
+            Err(RiskConfigError::Parse(_))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
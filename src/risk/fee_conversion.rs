@@ -0,0 +1,141 @@
+use crate::risk::shadow_ledger::TradeRecord;
+use crate::types::Price;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Values the fee of a trade in the quote asset of its symbol
+pub trait FeeConverter: Send + Sync + fmt::Debug {
+    /// Get the trade's fee in its symbol's quote asset
+    /// `prices` holds the latest price by symbol. Returns None if the fee asset
+    /// cannot be priced.
+    fn fee_in_quote(&self, trade: &TradeRecord, prices: &HashMap<String, Price>)
+        -> Option<Decimal>;
+}
+
+/// Converts fees through the trade's own price or the market price of the fee asset
+///
+/// A fee in the quote asset is taken as is, one in the base asset is valued at the
+/// trade price, and any other asset (such as BNB) at the latest price of its pair
+/// with the quote asset, in either direction.
+#[derive(Debug, Clone)]
+pub struct MarketFeeConverter {
+    /// Quote assets recognised at the end of symbol names, longest match first
+    quote_assets: Vec<String>,
+}
+
+impl Default for MarketFeeConverter {
+    fn default() -> Self {
+        Self::new(["USDT", "USDC", "FDUSD", "BUSD", "USD", "BTC", "ETH", "BNB"])
+    }
+}
+
+impl MarketFeeConverter {
+    /// Create a converter recognising the given quote assets
+    pub fn new<S: Into<String>>(quote_assets: impl IntoIterator<Item = S>) -> Self {
+        let mut quote_assets: Vec<String> = quote_assets.into_iter().map(Into::into).collect();
+        quote_assets.sort_by_key(|asset| std::cmp::Reverse(asset.len()));
+        Self { quote_assets }
+    }
+
+    /// Split a symbol into its base and quote assets
+    /// Dashed names take their first two parts (`BTC-USDT-SWAP`); others are split at
+    /// the longest known quote asset they end with (`BTCUSDT`).
+    pub fn split_symbol<'a>(&self, symbol: &'a str) -> Option<(&'a str, &'a str)> {
+        let mut parts = symbol.split('-');
+        if let (Some(base), Some(quote)) = (parts.next(), parts.next()) {
+            return Some((base, quote));
+        }
+        self.quote_assets.iter().find_map(|quote| {
+            let base = symbol.strip_suffix(quote.as_str())?;
+            (!base.is_empty()).then(|| symbol.split_at(base.len()))
+        })
+    }
+
+    fn price_of(&self, asset: &str, quote: &str, prices: &HashMap<String, Price>) -> Option<Price> {
+        [
+            format!("{}{}", asset, quote),
+            format!("{}-{}", asset, quote),
+        ]
+        .iter()
+        .find_map(|symbol| prices.get(symbol).copied())
+        .filter(|price| price.is_positive())
+    }
+}
+
+impl FeeConverter for MarketFeeConverter {
+    fn fee_in_quote(
+        &self,
+        trade: &TradeRecord,
+        prices: &HashMap<String, Price>,
+    ) -> Option<Decimal> {
+        let fee = trade.fee.value();
+        if fee.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+        let (base, quote) = self.split_symbol(trade.symbol.value())?;
+        let asset = trade.fee_asset.as_str();
+        if asset == quote {
+            Some(fee)
+        } else if asset == base {
+            Some(fee * trade.price.value())
+        } else if let Some(price) = self.price_of(asset, quote, prices) {
+            Some(fee * price.value())
+        } else {
+            self.price_of(quote, asset, prices)
+                .map(|price| fee / price.value())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OrderSide;
+    use crate::types::{Size, Symbol};
+    use chrono::Utc;
+
+    fn trade(symbol: &str, fee: &str, fee_asset: &str) -> TradeRecord {
+        TradeRecord::new(
+            "t1".to_string(),
+            Symbol::new(symbol),
+            "binance".to_string(),
+            "o1".to_string(),
+            OrderSide::Buy,
+            Size::from_str("2").unwrap(),
+            Price::from_str("100").unwrap(),
+            Utc::now(),
+            Size::from_str(fee).unwrap(),
+            fee_asset.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_market_fee_converter() {
+        let converter = MarketFeeConverter::default();
+        assert_eq!(converter.split_symbol("ETHBTC"), Some(("ETH", "BTC")));
+        assert_eq!(converter.split_symbol("BTCFDUSD"), Some(("BTC", "FDUSD")));
+        assert_eq!(
+            converter.split_symbol("BTC-USDT-SWAP"),
+            Some(("BTC", "USDT"))
+        );
+
+        let prices = HashMap::from([
+            ("BNBUSDT".to_string(), Price::from_str("500").unwrap()),
+            ("BTCUSDT".to_string(), Price::from_str("50000").unwrap()),
+        ]);
+        let convert = |symbol: &str, fee: &str, asset: &str| {
+            converter.fee_in_quote(&trade(symbol, fee, asset), &prices)
+        };
+        assert_eq!(convert("SOLUSDT", "0.2", "USDT"), Some(Decimal::new(2, 1)));
+        // Base asset fees are valued at the trade price
+        assert_eq!(convert("SOLUSDT", "0.002", "SOL"), Some(Decimal::new(2, 1)));
+        assert_eq!(
+            convert("SOLUSDT", "0.0004", "BNB"),
+            Some(Decimal::new(2, 1))
+        );
+        // Pairs quoted the other way round are inverted
+        assert_eq!(convert("ETHBTC", "5", "USDT"), Some(Decimal::new(1, 4)));
+        assert_eq!(convert("ETHBTC", "1", "DOGE"), None);
+    }
+}
@@ -0,0 +1,367 @@
+use crate::core::events::FeeRecord;
+use crate::monitoring::alerts::{AlertLevel, AlertManager};
+use crate::risk::shadow_ledger::{ShadowLedger, TradeRecord};
+use crate::traits::FeeHistory;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Fee reconciliation configuration
+#[derive(Debug, Clone)]
+pub struct FeeReconciliationConfig {
+    /// Exchange whose fills are reconciled, as recorded in the ledger
+    pub exchange_id: String,
+    /// Symbols to reconcile
+    pub symbols: Vec<String>,
+    /// Largest per-fill fee difference treated as a match
+    pub tolerance: Decimal,
+    /// How far back each run looks
+    pub lookback: Duration,
+    /// Fills younger than this are left for the next run, as exchange history lags
+    pub settle_delay: Duration,
+    /// Interval between runs of the reconciliation job
+    pub interval: Duration,
+}
+
+impl Default for FeeReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            exchange_id: "binance".to_string(),
+            symbols: Vec::new(),
+            tolerance: Decimal::new(1, 8),
+            lookback: Duration::from_secs(24 * 60 * 60),
+            settle_delay: Duration::from_secs(60),
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// A fee that differs between the ledger and the exchange
+#[derive(Debug, Clone)]
+pub enum FeeDiscrepancy {
+    /// Both sides have the fill but disagree on the fee
+    Mismatch {
+        ledger: TradeRecord,
+        exchange: FeeRecord,
+    },
+    /// The exchange charged a fee for a fill the ledger does not have
+    MissingInLedger(FeeRecord),
+    /// The ledger has a fill the exchange did not report
+    MissingOnExchange(TradeRecord),
+}
+
+impl FeeDiscrepancy {
+    /// Fee charged by the exchange in excess of the ledger, by asset
+    fn leakage(&self) -> Vec<(String, Decimal)> {
+        match self {
+            FeeDiscrepancy::Mismatch { ledger, exchange }
+                if ledger.fee_asset == exchange.fee_asset =>
+            {
+                vec![(
+                    exchange.fee_asset.clone(),
+                    exchange.fee - ledger.fee.value(),
+                )]
+            }
+            FeeDiscrepancy::Mismatch { ledger, exchange } => vec![
+                (exchange.fee_asset.clone(), exchange.fee),
+                (ledger.fee_asset.clone(), -ledger.fee.value()),
+            ],
+            FeeDiscrepancy::MissingInLedger(exchange) => {
+                vec![(exchange.fee_asset.clone(), exchange.fee)]
+            }
+            FeeDiscrepancy::MissingOnExchange(ledger) => {
+                vec![(ledger.fee_asset.clone(), -ledger.fee.value())]
+            }
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            FeeDiscrepancy::Mismatch { ledger, exchange } => format!(
+                "{} trade {}: ledger fee {} {}, exchange fee {} {}",
+                exchange.symbol,
+                exchange.trade_id,
+                ledger.fee,
+                ledger.fee_asset,
+                exchange.fee,
+                exchange.fee_asset
+            ),
+            FeeDiscrepancy::MissingInLedger(exchange) => format!(
+                "{} trade {}: exchange fee {} {} has no ledger fill",
+                exchange.symbol, exchange.trade_id, exchange.fee, exchange.fee_asset
+            ),
+            FeeDiscrepancy::MissingOnExchange(ledger) => format!(
+                "{} trade {}: ledger fill not reported by the exchange",
+                ledger.symbol, ledger.trade_id
+            ),
+        }
+    }
+}
+
+/// Result of a reconciliation run
+#[derive(Debug, Clone, Default)]
+pub struct FeeReconciliationReport {
+    /// Start of the reconciled range
+    pub start: Option<DateTime<Utc>>,
+    /// End of the reconciled range
+    pub end: Option<DateTime<Utc>>,
+    /// Number of fills that matched
+    pub matched: usize,
+    /// Fills that did not match
+    pub discrepancies: Vec<FeeDiscrepancy>,
+    /// Net fees charged by the exchange beyond the ledger, by asset
+    pub leakage: BTreeMap<String, Decimal>,
+    /// Symbols whose exchange history could not be fetched
+    pub failures: Vec<String>,
+}
+
+impl FeeReconciliationReport {
+    /// Check if ledger and exchange agree
+    pub fn is_clean(&self) -> bool {
+        self.discrepancies.is_empty() && self.failures.is_empty()
+    }
+}
+
+/// Reconciles the fees recorded in the shadow ledger against the exchange's fee history
+pub struct FeeReconciler<S: FeeHistory> {
+    config: FeeReconciliationConfig,
+    shadow_ledger: Arc<ShadowLedger>,
+    source: Arc<S>,
+    alerts: Arc<AlertManager>,
+}
+
+impl<S> FeeReconciler<S>
+where
+    S: FeeHistory + Send + Sync + 'static,
+{
+    /// Create a new fee reconciler
+    pub fn new(
+        config: FeeReconciliationConfig,
+        shadow_ledger: Arc<ShadowLedger>,
+        source: Arc<S>,
+        alerts: Arc<AlertManager>,
+    ) -> Self {
+        Self {
+            config,
+            shadow_ledger,
+            source,
+            alerts,
+        }
+    }
+
+    /// Reconcile the fills in a time range, emitting an alert per discrepancy
+    pub async fn reconcile(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> FeeReconciliationReport {
+        let mut report = FeeReconciliationReport {
+            start: Some(start),
+            end: Some(end),
+            ..Default::default()
+        };
+        let ledger_trades = self.shadow_ledger.get_trades_in_range(start, end).await;
+
+        for symbol in &self.config.symbols {
+            let exchange_fees = match self
+                .source
+                .get_fee_history(
+                    symbol,
+                    start.timestamp_millis() as u64,
+                    end.timestamp_millis() as u64,
+                )
+                .await
+            {
+                Ok(fees) => fees,
+                Err(e) => {
+                    warn!("Failed to fetch fee history for {}: {}", symbol, e);
+                    report
+                        .failures
+                        .push(format!("Failed to fetch fee history for {}: {}", symbol, e));
+                    continue;
+                }
+            };
+
+            let mut ledger: HashMap<&str, &TradeRecord> = ledger_trades
+                .iter()
+                .filter(|t| t.symbol.value() == symbol && t.exchange_id == self.config.exchange_id)
+                .map(|t| (t.trade_id.as_str(), t))
+                .collect();
+
+            for fee in exchange_fees {
+                match ledger.remove(fee.trade_id.as_str()) {
+                    Some(trade)
+                        if trade.fee_asset == fee.fee_asset
+                            && (trade.fee.value() - fee.fee).abs() <= self.config.tolerance =>
+                    {
+                        report.matched += 1
+                    }
+                    Some(trade) => report.discrepancies.push(FeeDiscrepancy::Mismatch {
+                        ledger: trade.clone(),
+                        exchange: fee,
+                    }),
+                    None => report
+                        .discrepancies
+                        .push(FeeDiscrepancy::MissingInLedger(fee)),
+                }
+            }
+            report.discrepancies.extend(
+                ledger
+                    .into_values()
+                    .map(|t| FeeDiscrepancy::MissingOnExchange(t.clone())),
+            );
+        }
+
+        for discrepancy in &report.discrepancies {
+            for (asset, amount) in discrepancy.leakage() {
+                *report.leakage.entry(asset).or_insert(Decimal::ZERO) += amount;
+            }
+            self.alerts
+                .emit(
+                    AlertLevel::Warning,
+                    "fee_reconciliation",
+                    format!("Fee discrepancy: {}", discrepancy.describe()),
+                )
+                .await;
+        }
+        for failure in &report.failures {
+            self.alerts
+                .emit(AlertLevel::Error, "fee_reconciliation", failure.clone())
+                .await;
+        }
+
+        info!(
+            "Fee reconciliation {} - {}: matched={}, discrepancies={}, leakage={:?}",
+            start,
+            end,
+            report.matched,
+            report.discrepancies.len(),
+            report.leakage
+        );
+        report
+    }
+
+    /// Reconcile the configured lookback window, ending `settle_delay` before now
+    pub async fn reconcile_recent(&self) -> FeeReconciliationReport {
+        let end =
+            Utc::now() - chrono::Duration::from_std(self.config.settle_delay).unwrap_or_default();
+        let start = end - chrono::Duration::from_std(self.config.lookback).unwrap_or_default();
+        self.reconcile(start, end).await
+    }
+
+    /// Spawn the reconciliation job
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            loop {
+                interval.tick().await;
+                self.reconcile_recent().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OrderSide;
+    use crate::types::{Price, Size, Symbol};
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+
+    struct StaticFees(Vec<FeeRecord>);
+
+    #[async_trait]
+    impl FeeHistory for StaticFees {
+        type Error = String;
+
+        async fn get_fee_history(
+            &self,
+            symbol: &str,
+            start_time: u64,
+            end_time: u64,
+        ) -> Result<Vec<FeeRecord>, Self::Error> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|f| f.symbol.value() == symbol)
+                .filter(|f| f.timestamp >= start_time && f.timestamp <= end_time)
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn fee(trade_id: &str, amount: &str, timestamp: DateTime<Utc>) -> FeeRecord {
+        FeeRecord {
+            trade_id: trade_id.to_string(),
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            fee: Decimal::from_str_exact(amount).unwrap(),
+            fee_asset: "USDT".to_string(),
+            timestamp: timestamp.timestamp_millis() as u64,
+        }
+    }
+
+    fn trade(trade_id: &str, amount: &str, timestamp: DateTime<Utc>) -> TradeRecord {
+        TradeRecord::new(
+            trade_id.to_string(),
+            Symbol::new("BTCUSDT"),
+            "binance".to_string(),
+            "o1".to_string(),
+            OrderSide::Buy,
+            Size::from_str("1").unwrap(),
+            Price::from_str("50000").unwrap(),
+            timestamp,
+            Size::from_str(amount).unwrap(),
+            "USDT".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_reports_leakage() {
+        let t0 = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let ledger = Arc::new(ShadowLedger::new());
+        for record in [
+            trade("1", "5", t0),
+            trade("2", "5", t0),
+            trade("3", "5", t0),
+        ] {
+            ledger.add_trade(record).await;
+        }
+        let source = Arc::new(StaticFees(vec![
+            fee("1", "5", t0),
+            fee("2", "7.5", t0),
+            fee("4", "1.25", t0),
+        ]));
+        let alerts = Arc::new(AlertManager::new(100));
+        let config = FeeReconciliationConfig {
+            symbols: vec!["BTCUSDT".to_string()],
+            ..Default::default()
+        };
+        let reconciler = FeeReconciler::new(config, ledger, source, alerts.clone());
+
+        let report = reconciler
+            .reconcile(
+                t0 - chrono::Duration::hours(1),
+                t0 + chrono::Duration::hours(1),
+            )
+            .await;
+        assert_eq!(report.matched, 1);
+        assert_eq!(report.discrepancies.len(), 3);
+        assert!(report
+            .discrepancies
+            .iter()
+            .any(|d| matches!(d, FeeDiscrepancy::MissingOnExchange(t) if t.trade_id == "3")));
+        // +2.5 overcharged, +1.25 unrecorded, -5 not reported by the exchange
+        assert_eq!(report.leakage["USDT"], Decimal::new(-125, 2));
+        assert_eq!(
+            alerts.get_alerts_by_level(AlertLevel::Warning).await.len(),
+            3
+        );
+        assert!(!report.is_clean());
+    }
+}
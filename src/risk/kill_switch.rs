@@ -0,0 +1,294 @@
+use crate::core::events::{OrderId, RiskViolation, SystemEvent};
+use crate::monitoring::alerts::{Alert, AlertLevel, AlertManager};
+use crate::oms::order_manager::OrderManagerImpl;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+/// What tripped the kill switch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HaltSource {
+    /// A component halting the risk engine, such as the account loss breaker
+    RiskEngine,
+    /// An order violating a risk rule configured to halt
+    RiskViolation,
+    /// A monitoring alert at or above the configured level
+    MonitoringAlert,
+    /// An operator command
+    Operator,
+}
+
+/// Why and since when trading is halted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HaltRecord {
+    pub source: HaltSource,
+    pub reason: String,
+    /// Time the switch was tripped, in milliseconds
+    pub since: u64,
+}
+
+/// Outcome of tripping the kill switch
+#[derive(Debug, Clone, Default)]
+pub struct HaltReport {
+    /// Whether the switch was not already tripped
+    pub newly_tripped: bool,
+    /// Orders cancelled in the registered order managers
+    pub cancelled_orders: Vec<OrderId>,
+}
+
+/// Global kill switch
+///
+/// Any component can trip it: the risk engine and anything that halts it, rule
+/// violations chosen with `with_violation_rules`, monitoring alerts at or above
+/// `with_alert_level`, or an operator. While tripped the risk engine and order executor
+/// reject every new order. Tripping cancels the open orders of every registered order
+/// manager and broadcasts `SystemEvent::TradingHalted`. Clones share state.
+#[derive(Clone)]
+pub struct KillSwitch {
+    halt: Arc<RwLock<Option<HaltRecord>>>,
+    order_managers: Arc<RwLock<Vec<Arc<OrderManagerImpl>>>>,
+    events: broadcast::Sender<SystemEvent>,
+    violation_rules: BTreeSet<String>,
+    alert_level: AlertLevel,
+}
+
+impl Default for KillSwitch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KillSwitch {
+    /// Create an untripped kill switch with its own event channel
+    pub fn new() -> Self {
+        Self {
+            halt: Arc::new(RwLock::new(None)),
+            order_managers: Arc::new(RwLock::new(Vec::new())),
+            events: broadcast::channel(64).0,
+            violation_rules: BTreeSet::new(),
+            alert_level: AlertLevel::Critical,
+        }
+    }
+
+    /// Broadcast halts on a shared event channel (builder pattern)
+    pub fn with_event_sender(mut self, events: broadcast::Sender<SystemEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Trip when an order violates one of these rules (builder pattern)
+    pub fn with_violation_rules<I, S>(mut self, rules: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.violation_rules = rules.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Trip on alerts at or above a level, Critical by default (builder pattern)
+    pub fn with_alert_level(mut self, level: AlertLevel) -> Self {
+        self.alert_level = level;
+        self
+    }
+
+    /// Subscribe to halt and resume events
+    pub fn subscribe(&self) -> broadcast::Receiver<SystemEvent> {
+        self.events.subscribe()
+    }
+
+    /// Cancel an order manager's open orders whenever the switch trips
+    pub fn register_order_manager(&self, order_manager: Arc<OrderManagerImpl>) {
+        self.order_managers.write().unwrap().push(order_manager);
+    }
+
+    /// Check if trading is halted
+    pub fn is_tripped(&self) -> bool {
+        self.halt.read().unwrap().is_some()
+    }
+
+    /// Get why trading is halted, if it is
+    pub fn status(&self) -> Option<HaltRecord> {
+        self.halt.read().unwrap().clone()
+    }
+
+    /// Halt trading and cancel all open orders in the registered order managers
+    /// If already tripped the original record is kept, but orders are still cancelled.
+    pub async fn trip(&self, source: HaltSource, reason: impl Into<String>) -> HaltReport {
+        let reason = reason.into();
+        let newly_tripped = {
+            let mut halt = self.halt.write().unwrap();
+            if halt.is_none() {
+                warn!("Kill switch tripped ({:?}): {}", source, reason);
+                *halt = Some(HaltRecord {
+                    source,
+                    reason: reason.clone(),
+                    since: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_millis() as u64,
+                });
+                true
+            } else {
+                false
+            }
+        };
+        if newly_tripped {
+            // No subscribers is not an error
+            let _ = self.events.send(SystemEvent::TradingHalted(reason));
+        }
+
+        let order_managers = self.order_managers.read().unwrap().clone();
+        let mut cancelled_orders = Vec::new();
+        for order_manager in order_managers {
+            cancelled_orders.extend(order_manager.cancel_all_orders().await);
+        }
+        HaltReport {
+            newly_tripped,
+            cancelled_orders,
+        }
+    }
+
+    /// Let trading resume, returning the halt that was cleared
+    pub fn reset(&self) -> Option<HaltRecord> {
+        let record = self.halt.write().unwrap().take()?;
+        info!("Kill switch reset after {:?} halt", record.source);
+        let _ = self.events.send(SystemEvent::TradingResumed);
+        Some(record)
+    }
+
+    /// Trip if a violation is of a configured rule
+    pub async fn on_violation(&self, violation: &RiskViolation) -> Option<HaltReport> {
+        if !self.violation_rules.contains(&violation.rule) {
+            return None;
+        }
+        Some(
+            self.trip(
+                HaltSource::RiskViolation,
+                format!("{}: {}", violation.rule, violation.details),
+            )
+            .await,
+        )
+    }
+
+    /// Trip if an alert is at or above the configured level
+    pub async fn on_alert(&self, alert: &Alert) -> Option<HaltReport> {
+        if alert.level < self.alert_level {
+            return None;
+        }
+        Some(
+            self.trip(
+                HaltSource::MonitoringAlert,
+                format!("[{}] {}", alert.component, alert.message),
+            )
+            .await,
+        )
+    }
+
+    /// Trip on the alerts an alert manager emits from now on
+    pub async fn watch_alerts(&self, alerts: &AlertManager) {
+        let kill_switch = self.clone();
+        alerts
+            .register_callback(move |alert| {
+                if alert.level >= kill_switch.alert_level {
+                    let kill_switch = kill_switch.clone();
+                    let alert = alert.clone();
+                    tokio::spawn(async move { kill_switch.on_alert(&alert).await });
+                }
+            })
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{NewOrder, OrderSide, OrderType, TimeInForce};
+    use crate::oms::order_manager::OrderInfo;
+    use crate::types::{Price, Size, Symbol};
+
+    fn order_info(order_id: &str, symbol: &str) -> OrderInfo {
+        OrderInfo::new(
+            order_id.to_string(),
+            None,
+            Symbol::new(symbol),
+            OrderSide::Buy,
+            OrderType::Limit,
+            TimeInForce::GoodTillCancelled,
+            Size::from_str("1").unwrap(),
+            Some(Price::from_str("100").unwrap()),
+            "binance".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_kill_switch() {
+        let binance = Arc::new(OrderManagerImpl::new("binance".to_string()));
+        let okx = Arc::new(OrderManagerImpl::new("okx".to_string()));
+        binance.add_order(order_info("b1", "BTCUSDT")).await;
+        binance.add_order(order_info("b2", "ETHUSDT")).await;
+        okx.add_order(order_info("o1", "BTC-USDT")).await;
+
+        let kill_switch = KillSwitch::new().with_violation_rules(["MaxDrawdown"]);
+        kill_switch.register_order_manager(binance.clone());
+        kill_switch.register_order_manager(okx.clone());
+        let mut events = kill_switch.subscribe();
+
+        let violation = RiskViolation::new("OrderSizeLimit".to_string(), "too big".to_string());
+        assert!(kill_switch.on_violation(&violation).await.is_none());
+        assert!(!kill_switch.is_tripped());
+
+        let violation = RiskViolation::new("MaxDrawdown".to_string(), "down 20%".to_string());
+        let report = kill_switch.on_violation(&violation).await.unwrap();
+        assert!(report.newly_tripped);
+        assert_eq!(report.cancelled_orders.len(), 3);
+        assert!(binance.get_all_active_orders().await.is_empty());
+        assert!(okx.get_all_active_orders().await.is_empty());
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SystemEvent::TradingHalted("MaxDrawdown: down 20%".to_string())
+        );
+
+        // A second trip keeps the first reason
+        let report = kill_switch.trip(HaltSource::Operator, "manual").await;
+        assert!(!report.newly_tripped);
+        assert_eq!(
+            kill_switch.status().unwrap().source,
+            HaltSource::RiskViolation
+        );
+
+        // The risk engine rejects orders while a shared switch is tripped
+        let risk_engine = crate::risk::RiskEngine::new().with_kill_switch(kill_switch.clone());
+        let order = NewOrder::new_market_buy("BTCUSDT", Size::from_str("1").unwrap());
+        assert_eq!(
+            risk_engine.check_order(&order).await.unwrap_err().rule,
+            "TradingHalted"
+        );
+
+        assert!(kill_switch.reset().is_some());
+        assert_eq!(events.recv().await.unwrap(), SystemEvent::TradingResumed);
+        assert!(risk_engine.check_order(&order).await.is_ok());
+
+        // Alerts below the level are ignored
+        let alerts = AlertManager::new(10);
+        kill_switch.watch_alerts(&alerts).await;
+        alerts
+            .emit(AlertLevel::Error, "feed", "gap".to_string())
+            .await;
+        alerts
+            .emit(AlertLevel::Critical, "feed", "stale".to_string())
+            .await;
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SystemEvent::TradingHalted("[feed] stale".to_string())
+        );
+        assert_eq!(
+            kill_switch.status().unwrap().source,
+            HaltSource::MonitoringAlert
+        );
+    }
+}
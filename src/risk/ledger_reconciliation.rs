@@ -0,0 +1,546 @@
+use crate::core::events::{ExchangeFill, ExchangePosition};
+use crate::monitoring::metrics::MetricsCollector;
+use crate::risk::shadow_ledger::{ShadowLedger, TradeRecord};
+use crate::traits::TradeHistory;
+use crate::types::{Size, Symbol};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Gauge holding the divergence count of the latest run
+pub const LEDGER_DIVERGENCE_GAUGE: &str = "ledger_divergences";
+
+/// Ledger reconciliation configuration
+#[derive(Debug, Clone)]
+pub struct LedgerReconciliationConfig {
+    /// Exchange whose fills are reconciled, as recorded in the ledger
+    pub exchange_id: String,
+    /// Symbols to reconcile
+    pub symbols: Vec<String>,
+    /// Largest price difference treated as a match
+    pub price_tolerance: Decimal,
+    /// Largest size (fill and position) difference treated as a match
+    pub size_tolerance: Decimal,
+    /// Largest fee difference treated as a match
+    pub fee_tolerance: Decimal,
+    /// Largest difference between the ledger and exchange fill times
+    pub timestamp_tolerance: Duration,
+    /// How far back each run looks
+    pub lookback: Duration,
+    /// Fills younger than this are left for the next run, as exchange history lags
+    pub settle_delay: Duration,
+    /// Interval between runs of the reconciliation job
+    pub interval: Duration,
+}
+
+impl Default for LedgerReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            exchange_id: "binance".to_string(),
+            symbols: Vec::new(),
+            price_tolerance: Decimal::new(1, 8),
+            size_tolerance: Decimal::new(1, 8),
+            fee_tolerance: Decimal::new(1, 8),
+            timestamp_tolerance: Duration::from_secs(5),
+            lookback: Duration::from_secs(24 * 60 * 60),
+            settle_delay: Duration::from_secs(60),
+            interval: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+/// Fill field compared between the ledger and the exchange
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerField {
+    Side,
+    Price,
+    Size,
+    Fee,
+    FeeAsset,
+    Timestamp,
+}
+
+impl fmt::Display for LedgerField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LedgerField::Side => "side",
+            LedgerField::Price => "price",
+            LedgerField::Size => "size",
+            LedgerField::Fee => "fee",
+            LedgerField::FeeAsset => "fee_asset",
+            LedgerField::Timestamp => "timestamp",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A difference between the shadow ledger and the exchange
+#[derive(Debug, Clone)]
+pub enum LedgerDivergence {
+    /// Both sides have the fill but disagree on a field
+    FieldMismatch {
+        trade_id: String,
+        symbol: Symbol,
+        field: LedgerField,
+        ledger: String,
+        exchange: String,
+    },
+    /// The exchange reported a fill the ledger does not have
+    MissingInLedger(ExchangeFill),
+    /// The ledger has a fill the exchange did not report
+    MissingOnExchange(TradeRecord),
+    /// Position sizes disagree
+    PositionMismatch {
+        symbol: Symbol,
+        ledger: Size,
+        exchange: Size,
+    },
+}
+
+impl LedgerDivergence {
+    /// Get the symbol the divergence is on
+    pub fn symbol(&self) -> &Symbol {
+        match self {
+            LedgerDivergence::FieldMismatch { symbol, .. }
+            | LedgerDivergence::PositionMismatch { symbol, .. } => symbol,
+            LedgerDivergence::MissingInLedger(fill) => &fill.symbol,
+            LedgerDivergence::MissingOnExchange(trade) => &trade.symbol,
+        }
+    }
+}
+
+impl fmt::Display for LedgerDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerDivergence::FieldMismatch {
+                trade_id,
+                symbol,
+                field,
+                ledger,
+                exchange,
+            } => write!(
+                f,
+                "{} trade {}: {} is {} in the ledger, {} on the exchange",
+                symbol, trade_id, field, ledger, exchange
+            ),
+            LedgerDivergence::MissingInLedger(fill) => write!(
+                f,
+                "{} trade {}: exchange fill {:?} {} @ {} has no ledger entry",
+                fill.symbol, fill.trade_id, fill.side, fill.size, fill.price
+            ),
+            LedgerDivergence::MissingOnExchange(trade) => write!(
+                f,
+                "{} trade {}: ledger fill not reported by the exchange",
+                trade.symbol, trade.trade_id
+            ),
+            LedgerDivergence::PositionMismatch {
+                symbol,
+                ledger,
+                exchange,
+            } => write!(
+                f,
+                "{} position is {} in the ledger, {} on the exchange",
+                symbol, ledger, exchange
+            ),
+        }
+    }
+}
+
+/// Result of a reconciliation run
+#[derive(Debug, Clone, Default)]
+pub struct LedgerReconciliationReport {
+    /// Start of the reconciled range
+    pub start: Option<DateTime<Utc>>,
+    /// End of the reconciled range
+    pub end: Option<DateTime<Utc>>,
+    /// Number of fills that matched on every field
+    pub matched: usize,
+    /// Differences found
+    pub divergences: Vec<LedgerDivergence>,
+    /// Queries that could not be completed
+    pub failures: Vec<String>,
+}
+
+impl LedgerReconciliationReport {
+    /// Check if ledger and exchange agree
+    pub fn is_clean(&self) -> bool {
+        self.divergences.is_empty() && self.failures.is_empty()
+    }
+}
+
+/// Compares the shadow ledger against the exchange's own trade history and positions
+pub struct LedgerReconciler<S: TradeHistory> {
+    config: LedgerReconciliationConfig,
+    shadow_ledger: Arc<ShadowLedger>,
+    source: Arc<S>,
+    metrics: Arc<MetricsCollector>,
+}
+
+impl<S> LedgerReconciler<S>
+where
+    S: TradeHistory + Send + Sync + 'static,
+{
+    /// Create a new ledger reconciler
+    pub fn new(
+        config: LedgerReconciliationConfig,
+        shadow_ledger: Arc<ShadowLedger>,
+        source: Arc<S>,
+        metrics: Arc<MetricsCollector>,
+    ) -> Self {
+        Self {
+            config,
+            shadow_ledger,
+            source,
+            metrics,
+        }
+    }
+
+    fn compare_fill(
+        &self,
+        ledger: &TradeRecord,
+        exchange: &ExchangeFill,
+        divergences: &mut Vec<LedgerDivergence>,
+    ) -> bool {
+        let ledger_time = ledger.timestamp.timestamp_millis() as u64;
+        let checks = [
+            (LedgerField::Side, ledger.side == exchange.side),
+            (
+                LedgerField::Price,
+                (ledger.price.value() - exchange.price.value()).abs()
+                    <= self.config.price_tolerance,
+            ),
+            (
+                LedgerField::Size,
+                (ledger.quantity.value() - exchange.size.value()).abs()
+                    <= self.config.size_tolerance,
+            ),
+            (
+                LedgerField::Fee,
+                (ledger.fee.value() - exchange.fee).abs() <= self.config.fee_tolerance,
+            ),
+            (
+                LedgerField::FeeAsset,
+                ledger.fee_asset == exchange.fee_asset,
+            ),
+            (
+                LedgerField::Timestamp,
+                ledger_time.abs_diff(exchange.timestamp)
+                    <= self.config.timestamp_tolerance.as_millis() as u64,
+            ),
+        ];
+
+        let before = divergences.len();
+        for (field, matches) in checks {
+            if matches {
+                continue;
+            }
+            let (ledger_value, exchange_value) = match field {
+                LedgerField::Side => (format!("{:?}", ledger.side), format!("{:?}", exchange.side)),
+                LedgerField::Price => (ledger.price.to_string(), exchange.price.to_string()),
+                LedgerField::Size => (ledger.quantity.to_string(), exchange.size.to_string()),
+                LedgerField::Fee => (ledger.fee.to_string(), exchange.fee.to_string()),
+                LedgerField::FeeAsset => (ledger.fee_asset.clone(), exchange.fee_asset.clone()),
+                LedgerField::Timestamp => (ledger_time.to_string(), exchange.timestamp.to_string()),
+            };
+            divergences.push(LedgerDivergence::FieldMismatch {
+                trade_id: exchange.trade_id.clone(),
+                symbol: exchange.symbol.clone(),
+                field,
+                ledger: ledger_value,
+                exchange: exchange_value,
+            });
+        }
+        divergences.len() == before
+    }
+
+    async fn compare_positions(
+        &self,
+        positions: Vec<ExchangePosition>,
+        report: &mut LedgerReconciliationReport,
+    ) {
+        let exchange: HashMap<&str, Size> = positions
+            .iter()
+            .map(|p| (p.symbol.as_str(), p.size))
+            .collect();
+        for symbol in &self.config.symbols {
+            let ledger = self
+                .shadow_ledger
+                .get_position(symbol, &self.config.exchange_id)
+                .await
+                .map(|p| p.size)
+                .unwrap_or_else(Size::zero);
+            let exchange = exchange
+                .get(symbol.as_str())
+                .copied()
+                .unwrap_or_else(Size::zero);
+            if (ledger.value() - exchange.value()).abs() > self.config.size_tolerance {
+                report.divergences.push(LedgerDivergence::PositionMismatch {
+                    symbol: Symbol::new(symbol),
+                    ledger,
+                    exchange,
+                });
+            }
+        }
+    }
+
+    /// Reconcile the fills in a time range and the current positions
+    /// Sets the divergence gauge to the number of divergences found
+    pub async fn reconcile(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> LedgerReconciliationReport {
+        let mut report = LedgerReconciliationReport {
+            start: Some(start),
+            end: Some(end),
+            ..Default::default()
+        };
+        let ledger_trades = self.shadow_ledger.get_trades_in_range(start, end).await;
+
+        for symbol in &self.config.symbols {
+            let fills = match self
+                .source
+                .get_trade_history(
+                    symbol,
+                    start.timestamp_millis() as u64,
+                    end.timestamp_millis() as u64,
+                )
+                .await
+            {
+                Ok(fills) => fills,
+                Err(e) => {
+                    warn!("Failed to fetch trade history for {}: {}", symbol, e);
+                    report.failures.push(format!(
+                        "Failed to fetch trade history for {}: {}",
+                        symbol, e
+                    ));
+                    continue;
+                }
+            };
+
+            let mut ledger: HashMap<&str, &TradeRecord> = ledger_trades
+                .iter()
+                .filter(|t| t.symbol.value() == symbol && t.exchange_id == self.config.exchange_id)
+                .map(|t| (t.trade_id.as_str(), t))
+                .collect();
+
+            for fill in fills {
+                match ledger.remove(fill.trade_id.as_str()) {
+                    Some(trade) => {
+                        if self.compare_fill(trade, &fill, &mut report.divergences) {
+                            report.matched += 1;
+                        }
+                    }
+                    None => report
+                        .divergences
+                        .push(LedgerDivergence::MissingInLedger(fill)),
+                }
+            }
+            report.divergences.extend(
+                ledger
+                    .into_values()
+                    .map(|t| LedgerDivergence::MissingOnExchange(t.clone())),
+            );
+        }
+
+        match self.source.get_positions().await {
+            Ok(Some(positions)) => self.compare_positions(positions, &mut report).await,
+            Ok(None) => {}
+            Err(e) => {
+                warn!("Failed to fetch positions: {}", e);
+                report
+                    .failures
+                    .push(format!("Failed to fetch positions: {}", e));
+            }
+        }
+
+        for divergence in &report.divergences {
+            warn!("Ledger divergence: {}", divergence);
+        }
+        self.metrics
+            .set_gauge(LEDGER_DIVERGENCE_GAUGE, report.divergences.len() as f64)
+            .await;
+
+        info!(
+            "Ledger reconciliation {} - {}: matched={}, divergences={}, failures={}",
+            start,
+            end,
+            report.matched,
+            report.divergences.len(),
+            report.failures.len()
+        );
+        report
+    }
+
+    /// Reconcile the configured lookback window, ending `settle_delay` before now
+    pub async fn reconcile_recent(&self) -> LedgerReconciliationReport {
+        let end =
+            Utc::now() - chrono::Duration::from_std(self.config.settle_delay).unwrap_or_default();
+        let start = end - chrono::Duration::from_std(self.config.lookback).unwrap_or_default();
+        self.reconcile(start, end).await
+    }
+
+    /// Spawn the reconciliation job
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            loop {
+                interval.tick().await;
+                self.reconcile_recent().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OrderSide;
+    use crate::types::Price;
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+
+    struct StaticHistory {
+        fills: Vec<ExchangeFill>,
+        positions: Vec<ExchangePosition>,
+    }
+
+    #[async_trait]
+    impl TradeHistory for StaticHistory {
+        type Error = String;
+
+        async fn get_trade_history(
+            &self,
+            symbol: &str,
+            _start_time: u64,
+            _end_time: u64,
+        ) -> Result<Vec<ExchangeFill>, Self::Error> {
+            Ok(self
+                .fills
+                .iter()
+                .filter(|f| f.symbol.value() == symbol)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_positions(&self) -> Result<Option<Vec<ExchangePosition>>, Self::Error> {
+            Ok(Some(self.positions.clone()))
+        }
+    }
+
+    fn fill(trade_id: &str, price: &str, size: &str, timestamp: DateTime<Utc>) -> ExchangeFill {
+        ExchangeFill {
+            trade_id: trade_id.to_string(),
+            order_id: "o1".to_string(),
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            side: OrderSide::Buy,
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str(size).unwrap(),
+            fee: Decimal::new(5, 0),
+            fee_asset: "USDT".to_string(),
+            timestamp: timestamp.timestamp_millis() as u64,
+        }
+    }
+
+    fn trade(trade_id: &str, price: &str, size: &str, timestamp: DateTime<Utc>) -> TradeRecord {
+        TradeRecord::new(
+            trade_id.to_string(),
+            Symbol::new("BTCUSDT"),
+            "binance".to_string(),
+            "o1".to_string(),
+            OrderSide::Buy,
+            Size::from_str(size).unwrap(),
+            Price::from_str(price).unwrap(),
+            timestamp,
+            Size::from_str("5").unwrap(),
+            "USDT".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_field_by_field() {
+        let t0 = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let ledger = Arc::new(ShadowLedger::new());
+        for record in [
+            trade("1", "50000", "1", t0),
+            trade("2", "50000", "1", t0),
+            trade("3", "50000", "1", t0 + chrono::Duration::seconds(30)),
+        ] {
+            ledger.add_trade(record).await;
+        }
+        let source = Arc::new(StaticHistory {
+            fills: vec![
+                fill("1", "50000", "1", t0),
+                // Price and size both differ
+                fill("2", "50001", "0.5", t0),
+                // Recorded 30s late in the ledger
+                fill("3", "50000", "1", t0),
+                fill("4", "50000", "0.25", t0),
+            ],
+            positions: vec![ExchangePosition {
+                symbol: Symbol::new("BTCUSDT"),
+                exchange_id: "binance".to_string(),
+                size: Size::from_str("2.75").unwrap(),
+                entry_price: None,
+            }],
+        });
+        let metrics = Arc::new(MetricsCollector::new());
+        let config = LedgerReconciliationConfig {
+            symbols: vec!["BTCUSDT".to_string()],
+            ..Default::default()
+        };
+        let reconciler = LedgerReconciler::new(config, ledger, source, metrics.clone());
+
+        let report = reconciler
+            .reconcile(
+                t0 - chrono::Duration::hours(1),
+                t0 + chrono::Duration::hours(1),
+            )
+            .await;
+        assert_eq!(report.matched, 1);
+        let fields: Vec<(String, LedgerField)> = report
+            .divergences
+            .iter()
+            .filter_map(|d| match d {
+                LedgerDivergence::FieldMismatch {
+                    trade_id, field, ..
+                } => Some((trade_id.clone(), *field)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("2".to_string(), LedgerField::Price),
+                ("2".to_string(), LedgerField::Size),
+                ("3".to_string(), LedgerField::Timestamp),
+            ]
+        );
+        assert!(report
+            .divergences
+            .iter()
+            .any(|d| matches!(d, LedgerDivergence::MissingInLedger(f) if f.trade_id == "4")));
+        // Ledger holds 3, the exchange 2.75
+        assert!(report
+            .divergences
+            .iter()
+            .any(|d| matches!(d, LedgerDivergence::PositionMismatch { .. })));
+        assert_eq!(report.divergences.len(), 5);
+
+        let gauge = metrics
+            .get_metrics()
+            .await
+            .into_iter()
+            .find(|m| m.name == format!("gauge.{}", LEDGER_DIVERGENCE_GAUGE))
+            .unwrap();
+        assert!(
+            matches!(gauge.value, crate::monitoring::metrics::MetricValue::Gauge(v) if v == 5.0)
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use crate::risk::session::SessionPnl;
+use crate::risk::shadow_ledger::{
+    ClosedLot, CostBasisMethod, HistoricalPnL, PositionRecord, TradeRecord, TradeStats,
+};
+use crate::types::Price;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Params};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Ledger state that is replaced as a whole on every save
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LedgerState {
+    pub positions: Vec<PositionRecord>,
+    /// Daily realized P&L by session date ("%Y-%m-%d")
+    pub daily_pnl: HashMap<String, Decimal>,
+    /// Session P&L history, the last entry being the open session
+    pub sessions: Vec<SessionPnl>,
+    pub last_prices: HashMap<String, Price>,
+    pub historical_pnl: Vec<HistoricalPnL>,
+    pub peak_equity: Decimal,
+    #[serde(default)]
+    pub cost_basis_method: CostBasisMethod,
+    /// Totals over every trade, including those no longer kept in memory
+    #[serde(default)]
+    pub trade_stats: TradeStats,
+}
+
+/// Everything a shadow ledger keeps, as loaded from a store
+#[derive(Debug, Clone, Default)]
+pub struct LedgerSnapshot {
+    pub state: LedgerState,
+    /// Most recent trades, oldest first
+    pub trades: Vec<TradeRecord>,
+    /// Most recent closed lots, oldest first
+    pub closed_lots: Vec<ClosedLot>,
+}
+
+/// Changes to save: the current state plus the trades and lots added since the last save
+#[derive(Debug, Clone, Default)]
+pub struct LedgerUpdate {
+    pub state: LedgerState,
+    pub new_trades: Vec<TradeRecord>,
+    pub new_closed_lots: Vec<ClosedLot>,
+}
+
+/// Storage backend that keeps a shadow ledger across restarts
+pub trait LedgerStore: Send + Sync + fmt::Debug {
+    /// Load the saved ledger with at most `max_trades` of the latest trades and
+    /// `max_closed_lots` of the latest closed lots, or None if nothing has been saved yet
+    fn load(&self, max_trades: usize, max_closed_lots: usize)
+        -> io::Result<Option<LedgerSnapshot>>;
+
+    /// Load the saved trades stamped within a time range (inclusive), oldest first
+    fn load_trades(&self, start: DateTime<Utc>, end: DateTime<Utc>)
+        -> io::Result<Vec<TradeRecord>>;
+
+    /// Save an update: replace the state and append the new trades and closed lots
+    /// Blocking; the ledger calls it off the async runtime.
+    fn save(&self, update: &LedgerUpdate) -> io::Result<()>;
+}
+
+/// Ledger store backed by a SQLite database
+///
+/// Trades and closed lots are append-only tables, so a save writes only what changed
+/// since the previous one; the remaining state is a single row replaced on each save.
+/// Every save is one transaction, so a crash mid-write leaves the previous save intact.
+#[derive(Debug)]
+pub struct SqliteLedgerStore {
+    path: PathBuf,
+    conn: Mutex<Connection>,
+}
+
+impl SqliteLedgerStore {
+    /// Open or create the database at a path
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let conn = Connection::open(path.as_ref()).map_err(io::Error::other)?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(io::Error::other)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS ledger_state (
+                 id INTEGER PRIMARY KEY CHECK (id = 0),
+                 state TEXT NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS trades (
+                 seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                 trade_id TEXT NOT NULL,
+                 timestamp_ms INTEGER NOT NULL,
+                 trade TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS trades_timestamp ON trades (timestamp_ms);
+             CREATE TABLE IF NOT EXISTS closed_lots (
+                 seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                 lot TEXT NOT NULL
+             );",
+        )
+        .map_err(io::Error::other)?;
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Get the path of the database file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl LedgerStore for SqliteLedgerStore {
+    fn load(
+        &self,
+        max_trades: usize,
+        max_closed_lots: usize,
+    ) -> io::Result<Option<LedgerSnapshot>> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let state: Option<String> = conn
+            .query_row("SELECT state FROM ledger_state WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .optional()
+            .map_err(io::Error::other)?;
+        let Some(state) = state else {
+            return Ok(None);
+        };
+
+        let limit = |max: usize| i64::try_from(max).unwrap_or(i64::MAX);
+        let trades = query_json(
+            &conn,
+            "SELECT trade FROM (SELECT seq, trade FROM trades ORDER BY seq DESC LIMIT ?1)
+             ORDER BY seq",
+            [limit(max_trades)],
+        )?;
+        let closed_lots = query_json(
+            &conn,
+            "SELECT lot FROM (SELECT seq, lot FROM closed_lots ORDER BY seq DESC LIMIT ?1)
+             ORDER BY seq",
+            [limit(max_closed_lots)],
+        )?;
+
+        Ok(Some(LedgerSnapshot {
+            state: serde_json::from_str(&state)?,
+            trades,
+            closed_lots,
+        }))
+    }
+
+    fn load_trades(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> io::Result<Vec<TradeRecord>> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        query_json(
+            &conn,
+            "SELECT trade FROM trades WHERE timestamp_ms BETWEEN ?1 AND ?2 ORDER BY seq",
+            [start.timestamp_millis(), end.timestamp_millis()],
+        )
+    }
+
+    fn save(&self, update: &LedgerUpdate) -> io::Result<()> {
+        let state = serde_json::to_string(&update.state)?;
+        let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let tx = conn.transaction().map_err(io::Error::other)?;
+        {
+            let mut insert_trade = tx
+                .prepare_cached(
+                    "INSERT INTO trades (trade_id, timestamp_ms, trade) VALUES (?1, ?2, ?3)",
+                )
+                .map_err(io::Error::other)?;
+            for trade in &update.new_trades {
+                insert_trade
+                    .execute(params![
+                        trade.trade_id,
+                        trade.timestamp.timestamp_millis(),
+                        serde_json::to_string(trade)?
+                    ])
+                    .map_err(io::Error::other)?;
+            }
+            let mut insert_lot = tx
+                .prepare_cached("INSERT INTO closed_lots (lot) VALUES (?1)")
+                .map_err(io::Error::other)?;
+            for lot in &update.new_closed_lots {
+                insert_lot
+                    .execute(params![serde_json::to_string(lot)?])
+                    .map_err(io::Error::other)?;
+            }
+        }
+        tx.execute(
+            "INSERT INTO ledger_state (id, state) VALUES (0, ?1)
+             ON CONFLICT(id) DO UPDATE SET state = excluded.state",
+            params![state],
+        )
+        .map_err(io::Error::other)?;
+        tx.commit().map_err(io::Error::other)
+    }
+}
+
+/// Run a query selecting one JSON column and parse its rows
+fn query_json<T: DeserializeOwned>(
+    conn: &Connection,
+    sql: &str,
+    params: impl Params,
+) -> io::Result<Vec<T>> {
+    let mut stmt = conn.prepare_cached(sql).map_err(io::Error::other)?;
+    let rows = stmt
+        .query_map(params, |row| row.get::<_, String>(0))
+        .map_err(io::Error::other)?;
+    rows.map(|json| Ok(serde_json::from_str(&json.map_err(io::Error::other)?)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OrderSide;
+    use crate::types::{Size, Symbol};
+    use chrono::Utc;
+
+    fn trade(id: &str) -> TradeRecord {
+        TradeRecord::new(
+            id.to_string(),
+            Symbol::new("BTCUSDT"),
+            "binance".to_string(),
+            format!("order_{}", id),
+            OrderSide::Buy,
+            Size::from_str("1").unwrap(),
+            Price::from_str("100").unwrap(),
+            Utc::now(),
+            Size::from_str("0").unwrap(),
+            "USDT".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_saves_append_trades_and_replace_state() {
+        let path = std::env::temp_dir().join(format!("ledger_{}.db", uuid::Uuid::new_v4()));
+        let store = SqliteLedgerStore::open(&path).unwrap();
+        assert!(store.load(10, 10).unwrap().is_none());
+
+        for (id, peak) in [("1", 5), ("2", 7)] {
+            store
+                .save(&LedgerUpdate {
+                    state: LedgerState {
+                        peak_equity: Decimal::from(peak),
+                        ..LedgerState::default()
+                    },
+                    new_trades: vec![trade(id)],
+                    new_closed_lots: Vec::new(),
+                })
+                .unwrap();
+        }
+
+        let reopened = SqliteLedgerStore::open(&path).unwrap();
+        let snapshot = reopened.load(10, 10).unwrap().unwrap();
+        let ids: Vec<_> = snapshot
+            .trades
+            .iter()
+            .map(|t| t.trade_id.as_str())
+            .collect();
+        assert_eq!(ids, ["1", "2"]);
+        assert_eq!(snapshot.state.peak_equity, Decimal::from(7));
+        let latest = reopened.load(1, 10).unwrap().unwrap();
+        assert_eq!(latest.trades.len(), 1);
+        assert_eq!(latest.trades[0].trade_id, "2");
+        let now = Utc::now();
+        let in_range = reopened
+            .load_trades(now - chrono::Duration::hours(1), now)
+            .unwrap();
+        assert_eq!(in_range.len(), 2);
+        assert!(reopened
+            .load_trades(
+                now + chrono::Duration::hours(1),
+                now + chrono::Duration::hours(2)
+            )
+            .unwrap()
+            .is_empty());
+
+        drop((store, reopened));
+        for suffix in ["", "-wal", "-shm"] {
+            let mut file = path.clone().into_os_string();
+            file.push(suffix);
+            let _ = std::fs::remove_file(file);
+        }
+    }
+}
@@ -1,6 +1,43 @@
+pub mod approvals;
+pub mod bootstrap;
+pub mod circuit_breaker;
+pub mod config_watcher;
+pub mod fee_conversion;
+#[cfg(feature = "fee-reconciliation")]
+pub mod fee_reconciliation;
+pub mod kill_switch;
+pub mod ledger_reconciliation;
+pub mod ledger_store;
+pub mod portfolio;
+pub mod quarantine;
+pub mod reconciler;
 pub mod rules;
+pub mod session;
 pub mod shadow_ledger;
 
 pub use crate::core::events::RiskViolation;
-pub use rules::{RiskEngine, RiskRule};
-pub use shadow_ledger::ShadowLedger;
+pub use approvals::{ApprovalError, ApprovalToken, LimitKey, LimitOverrideManager};
+pub use bootstrap::{
+    BootstrapConfig, BootstrapError, BootstrapReport, ColdStartBootstrap, RecommendedLimits,
+};
+pub use circuit_breaker::{AccountLossBreaker, AccountLossConfig, BreachReport};
+pub use config_watcher::{RiskConfigError, RiskConfigWatcher, RiskLimits, SymbolLimits};
+pub use fee_conversion::{FeeConverter, MarketFeeConverter};
+#[cfg(feature = "fee-reconciliation")]
+pub use fee_reconciliation::{
+    FeeDiscrepancy, FeeReconciler, FeeReconciliationConfig, FeeReconciliationReport,
+};
+pub use kill_switch::{HaltRecord, HaltReport, HaltSource, KillSwitch};
+pub use ledger_reconciliation::{
+    LedgerDivergence, LedgerReconciler, LedgerReconciliationConfig, LedgerReconciliationReport,
+};
+pub use ledger_store::{LedgerSnapshot, LedgerState, LedgerStore, LedgerUpdate, SqliteLedgerStore};
+pub use portfolio::{PortfolioLevel, PortfolioNode, PortfolioView};
+pub use quarantine::{QuarantineSource, SymbolKillSwitch, SymbolQuarantine};
+pub use reconciler::{Discrepancy, Reconciler, ReconcilerConfig, ReconciliationReport};
+pub use rules::{
+    PriceBandRule, RiskEngine, RiskRule, SelfMatchAction, SelfMatchPreventionRule, VaRMethod,
+    VaRRule,
+};
+pub use session::{SessionConfig, SessionPnl, WeekendPolicy};
+pub use shadow_ledger::{ClosedLot, CostBasisMethod, ShadowLedger};
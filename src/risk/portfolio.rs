@@ -0,0 +1,462 @@
+use crate::oms::OrderManagerImpl;
+use crate::risk::shadow_ledger::{PositionRecord, ShadowLedger};
+use crate::types::Price;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Strategy key of fills no strategy is known for
+pub const UNATTRIBUTED: &str = "unattributed";
+
+/// Level of the portfolio hierarchy, from the top
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortfolioLevel {
+    Portfolio,
+    AssetClass,
+    Symbol,
+    Venue,
+    Strategy,
+}
+
+/// Exposure and P&L of one node of the portfolio hierarchy, rolled up from its children
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortfolioNode {
+    pub level: PortfolioLevel,
+    pub key: String,
+    /// Net position size; None above the symbol level, where instruments differ
+    pub net_size: Option<Decimal>,
+    /// Signed position value at the mark (average price without a mark)
+    pub net_exposure: Decimal,
+    /// Sum of the absolute exposures of the positions below
+    pub gross_exposure: Decimal,
+    pub realized_pnl: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub fees: Decimal,
+    pub trade_count: usize,
+    /// Children sorted by key
+    pub children: Vec<PortfolioNode>,
+}
+
+impl PortfolioNode {
+    fn empty(level: PortfolioLevel, key: impl Into<String>) -> Self {
+        let net_size = match level {
+            PortfolioLevel::Portfolio | PortfolioLevel::AssetClass => None,
+            _ => Some(Decimal::ZERO),
+        };
+        Self {
+            level,
+            key: key.into(),
+            net_size,
+            net_exposure: Decimal::ZERO,
+            gross_exposure: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            fees: Decimal::ZERO,
+            trade_count: 0,
+            children: Vec::new(),
+        }
+    }
+
+    fn from_position(
+        level: PortfolioLevel,
+        key: impl Into<String>,
+        position: &PositionRecord,
+        mark: Option<Price>,
+    ) -> Self {
+        let net_exposure = mark
+            .or(position.average_price)
+            .map(|price| position.size.value() * price.value())
+            .unwrap_or(Decimal::ZERO);
+        Self {
+            net_size: Some(position.size.value()),
+            net_exposure,
+            gross_exposure: net_exposure.abs(),
+            realized_pnl: position.realized_pnl,
+            unrealized_pnl: mark
+                .and_then(|mark| position.unrealized_pnl(mark))
+                .unwrap_or(Decimal::ZERO),
+            ..Self::empty(level, key)
+        }
+    }
+
+    /// Roll up children already in place
+    fn with_children(mut self, children: Vec<PortfolioNode>) -> Self {
+        for child in &children {
+            if let (Some(size), Some(child_size)) = (&mut self.net_size, child.net_size) {
+                *size += child_size;
+            }
+            self.net_exposure += child.net_exposure;
+            self.gross_exposure += child.gross_exposure;
+            self.realized_pnl += child.realized_pnl;
+            self.unrealized_pnl += child.unrealized_pnl;
+            self.fees += child.fees;
+            self.trade_count += child.trade_count;
+        }
+        self.children = children;
+        self
+    }
+
+    /// Get realized plus unrealized P&L
+    pub fn total_pnl(&self) -> Decimal {
+        self.realized_pnl + self.unrealized_pnl
+    }
+
+    /// Get a direct child by key
+    pub fn child(&self, key: &str) -> Option<&PortfolioNode> {
+        self.children.iter().find(|child| child.key == key)
+    }
+
+    /// Drill down by keys, e.g. `["spot", "BTCUSDT", "binance"]`
+    pub fn find<S: AsRef<str>>(&self, path: &[S]) -> Option<&PortfolioNode> {
+        path.iter()
+            .try_fold(self, |node, key| node.child(key.as_ref()))
+    }
+
+    /// Get every node at a level below this one, depth first
+    pub fn at_level(&self, level: PortfolioLevel) -> Vec<&PortfolioNode> {
+        if self.level == level {
+            return vec![self];
+        }
+        self.children
+            .iter()
+            .flat_map(|child| child.at_level(level))
+            .collect()
+    }
+}
+
+/// Figures of one strategy's fills on a symbol and venue
+#[derive(Default)]
+struct StrategyBook {
+    position: Option<PositionRecord>,
+    fees: Decimal,
+    trade_count: usize,
+}
+
+/// Hierarchical view of the shadow ledger: asset class → symbol → venue → strategy
+///
+/// Venue nodes carry the ledger's own positions, so funding, borrow interest and
+/// seeded positions are included. Strategy nodes replay the venue's fills by the
+/// strategy tag of their order; whatever the tagged fills do not explain is left
+/// in an `unattributed` child so every level adds up to its parent.
+pub struct PortfolioView {
+    ledger: Arc<ShadowLedger>,
+    order_manager: Option<Arc<OrderManagerImpl>>,
+    asset_classes: HashMap<String, String>,
+}
+
+impl PortfolioView {
+    /// Create a view over a shadow ledger
+    pub fn new(ledger: Arc<ShadowLedger>) -> Self {
+        Self {
+            ledger,
+            order_manager: None,
+            asset_classes: HashMap::new(),
+        }
+    }
+
+    /// Attribute fills to strategies by the tags of their orders (builder pattern)
+    pub fn with_order_manager(mut self, order_manager: Arc<OrderManagerImpl>) -> Self {
+        self.order_manager = Some(order_manager);
+        self
+    }
+
+    /// Assign a symbol to an asset class (builder pattern)
+    pub fn with_asset_class(
+        mut self,
+        symbol: impl Into<String>,
+        asset_class: impl Into<String>,
+    ) -> Self {
+        self.asset_classes.insert(symbol.into(), asset_class.into());
+        self
+    }
+
+    /// Get a symbol's asset class
+    /// Unassigned symbols are `perpetual` if named like a swap, otherwise `spot`.
+    pub fn asset_class(&self, symbol: &str) -> String {
+        if let Some(asset_class) = self.asset_classes.get(symbol) {
+            return asset_class.clone();
+        }
+        let upper = symbol.to_uppercase();
+        if upper.ends_with("-SWAP") || upper.ends_with("PERP") {
+            "perpetual".to_string()
+        } else {
+            "spot".to_string()
+        }
+    }
+
+    async fn strategy_tags(
+        &self,
+        order_ids: impl Iterator<Item = &str>,
+    ) -> HashMap<String, String> {
+        let mut tags = HashMap::new();
+        let Some(order_manager) = &self.order_manager else {
+            return tags;
+        };
+        for order_id in order_ids {
+            if tags.contains_key(order_id) {
+                continue;
+            }
+            if let Some(tag) = order_manager
+                .get_order(&order_id.to_string())
+                .await
+                .and_then(|order| order.strategy_tag)
+            {
+                tags.insert(order_id.to_string(), tag);
+            }
+        }
+        tags
+    }
+
+    /// Build the hierarchy, marking positions at the given prices
+    pub async fn snapshot(&self, marks: &HashMap<String, Price>) -> PortfolioNode {
+        // Every fill, including those the ledger only keeps in its store
+        let trades = self
+            .ledger
+            .get_trades_in_range(DateTime::<Utc>::MIN_UTC, DateTime::<Utc>::MAX_UTC)
+            .await;
+        let tags = self
+            .strategy_tags(trades.iter().map(|t| t.order_id.as_str()))
+            .await;
+
+        // Replay each strategy's fills per symbol and venue
+        let mut books: BTreeMap<(String, String), BTreeMap<String, StrategyBook>> = BTreeMap::new();
+        for trade in &trades {
+            let strategy = tags
+                .get(&trade.order_id)
+                .map(String::as_str)
+                .unwrap_or(UNATTRIBUTED);
+            let book = books
+                .entry((trade.symbol.value().to_string(), trade.exchange_id.clone()))
+                .or_default()
+                .entry(strategy.to_string())
+                .or_default();
+            book.position
+                .get_or_insert_with(|| {
+                    PositionRecord::new(trade.symbol.clone(), trade.exchange_id.clone())
+                })
+                .apply_trade(trade);
+            book.fees += trade.fee.value();
+            book.trade_count += 1;
+        }
+
+        let mut symbols: BTreeMap<String, Vec<PortfolioNode>> = BTreeMap::new();
+        let mut positions = self.ledger.get_all_positions().await;
+        positions.sort_by(|a, b| a.exchange_id.cmp(&b.exchange_id));
+        for position in &positions {
+            let symbol = position.symbol.value();
+            let mark = marks.get(symbol).copied();
+            let strategies = books
+                .remove(&(symbol.to_string(), position.exchange_id.clone()))
+                .unwrap_or_default();
+
+            let mut venue = PortfolioNode::from_position(
+                PortfolioLevel::Venue,
+                &position.exchange_id,
+                position,
+                mark,
+            );
+            venue.fees = strategies.values().map(|book| book.fees).sum();
+            venue.trade_count = strategies.values().map(|book| book.trade_count).sum();
+
+            let mut children: Vec<_> = strategies
+                .iter()
+                .filter(|(strategy, _)| strategy.as_str() != UNATTRIBUTED)
+                .map(|(strategy, book)| {
+                    let mut node = match &book.position {
+                        Some(replayed) => PortfolioNode::from_position(
+                            PortfolioLevel::Strategy,
+                            strategy.as_str(),
+                            replayed,
+                            mark,
+                        ),
+                        None => PortfolioNode::empty(PortfolioLevel::Strategy, strategy.as_str()),
+                    };
+                    node.fees = book.fees;
+                    node.trade_count = book.trade_count;
+                    node
+                })
+                .collect();
+
+            // Whatever tagged fills do not explain, such as untagged fills, carry
+            // costs and seeded positions, stays unattributed
+            let attributed = PortfolioNode::empty(PortfolioLevel::Strategy, UNATTRIBUTED)
+                .with_children(children.clone());
+            let net_exposure = venue.net_exposure - attributed.net_exposure;
+            let residual = PortfolioNode {
+                net_size: Some(
+                    venue.net_size.unwrap_or_default() - attributed.net_size.unwrap_or_default(),
+                ),
+                net_exposure,
+                gross_exposure: net_exposure.abs(),
+                realized_pnl: venue.realized_pnl - attributed.realized_pnl,
+                unrealized_pnl: venue.unrealized_pnl - attributed.unrealized_pnl,
+                fees: venue.fees - attributed.fees,
+                trade_count: venue.trade_count - attributed.trade_count,
+                children: Vec::new(),
+                ..attributed
+            };
+            if residual.trade_count > 0
+                || !residual.net_size.unwrap_or_default().is_zero()
+                || !residual.total_pnl().is_zero()
+            {
+                children.push(residual);
+            }
+            children.sort_by(|a, b| a.key.cmp(&b.key));
+            venue.children = children;
+
+            symbols.entry(symbol.to_string()).or_default().push(venue);
+        }
+
+        let mut asset_classes: BTreeMap<String, Vec<PortfolioNode>> = BTreeMap::new();
+        for (symbol, venues) in symbols {
+            let node =
+                PortfolioNode::empty(PortfolioLevel::Symbol, symbol.as_str()).with_children(venues);
+            asset_classes
+                .entry(self.asset_class(&symbol))
+                .or_default()
+                .push(node);
+        }
+        let classes = asset_classes
+            .into_iter()
+            .map(|(asset_class, symbols)| {
+                PortfolioNode::empty(PortfolioLevel::AssetClass, asset_class).with_children(symbols)
+            })
+            .collect();
+        PortfolioNode::empty(PortfolioLevel::Portfolio, "portfolio").with_children(classes)
+    }
+
+    /// Build the hierarchy, marking positions at the ledger's last traded prices
+    pub async fn snapshot_at_last_prices(&self) -> PortfolioNode {
+        let marks = self.ledger.get_last_prices().await;
+        self.snapshot(&marks).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{OrderSide, OrderType, TimeInForce};
+    use crate::oms::order_manager::OrderInfo;
+    use crate::risk::shadow_ledger::TradeRecord;
+    use crate::types::{Size, Symbol};
+    use chrono::Utc;
+
+    fn trade(
+        id: &str,
+        symbol: &str,
+        exchange: &str,
+        side: OrderSide,
+        size: &str,
+        price: &str,
+    ) -> TradeRecord {
+        TradeRecord::new(
+            id.to_string(),
+            Symbol::new(symbol),
+            exchange.to_string(),
+            format!("o_{}", id),
+            side,
+            Size::from_str(size).unwrap(),
+            Price::from_str(price).unwrap(),
+            Utc::now(),
+            Size::from_str("0").unwrap(),
+            "USDT".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_rollup_and_drill_down() {
+        let ledger = Arc::new(ShadowLedger::new());
+        let order_manager = Arc::new(OrderManagerImpl::new("binance".to_string()));
+        for (id, tag) in [("1", "mm"), ("2", "arb"), ("3", "mm")] {
+            let order = OrderInfo::new(
+                format!("o_{}", id),
+                None,
+                Symbol::new("BTCUSDT"),
+                OrderSide::Buy,
+                OrderType::Limit,
+                TimeInForce::GoodTillCancelled,
+                Size::from_str("1").unwrap(),
+                None,
+                "binance".to_string(),
+            )
+            .with_strategy_tag(tag);
+            order_manager.add_order(order).await;
+        }
+        ledger
+            .add_trade(trade("1", "BTCUSDT", "binance", OrderSide::Buy, "2", "100"))
+            .await;
+        ledger
+            .add_trade(trade("2", "BTCUSDT", "binance", OrderSide::Buy, "1", "100"))
+            .await;
+        ledger
+            .add_trade(trade("3", "BTCUSDT", "okx", OrderSide::Buy, "1", "100"))
+            .await;
+        // No order known, so unattributed
+        ledger
+            .add_trade(trade(
+                "4",
+                "ETH-USDT-SWAP",
+                "okx",
+                OrderSide::Buy,
+                "3",
+                "10",
+            ))
+            .await;
+
+        let view = PortfolioView::new(ledger.clone()).with_order_manager(order_manager);
+        let marks = HashMap::from([
+            ("BTCUSDT".to_string(), Price::from_str("110").unwrap()),
+            ("ETH-USDT-SWAP".to_string(), Price::from_str("10").unwrap()),
+        ]);
+        let portfolio = view.snapshot(&marks).await;
+
+        assert_eq!(portfolio.trade_count, 4);
+        assert_eq!(portfolio.net_exposure, Decimal::from(470));
+        assert_eq!(
+            portfolio.unrealized_pnl,
+            ledger.get_total_unrealized_pnl(&marks).await
+        );
+        assert_eq!(
+            portfolio
+                .children
+                .iter()
+                .map(|c| c.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["perpetual", "spot"]
+        );
+
+        let btc = portfolio.find(&["spot", "BTCUSDT"]).unwrap();
+        assert_eq!(btc.net_size, Some(Decimal::from(4)));
+        assert_eq!(btc.at_level(PortfolioLevel::Venue).len(), 2);
+        let binance = btc.child("binance").unwrap();
+        assert_eq!(binance.unrealized_pnl, Decimal::from(30));
+        let strategies: Vec<_> = binance.children.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(strategies, vec!["arb", "mm"]);
+        let mm = binance.child("mm").unwrap();
+        assert_eq!(mm.net_size, Some(Decimal::from(2)));
+        assert_eq!(mm.unrealized_pnl, Decimal::from(20));
+
+        let eth = portfolio
+            .find(&["perpetual", "ETH-USDT-SWAP", "okx", UNATTRIBUTED])
+            .unwrap();
+        assert_eq!(eth.net_size, Some(Decimal::from(3)));
+        assert!(portfolio.find(&["spot", "ETHUSDT"]).is_none());
+
+        // A position seeded without fills shows up unattributed
+        ledger
+            .seed_position(PositionRecord {
+                size: Size::from_str("1").unwrap(),
+                average_price: Some(Price::from_str("100").unwrap()),
+                ..PositionRecord::new(Symbol::new("BTCUSDT"), "kraken".to_string())
+            })
+            .await;
+        let portfolio = view.snapshot(&marks).await;
+        let kraken = portfolio.find(&["spot", "BTCUSDT", "kraken"]).unwrap();
+        assert_eq!(kraken.children.len(), 1);
+        assert_eq!(kraken.children[0].key, UNATTRIBUTED);
+        assert_eq!(kraken.children[0].net_exposure, Decimal::from(110));
+    }
+}
@@ -0,0 +1,371 @@
+use crate::core::events::{NewOrder, RiskViolation};
+use crate::monitoring::metrics::MetricsCollector;
+use crate::risk::ledger_reconciliation::LedgerReconciliationReport;
+use crate::traits::ExecutionClient;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Gauge holding the number of quarantined symbols
+pub const QUARANTINED_SYMBOLS_GAUGE: &str = "quarantined_symbols";
+
+/// What put a symbol in quarantine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuarantineSource {
+    /// A risk rule configured to quarantine on violation
+    RiskViolation,
+    /// The ledger and the exchange disagree on the symbol's fills or position
+    ReconciliationBreak,
+    /// An operator command
+    Operator,
+}
+
+/// A quarantined symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub symbol: String,
+    pub source: QuarantineSource,
+    pub reason: String,
+    /// Time the symbol was quarantined, in milliseconds
+    pub since: u64,
+}
+
+/// Symbols that may not trade while the rest of the system does
+///
+/// The risk engine rejects every order for a quarantined symbol until it is released.
+/// With a file, the list survives a restart so a symbol is never silently re-enabled.
+/// Clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolQuarantine {
+    entries: Arc<RwLock<BTreeMap<String, QuarantineEntry>>>,
+    path: Option<PathBuf>,
+}
+
+impl SymbolQuarantine {
+    /// Create an in-memory quarantine list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a quarantine list persisted to a JSON file, loading any entries already in it
+    pub fn with_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let quarantine = Self {
+            path: Some(path.clone()),
+            ..Self::new()
+        };
+        if path.exists() {
+            let entries: Vec<QuarantineEntry> =
+                serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            let mut map = quarantine.entries.write().unwrap();
+            for entry in entries {
+                map.insert(entry.symbol.clone(), entry);
+            }
+        }
+        Ok(quarantine)
+    }
+
+    /// Quarantine a symbol; returns false if it already was, keeping the original entry
+    pub fn quarantine(
+        &self,
+        symbol: &str,
+        source: QuarantineSource,
+        reason: impl Into<String>,
+    ) -> bool {
+        let mut entries = self.entries.write().unwrap();
+        if entries.contains_key(symbol) {
+            return false;
+        }
+        let entry = QuarantineEntry {
+            symbol: symbol.to_string(),
+            source,
+            reason: reason.into(),
+            since: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+        };
+        warn!(
+            "Symbol {} quarantined ({:?}): {}",
+            symbol, source, entry.reason
+        );
+        entries.insert(symbol.to_string(), entry);
+        self.persist(&entries);
+        true
+    }
+
+    /// Let a symbol trade again, returning its entry if it was quarantined
+    pub fn release(&self, symbol: &str) -> Option<QuarantineEntry> {
+        let mut entries = self.entries.write().unwrap();
+        let entry = entries.remove(symbol)?;
+        info!("Symbol {} released from quarantine", symbol);
+        self.persist(&entries);
+        Some(entry)
+    }
+
+    /// Check if a symbol is quarantined
+    pub fn is_quarantined(&self, symbol: &str) -> bool {
+        self.entries.read().unwrap().contains_key(symbol)
+    }
+
+    /// Get a symbol's quarantine entry
+    pub fn get(&self, symbol: &str) -> Option<QuarantineEntry> {
+        self.entries.read().unwrap().get(symbol).cloned()
+    }
+
+    /// Get all quarantined symbols, sorted by symbol
+    pub fn entries(&self) -> Vec<QuarantineEntry> {
+        self.entries.read().unwrap().values().cloned().collect()
+    }
+
+    /// Get the number of quarantined symbols
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    /// Check if no symbol is quarantined
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().unwrap().is_empty()
+    }
+
+    fn persist(&self, entries: &BTreeMap<String, QuarantineEntry>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let entries: Vec<&QuarantineEntry> = entries.values().collect();
+        let result = serde_json::to_string(&entries)
+            .map_err(io::Error::from)
+            .and_then(|json| std::fs::write(path, json));
+        if let Err(e) = result {
+            error!("Failed to persist symbol quarantine to {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Outcome of quarantining a symbol
+#[derive(Debug, Clone, Default)]
+pub struct QuarantineReport {
+    pub symbol: String,
+    /// Whether the symbol was not already quarantined
+    pub newly_quarantined: bool,
+    /// Number of open orders cancelled
+    pub cancelled_orders: usize,
+    /// Errors encountered while cancelling
+    pub failures: Vec<String>,
+}
+
+/// Per-symbol kill switch
+///
+/// Quarantines a symbol and cancels its open orders, leaving every other symbol
+/// trading. Symbols are quarantined by operator action, by violations of the risk
+/// rules chosen with `with_violation_rules`, or by ledger reconciliation breaks.
+pub struct SymbolKillSwitch<C: ExecutionClient> {
+    quarantine: SymbolQuarantine,
+    client: Arc<C>,
+    violation_rules: BTreeSet<String>,
+    metrics: Option<Arc<MetricsCollector>>,
+}
+
+impl<C> SymbolKillSwitch<C>
+where
+    C: ExecutionClient + Send + Sync,
+{
+    /// Create a kill switch over a quarantine list, usually the risk engine's
+    pub fn new(quarantine: SymbolQuarantine, client: Arc<C>) -> Self {
+        Self {
+            quarantine,
+            client,
+            violation_rules: BTreeSet::new(),
+            metrics: None,
+        }
+    }
+
+    /// Quarantine a symbol when an order for it violates one of these rules (builder pattern)
+    pub fn with_violation_rules<I, S>(mut self, rules: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.violation_rules = rules.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Publish the number of quarantined symbols as a gauge (builder pattern)
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Get the quarantine list
+    pub fn quarantine_list(&self) -> &SymbolQuarantine {
+        &self.quarantine
+    }
+
+    /// Quarantine a symbol and cancel its open orders
+    /// Orders are cancelled even if the symbol was already quarantined.
+    pub async fn quarantine(
+        &self,
+        symbol: &str,
+        source: QuarantineSource,
+        reason: impl Into<String>,
+    ) -> QuarantineReport {
+        let mut report = QuarantineReport {
+            symbol: symbol.to_string(),
+            newly_quarantined: self.quarantine.quarantine(symbol, source, reason),
+            ..Default::default()
+        };
+        self.publish().await;
+
+        match self.client.get_open_orders(Some(symbol)).await {
+            Ok(orders) => {
+                for order in orders {
+                    match self.client.cancel_order(order.order_id.clone()).await {
+                        Ok(()) => report.cancelled_orders += 1,
+                        Err(e) => report
+                            .failures
+                            .push(format!("Failed to cancel {}: {}", order.order_id, e)),
+                    }
+                }
+            }
+            Err(e) => report
+                .failures
+                .push(format!("Failed to fetch open orders for {}: {}", symbol, e)),
+        }
+        report
+    }
+
+    /// Release a symbol from quarantine
+    pub async fn release(&self, symbol: &str) -> Option<QuarantineEntry> {
+        let entry = self.quarantine.release(symbol);
+        self.publish().await;
+        entry
+    }
+
+    /// Quarantine an order's symbol if its violation is of a configured rule
+    pub async fn on_violation(
+        &self,
+        order: &NewOrder,
+        violation: &RiskViolation,
+    ) -> Option<QuarantineReport> {
+        if !self.violation_rules.contains(&violation.rule) {
+            return None;
+        }
+        Some(
+            self.quarantine(
+                order.symbol.value(),
+                QuarantineSource::RiskViolation,
+                format!("{}: {}", violation.rule, violation.details),
+            )
+            .await,
+        )
+    }
+
+    /// Quarantine every symbol with a divergence in a reconciliation report
+    pub async fn on_reconciliation(
+        &self,
+        report: &LedgerReconciliationReport,
+    ) -> Vec<QuarantineReport> {
+        let mut reasons: BTreeMap<&str, String> = BTreeMap::new();
+        for divergence in &report.divergences {
+            reasons
+                .entry(divergence.symbol().value())
+                .or_insert_with(|| divergence.to_string());
+        }
+        let mut reports = Vec::with_capacity(reasons.len());
+        for (symbol, reason) in reasons {
+            reports.push(
+                self.quarantine(symbol, QuarantineSource::ReconciliationBreak, reason)
+                    .await,
+            );
+        }
+        reports
+    }
+
+    async fn publish(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .set_gauge(QUARANTINED_SYMBOLS_GAUGE, self.quarantine.len() as f64)
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::mock::MockExecutionClient;
+    use crate::core::events::TimeInForce;
+    use crate::risk::RiskEngine;
+    use crate::types::{Price, Size};
+
+    fn order(symbol: &str) -> NewOrder {
+        NewOrder::new_limit_buy(
+            symbol,
+            Size::from_str("1").unwrap(),
+            Price::from_str("100").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_symbol_kill_switch() {
+        let path = std::env::temp_dir().join(format!("quarantine_{}.json", uuid::Uuid::new_v4()));
+        let quarantine = SymbolQuarantine::with_file(&path).unwrap();
+        let risk_engine = RiskEngine::new().with_quarantine(quarantine.clone());
+        let client = Arc::new(MockExecutionClient::new());
+        for symbol in ["BTCUSDT", "BTCUSDT", "ETHUSDT"] {
+            client.place_order(order(symbol)).await.unwrap();
+        }
+        let metrics = Arc::new(MetricsCollector::new());
+        let kill_switch = SymbolKillSwitch::new(quarantine.clone(), client.clone())
+            .with_violation_rules(["MaxPositionSize"])
+            .with_metrics(metrics.clone());
+
+        let violation = RiskViolation::new("MaxOrderSize".to_string(), "too big".to_string());
+        assert!(kill_switch
+            .on_violation(&order("BTCUSDT"), &violation)
+            .await
+            .is_none());
+        let violation = RiskViolation::new("MaxPositionSize".to_string(), "too long".to_string());
+        let report = kill_switch
+            .on_violation(&order("BTCUSDT"), &violation)
+            .await
+            .unwrap();
+        assert!(report.newly_quarantined);
+        assert_eq!(report.cancelled_orders, 2);
+        assert_eq!(client.get_open_orders(None).await.unwrap().len(), 1);
+        let gauge = metrics
+            .get_metrics()
+            .await
+            .into_iter()
+            .find(|m| m.name == format!("gauge.{}", QUARANTINED_SYMBOLS_GAUGE))
+            .unwrap();
+        assert!(
+            matches!(gauge.value, crate::monitoring::metrics::MetricValue::Gauge(v) if v == 1.0)
+        );
+
+        // Only the quarantined symbol is blocked
+        let rejected = risk_engine
+            .check_order(&order("BTCUSDT"))
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.rule, "SymbolQuarantined");
+        assert!(risk_engine.check_order(&order("ETHUSDT")).await.is_ok());
+
+        // The list survives a restart
+        let reopened = SymbolQuarantine::with_file(&path).unwrap();
+        let entry = reopened.get("BTCUSDT").unwrap();
+        assert_eq!(entry.source, QuarantineSource::RiskViolation);
+        assert!(entry.reason.starts_with("MaxPositionSize"));
+
+        assert!(kill_switch.release("BTCUSDT").await.is_some());
+        assert!(risk_engine.check_order(&order("BTCUSDT")).await.is_ok());
+        assert!(SymbolQuarantine::with_file(&path).unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
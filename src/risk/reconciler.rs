@@ -0,0 +1,431 @@
+use crate::core::events::{Balance, ExecutionReport, OrderId};
+use crate::monitoring::metrics::MetricsCollector;
+use crate::oms::OrderManagerImpl;
+use crate::risk::shadow_ledger::{PositionRecord, ShadowLedger};
+use crate::traits::ExecutionClient;
+use crate::types::{Size, Symbol};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use rust_decimal::Decimal;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Gauge holding the discrepancy count of the latest run
+pub const RECONCILIATION_DISCREPANCY_GAUGE: &str = "reconciliation_discrepancies";
+
+/// Position reconciliation configuration
+#[derive(Debug, Clone)]
+pub struct ReconcilerConfig {
+    /// Exchange whose positions are reconciled, as recorded in the ledger
+    pub exchange_id: String,
+    /// Quote asset of the symbols; a symbol's base asset is what remains of its name
+    pub quote_asset: String,
+    /// Symbols to reconcile, in addition to any the ledger holds on the exchange
+    pub symbols: Vec<String>,
+    /// Largest position difference treated as a match
+    pub size_tolerance: Decimal,
+    /// Overwrite mismatched ledger positions with the exchange's
+    pub auto_correct: bool,
+    /// Interval between runs of the reconciliation job
+    pub interval: Duration,
+}
+
+impl Default for ReconcilerConfig {
+    fn default() -> Self {
+        Self {
+            exchange_id: "binance".to_string(),
+            quote_asset: "USDT".to_string(),
+            symbols: Vec::new(),
+            size_tolerance: Decimal::new(1, 8),
+            auto_correct: false,
+            interval: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// A difference between the engine's view and the exchange's
+#[derive(Debug, Clone)]
+pub enum Discrepancy {
+    /// The ledger position differs from the exchange balance of the base asset
+    PositionMismatch {
+        symbol: Symbol,
+        ledger: Size,
+        exchange: Size,
+    },
+    /// An order open on the exchange that the order manager does not track as active
+    UntrackedOrder(ExecutionReport),
+    /// An order the order manager tracks as active that is not open on the exchange
+    StaleOrder { order_id: OrderId, symbol: Symbol },
+}
+
+impl Discrepancy {
+    /// Get the symbol the discrepancy is on
+    pub fn symbol(&self) -> &Symbol {
+        match self {
+            Discrepancy::PositionMismatch { symbol, .. }
+            | Discrepancy::StaleOrder { symbol, .. } => symbol,
+            Discrepancy::UntrackedOrder(report) => &report.symbol,
+        }
+    }
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::PositionMismatch {
+                symbol,
+                ledger,
+                exchange,
+            } => write!(
+                f,
+                "{} position is {} in the ledger, {} on the exchange",
+                symbol, ledger, exchange
+            ),
+            Discrepancy::UntrackedOrder(report) => write!(
+                f,
+                "{} order {} is open on the exchange but not tracked",
+                report.symbol, report.order_id
+            ),
+            Discrepancy::StaleOrder { order_id, symbol } => write!(
+                f,
+                "{} order {} is tracked as active but not open on the exchange",
+                symbol, order_id
+            ),
+        }
+    }
+}
+
+/// Result of a reconciliation run
+#[derive(Debug, Clone, Default)]
+pub struct ReconciliationReport {
+    /// When the exchange was queried
+    pub checked_at: Option<DateTime<Utc>>,
+    /// Number of positions that matched
+    pub matched_positions: usize,
+    /// Differences found
+    pub discrepancies: Vec<Discrepancy>,
+    /// Symbols whose ledger position was overwritten with the exchange's
+    pub corrected: Vec<Symbol>,
+    /// Queries that could not be completed
+    pub failures: Vec<String>,
+}
+
+impl ReconciliationReport {
+    /// Check if the engine and the exchange agree
+    pub fn is_clean(&self) -> bool {
+        self.discrepancies.is_empty() && self.failures.is_empty()
+    }
+}
+
+/// Compares shadow ledger positions and tracked orders against the exchange's balances
+/// and open orders
+///
+/// Run it once on startup, before trading, so a ledger restored from a store is checked
+/// against what actually happened while the engine was down; `spawn` keeps checking after.
+pub struct Reconciler<C: ExecutionClient> {
+    config: ReconcilerConfig,
+    shadow_ledger: Arc<ShadowLedger>,
+    client: Arc<C>,
+    order_manager: Option<Arc<OrderManagerImpl>>,
+    metrics: Option<Arc<MetricsCollector>>,
+}
+
+impl<C> Reconciler<C>
+where
+    C: ExecutionClient + Send + Sync + 'static,
+{
+    /// Create a new reconciler
+    pub fn new(config: ReconcilerConfig, shadow_ledger: Arc<ShadowLedger>, client: Arc<C>) -> Self {
+        Self {
+            config,
+            shadow_ledger,
+            client,
+            order_manager: None,
+            metrics: None,
+        }
+    }
+
+    /// Also compare open orders against an order manager (builder pattern)
+    pub fn with_order_manager(mut self, order_manager: Arc<OrderManagerImpl>) -> Self {
+        self.order_manager = Some(order_manager);
+        self
+    }
+
+    /// Publish the discrepancy count as a gauge (builder pattern)
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &ReconcilerConfig {
+        &self.config
+    }
+
+    async fn compare_positions(&self, balances: &[Balance], report: &mut ReconciliationReport) {
+        let mut symbols: BTreeSet<String> = self.config.symbols.iter().cloned().collect();
+        symbols.extend(
+            self.shadow_ledger
+                .get_positions_by_exchange(&self.config.exchange_id)
+                .await
+                .into_iter()
+                .map(|p| p.symbol.value().to_string()),
+        );
+        let balances: HashMap<&str, Decimal> = balances
+            .iter()
+            .map(|b| (b.asset.as_str(), b.total))
+            .collect();
+
+        for symbol in symbols {
+            let Some(base) = symbol.strip_suffix(self.config.quote_asset.as_str()) else {
+                report.failures.push(format!(
+                    "{} is not quoted in {}",
+                    symbol, self.config.quote_asset
+                ));
+                continue;
+            };
+            let ledger = self
+                .shadow_ledger
+                .get_position(&symbol, &self.config.exchange_id)
+                .await;
+            let ledger_size = ledger.as_ref().map(|p| p.size).unwrap_or_else(Size::zero);
+            let exchange = Size::new(balances.get(base).copied().unwrap_or(Decimal::ZERO));
+            if (ledger_size.value() - exchange.value()).abs() <= self.config.size_tolerance {
+                report.matched_positions += 1;
+                continue;
+            }
+
+            report.discrepancies.push(Discrepancy::PositionMismatch {
+                symbol: Symbol::new(&symbol),
+                ledger: ledger_size,
+                exchange,
+            });
+            if self.config.auto_correct {
+                self.correct_position(&symbol, ledger, exchange).await;
+                report.corrected.push(Symbol::new(&symbol));
+            }
+        }
+    }
+
+    /// Overwrite a ledger position with the exchange's size, keeping its realized P&L
+    /// The cost basis is kept if the position was held, otherwise taken from the last price.
+    async fn correct_position(&self, symbol: &str, ledger: Option<PositionRecord>, size: Size) {
+        let mut record = ledger.unwrap_or_else(|| {
+            PositionRecord::new(Symbol::new(symbol), self.config.exchange_id.clone())
+        });
+        record.size = size;
+        if size.is_zero() {
+            record.average_price = None;
+        } else if record.average_price.is_none() {
+            record.average_price = self
+                .shadow_ledger
+                .get_last_prices()
+                .await
+                .get(symbol)
+                .copied();
+        }
+        record.total_cost = record
+            .average_price
+            .map(|price| price.value() * size.value())
+            .unwrap_or(Decimal::ZERO);
+        record.last_updated = Utc::now();
+        warn!("Correcting {} ledger position to {}", symbol, size);
+        self.shadow_ledger.seed_position(record).await;
+    }
+
+    async fn compare_orders(
+        &self,
+        order_manager: &OrderManagerImpl,
+        open_orders: Vec<ExecutionReport>,
+        report: &mut ReconciliationReport,
+    ) {
+        let mut tracked: HashMap<OrderId, Symbol> = order_manager
+            .get_all_active_orders()
+            .await
+            .into_iter()
+            .map(|order| (order.order_id, order.symbol))
+            .collect();
+        for order in open_orders {
+            if tracked.remove(&order.order_id).is_none() {
+                report
+                    .discrepancies
+                    .push(Discrepancy::UntrackedOrder(order));
+            }
+        }
+        let mut stale: Vec<_> = tracked.into_iter().collect();
+        stale.sort_by(|a, b| a.0.cmp(&b.0));
+        report.discrepancies.extend(
+            stale
+                .into_iter()
+                .map(|(order_id, symbol)| Discrepancy::StaleOrder { order_id, symbol }),
+        );
+    }
+
+    /// Reconcile positions, and open orders if an order manager is set
+    /// Sets the discrepancy gauge to the number of discrepancies found
+    pub async fn reconcile(&self) -> ReconciliationReport {
+        let mut report = ReconciliationReport {
+            checked_at: Some(Utc::now()),
+            ..Default::default()
+        };
+
+        match self.client.get_balances().await {
+            Ok(balances) => self.compare_positions(&balances, &mut report).await,
+            Err(e) => {
+                warn!("Failed to fetch balances: {}", e);
+                report
+                    .failures
+                    .push(format!("Failed to fetch balances: {}", e));
+            }
+        }
+
+        if let Some(order_manager) = &self.order_manager {
+            match self.client.get_open_orders(None).await {
+                Ok(open_orders) => {
+                    self.compare_orders(order_manager, open_orders, &mut report)
+                        .await
+                }
+                Err(e) => {
+                    warn!("Failed to fetch open orders: {}", e);
+                    report
+                        .failures
+                        .push(format!("Failed to fetch open orders: {}", e));
+                }
+            }
+        }
+
+        for discrepancy in &report.discrepancies {
+            warn!("Reconciliation discrepancy: {}", discrepancy);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .set_gauge(
+                    RECONCILIATION_DISCREPANCY_GAUGE,
+                    report.discrepancies.len() as f64,
+                )
+                .await;
+        }
+
+        info!(
+            "Position reconciliation: matched={}, discrepancies={}, corrected={}, failures={}",
+            report.matched_positions,
+            report.discrepancies.len(),
+            report.corrected.len(),
+            report.failures.len()
+        );
+        report
+    }
+
+    /// Spawn the reconciliation job
+    /// The first run happens immediately, as the startup check.
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            loop {
+                interval.tick().await;
+                self.reconcile().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::mock::MockExecutionClient;
+    use crate::core::events::{NewOrder, OrderSide, TimeInForce};
+    use crate::risk::shadow_ledger::TradeRecord;
+    use crate::types::Price;
+
+    #[tokio::test]
+    async fn test_reconcile_positions_and_orders() {
+        let ledger = Arc::new(ShadowLedger::new());
+        for (id, symbol) in [("1", "BTCUSDT"), ("2", "ETHUSDT")] {
+            ledger
+                .add_trade(TradeRecord::new(
+                    id.to_string(),
+                    Symbol::new(symbol),
+                    "binance".to_string(),
+                    format!("o{}", id),
+                    OrderSide::Buy,
+                    Size::from_str("1").unwrap(),
+                    Price::from_str("100").unwrap(),
+                    Utc::now(),
+                    Size::from_str("0").unwrap(),
+                    "USDT".to_string(),
+                ))
+                .await;
+        }
+
+        let client = Arc::new(MockExecutionClient::new());
+        client
+            .set_balance("BTC", Size::from_str("1").unwrap(), Size::zero())
+            .await;
+        client
+            .set_balance("ETH", Size::from_str("0.5").unwrap(), Size::zero())
+            .await;
+        client
+            .set_balance("SOL", Size::from_str("3").unwrap(), Size::zero())
+            .await;
+        let open_order = client
+            .place_order(NewOrder::new_limit_buy(
+                "BTCUSDT",
+                Size::from_str("1").unwrap(),
+                Price::from_str("90").unwrap(),
+                TimeInForce::GoodTillCancelled,
+            ))
+            .await
+            .unwrap();
+
+        let config = ReconcilerConfig {
+            symbols: vec!["SOLUSDT".to_string()],
+            ..Default::default()
+        };
+        let order_manager = Arc::new(OrderManagerImpl::new("binance".to_string()));
+        let reconciler = Reconciler::new(config.clone(), ledger.clone(), client.clone())
+            .with_order_manager(order_manager);
+
+        let report = reconciler.reconcile().await;
+        assert_eq!(report.matched_positions, 1);
+        let mismatched: Vec<_> = report
+            .discrepancies
+            .iter()
+            .filter(|d| matches!(d, Discrepancy::PositionMismatch { .. }))
+            .map(|d| d.symbol().value().to_string())
+            .collect();
+        assert_eq!(mismatched, vec!["ETHUSDT", "SOLUSDT"]);
+        assert!(report.discrepancies.iter().any(
+            |d| matches!(d, Discrepancy::UntrackedOrder(order) if order.order_id == open_order)
+        ));
+        assert!(report.corrected.is_empty());
+        assert_eq!(
+            ledger
+                .get_position("ETHUSDT", "binance")
+                .await
+                .unwrap()
+                .size,
+            Size::from_str("1").unwrap()
+        );
+
+        // Auto-correct brings the ledger in line with the exchange
+        let reconciler = Reconciler::new(
+            ReconcilerConfig {
+                auto_correct: true,
+                ..config
+            },
+            ledger.clone(),
+            client,
+        );
+        let report = reconciler.reconcile().await;
+        assert_eq!(report.corrected.len(), 2);
+        let eth = ledger.get_position("ETHUSDT", "binance").await.unwrap();
+        assert_eq!(eth.size, Size::from_str("0.5").unwrap());
+        assert_eq!(eth.average_price, Some(Price::from_str("100").unwrap()));
+        let sol = ledger.get_position("SOLUSDT", "binance").await.unwrap();
+        assert_eq!(sol.size, Size::from_str("3").unwrap());
+        assert!(reconciler.reconcile().await.is_clean());
+    }
+}
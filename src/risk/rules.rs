@@ -1,6 +1,20 @@
-use crate::core::events::{NewOrder, OrderSide, Position, RiskViolation};
-use crate::types::{Price, Size};
-use std::collections::HashMap;
+use crate::core::clock::{system_clock, SharedClock};
+use crate::core::events::{
+    Liquidation, MarginAccount, NewOrder, OrderBookSnapshot, OrderId, OrderSide, Position,
+    RiskViolation, TimeInForce,
+};
+use crate::indicators::liquidation_indicators::LiquidationTracker;
+use crate::monitoring::alerts::{AlertLevel, AlertManager};
+use crate::oms::order_manager::{OrderInfo, OrderManagerImpl};
+use crate::orderbook::ExecutionLadder;
+use crate::risk::kill_switch::{HaltSource, KillSwitch};
+use crate::risk::quarantine::SymbolQuarantine;
+use crate::traits::ExecutionClient;
+use crate::types::{Notional, Price, Size};
+use log::{error, info, warn};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -14,9 +28,92 @@ pub trait RiskRule: Send + Sync {
         order: &NewOrder,
         risk_engine: &RiskEngine,
     ) -> Option<RiskViolation>;
+
+    /// Get the largest size of this order the rule would accept
+    /// Returns None if the rule cannot be satisfied by shrinking the order
+    async fn max_order_size(&self, _order: &NewOrder, _risk_engine: &RiskEngine) -> Option<Size> {
+        None
+    }
+}
+
+/// Largest size that keeps `|current + signed size|` within `max`
+fn remaining_capacity(current: Decimal, max: Decimal, side: OrderSide) -> Size {
+    let room = match side {
+        OrderSide::Buy => max.checked_sub(current),
+        OrderSide::Sell => max.checked_add(current),
+    };
+    Size::new(room.unwrap_or(Decimal::MAX).max(Decimal::ZERO))
+}
+
+/// Decimal places kept when shrinking an order to fit the limits
+const RESIZE_DECIMALS: u32 = 8;
+
+/// Execution client of one venue, as held by the risk engine
+type SharedExecutionClient =
+    Arc<dyn ExecutionClient<Error = Box<dyn std::error::Error + Send + Sync>> + Send + Sync>;
+
+/// What the risk engine does when an order violates a rule, besides rejecting it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreachAction {
+    /// Cancel resting orders on the order's symbol
+    CancelSymbolOrders,
+    /// Cancel resting orders on the order's symbol and flatten its position
+    FlattenSymbol,
+    /// Cancel every resting order
+    CancelAllOrders,
+    /// Cancel every resting order and flatten every position
+    FlattenAll,
+}
+
+/// Outcome of enforcing a rule breach
+#[derive(Debug, Clone, Default)]
+pub struct EnforcementReport {
+    /// Rule that was breached
+    pub rule: String,
+    /// Orders cancelled at the venues
+    pub cancelled_orders: Vec<OrderId>,
+    /// IOC orders sent to flatten positions
+    pub flatten_orders: Vec<OrderId>,
+    /// Errors encountered while cancelling or flattening
+    pub failures: Vec<String>,
+}
+
+/// How long order submissions are remembered for rate checks, in milliseconds
+const ORDER_RATE_HISTORY_MS: u64 = 60_000;
+
+/// What an order rate is counted over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderRateScope {
+    Symbol,
+    Exchange,
+}
+
+/// Recent order submission times by scope and key
+#[derive(Debug, Default)]
+struct OrderSubmissions {
+    times: HashMap<(OrderRateScope, String), VecDeque<u64>>,
+}
+
+impl OrderSubmissions {
+    fn record(&mut self, scope: OrderRateScope, key: &str, timestamp: u64) {
+        let times = self.times.entry((scope, key.to_string())).or_default();
+        times.push_back(timestamp);
+        let cutoff = timestamp.saturating_sub(ORDER_RATE_HISTORY_MS);
+        while times.front().is_some_and(|t| *t <= cutoff) {
+            times.pop_front();
+        }
+    }
+
+    fn count_since(&self, scope: OrderRateScope, key: &str, since: u64) -> usize {
+        self.times
+            .get(&(scope, key.to_string()))
+            .map_or(0, |times| times.iter().filter(|t| **t > since).count())
+    }
 }
 
 /// Risk engine that evaluates and enforces risk rules
+/// Clones share the same state
+#[derive(Clone)]
 pub struct RiskEngine {
     /// All risk rules
     rules: Arc<RwLock<Vec<Box<dyn RiskRule>>>>,
@@ -38,6 +135,28 @@ pub struct RiskEngine {
     max_open_orders: Arc<RwLock<usize>>,
     /// Current number of open orders
     open_orders_count: Arc<RwLock<usize>>,
+    /// Global halt shared with the components that can trip it
+    kill_switch: KillSwitch,
+    /// Symbols that may not trade
+    quarantine: SymbolQuarantine,
+    /// Margin accounts by isolated pair (None = cross account)
+    margin_accounts: Arc<RwLock<HashMap<Option<String>, MarginAccount>>>,
+    /// Times in milliseconds of recent order submissions by symbol and by exchange
+    order_submissions: Arc<std::sync::Mutex<OrderSubmissions>>,
+    /// Latest mid or reference price by symbol, for price sanity checks
+    reference_prices: Arc<RwLock<HashMap<String, Price>>>,
+    /// Latest order book by symbol, for checks against depth beyond the touch
+    order_books: Arc<RwLock<HashMap<String, OrderBookSnapshot>>>,
+    /// Resting orders, for rules that check new orders against our own quotes
+    order_manager: Option<Arc<OrderManagerImpl>>,
+    /// Execution clients by exchange, used to cancel orders and flatten positions
+    execution_clients: HashMap<String, SharedExecutionClient>,
+    /// Enforcement on breach by rule name
+    breach_actions: Arc<RwLock<HashMap<String, BreachAction>>>,
+    /// Maximum slippage from the reference price accepted by flattening orders
+    flatten_slippage: Decimal,
+    /// Time source for order rate checks
+    clock: SharedClock,
 }
 
 impl RiskEngine {
@@ -54,9 +173,87 @@ impl RiskEngine {
             max_total_exposure: Arc::new(RwLock::new(Price::new(rust_decimal::Decimal::MAX))),
             max_open_orders: Arc::new(RwLock::new(100)),
             open_orders_count: Arc::new(RwLock::new(0)),
+            kill_switch: KillSwitch::new(),
+            quarantine: SymbolQuarantine::new(),
+            margin_accounts: Arc::new(RwLock::new(HashMap::new())),
+            order_submissions: Arc::new(std::sync::Mutex::new(OrderSubmissions::default())),
+            reference_prices: Arc::new(RwLock::new(HashMap::new())),
+            order_books: Arc::new(RwLock::new(HashMap::new())),
+            order_manager: None,
+            execution_clients: HashMap::new(),
+            breach_actions: Arc::new(RwLock::new(HashMap::new())),
+            flatten_slippage: Decimal::new(5, 3),
+            clock: system_clock(),
         }
     }
 
+    /// Set the time source (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Get the time source
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Use a shared kill switch as the engine's halt (builder pattern)
+    pub fn with_kill_switch(mut self, kill_switch: KillSwitch) -> Self {
+        self.kill_switch = kill_switch;
+        self
+    }
+
+    /// Get the kill switch
+    pub fn kill_switch(&self) -> &KillSwitch {
+        &self.kill_switch
+    }
+
+    /// Use a shared, possibly persisted, symbol quarantine list (builder pattern)
+    pub fn with_quarantine(mut self, quarantine: SymbolQuarantine) -> Self {
+        self.quarantine = quarantine;
+        self
+    }
+
+    /// Get the symbol quarantine list
+    pub fn quarantine(&self) -> &SymbolQuarantine {
+        &self.quarantine
+    }
+
+    /// Give rules access to the resting orders of an order manager (builder pattern)
+    pub fn with_order_manager(mut self, order_manager: Arc<OrderManagerImpl>) -> Self {
+        self.order_manager = Some(order_manager);
+        self
+    }
+
+    /// Get the order manager holding our resting orders, if one was injected
+    pub fn order_manager(&self) -> Option<&Arc<OrderManagerImpl>> {
+        self.order_manager.as_ref()
+    }
+
+    /// Cancel orders and flatten positions on an exchange through this client (builder pattern)
+    pub fn with_execution_client(
+        mut self,
+        exchange_id: impl Into<String>,
+        client: SharedExecutionClient,
+    ) -> Self {
+        self.execution_clients.insert(exchange_id.into(), client);
+        self
+    }
+
+    /// Set the maximum slippage from the reference price for flattening orders,
+    /// 0.5% by default (builder pattern)
+    pub fn with_flatten_slippage(mut self, max_slippage: Decimal) -> Self {
+        self.flatten_slippage = max_slippage;
+        self
+    }
+
+    /// Set what happens, besides rejection, when an order violates a rule
+    pub async fn set_breach_action(&self, rule: &str, action: BreachAction) {
+        let mut actions = self.breach_actions.write().await;
+        actions.insert(rule.to_string(), action);
+    }
+
     /// Add a risk rule
     pub async fn add_rule(&self, rule: Box<dyn RiskRule>) {
         let mut rules = self.rules.write().await;
@@ -99,6 +296,71 @@ impl RiskEngine {
         balances.insert(asset.to_string(), balance);
     }
 
+    /// Update a cross or isolated margin account snapshot
+    pub async fn update_margin_account(&self, account: MarginAccount) {
+        let key = account.symbol.as_ref().map(|s| s.as_str().to_string());
+        self.margin_accounts.write().await.insert(key, account);
+    }
+
+    /// Get the margin account an order on a symbol trades against
+    /// The symbol's isolated account if there is one, otherwise the cross account.
+    pub async fn get_margin_account(&self, symbol: &str) -> Option<MarginAccount> {
+        let accounts = self.margin_accounts.read().await;
+        accounts
+            .get(&Some(symbol.to_string()))
+            .or_else(|| accounts.get(&None))
+            .cloned()
+    }
+
+    /// Get the outstanding loan (borrowed plus interest) of an asset across margin accounts
+    pub async fn get_borrowed(&self, asset: &str) -> Size {
+        let accounts = self.margin_accounts.read().await;
+        Size::new(accounts.values().map(|a| a.liability(asset)).sum())
+    }
+
+    /// Get the margin level of the account an order on a symbol trades against
+    pub async fn get_margin_level(&self, symbol: &str) -> Option<Decimal> {
+        self.get_margin_account(symbol)
+            .await
+            .and_then(|account| account.margin_level)
+    }
+
+    /// Set the reference price of a symbol from an external feed, such as a mark or index price
+    pub async fn update_reference_price(&self, symbol: &str, price: Price) {
+        let mut prices = self.reference_prices.write().await;
+        prices.insert(symbol.to_string(), price);
+    }
+
+    /// Keep the latest order book of a symbol and set its reference price to the mid
+    /// One-sided or empty books leave the previous reference price in place.
+    pub async fn update_order_book(&self, book: &OrderBookSnapshot) {
+        if let (Some(bid), Some(ask)) = (book.bids.first(), book.asks.first()) {
+            let mid = Price::new((bid.price.value() + ask.price.value()) / Decimal::TWO);
+            self.update_reference_price(book.symbol.value(), mid).await;
+        }
+        let mut books = self.order_books.write().await;
+        books.insert(book.symbol.value().to_string(), book.clone());
+    }
+
+    /// Price taking `size` from the latest order book of a symbol
+    pub async fn execution_ladder(
+        &self,
+        symbol: &str,
+        side: OrderSide,
+        size: Size,
+    ) -> Option<ExecutionLadder> {
+        let books = self.order_books.read().await;
+        books
+            .get(symbol)
+            .map(|book| book.execution_ladder(side, size))
+    }
+
+    /// Get the latest reference price of a symbol
+    pub async fn get_reference_price(&self, symbol: &str) -> Option<Price> {
+        let prices = self.reference_prices.read().await;
+        prices.get(symbol).copied()
+    }
+
     /// Update position for a symbol
     pub async fn update_position(&self, symbol: &str, position: Position) {
         let mut positions = self.positions.write().await;
@@ -135,8 +397,223 @@ impl RiskEngine {
         }
     }
 
+    /// Halt trading: every order is rejected until `resume_trading` is called
+    /// Trips the kill switch, keeping the first reason if it already was.
+    pub async fn halt_trading(&self, reason: impl Into<String>) {
+        self.kill_switch.trip(HaltSource::RiskEngine, reason).await;
+    }
+
+    /// Resume trading after a halt
+    pub async fn resume_trading(&self) {
+        self.kill_switch.reset();
+    }
+
+    /// Check if trading is halted
+    pub async fn is_halted(&self) -> bool {
+        self.kill_switch.is_tripped()
+    }
+
+    /// Get the reason trading was halted
+    pub async fn halt_reason(&self) -> Option<String> {
+        self.kill_switch.status().map(|halt| halt.reason)
+    }
+
     /// Check if an order passes all risk rules
+    #[tracing::instrument(
+        name = "risk.check_order",
+        skip_all,
+        fields(
+            symbol = %order.symbol,
+            side = ?order.side,
+            size = %order.size,
+            violation = tracing::field::Empty,
+        )
+    )]
     pub async fn check_order(&self, order: &NewOrder) -> Result<(), RiskViolation> {
+        let result = self.evaluate_order(order).await;
+        if let Err(violation) = &result {
+            tracing::Span::current().record("violation", violation.rule.as_str());
+            self.reject_order(order, violation).await;
+        }
+        result
+    }
+
+    /// Enforce the violation an order was finally rejected for
+    /// Trips the kill switch if the rule halts trading and applies the rule's breach action.
+    pub async fn reject_order(&self, order: &NewOrder, violation: &RiskViolation) {
+        self.kill_switch.on_violation(violation).await;
+        self.enforce_breach(violation, order.symbol.value()).await;
+    }
+
+    /// Apply the breach action configured for a violated rule
+    /// Returns None if the rule has no breach action.
+    pub async fn enforce_breach(
+        &self,
+        violation: &RiskViolation,
+        symbol: &str,
+    ) -> Option<EnforcementReport> {
+        let action = *self.breach_actions.read().await.get(&violation.rule)?;
+        warn!(
+            "Enforcing {:?} after {} breach on {}",
+            action, violation.rule, symbol
+        );
+        let mut report = EnforcementReport {
+            rule: violation.rule.clone(),
+            ..Default::default()
+        };
+        match action {
+            BreachAction::CancelSymbolOrders | BreachAction::FlattenSymbol => {
+                self.cancel_orders(Some(symbol), &mut report).await;
+            }
+            BreachAction::CancelAllOrders | BreachAction::FlattenAll => {
+                self.cancel_orders(None, &mut report).await;
+            }
+        }
+        let flatten: Vec<String> = match action {
+            BreachAction::FlattenSymbol => vec![symbol.to_string()],
+            BreachAction::FlattenAll => self
+                .get_all_positions()
+                .await
+                .into_iter()
+                .map(|position| position.symbol.value().to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
+        for symbol in flatten {
+            match self.flatten_position(&symbol).await {
+                Ok(Some(order_id)) => report.flatten_orders.push(order_id),
+                Ok(None) => {}
+                Err(e) => report.failures.push(e),
+            }
+        }
+
+        info!(
+            "{} breach enforced: cancelled={}, flatten_orders={}, failures={}",
+            report.rule,
+            report.cancelled_orders.len(),
+            report.flatten_orders.len(),
+            report.failures.len()
+        );
+        Some(report)
+    }
+
+    /// Get the execution client for an exchange
+    /// Orders not routed to a specific venue go to the only client, if there is one.
+    fn execution_client(&self, exchange_id: &str) -> Option<&SharedExecutionClient> {
+        self.execution_clients.get(exchange_id).or_else(|| {
+            let mut clients = self.execution_clients.values();
+            match (clients.next(), clients.next()) {
+                (Some(client), None) => Some(client),
+                _ => None,
+            }
+        })
+    }
+
+    /// Cancel the open orders of every execution client, on one symbol or all
+    async fn cancel_orders(&self, symbol: Option<&str>, report: &mut EnforcementReport) {
+        for (exchange_id, client) in &self.execution_clients {
+            let open_orders = match client.get_open_orders(symbol).await {
+                Ok(orders) => orders,
+                Err(e) => {
+                    report.failures.push(format!(
+                        "Failed to fetch open orders on {}: {}",
+                        exchange_id, e
+                    ));
+                    continue;
+                }
+            };
+            for order in open_orders {
+                match client.cancel_order(order.order_id.clone()).await {
+                    Ok(()) => {
+                        if let Some(order_manager) = &self.order_manager {
+                            order_manager.cancel_order(&order.order_id).await;
+                        }
+                        report.cancelled_orders.push(order.order_id);
+                    }
+                    Err(e) => {
+                        error!("Failed to cancel {}: {}", order.order_id, e);
+                        report
+                            .failures
+                            .push(format!("Failed to cancel {}: {}", order.order_id, e));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Close a symbol's position with an IOC limit order priced within the flatten slippage
+    /// of its reference price. Returns None if there is no position to close.
+    pub async fn flatten_position(&self, symbol: &str) -> Result<Option<OrderId>, String> {
+        let Some(position) = self
+            .get_position(symbol)
+            .await
+            .filter(|p| !p.size.is_zero())
+        else {
+            return Ok(None);
+        };
+        let reference = self
+            .get_reference_price(symbol)
+            .await
+            .ok_or_else(|| format!("No reference price to flatten {}", symbol))?;
+        let client = self
+            .execution_client(&position.exchange_id)
+            .ok_or_else(|| format!("No execution client to flatten {}", symbol))?;
+
+        let size = Size::new(position.size.value().abs());
+        let mut order = if position.size.is_positive() {
+            let limit = Price::new(reference.value() * (Decimal::ONE - self.flatten_slippage));
+            NewOrder::new_limit_sell(symbol, size, limit, TimeInForce::ImmediateOrCancel)
+        } else {
+            let limit = Price::new(reference.value() * (Decimal::ONE + self.flatten_slippage));
+            NewOrder::new_limit_buy(symbol, size, limit, TimeInForce::ImmediateOrCancel)
+        };
+        order.exchange_id = position.exchange_id.clone();
+        client
+            .place_order(order)
+            .await
+            .map(Some)
+            .map_err(|e| format!("Failed to flatten {}: {}", symbol, e))
+    }
+
+    /// Count an order as submitted now, for order rate checks
+    /// Call it once the order is sent; checking an order does not count it.
+    pub fn record_order_submission(&self, order: &NewOrder) {
+        self.record_order_submission_at(order, self.clock.now_millis());
+    }
+
+    /// Count an order as submitted at a time in milliseconds, such as during replay
+    pub fn record_order_submission_at(&self, order: &NewOrder, timestamp: u64) {
+        let mut submissions = self.order_submissions.lock().unwrap();
+        submissions.record(OrderRateScope::Symbol, order.symbol.value(), timestamp);
+        submissions.record(OrderRateScope::Exchange, &order.exchange_id, timestamp);
+    }
+
+    /// Get the number of orders submitted for a symbol or exchange after a time in milliseconds
+    /// Only the last minute of submissions is kept.
+    pub fn get_order_count_since(&self, scope: OrderRateScope, key: &str, since: u64) -> usize {
+        let submissions = self.order_submissions.lock().unwrap();
+        submissions.count_since(scope, key, since)
+    }
+
+    /// Check an order against the halt, quarantine and every rule without side effects
+    /// Unlike `check_order`, a violation is returned but not enforced.
+    pub async fn evaluate_order(&self, order: &NewOrder) -> Result<(), RiskViolation> {
+        if let Some(reason) = self.halt_reason().await {
+            return Err(RiskViolation::new(
+                "TradingHalted".to_string(),
+                format!("Order for {} rejected: {}", order.symbol, reason),
+            ));
+        }
+        if let Some(entry) = self.quarantine.get(order.symbol.value()) {
+            return Err(RiskViolation::new(
+                "SymbolQuarantined".to_string(),
+                format!(
+                    "Order for {} rejected: symbol quarantined: {}",
+                    order.symbol, entry.reason
+                ),
+            ));
+        }
+
         let rules = self.rules.read().await;
 
         // Check against all rules
@@ -149,6 +626,47 @@ impl RiskEngine {
         Ok(())
     }
 
+    /// Check an order, shrinking it to the largest size all rules accept if it fails
+    /// Returns the violation if the order cannot pass at any size. Nothing is enforced;
+    /// pass a final rejection to `reject_order`.
+    pub async fn resize_order(&self, order: &NewOrder) -> Result<NewOrder, RiskViolation> {
+        let violation = match self.evaluate_order(order).await {
+            Ok(()) => return Ok(order.clone()),
+            Err(violation) => violation,
+        };
+        if self.is_halted().await || self.quarantine.is_quarantined(order.symbol.value()) {
+            return Err(violation);
+        }
+
+        let mut max_size = order.size;
+        {
+            let rules = self.rules.read().await;
+            for rule in rules.iter() {
+                if rule.check_order(order, self).await.is_none() {
+                    continue;
+                }
+                match rule.max_order_size(order, self).await {
+                    Some(size) => max_size = max_size.min(size),
+                    None => return Err(violation),
+                }
+            }
+        }
+
+        let max_size = Size::new(
+            max_size
+                .value()
+                .round_dp_with_strategy(RESIZE_DECIMALS, RoundingStrategy::ToZero),
+        );
+        if !max_size.is_positive() {
+            return Err(violation);
+        }
+        let mut resized = order.clone();
+        resized.size = max_size;
+        // Shrinking for one rule must not break another
+        self.evaluate_order(&resized).await.map_err(|_| violation)?;
+        Ok(resized)
+    }
+
     /// Get current position for a symbol
     pub async fn get_position(&self, symbol: &str) -> Option<Position> {
         let positions = self.positions.read().await;
@@ -167,17 +685,15 @@ impl RiskEngine {
     /// Get total exposure across all positions
     pub async fn get_total_exposure(&self) -> Price {
         let positions = self.positions.read().await;
-        let total_exposure = positions
+        let total_exposure: Notional = positions
             .values()
-            .fold(rust_decimal::Decimal::ZERO, |acc, pos| {
-                if let Some(avg_price) = pos.average_price {
-                    acc + pos.size.value() * avg_price.value()
-                } else {
-                    acc
-                }
-            });
+            .filter_map(|pos| {
+                pos.average_price
+                    .map(|avg_price| pos.size.notional(avg_price))
+            })
+            .sum();
 
-        Price::new(total_exposure)
+        Price::new(total_exposure.value())
     }
 
     /// Get current number of open orders
@@ -244,37 +760,38 @@ impl RiskEngine {
     pub async fn get_position_stats(&self) -> PositionStats {
         let positions = self.positions.read().await;
         let total_positions = positions.len();
-        let long_positions = positions
-            .values()
-            .filter(|p| p.size.value() > rust_decimal::Decimal::ZERO)
-            .count();
-        let short_positions = positions
-            .values()
-            .filter(|p| p.size.value() < rust_decimal::Decimal::ZERO)
-            .count();
-        let total_exposure = positions
+        let long_positions = positions.values().filter(|p| p.size.is_positive()).count();
+        let short_positions = positions.values().filter(|p| p.size.is_negative()).count();
+        let total_exposure: Notional = positions
             .values()
-            .fold(rust_decimal::Decimal::ZERO, |acc, pos| {
-                if let Some(avg_price) = pos.average_price {
-                    acc + pos.size.value().abs() * avg_price.value()
-                } else {
-                    acc
-                }
-            });
+            .filter_map(|pos| {
+                pos.average_price
+                    .map(|avg_price| pos.size.abs().notional(avg_price))
+            })
+            .sum();
 
         PositionStats {
             total_positions,
             long_positions,
             short_positions,
-            total_exposure: Price::new(total_exposure),
+            total_exposure: Price::new(total_exposure.value()),
         }
     }
 
-    /// Cancel all orders for a symbol (stub - returns empty vector)
-    /// In a real implementation, this would interact with order management
-    pub async fn cancel_all_orders_for_symbol(&self, _symbol: &str) -> Vec<String> {
-        // Stub implementation - would need OrderManager integration
-        Vec::new()
+    /// Cancel all open orders for a symbol at every venue with an execution client
+    /// Returns the cancelled order IDs; failures are logged.
+    pub async fn cancel_all_orders_for_symbol(&self, symbol: &str) -> Vec<String> {
+        let mut report = EnforcementReport::default();
+        self.cancel_orders(Some(symbol), &mut report).await;
+        report.cancelled_orders
+    }
+
+    /// Cancel all open orders at every venue with an execution client
+    /// Returns the cancelled order IDs; failures are logged.
+    pub async fn cancel_all_orders(&self) -> Vec<String> {
+        let mut report = EnforcementReport::default();
+        self.cancel_orders(None, &mut report).await;
+        report.cancelled_orders
     }
 }
 
@@ -348,6 +865,22 @@ impl RiskRule for PositionSizeRule {
 
         None
     }
+
+    async fn max_order_size(&self, order: &NewOrder, risk_engine: &RiskEngine) -> Option<Size> {
+        let current_size = risk_engine
+            .get_position(order.symbol.as_str())
+            .await
+            .map(|p| p.size.value())
+            .unwrap_or(Decimal::ZERO);
+        let max_size = risk_engine
+            .get_max_position_size(order.symbol.as_str())
+            .await;
+        Some(remaining_capacity(
+            current_size,
+            max_size.value(),
+            order.side,
+        ))
+    }
 }
 
 /// Order size limit rule
@@ -393,6 +926,10 @@ impl RiskRule for OrderSizeRule {
 
         None
     }
+
+    async fn max_order_size(&self, order: &NewOrder, risk_engine: &RiskEngine) -> Option<Size> {
+        Some(risk_engine.get_max_order_size(order.symbol.as_str()).await)
+    }
 }
 
 /// Daily loss limit rule
@@ -465,6 +1002,24 @@ impl RiskRule for DailyLossRule {
 
         None
     }
+
+    async fn max_order_size(&self, order: &NewOrder, risk_engine: &RiskEngine) -> Option<Size> {
+        let price = order.price?;
+        let position = risk_engine.get_position(order.symbol.as_str()).await?;
+        let avg_price = position.average_price.unwrap_or(price);
+        let loss_per_unit = (price.value() - avg_price.value()).abs();
+        let remaining = risk_engine
+            .get_max_daily_loss(order.symbol.as_str())
+            .await
+            .value()
+            .checked_sub(
+                risk_engine
+                    .get_daily_loss(order.symbol.as_str())
+                    .await
+                    .value(),
+            )?;
+        Size::new(remaining.max(Decimal::ZERO)).checked_div(loss_per_unit)
+    }
 }
 
 /// Total exposure limit rule
@@ -491,9 +1046,8 @@ impl RiskRule for TotalExposureRule {
         let current_exposure = risk_engine.get_total_exposure().await;
 
         // Calculate potential new exposure
-        // Note: Price * Size returns Decimal, so we wrap in Price for arithmetic
         let potential_new_exposure = if let Some(price) = order.price {
-            let order_value = Price::new(price * order.size);
+            let order_value = Price::new(price.notional(order.size).value());
             match order.side {
                 OrderSide::Buy => current_exposure + order_value,
                 OrderSide::Sell => current_exposure - order_value,
@@ -515,6 +1069,17 @@ impl RiskRule for TotalExposureRule {
 
         None
     }
+
+    async fn max_order_size(&self, order: &NewOrder, risk_engine: &RiskEngine) -> Option<Size> {
+        let price = order.price.filter(|p| p.value() > Decimal::ZERO)?;
+        let current_exposure = risk_engine.get_total_exposure().await;
+        let room = remaining_capacity(
+            current_exposure.value(),
+            self.max_exposure.value(),
+            order.side,
+        );
+        room.checked_div(price.value())
+    }
 }
 
 /// Open orders count limit rule
@@ -625,6 +1190,56 @@ impl RiskRule for BalanceRule {
     }
 }
 
+/// Margin level rule
+/// Below the minimum margin level, only orders that pay down a loan are allowed:
+/// buys of a borrowed base asset and sells into a borrowed quote asset.
+pub struct MarginLevelRule {
+    /// Minimum margin level (total assets / total liabilities)
+    min_margin_level: Decimal,
+}
+
+impl MarginLevelRule {
+    /// Create a new margin level rule
+    pub fn new(min_margin_level: Decimal) -> Self {
+        Self { min_margin_level }
+    }
+}
+
+#[async_trait::async_trait]
+impl RiskRule for MarginLevelRule {
+    async fn check_order(
+        &self,
+        order: &NewOrder,
+        risk_engine: &RiskEngine,
+    ) -> Option<RiskViolation> {
+        let symbol = order.symbol.as_str();
+        let account = risk_engine.get_margin_account(symbol).await?;
+        let margin_level = account.margin_level?;
+        if margin_level >= self.min_margin_level {
+            return None;
+        }
+
+        let reduces_loan = account
+            .liabilities
+            .iter()
+            .any(|liability| match order.side {
+                OrderSide::Buy => symbol.starts_with(liability.asset.as_str()),
+                OrderSide::Sell => symbol.ends_with(liability.asset.as_str()),
+            });
+        if reduces_loan {
+            return None;
+        }
+
+        Some(RiskViolation::new(
+            "MarginLevel".to_string(),
+            format!(
+                "Margin level {} for {} is below minimum {}",
+                margin_level, symbol, self.min_margin_level
+            ),
+        ))
+    }
+}
+
 /// Maximum drawdown rule
 /// Limits the maximum drawdown from peak equity
 pub struct MaxDrawdownRule {
@@ -763,6 +1378,135 @@ impl RiskRule for ConcentrationLimitRule {
     }
 }
 
+/// A group of correlated symbols sharing one exposure limit
+#[derive(Debug, Clone)]
+pub struct ExposureCluster {
+    /// Cluster name, such as "btc-beta"
+    pub name: String,
+    /// Beta of each member against the cluster's driver
+    pub betas: HashMap<String, Decimal>,
+    /// Maximum net beta-adjusted notional of the cluster
+    pub max_exposure: Notional,
+}
+
+impl ExposureCluster {
+    /// Create an empty cluster
+    pub fn new(name: impl Into<String>, max_exposure: Notional) -> Self {
+        Self {
+            name: name.into(),
+            betas: HashMap::new(),
+            max_exposure,
+        }
+    }
+
+    /// Add a member symbol with its beta (builder pattern)
+    pub fn with_symbol(mut self, symbol: &str, beta: Decimal) -> Self {
+        self.betas.insert(symbol.to_string(), beta);
+        self
+    }
+}
+
+/// Correlated exposure limit rule
+/// Limits the net beta-adjusted notional of each configured cluster of symbols across
+/// all positions in the risk engine, so that positions in assets moving together (e.g.,
+/// BTC and high-beta alts) cannot add up past one limit. Positions are valued at the
+/// reference price, falling back to their average price. Orders reducing a cluster's
+/// exposure are always allowed.
+pub struct CorrelatedExposureRule {
+    clusters: Vec<ExposureCluster>,
+}
+
+impl Default for CorrelatedExposureRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CorrelatedExposureRule {
+    /// Create a rule with no clusters
+    pub fn new() -> Self {
+        Self {
+            clusters: Vec::new(),
+        }
+    }
+
+    /// Add a cluster of correlated symbols
+    pub fn add_cluster(&mut self, cluster: ExposureCluster) {
+        self.clusters.push(cluster);
+    }
+
+    /// Get the signed, beta-adjusted notional of a cluster's positions
+    pub async fn cluster_exposure(
+        &self,
+        cluster: &ExposureCluster,
+        risk_engine: &RiskEngine,
+    ) -> Decimal {
+        let mut exposure = Decimal::ZERO;
+        for (symbol, beta) in &cluster.betas {
+            let Some(position) = risk_engine.get_position(symbol).await else {
+                continue;
+            };
+            let price = risk_engine
+                .get_reference_price(symbol)
+                .await
+                .or(position.average_price);
+            if let Some(price) = price {
+                exposure += position.size.value() * price.value() * beta;
+            }
+        }
+        exposure
+    }
+}
+
+#[async_trait::async_trait]
+impl RiskRule for CorrelatedExposureRule {
+    async fn check_order(
+        &self,
+        order: &NewOrder,
+        risk_engine: &RiskEngine,
+    ) -> Option<RiskViolation> {
+        let symbol = order.symbol.as_str();
+        let mut price = None;
+        for cluster in &self.clusters {
+            let Some(beta) = cluster.betas.get(symbol) else {
+                continue;
+            };
+            if price.is_none() {
+                price = match order.price {
+                    Some(price) => Some(price),
+                    None => risk_engine.get_reference_price(symbol).await,
+                };
+            }
+            // Can't value a market order without a reference price
+            let price = price?;
+
+            let signed_size = match order.side {
+                OrderSide::Buy => order.size.value(),
+                OrderSide::Sell => -order.size.value(),
+            };
+            let current = self.cluster_exposure(cluster, risk_engine).await;
+            let new_exposure = current + signed_size * price.value() * beta;
+            if new_exposure.abs() > cluster.max_exposure.value()
+                && new_exposure.abs() > current.abs()
+            {
+                return Some(RiskViolation::new(
+                    "CorrelatedExposureLimit".to_string(),
+                    format!(
+                        "Order for {} would take cluster {} exposure to {}: current={}, max={}",
+                        order.symbol,
+                        cluster.name,
+                        new_exposure.round_dp(2),
+                        current.round_dp(2),
+                        cluster.max_exposure
+                    ),
+                ));
+            }
+        }
+
+        None
+    }
+}
+
 /// Minimum balance rule
 /// Ensures minimum balance is maintained for an asset
 pub struct MinimumBalanceRule {
@@ -906,41 +1650,777 @@ impl RiskRule for RateOfChangeLimitRule {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::Symbol;
-    use crate::TimeInForce;
-    use std::str::FromStr;
+/// Liquidation cascade state of a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CascadeState {
+    /// Liquidations at normal levels
+    Normal,
+    /// Liquidations picking up - market makers should widen spreads
+    Elevated,
+    /// Liquidation cascade in progress - trading should pause
+    Cascade,
+}
 
-    #[tokio::test]
-    async fn test_risk_engine_creation() {
-        let risk_engine = RiskEngine::new();
+/// Liquidation cascade rule
+/// Rejects orders on symbols with a liquidation cascade in progress and exposes a
+/// spread multiplier so market making strategies can widen quotes while liquidations rise
+#[derive(Clone)]
+pub struct LiquidationCascadeRule {
+    /// Liquidation trackers by symbol (std lock so synchronous strategies can read it)
+    trackers: Arc<std::sync::RwLock<HashMap<String, LiquidationTracker>>>,
+    /// Time window in milliseconds
+    window_ms: u64,
+    /// Liquidated notional within the window that marks elevated activity
+    elevated_notional: Notional,
+    /// Liquidated notional within the window that marks a cascade
+    cascade_notional: Notional,
+    /// Spread multiplier applied while liquidations are elevated
+    elevated_spread_multiplier: rust_decimal::Decimal,
+}
 
-        // Verify initial state
-        assert_eq!(risk_engine.get_open_orders_count().await, 0);
-        assert_eq!(risk_engine.get_max_open_orders().await, 100);
-        assert_eq!(
-            risk_engine.get_max_total_exposure().await,
-            Price::new(rust_decimal::Decimal::MAX)
-        );
+impl LiquidationCascadeRule {
+    /// Create a new liquidation cascade rule
+    pub fn new(window_ms: u64, elevated_notional: Notional, cascade_notional: Notional) -> Self {
+        Self {
+            trackers: Arc::new(std::sync::RwLock::new(HashMap::new())),
+            window_ms,
+            elevated_notional,
+            cascade_notional,
+            elevated_spread_multiplier: rust_decimal::Decimal::new(2, 0),
+        }
     }
 
-    #[tokio::test]
-    async fn test_position_size_rule() {
-        let risk_engine = RiskEngine::new();
-        let mut rule = PositionSizeRule::new();
-
-        // Set maximum position size
-        risk_engine
-            .set_max_position_size("BTCUSDT", Size::from_str("10.0").unwrap())
-            .await;
-        rule.set_max_position("BTCUSDT", Size::from_str("10.0").unwrap());
+    /// Set the spread multiplier applied while liquidations are elevated (builder pattern)
+    pub fn with_spread_multiplier(mut self, multiplier: rust_decimal::Decimal) -> Self {
+        self.elevated_spread_multiplier = multiplier;
+        self
+    }
 
-        // Add rule to engine
-        risk_engine.add_rule(Box::new(rule)).await;
+    /// Record a liquidation from the market data stream
+    pub fn record(&self, liquidation: &Liquidation) {
+        let mut trackers = self.trackers.write().unwrap();
+        trackers
+            .entry(liquidation.symbol.as_str().to_string())
+            .or_insert_with(|| LiquidationTracker::new(self.window_ms))
+            .add_liquidation(liquidation.clone());
+    }
 
-        // Create order that would exceed position limit
+    /// Liquidated notional for a symbol within the window ending at `now`
+    pub fn liquidation_notional_at(&self, symbol: &str, now: u64) -> Notional {
+        let mut trackers = self.trackers.write().unwrap();
+        match trackers.get_mut(symbol) {
+            Some(tracker) => {
+                tracker.remove_old_liquidations(now);
+                tracker.liquidation_notional()
+            }
+            None => Notional::zero(),
+        }
+    }
+
+    /// Get the cascade state of a symbol at the given time in milliseconds
+    pub fn state_at(&self, symbol: &str, now: u64) -> CascadeState {
+        let notional = self.liquidation_notional_at(symbol, now);
+        if notional >= self.cascade_notional {
+            CascadeState::Cascade
+        } else if notional >= self.elevated_notional {
+            CascadeState::Elevated
+        } else {
+            CascadeState::Normal
+        }
+    }
+
+    /// Get the current cascade state of a symbol
+    pub fn state(&self, symbol: &str) -> CascadeState {
+        self.state_at(symbol, chrono::Utc::now().timestamp_millis() as u64)
+    }
+
+    /// Spread multiplier for a symbol at the given time, None if quoting should pause
+    pub fn spread_multiplier_at(&self, symbol: &str, now: u64) -> Option<rust_decimal::Decimal> {
+        match self.state_at(symbol, now) {
+            CascadeState::Normal => Some(rust_decimal::Decimal::ONE),
+            CascadeState::Elevated => Some(self.elevated_spread_multiplier),
+            CascadeState::Cascade => None,
+        }
+    }
+
+    /// Current spread multiplier for a symbol, None if quoting should pause
+    pub fn spread_multiplier(&self, symbol: &str) -> Option<rust_decimal::Decimal> {
+        self.spread_multiplier_at(symbol, chrono::Utc::now().timestamp_millis() as u64)
+    }
+}
+
+#[async_trait::async_trait]
+impl RiskRule for LiquidationCascadeRule {
+    async fn check_order(
+        &self,
+        order: &NewOrder,
+        risk_engine: &RiskEngine,
+    ) -> Option<RiskViolation> {
+        let now = risk_engine.clock.now_millis();
+        if self.state_at(order.symbol.as_str(), now) == CascadeState::Cascade {
+            return Some(RiskViolation::new(
+                "LiquidationCascade".to_string(),
+                format!(
+                    "Liquidation cascade in progress for {}: notional={} within {}ms",
+                    order.symbol,
+                    self.liquidation_notional_at(order.symbol.as_str(), now),
+                    self.window_ms
+                ),
+            ));
+        }
+
+        None
+    }
+}
+
+/// Fat-finger price band rule
+/// Rejects limit orders priced further from the symbol's reference price than a maximum
+/// fraction (e.g., 0.05 for 5%). Reference prices come from order book mids or a feed
+/// pushed into the risk engine; orders on symbols without one are not checked.
+/// With an order book, the aggressive side of the band starts at the last level the
+/// order's size would reach, so large orders may cross as deep as the book goes.
+pub struct PriceBandRule {
+    /// Default maximum deviation from the reference price
+    max_deviation: Decimal,
+    /// Maximum deviation by symbol, overriding the default
+    symbol_deviations: HashMap<String, Decimal>,
+}
+
+impl PriceBandRule {
+    /// Create a new price band rule
+    pub fn new(max_deviation: Decimal) -> Self {
+        Self {
+            max_deviation,
+            symbol_deviations: HashMap::new(),
+        }
+    }
+
+    /// Set the maximum deviation for a symbol
+    pub fn set_max_deviation(&mut self, symbol: &str, max_deviation: Decimal) {
+        self.symbol_deviations
+            .insert(symbol.to_string(), max_deviation);
+    }
+
+    /// Get the maximum deviation for a symbol
+    pub fn max_deviation(&self, symbol: &str) -> Decimal {
+        self.symbol_deviations
+            .get(symbol)
+            .copied()
+            .unwrap_or(self.max_deviation)
+    }
+}
+
+#[async_trait::async_trait]
+impl RiskRule for PriceBandRule {
+    async fn check_order(
+        &self,
+        order: &NewOrder,
+        risk_engine: &RiskEngine,
+    ) -> Option<RiskViolation> {
+        let price = order.price?;
+        let reference = risk_engine
+            .get_reference_price(order.symbol.as_str())
+            .await
+            .filter(|reference| reference.is_positive())?;
+
+        // Prices between the mid and the depth the order needs are not deviations
+        let (mut low, mut high) = (reference, reference);
+        let sweep = risk_engine
+            .execution_ladder(order.symbol.as_str(), order.side, order.size)
+            .await
+            .and_then(|ladder| ladder.worst_price());
+        if let Some(sweep) = sweep {
+            match order.side {
+                OrderSide::Buy => high = high.max(sweep),
+                OrderSide::Sell => low = low.min(sweep),
+            }
+        }
+
+        let deviation = if price > high {
+            (price.value() - high.value()) / high.value()
+        } else if price < low && low.is_positive() {
+            (low.value() - price.value()) / low.value()
+        } else {
+            Decimal::ZERO
+        };
+        let max_deviation = self.max_deviation(order.symbol.as_str());
+        if deviation > max_deviation {
+            return Some(RiskViolation::new(
+                "PriceBand".to_string(),
+                format!(
+                    "Price {} for {} is {}% outside {}-{} for size {}: max={}%",
+                    price,
+                    order.symbol,
+                    (deviation * Decimal::ONE_HUNDRED).round_dp(2),
+                    low,
+                    high,
+                    order.size,
+                    max_deviation * Decimal::ONE_HUNDRED
+                ),
+            ));
+        }
+
+        None
+    }
+}
+
+/// How the VaR rule estimates portfolio value at risk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VaRMethod {
+    /// Normal approximation from the covariance of symbol returns
+    #[default]
+    Parametric,
+    /// Loss quantile of the current portfolio replayed over past returns
+    Historical,
+}
+
+/// Sampled returns of one symbol
+#[derive(Debug, Clone, Default)]
+struct ReturnHistory {
+    /// Latest price, used to value positions
+    last_price: f64,
+    /// Price and time of the last sample
+    sample_price: f64,
+    sample_time: u64,
+    returns: VecDeque<f64>,
+}
+
+/// Minimum returns a symbol needs before it counts towards VaR
+const MIN_VAR_RETURNS: usize = 2;
+
+/// Value-at-Risk limit rule
+/// Samples returns per symbol from market data prices and blocks orders that would take
+/// the one-period portfolio VaR at the configured confidence above a limit. Orders that
+/// reduce VaR are always allowed. Symbols with too little history are left out.
+#[derive(Clone)]
+pub struct VaRRule {
+    /// Return history by symbol (std lock so market data handlers can record synchronously)
+    history: Arc<std::sync::RwLock<HashMap<String, ReturnHistory>>>,
+    /// Maximum portfolio VaR in the quote asset
+    max_var: Notional,
+    /// Confidence level (e.g., 0.99)
+    confidence: f64,
+    /// Number of returns kept per symbol
+    window: usize,
+    /// Time between return samples in milliseconds
+    sample_interval_ms: u64,
+    method: VaRMethod,
+}
+
+impl VaRRule {
+    /// Create a parametric VaR rule over 250 one-minute returns
+    pub fn new(max_var: Notional, confidence: f64) -> Self {
+        Self {
+            history: Arc::new(std::sync::RwLock::new(HashMap::new())),
+            max_var,
+            confidence,
+            window: 250,
+            sample_interval_ms: 60_000,
+            method: VaRMethod::Parametric,
+        }
+    }
+
+    /// Set the VaR method (builder pattern)
+    pub fn with_method(mut self, method: VaRMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set the number of returns kept per symbol (builder pattern)
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(MIN_VAR_RETURNS);
+        self
+    }
+
+    /// Set the time between return samples in milliseconds (builder pattern)
+    pub fn with_sample_interval_ms(mut self, sample_interval_ms: u64) -> Self {
+        self.sample_interval_ms = sample_interval_ms;
+        self
+    }
+
+    /// Record a market price for a symbol at a time in milliseconds
+    /// A return is sampled once at least the sample interval has passed since the last one.
+    pub fn record_price(&self, symbol: &str, price: Price, timestamp: u64) {
+        let Some(price) = price.value().to_f64().filter(|p| *p > 0.0) else {
+            return;
+        };
+        let mut history = self.history.write().unwrap();
+        let entry = history.entry(symbol.to_string()).or_default();
+        entry.last_price = price;
+        if entry.sample_price <= 0.0 {
+            entry.sample_price = price;
+            entry.sample_time = timestamp;
+            return;
+        }
+        if timestamp < entry.sample_time.saturating_add(self.sample_interval_ms) {
+            return;
+        }
+        entry.returns.push_back(price / entry.sample_price - 1.0);
+        while entry.returns.len() > self.window {
+            entry.returns.pop_front();
+        }
+        entry.sample_price = price;
+        entry.sample_time = timestamp;
+    }
+
+    /// Get the number of returns sampled for a symbol
+    pub fn return_count(&self, symbol: &str) -> usize {
+        let history = self.history.read().unwrap();
+        history.get(symbol).map_or(0, |h| h.returns.len())
+    }
+
+    /// Compute the VaR of positions given as signed quote-asset exposure by symbol
+    pub fn var_of(&self, exposures: &HashMap<String, f64>) -> f64 {
+        let history = self.history.read().unwrap();
+        let held: Vec<(f64, &VecDeque<f64>)> = exposures
+            .iter()
+            .filter(|(_, exposure)| **exposure != 0.0)
+            .filter_map(|(symbol, exposure)| {
+                let returns = &history.get(symbol)?.returns;
+                (returns.len() >= MIN_VAR_RETURNS).then_some((*exposure, returns))
+            })
+            .collect();
+        let Some(n) = held.iter().map(|(_, returns)| returns.len()).min() else {
+            return 0.0;
+        };
+        // Align on the most recent n samples of every symbol
+        let series: Vec<(f64, Vec<f64>)> = held
+            .iter()
+            .map(|(exposure, returns)| {
+                (
+                    *exposure,
+                    returns.iter().skip(returns.len() - n).copied().collect(),
+                )
+            })
+            .collect();
+
+        match self.method {
+            VaRMethod::Historical => {
+                let mut pnl: Vec<f64> = (0..n)
+                    .map(|t| series.iter().map(|(e, r)| e * r[t]).sum())
+                    .collect();
+                pnl.sort_by(|a, b| a.total_cmp(b));
+                let index = (((1.0 - self.confidence) * n as f64).floor() as usize).min(n - 1);
+                (-pnl[index]).max(0.0)
+            }
+            VaRMethod::Parametric => {
+                let means: Vec<f64> = series
+                    .iter()
+                    .map(|(_, r)| r.iter().sum::<f64>() / n as f64)
+                    .collect();
+                let mut variance = 0.0;
+                for (i, (ei, ri)) in series.iter().enumerate() {
+                    for (j, (ej, rj)) in series.iter().enumerate() {
+                        let covariance = (0..n)
+                            .map(|t| (ri[t] - means[i]) * (rj[t] - means[j]))
+                            .sum::<f64>()
+                            / (n - 1) as f64;
+                        variance += ei * ej * covariance;
+                    }
+                }
+                normal_quantile(self.confidence) * variance.max(0.0).sqrt()
+            }
+        }
+    }
+
+    /// Signed quote-asset exposure by symbol of the risk engine's positions
+    async fn exposures(&self, risk_engine: &RiskEngine) -> HashMap<String, f64> {
+        let positions = risk_engine.get_all_positions().await;
+        let history = self.history.read().unwrap();
+        positions
+            .iter()
+            .filter_map(|position| {
+                let symbol = position.symbol.as_str();
+                let price = history
+                    .get(symbol)
+                    .map(|h| h.last_price)
+                    .or_else(|| position.average_price?.value().to_f64())?;
+                Some((symbol.to_string(), position.size.value().to_f64()? * price))
+            })
+            .collect()
+    }
+
+    /// Compute the VaR of the risk engine's current positions
+    pub async fn current_var(&self, risk_engine: &RiskEngine) -> f64 {
+        self.var_of(&self.exposures(risk_engine).await)
+    }
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation)
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    let p = p.clamp(1e-12, 1.0 - 1e-12);
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < 0.02425 {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - 0.02425 {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+#[async_trait::async_trait]
+impl RiskRule for VaRRule {
+    async fn check_order(
+        &self,
+        order: &NewOrder,
+        risk_engine: &RiskEngine,
+    ) -> Option<RiskViolation> {
+        let max_var = self.max_var.value().to_f64()?;
+        let mut exposures = self.exposures(risk_engine).await;
+        let current_var = self.var_of(&exposures);
+
+        let symbol = order.symbol.as_str();
+        let price = match order.price {
+            Some(price) => price.value().to_f64()?,
+            None => self.history.read().unwrap().get(symbol)?.last_price,
+        };
+        let size = order.size.value().to_f64()?;
+        let signed = match order.side {
+            OrderSide::Buy => size,
+            OrderSide::Sell => -size,
+        };
+        *exposures.entry(symbol.to_string()).or_insert(0.0) += signed * price;
+        let new_var = self.var_of(&exposures);
+
+        if new_var > max_var && new_var > current_var {
+            return Some(RiskViolation::new(
+                "VaRLimit".to_string(),
+                format!(
+                    "Order for {} would take {:.0}% VaR to {:.2}: current={:.2}, max={}",
+                    order.symbol,
+                    self.confidence * 100.0,
+                    new_var,
+                    current_var,
+                    self.max_var
+                ),
+            ));
+        }
+
+        None
+    }
+}
+
+/// Order rate limits over one second and one minute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderRateLimit {
+    pub per_second: usize,
+    pub per_minute: usize,
+}
+
+impl OrderRateLimit {
+    /// Create an order rate limit
+    pub fn new(per_second: usize, per_minute: usize) -> Self {
+        Self {
+            per_second,
+            per_minute,
+        }
+    }
+}
+
+/// Order message rate rule
+/// Rejects orders once the submissions recorded by the risk engine for the order's
+/// symbol or exchange reach a per-second or per-minute limit, and emits a warning alert.
+/// This guards against runaway strategies; venue request weights are the REST
+/// `RateLimiter`'s job.
+pub struct MessageRateRule {
+    /// Default limit for every symbol
+    symbol_limit: Option<OrderRateLimit>,
+    /// Limits by symbol, overriding the default
+    symbol_limits: HashMap<String, OrderRateLimit>,
+    /// Default limit for every exchange
+    exchange_limit: Option<OrderRateLimit>,
+    /// Limits by exchange, overriding the default
+    exchange_limits: HashMap<String, OrderRateLimit>,
+    alerts: Option<Arc<AlertManager>>,
+}
+
+impl Default for MessageRateRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageRateRule {
+    /// Create a rule with no limits
+    pub fn new() -> Self {
+        Self {
+            symbol_limit: None,
+            symbol_limits: HashMap::new(),
+            exchange_limit: None,
+            exchange_limits: HashMap::new(),
+            alerts: None,
+        }
+    }
+
+    /// Limit every symbol (builder pattern)
+    pub fn with_symbol_limit(mut self, limit: OrderRateLimit) -> Self {
+        self.symbol_limit = Some(limit);
+        self
+    }
+
+    /// Limit every exchange (builder pattern)
+    pub fn with_exchange_limit(mut self, limit: OrderRateLimit) -> Self {
+        self.exchange_limit = Some(limit);
+        self
+    }
+
+    /// Emit a warning alert for every rejected order (builder pattern)
+    pub fn with_alerts(mut self, alerts: Arc<AlertManager>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Set the limit for a symbol
+    pub fn set_symbol_limit(&mut self, symbol: &str, limit: OrderRateLimit) {
+        self.symbol_limits.insert(symbol.to_string(), limit);
+    }
+
+    /// Set the limit for an exchange
+    pub fn set_exchange_limit(&mut self, exchange_id: &str, limit: OrderRateLimit) {
+        self.exchange_limits.insert(exchange_id.to_string(), limit);
+    }
+
+    fn limit(&self, scope: OrderRateScope, key: &str) -> Option<OrderRateLimit> {
+        let (limits, default) = match scope {
+            OrderRateScope::Symbol => (&self.symbol_limits, self.symbol_limit),
+            OrderRateScope::Exchange => (&self.exchange_limits, self.exchange_limit),
+        };
+        limits.get(key).copied().or(default)
+    }
+
+    /// Check an order against the limits at a time in milliseconds
+    pub fn check_order_at(
+        &self,
+        order: &NewOrder,
+        risk_engine: &RiskEngine,
+        now: u64,
+    ) -> Option<RiskViolation> {
+        for (scope, key) in [
+            (OrderRateScope::Symbol, order.symbol.value()),
+            (OrderRateScope::Exchange, order.exchange_id.as_str()),
+        ] {
+            let Some(limit) = self.limit(scope, key) else {
+                continue;
+            };
+            for (window, window_ms, max) in [
+                ("second", 1_000, limit.per_second),
+                ("minute", 60_000, limit.per_minute),
+            ] {
+                let count =
+                    risk_engine.get_order_count_since(scope, key, now.saturating_sub(window_ms));
+                if count >= max {
+                    return Some(RiskViolation::new(
+                        "MessageRateLimit".to_string(),
+                        format!(
+                            "{:?} {} sent {} orders in the last {}: max={}",
+                            scope, key, count, window, max
+                        ),
+                    ));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[async_trait::async_trait]
+impl RiskRule for MessageRateRule {
+    async fn check_order(
+        &self,
+        order: &NewOrder,
+        risk_engine: &RiskEngine,
+    ) -> Option<RiskViolation> {
+        let violation = self.check_order_at(order, risk_engine, risk_engine.clock.now_millis())?;
+        if let Some(alerts) = &self.alerts {
+            alerts
+                .emit(AlertLevel::Warning, "risk", violation.details.clone())
+                .await;
+        }
+        Some(violation)
+    }
+}
+
+/// What the self-match prevention rule does with an order that would cross our quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelfMatchAction {
+    /// Reject the new order and leave the resting orders alone
+    #[default]
+    Reject,
+    /// Cancel the resting orders it would cross and let the new order replace them
+    CancelResting,
+}
+
+/// Self-match prevention rule
+/// Checks new orders against our resting orders on the same symbol and venue, taken from
+/// the order manager injected with `RiskEngine::with_order_manager`, so we never trade
+/// with ourselves. A buy crosses resting sells at or below its price, a sell crosses
+/// resting buys at or above it, and market orders cross every opposite resting order.
+pub struct SelfMatchPreventionRule {
+    action: SelfMatchAction,
+}
+
+impl Default for SelfMatchPreventionRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfMatchPreventionRule {
+    /// Create a rule that rejects crossing orders
+    pub fn new() -> Self {
+        Self {
+            action: SelfMatchAction::Reject,
+        }
+    }
+
+    /// Set what happens to crossing orders (builder pattern)
+    pub fn with_action(mut self, action: SelfMatchAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Check if an order would trade against a resting order
+    pub fn crosses(order: &NewOrder, resting: &OrderInfo) -> bool {
+        if resting.side == order.side {
+            return false;
+        }
+        let Some(resting_price) = resting.price else {
+            return false;
+        };
+        match (order.price, order.side) {
+            (None, _) => true,
+            (Some(price), OrderSide::Buy) => price >= resting_price,
+            (Some(price), OrderSide::Sell) => price <= resting_price,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RiskRule for SelfMatchPreventionRule {
+    async fn check_order(
+        &self,
+        order: &NewOrder,
+        risk_engine: &RiskEngine,
+    ) -> Option<RiskViolation> {
+        let order_manager = risk_engine.order_manager()?;
+        // Orders not routed to a specific venue are checked against every resting order
+        if order.exchange_id != "default" && order.exchange_id != order_manager.exchange_id() {
+            return None;
+        }
+
+        let crossed: Vec<OrderInfo> = order_manager
+            .get_active_orders_by_symbol(order.symbol.value())
+            .await
+            .into_iter()
+            .filter(|resting| Self::crosses(order, resting))
+            .collect();
+        if crossed.is_empty() {
+            return None;
+        }
+
+        match self.action {
+            SelfMatchAction::Reject => {
+                let order_ids: Vec<&str> = crossed.iter().map(|o| o.order_id.as_str()).collect();
+                Some(RiskViolation::new(
+                    "SelfMatchPrevention".to_string(),
+                    format!(
+                        "{:?} order for {} at {} would cross our resting orders: {}",
+                        order.side,
+                        order.symbol,
+                        order
+                            .price
+                            .map_or_else(|| "market".to_string(), |p| p.to_string()),
+                        order_ids.join(", ")
+                    ),
+                ))
+            }
+            SelfMatchAction::CancelResting => {
+                for resting in &crossed {
+                    if order_manager.cancel_order(&resting.order_id).await {
+                        warn!(
+                            "Cancelled resting order {} crossed by new {:?} order for {}",
+                            resting.order_id, order.side, order.symbol
+                        );
+                    }
+                }
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::SimulatedClock;
+    use crate::types::Symbol;
+    use crate::TimeInForce;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_risk_engine_creation() {
+        let risk_engine = RiskEngine::new();
+
+        // Verify initial state
+        assert_eq!(risk_engine.get_open_orders_count().await, 0);
+        assert_eq!(risk_engine.get_max_open_orders().await, 100);
+        assert_eq!(
+            risk_engine.get_max_total_exposure().await,
+            Price::new(rust_decimal::Decimal::MAX)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_position_size_rule() {
+        let risk_engine = RiskEngine::new();
+        let mut rule = PositionSizeRule::new();
+
+        // Set maximum position size
+        risk_engine
+            .set_max_position_size("BTCUSDT", Size::from_str("10.0").unwrap())
+            .await;
+        rule.set_max_position("BTCUSDT", Size::from_str("10.0").unwrap());
+
+        // Add rule to engine
+        risk_engine.add_rule(Box::new(rule)).await;
+
+        // Create order that would exceed position limit
         let order = NewOrder::new_limit_buy(
             "BTCUSDT".to_string(),
             Size::from_str("15.0").unwrap(), // Exceeds max position
@@ -1162,4 +2642,604 @@ mod tests {
         let result = risk_engine.check_order(&order).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_liquidation_cascade_rule() {
+        let rule = LiquidationCascadeRule::new(
+            60_000,
+            Notional::new(rust_decimal::Decimal::new(100_000, 0)),
+            Notional::new(rust_decimal::Decimal::new(1_000_000, 0)),
+        )
+        .with_spread_multiplier(rust_decimal::Decimal::new(3, 0));
+
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let liquidation = |size: &str| Liquidation {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance_futures".to_string(),
+            side: OrderSide::Sell,
+            price: Price::from_str("50000.0").unwrap(),
+            size: Size::from_str(size).unwrap(),
+            timestamp: now,
+        };
+
+        assert_eq!(rule.state_at("BTCUSDT", now), CascadeState::Normal);
+
+        // $150k liquidated: widen spreads
+        rule.record(&liquidation("3.0"));
+        assert_eq!(rule.state_at("BTCUSDT", now), CascadeState::Elevated);
+        assert_eq!(
+            rule.spread_multiplier_at("BTCUSDT", now),
+            Some(rust_decimal::Decimal::new(3, 0))
+        );
+
+        // $1.15M liquidated: pause trading
+        rule.record(&liquidation("20.0"));
+        assert_eq!(rule.spread_multiplier_at("BTCUSDT", now), None);
+
+        let risk_engine = RiskEngine::new();
+        risk_engine.add_rule(Box::new(rule.clone())).await;
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT".to_string(),
+            Size::from_str("1.0").unwrap(),
+            Price::from_str("49000.0").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let violation = risk_engine.check_order(&order).await.unwrap_err();
+        assert_eq!(violation.rule, "LiquidationCascade");
+
+        // Cascade ends once liquidations leave the window
+        assert_eq!(
+            rule.state_at("BTCUSDT", now + 120_000),
+            CascadeState::Normal
+        );
+        assert_eq!(rule.state_at("ETHUSDT", now), CascadeState::Normal);
+    }
+
+    #[tokio::test]
+    async fn test_margin_level_rule() {
+        use crate::core::events::{MarginLiability, MarginMode};
+
+        let risk_engine = RiskEngine::new();
+        risk_engine
+            .add_rule(Box::new(MarginLevelRule::new(
+                rust_decimal::Decimal::from_str("1.5").unwrap(),
+            )))
+            .await;
+        let buy = NewOrder::new_limit_buy(
+            "BTCUSDT".to_string(),
+            Size::from_str("1.0").unwrap(),
+            Price::from_str("50000.0").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let sell = NewOrder::new_limit_sell(
+            "BTCUSDT".to_string(),
+            Size::from_str("1.0").unwrap(),
+            Price::from_str("50000.0").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+
+        // No margin account: nothing to check
+        assert!(risk_engine.check_order(&buy).await.is_ok());
+
+        let account = |level: &str| MarginAccount {
+            exchange_id: "binance".to_string(),
+            mode: MarginMode::Cross,
+            symbol: None,
+            balances: Vec::new(),
+            liabilities: vec![MarginLiability {
+                asset: "USDT".to_string(),
+                borrowed: rust_decimal::Decimal::from(20_000),
+                interest: rust_decimal::Decimal::from(5),
+            }],
+            margin_level: Some(rust_decimal::Decimal::from_str(level).unwrap()),
+        };
+        risk_engine.update_margin_account(account("3.0")).await;
+        assert_eq!(
+            risk_engine.get_borrowed("USDT").await,
+            Size::from_str("20005").unwrap()
+        );
+        assert!(risk_engine.check_order(&buy).await.is_ok());
+
+        // Below the minimum, only selling into the borrowed USDT is allowed
+        risk_engine.update_margin_account(account("1.2")).await;
+        let violation = risk_engine.check_order(&buy).await.unwrap_err();
+        assert_eq!(violation.rule, "MarginLevel");
+        assert!(risk_engine.check_order(&sell).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_self_match_prevention_rule() {
+        use crate::core::events::OrderType;
+
+        let order_manager = Arc::new(OrderManagerImpl::new("binance".to_string()));
+        let resting = |order_id: &str, side: OrderSide, price: &str| {
+            OrderInfo::new(
+                order_id.to_string(),
+                None,
+                Symbol::new("BTCUSDT"),
+                side,
+                OrderType::Limit,
+                TimeInForce::GoodTillCancelled,
+                Size::from_str("1.0").unwrap(),
+                Some(Price::from_str(price).unwrap()),
+                "binance".to_string(),
+            )
+        };
+        order_manager
+            .add_order(resting("bid", OrderSide::Buy, "49990.0"))
+            .await;
+        order_manager
+            .add_order(resting("ask", OrderSide::Sell, "50010.0"))
+            .await;
+
+        let risk_engine = RiskEngine::new().with_order_manager(order_manager.clone());
+        risk_engine
+            .add_rule(Box::new(SelfMatchPreventionRule::new()))
+            .await;
+        let buy = |price: &str| {
+            NewOrder::new_limit_buy(
+                "BTCUSDT".to_string(),
+                Size::from_str("1.0").unwrap(),
+                Price::from_str(price).unwrap(),
+                TimeInForce::GoodTillCancelled,
+            )
+        };
+
+        // Inside our own spread, or on another symbol or venue: no self-match
+        assert!(risk_engine.check_order(&buy("50000.0")).await.is_ok());
+        let mut other_venue = buy("50010.0");
+        other_venue.exchange_id = "okx".to_string();
+        assert!(risk_engine.check_order(&other_venue).await.is_ok());
+
+        let violation = risk_engine.check_order(&buy("50010.0")).await.unwrap_err();
+        assert_eq!(violation.rule, "SelfMatchPrevention");
+        assert!(violation.details.contains("ask"));
+        let market_sell = NewOrder::new_market_sell("BTCUSDT", Size::from_str("1.0").unwrap());
+        assert!(risk_engine.check_order(&market_sell).await.is_err());
+
+        // Cancel-and-replace: the crossed ask goes, the bid stays
+        let risk_engine = RiskEngine::new().with_order_manager(order_manager.clone());
+        risk_engine
+            .add_rule(Box::new(
+                SelfMatchPreventionRule::new().with_action(SelfMatchAction::CancelResting),
+            ))
+            .await;
+        assert!(risk_engine.check_order(&buy("50020.0")).await.is_ok());
+        let active: Vec<String> = order_manager
+            .get_all_active_orders()
+            .await
+            .into_iter()
+            .map(|order| order.order_id)
+            .collect();
+        assert_eq!(active, vec!["bid".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_price_band_rule() {
+        use crate::core::events::OrderBookLevel;
+
+        let risk_engine = RiskEngine::new();
+        let mut rule = PriceBandRule::new(rust_decimal::Decimal::from_str("0.05").unwrap());
+        rule.set_max_deviation("ETHUSDT", rust_decimal::Decimal::from_str("0.01").unwrap());
+        risk_engine.add_rule(Box::new(rule)).await;
+        let buy = |symbol: &str, price: &str| {
+            NewOrder::new_limit_buy(
+                symbol.to_string(),
+                Size::from_str("1.0").unwrap(),
+                Price::from_str(price).unwrap(),
+                TimeInForce::GoodTillCancelled,
+            )
+        };
+
+        // No reference price yet: nothing to compare against
+        assert!(risk_engine
+            .check_order(&buy("BTCUSDT", "5000.0"))
+            .await
+            .is_ok());
+
+        let level = |price: &str| {
+            OrderBookLevel::new(
+                Price::from_str(price).unwrap(),
+                Size::from_str("1.0").unwrap(),
+            )
+        };
+        risk_engine
+            .update_order_book(&OrderBookSnapshot::new(
+                "BTCUSDT",
+                "binance",
+                vec![level("49990.0")],
+                vec![level("50010.0")],
+                0,
+            ))
+            .await;
+        assert_eq!(
+            risk_engine.get_reference_price("BTCUSDT").await,
+            Some(Price::from_str("50000.0").unwrap())
+        );
+        assert!(risk_engine
+            .check_order(&buy("BTCUSDT", "52500.0"))
+            .await
+            .is_ok());
+
+        // A missing digit is caught, market orders are not checked
+        let violation = risk_engine
+            .check_order(&buy("BTCUSDT", "5000.0"))
+            .await
+            .unwrap_err();
+        assert_eq!(violation.rule, "PriceBand");
+        let market = NewOrder::new_market_buy("BTCUSDT", Size::from_str("1.0").unwrap());
+        assert!(risk_engine.check_order(&market).await.is_ok());
+
+        // A size that needs deeper asks may be priced up to the band beyond them
+        risk_engine
+            .update_order_book(&OrderBookSnapshot::new(
+                "BTCUSDT",
+                "binance",
+                vec![level("49990.0")],
+                vec![
+                    level("50010.0"),
+                    OrderBookLevel::new(
+                        Price::from_str("53000.0").unwrap(),
+                        Size::from_str("5.0").unwrap(),
+                    ),
+                ],
+                0,
+            ))
+            .await;
+        let sized_buy = |size: &str| {
+            NewOrder::new_limit_buy(
+                "BTCUSDT".to_string(),
+                Size::from_str(size).unwrap(),
+                Price::from_str("55000.0").unwrap(),
+                TimeInForce::GoodTillCancelled,
+            )
+        };
+        assert!(risk_engine.check_order(&sized_buy("3.0")).await.is_ok());
+        assert!(risk_engine.check_order(&sized_buy("1.0")).await.is_err());
+        let ladder = risk_engine
+            .execution_ladder("BTCUSDT", OrderSide::Buy, Size::from_str("3.0").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            ladder.worst_price(),
+            Some(Price::from_str("53000.0").unwrap())
+        );
+
+        // Per-symbol bands override the default
+        risk_engine
+            .update_reference_price("ETHUSDT", Price::from_str("3000.0").unwrap())
+            .await;
+        assert!(risk_engine
+            .check_order(&buy("ETHUSDT", "3020.0"))
+            .await
+            .is_ok());
+        assert!(risk_engine
+            .check_order(&buy("ETHUSDT", "3060.0"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_var_rule() {
+        let rule = VaRRule::new(Notional::new(rust_decimal::Decimal::from(1_000)), 0.95)
+            .with_window(20)
+            .with_sample_interval_ms(1_000);
+        assert!((normal_quantile(0.95) - 1.6449).abs() < 1e-3);
+
+        // BTC alternates +-1%, samples closer than the interval are skipped
+        let mut price = 50_000.0;
+        for i in 0..=20u64 {
+            rule.record_price(
+                "BTCUSDT",
+                Price::from_str(&price.to_string()).unwrap(),
+                i * 1_000,
+            );
+            rule.record_price("BTCUSDT", Price::from_str("1.0").unwrap(), i * 1_000 + 1);
+            rule.record_price(
+                "BTCUSDT",
+                Price::from_str(&price.to_string()).unwrap(),
+                i * 1_000 + 2,
+            );
+            price *= if i % 2 == 0 { 1.01 } else { 1.0 / 1.01 };
+        }
+        assert_eq!(rule.return_count("BTCUSDT"), 20);
+
+        // $50k of BTC with ~1% moves: parametric 95% VaR is about $820
+        let exposures = HashMap::from([("BTCUSDT".to_string(), 50_000.0)]);
+        let var = rule.var_of(&exposures);
+        assert!(var > 780.0 && var < 860.0, "var={}", var);
+        let historical = rule.clone().with_method(VaRMethod::Historical);
+        let var = historical.var_of(&exposures);
+        assert!(var > 480.0 && var < 520.0, "var={}", var);
+        // No history, no VaR
+        assert_eq!(
+            rule.var_of(&HashMap::from([("ETHUSDT".to_string(), 1e6)])),
+            0.0
+        );
+
+        let risk_engine = RiskEngine::new();
+        risk_engine.add_rule(Box::new(rule.clone())).await;
+        let buy = |size: &str| {
+            NewOrder::new_limit_buy(
+                "BTCUSDT".to_string(),
+                Size::from_str(size).unwrap(),
+                Price::from_str("50000.0").unwrap(),
+                TimeInForce::GoodTillCancelled,
+            )
+        };
+        assert!(risk_engine.check_order(&buy("1.0")).await.is_ok());
+        let violation = risk_engine.check_order(&buy("2.0")).await.unwrap_err();
+        assert_eq!(violation.rule, "VaRLimit");
+
+        // Over the limit already, orders that reduce VaR still pass
+        risk_engine
+            .update_position(
+                "BTCUSDT",
+                Position {
+                    symbol: Symbol::new("BTCUSDT"),
+                    exchange_id: "binance".to_string(),
+                    size: Size::from_str("3.0").unwrap(),
+                    average_price: Some(Price::from_str("50000.0").unwrap()),
+                    unrealized_pnl: None,
+                },
+            )
+            .await;
+        assert!(rule.current_var(&risk_engine).await > 1_000.0);
+        let sell = NewOrder::new_market_sell("BTCUSDT", Size::from_str("1.0").unwrap());
+        assert!(risk_engine.check_order(&sell).await.is_ok());
+        assert!(risk_engine.check_order(&buy("0.1")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_message_rate_rule() {
+        let alerts = Arc::new(AlertManager::new(10));
+        let mut rule = MessageRateRule::new()
+            .with_symbol_limit(OrderRateLimit::new(3, 100))
+            .with_exchange_limit(OrderRateLimit::new(100, 5))
+            .with_alerts(alerts.clone());
+        rule.set_symbol_limit("ETHUSDT", OrderRateLimit::new(1, 100));
+
+        let now = 1_700_000_000_000;
+        let clock = SimulatedClock::at_millis(now);
+        let risk_engine = RiskEngine::new().with_clock(clock.shared());
+        risk_engine.add_rule(Box::new(rule)).await;
+        let order = |symbol: &str| {
+            let mut order = NewOrder::new_market_buy(symbol, Size::from_str("1.0").unwrap());
+            order.exchange_id = "binance".to_string();
+            order
+        };
+
+        // Checking an order does not count it; sending it does
+        for _ in 0..5 {
+            assert!(risk_engine.check_order(&order("BTCUSDT")).await.is_ok());
+        }
+        assert_eq!(
+            risk_engine.get_order_count_since(OrderRateScope::Symbol, "BTCUSDT", 0),
+            0
+        );
+
+        // Three BTC orders a second pass, the fourth does not
+        for _ in 0..3 {
+            assert!(risk_engine.check_order(&order("BTCUSDT")).await.is_ok());
+            risk_engine.record_order_submission(&order("BTCUSDT"));
+        }
+        let violation = risk_engine
+            .check_order(&order("BTCUSDT"))
+            .await
+            .unwrap_err();
+        assert_eq!(violation.rule, "MessageRateLimit");
+        assert!(violation.details.contains("second"));
+        let recent = alerts.get_recent_alerts(1).await;
+        assert_eq!(recent[0].level, AlertLevel::Warning);
+
+        // The symbol override is tighter
+        assert_eq!(
+            risk_engine.get_order_count_since(OrderRateScope::Symbol, "BTCUSDT", 0),
+            3
+        );
+        assert!(risk_engine.check_order(&order("ETHUSDT")).await.is_ok());
+        risk_engine.record_order_submission(&order("ETHUSDT"));
+        assert!(risk_engine.check_order(&order("ETHUSDT")).await.is_err());
+
+        // Submissions older than a second still count towards the exchange's minute limit
+        risk_engine.record_order_submission_at(&order("SOLUSDT"), now - 30_000);
+        let violation = risk_engine
+            .check_order(&order("SOLUSDT"))
+            .await
+            .unwrap_err();
+        assert!(violation
+            .details
+            .contains("Exchange binance sent 5 orders in the last minute"));
+        // A minute on the simulated clock, the window is empty again
+        clock.advance(std::time::Duration::from_secs(61));
+        assert!(risk_engine.check_order(&order("SOLUSDT")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_correlated_exposure_rule() {
+        let mut rule = CorrelatedExposureRule::new();
+        rule.add_cluster(
+            ExposureCluster::new(
+                "btc-beta",
+                Notional::new(rust_decimal::Decimal::from(100_000)),
+            )
+            .with_symbol("BTCUSDT", rust_decimal::Decimal::ONE)
+            .with_symbol("SOLUSDT", rust_decimal::Decimal::from_str("1.5").unwrap()),
+        );
+        let risk_engine = RiskEngine::new();
+        risk_engine.add_rule(Box::new(rule)).await;
+
+        let position = |symbol: &str, size: &str, price: &str| Position {
+            symbol: Symbol::new(symbol),
+            exchange_id: "binance".to_string(),
+            size: Size::from_str(size).unwrap(),
+            average_price: Some(Price::from_str(price).unwrap()),
+            unrealized_pnl: None,
+        };
+        let order = |symbol: &str, side: OrderSide, size: &str, price: &str| {
+            let mut order = NewOrder::new_limit_buy(
+                symbol.to_string(),
+                Size::from_str(size).unwrap(),
+                Price::from_str(price).unwrap(),
+                TimeInForce::GoodTillCancelled,
+            );
+            order.side = side;
+            order
+        };
+
+        // $60k of BTC plus $20k of SOL at beta 1.5 is $90k of cluster exposure
+        risk_engine
+            .update_position("BTCUSDT", position("BTCUSDT", "1.2", "50000"))
+            .await;
+        risk_engine
+            .update_position("SOLUSDT", position("SOLUSDT", "200", "100"))
+            .await;
+        // Another $10k of SOL would add $15k, each symbol alone being well within limits
+        let violation = risk_engine
+            .check_order(&order("SOLUSDT", OrderSide::Buy, "100", "100"))
+            .await
+            .unwrap_err();
+        assert_eq!(violation.rule, "CorrelatedExposureLimit");
+        assert!(risk_engine
+            .check_order(&order("SOLUSDT", OrderSide::Buy, "50", "100"))
+            .await
+            .is_ok());
+
+        // Symbols outside the cluster and hedging orders are not limited
+        assert!(risk_engine
+            .check_order(&order("ETHUSDT", OrderSide::Buy, "100", "3000"))
+            .await
+            .is_ok());
+        risk_engine
+            .update_reference_price("BTCUSDT", Price::from_str("80000").unwrap())
+            .await;
+        assert!(risk_engine
+            .check_order(&order("BTCUSDT", OrderSide::Buy, "0.01", "80000"))
+            .await
+            .is_err());
+        assert!(risk_engine
+            .check_order(&order("BTCUSDT", OrderSide::Sell, "0.1", "80000"))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_breach_enforcement() {
+        use crate::connectors::MockExecutionClient;
+        use crate::core::events::{OrderStatus, OrderType};
+
+        let binance = Arc::new(MockExecutionClient::new());
+        let okx = Arc::new(MockExecutionClient::new());
+        let order_manager = Arc::new(OrderManagerImpl::new("binance".to_string()));
+        let risk_engine = RiskEngine::new()
+            .with_order_manager(order_manager.clone())
+            .with_execution_client("binance", binance.clone())
+            .with_execution_client("okx", okx.clone());
+
+        let limit = |symbol: &str, price: &str| {
+            NewOrder::new_limit_buy(
+                symbol.to_string(),
+                Size::from_str("1.0").unwrap(),
+                Price::from_str(price).unwrap(),
+                TimeInForce::GoodTillCancelled,
+            )
+        };
+        let resting = binance
+            .place_order(limit("BTCUSDT", "49000"))
+            .await
+            .unwrap();
+        order_manager
+            .add_order(OrderInfo::new(
+                resting.clone(),
+                None,
+                Symbol::new("BTCUSDT"),
+                OrderSide::Buy,
+                OrderType::Limit,
+                TimeInForce::GoodTillCancelled,
+                Size::from_str("1.0").unwrap(),
+                Some(Price::from_str("49000").unwrap()),
+                "binance".to_string(),
+            ))
+            .await;
+        binance.place_order(limit("ETHUSDT", "2900")).await.unwrap();
+        okx.place_order(limit("BTCUSDT", "49000")).await.unwrap();
+
+        // Cancel-all for a symbol reaches every venue and the order manager
+        assert_eq!(
+            risk_engine
+                .cancel_all_orders_for_symbol("BTCUSDT")
+                .await
+                .len(),
+            2
+        );
+        assert_eq!(
+            order_manager.get_order(&resting).await.unwrap().status,
+            OrderStatus::Cancelled
+        );
+        assert_eq!(binance.get_open_orders(None).await.unwrap().len(), 1);
+
+        // A daily loss breach on a long position cancels everything and flattens it
+        risk_engine
+            .set_max_daily_loss("BTCUSDT", Price::from_str("100").unwrap())
+            .await;
+        risk_engine
+            .set_breach_action("DailyLossLimit", BreachAction::FlattenSymbol)
+            .await;
+        risk_engine.add_rule(Box::new(DailyLossRule::new())).await;
+        risk_engine
+            .update_position(
+                "BTCUSDT",
+                Position {
+                    symbol: Symbol::new("BTCUSDT"),
+                    exchange_id: "binance".to_string(),
+                    size: Size::from_str("2.0").unwrap(),
+                    average_price: Some(Price::from_str("50000").unwrap()),
+                    unrealized_pnl: None,
+                },
+            )
+            .await;
+        risk_engine
+            .update_reference_price("BTCUSDT", Price::from_str("48000").unwrap())
+            .await;
+        binance
+            .place_order(limit("BTCUSDT", "47000"))
+            .await
+            .unwrap();
+        let sell = NewOrder::new_limit_sell(
+            "BTCUSDT".to_string(),
+            Size::from_str("1.0").unwrap(),
+            Price::from_str("48000").unwrap(),
+            TimeInForce::GoodTillCancelled,
+        );
+        let violation = risk_engine.check_order(&sell).await.unwrap_err();
+        assert_eq!(violation.rule, "DailyLossLimit");
+
+        let open = binance.get_open_orders(None).await.unwrap();
+        assert_eq!(open.len(), 2);
+        let flatten = open
+            .iter()
+            .find(|order| order.symbol.value() == "BTCUSDT")
+            .unwrap();
+        assert_eq!(flatten.remaining_size, Size::from_str("2.0").unwrap());
+        assert_eq!(
+            flatten.average_price,
+            Some(Price::from_str("47760").unwrap())
+        );
+
+        // Nothing to flatten without a position, and no price to flatten at without a reference
+        assert_eq!(risk_engine.flatten_position("ETHUSDT").await, Ok(None));
+        risk_engine
+            .update_position(
+                "ETHUSDT",
+                Position {
+                    symbol: Symbol::new("ETHUSDT"),
+                    exchange_id: "binance".to_string(),
+                    size: Size::from_str("-3.0").unwrap(),
+                    average_price: Some(Price::from_str("3000").unwrap()),
+                    unrealized_pnl: None,
+                },
+            )
+            .await;
+        assert!(risk_engine.flatten_position("ETHUSDT").await.is_err());
+    }
 }
@@ -0,0 +1,302 @@
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// How weekend sessions are accounted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeekendPolicy {
+    /// Saturday and Sunday are sessions of their own (24/7 venues)
+    Separate,
+    /// Weekend activity is booked to Friday's session
+    MergeIntoFriday,
+    /// Weekend activity is booked to Monday's session
+    MergeIntoMonday,
+}
+
+/// Trading session calendar
+///
+/// A session starts at `rollover_time` local time in `timezone` and is labelled
+/// with the local date holding most of it: a midnight rollover labels sessions by
+/// their start date, while with the FX-style 17:00 New York rollover the session
+/// running from Monday 17:00 to Tuesday 17:00 is Tuesday's session. Local times
+/// are resolved through the timezone database, so sessions spanning a DST change
+/// are 23 or 25 hours long instead of drifting by an hour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionConfig {
+    /// Local time at which a new session starts
+    pub rollover_time: NaiveTime,
+    /// Timezone the rollover time is expressed in
+    pub timezone: Tz,
+    /// Weekend handling
+    pub weekend_policy: WeekendPolicy,
+}
+
+impl SessionConfig {
+    /// Create a new session configuration
+    pub fn new(rollover_time: NaiveTime, timezone: Tz) -> Self {
+        Self {
+            rollover_time,
+            timezone,
+            weekend_policy: WeekendPolicy::Separate,
+        }
+    }
+
+    /// Sessions rolling over at UTC midnight
+    pub fn utc_midnight() -> Self {
+        Self::new(NaiveTime::MIN, Tz::UTC)
+    }
+
+    /// Set the weekend policy (builder pattern)
+    pub fn with_weekend_policy(mut self, weekend_policy: WeekendPolicy) -> Self {
+        self.weekend_policy = weekend_policy;
+        self
+    }
+
+    /// Days between the local start date of a session and its label
+    fn label_offset(&self) -> Duration {
+        if self.rollover_time >= NaiveTime::from_hms_opt(12, 0, 0).unwrap() {
+            Duration::days(1)
+        } else {
+            Duration::zero()
+        }
+    }
+
+    /// Get the session a timestamp belongs to
+    pub fn session_date(&self, timestamp: DateTime<Utc>) -> NaiveDate {
+        let local = timestamp.with_timezone(&self.timezone).naive_local();
+        let start_date = if local.time() < self.rollover_time {
+            local.date() - Duration::days(1)
+        } else {
+            local.date()
+        };
+        let date = start_date + self.label_offset();
+
+        match (self.weekend_policy, date.weekday()) {
+            (WeekendPolicy::MergeIntoFriday, Weekday::Sat) => date - Duration::days(1),
+            (WeekendPolicy::MergeIntoFriday, Weekday::Sun) => date - Duration::days(2),
+            (WeekendPolicy::MergeIntoMonday, Weekday::Sat) => date + Duration::days(2),
+            (WeekendPolicy::MergeIntoMonday, Weekday::Sun) => date + Duration::days(1),
+            _ => date,
+        }
+    }
+
+    /// Get the instant at which the calendar session labelled `session_date` starts,
+    /// ignoring weekend merging
+    /// A rollover time skipped by a DST change resolves to the first instant after the gap
+    pub fn session_start(&self, session_date: NaiveDate) -> DateTime<Utc> {
+        let mut local = (session_date - self.label_offset()).and_time(self.rollover_time);
+        loop {
+            match self.timezone.from_local_datetime(&local) {
+                LocalResult::Single(dt) => return dt.with_timezone(&Utc),
+                LocalResult::Ambiguous(earliest, _) => return earliest.with_timezone(&Utc),
+                // Inside a DST gap: step forward until the local time exists
+                LocalResult::None => local += Duration::minutes(1),
+            }
+        }
+    }
+
+    /// Get the next rollover strictly after `timestamp`
+    pub fn next_rollover(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let current = self.session_date(timestamp);
+        let local_date = timestamp.with_timezone(&self.timezone).date_naive();
+
+        // Weekend merging can skip up to two rollovers
+        (-1..=8)
+            .map(|days| self.session_start(local_date + Duration::days(days)))
+            .find(|rollover| *rollover > timestamp && self.session_date(*rollover) != current)
+            .expect("a rollover occurs within a week")
+    }
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self::utc_midnight()
+    }
+}
+
+/// P&L of a single trading session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionPnl {
+    /// Session date
+    pub session_date: NaiveDate,
+    /// Realized P&L booked during the session
+    pub realized_pnl: Decimal,
+    /// Unrealized P&L carried forward from the previous session
+    pub opening_unrealized_pnl: Decimal,
+    /// Unrealized P&L at the session close (None while the session is open)
+    pub closing_unrealized_pnl: Option<Decimal>,
+}
+
+impl SessionPnl {
+    /// Open a new session carrying forward the given unrealized P&L
+    pub fn open(session_date: NaiveDate, opening_unrealized_pnl: Decimal) -> Self {
+        Self {
+            session_date,
+            realized_pnl: Decimal::ZERO,
+            opening_unrealized_pnl,
+            closing_unrealized_pnl: None,
+        }
+    }
+
+    /// Check if the session has been closed
+    pub fn is_closed(&self) -> bool {
+        self.closing_unrealized_pnl.is_some()
+    }
+
+    /// Session P&L: realized plus the change in unrealized since the session opened
+    pub fn total_pnl(&self, current_unrealized_pnl: Decimal) -> Decimal {
+        let unrealized = self
+            .closing_unrealized_pnl
+            .unwrap_or(current_unrealized_pnl);
+        self.realized_pnl + unrealized - self.opening_unrealized_pnl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+    use chrono_tz::Europe::London;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_utc_midnight_boundary() {
+        let config = SessionConfig::default();
+        assert_eq!(
+            config.session_date(utc("2024-03-04T23:59:59.999Z")),
+            date("2024-03-04")
+        );
+        assert_eq!(
+            config.session_date(utc("2024-03-05T00:00:00Z")),
+            date("2024-03-05")
+        );
+        assert_eq!(
+            config.next_rollover(utc("2024-03-04T12:00:00Z")),
+            utc("2024-03-05T00:00:00Z")
+        );
+        // A timestamp exactly at the rollover already belongs to the new session
+        assert_eq!(
+            config.next_rollover(utc("2024-03-05T00:00:00Z")),
+            utc("2024-03-06T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_new_york_rollover_across_dst() {
+        let config = SessionConfig::new(NaiveTime::from_hms_opt(17, 0, 0).unwrap(), New_York);
+
+        // EST (UTC-5): 17:00 local is 22:00 UTC and rolls into the next trade date
+        assert_eq!(
+            config.session_date(utc("2024-03-08T21:59:00Z")),
+            date("2024-03-08")
+        );
+        assert_eq!(
+            config.session_date(utc("2024-03-08T22:00:00Z")),
+            date("2024-03-09")
+        );
+
+        // DST starts on 2024-03-10; afterwards 17:00 EDT (UTC-4) is 21:00 UTC
+        assert_eq!(
+            config.session_start(date("2024-03-10")),
+            utc("2024-03-09T22:00:00Z")
+        );
+        assert_eq!(
+            config.session_start(date("2024-03-11")),
+            utc("2024-03-10T21:00:00Z")
+        );
+        assert_eq!(
+            config.session_date(utc("2024-03-11T21:30:00Z")),
+            date("2024-03-12")
+        );
+
+        // The session spanning the change is 23 hours long
+        let start = config.session_start(date("2024-03-10"));
+        assert_eq!(config.next_rollover(start) - start, Duration::hours(23));
+
+        // DST ends on 2024-11-03: the session spanning it is 25 hours long
+        let start = config.session_start(date("2024-11-03"));
+        assert_eq!(config.next_rollover(start) - start, Duration::hours(25));
+    }
+
+    #[test]
+    fn test_rollover_inside_dst_gap_and_overlap() {
+        // 01:30 London does not exist on 2024-03-31 and occurs twice on 2024-10-27
+        let config = SessionConfig::new(NaiveTime::from_hms_opt(1, 30, 0).unwrap(), London);
+
+        assert_eq!(
+            config.session_start(date("2024-03-31")),
+            utc("2024-03-31T01:00:00Z")
+        );
+        assert_eq!(
+            config.session_start(date("2024-10-27")),
+            utc("2024-10-27T00:30:00Z")
+        );
+        // The repeated 01:30 - 02:00 hour stays in the session that opened at the first one
+        assert_eq!(
+            config.session_date(utc("2024-10-27T01:45:00Z")),
+            date("2024-10-27")
+        );
+    }
+
+    #[test]
+    fn test_weekend_policies() {
+        let saturday = utc("2024-03-09T12:00:00Z");
+        let sunday = utc("2024-03-10T12:00:00Z");
+
+        let separate = SessionConfig::default();
+        assert_eq!(separate.session_date(saturday), date("2024-03-09"));
+
+        let friday = separate.with_weekend_policy(WeekendPolicy::MergeIntoFriday);
+        assert_eq!(friday.session_date(saturday), date("2024-03-08"));
+        assert_eq!(friday.session_date(sunday), date("2024-03-08"));
+        assert_eq!(
+            friday.next_rollover(utc("2024-03-08T12:00:00Z")),
+            utc("2024-03-11T00:00:00Z")
+        );
+
+        let monday = separate.with_weekend_policy(WeekendPolicy::MergeIntoMonday);
+        assert_eq!(monday.session_date(saturday), date("2024-03-11"));
+        assert_eq!(monday.session_date(sunday), date("2024-03-11"));
+        assert_eq!(
+            monday.next_rollover(utc("2024-03-08T12:00:00Z")),
+            utc("2024-03-09T00:00:00Z")
+        );
+        assert_eq!(monday.next_rollover(saturday), utc("2024-03-12T00:00:00Z"));
+
+        // FX week: Friday 17:00 New York opens Monday's session
+        let fx = SessionConfig::new(NaiveTime::from_hms_opt(17, 0, 0).unwrap(), New_York)
+            .with_weekend_policy(WeekendPolicy::MergeIntoMonday);
+        assert_eq!(
+            fx.session_date(utc("2024-03-08T22:30:00Z")),
+            date("2024-03-11")
+        );
+        assert_eq!(
+            fx.next_rollover(utc("2024-03-08T17:00:00Z")),
+            utc("2024-03-08T22:00:00Z")
+        );
+        assert_eq!(fx.next_rollover(saturday), utc("2024-03-11T21:00:00Z"));
+    }
+
+    #[test]
+    fn test_session_pnl_carry_forward() {
+        let mut session = SessionPnl::open(date("2024-03-05"), Decimal::new(100, 0));
+        session.realized_pnl = Decimal::new(50, 0);
+
+        // Unrealized moved from 100 to 80 during the session
+        assert_eq!(session.total_pnl(Decimal::new(80, 0)), Decimal::new(30, 0));
+
+        session.closing_unrealized_pnl = Some(Decimal::new(90, 0));
+        assert!(session.is_closed());
+        assert_eq!(session.total_pnl(Decimal::ZERO), Decimal::new(40, 0));
+    }
+}
@@ -1,10 +1,17 @@
-use crate::core::events::{ExecutionReport, OrderSide, OrderStatus};
+use crate::core::clock::{system_clock, SharedClock};
+use crate::core::events::{BorrowRate, ExecutionReport, FundingRate, OrderSide, OrderStatus};
+use crate::risk::fee_conversion::{FeeConverter, MarketFeeConverter};
+use crate::risk::ledger_store::{LedgerSnapshot, LedgerState, LedgerStore, LedgerUpdate};
+use crate::risk::session::{SessionConfig, SessionPnl};
 use crate::types::{Price, Size, Symbol};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use log::{error, warn};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
 
 /// Trade record in the shadow ledger
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,10 +32,13 @@ pub struct TradeRecord {
     pub price: Price,
     /// Trade timestamp
     pub timestamp: DateTime<Utc>,
-    /// Fee
+    /// Fee (negative for a maker rebate)
     pub fee: Size,
     /// Fee asset
     pub fee_asset: String,
+    /// Fee converted to the symbol's quote asset, stamped by the ledger when booked
+    #[serde(default)]
+    pub fee_in_quote: Option<rust_decimal::Decimal>,
 }
 
 impl TradeRecord {
@@ -56,6 +66,7 @@ impl TradeRecord {
             timestamp,
             fee,
             fee_asset,
+            fee_in_quote: None,
         }
     }
 
@@ -64,20 +75,62 @@ impl TradeRecord {
         self.quantity.value() * self.price.value()
     }
 
+    /// Get the fee in the quote asset
+    /// Falls back to the raw fee, as if paid in the quote asset, until converted.
+    pub fn quote_fee(&self) -> rust_decimal::Decimal {
+        self.fee_in_quote.unwrap_or(self.fee.value())
+    }
+
     /// Get net value after fee
     pub fn net_value(&self) -> rust_decimal::Decimal {
         let trade_value = self.value();
 
-        // For simplicity, assume fee is in the quote asset
-        // In a real implementation, you'd need to handle fee conversion
         if self.side == OrderSide::Buy {
-            trade_value + self.fee.value() // Buying costs more with fee
+            trade_value + self.quote_fee() // Buying costs more with fee
         } else {
-            trade_value - self.fee.value() // Selling earns less with fee
+            trade_value - self.quote_fee() // Selling earns less with fee
         }
     }
 }
 
+/// How the cost of the units a sell closes is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CostBasisMethod {
+    /// Every unit costs the position's average price
+    #[default]
+    AverageCost,
+    /// The oldest lots close first
+    Fifo,
+    /// The newest lots close first
+    Lifo,
+}
+
+/// Units opened together, at one cost per unit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lot {
+    /// Units left open
+    pub quantity: Size,
+    /// Entry price per unit, net of the opening fee and any carry booked since
+    pub price: Price,
+    /// When the lot was opened
+    pub opened_at: DateTime<Utc>,
+}
+
+/// Part of a lot closed by an opposite trade, for tax-style reporting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClosedLot {
+    pub symbol: Symbol,
+    pub exchange_id: String,
+    pub quantity: Size,
+    /// Cost per unit under the position's cost basis method
+    pub entry_price: Price,
+    pub exit_price: Price,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: DateTime<Utc>,
+    /// P&L less this part's share of the closing fee
+    pub realized_pnl: rust_decimal::Decimal,
+}
+
 /// Position record in the shadow ledger
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionRecord {
@@ -93,6 +146,18 @@ pub struct PositionRecord {
     pub total_cost: rust_decimal::Decimal,
     /// Realized P&L
     pub realized_pnl: rust_decimal::Decimal,
+    /// Funding and borrow interest booked into the cost basis (negative if received)
+    #[serde(default)]
+    pub carry_cost: rust_decimal::Decimal,
+    /// Time up to which borrow interest has been accrued (None while flat)
+    #[serde(default)]
+    pub accrued_until: Option<DateTime<Utc>>,
+    /// How sells pick the cost of the units they close
+    #[serde(default)]
+    pub cost_basis_method: CostBasisMethod,
+    /// Open lots of the position, long or short, oldest first
+    #[serde(default)]
+    pub lots: VecDeque<Lot>,
     /// Last update timestamp
     pub last_updated: DateTime<Utc>,
 }
@@ -107,10 +172,53 @@ impl PositionRecord {
             average_price: None,
             total_cost: rust_decimal::Decimal::ZERO,
             realized_pnl: rust_decimal::Decimal::ZERO,
+            carry_cost: rust_decimal::Decimal::ZERO,
+            accrued_until: None,
+            cost_basis_method: CostBasisMethod::default(),
+            lots: VecDeque::new(),
             last_updated: Utc::now(),
         }
     }
 
+    /// Track a position that has no lots, such as a seeded one, as a single lot
+    fn ensure_lots(&mut self) {
+        if !self.lots.is_empty() || self.size.is_zero() {
+            return;
+        }
+        if let Some(price) = self.average_price {
+            self.lots.push_back(Lot {
+                quantity: self.size.abs(),
+                price,
+                opened_at: self.last_updated,
+            });
+        }
+    }
+
+    /// Book a carrying cost (positive) or income (negative) into the cost basis at a time
+    /// The average price moves against the position, so unrealized and later realized
+    /// P&L include the cost. Flat positions have nothing to carry.
+    pub fn accrue_cost(&mut self, amount: rust_decimal::Decimal, at: DateTime<Utc>) {
+        if self.size.is_zero() {
+            return;
+        }
+        self.ensure_lots();
+        let per_unit = amount / self.size.value();
+        if let Some(avg_price) = self.average_price {
+            self.average_price = Some(Price::new(avg_price.value() + per_unit));
+        }
+        for lot in &mut self.lots {
+            lot.price = Price::new(lot.price.value() + per_unit);
+        }
+        // Longs pay more for their cost, shorts keep less of their proceeds
+        self.total_cost += if self.size.is_positive() {
+            amount
+        } else {
+            -amount
+        };
+        self.carry_cost += amount;
+        self.last_updated = at;
+    }
+
     /// Get unrealized P&L based on current market price
     pub fn unrealized_pnl(&self, current_price: Price) -> Option<rust_decimal::Decimal> {
         self.average_price.map(|avg_price| {
@@ -120,8 +228,8 @@ impl PositionRecord {
                 // Long position: P&L = (current_price - avg_price) * size
                 (current_price.value() - avg_price.value()) * self.size.value()
             } else {
-                // Short position: P&L = (avg_price - current_price) * size
-                (avg_price.value() - current_price.value()) * self.size.value()
+                // Short position: P&L = (avg_price - current_price) * |size|
+                (avg_price.value() - current_price.value()) * self.size.value().abs()
             }
         })
     }
@@ -136,72 +244,204 @@ impl PositionRecord {
     }
 
     /// Update position with a new trade
-    pub fn apply_trade(&mut self, trade: &TradeRecord) {
-        // Note: trade_value is available via trade.value() if needed
-        let _trade_value = trade.value();
+    /// A trade against the position closes lots under the position's cost basis method,
+    /// and any size beyond the position opens one the other way. The fee is split
+    /// between the two parts in proportion. Returns the lots closed.
+    pub fn apply_trade(&mut self, trade: &TradeRecord) -> Vec<ClosedLot> {
+        self.ensure_lots();
+        let is_long = self.size.is_positive();
+        let closing = match trade.side {
+            OrderSide::Buy => self.size.value() < rust_decimal::Decimal::ZERO,
+            OrderSide::Sell => is_long,
+        };
 
-        match trade.side {
-            OrderSide::Buy => {
-                // Buying increases position size and total cost
-                let new_size = self.size + trade.quantity;
-                let new_total_cost = self.total_cost + trade.net_value();
-
-                // Update average price
-                self.average_price = if new_size.is_zero() {
-                    None
-                } else {
-                    Some(Price::new(new_total_cost / new_size.value()))
+        let mut closed = Vec::new();
+        let mut to_close = if closing {
+            trade.quantity.min(self.size.abs())
+        } else {
+            Size::zero()
+        };
+        let fee_share = |quantity: Size| {
+            if trade.quantity.is_zero() {
+                rust_decimal::Decimal::ZERO
+            } else {
+                trade.quote_fee() * quantity.value() / trade.quantity.value()
+            }
+        };
+        while to_close.is_positive() {
+            let lot = match self.cost_basis_method {
+                CostBasisMethod::Lifo => self.lots.back_mut(),
+                _ => self.lots.front_mut(),
+            };
+            let Some(lot) = lot else {
+                break;
+            };
+            let quantity = to_close.min(lot.quantity);
+            let entry_price = match (self.cost_basis_method, self.average_price) {
+                (CostBasisMethod::AverageCost, Some(avg_price)) => avg_price,
+                _ => lot.price,
+            };
+            // Long: (exit - entry) * quantity; short: (entry - exit) * quantity
+            let gain = (trade.price.value() - entry_price.value()) * quantity.value();
+            closed.push(ClosedLot {
+                symbol: trade.symbol.clone(),
+                exchange_id: trade.exchange_id.clone(),
+                quantity,
+                entry_price,
+                exit_price: trade.price,
+                opened_at: lot.opened_at,
+                closed_at: trade.timestamp,
+                realized_pnl: if is_long { gain } else { -gain } - fee_share(quantity),
+            });
+            lot.quantity = lot.quantity - quantity;
+            to_close = to_close - quantity;
+            if lot.quantity.is_zero() {
+                match self.cost_basis_method {
+                    CostBasisMethod::Lifo => self.lots.pop_back(),
+                    _ => self.lots.pop_front(),
                 };
+            }
+        }
+        let closed_size = closed
+            .iter()
+            .fold(Size::zero(), |total, lot| total + lot.quantity);
+        self.realized_pnl += closed
+            .iter()
+            .map(|lot| lot.realized_pnl)
+            .sum::<rust_decimal::Decimal>();
 
-                self.size = new_size;
-                self.total_cost = new_total_cost;
+        // Shrink the position by what was closed
+        if closed_size.is_positive() {
+            self.size = if is_long {
+                self.size - closed_size
+            } else {
+                self.size + closed_size
+            };
+            if self.size.is_zero() {
+                self.average_price = None;
+                self.total_cost = rust_decimal::Decimal::ZERO;
+                self.accrued_until = None;
+                self.lots.clear();
+            } else {
+                self.rebase_cost();
             }
-            OrderSide::Sell => {
-                // Selling reduces position size and realizes P&L
-                let new_size = self.size - trade.quantity;
-
-                // Calculate realized P&L for this trade
-                let realized_pnl = if let Some(avg_price) = self.average_price {
-                    if !self.size.is_zero() {
-                        // P&L = (sell_price - avg_price) * quantity
-                        (trade.price.value() - avg_price.value()) * trade.quantity.value()
-                    } else {
-                        rust_decimal::Decimal::ZERO
-                    }
-                } else {
-                    rust_decimal::Decimal::ZERO
-                };
+        }
 
-                // Update realized P&L
-                self.realized_pnl += realized_pnl;
+        // Open or add to a position in the trade's direction with the rest
+        let opened = trade.quantity - closed_size;
+        if opened.is_positive() {
+            let fee = fee_share(opened);
+            // Buys pay the fee on top, sells receive less
+            let entry_value = match trade.side {
+                OrderSide::Buy => trade.price.value() * opened.value() + fee,
+                OrderSide::Sell => trade.price.value() * opened.value() - fee,
+            };
+            if self.size.is_zero() {
+                self.accrued_until = Some(trade.timestamp);
+            }
+            self.lots.push_back(Lot {
+                quantity: opened,
+                price: Price::new(entry_value / opened.value()),
+                opened_at: trade.timestamp,
+            });
+            self.size = match trade.side {
+                OrderSide::Buy => self.size + opened,
+                OrderSide::Sell => self.size - opened,
+            };
+            self.total_cost += entry_value;
+            self.average_price = Some(Price::new(self.total_cost / self.size.value().abs()));
+        }
 
-                // Update position size
-                self.size = new_size;
+        self.last_updated = trade.timestamp;
+        closed
+    }
 
-                // Clear average price and total cost if position is closed
-                if new_size.is_zero() {
-                    self.average_price = None;
-                    self.total_cost = rust_decimal::Decimal::ZERO;
-                }
+    /// Recompute the cost basis after lots closed
+    /// Average cost keeps its price; FIFO and LIFO follow the lots left open.
+    fn rebase_cost(&mut self) {
+        let size = self.size.value().abs();
+        if self.cost_basis_method == CostBasisMethod::AverageCost {
+            if let Some(avg_price) = self.average_price {
+                self.total_cost = avg_price.value() * size;
             }
+        } else {
+            self.total_cost = self
+                .lots
+                .iter()
+                .map(|lot| lot.price.value() * lot.quantity.value())
+                .sum();
+            self.average_price = Some(Price::new(self.total_cost / size));
         }
+    }
 
-        self.last_updated = Utc::now();
+    /// Switch how later trades pick the cost of the units they close
+    /// Under FIFO or LIFO the average price follows the lots left open.
+    pub fn set_cost_basis_method(&mut self, method: CostBasisMethod) {
+        self.ensure_lots();
+        self.cost_basis_method = method;
     }
 }
 
+/// Closed lots kept in memory by default; older ones are dropped
+pub const DEFAULT_CLOSED_LOT_RETENTION: usize = 10_000;
+
+/// Trades kept in memory by default; older ones are only in the store, if any
+pub const DEFAULT_TRADE_RETENTION: usize = 10_000;
+
+/// Delay between a change and the background save, so bursts of trades share one write
+pub const DEFAULT_SAVE_DELAY: Duration = Duration::from_millis(100);
+
+/// Changes not yet written to the store
+#[derive(Debug, Default)]
+struct PendingSave {
+    trades: Vec<TradeRecord>,
+    closed_lots: Vec<ClosedLot>,
+    /// Whether a background save is already scheduled
+    scheduled: bool,
+}
+
 /// Shadow ledger implementation
+/// Clones share the same ledger.
+#[derive(Clone)]
 pub struct ShadowLedger {
     /// All positions by symbol and exchange
     positions: Arc<RwLock<HashMap<String, PositionRecord>>>,
-    /// All trades
-    trades: Arc<RwLock<Vec<TradeRecord>>>,
-    /// Daily P&L by date
+    /// Most recent trades, in order
+    trades: Arc<RwLock<VecDeque<TradeRecord>>>,
+    /// Number of trades kept in memory
+    trade_retention: usize,
+    /// Totals over every trade booked
+    trade_stats: Arc<RwLock<TradeStats>>,
+    /// Daily P&L by session date
     daily_pnl: Arc<RwLock<HashMap<String, rust_decimal::Decimal>>>,
+    /// Session calendar used to assign trades to daily sessions
+    session_config: SessionConfig,
+    /// Session P&L history (the last entry is the open session)
+    sessions: Arc<RwLock<Vec<SessionPnl>>>,
+    /// Last traded price by symbol, used to mark positions at rollover
+    last_prices: Arc<RwLock<HashMap<String, Price>>>,
     /// Historical P&L records
     historical_pnl: Arc<RwLock<Vec<HistoricalPnL>>>,
     /// Peak equity value
     peak_equity: Arc<RwLock<rust_decimal::Decimal>>,
+    /// Time source for stamps the trades do not carry
+    clock: SharedClock,
+    /// Storage the ledger is saved to after every change
+    store: Option<Arc<dyn LedgerStore>>,
+    /// Trades and closed lots booked since the last save
+    pending: Arc<Mutex<PendingSave>>,
+    /// Delay before a change is saved in the background
+    save_delay: Duration,
+    /// Serializes saves so an older state never overwrites a newer one
+    save_lock: Arc<Mutex<()>>,
+    /// Converts fees paid in other assets to the quote asset
+    fee_converter: Arc<dyn FeeConverter>,
+    /// Cost basis method of new positions
+    cost_basis_method: Arc<RwLock<CostBasisMethod>>,
+    /// Most recent lots closed by sells, in order
+    closed_lots: Arc<RwLock<VecDeque<ClosedLot>>>,
+    /// Number of closed lots kept in memory
+    closed_lot_retention: usize,
 }
 
 impl ShadowLedger {
@@ -209,13 +449,199 @@ impl ShadowLedger {
     pub fn new() -> Self {
         Self {
             positions: Arc::new(RwLock::new(HashMap::new())),
-            trades: Arc::new(RwLock::new(Vec::new())),
+            trades: Arc::new(RwLock::new(VecDeque::new())),
+            trade_retention: DEFAULT_TRADE_RETENTION,
+            trade_stats: Arc::new(RwLock::new(TradeStats::default())),
             daily_pnl: Arc::new(RwLock::new(HashMap::new())),
+            session_config: SessionConfig::default(),
+            sessions: Arc::new(RwLock::new(Vec::new())),
+            last_prices: Arc::new(RwLock::new(HashMap::new())),
             historical_pnl: Arc::new(RwLock::new(Vec::new())),
             peak_equity: Arc::new(RwLock::new(rust_decimal::Decimal::ZERO)),
+            clock: system_clock(),
+            store: None,
+            pending: Arc::new(Mutex::new(PendingSave::default())),
+            save_delay: DEFAULT_SAVE_DELAY,
+            save_lock: Arc::new(Mutex::new(())),
+            fee_converter: Arc::new(MarketFeeConverter::default()),
+            cost_basis_method: Arc::new(RwLock::new(CostBasisMethod::default())),
+            closed_lots: Arc::new(RwLock::new(VecDeque::new())),
+            closed_lot_retention: DEFAULT_CLOSED_LOT_RETENTION,
         }
     }
 
+    /// Set the time source (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set how many trades are kept in memory (builder pattern)
+    /// Older trades are read back from the store when a query reaches past them.
+    pub fn with_trade_retention(mut self, retention: usize) -> Self {
+        self.trade_retention = retention;
+        self
+    }
+
+    /// Set how many closed lots are kept in memory (builder pattern)
+    pub fn with_closed_lot_retention(mut self, retention: usize) -> Self {
+        self.closed_lot_retention = retention;
+        self
+    }
+
+    /// Save the ledger to a store after every change (builder pattern)
+    /// Saves run in the background; call `load_from_store` on startup to pick up where
+    /// the last run left off and `flush` on shutdown to write what is still pending.
+    pub fn with_store(mut self, store: Arc<dyn LedgerStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Set the delay between a change and its background save (builder pattern)
+    pub fn with_save_delay(mut self, save_delay: Duration) -> Self {
+        self.save_delay = save_delay;
+        self
+    }
+
+    /// Set how fees paid in other assets are converted to the quote asset (builder pattern)
+    pub fn with_fee_converter(mut self, fee_converter: Arc<dyn FeeConverter>) -> Self {
+        self.fee_converter = fee_converter;
+        self
+    }
+
+    /// Replace the ledger's contents with the store's last snapshot
+    /// Returns false, leaving the ledger untouched, without a store or snapshot.
+    pub async fn load_from_store(&self) -> io::Result<bool> {
+        let Some(store) = &self.store else {
+            return Ok(false);
+        };
+        let store = store.clone();
+        let (trade_retention, lot_retention) = (self.trade_retention, self.closed_lot_retention);
+        let loaded =
+            tokio::task::spawn_blocking(move || store.load(trade_retention, lot_retention))
+                .await
+                .map_err(io::Error::other)??;
+        let Some(LedgerSnapshot {
+            state,
+            trades,
+            closed_lots,
+        }) = loaded
+        else {
+            return Ok(false);
+        };
+        *self.positions.write().await = state
+            .positions
+            .into_iter()
+            .map(|p| (Self::get_position_key(p.symbol.value(), &p.exchange_id), p))
+            .collect();
+        *self.trades.write().await = trades.into();
+        *self.trade_stats.write().await = state.trade_stats;
+        *self.daily_pnl.write().await = state.daily_pnl;
+        *self.sessions.write().await = state.sessions;
+        *self.last_prices.write().await = state.last_prices;
+        *self.historical_pnl.write().await = state.historical_pnl;
+        *self.peak_equity.write().await = state.peak_equity;
+        *self.cost_basis_method.write().await = state.cost_basis_method;
+        *self.closed_lots.write().await = closed_lots.into();
+        let mut pending = self.pending.lock().await;
+        pending.trades.clear();
+        pending.closed_lots.clear();
+        Ok(true)
+    }
+
+    /// Get a copy of everything the ledger keeps
+    pub async fn snapshot(&self) -> LedgerSnapshot {
+        LedgerSnapshot {
+            state: self.state().await,
+            trades: self.trades.read().await.iter().cloned().collect(),
+            closed_lots: self.closed_lots.read().await.iter().cloned().collect(),
+        }
+    }
+
+    /// Get a copy of the state a save replaces
+    async fn state(&self) -> LedgerState {
+        LedgerState {
+            positions: self.positions.read().await.values().cloned().collect(),
+            daily_pnl: self.daily_pnl.read().await.clone(),
+            sessions: self.sessions.read().await.clone(),
+            last_prices: self.last_prices.read().await.clone(),
+            historical_pnl: self.historical_pnl.read().await.clone(),
+            peak_equity: *self.peak_equity.read().await,
+            cost_basis_method: *self.cost_basis_method.read().await,
+            trade_stats: self.trade_stats.read().await.clone(),
+        }
+    }
+
+    /// Schedule a background save of the ledger to its store, if any
+    /// Changes made before the save runs are written together.
+    async fn persist(&self) {
+        if self.store.is_none() {
+            return;
+        }
+        {
+            let mut pending = self.pending.lock().await;
+            if pending.scheduled {
+                return;
+            }
+            pending.scheduled = true;
+        }
+        let ledger = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(ledger.save_delay).await;
+            if let Err(e) = ledger.flush().await {
+                error!("Failed to persist shadow ledger: {}", e);
+            }
+        });
+    }
+
+    /// Write pending changes to the store now
+    /// On failure the changes stay pending and are retried by the next save.
+    pub async fn flush(&self) -> io::Result<()> {
+        let Some(store) = self.store.clone() else {
+            return Ok(());
+        };
+        let _guard = self.save_lock.lock().await;
+        let (new_trades, new_closed_lots) = {
+            let mut pending = self.pending.lock().await;
+            pending.scheduled = false;
+            (
+                std::mem::take(&mut pending.trades),
+                std::mem::take(&mut pending.closed_lots),
+            )
+        };
+        let update = LedgerUpdate {
+            state: self.state().await,
+            new_trades,
+            new_closed_lots,
+        };
+        let (update, result) = tokio::task::spawn_blocking(move || {
+            let result = store.save(&update);
+            (update, result)
+        })
+        .await
+        .map_err(io::Error::other)?;
+        if result.is_err() {
+            // Put the changes back ahead of anything booked since
+            let mut pending = self.pending.lock().await;
+            let trades = std::mem::replace(&mut pending.trades, update.new_trades);
+            pending.trades.extend(trades);
+            let lots = std::mem::replace(&mut pending.closed_lots, update.new_closed_lots);
+            pending.closed_lots.extend(lots);
+        }
+        result
+    }
+
+    /// Set the session calendar used for daily P&L (builder pattern)
+    pub fn with_session_config(mut self, session_config: SessionConfig) -> Self {
+        self.session_config = session_config;
+        self
+    }
+
+    /// Get the session calendar
+    pub fn session_config(&self) -> &SessionConfig {
+        &self.session_config
+    }
+
     /// Get position key for a symbol and exchange
     fn get_position_key(symbol: &str, exchange_id: &str) -> String {
         format!("{}:{}", symbol, exchange_id)
@@ -237,30 +663,82 @@ impl ShadowLedger {
     }
 
     /// Record a new trade
-    pub async fn add_trade(&self, trade: TradeRecord) {
+    /// A fee paid in another asset is converted to the quote asset at the latest prices;
+    /// if it cannot be priced it is booked as if paid in the quote asset.
+    pub async fn add_trade(&self, mut trade: TradeRecord) {
+        // Roll the session before booking a trade that belongs to a new one
+        let marks = self.last_prices.read().await.clone();
+        self.roll_session(trade.timestamp, &marks).await;
+        if trade.fee_in_quote.is_none() {
+            trade.fee_in_quote = self.fee_converter.fee_in_quote(&trade, &marks);
+            if trade.fee_in_quote.is_none() {
+                warn!(
+                    "No price to convert {} {} fee on {} trade {}; booking it as quote",
+                    trade.fee, trade.fee_asset, trade.symbol, trade.trade_id
+                );
+            }
+        }
+        self.last_prices
+            .write()
+            .await
+            .insert(trade.symbol.value().to_string(), trade.price);
+
         // Add to trades list
         {
             let mut trades = self.trades.write().await;
-            trades.push(trade.clone());
+            trades.push_back(trade.clone());
+            let excess = trades.len().saturating_sub(self.trade_retention);
+            trades.drain(..excess);
+        }
+        self.trade_stats.write().await.record(&trade);
+        if self.store.is_some() {
+            self.pending.lock().await.trades.push(trade.clone());
         }
 
         // Update position
-        {
+        let cost_basis_method = *self.cost_basis_method.read().await;
+        let closed = {
             let position_key = Self::get_position_key(trade.symbol.value(), &trade.exchange_id);
             let mut positions = self.positions.write().await;
 
-            if let Some(position) = positions.get_mut(&position_key) {
-                position.apply_trade(&trade);
-            }
-        }
+            positions
+                .entry(position_key)
+                .or_insert_with(|| PositionRecord {
+                    cost_basis_method,
+                    ..PositionRecord::new(trade.symbol.clone(), trade.exchange_id.clone())
+                })
+                .apply_trade(&trade)
+        };
 
         // Update daily P&L
-        self.update_daily_pnl(&trade).await;
+        let trade_pnl = closed.iter().map(|lot| lot.realized_pnl).sum();
+        self.retain_closed_lots(closed).await;
+        self.update_daily_pnl(&trade, trade_pnl).await;
+        self.persist().await;
     }
 
-    /// Update daily P&L based on a trade
-    async fn update_daily_pnl(&self, trade: &TradeRecord) {
-        let date_key = trade.timestamp.format("%Y-%m-%d").to_string();
+    /// Append closed lots, dropping the oldest beyond the retention limit
+    async fn retain_closed_lots(&self, closed: Vec<ClosedLot>) {
+        if self.store.is_some() {
+            self.pending
+                .lock()
+                .await
+                .closed_lots
+                .extend(closed.iter().cloned());
+        }
+        let mut closed_lots = self.closed_lots.write().await;
+        closed_lots.extend(closed);
+        let excess = closed_lots.len().saturating_sub(self.closed_lot_retention);
+        closed_lots.drain(..excess);
+    }
+
+    /// Add a trade's realized P&L to its session's daily P&L
+    async fn update_daily_pnl(&self, trade: &TradeRecord, trade_pnl: rust_decimal::Decimal) {
+        let date_key = self
+            .session_config
+            .session_date(trade.timestamp)
+            .format("%Y-%m-%d")
+            .to_string();
         let mut daily_pnl = self.daily_pnl.write().await;
 
         let current_pnl = daily_pnl
@@ -268,27 +746,79 @@ impl ShadowLedger {
             .cloned()
             .unwrap_or(rust_decimal::Decimal::ZERO);
 
-        // Calculate P&L for this trade
-        let trade_pnl = if trade.side == OrderSide::Sell {
-            // For sells, we need to look up the position to calculate P&L
-            let position_key = Self::get_position_key(trade.symbol.value(), &trade.exchange_id);
-            let positions = self.positions.read().await;
-
-            if let Some(position) = positions.get(&position_key) {
-                if let Some(avg_price) = position.average_price {
-                    // P&L = (sell_price - avg_price) * quantity
-                    (trade.price.value() - avg_price.value()) * trade.quantity.value()
-                } else {
-                    rust_decimal::Decimal::ZERO
-                }
-            } else {
-                rust_decimal::Decimal::ZERO
+        daily_pnl.insert(date_key, current_pnl + trade_pnl);
+
+        let mut sessions = self.sessions.write().await;
+        if let Some(session) = sessions.last_mut() {
+            session.realized_pnl += trade_pnl;
+        }
+    }
+
+    /// Roll over to the session containing `now` if it has changed
+    /// Closes the open session at the current marks and carries its unrealized P&L
+    /// forward as the opening unrealized P&L of the new session.
+    /// Returns the session that was closed, if any.
+    pub async fn roll_session(
+        &self,
+        now: DateTime<Utc>,
+        market_prices: &HashMap<String, Price>,
+    ) -> Option<SessionPnl> {
+        let session_date = self.session_config.session_date(now);
+        if let Some(open) = self.sessions.read().await.last() {
+            // Late or out-of-order timestamps never reopen a past session
+            if open.session_date >= session_date {
+                return None;
             }
-        } else {
-            rust_decimal::Decimal::ZERO
-        };
+        }
 
-        daily_pnl.insert(date_key, current_pnl + trade_pnl);
+        let unrealized = self.get_total_unrealized_pnl(market_prices).await;
+        let mut sessions = self.sessions.write().await;
+        // Re-check under the write lock in case another task rolled first
+        if let Some(open) = sessions.last() {
+            if open.session_date >= session_date {
+                return None;
+            }
+        }
+
+        let closed = sessions.last_mut().map(|open| {
+            open.closing_unrealized_pnl = Some(unrealized);
+            open.clone()
+        });
+        sessions.push(SessionPnl::open(session_date, unrealized));
+        drop(sessions);
+        self.persist().await;
+        closed
+    }
+
+    /// Get the P&L of the open session, marked at the given prices
+    /// Rolls over automatically if `now` falls into a new session.
+    pub async fn get_session_pnl(
+        &self,
+        now: DateTime<Utc>,
+        market_prices: &HashMap<String, Price>,
+    ) -> rust_decimal::Decimal {
+        self.roll_session(now, market_prices).await;
+        let unrealized = self.get_total_unrealized_pnl(market_prices).await;
+        self.sessions
+            .read()
+            .await
+            .last()
+            .map(|session| session.total_pnl(unrealized))
+            .unwrap_or(rust_decimal::Decimal::ZERO)
+    }
+
+    /// Get a session by date
+    pub async fn get_session(&self, session_date: NaiveDate) -> Option<SessionPnl> {
+        let sessions = self.sessions.read().await;
+        sessions
+            .iter()
+            .find(|s| s.session_date == session_date)
+            .cloned()
+    }
+
+    /// Get all sessions, oldest first
+    pub async fn get_sessions(&self) -> Vec<SessionPnl> {
+        self.sessions.read().await.clone()
     }
 
     /// Get position for a symbol and exchange
@@ -304,13 +834,143 @@ impl ShadowLedger {
         positions.values().cloned().collect()
     }
 
-    /// Get all trades
+    /// Get the last traded price of every symbol, by symbol
+    pub async fn get_last_prices(&self) -> HashMap<String, Price> {
+        self.last_prices.read().await.clone()
+    }
+
+    /// Set the cost basis method of open and future positions
+    /// Lots already closed keep the P&L they realized.
+    pub async fn set_cost_basis_method(&self, method: CostBasisMethod) {
+        *self.cost_basis_method.write().await = method;
+        for position in self.positions.write().await.values_mut() {
+            position.set_cost_basis_method(method);
+        }
+        self.persist().await;
+    }
+
+    /// Get the cost basis method of new positions
+    pub async fn cost_basis_method(&self) -> CostBasisMethod {
+        *self.cost_basis_method.read().await
+    }
+
+    /// Get the most recent lots closed by sells, oldest first, for tax-style reporting
+    /// At most `closed_lot_retention` lots are kept (see `with_closed_lot_retention`).
+    pub async fn get_closed_lots(&self) -> Vec<ClosedLot> {
+        self.closed_lots.read().await.iter().cloned().collect()
+    }
+
+    /// Record the latest market price of a symbol
+    /// Used to mark positions and to convert fees paid in assets the ledger does not trade.
+    pub async fn update_price(&self, symbol: &str, price: Price) {
+        self.last_prices
+            .write()
+            .await
+            .insert(symbol.to_string(), price);
+    }
+
+    /// Check if the ledger has no trades or positions, as on a fresh start
+    pub async fn is_empty(&self) -> bool {
+        self.trades.read().await.is_empty() && self.positions.read().await.is_empty()
+    }
+
+    /// Seed a position that predates the ledger, such as one found on the exchange at startup
+    /// No trade is recorded, so the position carries no realized or daily P&L
+    pub async fn seed_position(&self, position: PositionRecord) {
+        let key = Self::get_position_key(position.symbol.value(), &position.exchange_id);
+        if let Some(price) = position.average_price {
+            self.last_prices
+                .write()
+                .await
+                .insert(position.symbol.value().to_string(), price);
+        }
+        self.positions.write().await.insert(key, position);
+        self.persist().await;
+    }
+
+    /// Settle a funding payment on a perpetual position
+    /// Longs pay and shorts receive a positive rate, on the position's value at the mark
+    /// price. Returns the cost booked, or None without an open position.
+    pub async fn apply_funding(
+        &self,
+        funding: &FundingRate,
+        mark_price: Price,
+    ) -> Option<rust_decimal::Decimal> {
+        let key = Self::get_position_key(funding.symbol.value(), &funding.exchange_id);
+        let payment = {
+            let mut positions = self.positions.write().await;
+            let position = positions.get_mut(&key).filter(|p| !p.size.is_zero())?;
+            let payment = funding.rate * mark_price.value() * position.size.value();
+            position.accrue_cost(
+                payment,
+                DateTime::from_timestamp_millis(funding.timestamp as i64)
+                    .unwrap_or_else(|| self.clock.now_utc()),
+            );
+            payment
+        };
+        self.persist().await;
+        Some(payment)
+    }
+
+    /// Accrue borrow interest on a margin position up to `now`
+    /// A long borrows the quote asset to pay for its cost; a short borrows the base
+    /// asset it sold, valued at the average price. The rate is skipped if its asset is
+    /// not the one the position borrows. Returns the cost booked, or None if nothing
+    /// was accrued.
+    pub async fn accrue_borrow_interest(
+        &self,
+        symbol: &str,
+        exchange_id: &str,
+        borrow: &BorrowRate,
+        now: DateTime<Utc>,
+    ) -> Option<rust_decimal::Decimal> {
+        let key = Self::get_position_key(symbol, exchange_id);
+        let mut positions = self.positions.write().await;
+        let position = positions.get_mut(&key).filter(|p| !p.size.is_zero())?;
+        let avg_price = position.average_price?.value();
+        let is_long = position.size.value() > rust_decimal::Decimal::ZERO;
+        let borrows_asset = if is_long {
+            symbol.ends_with(&borrow.asset)
+        } else {
+            symbol.starts_with(&borrow.asset)
+        };
+        if !borrows_asset {
+            return None;
+        }
+
+        let since = position.accrued_until.unwrap_or(now);
+        position.accrued_until = Some(now.max(since));
+        let elapsed_ms = (now - since).num_milliseconds();
+        if elapsed_ms <= 0 {
+            return None;
+        }
+        let hours =
+            rust_decimal::Decimal::from(elapsed_ms) / rust_decimal::Decimal::from(3_600_000);
+        let interest = position.size.value().abs() * avg_price * borrow.hourly_rate * hours;
+        position.accrue_cost(interest, now);
+        drop(positions);
+        self.persist().await;
+        Some(interest)
+    }
+
+    /// Get the total funding and borrow interest booked across all positions
+    pub async fn get_total_carry_cost(&self) -> rust_decimal::Decimal {
+        self.positions
+            .read()
+            .await
+            .values()
+            .map(|position| position.carry_cost)
+            .sum()
+    }
+
+    /// Get the trades kept in memory, the most recent up to the trade retention
+    /// Use `get_trades_in_range` to reach older trades in the store.
     pub async fn get_all_trades(&self) -> Vec<TradeRecord> {
         let trades = self.trades.read().await;
-        trades.clone()
+        trades.iter().cloned().collect()
     }
 
-    /// Get trades for a symbol
+    /// Get the trades kept in memory for a symbol
     pub async fn get_trades_for_symbol(&self, symbol: &str) -> Vec<TradeRecord> {
         let trades = self.trades.read().await;
         trades
@@ -320,7 +980,7 @@ impl ShadowLedger {
             .collect()
     }
 
-    /// Get daily P&L for a specific date
+    /// Get daily realized P&L for a specific session date ("%Y-%m-%d")
     pub async fn get_daily_pnl(&self, date: &str) -> rust_decimal::Decimal {
         let daily_pnl = self.daily_pnl.read().await;
         daily_pnl
@@ -381,7 +1041,7 @@ impl ShadowLedger {
             let trade_id = format!(
                 "{}_{}",
                 &report.order_id,
-                Utc::now().timestamp_nanos_opt().unwrap_or(0)
+                self.clock.now_utc().timestamp_nanos_opt().unwrap_or(0)
             );
             // Note: ExecutionReport doesn't track side, default to Buy for filled orders
             // In a real implementation, you'd look up the original order to get the side
@@ -396,7 +1056,7 @@ impl ShadowLedger {
                     .average_price
                     .unwrap_or(Price::new(rust_decimal::Decimal::ZERO)),
                 DateTime::from_timestamp((report.timestamp / 1000) as i64, 0)
-                    .unwrap_or_else(Utc::now),
+                    .unwrap_or_else(|| self.clock.now_utc()),
                 Size::new(rust_decimal::Decimal::ZERO), // Default to zero fee
                 "USDT".to_string(), // Default to USDT, in a real implementation you'd track this
             );
@@ -405,10 +1065,13 @@ impl ShadowLedger {
         }
     }
 
-    /// Reset daily P&L (typically called at start of day)
+    /// Reset daily P&L
+    /// Sessions roll over automatically; this clears the history and the open
+    /// session is reopened at the next trade or session P&L query.
     pub async fn reset_daily_pnl(&self) {
-        let mut daily_pnl = self.daily_pnl.write().await;
-        daily_pnl.clear();
+        self.daily_pnl.write().await.clear();
+        self.sessions.write().await.clear();
+        self.persist().await;
     }
 
     /// Get position statistics
@@ -442,41 +1105,14 @@ impl ShadowLedger {
         }
     }
 
-    /// Get trade statistics
+    /// Get trade statistics over every trade booked
     pub async fn get_trade_stats(&self) -> TradeStats {
-        let trades = self.trades.read().await;
-
-        let total_trades = trades.len();
-        let mut buy_trades = 0;
-        let mut sell_trades = 0;
-        let mut total_volume = Size::new(rust_decimal::Decimal::ZERO);
-        let mut total_value = rust_decimal::Decimal::ZERO;
-        let mut total_fees = Size::new(rust_decimal::Decimal::ZERO);
-
-        for trade in trades.iter() {
-            match trade.side {
-                OrderSide::Buy => buy_trades += 1,
-                OrderSide::Sell => sell_trades += 1,
-            }
-
-            total_volume = total_volume + trade.quantity;
-            total_value += trade.value();
-            total_fees = total_fees + trade.fee;
-        }
-
-        TradeStats {
-            total_trades,
-            buy_trades,
-            sell_trades,
-            total_volume,
-            total_value,
-            total_fees,
-        }
+        self.trade_stats.read().await.clone()
     }
 
     /// Record historical P&L snapshot
     pub async fn record_historical_pnl(&self, market_prices: &HashMap<String, Price>) {
-        let today = Utc::now().format("%Y-%m-%d").to_string();
+        let today = self.clock.now_utc().format("%Y-%m-%d").to_string();
         let realized_pnl = self.get_total_realized_pnl().await;
         let unrealized_pnl = self.get_total_unrealized_pnl(market_prices).await;
         let total_pnl = realized_pnl + unrealized_pnl;
@@ -502,8 +1138,8 @@ impl ShadowLedger {
             peak_equity,
         };
 
-        let mut historical_pnl = self.historical_pnl.write().await;
-        historical_pnl.push(historical);
+        self.historical_pnl.write().await.push(historical);
+        self.persist().await;
     }
 
     /// Get historical P&L records
@@ -637,17 +1273,52 @@ impl ShadowLedger {
     }
 
     /// Get trades within a time range
+    /// A range reaching past the trades kept in memory is read from the store, plus any
+    /// trades not saved yet; if the store fails, only the trades in memory are returned.
     pub async fn get_trades_in_range(
         &self,
         start: DateTime<Utc>,
         end: DateTime<Utc>,
     ) -> Vec<TradeRecord> {
-        let trades = self.trades.read().await;
-        trades
-            .iter()
-            .filter(|trade| trade.timestamp >= start && trade.timestamp <= end)
-            .cloned()
-            .collect()
+        let (recent, evicted) = {
+            let trades = self.trades.read().await;
+            let recent: Vec<TradeRecord> = trades
+                .iter()
+                .filter(|trade| trade.timestamp >= start && trade.timestamp <= end)
+                .cloned()
+                .collect();
+            let evicted = trades.len() >= self.trade_retention
+                && trades
+                    .front()
+                    .is_some_and(|oldest| oldest.timestamp >= start);
+            (recent, evicted)
+        };
+        let Some(store) = self.store.clone().filter(|_| evicted) else {
+            return recent;
+        };
+
+        let saved = tokio::task::spawn_blocking(move || store.load_trades(start, end))
+            .await
+            .map_err(io::Error::other)
+            .and_then(|result| result);
+        match saved {
+            Ok(mut trades) => {
+                let saved_ids: HashSet<(String, String)> = trades
+                    .iter()
+                    .map(|t| (t.exchange_id.clone(), t.trade_id.clone()))
+                    .collect();
+                trades.extend(
+                    recent.into_iter().filter(|t| {
+                        !saved_ids.contains(&(t.exchange_id.clone(), t.trade_id.clone()))
+                    }),
+                );
+                trades
+            }
+            Err(e) => {
+                warn!("Failed to read older trades from the ledger store: {}", e);
+                recent
+            }
+        }
     }
 
     /// Get positions by exchange
@@ -667,8 +1338,8 @@ impl ShadowLedger {
 
     /// Reset peak equity (typically called at start of new period)
     pub async fn reset_peak_equity(&self) {
-        let mut peak = self.peak_equity.write().await;
-        *peak = rust_decimal::Decimal::ZERO;
+        *self.peak_equity.write().await = rust_decimal::Decimal::ZERO;
+        self.persist().await;
     }
 }
 
@@ -686,7 +1357,7 @@ pub struct PositionStats {
 }
 
 /// Trade statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TradeStats {
     /// Total number of trades
     pub total_trades: usize,
@@ -698,10 +1369,37 @@ pub struct TradeStats {
     pub total_volume: Size,
     /// Total value traded
     pub total_value: rust_decimal::Decimal,
-    /// Total fees paid
+    /// Total fees paid, net of rebates
     pub total_fees: Size,
 }
 
+impl Default for TradeStats {
+    fn default() -> Self {
+        Self {
+            total_trades: 0,
+            buy_trades: 0,
+            sell_trades: 0,
+            total_volume: Size::zero(),
+            total_value: rust_decimal::Decimal::ZERO,
+            total_fees: Size::zero(),
+        }
+    }
+}
+
+impl TradeStats {
+    /// Add a trade to the totals
+    fn record(&mut self, trade: &TradeRecord) {
+        self.total_trades += 1;
+        match trade.side {
+            OrderSide::Buy => self.buy_trades += 1,
+            OrderSide::Sell => self.sell_trades += 1,
+        }
+        self.total_volume = self.total_volume + trade.quantity;
+        self.total_value += trade.value();
+        self.total_fees = self.total_fees + trade.fee;
+    }
+}
+
 /// Historical P&L record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoricalPnL {
@@ -737,6 +1435,7 @@ pub struct RiskMetrics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::risk::ledger_store::SqliteLedgerStore;
     use chrono::Utc;
     use std::str::FromStr;
 
@@ -837,8 +1536,8 @@ mod tests {
         assert_eq!(position.size, Size::from_str("0.7").unwrap()); // 1.0 - 0.3
         assert_eq!(
             position.realized_pnl,
-            rust_decimal::Decimal::from_str("299.9997").unwrap()
-        ); // (51000 - 50000.001) * 0.3
+            rust_decimal::Decimal::from_str("299.9987").unwrap()
+        ); // (51000 - 50000.001) * 0.3 - 0.001
     }
 
     #[test]
@@ -940,6 +1639,7 @@ mod tests {
             remaining_size: Size::from_str("0.0").unwrap(),
             average_price: Some(Price::from_str("50000.0").unwrap()),
             timestamp: Utc::now().timestamp_millis() as u64,
+            reject_reason: None,
         };
 
         // Process the execution report
@@ -1058,4 +1758,576 @@ mod tests {
         ); // 3 * 50000
         assert_eq!(trade_stats.total_fees, Size::from_str("0.003").unwrap()); // 3 * 0.001
     }
+
+    #[tokio::test]
+    async fn test_session_rollover_carries_unrealized_pnl() {
+        use crate::risk::session::WeekendPolicy;
+        use chrono::NaiveTime;
+        use chrono_tz::America::New_York;
+
+        // FX-style calendar: 17:00 New York rollover, weekend booked to Monday
+        let config = SessionConfig::new(NaiveTime::from_hms_opt(17, 0, 0).unwrap(), New_York)
+            .with_weekend_policy(WeekendPolicy::MergeIntoMonday);
+        let ledger = ShadowLedger::new().with_session_config(config);
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let trade = |id: &str, side, price: &str, timestamp| {
+            TradeRecord::new(
+                id.to_string(),
+                Symbol::new("BTCUSDT"),
+                "binance".to_string(),
+                format!("order_{}", id),
+                side,
+                Size::from_str("1.0").unwrap(),
+                Price::from_str(price).unwrap(),
+                timestamp,
+                Size::from_str("0").unwrap(),
+                "USDT".to_string(),
+            )
+        };
+
+        // Friday 2024-03-08 before the 17:00 EST (22:00 UTC) rollover
+        ledger
+            .add_trade(trade(
+                "1",
+                OrderSide::Buy,
+                "100",
+                at("2024-03-08T20:00:00Z"),
+            ))
+            .await;
+        ledger
+            .add_trade(trade(
+                "2",
+                OrderSide::Buy,
+                "110",
+                at("2024-03-08T21:30:00Z"),
+            ))
+            .await;
+
+        // The first trade after the rollover opens Monday's session, carrying
+        // forward the long of 2 @ 105 marked at the last price (110)
+        ledger
+            .add_trade(trade(
+                "3",
+                OrderSide::Sell,
+                "120",
+                at("2024-03-08T22:30:00Z"),
+            ))
+            .await;
+        let friday = ledger.get_session(day(8)).await.unwrap();
+        assert!(friday.is_closed());
+        assert_eq!(
+            friday.closing_unrealized_pnl,
+            Some(rust_decimal::Decimal::new(10, 0))
+        );
+        let monday = ledger.get_session(day(11)).await.unwrap();
+        assert_eq!(
+            monday.opening_unrealized_pnl,
+            rust_decimal::Decimal::new(10, 0)
+        );
+        assert_eq!(monday.realized_pnl, rust_decimal::Decimal::new(15, 0));
+        assert_eq!(
+            ledger.get_daily_pnl("2024-03-11").await,
+            rust_decimal::Decimal::new(15, 0)
+        );
+
+        // Sunday is still Monday's session: long of 1 @ 105 marked at 125 is
+        // unrealized 20, up 10 since the open
+        let mut marks = HashMap::new();
+        marks.insert("BTCUSDT".to_string(), Price::from_str("125").unwrap());
+        assert_eq!(
+            ledger
+                .get_session_pnl(at("2024-03-10T12:00:00Z"), &marks)
+                .await,
+            rust_decimal::Decimal::new(25, 0)
+        );
+
+        // Monday 17:00 EDT (21:00 UTC after the DST change) rolls into Tuesday
+        assert_eq!(
+            ledger
+                .get_session_pnl(at("2024-03-11T21:00:00Z"), &marks)
+                .await,
+            rust_decimal::Decimal::ZERO
+        );
+        let sessions = ledger.get_sessions().await;
+        assert_eq!(sessions.len(), 3);
+        assert_eq!(sessions[2].session_date, day(12));
+        assert_eq!(
+            sessions[1].closing_unrealized_pnl,
+            Some(rust_decimal::Decimal::new(20, 0))
+        );
+        assert_eq!(
+            sessions[2].opening_unrealized_pnl,
+            rust_decimal::Decimal::new(20, 0)
+        );
+
+        // Out-of-order timestamps never reopen a past session
+        assert!(ledger
+            .roll_session(at("2024-03-08T12:00:00Z"), &marks)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_carry_costs_accrue_into_cost_basis() {
+        let ledger = ShadowLedger::new();
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        ledger
+            .add_trade(TradeRecord::new(
+                "1".to_string(),
+                Symbol::new("BTCUSDT"),
+                "binance".to_string(),
+                "order_1".to_string(),
+                OrderSide::Buy,
+                Size::from_str("2").unwrap(),
+                Price::from_str("100").unwrap(),
+                at("2024-03-08T00:00:00Z"),
+                Size::from_str("0").unwrap(),
+                "USDT".to_string(),
+            ))
+            .await;
+
+        // 0.01% funding on 2 @ 110 costs the long 0.022
+        let funding = FundingRate {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            rate: rust_decimal::Decimal::from_str("0.0001").unwrap(),
+            predicted_rate: None,
+            next_funding_time: 0,
+            interval_hours: 8,
+            timestamp: 0,
+        };
+        assert_eq!(
+            ledger
+                .apply_funding(&funding, Price::from_str("110").unwrap())
+                .await,
+            Some(rust_decimal::Decimal::from_str("0.022").unwrap())
+        );
+
+        // Borrowing the 200.022 USDT cost basis for 6 hours at 0.001% per hour
+        let borrow = |asset: &str| BorrowRate {
+            asset: asset.to_string(),
+            exchange_id: "binance".to_string(),
+            hourly_rate: rust_decimal::Decimal::from_str("0.00001").unwrap(),
+            timestamp: 0,
+        };
+        assert_eq!(
+            ledger
+                .accrue_borrow_interest(
+                    "BTCUSDT",
+                    "binance",
+                    &borrow("BTC"),
+                    at("2024-03-08T06:00:00Z")
+                )
+                .await,
+            None
+        );
+        let interest = ledger
+            .accrue_borrow_interest(
+                "BTCUSDT",
+                "binance",
+                &borrow("USDT"),
+                at("2024-03-08T06:00:00Z"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            interest,
+            rust_decimal::Decimal::from_str("0.01200132").unwrap()
+        );
+        assert_eq!(
+            ledger
+                .accrue_borrow_interest(
+                    "BTCUSDT",
+                    "binance",
+                    &borrow("USDT"),
+                    at("2024-03-08T06:00:00Z")
+                )
+                .await,
+            None
+        );
+
+        let position = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+        let carry = rust_decimal::Decimal::from_str("0.03400132").unwrap();
+        assert_eq!(position.carry_cost, carry);
+        assert_eq!(ledger.get_total_carry_cost().await, position.carry_cost);
+
+        // Selling flat at 110 realizes the price gain less the carry
+        ledger
+            .add_trade(TradeRecord::new(
+                "2".to_string(),
+                Symbol::new("BTCUSDT"),
+                "binance".to_string(),
+                "order_2".to_string(),
+                OrderSide::Sell,
+                Size::from_str("2").unwrap(),
+                Price::from_str("110").unwrap(),
+                at("2024-03-08T07:00:00Z"),
+                Size::from_str("0").unwrap(),
+                "USDT".to_string(),
+            ))
+            .await;
+        let position = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+        let expected = rust_decimal::Decimal::from(20) - carry;
+        assert_eq!(position.realized_pnl, expected);
+        assert_eq!(position.accrued_until, None);
+    }
+
+    #[tokio::test]
+    async fn test_ledger_survives_restart() {
+        let path = std::env::temp_dir().join(format!("ledger_{}.db", uuid::Uuid::new_v4()));
+        let store: Arc<dyn LedgerStore> = Arc::new(SqliteLedgerStore::open(&path).unwrap());
+
+        let ledger = ShadowLedger::new().with_store(store.clone());
+        assert!(!ledger.load_from_store().await.unwrap());
+        for (id, side, price) in [("1", OrderSide::Buy, "100"), ("2", OrderSide::Sell, "110")] {
+            ledger
+                .add_trade(TradeRecord::new(
+                    id.to_string(),
+                    Symbol::new("BTCUSDT"),
+                    "binance".to_string(),
+                    format!("order_{}", id),
+                    side,
+                    Size::from_str("1").unwrap(),
+                    Price::from_str(price).unwrap(),
+                    Utc::now(),
+                    Size::from_str("0").unwrap(),
+                    "USDT".to_string(),
+                ))
+                .await;
+        }
+        ledger
+            .seed_position(PositionRecord {
+                size: Size::from_str("2").unwrap(),
+                average_price: Some(Price::from_str("10").unwrap()),
+                ..PositionRecord::new(Symbol::new("ETHUSDT"), "okx".to_string())
+            })
+            .await;
+        ledger.record_historical_pnl(&HashMap::new()).await;
+        ledger.flush().await.unwrap();
+
+        let restarted = ShadowLedger::new().with_store(store);
+        assert!(restarted.load_from_store().await.unwrap());
+        assert_eq!(restarted.get_all_trades().await.len(), 2);
+        assert_eq!(
+            restarted.get_total_realized_pnl().await,
+            ledger.get_total_realized_pnl().await
+        );
+        let eth = restarted.get_position("ETHUSDT", "okx").await.unwrap();
+        assert_eq!(eth.size, Size::from_str("2").unwrap());
+        assert_eq!(restarted.get_sessions().await, ledger.get_sessions().await);
+        assert_eq!(restarted.get_historical_pnl().await.len(), 1);
+        assert_eq!(
+            restarted.get_peak_equity().await,
+            ledger.get_peak_equity().await
+        );
+        assert_eq!(
+            restarted.get_last_prices().await,
+            ledger.get_last_prices().await
+        );
+
+        remove_database(&path);
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_saved_in_the_background() {
+        let path = std::env::temp_dir().join(format!("ledger_{}.db", uuid::Uuid::new_v4()));
+        let ledger = ShadowLedger::new()
+            .with_store(Arc::new(SqliteLedgerStore::open(&path).unwrap()))
+            .with_save_delay(std::time::Duration::from_millis(10));
+        for id in ["1", "2"] {
+            ledger
+                .add_trade(TradeRecord::new(
+                    id.to_string(),
+                    Symbol::new("BTCUSDT"),
+                    "binance".to_string(),
+                    format!("order_{}", id),
+                    OrderSide::Buy,
+                    Size::from_str("1").unwrap(),
+                    Price::from_str("100").unwrap(),
+                    Utc::now(),
+                    Size::from_str("0").unwrap(),
+                    "USDT".to_string(),
+                ))
+                .await;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        let saved = SqliteLedgerStore::open(&path)
+            .unwrap()
+            .load(10, 10)
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.trades.len(), 2);
+        assert_eq!(saved.state.positions.len(), 1);
+
+        remove_database(&path);
+    }
+
+    #[tokio::test]
+    async fn test_older_trades_are_read_from_the_store() {
+        let path = std::env::temp_dir().join(format!("ledger_{}.db", uuid::Uuid::new_v4()));
+        let store: Arc<dyn LedgerStore> = Arc::new(SqliteLedgerStore::open(&path).unwrap());
+        let ledger = ShadowLedger::new()
+            .with_store(store.clone())
+            .with_trade_retention(2)
+            .with_save_delay(std::time::Duration::from_secs(60));
+        let start = Utc::now();
+        let at = |minutes: i64| start + chrono::Duration::minutes(minutes);
+        for (id, minutes) in [("1", 0), ("2", 1)] {
+            ledger.add_trade(trade_at(id, at(minutes))).await;
+        }
+        ledger.flush().await.unwrap();
+        // Not saved yet, but still found alongside the saved ones
+        ledger.add_trade(trade_at("3", at(2))).await;
+
+        let ids = |trades: Vec<TradeRecord>| -> Vec<String> {
+            trades.into_iter().map(|t| t.trade_id).collect()
+        };
+        assert_eq!(ids(ledger.get_all_trades().await), ["2", "3"]);
+        assert_eq!(
+            ids(ledger.get_trades_in_range(at(0), at(2)).await),
+            ["1", "2", "3"]
+        );
+        assert_eq!(
+            ids(ledger.get_trades_in_range(at(1), at(2)).await),
+            ["2", "3"]
+        );
+        assert_eq!(ledger.get_trade_stats().await.total_trades, 3);
+
+        // A restart loads only the window, with the totals of every trade
+        ledger.flush().await.unwrap();
+        let restarted = ShadowLedger::new()
+            .with_store(store)
+            .with_trade_retention(2);
+        assert!(restarted.load_from_store().await.unwrap());
+        assert_eq!(ids(restarted.get_all_trades().await), ["2", "3"]);
+        assert_eq!(
+            restarted.get_trade_stats().await,
+            ledger.get_trade_stats().await
+        );
+        assert_eq!(
+            ids(restarted.get_trades_in_range(at(0), at(2)).await),
+            ["1", "2", "3"]
+        );
+
+        remove_database(&path);
+    }
+
+    fn trade_at(id: &str, timestamp: DateTime<Utc>) -> TradeRecord {
+        TradeRecord::new(
+            id.to_string(),
+            Symbol::new("BTCUSDT"),
+            "binance".to_string(),
+            format!("order_{}", id),
+            OrderSide::Buy,
+            Size::from_str("1").unwrap(),
+            Price::from_str("100").unwrap(),
+            timestamp,
+            Size::from_str("0").unwrap(),
+            "USDT".to_string(),
+        )
+    }
+
+    fn remove_database(path: &std::path::Path) {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut file = path.to_path_buf().into_os_string();
+            file.push(suffix);
+            let _ = std::fs::remove_file(file);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fees_in_other_assets_are_converted() {
+        let ledger = ShadowLedger::new();
+        ledger
+            .update_price("BNBUSDT", Price::from_str("500").unwrap())
+            .await;
+        for (id, side, price, fee, fee_asset) in [
+            ("1", OrderSide::Buy, "100", "0.02", "BNB"),
+            ("2", OrderSide::Sell, "120", "0.001", "BTC"),
+        ] {
+            ledger
+                .add_trade(TradeRecord::new(
+                    id.to_string(),
+                    Symbol::new("BTCUSDT"),
+                    "binance".to_string(),
+                    format!("order_{}", id),
+                    side,
+                    Size::from_str("1").unwrap(),
+                    Price::from_str(price).unwrap(),
+                    Utc::now(),
+                    Size::from_str(fee).unwrap(),
+                    fee_asset.to_string(),
+                ))
+                .await;
+            if side == OrderSide::Buy {
+                // 0.02 BNB at 500 adds 10 USDT to the cost basis
+                let position = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+                assert_eq!(
+                    position.average_price,
+                    Some(Price::from_str("110").unwrap())
+                );
+            }
+        }
+
+        // 0.001 BTC at the 120 fill price is 0.12 USDT off the 10 gained
+        let position = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+        assert_eq!(
+            position.realized_pnl,
+            rust_decimal::Decimal::from_str("9.88").unwrap()
+        );
+        let trades = ledger.get_all_trades().await;
+        assert_eq!(
+            trades[0].fee_in_quote,
+            Some(rust_decimal::Decimal::from(10))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cost_basis_methods() {
+        let trade = |id: &str, side: OrderSide, size: &str, price: &str| {
+            TradeRecord::new(
+                id.to_string(),
+                Symbol::new("BTCUSDT"),
+                "binance".to_string(),
+                format!("order_{}", id),
+                side,
+                Size::from_str(size).unwrap(),
+                Price::from_str(price).unwrap(),
+                Utc::now(),
+                Size::from_str("0").unwrap(),
+                "USDT".to_string(),
+            )
+        };
+        let dec = |s: &str| rust_decimal::Decimal::from_str(s).unwrap();
+
+        // Buy 1 at 100 and 1 at 110, then sell 1.5 at 120
+        let mut realized = Vec::new();
+        for method in [
+            CostBasisMethod::AverageCost,
+            CostBasisMethod::Fifo,
+            CostBasisMethod::Lifo,
+        ] {
+            let ledger = ShadowLedger::new();
+            ledger.set_cost_basis_method(method).await;
+            ledger
+                .add_trade(trade("1", OrderSide::Buy, "1", "100"))
+                .await;
+            ledger
+                .add_trade(trade("2", OrderSide::Buy, "1", "110"))
+                .await;
+            ledger
+                .add_trade(trade("3", OrderSide::Sell, "1.5", "120"))
+                .await;
+            let position = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+            realized.push((
+                position.realized_pnl,
+                position.average_price.unwrap(),
+                ledger.get_closed_lots().await.len(),
+            ));
+        }
+        assert_eq!(
+            realized,
+            vec![
+                // 1.5 at the 105 average
+                (dec("22.5"), Price::from_str("105").unwrap(), 2),
+                // 1 at 100 and 0.5 at 110, leaving 0.5 at 110
+                (dec("25"), Price::from_str("110").unwrap(), 2),
+                // 1 at 110 and 0.5 at 100, leaving 0.5 at 100
+                (dec("20"), Price::from_str("100").unwrap(), 2),
+            ]
+        );
+
+        // Switching method picks up the lots already open
+        let ledger = ShadowLedger::new();
+        ledger
+            .add_trade(trade("1", OrderSide::Buy, "1", "100"))
+            .await;
+        ledger
+            .add_trade(trade("2", OrderSide::Buy, "1", "110"))
+            .await;
+        ledger.set_cost_basis_method(CostBasisMethod::Lifo).await;
+        ledger
+            .add_trade(trade("3", OrderSide::Sell, "1", "120"))
+            .await;
+        let lots = ledger.get_closed_lots().await;
+        assert_eq!(lots[0].entry_price, Price::from_str("110").unwrap());
+        assert_eq!(lots[0].realized_pnl, dec("10"));
+        let position = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+        assert_eq!(position.total_cost, dec("100"));
+
+        // Only the most recent closed lots are kept
+        let ledger = ShadowLedger::new().with_closed_lot_retention(2);
+        ledger.set_cost_basis_method(CostBasisMethod::Fifo).await;
+        for (id, price) in [("1", "100"), ("2", "110"), ("3", "120")] {
+            ledger
+                .add_trade(trade(id, OrderSide::Buy, "1", price))
+                .await;
+        }
+        ledger
+            .add_trade(trade("4", OrderSide::Sell, "3", "130"))
+            .await;
+        let lots = ledger.get_closed_lots().await;
+        assert_eq!(lots.len(), 2);
+        assert_eq!(lots[0].entry_price, Price::from_str("110").unwrap());
+        assert_eq!(lots[1].entry_price, Price::from_str("120").unwrap());
+        assert_eq!(ledger.snapshot().await.closed_lots.len(), 2);
+        // Realized P&L still counts every lot
+        let position = ledger.get_position("BTCUSDT", "binance").await.unwrap();
+        assert_eq!(position.realized_pnl, dec("60"));
+    }
+
+    #[test]
+    fn test_short_and_flip() {
+        let trade = |side: OrderSide, size: &str, price: &str, fee: &str| {
+            TradeRecord::new(
+                "t".to_string(),
+                Symbol::new("BTCUSDT"),
+                "binance".to_string(),
+                "o".to_string(),
+                side,
+                Size::from_str(size).unwrap(),
+                Price::from_str(price).unwrap(),
+                Utc::now(),
+                Size::from_str(fee).unwrap(),
+                "USDT".to_string(),
+            )
+        };
+        let dec = |s: &str| rust_decimal::Decimal::from_str(s).unwrap();
+        let mut position = PositionRecord::new(Symbol::new("BTCUSDT"), "binance".to_string());
+
+        // Selling from flat opens a short
+        assert!(position
+            .apply_trade(&trade(OrderSide::Sell, "2", "100", "0"))
+            .is_empty());
+        assert_eq!(position.size, Size::from_str("-2").unwrap());
+        assert_eq!(
+            position.average_price,
+            Some(Price::from_str("100").unwrap())
+        );
+        assert_eq!(
+            position.unrealized_pnl(Price::from_str("90").unwrap()),
+            Some(dec("20"))
+        );
+
+        // Buying 3 closes the short for 20 less two thirds of the fee and opens a long
+        // of 1 carrying the last third
+        let closed = position.apply_trade(&trade(OrderSide::Buy, "3", "90", "0.3"));
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].quantity, Size::from_str("2").unwrap());
+        assert_eq!(position.realized_pnl, dec("19.8"));
+        assert_eq!(position.size, Size::from_str("1").unwrap());
+        assert_eq!(
+            position.average_price,
+            Some(Price::from_str("90.1").unwrap())
+        );
+        assert_eq!(position.total_cost, dec("90.1"));
+        assert!(position.accrued_until.is_some());
+
+        position.apply_trade(&trade(OrderSide::Sell, "1", "95", "0"));
+        assert!(position.size.is_zero());
+        assert_eq!(position.realized_pnl, dec("24.7"));
+        assert!(position.lots.is_empty());
+    }
 }
@@ -1,6 +1,16 @@
+use super::providers::KeyProvider;
+use super::rotation::{
+    ApiCredentials, ApiKeyStatus, KeyRevoker, RotationReport, SharedCredentials,
+};
+use std::collections::HashMap;
 use std::env;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default time a rotation waits for requests signed with the old key
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Secure API key wrapper that prevents accidental logging
 #[derive(Clone)]
@@ -101,18 +111,51 @@ impl FromStr for SecureApiKey {
 }
 
 /// API Key Manager for managing multiple API keys
+///
+/// Exchange accounts registered with `register_account` can be rotated at runtime:
+/// `rotate` switches every adapter sharing the account's credentials to the new pair,
+/// waits for requests signed with the old key, then revokes it.
 pub struct ApiKeyManager {
-    keys: std::collections::HashMap<String, SecureApiKey>,
+    keys: HashMap<String, SecureApiKey>,
+    accounts: HashMap<String, SharedCredentials>,
+    provider: Option<Arc<dyn KeyProvider>>,
+    revoker: Option<Arc<dyn KeyRevoker>>,
+    drain_timeout: Duration,
+    /// One rotation at a time, so drains and revocations do not interleave
+    rotation_lock: tokio::sync::Mutex<()>,
 }
 
 impl ApiKeyManager {
     /// Create a new API key manager
     pub fn new() -> Self {
         Self {
-            keys: std::collections::HashMap::new(),
+            keys: HashMap::new(),
+            accounts: HashMap::new(),
+            provider: None,
+            revoker: None,
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            rotation_lock: tokio::sync::Mutex::new(()),
         }
     }
 
+    /// Look up rotated credentials by secret name (builder pattern)
+    pub fn with_key_provider(mut self, provider: Arc<dyn KeyProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Revoke retired keys once drained (builder pattern)
+    pub fn with_revoker(mut self, revoker: Arc<dyn KeyRevoker>) -> Self {
+        self.revoker = Some(revoker);
+        self
+    }
+
+    /// Set how long a rotation waits for in-flight requests (builder pattern)
+    pub fn with_drain_timeout(mut self, drain_timeout: Duration) -> Self {
+        self.drain_timeout = drain_timeout;
+        self
+    }
+
     /// Add an API key
     pub fn add_key(&mut self, name: String, key: SecureApiKey) -> Result<(), ApiKeyError> {
         key.validate()?;
@@ -134,6 +177,137 @@ impl ApiKeyManager {
         Ok(())
     }
 
+    /// Load API keys by secret name from a key provider
+    pub async fn load_from_provider(
+        &mut self,
+        provider: &dyn KeyProvider,
+        mappings: &[(&str, &str)],
+    ) -> Result<(), ApiKeyError> {
+        for (name, secret_name) in mappings {
+            let key = provider.get(secret_name).await?;
+            self.add_key(name.to_string(), key)?;
+        }
+        Ok(())
+    }
+
+    /// Register an exchange account's credentials
+    /// Returns the handle to give the account's adapters, e.g. `BinanceClient::with_credentials`.
+    pub fn register_account(
+        &mut self,
+        account: &str,
+        credentials: ApiCredentials,
+    ) -> Result<SharedCredentials, ApiKeyError> {
+        credentials.validate()?;
+        let shared = SharedCredentials::new(credentials);
+        self.accounts.insert(account.to_string(), shared.clone());
+        Ok(shared)
+    }
+
+    /// Get an account's credentials handle
+    pub fn account_credentials(&self, account: &str) -> Option<SharedCredentials> {
+        self.accounts.get(account).cloned()
+    }
+
+    /// Get the status of every registered account, sorted by name
+    pub fn account_status(&self) -> Vec<ApiKeyStatus> {
+        let mut status: Vec<ApiKeyStatus> = self
+            .accounts
+            .iter()
+            .map(|(account, credentials)| ApiKeyStatus {
+                account: account.clone(),
+                api_key: credentials.api_key().mask(),
+                generation: credentials.generation(),
+                in_flight: credentials.in_flight(),
+            })
+            .collect();
+        status.sort_by(|a, b| a.account.cmp(&b.account));
+        status
+    }
+
+    /// Switch an account to new credentials, drain the old ones and revoke them
+    /// If requests are still in flight after the drain timeout the old key is not revoked.
+    pub async fn rotate(
+        &self,
+        account: &str,
+        credentials: ApiCredentials,
+    ) -> Result<RotationReport, ApiKeyError> {
+        let shared = self
+            .accounts
+            .get(account)
+            .ok_or_else(|| ApiKeyError::UnknownAccount(account.to_string()))?;
+        credentials.validate()?;
+        let _rotation = self.rotation_lock.lock().await;
+        if credentials.api_key.expose() == shared.api_key().expose() {
+            return Err(ApiKeyError::Rotation(format!(
+                "new key for {} is the current key",
+                account
+            )));
+        }
+
+        let new_key = credentials.api_key.mask();
+        let retired = shared.swap(credentials);
+        log::info!(
+            "Rotated API key of {} from {} to {} (generation {})",
+            account,
+            retired.api_key().mask(),
+            new_key,
+            retired.generation() + 1
+        );
+
+        let drained = retired.drain(self.drain_timeout).await;
+        let mut revoked = false;
+        let mut revoke_error = None;
+        if !drained {
+            log::warn!(
+                "{} requests still use the retired key of {}; not revoking it",
+                retired.in_flight(),
+                account
+            );
+            revoke_error = Some(format!(
+                "{} requests in flight after {:?}",
+                retired.in_flight(),
+                self.drain_timeout
+            ));
+        } else if let Some(revoker) = &self.revoker {
+            match revoker.revoke(account, retired.api_key()).await {
+                Ok(()) => revoked = true,
+                Err(e) => {
+                    log::error!("Failed to revoke retired key of {}: {}", account, e);
+                    revoke_error = Some(e.to_string());
+                }
+            }
+        }
+
+        Ok(RotationReport {
+            account: account.to_string(),
+            generation: retired.generation() + 1,
+            previous_key: retired.api_key().mask(),
+            new_key,
+            drained,
+            revoked,
+            revoke_error,
+        })
+    }
+
+    /// Rotate an account to a key and secret looked up by name in the key provider
+    /// Keeps the new secret out of the control API's requests and logs.
+    pub async fn rotate_from_provider(
+        &self,
+        account: &str,
+        api_key_name: &str,
+        api_secret_name: &str,
+    ) -> Result<RotationReport, ApiKeyError> {
+        let provider = self
+            .provider
+            .as_ref()
+            .ok_or_else(|| ApiKeyError::Rotation("no key provider configured".to_string()))?;
+        let credentials = ApiCredentials {
+            api_key: provider.get(api_key_name).await?,
+            api_secret: provider.get(api_secret_name).await?,
+        };
+        self.rotate(account, credentials).await
+    }
+
     /// Validate all keys
     pub fn validate_all(&self) -> Result<(), Vec<ApiKeyError>> {
         let mut errors = Vec::new();
@@ -164,6 +338,16 @@ pub enum ApiKeyError {
     InvalidDemoKey(String),
     TooShort,
     ValidationFailed(String, Box<ApiKeyError>),
+    /// No provider holds a secret with this name
+    NotFound(String),
+    /// A secret backend failed: unreadable file, Vault error, ...
+    Provider(String),
+    /// Encryption or decryption failed
+    Crypto(String),
+    /// No exchange account is registered under this name
+    UnknownAccount(String),
+    /// A key rotation was refused
+    Rotation(String),
 }
 
 impl fmt::Display for ApiKeyError {
@@ -184,6 +368,11 @@ impl fmt::Display for ApiKeyError {
             ApiKeyError::ValidationFailed(name, err) => {
                 write!(f, "Validation failed for key '{}': {}", name, err)
             }
+            ApiKeyError::NotFound(name) => write!(f, "Secret '{}' not found", name),
+            ApiKeyError::Provider(message) => write!(f, "Secret provider error: {}", message),
+            ApiKeyError::Crypto(message) => write!(f, "Secret store crypto error: {}", message),
+            ApiKeyError::UnknownAccount(account) => write!(f, "Unknown account '{}'", account),
+            ApiKeyError::Rotation(message) => write!(f, "Key rotation refused: {}", message),
         }
     }
 }
@@ -227,4 +416,84 @@ mod tests {
         assert!(manager.add_key("test".to_string(), key).is_ok());
         assert!(manager.get_key("test").is_some());
     }
+
+    struct RecordingRevoker {
+        revoked: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl KeyRevoker for RecordingRevoker {
+        async fn revoke(&self, account: &str, api_key: &SecureApiKey) -> Result<(), ApiKeyError> {
+            self.revoked
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", account, api_key.expose()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_key_rotation() {
+        let revoker = Arc::new(RecordingRevoker {
+            revoked: std::sync::Mutex::new(Vec::new()),
+        });
+        let mut manager = ApiKeyManager::new()
+            .with_revoker(revoker.clone())
+            .with_drain_timeout(Duration::from_millis(20));
+        let credentials = manager
+            .register_account(
+                "binance",
+                ApiCredentials::new("a".repeat(16), "b".repeat(16)),
+            )
+            .unwrap();
+
+        // A request in flight blocks revocation but not the switch
+        let lease = credentials.lease();
+        let report = manager
+            .rotate(
+                "binance",
+                ApiCredentials::new("c".repeat(16), "d".repeat(16)),
+            )
+            .await
+            .unwrap();
+        assert!(!report.drained && !report.revoked);
+        assert_eq!(credentials.lease().api_key(), "c".repeat(16));
+        drop(lease);
+
+        let report = manager
+            .rotate(
+                "binance",
+                ApiCredentials::new("e".repeat(16), "f".repeat(16)),
+            )
+            .await
+            .unwrap();
+        assert!(report.drained && report.revoked);
+        assert_eq!(report.generation, 3);
+        assert_eq!(report.previous_key, "cccc...cccc");
+        assert_eq!(
+            *revoker.revoked.lock().unwrap(),
+            vec![format!("binance:{}", "c".repeat(16))]
+        );
+        assert_eq!(manager.account_status()[0].api_key, "eeee...eeee");
+
+        assert!(matches!(
+            manager
+                .rotate("okx", ApiCredentials::new("g".repeat(16), "h".repeat(16)))
+                .await,
+            Err(ApiKeyError::UnknownAccount(_))
+        ));
+        assert!(matches!(
+            manager
+                .rotate(
+                    "binance",
+                    ApiCredentials::new("e".repeat(16), "i".repeat(16))
+                )
+                .await,
+            Err(ApiKeyError::Rotation(_))
+        ));
+        assert!(manager
+            .rotate("binance", ApiCredentials::new("short", "j".repeat(16)))
+            .await
+            .is_err());
+    }
 }
@@ -1,4 +1,15 @@
 /// Security module for secure API key management and validation
 pub mod api_keys;
+pub mod providers;
+pub mod rotation;
+pub mod secret_store;
 
 pub use api_keys::{ApiKeyError, ApiKeyManager, SecureApiKey};
+pub use providers::{
+    EnvKeyProvider, FileKeyProvider, KeyProvider, KeyProviderChain, VaultKeyProvider,
+};
+pub use rotation::{
+    ApiCredentials, ApiKeyStatus, CredentialLease, KeyRevoker, RetiredCredentials, RotationReport,
+    SharedCredentials,
+};
+pub use secret_store::{EncryptedKeyStore, MasterKey};
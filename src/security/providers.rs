@@ -0,0 +1,323 @@
+use super::api_keys::{ApiKeyError, SecureApiKey};
+use super::secret_store::EncryptedKeyStore;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Source of secrets looked up by name
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// Name used in logs
+    fn name(&self) -> &str;
+
+    /// Get a secret, or `ApiKeyError::NotFound` if this provider has none by that name
+    async fn get(&self, name: &str) -> Result<SecureApiKey, ApiKeyError>;
+}
+
+/// Reads secrets from environment variables
+/// `binance.api_secret` with prefix `HFT_SECRET_` reads `HFT_SECRET_BINANCE_API_SECRET`.
+pub struct EnvKeyProvider {
+    prefix: String,
+}
+
+impl EnvKeyProvider {
+    /// Create a provider reading variables starting with `prefix`
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    /// Get the variable holding a secret
+    pub fn var_name(&self, name: &str) -> String {
+        let suffix: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}{}", self.prefix, suffix)
+    }
+}
+
+#[async_trait]
+impl KeyProvider for EnvKeyProvider {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    async fn get(&self, name: &str) -> Result<SecureApiKey, ApiKeyError> {
+        match std::env::var(self.var_name(name)) {
+            Ok(value) if !value.is_empty() => Ok(SecureApiKey::new(value)),
+            Ok(_) => Err(ApiKeyError::Empty(self.var_name(name))),
+            Err(_) => Err(ApiKeyError::NotFound(name.to_string())),
+        }
+    }
+}
+
+/// Reads secrets from a directory with one file per secret
+/// Matches Docker and Kubernetes secret mounts; surrounding whitespace is trimmed.
+pub struct FileKeyProvider {
+    dir: PathBuf,
+}
+
+impl FileKeyProvider {
+    /// Create a provider reading files in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl KeyProvider for FileKeyProvider {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    async fn get(&self, name: &str) -> Result<SecureApiKey, ApiKeyError> {
+        // Names must not escape the directory
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(ApiKeyError::NotFound(name.to_string()));
+        }
+        let path = self.dir.join(name);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(value) if !value.trim().is_empty() => {
+                Ok(SecureApiKey::new(value.trim().to_string()))
+            }
+            Ok(_) => Err(ApiKeyError::Empty(path.display().to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(ApiKeyError::NotFound(name.to_string()))
+            }
+            Err(e) => Err(ApiKeyError::Provider(format!(
+                "failed to read {}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl KeyProvider for EncryptedKeyStore {
+    fn name(&self) -> &str {
+        "encrypted_store"
+    }
+
+    async fn get(&self, name: &str) -> Result<SecureApiKey, ApiKeyError> {
+        EncryptedKeyStore::get(self, name)
+    }
+}
+
+/// Reads secrets from a HashiCorp Vault KV version 2 secret
+/// Each secret name is a key of the secret's data, e.g. `api_secret` at `crypto-hft/binance`.
+pub struct VaultKeyProvider {
+    http_client: reqwest::Client,
+    addr: String,
+    token: SecureApiKey,
+    mount: String,
+    path: String,
+}
+
+impl VaultKeyProvider {
+    /// Create a provider reading `secret/data/{path}` on the server at `addr`
+    pub fn new(addr: &str, token: SecureApiKey, path: &str) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            addr: addr.trim_end_matches('/').to_string(),
+            token,
+            mount: "secret".to_string(),
+            path: path.trim_matches('/').to_string(),
+        }
+    }
+
+    /// Create a provider from `VAULT_ADDR` and `VAULT_TOKEN`
+    pub fn from_env(path: &str) -> Result<Self, ApiKeyError> {
+        let addr =
+            std::env::var("VAULT_ADDR").map_err(|_| ApiKeyError::Missing("VAULT_ADDR".into()))?;
+        let token = SecureApiKey::from_env("VAULT_TOKEN")?;
+        Ok(Self::new(&addr, token, path))
+    }
+
+    /// Use a KV engine mounted somewhere other than `secret` (builder pattern)
+    pub fn with_mount(mut self, mount: &str) -> Self {
+        self.mount = mount.trim_matches('/').to_string();
+        self
+    }
+
+    fn url(&self) -> String {
+        format!("{}/v1/{}/data/{}", self.addr, self.mount, self.path)
+    }
+}
+
+#[async_trait]
+impl KeyProvider for VaultKeyProvider {
+    fn name(&self) -> &str {
+        "vault"
+    }
+
+    async fn get(&self, name: &str) -> Result<SecureApiKey, ApiKeyError> {
+        let response = self
+            .http_client
+            .get(self.url())
+            .header("X-Vault-Token", self.token.expose())
+            .send()
+            .await
+            .map_err(|e| ApiKeyError::Provider(format!("vault request failed: {}", e)))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ApiKeyError::NotFound(name.to_string()));
+        }
+        if !response.status().is_success() {
+            return Err(ApiKeyError::Provider(format!(
+                "vault returned {} for {}",
+                response.status(),
+                self.path
+            )));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| ApiKeyError::Provider(format!("invalid vault response: {}", e)))?;
+        match body["data"]["data"][name].as_str() {
+            Some(value) if !value.is_empty() => Ok(SecureApiKey::new(value.to_string())),
+            Some(_) => Err(ApiKeyError::Empty(name.to_string())),
+            None => Err(ApiKeyError::NotFound(name.to_string())),
+        }
+    }
+}
+
+/// Tries providers in order, returning the first secret found
+/// Errors other than `NotFound` stop the search so a broken backend is not masked.
+#[derive(Default)]
+pub struct KeyProviderChain {
+    providers: Vec<Arc<dyn KeyProvider>>,
+}
+
+impl KeyProviderChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider after the existing ones (builder pattern)
+    pub fn with_provider(mut self, provider: Arc<dyn KeyProvider>) -> Self {
+        self.providers.push(provider);
+        self
+    }
+}
+
+#[async_trait]
+impl KeyProvider for KeyProviderChain {
+    fn name(&self) -> &str {
+        "chain"
+    }
+
+    async fn get(&self, name: &str) -> Result<SecureApiKey, ApiKeyError> {
+        for provider in &self.providers {
+            match provider.get(name).await {
+                Err(ApiKeyError::NotFound(_)) => continue,
+                Err(e) => {
+                    log::warn!(
+                        "Key provider {} failed for {}: {}",
+                        provider.name(),
+                        name,
+                        e
+                    );
+                    return Err(e);
+                }
+                found => return found,
+            }
+        }
+        Err(ApiKeyError::NotFound(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_env_and_file_providers() {
+        let env = EnvKeyProvider::new("HFT_TEST_SECRET_");
+        assert_eq!(
+            env.var_name("binance.api-key"),
+            "HFT_TEST_SECRET_BINANCE_API_KEY"
+        );
+        std::env::set_var("HFT_TEST_SECRET_OKX_API_KEY", "okx-key-from-env");
+
+        let dir = std::env::temp_dir().join(format!("secrets_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("binance.api_key"), "binance-key-from-file\n").unwrap();
+        let file = FileKeyProvider::new(&dir);
+        assert!(matches!(
+            file.get("../etc/passwd").await,
+            Err(ApiKeyError::NotFound(_))
+        ));
+
+        let chain = KeyProviderChain::new()
+            .with_provider(Arc::new(env))
+            .with_provider(Arc::new(file));
+        assert_eq!(
+            chain.get("okx.api_key").await.unwrap().expose(),
+            "okx-key-from-env"
+        );
+        assert_eq!(
+            chain.get("binance.api_key").await.unwrap().expose(),
+            "binance-key-from-file"
+        );
+        assert!(matches!(
+            chain.get("bybit.api_key").await,
+            Err(ApiKeyError::NotFound(_))
+        ));
+
+        std::env::remove_var("HFT_TEST_SECRET_OKX_API_KEY");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_vault_provider() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/kv/data/crypto-hft/binance"))
+            .and(header("X-Vault-Token", "vault-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "data": { "api_secret": "secret-from-vault" }, "metadata": {} }
+            })))
+            .mount(&server)
+            .await;
+
+        let vault = VaultKeyProvider::new(
+            &server.uri(),
+            SecureApiKey::new("vault-token".to_string()),
+            "crypto-hft/binance",
+        )
+        .with_mount("kv");
+        assert_eq!(
+            vault.get("api_secret").await.unwrap().expose(),
+            "secret-from-vault"
+        );
+        assert!(matches!(
+            vault.get("api_key").await,
+            Err(ApiKeyError::NotFound(_))
+        ));
+
+        let denied = VaultKeyProvider::new(
+            &server.uri(),
+            SecureApiKey::new("wrong".to_string()),
+            "crypto-hft/binance",
+        )
+        .with_mount("kv");
+        // wiremock answers unmatched requests with 404
+        assert!(denied.get("api_secret").await.is_err());
+    }
+}
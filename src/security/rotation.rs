@@ -0,0 +1,240 @@
+use super::api_keys::{ApiKeyError, SecureApiKey};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Poll interval while draining in-flight requests
+const DRAIN_POLL: Duration = Duration::from_millis(5);
+
+/// API key and secret of one exchange account
+#[derive(Clone, Debug)]
+pub struct ApiCredentials {
+    pub api_key: SecureApiKey,
+    pub api_secret: SecureApiKey,
+}
+
+impl ApiCredentials {
+    /// Create credentials from a key and secret
+    pub fn new(api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
+        Self {
+            api_key: SecureApiKey::new(api_key.into()),
+            api_secret: SecureApiKey::new(api_secret.into()),
+        }
+    }
+
+    /// Validate both the key and the secret
+    pub fn validate(&self) -> Result<(), ApiKeyError> {
+        self.api_key
+            .validate()
+            .map_err(|e| ApiKeyError::ValidationFailed("api_key".to_string(), Box::new(e)))?;
+        self.api_secret
+            .validate()
+            .map_err(|e| ApiKeyError::ValidationFailed("api_secret".to_string(), Box::new(e)))
+    }
+}
+
+/// One generation of credentials and the requests signed with it
+#[derive(Debug)]
+struct CredentialSlot {
+    credentials: ApiCredentials,
+    generation: u64,
+    in_flight: AtomicUsize,
+}
+
+/// Credentials shared by every adapter of one account
+/// Cloning shares the handle, so a rotation reaches all clones.
+#[derive(Clone, Debug)]
+pub struct SharedCredentials {
+    current: Arc<RwLock<Arc<CredentialSlot>>>,
+}
+
+impl SharedCredentials {
+    /// Create a handle holding the first generation of credentials
+    pub fn new(credentials: ApiCredentials) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(CredentialSlot {
+                credentials,
+                generation: 1,
+                in_flight: AtomicUsize::new(0),
+            }))),
+        }
+    }
+
+    /// Take the current credentials for one signed request
+    /// Keep the lease until the response arrives; rotations wait for it to drop.
+    pub fn lease(&self) -> CredentialLease {
+        // Count the request while holding the read lock so a concurrent swap either
+        // sees it or hands out the new slot
+        let current = self.current.read().unwrap_or_else(|e| e.into_inner());
+        current.in_flight.fetch_add(1, Ordering::AcqRel);
+        CredentialLease {
+            slot: Arc::clone(&current),
+        }
+    }
+
+    /// Get the generation of the current credentials, starting at 1
+    pub fn generation(&self) -> u64 {
+        self.slot().generation
+    }
+
+    /// Get the current API key
+    pub fn api_key(&self) -> SecureApiKey {
+        self.slot().credentials.api_key.clone()
+    }
+
+    /// Get the number of requests signed with the current credentials still in flight
+    pub fn in_flight(&self) -> usize {
+        self.slot().in_flight.load(Ordering::Acquire)
+    }
+
+    /// Replace the credentials; new leases get the new pair immediately
+    /// Returns the old generation so its in-flight requests can be drained.
+    pub fn swap(&self, credentials: ApiCredentials) -> RetiredCredentials {
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        let next = Arc::new(CredentialSlot {
+            credentials,
+            generation: current.generation + 1,
+            in_flight: AtomicUsize::new(0),
+        });
+        RetiredCredentials {
+            slot: std::mem::replace(&mut *current, next),
+        }
+    }
+
+    fn slot(&self) -> Arc<CredentialSlot> {
+        Arc::clone(&self.current.read().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Credentials held by one signed request
+#[derive(Debug)]
+pub struct CredentialLease {
+    slot: Arc<CredentialSlot>,
+}
+
+impl CredentialLease {
+    /// Get the API key to send with the request
+    pub fn api_key(&self) -> &str {
+        self.slot.credentials.api_key.expose()
+    }
+
+    /// Get the secret to sign the request with
+    pub fn api_secret(&self) -> &str {
+        self.slot.credentials.api_secret.expose()
+    }
+
+    /// Get the generation of the leased credentials
+    pub fn generation(&self) -> u64 {
+        self.slot.generation
+    }
+}
+
+impl Drop for CredentialLease {
+    fn drop(&mut self) {
+        self.slot.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Credentials replaced by a rotation
+#[derive(Debug)]
+pub struct RetiredCredentials {
+    slot: Arc<CredentialSlot>,
+}
+
+impl RetiredCredentials {
+    /// Get the retired API key
+    pub fn api_key(&self) -> &SecureApiKey {
+        &self.slot.credentials.api_key
+    }
+
+    /// Get the generation of the retired credentials
+    pub fn generation(&self) -> u64 {
+        self.slot.generation
+    }
+
+    /// Get the number of requests signed with these credentials still in flight
+    pub fn in_flight(&self) -> usize {
+        self.slot.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Wait until no request holds these credentials
+    /// Returns false if requests were still in flight after `timeout`.
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+        while self.in_flight() > 0 {
+            if tokio::time::Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(DRAIN_POLL).await;
+        }
+        true
+    }
+}
+
+/// Revokes retired API keys at the exchange or key management system
+#[async_trait]
+pub trait KeyRevoker: Send + Sync {
+    /// Revoke an account's retired key
+    async fn revoke(&self, account: &str, api_key: &SecureApiKey) -> Result<(), ApiKeyError>;
+}
+
+/// Outcome of an API key rotation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationReport {
+    pub account: String,
+    /// Generation of the new credentials
+    pub generation: u64,
+    /// Masked retired key
+    pub previous_key: String,
+    /// Masked new key
+    pub new_key: String,
+    /// All requests signed with the retired key finished before the timeout
+    pub drained: bool,
+    /// The revoker confirmed the retired key is revoked
+    pub revoked: bool,
+    /// Why the retired key was not revoked, if a revoker was set
+    pub revoke_error: Option<String>,
+}
+
+/// Status of one account's credentials
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyStatus {
+    pub account: String,
+    /// Masked current key
+    pub api_key: String,
+    pub generation: u64,
+    /// Requests signed with the current key still in flight
+    pub in_flight: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_swap_drains_old_leases() {
+        let shared = SharedCredentials::new(ApiCredentials::new("old-key", "old-secret"));
+        let adapter = shared.clone();
+        let old_request = adapter.lease();
+        assert_eq!(shared.in_flight(), 1);
+
+        let retired = shared.swap(ApiCredentials::new("new-key", "new-secret"));
+        // Requests already signed keep the old pair; new ones get the new pair
+        assert_eq!(old_request.api_key(), "old-key");
+        let new_request = adapter.lease();
+        assert_eq!(new_request.api_secret(), "new-secret");
+        assert_eq!(new_request.generation(), 2);
+        assert_eq!(shared.in_flight(), 1);
+
+        assert!(!retired.drain(Duration::from_millis(20)).await);
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(old_request);
+        });
+        assert!(retired.drain(Duration::from_secs(5)).await);
+        release.await.unwrap();
+        assert_eq!(retired.generation(), 1);
+    }
+}
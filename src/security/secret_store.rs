@@ -0,0 +1,276 @@
+use super::api_keys::{ApiKeyError, SecureApiKey};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the base64 master key
+pub const MASTER_KEY_ENV: &str = "HFT_MASTER_KEY";
+
+/// File format version written by this build
+const STORE_VERSION: u32 = 1;
+
+/// 256-bit key the secret store is encrypted with
+/// Generate one with `openssl rand -base64 32` and keep it outside the store's host.
+#[derive(Clone)]
+pub struct MasterKey([u8; 32]);
+
+impl MasterKey {
+    /// Create a key from raw bytes
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Generate a random key
+    pub fn generate() -> Self {
+        Self(Aes256Gcm::generate_key(&mut OsRng).into())
+    }
+
+    /// Decode a base64 key
+    pub fn from_base64(encoded: &str) -> Result<Self, ApiKeyError> {
+        let bytes = general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| ApiKeyError::Crypto(format!("master key is not base64: {}", e)))?;
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| ApiKeyError::Crypto("master key must be 32 bytes".to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// Read the base64 key from `HFT_MASTER_KEY`
+    pub fn from_env() -> Result<Self, ApiKeyError> {
+        let encoded = std::env::var(MASTER_KEY_ENV)
+            .map_err(|_| ApiKeyError::Missing(MASTER_KEY_ENV.to_string()))?;
+        Self::from_base64(&encoded)
+    }
+
+    /// Encode the key as base64
+    pub fn to_base64(&self) -> String {
+        general_purpose::STANDARD.encode(self.0)
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+    }
+}
+
+impl fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MasterKey(****)")
+    }
+}
+
+impl Drop for MasterKey {
+    fn drop(&mut self) {
+        self.0 = [0; 32];
+    }
+}
+
+/// One encrypted secret
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct EncryptedEntry {
+    /// Base64 96-bit nonce, unique per encryption
+    nonce: String,
+    /// Base64 AES-256-GCM ciphertext and tag
+    ciphertext: String,
+}
+
+/// On-disk form of the store
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreFile {
+    version: u32,
+    entries: BTreeMap<String, EncryptedEntry>,
+}
+
+/// Secrets encrypted at rest with AES-256-GCM
+///
+/// The file is JSON mapping each secret's name to its nonce and ciphertext; names stay
+/// readable, values do not. Each name is bound to its ciphertext as associated data, so
+/// entries cannot be swapped between names. Changes are written with `save`.
+pub struct EncryptedKeyStore {
+    path: PathBuf,
+    master_key: MasterKey,
+    entries: BTreeMap<String, EncryptedEntry>,
+}
+
+impl EncryptedKeyStore {
+    /// Open a store, starting empty if the file does not exist yet
+    pub fn open(path: impl AsRef<Path>, master_key: MasterKey) -> Result<Self, ApiKeyError> {
+        let path = path.as_ref().to_path_buf();
+        let file = if path.exists() {
+            let json = std::fs::read_to_string(&path).map_err(|e| {
+                ApiKeyError::Provider(format!("failed to read {}: {}", path.display(), e))
+            })?;
+            let file: StoreFile = serde_json::from_str(&json).map_err(|e| {
+                ApiKeyError::Provider(format!("invalid key store {}: {}", path.display(), e))
+            })?;
+            if file.version != STORE_VERSION {
+                return Err(ApiKeyError::Provider(format!(
+                    "unsupported key store version {}",
+                    file.version
+                )));
+            }
+            file
+        } else {
+            StoreFile::default()
+        };
+        Ok(Self {
+            path,
+            master_key,
+            entries: file.entries,
+        })
+    }
+
+    /// Encrypt and add a secret, replacing any with the same name
+    pub fn insert(&mut self, name: &str, secret: &str) -> Result<(), ApiKeyError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .master_key
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: secret.as_bytes(),
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| ApiKeyError::Crypto(format!("failed to encrypt {}", name)))?;
+        self.entries.insert(
+            name.to_string(),
+            EncryptedEntry {
+                nonce: general_purpose::STANDARD.encode(nonce),
+                ciphertext: general_purpose::STANDARD.encode(ciphertext),
+            },
+        );
+        Ok(())
+    }
+
+    /// Decrypt a secret
+    pub fn get(&self, name: &str) -> Result<SecureApiKey, ApiKeyError> {
+        let entry = self
+            .entries
+            .get(name)
+            .ok_or_else(|| ApiKeyError::NotFound(name.to_string()))?;
+        let corrupt = || ApiKeyError::Crypto(format!("entry {} is corrupt", name));
+        let nonce = general_purpose::STANDARD
+            .decode(&entry.nonce)
+            .map_err(|_| corrupt())?;
+        if nonce.len() != 12 {
+            return Err(corrupt());
+        }
+        let ciphertext = general_purpose::STANDARD
+            .decode(&entry.ciphertext)
+            .map_err(|_| corrupt())?;
+        let plaintext = self
+            .master_key
+            .cipher()
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                ApiKeyError::Crypto(format!(
+                    "failed to decrypt {}: wrong master key or tampered entry",
+                    name
+                ))
+            })?;
+        String::from_utf8(plaintext)
+            .map(SecureApiKey::new)
+            .map_err(|_| corrupt())
+    }
+
+    /// Remove a secret; returns false if there was none
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    /// Get the names of the stored secrets
+    pub fn names(&self) -> Vec<String> {
+        self.entries.keys().cloned().collect()
+    }
+
+    /// Write the store, replacing the file atomically
+    /// On Unix the file is readable by its owner only.
+    pub fn save(&self) -> Result<(), ApiKeyError> {
+        let file = StoreFile {
+            version: STORE_VERSION,
+            entries: self.entries.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|e| ApiKeyError::Provider(e.to_string()))?;
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let write = || -> std::io::Result<()> {
+            std::fs::write(&tmp, json)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))?;
+            }
+            std::fs::rename(&tmp, &self.path)
+        };
+        write().map_err(|e| {
+            ApiKeyError::Provider(format!("failed to write {}: {}", self.path.display(), e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_key_store() {
+        let path = std::env::temp_dir().join(format!("keys_{}.json", uuid::Uuid::new_v4()));
+        let master_key = MasterKey::generate();
+        let encoded = master_key.to_base64();
+
+        let mut store = EncryptedKeyStore::open(&path, master_key).unwrap();
+        store
+            .insert("binance.api_key", "k-1234567890abcdef")
+            .unwrap();
+        store
+            .insert("binance.api_secret", "s-1234567890abcdef")
+            .unwrap();
+        store.save().unwrap();
+
+        // Plaintext never reaches the file
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(!json.contains("1234567890abcdef"));
+        assert!(json.contains("binance.api_key"));
+
+        let store =
+            EncryptedKeyStore::open(&path, MasterKey::from_base64(&encoded).unwrap()).unwrap();
+        assert_eq!(
+            store.get("binance.api_secret").unwrap().expose(),
+            "s-1234567890abcdef"
+        );
+        assert!(matches!(
+            store.get("okx.api_key"),
+            Err(ApiKeyError::NotFound(_))
+        ));
+
+        // Wrong keys and swapped entries fail authentication
+        let wrong = EncryptedKeyStore::open(&path, MasterKey::generate()).unwrap();
+        assert!(matches!(
+            wrong.get("binance.api_key"),
+            Err(ApiKeyError::Crypto(_))
+        ));
+        let mut file: StoreFile = serde_json::from_str(&json).unwrap();
+        let secret = file.entries["binance.api_secret"].clone();
+        file.entries.insert("binance.api_key".to_string(), secret);
+        std::fs::write(&path, serde_json::to_string(&file).unwrap()).unwrap();
+        let swapped =
+            EncryptedKeyStore::open(&path, MasterKey::from_base64(&encoded).unwrap()).unwrap();
+        assert!(swapped.get("binance.api_key").is_err());
+
+        assert!(MasterKey::from_base64("c2hvcnQ=").is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
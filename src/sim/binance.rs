@@ -0,0 +1,934 @@
+use crate::sim::scenario::{Scenario, ScenarioStep};
+use crate::traits::OrderSide;
+use crate::types::{Price, Size};
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::server::{
+    Callback, ErrorResponse, Request, Response,
+};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Listen key handed out by `POST /api/v3/userDataStream`
+pub const LISTEN_KEY: &str = "mock-listen-key";
+
+/// Messages queued for the WebSocket connections
+const FEED_CAPACITY: usize = 1024;
+
+/// Local Binance spot exchange for integration tests
+/// Stops serving when dropped.
+pub struct MockExchangeServer {
+    rest_addr: SocketAddr,
+    ws_addr: SocketAddr,
+    state: Arc<Mutex<ExchangeState>>,
+    feed: broadcast::Sender<FeedMessage>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+/// Message for the WebSocket connections
+#[derive(Debug, Clone)]
+enum FeedMessage {
+    /// Market data on a stream such as `btcusdt@depth`
+    Market { stream: String, payload: String },
+    /// User data stream event
+    User(String),
+    /// Close the connection
+    Close,
+}
+
+/// Order held by the simulator
+#[derive(Debug, Clone)]
+struct SimOrder {
+    order_id: u64,
+    client_order_id: String,
+    symbol: String,
+    side: String,
+    order_type: String,
+    time_in_force: String,
+    price: Decimal,
+    orig_qty: Decimal,
+    executed_qty: Decimal,
+    cumulative_quote_qty: Decimal,
+    status: &'static str,
+    time: u64,
+    update_time: u64,
+}
+
+impl SimOrder {
+    fn is_open(&self) -> bool {
+        matches!(self.status, "NEW" | "PARTIALLY_FILLED")
+    }
+
+    /// Order as returned by the order endpoints
+    fn to_json(&self) -> Value {
+        json!({
+            "symbol": self.symbol,
+            "orderId": self.order_id,
+            "orderListId": -1,
+            "clientOrderId": self.client_order_id,
+            "price": self.price.normalize().to_string(),
+            "origQty": self.orig_qty.normalize().to_string(),
+            "executedQty": self.executed_qty.normalize().to_string(),
+            "cummulativeQuoteQty": self.cumulative_quote_qty.normalize().to_string(),
+            "status": self.status,
+            "timeInForce": self.time_in_force,
+            "type": self.order_type,
+            "side": self.side,
+            "time": self.time,
+            "updateTime": self.update_time,
+            "transactTime": self.update_time,
+            "isWorking": true,
+        })
+    }
+
+    /// `executionReport` user data event for the order's latest change
+    fn execution_report(
+        &self,
+        execution_type: &str,
+        fill: Option<(Decimal, Decimal, i64)>,
+    ) -> String {
+        let (last_qty, last_price, trade_id) = fill.unwrap_or((Decimal::ZERO, Decimal::ZERO, -1));
+        json!({
+            "e": "executionReport",
+            "E": self.update_time,
+            "s": self.symbol,
+            "c": self.client_order_id,
+            "S": self.side,
+            "o": self.order_type,
+            "f": self.time_in_force,
+            "q": self.orig_qty.normalize().to_string(),
+            "p": self.price.normalize().to_string(),
+            "x": execution_type,
+            "X": self.status,
+            "r": "NONE",
+            "i": self.order_id,
+            "l": last_qty.normalize().to_string(),
+            "z": self.executed_qty.normalize().to_string(),
+            "L": last_price.normalize().to_string(),
+            "n": "0",
+            "N": null,
+            "T": self.update_time,
+            "t": trade_id,
+            "m": false,
+            "Z": self.cumulative_quote_qty.normalize().to_string(),
+        })
+        .to_string()
+    }
+}
+
+/// REST request, with query and form parameters merged
+#[derive(Debug)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    params: HashMap<String, String>,
+}
+
+impl HttpRequest {
+    fn param(&self, name: &str) -> Result<&str, HttpResponse> {
+        self.params
+            .get(name)
+            .map(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| {
+                HttpResponse::error(
+                    400,
+                    -1102,
+                    &format!(
+                        "Mandatory parameter '{}' was not sent, was empty/null, or malformed.",
+                        name
+                    ),
+                )
+            })
+    }
+
+    fn decimal_param(&self, name: &str) -> Result<Decimal, HttpResponse> {
+        let value = self.param(name)?;
+        Decimal::from_str(value).map_err(|_| {
+            HttpResponse::error(
+                400,
+                -1100,
+                &format!("Illegal characters found in parameter '{}'.", name),
+            )
+        })
+    }
+}
+
+#[derive(Debug)]
+struct HttpResponse {
+    status: u16,
+    body: Value,
+    retry_after: Option<u64>,
+}
+
+impl HttpResponse {
+    fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            body,
+            retry_after: None,
+        }
+    }
+
+    fn error(status: u16, code: i64, msg: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "code": code, "msg": msg }),
+            retry_after: None,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            429 => "Too Many Requests",
+            _ => "Error",
+        };
+        let body = self.body.to_string();
+        let retry_after = self
+            .retry_after
+            .map(|secs| format!("Retry-After: {}\r\n", secs))
+            .unwrap_or_default();
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}\r\n{}",
+            self.status,
+            reason,
+            body.len(),
+            retry_after,
+            body
+        )
+        .into_bytes()
+    }
+}
+
+/// Accounts, orders and script of the simulated exchange
+#[derive(Debug)]
+struct ExchangeState {
+    scenario: Scenario,
+    balances: BTreeMap<String, Decimal>,
+    orders: BTreeMap<u64, SimOrder>,
+    next_order_id: u64,
+    next_trade_id: i64,
+    /// Last depth update ID per symbol
+    update_ids: HashMap<String, u64>,
+    /// `METHOD /path` of every REST request received
+    requests: Vec<String>,
+}
+
+impl ExchangeState {
+    fn handle(
+        &mut self,
+        request: &HttpRequest,
+        feed: &broadcast::Sender<FeedMessage>,
+    ) -> HttpResponse {
+        self.requests
+            .push(format!("{} {}", request.method, request.path));
+        let rate_limited = self.scenario.take(
+            |step| matches!(step, ScenarioStep::RateLimit { path, .. } if *path == request.path),
+        );
+        if let Some(ScenarioStep::RateLimit {
+            retry_after_secs, ..
+        }) = rate_limited
+        {
+            let mut response = HttpResponse::error(
+                429,
+                -1003,
+                "Too many requests; current limit of IP is exceeded.",
+            );
+            response.retry_after = Some(retry_after_secs);
+            return response;
+        }
+
+        let signed = matches!(
+            request.path.as_str(),
+            "/api/v3/order" | "/api/v3/openOrders" | "/api/v3/account"
+        );
+        if signed {
+            if let Err(response) = request.param("signature") {
+                return response;
+            }
+        }
+
+        let now = now_millis();
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/api/v3/ping") => Ok(HttpResponse::ok(json!({}))),
+            ("GET", "/api/v3/time") => Ok(HttpResponse::ok(json!({ "serverTime": now }))),
+            ("POST", "/api/v3/userDataStream") => {
+                Ok(HttpResponse::ok(json!({ "listenKey": LISTEN_KEY })))
+            }
+            ("PUT" | "DELETE", "/api/v3/userDataStream") => Ok(HttpResponse::ok(json!({}))),
+            ("POST", "/api/v3/order") => self.new_order(request, now, feed),
+            ("DELETE", "/api/v3/order") => self.cancel_order(request, now, feed),
+            ("GET", "/api/v3/order") => self
+                .find_order(request)
+                .and_then(|order| {
+                    order.ok_or_else(|| HttpResponse::error(400, -2013, "Order does not exist."))
+                })
+                .map(|order| HttpResponse::ok(order.to_json())),
+            ("GET", "/api/v3/openOrders") => {
+                let symbol = request.params.get("symbol");
+                let orders: Vec<Value> = self
+                    .orders
+                    .values()
+                    .filter(|order| order.is_open())
+                    .filter(|order| symbol.is_none_or(|symbol| *symbol == order.symbol))
+                    .map(SimOrder::to_json)
+                    .collect();
+                Ok(HttpResponse::ok(Value::Array(orders)))
+            }
+            ("GET", "/api/v3/account") => {
+                let balances: Vec<Value> = self
+                    .balances
+                    .iter()
+                    .map(|(asset, free)| {
+                        json!({
+                            "asset": asset,
+                            "free": free.normalize().to_string(),
+                            "locked": "0",
+                        })
+                    })
+                    .collect();
+                Ok(HttpResponse::ok(json!({ "balances": balances })))
+            }
+            _ => Err(HttpResponse::error(404, -1000, "Unknown endpoint.")),
+        };
+        result.unwrap_or_else(|response| response)
+    }
+
+    fn new_order(
+        &mut self,
+        request: &HttpRequest,
+        now: u64,
+        feed: &broadcast::Sender<FeedMessage>,
+    ) -> Result<HttpResponse, HttpResponse> {
+        let symbol = request.param("symbol")?.to_string();
+        let side = request.param("side")?.to_string();
+        let order_type = request.param("type")?.to_string();
+        let price = match request.params.get("price") {
+            Some(_) => Some(request.decimal_param("price")?),
+            None => None,
+        };
+        let quote_qty = match request.params.get("quoteOrderQty") {
+            Some(_) => Some(request.decimal_param("quoteOrderQty")?),
+            None => None,
+        };
+        let quantity = match quote_qty {
+            Some(_) => None,
+            None => Some(request.decimal_param("quantity")?),
+        };
+
+        // The order meets whichever order step comes first in the script
+        let step = self.scenario.take(|step| {
+            matches!(
+                step,
+                ScenarioStep::RejectOrder { .. } | ScenarioStep::Fill { .. }
+            )
+        });
+        if let Some(ScenarioStep::RejectOrder { code, msg }) = step {
+            return Err(HttpResponse::error(400, code, &msg));
+        }
+
+        let order_id = self.next_order_id;
+        self.next_order_id += 1;
+        let mut order = SimOrder {
+            order_id,
+            client_order_id: request
+                .params
+                .get("newClientOrderId")
+                .cloned()
+                .unwrap_or_else(|| format!("mock{}", order_id)),
+            symbol,
+            side,
+            order_type,
+            time_in_force: request
+                .params
+                .get("timeInForce")
+                .cloned()
+                .unwrap_or_else(|| "GTC".to_string()),
+            price: price.unwrap_or_default(),
+            orig_qty: quantity.unwrap_or_default(),
+            executed_qty: Decimal::ZERO,
+            cumulative_quote_qty: Decimal::ZERO,
+            status: "NEW",
+            time: now,
+            update_time: now,
+        };
+        let _ = feed.send(FeedMessage::User(order.execution_report("NEW", None)));
+
+        let fill_price = match step {
+            Some(ScenarioStep::Fill {
+                price: fill_price, ..
+            }) => fill_price.map(|price| price.value()).or(price),
+            _ => None,
+        };
+        if let (Some(ScenarioStep::Fill { ratio, .. }), Some(fill_price)) = (step, fill_price) {
+            // Quote-sized orders are sized at the fill price
+            if let Some(quote_qty) = quote_qty {
+                order.orig_qty = quote_qty / fill_price;
+            }
+            let fill_qty = (order.orig_qty * ratio).min(order.orig_qty);
+            if fill_qty > Decimal::ZERO {
+                order.executed_qty = fill_qty;
+                order.cumulative_quote_qty = fill_qty * fill_price;
+                order.status = if fill_qty == order.orig_qty {
+                    "FILLED"
+                } else {
+                    "PARTIALLY_FILLED"
+                };
+                let trade_id = self.next_trade_id;
+                self.next_trade_id += 1;
+                let report =
+                    order.execution_report("TRADE", Some((fill_qty, fill_price, trade_id)));
+                let _ = feed.send(FeedMessage::User(report));
+            }
+        }
+
+        // Nothing else trades against the order, so only resting limit orders stay open
+        if order.is_open() && (order.order_type == "MARKET" || order.time_in_force != "GTC") {
+            order.status = "EXPIRED";
+            let _ = feed.send(FeedMessage::User(order.execution_report("EXPIRED", None)));
+        }
+
+        let response = HttpResponse::ok(order.to_json());
+        self.orders.insert(order_id, order);
+        Ok(response)
+    }
+
+    fn cancel_order(
+        &mut self,
+        request: &HttpRequest,
+        now: u64,
+        feed: &broadcast::Sender<FeedMessage>,
+    ) -> Result<HttpResponse, HttpResponse> {
+        let order_id = self
+            .find_order(request)?
+            .filter(|order| order.is_open())
+            .map(|order| order.order_id)
+            .ok_or_else(|| HttpResponse::error(400, -2011, "Unknown order sent."))?;
+        let order = self
+            .orders
+            .get_mut(&order_id)
+            .expect("order was just found");
+        order.status = "CANCELED";
+        order.update_time = now;
+        let _ = feed.send(FeedMessage::User(order.execution_report("CANCELED", None)));
+        Ok(HttpResponse::ok(order.to_json()))
+    }
+
+    /// Look an order up by `orderId` or `origClientOrderId`
+    fn find_order(&self, request: &HttpRequest) -> Result<Option<&SimOrder>, HttpResponse> {
+        let symbol = request.param("symbol")?;
+        let order = match request.params.get("orderId") {
+            Some(order_id) => order_id
+                .parse::<u64>()
+                .ok()
+                .and_then(|order_id| self.orders.get(&order_id)),
+            None => {
+                let client_order_id = request.param("origClientOrderId")?;
+                self.orders
+                    .values()
+                    .find(|order| order.client_order_id == client_order_id)
+            }
+        };
+        Ok(order.filter(|order| order.symbol == symbol))
+    }
+}
+
+impl MockExchangeServer {
+    /// Start serving on two free localhost ports
+    pub async fn start(scenario: Scenario) -> io::Result<Self> {
+        let rest_listener = TcpListener::bind("127.0.0.1:0").await?;
+        let ws_listener = TcpListener::bind("127.0.0.1:0").await?;
+        let (feed, _) = broadcast::channel(FEED_CAPACITY);
+        let state = Arc::new(Mutex::new(ExchangeState {
+            balances: scenario.balances().iter().cloned().collect(),
+            scenario,
+            orders: BTreeMap::new(),
+            next_order_id: 1,
+            next_trade_id: 1,
+            update_ids: HashMap::new(),
+            requests: Vec::new(),
+        }));
+
+        let rest_addr = rest_listener.local_addr()?;
+        let ws_addr = ws_listener.local_addr()?;
+        let tasks = vec![
+            tokio::spawn(serve_rest(rest_listener, state.clone(), feed.clone())),
+            tokio::spawn(serve_ws(ws_listener, feed.clone())),
+        ];
+        log::info!(
+            "Mock exchange serving REST on {} and WebSocket on {}",
+            rest_addr,
+            ws_addr
+        );
+
+        Ok(Self {
+            rest_addr,
+            ws_addr,
+            state,
+            feed,
+            tasks,
+        })
+    }
+
+    /// Base URL to pass to `with_rest_url`
+    pub fn rest_url(&self) -> String {
+        format!("http://{}", self.rest_addr)
+    }
+
+    /// Host to pass to `with_ws_url`
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}", self.ws_addr)
+    }
+
+    /// Append a step to the script while running
+    pub fn script(&self, step: ScenarioStep) {
+        self.lock().scenario.push(step);
+    }
+
+    /// Get `METHOD /path` of every REST request received so far
+    pub fn requests(&self) -> Vec<String> {
+        self.lock().requests.clone()
+    }
+
+    /// Publish a depth update on `<symbol>@depth`, numbered after the previous one
+    pub fn publish_depth_update(
+        &self,
+        symbol: &str,
+        bids: &[(Price, Size)],
+        asks: &[(Price, Size)],
+    ) {
+        let levels = |levels: &[(Price, Size)]| -> Vec<Value> {
+            levels
+                .iter()
+                .map(|(price, size)| json!([price.to_string(), size.to_string()]))
+                .collect()
+        };
+        let mut state = self.lock();
+        let update_id = state.update_ids.entry(symbol.to_uppercase()).or_insert(0);
+        *update_id += 1;
+        let payload = json!({
+            "e": "depthUpdate",
+            "E": now_millis(),
+            "s": symbol.to_uppercase(),
+            "U": *update_id,
+            "u": *update_id,
+            "b": levels(bids),
+            "a": levels(asks),
+        });
+        self.publish_locked(
+            &mut state,
+            format!("{}@depth", symbol.to_lowercase()),
+            payload.to_string(),
+        );
+    }
+
+    /// Publish a trade on `<symbol>@trade`, with the side of the taker
+    pub fn publish_trade(&self, symbol: &str, price: Price, size: Size, side: OrderSide) {
+        let mut state = self.lock();
+        let trade_id = state.next_trade_id;
+        state.next_trade_id += 1;
+        let now = now_millis();
+        let payload = json!({
+            "e": "trade",
+            "E": now,
+            "s": symbol.to_uppercase(),
+            "t": trade_id,
+            "p": price.to_string(),
+            "q": size.to_string(),
+            "b": 0,
+            "a": 0,
+            "T": now,
+            "m": side == OrderSide::Sell,
+        });
+        self.publish_locked(
+            &mut state,
+            format!("{}@trade", symbol.to_lowercase()),
+            payload.to_string(),
+        );
+    }
+
+    /// Publish a raw message on a stream, e.g. `btcusdt@bookTicker`
+    pub fn publish(&self, stream: &str, payload: impl Into<String>) {
+        let mut state = self.lock();
+        self.publish_locked(&mut state, stream.to_string(), payload.into());
+    }
+
+    /// Close every WebSocket connection now
+    pub fn disconnect_all(&self) {
+        let _ = self.feed.send(FeedMessage::Close);
+    }
+
+    fn publish_locked(&self, state: &mut ExchangeState, stream: String, payload: String) {
+        let _ = self.feed.send(FeedMessage::Market { stream, payload });
+        if state.scenario.count_message() {
+            log::info!("Mock exchange dropping WebSocket connections");
+            self.disconnect_all();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ExchangeState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for MockExchangeServer {
+    fn drop(&mut self) {
+        self.disconnect_all();
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
+
+async fn serve_rest(
+    listener: TcpListener,
+    state: Arc<Mutex<ExchangeState>>,
+    feed: broadcast::Sender<FeedMessage>,
+) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let state = state.clone();
+        let feed = feed.clone();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(stream);
+            // Clients keep connections alive across requests
+            while let Some(request) = read_request(&mut reader).await {
+                let response = state
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .handle(&request, &feed);
+                if reader
+                    .get_mut()
+                    .write_all(&response.to_bytes())
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// Read one HTTP/1.1 request, or None once the client hangs up
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Option<HttpRequest> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?.to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await.ok()? == 0 {
+            return None;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().ok()?;
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await.ok()?;
+
+    // Signed GET and DELETE requests may carry their parameters in the body
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let mut params = parse_params(query);
+    params.extend(parse_params(&String::from_utf8_lossy(&body)));
+    Some(HttpRequest {
+        method,
+        path: path.to_string(),
+        params,
+    })
+}
+
+fn parse_params(encoded: &str) -> HashMap<String, String> {
+    encoded
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (percent_decode(key), percent_decode(value)))
+        .collect()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// What a WebSocket connection subscribed to, from its URL path
+#[derive(Debug)]
+enum Subscription {
+    /// `/ws/<listenKey>`
+    UserData,
+    /// `/ws/<stream>` or `/stream?streams=<stream>/<stream>`, the latter wrapped in
+    /// `{"stream": ..., "data": ...}`
+    Streams { names: Vec<String>, combined: bool },
+}
+
+impl Subscription {
+    fn from_path(path: &str) -> Self {
+        if let Some(streams) = path.strip_prefix("/stream?streams=") {
+            return Subscription::Streams {
+                names: streams.split('/').map(|name| name.to_string()).collect(),
+                combined: true,
+            };
+        }
+        match path.strip_prefix("/ws/") {
+            Some(LISTEN_KEY) => Subscription::UserData,
+            Some(stream) => Subscription::Streams {
+                names: vec![stream.to_string()],
+                combined: false,
+            },
+            None => Subscription::Streams {
+                names: Vec::new(),
+                combined: false,
+            },
+        }
+    }
+
+    /// Text frame of a feed message, if the connection subscribed to it
+    fn frame(&self, message: &FeedMessage) -> Option<String> {
+        match (self, message) {
+            (Subscription::UserData, FeedMessage::User(payload)) => Some(payload.clone()),
+            (
+                Subscription::Streams { names, combined },
+                FeedMessage::Market { stream, payload },
+            ) if names.contains(stream) => Some(match combined {
+                true => format!(r#"{{"stream":"{}","data":{}}}"#, stream, payload),
+                false => payload.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+async fn serve_ws(listener: TcpListener, feed: broadcast::Sender<FeedMessage>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        // Subscribe before the handshake completes, so a client sees everything
+        // published once it has connected
+        let messages = feed.subscribe();
+        tokio::spawn(serve_ws_connection(stream, messages));
+    }
+}
+
+/// Handshake callback that keeps the request path
+struct RecordPath<'a>(&'a mut String);
+
+impl Callback for RecordPath<'_> {
+    fn on_request(self, request: &Request, response: Response) -> Result<Response, ErrorResponse> {
+        *self.0 = request.uri().to_string();
+        Ok(response)
+    }
+}
+
+async fn serve_ws_connection(stream: TcpStream, mut messages: broadcast::Receiver<FeedMessage>) {
+    let mut path = String::new();
+    let callback = RecordPath(&mut path);
+    let Ok(mut ws) = tokio_tungstenite::accept_hdr_async(stream, callback).await else {
+        return;
+    };
+    let subscription = Subscription::from_path(&path);
+
+    loop {
+        tokio::select! {
+            message = messages.recv() => match message {
+                Ok(FeedMessage::Close) | Err(broadcast::error::RecvError::Closed) => {
+                    let _ = ws.close(None).await;
+                    return;
+                }
+                Ok(message) => {
+                    if let Some(text) = subscription.frame(&message) {
+                        if ws.send(Message::Text(text)).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::warn!("Mock exchange WebSocket client skipped {} messages", skipped);
+                }
+            },
+            incoming = ws.next() => match incoming {
+                Some(Ok(Message::Ping(data))) => {
+                    if ws.send(Message::Pong(data)).await.is_err() {
+                        return;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::BinanceUserDataMessage;
+    use crate::core::reject::RejectReason;
+    use crate::exchanges::binance::{BinanceError, BinanceWebSocket, MarketType};
+    use crate::exchanges::BinanceAdapter;
+    use crate::traits::{
+        ExecutionClient, MarketDataStream, MarketEvent, NewOrder, OrderStatus, TimeInForce,
+    };
+
+    fn limit_buy(price: &str, size: &str) -> NewOrder {
+        NewOrder::new_limit_buy(
+            "BTCUSDT".to_string(),
+            Size::from_str(size).unwrap(),
+            Price::from_str(price).unwrap(),
+            TimeInForce::GoodTillCancelled,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_adapter_orders_against_mock_exchange() {
+        let server = MockExchangeServer::start(
+            Scenario::new()
+                .with_balance("USDT", Decimal::new(1000, 0))
+                .fill(Decimal::new(5, 1), None)
+                .reject_order(
+                    -2010,
+                    "Account has insufficient balance for requested action.",
+                ),
+        )
+        .await
+        .unwrap();
+        let adapter = BinanceAdapter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+            MarketType::Spot,
+        )
+        .with_rest_url(server.rest_url());
+        let (mut user_data, _) =
+            tokio_tungstenite::connect_async(format!("{}/ws/{}", server.ws_url(), LISTEN_KEY))
+                .await
+                .unwrap();
+
+        // Half filled on placement; the status query is rate-limited once and retried
+        let order_id = adapter.place_order(limit_buy("100", "1")).await.unwrap();
+        server.script(ScenarioStep::RateLimit {
+            path: "/api/v3/order".to_string(),
+            retry_after_secs: 0,
+        });
+        let report = adapter.get_order_status(order_id.clone()).await.unwrap();
+        assert_eq!(report.status, OrderStatus::PartiallyFilled);
+        assert_eq!(report.filled_size, Size::from_str("0.5").unwrap());
+        assert_eq!(report.average_price, Some(Price::from_str("100").unwrap()));
+        let queries = server
+            .requests()
+            .iter()
+            .filter(|request| *request == "GET /api/v3/order")
+            .count();
+        assert_eq!(queries, 2);
+
+        let mut execution_types = Vec::new();
+        while execution_types.len() < 2 {
+            if let Some(Ok(Message::Text(text))) = user_data.next().await {
+                if let Ok(BinanceUserDataMessage::ExecutionReport(report)) =
+                    BinanceUserDataMessage::from_json(&text)
+                {
+                    execution_types.push(report.x.clone());
+                }
+            }
+        }
+        assert_eq!(execution_types, vec!["NEW", "TRADE"]);
+
+        match adapter.place_order(limit_buy("100", "1")).await {
+            Err(BinanceError::Rejected(reject)) => {
+                assert_eq!(reject.reason, RejectReason::InsufficientBalance)
+            }
+            other => panic!("expected a reject, got {:?}", other),
+        }
+
+        adapter.cancel_order(order_id.clone()).await.unwrap();
+        let report = adapter.get_order_status(order_id.clone()).await.unwrap();
+        assert_eq!(report.status, OrderStatus::Cancelled);
+        assert!(adapter.cancel_order(order_id).await.is_err());
+
+        let balances = adapter.get_balances().await.unwrap();
+        assert_eq!(balances[0].asset, "USDT");
+    }
+
+    #[tokio::test]
+    async fn test_market_data_stream_disconnect() {
+        let server = MockExchangeServer::start(Scenario::new().disconnect_after(2))
+            .await
+            .unwrap();
+        let mut websocket = BinanceWebSocket::new().with_ws_url(server.ws_url());
+        websocket.connect(&["BTCUSDT"]).await.unwrap();
+
+        let level = [(
+            Price::from_str("100").unwrap(),
+            Size::from_str("1").unwrap(),
+        )];
+        server.publish_depth_update("BTCUSDT", &level, &level);
+        server.publish_depth_update("BTCUSDT", &level, &level);
+        for _ in 0..2 {
+            let event = websocket.next().await.unwrap().unwrap();
+            assert!(matches!(event, MarketEvent::OrderBookDelta(_)));
+        }
+        assert!(websocket.next().await.is_none());
+
+        // Reconnecting picks the stream up again
+        websocket.connect(&["BTCUSDT"]).await.unwrap();
+        server.publish_trade(
+            "BTCUSDT",
+            Price::from_str("100").unwrap(),
+            Size::from_str("1").unwrap(),
+            OrderSide::Buy,
+        );
+        server.publish_depth_update("BTCUSDT", &level, &level);
+        match websocket.next().await.unwrap().unwrap() {
+            MarketEvent::OrderBookDelta(delta) => {
+                assert_eq!(delta.symbol.as_str(), "BTCUSDT")
+            }
+            other => panic!("expected a depth update, got {:?}", other),
+        }
+    }
+}
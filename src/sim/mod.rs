@@ -0,0 +1,5 @@
+pub mod binance;
+pub mod scenario;
+
+pub use binance::{MockExchangeServer, LISTEN_KEY};
+pub use scenario::{Scenario, ScenarioStep};
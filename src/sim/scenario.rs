@@ -0,0 +1,114 @@
+use crate::types::Price;
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+
+/// A scripted departure from normal exchange behaviour
+/// Each step applies to the first request or message it matches and is then used up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenarioStep {
+    /// Answer the next request to a REST path with HTTP 429 and a Retry-After header
+    RateLimit { path: String, retry_after_secs: u64 },
+    /// Reject the next new order with a Binance error code and message
+    RejectOrder { code: i64, msg: String },
+    /// Fill a ratio of the next new order's quantity, at the given price or else its
+    /// limit price, and leave the rest working
+    Fill {
+        ratio: Decimal,
+        price: Option<Price>,
+    },
+    /// Close every WebSocket connection right after the given number of further market
+    /// data messages
+    Disconnect { after_messages: usize },
+}
+
+/// Script and starting state of a mock exchange
+#[derive(Debug, Clone, Default)]
+pub struct Scenario {
+    steps: VecDeque<ScenarioStep>,
+    balances: Vec<(String, Decimal)>,
+}
+
+impl Scenario {
+    /// Create a scenario where the exchange behaves normally and holds no balances
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with a free balance of an asset (builder pattern)
+    pub fn with_balance(mut self, asset: impl Into<String>, free: Decimal) -> Self {
+        self.balances.push((asset.into(), free));
+        self
+    }
+
+    /// Append a step to the script (builder pattern)
+    pub fn then(mut self, step: ScenarioStep) -> Self {
+        self.steps.push_back(step);
+        self
+    }
+
+    /// Rate-limit the next request to a path (builder pattern)
+    pub fn rate_limit(self, path: impl Into<String>, retry_after_secs: u64) -> Self {
+        self.then(ScenarioStep::RateLimit {
+            path: path.into(),
+            retry_after_secs,
+        })
+    }
+
+    /// Reject the next new order (builder pattern)
+    pub fn reject_order(self, code: i64, msg: impl Into<String>) -> Self {
+        self.then(ScenarioStep::RejectOrder {
+            code,
+            msg: msg.into(),
+        })
+    }
+
+    /// Fill a ratio of the next new order (builder pattern)
+    pub fn fill(self, ratio: Decimal, price: Option<Price>) -> Self {
+        self.then(ScenarioStep::Fill { ratio, price })
+    }
+
+    /// Drop the WebSocket connections after some market data messages (builder pattern)
+    pub fn disconnect_after(self, after_messages: usize) -> Self {
+        self.then(ScenarioStep::Disconnect { after_messages })
+    }
+
+    /// Get the starting balances
+    pub fn balances(&self) -> &[(String, Decimal)] {
+        &self.balances
+    }
+
+    /// Steps not used up yet, in script order
+    pub fn remaining_steps(&self) -> impl Iterator<Item = &ScenarioStep> {
+        self.steps.iter()
+    }
+
+    pub(crate) fn push(&mut self, step: ScenarioStep) {
+        self.steps.push_back(step);
+    }
+
+    /// Use up the first step a predicate matches
+    pub(crate) fn take(&mut self, matches: impl Fn(&ScenarioStep) -> bool) -> Option<ScenarioStep> {
+        let index = self.steps.iter().position(matches)?;
+        self.steps.remove(index)
+    }
+
+    /// Count a market data message against the first pending disconnect
+    /// Returns true if the connections should be closed now.
+    pub(crate) fn count_message(&mut self) -> bool {
+        let Some(index) = self
+            .steps
+            .iter()
+            .position(|step| matches!(step, ScenarioStep::Disconnect { .. }))
+        else {
+            return false;
+        };
+        if let Some(ScenarioStep::Disconnect { after_messages }) = self.steps.get_mut(index) {
+            *after_messages = after_messages.saturating_sub(1);
+            if *after_messages > 0 {
+                return false;
+            }
+        }
+        self.steps.remove(index);
+        true
+    }
+}
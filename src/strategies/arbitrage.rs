@@ -1,11 +1,13 @@
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, info, warn};
 
+use crate::core::clock::{system_clock, SharedClock};
 use crate::core::events::{
-    MarketEvent, NewOrder, OrderBookDelta, OrderBookSnapshot, OrderSide, OrderStatus, OrderType,
-    Signal, TimeInForce, TradingEvent,
+    MarketEvent, NewOrder, OrderSide, OrderStatus, OrderType, Signal, TimeInForce, TradingEvent,
 };
+use crate::orderbook::{ConsolidatedBook, ExecutionLadder, Nbbo, VenueQuote};
+use crate::strategies::order_sizer::OrderSizer;
 use crate::traits::strategy::{
     PositionManager, RiskManager, SignalValidator, Strategy, StrategyConfig, StrategyMetrics,
     StrategyState,
@@ -58,6 +60,8 @@ pub struct ArbitrageOpportunity {
     pub price_sell: Price,
     pub spread: Price,
     pub spread_percentage: rust_decimal::Decimal,
+    /// Size of each leg
+    pub size: Size,
     pub estimated_profit: rust_decimal::Decimal,
     pub timestamp: std::time::Instant,
 }
@@ -122,11 +126,18 @@ pub struct ArbitrageStrategy {
     config: ArbitrageConfig,
     state: ArbitrageState,
     exchanges: HashMap<String, String>, // Exchange name -> Exchange ID mapping
-    price_cache: HashMap<String, HashMap<Symbol, (Price, std::time::Instant)>>,
+    /// Books of every tracked venue; its NBBO updates drive opportunity detection
+    book: ConsolidatedBook,
+    /// Venues without an execution path; opportunities on them are only reported
+    signal_only_venues: HashSet<String>,
     signal_validator: Option<Box<dyn SignalValidator>>, // SignalValidator doesn't have Error type
     risk_manager: Option<Box<dyn RiskManager<Error = BoxedError>>>,
     position_manager: Option<Box<dyn PositionManager<Error = BoxedError>>>,
     metrics: StrategyMetrics,
+    /// Time source for opportunity expiry and book staleness
+    clock: SharedClock,
+    /// Sizes legs from depth, volume and fill rates instead of `max_position_size` (optional)
+    order_sizer: Option<OrderSizer>,
 }
 
 impl ArbitrageStrategy {
@@ -145,7 +156,9 @@ impl ArbitrageStrategy {
                 last_update: std::time::Instant::now(),
             },
             exchanges: HashMap::new(), // Exchange name -> ID mapping
-            price_cache: HashMap::new(),
+            // Books not updated in the last 5 seconds are too stale to trade against
+            book: ConsolidatedBook::new().with_max_age(std::time::Duration::from_secs(5)),
+            signal_only_venues: HashSet::new(),
             signal_validator: None,
             risk_manager: None,
             position_manager: None,
@@ -162,15 +175,50 @@ impl ArbitrageStrategy {
                 average_trade_pnl: rust_decimal::Decimal::ZERO,
                 win_rate: rust_decimal::Decimal::ZERO,
                 average_holding_time_ms: 0,
+                arrival_slippage_bps: rust_decimal::Decimal::ZERO,
             },
+            clock: system_clock(),
+            order_sizer: None,
         }
     }
 
-    /// Initialize price cache for an exchange
+    /// Set the time source for opportunity expiry and book staleness (builder pattern)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.state.last_update = clock.now_instant();
+        self.book = std::mem::take(&mut self.book).with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
+    /// Size legs with an order sizer, capped at `max_position_size` (builder pattern)
+    pub fn with_order_sizer(mut self, order_sizer: OrderSizer) -> Self {
+        self.order_sizer = Some(order_sizer);
+        self
+    }
+
+    /// Get the order sizer
+    pub fn order_sizer(&self) -> Option<&OrderSizer> {
+        self.order_sizer.as_ref()
+    }
+
+    /// Start tracking an exchange's order books
     pub fn initialize_exchange_cache(&mut self, exchange_name: String) {
-        self.price_cache
-            .insert(exchange_name.clone(), HashMap::new());
-        info!("Initialized price cache for exchange: {}", exchange_name);
+        info!("Tracking order books for exchange: {}", exchange_name);
+        self.book.add_venue(exchange_name);
+    }
+
+    /// Get the consolidated book across tracked exchanges
+    pub fn book(&self) -> &ConsolidatedBook {
+        &self.book
+    }
+
+    /// Track a venue whose legs must be executed by hand, such as a DEX aggregator
+    /// Opportunities involving it are kept in the state and logged, but produce no orders.
+    pub fn with_signal_only_venue(mut self, venue: impl Into<String>) -> Self {
+        let venue = venue.into();
+        self.book.add_venue(venue.clone());
+        self.signal_only_venues.insert(venue);
+        self
     }
 
     /// Set the signal validator
@@ -194,138 +242,75 @@ impl ArbitrageStrategy {
         self
     }
 
-    /// Update price cache with new market data from order book snapshot
-    fn update_price_cache_from_snapshot(&mut self, snapshot: &OrderBookSnapshot) {
-        if let Some(cache) = self.price_cache.get_mut(&snapshot.exchange_id) {
-            // Use mid price (average of best bid and ask)
-            if let (Some(best_bid), Some(best_ask)) = (snapshot.bids.first(), snapshot.asks.first())
-            {
-                let mid_price = (best_bid.price.value() + best_ask.price.value())
-                    / rust_decimal::Decimal::new(2, 0);
-                cache.insert(
-                    snapshot.symbol.clone(),
-                    (Price::new(mid_price), std::time::Instant::now()),
-                );
-            }
+    /// Check an NBBO update for a cross between venues
+    /// Buying at the best ask on one venue and selling at the best bid on another must clear
+    /// the minimum spread.
+    fn identify_opportunity(&self, nbbo: &Nbbo) -> Option<ArbitrageOpportunity> {
+        if !nbbo.is_crossed() {
+            return None;
+        }
+        let (bid, ask) = (nbbo.best_bid.as_ref()?, nbbo.best_ask.as_ref()?);
+        let spread = bid.price - ask.price;
+        let spread_percentage = spread.value() / ask.price.value();
+
+        // Convert min_spread_bps from basis points to decimal (e.g., 5 bps = 0.0005)
+        let min_spread_decimal = self.config.min_spread_bps / rust_decimal::Decimal::new(10000, 0);
+        if spread_percentage < min_spread_decimal {
+            return None;
         }
-    }
 
-    /// Update price cache with new market data from order book delta
-    fn update_price_cache_from_delta(&mut self, delta: &OrderBookDelta) {
-        if let Some(cache) = self.price_cache.get_mut(&delta.exchange_id) {
-            // Use mid price (average of best bid and ask)
-            if let (Some(best_bid), Some(best_ask)) = (delta.bids.first(), delta.asks.first()) {
-                let mid_price = (best_bid.price.value() + best_ask.price.value())
-                    / rust_decimal::Decimal::new(2, 0);
-                cache.insert(
-                    delta.symbol.clone(),
-                    (Price::new(mid_price), std::time::Instant::now()),
-                );
+        // Neither leg should take more than the two books cross by through their depth
+        let max_size = self.config.max_position_size;
+        // A sizer takes a fraction of the depth, so it needs to see correspondingly more
+        let walk_size = match &self.order_sizer {
+            Some(sizer) if sizer.config().depth_fraction > rust_decimal::Decimal::ZERO => {
+                Size::new(max_size.value() / sizer.config().depth_fraction)
             }
+            _ => max_size,
+        };
+        let depth = crossed_depth(
+            &self.venue_ladder(nbbo, ask, OrderSide::Buy, walk_size),
+            &self.venue_ladder(nbbo, bid, OrderSide::Sell, walk_size),
+            min_spread_decimal,
+        );
+        let size = match &self.order_sizer {
+            Some(sizer) => sizer.size(nbbo.symbol.value(), depth).size.min(max_size),
+            None => depth.min(max_size),
+        };
+        if size.is_zero() {
+            debug!("Arbitrage opportunity on {} too thin to size", nbbo.symbol);
+            return None;
         }
+
+        // Both legs are limited at the last level they need to reach
+        let buy = self.venue_ladder(nbbo, ask, OrderSide::Buy, size);
+        let sell = self.venue_ladder(nbbo, bid, OrderSide::Sell, size);
+        Some(ArbitrageOpportunity {
+            symbol: nbbo.symbol.clone(),
+            exchange_buy: ask.exchange_id.clone(),
+            exchange_sell: bid.exchange_id.clone(),
+            price_buy: buy.worst_price()?,
+            price_sell: sell.worst_price()?,
+            spread,
+            spread_percentage,
+            size,
+            estimated_profit: sell.notional - buy.notional,
+            timestamp: self.clock.now_instant(),
+        })
     }
 
-    /// Get price from cache
-    fn get_price_from_cache(&self, exchange: &str, symbol: &Symbol) -> Option<Price> {
-        if let Some(cache) = self.price_cache.get(exchange) {
-            if let Some((price, timestamp)) = cache.get(symbol) {
-                // Check if price is still fresh (within last 5 seconds)
-                if timestamp.elapsed().as_secs() < 5 {
-                    return Some(*price);
-                }
-            }
-        }
-        None
-    }
-
-    /// Identify arbitrage opportunities across exchanges
-    fn identify_opportunities(&mut self) -> Vec<ArbitrageOpportunity> {
-        let mut opportunities = Vec::new();
-        let exchange_names: Vec<String> = self.price_cache.keys().cloned().collect();
-
-        // For each pair of exchanges, check for arbitrage opportunities
-        for i in 0..exchange_names.len() {
-            for j in (i + 1)..exchange_names.len() {
-                let exchange_a = &exchange_names[i];
-                let exchange_b = &exchange_names[j];
-
-                // Get common symbols between the two exchanges
-                let symbols_a: Vec<Symbol> = self
-                    .price_cache
-                    .get(exchange_a)
-                    .map(|c| c.keys().cloned().collect())
-                    .unwrap_or_default();
-                let symbols_b: Vec<Symbol> = self
-                    .price_cache
-                    .get(exchange_b)
-                    .map(|c| c.keys().cloned().collect())
-                    .unwrap_or_default();
-
-                for symbol in symbols_a.iter().filter(|s| symbols_b.contains(s)) {
-                    if let (Some(price_a), Some(price_b)) = (
-                        self.get_price_from_cache(exchange_a, symbol),
-                        self.get_price_from_cache(exchange_b, symbol),
-                    ) {
-                        // Check if there's an arbitrage opportunity
-                        // We need to compare bid on one exchange with ask on another
-                        // For simplicity, we'll use mid prices and assume we can buy at mid and sell at mid
-                        // In reality, we'd need to track best bid/ask separately
-                        if price_a < price_b {
-                            let spread = price_b - price_a;
-                            let spread_percentage = spread.value() / price_a.value();
-
-                            // Convert min_spread_bps from basis points to decimal (e.g., 5 bps = 0.0005)
-                            let min_spread_decimal =
-                                self.config.min_spread_bps / rust_decimal::Decimal::new(10000, 0);
-
-                            if spread_percentage >= min_spread_decimal {
-                                let estimated_profit =
-                                    spread.value() * self.config.max_position_size.value();
-                                let opportunity = ArbitrageOpportunity {
-                                    symbol: symbol.clone(),
-                                    exchange_buy: exchange_a.clone(),
-                                    exchange_sell: exchange_b.clone(),
-                                    price_buy: price_a,
-                                    price_sell: price_b,
-                                    spread,
-                                    spread_percentage,
-                                    estimated_profit,
-                                    timestamp: std::time::Instant::now(),
-                                };
-                                opportunities.push(opportunity);
-                            }
-                        } else if price_b < price_a {
-                            let spread = price_a - price_b;
-                            let spread_percentage = spread.value() / price_b.value();
-
-                            let min_spread_decimal =
-                                self.config.min_spread_bps / rust_decimal::Decimal::new(10000, 0);
-
-                            if spread_percentage >= min_spread_decimal {
-                                let estimated_profit =
-                                    spread.value() * self.config.max_position_size.value();
-                                let opportunity = ArbitrageOpportunity {
-                                    symbol: symbol.clone(),
-                                    exchange_buy: exchange_b.clone(),
-                                    exchange_sell: exchange_a.clone(),
-                                    price_buy: price_b,
-                                    price_sell: price_a,
-                                    spread,
-                                    spread_percentage,
-                                    estimated_profit,
-                                    timestamp: std::time::Instant::now(),
-                                };
-                                opportunities.push(opportunity);
-                            }
-                        }
-                    }
-                }
-            }
+    /// Price taking `size` on a venue's book, or at its touch quote if the book is not held
+    fn venue_ladder(
+        &self,
+        nbbo: &Nbbo,
+        quote: &VenueQuote,
+        side: OrderSide,
+        size: Size,
+    ) -> ExecutionLadder {
+        match self.book.venue_book(&nbbo.symbol, &quote.exchange_id) {
+            Some(book) => book.execution_ladder(side, size),
+            None => ExecutionLadder::walk(side, size, [(quote.price, quote.size)]),
         }
-
-        // Sort opportunities by estimated profit (descending)
-        opportunities.sort_by(|a, b| b.estimated_profit.cmp(&a.estimated_profit));
-        opportunities
     }
 
     /// Execute an arbitrage opportunity
@@ -347,11 +332,11 @@ impl ArbitrageStrategy {
             exchange_sell: opportunity.exchange_sell.clone(),
             price_buy: opportunity.price_buy,
             price_sell: opportunity.price_sell,
-            size: self.config.max_position_size,
+            size: opportunity.size,
             buy_order_id: None,
             sell_order_id: None,
             status: ArbitrageTradeStatus::Pending,
-            timestamp: std::time::Instant::now(),
+            timestamp: self.clock.now_instant(),
         };
 
         // Add to executed trades
@@ -365,7 +350,7 @@ impl ArbitrageStrategy {
 
     /// Clean up expired opportunities
     fn cleanup_expired_opportunities(&mut self) {
-        let now = std::time::Instant::now();
+        let now = self.clock.now_instant();
         self.state.active_opportunities.retain(|_, opportunity| {
             now.duration_since(opportunity.timestamp).as_millis()
                 < self.config.opportunity_timeout_ms as u128
@@ -373,6 +358,31 @@ impl ArbitrageStrategy {
     }
 }
 
+/// Size both legs can take while every level bought is below every level sold by
+/// at least `min_spread` (a fraction of the buy price)
+fn crossed_depth(
+    buy: &ExecutionLadder,
+    sell: &ExecutionLadder,
+    min_spread: rust_decimal::Decimal,
+) -> Size {
+    let (mut buys, mut sells) = (buy.steps.iter().peekable(), sell.steps.iter().peekable());
+    let mut depth = Size::zero();
+    while let (Some(bought), Some(sold)) = (buys.peek(), sells.peek()) {
+        let spread = (sold.price - bought.price).value() / bought.price.value();
+        if spread < min_spread {
+            break;
+        }
+        depth = bought.cumulative_size.min(sold.cumulative_size);
+        if bought.cumulative_size <= depth {
+            buys.next();
+        }
+        if sold.cumulative_size <= depth {
+            sells.next();
+        }
+    }
+    depth
+}
+
 #[async_trait]
 impl Strategy for ArbitrageStrategy {
     type Error = ArbitrageError;
@@ -380,43 +390,79 @@ impl Strategy for ArbitrageStrategy {
     async fn initialize(&mut self, config: StrategyConfig) -> Result<(), Self::Error> {
         info!("Initializing arbitrage strategy with config: {:?}", config);
 
-        // Initialize price cache for all exchanges
+        // Track the books of all exchanges
         for exchange_name in &config.exchanges {
-            if !self.price_cache.contains_key(exchange_name) {
-                self.price_cache
-                    .insert(exchange_name.clone(), HashMap::new());
-            }
+            self.book.add_venue(exchange_name.clone());
         }
 
         Ok(())
     }
 
-    async fn on_market_event(&mut self, event: MarketEvent) -> Result<Vec<Signal>, Self::Error> {
-        debug!("Processing market event: {:?}", event);
-
-        // Update price cache with new market data
-        match &event {
-            MarketEvent::OrderBookSnapshot(snapshot) => {
-                self.update_price_cache_from_snapshot(snapshot);
+    /// Accepts the `ArbitrageConfig` fields by name; open opportunities and books are kept
+    async fn update_config(&mut self, config: StrategyConfig) -> Result<(), Self::Error> {
+        let mut updated = self.config.clone();
+        let parse = |updated: &mut ArbitrageConfig| -> Result<(), String> {
+            config.check_parameters(&[
+                "min_spread_bps",
+                "max_position_size",
+                "max_exposure",
+                "slippage_tolerance",
+                "execution_delay_ms",
+                "opportunity_timeout_ms",
+            ])?;
+            if let Some(value) = config.parameter("min_spread_bps")? {
+                updated.min_spread_bps = value;
+            }
+            if let Some(value) = config.parameter("max_position_size")? {
+                updated.max_position_size = Size::new(value);
+            }
+            if let Some(value) = config.parameter("max_exposure")? {
+                updated.max_exposure = value;
+            }
+            if let Some(value) = config.parameter("slippage_tolerance")? {
+                updated.slippage_tolerance = value;
             }
-            MarketEvent::OrderBookDelta(delta) => {
-                self.update_price_cache_from_delta(delta);
+            if let Some(value) = config.parameter("execution_delay_ms")? {
+                updated.execution_delay_ms = value;
             }
-            MarketEvent::Trade(_) => {
-                // Trades don't directly update price cache
-                // In a real implementation, we might track trade prices
+            if let Some(value) = config.parameter("opportunity_timeout_ms")? {
+                updated.opportunity_timeout_ms = value;
             }
+            Ok(())
+        };
+        parse(&mut updated).map_err(ArbitrageError::new)?;
+        info!("Updated arbitrage config: {:?}", updated);
+        self.config = updated;
+        Ok(())
+    }
+
+    async fn on_market_event(&mut self, event: MarketEvent) -> Result<Vec<Signal>, Self::Error> {
+        crate::sampled_debug!(event.exchange_id(), "Processing market event: {:?}", event);
+
+        if let MarketEvent::Desync(desync) = &event {
+            // The consolidated book leaves the venue out until its book is resynced
+            warn!(
+                "Book for {} on {} out of sync: {}",
+                desync.symbol, desync.exchange_id, desync.error
+            );
+        }
+
+        if let (Some(sizer), MarketEvent::Trade(trade)) = (&mut self.order_sizer, &event) {
+            sizer.on_trade(trade);
         }
 
-        // Identify arbitrage opportunities
-        let opportunities = self.identify_opportunities();
+        // Identify arbitrage opportunities when the best prices across venues move
+        let opportunity = self
+            .book
+            .apply(&event)
+            .and_then(|nbbo| self.identify_opportunity(&nbbo));
 
         // Clean up expired opportunities
         self.cleanup_expired_opportunities();
 
         // Process new opportunities and generate signals
         let mut signals = Vec::new();
-        for opportunity in opportunities {
+        if let Some(opportunity) = opportunity {
             let opportunity_id = format!(
                 "{}_{}_{}",
                 opportunity.symbol.to_string(),
@@ -434,6 +480,21 @@ impl Strategy for ArbitrageStrategy {
                     .active_opportunities
                     .insert(opportunity_id.clone(), opportunity.clone());
 
+                if self.signal_only_venues.contains(&opportunity.exchange_buy)
+                    || self.signal_only_venues.contains(&opportunity.exchange_sell)
+                {
+                    info!(
+                        "Manual arbitrage opportunity on {}: buy at {} on {}, sell at {} on {} ({} bps)",
+                        opportunity.symbol,
+                        opportunity.price_buy,
+                        opportunity.exchange_buy,
+                        opportunity.price_sell,
+                        opportunity.exchange_sell,
+                        opportunity.spread_percentage * rust_decimal::Decimal::new(10000, 0)
+                    );
+                    return Ok(signals);
+                }
+
                 // Record the opportunity execution
                 let trade_id = self.record_opportunity_execution(opportunity.clone());
 
@@ -445,8 +506,9 @@ impl Strategy for ArbitrageStrategy {
                     order_type: OrderType::Limit,
                     time_in_force: TimeInForce::ImmediateOrCancel, // Use IOC for arbitrage
                     price: Some(opportunity.price_buy),
-                    size: self.config.max_position_size,
+                    size: opportunity.size,
                     client_order_id: Some(format!("arb_buy_{}", trade_id)),
+                    quote_size: None,
                 };
 
                 let buy_signal = Signal::PlaceOrder { order: buy_order };
@@ -478,8 +540,9 @@ impl Strategy for ArbitrageStrategy {
                         order_type: OrderType::Limit,
                         time_in_force: TimeInForce::ImmediateOrCancel,
                         price: Some(opportunity.price_sell),
-                        size: self.config.max_position_size,
+                        size: opportunity.size,
                         client_order_id: Some(format!("arb_sell_{}", trade_id)),
+                        quote_size: None,
                     };
 
                     let sell_signal = Signal::PlaceOrder { order: sell_order };
@@ -501,6 +564,23 @@ impl Strategy for ArbitrageStrategy {
         // Update arbitrage trade status based on execution reports
         match &event {
             TradingEvent::ExecutionReport(report) => {
+                // Feed how much of each finished leg filled back into the sizer
+                let is_leg = report
+                    .client_order_id
+                    .as_deref()
+                    .is_some_and(|id| id.starts_with("arb_"));
+                let finished = matches!(
+                    report.status,
+                    OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Expired
+                );
+                if let (Some(sizer), true, true) = (&mut self.order_sizer, is_leg, finished) {
+                    sizer.record_fill(
+                        report.symbol.value(),
+                        report.filled_size + report.remaining_size,
+                        report.filled_size,
+                    );
+                }
+
                 for trade in &mut self.state.executed_trades {
                     // Check if this execution report matches our buy order
                     if let Some(buy_order_id) = &trade.buy_order_id {
@@ -662,22 +742,128 @@ mod tests {
         let mut strategy = ArbitrageStrategy::new();
 
         strategy.initialize_exchange_cache("test".to_string());
-        assert!(strategy.price_cache.contains_key("test"));
+        assert!(strategy.book().has_venue("test"));
     }
 
     #[test]
-    fn test_get_price_from_cache() {
-        let mut strategy = ArbitrageStrategy::new();
-        strategy.initialize_exchange_cache("test".to_string());
+    fn test_identify_opportunity() {
+        use crate::orderbook::VenueQuote;
 
-        let symbol = Symbol::new("BTCUSDT");
-        let price = Price::from_str("50000.0").unwrap();
+        let strategy = ArbitrageStrategy::new();
+        let quote = |exchange: &str, price: &str| VenueQuote {
+            exchange_id: exchange.to_string(),
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str("1").unwrap(),
+        };
+        let mut nbbo = Nbbo {
+            symbol: Symbol::new("BTCUSDT"),
+            best_bid: Some(quote("okx", "50030")),
+            best_ask: Some(quote("binance", "50000")),
+            timestamp: 0,
+        };
 
-        if let Some(cache) = strategy.price_cache.get_mut("test") {
-            cache.insert(symbol.clone(), (price, std::time::Instant::now()));
-        }
+        let opportunity = strategy.identify_opportunity(&nbbo).unwrap();
+        assert_eq!(opportunity.exchange_buy, "binance");
+        assert_eq!(opportunity.exchange_sell, "okx");
+        assert_eq!(opportunity.spread, Price::from_str("30").unwrap());
+
+        // 0.02 bps is below the minimum spread
+        nbbo.best_bid = Some(quote("okx", "50000.1"));
+        assert!(strategy.identify_opportunity(&nbbo).is_none());
+    }
+
+    #[test]
+    fn test_order_sizer_sizes_legs() {
+        use crate::orderbook::VenueQuote;
+        use crate::strategies::order_sizer::OrderSizerConfig;
+
+        let config = ArbitrageConfig {
+            max_position_size: Size::from_str("0.4").unwrap(),
+            ..Default::default()
+        };
+        let quote = |exchange: &str, price: &str, size: &str| VenueQuote {
+            exchange_id: exchange.to_string(),
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str(size).unwrap(),
+        };
+        let nbbo = |ask_size: &str| Nbbo {
+            symbol: Symbol::new("BTCUSDT"),
+            best_bid: Some(quote("okx", "50030", "2")),
+            best_ask: Some(quote("binance", "50000", ask_size)),
+            timestamp: 0,
+        };
+        let strategy = ArbitrageStrategy::with_config(config).with_order_sizer(OrderSizer::new(
+            OrderSizerConfig {
+                max_size: Size::from_str("10").unwrap(),
+                ..Default::default()
+            },
+        ));
 
-        assert_eq!(strategy.get_price_from_cache("test", &symbol), Some(price));
+        // Half of the thinner book, then capped at the maximum position size
+        let opportunity = strategy.identify_opportunity(&nbbo("0.5")).unwrap();
+        assert_eq!(opportunity.size, Size::from_str("0.25").unwrap());
+        assert_eq!(
+            opportunity.estimated_profit,
+            rust_decimal::Decimal::new(75, 1)
+        );
+        let opportunity = strategy.identify_opportunity(&nbbo("5")).unwrap();
+        assert_eq!(opportunity.size, Size::from_str("0.4").unwrap());
+        assert!(strategy.identify_opportunity(&nbbo("0.001")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sizes_legs_from_book_depth() {
+        use crate::core::events::{OrderBookLevel, OrderBookSnapshot};
+
+        let mut strategy = ArbitrageStrategy::with_config(ArbitrageConfig {
+            max_position_size: Size::from_str("1").unwrap(),
+            ..Default::default()
+        });
+        strategy.initialize_exchange_cache("binance".to_string());
+        strategy.initialize_exchange_cache("okx".to_string());
+        let levels = |levels: &[(&str, &str)]| {
+            levels
+                .iter()
+                .map(|(price, size)| {
+                    OrderBookLevel::new(
+                        Price::from_str(price).unwrap(),
+                        Size::from_str(size).unwrap(),
+                    )
+                })
+                .collect()
+        };
+
+        strategy
+            .on_market_event(MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+                "BTCUSDT",
+                "binance",
+                levels(&[("49990", "1")]),
+                levels(&[("50000", "0.05"), ("50010", "0.05"), ("50100", "1")]),
+                0,
+            )))
+            .await
+            .unwrap();
+        let signals = strategy
+            .on_market_event(MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+                "BTCUSDT",
+                "okx",
+                levels(&[("50060", "0.03"), ("50040", "1")]),
+                levels(&[("50070", "1")]),
+                0,
+            )))
+            .await
+            .unwrap();
+
+        // Buying past 50010 no longer clears the minimum spread against any bid
+        let opportunity = strategy.state.active_opportunities.values().next().unwrap();
+        assert_eq!(opportunity.size, Size::from_str("0.1").unwrap());
+        assert_eq!(opportunity.price_buy, Price::from_str("50010").unwrap());
+        assert_eq!(opportunity.price_sell, Price::from_str("50040").unwrap());
+        assert_eq!(
+            opportunity.estimated_profit,
+            rust_decimal::Decimal::new(41, 1)
+        );
+        assert_eq!(signals.len(), 2);
     }
 
     #[tokio::test]
@@ -692,8 +878,47 @@ mod tests {
         };
 
         assert!(strategy.initialize(config).await.is_ok());
-        assert!(strategy.price_cache.contains_key("binance"));
-        assert!(strategy.price_cache.contains_key("okx"));
+        assert!(strategy.book().has_venue("binance"));
+        assert!(strategy.book().has_venue("okx"));
+    }
+
+    #[tokio::test]
+    async fn test_signal_only_venue_produces_no_orders() {
+        use crate::core::events::{OrderBookLevel, OrderBookSnapshot};
+
+        let mut strategy = ArbitrageStrategy::new().with_signal_only_venue("dex_0x");
+        strategy.initialize_exchange_cache("binance".to_string());
+        let book = |exchange: &str, bid: &str, ask: &str| {
+            MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+                "ETHUSDC",
+                exchange,
+                vec![OrderBookLevel::new(
+                    Price::from_str(bid).unwrap(),
+                    Size::from_str("1").unwrap(),
+                )],
+                vec![OrderBookLevel::new(
+                    Price::from_str(ask).unwrap(),
+                    Size::from_str("1").unwrap(),
+                )],
+                0,
+            ))
+        };
+
+        strategy
+            .on_market_event(book("binance", "2999", "3001"))
+            .await
+            .unwrap();
+        let signals = strategy
+            .on_market_event(book("dex_0x", "3049", "3051"))
+            .await
+            .unwrap();
+
+        assert!(signals.is_empty());
+        assert_eq!(strategy.state.active_opportunities.len(), 1);
+        assert!(strategy.state.executed_trades.is_empty());
+        let opportunity = strategy.state.active_opportunities.values().next().unwrap();
+        assert_eq!(opportunity.exchange_buy, "binance");
+        assert_eq!(opportunity.exchange_sell, "dex_0x");
     }
 
     #[tokio::test]
@@ -0,0 +1,194 @@
+use crate::types::Price;
+use rust_decimal::prelude::*;
+use std::collections::HashMap;
+
+/// Avellaneda-Stoikov model parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AvellanedaStoikovConfig {
+    /// Risk aversion (gamma): how strongly inventory skews the quotes
+    pub risk_aversion: f64,
+    /// Order book liquidity (kappa): how fast fill probability decays away from the mid
+    pub order_book_liquidity: f64,
+    /// Trading horizon in milliseconds; the time left restarts every horizon
+    pub horizon_ms: u64,
+    /// Decay of the EWMA volatility estimate per observation (0.0 to 1.0)
+    pub volatility_decay: f64,
+    /// Volatility in price units per square-root second used until the mid has moved
+    pub initial_volatility: f64,
+}
+
+impl Default for AvellanedaStoikovConfig {
+    fn default() -> Self {
+        Self {
+            risk_aversion: 0.1,
+            order_book_liquidity: 1.5,
+            horizon_ms: 3_600_000,
+            volatility_decay: 0.94,
+            initial_volatility: 0.0,
+        }
+    }
+}
+
+/// EWMA of the mid price variance per second
+#[derive(Debug, Clone, Copy)]
+struct VolatilityEstimate {
+    last_mid: f64,
+    last_timestamp: u64,
+    variance_rate: f64,
+}
+
+/// Avellaneda-Stoikov quoting model
+///
+/// Quotes around a reservation price `r = s - q * gamma * sigma^2 * (T - t)`, shifted
+/// away from the inventory `q`, with a total spread of
+/// `gamma * sigma^2 * (T - t) + (2 / gamma) * ln(1 + gamma / kappa)`. The volatility
+/// `sigma` is estimated per symbol from the observed mid prices, and `T - t` is the time
+/// left in the current horizon, in seconds.
+#[derive(Debug, Clone)]
+pub struct AvellanedaStoikov {
+    config: AvellanedaStoikovConfig,
+    volatility: HashMap<String, VolatilityEstimate>,
+}
+
+impl AvellanedaStoikov {
+    /// Create a model with the given parameters
+    pub fn new(config: AvellanedaStoikovConfig) -> Self {
+        Self {
+            config,
+            volatility: HashMap::new(),
+        }
+    }
+
+    /// Get the model parameters
+    pub fn config(&self) -> &AvellanedaStoikovConfig {
+        &self.config
+    }
+
+    /// Record a mid price observed at a time in milliseconds
+    pub fn observe(&mut self, symbol: &str, mid_price: Price, timestamp: u64) {
+        let Some(mid) = mid_price.value().to_f64() else {
+            return;
+        };
+        let initial_variance = self.config.initial_volatility.powi(2);
+        let decay = self.config.volatility_decay.clamp(0.0, 1.0);
+        let estimate = self
+            .volatility
+            .entry(symbol.to_string())
+            .or_insert(VolatilityEstimate {
+                last_mid: mid,
+                last_timestamp: timestamp,
+                variance_rate: initial_variance,
+            });
+        if timestamp <= estimate.last_timestamp {
+            return;
+        }
+        let elapsed_secs = (timestamp - estimate.last_timestamp) as f64 / 1000.0;
+        let change = mid - estimate.last_mid;
+        estimate.variance_rate =
+            decay * estimate.variance_rate + (1.0 - decay) * change * change / elapsed_secs;
+        estimate.last_mid = mid;
+        estimate.last_timestamp = timestamp;
+    }
+
+    /// Get the volatility estimate of a symbol in price units per square-root second
+    pub fn volatility(&self, symbol: &str) -> f64 {
+        self.volatility
+            .get(symbol)
+            .map_or(self.config.initial_volatility, |estimate| {
+                estimate.variance_rate.sqrt()
+            })
+    }
+
+    /// Get the time left in the current horizon at a time in milliseconds, in seconds
+    pub fn time_left_secs(&self, timestamp: u64) -> f64 {
+        let horizon_ms = self.config.horizon_ms.max(1);
+        (horizon_ms - timestamp % horizon_ms) as f64 / 1000.0
+    }
+
+    /// Get the reservation price for an inventory (in lots) at a time in milliseconds
+    pub fn reservation_price(
+        &self,
+        symbol: &str,
+        mid_price: Price,
+        inventory: f64,
+        timestamp: u64,
+    ) -> Price {
+        let sigma = self.volatility(symbol);
+        let shift =
+            inventory * self.config.risk_aversion * sigma * sigma * self.time_left_secs(timestamp);
+        Price::new(mid_price.value() - Decimal::from_f64(shift).unwrap_or(Decimal::ZERO))
+    }
+
+    /// Get the optimal total spread between bid and ask at a time in milliseconds
+    pub fn optimal_spread(&self, symbol: &str, timestamp: u64) -> Price {
+        let gamma = self.config.risk_aversion;
+        let sigma = self.volatility(symbol);
+        let inventory_term = gamma * sigma * sigma * self.time_left_secs(timestamp);
+        let liquidity_term = if gamma > 0.0 && self.config.order_book_liquidity > 0.0 {
+            (2.0 / gamma) * (1.0 + gamma / self.config.order_book_liquidity).ln()
+        } else {
+            0.0
+        };
+        Price::new(
+            Decimal::from_f64(inventory_term + liquidity_term)
+                .unwrap_or(Decimal::ZERO)
+                .max(Decimal::ZERO),
+        )
+    }
+
+    /// Get the bid and ask quotes for an inventory (in lots) at a time in milliseconds
+    pub fn quotes(
+        &self,
+        symbol: &str,
+        mid_price: Price,
+        inventory: f64,
+        timestamp: u64,
+    ) -> (Price, Price) {
+        let reservation = self.reservation_price(symbol, mid_price, inventory, timestamp);
+        let half_spread = self.optimal_spread(symbol, timestamp) / Decimal::TWO;
+        (reservation - half_spread, reservation + half_spread)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avellaneda_stoikov_quotes() {
+        let mut model = AvellanedaStoikov::new(AvellanedaStoikovConfig {
+            risk_aversion: 0.5,
+            order_book_liquidity: 1.0,
+            horizon_ms: 100_000,
+            volatility_decay: 0.0,
+            initial_volatility: 0.0,
+        });
+        let mid = Price::from_str("100").unwrap();
+
+        // A one-second move of 0.2 gives a volatility of 0.2 per root second
+        model.observe("BTCUSDT", mid, 1_000);
+        model.observe("BTCUSDT", Price::from_str("100.2").unwrap(), 2_000);
+        assert!((model.volatility("BTCUSDT") - 0.2).abs() < 1e-9);
+        assert_eq!(model.time_left_secs(2_000), 98.0);
+
+        // Flat inventory quotes symmetrically around the mid
+        let (bid, ask) = model.quotes("BTCUSDT", mid, 0.0, 2_000);
+        assert_eq!(bid + ask, mid * Decimal::TWO);
+        // 0.5 * 0.04 * 98 + 4 * ln(1.5)
+        let spread = (ask - bid).value().to_f64().unwrap();
+        assert!((spread - (1.96 + 4.0 * 1.5f64.ln())).abs() < 1e-6);
+
+        // Long inventory shifts both quotes down, short shifts them up
+        let (long_bid, long_ask) = model.quotes("BTCUSDT", mid, 2.0, 2_000);
+        assert!(long_bid < bid && long_ask < ask);
+        // 100 - 2 * 0.5 * 0.04 * 98
+        let reservation = model.reservation_price("BTCUSDT", mid, 2.0, 2_000);
+        assert!((reservation.value().to_f64().unwrap() - 96.08).abs() < 1e-6);
+        let (short_bid, _) = model.quotes("BTCUSDT", mid, -2.0, 2_000);
+        assert!(short_bid > bid);
+
+        // The inventory skew shrinks as the horizon runs out
+        let late = model.reservation_price("BTCUSDT", mid, 2.0, 99_000);
+        assert!(late > Price::from_str("99.9").unwrap());
+    }
+}
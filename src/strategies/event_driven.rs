@@ -32,6 +32,13 @@ where
             MarketEvent::OrderBookSnapshot(ref snapshot) => &snapshot.symbol,
             MarketEvent::OrderBookDelta(ref delta) => &delta.symbol,
             MarketEvent::Trade(ref trade) => &trade.symbol,
+            MarketEvent::BestBidAsk(ref quote) => &quote.symbol,
+            MarketEvent::Candle(ref candle) => &candle.symbol,
+            MarketEvent::Liquidation(ref liquidation) => &liquidation.symbol,
+            MarketEvent::OpenInterest(ref open_interest) => &open_interest.symbol,
+            MarketEvent::MarkPrice(ref mark_price) => &mark_price.symbol,
+            MarketEvent::Desync(ref desync) => &desync.symbol,
+            MarketEvent::BookMetrics(ref metrics) => &metrics.symbol,
         };
 
         let symbol_str = symbol.value().to_string();
@@ -42,6 +49,11 @@ where
 
         market_state.update(&event);
 
+        // Don't trade on a book that is out of sync with the venue
+        if market_state.is_desynced() {
+            return None;
+        }
+
         // Check if we should generate a signal
         let now = std::time::Instant::now();
         let last_signal = self.last_signal_time.get(&symbol_str);
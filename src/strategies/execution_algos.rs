@@ -0,0 +1,700 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::core::events::{
+    ExchangeId, ExecutionReport, MarketEvent, NewOrder, OrderSide, OrderStatus, Signal,
+    TimeInForce, Timestamp, TradingEvent,
+};
+use crate::traits::strategy::{Strategy, StrategyConfig, StrategyMetrics, StrategyState};
+use crate::types::{Price, Size};
+
+/// Error type for the execution algorithms
+#[derive(Debug, Clone)]
+pub struct ExecutionAlgoError {
+    pub message: String,
+}
+
+impl ExecutionAlgoError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ExecutionAlgoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "ExecutionAlgoError: {}", self.message)
+    }
+}
+
+impl std::error::Error for ExecutionAlgoError {}
+
+/// Parent order worked by an execution algorithm
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParentOrder {
+    pub symbol: String,
+    pub exchange_id: ExchangeId,
+    pub side: OrderSide,
+    pub size: Size,
+    /// Worst price a child order may be sent at
+    pub limit_price: Option<Price>,
+}
+
+impl ParentOrder {
+    /// Create a parent order without a limit price
+    pub fn new(
+        symbol: impl Into<String>,
+        exchange_id: impl Into<String>,
+        side: OrderSide,
+        size: Size,
+    ) -> Self {
+        Self {
+            symbol: symbol.into(),
+            exchange_id: exchange_id.into(),
+            side,
+            size,
+            limit_price: None,
+        }
+    }
+
+    /// Set the worst price child orders may be sent at (builder pattern)
+    pub fn with_limit_price(mut self, limit_price: Price) -> Self {
+        self.limit_price = Some(limit_price);
+        self
+    }
+}
+
+/// Fill state of a child order
+#[derive(Debug, Clone, Copy)]
+struct ChildOrder {
+    size: Size,
+    filled: Size,
+    average_price: Option<Price>,
+    done: bool,
+}
+
+/// Child order bookkeeping shared by the execution algorithms
+///
+/// Children are sent as IOC limit orders at the touch. Quantity left unfilled by a
+/// finished child goes back to the parent and is picked up by the next slice.
+#[derive(Debug, Clone)]
+struct SliceTracker {
+    id_prefix: String,
+    parent: ParentOrder,
+    best_bid: Option<Price>,
+    best_ask: Option<Price>,
+    /// Mid price when the first book for the parent was seen
+    arrival_price: Option<Price>,
+    start_time: Option<Timestamp>,
+    children: HashMap<String, ChildOrder>,
+    next_child: u64,
+}
+
+impl SliceTracker {
+    fn new(id_prefix: &str, parent: ParentOrder) -> Self {
+        Self {
+            id_prefix: id_prefix.to_string(),
+            parent,
+            best_bid: None,
+            best_ask: None,
+            arrival_price: None,
+            start_time: None,
+            children: HashMap::new(),
+            next_child: 0,
+        }
+    }
+
+    fn is_parent_market(&self, event: &MarketEvent) -> bool {
+        event.symbol() == self.parent.symbol && event.exchange_id() == self.parent.exchange_id
+    }
+
+    /// Update the touch from a book snapshot, starting the clock on the first one
+    fn observe_book(&mut self, event: &MarketEvent) {
+        let MarketEvent::OrderBookSnapshot(snapshot) = event else {
+            return;
+        };
+        if let Some(bid) = snapshot.bids.first() {
+            self.best_bid = Some(bid.price);
+        }
+        if let Some(ask) = snapshot.asks.first() {
+            self.best_ask = Some(ask.price);
+        }
+        if self.arrival_price.is_none() {
+            if let (Some(bid), Some(ask)) = (self.best_bid, self.best_ask) {
+                self.arrival_price = Some((bid + ask) / Decimal::TWO);
+                self.start_time = Some(snapshot.timestamp);
+            }
+        }
+    }
+
+    /// Quantity filled across all children
+    fn filled(&self) -> Size {
+        self.children
+            .values()
+            .fold(Size::zero(), |total, child| total + child.filled)
+    }
+
+    /// Quantity filled or still working
+    fn committed(&self) -> Size {
+        self.children.values().fold(Size::zero(), |total, child| {
+            total + if child.done { child.filled } else { child.size }
+        })
+    }
+
+    fn remaining(&self) -> Size {
+        self.parent.size - self.committed()
+    }
+
+    fn average_fill_price(&self) -> Option<Price> {
+        let filled = self.filled();
+        if filled.is_zero() {
+            return None;
+        }
+        let notional: Decimal = self
+            .children
+            .values()
+            .filter_map(|child| {
+                child
+                    .average_price
+                    .map(|price| price.value() * child.filled.value())
+            })
+            .sum();
+        Some(Price::new(notional / filled.value()))
+    }
+
+    /// Average fill price against the arrival price, in basis points (positive is worse)
+    fn slippage_bps(&self) -> Option<Decimal> {
+        let arrival = self.arrival_price.filter(|price| !price.is_zero())?;
+        let average = self.average_fill_price()?;
+        let slippage =
+            (average.value() - arrival.value()) / arrival.value() * Decimal::from(10_000);
+        Some(match self.parent.side {
+            OrderSide::Buy => slippage,
+            OrderSide::Sell => -slippage,
+        })
+    }
+
+    /// Send a child for up to `size`, unless the touch is beyond the parent's limit
+    fn child_order(&mut self, size: Size) -> Option<Signal> {
+        let size = if size > self.remaining() {
+            self.remaining()
+        } else {
+            size
+        };
+        if !size.is_positive() {
+            return None;
+        }
+        let price = match self.parent.side {
+            OrderSide::Buy => self.best_ask?,
+            OrderSide::Sell => self.best_bid?,
+        };
+        let beyond_limit = self
+            .parent
+            .limit_price
+            .is_some_and(|limit| match self.parent.side {
+                OrderSide::Buy => price > limit,
+                OrderSide::Sell => price < limit,
+            });
+        if beyond_limit {
+            debug!(
+                "Holding {} child for {}: touch {} is beyond the limit",
+                self.id_prefix, self.parent.symbol, price
+            );
+            return None;
+        }
+
+        let client_order_id = format!(
+            "{}_{}_{}",
+            self.id_prefix, self.parent.symbol, self.next_child
+        );
+        self.next_child += 1;
+        self.children.insert(
+            client_order_id.clone(),
+            ChildOrder {
+                size,
+                filled: Size::zero(),
+                average_price: None,
+                done: false,
+            },
+        );
+
+        let mut order = match self.parent.side {
+            OrderSide::Buy => NewOrder::new_limit_buy(
+                self.parent.symbol.clone(),
+                size,
+                price,
+                TimeInForce::ImmediateOrCancel,
+            ),
+            OrderSide::Sell => NewOrder::new_limit_sell(
+                self.parent.symbol.clone(),
+                size,
+                price,
+                TimeInForce::ImmediateOrCancel,
+            ),
+        };
+        order.exchange_id = self.parent.exchange_id.clone();
+        order.client_order_id = Some(client_order_id);
+        Some(Signal::PlaceOrder { order })
+    }
+
+    /// Record a fill update for one of the children
+    fn on_execution_report(&mut self, report: &ExecutionReport) {
+        let id = report.client_order_id.as_ref().unwrap_or(&report.order_id);
+        let Some(child) = self.children.get_mut(id) else {
+            return;
+        };
+        child.filled = report.filled_size;
+        if report.average_price.is_some() {
+            child.average_price = report.average_price;
+        }
+        child.done = matches!(
+            report.status,
+            OrderStatus::Filled
+                | OrderStatus::Cancelled
+                | OrderStatus::Rejected
+                | OrderStatus::Expired
+        );
+    }
+
+    fn metrics(&self) -> StrategyMetrics {
+        StrategyMetrics {
+            total_trades: self
+                .children
+                .values()
+                .filter(|child| child.filled.is_positive())
+                .count() as u64,
+            arrival_slippage_bps: self.slippage_bps().unwrap_or(Decimal::ZERO),
+            ..Default::default()
+        }
+    }
+}
+
+/// Time-weighted execution
+///
+/// Splits the parent order into equal slices sent at even intervals over the duration,
+/// starting with the first book seen for the symbol.
+#[derive(Debug, Clone)]
+pub struct TwapExecutor {
+    tracker: SliceTracker,
+    duration_ms: u64,
+    slices: u32,
+}
+
+impl TwapExecutor {
+    /// Create a TWAP executor sending `slices` children over `duration`
+    pub fn new(parent: ParentOrder, duration: Duration, slices: u32) -> Self {
+        Self {
+            tracker: SliceTracker::new("twap", parent),
+            duration_ms: (duration.as_millis() as u64).max(1),
+            slices: slices.max(1),
+        }
+    }
+
+    /// Get the parent order
+    pub fn parent(&self) -> &ParentOrder {
+        &self.tracker.parent
+    }
+
+    /// Get the mid price when execution started
+    pub fn arrival_price(&self) -> Option<Price> {
+        self.tracker.arrival_price
+    }
+
+    /// Get the quantity filled so far
+    pub fn filled(&self) -> Size {
+        self.tracker.filled()
+    }
+
+    /// Get the average fill price across all children
+    pub fn average_fill_price(&self) -> Option<Price> {
+        self.tracker.average_fill_price()
+    }
+
+    /// Check if the whole parent order has filled
+    pub fn is_complete(&self) -> bool {
+        self.tracker.filled() >= self.tracker.parent.size
+    }
+
+    /// Get the quantity that should be filled or working at a time in milliseconds
+    pub fn target_quantity(&self, timestamp: Timestamp) -> Size {
+        let Some(start) = self.tracker.start_time else {
+            return Size::zero();
+        };
+        let elapsed = timestamp.saturating_sub(start);
+        let due = (elapsed * self.slices as u64 / self.duration_ms + 1).min(self.slices as u64);
+        self.tracker.parent.size * Decimal::from(due) / Decimal::from(self.slices)
+    }
+}
+
+#[async_trait]
+impl Strategy for TwapExecutor {
+    type Error = ExecutionAlgoError;
+
+    async fn initialize(&mut self, _config: StrategyConfig) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// The schedule is fixed once started, so no parameters can change
+    async fn update_config(&mut self, config: StrategyConfig) -> Result<(), Self::Error> {
+        config
+            .check_parameters(&[])
+            .map_err(ExecutionAlgoError::new)
+    }
+
+    async fn on_market_event(&mut self, event: MarketEvent) -> Result<Vec<Signal>, Self::Error> {
+        if !self.tracker.is_parent_market(&event) {
+            return Ok(Vec::new());
+        }
+        self.tracker.observe_book(&event);
+
+        let shortfall = self.target_quantity(event.timestamp()) - self.tracker.committed();
+        Ok(self.tracker.child_order(shortfall).into_iter().collect())
+    }
+
+    async fn on_trading_event(&mut self, event: TradingEvent) -> Result<(), Self::Error> {
+        if let TradingEvent::ExecutionReport(report) = event {
+            self.tracker.on_execution_report(&report);
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> StrategyState {
+        StrategyState::Execution
+    }
+
+    fn get_metrics(&self) -> StrategyMetrics {
+        self.tracker.metrics()
+    }
+
+    async fn shutdown(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Volume-weighted execution
+///
+/// Keeps the parent order's fills at a fixed share of the volume traded in the symbol
+/// since execution started, so the order follows the market's own volume profile.
+#[derive(Debug, Clone)]
+pub struct VwapExecutor {
+    tracker: SliceTracker,
+    participation_rate: Decimal,
+    min_child_size: Size,
+    observed_volume: Size,
+}
+
+impl VwapExecutor {
+    /// Create a VWAP executor targeting a share of traded volume (e.g. 0.1 for 10%)
+    pub fn new(parent: ParentOrder, participation_rate: Decimal) -> Self {
+        Self {
+            tracker: SliceTracker::new("vwap", parent),
+            participation_rate,
+            min_child_size: Size::zero(),
+            observed_volume: Size::zero(),
+        }
+    }
+
+    /// Set the smallest child worth sending (builder pattern)
+    /// The last child of the parent order may be smaller.
+    pub fn with_min_child_size(mut self, min_child_size: Size) -> Self {
+        self.min_child_size = min_child_size;
+        self
+    }
+
+    /// Get the parent order
+    pub fn parent(&self) -> &ParentOrder {
+        &self.tracker.parent
+    }
+
+    /// Get the mid price when execution started
+    pub fn arrival_price(&self) -> Option<Price> {
+        self.tracker.arrival_price
+    }
+
+    /// Get the volume traded in the symbol since execution started
+    pub fn observed_volume(&self) -> Size {
+        self.observed_volume
+    }
+
+    /// Get the quantity filled so far
+    pub fn filled(&self) -> Size {
+        self.tracker.filled()
+    }
+
+    /// Get the average fill price across all children
+    pub fn average_fill_price(&self) -> Option<Price> {
+        self.tracker.average_fill_price()
+    }
+
+    /// Check if the whole parent order has filled
+    pub fn is_complete(&self) -> bool {
+        self.tracker.filled() >= self.tracker.parent.size
+    }
+
+    /// Get the quantity that should be filled or working given the observed volume
+    pub fn target_quantity(&self) -> Size {
+        let target = self.observed_volume * self.participation_rate;
+        if target > self.tracker.parent.size {
+            self.tracker.parent.size
+        } else {
+            target
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for VwapExecutor {
+    type Error = ExecutionAlgoError;
+
+    async fn initialize(&mut self, _config: StrategyConfig) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Accepts `participation_rate` and `min_child_size`
+    async fn update_config(&mut self, config: StrategyConfig) -> Result<(), Self::Error> {
+        let parse = || -> Result<(Option<Decimal>, Option<Size>), String> {
+            config.check_parameters(&["participation_rate", "min_child_size"])?;
+            Ok((
+                config.parameter("participation_rate")?,
+                config.parameter("min_child_size")?.map(Size::new),
+            ))
+        };
+        let (participation_rate, min_child_size) = parse().map_err(ExecutionAlgoError::new)?;
+        if let Some(rate) = participation_rate {
+            self.participation_rate = rate;
+        }
+        if let Some(size) = min_child_size {
+            self.min_child_size = size;
+        }
+        Ok(())
+    }
+
+    async fn on_market_event(&mut self, event: MarketEvent) -> Result<Vec<Signal>, Self::Error> {
+        if !self.tracker.is_parent_market(&event) {
+            return Ok(Vec::new());
+        }
+        self.tracker.observe_book(&event);
+        if let MarketEvent::Trade(trade) = &event {
+            // Volume before the first book is not part of the schedule
+            if self.tracker.start_time.is_some() {
+                self.observed_volume = self.observed_volume + trade.size;
+            }
+        }
+
+        let shortfall = self.target_quantity() - self.tracker.committed();
+        if shortfall < self.min_child_size && shortfall < self.tracker.remaining() {
+            return Ok(Vec::new());
+        }
+        Ok(self.tracker.child_order(shortfall).into_iter().collect())
+    }
+
+    async fn on_trading_event(&mut self, event: TradingEvent) -> Result<(), Self::Error> {
+        if let TradingEvent::ExecutionReport(report) = event {
+            self.tracker.on_execution_report(&report);
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> StrategyState {
+        StrategyState::Execution
+    }
+
+    fn get_metrics(&self) -> StrategyMetrics {
+        self.tracker.metrics()
+    }
+
+    async fn shutdown(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{OrderBookLevel, OrderBookSnapshot, Trade};
+
+    fn book(bid: &str, ask: &str, timestamp: Timestamp) -> MarketEvent {
+        let level = |price: &str| {
+            OrderBookLevel::new(
+                Price::from_str(price).unwrap(),
+                Size::from_str("5").unwrap(),
+            )
+        };
+        MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+            "BTCUSDT",
+            "binance",
+            vec![level(bid)],
+            vec![level(ask)],
+            timestamp,
+        ))
+    }
+
+    fn trade(size: &str, timestamp: Timestamp) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            symbol: "BTCUSDT".into(),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str("100").unwrap(),
+            size: Size::from_str(size).unwrap(),
+            side: OrderSide::Buy,
+            timestamp,
+            trade_id: None,
+        })
+    }
+
+    fn report(signal: &Signal, status: OrderStatus, filled: &str, price: &str) -> TradingEvent {
+        let Signal::PlaceOrder { order } = signal else {
+            panic!("Expected a child order");
+        };
+        let filled_size = Size::from_str(filled).unwrap();
+        TradingEvent::ExecutionReport(ExecutionReport {
+            order_id: "venue_id".to_string(),
+            client_order_id: order.client_order_id.clone(),
+            symbol: order.symbol.clone(),
+            exchange_id: order.exchange_id.clone(),
+            status,
+            filled_size,
+            remaining_size: order.size - filled_size,
+            average_price: Some(Price::from_str(price).unwrap()),
+            timestamp: 0,
+            reject_reason: None,
+        })
+    }
+
+    fn child_size(signal: &Signal) -> Size {
+        match signal {
+            Signal::PlaceOrder { order } => order.size,
+            _ => panic!("Expected a child order"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_twap_executor() {
+        let parent = ParentOrder::new(
+            "BTCUSDT",
+            "binance",
+            OrderSide::Buy,
+            Size::from_str("1").unwrap(),
+        );
+        let mut twap = TwapExecutor::new(parent, Duration::from_secs(4), 4);
+
+        // The first book sets the arrival price and sends the first slice at the ask
+        let signals = twap.on_market_event(book("99", "101", 0)).await.unwrap();
+        assert_eq!(twap.arrival_price(), Some(Price::from_str("100").unwrap()));
+        assert_eq!(signals.len(), 1);
+        let Signal::PlaceOrder { order } = &signals[0] else {
+            panic!("Expected a child order");
+        };
+        assert_eq!(order.size, Size::from_str("0.25").unwrap());
+        assert_eq!(order.price, Some(Price::from_str("101").unwrap()));
+        assert_eq!(order.time_in_force, TimeInForce::ImmediateOrCancel);
+        assert_eq!(order.exchange_id, "binance");
+        twap.on_trading_event(report(&signals[0], OrderStatus::Filled, "0.25", "101"))
+            .await
+            .unwrap();
+
+        // Nothing more until the next slice is due
+        assert!(twap
+            .on_market_event(book("99", "101", 500))
+            .await
+            .unwrap()
+            .is_empty());
+        let signals = twap
+            .on_market_event(book("99", "101", 1_000))
+            .await
+            .unwrap();
+        assert_eq!(child_size(&signals[0]), Size::from_str("0.25").unwrap());
+
+        // The unfilled part of a cancelled child rolls into the next slice
+        twap.on_trading_event(report(&signals[0], OrderStatus::Cancelled, "0.1", "101"))
+            .await
+            .unwrap();
+        let signals = twap
+            .on_market_event(book("99", "101", 2_000))
+            .await
+            .unwrap();
+        assert_eq!(child_size(&signals[0]), Size::from_str("0.4").unwrap());
+
+        // Paying 101 against a 100 arrival is 100 bps of slippage
+        let metrics = twap.get_metrics();
+        assert_eq!(metrics.total_trades, 2);
+        assert_eq!(metrics.arrival_slippage_bps, Decimal::from(100));
+        assert_eq!(twap.filled(), Size::from_str("0.35").unwrap());
+        assert!(!twap.is_complete());
+
+        // Other symbols are ignored
+        let other = MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+            "ETHUSDT",
+            "binance",
+            vec![],
+            vec![],
+            3_000,
+        ));
+        assert!(twap.on_market_event(other).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_vwap_executor() {
+        let parent = ParentOrder::new(
+            "BTCUSDT",
+            "binance",
+            OrderSide::Sell,
+            Size::from_str("1").unwrap(),
+        )
+        .with_limit_price(Price::from_str("98").unwrap());
+        let mut vwap = VwapExecutor::new(parent, Decimal::new(1, 1))
+            .with_min_child_size(Size::from_str("0.2").unwrap());
+
+        // Volume before the first book does not count
+        assert!(vwap
+            .on_market_event(trade("10", 0))
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(vwap
+            .on_market_event(book("99", "101", 0))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(vwap.observed_volume(), Size::zero());
+
+        // 10% of 1 is below the minimum child size
+        assert!(vwap
+            .on_market_event(trade("1", 100))
+            .await
+            .unwrap()
+            .is_empty());
+        let signals = vwap.on_market_event(trade("4", 200)).await.unwrap();
+        assert_eq!(child_size(&signals[0]), Size::from_str("0.5").unwrap());
+        vwap.on_trading_event(report(&signals[0], OrderStatus::Filled, "0.5", "99"))
+            .await
+            .unwrap();
+
+        // Selling at 99 against a 100 arrival is 100 bps of slippage
+        assert_eq!(vwap.get_metrics().arrival_slippage_bps, Decimal::from(100));
+
+        // No children below the limit price
+        assert!(vwap
+            .on_market_event(book("97", "99", 300))
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(vwap
+            .on_market_event(trade("10", 400))
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The target is capped at the parent size
+        let signals = vwap.on_market_event(book("99", "100", 500)).await.unwrap();
+        assert_eq!(vwap.target_quantity(), Size::from_str("1").unwrap());
+        assert_eq!(child_size(&signals[0]), Size::from_str("0.5").unwrap());
+        vwap.on_trading_event(report(&signals[0], OrderStatus::Filled, "0.5", "99"))
+            .await
+            .unwrap();
+        assert!(vwap.is_complete());
+    }
+}
@@ -0,0 +1,373 @@
+use crate::traits::OrderSide;
+use crate::types::{Price, Size, Symbol};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+
+/// How an order is worked against the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExecutionStyle {
+    /// Post at the touch on our own side, behind the existing queue
+    Passive,
+    /// Improve the touch by one tick, becoming the new best price
+    Join,
+    /// Take liquidity at the opposite touch
+    Cross,
+}
+
+/// Probability that a resting order fills within a horizon
+///
+/// Aggressive volume arriving at our price is modelled as exponential with mean
+/// `volume_rate * horizon_secs`; the order fills once that volume clears the queue
+/// ahead of it and its own size.
+pub fn fill_probability(queue_ahead: Size, size: Size, volume_rate: f64, horizon_secs: f64) -> f64 {
+    let needed = (queue_ahead.value() + size.value()).to_f64().unwrap_or(0.0);
+    let expected = volume_rate * horizon_secs;
+    if expected <= 0.0 {
+        return 0.0;
+    }
+    (-needed / expected).exp().clamp(0.0, 1.0)
+}
+
+/// Inputs to an execution style decision
+#[derive(Debug, Clone)]
+pub struct ExecutionRequest {
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub size: Size,
+    pub best_bid: Price,
+    pub best_ask: Price,
+    /// How urgently the order must fill, from 0 (patient) to 1 (now)
+    pub urgency: f64,
+    /// Estimated fill probability when posting at the touch
+    pub passive_fill_probability: f64,
+    /// Estimated fill probability when improving the touch by one tick
+    pub join_fill_probability: f64,
+}
+
+/// Execution style selector configuration
+#[derive(Debug, Clone)]
+pub struct ExecutionStyleConfig {
+    /// Price increment used when joining the spread
+    pub tick_size: Price,
+    /// Maker fee rate (negative for a rebate)
+    pub maker_fee: Decimal,
+    /// Taker fee rate
+    pub taker_fee: Decimal,
+    /// Expected cost in bps of missing a fill at full urgency, on top of crossing later
+    pub miss_penalty_bps: f64,
+    /// Urgency at or above which orders always cross
+    pub cross_urgency: f64,
+    /// Number of decisions kept for calibration
+    pub max_history: usize,
+}
+
+impl Default for ExecutionStyleConfig {
+    fn default() -> Self {
+        Self {
+            tick_size: Price::new(Decimal::new(1, 2)),
+            maker_fee: Decimal::new(2, 4),
+            taker_fee: Decimal::new(4, 4),
+            miss_penalty_bps: 10.0,
+            cross_urgency: 0.95,
+            max_history: 10_000,
+        }
+    }
+}
+
+/// What actually happened to an order after the decision
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionOutcome {
+    pub filled_size: Size,
+    pub average_price: Option<Price>,
+    /// Time from decision to the last fill
+    pub time_to_fill_ms: Option<u64>,
+}
+
+/// A recorded execution style decision
+#[derive(Debug, Clone)]
+pub struct ExecutionDecision {
+    pub id: u64,
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub size: Size,
+    pub urgency: f64,
+    pub style: ExecutionStyle,
+    /// Limit price for the chosen style
+    pub price: Price,
+    /// Fill probability the decision assumed
+    pub predicted_fill_probability: f64,
+    /// Expected cost versus the mid, in bps
+    pub expected_cost_bps: f64,
+    pub outcome: Option<ExecutionOutcome>,
+}
+
+impl ExecutionDecision {
+    /// Check if the order filled in full
+    pub fn filled(&self) -> Option<bool> {
+        self.outcome
+            .as_ref()
+            .map(|o| o.filled_size.value() >= self.size.value())
+    }
+}
+
+/// Realized fill rate for decisions with similar predicted fill probabilities
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationBucket {
+    /// Predicted probability range [low, high)
+    pub predicted_low: f64,
+    pub predicted_high: f64,
+    /// Decisions with a recorded outcome
+    pub decisions: usize,
+    pub fills: usize,
+}
+
+impl CalibrationBucket {
+    /// Fraction of decisions that filled
+    pub fn realized_rate(&self) -> Option<f64> {
+        (self.decisions > 0).then(|| self.fills as f64 / self.decisions as f64)
+    }
+}
+
+/// Chooses between posting passively, joining the spread, or crossing
+///
+/// Each style is scored by its expected cost versus the mid: the cost if it fills,
+/// weighted by its fill probability, plus the cost of crossing later and an
+/// urgency-scaled miss penalty if it does not. Decisions and their outcomes are kept
+/// so the fill probability estimates can be calibrated.
+#[derive(Debug)]
+pub struct ExecutionStyleSelector {
+    config: ExecutionStyleConfig,
+    decisions: VecDeque<ExecutionDecision>,
+    next_id: u64,
+}
+
+impl ExecutionStyleSelector {
+    /// Create a new selector
+    pub fn new(config: ExecutionStyleConfig) -> Self {
+        Self {
+            config,
+            decisions: VecDeque::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Price, fill probability and expected cost in bps of each available style
+    pub fn evaluate(&self, request: &ExecutionRequest) -> Vec<(ExecutionStyle, Price, f64, f64)> {
+        let bid = request.best_bid.value();
+        let ask = request.best_ask.value();
+        let mid = (bid + ask) / Decimal::TWO;
+        if mid <= Decimal::ZERO {
+            return Vec::new();
+        }
+        let tick = self.config.tick_size.value();
+        let bps = |price: Decimal, fee: Decimal| -> f64 {
+            // Positive cost means paying more than the mid (buys) or receiving less (sells)
+            let slippage = match request.side {
+                OrderSide::Buy => price - mid,
+                OrderSide::Sell => mid - price,
+            };
+            ((slippage / mid + fee) * Decimal::from(10_000))
+                .to_f64()
+                .unwrap_or(0.0)
+        };
+
+        let (touch, opposite, improved) = match request.side {
+            OrderSide::Buy => (bid, ask, bid + tick),
+            OrderSide::Sell => (ask, bid, ask - tick),
+        };
+        let cross_cost = bps(opposite, self.config.taker_fee);
+        let urgency = request.urgency.clamp(0.0, 1.0);
+        let miss_cost = cross_cost + urgency * self.config.miss_penalty_bps;
+        let expected = |probability: f64, filled_cost: f64| {
+            let p = probability.clamp(0.0, 1.0);
+            p * filled_cost + (1.0 - p) * miss_cost
+        };
+
+        let mut styles = vec![(ExecutionStyle::Cross, Price::new(opposite), 1.0, cross_cost)];
+        if urgency < self.config.cross_urgency {
+            styles.push((
+                ExecutionStyle::Passive,
+                Price::new(touch),
+                request.passive_fill_probability,
+                expected(
+                    request.passive_fill_probability,
+                    bps(touch, self.config.maker_fee),
+                ),
+            ));
+            // Joining is only possible while the improved price stays inside the spread
+            if tick > Decimal::ZERO && improved > bid && improved < ask {
+                styles.push((
+                    ExecutionStyle::Join,
+                    Price::new(improved),
+                    request.join_fill_probability,
+                    expected(
+                        request.join_fill_probability,
+                        bps(improved, self.config.maker_fee),
+                    ),
+                ));
+            }
+        }
+        styles
+    }
+
+    /// Choose a style for an order and record the decision
+    /// Returns None if the book has no valid mid
+    pub fn select(&mut self, request: &ExecutionRequest) -> Option<ExecutionDecision> {
+        let (style, price, probability, cost) = self
+            .evaluate(request)
+            .into_iter()
+            .min_by(|a, b| a.3.total_cmp(&b.3))?;
+
+        let decision = ExecutionDecision {
+            id: self.next_id,
+            symbol: request.symbol.clone(),
+            side: request.side,
+            size: request.size,
+            urgency: request.urgency,
+            style,
+            price,
+            predicted_fill_probability: probability,
+            expected_cost_bps: cost,
+            outcome: None,
+        };
+        self.next_id += 1;
+        self.decisions.push_back(decision.clone());
+        while self.decisions.len() > self.config.max_history {
+            self.decisions.pop_front();
+        }
+        Some(decision)
+    }
+
+    /// Record the outcome of a decision
+    /// Returns false if the decision is unknown or has aged out of the history
+    pub fn record_outcome(&mut self, decision_id: u64, outcome: ExecutionOutcome) -> bool {
+        match self.decisions.iter_mut().find(|d| d.id == decision_id) {
+            Some(decision) => {
+                decision.outcome = Some(outcome);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the recorded decisions, oldest first
+    pub fn decisions(&self) -> impl Iterator<Item = &ExecutionDecision> {
+        self.decisions.iter()
+    }
+
+    /// Compare predicted fill probabilities with realized fills for a style
+    pub fn calibration(&self, style: ExecutionStyle, buckets: usize) -> Vec<CalibrationBucket> {
+        let buckets = buckets.max(1);
+        let width = 1.0 / buckets as f64;
+        let mut result: Vec<CalibrationBucket> = (0..buckets)
+            .map(|i| CalibrationBucket {
+                predicted_low: i as f64 * width,
+                predicted_high: (i + 1) as f64 * width,
+                decisions: 0,
+                fills: 0,
+            })
+            .collect();
+
+        for decision in self.decisions.iter().filter(|d| d.style == style) {
+            let Some(filled) = decision.filled() else {
+                continue;
+            };
+            let index = ((decision.predicted_fill_probability / width) as usize).min(buckets - 1);
+            result[index].decisions += 1;
+            if filled {
+                result[index].fills += 1;
+            }
+        }
+        result
+    }
+}
+
+impl Default for ExecutionStyleSelector {
+    fn default() -> Self {
+        Self::new(ExecutionStyleConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(urgency: f64, passive: f64, join: f64) -> ExecutionRequest {
+        ExecutionRequest {
+            symbol: Symbol::new("BTCUSDT"),
+            side: OrderSide::Buy,
+            size: Size::from_str("1").unwrap(),
+            best_bid: Price::from_str("100.00").unwrap(),
+            best_ask: Price::from_str("100.10").unwrap(),
+            urgency,
+            passive_fill_probability: passive,
+            join_fill_probability: join,
+        }
+    }
+
+    #[test]
+    fn test_select_style() {
+        let mut selector = ExecutionStyleSelector::default();
+
+        // Patient with a likely passive fill: post at the touch
+        let decision = selector.select(&request(0.1, 0.9, 0.95)).unwrap();
+        assert_eq!(decision.style, ExecutionStyle::Passive);
+        assert_eq!(decision.price, Price::from_str("100.00").unwrap());
+
+        // Deep queue but joining would likely fill: improve by a tick
+        let decision = selector.select(&request(0.5, 0.1, 0.9)).unwrap();
+        assert_eq!(decision.style, ExecutionStyle::Join);
+        assert_eq!(decision.price, Price::from_str("100.01").unwrap());
+
+        // Urgent and unlikely to fill passively: cross
+        let decision = selector.select(&request(0.9, 0.05, 0.1)).unwrap();
+        assert_eq!(decision.style, ExecutionStyle::Cross);
+        assert_eq!(decision.price, Price::from_str("100.10").unwrap());
+        let decision = selector.select(&request(1.0, 0.99, 0.99)).unwrap();
+        assert_eq!(decision.style, ExecutionStyle::Cross);
+
+        assert!(
+            fill_probability(Size::zero(), Size::from_str("1").unwrap(), 10.0, 1.0)
+                > fill_probability(
+                    Size::from_str("20").unwrap(),
+                    Size::from_str("1").unwrap(),
+                    10.0,
+                    1.0
+                )
+        );
+    }
+
+    #[test]
+    fn test_calibration() {
+        let mut selector = ExecutionStyleSelector::default();
+        for i in 0..4 {
+            let decision = selector.select(&request(0.1, 0.9, 0.95)).unwrap();
+            let filled_size = if i == 0 { Size::zero() } else { decision.size };
+            assert!(selector.record_outcome(
+                decision.id,
+                ExecutionOutcome {
+                    filled_size,
+                    average_price: Some(decision.price),
+                    time_to_fill_ms: Some(250),
+                }
+            ));
+        }
+        assert!(!selector.record_outcome(
+            99,
+            ExecutionOutcome {
+                filled_size: Size::zero(),
+                average_price: None,
+                time_to_fill_ms: None,
+            }
+        ));
+
+        let buckets = selector.calibration(ExecutionStyle::Passive, 10);
+        assert_eq!(buckets[9].decisions, 4);
+        assert_eq!(buckets[9].realized_rate(), Some(0.75));
+        assert!(selector
+            .calibration(ExecutionStyle::Cross, 10)
+            .iter()
+            .all(|b| b.decisions == 0));
+    }
+}
@@ -1,8 +1,11 @@
-use crate::core::events::Trade;
+use crate::core::events::{Trade, TradingFees};
 use crate::indicators::trade_flow_indicators::{TradeFlowIndicator, TradeFlowMomentum};
+use crate::risk::rules::LiquidationCascadeRule;
+use crate::strategies::avellaneda::{AvellanedaStoikov, AvellanedaStoikovConfig};
 use crate::strategies::prediction::LinearRegressionPredictor;
+use crate::strategies::quote_guard::QuoteGuard;
 use crate::strategy::{MarketState, Signal, Strategy};
-use crate::traits::{NewOrder, OrderSide, TimeInForce};
+use crate::traits::{NewOrder, OrderSide, StrategyConfig, TimeInForce};
 use crate::types::{Price, Size};
 use rust_decimal::prelude::*;
 use std::collections::HashMap;
@@ -10,6 +13,7 @@ use std::time::{Duration, Instant};
 
 /// Market making strategy that places bid and ask orders around the current market price
 /// Can optionally use price prediction to improve order placement
+#[derive(Clone)]
 pub struct MarketMakingStrategy {
     /// Target spread to maintain between bid and ask orders
     target_spread: Price,
@@ -39,6 +43,14 @@ pub struct MarketMakingStrategy {
     prediction_horizon_seconds: u64,
     /// Weight for prediction adjustment (0.0 to 1.0)
     prediction_weight: f64,
+    /// Guard against quotes that would cross the spread
+    quote_guard: QuoteGuard,
+    /// Liquidation cascade rule used to widen spreads or pause quoting (optional)
+    liquidation_guard: Option<LiquidationCascadeRule>,
+    /// Venue fees used to decide which spreads are worth quoting (optional)
+    fees: Option<TradingFees>,
+    /// Inventory-aware quoting model replacing the fixed spread around the mid (optional)
+    avellaneda: Option<AvellanedaStoikov>,
 }
 
 impl MarketMakingStrategy {
@@ -65,6 +77,10 @@ impl MarketMakingStrategy {
             enable_prediction: false,
             prediction_horizon_seconds: 60,
             prediction_weight: 0.3,
+            quote_guard: QuoteGuard::default(),
+            liquidation_guard: None,
+            fees: None,
+            avellaneda: None,
         }
     }
 
@@ -97,9 +113,68 @@ impl MarketMakingStrategy {
             enable_prediction: true,
             prediction_horizon_seconds,
             prediction_weight: prediction_weight.max(0.0).min(1.0),
+            quote_guard: QuoteGuard::default(),
+            liquidation_guard: None,
+            fees: None,
+            avellaneda: None,
         }
     }
 
+    /// Set the guard used to prevent spread-crossing quotes (builder pattern)
+    pub fn with_quote_guard(mut self, quote_guard: QuoteGuard) -> Self {
+        self.quote_guard = quote_guard;
+        self
+    }
+
+    /// Get the quote guard
+    pub fn quote_guard(&self) -> &QuoteGuard {
+        &self.quote_guard
+    }
+
+    /// Widen spreads or pause quoting based on liquidation activity (builder pattern)
+    pub fn with_liquidation_guard(mut self, rule: LiquidationCascadeRule) -> Self {
+        self.liquidation_guard = Some(rule);
+        self
+    }
+
+    /// Quote with the Avellaneda-Stoikov model (builder pattern)
+    /// The first level is quoted around the inventory-skewed reservation price at the
+    /// model's optimal spread, with inventory counted in base order sizes; further levels
+    /// step out by the target spread.
+    pub fn with_avellaneda_stoikov(mut self, config: AvellanedaStoikovConfig) -> Self {
+        self.avellaneda = Some(AvellanedaStoikov::new(config));
+        self
+    }
+
+    /// Get the Avellaneda-Stoikov model, if quoting with it
+    pub fn avellaneda_stoikov(&self) -> Option<&AvellanedaStoikov> {
+        self.avellaneda.as_ref()
+    }
+
+    /// Account for the venue's maker fees when deciding which spreads to quote (builder pattern)
+    /// A maker rebate lets the strategy quote markets tighter than the target spread.
+    pub fn with_fees(mut self, fees: TradingFees) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Get the smallest market spread worth quoting at a mid price
+    /// This is the target spread plus the maker fees of a round trip, or less the
+    /// rebate it earns, and never below zero.
+    pub fn min_profitable_spread(&self, mid_price: Price) -> Price {
+        let fees = self
+            .fees
+            .as_ref()
+            .map(|fees| fees.round_trip_maker_cost(mid_price))
+            .unwrap_or(Decimal::ZERO);
+        Price::new((self.target_spread.value() + fees).max(Decimal::ZERO))
+    }
+
+    /// Explicitly allow (or forbid) quotes that cross the spread
+    pub fn set_allow_cross(&mut self, allow_cross: bool) {
+        self.quote_guard.set_allow_cross(allow_cross);
+    }
+
     /// Enable or disable prediction
     pub fn set_prediction_enabled(&mut self, enabled: bool) {
         self.enable_prediction = enabled;
@@ -257,8 +332,11 @@ impl MarketMakingStrategy {
         best_bid: Price,
         best_ask: Price,
         inventory_skew: f64,
+        spread_multiplier: Decimal,
         symbol: &str,
+        timestamp: u64,
     ) -> (Vec<Price>, Vec<Price>) {
+        let target_spread = self.target_spread * spread_multiplier;
         let mut mid_price = best_bid + (best_ask - best_bid) / Decimal::new(2, 0);
 
         // Apply prediction adjustment if enabled
@@ -268,16 +346,39 @@ impl MarketMakingStrategy {
             mid_price = mid_price + Price::new(adjustment);
         }
 
+        if let Some(model) = &self.avellaneda {
+            let inventory = if self.base_order_size.is_zero() {
+                0.0
+            } else {
+                (self.get_position(symbol).value() / self.base_order_size.value())
+                    .to_f64()
+                    .unwrap_or(0.0)
+            };
+            let reservation = model.reservation_price(symbol, mid_price, inventory, timestamp);
+            let half_spread =
+                model.optimal_spread(symbol, timestamp) * spread_multiplier / Decimal::TWO;
+            let levels = (0..self.max_order_levels)
+                .map(|i| target_spread * Decimal::from_usize(i).unwrap_or(Decimal::ZERO));
+            let bid_prices = levels
+                .clone()
+                .map(|offset| reservation - half_spread - offset)
+                .collect();
+            let ask_prices = levels
+                .map(|offset| reservation + half_spread + offset)
+                .collect();
+            return (bid_prices, ask_prices);
+        }
+
         // Calculate bid prices (below mid price)
         let mut bid_prices = Vec::new();
         for i in 0..self.max_order_levels {
             // Adjust spread based on inventory skew
             let spread_adjustment = if inventory_skew > 1.0 {
                 // We're short, tighten bid prices (move closer to mid)
-                self.target_spread * Decimal::from_f64(2.0 - inventory_skew).unwrap_or(Decimal::ONE)
+                target_spread * Decimal::from_f64(2.0 - inventory_skew).unwrap_or(Decimal::ONE)
             } else {
                 // We're long, widen bid prices (move further from mid)
-                self.target_spread * Decimal::from_f64(inventory_skew).unwrap_or(Decimal::ONE)
+                target_spread * Decimal::from_f64(inventory_skew).unwrap_or(Decimal::ONE)
             };
 
             let price_offset =
@@ -292,10 +393,10 @@ impl MarketMakingStrategy {
             // Adjust spread based on inventory skew
             let spread_adjustment = if inventory_skew < 1.0 {
                 // We're long, tighten ask prices (move closer to mid)
-                self.target_spread * Decimal::from_f64(inventory_skew).unwrap_or(Decimal::ONE)
+                target_spread * Decimal::from_f64(inventory_skew).unwrap_or(Decimal::ONE)
             } else {
                 // We're short, widen ask prices (move further from mid)
-                self.target_spread * Decimal::from_f64(2.0 - inventory_skew).unwrap_or(Decimal::ONE)
+                target_spread * Decimal::from_f64(2.0 - inventory_skew).unwrap_or(Decimal::ONE)
             };
 
             let price_offset =
@@ -384,29 +485,51 @@ impl Strategy for MarketMakingStrategy {
     fn generate_signal(&mut self, market_state: &MarketState) -> Option<Signal> {
         let symbol = &market_state.symbol;
 
+        // Get best bid and ask
+        let (best_bid_price, _best_bid_size) = market_state.best_bid()?;
+        let (best_ask_price, _best_ask_size) = market_state.best_ask()?;
+
+        // The volatility estimate needs every book update, not just the ones we quote on
+        if let Some(model) = &mut self.avellaneda {
+            let mid_price = best_bid_price + (best_ask_price - best_bid_price) / Decimal::TWO;
+            model.observe(symbol, mid_price, market_state.last_update);
+        }
+
         // Check if we should refresh orders
         if !self.should_refresh_orders(symbol) {
             return None;
         }
 
-        // Get best bid and ask
-        let (best_bid_price, _best_bid_size) = market_state.best_bid()?;
-        let (best_ask_price, _best_ask_size) = market_state.best_ask()?;
-
         // Calculate current spread
         let current_spread = best_ask_price - best_bid_price;
 
-        // If spread is too small, don't place orders
-        if current_spread < self.target_spread {
+        // If spread is too small to pay for the round trip, don't place orders
+        let mid_price = best_bid_price + (best_ask_price - best_bid_price) / Decimal::TWO;
+        if current_spread < self.min_profitable_spread(mid_price) {
             return None;
         }
 
+        // Widen spreads while liquidations are elevated; pull quotes during a cascade
+        let spread_multiplier = match &self.liquidation_guard {
+            Some(guard) => match guard.spread_multiplier(symbol) {
+                Some(multiplier) => multiplier,
+                None => return self.cancel_all_orders(symbol).into_iter().next(),
+            },
+            None => Decimal::ONE,
+        };
+
         // Calculate inventory skew
         let inventory_skew = self.calculate_inventory_skew(symbol);
 
         // Calculate order prices and sizes
-        let (bid_prices, ask_prices) =
-            self.calculate_order_prices(best_bid_price, best_ask_price, inventory_skew, symbol);
+        let (bid_prices, ask_prices) = self.calculate_order_prices(
+            best_bid_price,
+            best_ask_price,
+            inventory_skew,
+            spread_multiplier,
+            symbol,
+            market_state.last_update,
+        );
 
         let (bid_sizes, ask_sizes) = self.calculate_order_sizes(inventory_skew);
 
@@ -420,10 +543,22 @@ impl Strategy for MarketMakingStrategy {
         // Add bid orders
         for (i, (price, size)) in bid_prices.iter().zip(bid_sizes.iter()).enumerate() {
             if self.can_place_order(symbol, OrderSide::Buy, *size) {
+                let Some(price) = self
+                    .quote_guard
+                    .check(
+                        OrderSide::Buy,
+                        *price,
+                        Some(best_bid_price),
+                        Some(best_ask_price),
+                    )
+                    .price()
+                else {
+                    continue;
+                };
                 let order = NewOrder::new_limit_buy(
                     symbol.clone(),
                     *size,
-                    *price,
+                    price,
                     TimeInForce::GoodTillCancelled,
                 )
                 .with_client_order_id(format!("mm_bid_{}_{}", symbol, i));
@@ -438,10 +573,22 @@ impl Strategy for MarketMakingStrategy {
         // Add ask orders
         for (i, (price, size)) in ask_prices.iter().zip(ask_sizes.iter()).enumerate() {
             if self.can_place_order(symbol, OrderSide::Sell, *size) {
+                let Some(price) = self
+                    .quote_guard
+                    .check(
+                        OrderSide::Sell,
+                        *price,
+                        Some(best_bid_price),
+                        Some(best_ask_price),
+                    )
+                    .price()
+                else {
+                    continue;
+                };
                 let order = NewOrder::new_limit_sell(
                     symbol.clone(),
                     *size,
-                    *price,
+                    price,
                     TimeInForce::GoodTillCancelled,
                 )
                 .with_client_order_id(format!("mm_ask_{}_{}", symbol, i));
@@ -471,6 +618,57 @@ impl Strategy for MarketMakingStrategy {
             None
         }
     }
+
+    /// Accepts `target_spread`, `base_order_size`, `max_position_size`, `max_order_levels`,
+    /// `order_refresh_time_ms` and `prediction_weight`; positions and working orders are kept
+    fn update_config(&mut self, config: StrategyConfig) -> Result<(), String> {
+        config.check_parameters(&[
+            "target_spread",
+            "base_order_size",
+            "max_position_size",
+            "max_order_levels",
+            "order_refresh_time_ms",
+            "prediction_weight",
+        ])?;
+        let target_spread = config.parameter::<Decimal>("target_spread")?;
+        let base_order_size = config.parameter::<Decimal>("base_order_size")?;
+        let max_position_size = config.parameter::<Decimal>("max_position_size")?;
+        let max_order_levels = config.parameter::<usize>("max_order_levels")?;
+        let order_refresh_time_ms = config.parameter::<u64>("order_refresh_time_ms")?;
+        let prediction_weight = config.parameter::<f64>("prediction_weight")?;
+        for (name, value) in [
+            ("target_spread", target_spread),
+            ("base_order_size", base_order_size),
+            ("max_position_size", max_position_size),
+        ] {
+            if value.is_some_and(|value| value <= Decimal::ZERO) {
+                return Err(format!("{} must be positive", name));
+            }
+        }
+        if max_order_levels == Some(0) {
+            return Err("max_order_levels must be at least 1".to_string());
+        }
+
+        if let Some(spread) = target_spread {
+            self.target_spread = Price::new(spread);
+        }
+        if let Some(size) = base_order_size {
+            self.base_order_size = Size::new(size);
+        }
+        if let Some(size) = max_position_size {
+            self.max_position_size = Size::new(size);
+        }
+        if let Some(levels) = max_order_levels {
+            self.max_order_levels = levels;
+        }
+        if let Some(refresh_ms) = order_refresh_time_ms {
+            self.order_refresh_time = Duration::from_millis(refresh_ms);
+        }
+        if let Some(weight) = prediction_weight {
+            self.prediction_weight = weight.clamp(0.0, 1.0);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -479,6 +677,46 @@ mod tests {
     use crate::orderbook::{OrderBookLevel, OrderBookSnapshot};
     use crate::traits::MarketEvent;
 
+    #[test]
+    fn test_update_config_keeps_positions() {
+        let mut strategy = MarketMakingStrategy::new(
+            Price::from_str("0.5").unwrap(),
+            Size::from_str("0.1").unwrap(),
+            Size::from_str("1.0").unwrap(),
+            5,
+            Duration::from_millis(100),
+        );
+        strategy.update_position("BTCUSDT", Size::from_str("0.3").unwrap());
+
+        let mut config = StrategyConfig::default();
+        config
+            .parameters
+            .insert("target_spread".to_string(), "0.8".to_string());
+        config
+            .parameters
+            .insert("order_refresh_time_ms".to_string(), "250".to_string());
+        strategy.update_config(config).unwrap();
+        assert_eq!(strategy.target_spread(), Price::from_str("0.8").unwrap());
+        assert_eq!(strategy.order_refresh_time(), Duration::from_millis(250));
+        assert_eq!(strategy.base_order_size(), Size::from_str("0.1").unwrap());
+        assert_eq!(
+            strategy.get_position("BTCUSDT"),
+            Size::from_str("0.3").unwrap()
+        );
+
+        // One bad parameter rejects the whole update
+        let mut config = StrategyConfig::default();
+        config
+            .parameters
+            .insert("base_order_size".to_string(), "0.2".to_string());
+        config
+            .parameters
+            .insert("max_order_levels".to_string(), "0".to_string());
+        assert!(strategy.update_config(config).is_err());
+        assert_eq!(strategy.base_order_size(), Size::from_str("0.1").unwrap());
+        assert_eq!(strategy.max_order_levels(), 5);
+    }
+
     #[test]
     fn test_market_making_strategy_creation() {
         let strategy = MarketMakingStrategy::new(
@@ -664,4 +902,279 @@ mod tests {
         let signal2 = strategy.generate_signal(&market_state);
         assert!(signal2.is_none());
     }
+
+    #[test]
+    fn test_quote_guard_configuration() {
+        let mut strategy = MarketMakingStrategy::new(
+            Price::from_str("0.5").unwrap(),
+            Size::from_str("0.1").unwrap(),
+            Size::from_str("1.0").unwrap(),
+            1,
+            Duration::from_millis(100),
+        )
+        .with_quote_guard(QuoteGuard::new(
+            crate::strategies::quote_guard::CrossAction::Reprice,
+            Price::from_str("0.01").unwrap(),
+        ));
+
+        assert!(!strategy.quote_guard().allow_cross());
+        strategy.set_allow_cross(true);
+        assert!(strategy.quote_guard().allow_cross());
+        strategy.set_allow_cross(false);
+
+        let mut market_state = MarketState::new("BTCUSDT".to_string());
+        let snapshot = OrderBookSnapshot::new(
+            "BTCUSDT".to_string(),
+            "binance".to_string(),
+            vec![OrderBookLevel::new(
+                Price::from_str("100.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            vec![OrderBookLevel::new(
+                Price::from_str("101.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            123456789,
+        );
+        market_state.update(&MarketEvent::OrderBookSnapshot(snapshot));
+
+        // Generated quotes stay on their own side of the book
+        match strategy.generate_signal(&market_state) {
+            Some(Signal::PlaceOrder { order }) => {
+                assert_eq!(order.side, OrderSide::Buy);
+                assert!(order.price.unwrap() < Price::from_str("101.00").unwrap());
+            }
+            other => panic!("Expected place order signal, got {:?}", other),
+        }
+        assert_eq!(strategy.quote_guard().crossings_prevented(), 0);
+    }
+
+    #[test]
+    fn test_liquidation_guard_widens_and_pauses() {
+        use crate::core::events::Liquidation;
+        use crate::types::{Notional, Symbol};
+
+        let rule = LiquidationCascadeRule::new(
+            60_000,
+            Notional::new(Decimal::new(1_000, 0)),
+            Notional::new(Decimal::new(10_000, 0)),
+        );
+        let mut strategy = MarketMakingStrategy::new(
+            Price::from_str("0.5").unwrap(),
+            Size::from_str("0.1").unwrap(),
+            Size::from_str("1.0").unwrap(),
+            1,
+            Duration::from_millis(0),
+        )
+        .with_liquidation_guard(rule.clone());
+
+        let mut market_state = MarketState::new("BTCUSDT".to_string());
+        let snapshot = OrderBookSnapshot::new(
+            "BTCUSDT".to_string(),
+            "binance".to_string(),
+            vec![OrderBookLevel::new(
+                Price::from_str("100.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            vec![OrderBookLevel::new(
+                Price::from_str("102.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            123456789,
+        );
+        market_state.update(&MarketEvent::OrderBookSnapshot(snapshot));
+
+        let bid_price = |signal: Option<Signal>| match signal {
+            Some(Signal::PlaceOrder { order }) => order.price.unwrap(),
+            other => panic!("Expected place order signal, got {:?}", other),
+        };
+        assert_eq!(
+            bid_price(strategy.generate_signal(&market_state)),
+            Price::from_str("100.5").unwrap()
+        );
+
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let liquidation = |size: &str| Liquidation {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance_futures".to_string(),
+            side: OrderSide::Sell,
+            price: Price::from_str("100").unwrap(),
+            size: Size::from_str(size).unwrap(),
+            timestamp: now,
+        };
+
+        // Elevated liquidations double the spread
+        rule.record(&liquidation("20"));
+        strategy.active_orders.clear();
+        assert_eq!(
+            bid_price(strategy.generate_signal(&market_state)),
+            Price::from_str("100.0").unwrap()
+        );
+
+        // A cascade pulls the resting quotes
+        rule.record(&liquidation("100"));
+        assert!(matches!(
+            strategy.generate_signal(&market_state),
+            Some(Signal::CancelAllOrders { .. })
+        ));
+        assert!(strategy.generate_signal(&market_state).is_none());
+    }
+
+    #[test]
+    fn test_maker_rebate_lowers_min_profitable_spread() {
+        let mut market_state = MarketState::new("BTCUSDT".to_string());
+        market_state.update(&MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+            "BTCUSDT".to_string(),
+            "binance".to_string(),
+            vec![OrderBookLevel::new(
+                Price::from_str("100.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            vec![OrderBookLevel::new(
+                Price::from_str("101.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            123456789,
+        )));
+        let strategy = |target_spread: &str, maker_fee: Option<&str>| {
+            let strategy = MarketMakingStrategy::new(
+                Price::from_str(target_spread).unwrap(),
+                Size::from_str("0.1").unwrap(),
+                Size::from_str("1.0").unwrap(),
+                1,
+                Duration::from_millis(100),
+            );
+            match maker_fee {
+                Some(fee) => strategy.with_fees(TradingFees::new(
+                    "BTCUSDT".to_string(),
+                    Size::from_str(fee).unwrap(),
+                    Size::from_str("0.001").unwrap(),
+                )),
+                None => strategy,
+            }
+        };
+
+        // The 1.00 market is tighter than the 1.20 target
+        assert!(strategy("1.2", None)
+            .generate_signal(&market_state)
+            .is_none());
+
+        // A 10 bps rebate earns 0.201 per round trip at 100.5, enough to quote it
+        let mut rebated = strategy("1.2", Some("-0.001"));
+        assert_eq!(
+            rebated.min_profitable_spread(Price::from_str("100.5").unwrap()),
+            Price::from_str("0.999").unwrap()
+        );
+        assert!(rebated.generate_signal(&market_state).is_some());
+
+        // A 10 bps fee costs the same, so a 0.90 target no longer fits
+        assert!(strategy("0.9", None)
+            .generate_signal(&market_state)
+            .is_some());
+        assert!(strategy("0.9", Some("0.001"))
+            .generate_signal(&market_state)
+            .is_none());
+    }
+
+    #[test]
+    fn test_avellaneda_stoikov_skews_quotes_with_inventory() {
+        let config = AvellanedaStoikovConfig {
+            risk_aversion: 0.5,
+            order_book_liquidity: 1.0,
+            horizon_ms: 100_000,
+            volatility_decay: 0.0,
+            initial_volatility: 0.1,
+        };
+        let strategy = || {
+            MarketMakingStrategy::new(
+                Price::from_str("0.5").unwrap(),
+                Size::from_str("0.1").unwrap(),
+                Size::from_str("10.0").unwrap(),
+                2,
+                Duration::from_millis(100),
+            )
+            .with_avellaneda_stoikov(config)
+        };
+        let mut market_state = MarketState::new("BTCUSDT".to_string());
+        market_state.update(&MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+            "BTCUSDT".to_string(),
+            "binance".to_string(),
+            vec![OrderBookLevel::new(
+                Price::from_str("99.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            vec![OrderBookLevel::new(
+                Price::from_str("101.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            2_000,
+        )));
+        let first_bid =
+            |strategy: &mut MarketMakingStrategy| match strategy.generate_signal(&market_state) {
+                Some(Signal::PlaceOrder { order }) => {
+                    assert_eq!(order.side, OrderSide::Buy);
+                    order.price.unwrap()
+                }
+                other => panic!("expected a bid, got {:?}", other),
+            };
+
+        // Flat: half of 0.5 * 0.01 * 98 + 4 * ln(1.5) below the mid
+        let mut flat = strategy();
+        let flat_bid = first_bid(&mut flat);
+        let expected = 100.0 - (0.49 + 4.0 * 1.5f64.ln()) / 2.0;
+        assert!((flat_bid.value().to_f64().unwrap() - expected).abs() < 1e-6);
+
+        // Five lots long moves the reservation price 5 * 0.5 * 0.01 * 98 lower
+        let mut long = strategy();
+        long.update_position("BTCUSDT", Size::from_str("0.5").unwrap());
+        let long_bid = first_bid(&mut long);
+        assert!(((flat_bid - long_bid).value().to_f64().unwrap() - 2.45).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_avellaneda_stoikov_crossing_quotes_are_rejected() {
+        let mut strategy = MarketMakingStrategy::new(
+            Price::from_str("0.5").unwrap(),
+            Size::from_str("0.1").unwrap(),
+            Size::from_str("10.0").unwrap(),
+            1,
+            Duration::from_millis(100),
+        )
+        .with_avellaneda_stoikov(AvellanedaStoikovConfig {
+            risk_aversion: 0.5,
+            order_book_liquidity: 1.0,
+            horizon_ms: 100_000,
+            volatility_decay: 0.0,
+            initial_volatility: 0.1,
+        });
+        // Five lots long puts the reservation price at 97.55 and the raw ask near 98.6,
+        // below the best bid
+        strategy.update_position("BTCUSDT", Size::from_str("0.5").unwrap());
+
+        let mut market_state = MarketState::new("BTCUSDT".to_string());
+        market_state.update(&MarketEvent::OrderBookSnapshot(OrderBookSnapshot::new(
+            "BTCUSDT".to_string(),
+            "binance".to_string(),
+            vec![OrderBookLevel::new(
+                Price::from_str("99.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            vec![OrderBookLevel::new(
+                Price::from_str("101.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            2_000,
+        )));
+
+        match strategy.generate_signal(&market_state) {
+            Some(Signal::PlaceOrder { order }) => {
+                assert_eq!(order.side, OrderSide::Buy);
+                assert!(order.price.unwrap() < Price::from_str("99.00").unwrap());
+            }
+            other => panic!("expected a bid, got {:?}", other),
+        }
+        assert_eq!(strategy.quote_guard().crossings_prevented(), 1);
+        let working = &strategy.active_orders["BTCUSDT"];
+        assert!(working.iter().all(|order| order.side == OrderSide::Buy));
+    }
 }
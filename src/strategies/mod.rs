@@ -1,15 +1,28 @@
 pub mod arbitrage;
+pub mod avellaneda;
 pub mod event_driven;
+pub mod execution_algos;
+pub mod execution_style;
 pub mod market_making;
+pub mod order_sizer;
 pub mod portfolio_rebalance;
 pub mod prediction;
+pub mod quote_guard;
 pub mod simple_arbitrage;
 
 pub use arbitrage::ArbitrageStrategy;
+pub use avellaneda::{AvellanedaStoikov, AvellanedaStoikovConfig};
 pub use event_driven::EventDrivenStrategy;
+pub use execution_algos::{ExecutionAlgoError, ParentOrder, TwapExecutor, VwapExecutor};
+pub use execution_style::{
+    CalibrationBucket, ExecutionDecision, ExecutionOutcome, ExecutionRequest, ExecutionStyle,
+    ExecutionStyleConfig, ExecutionStyleSelector,
+};
 pub use market_making::MarketMakingStrategy;
+pub use order_sizer::{OrderSizer, OrderSizerConfig, SizeRecommendation};
 pub use portfolio_rebalance::PortfolioRebalancingStrategy as PortfolioRebalancer;
 pub use prediction::LinearRegressionPredictor;
+pub use quote_guard::{CrossAction, QuoteDecision, QuoteGuard};
 pub use simple_arbitrage::SimpleArbitrageStrategyImpl as SimpleArbitrageStrategy;
 
 #[cfg(test)]
@@ -0,0 +1,283 @@
+use crate::core::events::{Timestamp, Trade};
+use crate::types::Size;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Child order sizing configuration
+#[derive(Debug, Clone)]
+pub struct OrderSizerConfig {
+    /// Largest child order
+    pub max_size: Size,
+    /// Smallest child order worth sending; smaller sizes come out as zero
+    pub min_size: Size,
+    /// Increment sizes are rounded down to
+    pub lot_size: Size,
+    /// Fraction of the volume traded over the window one child order may take
+    pub participation_rate: Decimal,
+    /// Fraction of the visible depth one child order may take
+    pub depth_fraction: Decimal,
+    /// Window over which traded volume and volatility are measured
+    pub window: Duration,
+    /// Trade-to-trade volatility in bps above which sizes shrink in proportion
+    pub volatility_target_bps: f64,
+    /// Weight of the latest outcome in the fill rate average
+    pub fill_rate_alpha: f64,
+    /// Smallest scale the fill rate can apply
+    pub min_fill_scale: f64,
+}
+
+impl Default for OrderSizerConfig {
+    fn default() -> Self {
+        Self {
+            max_size: Size::new(Decimal::ONE),
+            min_size: Size::new(Decimal::new(1, 3)),
+            lot_size: Size::new(Decimal::new(1, 3)),
+            participation_rate: Decimal::new(1, 1),
+            depth_fraction: Decimal::new(5, 1),
+            window: Duration::from_secs(60),
+            volatility_target_bps: 5.0,
+            fill_rate_alpha: 0.2,
+            min_fill_scale: 0.25,
+        }
+    }
+}
+
+/// A sized child order and what bound it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeRecommendation {
+    /// Size to send, zero if below the minimum
+    pub size: Size,
+    /// Participation cap from the window's traded volume (None without trades)
+    pub volume_cap: Option<Size>,
+    /// Cap from the visible depth
+    pub depth_cap: Size,
+    /// Trade-to-trade volatility over the window in bps (None with under three trades)
+    pub volatility_bps: Option<f64>,
+    /// Scale applied for volatility above the target
+    pub volatility_scale: f64,
+    /// Average fraction of recent child orders that filled
+    pub fill_rate: f64,
+}
+
+#[derive(Debug, Clone)]
+struct SymbolActivity {
+    /// Trade time, size and price within the window, oldest first
+    trades: VecDeque<(Timestamp, Decimal, f64)>,
+    fill_rate: f64,
+}
+
+impl Default for SymbolActivity {
+    fn default() -> Self {
+        Self {
+            trades: VecDeque::new(),
+            fill_rate: 1.0,
+        }
+    }
+}
+
+impl SymbolActivity {
+    fn volume(&self) -> Decimal {
+        self.trades.iter().map(|(_, size, _)| *size).sum()
+    }
+
+    fn volatility_bps(&self) -> Option<f64> {
+        let returns: Vec<f64> = self
+            .trades
+            .iter()
+            .zip(self.trades.iter().skip(1))
+            .filter(|((_, _, a), (_, _, b))| *a > 0.0 && *b > 0.0)
+            .map(|((_, _, a), (_, _, b))| (b / a).ln() * 10_000.0)
+            .collect();
+        if returns.len() < 2 {
+            return None;
+        }
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance =
+            returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64;
+        Some(variance.sqrt())
+    }
+}
+
+/// Sizes child orders from book depth, recent volume, volatility and fill rates
+///
+/// A child order takes the smallest of the maximum size, a participation share of the
+/// volume traded over the window and a share of the visible depth. The result shrinks
+/// in proportion while volatility runs above the target, and with the average fill
+/// rate of recent child orders, so orders that keep missing get smaller.
+#[derive(Debug, Clone, Default)]
+pub struct OrderSizer {
+    config: OrderSizerConfig,
+    symbols: HashMap<String, SymbolActivity>,
+}
+
+impl OrderSizer {
+    /// Create a new order sizer
+    pub fn new(config: OrderSizerConfig) -> Self {
+        Self {
+            config,
+            symbols: HashMap::new(),
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &OrderSizerConfig {
+        &self.config
+    }
+
+    /// Record a market trade
+    /// Trades older than the window, by the latest trade's time, are dropped.
+    pub fn on_trade(&mut self, trade: &Trade) {
+        let window = self.config.window.as_millis() as Timestamp;
+        let activity = self
+            .symbols
+            .entry(trade.symbol.value().to_string())
+            .or_default();
+        activity.trades.push_back((
+            trade.timestamp,
+            trade.size.value(),
+            trade.price.value().to_f64().unwrap_or(0.0),
+        ));
+        let cutoff = trade.timestamp.saturating_sub(window);
+        while activity
+            .trades
+            .front()
+            .is_some_and(|(timestamp, _, _)| *timestamp < cutoff)
+        {
+            activity.trades.pop_front();
+        }
+    }
+
+    /// Record how much of a child order filled
+    pub fn record_fill(&mut self, symbol: &str, requested: Size, filled: Size) {
+        if !requested.is_positive() {
+            return;
+        }
+        let ratio = (filled.value() / requested.value())
+            .to_f64()
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0);
+        let alpha = self.config.fill_rate_alpha.clamp(0.0, 1.0);
+        let activity = self.symbols.entry(symbol.to_string()).or_default();
+        activity.fill_rate = activity.fill_rate * (1.0 - alpha) + ratio * alpha;
+    }
+
+    /// Size a child order against `depth`, the size visible at the prices it would take
+    pub fn size(&self, symbol: &str, depth: Size) -> SizeRecommendation {
+        let config = &self.config;
+        let unseen = SymbolActivity::default();
+        let activity = self.symbols.get(symbol).unwrap_or(&unseen);
+
+        let volume = activity.volume();
+        let volume_cap = (!volume.is_zero()).then(|| Size::new(volume * config.participation_rate));
+        let depth_cap = Size::new(depth.value().max(Decimal::ZERO) * config.depth_fraction);
+        let volatility_bps = activity.volatility_bps();
+        let volatility_scale = match volatility_bps {
+            Some(vol) if vol > config.volatility_target_bps => config.volatility_target_bps / vol,
+            _ => 1.0,
+        };
+        let fill_scale = activity.fill_rate.max(config.min_fill_scale);
+
+        let cap = [Some(config.max_size), volume_cap, Some(depth_cap)]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(config.max_size);
+        let scale = Decimal::from_f64(volatility_scale * fill_scale).unwrap_or(Decimal::ONE);
+        let mut size = cap.value() * scale;
+        if config.lot_size.is_positive() {
+            size = (size / config.lot_size.value())
+                .round_dp_with_strategy(0, RoundingStrategy::ToZero)
+                * config.lot_size.value();
+        }
+        if size < config.min_size.value() {
+            size = Decimal::ZERO;
+        }
+
+        SizeRecommendation {
+            size: Size::new(size),
+            volume_cap,
+            depth_cap,
+            volatility_bps,
+            volatility_scale,
+            fill_rate: activity.fill_rate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OrderSide;
+    use crate::types::{Price, Symbol};
+
+    fn trade(seconds: u64, price: &str, size: &str) -> Trade {
+        Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str(price).unwrap(),
+            size: Size::from_str(size).unwrap(),
+            side: OrderSide::Buy,
+            timestamp: seconds * 1000,
+            trade_id: None,
+        }
+    }
+
+    #[test]
+    fn test_order_sizer() {
+        let mut sizer = OrderSizer::new(OrderSizerConfig {
+            max_size: Size::from_str("10").unwrap(),
+            min_size: Size::from_str("0.01").unwrap(),
+            lot_size: Size::from_str("0.01").unwrap(),
+            fill_rate_alpha: 0.5,
+            ..Default::default()
+        });
+        let depth = Size::from_str("3").unwrap();
+
+        // Without trades only the depth and maximum bind
+        let sized = sizer.size("BTCUSDT", Size::from_str("100").unwrap());
+        assert_eq!(sized.volume_cap, None);
+        assert_eq!(sized.size, Size::from_str("10").unwrap());
+
+        for seconds in 0..4 {
+            sizer.on_trade(&trade(seconds, "100", "5"));
+        }
+        // 10% of 20 traded is 2, half of the 3 visible is 1.5
+        let sized = sizer.size("BTCUSDT", depth);
+        assert_eq!(sized.volume_cap, Some(Size::from_str("2").unwrap()));
+        assert_eq!(sized.depth_cap, Size::from_str("1.5").unwrap());
+        assert_eq!(sized.volatility_bps, Some(0.0));
+        assert_eq!(sized.size, Size::from_str("1.5").unwrap());
+
+        // A half-filled child order scales the next one by the 0.75 average
+        sizer.record_fill(
+            "BTCUSDT",
+            Size::from_str("1.5").unwrap(),
+            Size::from_str("0.75").unwrap(),
+        );
+        let sized = sizer.size("BTCUSDT", depth);
+        assert_eq!(sized.fill_rate, 0.75);
+        assert_eq!(sized.size, Size::from_str("1.12").unwrap());
+
+        // A 1% jump pushes volatility over the 5 bps target
+        sizer.on_trade(&trade(4, "101", "5"));
+        let sized = sizer.size("BTCUSDT", depth);
+        assert!(sized.volatility_bps.unwrap() > 5.0);
+        assert!(sized.volatility_scale < 0.2);
+        assert!(sized.size < Size::from_str("0.25").unwrap());
+
+        // Trades age out of the window; with one left there is no volatility estimate
+        sizer.on_trade(&trade(70, "101", "6"));
+        let sized = sizer.size("BTCUSDT", Size::from_str("100").unwrap());
+        assert_eq!(sized.volume_cap, Some(Size::from_str("0.6").unwrap()));
+        assert_eq!(sized.volatility_bps, None);
+        assert_eq!(sized.size, Size::from_str("0.45").unwrap());
+
+        // Sizes under the minimum are not worth sending
+        assert!(sizer
+            .size("BTCUSDT", Size::from_str("0.01").unwrap())
+            .size
+            .is_zero());
+    }
+}
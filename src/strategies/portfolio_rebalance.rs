@@ -31,6 +31,7 @@ impl RebalanceData {
 }
 
 /// Portfolio rebalancing strategy that maintains target allocations
+#[derive(Clone)]
 pub struct PortfolioRebalancingStrategy {
     /// Target allocations for each asset
     target_allocations: HashMap<String, Size>,
@@ -1,4 +1,5 @@
 use crate::core::events::Trade;
+use crate::indicators::TradeFlowIndicator;
 use crate::types::Price;
 use rust_decimal::prelude::*;
 use rust_decimal::Decimal;
@@ -6,6 +7,7 @@ use std::collections::VecDeque;
 
 /// Linear regression model for short-term price prediction
 /// Uses ordinary least squares (OLS) to fit a linear model to historical price data
+#[derive(Clone)]
 pub struct LinearRegressionPredictor {
     /// Historical price data points (timestamp, price)
     price_history: VecDeque<(u64, Price)>,
@@ -17,6 +19,8 @@ pub struct LinearRegressionPredictor {
     coefficients: Option<(f64, f64)>,
     /// Last update timestamp
     last_update: Option<u64>,
+    /// Signed order flow of recent trades, if enabled
+    order_flow: Option<TradeFlowIndicator>,
 }
 
 impl LinearRegressionPredictor {
@@ -28,9 +32,18 @@ impl LinearRegressionPredictor {
             min_data_points,
             coefficients: None,
             last_update: None,
+            order_flow: None,
         }
     }
 
+    /// Track signed order flow of incoming trades (builder pattern)
+    /// Trades should carry the aggressor side, inferred by `AggressorClassifier`
+    /// for venues that don't report it
+    pub fn with_order_flow(mut self, max_trades: usize, time_window_ms: u64) -> Self {
+        self.order_flow = Some(TradeFlowIndicator::new(max_trades, time_window_ms));
+        self
+    }
+
     /// Update the model with a new price point
     pub fn update(&mut self, timestamp: u64, price: Price) {
         // Add new data point
@@ -52,6 +65,16 @@ impl LinearRegressionPredictor {
     /// Update the model with a trade event
     pub fn update_from_trade(&mut self, trade: &Trade) {
         self.update(trade.timestamp, trade.price);
+        if let Some(order_flow) = self.order_flow.as_mut() {
+            order_flow.add_trade(trade.clone());
+        }
+    }
+
+    /// Get the order flow imbalance of recent trades
+    /// Returns a value between -1.0 (all sells) and 1.0 (all buys), or None if
+    /// order flow tracking is disabled
+    pub fn order_flow_imbalance(&self) -> Option<f64> {
+        self.order_flow.as_ref()?.flow_ratio()
     }
 
     /// Recalculate the linear regression coefficients using OLS
@@ -202,6 +225,9 @@ impl LinearRegressionPredictor {
         self.price_history.clear();
         self.coefficients = None;
         self.last_update = None;
+        if let Some(order_flow) = self.order_flow.as_mut() {
+            order_flow.clear();
+        }
     }
 
     /// Get the last update timestamp
@@ -350,4 +376,33 @@ mod tests {
         // Should only keep the last 5 data points
         assert_eq!(predictor.data_point_count(), 5);
     }
+
+    #[test]
+    fn test_order_flow_imbalance() {
+        use crate::core::events::OrderSide;
+        use crate::indicators::AggressorClassifier;
+
+        let predictor = LinearRegressionPredictor::new(100, 5);
+        assert_eq!(predictor.order_flow_imbalance(), None);
+
+        // Venue without aggressor flag: sides come from the tick rule
+        let mut predictor = LinearRegressionPredictor::new(100, 5).with_order_flow(100, 60_000);
+        let mut classifier = AggressorClassifier::new().with_inferred_venue("dex");
+        for (i, price) in ["100", "101", "102", "101.5"].iter().enumerate() {
+            let trade = Trade {
+                symbol: Symbol::new("BTCUSDT"),
+                exchange_id: "dex".to_string(),
+                price: Price::from_str(price).unwrap(),
+                size: Size::from_str("1.0").unwrap(),
+                side: OrderSide::Buy,
+                timestamp: 1000 + i as u64 * 1000,
+                trade_id: None,
+            };
+            let (trade, _) = classifier.classify(trade);
+            predictor.update_from_trade(&trade);
+        }
+
+        // Three buys (first trade keeps its reported side) and one sell
+        assert_eq!(predictor.order_flow_imbalance(), Some(0.5));
+    }
 }
@@ -0,0 +1,258 @@
+use crate::traits::{NewOrder, OrderSide};
+use crate::types::Price;
+
+/// What to do with a quote that would cross the spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossAction {
+    /// Drop the quote
+    Reject,
+    /// Move the quote one tick behind the opposite touch
+    Reprice,
+}
+
+/// Outcome of checking a quote against the current book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteDecision {
+    /// Quote is passive (or crossing is explicitly allowed)
+    Accept(Price),
+    /// Quote would have crossed and was moved to a passive price
+    Repriced(Price),
+    /// Quote would have crossed and must not be sent
+    Rejected,
+}
+
+impl QuoteDecision {
+    /// Get the price to quote, if any
+    pub fn price(&self) -> Option<Price> {
+        match self {
+            QuoteDecision::Accept(price) | QuoteDecision::Repriced(price) => Some(*price),
+            QuoteDecision::Rejected => None,
+        }
+    }
+}
+
+/// Guard that keeps quote-producing strategies from unintentionally crossing the spread
+///
+/// A bid at or above the best ask (or an ask at or below the best bid) executes as a
+/// taker order. Unless crossing is explicitly allowed, such quotes are either rejected
+/// or repriced one tick behind the opposite touch.
+#[derive(Debug, Clone)]
+pub struct QuoteGuard {
+    /// Action for crossing quotes
+    action: CrossAction,
+    /// Explicitly allow crossing quotes
+    allow_cross: bool,
+    /// Tick size used when repricing
+    tick_size: Price,
+    /// Number of crossing quotes caught so far
+    crossings_prevented: u64,
+}
+
+impl Default for QuoteGuard {
+    fn default() -> Self {
+        Self::new(CrossAction::Reject, Price::new(rust_decimal::Decimal::ZERO))
+    }
+}
+
+impl QuoteGuard {
+    /// Create a new quote guard
+    pub fn new(action: CrossAction, tick_size: Price) -> Self {
+        Self {
+            action,
+            allow_cross: false,
+            tick_size,
+            crossings_prevented: 0,
+        }
+    }
+
+    /// Explicitly allow crossing quotes (builder pattern)
+    pub fn with_allow_cross(mut self, allow_cross: bool) -> Self {
+        self.allow_cross = allow_cross;
+        self
+    }
+
+    /// Set whether crossing quotes are explicitly allowed
+    pub fn set_allow_cross(&mut self, allow_cross: bool) {
+        self.allow_cross = allow_cross;
+    }
+
+    /// Check if crossing quotes are explicitly allowed
+    pub fn allow_cross(&self) -> bool {
+        self.allow_cross
+    }
+
+    /// Get the action taken for crossing quotes
+    pub fn action(&self) -> CrossAction {
+        self.action
+    }
+
+    /// Get the number of crossing quotes caught so far
+    pub fn crossings_prevented(&self) -> u64 {
+        self.crossings_prevented
+    }
+
+    /// Check if a quote would cross the opposite touch
+    pub fn would_cross(
+        side: OrderSide,
+        price: Price,
+        best_bid: Option<Price>,
+        best_ask: Option<Price>,
+    ) -> bool {
+        match side {
+            OrderSide::Buy => best_ask.is_some_and(|ask| price >= ask),
+            OrderSide::Sell => best_bid.is_some_and(|bid| price <= bid),
+        }
+    }
+
+    /// Check a quote against the current best bid and ask
+    pub fn check(
+        &mut self,
+        side: OrderSide,
+        price: Price,
+        best_bid: Option<Price>,
+        best_ask: Option<Price>,
+    ) -> QuoteDecision {
+        if self.allow_cross || !Self::would_cross(side, price, best_bid, best_ask) {
+            return QuoteDecision::Accept(price);
+        }
+
+        self.crossings_prevented += 1;
+
+        if self.action == CrossAction::Reject || self.tick_size.value().is_zero() {
+            return QuoteDecision::Rejected;
+        }
+
+        let repriced = match (side, best_bid, best_ask) {
+            (OrderSide::Buy, _, Some(ask)) => ask - self.tick_size,
+            (OrderSide::Sell, Some(bid), _) => bid + self.tick_size,
+            _ => return QuoteDecision::Rejected,
+        };
+
+        // Repricing must not cross the other way in a locked or one-tick book
+        let still_valid = match (side, best_bid, best_ask) {
+            (OrderSide::Buy, Some(bid), _) => repriced >= bid,
+            (OrderSide::Sell, _, Some(ask)) => repriced <= ask,
+            _ => true,
+        };
+
+        if repriced.value().is_sign_positive() && !repriced.value().is_zero() && still_valid {
+            QuoteDecision::Repriced(repriced)
+        } else {
+            QuoteDecision::Rejected
+        }
+    }
+
+    /// Apply the guard to a limit order, returning the (possibly repriced) order
+    /// Market orders and orders without a price are passed through unchanged
+    pub fn guard_order(
+        &mut self,
+        mut order: NewOrder,
+        best_bid: Option<Price>,
+        best_ask: Option<Price>,
+    ) -> Option<NewOrder> {
+        let price = match (order.order_type, order.price) {
+            (order_type, Some(price)) if order_type.is_limit() => price,
+            _ => return Some(order),
+        };
+
+        let price = self.check(order.side, price, best_bid, best_ask).price()?;
+        order.price = Some(price);
+        Some(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::TimeInForce;
+    use crate::types::Size;
+
+    fn p(value: &str) -> Price {
+        Price::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_passive_quotes_are_accepted() {
+        let mut guard = QuoteGuard::default();
+
+        assert_eq!(
+            guard.check(OrderSide::Buy, p("99.5"), Some(p("100")), Some(p("101"))),
+            QuoteDecision::Accept(p("99.5"))
+        );
+        assert_eq!(
+            guard.check(OrderSide::Sell, p("101.5"), Some(p("100")), Some(p("101"))),
+            QuoteDecision::Accept(p("101.5"))
+        );
+        assert_eq!(guard.crossings_prevented(), 0);
+    }
+
+    #[test]
+    fn test_crossing_quotes_are_rejected() {
+        let mut guard = QuoteGuard::default();
+
+        // Bid at the best ask
+        assert_eq!(
+            guard.check(OrderSide::Buy, p("101"), Some(p("100")), Some(p("101"))),
+            QuoteDecision::Rejected
+        );
+        // Ask below the best bid
+        assert_eq!(
+            guard.check(OrderSide::Sell, p("99"), Some(p("100")), Some(p("101"))),
+            QuoteDecision::Rejected
+        );
+        assert_eq!(guard.crossings_prevented(), 2);
+    }
+
+    #[test]
+    fn test_crossing_quotes_are_repriced() {
+        let mut guard = QuoteGuard::new(CrossAction::Reprice, p("0.1"));
+
+        assert_eq!(
+            guard.check(OrderSide::Buy, p("102"), Some(p("100")), Some(p("101"))),
+            QuoteDecision::Repriced(p("100.9"))
+        );
+        assert_eq!(
+            guard.check(OrderSide::Sell, p("99"), Some(p("100")), Some(p("101"))),
+            QuoteDecision::Repriced(p("100.1"))
+        );
+
+        // One-tick book: repricing would cross the other way
+        assert_eq!(
+            guard.check(OrderSide::Buy, p("101"), Some(p("100.95")), Some(p("101"))),
+            QuoteDecision::Rejected
+        );
+    }
+
+    #[test]
+    fn test_explicit_cross_flag() {
+        let mut guard = QuoteGuard::default().with_allow_cross(true);
+
+        assert_eq!(
+            guard.check(OrderSide::Buy, p("102"), Some(p("100")), Some(p("101"))),
+            QuoteDecision::Accept(p("102"))
+        );
+        assert_eq!(guard.crossings_prevented(), 0);
+    }
+
+    #[test]
+    fn test_guard_order() {
+        let mut guard = QuoteGuard::new(CrossAction::Reprice, p("0.5"));
+
+        let order = NewOrder::new_limit_buy(
+            "BTCUSDT",
+            Size::from_str("1").unwrap(),
+            p("105"),
+            TimeInForce::GoodTillCancelled,
+        );
+        let guarded = guard
+            .guard_order(order, Some(p("100")), Some(p("101")))
+            .unwrap();
+        assert_eq!(guarded.price, Some(p("100.5")));
+
+        // Market orders are intentionally aggressive and pass through
+        let market = NewOrder::new_market_buy("BTCUSDT", Size::from_str("1").unwrap());
+        assert!(guard
+            .guard_order(market, Some(p("100")), Some(p("101")))
+            .is_some());
+    }
+}
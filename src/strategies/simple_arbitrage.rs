@@ -3,6 +3,7 @@ use crate::types::{Price, Size};
 use std::collections::HashMap;
 
 /// Simple arbitrage strategy that looks for price differences between exchanges
+#[derive(Clone)]
 pub struct SimpleArbitrageStrategyImpl {
     /// Minimum spread to trigger arbitrage
     min_spread: Price,
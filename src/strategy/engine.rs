@@ -1,12 +1,17 @@
-use crate::core::events::NewOrder;
+use crate::core::events::{BestBidAsk, BookMetrics, NewOrder, Trade};
+use crate::indicators::{Indicator, RealizedVolatility};
 use crate::orderbook::OrderBook;
-use crate::traits::MarketEvent;
+use crate::traits::{MarketEvent, StrategyConfig};
 use crate::types::{Price, Size};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 /// Trading signal generated by a strategy
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "signal", rename_all = "snake_case")]
 pub enum Signal {
     /// Place a new order
     PlaceOrder { order: NewOrder },
@@ -48,6 +53,12 @@ pub enum Signal {
     },
 }
 
+/// Book levels per side summed into `MarketState::bid_depth` and `ask_depth`
+pub const MARKET_STATE_DEPTH_LEVELS: usize = 10;
+
+/// Mid-price changes `MarketState::volatility` is measured over
+pub const MARKET_STATE_VOLATILITY_PERIOD: usize = 20;
+
 /// Market state for a symbol
 #[derive(Debug, Clone)]
 pub struct MarketState {
@@ -57,6 +68,19 @@ pub struct MarketState {
     pub order_book: OrderBook,
     /// Last update timestamp
     pub last_update: u64,
+    /// Latest derived book metrics, if the feed carries them
+    pub metrics: Option<BookMetrics>,
+    /// Latest top-of-book quote, from the book ticker stream
+    pub quote: Option<BestBidAsk>,
+    /// Latest trade
+    pub last_trade: Option<Trade>,
+    /// Bid size over the top `MARKET_STATE_DEPTH_LEVELS` levels of the book
+    pub bid_depth: Size,
+    /// Ask size over the top `MARKET_STATE_DEPTH_LEVELS` levels of the book
+    pub ask_depth: Size,
+    /// Mid price the volatility last saw
+    last_mid: Option<Price>,
+    volatility: RealizedVolatility<f64>,
 }
 
 impl MarketState {
@@ -66,43 +90,168 @@ impl MarketState {
             symbol,
             order_book,
             last_update: 0,
+            metrics: None,
+            quote: None,
+            last_trade: None,
+            bid_depth: Size::new(Decimal::ZERO),
+            ask_depth: Size::new(Decimal::ZERO),
+            last_mid: None,
+            volatility: RealizedVolatility::new(MARKET_STATE_VOLATILITY_PERIOD),
         }
     }
 
+    /// Create a market state around an existing book, e.g. one restored from disk
+    pub fn from_order_book(order_book: OrderBook) -> Self {
+        let mut market_state = Self::new(order_book.symbol().to_string());
+        market_state.last_update = order_book.last_update();
+        market_state.order_book = order_book;
+        market_state.refresh_book();
+        market_state
+    }
+
     /// Update market state with a new event
     pub fn update(&mut self, event: &MarketEvent) {
         match event {
             MarketEvent::OrderBookSnapshot(snapshot) => {
                 self.order_book.apply_snapshot(snapshot.clone());
                 self.last_update = snapshot.timestamp;
+                self.refresh_book();
             }
             MarketEvent::OrderBookDelta(delta) => {
                 self.order_book.apply_delta(delta.clone());
                 self.last_update = delta.timestamp;
+                self.refresh_book();
+            }
+            MarketEvent::Trade(trade) => {
+                self.last_trade = Some(trade.clone());
+                self.last_update = self.last_update.max(trade.timestamp);
+            }
+            MarketEvent::BestBidAsk(quote) => {
+                // Top-of-book quotes don't carry the depth to update the book with,
+                // so they are kept alongside it and win while they are newer
+                self.quote = Some(quote.clone());
+                self.last_update = self.last_update.max(quote.timestamp);
+                self.observe_mid();
             }
-            MarketEvent::Trade(_) => {
-                // Trades don't directly affect order book
-                // In a real implementation, you might track trade history
+            MarketEvent::Candle(_) => {
+                // Candles summarize trades and don't affect the order book
+            }
+            MarketEvent::Liquidation(_)
+            | MarketEvent::OpenInterest(_)
+            | MarketEvent::MarkPrice(_) => {
+                // Positioning data doesn't affect order book
+            }
+            MarketEvent::Desync(desync) => {
+                self.order_book.mark_desynced(desync.error.clone());
+            }
+            MarketEvent::BookMetrics(metrics) => {
+                self.metrics = Some(metrics.clone());
             }
         }
     }
 
+    /// Recompute the depth and volatility after the book changed
+    fn refresh_book(&mut self) {
+        let sum = |levels: &[(Price, Size)]| {
+            levels
+                .iter()
+                .fold(Size::new(Decimal::ZERO), |total, &(_, size)| total + size)
+        };
+        self.bid_depth = sum(&self.order_book.top_bids(MARKET_STATE_DEPTH_LEVELS));
+        self.ask_depth = sum(&self.order_book.top_asks(MARKET_STATE_DEPTH_LEVELS));
+        self.observe_mid();
+    }
+
+    /// Feed the mid price to the volatility when it moved
+    fn observe_mid(&mut self) {
+        let Some(mid) = self.mid_price() else {
+            return;
+        };
+        if self.last_mid.replace(mid) != Some(mid) {
+            if let Some(mid) = mid.value().to_f64() {
+                self.volatility.update(mid);
+            }
+        }
+    }
+
+    /// Get the book ticker quote if it is newer than the book, or the book has no levels
+    fn current_quote(&self) -> Option<&BestBidAsk> {
+        self.quote.as_ref().filter(|quote| {
+            quote.timestamp > self.order_book.last_update()
+                || (self.order_book.best_bid().is_none() && self.order_book.best_ask().is_none())
+        })
+    }
+
     /// Get the best bid price
+    /// The book ticker quote is used while it is newer than the book.
     pub fn best_bid(&self) -> Option<(Price, Size)> {
-        self.order_book.best_bid()
+        match self.current_quote() {
+            Some(quote) => Some((quote.bid_price, quote.bid_size)),
+            None => self.order_book.best_bid(),
+        }
     }
 
     /// Get the best ask price
+    /// The book ticker quote is used while it is newer than the book.
     pub fn best_ask(&self) -> Option<(Price, Size)> {
-        self.order_book.best_ask()
+        match self.current_quote() {
+            Some(quote) => Some((quote.ask_price, quote.ask_size)),
+            None => self.order_book.best_ask(),
+        }
     }
 
     /// Get the spread
     pub fn spread(&self) -> Option<Price> {
-        self.order_book.spread()
+        let (bid_price, _) = self.best_bid()?;
+        let (ask_price, _) = self.best_ask()?;
+        Some(ask_price - bid_price)
+    }
+
+    /// Get the mid price between the best bid and ask
+    pub fn mid_price(&self) -> Option<Price> {
+        let (bid_price, _) = self.best_bid()?;
+        let (ask_price, _) = self.best_ask()?;
+        Some(Price::new(
+            (bid_price.value() + ask_price.value()) / Decimal::TWO,
+        ))
+    }
+
+    /// Get the price of the latest trade
+    pub fn last_price(&self) -> Option<Price> {
+        self.last_trade.as_ref().map(|trade| trade.price)
+    }
+
+    /// Get the realized volatility of the mid price, per mid-price change
+    /// `None` until `MARKET_STATE_VOLATILITY_PERIOD` changes have been seen.
+    pub fn volatility(&self) -> Option<f64> {
+        self.volatility.value()
+    }
+
+    /// Check if the book was restored from disk and has not been resynced yet
+    pub fn is_stale(&self) -> bool {
+        self.order_book.is_stale()
+    }
+
+    /// Check if the book fell out of sync with the venue and awaits a snapshot
+    pub fn is_desynced(&self) -> bool {
+        self.order_book.is_desynced()
     }
 }
 
+/// What a strategy would do for a symbol right now, computed without executing anything
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignalPreview {
+    pub symbol: String,
+    /// Signal the strategy computes from the current market state
+    pub signal: Option<Signal>,
+    /// Time left before the engine would emit a signal for this symbol again
+    pub cooldown_remaining_ms: u64,
+    /// The book was restored from disk and has not been resynced
+    pub stale: bool,
+    /// Timestamp of the last market update applied
+    pub last_update: u64,
+}
+
 /// Strategy engine that processes market events and generates trading signals
 pub struct StrategyEngine<S> {
     /// The strategy implementation
@@ -129,6 +278,13 @@ where
         }
     }
 
+    /// Seed the market state of a symbol with a book restored from disk
+    pub fn restore_order_book(&mut self, order_book: OrderBook) {
+        let market_state = MarketState::from_order_book(order_book);
+        self.market_states
+            .insert(market_state.symbol.clone(), market_state);
+    }
+
     /// Process a Market event and potentially generate a signal
     pub fn process_event(&mut self, event: MarketEvent) -> Option<Signal> {
         // Update market state
@@ -136,6 +292,13 @@ where
             MarketEvent::OrderBookSnapshot(ref snapshot) => snapshot.symbol.clone(),
             MarketEvent::OrderBookDelta(ref delta) => delta.symbol.clone(),
             MarketEvent::Trade(ref trade) => trade.symbol.clone(),
+            MarketEvent::BestBidAsk(ref quote) => quote.symbol.clone(),
+            MarketEvent::Candle(ref candle) => candle.symbol.clone(),
+            MarketEvent::Liquidation(ref liquidation) => liquidation.symbol.clone(),
+            MarketEvent::OpenInterest(ref open_interest) => open_interest.symbol.clone(),
+            MarketEvent::MarkPrice(ref mark_price) => mark_price.symbol.clone(),
+            MarketEvent::Desync(ref desync) => desync.symbol.clone(),
+            MarketEvent::BookMetrics(ref metrics) => metrics.symbol.clone(),
         };
 
         let symbol_str = symbol.value().to_string();
@@ -146,6 +309,11 @@ where
 
         market_state.update(&event);
 
+        // Don't trade on a book that is out of sync with the venue
+        if market_state.is_desynced() {
+            return None;
+        }
+
         // Check if we should generate a signal
         let now = Instant::now();
         let last_signal = self.last_signal_time.get(&symbol_str);
@@ -176,6 +344,11 @@ where
         &self.market_states
     }
 
+    /// Change the strategy's parameters, keeping its state and the market states
+    pub fn update_config(&mut self, config: StrategyConfig) -> Result<(), String> {
+        self.strategy.update_config(config)
+    }
+
     /// Generate signals for all market states
     /// Returns a vector of signals generated from all available market states
     pub fn generate_signals(&mut self) -> Vec<Signal> {
@@ -196,6 +369,9 @@ where
 
             // Get market state and generate signal
             if let Some(market_state) = self.market_states.get(&symbol) {
+                if market_state.is_desynced() {
+                    continue;
+                }
                 if let Some(signal) = self.strategy.generate_signal(market_state) {
                     self.last_signal_time.insert(symbol.clone(), now);
                     signals.push(signal);
@@ -207,10 +383,71 @@ where
     }
 }
 
+impl<S> StrategyEngine<S>
+where
+    S: Strategy + Clone,
+{
+    /// Recompute signals against the current market states without executing them
+    ///
+    /// The strategy is cloned first, so refresh timers, cooldowns and tracked orders of
+    /// the live strategy are left untouched. Signals are computed even while a symbol
+    /// is cooling down; `cooldown_remaining_ms` says whether the engine would hold them.
+    pub fn preview_signals(&self, symbol: Option<&str>) -> Vec<SignalPreview> {
+        let mut strategy = self.strategy.clone();
+        let now = Instant::now();
+
+        let mut symbols: Vec<&String> = self
+            .market_states
+            .keys()
+            .filter(|s| symbol.is_none_or(|symbol| s.as_str() == symbol))
+            .collect();
+        symbols.sort();
+
+        symbols
+            .into_iter()
+            .map(|symbol| {
+                let market_state = &self.market_states[symbol];
+                let cooldown_remaining = self
+                    .last_signal_time
+                    .get(symbol)
+                    .map(|last| {
+                        self.signal_cooldown
+                            .saturating_sub(now.duration_since(*last))
+                    })
+                    .unwrap_or_default();
+                SignalPreview {
+                    symbol: symbol.clone(),
+                    signal: strategy.generate_signal(market_state),
+                    cooldown_remaining_ms: cooldown_remaining.as_millis() as u64,
+                    stale: market_state.is_stale(),
+                    last_update: market_state.last_update,
+                }
+            })
+            .collect()
+    }
+}
+
 /// Trait that all strategies must implement
 pub trait Strategy {
     /// Generate a trading signal based on the current market state
     fn generate_signal(&mut self, market_state: &MarketState) -> Option<Signal>;
+
+    /// Change parameters while running, keeping positions and other state
+    /// Only the parameters set in `config` change; an invalid one rejects the whole update.
+    /// Strategies without live parameters reject every update.
+    fn update_config(&mut self, _config: StrategyConfig) -> Result<(), String> {
+        Err("Strategy does not support live config updates".to_string())
+    }
+}
+
+impl<S: Strategy + ?Sized> Strategy for Box<S> {
+    fn generate_signal(&mut self, market_state: &MarketState) -> Option<Signal> {
+        (**self).generate_signal(market_state)
+    }
+
+    fn update_config(&mut self, config: StrategyConfig) -> Result<(), String> {
+        (**self).update_config(config)
+    }
 }
 
 /// Trait for processing market events from a stream
@@ -360,4 +597,138 @@ mod tests {
         );
         assert_eq!(market_state.last_update, 123456789);
     }
+
+    #[test]
+    fn test_market_state_tracks_quotes_trades_and_volatility() {
+        let quote = |bid: &str, ask: &str, timestamp: u64| {
+            MarketEvent::BestBidAsk(crate::core::events::BestBidAsk {
+                symbol: "BTCUSDT".into(),
+                exchange_id: "binance".to_string(),
+                bid_price: Price::from_str(bid).unwrap(),
+                bid_size: Size::from_str("2.0").unwrap(),
+                ask_price: Price::from_str(ask).unwrap(),
+                ask_size: Size::from_str("3.0").unwrap(),
+                update_id: timestamp,
+                timestamp,
+            })
+        };
+        let strategy = crate::strategy::SimpleArbitrageStrategy::new(
+            Price::from_str("0.5").unwrap(),
+            Size::from_str("0.1").unwrap(),
+            Size::from_str("10.0").unwrap(),
+        );
+        let mut engine = StrategyEngine::new(strategy, Duration::ZERO);
+
+        // A book ticker feed alone is enough to trade on
+        assert!(matches!(
+            engine.process_event(quote("100.00", "101.00", 1)),
+            Some(Signal::Arbitrage { .. })
+        ));
+        engine.process_event(MarketEvent::Trade(Trade {
+            symbol: "BTCUSDT".into(),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str("101.00").unwrap(),
+            size: Size::from_str("0.5").unwrap(),
+            side: crate::core::events::OrderSide::Buy,
+            timestamp: 2,
+            trade_id: None,
+        }));
+        let state = engine.get_market_state("BTCUSDT").unwrap();
+        assert_eq!(state.mid_price(), Some(Price::from_str("100.50").unwrap()));
+        assert_eq!(state.last_price(), Some(Price::from_str("101.00").unwrap()));
+        assert_eq!(state.last_update, 2);
+        assert_eq!(state.volatility(), None);
+
+        // A newer book takes over from the quote and fills in the depth
+        engine.process_event(MarketEvent::OrderBookSnapshot(
+            crate::orderbook::OrderBookSnapshot::new(
+                "BTCUSDT",
+                "binance",
+                vec![
+                    crate::orderbook::OrderBookLevel::new(
+                        Price::from_str("100.10").unwrap(),
+                        Size::from_str("1.0").unwrap(),
+                    ),
+                    crate::orderbook::OrderBookLevel::new(
+                        Price::from_str("100.00").unwrap(),
+                        Size::from_str("4.0").unwrap(),
+                    ),
+                ],
+                vec![crate::orderbook::OrderBookLevel::new(
+                    Price::from_str("100.90").unwrap(),
+                    Size::from_str("1.5").unwrap(),
+                )],
+                3,
+            ),
+        ));
+        let state = engine.get_market_state("BTCUSDT").unwrap();
+        assert_eq!(state.spread(), Some(Price::from_str("0.80").unwrap()));
+        assert_eq!(state.bid_depth, Size::from_str("5.0").unwrap());
+        assert_eq!(state.ask_depth, Size::from_str("1.5").unwrap());
+
+        for i in 0..MARKET_STATE_VOLATILITY_PERIOD as u64 {
+            let bid = if i % 2 == 0 { "100.20" } else { "100.00" };
+            engine.process_event(quote(bid, "101.00", 4 + i));
+        }
+        let volatility = engine
+            .get_market_state("BTCUSDT")
+            .unwrap()
+            .volatility()
+            .unwrap();
+        assert!(volatility > 0.0 && volatility < 0.01);
+    }
+
+    #[test]
+    fn test_restore_order_book() {
+        let mut engine = StrategyEngine::new(MockStrategy::new(false), Duration::from_millis(100));
+
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.apply_snapshot(crate::orderbook::OrderBookSnapshot::new(
+            "BTCUSDT".to_string(),
+            "binance".to_string(),
+            vec![crate::orderbook::OrderBookLevel::new(
+                Price::from_str("100.00").unwrap(),
+                Size::from_str("10.0").unwrap(),
+            )],
+            vec![],
+            123456789,
+        ));
+        book.mark_stale();
+        engine.restore_order_book(book);
+
+        // Restored book is served immediately but flagged until a live snapshot arrives
+        let market_state = engine.get_market_state("BTCUSDT").unwrap();
+        assert!(market_state.is_stale());
+        assert_eq!(market_state.last_update, 123456789);
+        assert!(market_state.best_bid().is_some());
+    }
+
+    #[test]
+    fn test_desync_pauses_signals_until_snapshot() {
+        let mut engine = StrategyEngine::new(MockStrategy::new(true), Duration::ZERO);
+        let snapshot = MarketEvent::OrderBookSnapshot(crate::orderbook::OrderBookSnapshot::new(
+            "BTCUSDT",
+            "binance",
+            vec![],
+            vec![],
+            1,
+        ));
+        let desync = MarketEvent::Desync(crate::core::events::BookDesync {
+            symbol: "BTCUSDT".into(),
+            exchange_id: "binance".to_string(),
+            error: crate::orderbook::BookIntegrityError::SequenceGap {
+                expected: 11,
+                received: 15,
+            },
+            timestamp: 2,
+        });
+
+        assert!(engine.process_event(snapshot.clone()).is_some());
+        assert!(engine.process_event(desync).is_none());
+        assert!(engine.get_market_state("BTCUSDT").unwrap().is_desynced());
+        assert!(engine.generate_signals().is_empty());
+
+        assert!(engine.process_event(snapshot).is_some());
+        assert!(!engine.get_market_state("BTCUSDT").unwrap().is_desynced());
+    }
 }
@@ -1,5 +1,9 @@
 pub mod engine;
+pub mod scheduler;
 pub mod simple_arbitrage;
 
-pub use engine::{MarketState, Signal, Strategy, StrategyEngine};
+pub use engine::{MarketState, Signal, SignalPreview, Strategy, StrategyEngine};
+pub use scheduler::{
+    DispatchOrder, ScheduledSignal, SchedulerError, StrategyRegistration, StrategyScheduler,
+};
 pub use simple_arbitrage::SimpleArbitrageStrategy;
@@ -0,0 +1,452 @@
+use crate::strategy::{Signal, Strategy, StrategyEngine};
+use crate::traits::MarketEvent;
+use log::{error, info, warn};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+/// Order strategies see each event in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DispatchOrder {
+    /// Rotate which strategy goes first on every event
+    #[default]
+    RoundRobin,
+    /// Highest priority first, ties in registration order
+    Priority,
+}
+
+/// Strategy to run under a `StrategyScheduler`
+pub struct StrategyRegistration {
+    id: String,
+    strategy: Box<dyn Strategy + Send + Sync>,
+    symbols: Option<HashSet<String>>,
+    priority: i32,
+    signal_cooldown: Duration,
+}
+
+impl StrategyRegistration {
+    /// Register a strategy for every symbol, with priority 0 and a 100ms signal cooldown
+    pub fn new(id: impl Into<String>, strategy: impl Strategy + Send + Sync + 'static) -> Self {
+        Self {
+            id: id.into(),
+            strategy: Box::new(strategy),
+            symbols: None,
+            priority: 0,
+            signal_cooldown: Duration::from_millis(100),
+        }
+    }
+
+    /// Only dispatch events for these symbols (builder pattern)
+    pub fn with_symbols<I, S>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.symbols = Some(symbols.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the dispatch priority, higher first (builder pattern)
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the minimum time between signals per symbol (builder pattern)
+    pub fn with_signal_cooldown(mut self, cooldown: Duration) -> Self {
+        self.signal_cooldown = cooldown;
+        self
+    }
+}
+
+/// Scheduler errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchedulerError {
+    DuplicateStrategy(String),
+    UnknownStrategy(String),
+}
+
+impl fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchedulerError::DuplicateStrategy(id) => {
+                write!(f, "Strategy {} is already registered", id)
+            }
+            SchedulerError::UnknownStrategy(id) => write!(f, "Unknown strategy {}", id),
+        }
+    }
+}
+
+impl std::error::Error for SchedulerError {}
+
+/// A registered strategy and its health
+struct StrategySlot {
+    id: String,
+    engine: StrategyEngine<Box<dyn Strategy + Send + Sync>>,
+    symbols: Option<HashSet<String>>,
+    priority: i32,
+    /// Panics since the strategy was registered or last enabled
+    panics: u32,
+    disabled: bool,
+}
+
+impl StrategySlot {
+    fn accepts(&self, symbol: &str) -> bool {
+        !self.disabled
+            && self
+                .symbols
+                .as_ref()
+                .is_none_or(|symbols| symbols.contains(symbol))
+    }
+}
+
+/// Signal produced by a scheduled strategy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledSignal {
+    pub strategy_id: String,
+    pub signal: Signal,
+}
+
+/// Runs several strategies side by side on one event stream
+///
+/// Each strategy has its own engine, market state and cooldowns and only sees events for
+/// its symbols. A strategy that panics is isolated: the panic is caught, the others still
+/// see the event, and after `max_panics` panics the strategy is disabled until `enable`.
+pub struct StrategyScheduler {
+    slots: Vec<StrategySlot>,
+    dispatch: DispatchOrder,
+    max_panics: u32,
+    /// Slot going first on the next round-robin dispatch
+    next_first: usize,
+}
+
+impl StrategyScheduler {
+    /// Create an empty scheduler dispatching round-robin, disabling a strategy on its
+    /// first panic
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            dispatch: DispatchOrder::default(),
+            max_panics: 1,
+            next_first: 0,
+        }
+    }
+
+    /// Set the order strategies see events in (builder pattern)
+    pub fn with_dispatch(mut self, dispatch: DispatchOrder) -> Self {
+        self.dispatch = dispatch;
+        self
+    }
+
+    /// Set the number of panics after which a strategy is disabled (builder pattern)
+    pub fn with_max_panics(mut self, max_panics: u32) -> Self {
+        self.max_panics = max_panics.max(1);
+        self
+    }
+
+    /// Add a strategy
+    pub fn register(&mut self, registration: StrategyRegistration) -> Result<(), SchedulerError> {
+        if self.slots.iter().any(|slot| slot.id == registration.id) {
+            return Err(SchedulerError::DuplicateStrategy(registration.id));
+        }
+        info!(
+            "Registered strategy {} (priority {}, symbols {:?})",
+            registration.id, registration.priority, registration.symbols
+        );
+        self.slots.push(StrategySlot {
+            id: registration.id,
+            engine: StrategyEngine::new(registration.strategy, registration.signal_cooldown),
+            symbols: registration.symbols,
+            priority: registration.priority,
+            panics: 0,
+            disabled: false,
+        });
+        // Stable sort keeps registration order among equal priorities
+        if self.dispatch == DispatchOrder::Priority {
+            self.slots.sort_by_key(|slot| Reverse(slot.priority));
+        }
+        Ok(())
+    }
+
+    /// Remove a strategy
+    pub fn unregister(&mut self, id: &str) -> Result<(), SchedulerError> {
+        let index = self.index_of(id)?;
+        self.slots.remove(index);
+        self.next_first = 0;
+        Ok(())
+    }
+
+    /// Re-enable a strategy disabled after panicking, clearing its panic count
+    pub fn enable(&mut self, id: &str) -> Result<(), SchedulerError> {
+        let index = self.index_of(id)?;
+        let slot = &mut self.slots[index];
+        slot.disabled = false;
+        slot.panics = 0;
+        Ok(())
+    }
+
+    /// Check if a strategy was disabled after panicking
+    pub fn is_disabled(&self, id: &str) -> bool {
+        self.slots.iter().any(|slot| slot.id == id && slot.disabled)
+    }
+
+    /// Get the number of panics of a strategy since it was registered or last enabled
+    pub fn panics(&self, id: &str) -> u32 {
+        self.slots
+            .iter()
+            .find(|slot| slot.id == id)
+            .map_or(0, |slot| slot.panics)
+    }
+
+    /// Get the IDs of the registered strategies, in priority order
+    pub fn strategy_ids(&self) -> Vec<&str> {
+        self.slots.iter().map(|slot| slot.id.as_str()).collect()
+    }
+
+    /// Get the number of registered strategies
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Check if no strategies are registered
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    fn index_of(&self, id: &str) -> Result<usize, SchedulerError> {
+        self.slots
+            .iter()
+            .position(|slot| slot.id == id)
+            .ok_or_else(|| SchedulerError::UnknownStrategy(id.to_string()))
+    }
+
+    /// Slot indices in the order of the next dispatch
+    fn dispatch_order(&mut self) -> Vec<usize> {
+        let len = self.slots.len();
+        match self.dispatch {
+            DispatchOrder::Priority => (0..len).collect(),
+            DispatchOrder::RoundRobin => {
+                let first = if len == 0 { 0 } else { self.next_first % len };
+                self.next_first = first + 1;
+                (0..len).map(|i| (first + i) % len).collect()
+            }
+        }
+    }
+
+    /// Run a strategy call, catching and counting a panic
+    fn run_isolated<T>(
+        slot: &mut StrategySlot,
+        max_panics: u32,
+        call: impl FnOnce(&mut StrategyEngine<Box<dyn Strategy + Send + Sync>>) -> T,
+    ) -> Option<T> {
+        match panic::catch_unwind(AssertUnwindSafe(|| call(&mut slot.engine))) {
+            Ok(result) => Some(result),
+            Err(_) => {
+                slot.panics += 1;
+                if slot.panics >= max_panics {
+                    slot.disabled = true;
+                    error!(
+                        "Strategy {} panicked {} times, disabling it",
+                        slot.id, slot.panics
+                    );
+                } else {
+                    warn!("Strategy {} panicked, skipping the event", slot.id);
+                }
+                None
+            }
+        }
+    }
+
+    /// Dispatch a market event to the strategies trading its symbol
+    pub fn process_event(&mut self, event: &MarketEvent) -> Vec<ScheduledSignal> {
+        let max_panics = self.max_panics;
+        let mut signals = Vec::new();
+        for index in self.dispatch_order() {
+            let slot = &mut self.slots[index];
+            if !slot.accepts(event.symbol()) {
+                continue;
+            }
+            let signal = Self::run_isolated(slot, max_panics, |engine| {
+                engine.process_event(event.clone())
+            });
+            if let Some(Some(signal)) = signal {
+                signals.push(ScheduledSignal {
+                    strategy_id: slot.id.clone(),
+                    signal,
+                });
+            }
+        }
+        signals
+    }
+
+    /// Let every strategy generate signals from its current market states
+    pub fn generate_signals(&mut self) -> Vec<ScheduledSignal> {
+        let max_panics = self.max_panics;
+        let mut signals = Vec::new();
+        for index in self.dispatch_order() {
+            let slot = &mut self.slots[index];
+            if slot.disabled {
+                continue;
+            }
+            if let Some(generated) =
+                Self::run_isolated(slot, max_panics, |engine| engine.generate_signals())
+            {
+                signals.extend(generated.into_iter().map(|signal| ScheduledSignal {
+                    strategy_id: slot.id.clone(),
+                    signal,
+                }));
+            }
+        }
+        signals
+    }
+}
+
+impl Default for StrategyScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{OrderSide, Trade};
+    use crate::strategy::MarketState;
+    use crate::types::{Price, Size};
+    use std::collections::HashMap;
+
+    /// Signals on every event, or panics on a chosen symbol
+    struct Echo {
+        name: &'static str,
+        panic_on: Option<&'static str>,
+    }
+
+    impl Strategy for Echo {
+        fn generate_signal(&mut self, market_state: &MarketState) -> Option<Signal> {
+            if self.panic_on == Some(market_state.symbol.as_str()) {
+                panic!("{} cannot trade {}", self.name, market_state.symbol);
+            }
+            Some(Signal::Custom {
+                name: self.name.to_string(),
+                data: HashMap::new(),
+            })
+        }
+    }
+
+    fn echo(name: &'static str, panic_on: Option<&'static str>) -> Echo {
+        Echo { name, panic_on }
+    }
+
+    fn trade(symbol: &str) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            symbol: symbol.into(),
+            exchange_id: "binance".to_string(),
+            price: Price::from_str("100").unwrap(),
+            size: Size::from_str("1").unwrap(),
+            side: OrderSide::Buy,
+            timestamp: 0,
+            trade_id: None,
+        })
+    }
+
+    fn ids(signals: &[ScheduledSignal]) -> Vec<&str> {
+        signals.iter().map(|s| s.strategy_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_strategy_scheduler_dispatch() {
+        let mut scheduler = StrategyScheduler::new();
+        for id in ["a", "b", "c"] {
+            scheduler
+                .register(
+                    StrategyRegistration::new(id, echo(id, None))
+                        .with_signal_cooldown(Duration::ZERO),
+                )
+                .unwrap();
+        }
+        scheduler
+            .register(StrategyRegistration::new("eth", echo("eth", None)).with_symbols(["ETHUSDT"]))
+            .unwrap();
+        assert_eq!(
+            scheduler.register(StrategyRegistration::new("a", echo("a", None))),
+            Err(SchedulerError::DuplicateStrategy("a".to_string()))
+        );
+
+        // Round-robin rotates the first strategy; the symbol filter skips "eth"
+        assert_eq!(
+            ids(&scheduler.process_event(&trade("BTCUSDT"))),
+            ["a", "b", "c"]
+        );
+        assert_eq!(
+            ids(&scheduler.process_event(&trade("BTCUSDT"))),
+            ["b", "c", "a"]
+        );
+        assert_eq!(
+            ids(&scheduler.process_event(&trade("ETHUSDT"))),
+            ["c", "eth", "a", "b"]
+        );
+
+        let mut scheduler = StrategyScheduler::new().with_dispatch(DispatchOrder::Priority);
+        scheduler
+            .register(StrategyRegistration::new("low", echo("low", None)).with_priority(-1))
+            .unwrap();
+        scheduler
+            .register(StrategyRegistration::new("high", echo("high", None)).with_priority(5))
+            .unwrap();
+        scheduler
+            .register(StrategyRegistration::new("mid", echo("mid", None)))
+            .unwrap();
+        assert_eq!(scheduler.strategy_ids(), ["high", "mid", "low"]);
+        assert_eq!(
+            ids(&scheduler.process_event(&trade("BTCUSDT"))),
+            ["high", "mid", "low"]
+        );
+    }
+
+    #[test]
+    fn test_strategy_scheduler_isolates_panics() {
+        let mut scheduler = StrategyScheduler::new()
+            .with_dispatch(DispatchOrder::Priority)
+            .with_max_panics(2);
+        scheduler
+            .register(
+                StrategyRegistration::new("fragile", echo("fragile", Some("ETHUSDT")))
+                    .with_signal_cooldown(Duration::ZERO),
+            )
+            .unwrap();
+        scheduler
+            .register(
+                StrategyRegistration::new("steady", echo("steady", None))
+                    .with_signal_cooldown(Duration::ZERO),
+            )
+            .unwrap();
+
+        // The panic is contained and the other strategy still trades
+        assert_eq!(ids(&scheduler.process_event(&trade("ETHUSDT"))), ["steady"]);
+        assert_eq!(scheduler.panics("fragile"), 1);
+        assert!(!scheduler.is_disabled("fragile"));
+        assert_eq!(
+            ids(&scheduler.process_event(&trade("BTCUSDT"))),
+            ["fragile", "steady"]
+        );
+
+        // The second panic disables it until re-enabled
+        assert_eq!(ids(&scheduler.generate_signals()), ["steady", "steady"]);
+        assert!(scheduler.is_disabled("fragile"));
+        assert_eq!(ids(&scheduler.process_event(&trade("BTCUSDT"))), ["steady"]);
+
+        scheduler.enable("fragile").unwrap();
+        assert_eq!(scheduler.panics("fragile"), 0);
+        assert_eq!(
+            ids(&scheduler.process_event(&trade("BTCUSDT"))),
+            ["fragile", "steady"]
+        );
+        scheduler.unregister("fragile").unwrap();
+        assert_eq!(
+            scheduler.enable("fragile"),
+            Err(SchedulerError::UnknownStrategy("fragile".to_string()))
+        );
+    }
+}
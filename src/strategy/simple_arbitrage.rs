@@ -1,9 +1,11 @@
 use crate::strategy::engine::{MarketState, Signal, Strategy};
+use crate::traits::StrategyConfig;
 use crate::types::{Price, Size};
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 
 /// Simple arbitrage strategy that looks for price differences between exchanges
+#[derive(Clone)]
 pub struct SimpleArbitrageStrategy {
     /// Minimum spread to trigger arbitrage
     min_spread: Price,
@@ -117,6 +119,25 @@ impl Strategy for SimpleArbitrageStrategy {
             expected_profit: Price::new(expected_profit),
         })
     }
+
+    /// Accepts `min_spread`, `min_quantity` and `max_position`; positions are kept
+    fn update_config(&mut self, config: StrategyConfig) -> Result<(), String> {
+        config.check_parameters(&["min_spread", "min_quantity", "max_position"])?;
+        let min_spread = config.parameter::<Decimal>("min_spread")?;
+        let min_quantity = config.parameter::<Decimal>("min_quantity")?;
+        let max_position = config.parameter::<Decimal>("max_position")?;
+
+        if let Some(spread) = min_spread {
+            self.min_spread = Price::new(spread);
+        }
+        if let Some(quantity) = min_quantity {
+            self.min_quantity = Size::new(quantity);
+        }
+        if let Some(position) = max_position {
+            self.max_position = Size::new(position);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -3,9 +3,14 @@
 
 pub use crate::core::events::{
     Balance,
+    ExchangeFill,
     // Type aliases
     ExchangeId,
+    ExchangePosition,
     ExecutionReport,
+    ExpiryEvent,
+    ExpiryStage,
+    FeeRecord,
     // Events
     MarketEvent,
     NewOrder,
@@ -1,4 +1,8 @@
-use crate::core::events::{Balance, ExecutionReport, NewOrder, OrderId, TradingFees};
+use crate::core::events::{
+    Balance, ExchangeFill, ExchangePosition, ExecutionReport, FeeRecord, NewOrder, OrderId,
+    OrderStatus, OrderType, Timestamp, TradingFees,
+};
+use crate::types::{Price, Size};
 use async_trait::async_trait;
 
 /// Trait for order execution
@@ -14,6 +18,18 @@ pub trait ExecutionClient {
     /// Cancel an existing order
     async fn cancel_order(&self, order_id: OrderId) -> Result<(), Self::Error>;
 
+    /// Change the price and/or open size of an order in one request
+    /// Returns the ID of the order now working, or None if the venue cannot amend natively,
+    /// in which case `OrderExecutor` cancels and places the order itself.
+    async fn amend_order(
+        &self,
+        _order_id: OrderId,
+        _new_price: Option<Price>,
+        _new_size: Option<Size>,
+    ) -> Result<Option<OrderId>, Self::Error> {
+        Ok(None)
+    }
+
     /// Get the status of an order
     async fn get_order_status(&self, order_id: OrderId) -> Result<ExecutionReport, Self::Error>;
 
@@ -35,6 +51,180 @@ pub trait ExecutionClient {
 
     /// Get trading fees for a symbol
     async fn get_trading_fees(&self, symbol: &str) -> Result<TradingFees, Self::Error>;
+
+    /// Tell the exchange the session is alive
+    /// Adapters with `SessionSafety` re-arm the venue's cancel-on-disconnect timer here;
+    /// the default only checks the venue answers, by fetching balances.
+    async fn heartbeat(&self) -> Result<(), Self::Error> {
+        self.get_balances().await.map(|_| ())
+    }
+
+    /// Check if orders of this type can be sized in the quote asset natively
+    /// Quote-sized orders the venue cannot take are converted to a base size before sending
+    fn supports_quote_size(&self, _order_type: OrderType) -> bool {
+        false
+    }
+
+    /// Check if iceberg orders can be sent natively
+    /// Iceberg orders the venue cannot take are emulated by `OrderExecutor`
+    fn supports_iceberg(&self) -> bool {
+        false
+    }
+}
+
+/// Trait for fetching the fees an exchange actually charged
+#[async_trait]
+pub trait FeeHistory {
+    /// Error type for this source
+    type Error: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static;
+
+    /// Get per-fill fees for a symbol in a time range (milliseconds, inclusive)
+    async fn get_fee_history(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<FeeRecord>, Self::Error>;
+}
+
+/// Trait for reading the exchange's clock
+#[async_trait]
+pub trait ServerClock {
+    /// Error type for this source
+    type Error: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static;
+
+    /// Get the exchange's current time in milliseconds
+    async fn server_time(&self) -> Result<Timestamp, Self::Error>;
+}
+
+/// Trait for fetching the exchange's own record of fills and positions
+#[async_trait]
+pub trait TradeHistory {
+    /// Error type for this source
+    type Error: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static;
+
+    /// Get fills for a symbol in a time range (milliseconds, inclusive)
+    async fn get_trade_history(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<ExchangeFill>, Self::Error>;
+
+    /// Get open positions
+    /// Venues without positions (spot) return None, and positions are not compared
+    async fn get_positions(&self) -> Result<Option<Vec<ExchangePosition>>, Self::Error> {
+        Ok(None)
+    }
+}
+
+/// Filter, sort order and page for an order query
+///
+/// The time range is on each order's last update, in milliseconds, inclusive. Matching
+/// orders are sorted by that time, oldest first unless `newest_first` is set, then
+/// `offset` orders are skipped and at most `limit` returned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderQuery {
+    pub symbol: Option<String>,
+    /// Statuses to include; empty includes every status
+    pub statuses: Vec<OrderStatus>,
+    pub start_time: Option<Timestamp>,
+    pub end_time: Option<Timestamp>,
+    /// Strategy that placed the order
+    pub strategy_tag: Option<String>,
+    pub newest_first: bool,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl OrderQuery {
+    /// Create a query matching every order
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only include orders for a symbol
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Include orders in a status; may be called repeatedly
+    pub fn with_status(mut self, status: OrderStatus) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    /// Only include orders last updated between `start_time` and `end_time`
+    pub fn with_time_range(mut self, start_time: Timestamp, end_time: Timestamp) -> Self {
+        self.start_time = Some(start_time);
+        self.end_time = Some(end_time);
+        self
+    }
+
+    /// Only include orders placed by a strategy
+    pub fn with_strategy_tag(mut self, strategy_tag: impl Into<String>) -> Self {
+        self.strategy_tag = Some(strategy_tag.into());
+        self
+    }
+
+    /// Sort the most recently updated orders first
+    pub fn newest_first(mut self) -> Self {
+        self.newest_first = true;
+        self
+    }
+
+    /// Skip `offset` matching orders and return at most `limit`
+    pub fn with_page(mut self, offset: usize, limit: usize) -> Self {
+        self.offset = offset;
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Check if an order matches the filters
+    pub fn matches(
+        &self,
+        symbol: &str,
+        status: OrderStatus,
+        timestamp: Timestamp,
+        strategy_tag: Option<&str>,
+    ) -> bool {
+        self.symbol.as_deref().is_none_or(|s| s == symbol)
+            && (self.statuses.is_empty() || self.statuses.contains(&status))
+            && self.start_time.is_none_or(|start| timestamp >= start)
+            && self.end_time.is_none_or(|end| timestamp <= end)
+            && self
+                .strategy_tag
+                .as_deref()
+                .is_none_or(|tag| strategy_tag == Some(tag))
+    }
+
+    /// Sort matching reports and cut out the requested page
+    pub fn paginate(&self, mut reports: Vec<ExecutionReport>) -> OrderPage {
+        reports.sort_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then_with(|| a.order_id.cmp(&b.order_id))
+        });
+        if self.newest_first {
+            reports.reverse();
+        }
+        let total = reports.len();
+        let orders = reports
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        OrderPage { orders, total }
+    }
+}
+
+/// One page of an order query
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderPage {
+    pub orders: Vec<ExecutionReport>,
+    /// Orders matching the query across all pages
+    pub total: usize,
 }
 
 /// Trait for order management
@@ -56,4 +246,32 @@ pub trait OrderManager {
 
     /// Get open orders
     async fn get_open_orders(&self) -> Result<Vec<ExecutionReport>, Self::Error>;
+
+    /// Record that an order was replaced by a new one with a new price and/or open size
+    /// The default keeps no history; the replacement is tracked from its execution reports.
+    async fn record_replacement(
+        &mut self,
+        _order_id: &OrderId,
+        _new_order_id: OrderId,
+        _new_price: Option<Price>,
+        _new_size: Option<Size>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Get orders matching a query, sorted and paged
+    /// The default filters `get_all_orders`; its reports carry no strategy tag, so queries
+    /// by tag match nothing unless the manager overrides this.
+    async fn query_orders(&self, query: &OrderQuery) -> Result<OrderPage, Self::Error>
+    where
+        Self: Sync,
+    {
+        let reports = self.get_all_orders().await?;
+        Ok(query.paginate(
+            reports
+                .into_iter()
+                .filter(|r| query.matches(r.symbol.as_str(), r.status, r.timestamp, None))
+                .collect(),
+        ))
+    }
 }
@@ -1,5 +1,7 @@
-use crate::core::events::{MarketEvent, OrderBookSnapshot, Trade};
+use crate::core::clock::Clock;
+use crate::core::events::{Candle, CandleInterval, MarketEvent, OrderBookSnapshot, Trade};
 use async_trait::async_trait;
+use futures_util::FutureExt;
 
 /// Trait for streaming market data
 /// This allows the strategy to be independent of the specific exchange implementation
@@ -19,6 +21,28 @@ pub trait MarketDataStream {
     /// Returns None if the stream is closed
     async fn next(&mut self) -> Option<Result<MarketEvent, Self::Error>>;
 
+    /// Wait for the next event, then take up to `max` events in total that are already ready
+    /// Returns an empty batch if the stream is closed. Bursts of depth updates can then be
+    /// applied to a shared book under one lock. Streams whose `next` is not cancel safe
+    /// should override this to take only buffered events.
+    async fn next_batch(&mut self, max: usize) -> Vec<Result<MarketEvent, Self::Error>>
+    where
+        Self: Send,
+    {
+        let mut batch = Vec::new();
+        let Some(first) = self.next().await else {
+            return batch;
+        };
+        batch.push(first);
+        while batch.len() < max {
+            match self.next().now_or_never() {
+                Some(Some(event)) => batch.push(event),
+                _ => break,
+            }
+        }
+        batch
+    }
+
     /// Check if the stream is connected
     fn is_connected(&self) -> bool;
 
@@ -47,4 +71,39 @@ pub trait MarketDataHistory {
         start_time: u64,
         end_time: u64,
     ) -> Result<Vec<Trade>, Self::Error>;
+
+    /// Get candles opened between two timestamps (inclusive), oldest first
+    async fn get_candles(
+        &self,
+        symbol: &str,
+        interval: CandleInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<Candle>, Self::Error>;
+
+    /// Get the last `count` candles closed as of `clock`, oldest first
+    /// Used to warm up indicators such as EMA or RSI before trading live.
+    async fn get_recent_candles(
+        &self,
+        symbol: &str,
+        interval: CandleInterval,
+        count: usize,
+        clock: &dyn Clock,
+    ) -> Result<Vec<Candle>, Self::Error>
+    where
+        Self: Sync,
+    {
+        let interval_ms = interval.duration_ms();
+        let now = clock.now_millis();
+        // Open time of the candle still in progress
+        let current = now - now % interval_ms;
+        let start = current.saturating_sub(count as u64 * interval_ms);
+        let mut candles = self
+            .get_candles(symbol, interval, start, current.saturating_sub(1))
+            .await?;
+        candles.retain(|candle| candle.closed && candle.close_time < now);
+        let excess = candles.len().saturating_sub(count);
+        candles.drain(..excess);
+        Ok(candles)
+    }
 }
@@ -5,16 +5,22 @@ pub mod strategy;
 
 // Re-export all traits
 pub use execution::ExecutionClient;
-pub use execution::OrderManager;
+pub use execution::FeeHistory;
+pub use execution::ServerClock;
+pub use execution::TradeHistory;
+pub use execution::{OrderManager, OrderPage, OrderQuery};
 pub use market_data::MarketDataHistory;
 pub use market_data::MarketDataStream;
 
 // Re-export all types from events (which now re-exports from core::events)
 pub use events::{
     Balance,
+    ExchangeFill,
     // Type aliases
     ExchangeId,
+    ExchangePosition,
     ExecutionReport,
+    FeeRecord,
     // Enums
     MarketEvent,
     NewOrder,
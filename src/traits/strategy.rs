@@ -1,6 +1,8 @@
 use crate::core::events::{MarketEvent, Signal, TradingEvent};
 use crate::types::Symbol;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// Strategy state
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -8,10 +10,11 @@ pub enum StrategyState {
     MarketMaking,
     Arbitrage(crate::strategies::arbitrage::ArbitrageState),
     Prediction,
+    Execution,
 }
 
 /// Strategy metrics
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct StrategyMetrics {
     pub total_trades: u64,
     pub winning_trades: u64,
@@ -25,17 +28,52 @@ pub struct StrategyMetrics {
     pub average_trade_pnl: rust_decimal::Decimal,
     pub win_rate: rust_decimal::Decimal,
     pub average_holding_time_ms: u64,
+    /// Average fill price against the arrival price, in basis points (positive is worse)
+    pub arrival_slippage_bps: rust_decimal::Decimal,
 }
 
 /// Strategy configuration
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StrategyConfig {
+    #[serde(default)]
     pub strategy_type: String,
+    #[serde(default)]
     pub symbols: Vec<Symbol>,
+    #[serde(default)]
     pub exchanges: Vec<String>,
+    #[serde(default)]
     pub parameters: std::collections::HashMap<String, String>,
 }
 
+impl StrategyConfig {
+    /// Parse a parameter, None if it is not set
+    pub fn parameter<T: FromStr>(&self, key: &str) -> Result<Option<T>, String> {
+        self.parameters
+            .get(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid value '{}' for {}", value, key))
+            })
+            .transpose()
+    }
+
+    /// Reject parameters other than `known`, so a misspelt one is not silently ignored
+    pub fn check_parameters(&self, known: &[&str]) -> Result<(), String> {
+        let mut unknown: Vec<&str> = self
+            .parameters
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !known.contains(key))
+            .collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        unknown.sort_unstable();
+        Err(format!("Unknown parameters: {}", unknown.join(", ")))
+    }
+}
+
 /// Main strategy trait
 /// All trading strategies must implement this trait
 #[async_trait]
@@ -46,6 +84,10 @@ pub trait Strategy: Send + Sync {
     /// Initialize the strategy with configuration
     async fn initialize(&mut self, config: StrategyConfig) -> Result<(), Self::Error>;
 
+    /// Change parameters while running, keeping positions, books and other state
+    /// Only the parameters set in `config` change; an invalid one rejects the whole update.
+    async fn update_config(&mut self, config: StrategyConfig) -> Result<(), Self::Error>;
+
     /// Process a market event and generate signals
     async fn on_market_event(&mut self, event: MarketEvent) -> Result<Vec<Signal>, Self::Error>;
 
@@ -128,6 +170,16 @@ mod tests {
         assert_eq!(config.symbols.len(), 1);
         assert_eq!(config.exchanges.len(), 1);
         assert_eq!(config.parameters.get("spread_bps"), Some(&"10".to_string()));
+        assert_eq!(config.parameter::<u32>("spread_bps"), Ok(Some(10)));
+        assert_eq!(config.parameter::<u32>("levels"), Ok(None));
+        assert!(config.parameter::<u32>("order_size").is_err());
+        assert!(config
+            .check_parameters(&["spread_bps", "order_size"])
+            .is_ok());
+        assert_eq!(
+            config.check_parameters(&["spread_bps"]),
+            Err("Unknown parameters: order_size".to_string())
+        );
     }
 
     #[test]
@@ -145,6 +197,7 @@ mod tests {
             average_trade_pnl: rust_decimal::Decimal::new(50, 2), // 0.50
             win_rate: rust_decimal::Decimal::new(55, 2),   // 0.55
             average_holding_time_ms: 5000,
+            arrival_slippage_bps: rust_decimal::Decimal::ZERO,
         };
 
         assert_eq!(metrics.total_trades, 100);
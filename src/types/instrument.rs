@@ -0,0 +1,361 @@
+use crate::types::{Notional, Price, Size, Symbol};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Trading rules of an instrument that determine how prices and quantities are sent
+///
+/// Exchanges reject order parameters that are not multiples of the tick/step size or
+/// that carry more decimal places than the instrument allows, so order payloads are
+/// formatted with the fixed number of decimals implied by the tick and step size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstrumentSpec {
+    /// Instrument symbol
+    pub symbol: Symbol,
+    /// Minimum price increment
+    pub tick_size: Price,
+    /// Minimum quantity increment
+    pub step_size: Size,
+    /// Underlying pair shared by every contract in a dated futures series
+    #[serde(default)]
+    pub underlying: Option<String>,
+    /// Delivery time in milliseconds (None for spot and perpetuals)
+    #[serde(default)]
+    pub expiry: Option<u64>,
+}
+
+impl InstrumentSpec {
+    /// Create a new instrument spec
+    pub fn new(symbol: impl Into<Symbol>, tick_size: Price, step_size: Size) -> Self {
+        Self {
+            symbol: symbol.into(),
+            tick_size,
+            step_size,
+            underlying: None,
+            expiry: None,
+        }
+    }
+
+    /// Mark the instrument as a dated contract on `underlying` (builder pattern)
+    pub fn with_expiry(mut self, underlying: impl Into<String>, expiry: u64) -> Self {
+        self.underlying = Some(underlying.into());
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// Check if the instrument has a delivery date
+    pub fn is_dated(&self) -> bool {
+        self.expiry.is_some()
+    }
+
+    /// Milliseconds left until delivery (zero once expired, None if not dated)
+    pub fn time_to_expiry(&self, now: u64) -> Option<u64> {
+        self.expiry.map(|expiry| expiry.saturating_sub(now))
+    }
+
+    /// Check if the instrument has passed its delivery time
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expiry.is_some_and(|expiry| now >= expiry)
+    }
+
+    /// Number of decimal places allowed for prices
+    pub fn price_decimals(&self) -> u32 {
+        decimals_of(self.tick_size.value())
+    }
+
+    /// Number of decimal places allowed for quantities
+    pub fn size_decimals(&self) -> u32 {
+        decimals_of(self.step_size.value())
+    }
+
+    /// Round a price to the nearest tick
+    pub fn round_price(&self, price: Price) -> Price {
+        Price::new(quantize(
+            price.value(),
+            self.tick_size.value(),
+            RoundingStrategy::MidpointNearestEven,
+        ))
+    }
+
+    /// Round a quantity down to the step size so it never exceeds the requested size
+    pub fn round_size(&self, size: Size) -> Size {
+        Size::new(quantize(
+            size.value(),
+            self.step_size.value(),
+            RoundingStrategy::ToZero,
+        ))
+    }
+
+    /// Format a price for an order payload
+    pub fn format_price(&self, price: Price) -> String {
+        format_fixed(self.round_price(price).value(), self.price_decimals())
+    }
+
+    /// Format a quantity for an order payload
+    pub fn format_size(&self, size: Size) -> String {
+        format_fixed(self.round_size(size).value(), self.size_decimals())
+    }
+
+    /// Format a quote-asset amount (e.g. `quoteOrderQty`) at the price precision
+    /// Rounded down so the order never spends more than requested.
+    pub fn format_quote_size(&self, amount: Notional) -> String {
+        let decimals = self.price_decimals();
+        format_fixed(
+            amount
+                .value()
+                .round_dp_with_strategy(decimals, RoundingStrategy::ToZero),
+            decimals,
+        )
+    }
+}
+
+/// Instrument specs by symbol
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentRegistry {
+    instruments: HashMap<String, InstrumentSpec>,
+}
+
+impl InstrumentRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace an instrument
+    pub fn insert(&mut self, spec: InstrumentSpec) {
+        self.instruments
+            .insert(spec.symbol.value().to_string(), spec);
+    }
+
+    /// Get an instrument by symbol
+    pub fn get(&self, symbol: &str) -> Option<&InstrumentSpec> {
+        self.instruments.get(symbol)
+    }
+
+    /// Number of instruments
+    pub fn len(&self) -> usize {
+        self.instruments.len()
+    }
+
+    /// Check if the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.instruments.is_empty()
+    }
+
+    /// Iterate over the dated contracts
+    pub fn dated(&self) -> impl Iterator<Item = &InstrumentSpec> {
+        self.instruments.values().filter(|spec| spec.is_dated())
+    }
+
+    /// The contract on the same underlying that expires soonest after `symbol`
+    pub fn next_contract(&self, symbol: &str) -> Option<&InstrumentSpec> {
+        let current = self.get(symbol)?;
+        let expiry = current.expiry?;
+        self.dated()
+            .filter(|spec| spec.underlying == current.underlying)
+            .filter(|spec| spec.expiry.is_some_and(|e| e > expiry))
+            .min_by_key(|spec| spec.expiry)
+    }
+}
+
+impl FromIterator<InstrumentSpec> for InstrumentRegistry {
+    fn from_iter<I: IntoIterator<Item = InstrumentSpec>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        for spec in iter {
+            registry.insert(spec);
+        }
+        registry
+    }
+}
+
+/// Format a price without an instrument spec (no exponent, no trailing zeros)
+pub fn format_price_plain(price: Price) -> String {
+    price.value().normalize().to_string()
+}
+
+/// Format a quantity without an instrument spec (no exponent, no trailing zeros)
+pub fn format_size_plain(size: Size) -> String {
+    size.value().normalize().to_string()
+}
+
+/// Format a quote-asset amount without an instrument spec (no exponent, no trailing zeros)
+pub fn format_quote_plain(amount: Notional) -> String {
+    amount.value().normalize().to_string()
+}
+
+/// Decimal places implied by an increment (e.g. 0.00100000 -> 3)
+fn decimals_of(increment: Decimal) -> u32 {
+    increment.normalize().scale()
+}
+
+/// Snap a value to a multiple of an increment
+fn quantize(value: Decimal, increment: Decimal, strategy: RoundingStrategy) -> Decimal {
+    if increment.is_zero() {
+        return value;
+    }
+    let steps = (value / increment).round_dp_with_strategy(0, strategy);
+    (steps * increment).normalize()
+}
+
+/// Format with a fixed number of decimals, never using exponent notation
+fn format_fixed(value: Decimal, decimals: u32) -> String {
+    let mut rounded = value.round_dp(decimals);
+    rounded.rescale(decimals);
+    rounded.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::prelude::FromPrimitive;
+
+    fn btc_spec() -> InstrumentSpec {
+        InstrumentSpec::new(
+            "BTCUSDT",
+            Price::from_str("0.01000000").unwrap(),
+            Size::from_str("0.00001000").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_decimals_from_filters() {
+        let spec = btc_spec();
+        assert_eq!(spec.price_decimals(), 2);
+        assert_eq!(spec.size_decimals(), 5);
+
+        let whole = InstrumentSpec::new(
+            "SHIBUSDT",
+            Price::from_str("0.00000001").unwrap(),
+            Size::from_str("1.00").unwrap(),
+        );
+        assert_eq!(whole.price_decimals(), 8);
+        assert_eq!(whole.size_decimals(), 0);
+    }
+
+    #[test]
+    fn test_excess_precision_is_trimmed() {
+        // Binance rejects this with "Filter failure: PRICE_FILTER"
+        let spec = btc_spec();
+        assert_eq!(
+            spec.format_price(Price::from_str("50000.123456789").unwrap()),
+            "50000.12"
+        );
+        // ... and this with "Filter failure: LOT_SIZE"
+        assert_eq!(
+            spec.format_size(Size::from_str("0.123456789").unwrap()),
+            "0.12345"
+        );
+    }
+
+    #[test]
+    fn test_float_artifacts_are_removed() {
+        // f64 -> Decimal conversions carry binary noise into the payload
+        let spec = btc_spec();
+        let noisy = Price::new(Decimal::from_f64(0.1 + 0.2).unwrap() * Decimal::new(100000, 0));
+        assert_eq!(spec.format_price(noisy), "30000.00");
+
+        let tiny = Size::new(Decimal::from_f64(1e-5).unwrap());
+        assert_eq!(spec.format_size(tiny), "0.00001");
+        assert!(!spec.format_size(tiny).contains('e'));
+    }
+
+    #[test]
+    fn test_fixed_decimals_are_padded() {
+        let spec = btc_spec();
+        assert_eq!(
+            spec.format_price(Price::from_str("50000").unwrap()),
+            "50000.00"
+        );
+        assert_eq!(spec.format_size(Size::from_str("1").unwrap()), "1.00000");
+    }
+
+    #[test]
+    fn test_size_is_never_rounded_up() {
+        let spec = btc_spec();
+        assert_eq!(
+            spec.round_size(Size::from_str("0.000019999").unwrap()),
+            Size::from_str("0.00001").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_price_snaps_to_coarse_tick() {
+        let spec = InstrumentSpec::new(
+            "BTCUSD_PERP",
+            Price::from_str("0.5").unwrap(),
+            Size::from_str("1").unwrap(),
+        );
+        assert_eq!(
+            spec.format_price(Price::from_str("100.3").unwrap()),
+            "100.5"
+        );
+        assert_eq!(
+            spec.format_price(Price::from_str("100.2").unwrap()),
+            "100.0"
+        );
+    }
+
+    #[test]
+    fn test_next_contract_in_series() {
+        let spec = |symbol: &str| {
+            InstrumentSpec::new(
+                symbol,
+                Price::from_str("0.1").unwrap(),
+                Size::from_str("0.001").unwrap(),
+            )
+        };
+        let registry: InstrumentRegistry = vec![
+            spec("BTCUSDT_260925").with_expiry("BTCUSDT", 3_000),
+            spec("BTCUSDT_260626").with_expiry("BTCUSDT", 2_000),
+            spec("BTCUSDT_261225").with_expiry("BTCUSDT", 4_000),
+            spec("ETHUSDT_260925").with_expiry("ETHUSDT", 3_000),
+            spec("BTCUSDT"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(registry.dated().count(), 4);
+        assert_eq!(
+            registry
+                .next_contract("BTCUSDT_260626")
+                .unwrap()
+                .symbol
+                .value(),
+            "BTCUSDT_260925"
+        );
+        assert!(registry.next_contract("BTCUSDT_261225").is_none());
+        assert!(registry.next_contract("BTCUSDT").is_none());
+
+        let june = registry.get("BTCUSDT_260626").unwrap();
+        assert_eq!(june.time_to_expiry(1_500), Some(500));
+        assert!(!june.is_expired(1_999));
+        assert!(june.is_expired(2_000));
+        assert_eq!(june.time_to_expiry(2_500), Some(0));
+    }
+
+    #[test]
+    fn test_plain_formatting() {
+        assert_eq!(
+            format_price_plain(Price::from_str("100.5000").unwrap()),
+            "100.5"
+        );
+        assert_eq!(format_size_plain(Size::from_str("2.000").unwrap()), "2");
+        assert_eq!(
+            format_quote_plain(Notional::new(Decimal::new(25_000, 2))),
+            "250"
+        );
+    }
+
+    #[test]
+    fn test_quote_size_uses_price_precision() {
+        let spec = btc_spec();
+        assert_eq!(
+            spec.format_quote_size(Notional::new(Decimal::new(1_234_567, 4))),
+            "123.45"
+        );
+        assert_eq!(
+            spec.format_quote_size(Notional::new(Decimal::new(50, 0))),
+            "50.00"
+        );
+    }
+}
@@ -1,7 +1,11 @@
+pub mod instrument;
+pub mod notional;
 pub mod price;
 pub mod size;
 pub mod symbol;
 
+pub use instrument::{InstrumentRegistry, InstrumentSpec};
+pub use notional::Notional;
 pub use price::Price;
 pub use size::Size;
 pub use symbol::Symbol;
@@ -0,0 +1,218 @@
+use crate::types::{Price, Size};
+use rust_decimal::prelude::{Signed, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Notional type using NewType pattern for type safety
+/// Represents a quote-currency value (price × size) and is distinct from Price and Size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Notional(pub Decimal);
+
+impl Notional {
+    /// Create a new Notional from a Decimal
+    pub fn new(value: Decimal) -> Self {
+        Self(value)
+    }
+
+    /// Create a zero Notional
+    pub fn zero() -> Self {
+        Self(Decimal::ZERO)
+    }
+
+    /// Compute the notional of a price and size
+    pub fn of(price: Price, size: Size) -> Self {
+        Self(price.0 * size.0)
+    }
+
+    /// Get the underlying Decimal value
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    /// Check if the notional is zero
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Get the absolute value of the notional
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Get the sign of the notional (-1, 0 or 1)
+    pub fn signum(&self) -> Decimal {
+        self.0.signum()
+    }
+
+    /// Checked addition, returns None on overflow
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Checked subtraction, returns None on overflow
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Size that this notional buys at a price, None if the price is zero
+    pub fn to_size(self, price: Price) -> Option<Size> {
+        self.0.checked_div(price.0).map(Size::new)
+    }
+
+    /// Average price of this notional over a size, None if the size is zero
+    pub fn to_price(self, size: Size) -> Option<Price> {
+        self.0.checked_div(size.0).map(Price::new)
+    }
+
+    /// Convert to f64 (lossy, for statistics and logging only)
+    pub fn to_f64(&self) -> Option<f64> {
+        self.0.to_f64()
+    }
+}
+
+impl Price {
+    /// Compute the notional value of a size at this price
+    pub fn notional(self, size: Size) -> Notional {
+        Notional::of(self, size)
+    }
+}
+
+impl Size {
+    /// Compute the notional value of this size at a price
+    pub fn notional(self, price: Price) -> Notional {
+        Notional::of(price, self)
+    }
+}
+
+impl fmt::Display for Notional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// Custom serialization to preserve decimal places
+impl Serialize for Notional {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+// Custom deserialization from string
+impl<'de> Deserialize<'de> for Notional {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let decimal = Decimal::from_str(&s).map_err(serde::de::Error::custom)?;
+        Ok(Notional(decimal))
+    }
+}
+
+impl std::ops::Add for Notional {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl std::ops::Sub for Notional {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl std::ops::AddAssign for Notional {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl std::ops::SubAssign for Notional {
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0;
+    }
+}
+
+impl std::ops::Neg for Notional {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl std::ops::Mul<Decimal> for Notional {
+    type Output = Self;
+
+    fn mul(self, rhs: Decimal) -> Self {
+        Self(self.0 * rhs)
+    }
+}
+
+impl std::iter::Sum for Notional {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, n| acc + n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notional_from_price_and_size() {
+        let price = Price::from_str("50000").unwrap();
+        let size = Size::from_str("0.5").unwrap();
+
+        assert_eq!(price.notional(size), Notional::new(Decimal::new(25000, 0)));
+        assert_eq!(size.notional(price), price.notional(size));
+    }
+
+    #[test]
+    fn test_notional_conversions() {
+        let notional = Notional::new(Decimal::new(1000, 0));
+
+        assert_eq!(
+            notional.to_size(Price::from_str("500").unwrap()),
+            Some(Size::from_str("2").unwrap())
+        );
+        assert_eq!(
+            notional.to_price(Size::from_str("4").unwrap()),
+            Some(Price::from_str("250").unwrap())
+        );
+        assert_eq!(notional.to_size(Price::zero()), None);
+    }
+
+    #[test]
+    fn test_notional_arithmetic() {
+        let a = Notional::new(Decimal::new(100, 0));
+        let b = Notional::new(Decimal::new(250, 0));
+
+        assert_eq!((a - b).abs(), Notional::new(Decimal::new(150, 0)));
+        assert_eq!((a - b).signum(), Decimal::NEGATIVE_ONE);
+        assert_eq!(
+            [a, b].into_iter().sum::<Notional>().value(),
+            Decimal::new(350, 0)
+        );
+        assert_eq!(Notional::new(Decimal::MAX).checked_add(a), None);
+    }
+
+    #[test]
+    fn test_notional_serialization() {
+        let notional = Notional::new(Decimal::new(12345, 2));
+        let json = serde_json::to_string(&notional).unwrap();
+        assert_eq!(json, "\"123.45\"");
+
+        let deserialized: Notional = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, notional);
+    }
+}
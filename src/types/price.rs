@@ -1,3 +1,4 @@
+use rust_decimal::prelude::{FromPrimitive, Signed, ToPrimitive};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
@@ -112,6 +113,61 @@ impl Price {
     pub fn abs(&self) -> Self {
         Self(self.0.abs())
     }
+
+    /// Create a zero Price
+    pub fn zero() -> Self {
+        Self(Decimal::ZERO)
+    }
+
+    /// Check if the price is zero
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Get the sign of the price (-1, 0 or 1)
+    pub fn signum(&self) -> Decimal {
+        self.0.signum()
+    }
+
+    /// Check if the price is strictly positive
+    pub fn is_positive(&self) -> bool {
+        self.0 > Decimal::ZERO
+    }
+
+    /// Check if the price is strictly negative
+    pub fn is_negative(&self) -> bool {
+        self.0 < Decimal::ZERO
+    }
+
+    /// Checked addition, returns None on overflow
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Checked subtraction, returns None on overflow
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Checked multiplication by a scalar, returns None on overflow
+    pub fn checked_mul(self, rhs: Decimal) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self)
+    }
+
+    /// Checked division by a scalar, returns None on division by zero or overflow
+    pub fn checked_div(self, rhs: Decimal) -> Option<Self> {
+        self.0.checked_div(rhs).map(Self)
+    }
+
+    /// Convert to f64 (lossy, for statistics and logging only)
+    pub fn to_f64(&self) -> Option<f64> {
+        self.0.to_f64()
+    }
+
+    /// Create from f64, returns None for NaN or infinite values
+    pub fn from_f64(value: f64) -> Option<Self> {
+        Decimal::from_f64(value).map(Self)
+    }
 }
 
 #[cfg(test)]
@@ -164,4 +220,41 @@ mod tests {
         let deserialized: Price = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, price);
     }
+
+    #[test]
+    fn test_price_sign_helpers() {
+        let positive = Price::from_str("2.5").unwrap();
+        let negative = Price::from_str("-2.5").unwrap();
+
+        assert_eq!(positive.signum(), Decimal::ONE);
+        assert_eq!(negative.signum(), Decimal::NEGATIVE_ONE);
+        assert!(positive.is_positive() && !positive.is_negative());
+        assert!(negative.is_negative() && !negative.is_positive());
+        assert_eq!(negative.abs(), positive);
+        assert_eq!(positive.min(negative), negative);
+        assert_eq!(positive.max(negative), positive);
+    }
+
+    #[test]
+    fn test_price_checked_arithmetic() {
+        let a = Price::from_str("1.5").unwrap();
+        let b = Price::from_str("0.5").unwrap();
+
+        assert_eq!(a.checked_add(b), Some(Price::from_str("2.0").unwrap()));
+        assert_eq!(a.checked_sub(b), Some(Price::from_str("1.0").unwrap()));
+        assert_eq!(
+            a.checked_mul(Decimal::new(2, 0)),
+            Some(Price::from_str("3.0").unwrap())
+        );
+        assert_eq!(a.checked_div(Decimal::ZERO), None);
+        assert_eq!(Price::new(Decimal::MAX).checked_add(a), None);
+    }
+
+    #[test]
+    fn test_price_f64_conversion() {
+        let value = Price::from_f64(1.25).unwrap();
+        assert_eq!(value, Price::from_str("1.25").unwrap());
+        assert_eq!(value.to_f64(), Some(1.25));
+        assert!(Price::from_f64(f64::NAN).is_none());
+    }
 }
@@ -237,6 +237,7 @@ async fn test_arbitrage_with_trading_events() {
             remaining_size: Size::from_str("0.0").unwrap(),
             average_price: Some(Price::from_str("50000.00").unwrap()),
             timestamp: 123456800,
+            reject_reason: None,
         };
         
         let trading_event = TradingEvent::ExecutionReport(buy_execution);
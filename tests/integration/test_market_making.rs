@@ -136,6 +136,7 @@ async fn test_market_making_with_order_updates() {
                                 .duration_since(std::time::UNIX_EPOCH)
                                 .unwrap()
                                 .as_millis() as u64,
+                            reject_reason: None,
                         };
                         
                         // In a real implementation, we'd handle the execution report
@@ -324,6 +325,7 @@ async fn test_market_making_with_risk_limits() {
                                 .duration_since(std::time::UNIX_EPOCH)
                                 .unwrap()
                                 .as_millis() as u64,
+                            reject_reason: None,
                         };
                         
                         // In a real implementation, we'd update position based on execution
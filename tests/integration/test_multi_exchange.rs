@@ -50,6 +50,7 @@ async fn test_multi_exchange_order_placement() {
         quantity: Size::from_str("0.1").unwrap(),
         price: Some(Price::from_str("50000").unwrap()),
         client_order_id: Some("gate_order_1".to_string()),
+        quote_size: None,
     };
     
     let result1 = manager.place_order("gate", order1).await;
@@ -64,6 +65,7 @@ async fn test_multi_exchange_order_placement() {
         quantity: Size::from_str("0.1").unwrap(),
         price: Some(Price::from_str("51000").unwrap()),
         client_order_id: Some("bybit_order_1".to_string()),
+        quote_size: None,
     };
     
     let result2 = manager.place_order("bybit", order2).await;
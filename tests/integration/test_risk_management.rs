@@ -36,6 +36,7 @@ async fn test_risk_workflow_order_validation() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("3.0").unwrap(),
         client_order_id: Some("valid_order".to_string()),
+        quote_size: None,
     };
     
     let result = risk_engine.check_order(&valid_order).await;
@@ -51,6 +52,7 @@ async fn test_risk_workflow_order_validation() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("7.0").unwrap(),
         client_order_id: Some("invalid_order".to_string()),
+        quote_size: None,
     };
     
     let result = risk_engine.check_order(&invalid_order).await;
@@ -95,6 +97,7 @@ async fn test_risk_workflow_position_tracking() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("6.0").unwrap(), // 5.0 + 6.0 = 11.0 > 10.0
         client_order_id: Some("test_order".to_string()),
+        quote_size: None,
     };
     
     let result = risk_engine.check_order(&order).await;
@@ -170,6 +173,7 @@ async fn test_risk_workflow_total_exposure() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("1.0").unwrap(),
         client_order_id: Some("test_order".to_string()),
+        quote_size: None,
     };
     
     let result = risk_engine.check_order(&order).await;
@@ -228,6 +232,7 @@ async fn test_risk_manager_execution_report_handling() {
         quantity: Size::from_str("1.0").unwrap(),
         price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: chrono::Utc::now().timestamp_millis() as u64,
+        reject_reason: None,
     };
     
     // Handle execution report
@@ -342,6 +347,7 @@ async fn test_risk_workflow_open_orders_limit() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("1.0").unwrap(),
         client_order_id: Some("test_order".to_string()),
+        quote_size: None,
     };
     
     let result = risk_engine.check_order(&order).await;
@@ -0,0 +1,312 @@
+//! End-to-end pipeline test
+//!
+//! Replays the recorded sample in `tests/data/pipeline_sample.jsonl` through a simple
+//! threshold strategy, the risk engine, the dry-run execution client and the shadow
+//! ledger, and checks the final positions, trade count and P&L against golden values.
+//! Any change in behaviour along the pipeline shows up as a changed number here.
+//!
+//! Runs with plain `cargo test`; on its own with `cargo test --test test_full_pipeline`.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crypto_hft::backtest::read_events;
+use crypto_hft::connectors::dry_run::DryRunExecutionClient;
+use crypto_hft::core::events::{MarketEvent, Position, Trade};
+use crypto_hft::risk::rules::{OrderSizeRule, PositionSizeRule};
+use crypto_hft::risk::shadow_ledger::TradeRecord;
+use crypto_hft::traits::{ExecutionClient, ExecutionReport, NewOrder, OrderSide, OrderStatus};
+use crypto_hft::{Price, Size, Symbol};
+use crypto_hft::{
+    RiskEngine, ShadowLedger, Signal, Strategy, StrategyConfig, StrategyMetrics, StrategyState,
+    TradingEvent,
+};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const SYMBOL: &str = "BTCUSDT";
+const EXCHANGE: &str = "binance";
+
+/// Buys one unit when the price drops to `buy_at` and sells the inventory when it
+/// rises to `sell_at`, signalling only on the print that crosses the threshold
+struct Threshold {
+    buy_at: Price,
+    sell_at: Price,
+    last_price: Option<Price>,
+    inventory: Size,
+    fills: u64,
+}
+
+impl Threshold {
+    fn new(buy_at: &str, sell_at: &str) -> Self {
+        Self {
+            buy_at: Price::from_str(buy_at).unwrap(),
+            sell_at: Price::from_str(sell_at).unwrap(),
+            last_price: None,
+            inventory: Size::zero(),
+            fills: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for Threshold {
+    type Error = std::io::Error;
+
+    async fn initialize(&mut self, _config: StrategyConfig) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn update_config(&mut self, _config: StrategyConfig) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn on_market_event(&mut self, event: MarketEvent) -> Result<Vec<Signal>, Self::Error> {
+        let MarketEvent::Trade(trade) = event else {
+            return Ok(vec![]);
+        };
+        let Some(last) = self.last_price.replace(trade.price) else {
+            return Ok(vec![]);
+        };
+        let order = if last > self.buy_at && trade.price <= self.buy_at {
+            NewOrder::new_market_buy(SYMBOL, Size::from_str("1").unwrap())
+        } else if last < self.sell_at && trade.price >= self.sell_at && self.inventory.is_positive()
+        {
+            NewOrder::new_market_sell(SYMBOL, self.inventory)
+        } else {
+            return Ok(vec![]);
+        };
+        Ok(vec![Signal::PlaceOrder {
+            order: NewOrder {
+                exchange_id: EXCHANGE.to_string(),
+                ..order
+            },
+        }])
+    }
+
+    async fn on_trading_event(&mut self, event: TradingEvent) -> Result<(), Self::Error> {
+        if let TradingEvent::ExecutionReport(report) = event {
+            if report.status == OrderStatus::Filled {
+                self.fills += 1;
+                // Sells always close the whole inventory
+                self.inventory = if self.inventory.is_zero() {
+                    report.filled_size
+                } else {
+                    Size::zero()
+                };
+            }
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> StrategyState {
+        StrategyState::Prediction
+    }
+
+    fn get_metrics(&self) -> StrategyMetrics {
+        StrategyMetrics {
+            total_trades: self.fills,
+            winning_trades: 0,
+            losing_trades: 0,
+            total_pnl: Decimal::ZERO,
+            gross_profit: Decimal::ZERO,
+            gross_loss: Decimal::ZERO,
+            profit_factor: Decimal::ZERO,
+            max_drawdown: Decimal::ZERO,
+            sharpe_ratio: Decimal::ZERO,
+            average_trade_pnl: Decimal::ZERO,
+            win_rate: Decimal::ZERO,
+            average_holding_time_ms: 0,
+            arrival_slippage_bps: Decimal::ZERO,
+        }
+    }
+
+    async fn shutdown(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// The pipeline under test, wired the way the live event loop wires it
+struct Pipeline {
+    strategy: Threshold,
+    risk: RiskEngine,
+    client: DryRunExecutionClient,
+    ledger: ShadowLedger,
+    /// Orders accepted by the dry-run client, waiting for the next print to fill them
+    pending: Vec<(String, NewOrder)>,
+    marks: HashMap<String, Price>,
+    rejected: Vec<String>,
+}
+
+impl Pipeline {
+    async fn new() -> Self {
+        let one = Size::from_str("1").unwrap();
+        let risk = RiskEngine::new();
+        risk.set_max_order_size(SYMBOL, one).await;
+        risk.set_max_position_size(SYMBOL, one).await;
+        risk.add_rule(Box::new(OrderSizeRule::new())).await;
+        risk.add_rule(Box::new(PositionSizeRule::new())).await;
+        Self {
+            strategy: Threshold::new("100", "103"),
+            risk,
+            client: DryRunExecutionClient::new(),
+            ledger: ShadowLedger::new(),
+            pending: Vec::new(),
+            marks: HashMap::new(),
+            rejected: Vec::new(),
+        }
+    }
+
+    async fn on_event(&mut self, event: MarketEvent) {
+        // Market orders sent on the previous print fill at this one
+        if let MarketEvent::Trade(trade) = &event {
+            self.marks
+                .insert(trade.symbol.value().to_string(), trade.price);
+            for (order_id, order) in std::mem::take(&mut self.pending) {
+                self.fill(order_id, order, trade).await;
+            }
+        }
+
+        let signals = self.strategy.on_market_event(event).await.unwrap();
+        for signal in signals {
+            let Signal::PlaceOrder { order } = signal else {
+                continue;
+            };
+            match self.risk.check_order(&order).await {
+                Ok(()) => {
+                    let order_id = self.client.place_order(order.clone()).await.unwrap();
+                    self.pending.push((order_id, order));
+                }
+                Err(violation) => self.rejected.push(violation.rule),
+            }
+        }
+    }
+
+    async fn fill(&mut self, order_id: String, order: NewOrder, trade: &Trade) {
+        let fees = self.client.get_trading_fees(SYMBOL).await.unwrap();
+        let fee = fees.fee(trade.price.value() * order.size.value(), false);
+        self.ledger
+            .add_trade(TradeRecord::new(
+                format!("{}_fill", order_id),
+                order.symbol.clone(),
+                order.exchange_id.clone(),
+                order_id.clone(),
+                order.side,
+                order.size,
+                trade.price,
+                DateTime::<Utc>::from_timestamp_millis(trade.timestamp as i64).unwrap(),
+                Size::new(fee),
+                "USDT".to_string(),
+            ))
+            .await;
+
+        let position = self
+            .ledger
+            .get_position(SYMBOL, EXCHANGE)
+            .await
+            .expect("fill creates a position");
+        self.risk
+            .update_position(
+                SYMBOL,
+                Position {
+                    symbol: Symbol::new(SYMBOL),
+                    exchange_id: EXCHANGE.to_string(),
+                    size: position.size,
+                    average_price: position.average_price,
+                    unrealized_pnl: None,
+                },
+            )
+            .await;
+
+        let report = ExecutionReport {
+            order_id,
+            client_order_id: None,
+            symbol: order.symbol,
+            exchange_id: order.exchange_id,
+            status: OrderStatus::Filled,
+            filled_size: order.size,
+            remaining_size: Size::zero(),
+            average_price: Some(trade.price),
+            timestamp: trade.timestamp,
+            reject_reason: None,
+        };
+        self.strategy
+            .on_trading_event(TradingEvent::ExecutionReport(report))
+            .await
+            .unwrap();
+    }
+}
+
+fn dec(s: &str) -> Decimal {
+    Decimal::from_str(s).unwrap()
+}
+
+#[tokio::test]
+async fn test_replayed_sample_matches_golden_values() {
+    let events = read_events(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/pipeline_sample.jsonl"
+    ))
+    .unwrap();
+    assert_eq!(events.len(), 11);
+
+    let mut pipeline = Pipeline::new().await;
+    for event in events {
+        pipeline.on_event(event).await;
+    }
+
+    // Buy at 100.5, sell at 102.5, buy again at 99.2; the second dip to 99.5 would
+    // have taken the position to 2 against a limit of 1
+    assert_eq!(pipeline.rejected, vec!["PositionSizeLimit".to_string()]);
+    assert!(pipeline.pending.is_empty());
+    assert_eq!(pipeline.strategy.get_metrics().total_trades, 3);
+
+    let trades = pipeline.ledger.get_all_trades().await;
+    let fills: Vec<(OrderSide, Price)> = trades.iter().map(|t| (t.side, t.price)).collect();
+    assert_eq!(
+        fills,
+        vec![
+            (OrderSide::Buy, Price::from_str("100.5").unwrap()),
+            (OrderSide::Sell, Price::from_str("102.5").unwrap()),
+            (OrderSide::Buy, Price::from_str("99.2").unwrap()),
+        ]
+    );
+    let stats = pipeline.ledger.get_trade_stats().await;
+    assert_eq!(stats.total_trades, 3);
+    assert_eq!(stats.total_fees, Size::from_str("0.3022").unwrap());
+
+    // 0.1% taker fees: the first round trip makes 2 less 0.1005 and 0.1025
+    let position = pipeline
+        .ledger
+        .get_position(SYMBOL, EXCHANGE)
+        .await
+        .unwrap();
+    assert_eq!(position.size, Size::from_str("1").unwrap());
+    assert_eq!(
+        position.average_price,
+        Some(Price::from_str("99.2992").unwrap())
+    );
+    assert_eq!(pipeline.ledger.get_total_realized_pnl().await, dec("1.797"));
+    assert_eq!(
+        pipeline
+            .ledger
+            .get_total_unrealized_pnl(&pipeline.marks)
+            .await,
+        dec("1.7008")
+    );
+    assert_eq!(
+        pipeline.risk.get_position(SYMBOL).await.unwrap().size,
+        position.size
+    );
+
+    // The dry-run venue saw every accepted order
+    assert_eq!(
+        pipeline
+            .client
+            .get_order_history(None, None)
+            .await
+            .unwrap()
+            .len(),
+        3
+    );
+}
@@ -40,6 +40,7 @@ fn test_execution_report_fields() {
         remaining_size: Size::from_str("1.0").unwrap(), // Required field
         average_price: None,                            // Required field (Option<Price>)
         timestamp: 1234567890u64,
+        reject_reason: None,
     };
 
     // Verify fields are accessible
@@ -152,6 +153,7 @@ async fn test_dry_run_place_order() {
         price: Some(Price::from_str("50000.00").unwrap()),
         size: Size::from_str("1.0").unwrap(),
         client_order_id: Some("test_phase10_1".to_string()),
+        quote_size: None,
     };
 
     let result = client.place_order(order).await;
@@ -175,6 +177,7 @@ async fn test_dry_run_cancel_order() {
         price: Some(Price::from_str("50000.00").unwrap()),
         size: Size::from_str("1.0").unwrap(),
         client_order_id: None,
+        quote_size: None,
     };
 
     let order_id = client.place_order(order).await.unwrap();
@@ -217,6 +220,7 @@ async fn test_dry_run_get_open_orders() {
         price: Some(Price::from_str("3000.00").unwrap()),
         size: Size::from_str("2.0").unwrap(),
         client_order_id: None,
+        quote_size: None,
     };
 
     client.place_order(order).await.unwrap();
@@ -250,6 +254,7 @@ async fn test_dry_run_get_order_history() {
             price: None,
             size: Size::from_str("0.1").unwrap(),
             client_order_id: Some(format!("history_test_{}", i)),
+            quote_size: None,
         };
         client.place_order(order).await.unwrap();
     }
@@ -290,6 +295,7 @@ async fn test_dry_run_stop_loss_order() {
         price: Some(Price::from_str("45000.00").unwrap()),
         size: Size::from_str("0.5").unwrap(),
         client_order_id: Some("stop_loss_test".to_string()),
+        quote_size: None,
     };
 
     let result = client.place_order(order).await;
@@ -310,6 +316,7 @@ async fn test_dry_run_stop_limit_order() {
         price: Some(Price::from_str("44000.00").unwrap()),
         size: Size::from_str("0.5").unwrap(),
         client_order_id: Some("stop_limit_test".to_string()),
+        quote_size: None,
     };
 
     let result = client.place_order(order).await;
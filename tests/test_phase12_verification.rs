@@ -332,6 +332,7 @@ fn test_t087_execution_report() {
         remaining_size: Size::zero(),
         average_price: Some(Price::from_str("100.00").unwrap()),
         timestamp: 1234567890,
+        reject_reason: None,
     };
 
     assert_eq!(report.order_id, "12345");
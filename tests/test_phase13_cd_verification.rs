@@ -46,6 +46,7 @@ fn test_execution_report_correct_fields() {
         remaining_size: Size::from_str("0.0").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: 1700000000000,
+        reject_reason: None,
     };
 
     assert_eq!(report.order_id, "order_123");
@@ -75,6 +76,7 @@ fn test_execution_report_no_average_price() {
         remaining_size: Size::from_str("1.0").unwrap(),
         average_price: None,
         timestamp: 1700000000000,
+        reject_reason: None,
     };
 
     assert_eq!(report.status, OrderStatus::New);
@@ -105,6 +107,7 @@ fn test_execution_report_all_status_variants() {
             remaining_size: Size::from_str("1.0").unwrap(),
             average_price: None,
             timestamp: 0,
+            reject_reason: None,
         };
 
         // Just verify it compiles and status matches
@@ -129,6 +132,7 @@ fn test_execution_report_for_risk_manager() {
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_millis() as u64,
+        reject_reason: None,
     };
 
     // Verify fields are accessible as used in risk_manager
@@ -151,6 +155,7 @@ fn test_execution_report_for_shadow_ledger() {
         remaining_size: Size::from_str("0.0").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: chrono::Utc::now().timestamp_millis() as u64,
+        reject_reason: None,
     };
 
     // Verify all fields needed by shadow_ledger are present and correct
@@ -180,6 +185,7 @@ fn test_new_order_uses_size_field() {
         price: Some(Price::from_str("50000.0").unwrap()),
         size: Size::from_str("1.0").unwrap(), // NOTE: This is 'size', NOT 'quantity'
         client_order_id: Some("client_123".to_string()),
+        quote_size: None,
     };
 
     // Verify size field is accessible and correct
@@ -255,6 +261,7 @@ fn test_order_to_execution_report_flow() {
         price: Some(Price::from_str("50000.0").unwrap()),
         size: Size::from_str("1.0").unwrap(),
         client_order_id: Some("client_123".to_string()),
+        quote_size: None,
     };
 
     // Step 2: Simulate receiving an execution report
@@ -268,6 +275,7 @@ fn test_order_to_execution_report_flow() {
         remaining_size: Size::from_str("0.0").unwrap(),
         average_price: new_order.price,
         timestamp: 1700000000000,
+        reject_reason: None,
     };
 
     // Verify the flow works correctly
@@ -293,6 +301,7 @@ fn test_partial_fill_execution_report() {
         remaining_size,
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: 1700000000000,
+        reject_reason: None,
     };
 
     // Verify partial fill accounting
@@ -322,6 +331,7 @@ fn test_zero_size_handling() {
         remaining_size: Size::from_str("1.0").unwrap(),
         average_price: None,
         timestamp: 0,
+        reject_reason: None,
     };
 
     assert!(report.filled_size.is_zero());
@@ -345,6 +355,7 @@ fn test_large_size_values() {
         remaining_size: Size::from_str("0.0").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: 0,
+        reject_reason: None,
     };
 
     assert_eq!(report.filled_size, large_size);
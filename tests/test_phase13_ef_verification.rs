@@ -62,6 +62,7 @@ fn test_execution_report_with_filled_status() {
         remaining_size: Size::from_str("0.0").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: Utc::now().timestamp_millis() as u64,
+        reject_reason: None,
     };
     
     // Verify status field
@@ -88,6 +89,7 @@ fn test_order_status_filled_in_conditional() {
         remaining_size: Size::zero(),
         average_price: Some(Price::from_str("3000.0").unwrap()),
         timestamp: 1700000000000,
+        reject_reason: None,
     };
     
     // This is the pattern used in shadow_ledger.rs line 379
@@ -248,6 +250,7 @@ async fn test_shadow_ledger_process_execution_report_pattern() {
         remaining_size: Size::zero(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: Utc::now().timestamp_millis() as u64,
+        reject_reason: None,
     };
     
     // This should not panic - uses correct OrderStatus pattern
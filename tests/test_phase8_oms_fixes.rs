@@ -48,6 +48,7 @@ fn test_order_info_creation_and_update() {
         remaining_size: Size::from_str("0.5").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: 1638368000000,
+        reject_reason: None,
     };
 
     // Update should work on mutable order_info
@@ -75,6 +76,7 @@ fn test_execution_report_average_price_field() {
         remaining_size: Size::from_str("0.0").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: 1638368000000,
+        reject_reason: None,
     };
 
     // Verify average_price field exists and is correct
@@ -111,6 +113,7 @@ fn test_order_info_update_with_average_price() {
         remaining_size: Size::from_str("0.0").unwrap(),
         average_price: Some(Price::from_str("50100.0").unwrap()),
         timestamp: 1638368000000,
+        reject_reason: None,
     };
 
     order_info.update(&report);
@@ -156,6 +159,7 @@ fn test_order_info_fill_percentage() {
         remaining_size: Size::from_str("0.5").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: 1638368000000,
+        reject_reason: None,
     };
 
     order_info.update(&report);
@@ -172,6 +176,7 @@ fn test_order_info_fill_percentage() {
         remaining_size: Size::from_str("0.0").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: 1638368000000,
+        reject_reason: None,
     };
 
     order_info.update(&report);
@@ -286,6 +291,7 @@ async fn test_order_manager_handle_execution_report() {
         remaining_size: Size::from_str("0.5").unwrap(),
         average_price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: 1638368000000,
+        reject_reason: None,
     };
 
     order_manager.handle_execution_report(report).await.unwrap();
@@ -328,6 +334,7 @@ async fn test_order_manager_cancelled_order() {
         remaining_size: Size::from_str("1.0").unwrap(),
         average_price: None,
         timestamp: 1638368000000,
+        reject_reason: None,
     };
 
     order_manager.handle_execution_report(report).await.unwrap();
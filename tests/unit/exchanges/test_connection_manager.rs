@@ -112,6 +112,7 @@ async fn test_connection_manager_place_order() {
         quantity: crypto_hft::types::Size::from_str("0.1").unwrap(),
         price: Some(crypto_hft::types::Price::from_str("50000").unwrap()),
         client_order_id: Some("test_order".to_string()),
+        quote_size: None,
     };
     
     let result = manager.place_order("gate", order).await;
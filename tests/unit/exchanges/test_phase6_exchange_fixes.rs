@@ -97,6 +97,7 @@ mod dry_run_connector_tests {
             price: Some(Price::from_str("50000.00").unwrap()),
             size: Size::from_str("1.0").unwrap(), // Use 'size', not 'quantity'
             client_order_id: Some("test_123".to_string()),
+            quote_size: None,
         };
 
         assert_eq!(order.size, Size::from_str("1.0").unwrap());
@@ -116,6 +117,7 @@ mod dry_run_connector_tests {
             remaining_size: Size::from_str("1.0").unwrap(),
             average_price: None,
             timestamp: 1699000000000u64,
+            reject_reason: None,
         };
 
         assert_eq!(report.order_id, "test_order_1");
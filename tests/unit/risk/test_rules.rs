@@ -155,6 +155,7 @@ async fn test_position_size_rule() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("15.0").unwrap(),
         client_order_id: Some("test_order_1".to_string()),
+        quote_size: None,
     };
     
     // Check should fail
@@ -174,6 +175,7 @@ async fn test_position_size_rule() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("5.0").unwrap(),
         client_order_id: Some("test_order_2".to_string()),
+        quote_size: None,
     };
     
     // Should pass
@@ -203,6 +205,7 @@ async fn test_order_size_rule() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("10.0").unwrap(),
         client_order_id: Some("test_order_1".to_string()),
+        quote_size: None,
     };
     
     // Check should fail
@@ -249,6 +252,7 @@ async fn test_daily_loss_rule() {
         price: Some(Price::from_str("49000.0").unwrap()), // $1000 loss
         quantity: Size::from_str("1.0").unwrap(),
         client_order_id: Some("test_order_1".to_string()),
+        quote_size: None,
     };
     
     // Check should fail
@@ -288,6 +292,7 @@ async fn test_total_exposure_rule() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("1.0").unwrap(),
         client_order_id: Some("test_order_1".to_string()),
+        quote_size: None,
     };
     
     // Check should fail
@@ -321,6 +326,7 @@ async fn test_open_orders_count_rule() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("1.0").unwrap(),
         client_order_id: Some("test_order_1".to_string()),
+        quote_size: None,
     };
     
     // Check should fail
@@ -353,6 +359,7 @@ async fn test_balance_rule() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("0.02").unwrap(), // Requires 1000 USDT but only 500 available after min balance
         client_order_id: Some("test_order_1".to_string()),
+        quote_size: None,
     };
     
     // Check should fail
@@ -386,6 +393,7 @@ async fn test_multiple_rules() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("7.0").unwrap(), // Exceeds max order size but not max position
         client_order_id: Some("test_order_1".to_string()),
+        quote_size: None,
     };
     
     // Check should fail due to order size rule
@@ -405,6 +413,7 @@ async fn test_multiple_rules() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("3.0").unwrap(), // Passes both rules
         client_order_id: Some("test_order_2".to_string()),
+        quote_size: None,
     };
     
     // Check should pass
@@ -431,6 +440,7 @@ async fn test_risk_engine_rule_removal() {
         price: Some(Price::from_str("50000.0").unwrap()),
         quantity: Size::from_str("10.0").unwrap(),
         client_order_id: Some("test_order_1".to_string()),
+        quote_size: None,
     };
     
     let result = risk_engine.check_order(&order).await;
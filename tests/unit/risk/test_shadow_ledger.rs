@@ -203,6 +203,7 @@ async fn test_shadow_ledger_process_execution_report() {
         quantity: Size::from_str("1.0").unwrap(),
         price: Some(Price::from_str("50000.0").unwrap()),
         timestamp: Utc::now().timestamp_millis() as u64,
+        reject_reason: None,
     };
     
     // Process the execution report
@@ -72,6 +72,7 @@ mod new_order_tests {
             price: Some(price),
             size: size.clone(),
             client_order_id: Some("test-123".to_string()),
+            quote_size: None,
         };
         
         // Verify we can access size field
@@ -91,6 +92,7 @@ mod new_order_tests {
             price: None,
             size: Size::new(Decimal::new(100, 2)),
             client_order_id: None,
+            quote_size: None,
         };
         
         let _order_size = order.size; // Should compile with 'size', not 'quantity'
@@ -142,6 +144,7 @@ mod execution_report_tests {
             remaining_size: Size::new(Decimal::new(50, 2)),
             average_price: Some(Price::new(Decimal::new(5000000, 2))),
             timestamp: 1638368000000,
+            reject_reason: None,
         };
         
         assert!(report.average_price.is_some());
@@ -164,6 +167,7 @@ mod execution_report_tests {
             remaining_size: Size::new(Decimal::new(100, 2)),
             average_price: None,
             timestamp: 1638368000000,
+            reject_reason: None,
         };
         
         assert!(report.average_price.is_none());
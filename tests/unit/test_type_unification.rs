@@ -96,6 +96,7 @@ mod type_unification_tests {
             price: Some(price),
             size,  // Should be 'size' not 'quantity'
             client_order_id: Some("client-123".to_string()),
+            quote_size: None,
         };
         
         assert_eq!(order.symbol, symbol);
@@ -122,6 +123,7 @@ mod type_unification_tests {
             remaining_size,
             average_price: Some(average_price),
             timestamp: 1638368000000u64,
+            reject_reason: None,
         };
         
         assert!(report.average_price.is_some());
@@ -196,6 +198,7 @@ mod type_unification_tests {
             price: Some(price),
             size,
             client_order_id: None,
+            quote_size: None,
         };
         
         // Test all Signal variants exist